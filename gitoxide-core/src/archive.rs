@@ -0,0 +1,91 @@
+use std::path::Path;
+
+use anyhow::bail;
+use gix::{
+    objs::{CommitRef, FindExt, TagRef},
+    worktree::archive,
+    Count, NestedProgress, Progress,
+};
+
+pub struct Options {
+    pub format: Option<archive::Format>,
+    pub prefix: Option<String>,
+}
+
+/// Write the tree of `treeish`, the hex id of a commit, tag or tree, to `destination_path` as archive, reading objects
+/// from `objects_directory` without a repository.
+///
+/// As there is no repository, there are no attributes, so `export-ignore` and `export-subst` have no effect, and blobs
+/// are written as stored.
+pub fn from_tree(
+    objects_directory: &Path,
+    treeish: &str,
+    destination_path: &Path,
+    mut progress: impl NestedProgress,
+    Options { format, prefix }: Options,
+) -> anyhow::Result<()> {
+    let format = format.map_or_else(|| crate::repository::archive::format_from_ext(destination_path), Ok)?;
+    let objects = gix::odb::at(objects_directory)?.into_arc()?;
+    let mut id = gix::ObjectId::from_hex(treeish.as_bytes())?;
+    let mut buf = Vec::new();
+    let (tree, modification_time) = loop {
+        let object = objects.find(&id, &mut buf)?;
+        match object.kind {
+            gix::object::Kind::Commit => {
+                let commit = CommitRef::from_bytes(object.data)?;
+                break (commit.tree(), Some(commit.committer.time.seconds));
+            }
+            gix::object::Kind::Tag => id = TagRef::from_bytes(object.data)?.target(),
+            gix::object::Kind::Tree => break (id, None),
+            gix::object::Kind::Blob => bail!("Cannot derive commit or tree from blob at {id}"),
+        }
+    };
+
+    let start = std::time::Instant::now();
+    let mut stream = gix::worktree::stream::from_tree(
+        tree,
+        objects,
+        gix::filter::plumbing::Pipeline::default(),
+        |_path, _mode, _attrs| Ok::<_, std::convert::Infallible>(()),
+    );
+
+    let mut entries = progress.add_child("entries");
+    entries.init(None, gix::progress::count("entries"));
+    let mut bytes = progress.add_child("written");
+    bytes.init(None, gix::progress::bytes());
+
+    let mut file = gix::progress::Write {
+        inner: std::io::BufWriter::with_capacity(128 * 1024, std::fs::File::create(destination_path)?),
+        progress: &mut bytes,
+    };
+    if format == archive::Format::InternalTransientNonPersistable {
+        std::io::copy(&mut stream.into_read(), &mut file)?;
+    } else {
+        archive::write_stream_seek(
+            &mut stream,
+            |stream| {
+                if gix::interrupt::is_triggered() {
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, "Cancelled by user").into());
+                }
+                let res = stream.next_entry();
+                entries.inc();
+                res
+            },
+            &mut file,
+            archive::Options {
+                format,
+                tree_prefix: prefix.map(gix::bstr::BString::from),
+                modification_time: modification_time.unwrap_or_else(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_secs())
+                        .unwrap_or_default() as gix::date::SecondsSinceUnixEpoch
+                }),
+            },
+        )?;
+    }
+
+    entries.show_throughput(start);
+    bytes.show_throughput(start);
+    Ok(())
+}
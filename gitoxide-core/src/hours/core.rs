@@ -1,48 +1,52 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap},
     sync::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
 };
 
-use gix::bstr::BStr;
+use gix::bstr::{BStr, BString, ByteSlice};
 
 use crate::hours::{
     util::{add_lines, remove_lines},
-    CommitIdx, FileStats, LineStats, WorkByEmail, WorkByPerson,
+    BreakdownKind, CommitIdx, FileStats, LineStats, PathChanges, WorkByEmail, WorkByPath, WorkByPerson,
 };
 
 const MINUTES_PER_HOUR: f32 = 60.0;
 pub const HOURS_PER_WORKDAY: f32 = 8.0;
 
+const MAX_COMMIT_DIFFERENCE_IN_MINUTES: f32 = 2.0 * MINUTES_PER_HOUR;
+const FIRST_COMMIT_ADDITION_IN_MINUTES: f32 = 2.0 * MINUTES_PER_HOUR;
+
+/// Return `(commit-index, hours)` for each of the `commits` of a single author, which are expected to be sorted
+/// by time in descending order, starting with the oldest commit.
+pub fn hours_by_commit(commits: &[(u32, gix::actor::SignatureRef<'static>)]) -> Vec<(CommitIdx, f32)> {
+    let mut out = Vec::with_capacity(commits.len());
+    let mut prev_seconds = None;
+    for (commit_idx, author) in commits.iter().rev() {
+        let hours = match prev_seconds {
+            Some(prev_seconds) => {
+                let change_in_minutes = (author.time.seconds.saturating_sub(prev_seconds)) as f32 / MINUTES_PER_HOUR;
+                if change_in_minutes < MAX_COMMIT_DIFFERENCE_IN_MINUTES {
+                    change_in_minutes / MINUTES_PER_HOUR
+                } else {
+                    FIRST_COMMIT_ADDITION_IN_MINUTES / MINUTES_PER_HOUR
+                }
+            }
+            None => FIRST_COMMIT_ADDITION_IN_MINUTES / MINUTES_PER_HOUR,
+        };
+        prev_seconds = Some(author.time.seconds);
+        out.push((*commit_idx, hours));
+    }
+    out
+}
+
 pub fn estimate_hours(
     commits: &[(u32, gix::actor::SignatureRef<'static>)],
-    stats: &[(u32, FileStats, LineStats)],
+    stats: &[(u32, FileStats, LineStats, PathChanges)],
 ) -> WorkByEmail {
     assert!(!commits.is_empty());
-    const MAX_COMMIT_DIFFERENCE_IN_MINUTES: f32 = 2.0 * MINUTES_PER_HOUR;
-    const FIRST_COMMIT_ADDITION_IN_MINUTES: f32 = 2.0 * MINUTES_PER_HOUR;
-
-    let hours_for_commits = {
-        let mut hours = 0.0;
-
-        let mut commits = commits.iter().map(|t| &t.1).rev();
-        let mut cur = commits.next().expect("at least one commit if we are here");
-
-        for next in commits {
-            let change_in_minutes = (next.time.seconds.saturating_sub(cur.time.seconds)) as f32 / MINUTES_PER_HOUR;
-            if change_in_minutes < MAX_COMMIT_DIFFERENCE_IN_MINUTES {
-                hours += change_in_minutes / MINUTES_PER_HOUR
-            } else {
-                hours += FIRST_COMMIT_ADDITION_IN_MINUTES / MINUTES_PER_HOUR
-            }
-            cur = next;
-        }
-
-        hours
-    };
-
     let author = &commits[0].1;
     let (files, lines) = (!stats.is_empty())
         .then(|| {
@@ -65,24 +69,56 @@ pub fn estimate_hours(
     WorkByEmail {
         name: author.name,
         email: author.email,
-        hours: FIRST_COMMIT_ADDITION_IN_MINUTES / 60.0 + hours_for_commits,
+        hours: hours_by_commit(commits).into_iter().map(|(_, hours)| hours).sum(),
         num_commits: commits.len() as u32,
         files,
         lines,
     }
 }
 
+/// Distribute the hours spent on each of the `commits` of a single author onto the paths they changed according to `stats`,
+/// and return the hours of commits that didn't change any of the selected paths, or that weren't diffed like merge commits.
+pub fn attribute_hours_to_paths(
+    commits: &[(u32, gix::actor::SignatureRef<'static>)],
+    stats: &[(u32, FileStats, LineStats, PathChanges)],
+    out: &mut BTreeMap<BString, WorkByPath>,
+) -> f32 {
+    let mut unattributed_hours = 0.0;
+    for (commit_idx, hours) in hours_by_commit(commits) {
+        let changes = match stats.binary_search_by(|t| t.0.cmp(&commit_idx)) {
+            Ok(idx) => &stats[idx].3,
+            Err(_) => {
+                unattributed_hours += hours;
+                continue;
+            }
+        };
+        let num_changes: usize = changes.iter().map(|(_, num_changes)| num_changes).sum();
+        if num_changes == 0 {
+            unattributed_hours += hours;
+            continue;
+        }
+        for (path, num_path_changes) in changes {
+            let work = out.entry(path.clone()).or_default();
+            work.hours += hours * *num_path_changes as f32 / num_changes as f32;
+            work.num_commits += 1;
+            work.files_changed += num_path_changes;
+        }
+    }
+    unattributed_hours
+}
+
 type CommitChangeLineCounters = (Arc<AtomicUsize>, Arc<AtomicUsize>, Arc<AtomicUsize>);
 
 type SpawnResultWithReturnChannelAndWorkers<'scope> = (
     crossbeam_channel::Sender<Vec<(CommitIdx, Option<gix::hash::ObjectId>, gix::hash::ObjectId)>>,
-    Vec<std::thread::ScopedJoinHandle<'scope, anyhow::Result<Vec<(CommitIdx, FileStats, LineStats)>>>>,
+    Vec<std::thread::ScopedJoinHandle<'scope, anyhow::Result<Vec<(CommitIdx, FileStats, LineStats, PathChanges)>>>>,
 );
 
 pub fn spawn_tree_delta_threads<'scope>(
     scope: &'scope std::thread::Scope<'scope, '_>,
     threads: usize,
     line_stats: bool,
+    breakdown: Option<(BreakdownKind, Option<gix::PathspecDetached>)>,
     repo: gix::Repository,
     stat_counters: CommitChangeLineCounters,
 ) -> SpawnResultWithReturnChannelAndWorkers<'scope> {
@@ -94,6 +130,7 @@ pub fn spawn_tree_delta_threads<'scope>(
                 let mut repo = repo.clone();
                 repo.object_cache_size_if_unset((850 * 1024 * 1024) / threads);
                 let rx = rx.clone();
+                let mut breakdown = breakdown.clone();
                 move || -> Result<_, anyhow::Error> {
                     let mut out = Vec::new();
                     let (commits, changes, lines_count) = stats_counters;
@@ -113,6 +150,7 @@ pub fn spawn_tree_delta_threads<'scope>(
                             }
                             let mut files = FileStats::default();
                             let mut lines = LineStats::default();
+                            let mut path_changes = BTreeMap::<BString, usize>::new();
                             let from = match parent_commit {
                                 Some(id) => match repo.find_object(id).ok().and_then(|c| c.peel_to_tree().ok()) {
                                     Some(tree) => tree,
@@ -124,65 +162,95 @@ pub fn spawn_tree_delta_threads<'scope>(
                                 Some(c) => c,
                                 None => continue,
                             };
-                            from.changes()?
-                                .track_filename()
-                                .track_rewrites(None)
-                                .for_each_to_obtain_tree(&to, |change| {
-                                    use gix::object::tree::diff::change::Event::*;
-                                    changes.fetch_add(1, Ordering::Relaxed);
-                                    match change.event {
-                                        Rewrite { .. } => {
-                                            unreachable!("we turned that off")
-                                        }
-                                        Addition { entry_mode, id } => {
-                                            if entry_mode.is_no_tree() {
-                                                files.added += 1;
-                                                add_lines(line_stats, &lines_count, &mut lines, id);
-                                            }
-                                        }
-                                        Deletion { entry_mode, id } => {
-                                            if entry_mode.is_no_tree() {
-                                                files.removed += 1;
-                                                remove_lines(line_stats, &lines_count, &mut lines, id);
-                                            }
+                            let mut platform = from.changes()?;
+                            if breakdown.is_some() {
+                                platform.track_path();
+                            } else {
+                                platform.track_filename();
+                            }
+                            platform.track_rewrites(None).for_each_to_obtain_tree(&to, |change| {
+                                use gix::object::tree::diff::change::Event::*;
+                                changes.fetch_add(1, Ordering::Relaxed);
+                                if let Some((kind, pathspec)) = breakdown.as_mut() {
+                                    let is_file_change = match change.event {
+                                        Addition { entry_mode, .. } | Deletion { entry_mode, .. } => {
+                                            entry_mode.is_no_tree()
                                         }
                                         Modification {
                                             entry_mode,
                                             previous_entry_mode,
-                                            id,
-                                            previous_id,
-                                        } => match (previous_entry_mode.is_blob(), entry_mode.is_blob()) {
-                                            (false, false) => {}
-                                            (false, true) => {
-                                                files.added += 1;
-                                                add_lines(line_stats, &lines_count, &mut lines, id);
-                                            }
-                                            (true, false) => {
-                                                files.removed += 1;
-                                                remove_lines(line_stats, &lines_count, &mut lines, previous_id);
-                                            }
-                                            (true, true) => {
-                                                files.modified += 1;
-                                                if let Some(cache) = cache.as_mut() {
-                                                    let mut diff = change.diff(cache).map_err(|err| {
-                                                        std::io::Error::new(std::io::ErrorKind::Other, err)
-                                                    })?;
-                                                    let mut nl = 0;
-                                                    if let Some(counts) = diff.line_counts().map_err(|err| {
-                                                        std::io::Error::new(std::io::ErrorKind::Other, err)
-                                                    })? {
-                                                        nl += counts.insertions as usize + counts.removals as usize;
-                                                        lines.added += counts.insertions as usize;
-                                                        lines.removed += counts.removals as usize;
-                                                        lines_count.fetch_add(nl, Ordering::Relaxed);
-                                                    }
+                                            ..
+                                        } => entry_mode.is_blob() || previous_entry_mode.is_blob(),
+                                        Rewrite { .. } => unreachable!("we turned that off"),
+                                    };
+                                    if is_file_change
+                                        && pathspec
+                                            .as_mut()
+                                            .map_or(true, |pathspec| pathspec.is_included(change.location, Some(false)))
+                                    {
+                                        let path = match kind {
+                                            BreakdownKind::File => change.location,
+                                            BreakdownKind::TopLevelDirectory => change
+                                                .location
+                                                .find_byte(b'/')
+                                                .map_or(".".into(), |pos| change.location[..pos].as_bstr()),
+                                        };
+                                        *path_changes.entry(path.to_owned()).or_default() += 1;
+                                    }
+                                }
+                                match change.event {
+                                    Rewrite { .. } => {
+                                        unreachable!("we turned that off")
+                                    }
+                                    Addition { entry_mode, id } => {
+                                        if entry_mode.is_no_tree() {
+                                            files.added += 1;
+                                            add_lines(line_stats, &lines_count, &mut lines, id);
+                                        }
+                                    }
+                                    Deletion { entry_mode, id } => {
+                                        if entry_mode.is_no_tree() {
+                                            files.removed += 1;
+                                            remove_lines(line_stats, &lines_count, &mut lines, id);
+                                        }
+                                    }
+                                    Modification {
+                                        entry_mode,
+                                        previous_entry_mode,
+                                        id,
+                                        previous_id,
+                                    } => match (previous_entry_mode.is_blob(), entry_mode.is_blob()) {
+                                        (false, false) => {}
+                                        (false, true) => {
+                                            files.added += 1;
+                                            add_lines(line_stats, &lines_count, &mut lines, id);
+                                        }
+                                        (true, false) => {
+                                            files.removed += 1;
+                                            remove_lines(line_stats, &lines_count, &mut lines, previous_id);
+                                        }
+                                        (true, true) => {
+                                            files.modified += 1;
+                                            if let Some(cache) = cache.as_mut() {
+                                                let mut diff = change.diff(cache).map_err(|err| {
+                                                    std::io::Error::new(std::io::ErrorKind::Other, err)
+                                                })?;
+                                                let mut nl = 0;
+                                                if let Some(counts) = diff.line_counts().map_err(|err| {
+                                                    std::io::Error::new(std::io::ErrorKind::Other, err)
+                                                })? {
+                                                    nl += counts.insertions as usize + counts.removals as usize;
+                                                    lines.added += counts.insertions as usize;
+                                                    lines.removed += counts.removals as usize;
+                                                    lines_count.fetch_add(nl, Ordering::Relaxed);
                                                 }
                                             }
-                                        },
-                                    }
-                                    Ok::<_, std::io::Error>(Default::default())
-                                })?;
-                            out.push((commit_idx, files, lines));
+                                        }
+                                    },
+                                }
+                                Ok::<_, std::io::Error>(Default::default())
+                            })?;
+                            out.push((commit_idx, files, lines, path_changes.into_iter().collect()));
                         }
                     }
                     Ok(out)
@@ -1,9 +1,14 @@
-use std::{collections::BTreeSet, io, path::Path, time::Instant};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    path::Path,
+    time::Instant,
+};
 
 use anyhow::bail;
 use gix::{
     actor,
-    bstr::{BStr, ByteSlice},
+    bstr::{BStr, BString, ByteSlice},
     prelude::*,
     progress, Count, NestedProgress, Progress,
 };
@@ -23,10 +28,43 @@ pub struct Context<W> {
     /// Omit unifying identities by name and email which can lead to the same author appear multiple times
     /// due to using different names or email addresses.
     pub omit_unify_identities: bool,
+    /// If set, attribute the estimated time to paths as well.
+    pub breakdown: Option<Breakdown>,
     /// Where to write our output to
     pub out: W,
 }
 
+/// Configure how to attribute the estimated time to paths, using the files changed by each commit.
+pub struct Breakdown {
+    /// The paths to attribute time to.
+    pub kind: BreakdownKind,
+    /// If not empty, only files matching these pathspecs receive a share of the time spent on a commit.
+    pub pathspecs: Vec<BString>,
+    /// How to display the breakdown.
+    pub format: BreakdownFormat,
+}
+
+/// The paths to attribute the estimated time to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BreakdownKind {
+    /// Attribute time to the top-level directories of the repository, with files at its root attributed to `.`.
+    TopLevelDirectory,
+    /// Attribute time to each file individually.
+    File,
+}
+
+/// The format in which to write the breakdown of time by path.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BreakdownFormat {
+    /// Display the breakdown after the summary.
+    Human,
+    /// Write only the breakdown as comma-separated values, with a header line.
+    Csv,
+    /// Write only the breakdown as array of JSON objects.
+    #[cfg(feature = "serde")]
+    Json,
+}
+
 /// Estimate the hours it takes to produce the content of the repository in `_working_dir_`, with `_refname_` for
 /// the start of the commit graph traversal.
 ///
@@ -44,6 +82,7 @@ pub fn estimate<W, P>(
         line_stats,
         omit_unify_identities,
         threads,
+        breakdown,
         mut out,
     }: Context<W>,
 ) -> anyhow::Result<()>
@@ -54,7 +93,23 @@ where
     let repo = gix::discover(working_dir)?;
     let commit_id = repo.rev_parse_single(rev_spec)?.detach();
     let mut string_heap = BTreeSet::<&'static [u8]>::new();
-    let needs_stats = file_stats || line_stats;
+    let needs_stats = file_stats || line_stats || breakdown.is_some();
+    let breakdown_pathspec = match &breakdown {
+        Some(breakdown) if !breakdown.pathspecs.is_empty() => {
+            let index = repo.index_or_empty()?;
+            Some(
+                repo.pathspec(
+                    true,
+                    &breakdown.pathspecs,
+                    false,
+                    &index,
+                    gix::worktree::stack::state::attributes::Source::IdMapping,
+                )?
+                .detach()?,
+            )
+        }
+        _ => None,
+    };
     let threads = gix::features::parallel::num_threads(threads);
 
     let (commit_authors, stats, is_shallow, skipped_merge_commits) = {
@@ -129,6 +184,9 @@ where
                         scope,
                         threads,
                         line_stats,
+                        breakdown
+                            .as_ref()
+                            .map(|breakdown| (breakdown.kind, breakdown_pathspec.clone())),
                         repo.clone(),
                         stats_counters.clone().expect("counters are set"),
                     );
@@ -169,9 +227,8 @@ where
                                 tx_tree
                                     .send(std::mem::replace(&mut chunk, Vec::with_capacity(CHUNK_SIZE)))
                                     .ok();
-                            } else {
-                                chunk.push((commit_idx, first_parent, commit))
                             }
+                            chunk.push((commit_idx, first_parent, commit));
                         }
                         commit_idx += 1;
                     }
@@ -226,19 +283,28 @@ where
     let mut slice_start = 0;
     let mut results_by_hours = Vec::new();
     let mut ignored_bot_commits = 0_u32;
+    let mut results_by_path = BTreeMap::new();
+    let mut unattributed_hours = 0.0;
     for (idx, (_, elm)) in commit_authors.iter().enumerate() {
         if elm.email != *current_email {
-            let estimate = estimate_hours(&commit_authors[slice_start..idx], &stats);
+            let commits = &commit_authors[slice_start..idx];
+            let estimate = estimate_hours(commits, &stats);
             slice_start = idx;
             current_email = &elm.email;
             if ignore_bots && estimate.name.contains_str(b"[bot]") {
                 ignored_bot_commits += estimate.num_commits;
                 continue;
             }
+            if breakdown.is_some() {
+                unattributed_hours += attribute_hours_to_paths(commits, &stats, &mut results_by_path);
+            }
             results_by_hours.push(estimate);
         }
     }
     if let Some(commits) = commit_authors.get(slice_start..) {
+        if breakdown.is_some() {
+            unattributed_hours += attribute_hours_to_paths(commits, &stats, &mut results_by_path);
+        }
         results_by_hours.push(estimate_hours(commits, &stats));
     }
 
@@ -267,6 +333,82 @@ where
         .map(|e| (e.hours, e.num_commits, e.files, e.lines))
         .reduce(|a, b| (a.0 + b.0, a.1 + b.1, a.2.clone().added(&b.2), a.3.clone().added(&b.3)))
         .expect("at least one commit at this point");
+    if let Some(Breakdown { kind, format, .. }) = breakdown.as_ref() {
+        let mut results_by_path: Vec<_> = results_by_path.into_iter().collect();
+        results_by_path.sort_by(|a, b| {
+            b.1.hours
+                .partial_cmp(&a.1.hours)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        match format {
+            BreakdownFormat::Human => {}
+            BreakdownFormat::Csv => {
+                writeln!(out, "path,hours,commits,files_changed")?;
+                for (path, work) in &results_by_path {
+                    writeln!(
+                        out,
+                        "{},{:.02},{},{}",
+                        csv_field(path.as_ref()),
+                        work.hours,
+                        work.num_commits,
+                        work.files_changed
+                    )?;
+                }
+                return Ok(());
+            }
+            #[cfg(feature = "serde")]
+            BreakdownFormat::Json => {
+                #[derive(serde::Serialize)]
+                struct JsonWorkByPath {
+                    path: String,
+                    hours: f32,
+                    commits: u32,
+                    files_changed: usize,
+                }
+                serde_json::to_writer_pretty(
+                    &mut out,
+                    &results_by_path
+                        .iter()
+                        .map(|(path, work)| JsonWorkByPath {
+                            path: path.to_string(),
+                            hours: work.hours,
+                            commits: work.num_commits,
+                            files_changed: work.files_changed,
+                        })
+                        .collect::<Vec<_>>(),
+                )?;
+                writeln!(out)?;
+                return Ok(());
+            }
+        }
+        writeln!(
+            out,
+            "time spent by {}:",
+            match kind {
+                BreakdownKind::TopLevelDirectory => "top-level directory",
+                BreakdownKind::File => "file",
+            }
+        )?;
+        for (path, work) in &results_by_path {
+            writeln!(
+                out,
+                "{:>10.02}h ({:>6.02}%) in {} commits: {}",
+                work.hours,
+                (work.hours / total_hours) * 100.0,
+                work.num_commits,
+                path
+            )?;
+        }
+        if unattributed_hours != 0.0 {
+            writeln!(
+                out,
+                "{unattributed_hours:>10.02}h ({:>6.02}%) in commits without selected changes, merges and root commits",
+                (unattributed_hours / total_hours) * 100.0
+            )?;
+        }
+        writeln!(out)?;
+    }
     if show_pii {
         results_by_hours.sort_by(|a, b| a.hours.partial_cmp(&b.hours).unwrap_or(std::cmp::Ordering::Equal));
         for entry in &results_by_hours {
@@ -329,10 +471,22 @@ where
     Ok(())
 }
 
+/// Quote `field` for use in a CSV file if it contains characters that would otherwise be ambiguous.
+fn csv_field(field: &BStr) -> std::borrow::Cow<'_, BStr> {
+    if field.find_byteset(b",\"\r\n").is_some() {
+        let mut quoted = BString::from("\"");
+        quoted.extend_from_slice(&field.replace("\"", "\"\""));
+        quoted.push(b'"');
+        quoted.into()
+    } else {
+        field.into()
+    }
+}
+
 mod core;
-use self::core::{deduplicate_identities, estimate_hours, HOURS_PER_WORKDAY};
+use self::core::{attribute_hours_to_paths, deduplicate_identities, estimate_hours, HOURS_PER_WORKDAY};
 
 mod util;
-use util::{CommitIdx, FileStats, LineStats, WorkByEmail, WorkByPerson};
+use util::{CommitIdx, FileStats, LineStats, PathChanges, WorkByEmail, WorkByPath, WorkByPerson};
 
 use crate::hours::core::spawn_tree_delta_threads;
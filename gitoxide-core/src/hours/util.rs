@@ -1,6 +1,6 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
-use gix::bstr::{BStr, ByteSlice};
+use gix::bstr::{BStr, BString, ByteSlice};
 
 use crate::hours::core::HOURS_PER_WORKDAY;
 
@@ -183,9 +183,22 @@ impl LineStats {
     }
 }
 
+/// The time spent on a path, as attributed from the commits that changed it.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct WorkByPath {
+    pub hours: f32,
+    /// The amount of commits that changed the path.
+    pub num_commits: u32,
+    /// The amount of files that were added, removed or modified in the path, summed over all commits.
+    pub files_changed: usize,
+}
+
 /// An index able to address any commit
 pub type CommitIdx = u32;
 
+/// The amount of changed files in a particular commit by the path they were attributed to.
+pub type PathChanges = Vec<(BString, usize)>;
+
 pub fn add_lines(line_stats: bool, lines_counter: &AtomicUsize, lines: &mut LineStats, id: gix::Id<'_>) {
     if let Some(Ok(blob)) = line_stats.then(|| id.object()) {
         let nl = blob.data.lines_with_terminator().count();
@@ -63,6 +63,19 @@ impl FromStr for OutputFormat {
     }
 }
 
+/// Write `value` as a single line of JSON to `out`.
+///
+/// Commands that produce lists with [`OutputFormat::Json`] write one object per line, so their output can be
+/// consumed line by line.
+#[cfg(feature = "serde")]
+pub(crate) fn write_json_line(mut out: impl std::io::Write, value: &impl serde::Serialize) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut out, value)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+#[cfg(feature = "archive")]
+pub mod archive;
 pub mod commitgraph;
 #[cfg(feature = "corpus")]
 pub mod corpus;
@@ -77,7 +90,11 @@ pub mod organize;
 pub mod pack;
 #[cfg(feature = "query")]
 pub mod query;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod remote;
 pub mod repository;
+#[cfg(feature = "blocking-client")]
+pub mod serve;
 
 mod discover;
 pub use discover::discover;
@@ -4,7 +4,10 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use gix::{objs::bstr::ByteSlice, progress, NestedProgress, Progress};
+use gix::{
+    objs::bstr::{BString, ByteSlice},
+    progress, NestedProgress, Progress,
+};
 
 #[derive(Default, Copy, Clone, Eq, PartialEq)]
 pub enum Mode {
@@ -101,21 +104,163 @@ fn find_origin_remote(repo: &Path) -> anyhow::Result<Option<gix_url::Url>> {
         .transpose()?)
 }
 
-fn handle(
-    mode: Mode,
-    kind: gix::repository::Kind,
-    git_workdir: &Path,
-    canonicalized_destination: &Path,
-    progress: &mut impl Progress,
-) -> anyhow::Result<()> {
-    if let gix::repository::Kind::WorkTree { is_linked: true } = kind {
-        return Ok(());
+/// The layout used if none is specified, which places repositories at a path matching their `origin` URL.
+pub const DEFAULT_LAYOUT: &str = "{host}/{path}";
+
+/// Additional configuration for [`run()`].
+pub struct Options {
+    /// A template for the path of each repository relative to the destination directory, with placeholders
+    /// like `{host}` replaced by parts of the URL of its `origin` remote.
+    ///
+    /// Supported placeholders are `{host}`, `{port}`, `{path}` for the entire path of the URL, `{owner}` for all but
+    /// its last component and `{repo}` for its last component. See [`DEFAULT_LAYOUT`] for an example.
+    pub layout: String,
+    /// Skip repositories whose path relative to the source directory, or whose `origin` URL, matches any of these glob patterns.
+    pub excludes: Vec<BString>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Placeholder {
+    Host,
+    Port,
+    Path,
+    Owner,
+    Repo,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+enum Token {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+fn parse_layout(layout: &str) -> anyhow::Result<Vec<Token>> {
+    let mut out = Vec::new();
+    let mut rest = layout;
+    while let Some(start) = rest.find('{') {
+        if start != 0 {
+            out.push(Token::Literal(rest[..start].to_owned()));
+        }
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed placeholder in layout {layout:?}"))?
+            + start;
+        out.push(Token::Placeholder(match &rest[start + 1..end] {
+            "host" => Placeholder::Host,
+            "port" => Placeholder::Port,
+            "path" => Placeholder::Path,
+            "owner" => Placeholder::Owner,
+            "repo" => Placeholder::Repo,
+            unknown => anyhow::bail!(
+                "Unknown placeholder {{{unknown}}} in layout {layout:?}, expected one of {{host}}, {{port}}, {{path}}, {{owner}} or {{repo}}"
+            ),
+        }));
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        out.push(Token::Literal(rest.to_owned()));
     }
+    Ok(out)
+}
+
+/// Substitute the placeholders in `layout` with the respective parts of `url` and return the resulting relative path,
+/// or `None` along with the name of the placeholder that had no value.
+fn expand_layout(
+    layout: &[Token],
+    url: &gix_url::Url,
+    kind: gix::repository::Kind,
+) -> anyhow::Result<Result<PathBuf, &'static str>> {
     fn to_relative(path: PathBuf) -> PathBuf {
         path.components()
             .skip_while(|c| c == &std::path::Component::RootDir)
             .collect()
     }
+    let path = to_relative({
+        let mut path = gix_url::expand_path(None, url.path.as_bstr())?;
+        match kind {
+            gix::repository::Kind::Submodule => {
+                unreachable!("BUG: We should not try to relocated submodules and not find them the first place")
+            }
+            gix::repository::Kind::Bare => path,
+            gix::repository::Kind::WorkTree { .. } => {
+                if let Some(ext) = path.extension() {
+                    if ext == "git" {
+                        path.set_extension("");
+                    }
+                }
+                path
+            }
+        }
+    });
+
+    let mut out = String::new();
+    for token in layout {
+        match token {
+            Token::Literal(literal) => out.push_str(literal),
+            Token::Placeholder(placeholder) => {
+                let value = match placeholder {
+                    Placeholder::Host => url.host().map(ToOwned::to_owned),
+                    Placeholder::Port => url.port.map(|port| port.to_string()),
+                    Placeholder::Path => Some(path.to_string_lossy().into_owned()),
+                    Placeholder::Owner => path.parent().map(|owner| owner.to_string_lossy().into_owned()),
+                    Placeholder::Repo => path.file_name().map(|repo| repo.to_string_lossy().into_owned()),
+                };
+                match value {
+                    Some(value) => out.push_str(&value),
+                    None => {
+                        return Ok(Err(match placeholder {
+                            Placeholder::Host => "host",
+                            Placeholder::Port => "port",
+                            Placeholder::Path => "path",
+                            Placeholder::Owner => "owner",
+                            Placeholder::Repo => "repo",
+                        }))
+                    }
+                }
+            }
+        }
+    }
+    // Empty placeholders would leave empty components behind, which are dropped here.
+    let out: PathBuf = Path::new(&out)
+        .components()
+        .filter(|c| matches!(c, std::path::Component::Normal(_)))
+        .collect();
+    if Path::new(&out).components().count() == 0 {
+        return Ok(Err("path"));
+    }
+    Ok(Ok(out))
+}
+
+fn is_excluded(excludes: &[BString], git_workdir: &Path, source_dir: &Path, url: Option<&gix_url::Url>) -> bool {
+    if excludes.is_empty() {
+        return false;
+    }
+    let relative_path = gix::path::to_unix_separators_on_windows(gix::path::into_bstr(
+        git_workdir.strip_prefix(source_dir).unwrap_or(git_workdir),
+    ));
+    let url = url.map(gix_url::Url::to_bstring);
+    excludes.iter().any(|pattern| {
+        std::iter::once(relative_path.as_ref())
+            .chain(url.as_ref().map(AsRef::as_ref))
+            .any(|value| gix::glob::wildmatch(pattern.as_ref(), value, gix::glob::wildmatch::Mode::empty()))
+    })
+}
+
+/// Return the absolute and the displayable destination of the repository at `git_workdir`, or `None` if it should stay where it is.
+#[allow(clippy::too_many_arguments)]
+fn plan(
+    kind: gix::repository::Kind,
+    git_workdir: &Path,
+    source_dir: &Path,
+    destination: &Path,
+    canonicalized_destination: &Path,
+    layout: &[Token],
+    excludes: &[BString],
+    progress: &mut impl Progress,
+) -> anyhow::Result<Option<(PathBuf, PathBuf)>> {
+    if let gix::repository::Kind::WorkTree { is_linked: true } = kind {
+        return Ok(None);
+    }
 
     fn find_parent_repo(mut git_workdir: &Path) -> Option<PathBuf> {
         while let Some(parent) = git_workdir.parent() {
@@ -142,16 +287,21 @@ fn handle(
             git_workdir.display(),
             parent_repo_path
         ));
-        return Ok(());
+        return Ok(None);
     }
 
-    let url = match find_origin_remote(git_workdir)? {
+    let url = find_origin_remote(git_workdir)?;
+    if is_excluded(excludes, git_workdir, source_dir, url.as_ref()) {
+        progress.info(format!("Skipping excluded repository at {:?}", git_workdir.display()));
+        return Ok(None);
+    }
+    let url = match url {
         None => {
             progress.info(format!(
                 "Skipping repository {:?} without 'origin' remote",
                 git_workdir.display()
             ));
-            return Ok(());
+            return Ok(None);
         }
         Some(url) => url,
     };
@@ -161,65 +311,60 @@ fn handle(
             git_workdir.display(),
             url.to_bstring()
         ));
-        return Ok(());
+        return Ok(None);
     }
 
-    let destination = canonicalized_destination
-        .join(match url.host() {
-            Some(h) => h,
-            None => return Ok(()),
-        })
-        .join(to_relative({
-            let mut path = gix_url::expand_path(None, url.path.as_bstr())?;
-            match kind {
-                gix::repository::Kind::Submodule => {
-                    unreachable!("BUG: We should not try to relocated submodules and not find them the first place")
-                }
-                gix::repository::Kind::Bare => path,
-                gix::repository::Kind::WorkTree { .. } => {
-                    if let Some(ext) = path.extension() {
-                        if ext == "git" {
-                            path.set_extension("");
-                        }
-                    }
-                    path
-                }
-            }
-        }));
-
-    if let Ok(destination) = destination.canonicalize() {
-        if git_workdir.canonicalize()? == destination {
-            return Ok(());
+    let relative_destination = match expand_layout(layout, &url, kind)? {
+        Ok(path) => path,
+        Err(placeholder) => {
+            progress.info(format!(
+                "Skipping repository at {:?} as its remote URL {:?} has no value for the '{placeholder}' placeholder",
+                git_workdir.display(),
+                url.to_bstring()
+            ));
+            return Ok(None);
         }
-    }
-    match mode {
-        Mode::Simulate => progress.info(format!(
-            "WOULD move {} to {}",
+    };
+    if relative_destination
+        .components()
+        .any(|c| c == std::path::Component::ParentDir)
+    {
+        progress.fail(format!(
+            "Skipping repository at {:?} as its destination {:?} would be outside of the destination directory",
             git_workdir.display(),
-            destination.display()
-        )),
-        Mode::Execute => {
-            if destination.starts_with(
-                git_workdir
-                    .canonicalize()
-                    .ok()
-                    .map(Cow::Owned)
-                    .unwrap_or(Cow::Borrowed(git_workdir)),
-            ) {
-                let tempdir = tempfile::tempdir_in(canonicalized_destination)?;
-                let tempdest = tempdir
-                    .path()
-                    .join(destination.file_name().expect("repo destination is not the root"));
-                std::fs::rename(git_workdir, &tempdest)?;
-                std::fs::create_dir_all(destination.parent().expect("repo destination is not the root"))?;
-                std::fs::rename(&tempdest, &destination)?;
-            } else {
-                std::fs::create_dir_all(destination.parent().expect("repo destination is not the root"))?;
-                std::fs::rename(git_workdir, &destination)?;
-            }
-            progress.done(format!("Moving {} to {}", git_workdir.display(), destination.display()));
+            relative_destination
+        ));
+        return Ok(None);
+    }
+
+    let absolute_destination = canonicalized_destination.join(&relative_destination);
+    if let Ok(absolute_destination) = absolute_destination.canonicalize() {
+        if git_workdir.canonicalize()? == absolute_destination {
+            return Ok(None);
         }
     }
+    Ok(Some((absolute_destination, destination.join(relative_destination))))
+}
+
+fn move_repository(git_workdir: &Path, destination: &Path, canonicalized_destination: &Path) -> anyhow::Result<()> {
+    if destination.starts_with(
+        git_workdir
+            .canonicalize()
+            .ok()
+            .map(Cow::Owned)
+            .unwrap_or(Cow::Borrowed(git_workdir)),
+    ) {
+        let tempdir = tempfile::tempdir_in(canonicalized_destination)?;
+        let tempdest = tempdir
+            .path()
+            .join(destination.file_name().expect("repo destination is not the root"));
+        std::fs::rename(git_workdir, &tempdest)?;
+        std::fs::create_dir_all(destination.parent().expect("repo destination is not the root"))?;
+        std::fs::rename(&tempdest, destination)?;
+    } else {
+        std::fs::create_dir_all(destination.parent().expect("repo destination is not the root"))?;
+        std::fs::rename(git_workdir, destination)?;
+    }
     Ok(())
 }
 
@@ -239,25 +384,80 @@ pub fn discover<P: NestedProgress>(
     Ok(())
 }
 
+/// Move all repositories found in `source_dir` into `destination` according to the layout in `options`.
+///
+/// All planned moves are written to `out` first, and only performed if `mode` is [`Mode::Execute`].
 pub fn run<P: NestedProgress>(
     mode: Mode,
     source_dir: impl AsRef<Path>,
     destination: impl AsRef<Path>,
+    Options { layout, excludes }: Options,
+    mut out: impl std::io::Write,
     mut progress: P,
     threads: Option<usize>,
 ) -> anyhow::Result<()> {
+    let layout = parse_layout(&layout)?;
     let mut num_errors = 0usize;
-    let destination = destination.as_ref().canonicalize()?;
+    let source_dir = source_dir.as_ref();
+    let destination = destination.as_ref();
+    let canonicalized_destination = destination.canonicalize()?;
+    let mut moves = Vec::new();
     for (path_to_move, kind) in
         find_git_repository_workdirs(source_dir, progress.add_child("Searching repositories"), false, threads)
     {
-        if let Err(err) = handle(mode, kind, &path_to_move, &destination, &mut progress) {
-            progress.fail(format!(
-                "Error when handling directory {:?}: {}",
-                path_to_move.display(),
-                err
-            ));
-            num_errors += 1;
+        match plan(
+            kind,
+            &path_to_move,
+            source_dir,
+            destination,
+            &canonicalized_destination,
+            &layout,
+            &excludes,
+            &mut progress,
+        ) {
+            Ok(Some((absolute_destination, display_destination))) => {
+                writeln!(out, "{} -> {}", path_to_move.display(), display_destination.display())?;
+                moves.push((path_to_move, absolute_destination));
+            }
+            Ok(None) => {}
+            Err(err) => {
+                progress.fail(format!(
+                    "Error when handling directory {:?}: {}",
+                    path_to_move.display(),
+                    err
+                ));
+                num_errors += 1;
+            }
+        }
+    }
+
+    match mode {
+        Mode::Simulate => {
+            if !moves.is_empty() {
+                progress.info(format!(
+                    "WOULD move {} repositories, pass --execute to apply",
+                    moves.len()
+                ));
+            }
+        }
+        Mode::Execute => {
+            for (path_to_move, destination) in moves {
+                match move_repository(&path_to_move, &destination, &canonicalized_destination) {
+                    Ok(()) => progress.done(format!(
+                        "Moving {} to {}",
+                        path_to_move.display(),
+                        destination.display()
+                    )),
+                    Err(err) => {
+                        progress.fail(format!(
+                            "Error when moving directory {:?}: {}",
+                            path_to_move.display(),
+                            err
+                        ));
+                        num_errors += 1;
+                    }
+                }
+            }
         }
     }
 
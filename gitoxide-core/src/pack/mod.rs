@@ -1,6 +1,7 @@
 pub mod explode;
 pub mod index;
 pub mod multi_index;
+pub mod stats;
 pub mod verify;
 
 #[cfg(any(feature = "async-client", feature = "blocking-client"))]
@@ -0,0 +1,150 @@
+use std::{io, path::Path, sync::atomic::AtomicBool};
+
+use anyhow::{Context as AnyhowContext, Result};
+use bytesize::ByteSize;
+use gix::{odb::pack, NestedProgress};
+
+use crate::OutputFormat;
+
+pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
+
+pub struct Context<'a, W: io::Write> {
+    /// If set, list all objects like `git verify-pack -v` does before the summary.
+    pub objects: bool,
+    /// The amount of objects occupying the most space in the pack to list.
+    pub top: usize,
+    pub format: OutputFormat,
+    pub out: W,
+    pub should_interrupt: &'a AtomicBool,
+    pub object_hash: gix::hash::Kind,
+}
+
+pub fn pack_or_pack_index<W: io::Write>(
+    path: impl AsRef<Path>,
+    mut progress: impl NestedProgress + 'static,
+    Context {
+        objects,
+        top,
+        format,
+        mut out,
+        should_interrupt,
+        object_hash,
+    }: Context<'_, W>,
+) -> Result<()> {
+    let path = path.as_ref();
+    let bundle = pack::Bundle::at(path, object_hash)
+        .with_context(|| format!("Could not open pack and index at '{}'", path.display()))?;
+    progress.set_name("analyze".into());
+    let outcome = bundle.analyze(&mut progress, should_interrupt)?;
+
+    match format {
+        OutputFormat::Human => {
+            if objects {
+                print_objects(&mut out, &outcome.objects)?;
+            }
+            print_statistics(&mut out, &outcome.statistics)?;
+            print_top_space_consumers(&mut out, &outcome.top_space_consumers(top))?;
+        }
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => {
+            #[derive(serde::Serialize)]
+            struct Stats<'a> {
+                #[serde(skip_serializing_if = "Option::is_none")]
+                objects: Option<&'a [pack::bundle::analyze::Object]>,
+                statistics: &'a pack::bundle::analyze::Statistics,
+                top_space_consumers: Vec<&'a pack::bundle::analyze::Object>,
+            }
+            crate::write_json_line(
+                out,
+                &Stats {
+                    objects: objects.then_some(outcome.objects.as_slice()),
+                    statistics: &outcome.statistics,
+                    top_space_consumers: outcome.top_space_consumers(top),
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn print_objects(out: &mut impl io::Write, objects: &[pack::bundle::analyze::Object]) -> io::Result<()> {
+    for object in objects {
+        write!(
+            out,
+            "{} {:<6} {} {} {}",
+            object.id, object.kind, object.decompressed_size, object.compressed_size, object.pack_offset
+        )?;
+        if let Some(base) = object.base {
+            write!(out, " {} {}", object.num_deltas, base)?;
+        }
+        writeln!(out)?;
+    }
+    writeln!(out)
+}
+
+fn print_statistics(out: &mut impl io::Write, stats: &pack::bundle::analyze::Statistics) -> io::Result<()> {
+    writeln!(out, "objects per delta chain length")?;
+    for (chain_length, object_count) in &stats.objects_per_chain_length {
+        writeln!(out, "\t{chain_length:>2}: {object_count}")?;
+    }
+    writeln!(out, "\t->: {}", stats.total.count)?;
+    if let Some((id, chain_length)) = stats.longest_chain {
+        writeln!(out, "\tlongest chain: {chain_length} ({id})")?;
+    }
+
+    writeln!(out, "\nobjects by kind")?;
+    writeln!(
+        out,
+        "\t{:<8} {:>8} {:>10} {:>12} {:>12} {:>12}",
+        "kind", "count", "deltified", "compressed", "inflated", "object size"
+    )?;
+    for (name, summary) in [
+        ("commits", &stats.commits),
+        ("trees", &stats.trees),
+        ("blobs", &stats.blobs),
+        ("tags", &stats.tags),
+        ("total", &stats.total),
+    ] {
+        writeln!(
+            out,
+            "\t{:<8} {:>8} {:>10} {:>12} {:>12} {:>12}",
+            name,
+            summary.count,
+            summary.num_deltified,
+            ByteSize(summary.compressed_size).to_string(),
+            ByteSize(summary.decompressed_size).to_string(),
+            ByteSize(summary.object_size).to_string()
+        )?;
+    }
+
+    writeln!(out, "\nlargest objects")?;
+    for (name, summary) in [
+        ("commit", &stats.commits),
+        ("tree", &stats.trees),
+        ("blob", &stats.blobs),
+        ("tag", &stats.tags),
+    ] {
+        if let Some((id, size)) = summary.largest_object {
+            writeln!(out, "\t{name:<6} {} ({id})", ByteSize(size))?;
+        }
+    }
+    writeln!(out, "\n\tpack size: {}", ByteSize(stats.pack_size))
+}
+
+fn print_top_space_consumers(out: &mut impl io::Write, objects: &[&pack::bundle::analyze::Object]) -> io::Result<()> {
+    if objects.is_empty() {
+        return Ok(());
+    }
+    writeln!(out, "\ntop space consumers")?;
+    for object in objects {
+        writeln!(
+            out,
+            "\t{} {:<6} {:>10} in pack, {:>10} as object",
+            object.id,
+            object.kind,
+            ByteSize(object.compressed_size).to_string(),
+            ByteSize(object.object_size).to_string()
+        )?;
+    }
+    Ok(())
+}
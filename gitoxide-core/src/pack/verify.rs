@@ -175,7 +175,16 @@ where
                             }
                         },
                         #[cfg(feature = "serde")]
-                        Some(OutputFormat::Json) => serde_json::to_writer_pretty(out, &multi_index.index_names().iter().zip(res.pack_traverse_statistics).collect::<Vec<_>>())?,
+                        Some(OutputFormat::Json) => {
+                            for (index_name, stats) in multi_index.index_names().iter().zip(res.pack_traverse_statistics) {
+                                #[derive(serde::Serialize)]
+                                struct IndexStatistics<'a> {
+                                    index: &'a Path,
+                                    statistics: index::traverse::Statistics,
+                                }
+                                crate::write_json_line(&mut out, &IndexStatistics { index: index_name, statistics: stats })?;
+                            }
+                        },
                         _ => {}
                     };
                     return Ok(())
@@ -193,7 +202,7 @@ where
         match output_statistics {
             Some(OutputFormat::Human) => drop(print_statistics(&mut out, stats)),
             #[cfg(feature = "serde")]
-            Some(OutputFormat::Json) => serde_json::to_writer_pretty(out, stats)?,
+            Some(OutputFormat::Json) => crate::write_json_line(out, stats)?,
             _ => {}
         };
     }
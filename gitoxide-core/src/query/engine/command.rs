@@ -123,7 +123,85 @@ impl query::Engine {
                 }
                 Ok(())
             }
+            Command::Authors => {
+                let start = std::time::Instant::now();
+                let mut progress = progress.add_child("run sql query");
+                progress.init(None, gix::progress::count("authors"));
+                let mut by_author = self.con.prepare(
+                    r#"
+                    SELECT commits.author_name, commits.author_email, COUNT(DISTINCT commits.hash),
+                           COUNT(commit_file.file_id),
+                           COALESCE(SUM(commit_file.lines_added), 0), COALESCE(SUM(commit_file.lines_removed), 0)
+                    FROM commits LEFT JOIN commit_file ON commit_file.hash = commits.hash
+                    WHERE commits.author_email IS NOT NULL
+                    GROUP BY commits.author_email
+                    ORDER BY 3 DESC, 2
+                    "#,
+                )?;
+                let authors = by_author
+                    .query_map([], |r| {
+                        Ok(authors::Author {
+                            name: r.get(0)?,
+                            email: r.get(1)?,
+                            commits: r.get(2)?,
+                            files_changed: r.get(3)?,
+                            lines_added: r.get(4)?,
+                            lines_removed: r.get(5)?,
+                        })
+                    })?
+                    .inspect(|_| progress.inc())
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                let mut commits_by_hour = [0usize; 24];
+                let mut times = self
+                    .con
+                    .prepare("SELECT author_time, author_offset FROM commits WHERE author_time IS NOT NULL")?;
+                for row in times.query_map([], |r| Ok((r.get::<_, i64>(0)?, r.get::<_, i64>(1)?)))? {
+                    let (seconds, offset) = row?;
+                    commits_by_hour[((seconds + offset).rem_euclid(24 * 60 * 60) / (60 * 60)) as usize] += 1;
+                }
+                progress.show_throughput(start);
+
+                for author in &authors {
+                    author.write_to(&mut out)?;
+                }
+                authors::write_hours(&mut out, &commits_by_hour)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+mod authors {
+    pub struct Author {
+        pub name: String,
+        pub email: String,
+        pub commits: usize,
+        pub files_changed: usize,
+        pub lines_added: usize,
+        pub lines_removed: usize,
+    }
+
+    impl Author {
+        pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+            writeln!(
+                out,
+                "{:>6} commits | {:>7} files | +{:<8} -{:<8} | {} <{}>",
+                self.commits, self.files_changed, self.lines_added, self.lines_removed, self.name, self.email
+            )
+        }
+    }
+
+    /// Write a histogram of `commits_by_hour`, the amount of commits authored in each hour of the day in the authors local time.
+    pub fn write_hours(mut out: impl std::io::Write, commits_by_hour: &[usize; 24]) -> std::io::Result<()> {
+        const NUM_CHARS: usize = 40;
+        let max = commits_by_hour.iter().copied().max().unwrap_or_default();
+        writeln!(out, "\ncommits by hour of day (local time of the author)")?;
+        for (hour, count) in commits_by_hour.iter().enumerate() {
+            let bar_len = (count * NUM_CHARS).checked_div(max).unwrap_or_default();
+            writeln!(out, "{hour:02}:00 {count:>7} {}", "#".repeat(bar_len))?;
         }
+        Ok(())
     }
 }
 
@@ -3,6 +3,9 @@ pub enum Command {
         /// The repo-relative path to the file to trace
         spec: gix::pathspec::Pattern,
     },
+    /// Show the amount of commits, changed files and lines for each author, along with the hours of the day at which
+    /// commits were authored.
+    Authors,
 }
 
 pub(crate) mod update;
@@ -68,6 +68,8 @@ pub fn update(
         struct CommitDiffStats {
             /// The id of the commit which was diffed with its predecessor
             id: gix::hash::ObjectId,
+            /// The author of the commit, if it could be decoded.
+            author: Option<gix::actor::Signature>,
             changes: Vec<FileChange>,
         }
         let start = Instant::now();
@@ -98,8 +100,14 @@ pub fn update(
                         mut insert_file_path,
                     } = Updates::new(&trans)?;
                     for stats in InOrderIter::from(rx_stats.into_iter()) {
-                        for CommitDiffStats { id, changes } in stats.expect("infallible") {
-                            new_commit.execute(params![id.as_bytes()])?;
+                        for CommitDiffStats { id, author, changes } in stats.expect("infallible") {
+                            new_commit.execute(params![
+                                id.as_bytes(),
+                                author.as_ref().map(|a| a.name.to_str_lossy()),
+                                author.as_ref().map(|a| a.email.to_str_lossy()),
+                                author.as_ref().map(|a| a.time.seconds),
+                                author.as_ref().map(|a| a.time.offset),
+                            ])?;
                             for change in changes {
                                 insert_file_path.execute(params![change.relpath.to_str_lossy()])?;
                                 let (has_diff, lines) = change.lines.map(|l| (true, l)).unwrap_or_default();
@@ -189,6 +197,10 @@ pub fn update(
                                         return Ok(());
                                     }
                                     let mut out = Vec::new();
+                                    let author = repo
+                                        .find_object(commit)
+                                        .ok()
+                                        .and_then(|c| c.into_commit().author().ok().map(|a| a.to_owned()));
                                     if compute_stats {
                                         let from = match parent_commit {
                                             Some(id) => {
@@ -309,11 +321,13 @@ pub fn update(
                                             })?;
                                         out_chunk.push(CommitDiffStats {
                                             id: commit,
+                                            author,
                                             changes: out,
                                         });
                                     } else {
                                         out_chunk.push(CommitDiffStats {
                                             id: commit,
+                                            author,
                                             changes: Vec::new(),
                                         })
                                     }
@@ -570,8 +584,8 @@ impl<'a> Updates<'a> {
     fn new(trans: &'a Transaction<'_>) -> rusqlite::Result<Self> {
         let new_commit = trans.prepare(
             r#"INSERT INTO
-               commits(hash)
-               VALUES(?)"#,
+               commits(hash, author_name, author_email, author_time, author_offset)
+               VALUES(?, ?, ?, ?, ?)"#,
         )?;
         let insert_commit_file = trans.prepare(
             r#"
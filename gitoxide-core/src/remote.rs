@@ -0,0 +1,80 @@
+pub mod ls_tags {
+    use crate::{net, OutputFormat};
+
+    pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
+
+    pub struct Options {
+        pub format: OutputFormat,
+        pub protocol: Option<net::Protocol>,
+    }
+}
+
+/// List the tags of the remote at `url` along with the objects they peel to, without a local repository,
+/// similar to `git ls-remote --tags`.
+#[gix::protocol::maybe_async::maybe_async]
+pub async fn ls_tags(
+    url: &str,
+    mut progress: impl gix::Progress,
+    mut out: impl std::io::Write,
+    ls_tags::Options { format, protocol }: ls_tags::Options,
+) -> anyhow::Result<()> {
+    use gix::{bstr::ByteSlice, config::tree::Key, protocol};
+
+    use crate::{repository::remote::refs::print, OutputFormat};
+
+    const PREFIX: &str = "refs/tags/";
+    let trace = std::env::var_os(
+        gix::config::tree::Gitoxide::TRACE_PACKET
+            .environment_override()
+            .expect("set"),
+    )
+    .is_some();
+
+    progress.info(format!("Connecting to {url:?}"));
+    let mut transport = crate::net::connect(
+        url,
+        #[allow(clippy::needless_update)]
+        protocol::transport::client::connect::Options {
+            version: protocol.unwrap_or_default().into(),
+            ..Default::default()
+        },
+    )
+    .await?;
+    let mut outcome = protocol::fetch::handshake(
+        &mut transport,
+        protocol::credentials::builtin,
+        Vec::new(),
+        &mut progress,
+    )
+    .await?;
+    let mut refs = match outcome.refs.take() {
+        Some(refs) => refs,
+        None => {
+            protocol::ls_refs(
+                &mut transport,
+                &outcome.capabilities,
+                |_capabilities, arguments, _features| {
+                    arguments.push(format!("ref-prefix {PREFIX}").into());
+                    Ok(protocol::ls_refs::Action::Continue)
+                },
+                &mut progress,
+                trace,
+            )
+            .await?
+        }
+    };
+    protocol::indicate_end_of_interaction(&mut transport, trace).await?;
+
+    // Servers that don't speak protocol V2 send all of their references.
+    refs.retain(|r| r.unpack().0.starts_with_str(PREFIX));
+    match format {
+        OutputFormat::Human => print(&mut out, &refs)?,
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => {
+            for r in refs {
+                crate::write_json_line(&mut out, &crate::repository::remote::JsonRef::from(r))?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -24,10 +24,10 @@ pub fn stream(
 ) -> anyhow::Result<()> {
     let format = format.map_or_else(|| format_from_ext(destination_path), Ok)?;
     let object = repo.rev_parse_single(rev_spec.unwrap_or("HEAD"))?.object()?;
-    let (modification_date, tree) = fetch_rev_info(object)?;
+    let (modification_date, commit_or_tree) = fetch_rev_info(object)?;
 
     let start = std::time::Instant::now();
-    let (mut stream, index) = repo.worktree_stream(tree)?;
+    let (mut stream, index) = repo.worktree_stream(commit_or_tree)?;
     if !add_paths.is_empty() {
         let root = gix::path::realpath(
             repo.work_dir()
@@ -84,7 +84,8 @@ fn fetch_rev_info(
     Ok(match object.kind {
         gix::object::Kind::Commit => {
             let commit = object.into_commit();
-            (Some(commit.committer()?.time.seconds), commit.tree_id()?.detach())
+            // Pass the commit itself so `export-subst` placeholders can be expanded.
+            (Some(commit.committer()?.time.seconds), commit.id)
         }
         gix::object::Kind::Tree => (None, object.id),
         gix::object::Kind::Tag => fetch_rev_info(object.peel_to_kind(gix::object::Kind::Commit)?)?,
@@ -92,7 +93,7 @@ fn fetch_rev_info(
     })
 }
 
-fn format_from_ext(path: &Path) -> anyhow::Result<archive::Format> {
+pub(crate) fn format_from_ext(path: &Path) -> anyhow::Result<archive::Format> {
     Ok(match path.extension().and_then(std::ffi::OsStr::to_str) {
         None => bail!("Cannot derive archive format from a file without extension"),
         Some("tar") => archive::Format::Tar,
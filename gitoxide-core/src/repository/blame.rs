@@ -0,0 +1,220 @@
+use std::{collections::HashMap, ffi::OsString};
+
+use anyhow::{bail, Context};
+use gix::{
+    bstr::{BStr, BString, ByteSlice},
+    prelude::ObjectIdExt,
+};
+
+use crate::OutputFormat;
+
+pub struct Options {
+    pub output_format: OutputFormat,
+    /// The lines to blame, or all lines if empty.
+    pub ranges: Vec<gix::blame::LineRange>,
+    /// If `true`, show the information about each commit in a format designed for machine consumption, like `git blame --porcelain`.
+    pub porcelain: bool,
+    /// If `true`, print statistics about the work performed to `err`.
+    pub statistics: bool,
+}
+
+pub fn blame(
+    mut repo: gix::Repository,
+    file: OsString,
+    rev_spec: Option<&str>,
+    mut out: impl std::io::Write,
+    mut err: impl std::io::Write,
+    Options {
+        output_format,
+        ranges,
+        porcelain,
+        statistics,
+    }: Options,
+) -> anyhow::Result<()> {
+    if output_format != OutputFormat::Human {
+        bail!("Only human output is currently supported");
+    }
+    repo.object_cache_size_if_unset(4 * 1024 * 1024);
+
+    let path = repo.prefix()?.unwrap_or(std::path::Path::new("")).join(file);
+    let path = gix::path::to_unix_separators_on_windows(gix::path::into_bstr(path)).into_owned();
+    let commit = repo
+        .rev_parse_single(rev_spec.unwrap_or("HEAD"))?
+        .object()?
+        .peel_to_kind(gix::object::Kind::Commit)
+        .context("Need committish to blame the file at")?
+        .id;
+    let outcome = repo.blame(
+        commit,
+        path.as_ref(),
+        gix::blame::Options {
+            ranges,
+            ..Default::default()
+        },
+    )?;
+
+    let mut commits = Commits {
+        repo: &repo,
+        mailmap: repo.mailmap_resolver(),
+        shallow: repo.shallow_commits()?,
+        show_root: repo.config_snapshot().boolean("blame.showRoot").unwrap_or(false),
+        infos: HashMap::default(),
+    };
+    if porcelain {
+        write_porcelain(&mut out, &outcome, &mut commits)?;
+    } else {
+        write_human(&mut out, &outcome, path.as_ref(), &mut commits)?;
+    }
+
+    if statistics {
+        writeln!(err, "{:#?}", outcome.statistics).ok();
+    }
+    Ok(())
+}
+
+/// The information to display about a commit.
+struct CommitInfo {
+    author: gix::actor::Signature,
+    committer: gix::actor::Signature,
+    summary: BString,
+    /// If `true`, the commit has no parents to pass blame on to and is displayed like `git blame` displays boundary commits.
+    is_boundary: bool,
+}
+
+/// Decoded commits by their id, with identities resolved through the mailmap.
+struct Commits<'repo> {
+    repo: &'repo gix::Repository,
+    mailmap: gix::mailmap::Resolver,
+    shallow: Option<gix::shallow::Commits>,
+    /// If `true`, root commits aren't treated as boundary commits, like `blame.showRoot` does.
+    show_root: bool,
+    infos: HashMap<gix::ObjectId, CommitInfo>,
+}
+
+impl Commits<'_> {
+    /// Return the information about `id`, and `true` if it was requested for the first time.
+    fn get(&mut self, id: gix::ObjectId) -> anyhow::Result<(&CommitInfo, bool)> {
+        let is_new = !self.infos.contains_key(&id);
+        if is_new {
+            let commit = self.repo.find_object(id)?.try_into_commit()?;
+            let decoded = commit.decode()?;
+            let is_shallow = matches!(&self.shallow, Some(shallow) if shallow.binary_search(&id).is_ok());
+            let info = CommitInfo {
+                author: self.mailmap.resolve_signature(decoded.author),
+                committer: self.mailmap.resolve_signature(decoded.committer),
+                summary: decoded.message().summary().into_owned(),
+                is_boundary: !self.show_root && (is_shallow || decoded.parents.is_empty()),
+            };
+            self.infos.insert(id, info);
+        }
+        Ok((&self.infos[&id], is_new))
+    }
+}
+
+fn write_human(
+    out: &mut dyn std::io::Write,
+    outcome: &gix::blame::Outcome,
+    path: &BStr,
+    commits: &mut Commits<'_>,
+) -> anyhow::Result<()> {
+    let repo = commits.repo;
+    let mut hex_len = 0;
+    let mut author_len = 0;
+    let mut path_len = 0;
+    let mut show_path = false;
+    for entry in &outcome.entries {
+        let (info, is_new) = commits.get(entry.commit_id)?;
+        if is_new {
+            author_len = author_len.max(info.author.name.chars().count());
+            hex_len = hex_len.max(entry.commit_id.attach(repo).shorten_or_id().hex_len() + 1);
+        }
+        show_path |= entry.source_path != path;
+        path_len = path_len.max(entry.source_path.chars().count());
+    }
+    let line_number_len = outcome
+        .entries
+        .last()
+        .map_or(1, |entry| entry.range_in_blamed_file().end.to_string().len());
+
+    for (entry, lines) in outcome.entries_with_lines() {
+        let (info, _) = commits.get(entry.commit_id)?;
+        let hex = entry.commit_id.to_hex().to_string();
+        let id = if info.is_boundary {
+            format!("^{}", &hex[..hex_len - 1])
+        } else {
+            hex[..hex_len].to_owned()
+        };
+        let author = &info.author;
+        let pad = author_len - author.name.chars().count();
+        let date = author.time.format(gix::date::time::format::ISO8601);
+        for (line_number, line) in entry.range_in_blamed_file().zip(lines) {
+            write!(out, "{id}")?;
+            if show_path {
+                write!(out, " {:<path_len$}", entry.source_path.to_str_lossy())?;
+            }
+            write!(
+                out,
+                " ({}{:pad$} {date} {:>line_number_len$}) ",
+                author.name,
+                "",
+                line_number + 1
+            )?;
+            write_line(out, line)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_porcelain(
+    out: &mut dyn std::io::Write,
+    outcome: &gix::blame::Outcome,
+    commits: &mut Commits<'_>,
+) -> anyhow::Result<()> {
+    for (entry, lines) in outcome.entries_with_lines() {
+        let (info, is_new) = commits.get(entry.commit_id)?;
+        let source_lines = entry.range_in_source_file();
+        for (count, ((line_number, source_line_number), line)) in
+            entry.range_in_blamed_file().zip(source_lines).zip(lines).enumerate()
+        {
+            write!(
+                out,
+                "{} {} {}",
+                entry.commit_id,
+                source_line_number + 1,
+                line_number + 1
+            )?;
+            if count == 0 {
+                writeln!(out, " {}", entry.len)?;
+                if is_new {
+                    for (kind, signature) in [("author", &info.author), ("committer", &info.committer)] {
+                        let time = signature.time.format(gix::date::time::format::RAW);
+                        let (seconds, offset) = time.split_once(' ').unwrap_or((&time, "+0000"));
+                        writeln!(out, "{kind} {}", signature.name)?;
+                        writeln!(out, "{kind}-mail <{}>", signature.email)?;
+                        writeln!(out, "{kind}-time {seconds}")?;
+                        writeln!(out, "{kind}-tz {offset}")?;
+                    }
+                    writeln!(out, "summary {}", info.summary)?;
+                    if info.is_boundary {
+                        writeln!(out, "boundary")?;
+                    }
+                    writeln!(out, "filename {}", entry.source_path)?;
+                }
+            } else {
+                writeln!(out)?;
+            }
+            write!(out, "\t")?;
+            write_line(out, line)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write `line` with its terminator, adding one if it's the last line of a file that doesn't end with a newline.
+fn write_line(out: &mut dyn std::io::Write, line: &BStr) -> std::io::Result<()> {
+    out.write_all(line)?;
+    if !line.ends_with(b"\n") {
+        out.write_all(b"\n")?;
+    }
+    Ok(())
+}
@@ -5,6 +5,8 @@ pub struct Options {
     pub bare: bool,
     pub handshake_info: bool,
     pub no_tags: bool,
+    pub single_branch: bool,
+    pub filter: Option<String>,
     pub shallow: gix::remote::fetch::Shallow,
 }
 
@@ -31,6 +33,8 @@ pub(crate) mod function {
             handshake_info,
             bare,
             no_tags,
+            single_branch,
+            filter,
             shallow,
         }: Options,
     ) -> anyhow::Result<()>
@@ -73,7 +77,14 @@ pub(crate) mod function {
         if no_tags {
             prepare = prepare.configure_remote(|r| Ok(r.with_fetch_tags(gix::remote::fetch::Tags::None)));
         }
+        if let Some(filter) = filter {
+            if !bare {
+                bail!("Partial clones must be bare as objects missing for a checkout can't be fetched on demand");
+            }
+            prepare = prepare.with_filter(filter);
+        }
         let (mut checkout, fetch_outcome) = prepare
+            .with_single_branch(single_branch)
             .with_shallow(shallow)
             .fetch_then_checkout(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
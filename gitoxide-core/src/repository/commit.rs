@@ -53,6 +53,8 @@ pub fn describe(
         max_candidates,
         long_format,
         dirty_suffix,
+        include_patterns,
+        exclude_patterns,
     }: describe::Options,
 ) -> Result<()> {
     repo.object_cache_size_if_unset(4 * 1024 * 1024);
@@ -74,6 +76,8 @@ pub fn describe(
         .traverse_first_parent(first_parent)
         .id_as_fallback(always)
         .max_candidates(max_candidates)
+        .include_patterns(include_patterns)
+        .exclude_patterns(exclude_patterns)
         .try_resolve()?
         .with_context(|| format!("Did not find a single candidate ref for naming id '{}'", commit.id))?;
 
@@ -99,5 +103,7 @@ pub mod describe {
         pub statistics: bool,
         pub max_candidates: usize,
         pub dirty_suffix: Option<String>,
+        pub include_patterns: Vec<String>,
+        pub exclude_patterns: Vec<String>,
     }
 }
@@ -18,6 +18,7 @@ pub(crate) mod function {
 
     pub fn verify<W1, W2>(
         repo: gix::Repository,
+        mut progress: impl gix::Progress,
         Context {
             err: _err,
             mut out,
@@ -30,12 +31,12 @@ pub(crate) mod function {
     {
         let g = repo.commit_graph()?;
 
-        #[allow(clippy::unnecessary_wraps, unknown_lints)]
-        fn noop_processor(_commit: &gix::commitgraph::file::Commit<'_>) -> std::result::Result<(), std::fmt::Error> {
-            Ok(())
-        }
+        progress.init(Some(g.num_commits() as usize), gix::progress::count("commits"));
         let stats = g
-            .verify_integrity(noop_processor)
+            .verify_integrity(|_commit| {
+                progress.inc();
+                Ok::<_, std::fmt::Error>(())
+            })
             .with_context(|| "Verification failure")?;
 
         #[cfg_attr(not(feature = "serde"), allow(clippy::single_match))]
@@ -0,0 +1,38 @@
+pub(crate) mod function {
+    use anyhow::bail;
+    use gix::{maintenance::commit_graph, NestedProgress};
+
+    use crate::OutputFormat;
+
+    pub fn write(
+        repo: gix::Repository,
+        split: bool,
+        mut progress: impl NestedProgress,
+        mut out: impl std::io::Write,
+        format: OutputFormat,
+    ) -> anyhow::Result<()> {
+        if format != OutputFormat::Human {
+            bail!("Only human output is currently supported");
+        }
+        let mut commits = progress.add_child("traverse commits");
+        let outcome = repo.write_commit_graph(commit_graph::Options { split }, &mut commits)?;
+        if repo.is_shallow() {
+            writeln!(out, "No commit-graph was written as the repository is shallow")?;
+        } else if split && outcome.commits == 0 {
+            writeln!(
+                out,
+                "All commits are already in the commit-graph, the chain has {} layers",
+                outcome.layers
+            )?;
+        } else if split {
+            writeln!(
+                out,
+                "Wrote {} commits into a new layer, the chain has {} layers",
+                outcome.commits, outcome.layers
+            )?;
+        } else {
+            writeln!(out, "Wrote {} commits", outcome.commits)?;
+        }
+        Ok(())
+    }
+}
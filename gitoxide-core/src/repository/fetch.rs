@@ -12,6 +12,10 @@ pub struct Options {
     pub handshake_info: bool,
     pub negotiation_info: bool,
     pub open_negotiation_graph: Option<std::path::PathBuf>,
+    /// If `true`, delete local references whose counterpart on the remote is gone.
+    pub prune: bool,
+    /// If `true`, fetch all tags of the remote.
+    pub tags: bool,
 }
 
 pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=3;
@@ -44,6 +48,8 @@ pub(crate) mod function {
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
+            prune,
+            tags,
             shallow,
             ref_specs,
         }: Options,
@@ -61,10 +67,14 @@ pub(crate) mod function {
             remote.replace_refspecs(ref_specs.iter(), gix::remote::Direction::Fetch)?;
             remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
         }
+        if tags {
+            remote = remote.with_fetch_tags(gix::remote::fetch::Tags::All);
+        }
         let res: gix::remote::fetch::Outcome = remote
             .connect(gix::remote::Direction::Fetch)?
             .prepare_fetch(&mut progress, Default::default())?
             .with_dry_run(dry_run)
+            .with_prune(prune)
             .with_shallow(shallow)
             .receive(&mut progress, &gix::interrupt::IS_INTERRUPTED)?;
 
@@ -194,7 +204,22 @@ pub(crate) mod function {
         writeln!(out, "Negotiation Phase Information")?;
         match negotiate {
             Some(negotiate) => {
-                writeln!(out, "\t{:?}", negotiate.rounds)?;
+                for (round_idx, round) in negotiate.rounds.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "\tround {}: sent {} of {} haves, {} in vain{}",
+                        round_idx + 1,
+                        round.haves_sent,
+                        round.haves_to_send,
+                        round.in_vain,
+                        if round.previous_response_had_at_least_one_in_common {
+                            ", previous response acknowledged common commits"
+                        } else {
+                            ""
+                        }
+                    )?;
+                }
+                writeln!(out, "\tnum rounds: {}", negotiate.rounds.len())?;
                 writeln!(out, "\tnum commits traversed in graph: {}", negotiate.graph.len())
             }
             None => writeln!(out, "\tno negotiation performed"),
@@ -290,6 +315,12 @@ pub(crate) mod function {
             }?;
         }
         consume_skipped_tags(&mut skipped_due_to_implicit_tag, &mut out)?;
+        if !update_refs.pruned.is_empty() {
+            writeln!(out, "pruned (gone on remote)")?;
+            for edit in &update_refs.pruned {
+                writeln!(out, "\t{} [deleted]", edit.name)?;
+            }
+        }
         if !map.fixes.is_empty() {
             writeln!(
                 err,
@@ -6,6 +6,8 @@ pub struct Options {
     pub statistics: bool,
     pub simple: bool,
     pub recurse_submodules: bool,
+    /// If true, show details about the extensions of the index after its entries.
+    pub extensions: bool,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -47,16 +49,12 @@ pub(crate) mod function {
             attributes,
             statistics,
             recurse_submodules,
+            extensions,
         }: Options,
     ) -> anyhow::Result<()> {
         let mut out = BufWriter::with_capacity(64 * 1024, out);
         let mut all_attrs = statistics.then(BTreeSet::new);
 
-        #[cfg(feature = "serde")]
-        if let OutputFormat::Json = format {
-            out.write_all(b"[\n")?;
-        }
-
         let stats = print_entries(
             &repo,
             attributes,
@@ -69,14 +67,31 @@ pub(crate) mod function {
             &mut out,
         )?;
 
+        let extensions = extensions
+            .then(|| {
+                repo.index_or_load_from_head()
+                    .map(|index| Extensions::from(&*index))
+                    .map_err(Box::new)
+            })
+            .transpose()?;
         #[cfg(feature = "serde")]
         if format == OutputFormat::Json {
-            out.write_all(b"]\n")?;
+            if let Some(extensions) = extensions.as_ref() {
+                #[derive(serde::Serialize)]
+                struct Line<'a> {
+                    extensions: &'a Extensions,
+                }
+                crate::write_json_line(&mut out, &Line { extensions })?;
+            }
             out.flush()?;
             if statistics {
                 serde_json::to_writer_pretty(&mut err, &stats)?;
             }
         }
+        if let Some(extensions) = extensions.filter(|_| format == OutputFormat::Human) {
+            extensions.write_to(&mut out)?;
+            out.flush()?;
+        }
         if format == OutputFormat::Human && statistics {
             out.flush()?;
             writeln!(err, "{stats:#?}")?;
@@ -126,8 +141,7 @@ pub(crate) mod function {
         };
         if let Some(entries) = index.prefixed_entries(pathspec.common_prefix()) {
             stats.entries_after_prune = entries.len();
-            let mut entries = entries.iter().peekable();
-            while let Some(entry) = entries.next() {
+            for entry in entries {
                 let mut last_match = None;
                 let attrs = cache
                     .as_mut()
@@ -230,7 +244,7 @@ pub(crate) mod function {
                             }?
                         }
                         #[cfg(feature = "serde")]
-                        OutputFormat::Json => to_json(out, &index, entry, attrs, entries.peek().is_none(), prefix)?,
+                        OutputFormat::Json => to_json(out, &index, entry, attrs, prefix)?,
                     }
                 }
             }
@@ -317,13 +331,115 @@ pub(crate) mod function {
         pub submodule: Vec<(BString, Statistics)>,
     }
 
+    /// Information about the extensions of an index.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    struct Extensions {
+        tree: Option<Tree>,
+        /// The checksum of the shared index this split index is linked to.
+        link: Option<String>,
+        /// The amount of paths whose conflicts can be restored.
+        resolve_undo: Option<usize>,
+        untracked_cache: bool,
+        fs_monitor: bool,
+        end_of_index_entry: bool,
+        index_entry_offset_table: bool,
+        sparse_directories: bool,
+    }
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    struct Tree {
+        name: String,
+        id: String,
+        /// The amount of entries the tree covers, or `None` if it was invalidated.
+        num_entries: Option<u32>,
+        children: Vec<Tree>,
+    }
+
+    impl From<&gix::index::extension::Tree> for Tree {
+        fn from(tree: &gix::index::extension::Tree) -> Self {
+            use gix::bstr::ByteSlice;
+            Tree {
+                name: tree.name.as_bstr().to_string(),
+                id: tree.id.to_hex().to_string(),
+                num_entries: tree.num_entries,
+                children: tree.children.iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl From<&gix::index::File> for Extensions {
+        fn from(index: &gix::index::File) -> Self {
+            Extensions {
+                tree: index.tree().map(Into::into),
+                link: index.link().map(|link| link.shared_index_checksum.to_hex().to_string()),
+                resolve_undo: index.resolve_undo().map(Vec::len),
+                untracked_cache: index.untracked().is_some(),
+                fs_monitor: index.fs_monitor().is_some(),
+                end_of_index_entry: index.had_end_of_index_marker(),
+                index_entry_offset_table: index.had_offset_table(),
+                sparse_directories: index.is_sparse(),
+            }
+        }
+    }
+
+    impl Extensions {
+        fn write_to(&self, out: &mut impl std::io::Write) -> std::io::Result<()> {
+            fn write_tree(out: &mut impl std::io::Write, tree: &Tree, level: usize) -> std::io::Result<()> {
+                writeln!(
+                    out,
+                    "{:indent$}{} {} {}",
+                    "",
+                    tree.id,
+                    tree.num_entries
+                        .map_or_else(|| "invalid".to_string(), |num_entries| format!("{num_entries} entries")),
+                    if level == 0 { "(root)" } else { tree.name.as_str() },
+                    indent = level * 2
+                )?;
+                for child in &tree.children {
+                    write_tree(out, child, level + 1)?;
+                }
+                Ok(())
+            }
+            writeln!(out, "\nExtensions:")?;
+            match &self.tree {
+                Some(tree) => {
+                    writeln!(out, "tree (TREE):")?;
+                    write_tree(out, tree, 0)?;
+                }
+                None => writeln!(out, "tree (TREE): none")?,
+            }
+            writeln!(
+                out,
+                "link: {}",
+                self.link
+                    .as_deref()
+                    .map_or("none".into(), |checksum| format!("shared index {checksum}"))
+            )?;
+            writeln!(
+                out,
+                "resolve-undo (REUC): {}",
+                self.resolve_undo
+                    .map_or("none".into(), |num_paths| format!("{num_paths} paths"))
+            )?;
+            for (name, present) in [
+                ("untracked (UNTR)", self.untracked_cache),
+                ("fs-monitor (FSMN)", self.fs_monitor),
+                ("end-of-index (EOIE)", self.end_of_index_entry),
+                ("offset-table (IEOT)", self.index_entry_offset_table),
+                ("sparse directories (sdir)", self.sparse_directories),
+            ] {
+                writeln!(out, "{name}: {}", if present { "yes" } else { "no" })?;
+            }
+            Ok(())
+        }
+    }
+
     #[cfg(feature = "serde")]
     fn to_json(
-        mut out: &mut impl std::io::Write,
+        out: &mut impl std::io::Write,
         index: &gix::index::File,
         entry: &gix::index::Entry,
         attrs: Option<Attrs>,
-        is_last: bool,
         prefix: &BStr,
     ) -> anyhow::Result<()> {
         use gix::bstr::ByteSlice;
@@ -332,17 +448,19 @@ pub(crate) mod function {
             stat: &'a gix::index::entry::Stat,
             hex_id: String,
             flags: u32,
+            stage: u32,
             mode: u32,
             path: std::borrow::Cow<'a, str>,
             meta: Option<Attrs>,
         }
 
-        serde_json::to_writer(
-            &mut out,
+        crate::write_json_line(
+            out,
             &Entry {
                 stat: &entry.stat,
                 hex_id: entry.id.to_hex().to_string(),
                 flags: entry.flags.bits(),
+                stage: entry.flags.stage() as u32,
                 mode: entry.mode.bits(),
                 path: if prefix.is_empty() {
                     entry.path(index).to_str_lossy()
@@ -353,14 +471,7 @@ pub(crate) mod function {
                 },
                 meta: attrs,
             },
-        )?;
-
-        if is_last {
-            out.write_all(b"\n")?;
-        } else {
-            out.write_all(b",\n")?;
-        }
-        Ok(())
+        )
     }
 
     fn to_human_simple(
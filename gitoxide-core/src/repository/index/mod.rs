@@ -2,6 +2,20 @@ use std::{ffi::OsString, path::PathBuf};
 
 use anyhow::bail;
 
+use crate::OutputFormat;
+
+pub fn verify(repo: gix::Repository, mut out: impl std::io::Write, format: OutputFormat) -> anyhow::Result<()> {
+    let index = repo.open_index()?;
+    index.verify_integrity()?;
+    index.verify_entries()?;
+    index.verify_extensions(true, &repo.objects)?;
+    #[cfg_attr(not(feature = "serde"), allow(irrefutable_let_patterns))]
+    if let OutputFormat::Human = format {
+        writeln!(out, "OK").ok();
+    }
+    Ok(())
+}
+
 pub fn from_tree(
     repo: gix::Repository,
     mut spec: OsString,
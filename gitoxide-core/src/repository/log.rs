@@ -0,0 +1,200 @@
+use std::ffi::OsString;
+
+use anyhow::bail;
+use gix::{
+    bstr::{BString, ByteSlice},
+    revision::plumbing::Spec,
+};
+
+use crate::OutputFormat;
+
+/// The way each commit is displayed, named like the respective `git log --format` option.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// The abbreviated commit id followed by the title line.
+    Oneline,
+    /// The commit id, author and title line.
+    Short,
+    /// The commit id, author, author date and message.
+    #[default]
+    Medium,
+    /// The commit id, author, committer and message.
+    Full,
+    /// The commit id, author, author date, committer, commit date and message.
+    Fuller,
+}
+
+pub struct Options {
+    pub format: Format,
+    pub output_format: OutputFormat,
+    /// The maximum amount of commits to display.
+    pub max_count: Option<usize>,
+    /// If `true`, show no parents before all of their children are shown, and avoid intermixing multiple lines of history.
+    pub topo_order: bool,
+    /// If `true`, only follow the first parent of merge commits.
+    pub first_parent: bool,
+    /// If `true`, follow the history of the single file given as pathspec across renames.
+    pub follow: bool,
+}
+
+pub fn log(
+    mut repo: gix::Repository,
+    revisions: Vec<OsString>,
+    pathspecs: Vec<BString>,
+    mut out: impl std::io::Write,
+    Options {
+        format,
+        output_format,
+        max_count,
+        topo_order,
+        first_parent,
+        follow,
+    }: Options,
+) -> anyhow::Result<()> {
+    if output_format != OutputFormat::Human {
+        bail!("Only human output is currently supported");
+    }
+    repo.object_cache_size_if_unset(4 * 1024 * 1024);
+
+    let mut tips = Vec::new();
+    let mut hidden = Vec::new();
+    for spec in &revisions {
+        let spec = gix::path::os_str_into_bstr(spec)?;
+        match repo.rev_parse(spec)?.detach() {
+            Spec::Include(id) => tips.push(id),
+            Spec::Exclude(id) => hidden.push(id),
+            Spec::Range { from, to } => {
+                tips.push(to);
+                hidden.push(from);
+            }
+            Spec::IncludeOnlyParents(id) => tips.extend(parent_ids(&repo, id)?),
+            Spec::ExcludeParents(id) => {
+                tips.push(id);
+                hidden.extend(parent_ids(&repo, id)?);
+            }
+            Spec::Merge { .. } => {
+                bail!("Symmetric differences like 'a...b' are not supported yet: {spec}")
+            }
+        }
+    }
+    if tips.is_empty() {
+        tips.push(repo.head_id()?.detach());
+    }
+
+    let options = gix::log::Options {
+        tips: tips.clone(),
+        hidden: hidden.clone(),
+        first_parent,
+        follow,
+        ..Default::default()
+    };
+    let commits: Box<dyn Iterator<Item = anyhow::Result<gix::ObjectId>>> = if topo_order {
+        // The log determines which commits are of interest, but their order is the one of the topological walk.
+        let interesting = if pathspecs.is_empty() {
+            None
+        } else {
+            Some(
+                repo.log(&pathspecs, options)?
+                    .map(|info| info.map(|info| info.id).map_err(Box::new))
+                    .collect::<Result<gix::hashtable::HashSet<_>, _>>()?,
+            )
+        };
+        let walk = gix::traverse::commit::topo::Builder::from_iters(&repo.objects, tips, Some(hidden))
+            .sorting(gix::traverse::commit::topo::Sorting::TopoOrder)
+            .parents(if first_parent {
+                gix::traverse::commit::Parents::First
+            } else {
+                gix::traverse::commit::Parents::All
+            })
+            .build()?;
+        Box::new(
+            walk.map(|info| info.map(|info| info.id).map_err(Into::into))
+                .filter(move |id| match (&interesting, id) {
+                    (Some(interesting), Ok(id)) => interesting.contains(id),
+                    _ => true,
+                }),
+        )
+    } else {
+        Box::new(
+            repo.log(&pathspecs, options)?
+                .map(|info| info.map(|info| info.id).map_err(Into::into)),
+        )
+    };
+
+    for (count, id) in commits.enumerate() {
+        if max_count.is_some_and(|max| count == max) {
+            break;
+        }
+        if gix::interrupt::is_triggered() {
+            bail!("interrupted by user");
+        }
+        let commit = repo.find_object(id?)?.into_commit();
+        if format != Format::Oneline && count != 0 {
+            writeln!(out)?;
+        }
+        write_commit(&mut out, &commit, format)?;
+    }
+    Ok(())
+}
+
+fn parent_ids(repo: &gix::Repository, id: gix::ObjectId) -> anyhow::Result<Vec<gix::ObjectId>> {
+    Ok(repo
+        .find_object(id)?
+        .peel_to_kind(gix::object::Kind::Commit)?
+        .into_commit()
+        .parent_ids()
+        .map(gix::Id::detach)
+        .collect())
+}
+
+fn write_commit(out: &mut dyn std::io::Write, commit: &gix::Commit<'_>, format: Format) -> anyhow::Result<()> {
+    let message = commit.message_raw()?;
+    if format == Format::Oneline {
+        let title = message.lines().next().unwrap_or_default();
+        writeln!(out, "{} {}", commit.id().shorten_or_id(), title.as_bstr())?;
+        return Ok(());
+    }
+
+    writeln!(out, "commit {}", commit.id)?;
+    let parent_ids: Vec<_> = commit.parent_ids().collect();
+    if parent_ids.len() > 1 {
+        write!(out, "Merge:")?;
+        for id in parent_ids {
+            write!(out, " {}", id.shorten_or_id())?;
+        }
+        writeln!(out)?;
+    }
+    let author = commit.author()?;
+    let committer = commit.committer()?;
+    let date = |time: gix::date::Time| time.format(gix::date::time::format::DEFAULT);
+    match format {
+        Format::Oneline => unreachable!("handled early"),
+        Format::Short => writeln!(out, "Author: {} <{}>", author.name, author.email)?,
+        Format::Medium => {
+            writeln!(out, "Author: {} <{}>", author.name, author.email)?;
+            writeln!(out, "Date:   {}", date(author.time))?;
+        }
+        Format::Full => {
+            writeln!(out, "Author: {} <{}>", author.name, author.email)?;
+            writeln!(out, "Commit: {} <{}>", committer.name, committer.email)?;
+        }
+        Format::Fuller => {
+            writeln!(out, "Author:     {} <{}>", author.name, author.email)?;
+            writeln!(out, "AuthorDate: {}", date(author.time))?;
+            writeln!(out, "Commit:     {} <{}>", committer.name, committer.email)?;
+            writeln!(out, "CommitDate: {}", date(committer.time))?;
+        }
+    }
+    writeln!(out)?;
+
+    let message = message.trim_end();
+    let message = if format == Format::Short {
+        message.lines().next().unwrap_or_default()
+    } else {
+        message
+    };
+    for line in message.lines() {
+        writeln!(out, "    {}", line.as_bstr())?;
+    }
+    Ok(())
+}
@@ -24,6 +24,8 @@ pub mod config;
 mod credential;
 pub use credential::function as credential;
 pub mod attributes;
+pub mod blame;
+pub use blame::blame;
 #[cfg(feature = "clean")]
 pub mod clean;
 pub mod dirty;
@@ -43,6 +45,8 @@ pub mod commitgraph;
 mod fsck;
 pub use fsck::function as fsck;
 pub mod index;
+pub mod log;
+pub use log::log;
 pub mod mailmap;
 pub mod odb;
 pub mod remote;
@@ -18,7 +18,11 @@ mod refs_impl {
         pub const PROGRESS_RANGE: std::ops::RangeInclusive<u8> = 1..=2;
 
         pub enum Kind {
-            Remote,
+            Remote {
+                /// Only show references starting with any of these prefixes, which are passed to the server
+                /// to have it filter its references with protocol V2.
+                ref_prefixes: Vec<BString>,
+            },
             Tracking {
                 ref_specs: Vec<BString>,
                 show_unmapped_remote_refs: bool,
@@ -54,9 +58,6 @@ mod refs_impl {
             show_unmapped_remote_refs,
         } = &kind
         {
-            if format != OutputFormat::Human {
-                bail!("JSON output isn't yet supported for listing ref-mappings.");
-            }
             if !ref_specs.is_empty() {
                 remote.replace_refspecs(ref_specs.iter(), gix::remote::Direction::Fetch)?;
                 remote = remote.with_fetch_tags(gix::remote::fetch::Tags::None);
@@ -72,13 +73,18 @@ mod refs_impl {
                 .context("Remote didn't have a URL to connect to")?
                 .to_bstring()
         ));
-        let map = remote
+        let extra_ref_prefixes = match &kind {
+            refs::Kind::Remote { ref_prefixes } => ref_prefixes.clone(),
+            refs::Kind::Tracking { .. } => Vec::new(),
+        };
+        let mut map = remote
             .connect(gix::remote::Direction::Fetch)
             .await?
             .ref_map(
                 &mut progress,
                 gix::remote::ref_map::Options {
-                    prefix_from_spec_as_filter_on_remote: !matches!(kind, refs::Kind::Remote),
+                    prefix_from_spec_as_filter_on_remote: !matches!(kind, refs::Kind::Remote { .. }),
+                    extra_ref_prefixes,
                     ..Default::default()
                 },
             )
@@ -94,17 +100,26 @@ mod refs_impl {
                 remote.refspecs(gix::remote::Direction::Fetch),
                 map,
                 show_unmapped,
+                format,
                 out,
                 err,
             ),
-            refs::Kind::Remote => {
+            refs::Kind::Remote { ref_prefixes } => {
+                if !ref_prefixes.is_empty() {
+                    // Servers that don't speak protocol V2 send all of their references, so filter them here as well.
+                    map.remote_refs.retain(|r| {
+                        let name = r.unpack().0;
+                        ref_prefixes.iter().any(|prefix| name.starts_with(prefix))
+                    });
+                }
                 match format {
                     OutputFormat::Human => drop(print(out, &map.remote_refs)),
                     #[cfg(feature = "serde")]
-                    OutputFormat::Json => serde_json::to_writer_pretty(
-                        out,
-                        &map.remote_refs.into_iter().map(JsonRef::from).collect::<Vec<_>>(),
-                    )?,
+                    OutputFormat::Json => {
+                        for remote_ref in map.remote_refs {
+                            crate::write_json_line(&mut out, &JsonRef::from(remote_ref))?;
+                        }
+                    }
                 };
                 Ok(())
             }
@@ -116,12 +131,39 @@ mod refs_impl {
         refspecs: &[RefSpec],
         mut map: gix::remote::fetch::RefMap,
         show_unmapped_remotes: bool,
+        format: OutputFormat,
         mut out: impl std::io::Write,
         mut err: impl std::io::Write,
     ) -> anyhow::Result<()> {
         let mut last_spec_index = gix::remote::fetch::SpecIndex::ExplicitInRemote(usize::MAX);
         map.mappings.sort_by_key(|m| m.spec_index);
         for mapping in &map.mappings {
+            #[cfg(feature = "serde")]
+            if format == OutputFormat::Json {
+                let spec = mapping
+                    .spec_index
+                    .get(refspecs, &map.extra_refspecs)
+                    .expect("refspecs here are the ones used for mapping");
+                let object = mapping.remote.as_id();
+                crate::write_json_line(
+                    &mut out,
+                    &JsonMapping {
+                        spec: spec.to_ref().to_bstring().to_string(),
+                        implicit: mapping.spec_index.implicit_index().is_some(),
+                        remote_ref: match &mapping.remote {
+                            Source::Ref(r) => Some(JsonRef::from(r.clone())),
+                            Source::ObjectId(_) => None,
+                        },
+                        object: object.map(|id| id.to_hex().to_string()),
+                        local: mapping.local.as_ref().map(ToString::to_string),
+                        status: match (&mapping.local, object) {
+                            (Some(local), Some(id)) => Some(tracking_status(repo, local.as_ref(), id)?),
+                            _ => None,
+                        },
+                    },
+                )?;
+                continue;
+            }
             if mapping.spec_index != last_spec_index {
                 last_spec_index = mapping.spec_index;
                 let spec = mapping
@@ -154,23 +196,11 @@ mod refs_impl {
             };
             match &mapping.local {
                 Some(local) => {
-                    write!(out, " -> {local} ")?;
-                    match repo.try_find_reference(local)? {
-                        Some(tracking) => {
-                            let msg = match tracking.try_id() {
-                                Some(id) => {
-                                    if id.as_ref() == target_id {
-                                        "[up-to-date]"
-                                    } else {
-                                        "[changed]"
-                                    }
-                                }
-                                None => "[skipped]",
-                            };
-                            writeln!(out, "{msg}")
-                        }
-                        None => writeln!(out, "[new]"),
-                    }
+                    writeln!(
+                        out,
+                        " -> {local} [{}]",
+                        tracking_status(repo, local.as_ref(), target_id)?
+                    )
                 }
                 None => writeln!(out, " (fetch only)"),
             }?;
@@ -209,15 +239,34 @@ mod refs_impl {
                 refspecs.len()
             )?;
             if show_unmapped_remotes {
-                writeln!(&mut out, "\nFiltered: ")?;
+                if format == OutputFormat::Human {
+                    writeln!(&mut out, "\nFiltered: ")?;
+                }
                 for remote_ref in map.remote_refs.iter().filter(|r| {
                     !map.mappings.iter().any(|m| match &m.remote {
                         Source::Ref(other) => other == *r,
                         Source::ObjectId(_) => false,
                     })
                 }) {
-                    print_ref(&mut out, remote_ref)?;
-                    writeln!(&mut out)?;
+                    match format {
+                        OutputFormat::Human => {
+                            print_ref(&mut out, remote_ref)?;
+                            writeln!(&mut out)?;
+                        }
+                        #[cfg(feature = "serde")]
+                        OutputFormat::Json => {
+                            #[derive(serde::Serialize)]
+                            struct Filtered {
+                                filtered: JsonRef,
+                            }
+                            crate::write_json_line(
+                                &mut out,
+                                &Filtered {
+                                    filtered: remote_ref.clone().into(),
+                                },
+                            )?
+                        }
+                    }
                 }
             }
         }
@@ -227,6 +276,40 @@ mod refs_impl {
         Ok(())
     }
 
+    /// Return how the tracking branch `local` would change if it was set to `target_id`, like `up-to-date` or `new`.
+    fn tracking_status(
+        repo: &gix::Repository,
+        local: &gix::bstr::BStr,
+        target_id: &gix::hash::oid,
+    ) -> anyhow::Result<&'static str> {
+        Ok(match repo.try_find_reference(local)? {
+            Some(tracking) => match tracking.try_id() {
+                Some(id) if id.as_ref() == target_id => "up-to-date",
+                Some(_) => "changed",
+                None => "skipped",
+            },
+            None => "new",
+        })
+    }
+
+    /// A mapping of a remote reference to a local tracking branch, as emitted with [`OutputFormat::Json`].
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize)]
+    struct JsonMapping {
+        /// The refspec that produced the mapping.
+        spec: String,
+        /// If `true`, the refspec was added implicitly, for instance to fetch tags.
+        implicit: bool,
+        /// The remote reference, or `None` if an object id was fetched directly.
+        remote_ref: Option<JsonRef>,
+        /// The object the local tracking branch would point to, or `None` if the remote reference is unborn.
+        object: Option<String>,
+        /// The local tracking branch, or `None` if the object is only fetched.
+        local: Option<String>,
+        /// What would happen to `local`, one of `new`, `up-to-date`, `changed` or `skipped`.
+        status: Option<&'static str>,
+    }
+
     #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum JsonRef {
         Peeled {
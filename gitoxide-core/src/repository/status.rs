@@ -1,7 +1,9 @@
 use anyhow::bail;
-use gix::bstr::{BStr, BString};
-use gix::status::index_worktree::iter::Item;
+use gix::bstr::{BStr, BString, ByteSlice};
+use gix::status::index_worktree::iter::{Item, RewriteSource};
 use gix_status::index_as_worktree::{Change, Conflict, EntryStatus};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 use crate::OutputFormat;
@@ -38,6 +40,10 @@ pub struct Options {
     pub statistics: bool,
     pub allow_write: bool,
     pub index_worktree_renames: Option<f32>,
+    /// If `true`, show information about the current branch, like `git status --branch`.
+    pub branch: bool,
+    /// If `true`, terminate entries with NUL and write paths without quoting them, like `git status -z`.
+    pub null_terminated: bool,
 }
 
 pub fn show(
@@ -55,14 +61,13 @@ pub fn show(
         allow_write,
         statistics,
         index_worktree_renames,
+        branch,
+        null_terminated,
     }: Options,
 ) -> anyhow::Result<()> {
     if output_format != OutputFormat::Human {
         bail!("Only human format is supported right now");
     }
-    if !matches!(format, Format::Simplified) {
-        bail!("Only the simplified format is currently implemented");
-    }
 
     let start = std::time::Instant::now();
     let prefix = repo.prefix()?.unwrap_or(Path::new(""));
@@ -108,104 +113,617 @@ pub fn show(
             }
             None => gix::status::Submodule::AsConfigured { check_dirty: false },
         })
-        .into_index_worktree_iter(pathspecs)?;
+        .into_index_worktree_iter(pathspecs.clone())?;
 
+    let mut changes = Changes::default();
     for item in iter.by_ref() {
-        let item = item?;
+        changes.add_index_worktree_item(&repo, item?);
+    }
+    if gix::interrupt::is_triggered() {
+        bail!("interrupted by user");
+    }
+
+    let outcome = iter.outcome_mut().expect("successful iteration has outcome");
+    changes.add_tree_index_changes(&repo, &outcome.index, &pathspecs)?;
+
+    let paths = Paths {
+        null_terminated,
+        quote_non_ascii: repo.quote_path(),
+    };
+    match format {
+        Format::Simplified => {
+            if branch {
+                print_branch_simplified(&mut out, &repo, paths)?;
+            }
+            print_simplified(&mut out, prefix, &changes, paths)?
+        }
+        Format::PorcelainV2 => {
+            if branch {
+                print_branch_porcelain_v2(&mut out, &repo, paths)?;
+            }
+            print_porcelain_v2(&mut out, &outcome.index, &changes, paths)?
+        }
+    }
+
+    if outcome.has_changes() && allow_write {
+        outcome.write_changes().transpose()?;
+    }
+
+    if statistics {
+        writeln!(err, "{outcome:#?}", outcome = outcome.index_worktree).ok();
+    }
+
+    progress.init(Some(outcome.index.entries().len()), gix::progress::count("files"));
+    progress.set(outcome.index.entries().len());
+    progress.show_throughput(start);
+    Ok(())
+}
+
+/// A change between the tree of `HEAD` and the index.
+struct TreeIndexChange {
+    /// The status letter, like `M` or `R`.
+    status: u8,
+    /// The mode and id in the tree of `HEAD`, or `None` if the entry was added.
+    head: Option<(u32, gix::ObjectId)>,
+    /// The mode and id in the index, or `None` if the entry was deleted.
+    index: Option<(u32, gix::ObjectId)>,
+    /// The path of the source of a rename or copy, along with the similarity score from 0 to 100.
+    source: Option<(BString, u8)>,
+}
+
+/// A change between the index and the worktree.
+enum IndexWorktreeChange {
+    Modification {
+        /// The status letter, like `M` or `D`.
+        status: u8,
+        /// If `true`, the executable bit of the file changed.
+        executable_bit_changed: bool,
+        entry: gix::index::Entry,
+        /// The mode of the file on disk.
+        worktree_mode: u32,
+        submodule: Option<gix::submodule::Status>,
+    },
+    Conflict(Conflict),
+    Rewrite {
+        source_rela_path: BString,
+        source_entry: gix::index::Entry,
+        worktree_mode: u32,
+        /// The similarity score from 0 to 100.
+        score: u8,
+        copy: bool,
+    },
+}
+
+/// All changes by their repository-relative path.
+#[derive(Default)]
+struct Changes {
+    tracked: BTreeMap<BString, (Option<TreeIndexChange>, Option<IndexWorktreeChange>)>,
+    untracked: Vec<(BString, bool)>,
+    ignored: Vec<(BString, bool)>,
+}
+
+impl Changes {
+    fn add_index_worktree_item(&mut self, repo: &gix::Repository, item: Item) {
         match item {
             Item::Modification {
-                entry: _,
-                entry_index: _,
+                entry,
                 rela_path,
                 status,
-            } => print_index_entry_status(&mut out, prefix, rela_path.as_ref(), status)?,
+                ..
+            } => {
+                let change = match status {
+                    EntryStatus::Conflict(conflict) => IndexWorktreeChange::Conflict(conflict),
+                    EntryStatus::Change(change) => {
+                        let executable_bit_changed = matches!(
+                            change,
+                            Change::Modification {
+                                executable_bit_changed: true,
+                                ..
+                            }
+                        );
+                        let (status, worktree_mode, submodule) = match change {
+                            Change::Removed => (b'D', 0, None),
+                            Change::Type => (b'T', worktree_mode(repo, rela_path.as_ref(), &entry), None),
+                            Change::Modification { .. } => {
+                                (b'M', worktree_mode(repo, rela_path.as_ref(), &entry), None)
+                            }
+                            Change::SubmoduleModification(status) => (b'M', entry.mode.bits(), Some(status)),
+                        };
+                        IndexWorktreeChange::Modification {
+                            status,
+                            executable_bit_changed,
+                            entry,
+                            worktree_mode,
+                            submodule,
+                        }
+                    }
+                    EntryStatus::NeedsUpdate(_) => return,
+                    EntryStatus::IntentToAdd => IndexWorktreeChange::Modification {
+                        status: b'A',
+                        executable_bit_changed: false,
+                        worktree_mode: worktree_mode(repo, rela_path.as_ref(), &entry),
+                        entry,
+                        submodule: None,
+                    },
+                };
+                self.tracked.entry(rela_path).or_default().1 = Some(change);
+            }
             Item::DirectoryContents {
                 entry,
                 collapsed_directory_status,
             } => {
-                if collapsed_directory_status.is_none() {
-                    writeln!(
-                        out,
-                        "{status: >3} {rela_path}{slash}",
-                        status = "?",
-                        rela_path =
-                            gix::path::relativize_with_prefix(&gix::path::from_bstr(entry.rela_path), prefix).display(),
-                        slash = if entry.disk_kind.unwrap_or(gix::dir::entry::Kind::File).is_dir() {
-                            "/"
-                        } else {
-                            ""
-                        }
-                    )?;
+                if collapsed_directory_status.is_some() {
+                    return;
+                }
+                let is_dir = entry.disk_kind.map_or(false, |kind| kind.is_dir());
+                match entry.status {
+                    gix::dir::entry::Status::Untracked => self.untracked.push((entry.rela_path, is_dir)),
+                    gix::dir::entry::Status::Ignored(_) => self.ignored.push((entry.rela_path, is_dir)),
+                    gix::dir::entry::Status::Tracked | gix::dir::entry::Status::Pruned => {}
                 }
             }
             Item::Rewrite {
                 source,
                 dirwalk_entry,
-                copy: _, // TODO: how to visualize copies?
+                dirwalk_entry_id,
+                diff,
+                copy,
                 ..
             } => {
-                // TODO: handle multi-status characters, there can also be modifications at the same time as determined by their ID and potentially diffstats.
-                writeln!(
-                    out,
-                    "{status: >3} {source_rela_path} → {dest_rela_path}",
-                    status = "R",
-                    source_rela_path =
-                        gix::path::relativize_with_prefix(&gix::path::from_bstr(source.rela_path()), prefix).display(),
-                    dest_rela_path = gix::path::relativize_with_prefix(
-                        &gix::path::from_bstr(dirwalk_entry.rela_path.as_ref()),
-                        prefix
-                    )
-                    .display(),
-                )?;
+                let RewriteSource::RewriteFromIndex {
+                    source_entry,
+                    source_rela_path,
+                    ..
+                } = source
+                else {
+                    // Copies from untracked files don't affect tracked files.
+                    self.untracked.push((dirwalk_entry.rela_path, false));
+                    return;
+                };
+                let score = if source_entry.id == dirwalk_entry_id {
+                    100
+                } else {
+                    diff.map_or(100, |diff| (diff.similarity * 100.0) as u8)
+                };
+                let worktree_mode = match dirwalk_entry.disk_kind {
+                    Some(gix::dir::entry::Kind::Symlink) => gix::index::entry::Mode::SYMLINK.bits(),
+                    _ => worktree_mode(repo, dirwalk_entry.rela_path.as_ref(), &source_entry),
+                };
+                self.tracked.entry(dirwalk_entry.rela_path).or_default().1 = Some(IndexWorktreeChange::Rewrite {
+                    source_rela_path,
+                    source_entry,
+                    worktree_mode,
+                    score,
+                    copy,
+                });
             }
         }
     }
-    if gix::interrupt::is_triggered() {
-        bail!("interrupted by user");
+
+    fn add_tree_index_changes(
+        &mut self,
+        repo: &gix::Repository,
+        index: &gix::index::State,
+        pathspecs: &[BString],
+    ) -> anyhow::Result<()> {
+        let mut pathspec = repo.pathspec(
+            false,
+            pathspecs,
+            true,
+            index,
+            gix::worktree::stack::state::attributes::Source::IdMapping,
+        )?;
+        let head_tree = match repo.head()?.try_peel_to_id_in_place()? {
+            Some(id) => id.object()?.peel_to_tree()?.id,
+            None => repo.empty_tree().id,
+        };
+        // `git status` follows `status.renames` and `diff.renames`, but detects renames if neither is set.
+        let rewrites = match gix::diff::new_rewrites(repo.config_snapshot().plumbing(), true)? {
+            Some(rewrites) => Some(rewrites),
+            None if repo.config_snapshot().boolean("diff.renames") == Some(false) => None,
+            None => Some(Default::default()),
+        };
+        repo.tree_index_status(&head_tree, index, rewrites, |change| {
+            if !pathspec.is_included(change.location, Some(false)) {
+                return Ok::<_, std::convert::Infallible>(gix::object::tree::diff::Action::Continue);
+            }
+            use gix::object::tree::diff::change::Event;
+            let change_ = match change.event {
+                Event::Addition { entry_mode, id } => TreeIndexChange {
+                    status: b'A',
+                    head: None,
+                    index: Some((entry_mode.0.into(), id.detach())),
+                    source: None,
+                },
+                Event::Deletion { entry_mode, id } => TreeIndexChange {
+                    status: b'D',
+                    head: Some((entry_mode.0.into(), id.detach())),
+                    index: None,
+                    source: None,
+                },
+                Event::Modification {
+                    previous_entry_mode,
+                    previous_id,
+                    entry_mode,
+                    id,
+                } => TreeIndexChange {
+                    status: if previous_entry_mode.kind() == entry_mode.kind()
+                        || (previous_entry_mode.is_blob() && entry_mode.is_blob())
+                    {
+                        b'M'
+                    } else {
+                        b'T'
+                    },
+                    head: Some((previous_entry_mode.0.into(), previous_id.detach())),
+                    index: Some((entry_mode.0.into(), id.detach())),
+                    source: None,
+                },
+                Event::Rewrite {
+                    source_location,
+                    source_entry_mode,
+                    source_id,
+                    diff,
+                    entry_mode,
+                    id,
+                    copy,
+                } => TreeIndexChange {
+                    status: if copy { b'C' } else { b'R' },
+                    head: Some((source_entry_mode.0.into(), source_id.detach())),
+                    index: Some((entry_mode.0.into(), id.detach())),
+                    source: Some((
+                        source_location.to_owned(),
+                        diff.map_or(100, |diff| (diff.similarity * 100.0) as u8),
+                    )),
+                },
+            };
+            self.tracked.entry(change.location.to_owned()).or_default().0 = Some(change_);
+            Ok(gix::object::tree::diff::Action::Continue)
+        })?;
+        Ok(())
     }
+}
 
-    let out = iter.outcome_mut().expect("successful iteration has outcome");
+/// Return the mode of the file at `rela_path` in the worktree of `repo`, or the mode of `entry` if it can't be determined.
+fn worktree_mode(repo: &gix::Repository, rela_path: &BStr, entry: &gix::index::Entry) -> u32 {
+    use gix::index::entry::Mode;
+    let Some(metadata) = repo
+        .work_dir()
+        .and_then(|dir| std::fs::symlink_metadata(dir.join(gix::path::from_bstr(rela_path))).ok())
+    else {
+        return entry.mode.bits();
+    };
+    if metadata.is_symlink() {
+        Mode::SYMLINK
+    } else if metadata.is_dir() {
+        Mode::COMMIT
+    } else if gix::fs::is_executable(&metadata) {
+        Mode::FILE_EXECUTABLE
+    } else if cfg!(windows) {
+        entry.mode
+    } else {
+        Mode::FILE
+    }
+    .bits()
+}
+
+/// How paths are written, and how entries are terminated.
+#[derive(Copy, Clone)]
+struct Paths {
+    /// If `true`, entries are terminated with NUL and paths are written as is, otherwise with a newline and quoted.
+    null_terminated: bool,
+    /// If `true`, bytes outside of ASCII are escaped when quoting, as configured by `core.quotePath`.
+    quote_non_ascii: bool,
+}
 
-    if out.has_changes() && allow_write {
-        out.write_changes().transpose()?;
+impl Paths {
+    fn quote<'a>(&self, path: &'a BStr) -> Cow<'a, BStr> {
+        if self.null_terminated {
+            path.into()
+        } else {
+            gix_quote::ansi_c::quote(path, self.quote_non_ascii)
+        }
     }
 
-    if statistics {
-        writeln!(err, "{outcome:#?}", outcome = out.index_worktree).ok();
+    fn eol(&self) -> &'static [u8] {
+        if self.null_terminated {
+            b"\0"
+        } else {
+            b"\n"
+        }
     }
+}
 
-    writeln!(err, "\nhead -> index isn't implemented yet")?;
-    progress.init(Some(out.index.entries().len()), gix::progress::count("files"));
-    progress.set(out.index.entries().len());
-    progress.show_throughput(start);
+fn print_branch_simplified(out: &mut dyn std::io::Write, repo: &gix::Repository, paths: Paths) -> anyhow::Result<()> {
+    let head = repo.head()?;
+    match head.referent_name() {
+        Some(name) => {
+            write!(out, "## {}", name.shorten())?;
+            if let Some(upstream) = repo
+                .branch_remote_tracking_ref_name(name, gix::remote::Direction::Fetch)
+                .transpose()?
+            {
+                write!(out, "...{}", upstream.shorten())?;
+            }
+            if head.is_unborn() {
+                write!(out, " (no commits yet)")?;
+            }
+        }
+        None => write!(out, "## HEAD (no branch)")?,
+    }
+    out.write_all(paths.eol())?;
     Ok(())
 }
 
-fn print_index_entry_status(
+fn print_branch_porcelain_v2(out: &mut dyn std::io::Write, repo: &gix::Repository, paths: Paths) -> anyhow::Result<()> {
+    let mut head = repo.head()?;
+    match head.try_peel_to_id_in_place()? {
+        Some(id) => write!(out, "# branch.oid {id}")?,
+        None => write!(out, "# branch.oid (initial)")?,
+    }
+    out.write_all(paths.eol())?;
+    match head.referent_name() {
+        Some(name) => {
+            write!(out, "# branch.head {}", name.shorten())?;
+            out.write_all(paths.eol())?;
+            if let Some(upstream) = repo
+                .branch_remote_tracking_ref_name(name, gix::remote::Direction::Fetch)
+                .transpose()?
+            {
+                write!(out, "# branch.upstream {}", upstream.shorten())?;
+                out.write_all(paths.eol())?;
+            }
+        }
+        None => {
+            write!(out, "# branch.head (detached)")?;
+            out.write_all(paths.eol())?;
+        }
+    }
+    Ok(())
+}
+
+fn print_simplified(
     out: &mut dyn std::io::Write,
     prefix: &Path,
-    rela_path: &BStr,
-    status: EntryStatus<(), gix::submodule::Status>,
+    changes: &Changes,
+    paths: Paths,
 ) -> std::io::Result<()> {
-    let char_storage;
-    let status = match status {
-        EntryStatus::Conflict(conflict) => as_str(conflict),
-        EntryStatus::Change(change) => {
-            char_storage = change_to_char(&change);
-            std::str::from_utf8(std::slice::from_ref(&char_storage)).expect("valid ASCII")
+    let display = |rela_path: &BStr| -> BString {
+        gix::path::into_bstr(gix::path::relativize_with_prefix(&gix::path::from_bstr(rela_path), prefix).into_owned())
+            .into_owned()
+    };
+    for (rela_path, (tree_index, index_worktree)) in &changes.tracked {
+        let mut status = String::with_capacity(2);
+        let mut source = None;
+        match index_worktree {
+            Some(IndexWorktreeChange::Conflict(conflict)) => status.push_str(conflict_to_str(*conflict)),
+            _ => {
+                match tree_index {
+                    Some(change) => {
+                        status.push(change.status.into());
+                        source = change.source.as_ref().map(|(path, _score)| path.as_bstr());
+                    }
+                    None => status.push(' '),
+                }
+                match index_worktree {
+                    Some(IndexWorktreeChange::Modification {
+                        executable_bit_changed: true,
+                        ..
+                    }) => status.push('X'),
+                    Some(IndexWorktreeChange::Modification { status: s, .. }) => status.push((*s).into()),
+                    Some(IndexWorktreeChange::Rewrite {
+                        source_rela_path, copy, ..
+                    }) => {
+                        status.push(if *copy { 'C' } else { 'R' });
+                        source = Some(source_rela_path.as_bstr());
+                    }
+                    Some(IndexWorktreeChange::Conflict(_)) => unreachable!("handled above"),
+                    None => {}
+                }
+            }
         }
-        EntryStatus::NeedsUpdate(_stat) => {
-            return Ok(());
+        write!(out, "{status: >3} ")?;
+        let path = display(rela_path.as_ref());
+        match source.map(display) {
+            Some(source) if paths.null_terminated => {
+                out.write_all(&path)?;
+                out.write_all(b"\0")?;
+                out.write_all(&source)?;
+            }
+            Some(source) => {
+                out.write_all(&paths.quote(source.as_ref()))?;
+                out.write_all(" → ".as_bytes())?;
+                out.write_all(&paths.quote(path.as_ref()))?;
+            }
+            None => out.write_all(&paths.quote(path.as_ref()))?,
         }
-        EntryStatus::IntentToAdd => "A",
-    };
+        if let Some(IndexWorktreeChange::Modification {
+            submodule: Some(submodule),
+            ..
+        }) = index_worktree
+        {
+            let summary = submodule_summary(submodule);
+            if !summary.is_empty() {
+                write!(out, " ({})", summary.join(", "))?;
+            }
+        }
+        out.write_all(paths.eol())?;
+    }
+    for (status, entries) in [("?", &changes.untracked), ("!", &changes.ignored)] {
+        for (rela_path, is_dir) in entries {
+            let mut path = display(rela_path.as_ref());
+            if *is_dir {
+                path.push(b'/');
+            }
+            write!(out, "{status: >3} ")?;
+            out.write_all(&paths.quote(path.as_ref()))?;
+            out.write_all(paths.eol())?;
+        }
+    }
+    Ok(())
+}
 
-    let rela_path = gix::path::from_bstr(rela_path);
-    let display_path = gix::path::relativize_with_prefix(&rela_path, prefix);
-    writeln!(out, "{status: >3} {}", display_path.display())
+/// Describe the changes of a submodule similar to `git status`.
+fn submodule_summary(status: &gix::submodule::Status) -> Vec<&'static str> {
+    let mut out = Vec::new();
+    let (commit_changed, modified, untracked) = submodule_flags(status);
+    if commit_changed {
+        out.push("new commits");
+    }
+    if modified {
+        out.push("modified content");
+    }
+    if untracked {
+        out.push("untracked content");
+    }
+    out
 }
 
-fn as_str(c: Conflict) -> &'static str {
+/// Return `(commit_changed, has_tracked_changes, has_untracked_files)` for the submodule with `status`.
+fn submodule_flags(status: &gix::submodule::Status) -> (bool, bool, bool) {
+    let commit_changed = status.checked_out_head_id.is_some() && status.checked_out_head_id != status.index_id;
+    let changes = status.changes.as_deref().unwrap_or_default();
+    let modified = changes.iter().any(|item| {
+        !matches!(
+            item,
+            Item::DirectoryContents { .. }
+                | Item::Modification {
+                    status: EntryStatus::NeedsUpdate(_),
+                    ..
+                }
+        )
+    });
+    let untracked = changes.iter().any(|item| {
+        matches!(item, Item::DirectoryContents { entry, .. } if entry.status == gix::dir::entry::Status::Untracked)
+    });
+    (commit_changed, modified, untracked)
+}
+
+fn print_porcelain_v2(
+    out: &mut dyn std::io::Write,
+    index: &gix::index::State,
+    changes: &Changes,
+    paths: Paths,
+) -> std::io::Result<()> {
+    let null = gix::ObjectId::null(index.object_hash());
+    for (rela_path, (tree_index, index_worktree)) in &changes.tracked {
+        if let Some(IndexWorktreeChange::Conflict(conflict)) = index_worktree {
+            use gix::index::entry::Stage;
+            let stages = [Stage::Base, Stage::Ours, Stage::Theirs]
+                .map(|stage| index.entry_by_path_and_stage(rela_path.as_ref(), stage));
+            let modes = stages.map(|e| e.map_or(0, |e| e.mode.bits()));
+            let ids = stages.map(|e| e.map_or(null, |e| e.id));
+            write!(
+                out,
+                "u {xy} N... {m1:06o} {m2:06o} {m3:06o} {mw:06o} {h1} {h2} {h3} ",
+                xy = conflict_to_str(*conflict),
+                m1 = modes[0],
+                m2 = modes[1],
+                m3 = modes[2],
+                mw = if modes[1] != 0 { modes[1] } else { modes[2] },
+                h1 = ids[0],
+                h2 = ids[1],
+                h3 = ids[2],
+            )?;
+            out.write_all(&paths.quote(rela_path.as_ref()))?;
+            out.write_all(paths.eol())?;
+            continue;
+        }
+
+        let (x, head, index_state, mut source) = match tree_index {
+            Some(change) => (
+                change.status,
+                change.head,
+                change.index,
+                change
+                    .source
+                    .as_ref()
+                    .map(|(path, score)| (change.status, *score, path.as_bstr())),
+            ),
+            None => {
+                let entry = match index_worktree {
+                    Some(IndexWorktreeChange::Modification { entry, .. }) => Some(entry),
+                    Some(IndexWorktreeChange::Rewrite { source_entry, .. }) => Some(source_entry),
+                    Some(IndexWorktreeChange::Conflict(_)) | None => None,
+                };
+                let state = entry.map(|e| (e.mode.bits(), e.id));
+                (b'.', state, state, None)
+            }
+        };
+        let (y, worktree_mode, submodule) = match index_worktree {
+            Some(IndexWorktreeChange::Modification {
+                status,
+                worktree_mode,
+                submodule,
+                ..
+            }) => (*status, *worktree_mode, submodule.as_ref()),
+            Some(IndexWorktreeChange::Rewrite {
+                source_rela_path,
+                worktree_mode,
+                score,
+                copy,
+                ..
+            }) => {
+                let status = if *copy { b'C' } else { b'R' };
+                source = Some((status, *score, source_rela_path.as_bstr()));
+                (status, *worktree_mode, None)
+            }
+            Some(IndexWorktreeChange::Conflict(_)) => unreachable!("handled above"),
+            None => (b'.', index_state.map_or(0, |(mode, _)| mode), None),
+        };
+        let is_submodule = [head, index_state]
+            .iter()
+            .flatten()
+            .any(|(mode, _)| *mode == gix::index::entry::Mode::COMMIT.bits());
+        let sub = match submodule {
+            Some(status) => {
+                let (commit_changed, modified, untracked) = submodule_flags(status);
+                format!(
+                    "S{}{}{}",
+                    if commit_changed { 'C' } else { '.' },
+                    if modified { 'M' } else { '.' },
+                    if untracked { 'U' } else { '.' }
+                )
+            }
+            None if is_submodule => "S...".into(),
+            None => "N...".into(),
+        };
+        let mode_and_id = |state: Option<(u32, gix::ObjectId)>| state.unwrap_or((0, null));
+        let (head_mode, head_id) = mode_and_id(head);
+        let (index_mode, index_id) = mode_and_id(index_state);
+        let common = format!(
+            "{x}{y} {sub} {head_mode:06o} {index_mode:06o} {worktree_mode:06o} {head_id} {index_id}",
+            x = x as char,
+            y = y as char,
+        );
+        match source {
+            Some((status, score, source)) => {
+                write!(out, "2 {common} {}{score} ", status as char)?;
+                out.write_all(&paths.quote(rela_path.as_ref()))?;
+                out.write_all(if paths.null_terminated { b"\0" } else { b"\t" })?;
+                out.write_all(&paths.quote(source))?;
+            }
+            None => {
+                write!(out, "1 {common} ")?;
+                out.write_all(&paths.quote(rela_path.as_ref()))?;
+            }
+        }
+        out.write_all(paths.eol())?;
+    }
+    for (status, entries) in [("?", &changes.untracked), ("!", &changes.ignored)] {
+        for (rela_path, is_dir) in entries {
+            let mut path = rela_path.clone();
+            if *is_dir {
+                path.push(b'/');
+            }
+            write!(out, "{status} ")?;
+            out.write_all(&paths.quote(path.as_ref()))?;
+            out.write_all(paths.eol())?;
+        }
+    }
+    Ok(())
+}
+
+fn conflict_to_str(c: Conflict) -> &'static str {
     match c {
         Conflict::BothDeleted => "DD",
         Conflict::AddedByUs => "AU",
@@ -216,21 +734,3 @@ fn as_str(c: Conflict) -> &'static str {
         Conflict::BothModified => "UU",
     }
 }
-
-fn change_to_char(change: &Change<(), gix::submodule::Status>) -> u8 {
-    // Known status letters: https://github.com/git/git/blob/6807fcfedab84bc8cd0fbf721bc13c4e68cda9ae/diff.h#L613
-    match change {
-        Change::Removed => b'D',
-        Change::Type => b'T',
-        Change::SubmoduleModification(_) => b'M',
-        Change::Modification {
-            executable_bit_changed, ..
-        } => {
-            if *executable_bit_changed {
-                b'X'
-            } else {
-                b'M'
-            }
-        }
-    }
-}
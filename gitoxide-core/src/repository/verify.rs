@@ -50,13 +50,20 @@ pub fn integrity(
         Some(OutputFormat::Human) => writeln!(out, "Human output is currently unsupported, use JSON instead")?,
         #[cfg(feature = "serde")]
         Some(OutputFormat::Json) => {
-            serde_json::to_writer_pretty(
-                out,
-                &serde_json::json!({
-                    "index_statistics" : outcome.index_statistics,
-                    "loose_object-stores" : outcome.loose_object_stores
-                }),
-            )?;
+            use gix::odb::store::verify::integrity::{IndexStatistics, LooseObjectStatistics};
+            /// Each line names the kind of statistics it contains.
+            #[derive(serde::Serialize)]
+            #[serde(rename_all = "snake_case")]
+            enum Line<'a> {
+                IndexStatistics(&'a IndexStatistics),
+                LooseObjectStore(&'a LooseObjectStatistics),
+            }
+            for index in &outcome.index_statistics {
+                crate::write_json_line(&mut out, &Line::IndexStatistics(index))?;
+            }
+            for store in &outcome.loose_object_stores {
+                crate::write_json_line(&mut out, &Line::LooseObjectStore(store))?;
+            }
         }
         None => {}
     }
@@ -0,0 +1,257 @@
+//! Just enough HTTP/1.1 to serve the smart HTTP protocol, with one request per connection.
+use std::{
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    net::TcpStream,
+};
+
+use super::{
+    smart_http::{self, Endpoint, MAX_BODY_LEN},
+    Server,
+};
+use anyhow::{anyhow, bail, Context};
+
+/// The longest line we accept in the head of a request.
+const MAX_LINE_LEN: u64 = 8 * 1024;
+
+/// Handle the request on `stream` and return the path of the repository that was requested.
+pub(super) fn handle(server: &Server, stream: TcpStream) -> anyhow::Result<String> {
+    let mut input = BufReader::new(stream.try_clone()?);
+    let mut out = BufWriter::new(stream);
+    let request = match Request::read_from(&mut input) {
+        Ok(request) => request,
+        Err(err) => {
+            respond_with_error(&mut out, "400 Bad Request", &format!("{err:#}"))?;
+            return Err(err);
+        }
+    };
+    let res = handle_request(server, &request, &mut input, &mut out);
+    if let Err(Error::Status { status, message }) = &res {
+        respond_with_error(&mut out, status, message)?;
+    }
+    res.map(|()| request.path).map_err(|err| match err {
+        Error::Status { status, message } => anyhow!("{status}: {message}"),
+        Error::Other(err) => err,
+    })
+}
+
+enum Error {
+    /// Respond with the given status and `message`, as no other response was sent yet.
+    Status {
+        status: &'static str,
+        message: String,
+    },
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        Error::Other(err)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Other(err.into())
+    }
+}
+
+fn status(status: &'static str, message: impl Into<String>) -> Error {
+    Error::Status {
+        status,
+        message: message.into(),
+    }
+}
+
+fn handle_request(
+    server: &Server,
+    request: &Request,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> Result<(), Error> {
+    let Some((path, endpoint)) = Endpoint::from_path(&request.path) else {
+        return Err(status(
+            "404 Not Found",
+            format!("Nothing to serve at '{}'", request.path),
+        ));
+    };
+    let repo = server
+        .open_repository(path.into())
+        .map_err(|err| status("404 Not Found", err.to_string()))?;
+    let mut body = Vec::new();
+    if endpoint == Endpoint::UploadPack && request.method == "POST" {
+        if request
+            .header("Expect")
+            .map_or(false, |value| value.eq_ignore_ascii_case("100-continue"))
+        {
+            out.write_all(b"HTTP/1.1 100 Continue\r\n\r\n")?;
+            out.flush()?;
+        }
+        body = request
+            .read_body(input)
+            .map_err(|err| status("400 Bad Request", format!("{err:#}")))?;
+    }
+    let response = smart_http::handle(
+        &repo,
+        endpoint,
+        smart_http::Request {
+            method: &request.method,
+            query: &request.query,
+            git_protocol: request.header("Git-Protocol"),
+            content_encoding: request.header("Content-Encoding"),
+            body,
+        },
+    );
+    let status = format!("{} {}", response.status, response.reason());
+    match response.body {
+        smart_http::Body::Message(message) => {
+            respond_with_error(out, &status, &message)?;
+            Err(anyhow!("{status}: {message}").into())
+        }
+        body => {
+            write_head(out, &status, response.content_type)?;
+            Ok(body.write_to(out)?)
+        }
+    }
+}
+
+/// The head of an HTTP request.
+struct Request {
+    method: String,
+    /// The percent-decoded path of the request target.
+    path: String,
+    /// The query of the request target, without the leading `?`.
+    query: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn read_from(input: &mut impl BufRead) -> anyhow::Result<Self> {
+        let request_line = read_line(input)?;
+        let mut tokens = request_line.split(' ');
+        let (Some(method), Some(target), Some(version), None) =
+            (tokens.next(), tokens.next(), tokens.next(), tokens.next())
+        else {
+            bail!("Malformed request line '{request_line}'");
+        };
+        if !version.starts_with("HTTP/1.") {
+            bail!("Unsupported HTTP version '{version}'");
+        }
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let mut headers = Vec::new();
+        loop {
+            let line = read_line(input)?;
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| anyhow!("Malformed header line '{line}'"))?;
+            headers.push((name.trim().to_owned(), value.trim().to_owned()));
+        }
+        Ok(Request {
+            method: method.to_owned(),
+            path: percent_decode(path)?,
+            query: query.to_owned(),
+            headers,
+        })
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find_map(|(key, value)| key.eq_ignore_ascii_case(name).then_some(value.as_str()))
+    }
+
+    /// Read the body following the head of this request from `input`, with its transfer encoding removed.
+    fn read_body(&self, input: &mut impl BufRead) -> anyhow::Result<Vec<u8>> {
+        let mut body = Vec::new();
+        if self
+            .header("Transfer-Encoding")
+            .map_or(false, |value| value.eq_ignore_ascii_case("chunked"))
+        {
+            loop {
+                let line = read_line(input)?;
+                let size = line.split(';').next().unwrap_or_default().trim();
+                let size = u64::from_str_radix(size, 16).with_context(|| format!("Invalid chunk size '{size}'"))?;
+                if size == 0 {
+                    // Skip trailers.
+                    while !read_line(input)?.is_empty() {}
+                    break;
+                }
+                if body.len() as u64 + size > MAX_BODY_LEN {
+                    bail!("Request body exceeds {MAX_BODY_LEN} bytes");
+                }
+                input.by_ref().take(size).read_to_end(&mut body)?;
+                if !read_line(input)?.is_empty() {
+                    bail!("Chunk data isn't followed by a line break");
+                }
+            }
+        } else {
+            let len: u64 = self
+                .header("Content-Length")
+                .ok_or_else(|| anyhow!("Need Content-Length or chunked Transfer-Encoding"))?
+                .parse()
+                .context("Invalid Content-Length")?;
+            if len > MAX_BODY_LEN {
+                bail!("Request body exceeds {MAX_BODY_LEN} bytes");
+            }
+            input.by_ref().take(len).read_to_end(&mut body)?;
+            if body.len() as u64 != len {
+                bail!("Request body ended after {} of {len} bytes", body.len());
+            }
+        }
+        Ok(body)
+    }
+}
+
+/// Read a line terminated by CRLF or LF and return it without the terminator.
+fn read_line(input: &mut impl BufRead) -> anyhow::Result<String> {
+    let mut line = Vec::new();
+    input.by_ref().take(MAX_LINE_LEN).read_until(b'\n', &mut line)?;
+    if line.pop() != Some(b'\n') {
+        bail!("Request ended unexpectedly or contains an overly long line");
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    Ok(String::from_utf8(line)?)
+}
+
+/// Decode `%XX` escapes in `path`.
+fn percent_decode(path: &str) -> anyhow::Result<String> {
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte == b'%' {
+            let hex = [bytes.next(), bytes.next()];
+            let hex = match hex {
+                [Some(high), Some(low)] => [high, low],
+                _ => bail!("Truncated escape in path '{path}'"),
+            };
+            let hex = std::str::from_utf8(&hex)?;
+            decoded.push(u8::from_str_radix(hex, 16).with_context(|| format!("Invalid escape '%{hex}'"))?);
+        } else {
+            decoded.push(byte);
+        }
+    }
+    Ok(String::from_utf8(decoded)?)
+}
+
+fn write_head(out: &mut impl Write, status: &str, content_type: &str) -> io::Result<()> {
+    // Without a content-length, the end of the response is signalled by closing the connection.
+    write!(
+        out,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n"
+    )
+}
+
+fn respond_with_error(out: &mut impl Write, status: &str, message: &str) -> io::Result<()> {
+    let message = format!("{message}\n");
+    write!(
+        out,
+        "HTTP/1.1 {status}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        message.len()
+    )?;
+    out.write_all(message.as_bytes())?;
+    out.flush()
+}
@@ -0,0 +1,210 @@
+//! A read-only server for the repositories below a base directory, reachable via the `git://` protocol and smart HTTP.
+//!
+//! Repositories are only served if they contain a `git-daemon-export-ok` file, just like `git daemon` expects it,
+//! unless all of them are exported. Clients must use protocol version 2.
+use std::{
+    io::{self, BufWriter, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Component, PathBuf},
+    sync::{mpsc, Arc},
+    time::Duration,
+};
+
+use anyhow::{anyhow, bail, Context};
+use gix::{
+    bstr::{BStr, ByteSlice},
+    protocol::transport::packetline::{encode, PacketLineRef, StreamingPeekableIter},
+};
+
+mod http;
+pub mod smart_http;
+pub mod upload_pack;
+
+/// The name of the file in a repository's `git` directory that marks it as exported.
+pub const EXPORT_OK_FILE: &str = "git-daemon-export-ok";
+
+pub struct Options {
+    /// The directory below which all served repositories are located.
+    pub base_path: PathBuf,
+    /// If set, the address to listen on for connections using the `git://` protocol.
+    pub git_address: Option<SocketAddr>,
+    /// If set, the address to listen on for smart HTTP connections.
+    pub http_address: Option<SocketAddr>,
+    /// If `true`, serve all repositories, not only those containing an [`EXPORT_OK_FILE`].
+    pub export_all: bool,
+    /// If set, the longest time to wait for a client to send data or to receive it before dropping its connection.
+    pub timeout: Option<Duration>,
+}
+
+/// The protocol spoken on a connection.
+#[derive(Debug, Copy, Clone)]
+enum Transport {
+    Git,
+    Http,
+}
+
+impl std::fmt::Display for Transport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Transport::Git => "git://",
+            Transport::Http => "HTTP",
+        })
+    }
+}
+
+/// The state shared by all connections.
+struct Server {
+    /// The canonicalized base path, with all symlinks resolved.
+    base_path: PathBuf,
+    export_all: bool,
+    timeout: Option<Duration>,
+}
+
+/// Serve the repositories below `options.base_path` until interrupted, logging each connection to `err`.
+pub fn serve(options: Options, mut err: impl Write) -> anyhow::Result<()> {
+    let Options {
+        base_path,
+        git_address,
+        http_address,
+        export_all,
+        timeout,
+    } = options;
+    if git_address.is_none() && http_address.is_none() {
+        bail!("Need an address to listen on for git:// or HTTP connections");
+    }
+    let server = Arc::new(Server {
+        base_path: gix::path::realpath(&base_path)
+            .with_context(|| format!("Base path at '{}' is inaccessible", base_path.display()))?,
+        export_all,
+        timeout,
+    });
+
+    let mut listeners = Vec::new();
+    for (address, transport) in [(git_address, Transport::Git), (http_address, Transport::Http)] {
+        let Some(address) = address else { continue };
+        let listener =
+            TcpListener::bind(address).with_context(|| format!("Could not listen for {transport} on {address}"))?;
+        // Accept connections without blocking to be able to respond to interrupts.
+        listener.set_nonblocking(true)?;
+        writeln!(
+            err,
+            "Listening for {transport} connections on {}",
+            listener.local_addr()?
+        )?;
+        listeners.push((listener, transport));
+    }
+
+    let (log, messages) = mpsc::channel::<String>();
+    while !gix::interrupt::is_triggered() {
+        let mut is_idle = true;
+        for (listener, transport) in &listeners {
+            let (stream, peer) = match listener.accept() {
+                Ok(connection) => connection,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(accept_err) => {
+                    writeln!(err, "Failed to accept {transport} connection: {accept_err}")?;
+                    continue;
+                }
+            };
+            is_idle = false;
+            let server = Arc::clone(&server);
+            let log = log.clone();
+            let transport = *transport;
+            std::thread::spawn(move || {
+                let message = match server.handle(stream, transport) {
+                    Ok(path) => format!("{peer} {transport} served '{path}'"),
+                    Err(err) => format!("{peer} {transport} failed: {err:#}"),
+                };
+                log.send(message).ok();
+            });
+        }
+        for message in messages.try_iter() {
+            writeln!(err, "{message}")?;
+        }
+        if is_idle {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+    Ok(())
+}
+
+impl Server {
+    /// Handle all requests on `stream` and return the path of the repository that was requested.
+    fn handle(&self, stream: TcpStream, transport: Transport) -> anyhow::Result<String> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(self.timeout)?;
+        stream.set_write_timeout(self.timeout)?;
+        match transport {
+            Transport::Git => self.handle_git(stream),
+            Transport::Http => http::handle(self, stream),
+        }
+    }
+
+    /// Handle a `git://` connection, which starts with a request line naming the service and the repository.
+    fn handle_git(&self, stream: TcpStream) -> anyhow::Result<String> {
+        let mut out = BufWriter::new(stream.try_clone()?);
+        let (path, res) = {
+            let mut input = StreamingPeekableIter::new(&stream, &[PacketLineRef::Flush], false);
+            let line = input
+                .read_line()
+                .ok_or_else(|| anyhow!("Connection closed without request"))???;
+            let request = line
+                .as_bstr()
+                .ok_or_else(|| anyhow!("Expected request line, got {line:?}"))?;
+            let (command, parameters) = request.split_once_str(b"\0").unwrap_or((request, &[]));
+            let (service, path) = command.split_once_str(b" ").unwrap_or((command, &[]));
+            let path = path.as_bstr().to_owned();
+            let res = if service != b"git-upload-pack" {
+                Err(anyhow!(
+                    "Service '{}' isn't supported by this read-only server",
+                    service.as_bstr()
+                ))
+            } else if !parameters.split_str(b"\0").any(|parameter| parameter == b"version=2") {
+                Err(anyhow!("Only protocol version 2 is supported"))
+            } else {
+                self.open_repository(path.as_ref())
+            };
+            (path, res)
+        };
+        let repo = match res {
+            Ok(repo) => repo,
+            Err(err) => {
+                encode::error_to_write(format!("{err:#}").as_bytes(), &mut out)?;
+                out.flush()?;
+                return Err(err);
+            }
+        };
+        upload_pack::write_capabilities(&repo, &mut out)?;
+        upload_pack::serve(&repo, &stream, &mut out)?;
+        Ok(path.to_string())
+    }
+
+    /// Open the repository at `path` as requested by a client, if it's located below the base path and exported.
+    fn open_repository(&self, path: &BStr) -> anyhow::Result<gix::Repository> {
+        let not_found = || anyhow!("No exported repository at '{path}'");
+        let mut relative_path = PathBuf::new();
+        for component in gix::path::try_from_bstr(path).map_err(|_| not_found())?.components() {
+            match component {
+                Component::Normal(name) => relative_path.push(name),
+                Component::RootDir | Component::CurDir => {}
+                Component::ParentDir | Component::Prefix(_) => return Err(not_found()),
+            }
+        }
+        let path = self.base_path.join(relative_path);
+        let mut path_with_extension = path.clone().into_os_string();
+        path_with_extension.push(".git");
+        for candidate in [path, path_with_extension.into()] {
+            if !candidate.is_dir() {
+                continue;
+            }
+            let Ok(repo) = gix::open(&candidate) else { continue };
+            let is_below_base_path =
+                gix::path::realpath(repo.git_dir()).map_or(false, |git_dir| git_dir.starts_with(&self.base_path));
+            if is_below_base_path && (self.export_all || repo.git_dir().join(EXPORT_OK_FILE).is_file()) {
+                return Ok(repo);
+            }
+            break;
+        }
+        Err(not_found())
+    }
+}
@@ -0,0 +1,219 @@
+//! A transport-agnostic handler for the read-only endpoints of the [smart HTTP protocol](https://git-scm.com/docs/http-protocol),
+//! to serve repositories from within any HTTP server.
+//!
+//! Use [`Endpoint::from_path()`] to learn which repository is requested, open it, and pass it to [`handle()`] along with the
+//! request. The returned [`Response`] must not be cached, and its body is produced while it's written. Only protocol version 2
+//! is supported.
+use std::io::Write;
+
+use anyhow::{anyhow, bail};
+use gix::bstr::ByteSlice;
+
+use super::upload_pack;
+
+/// The largest request body we accept after decompression, which is plenty for negotiating even with many `have` lines.
+pub const MAX_BODY_LEN: u64 = 64 * 1024 * 1024;
+
+/// An endpoint of the smart HTTP protocol, below the path of a repository.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    /// `info/refs`, to obtain the capabilities of the server.
+    InfoRefs,
+    /// `git-upload-pack`, to run the commands to list references and fetch packs.
+    UploadPack,
+    /// `git-receive-pack`, which is always rejected as pushing isn't supported.
+    ReceivePack,
+}
+
+impl Endpoint {
+    /// Split `path` into the path of the repository and the endpoint it ends with, or return `None` if it doesn't end with one.
+    pub fn from_path(path: &str) -> Option<(&str, Endpoint)> {
+        [
+            ("/info/refs", Endpoint::InfoRefs),
+            ("/git-upload-pack", Endpoint::UploadPack),
+            ("/git-receive-pack", Endpoint::ReceivePack),
+        ]
+        .into_iter()
+        .find_map(|(suffix, endpoint)| path.strip_suffix(suffix).map(|repo_path| (repo_path, endpoint)))
+    }
+}
+
+/// The parts of an HTTP request needed to respond to it.
+#[derive(Debug, Clone, Default)]
+pub struct Request<'a> {
+    /// The request method, like `GET` or `POST`.
+    pub method: &'a str,
+    /// The query of the request URL without the leading `?`, like `service=git-upload-pack`.
+    pub query: &'a str,
+    /// The value of the `Git-Protocol` header, if present.
+    pub git_protocol: Option<&'a str>,
+    /// The value of the `Content-Encoding` header, if present.
+    pub content_encoding: Option<&'a str>,
+    /// The body of the request, with any transfer encoding already removed.
+    pub body: Vec<u8>,
+}
+
+impl Request<'_> {
+    fn query_value(&self, name: &str) -> Option<&str> {
+        self.query
+            .split('&')
+            .find_map(|parameter| parameter.split_once('=').filter(|(key, _)| *key == name))
+            .map(|(_, value)| value)
+    }
+}
+
+/// The response to a [`Request`].
+pub struct Response<'repo> {
+    /// The HTTP status code, like `200`.
+    pub status: u16,
+    /// The value of the `Content-Type` header.
+    pub content_type: &'static str,
+    /// The body of the response.
+    pub body: Body<'repo>,
+}
+
+/// The body of a [`Response`].
+pub enum Body<'repo> {
+    /// A message explaining why the request was rejected, as plain text.
+    Message(String),
+    /// The capabilities of the server.
+    Advertisement(&'repo gix::Repository),
+    /// The result of the upload-pack `command`, which is computed while it's written.
+    UploadPack {
+        /// The repository to serve.
+        repo: &'repo gix::Repository,
+        /// The decompressed body of the request, containing the command to run.
+        command: Vec<u8>,
+    },
+}
+
+impl Response<'_> {
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Response {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: Body::Message(message.into()),
+        }
+    }
+
+    /// Return the reason phrase belonging to our status code.
+    pub fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            _ => "Unknown",
+        }
+    }
+}
+
+impl Body<'_> {
+    /// Write the body to `out`, computing it as needed.
+    ///
+    /// Note that errors may occur after parts of the body were written already, which can only be signalled to the client
+    /// by closing the connection.
+    pub fn write_to(self, mut out: impl Write) -> anyhow::Result<()> {
+        match self {
+            Body::Message(message) => writeln!(out, "{message}")?,
+            Body::Advertisement(repo) => upload_pack::write_capabilities(repo, &mut out)?,
+            Body::UploadPack { repo, command } => upload_pack::serve(repo, command.as_slice(), &mut out)?,
+        }
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// Respond to `request` for `endpoint` of `repo`, which must already have been checked to be allowed for serving.
+pub fn handle<'repo>(repo: &'repo gix::Repository, endpoint: Endpoint, request: Request<'_>) -> Response<'repo> {
+    match endpoint {
+        Endpoint::InfoRefs => {
+            if request.method != "GET" {
+                return Response::error(405, "Refs can only be obtained with GET");
+            }
+            match request.query_value("service") {
+                Some("git-upload-pack") => {}
+                Some("git-receive-pack") => {
+                    return Response::error(403, "Pushing isn't supported by this read-only server")
+                }
+                _ => return Response::error(403, "Only the smart HTTP protocol is supported"),
+            }
+            if !request
+                .git_protocol
+                .is_some_and(|value| value.split(':').any(|parameter| parameter == "version=2"))
+            {
+                return Response::error(400, "Only protocol version 2 is supported");
+            }
+            Response {
+                status: 200,
+                content_type: "application/x-git-upload-pack-advertisement",
+                body: Body::Advertisement(repo),
+            }
+        }
+        Endpoint::UploadPack => {
+            if request.method != "POST" {
+                return Response::error(405, "Commands must be sent with POST");
+            }
+            let command = match request.content_encoding {
+                None | Some("identity") => request.body,
+                Some("gzip" | "x-gzip") => match gunzip(&request.body) {
+                    Ok(body) => body,
+                    Err(err) => return Response::error(400, format!("{err:#}")),
+                },
+                Some(encoding) => return Response::error(400, format!("Unsupported Content-Encoding '{encoding}'")),
+            };
+            Response {
+                status: 200,
+                content_type: "application/x-git-upload-pack-result",
+                body: Body::UploadPack { repo, command },
+            }
+        }
+        Endpoint::ReceivePack => Response::error(403, "Pushing isn't supported by this read-only server"),
+    }
+}
+
+/// Decompress a gzip-encoded `body`, like `git` sends for larger requests.
+fn gunzip(body: &[u8]) -> anyhow::Result<Vec<u8>> {
+    const FHCRC: u8 = 1 << 1;
+    const FEXTRA: u8 = 1 << 2;
+    const FNAME: u8 = 1 << 3;
+    const FCOMMENT: u8 = 1 << 4;
+    let truncated = || anyhow!("Truncated gzip header");
+    if body.get(..3) != Some(&[0x1f, 0x8b, 8]) {
+        bail!("Request body isn't gzip-compressed");
+    }
+    let flags = *body.get(3).ok_or_else(truncated)?;
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let len = body.get(pos..pos + 2).ok_or_else(truncated)?;
+        pos += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            pos += body
+                .get(pos..)
+                .and_then(|rest| rest.find_byte(0))
+                .ok_or_else(truncated)?
+                + 1;
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let mut compressed = body.get(pos..).ok_or_else(truncated)?;
+    let mut state = gix::features::zlib::Decompress::new(false);
+    let mut buf = vec![0; 64 * 1024];
+    let mut decompressed = Vec::new();
+    loop {
+        let len = gix::features::zlib::stream::inflate::read(&mut compressed, &mut state, &mut buf)?;
+        if len == 0 {
+            break;
+        }
+        decompressed.extend_from_slice(&buf[..len]);
+        if decompressed.len() as u64 > MAX_BODY_LEN {
+            bail!("Request body exceeds {MAX_BODY_LEN} bytes");
+        }
+    }
+    Ok(decompressed)
+}
@@ -0,0 +1,420 @@
+//! The server side of `git upload-pack`, speaking [protocol version 2](https://git-scm.com/docs/protocol-v2) only
+//! and independent of the transport that carries it.
+//!
+//! The `ls-refs` and `fetch` commands are supported, and clients may only ask for objects that references point to.
+use std::{
+    io::{self, BufWriter, Write},
+    sync::atomic::AtomicBool,
+};
+
+use anyhow::{anyhow, bail};
+use gix::{
+    bstr::{BStr, BString, ByteSlice},
+    hashtable::HashSet,
+    objs::FindExt,
+    odb::pack,
+    protocol::transport::packetline::{encode, Channel, PacketLineRef, StreamingPeekableIter},
+    ObjectId,
+};
+
+/// The largest amount of pack data that fits into a single side-band packet line.
+const MAX_BAND_DATA_LEN: usize = 65515;
+
+/// Write the capabilities advertised at the beginning of each conversation with a client to `out`.
+pub fn write_capabilities(repo: &gix::Repository, mut out: impl Write) -> io::Result<()> {
+    let object_format = match repo.object_hash() {
+        gix::hash::Kind::Sha1 => "sha1",
+    };
+    for capability in [
+        "version 2".into(),
+        format!("agent={}", gix::protocol::agent(gix::env::agent())),
+        "ls-refs=unborn".into(),
+        "fetch".into(),
+        format!("object-format={object_format}"),
+    ] {
+        encode::text_to_write(capability.as_bytes(), &mut out)?;
+    }
+    encode::flush_to_write(&mut out)?;
+    out.flush()
+}
+
+/// Answer all commands read from `input` by writing responses to `out`, until the client signals that it's done.
+///
+/// If a command fails, its error is sent to the client before it's returned.
+pub fn serve(repo: &gix::Repository, input: impl io::Read, mut out: impl Write) -> anyhow::Result<()> {
+    let mut input = StreamingPeekableIter::new(input, &[PacketLineRef::Flush], false);
+    while let Some(command) = read_command(&mut input)? {
+        let res = match command.name.as_bytes() {
+            b"ls-refs" => ls_refs(repo, &command.arguments, &mut out),
+            b"fetch" => fetch(repo, &command.arguments, &mut out),
+            name => Err(anyhow!("Unknown command '{}'", name.as_bstr())),
+        };
+        if let Err(err) = res {
+            encode::error_to_write(format!("{err:#}").as_bytes(), &mut out).ok();
+            out.flush().ok();
+            return Err(err);
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// A command sent by the client.
+struct Command {
+    name: BString,
+    /// The arguments following the capabilities the client sent along with the command.
+    arguments: Vec<BString>,
+}
+
+/// Read the next command from `input`, or return `None` if the client is done.
+fn read_command(input: &mut StreamingPeekableIter<impl io::Read>) -> anyhow::Result<Option<Command>> {
+    input.reset();
+    let mut name = None;
+    let mut arguments = Vec::new();
+    let mut saw_delimiter = false;
+    let mut is_first_line = true;
+    while let Some(line) = input.read_line() {
+        let line = match line {
+            Ok(line) => line?,
+            Err(err) if is_first_line && err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        is_first_line = false;
+        let data = match line {
+            PacketLineRef::Delimiter => {
+                saw_delimiter = true;
+                continue;
+            }
+            PacketLineRef::Data(data) => data.strip_suffix(b"\n").unwrap_or(data).as_bstr(),
+            PacketLineRef::Flush | PacketLineRef::ResponseEnd => bail!("Unexpected packet line in command"),
+        };
+        if saw_delimiter {
+            arguments.push(data.to_owned());
+        } else if name.is_none() {
+            name = Some(
+                data.strip_prefix(b"command=")
+                    .ok_or_else(|| anyhow!("Expected a command, got '{data}'"))?
+                    .as_bstr()
+                    .to_owned(),
+            );
+        }
+    }
+    Ok(name.map(|name| Command { name, arguments }))
+}
+
+/// A reference as listed by `ls-refs`.
+struct Ref {
+    name: BString,
+    /// The object the reference points to after following symbolic references.
+    id: ObjectId,
+    /// The object at the end of the chain of tags that `id` starts, if it's a tag.
+    peeled: Option<ObjectId>,
+    /// The name of the reference a symbolic reference ultimately points to.
+    symref_target: Option<BString>,
+}
+
+impl Ref {
+    fn from_reference(mut reference: gix::Reference<'_>) -> anyhow::Result<Option<Self>> {
+        let name = reference.name().as_bstr().to_owned();
+        let mut symref_target = None;
+        while reference.target().try_name().is_some() {
+            reference = match reference.follow().expect("symbolic references can be followed") {
+                Ok(next) => next,
+                Err(gix::refs::file::find::existing::Error::NotFound { .. }) => return Ok(None),
+                Err(err) => return Err(err.into()),
+            };
+            symref_target = Some(reference.name().as_bstr().to_owned());
+        }
+        let Some(id) = reference.try_id().map(gix::Id::detach) else {
+            return Ok(None);
+        };
+        let peeled = reference.into_fully_peeled_id()?.detach();
+        Ok(Some(Ref {
+            name,
+            id,
+            peeled: (peeled != id).then_some(peeled),
+            symref_target,
+        }))
+    }
+}
+
+/// Return `HEAD` and all other references, along with the name of the branch `HEAD` points to if it's unborn.
+fn references(repo: &gix::Repository) -> anyhow::Result<(Vec<Ref>, Option<BString>)> {
+    let head = repo.head()?;
+    let unborn_head = match &head.kind {
+        gix::head::Kind::Unborn(name) => Some(name.as_bstr().to_owned()),
+        _ => None,
+    };
+    let mut refs = Vec::new();
+    refs.extend(Ref::from_reference(repo.find_reference("HEAD")?)?);
+    for reference in repo.references()?.all()? {
+        refs.extend(Ref::from_reference(reference.map_err(|err| anyhow!(err))?)?);
+    }
+    Ok((refs, unborn_head))
+}
+
+fn ls_refs(repo: &gix::Repository, arguments: &[BString], mut out: impl Write) -> anyhow::Result<()> {
+    let mut symrefs = false;
+    let mut peel = false;
+    let mut unborn = false;
+    let mut prefixes = Vec::new();
+    for argument in arguments {
+        match argument.as_bytes() {
+            b"symrefs" => symrefs = true,
+            b"peel" => peel = true,
+            b"unborn" => unborn = true,
+            argument => match argument.strip_prefix(b"ref-prefix ") {
+                Some(prefix) => prefixes.push(prefix.as_bstr()),
+                None => bail!("Unsupported argument to 'ls-refs': '{}'", argument.as_bstr()),
+            },
+        }
+    }
+    let is_selected = |name: &BStr| prefixes.is_empty() || prefixes.iter().any(|prefix| name.starts_with(prefix));
+
+    let (refs, unborn_head) = references(repo)?;
+    if let Some(target) = unborn_head.filter(|_| unborn && is_selected("HEAD".into())) {
+        let line = if symrefs {
+            format!("unborn HEAD symref-target:{target}")
+        } else {
+            "unborn HEAD".into()
+        };
+        encode::text_to_write(line.as_bytes(), &mut out)?;
+    }
+    for reference in refs.iter().filter(|r| is_selected(r.name.as_ref())) {
+        let mut line = format!("{} {}", reference.id, reference.name);
+        if let Some(target) = reference.symref_target.as_ref().filter(|_| symrefs) {
+            line.push_str(&format!(" symref-target:{target}"));
+        }
+        if let Some(peeled) = reference.peeled.filter(|_| peel) {
+            line.push_str(&format!(" peeled:{peeled}"));
+        }
+        encode::text_to_write(line.as_bytes(), &mut out)?;
+    }
+    encode::flush_to_write(&mut out)?;
+    Ok(())
+}
+
+fn fetch(repo: &gix::Repository, arguments: &[BString], mut out: impl Write) -> anyhow::Result<()> {
+    let mut wants = Vec::new();
+    let mut haves = Vec::new();
+    let mut done = false;
+    let mut include_tag = false;
+    for argument in arguments {
+        if let Some(hex) = argument.strip_prefix(b"want ") {
+            wants.push(ObjectId::from_hex(hex)?);
+        } else if let Some(hex) = argument.strip_prefix(b"have ") {
+            haves.push(ObjectId::from_hex(hex)?);
+        } else {
+            match argument.as_bytes() {
+                b"done" => done = true,
+                b"include-tag" => include_tag = true,
+                b"thin-pack" | b"ofs-delta" | b"no-progress" => {}
+                _ => bail!("Unsupported argument to 'fetch': '{argument}'"),
+            }
+        }
+    }
+    if wants.is_empty() {
+        bail!("Need at least one object to fetch");
+    }
+
+    let (refs, _) = references(repo)?;
+    if let Some(want) = wants
+        .iter()
+        .find(|want| !refs.iter().any(|r| r.id == **want || r.peeled == Some(**want)))
+    {
+        bail!("Not our ref {want}");
+    }
+    let common: Vec<_> = haves
+        .into_iter()
+        .filter(|id| {
+            repo.find_header(*id)
+                .map_or(false, |header| header.kind() == gix::object::Kind::Commit)
+        })
+        .collect();
+
+    if !done {
+        encode::text_to_write(b"acknowledgments", &mut out)?;
+        if common.is_empty() {
+            encode::text_to_write(b"NAK", &mut out)?;
+            encode::flush_to_write(&mut out)?;
+            return Ok(());
+        }
+        for id in &common {
+            encode::text_to_write(format!("ACK {id}").as_bytes(), &mut out)?;
+        }
+        encode::text_to_write(b"ready", &mut out)?;
+        encode::delim_to_write(&mut out)?;
+    }
+    encode::text_to_write(b"packfile", &mut out)?;
+    let objects = objects_to_send(repo, &wants, &common, include_tag.then_some(refs.as_slice()))?;
+    write_pack(repo, objects, &mut out)?;
+    encode::flush_to_write(&mut out)?;
+    Ok(())
+}
+
+/// Return the ids of all objects reachable from `wants`, but not from the `common` commits that the client already has.
+///
+/// If `tags` is set, annotated tags among them which point to one of the objects to send are sent as well.
+fn objects_to_send(
+    repo: &gix::Repository,
+    wants: &[ObjectId],
+    common: &[ObjectId],
+    tags: Option<&[Ref]>,
+) -> anyhow::Result<Vec<ObjectId>> {
+    let mut objects = Vec::new();
+    let mut seen = HashSet::default();
+    let mut tips = Vec::new();
+    let mut trees = Vec::new();
+    for want in wants {
+        let mut id = *want;
+        loop {
+            let object = repo.find_object(id)?;
+            match object.kind {
+                gix::object::Kind::Tag => {
+                    if seen.insert(id) {
+                        objects.push(id);
+                        id = object.into_tag().target_id()?.detach();
+                        continue;
+                    }
+                }
+                gix::object::Kind::Commit => tips.push(id),
+                gix::object::Kind::Tree => trees.push(id),
+                gix::object::Kind::Blob => {
+                    if seen.insert(id) {
+                        objects.push(id);
+                    }
+                }
+            }
+            break;
+        }
+    }
+
+    let commits = gix::traverse::commit::topo::Builder::from_iters(&repo.objects, tips, Some(common.iter().copied()))
+        .build()?
+        .collect::<Result<Vec<_>, _>>()?;
+    let commit_ids: HashSet<_> = commits.iter().map(|info| info.id).collect();
+    let mut boundary = HashSet::default();
+    for info in &commits {
+        boundary.extend(info.parent_ids.iter().filter(|id| !commit_ids.contains(*id)).copied());
+    }
+
+    // The trees of commits the client has are known to it, so don't send them again.
+    let mut buf = Vec::new();
+    for id in boundary {
+        let tree = repo.objects.find_commit_iter(&id, &mut buf)?.tree_id()?;
+        add_tree(repo, tree, &mut seen, None)?;
+    }
+    for info in &commits {
+        objects.push(info.id);
+        let tree = repo.objects.find_commit_iter(&info.id, &mut buf)?.tree_id()?;
+        add_tree(repo, tree, &mut seen, Some(&mut objects))?;
+    }
+    for tree in trees {
+        add_tree(repo, tree, &mut seen, Some(&mut objects))?;
+    }
+
+    for tag in tags.unwrap_or_default() {
+        let Some(peeled) = tag.peeled else { continue };
+        if !(commit_ids.contains(&peeled) || seen.contains(&peeled)) {
+            continue;
+        }
+        let mut id = tag.id;
+        while id != peeled && seen.insert(id) {
+            objects.push(id);
+            id = repo.find_object(id)?.into_tag().target_id()?.detach();
+        }
+    }
+    Ok(objects)
+}
+
+/// Add `tree` and all trees and blobs it contains to `seen` and, if set, to `objects`, unless they were seen before.
+fn add_tree(
+    repo: &gix::Repository,
+    tree: ObjectId,
+    seen: &mut HashSet<ObjectId>,
+    mut objects: Option<&mut Vec<ObjectId>>,
+) -> anyhow::Result<()> {
+    if !seen.insert(tree) {
+        return Ok(());
+    }
+    if let Some(objects) = objects.as_deref_mut() {
+        objects.push(tree);
+    }
+    let mut trees = vec![tree];
+    let mut buf = Vec::new();
+    while let Some(tree) = trees.pop() {
+        for entry in repo.objects.find_tree_iter(&tree, &mut buf)? {
+            let entry = entry?;
+            // Submodule commits are never part of the repository.
+            if entry.mode.is_commit() || !seen.insert(entry.oid.to_owned()) {
+                continue;
+            }
+            if let Some(objects) = objects.as_deref_mut() {
+                objects.push(entry.oid.to_owned());
+            }
+            if entry.mode.is_tree() {
+                trees.push(entry.oid.to_owned());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write a pack with all `objects` to `out`, using side-band packet lines as expected in the `packfile` section.
+fn write_pack(repo: &gix::Repository, objects: Vec<ObjectId>, out: impl Write) -> anyhow::Result<()> {
+    let should_interrupt = AtomicBool::new(false);
+    let mut handle = repo.objects.clone().into_arc()?;
+    handle.prevent_pack_unload();
+    handle.ignore_replacements = true;
+    let (counts, _) = pack::data::output::count::objects_unthreaded(
+        &handle,
+        &mut objects.into_iter().map(Ok),
+        &gix::progress::Discard,
+        &should_interrupt,
+        pack::data::output::count::objects::ObjectExpansion::AsIs,
+    )?;
+    let num_objects = counts.len();
+    let compression_level = repo.pack_compression()?.level();
+    let zlib_backend = repo.zlib_backend()?;
+    let entries = gix::parallel::InOrderIter::from(pack::data::output::entry::iter_from_counts(
+        counts,
+        handle,
+        Box::new(gix::progress::Discard),
+        pack::data::output::entry::iter_from_counts::Options {
+            thread_limit: None,
+            mode: pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+            allow_thin_pack: false,
+            chunk_size: 1000,
+            version: pack::data::Version::V2,
+            compression_level,
+            zlib_backend,
+        },
+    ));
+    let mut out = BufWriter::with_capacity(MAX_BAND_DATA_LEN, SideBand(out));
+    for written in pack::data::output::bytes::FromEntriesIter::new(
+        entries,
+        &mut out,
+        num_objects as u32,
+        pack::data::Version::V2,
+        repo.object_hash(),
+    ) {
+        written?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// A writer that sends everything written to it as pack data in side-band packet lines.
+struct SideBand<W>(W);
+
+impl<W: Write> Write for SideBand<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(MAX_BAND_DATA_LEN);
+        encode::band_to_write(Channel::Data, &buf[..len], &mut self.0)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
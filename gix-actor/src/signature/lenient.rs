@@ -0,0 +1,120 @@
+use bstr::ByteSlice;
+use gix_date::{time::Sign, OffsetInSeconds, SecondsSinceUnixEpoch, Time};
+use gix_utils::btoi::to_signed;
+
+use crate::{signature::Lenient, SignatureRef};
+
+impl<'a> Lenient<'a> {
+    /// Parse `data` like `name <email> 1528473343 +0230` leniently, similar to how `git` does it when displaying
+    /// signatures, which never fails.
+    ///
+    /// * The name ends at the first `<`, with trailing whitespace removed, and the email ends at the first `>` after it.
+    /// * Without `<`, the last word is taken as email if it contains an `@`.
+    /// * The timestamp and timezone follow the last `>`, and a missing timezone is assumed to be `+0000`.
+    /// * Timezones with more than four digits are used if the excess digits are leading zeroes, and are
+    ///   assumed to be `+0000` otherwise or if they can't be represented.
+    pub fn from_bytes(data: &'a [u8]) -> Self {
+        let (identity, time) = split_time(data);
+        let (name, email) = split_identity(identity);
+        Lenient {
+            raw: data.as_bstr(),
+            signature: SignatureRef {
+                name: name.as_bstr(),
+                email: email.as_bstr(),
+                time,
+            },
+        }
+    }
+
+    /// Return `true` if serializing [`signature`](Self::signature) would reproduce [`raw`](Self::raw) exactly,
+    /// which is the case for all well-formed signatures.
+    pub fn is_lossless(&self) -> bool {
+        let mut buf = Vec::with_capacity(self.raw.len());
+        self.signature.write_to(&mut buf).is_ok() && buf == self.raw
+    }
+
+    /// Serialize the original bytes of this instance to `out`.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        out.write_all(self.raw)
+    }
+}
+
+/// Return the identity portion of `data` along with the time that follows it, or the default time if there is none.
+fn split_time(data: &[u8]) -> (&[u8], Time) {
+    // The email may contain digits, so the time can only follow it.
+    let time_start = data.rfind_byte(b'>').map_or(0, |pos| pos + 1);
+    let (identity, last) = split_last_word(&data[time_start..]);
+    let (identity, time) = match parse_offset(last) {
+        Some((sign, offset)) => {
+            let (identity, seconds) = split_last_word(identity);
+            match to_signed::<SecondsSinceUnixEpoch>(seconds) {
+                Ok(seconds) => (identity, Time { seconds, offset, sign }),
+                Err(_) => return (data, Time::default()),
+            }
+        }
+        None => match to_signed::<SecondsSinceUnixEpoch>(last) {
+            Ok(seconds) => (identity, Time::new(seconds, 0)),
+            Err(_) => return (data, Time::default()),
+        },
+    };
+    (&data[..time_start + identity.len()], time)
+}
+
+/// Return the name and email of `identity`.
+fn split_identity(identity: &[u8]) -> (&[u8], &[u8]) {
+    match identity.find_byte(b'<') {
+        Some(pos) => {
+            let email = &identity[pos + 1..];
+            let email = match email.find_byte(b'>') {
+                Some(end) => &email[..end],
+                None => email.trim_end(),
+            };
+            (identity[..pos].trim_end(), email)
+        }
+        None => {
+            let (name, email) = split_last_word(identity);
+            let email = email.strip_suffix(b">").unwrap_or(email);
+            if email.contains(&b'@') {
+                (name, email)
+            } else {
+                (identity.trim_end(), &[])
+            }
+        }
+    }
+}
+
+/// Split the last whitespace-separated word off `data` and return the remaining bytes without trailing whitespace,
+/// along with the word.
+fn split_last_word(data: &[u8]) -> (&[u8], &[u8]) {
+    let data = data.trim_end();
+    match data.rfind_byteset(b" \t") {
+        Some(pos) => (data[..pos].trim_end(), &data[pos + 1..]),
+        None => (&[], data),
+    }
+}
+
+/// Parse a timezone like `+0230`, allowing repeated signs like git does.
+fn parse_offset(tz: &[u8]) -> Option<(Sign, OffsetInSeconds)> {
+    let sign = match tz.first()? {
+        b'-' => Sign::Minus,
+        b'+' => Sign::Plus,
+        _ => return None,
+    };
+    let digits = tz.trim_start_with(|c| c == '-' || c == '+');
+    if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    let digits = match digits.len().checked_sub(4) {
+        Some(excess) if digits[..excess].iter().all(|d| *d == b'0') => &digits[excess..],
+        Some(_) => return Some((sign, 0)),
+        None => digits,
+    };
+    let hhmm: OffsetInSeconds = to_signed(digits).ok()?;
+    let (hours, minutes) = (hhmm / 100, hhmm % 100);
+    let offset = if minutes < 60 {
+        (hours * 3600 + minutes * 60) * if sign == Sign::Minus { -1 } else { 1 }
+    } else {
+        0
+    };
+    Some((sign, offset))
+}
@@ -132,3 +132,21 @@ pub(crate) mod write {
 #[allow(clippy::empty_docs)]
 pub mod decode;
 pub use decode::function::decode;
+
+mod lenient;
+
+/// A signature parsed with [`Lenient::from_bytes()`], which tolerates breakage seen in real-world repositories
+/// like missing email brackets, unusual or absent timezones and negative zero offsets.
+///
+/// The original bytes are retained so the signature can be written back without loss, while
+/// [`signature`](Self::signature) is a best-effort interpretation of them.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lenient<'a> {
+    /// The bytes the signature was parsed from.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub raw: &'a bstr::BStr,
+    /// The best-effort interpretation of `raw`, with fields that couldn't be found left empty, or at their default.
+    #[cfg_attr(feature = "serde", serde(borrow))]
+    pub signature: crate::SignatureRef<'a>,
+}
@@ -105,3 +105,113 @@ fn parse_timestamp_with_trailing_digits() {
         }
     );
 }
+
+mod lenient {
+    use gix_actor::{
+        date::{time::Sign, Time},
+        signature::Lenient,
+    };
+
+    fn parse(input: &str) -> (&str, &str, Time) {
+        let lenient = Lenient::from_bytes(input.as_bytes());
+        assert_eq!(lenient.raw, input, "the input is retained");
+        let mut buf = Vec::new();
+        lenient.write_to(&mut buf).unwrap();
+        assert_eq!(buf, input.as_bytes(), "the original bytes are written back");
+        let signature = lenient.signature;
+        (
+            std::str::from_utf8(signature.name).unwrap(),
+            std::str::from_utf8(signature.email).unwrap(),
+            signature.time,
+        )
+    }
+
+    #[test]
+    fn well_formed_signatures_are_parsed_like_strictly_and_lossless() {
+        for input in [
+            "Sebastian Thiel <byronimo@gmail.com> 1528473343 +0230",
+            "Sebastian Thiel <byronimo@gmail.com> -1500 -0030",
+            " <> 12345 -1215",
+            "name <name@example.com> 1528473343 -0000",
+        ] {
+            let lenient = Lenient::from_bytes(input.as_bytes());
+            assert_eq!(
+                lenient.signature,
+                gix_actor::SignatureRef::from_bytes::<()>(input.as_bytes()).unwrap(),
+                "{input}"
+            );
+            assert!(lenient.is_lossless(), "{input}");
+        }
+    }
+
+    #[test]
+    fn negative_zero_offsets_are_preserved() {
+        let (_, _, time) = parse("name <name@example.com> 1528473343 -0000");
+        assert_eq!(
+            time,
+            Time {
+                seconds: 1528473343,
+                offset: 0,
+                sign: Sign::Minus
+            }
+        );
+    }
+
+    #[test]
+    fn missing_email_brackets() {
+        assert_eq!(
+            parse("first last name@example.com 1312735823 +0200"),
+            ("first last", "name@example.com", Time::new(1312735823, 7200))
+        );
+        assert_eq!(
+            parse("first last <name@example.com 1312735823 +0200"),
+            ("first last", "name@example.com", Time::new(1312735823, 7200)),
+            "without closing bracket, the email ends before the time"
+        );
+        assert_eq!(
+            parse("first last name@example.com> 1312735823 +0200"),
+            ("first last", "name@example.com", Time::new(1312735823, 7200))
+        );
+        assert_eq!(
+            parse("first last 1312735823 +0200"),
+            ("first last", "", Time::new(1312735823, 7200))
+        );
+        assert_eq!(
+            parse("first last <name@example.com>1312735823 +0200"),
+            ("first last", "name@example.com", Time::new(1312735823, 7200)),
+            "missing whitespace is fine"
+        );
+    }
+
+    #[test]
+    fn unusual_or_absent_timezones() {
+        for (input, offset) in [
+            ("name <name@example.com> 1312735823", 0),
+            ("name <name@example.com> 1312735823 +051800", 0),
+            ("name <name@example.com> 1312735823 +00530", 19800),
+            ("name <name@example.com> 1312735823 +530", 19800),
+            ("name <name@example.com> 1312735823 --700", -25200),
+            ("name <name@example.com> 1312735823 +0090", 0),
+        ] {
+            let (name, email, time) = parse(input);
+            assert_eq!((name, email), ("name", "name@example.com"), "{input}");
+            assert_eq!(time.seconds, 1312735823, "{input}");
+            assert_eq!(time.offset, offset, "{input}");
+            assert!(!Lenient::from_bytes(input.as_bytes()).is_lossless(), "{input}");
+        }
+    }
+
+    #[test]
+    fn missing_time() {
+        assert_eq!(
+            parse("name <name@example.com>"),
+            ("name", "name@example.com", Time::default())
+        );
+        assert_eq!(
+            parse("name <name@example.com> +0100"),
+            ("name", "name@example.com", Time::default()),
+            "a timezone without timestamp is ignored"
+        );
+        assert_eq!(parse(""), ("", "", Time::default()));
+    }
+}
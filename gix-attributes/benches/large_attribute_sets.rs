@@ -0,0 +1,69 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gix_attributes::{
+    search::{MetadataCollection, Outcome},
+    Search,
+};
+use gix_glob::pattern::Case;
+
+/// Like the attributes generated by `git lfs track` for many files and file types.
+fn lfs_patterns(num_patterns: usize) -> String {
+    (0..num_patterns)
+        .map(|i| match i % 3 {
+            0 => format!("*.ext{i} filter=lfs diff=lfs merge=lfs -text\n"),
+            1 => format!("assets/file{i}.bin filter=lfs diff=lfs merge=lfs -text\n"),
+            _ => format!("texture{i}.png filter=lfs diff=lfs merge=lfs -text\n"),
+        })
+        .collect()
+}
+
+const PATHS: &[&str] = &[
+    "src/lib.rs",
+    "a/deeply/nested/file.ext3",
+    "assets/file4.bin",
+    "assets/textures/texture5.png",
+    "README.md",
+];
+
+fn search(c: &mut Criterion) {
+    for num_patterns in [100, 10_000] {
+        let mut collection = MetadataCollection::default();
+        let mut linear = Search::default();
+        linear.add_patterns_buffer(
+            lfs_patterns(num_patterns).as_bytes(),
+            "<generated>".into(),
+            None,
+            &mut collection,
+            true,
+        );
+        let mut indexed = linear.clone();
+        indexed.build_index();
+        let mut out = Outcome::default();
+
+        for (name, search) in [("linear", &linear), ("indexed", &indexed)] {
+            c.bench_function(
+                &format!("Search::attributes_for() {num_patterns} patterns ({name})"),
+                |b| {
+                    b.iter(|| {
+                        for path in PATHS {
+                            black_box(
+                                search
+                                    .attributes_for((*path).into(), Case::Sensitive, None, &collection, &mut out)
+                                    .count(),
+                            );
+                        }
+                    })
+                },
+            );
+        }
+        c.bench_function(&format!("Search::build_index() {num_patterns} patterns"), |b| {
+            b.iter(|| {
+                let mut search = linear.clone();
+                search.build_index();
+                black_box(search)
+            })
+        });
+    }
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
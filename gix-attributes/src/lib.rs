@@ -27,6 +27,10 @@ pub mod search;
 #[allow(clippy::empty_docs)]
 pub mod parse;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod write;
+
 /// Parse attribute assignments line by line from `bytes`, and fail the operation on error.
 ///
 /// For leniency, ignore errors using `filter_map(Result::ok)` for example.
@@ -34,6 +38,21 @@ pub fn parse(bytes: &[u8]) -> parse::Lines<'_> {
     parse::Lines::new(bytes)
 }
 
+/// Write all patterns and macros of `list` to `out` in the order they are stored in, one per line, in the format
+/// of `.gitattributes` files so that [`parse()`] yields them again.
+///
+/// Use this to write `list` back after adding or removing patterns or assignments. Note that comments and empty lines
+/// aren't retained when parsing, and thus aren't written either.
+pub fn write(
+    list: &gix_glob::search::pattern::List<search::Attributes>,
+    out: &mut dyn std::io::Write,
+) -> std::io::Result<()> {
+    for mapping in &list.patterns {
+        write::mapping(mapping, out)?;
+    }
+    Ok(())
+}
+
 /// The state an attribute can be in, referencing the value.
 ///
 /// Note that this doesn't contain the name.
@@ -107,6 +126,9 @@ pub struct Search {
     ///
     /// When matching, this order is reversed.
     patterns: Vec<gix_glob::search::pattern::List<search::Attributes>>,
+    /// Indices for the first pattern lists, as created by [`Search::build_index()`].
+    /// Lists without index are searched linearly.
+    indices: Vec<search::Index>,
 }
 
 /// A list of known global sources for git attribute files in order of ascending precedence.
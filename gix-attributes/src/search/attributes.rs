@@ -5,6 +5,7 @@ use gix_glob::search::{pattern, Pattern};
 
 use super::Attributes;
 use crate::{
+    search,
     search::{Assignments, MetadataCollection, Outcome, TrackedAssignment, Value},
     Search,
 };
@@ -91,7 +92,22 @@ impl Search {
 
     /// Pop the last attribute patterns list from our queue.
     pub fn pop_pattern_list(&mut self) -> Option<gix_glob::search::pattern::List<Attributes>> {
-        self.patterns.pop()
+        let list = self.patterns.pop();
+        self.indices.truncate(self.patterns.len());
+        list
+    }
+
+    /// Index all pattern lists we currently contain, which makes matching paths against lists with many patterns
+    /// considerably faster, like those generated by tools that assign attributes to thousands of files.
+    ///
+    /// Patterns without wildcards and patterns like `*.ext` are bucketed by the literal they match, so only the few
+    /// that may match a path are tried, along with all other patterns.
+    /// Pattern lists added afterwards aren't indexed unless this method is called again, but are still searched.
+    /// Note that indexing takes time on its own, and only pays off if many paths are matched against many patterns.
+    pub fn build_index(&mut self) {
+        let num_indexed = self.indices.len();
+        self.indices
+            .extend(self.patterns[num_indexed..].iter().map(search::Index::new));
     }
 }
 
@@ -108,13 +124,43 @@ impl Search {
     ) -> bool {
         let basename_pos = relative_path.rfind(b"/").map(|p| p + 1);
         let mut has_match = false;
-        self.patterns.iter().rev().any(|pl| {
-            has_match |= pattern_matching_relative_path(pl, relative_path, basename_pos, case, is_dir, out);
+        self.patterns.iter().enumerate().rev().any(|(idx, pl)| {
+            has_match |= pattern_matching_relative_path(
+                pl,
+                self.indices.get(idx),
+                relative_path,
+                basename_pos,
+                case,
+                is_dir,
+                out,
+            );
             out.is_done()
         });
         has_match
     }
 
+    /// Compute all attributes in effect for `relative_path`, a path relative to the repository, similar to `git check-attr -a`,
+    /// and return their assignments in order of declaration.
+    ///
+    /// Macros are resolved into the attributes they assign, the last matching pattern wins, and attributes that
+    /// were made unspecified with `!` are omitted.
+    /// `out` is initialized with `collection` and reset, so it can be reused for multiple calls, but it must not have been
+    /// initialized [with a selection](Outcome::initialize_with_selection()).
+    pub fn attributes_for<'out>(
+        &self,
+        relative_path: &BStr,
+        case: gix_glob::pattern::Case,
+        is_dir: Option<bool>,
+        collection: &MetadataCollection,
+        out: &'out mut Outcome,
+    ) -> impl Iterator<Item = crate::AssignmentRef<'out>> + 'out {
+        out.initialize(collection);
+        out.reset();
+        self.pattern_matching_relative_path(relative_path, case, is_dir, out);
+        out.iter()
+            .filter_map(|m| (!m.assignment.state.is_unspecified()).then_some(m.assignment))
+    }
+
     /// Return the amount of pattern lists contained in this instance.
     pub fn num_pattern_lists(&self) -> usize {
         self.patterns.len()
@@ -183,7 +229,7 @@ impl Pattern for Attributes {
 }
 
 impl Attributes {
-    fn may_use_glob_pattern(pattern: &gix_glob::Pattern) -> bool {
+    pub(crate) fn may_use_glob_pattern(pattern: &gix_glob::Pattern) -> bool {
         pattern.mode != macro_mode()
     }
 }
@@ -192,14 +238,15 @@ fn macro_mode() -> gix_glob::pattern::Mode {
     gix_glob::pattern::Mode::all()
 }
 
-/// Append all matches of patterns matching `relative_path` to `out`,
-/// providing a pre-computed `basename_pos` which is the starting position of the basename of `relative_path`.
+/// Append all matches of patterns matching `relative_path` to `out`, using `index` of `list` to only try the patterns
+/// which may match, providing a pre-computed `basename_pos` which is the starting position of the basename of `relative_path`.
 /// `case` specifies whether cases should be folded during matching or not.
 /// `is_dir` is true if `relative_path` is a directory.
 /// Return `true` if at least one pattern matched.
 #[allow(unused_variables)]
 fn pattern_matching_relative_path(
     list: &gix_glob::search::pattern::List<Attributes>,
+    index: Option<&search::Index>,
     relative_path: &BStr,
     basename_pos: Option<usize>,
     case: gix_glob::pattern::Case,
@@ -212,15 +259,29 @@ fn pattern_matching_relative_path(
             None => return false,
         };
     let cur_len = out.remaining();
+    let (mut indexed, mut all);
+    let mappings: &mut dyn Iterator<Item = &pattern::Mapping<Value>> = match index {
+        Some(index) => {
+            indexed = index
+                .candidates(relative_path, basename_start_pos)
+                .into_iter()
+                .map(|idx| &list.patterns[idx]);
+            &mut indexed
+        }
+        None => {
+            all = list
+                .patterns
+                .iter()
+                .rev()
+                .filter(|pm| Attributes::may_use_glob_pattern(&pm.pattern));
+            &mut all
+        }
+    };
     'outer: for pattern::Mapping {
         pattern,
         value,
         sequence_number,
-    } in list
-        .patterns
-        .iter()
-        .rev()
-        .filter(|pm| Attributes::may_use_glob_pattern(&pm.pattern))
+    } in mappings
     {
         let value: &Value = value;
         let attrs = match value {
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_glob::pattern::Mode;
+
+use crate::search::Attributes;
+
+/// An index into the patterns of a single list to find the ones that may match a path without trying all of them.
+///
+/// Patterns are bucketed by the literal they match, and keys are ASCII-lowercased so lookups work with both case-sensitive
+/// and case-folding matches. Candidates are a superset of the matching patterns, and still have to be matched.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Default)]
+pub(crate) struct Index {
+    /// Patterns without wildcards that match the basename of a path, by basename.
+    by_basename: BTreeMap<BString, Vec<usize>>,
+    /// Patterns like `*.ext` that match the end of the basename of a path, by the extension of the basenames they match.
+    by_extension: BTreeMap<BString, Vec<usize>>,
+    /// Patterns without wildcards that match the whole path, by path.
+    by_path: BTreeMap<BString, Vec<usize>>,
+    /// Patterns that have to be tried with every path.
+    other: Vec<usize>,
+}
+
+impl Index {
+    /// Create an index for all patterns of `list` which can be matched.
+    pub(crate) fn new(list: &gix_glob::search::pattern::List<Attributes>) -> Self {
+        let mut index = Index::default();
+        for (idx, mapping) in list.patterns.iter().enumerate() {
+            let pattern = &mapping.pattern;
+            if !Attributes::may_use_glob_pattern(pattern) {
+                continue;
+            }
+            let matches_basename = pattern.mode.contains(Mode::NO_SUB_DIR) && !pattern.mode.contains(Mode::ABSOLUTE);
+            let bucket = match pattern.first_wildcard_pos {
+                None if matches_basename => index.by_basename.entry(pattern.text.to_ascii_lowercase().into()),
+                None => index.by_path.entry(pattern.text.to_ascii_lowercase().into()),
+                Some(0) if matches_basename && pattern.mode.contains(Mode::ENDS_WITH) => {
+                    match extension(pattern.text[1..].as_bstr()) {
+                        Some(ext) => index.by_extension.entry(ext.to_ascii_lowercase().into()),
+                        None => {
+                            index.other.push(idx);
+                            continue;
+                        }
+                    }
+                }
+                Some(_) => {
+                    index.other.push(idx);
+                    continue;
+                }
+            };
+            bucket.or_default().push(idx);
+        }
+        index
+    }
+
+    /// Return the indices of all patterns that may match `relative_path` with its basename starting at `basename_pos`,
+    /// in the descending order in which they have to be tried.
+    pub(crate) fn candidates(&self, relative_path: &BStr, basename_pos: Option<usize>) -> Vec<usize> {
+        let path = BString::from(relative_path.to_ascii_lowercase());
+        let basename = path[basename_pos.unwrap_or_default()..].as_bstr();
+        let mut out = self.other.clone();
+        for (map, key) in [
+            (&self.by_path, Some(path.as_bstr())),
+            (&self.by_basename, Some(basename)),
+            (&self.by_extension, extension(basename)),
+        ] {
+            if let Some(indices) = key.and_then(|key| map.get(key)) {
+                out.extend_from_slice(indices);
+            }
+        }
+        out.sort_unstable_by(|a, b| b.cmp(a));
+        out
+    }
+}
+
+/// Return the part of `name` after its last dot, if there is one.
+fn extension(name: &BStr) -> Option<&BStr> {
+    name.rfind_byte(b'.').map(|pos| name[pos + 1..].as_bstr())
+}
@@ -6,6 +6,8 @@ use smallvec::SmallVec;
 use crate::{Assignment, AssignmentRef};
 
 mod attributes;
+mod index;
+pub(crate) use index::Index;
 mod outcome;
 mod refmap;
 pub(crate) use refmap::RefMap;
@@ -1,37 +1,35 @@
 use bstr::{BStr, ByteSlice};
-use kstring::{KString, KStringRef};
+use smallvec::SmallVec;
 
 use crate::{State, StateRef};
 
+/// The amount of bytes a [`Value`] can hold without allocating.
+const INLINE_VALUE_LEN: usize = 23;
+
 /// A container to encapsulate a tightly packed and typically unallocated byte value that isn't necessarily UTF8 encoded.
+///
+/// With the `serde` feature, it's serialized as string if it's valid UTF-8, and as bytes otherwise.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct Value(KString);
+pub struct Value(SmallVec<[u8; INLINE_VALUE_LEN]>);
 
-/// A reference container to encapsulate a tightly packed and typically unallocated byte value that isn't necessarily UTF8 encoded.
+/// A reference container to encapsulate a byte value that isn't necessarily UTF8 encoded.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct ValueRef<'a>(#[cfg_attr(feature = "serde", serde(borrow))] KStringRef<'a>);
+pub struct ValueRef<'a>(#[cfg_attr(feature = "serde", serde(borrow))] &'a BStr);
 
 /// Lifecycle
 impl<'a> ValueRef<'a> {
     /// Keep `input` as our value.
     pub fn from_bytes(input: &'a [u8]) -> Self {
-        Self(KStringRef::from_ref(
-            // SAFETY: our API makes accessing that value as `str` impossible, so illformed UTF8 is never exposed as such.
-            #[allow(unsafe_code)]
-            unsafe {
-                std::str::from_utf8_unchecked(input)
-            },
-        ))
+        Self(input.as_bstr())
     }
 }
 
 /// Access and conversions
-impl ValueRef<'_> {
+impl<'a> ValueRef<'a> {
     /// Access this value as byte string.
-    pub fn as_bstr(&self) -> &BStr {
-        self.0.as_bytes().as_bstr()
+    pub fn as_bstr(&self) -> &'a BStr {
+        self.0
     }
 
     /// Convert this instance into its owned form.
@@ -48,13 +46,19 @@ impl<'a> From<&'a str> for ValueRef<'a> {
 
 impl<'a> From<ValueRef<'a>> for Value {
     fn from(v: ValueRef<'a>) -> Self {
-        Value(v.0.into())
+        Value(SmallVec::from_slice(v.0))
     }
 }
 
 impl From<&str> for Value {
     fn from(v: &str) -> Self {
-        Value(KString::from_ref(v))
+        Value(SmallVec::from_slice(v.as_bytes()))
+    }
+}
+
+impl From<&[u8]> for Value {
+    fn from(v: &[u8]) -> Self {
+        Value(SmallVec::from_slice(v))
     }
 }
 
@@ -62,7 +66,59 @@ impl From<&str> for Value {
 impl Value {
     /// Return ourselves as reference.
     pub fn as_ref(&self) -> ValueRef<'_> {
-        ValueRef(self.0.as_ref())
+        ValueRef(self.0.as_bstr())
+    }
+
+    /// Access this value as byte string.
+    pub fn as_bstr(&self) -> &BStr {
+        self.0.as_bstr()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use bstr::ByteSlice;
+    use smallvec::SmallVec;
+
+    use super::Value;
+
+    impl serde::Serialize for Value {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.0.to_str() {
+                Ok(value) => serializer.serialize_str(value),
+                Err(_) => serializer.serialize_bytes(&self.0),
+            }
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Value {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            struct Visitor;
+            impl<'de> serde::de::Visitor<'de> for Visitor {
+                type Value = Value;
+
+                fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str("a string or bytes")
+                }
+
+                fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                    Ok(v.into())
+                }
+
+                fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                    Ok(v.into())
+                }
+
+                fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                    let mut bytes = SmallVec::new();
+                    while let Some(byte) = seq.next_element()? {
+                        bytes.push(byte);
+                    }
+                    Ok(Value(bytes))
+                }
+            }
+            deserializer.deserialize_any(Visitor)
+        }
     }
 }
 
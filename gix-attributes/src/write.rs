@@ -0,0 +1,91 @@
+use std::{borrow::Cow, io};
+
+use bstr::{BString, ByteSlice};
+use gix_glob::search::pattern::Mapping;
+
+use crate::{search, AssignmentRef, StateRef};
+
+/// Write `mapping` as a single line of a `.gitattributes` file to `out`, including the trailing newline.
+///
+/// Macros are written as `[attr]<name>`, and patterns are escaped or quoted as needed to be parsed back into the same pattern.
+/// Fail if an assigned value contains whitespace, as it couldn't be parsed back.
+pub fn mapping(mapping: &Mapping<search::Value>, out: &mut dyn io::Write) -> io::Result<()> {
+    let assignments = match &mapping.value {
+        search::Value::MacroAssignments { assignments, .. } => {
+            out.write_all(b"[attr]")?;
+            out.write_all(&mapping.pattern.text)?;
+            assignments
+        }
+        search::Value::Assignments(assignments) => {
+            out.write_all(&pattern(&mapping.pattern))?;
+            assignments
+        }
+    };
+    for assignment in assignments {
+        out.write_all(b" ")?;
+        self::assignment(assignment.inner.as_ref(), out)?;
+    }
+    out.write_all(b"\n")
+}
+
+/// Write `assignment` like `attr`, `-attr`, `!attr` or `attr=value` to `out`.
+///
+/// Fail if the value contains whitespace, as it couldn't be parsed back.
+pub fn assignment(assignment: AssignmentRef<'_>, out: &mut dyn io::Write) -> io::Result<()> {
+    let name = assignment.name.as_str().as_bytes();
+    match assignment.state {
+        StateRef::Set => out.write_all(name),
+        StateRef::Unset => {
+            out.write_all(b"-")?;
+            out.write_all(name)
+        }
+        StateRef::Unspecified => {
+            out.write_all(b"!")?;
+            out.write_all(name)
+        }
+        StateRef::Value(value) => {
+            let value = value.as_bstr();
+            if value.find_byteset(b" \t\r\n").is_some() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "The value {value:?} of attribute '{}' contains whitespace",
+                        assignment.name.as_str()
+                    ),
+                ));
+            }
+            out.write_all(name)?;
+            out.write_all(b"=")?;
+            out.write_all(value)
+        }
+    }
+}
+
+/// Return `pattern` as it has to be written to be parsed back into the same pattern.
+fn pattern(pattern: &gix_glob::Pattern) -> BString {
+    use gix_glob::pattern::Mode;
+    let mut text = BString::default();
+    if pattern.mode.contains(Mode::ABSOLUTE) {
+        text.push(b'/');
+    } else if matches!(pattern.text.first(), Some(b'!' | b'#')) {
+        text.push(b'\\');
+    }
+    text.extend_from_slice(&pattern.text);
+    if pattern.mode.contains(Mode::MUST_BE_DIR) {
+        text.push(b'/');
+    }
+    let needs_quotes = matches!(gix_quote::ansi_c::quote(pattern.text.as_bstr(), false), Cow::Owned(_))
+        || pattern.text.contains(&b' ');
+    if !needs_quotes {
+        return text;
+    }
+    match gix_quote::ansi_c::quote(text.as_bstr(), false) {
+        Cow::Owned(quoted) => quoted,
+        Cow::Borrowed(_) => {
+            let mut quoted = BString::from("\"");
+            quoted.extend_from_slice(&text);
+            quoted.push(b'"');
+            quoted
+        }
+    }
+}
@@ -3,3 +3,4 @@ mod assignment;
 mod parse;
 mod search;
 mod state;
+mod write;
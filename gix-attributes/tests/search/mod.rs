@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 
 use bstr::{BStr, ByteSlice};
 use gix_attributes::{
-    search::{AttributeId, Outcome},
+    search::{AttributeId, MetadataCollection, Outcome},
     AssignmentRef, NameRef, StateRef,
 };
 use gix_glob::pattern::Case;
@@ -96,23 +96,110 @@ fn baseline() -> crate::Result {
         "should have loaded all files, and the builtins"
     );
 
-    let mut actual = gix_attributes::search::Outcome::default();
-    actual.initialize(&collection);
-    for (rela_path, expected) in (baseline::Expectations { lines: input.lines() }) {
-        actual.reset();
-        let has_match = group.pattern_matching_relative_path(rela_path, case, None, &mut actual);
-        assert_references(&actual);
-        let actual: Vec<_> = actual
-            .iter()
-            .filter_map(|m| (!m.assignment.state.is_unspecified()).then_some(m.assignment))
-            .collect();
-        assert_eq!(actual, expected, "we have the same matches: {rela_path:?}");
-        assert_ne!(has_match, actual.is_empty());
+    for indexed in [false, true] {
+        if indexed {
+            group.build_index();
+        }
+        let mut actual = gix_attributes::search::Outcome::default();
+        actual.initialize(&collection);
+        for (rela_path, expected) in (baseline::Expectations { lines: input.lines() }) {
+            actual.reset();
+            let has_match = group.pattern_matching_relative_path(rela_path, case, None, &mut actual);
+            assert_references(&actual);
+            let actual: Vec<_> = actual
+                .iter()
+                .filter_map(|m| (!m.assignment.state.is_unspecified()).then_some(m.assignment))
+                .collect();
+            assert_eq!(
+                actual, expected,
+                "we have the same matches: {rela_path:?} (indexed: {indexed})"
+            );
+            assert_ne!(has_match, actual.is_empty());
+
+            let mut out = Outcome::default();
+            assert_eq!(
+                group
+                    .attributes_for(rela_path, case, None, &collection, &mut out)
+                    .collect::<Vec<_>>(),
+                actual,
+                "{rela_path:?}: the convenience query yields the same effective attributes"
+            );
+        }
     }
 
     Ok(())
 }
 
+#[test]
+fn indexed_search_matches_like_linear_search() {
+    let mut patterns = String::new();
+    for i in 0..50 {
+        patterns.push_str(&format!(
+            "*.ext{i} ext{m}\nfile{i}.txt -ext{m} name\n/dir{i}/file root=dir{i}\ndir{i}/*.BIN bin\n*suffix{i} suffix\n[attr]macro{i} name=macro{i}\nFile{i}.* macro{i}\n*.Tar.gz -bin\n",
+            m = i % 3
+        ));
+    }
+    let mut collection = MetadataCollection::default();
+    let mut group = gix_attributes::Search::default();
+    group.add_patterns_buffer(patterns.as_bytes(), "<memory>".into(), None, &mut collection, true);
+    group.add_patterns_buffer(
+        b"*.ext1 -ext1\n* all",
+        "sub/.gitattributes".into(),
+        Some("".as_ref()),
+        &mut collection,
+        true,
+    );
+    let mut indexed = group.clone();
+    indexed.build_index();
+
+    let mut expected_out = Outcome::default();
+    let mut actual_out = Outcome::default();
+    for case in [Case::Sensitive, Case::Fold] {
+        for path in [
+            "a.ext1",
+            "sub/a.ext1",
+            "sub/A.EXT1",
+            "file1.txt",
+            "x/FILE2.txt",
+            "File3.txt",
+            "dir4/file",
+            "x/dir4/file",
+            "DIR4/FILE",
+            "dir5/a.bin",
+            "dir5/b/a.bin",
+            "dir6",
+            "nosuffix7",
+            "a.b.tar.gz",
+            "a.TAR.GZ",
+            "no-extension",
+            "trailing.",
+        ] {
+            let expected: Vec<_> = group
+                .attributes_for(path.into(), case, None, &collection, &mut expected_out)
+                .collect();
+            let actual: Vec<_> = indexed
+                .attributes_for(path.into(), case, None, &collection, &mut actual_out)
+                .collect();
+            assert_eq!(
+                actual, expected,
+                "{path:?} {case:?}: the index doesn't affect the outcome"
+            );
+        }
+    }
+
+    indexed.pop_pattern_list();
+    group.pop_pattern_list();
+    indexed.add_patterns_buffer(b"*.ext1 -ext1", "<memory>".into(), None, &mut collection, true);
+    group.add_patterns_buffer(b"*.ext1 -ext1", "<memory>".into(), None, &mut collection, true);
+    let expected: Vec<_> = group
+        .attributes_for("a.ext1".into(), Case::Sensitive, None, &collection, &mut expected_out)
+        .collect();
+    let actual: Vec<_> = indexed
+        .attributes_for("a.ext1".into(), Case::Sensitive, None, &collection, &mut actual_out)
+        .collect();
+    assert_eq!(actual, expected, "lists added after indexing are searched as well");
+}
+
 fn assert_references(out: &Outcome) {
     for m in out.iter() {
         if let Some(source) = m.kind.source_id() {
@@ -224,6 +311,50 @@ fn all_attributes_are_listed_in_declaration_order() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn macros_expand_into_the_attributes_they_assign() {
+    let mut buf = Vec::new();
+    let mut collection = gix_attributes::search::MetadataCollection::default();
+    let mut group = gix_attributes::Search::new_globals(None::<std::path::PathBuf>, &mut buf, &mut collection)
+        .expect("no files to read");
+    group.add_patterns_buffer(
+        b"[attr]my-binary binary -whitespace\n*.dat my-binary\n*.bin binary text\n",
+        "<memory>".into(),
+        None,
+        &mut collection,
+        true, /* allow macros */
+    );
+
+    let mut out = Outcome::default();
+    out.initialize_with_selection(&collection, ["diff", "merge", "text", "whitespace"]);
+    let mut selected = |rela_path: &str| {
+        out.reset();
+        group.pattern_matching_relative_path(rela_path.into(), Case::Sensitive, None, &mut out);
+        out.iter_selected()
+            .map(|m| format!("{}={:?}", m.assignment.name.as_str(), m.assignment.state))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        selected("a.dat"),
+        ["diff=Unset", "merge=Unset", "text=Unset", "whitespace=Unset"],
+        "custom macros resolve through the builtin `binary` macro"
+    );
+    assert_eq!(
+        selected("a.bin"),
+        ["diff=Unset", "merge=Unset", "text=Set", "whitespace=Unspecified"],
+        "attributes following a macro override what it assigns"
+    );
+    assert_eq!(
+        selected("a.txt"),
+        [
+            "diff=Unspecified",
+            "merge=Unspecified",
+            "text=Unspecified",
+            "whitespace=Unspecified"
+        ]
+    );
+}
+
 #[test]
 fn given_attributes_are_made_available_in_given_order() -> crate::Result {
     let (mut group, mut collection, base, input) =
@@ -12,6 +12,15 @@ mod value {
         assert_eq!(ValueRef::from_bytes(ILLFORMED_UTF8).as_bstr(), ILLFORMED_UTF8);
         assert_eq!(ValueRef::from_bytes("utf8".as_bytes()).as_bstr(), "utf8");
     }
+
+    #[test]
+    fn to_owned_and_back() {
+        for input in [ILLFORMED_UTF8, b"a value that is too long to be stored inline"] {
+            let value = ValueRef::from_bytes(input).to_owned();
+            assert_eq!(value.as_bstr(), input);
+            assert_eq!(value.as_ref(), ValueRef::from_bytes(input));
+        }
+    }
 }
 
 #[test]
@@ -23,3 +32,17 @@ fn from_value() {
         "this can round-trip with care"
     );
 }
+
+#[test]
+fn illformed_utf8_values_are_parsed_and_written() -> crate::Result {
+    let mut input = b"*.txt a=".to_vec();
+    input.extend_from_slice(ILLFORMED_UTF8);
+    let (_, mut assignments, _) = gix_attributes::parse(&input).next().expect("one line")?;
+    let assignment = assignments.next().expect("one assignment")?.to_owned();
+    assert_eq!(assignment.state.as_ref().as_bstr(), Some(ILLFORMED_UTF8.into()));
+
+    let mut out = Vec::new();
+    gix_attributes::write::assignment(assignment.as_ref(), &mut out)?;
+    assert_eq!(out, &input[6..]);
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use bstr::ByteSlice;
+use gix_attributes::{
+    search::{Attributes, TrackedAssignment, Value},
+    Assignment, StateRef,
+};
+use gix_glob::search::pattern::{List, Mapping};
+
+#[test]
+fn fixture_round_trips_without_comments() -> crate::Result {
+    let input = gix_testtools::fixture_bytes("attributes/various.txt");
+    let list = list(&input);
+    let written = write(&list)?;
+    assert_eq!(
+        written.as_bstr(),
+        "*.[oa] c\n*.html a b=c\n\\!foo.html x\n\\#a/path -a\n/* !b\n",
+        "escapes are kept, but superfluous quotes and whitespace are dropped"
+    );
+    assert_eq!(patterns_and_values(&list), patterns_and_values(&self::list(&written)));
+    Ok(())
+}
+
+#[test]
+fn patterns_are_quoted_if_needed() -> crate::Result {
+    for input in [
+        "\"with space\" a",
+        "\"with\\ttab\" a",
+        "\"with\\\\backslash and space\" a",
+        "\"\\\"quoted\" a",
+        "\"/absolute dir/\" a",
+        "\"\\\\!exclamation mark\" a",
+        "[attr]macro a -b !c d=e",
+        "emptymacro",
+    ] {
+        let list = list(input.as_bytes());
+        assert_eq!(list.patterns.len(), 1);
+        let written = write(&list)?;
+        assert_eq!(written.as_bstr(), format!("{input}\n"), "{input}");
+        assert_eq!(patterns_and_values(&list), patterns_and_values(&self::list(&written)));
+    }
+    Ok(())
+}
+
+#[test]
+fn assignments_can_be_added_and_removed() -> crate::Result {
+    let mut list = list(b"[attr]binary -diff -merge -text\n*.png binary\n*.txt text eol=lf\n");
+    let Value::Assignments(assignments) = &mut list.patterns[2].value else {
+        unreachable!("not a macro")
+    };
+    assignments.retain(|a| a.inner.name.as_str() != "eol");
+    list.patterns.push(Mapping {
+        pattern: gix_glob::parse("*.psd").expect("valid"),
+        value: Value::Assignments(
+            ["filter=lfs", "diff=lfs", "merge=lfs", "-text"]
+                .into_iter()
+                .map(|a| TrackedAssignment {
+                    id: Default::default(),
+                    inner: assignment(a),
+                })
+                .collect(),
+        ),
+        sequence_number: 0,
+    });
+    assert_eq!(
+        write(&list)?.as_bstr(),
+        "[attr]binary -diff -merge -text\n*.png binary\n*.txt text\n*.psd filter=lfs diff=lfs merge=lfs -text\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn values_with_whitespace_cannot_be_written() {
+    let mut out = Vec::new();
+    let err = gix_attributes::write::assignment(
+        gix_attributes::AssignmentRef {
+            name: assignment("a").name.as_ref(),
+            state: StateRef::from_bytes(b"with space"),
+        },
+        &mut out,
+    )
+    .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+fn assignment(input: &str) -> Assignment {
+    gix_attributes::parse::Iter::new(input.into())
+        .next()
+        .expect("one assignment")
+        .expect("valid")
+        .to_owned()
+}
+
+fn list(input: &[u8]) -> List<Attributes> {
+    List::from_bytes(input, "<memory>".into(), None)
+}
+
+fn write(list: &List<Attributes>) -> std::io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    gix_attributes::write(list, &mut out)?;
+    Ok(out)
+}
+
+fn patterns_and_values(list: &List<Attributes>) -> Vec<(&gix_glob::Pattern, &Value)> {
+    list.patterns.iter().map(|m| (&m.pattern, &m.value)).collect()
+}
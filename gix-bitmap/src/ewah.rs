@@ -50,8 +50,211 @@ pub fn decode(data: &[u8]) -> Result<(Vec, &[u8]), decode::Error> {
     ))
 }
 
+mod write {
+    use super::{rlw, Vec};
+
+    impl Default for Vec {
+        fn default() -> Self {
+            Vec {
+                num_bits: 0,
+                bits: vec![0],
+                rlw: 0,
+            }
+        }
+    }
+
+    impl Vec {
+        /// Create a new, empty bitmap.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Create a bitmap with all bits at `indices` set, which must be strictly increasing.
+        pub fn from_set_bits(indices: impl IntoIterator<Item = usize>) -> Self {
+            let mut out = Self::default();
+            for index in indices {
+                out.set(index);
+            }
+            out
+        }
+
+        /// Set the bit at `index`, which must be past all bits we already hold, and extend the bitmap accordingly.
+        ///
+        /// # Panics
+        ///
+        /// If `index` isn't larger than the index of the last bit we hold.
+        pub fn set(&mut self, index: usize) {
+            const WORD_BITS: usize = 64;
+            let num_bits = self.num_bits();
+            assert!(
+                index >= num_bits,
+                "BUG: bits must be set in increasing order, {index} is not past {num_bits}"
+            );
+            let distance = (index + WORD_BITS) / WORD_BITS - (num_bits + WORD_BITS - 1) / WORD_BITS;
+            self.num_bits = (index + 1).try_into().expect("bitmaps are limited to u32::MAX bits");
+            let bit = 1u64 << (index % WORD_BITS);
+            if distance > 0 {
+                if distance > 1 {
+                    self.add_run_words(false, (distance - 1) as u64);
+                }
+                self.add_literal_word(bit);
+                return;
+            }
+            if rlw::literal_words(self.rlw_word()) == 0 {
+                let running_len = rlw::running_len(self.rlw_word());
+                rlw::set_running_len(self.rlw_word_mut(), running_len - 1);
+                self.add_literal_word(bit);
+                return;
+            }
+            let last = self.bits.last_mut().expect("at least one rlw");
+            *last |= bit;
+            if *last == u64::MAX {
+                self.bits.pop();
+                let literal_words = rlw::literal_words(self.rlw_word());
+                rlw::set_literal_words(self.rlw_word_mut(), literal_words - 1);
+                self.add_run_word(true);
+            }
+        }
+
+        /// Append all 64 bits of `word`, compressing it into a run if all of its bits are the same.
+        pub fn push_word(&mut self, word: u64) {
+            self.num_bits += 64;
+            match word {
+                0 => self.add_run_word(false),
+                u64::MAX => self.add_run_word(true),
+                word => self.add_literal_word(word),
+            }
+        }
+
+        /// Append `num_words` words of 64 bits each with all bits set to `bit`, stored as run-length.
+        pub fn push_run(&mut self, bit: bool, num_words: u64) {
+            if num_words == 0 {
+                return;
+            }
+            self.num_bits = u64::from(self.num_bits)
+                .checked_add(num_words * 64)
+                .and_then(|num_bits| u32::try_from(num_bits).ok())
+                .expect("bitmaps are limited to u32::MAX bits");
+            self.add_run_words(bit, num_words);
+        }
+
+        fn add_run_word(&mut self, bit: bool) {
+            let w = self.rlw_word();
+            let no_literal = rlw::literal_words(w) == 0;
+            let running_len = rlw::running_len(w);
+            if no_literal && running_len == 0 {
+                rlw::set_run_bit(self.rlw_word_mut(), bit);
+            }
+            if no_literal && rlw::run_bit(self.rlw_word()) == bit && running_len < rlw::LARGEST_RUNNING_COUNT {
+                rlw::set_running_len(self.rlw_word_mut(), running_len + 1);
+            } else {
+                self.push_rlw();
+                rlw::set_run_bit(self.rlw_word_mut(), bit);
+                rlw::set_running_len(self.rlw_word_mut(), 1);
+            }
+        }
+
+        fn add_run_words(&mut self, bit: bool, mut num_words: u64) {
+            let w = self.rlw_word();
+            if rlw::run_bit(w) != bit && rlw::running_len(w) == 0 && rlw::literal_words(w) == 0 {
+                rlw::set_run_bit(self.rlw_word_mut(), bit);
+            } else if rlw::literal_words(w) != 0 || rlw::run_bit(w) != bit {
+                self.push_rlw();
+                rlw::set_run_bit(self.rlw_word_mut(), bit);
+            }
+            let running_len = rlw::running_len(self.rlw_word());
+            let can_add = num_words.min(rlw::LARGEST_RUNNING_COUNT - running_len);
+            rlw::set_running_len(self.rlw_word_mut(), running_len + can_add);
+            num_words -= can_add;
+            while num_words > 0 {
+                let can_add = num_words.min(rlw::LARGEST_RUNNING_COUNT);
+                self.push_rlw();
+                rlw::set_run_bit(self.rlw_word_mut(), bit);
+                rlw::set_running_len(self.rlw_word_mut(), can_add);
+                num_words -= can_add;
+            }
+        }
+
+        fn add_literal_word(&mut self, word: u64) {
+            let literal_words = rlw::literal_words(self.rlw_word());
+            if literal_words >= rlw::LARGEST_LITERAL_COUNT {
+                self.push_rlw();
+                rlw::set_literal_words(self.rlw_word_mut(), 1);
+            } else {
+                rlw::set_literal_words(self.rlw_word_mut(), literal_words + 1);
+            }
+            self.bits.push(word);
+        }
+
+        fn push_rlw(&mut self) {
+            self.rlw = self.bits.len() as u64;
+            self.bits.push(0);
+        }
+
+        fn rlw_word(&self) -> u64 {
+            self.bits[self.rlw as usize]
+        }
+
+        fn rlw_word_mut(&mut self) -> &mut u64 {
+            &mut self.bits[self.rlw as usize]
+        }
+
+        /// Write this bitmap in the format understood by [`decode()`](super::decode()) to `out`.
+        pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+            let len = u32::try_from(self.bits.len())
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "too many words in bitmap"))?;
+            out.write_all(&self.num_bits.to_be_bytes())?;
+            out.write_all(&len.to_be_bytes())?;
+            for word in &self.bits {
+                out.write_all(&word.to_be_bytes())?;
+            }
+            out.write_all(&(self.rlw as u32).to_be_bytes())?;
+            Ok(())
+        }
+    }
+}
+
+/// Utilities to read and write run-length words, which are stored as `[run bit | 32 bits running length | 31 bits literal words]`
+/// from the least significant bit.
+mod rlw {
+    pub(super) const RUNNING_BITS: u64 = 4 * 8;
+    pub(super) const LITERAL_BITS: u64 = 64 - 1 - RUNNING_BITS;
+    pub(super) const LARGEST_RUNNING_COUNT: u64 = (1 << RUNNING_BITS) - 1;
+    pub(super) const LARGEST_LITERAL_COUNT: u64 = (1 << LITERAL_BITS) - 1;
+
+    #[inline]
+    pub(super) fn run_bit(w: u64) -> bool {
+        w & 1 == 1
+    }
+
+    #[inline]
+    pub(super) fn running_len(w: u64) -> u64 {
+        (w >> 1) & LARGEST_RUNNING_COUNT
+    }
+
+    #[inline]
+    pub(super) fn literal_words(w: u64) -> u64 {
+        w >> (1 + RUNNING_BITS)
+    }
+
+    #[inline]
+    pub(super) fn set_run_bit(w: &mut u64, bit: bool) {
+        *w = (*w & !1) | u64::from(bit);
+    }
+
+    #[inline]
+    pub(super) fn set_running_len(w: &mut u64, len: u64) {
+        *w = (*w & !(LARGEST_RUNNING_COUNT << 1)) | (len << 1);
+    }
+
+    #[inline]
+    pub(super) fn set_literal_words(w: &mut u64, len: u64) {
+        *w = (*w & ((1 << (1 + RUNNING_BITS)) - 1)) | (len << (1 + RUNNING_BITS));
+    }
+}
+
 mod access {
-    use super::Vec;
+    use super::{rlw, Vec};
 
     impl Vec {
         /// Call `f(index)` for each bit that is true, given the index of the bit that identifies it uniquely within the bit array.
@@ -62,7 +265,7 @@ mod access {
             let mut index = 0usize;
             let mut iter = self.bits.iter();
             while let Some(word) = iter.next() {
-                if rlw_runbit_is_set(word) {
+                if rlw::run_bit(*word) {
                     let len = rlw_running_len_bits(word);
                     for _ in 0..len {
                         f(index)?;
@@ -72,7 +275,7 @@ mod access {
                     index += usize::try_from(rlw_running_len_bits(word)).ok()?;
                 }
 
-                for _ in 0..rlw_literal_words(word) {
+                for _ in 0..rlw::literal_words(*word) {
                     let word = iter
                         .next()
                         .expect("BUG: ran out of words while going through uncompressed portion");
@@ -95,31 +298,12 @@ mod access {
 
     #[inline]
     fn rlw_running_len_bits(w: &u64) -> u64 {
-        rlw_running_len(w) * 64
+        rlw::running_len(*w) * 64
     }
-
-    #[inline]
-    fn rlw_running_len(w: &u64) -> u64 {
-        (w >> 1) & RLW_LARGEST_RUNNING_COUNT
-    }
-
-    #[inline]
-    fn rlw_literal_words(w: &u64) -> u64 {
-        w >> (1 + RLW_RUNNING_BITS)
-    }
-
-    #[inline]
-    fn rlw_runbit_is_set(w: &u64) -> bool {
-        w & 1 == 1
-    }
-
-    const RLW_RUNNING_BITS: u64 = 4 * 8;
-    const RLW_LARGEST_RUNNING_COUNT: u64 = (1 << RLW_RUNNING_BITS) - 1;
 }
 
 /// A growable collection of u64 that are seen as stream of individual bits.
-#[allow(dead_code)]
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Vec {
     num_bits: u32,
     bits: std::vec::Vec<u64>,
@@ -0,0 +1,107 @@
+mod ewah {
+    use gix_bitmap::ewah;
+
+    fn set_bits(bitmap: &ewah::Vec) -> Vec<usize> {
+        let mut out = Vec::new();
+        bitmap.for_each_set_bit(|index| {
+            out.push(index);
+            Some(())
+        });
+        out
+    }
+
+    fn round_trip(bitmap: &ewah::Vec) -> Vec<u8> {
+        let mut buf = Vec::new();
+        bitmap.write_to(&mut buf).expect("writing to memory works");
+        buf.extend_from_slice(b"trailer");
+        let (decoded, rest) = ewah::decode(&buf).expect("valid");
+        assert_eq!(&decoded, bitmap, "decoding yields the same bitmap");
+        assert_eq!(rest, b"trailer", "the encoded bitmap is consumed exactly");
+        buf.truncate(buf.len() - b"trailer".len());
+        buf
+    }
+
+    #[test]
+    fn empty() {
+        let bitmap = ewah::Vec::new();
+        assert_eq!(bitmap.num_bits(), 0);
+        assert!(set_bits(&bitmap).is_empty());
+        assert_eq!(
+            round_trip(&bitmap),
+            [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            "no bits, one run-length word which is all zero, pointing at itself"
+        );
+    }
+
+    #[test]
+    fn single_bit_is_a_literal_word() {
+        let bitmap = ewah::Vec::from_set_bits(Some(0));
+        assert_eq!(bitmap.num_bits(), 1);
+        assert_eq!(
+            round_trip(&bitmap),
+            [
+                0, 0, 0, 1, // num bits
+                0, 0, 0, 2, // num words
+                0, 0, 0, 2, 0, 0, 0, 0, // rlw with one literal word
+                0, 0, 0, 0, 0, 0, 0, 1, // literal word
+                0, 0, 0, 0, // rlw position
+            ]
+        );
+    }
+
+    #[test]
+    fn set_bits_round_trip_in_various_patterns() {
+        for indices in [
+            vec![0, 1, 2, 63, 64, 127],
+            vec![5, 100_000, 100_001, 1_000_000],
+            (0..64 * 3).collect(),
+            (10..64 * 5 + 3).collect(),
+            (0..10_000).step_by(3).collect(),
+            (0..64 * 4).chain(64 * 40..64 * 41).chain(Some(64 * 100 + 1)).collect(),
+        ] {
+            let bitmap = ewah::Vec::from_set_bits(indices.iter().copied());
+            assert_eq!(bitmap.num_bits(), indices.last().expect("non-empty") + 1);
+            assert_eq!(set_bits(&bitmap), indices);
+            round_trip(&bitmap);
+        }
+    }
+
+    #[test]
+    fn runs_are_compressed() {
+        let bitmap = ewah::Vec::from_set_bits((0..64 * 1000).chain(Some(64 * 100_000)));
+        let encoded = round_trip(&bitmap);
+        assert_eq!(
+            encoded.len(),
+            4 + 4 + 3 * 8 + 4,
+            "a run of ones, a run of zeroes and a literal"
+        );
+    }
+
+    #[test]
+    fn words_and_runs() {
+        let mut bitmap = ewah::Vec::new();
+        bitmap.push_run(false, 2);
+        bitmap.push_word(0b101);
+        bitmap.push_word(u64::MAX);
+        bitmap.push_run(true, 1);
+        bitmap.push_word(0);
+        bitmap.set(64 * 6 + 1);
+        assert_eq!(bitmap.num_bits(), 64 * 6 + 2);
+        assert_eq!(
+            set_bits(&bitmap),
+            [128, 130]
+                .into_iter()
+                .chain(64 * 3..64 * 5)
+                .chain(Some(64 * 6 + 1))
+                .collect::<Vec<_>>()
+        );
+        round_trip(&bitmap);
+    }
+
+    #[test]
+    #[should_panic]
+    fn bits_must_be_set_in_increasing_order() {
+        let mut bitmap = ewah::Vec::from_set_bits(Some(10));
+        bitmap.set(10);
+    }
+}
@@ -16,6 +16,8 @@ pub type Offset = u64;
 pub struct Index {
     /// If true, we use `chunks` in a way that facilitates writing them.
     will_write: bool,
+    /// The alignment in bytes of the offset of each chunk when writing.
+    alignment: u64,
     /// Validated chunks as defined by their index entries.
     ///
     /// Note that this list cannot be empty.
@@ -8,17 +8,20 @@ mod write_chunk {
     /// A [`Write`][std::io::Write] implementation that validates chunk sizes while allowing the user to know
     /// which chunk is to be written next.
     pub struct Chunk<W> {
-        chunks_to_write: VecDeque<index::Entry>,
+        /// The chunks to write along with the amount of padding bytes to write before each of them.
+        chunks_to_write: VecDeque<(index::Entry, u64)>,
         inner: W,
         next_chunk: Option<index::Entry>,
         written_bytes: usize,
+        /// The padding to write before the next byte of chunk data.
+        pending_padding: u64,
     }
 
     impl<W> Chunk<W>
     where
         W: std::io::Write,
     {
-        pub(crate) fn new(out: W, chunks: VecDeque<index::Entry>) -> Chunk<W>
+        pub(crate) fn new(out: W, chunks: VecDeque<(index::Entry, u64)>) -> Chunk<W>
         where
             W: std::io::Write,
         {
@@ -27,6 +30,7 @@ mod write_chunk {
                 inner: out,
                 next_chunk: None,
                 written_bytes: 0,
+                pending_padding: 0,
             }
         }
     }
@@ -36,22 +40,34 @@ mod write_chunk {
         W: std::io::Write,
     {
         fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.write_pending_padding()?;
             let written = self.inner.write(buf)?;
             self.written_bytes += written;
             Ok(written)
         }
 
         fn flush(&mut self) -> std::io::Result<()> {
+            self.write_pending_padding()?;
             self.inner.flush()
         }
     }
 
-    impl<W> Chunk<W> {
+    impl<W> Chunk<W>
+    where
+        W: std::io::Write,
+    {
         /// Return the inner writer - should only be called once there is no more chunk to write.
+        ///
+        /// Call [`flush()`](std::io::Write::flush()) beforehand if the last chunk may be empty and needs padding.
         pub fn into_inner(self) -> W {
             self.inner
         }
+
         /// Return the next chunk-id to write, if there is one.
+        ///
+        /// # Panics
+        ///
+        /// If the previous chunk wasn't written with exactly the planned amount of bytes.
         pub fn next_chunk(&mut self) -> Option<crate::Id> {
             if let Some(entry) = self.next_chunk.take() {
                 assert_eq!(
@@ -64,9 +80,47 @@ mod write_chunk {
                 )
             }
             self.written_bytes = 0;
-            self.next_chunk = self.chunks_to_write.pop_front();
+            let (next_chunk, padding) = self.chunks_to_write.pop_front()?;
+            self.pending_padding += padding;
+            self.next_chunk = Some(next_chunk);
             self.next_chunk.as_ref().map(|e| e.kind)
         }
+
+        fn write_pending_padding(&mut self) -> std::io::Result<()> {
+            const ZEROES: [u8; 8] = [0; 8];
+            while self.pending_padding != 0 {
+                let len = self.pending_padding.min(ZEROES.len() as u64) as usize;
+                self.inner.write_all(&ZEROES[..len])?;
+                self.pending_padding -= len as u64;
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "checksum")]
+    impl<W> Chunk<gix_features::hash::Write<W>>
+    where
+        W: std::io::Write,
+    {
+        /// Finish the file after all chunks were written by appending the checksum over everything written
+        /// to the hashing writer, including the header written before the table of contents.
+        ///
+        /// Return the checksum along with the writer it was written to.
+        ///
+        /// # Panics
+        ///
+        /// If not all planned chunks were written.
+        pub fn write_trailing_checksum(mut self) -> std::io::Result<(gix_hash::ObjectId, W)> {
+            assert!(
+                self.next_chunk().is_none(),
+                "BUG: all planned chunks must be written before finishing the file"
+            );
+            self.write_pending_padding()?;
+            let gix_features::hash::Write { hash, mut inner } = self.inner;
+            let checksum: gix_hash::ObjectId = hash.digest().into();
+            inner.write_all(checksum.as_slice())?;
+            Ok((checksum, inner))
+        }
     }
 }
 pub use write_chunk::Chunk;
@@ -78,9 +132,21 @@ impl Index {
     pub fn for_writing() -> Self {
         Index {
             will_write: true,
+            alignment: 1,
             chunks: Vec::new(),
         }
     }
+
+    /// Let each chunk start at an offset in the file that is a multiple of `alignment` bytes by padding
+    /// the preceding data with zeroes.
+    ///
+    /// Note that readers see the padding as part of the preceding chunk.
+    pub fn with_alignment(mut self, alignment: u64) -> Self {
+        assert!(self.will_write, "BUG: create the index with `for_writing()`");
+        assert_ne!(alignment, 0, "BUG: alignment must be at least 1");
+        self.alignment = alignment;
+        self
+    }
     /// Plan to write a new chunk as part of the index when [`into_write()`][Index::into_write()] is called.
     pub fn plan_chunk(&mut self, chunk: crate::Id, exact_size_on_disk: u64) {
         assert!(self.will_write, "BUG: create the index with `for_writing()`");
@@ -95,12 +161,23 @@ impl Index {
         })
     }
 
-    /// Return the total size of all planned chunks thus far.
+    /// Return the total size of all planned chunks thus far, without padding for alignment.
     pub fn planned_storage_size(&self) -> u64 {
         assert!(self.will_write, "BUG: create the index with `for_writing()`");
         self.chunks.iter().map(|e| e.offset.end).sum()
     }
 
+    /// Return the total size of all planned chunks thus far if the table of contents starts at `current_offset`,
+    /// including padding for alignment.
+    pub fn planned_storage_size_with_padding(&self, current_offset: usize) -> u64 {
+        assert!(self.will_write, "BUG: create the index with `for_writing()`");
+        let start = (current_offset + Self::size_for_entries(self.num_chunks())) as u64;
+        let end = self.chunks.iter().fold(start, |offset, e| {
+            offset + padding_for(offset, self.alignment) + e.offset.end
+        });
+        end - start
+    }
+
     /// Return the amount of chunks we currently know.
     pub fn num_chunks(&self) -> usize {
         self.chunks.len()
@@ -120,17 +197,26 @@ impl Index {
         // First chunk starts past the table of contents
         let mut current_offset = (current_offset + Self::size_for_entries(self.num_chunks())) as u64;
 
-        for entry in &self.chunks {
+        let mut chunks = std::collections::VecDeque::with_capacity(self.chunks.len());
+        for entry in self.chunks {
+            let padding = padding_for(current_offset, self.alignment);
+            current_offset += padding;
             out.write_all(&entry.kind)?;
             out.write_all(&current_offset.to_be_bytes())?;
 
             current_offset += entry.offset.end;
+            chunks.push_back((entry, padding));
         }
 
         // sentinel to mark end of chunks
         out.write_all(&0u32.to_be_bytes())?;
         out.write_all(&current_offset.to_be_bytes())?;
 
-        Ok(Chunk::new(out, self.chunks.into()))
+        Ok(Chunk::new(out, chunks))
     }
 }
+
+/// Return the amount of bytes needed to move `offset` to the next multiple of `alignment`.
+fn padding_for(offset: u64, alignment: u64) -> u64 {
+    (alignment - offset % alignment) % alignment
+}
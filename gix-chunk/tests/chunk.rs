@@ -0,0 +1,66 @@
+mod write {
+    use std::io::Write;
+
+    use gix_chunk::file::Index;
+
+    const HEADER: &[u8] = b"HEAD!";
+
+    fn write_file(alignment: u64) -> Result<(Vec<u8>, gix_hash::ObjectId), Box<dyn std::error::Error>> {
+        let chunks: [(gix_chunk::Id, &[u8]); 3] = [(*b"AAAA", b"abc"), (*b"TINY", b"x"), (*b"BBBB", b"defgh")];
+        let mut index = Index::for_writing().with_alignment(alignment);
+        for (id, data) in chunks {
+            index.plan_chunk(id, data.len() as u64);
+        }
+        let planned_size = index.planned_storage_size_with_padding(HEADER.len());
+
+        let mut out = gix_features::hash::Write::new(Vec::new(), gix_hash::Kind::Sha1);
+        out.write_all(HEADER)?;
+        let mut chunk_write = index.into_write(out, HEADER.len())?;
+        while let Some(id) = chunk_write.next_chunk() {
+            let data = chunks.iter().find_map(|(cid, data)| (*cid == id).then_some(*data));
+            chunk_write.write_all(data.expect("planned chunk"))?;
+        }
+        let (checksum, buf) = chunk_write.write_trailing_checksum()?;
+        assert_eq!(
+            buf.len() as u64,
+            HEADER.len() as u64 + Index::size_for_entries(chunks.len()) as u64 + planned_size + 20,
+            "the planned size accounts for padding"
+        );
+        Ok((buf, checksum))
+    }
+
+    #[test]
+    fn aligned_chunks_can_be_read_back_and_are_checksummed() -> Result<(), Box<dyn std::error::Error>> {
+        for alignment in [1, 4, 8] {
+            let (buf, checksum) = write_file(alignment)?;
+            let (data, trailer) = buf.split_at(buf.len() - 20);
+            assert_eq!(trailer, checksum.as_slice());
+            let mut hasher = gix_features::hash::hasher(gix_hash::Kind::Sha1);
+            hasher.update(data);
+            assert_eq!(gix_hash::ObjectId::from(hasher.digest()), checksum);
+
+            let index = Index::from_bytes(data, HEADER.len(), 3)?;
+            for (id, expected) in [(*b"AAAA", &b"abc"[..]), (*b"TINY", b"x"), (*b"BBBB", b"defgh")] {
+                let range = index.offset_by_id(id)?;
+                assert_eq!(range.start % alignment, 0, "chunks start aligned");
+                let chunk = index.data_by_id(data, id)?;
+                assert_eq!(&chunk[..expected.len()], expected);
+                assert!(
+                    chunk[expected.len()..].iter().all(|b| *b == 0),
+                    "padding is zeroed and seen as part of the preceding chunk"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic]
+    fn all_chunks_must_be_written_before_the_checksum() {
+        let mut index = Index::for_writing();
+        index.plan_chunk(*b"AAAA", 1);
+        let out = gix_features::hash::Write::new(Vec::new(), gix_hash::Kind::Sha1);
+        let chunk_write = index.into_write(out, 0).expect("in-memory");
+        chunk_write.write_trailing_checksum().ok();
+    }
+}
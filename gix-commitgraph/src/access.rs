@@ -26,6 +26,12 @@ impl Graph {
         r.file.id_at(r.pos)
     }
 
+    /// Return the files this graph consists of, starting with the base graph, in the order of the chain of split
+    /// commit-graph files.
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
+
     /// Iterate over commits in unsorted order.
     pub fn iter_commits(&self) -> impl Iterator<Item = Commit<'_>> {
         self.files.iter().flat_map(File::iter_commits)
@@ -0,0 +1,255 @@
+//! Write commit-graph files.
+use std::io::Write;
+
+use crate::{
+    file::{
+        BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
+        EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN, HEADER_LEN, LAST_EXTENDED_EDGE_MASK, NO_PARENT,
+        OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, Graph, Position, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+/// The error returned by [`File::write_to()`] and [`File::write_split_to()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Commit {id} has parent {parent} which isn't part of the commits to write or the base graph")]
+    MissingParent {
+        id: gix_hash::ObjectId,
+        parent: gix_hash::ObjectId,
+    },
+    #[error("Cannot write {0} commits into a single commit-graph file")]
+    TooManyCommits(usize),
+    #[error("A commit-graph file can't be based on {0} commit-graph files")]
+    TooManyBaseGraphs(usize),
+}
+
+/// A commit as passed to [`File::write_to()`] and [`File::write_split_to()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The id of the commit's root tree.
+    pub tree: gix_hash::ObjectId,
+    /// The ids of all parents of the commit, in order.
+    pub parents: Vec<gix_hash::ObjectId>,
+    /// The committer time in seconds since the unix epoch. Only the lower 34 bits are stored.
+    pub commit_time: u64,
+}
+
+/// The result of [`File::write_to()`] and [`File::write_split_to()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of commits written, after removing duplicates.
+    pub num_commits: u32,
+    /// The checksum over all written bytes, which is also written as trailer.
+    pub checksum: gix_hash::ObjectId,
+}
+
+/// Writing
+impl File {
+    /// Write all `commits` as a single, non-split commit-graph file of `object_hash` to `out`, and return information about it.
+    ///
+    /// All parents of `commits` must be part of `commits` as well, and commits with the same id are only written once.
+    /// Generation numbers are computed on the fly.
+    pub fn write_to(commits: Vec<Commit>, out: &mut dyn Write, object_hash: gix_hash::Kind) -> Result<Outcome, Error> {
+        write(commits, None, out, object_hash)
+    }
+
+    /// Write all `commits` which aren't yet contained in `base` as a commit-graph file of `object_hash` to `out`,
+    /// which is layered on top of all files of `base` in a chain of split commit-graph files, and return information about it.
+    ///
+    /// All parents of `commits` must be part of `commits` or `base`, and commits with the same id are only written once.
+    /// The caller is responsible for storing the file as `graph-<checksum>.graph` and adding it to the `commit-graph-chain` file.
+    pub fn write_split_to(
+        commits: Vec<Commit>,
+        base: &Graph,
+        out: &mut dyn Write,
+        object_hash: gix_hash::Kind,
+    ) -> Result<Outcome, Error> {
+        write(commits, Some(base), out, object_hash)
+    }
+}
+
+fn write(
+    mut commits: Vec<Commit>,
+    base: Option<&Graph>,
+    out: &mut dyn Write,
+    object_hash: gix_hash::Kind,
+) -> Result<Outcome, Error> {
+    if let Some(base) = base {
+        commits.retain(|commit| base.lookup(commit.id).is_none());
+    }
+    commits.sort_by_key(|commit| commit.id);
+    commits.dedup_by(|a, b| a.id == b.id);
+    let base_commits = base.map_or(0, Graph::num_commits);
+    let num_commits: u32 = commits
+        .len()
+        .try_into()
+        .ok()
+        .filter(|num: &u32| {
+            num.checked_add(base_commits)
+                .map_or(false, |total| total <= MAX_COMMITS)
+        })
+        .ok_or(Error::TooManyCommits(commits.len()))?;
+    let base_files = base.map_or(&[][..], |base| base.files.as_slice());
+    let num_base_graphs: u8 = base_files
+        .len()
+        .try_into()
+        .map_err(|_| Error::TooManyBaseGraphs(base_files.len()))?;
+
+    // Parents are stored by their position in the whole graph, which places all commits of `base` first.
+    let position = |id: &gix_hash::ObjectId| {
+        commits
+            .binary_search_by(|c| c.id.cmp(id))
+            .ok()
+            .map(|pos| base_commits + pos as u32)
+            .or_else(|| base.and_then(|base| base.lookup(id)).map(|pos| pos.0))
+    };
+    let mut parents = Vec::with_capacity(commits.len());
+    for commit in &commits {
+        parents.push(
+            commit
+                .parents
+                .iter()
+                .map(|parent| {
+                    position(parent).ok_or(Error::MissingParent {
+                        id: commit.id,
+                        parent: *parent,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+    }
+    let generations = generations(&parents, base);
+    let num_extra_edges: usize = parents.iter().filter(|p| p.len() > 2).map(|p| p.len() - 1).sum();
+
+    let hash_len = object_hash.len_in_bytes();
+    let mut chunks = gix_chunk::file::Index::for_writing();
+    chunks.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+    chunks.plan_chunk(OID_LOOKUP_CHUNK_ID, (commits.len() * hash_len) as u64);
+    chunks.plan_chunk(
+        COMMIT_DATA_CHUNK_ID,
+        (commits.len() * (hash_len + COMMIT_DATA_ENTRY_SIZE_SANS_HASH)) as u64,
+    );
+    if num_extra_edges != 0 {
+        chunks.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (num_extra_edges * 4) as u64);
+    }
+    if num_base_graphs != 0 {
+        chunks.plan_chunk(BASE_GRAPHS_LIST_CHUNK_ID, (base_files.len() * hash_len) as u64);
+    }
+
+    let mut out = gix_features::hash::Write::new(out, object_hash);
+    out.write_all(SIGNATURE)?;
+    out.write_all(&[1 /* version */, object_hash as u8])?;
+    out.write_all(&[
+        chunks.num_chunks().try_into().expect("BUG: wrote more than 256 chunks"),
+        num_base_graphs,
+    ])?;
+
+    let mut chunk_write = chunks.into_write(out, HEADER_LEN)?;
+    while let Some(chunk) = chunk_write.next_chunk() {
+        match chunk {
+            OID_FAN_CHUNK_ID => {
+                let mut count = 0u32;
+                let mut commits = commits.iter().peekable();
+                for first_byte in 0..FAN_LEN {
+                    while commits
+                        .next_if(|commit| usize::from(commit.id.first_byte()) == first_byte)
+                        .is_some()
+                    {
+                        count += 1;
+                    }
+                    chunk_write.write_all(&count.to_be_bytes())?;
+                }
+            }
+            OID_LOOKUP_CHUNK_ID => {
+                for commit in &commits {
+                    chunk_write.write_all(commit.id.as_slice())?;
+                }
+            }
+            COMMIT_DATA_CHUNK_ID => {
+                let mut extra_edge_index = 0u32;
+                for ((commit, parents), generation) in commits.iter().zip(&parents).zip(&generations) {
+                    chunk_write.write_all(commit.tree.as_slice())?;
+                    let parent1 = parents.first().copied().unwrap_or(NO_PARENT);
+                    let parent2 = match parents.len() {
+                        0 | 1 => NO_PARENT,
+                        2 => parents[1],
+                        num_parents => {
+                            let edge = EXTENDED_EDGES_MASK | extra_edge_index;
+                            extra_edge_index += num_parents as u32 - 1;
+                            edge
+                        }
+                    };
+                    chunk_write.write_all(&parent1.to_be_bytes())?;
+                    chunk_write.write_all(&parent2.to_be_bytes())?;
+                    let time = commit.commit_time & 0x0003_ffff_ffff;
+                    chunk_write.write_all(&((generation << 2) | (time >> 32) as u32).to_be_bytes())?;
+                    chunk_write.write_all(&(time as u32).to_be_bytes())?;
+                }
+            }
+            EXTENDED_EDGES_LIST_CHUNK_ID => {
+                for parents in parents.iter().filter(|p| p.len() > 2) {
+                    let (last, rest) = parents[1..].split_last().expect("more than two parents");
+                    for pos in rest {
+                        chunk_write.write_all(&pos.to_be_bytes())?;
+                    }
+                    chunk_write.write_all(&(LAST_EXTENDED_EDGE_MASK | *last).to_be_bytes())?;
+                }
+            }
+            BASE_GRAPHS_LIST_CHUNK_ID => {
+                for file in base_files {
+                    chunk_write.write_all(file.checksum().as_bytes())?;
+                }
+            }
+            unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+        }
+    }
+
+    let (checksum, _out) = chunk_write.write_trailing_checksum()?;
+    Ok(Outcome { num_commits, checksum })
+}
+
+/// Compute the generation of each commit from the graph positions of their `parents`, capped at [`GENERATION_NUMBER_MAX`].
+/// The generations of parents in `base` are taken from there.
+///
+/// Parents that are already being visited are ignored, so made-up cycles can't prevent this from terminating.
+fn generations(parents: &[Vec<u32>], base: Option<&Graph>) -> Vec<u32> {
+    let base_commits = base.map_or(0, Graph::num_commits);
+    let local = |parent: u32| parent.checked_sub(base_commits).map(|pos| pos as usize);
+    let mut generations = vec![0u32; parents.len()];
+    let mut visiting = vec![false; parents.len()];
+    let mut stack = Vec::new();
+    for start in 0..parents.len() {
+        if generations[start] != 0 {
+            continue;
+        }
+        stack.push(start);
+        while let Some(&pos) = stack.last() {
+            visiting[pos] = true;
+            let len_before = stack.len();
+            stack.extend(
+                parents[pos]
+                    .iter()
+                    .filter_map(|parent| local(*parent))
+                    .filter(|parent| generations[*parent] == 0 && !visiting[*parent]),
+            );
+            if stack.len() == len_before {
+                stack.pop();
+                generations[pos] = parents[pos]
+                    .iter()
+                    .map(|parent| match local(*parent) {
+                        Some(pos) => generations[pos],
+                        None => base.map_or(0, |base| base.commit_at(Position(*parent)).generation()),
+                    })
+                    .max()
+                    .map_or(1, |generation| (generation + 1).min(GENERATION_NUMBER_MAX));
+            }
+        }
+    }
+    generations
+}
@@ -0,0 +1,150 @@
+use gix_commitgraph::{file::write, Graph};
+
+use crate::{check_common, graph_and_expected};
+
+fn commits_of(graph: &Graph) -> Vec<write::Commit> {
+    graph
+        .iter_commits()
+        .map(|commit| write::Commit {
+            id: commit.id().to_owned(),
+            tree: commit.root_tree_id().to_owned(),
+            parents: commit
+                .iter_parents()
+                .map(|pos| graph.id_at(pos.expect("valid parent")).to_owned())
+                .collect(),
+            commit_time: commit.committer_timestamp(),
+        })
+        .collect()
+}
+
+#[test]
+fn round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    for (script, refs) in [
+        ("single_parent.sh", &["parent", "child"][..]),
+        ("two_parents.sh", &["parent1", "parent2", "child"]),
+        (
+            "octopus_merges.sh",
+            &[
+                "root",
+                "parent1",
+                "parent2",
+                "parent3",
+                "parent4",
+                "three_parents",
+                "four_parents",
+            ],
+        ),
+    ] {
+        let (expected_graph, expected) = graph_and_expected(script, refs);
+        let mut commits = commits_of(&expected_graph);
+        commits.reverse();
+        commits.push(commits[0].clone());
+
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let path = tmp.path().join("commit-graph");
+        let mut out = Vec::new();
+        let outcome = gix_commitgraph::File::write_to(commits, &mut out, gix_hash::Kind::Sha1)?;
+        assert_eq!(
+            outcome.num_commits,
+            expected_graph.num_commits(),
+            "duplicates are removed"
+        );
+        std::fs::write(&path, out)?;
+
+        let file = gix_commitgraph::File::at(&path)?;
+        assert_eq!(file.verify_checksum().expect("valid"), outcome.checksum);
+        let actual_graph = Graph::at(&path)?;
+        check_common(&actual_graph, &expected);
+        for info in expected.values() {
+            assert_eq!(
+                actual_graph.commit_at(info.pos()).generation(),
+                expected_graph.commit_at(info.pos()).generation(),
+                "{script}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn parents_must_be_written_as_well() {
+    let id = |byte: u8| gix_hash::ObjectId::from([byte; 20]);
+    let err = gix_commitgraph::File::write_to(
+        vec![write::Commit {
+            id: id(1),
+            tree: id(2),
+            parents: vec![id(3)],
+            commit_time: 0,
+        }],
+        &mut Vec::new(),
+        gix_hash::Kind::Sha1,
+    )
+    .unwrap_err();
+    assert!(matches!(err, write::Error::MissingParent { .. }));
+}
+
+#[test]
+fn split_layer_on_top_of_base() -> Result<(), Box<dyn std::error::Error>> {
+    let (expected_graph, _) = graph_and_expected(
+        "octopus_merges.sh",
+        &[
+            "root",
+            "parent1",
+            "parent2",
+            "parent3",
+            "parent4",
+            "three_parents",
+            "four_parents",
+        ],
+    );
+    let commits = commits_of(&expected_graph);
+    let roots: Vec<_> = commits.iter().filter(|c| c.parents.is_empty()).cloned().collect();
+
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let base_path = tmp.path().join("commit-graph");
+    let mut out = Vec::new();
+    gix_commitgraph::File::write_to(roots.clone(), &mut out, gix_hash::Kind::Sha1)?;
+    std::fs::write(&base_path, out)?;
+
+    let mut out = Vec::new();
+    let outcome = gix_commitgraph::File::write_split_to(
+        commits.clone(),
+        &Graph::at(&base_path)?,
+        &mut out,
+        gix_hash::Kind::Sha1,
+    )?;
+    assert_eq!(
+        outcome.num_commits as usize,
+        commits.len() - roots.len(),
+        "commits of the base graph aren't written again"
+    );
+    let layer_path = tmp.path().join(format!("graph-{}.graph", outcome.checksum));
+    std::fs::write(&layer_path, out)?;
+
+    let base = gix_commitgraph::File::at(&base_path)?;
+    let layer = gix_commitgraph::File::at(&layer_path)?;
+    assert_eq!(layer.base_graph_count(), 1);
+    assert_eq!(layer.iter_base_graph_ids().collect::<Vec<_>>(), [base.checksum()]);
+    assert_eq!(layer.verify_checksum().expect("valid"), outcome.checksum);
+
+    let actual_graph = Graph::new(vec![base, layer])?;
+    actual_graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    let parent_ids = |graph: &Graph, commit: gix_commitgraph::file::Commit<'_>| -> Vec<gix_hash::ObjectId> {
+        commit
+            .iter_parents()
+            .map(|pos| graph.id_at(pos.expect("valid parent")).to_owned())
+            .collect()
+    };
+    for expected in expected_graph.iter_commits() {
+        let actual = actual_graph
+            .commit_by_id(expected.id())
+            .expect("all commits are present");
+        assert_eq!(actual.generation(), expected.generation());
+        assert_eq!(
+            parent_ids(&actual_graph, actual),
+            parent_ids(&expected_graph, expected),
+            "parents in the base graph are referred to by their position in the whole graph"
+        );
+    }
+    Ok(())
+}
@@ -25,7 +25,12 @@ pub(crate) mod function {
         SecondsSinceUnixEpoch, Time,
     };
 
-    #[allow(missing_docs)]
+    /// Parse `input` as date like `git` would, with `now` being the reference for relative dates.
+    ///
+    /// Besides well-known formats like ISO8601, RFC2822, seconds since UNIX epoch (optionally prefixed with `@`) and
+    /// `<seconds> <+|-hhmm>`, which keep their timezone, approximate dates like `2 weeks ago`, `yesterday`,
+    /// `noon last tuesday` or `June 7, 6am` are understood as well, similar to `approxidate` in `git`.
+    /// These are interpreted in UTC and fail with [`Error::MissingCurrentTime`] if `now` isn't set.
     pub fn parse(input: &str, now: Option<SystemTime>) -> Result<Time, Error> {
         // TODO: actual implementation, this is just to not constantly fail
         if input == "1979-02-26 18:30:00" {
@@ -51,8 +56,15 @@ pub(crate) mod function {
         } else if let Some(val) = parse_raw(input) {
             // Format::Raw
             val
-        } else if let Some(time) = relative::parse(input, now).transpose()? {
-            Time::new(time.unix_timestamp(), time.offset().whole_seconds())
+        } else if let Some(val) = input.trim_start().strip_prefix('@').and_then(|input| {
+            SecondsSinceUnixEpoch::from_str(input.trim_end())
+                .ok()
+                .map(|seconds| Time::new(seconds, 0))
+                .or_else(|| parse_raw(input))
+        }) {
+            val
+        } else if let Some(seconds) = relative::parse(input, now).transpose()? {
+            Time::new(seconds, 0)
         } else {
             return Err(Error::InvalidDateString { input: input.into() });
         })
@@ -85,59 +97,4 @@ pub(crate) mod function {
     }
 }
 
-mod relative {
-    use std::{str::FromStr, time::SystemTime};
-
-    use time::{Duration, OffsetDateTime};
-
-    use crate::parse::Error;
-
-    fn parse_inner(input: &str) -> Option<Duration> {
-        let mut split = input.split_whitespace();
-        let multiplier = i64::from_str(split.next()?).ok()?;
-        let period = split.next()?;
-        if split.next()? != "ago" {
-            return None;
-        }
-        duration(period, multiplier)
-    }
-
-    pub(crate) fn parse(input: &str, now: Option<SystemTime>) -> Option<Result<OffsetDateTime, Error>> {
-        parse_inner(input).map(|offset| {
-            let offset = std::time::Duration::from_secs(offset.whole_seconds().try_into()?);
-            now.ok_or(Error::MissingCurrentTime).and_then(|now| {
-                std::panic::catch_unwind(|| {
-                    now.checked_sub(offset)
-                        .expect("BUG: values can't be large enough to cause underflow")
-                        .into()
-                })
-                .map_err(|_| Error::RelativeTimeConversion)
-            })
-        })
-    }
-
-    fn duration(period: &str, multiplier: i64) -> Option<Duration> {
-        let period = period.strip_suffix('s').unwrap_or(period);
-        let seconds: i64 = match period {
-            "second" => 1,
-            "minute" => 60,
-            "hour" => 60 * 60,
-            "day" => 24 * 60 * 60,
-            "week" => 7 * 24 * 60 * 60,
-            // TODO months & years? YES
-            // Ignore values you don't know, assume seconds then (so does git)
-            _ => return None,
-        };
-        seconds.checked_mul(multiplier).map(Duration::seconds)
-    }
-
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-
-        #[test]
-        fn two_weeks_ago() {
-            assert_eq!(parse_inner("2 weeks ago"), Some(Duration::weeks(2)));
-        }
-    }
-}
+mod relative;
@@ -0,0 +1,461 @@
+//! An implementation of `git`'s `approxidate`, which understands relative dates like `2 weeks ago` or `noon yesterday`,
+//! along with approximate absolute dates like `June 7, 6am`.
+//!
+//! ### Deviation
+//!
+//! * All calculations are performed in UTC instead of the local timezone.
+//! * Input with non-ASCII characters or negative numbers is rejected instead of silently ignoring the offending characters.
+use std::time::SystemTime;
+
+use time::{Date, Duration, Month, OffsetDateTime};
+
+use crate::{parse::Error, SecondsSinceUnixEpoch};
+
+const DAY: i64 = 24 * 60 * 60;
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sundays",
+    "Mondays",
+    "Tuesdays",
+    "Wednesdays",
+    "Thursdays",
+    "Fridays",
+    "Saturdays",
+];
+const NUMBER_NAMES: [&str; 11] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+];
+const UNITS: [(&str, i64); 5] = [
+    ("seconds", 1),
+    ("minutes", 60),
+    ("hours", 60 * 60),
+    ("days", DAY),
+    ("weeks", 7 * DAY),
+];
+
+/// Parse `input` relative to `now` like `git` would, or return `None` if nothing in `input` could be understood.
+pub(crate) fn parse(input: &str, now: Option<SystemTime>) -> Option<Result<SecondsSinceUnixEpoch, Error>> {
+    let now_seconds = match now
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+        .duration_since(SystemTime::UNIX_EPOCH)
+    {
+        Ok(elapsed) => elapsed.as_secs() as SecondsSinceUnixEpoch,
+        Err(err) => -(err.duration().as_secs() as SecondsSinceUnixEpoch),
+    };
+    let res = (|| {
+        let mut state = State::new(now_seconds)?;
+        state.parse(input.as_bytes())?;
+        Ok(state
+            .touched
+            .then(|| state.tm.update(&state.now, 0))
+            .transpose()?
+            .map(|seconds| now.map(|_| seconds).ok_or(Error::MissingCurrentTime)))
+    })();
+    match res {
+        Ok(Some(res)) => Some(res),
+        Ok(None) => None,
+        Err(err) => Some(Err(err)),
+    }
+}
+
+/// A broken-down time similar to `struct tm`, with unset date fields.
+#[derive(Clone, Copy)]
+struct Tm {
+    year: Option<i64>,
+    /// Zero-based.
+    month: Option<i64>,
+    day: Option<i64>,
+    hour: i64,
+    minute: i64,
+    second: i64,
+    /// Zero-based, starting on Sunday.
+    weekday: i64,
+}
+
+impl Tm {
+    fn from_seconds(seconds: SecondsSinceUnixEpoch) -> Result<Self, Error> {
+        let time = OffsetDateTime::from_unix_timestamp(seconds).map_err(|_| Error::RelativeTimeConversion)?;
+        Ok(Tm {
+            year: Some(time.year().into()),
+            month: Some(i64::from(u8::from(time.month())) - 1),
+            day: Some(time.day().into()),
+            hour: time.hour().into(),
+            minute: time.minute().into(),
+            second: time.second().into(),
+            weekday: time.weekday().number_days_from_sunday().into(),
+        })
+    }
+
+    /// Like `mktime()`, days past the end of the month roll over into the next one.
+    fn to_seconds(self) -> Result<SecondsSinceUnixEpoch, Error> {
+        let (Some(year), Some(month), Some(day)) = (self.year, self.month, self.day) else {
+            unreachable!("BUG: date fields are set before conversion")
+        };
+        let month = Month::try_from(u8::try_from(month + 1)?).map_err(|_| Error::RelativeTimeConversion)?;
+        let date = Date::from_calendar_date(year.try_into()?, month, 1)
+            .ok()
+            .and_then(|date| date.checked_add(Duration::days(day - 1)))
+            .ok_or(Error::RelativeTimeConversion)?;
+        date.midnight()
+            .assume_utc()
+            .unix_timestamp()
+            .checked_add(self.hour * 60 * 60 + self.minute * 60 + self.second)
+            .ok_or(Error::RelativeTimeConversion)
+    }
+
+    /// Fill in unset date fields from `now` and go back `seconds` in time, returning the seconds since epoch after
+    /// updating all fields.
+    fn update(&mut self, now: &Tm, seconds: i64) -> Result<SecondsSinceUnixEpoch, Error> {
+        self.day = self.day.or(now.day);
+        self.month = self.month.or(now.month);
+        if self.year.is_none() {
+            self.year = now
+                .year
+                .map(|year| if self.month > now.month { year - 1 } else { year });
+        }
+        let seconds = self
+            .to_seconds()?
+            .checked_sub(seconds)
+            .ok_or(Error::RelativeTimeConversion)?;
+        *self = Tm::from_seconds(seconds)?;
+        Ok(seconds)
+    }
+
+    /// Set the date if it is valid, similar to `set_date()` in `git`. If `now` is set, the date is only changed if it
+    /// isn't more than 10 days in the future, and a missing year is taken from `now`.
+    fn set_date(&mut self, year: i64, month: i64, day: i64, now: Option<(&Tm, SecondsSinceUnixEpoch)>) -> bool {
+        if !(1..13).contains(&month) || !(1..32).contains(&day) {
+            return false;
+        }
+        let mut check = *self;
+        let tm = if now.is_some() { &mut check } else { &mut *self };
+        tm.month = Some(month - 1);
+        tm.day = Some(day);
+        tm.year = Some(match year {
+            -1 => match now {
+                Some((now, _)) => now.year.expect("set"),
+                None => return false,
+            },
+            1970..=2099 => year,
+            71..=99 => 1900 + year,
+            _ if year < 38 => 2000 + year,
+            _ => return false,
+        });
+        let Some((_, now_seconds)) = now else { return true };
+
+        // It doesn't make sense to specify dates way into the future.
+        if (1970..=2099).contains(&check.year.expect("set"))
+            && check
+                .to_seconds()
+                .map_or(false, |specified| now_seconds + 10 * DAY < specified)
+        {
+            return false;
+        }
+        self.month = check.month;
+        self.day = check.day;
+        if year != -1 {
+            self.year = check.year;
+        }
+        true
+    }
+}
+
+struct State {
+    tm: Tm,
+    now: Tm,
+    now_seconds: SecondsSinceUnixEpoch,
+    /// A number that was seen but not yet used, or 0.
+    number: i64,
+    /// If `true`, at least one token was understood.
+    touched: bool,
+}
+
+impl State {
+    fn new(now_seconds: SecondsSinceUnixEpoch) -> Result<Self, Error> {
+        let now = Tm::from_seconds(now_seconds)?;
+        Ok(State {
+            tm: Tm {
+                year: None,
+                month: None,
+                day: None,
+                ..now
+            },
+            now,
+            now_seconds,
+            number: 0,
+            touched: false,
+        })
+    }
+
+    fn parse(&mut self, input: &[u8]) -> Result<(), Error> {
+        let invalid = || Error::InvalidDateString {
+            input: String::from_utf8_lossy(input).into(),
+        };
+        let mut pos = 0;
+        while let Some(&byte) = input.get(pos) {
+            if byte.is_ascii_digit() {
+                let is_negative = pos.checked_sub(1).map_or(false, |prev| {
+                    input[prev] == b'-' && (prev == 0 || !input[prev - 1].is_ascii_alphanumeric())
+                });
+                if is_negative {
+                    return Err(invalid());
+                }
+                self.pending_number();
+                pos = self.digits(input, pos)?;
+                self.touched = true;
+            } else if byte.is_ascii_alphabetic() {
+                pos = self.word(input, pos)?;
+            } else if !byte.is_ascii() {
+                return Err(invalid());
+            } else {
+                pos += 1;
+            }
+        }
+        self.pending_number();
+        Ok(())
+    }
+
+    /// Use a number that wasn't consumed by anything else as day of the month, month or year, in that order.
+    fn pending_number(&mut self) {
+        let number = std::mem::take(&mut self.number);
+        if number == 0 {
+            return;
+        }
+        let tm = &mut self.tm;
+        if tm.day.is_none() && number < 32 {
+            tm.day = Some(number);
+        } else if tm.month.is_none() && number < 13 {
+            tm.month = Some(number - 1);
+        } else if tm.year.is_none() {
+            tm.year = match number {
+                1970..=2099 => Some(number),
+                70..=99 => Some(1900 + number),
+                _ if number < 38 => Some(2000 + number),
+                _ => None,
+            };
+        }
+    }
+
+    /// Parse the number at `start` and possibly the date or time it is part of, returning the position past it.
+    fn digits(&mut self, input: &[u8], start: usize) -> Result<usize, Error> {
+        let (number, end) = parse_number(input, start);
+        if let Some(&separator @ (b':' | b'.' | b'/' | b'-')) = input.get(end) {
+            if input.get(end + 1).map_or(false, u8::is_ascii_digit) {
+                if let Some(end) = self.multi_number(input, number, separator, end) {
+                    return Ok(end);
+                }
+            }
+        }
+        // Accept zero-padding only for small numbers ("Dec 02", never "Dec 0002")
+        if input[start] != b'0' || end - start <= 2 {
+            self.number = number;
+        }
+        Ok(end)
+    }
+
+    /// Handle `number` followed by `separator` and more digits at `end` as time or date, or return `None` if that's not
+    /// possible.
+    fn multi_number(&mut self, input: &[u8], number: i64, separator: u8, end: usize) -> Option<usize> {
+        let (number2, mut end) = parse_number(input, end + 1);
+        let mut number3 = -1;
+        if input.get(end) == Some(&separator) && input.get(end + 1).map_or(false, u8::is_ascii_digit) {
+            (number3, end) = parse_number(input, end + 1);
+        }
+
+        let tm = &mut self.tm;
+        if separator == b':' {
+            let number3 = number3.max(0);
+            if number < 25 && number2 < 60 && number3 <= 60 {
+                tm.hour = number;
+                tm.minute = number2;
+                tm.second = number3;
+                return Some(end);
+            }
+            return None;
+        }
+
+        let now = Some((&self.now, self.now_seconds));
+        let is_date = (number > 70 && (tm.set_date(number, number2, number3, None) || tm.set_date(number, number3, number2, None)))
+            // Giving precedence to mm/dd/yy[yy] only if the separator isn't '.', as dd.mm.yy[yy] is the norm in Europe.
+            || (separator != b'.' && tm.set_date(number3, number, number2, now))
+            || tm.set_date(number3, number2, number, now)
+            || (separator == b'.' && tm.set_date(number3, number, number2, now));
+        is_date.then_some(end)
+    }
+
+    /// Handle the word starting at `start` and return the position past it.
+    fn word(&mut self, input: &[u8], start: usize) -> Result<usize, Error> {
+        let word = &input[start..];
+        let end = start
+            + word
+                .iter()
+                .position(|byte| !byte.is_ascii_alphabetic())
+                .unwrap_or(word.len());
+
+        if let Some(month) = MONTH_NAMES.iter().position(|name| match_string(word, name) >= 3) {
+            self.tm.month = Some(month as i64);
+            self.touched = true;
+            return Ok(end);
+        }
+
+        if let Some(special) = ["yesterday", "noon", "midnight", "tea", "PM", "AM", "never", "now"]
+            .into_iter()
+            .find(|name| match_string(word, name) == name.len())
+        {
+            self.special(special)?;
+            self.touched = true;
+            return Ok(end);
+        }
+
+        // Without a number, units and weekdays have no meaning, so only numbers can be introduced.
+        if self.number == 0 {
+            if let Some(number) = NUMBER_NAMES
+                .iter()
+                .skip(1)
+                .position(|name| match_string(word, name) == name.len())
+            {
+                self.number = number as i64 + 1;
+                self.touched = true;
+            } else if match_string(word, "last") == 4 {
+                self.number = 1;
+                self.touched = true;
+            }
+            return Ok(end);
+        }
+
+        if let Some((_, seconds)) = UNITS
+            .iter()
+            .find(|(name, _)| match_string(word, name) >= name.len() - 1)
+        {
+            let seconds = seconds
+                .checked_mul(std::mem::take(&mut self.number))
+                .ok_or(Error::RelativeTimeConversion)?;
+            self.tm.update(&self.now, seconds)?;
+        } else if let Some(weekday) = WEEKDAY_NAMES.iter().position(|name| match_string(word, name) >= 3) {
+            let mut weeks = std::mem::take(&mut self.number) - 1;
+            let mut days = self.tm.weekday - weekday as i64;
+            if days <= 0 {
+                weeks += 1;
+            }
+            days = weeks
+                .checked_mul(7)
+                .and_then(|weeks| weeks.checked_add(days))
+                .and_then(|days| days.checked_mul(DAY))
+                .ok_or(Error::RelativeTimeConversion)?;
+            self.tm.update(&self.now, days)?;
+        } else if match_string(word, "months") >= 5 {
+            self.tm.update(&self.now, 0)?;
+            let months =
+                self.tm.month.expect("set") - std::mem::take(&mut self.number) + 12 * self.tm.year.expect("set");
+            self.tm.year = Some(months.div_euclid(12));
+            self.tm.month = Some(months.rem_euclid(12));
+        } else if match_string(word, "years") >= 4 {
+            self.tm.update(&self.now, 0)?;
+            self.tm.year = self.tm.year.map(|year| year - std::mem::take(&mut self.number));
+        } else {
+            return Ok(end);
+        }
+        self.touched = true;
+        Ok(end)
+    }
+
+    fn special(&mut self, name: &str) -> Result<(), Error> {
+        match name {
+            "yesterday" => {
+                self.number = 0;
+                self.tm.update(&self.now, DAY)?;
+            }
+            "noon" => self.time_of_day(12)?,
+            "midnight" => self.time_of_day(0)?,
+            "tea" => self.time_of_day(17)?,
+            "PM" | "AM" => {
+                let number = std::mem::take(&mut self.number);
+                let mut hour = self.tm.hour;
+                if number != 0 {
+                    hour = number;
+                    self.tm.minute = 0;
+                    self.tm.second = 0;
+                }
+                self.tm.hour = hour % 12 + if name == "PM" { 12 } else { 0 };
+            }
+            "never" => {
+                self.tm = Tm::from_seconds(0)?;
+                self.number = 0;
+            }
+            "now" => {
+                self.number = 0;
+                self.tm.update(&self.now, 0)?;
+            }
+            _ => unreachable!("BUG: all special names are handled"),
+        }
+        Ok(())
+    }
+
+    /// Set the time to `hour` o'clock, on the previous day if it's earlier than that.
+    fn time_of_day(&mut self, hour: i64) -> Result<(), Error> {
+        self.pending_number();
+        if self.tm.hour < hour {
+            self.tm.update(&self.now, DAY)?;
+        }
+        self.tm.hour = hour;
+        self.tm.minute = 0;
+        self.tm.second = 0;
+        Ok(())
+    }
+}
+
+/// Parse the decimal number at `start`, saturating on overflow, and return it along with the position past it.
+fn parse_number(input: &[u8], start: usize) -> (i64, usize) {
+    let len = input[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .unwrap_or(input.len() - start);
+    let number = input[start..start + len].iter().fold(0i64, |number, digit| {
+        number.saturating_mul(10).saturating_add(i64::from(digit - b'0'))
+    });
+    (number, start + len)
+}
+
+/// Return the amount of characters of `word` that match `name` case-insensitively, or 0 if `word` continues with an
+/// alphanumeric character that doesn't match.
+fn match_string(word: &[u8], name: &str) -> usize {
+    let name = name.as_bytes();
+    for (i, byte) in word.iter().enumerate() {
+        if name
+            .get(i)
+            .map_or(false, |expected| expected.eq_ignore_ascii_case(byte))
+        {
+            continue;
+        }
+        return if byte.is_ascii_alphanumeric() { 0 } else { i };
+    }
+    word.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_weeks_ago() {
+        let now = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1251660000);
+        assert_eq!(
+            parse("2 weeks ago", Some(now)).expect("valid").expect("in range"),
+            1251660000 - 2 * 7 * DAY
+        );
+    }
+}
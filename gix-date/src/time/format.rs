@@ -49,6 +49,82 @@ pub const DEFAULT: &[FormatItem<'_>] = format_description!(
      [offset_hour sign:mandatory][offset_minute]"
 );
 
+/// E.g. `Thu Sep 4 10:45:06 2022`, which is [`DEFAULT`] without the offset as output by `git log --date=local`
+/// after converting the time to the local time zone.
+pub const LOCAL: &[FormatItem<'_>] = format_description!(
+    "[weekday repr:short] \
+     [month repr:short] \
+     [day padding:none] \
+     [hour]:[minute]:[second] \
+     [year]"
+);
+
+/// E.g. `2 weeks ago`
+pub const RELATIVE: Format<'static> = Format::Relative;
+
+/// E.g. `Fri 10:45` for a time in the last few days, or `Sep 4 2021` for a time in a past year.
+pub const HUMAN: Format<'static> = Format::Human;
+
+mod human;
+mod strftime;
+
+/// A date format as named by `git log --date=<name>` or the `log.date` configuration variable.
+#[derive(Debug, Clone, Copy)]
+pub struct Named<'a> {
+    /// The format to use.
+    pub format: Format<'a>,
+    /// If `true`, the time is converted to the local time zone before formatting it, as indicated by the `-local` suffix.
+    pub local: bool,
+}
+
+impl<'a> Named<'a> {
+    /// Parse a format `name` like `iso-strict`, `human-local` or `format:%Y-%m-%d`, or return `None` if it is unknown.
+    ///
+    /// `local` is a historical alias for `default-local`. Note that `auto:<name>` isn't supported as it depends on
+    /// the terminal.
+    pub fn from_name(name: &'a str) -> Option<Self> {
+        let name = if name == "local" { "default-local" } else { name };
+        let prefix = [
+            "relative",
+            "iso8601-strict",
+            "iso-strict",
+            "iso8601",
+            "iso",
+            "rfc2822",
+            "rfc",
+            "short",
+            "default",
+            "human",
+            "raw",
+            "unix",
+            "format",
+        ]
+        .into_iter()
+        .find(|prefix| name.starts_with(prefix))?;
+        let rest = &name[prefix.len()..];
+        let (local, rest) = match rest.strip_prefix("-local") {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+        let format = match prefix {
+            "format" => Format::Strftime(rest.strip_prefix(':')?),
+            _ if !rest.is_empty() => return None,
+            "relative" => RELATIVE,
+            "iso8601-strict" | "iso-strict" => ISO8601_STRICT.into(),
+            "iso8601" | "iso" => ISO8601.into(),
+            "rfc2822" | "rfc" => GIT_RFC2822.into(),
+            "short" => SHORT.into(),
+            "default" if local => LOCAL.into(),
+            "default" => DEFAULT.into(),
+            "human" => HUMAN,
+            "raw" => RAW,
+            "unix" => UNIX,
+            _ => unreachable!("all prefixes are handled"),
+        };
+        Some(Named { format, local })
+    }
+}
+
 mod format_impls {
     use time::format_description::FormatItem;
 
@@ -67,11 +143,29 @@ impl Time {
     ///
     /// Use the [`format_description`](https://time-rs.github.io/book/api/format-description.html) macro to create and
     /// validate formats at compile time, courtesy of the [`time`] crate.
+    ///
+    /// Relative formats like [`Format::Human`] are relative to the current local time.
     pub fn format<'a>(&self, format: impl Into<Format<'a>>) -> String {
-        self.format_inner(format.into())
+        self.format_inner(format.into(), None)
+    }
+
+    /// Like [`format()`](Self::format()), but with relative formats like [`Format::Relative`] being relative to `now`
+    /// instead of the current time.
+    pub fn format_at<'a>(&self, format: impl Into<Format<'a>>, now: Time) -> String {
+        self.format_inner(format.into(), Some(now))
     }
 
-    fn format_inner(&self, format: Format<'_>) -> String {
+    /// Format this instance according to the `named` format, converting it to the local time zone first if required.
+    pub fn format_named(&self, named: Named<'_>) -> String {
+        let time = if named.local {
+            self.to_local().unwrap_or(*self)
+        } else {
+            *self
+        };
+        time.format_inner(named.format, None)
+    }
+
+    fn format_inner(&self, format: Format<'_>, now: Option<Time>) -> String {
         match format {
             Format::Custom(format) => self
                 .to_time()
@@ -79,14 +173,38 @@ impl Time {
                 .expect("well-known format into memory never fails"),
             Format::Unix => self.seconds.to_string(),
             Format::Raw => self.to_bstring().to_string(),
+            Format::Strftime(format) => strftime::format(format, self.seconds, self.to_time()),
+            Format::Relative => human::relative(self.seconds, now.unwrap_or_else(Time::now_local_or_utc).seconds),
+            Format::Human => human::format(self, now.unwrap_or_else(Time::now_local_or_utc)),
         }
     }
 }
 
+/// Conversion
 impl Time {
+    /// Return this time with the offset of the local time zone at this point in time, or `None` if the local time
+    /// zone couldn't be determined.
+    pub fn to_local(&self) -> Option<Time> {
+        let offset = time::UtcOffset::local_offset_at(time::OffsetDateTime::from_unix_timestamp(self.seconds).ok()?)
+            .ok()?
+            .whole_seconds();
+        Some(Time::new(self.seconds, offset))
+    }
+
     fn to_time(self) -> time::OffsetDateTime {
         time::OffsetDateTime::from_unix_timestamp(self.seconds)
             .expect("always valid unix time")
             .to_offset(time::UtcOffset::from_whole_seconds(self.offset).expect("valid offset"))
     }
 }
+
+/// Write `offset` in seconds like `+0130` to `out`.
+fn write_offset(out: &mut String, offset: crate::OffsetInSeconds) {
+    let minutes = offset.unsigned_abs() / 60;
+    out.push_str(&format!(
+        "{}{:02}{:02}",
+        if offset < 0 { '-' } else { '+' },
+        minutes / 60,
+        minutes % 60
+    ));
+}
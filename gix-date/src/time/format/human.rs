@@ -0,0 +1,84 @@
+use crate::{time::format::write_offset, SecondsSinceUnixEpoch, Time};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Describe how long ago `seconds` is from `now`, like `git` does with `--date=relative`.
+pub(super) fn relative(seconds: SecondsSinceUnixEpoch, now: SecondsSinceUnixEpoch) -> String {
+    fn ago(amount: i64, unit: &str) -> String {
+        format!("{amount} {unit}{} ago", if amount == 1 { "" } else { "s" })
+    }
+
+    if now < seconds {
+        return "in the future".into();
+    }
+    let diff = now - seconds;
+    if diff < 90 {
+        return ago(diff, "second");
+    }
+    let minutes = (diff + 30) / 60;
+    if minutes < 90 {
+        return ago(minutes, "minute");
+    }
+    let hours = (minutes + 30) / 60;
+    if hours < 36 {
+        return ago(hours, "hour");
+    }
+    let days = (hours + 12) / 24;
+    if days < 14 {
+        ago(days, "day")
+    } else if days < 70 {
+        ago((days + 3) / 7, "week")
+    } else if days < 365 {
+        ago((days + 15) / 30, "month")
+    } else if days < 1825 {
+        let total_months = (days * 12 * 2 + 365) / (365 * 2);
+        let (years, months) = (total_months / 12, total_months % 12);
+        if months == 0 {
+            ago(years, "year")
+        } else {
+            format!(
+                "{years} year{}, {}",
+                if years == 1 { "" } else { "s" },
+                ago(months, "month")
+            )
+        }
+    } else {
+        ago((days + 183) / 365, "year")
+    }
+}
+
+/// Format `time` like `git` does with `--date=human`, omitting details that are implied by `now`.
+pub(super) fn format(time: &Time, now: Time) -> String {
+    let (tm, human_tm) = (time.to_time(), now.to_time());
+    let hide_year = tm.year() == human_tm.year();
+    let same_month = hide_year && tm.month() == human_tm.month();
+    if same_month && tm.day() == human_tm.day() {
+        return relative(time.seconds, now.seconds);
+    }
+    // Leave just the weekday if it was a few days ago.
+    let hide_date = same_month && tm.day() < human_tm.day() && tm.day() + 5 > human_tm.day();
+    let hide_offset = time.offset == now.offset || !hide_date;
+
+    let mut out = String::new();
+    if hide_year {
+        out.push_str(WEEKDAYS[tm.weekday().number_days_from_monday() as usize]);
+        out.push(' ');
+    }
+    if !hide_date {
+        out.push_str(&format!("{} {} ", MONTHS[tm.month() as usize - 1], tm.day()));
+    }
+    if hide_year {
+        out.push_str(&format!("{:02}:{:02}", tm.hour(), tm.minute()));
+    } else {
+        out.truncate(out.trim_end().len());
+        out.push_str(&format!(" {}", tm.year()));
+    }
+    if !hide_offset {
+        out.push(' ');
+        write_offset(&mut out, time.offset);
+    }
+    out
+}
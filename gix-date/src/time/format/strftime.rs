@@ -0,0 +1,105 @@
+use time::OffsetDateTime;
+
+use crate::{time::format::write_offset, SecondsSinceUnixEpoch};
+
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Format `tm`, which represents `seconds` since 1970, according to the `strftime`-like `format`,
+/// the way `git` does with `--date=format:<format>` in the `C` locale.
+///
+/// Unknown conversion specifications are copied verbatim.
+pub(super) fn format(format: &str, seconds: SecondsSinceUnixEpoch, tm: OffsetDateTime) -> String {
+    let mut out = String::with_capacity(format.len() * 2);
+    let mut chars = format.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let Some(spec) = chars.next() else {
+            out.push('%');
+            break;
+        };
+        write_spec(&mut out, spec, seconds, tm);
+    }
+    out
+}
+
+fn write_spec(out: &mut String, spec: char, seconds: SecondsSinceUnixEpoch, tm: OffsetDateTime) {
+    let weekday = tm.weekday().number_days_from_sunday() as usize;
+    let month = tm.month() as usize - 1;
+    let hour12 = match tm.hour() % 12 {
+        0 => 12,
+        hour => hour,
+    };
+    let day_of_year = i32::from(tm.ordinal()) - 1;
+    match spec {
+        'a' => out.push_str(&WEEKDAYS[weekday][..3]),
+        'A' => out.push_str(WEEKDAYS[weekday]),
+        'b' | 'h' => out.push_str(&MONTHS[month][..3]),
+        'B' => out.push_str(MONTHS[month]),
+        'c' => out.push_str(&format("%a %b %e %H:%M:%S %Y", seconds, tm)),
+        'C' => out.push_str(&format!("{:02}", tm.year().div_euclid(100))),
+        'd' => out.push_str(&format!("{:02}", tm.day())),
+        'D' | 'x' => out.push_str(&format("%m/%d/%y", seconds, tm)),
+        'e' => out.push_str(&format!("{:2}", tm.day())),
+        'F' => out.push_str(&format("%Y-%m-%d", seconds, tm)),
+        'g' => out.push_str(&format!("{:02}", tm.to_iso_week_date().0.rem_euclid(100))),
+        'G' => out.push_str(&tm.to_iso_week_date().0.to_string()),
+        'H' => out.push_str(&format!("{:02}", tm.hour())),
+        'I' => out.push_str(&format!("{hour12:02}")),
+        'j' => out.push_str(&format!("{:03}", tm.ordinal())),
+        'k' => out.push_str(&format!("{:2}", tm.hour())),
+        'l' => out.push_str(&format!("{hour12:2}")),
+        'm' => out.push_str(&format!("{:02}", month + 1)),
+        'M' => out.push_str(&format!("{:02}", tm.minute())),
+        'n' => out.push('\n'),
+        'p' => out.push_str(if tm.hour() < 12 { "AM" } else { "PM" }),
+        'P' => out.push_str(if tm.hour() < 12 { "am" } else { "pm" }),
+        'r' => out.push_str(&format("%I:%M:%S %p", seconds, tm)),
+        'R' => out.push_str(&format("%H:%M", seconds, tm)),
+        's' => out.push_str(&seconds.to_string()),
+        'S' => out.push_str(&format!("{:02}", tm.second())),
+        't' => out.push('\t'),
+        'T' | 'X' => out.push_str(&format("%H:%M:%S", seconds, tm)),
+        'u' => out.push_str(&tm.weekday().number_from_monday().to_string()),
+        'U' => out.push_str(&format!("{:02}", (day_of_year + 7 - weekday as i32) / 7)),
+        'V' => out.push_str(&format!("{:02}", tm.iso_week())),
+        'w' => out.push_str(&weekday.to_string()),
+        'W' => out.push_str(&format!(
+            "{:02}",
+            (day_of_year + 7 - i32::from(tm.weekday().number_days_from_monday())) / 7
+        )),
+        'y' => out.push_str(&format!("{:02}", tm.year().rem_euclid(100))),
+        'Y' => out.push_str(&tm.year().to_string()),
+        'z' => write_offset(out, tm.offset().whole_seconds()),
+        'Z' => {}
+        '%' => out.push('%'),
+        unknown => {
+            out.push('%');
+            out.push(unknown);
+        }
+    }
+}
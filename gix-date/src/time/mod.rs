@@ -26,6 +26,15 @@ pub enum Format<'a> {
     Unix,
     /// The seconds since 1970, followed by the offset, like `1660874655 +0800`
     Raw,
+    /// A `strftime` format string like `%Y-%m-%d %H:%M`, as used by `git log --date=format:<format>`.
+    ///
+    /// `%z` is the offset of the time, `%Z` is always empty and `%s` are the seconds since 1970.
+    Strftime(&'a str),
+    /// The time relative to the current time, like `2 weeks ago`, as used by `git log --date=relative`.
+    Relative,
+    /// Like the default `git` format, but with details omitted depending on how far the time is from the current time,
+    /// like `Fri 10:45` for times in the last few days, as used by `git log --date=human`.
+    Human,
 }
 
 ///
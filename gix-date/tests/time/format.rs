@@ -80,6 +80,80 @@ fn custom_compile_time() {
     );
 }
 
+#[test]
+fn strftime() {
+    assert_eq!(
+        time().format(Format::Strftime("%Y-%m-%d %H:%M:%S %z (%s) %a %b %e %j %Z%% %Q")),
+        "1973-11-30 00:03:09 +0230 (123456789) Fri Nov 30 334 % %Q"
+    );
+    assert_eq!(
+        time_dec1().format(Format::Strftime("%c %I%p %U %V")),
+        "Sat Dec  1 00:03:09 1973 12AM 47 48"
+    );
+    assert_eq!(time().format(Format::Strftime("")), "");
+}
+
+#[test]
+fn relative() {
+    let now = Time::new(1251660000, 0);
+    for (seconds, expected) in [
+        (1251660000, "0 seconds ago"),
+        (1251659909, "2 minutes ago"),
+        (1251656400, "60 minutes ago"),
+        (1251314400, "4 days ago"),
+        (1250536800, "13 days ago"),
+        (1220210400, "12 months ago"),
+        (1220037600, "1 year ago"),
+        (1251660010, "in the future"),
+    ] {
+        assert_eq!(Time::new(seconds, 0).format_at(format::RELATIVE, now), expected);
+    }
+}
+
+#[test]
+fn human() {
+    let now = Time::new(1251660000, 0);
+    for (seconds, offset, expected) in [
+        (1251660000, 7200, "0 seconds ago"),
+        (1251659909, 50400, "Mon Aug 31 09:18"),
+        (1251656400, -43200, "60 minutes ago"),
+        (1251314400, 3600, "Wed 20:20 +0100"),
+        (1250536800, 50400, "Tue Aug 18 09:20"),
+        (1220210400, 3600, "Aug 31 2008"),
+        (1220037600, -43200, "Aug 29 2008"),
+    ] {
+        assert_eq!(
+            Time::new(seconds, offset).format_at(format::HUMAN, now),
+            expected,
+            "{seconds} {offset}"
+        );
+    }
+}
+
+#[test]
+fn named() {
+    use format::Named;
+    for (name, expected, local) in [
+        ("default", "Fri Nov 30 00:03:09 1973 +0230", false),
+        ("iso", "1973-11-30 00:03:09 +0230", false),
+        ("iso8601-strict", "1973-11-30T00:03:09+02:30", false),
+        ("rfc", "Fri, 30 Nov 1973 00:03:09 +0230", false),
+        ("short", "1973-11-30", false),
+        ("raw-local", "123456789 +0230", true),
+        ("unix", "123456789", false),
+        ("local", "Fri Nov 30 00:03:09 1973", true),
+        ("format:%d.%m.%y", "30.11.73", false),
+        ("format-local:", "", true),
+    ] {
+        let named = Named::from_name(name).unwrap_or_else(|| panic!("{name:?} is known"));
+        assert_eq!(named.local, local, "{name}");
+        assert_eq!(time().format(named.format), expected, "{name}");
+    }
+    for unknown in ["", "iso-foo", "format", "human-", "relative-local-local", "auto:human"] {
+        assert!(Named::from_name(unknown).is_none(), "{unknown:?}");
+    }
+}
+
 fn time() -> Time {
     Time {
         seconds: 123456789,
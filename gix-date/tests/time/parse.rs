@@ -84,6 +84,27 @@ fn raw() {
     }
 }
 
+#[test]
+fn raw_with_at_prefix() {
+    assert_eq!(
+        gix_date::parse("@1660874655", None).unwrap(),
+        Time {
+            seconds: 1660874655,
+            offset: 0,
+            sign: Sign::Plus,
+        },
+    );
+    assert_eq!(
+        gix_date::parse("@1660874655 +0800", None).unwrap(),
+        Time {
+            seconds: 1660874655,
+            offset: 28800,
+            sign: Sign::Plus,
+        },
+        "the timezone is retained"
+    );
+}
+
 #[test]
 fn bad_raw() {
     for bad_date_str in [
@@ -167,6 +188,63 @@ mod relative {
         assert_eq!(date.seconds, -1);
     }
 
+    #[test]
+    fn approximate_dates_like_git() {
+        let now = Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(1251660000));
+        // Obtained with `GIT_TEST_DATE_NOW=1251660000 TZ=UTC git rev-parse --since=<input>`, with `now` being a Sunday.
+        for (input, expected) in [
+            ("now", 1251660000),
+            ("5 seconds ago", 1251659995),
+            ("2.years.3.months.ago", 1180552800),
+            ("12:34:56.3.days.ago", 1251376496),
+            ("3 weeks 2 days ago", 1249672800),
+            ("one hour", 1251656400),
+            ("1 month ago", 1248981600),
+            ("last year", 1220124000),
+            ("yesterday", 1251573600),
+            ("6am yesterday", 1251525600),
+            ("yesterday 5pm", 1251565200),
+            ("midnight yesterday", 1251504000),
+            ("noon today", 1251633600),
+            ("10am noon", 1251547200),
+            ("tea", 1251651600),
+            ("12am", 1251590400),
+            ("last tuesday", 1251228000),
+            ("noon last tuesday", 1251201600),
+            ("2 friday", 1250882400),
+            ("January 5th noon pm", 1231156800),
+            ("July 5th", 1246821600),
+            ("Dec 25", 1230232800),
+            ("Jun 6, 5AM", 1244264400),
+            ("6AM, June 7, 2009", 1244354400),
+            ("06/05/2009", 1244229600),
+            ("06.05.2009", 1241637600),
+            ("1.2.3", 1044127200),
+            ("99/12/31", 946668000),
+            ("12/25", 1755026400),
+            ("Feb 30", 1236021600),
+            ("last", 1249154400),
+            ("never", 0),
+        ] {
+            let actual = gix_date::parse(input, now).unwrap_or_else(|err| panic!("{input:?}: {err}"));
+            assert_eq!(actual, gix_date::Time::new(expected, 0), "{input:?}");
+        }
+
+        for input in ["friday", "days", "today", "ago", "Z"] {
+            assert!(
+                matches!(
+                    gix_date::parse(input, now),
+                    Err(gix_date::parse::Error::InvalidDateString { .. })
+                ),
+                "{input:?}: nothing was understood, and weekdays or units need a number"
+            );
+        }
+        assert!(matches!(
+            gix_date::parse("noon", None),
+            Err(gix_date::parse::Error::MissingCurrentTime)
+        ));
+    }
+
     #[test]
     fn various() {
         let now = SystemTime::now();
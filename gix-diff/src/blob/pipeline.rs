@@ -240,18 +240,11 @@ impl Pipeline {
         out.clear();
         attributes(rela_path, &mut self.attrs);
         let attr = self.attrs.iter_selected().next().expect("pre-initialized with 'diff'");
-        let driver_index = attr
-            .assignment
-            .state
-            .as_bstr()
-            .and_then(|name| self.drivers.binary_search_by(|d| d.name.as_bstr().cmp(name)).ok());
+        let (driver_index, is_binary) = is_binary_by_attribute(attr.assignment.state, &self.drivers);
         let driver = driver_index.map(|idx| &self.drivers[idx]);
-        let mut is_binary = if let Some(driver) = driver {
-            driver
-                .is_binary
-                .map(|is_binary| is_binary && driver.binary_to_text_command.is_none())
-        } else {
-            attr.assignment.state.is_unset().then_some(true)
+        let mut is_binary = match driver {
+            Some(driver) => is_binary.map(|is_binary| is_binary && driver.binary_to_text_command.is_none()),
+            None => is_binary,
         };
         match self.roots.by_kind(kind) {
             Some(root) => {
@@ -496,11 +489,37 @@ impl Pipeline {
     }
 }
 
-fn is_binary_buf(buf: &[u8]) -> bool {
-    let buf = &buf[..buf.len().min(8000)];
+/// The amount of bytes at the beginning of a buffer that [`is_binary_buf()`] searches for null bytes.
+pub const FIRST_FEW_BYTES: usize = 8000;
+
+/// Return `true` if `buf` looks binary as it contains a null byte within its [first few bytes](FIRST_FEW_BYTES),
+/// which is the heuristic `git` uses.
+pub fn is_binary_buf(buf: &[u8]) -> bool {
+    let buf = &buf[..buf.len().min(FIRST_FEW_BYTES)];
     buf.contains(&0)
 }
 
+/// Return the index of the driver in `drivers` that the `diff` attribute with `state` refers to, along with
+/// whether the resource is binary according to the attribute alone, or `None` if its content has to decide.
+///
+/// A resource is binary if `diff` is unset and text if it is set. If `diff` names a driver, its
+/// [`is_binary`](Driver::is_binary) field decides, without considering its
+/// [binary-to-text conversion](Driver::binary_to_text_command). `drivers` must be sorted by name.
+pub fn is_binary_by_attribute(
+    state: gix_filter::attributes::StateRef<'_>,
+    drivers: &[Driver],
+) -> (Option<usize>, Option<bool>) {
+    match state {
+        gix_filter::attributes::StateRef::Unset => (None, Some(true)),
+        gix_filter::attributes::StateRef::Set => (None, Some(false)),
+        gix_filter::attributes::StateRef::Value(name) => {
+            let driver_index = drivers.binary_search_by(|d| d.name.as_bstr().cmp(name.as_bstr())).ok();
+            (driver_index, driver_index.and_then(|idx| drivers[idx].is_binary))
+        }
+        gix_filter::attributes::StateRef::Unspecified => (None, None),
+    }
+}
+
 fn none_if_missing<T>(res: std::io::Result<T>) -> std::io::Result<Option<T>> {
     match res {
         Ok(data) => Ok(Some(data)),
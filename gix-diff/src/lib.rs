@@ -50,3 +50,6 @@ pub mod tree;
 ///
 #[cfg(feature = "blob")]
 pub mod blob;
+
+/// Parse, create and write patches in the unified diff format, and apply them to content, similar to `git apply`.
+pub mod patch;
@@ -0,0 +1,179 @@
+use bstr::ByteSlice;
+
+use crate::patch::{Hunk, Line};
+
+/// The outcome of [`text()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The content with all hunks applied that could be applied.
+    pub data: Vec<u8>,
+    /// The indices of the hunks that couldn't be applied as their lines weren't found.
+    pub rejected: Vec<usize>,
+}
+
+impl Outcome {
+    /// Return `true` if all hunks were applied.
+    pub fn is_complete(&self) -> bool {
+        self.rejected.is_empty()
+    }
+}
+
+/// Apply all `hunks` to `old`, which are expected to be ordered by their position in the file, similar to `git apply`.
+///
+/// Each hunk is applied where its removed and context lines match exactly, searching outwards from the position it
+/// names while accounting for the shift in position of previously applied hunks.
+/// Hunks without leading context must match at the beginning of the file if they start at its first line, and hunks
+/// without trailing context must match at its end.
+/// Hunks that can't be applied are [rejected](Outcome::rejected).
+pub fn text(old: &[u8], hunks: &[Hunk]) -> Outcome {
+    let old_lines: Vec<&[u8]> = old.lines_with_terminator().collect();
+    let mut data = Vec::with_capacity(old.len());
+    let mut rejected = Vec::new();
+    let mut copied = 0;
+    let mut offset = 0isize;
+    for (idx, hunk) in hunks.iter().enumerate() {
+        let preimage: Vec<&[u8]> = hunk
+            .lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Context(line) | Line::Removal(line) => Some(line.as_slice()),
+                Line::Addition(_) => None,
+            })
+            .collect();
+        let leading_context = hunk
+            .lines
+            .iter()
+            .take_while(|line| matches!(line, Line::Context(_)))
+            .count();
+        let trailing_context = hunk
+            .lines
+            .iter()
+            .rev()
+            .take_while(|line| matches!(line, Line::Context(_)))
+            .count();
+        let match_beginning = hunk.old_start == 0 || (hunk.old_start == 1 && leading_context == 0);
+        let match_end = trailing_context == 0 && !preimage.is_empty();
+
+        // With zero lines, the start is the line after which to insert.
+        let position = if hunk.old_lines == 0 {
+            hunk.old_start as isize
+        } else {
+            hunk.old_start as isize - 1
+        };
+        let Some(last_start) = old_lines.len().checked_sub(preimage.len()) else {
+            rejected.push(idx);
+            continue;
+        };
+        let matches_at = |pos: usize| {
+            pos >= copied
+                && pos <= last_start
+                && (!match_beginning || pos == 0)
+                && (!match_end || pos == last_start)
+                && old_lines[pos..pos + preimage.len()] == preimage[..]
+        };
+        let expected = (position + offset).clamp(0, last_start as isize) as usize;
+        let found = (0..=old_lines.len()).find_map(|distance| {
+            let before = expected.checked_sub(distance).filter(|pos| matches_at(*pos));
+            before.or_else(|| Some(expected + distance).filter(|pos| matches_at(*pos)))
+        });
+        let Some(pos) = found else {
+            rejected.push(idx);
+            continue;
+        };
+
+        for line in &old_lines[copied..pos] {
+            data.extend_from_slice(line);
+        }
+        for line in &hunk.lines {
+            if let Line::Context(line) | Line::Addition(line) = line {
+                data.extend_from_slice(line);
+            }
+        }
+        copied = pos + preimage.len();
+        offset = pos as isize - position;
+    }
+    for line in &old_lines[copied..] {
+        data.extend_from_slice(line);
+    }
+    Outcome { data, rejected }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod delta {
+    /// The error returned by [`delta()`](super::delta()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The delta expects a base of {expected} bytes, but it has {actual} bytes")]
+        BaseSize { expected: u64, actual: u64 },
+        #[error("The delta is malformed")]
+        Malformed,
+    }
+}
+
+/// Apply the decompressed `delta` of a [binary patch](crate::patch::BinaryKind::Delta) to `base`, returning the result.
+///
+/// The delta has the same format as the deltas in packs.
+pub fn delta(base: &[u8], delta: &[u8]) -> Result<Vec<u8>, delta::Error> {
+    fn size(data: &mut &[u8]) -> Result<u64, delta::Error> {
+        let mut size = 0u64;
+        let mut shift = 0;
+        loop {
+            let (&byte, rest) = data.split_first().ok_or(delta::Error::Malformed)?;
+            *data = rest;
+            size |= u64::from(byte & 0x7f)
+                .checked_shl(shift)
+                .ok_or(delta::Error::Malformed)?;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                return Ok(size);
+            }
+        }
+    }
+
+    let mut data = delta;
+    let base_size = size(&mut data)?;
+    if base_size != base.len() as u64 {
+        return Err(delta::Error::BaseSize {
+            expected: base_size,
+            actual: base.len() as u64,
+        });
+    }
+    let result_size = size(&mut data)?;
+    let mut out = Vec::with_capacity(result_size as usize);
+    while let Some((&cmd, rest)) = data.split_first() {
+        data = rest;
+        if cmd & 0x80 != 0 {
+            let mut next = |bit: u8, shift: u32| -> Result<usize, delta::Error> {
+                if cmd & bit == 0 {
+                    return Ok(0);
+                }
+                let (&byte, rest) = data.split_first().ok_or(delta::Error::Malformed)?;
+                data = rest;
+                Ok((byte as usize) << shift)
+            };
+            let ofs = next(0x01, 0)? | next(0x02, 8)? | next(0x04, 16)? | next(0x08, 24)?;
+            let mut len = next(0x10, 0)? | next(0x20, 8)? | next(0x40, 16)?;
+            if len == 0 {
+                len = 0x10000;
+            }
+            let copy = ofs
+                .checked_add(len)
+                .and_then(|end| base.get(ofs..end))
+                .ok_or(delta::Error::Malformed)?;
+            out.extend_from_slice(copy);
+        } else if cmd != 0 {
+            let len = cmd as usize;
+            let insert = data.get(..len).ok_or(delta::Error::Malformed)?;
+            out.extend_from_slice(insert);
+            data = &data[len..];
+        } else {
+            return Err(delta::Error::Malformed);
+        }
+    }
+    if out.len() as u64 != result_size {
+        return Err(delta::Error::Malformed);
+    }
+    Ok(out)
+}
@@ -0,0 +1,107 @@
+use std::ops::Range;
+
+use bstr::{BString, ByteSlice};
+
+use crate::{
+    blob::{intern::InternedInput, sources, Algorithm},
+    patch::{Hunk, Line},
+};
+
+/// Options for use with [`hunks()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The amount of unchanged lines to show before and after each change, defaulting to `3` like `git diff -U3`.
+    ///
+    /// Changes that are separated by no more than twice this amount of lines are shown in the same hunk.
+    pub context_lines: u32,
+    /// The algorithm to compute the changed lines with, defaulting to [`Algorithm::Myers`] like `git` does.
+    pub algorithm: Algorithm,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            context_lines: 3,
+            algorithm: Algorithm::Myers,
+        }
+    }
+}
+
+/// Compute the hunks that turn the lines of `old` into the lines of `new`, as shown by `git diff`, using `options`
+/// to configure the algorithm and the amount of context.
+///
+/// Each hunk has the line before it that looks like the start of a function as its [section](Hunk::section), using the
+/// default heuristic of `git` which picks the closest line starting with a letter, `_` or `$`.
+/// The result is empty if `old` and `new` are the same.
+pub fn hunks(old: &[u8], new: &[u8], options: Options) -> Vec<Hunk> {
+    let input = InternedInput::new(
+        sources::byte_lines_with_terminator(old),
+        sources::byte_lines_with_terminator(new),
+    );
+    let mut changes = Vec::<(Range<u32>, Range<u32>)>::new();
+    crate::blob::diff(options.algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+        changes.push((before, after));
+    });
+
+    let line = |token: &crate::blob::intern::Token| BString::from(input.interner[*token]);
+    let context = options.context_lines;
+    let mut hunks = Vec::new();
+    let mut remaining = changes.as_slice();
+    while let Some(first) = remaining.first() {
+        let len = remaining
+            .windows(2)
+            .position(|pair| pair[1].0.start - pair[0].0.end > 2 * context)
+            .map_or(remaining.len(), |pos| pos + 1);
+        let (group, rest) = remaining.split_at(len);
+        remaining = rest;
+        let last = group.last().expect("at least one change");
+
+        let old_start = first.0.start.saturating_sub(context);
+        let new_start = first.1.start - (first.0.start - old_start);
+        let old_end = (last.0.end + context).min(input.before.len() as u32);
+        let new_end = last.1.end + (old_end - last.0.end);
+        let mut lines = Vec::new();
+        let mut pos = old_start;
+        for (before, after) in group {
+            lines.extend(
+                input.before[pos as usize..before.start as usize]
+                    .iter()
+                    .map(|t| Line::Context(line(t))),
+            );
+            lines.extend(
+                input.before[before.start as usize..before.end as usize]
+                    .iter()
+                    .map(|t| Line::Removal(line(t))),
+            );
+            lines.extend(
+                input.after[after.start as usize..after.end as usize]
+                    .iter()
+                    .map(|t| Line::Addition(line(t))),
+            );
+            pos = before.end;
+        }
+        lines.extend(
+            input.before[pos as usize..old_end as usize]
+                .iter()
+                .map(|t| Line::Context(line(t))),
+        );
+
+        let section = input.before[..old_start as usize]
+            .iter()
+            .rev()
+            .map(|token| input.interner[*token])
+            .find(|line| matches!(line.first(), Some(b) if b.is_ascii_alphabetic() || *b == b'_' || *b == b'$'))
+            .map(|line| line[..line.len().min(80)].trim_end().into())
+            .unwrap_or_default();
+        let start = |start: u32, end: u32| if end > start { start + 1 } else { start };
+        hunks.push(Hunk {
+            old_start: start(old_start, old_end),
+            old_lines: old_end - old_start,
+            new_start: start(new_start, new_end),
+            new_lines: new_end - new_start,
+            section,
+            lines,
+        });
+    }
+    hunks
+}
@@ -0,0 +1,179 @@
+use bstr::{BStr, BString, ByteSlice};
+use gix_object::tree::EntryMode;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse;
+pub use parse::function::parse;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply;
+
+/// Create hunks from the differences between two versions of a file.
+#[cfg(feature = "blob")]
+pub mod create;
+
+mod write;
+
+/// A line of a [`Hunk`], including its line terminator unless it is the last line of a file without a trailing newline.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Line {
+    /// A line that is the same before and after the change.
+    Context(BString),
+    /// A line that is removed by the change.
+    Removal(BString),
+    /// A line that is added by the change.
+    Addition(BString),
+}
+
+impl Line {
+    /// Return the content of the line, without its prefix.
+    pub fn content(&self) -> &BStr {
+        match self {
+            Line::Context(line) | Line::Removal(line) | Line::Addition(line) => line.as_bstr(),
+        }
+    }
+}
+
+/// A region of a file with the lines that changed in it, along with some unchanged lines for context.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk {
+    /// The one-based number of the first line of the hunk in the old version of the file, or the line after which lines are
+    /// added if [`old_lines`](Self::old_lines) is `0`.
+    pub old_start: u32,
+    /// The amount of lines of the hunk in the old version of the file.
+    pub old_lines: u32,
+    /// The one-based number of the first line of the hunk in the new version of the file.
+    pub new_start: u32,
+    /// The amount of lines of the hunk in the new version of the file.
+    pub new_lines: u32,
+    /// The text after the closing `@@` of the hunk header, typically the name of the enclosing function, without leading
+    /// whitespace.
+    pub section: BString,
+    /// All lines of the hunk.
+    pub lines: Vec<Line>,
+}
+
+impl Hunk {
+    /// Write this hunk in the unified diff format to `out`, including its header.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        let range = |start: u32, lines: u32| {
+            if lines == 1 {
+                start.to_string()
+            } else {
+                format!("{start},{lines}")
+            }
+        };
+        write!(
+            out,
+            "@@ -{} +{} @@",
+            range(self.old_start, self.old_lines),
+            range(self.new_start, self.new_lines)
+        )?;
+        if !self.section.is_empty() {
+            out.write_all(b" ")?;
+            out.write_all(&self.section)?;
+        }
+        out.write_all(b"\n")?;
+        for line in &self.lines {
+            let (prefix, content) = match line {
+                Line::Context(line) => (b" ", line),
+                Line::Removal(line) => (b"-", line),
+                Line::Addition(line) => (b"+", line),
+            };
+            out.write_all(prefix)?;
+            out.write_all(content)?;
+            if !content.ends_with(b"\n") {
+                out.write_all(b"\n\\ No newline at end of file\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The way a file is changed by a [`File`] patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operation {
+    /// The file exists before and after the change, possibly with a different mode.
+    Modification,
+    /// The file is created.
+    Addition,
+    /// The file is deleted.
+    Deletion,
+    /// The file is moved from its old path to its new path, possibly with changes.
+    Rename,
+    /// The file at the old path is copied to the new path, possibly with changes.
+    Copy,
+}
+
+/// The way the data of a [`BinaryHunk`] is to be interpreted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BinaryKind {
+    /// The data is the entire content of the file.
+    Literal,
+    /// The data is a delta against the other version of the file, in the format used in packs.
+    Delta,
+}
+
+/// The data of a binary patch in one direction, as found after `GIT binary patch`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BinaryHunk {
+    /// How to interpret the data once it is decompressed.
+    pub kind: BinaryKind,
+    /// The size of the data once it is decompressed.
+    pub size: u64,
+    /// The zlib-compressed data.
+    pub data: Vec<u8>,
+}
+
+/// The changes to the content of a file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Content {
+    /// The hunks of a text file, which are empty if only the path or the mode changed.
+    Text(Vec<Hunk>),
+    /// The data of a binary file.
+    Binary {
+        /// The data to obtain the new version from the old one.
+        forward: BinaryHunk,
+        /// The data to obtain the old version from the new one, if present.
+        reverse: Option<BinaryHunk>,
+    },
+    /// A binary file changed, but the patch doesn't contain the data to apply the change, as announced by
+    /// `Binary files a/… and b/… differ`.
+    BinaryWithoutData,
+}
+
+/// The patch of a single file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct File {
+    /// The way the file is changed.
+    pub operation: Operation,
+    /// The path of the file before the change, or `None` if it is [added](Operation::Addition).
+    pub old_path: Option<BString>,
+    /// The path of the file after the change, or `None` if it is [deleted](Operation::Deletion).
+    pub new_path: Option<BString>,
+    /// The mode of the file before the change, if known.
+    pub old_mode: Option<EntryMode>,
+    /// The mode of the file after the change, if known.
+    pub new_mode: Option<EntryMode>,
+    /// The possibly abbreviated hexadecimal id of the blob before the change, as found on the `index` line.
+    pub old_id: Option<BString>,
+    /// The possibly abbreviated hexadecimal id of the blob after the change, as found on the `index` line.
+    pub new_id: Option<BString>,
+    /// The similarity of the old and new file in percent, for renames and copies.
+    pub similarity: Option<u8>,
+    /// The changes to the content of the file.
+    pub content: Content,
+}
+
+impl File {
+    /// Return the path of the file after the change, or before the change if it is deleted.
+    pub fn path(&self) -> &BStr {
+        self.new_path
+            .as_ref()
+            .or(self.old_path.as_ref())
+            .expect("at least one path is always set")
+            .as_bstr()
+    }
+}
@@ -0,0 +1,468 @@
+use bstr::BString;
+
+/// Options for use with [`parse()`](crate::patch::parse()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The amount of leading path components to remove from the paths in `diff --git`, `---` and `+++` lines,
+    /// defaulting to `1` to remove the `a/` and `b/` prefixes, similar to `git apply -p<n>`.
+    pub strip: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { strip: 1 }
+    }
+}
+
+/// The error returned by [`parse()`](crate::patch::parse()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The hunk header at line {line} is malformed")]
+    HunkHeader { line: usize },
+    #[error("The hunk starting at line {line} ends before all of its lines were read")]
+    TruncatedHunk { line: usize },
+    #[error("Line {line} isn't valid within a hunk")]
+    HunkLine { line: usize },
+    #[error("Could not determine the path of the file patched at line {line}")]
+    MissingPath { line: usize },
+    #[error("Path \"{path}\" at line {line} doesn't have {strip} leading components to strip")]
+    Strip { path: BString, strip: usize, line: usize },
+    #[error("The binary patch at line {line} is malformed")]
+    BinaryPatch { line: usize },
+    #[error("The mode at line {line} is invalid")]
+    Mode { line: usize },
+}
+
+pub(super) mod function {
+    use bstr::{BStr, BString, ByteSlice};
+    use gix_object::tree::EntryMode;
+
+    use super::{Error, Options};
+    use crate::patch::{BinaryHunk, BinaryKind, Content, File, Hunk, Line, Operation};
+
+    /// Parse all file patches in `input` in the unified diff format, as produced by `git diff` or contained in the emails
+    /// of `git format-patch`, using `options` to interpret paths.
+    ///
+    /// Besides the extended headers of `git` for modes, renames, copies and blob ids, binary patches are supported as well.
+    /// Text before, between and after the patches is ignored, so the input may be an entire email.
+    pub fn parse(input: &[u8], options: Options) -> Result<Vec<File>, Error> {
+        let mut lines = Lines {
+            lines: input.lines_with_terminator().collect(),
+            pos: 0,
+        };
+        let mut files = Vec::new();
+        while let Some(line) = lines.peek() {
+            if let Some(names) = line.strip_prefix(b"diff --git ") {
+                lines.pos += 1;
+                files.push(parse_git_file(&mut lines, names, options)?);
+            } else if line.starts_with(b"--- ") && matches!(lines.peek_at(1), Some(next) if next.starts_with(b"+++ ")) {
+                files.push(parse_traditional_file(&mut lines, options)?);
+            } else {
+                lines.pos += 1;
+            }
+        }
+        Ok(files)
+    }
+
+    struct Lines<'a> {
+        lines: Vec<&'a [u8]>,
+        pos: usize,
+    }
+
+    impl<'a> Lines<'a> {
+        fn peek(&self) -> Option<&'a [u8]> {
+            self.lines.get(self.pos).copied()
+        }
+
+        fn peek_at(&self, offset: usize) -> Option<&'a [u8]> {
+            self.lines.get(self.pos + offset).copied()
+        }
+
+        /// The one-based number of the current line.
+        fn number(&self) -> usize {
+            self.pos + 1
+        }
+    }
+
+    fn parse_git_file(lines: &mut Lines<'_>, names: &[u8], options: Options) -> Result<File, Error> {
+        let header_line = lines.number() - 1;
+        let mut file = File {
+            operation: Operation::Modification,
+            old_path: None,
+            new_path: None,
+            old_mode: None,
+            new_mode: None,
+            old_id: None,
+            new_id: None,
+            similarity: None,
+            content: Content::Text(Vec::new()),
+        };
+        let (mut old_path, mut new_path) = match git_header_names(trim_newline(names)) {
+            Some((old, new)) => (
+                Some(strip(old, options.strip, header_line)?),
+                Some(strip(new, options.strip, header_line)?),
+            ),
+            None => (None, None),
+        };
+
+        while let Some(line) = lines.peek() {
+            let line_number = lines.number();
+            let content = trim_newline(line);
+            let mode = |value: &[u8]| parse_mode(value).ok_or(Error::Mode { line: line_number });
+            if let Some(value) = content.strip_prefix(b"old mode ") {
+                file.old_mode = Some(mode(value)?);
+            } else if let Some(value) = content.strip_prefix(b"new mode ") {
+                file.new_mode = Some(mode(value)?);
+            } else if let Some(value) = content.strip_prefix(b"deleted file mode ") {
+                file.operation = Operation::Deletion;
+                file.old_mode = Some(mode(value)?);
+            } else if let Some(value) = content.strip_prefix(b"new file mode ") {
+                file.operation = Operation::Addition;
+                file.new_mode = Some(mode(value)?);
+            } else if let Some(path) = content.strip_prefix(b"rename from ") {
+                file.operation = Operation::Rename;
+                old_path = Some(unquote_path(path));
+            } else if let Some(path) = content.strip_prefix(b"rename to ") {
+                file.operation = Operation::Rename;
+                new_path = Some(unquote_path(path));
+            } else if let Some(path) = content.strip_prefix(b"copy from ") {
+                file.operation = Operation::Copy;
+                old_path = Some(unquote_path(path));
+            } else if let Some(path) = content.strip_prefix(b"copy to ") {
+                file.operation = Operation::Copy;
+                new_path = Some(unquote_path(path));
+            } else if let Some(value) = content.strip_prefix(b"similarity index ") {
+                file.similarity = value.strip_suffix(b"%").and_then(|v| v.to_str().ok()?.parse().ok());
+            } else if content.starts_with(b"dissimilarity index ") {
+                // Complete rewrites are handled like any other modification.
+            } else if let Some(value) = content.strip_prefix(b"index ") {
+                let (ids, mode_value) = match value.find_byte(b' ') {
+                    Some(pos) => (&value[..pos], Some(&value[pos + 1..])),
+                    None => (value, None),
+                };
+                if let Some((old, new)) = ids.split_once_str("..") {
+                    file.old_id = Some(old.into());
+                    file.new_id = Some(new.into());
+                }
+                if let Some(value) = mode_value {
+                    let value = mode(value)?;
+                    file.old_mode = Some(value);
+                    file.new_mode = Some(value);
+                }
+            } else {
+                break;
+            }
+            lines.pos += 1;
+        }
+
+        if let (Some(old), Some(new)) = (lines.peek(), lines.peek_at(1)) {
+            if let (Some(old), Some(new)) = (old.strip_prefix(b"--- "), new.strip_prefix(b"+++ ")) {
+                let line_number = lines.number();
+                if file.operation != Operation::Rename && file.operation != Operation::Copy {
+                    old_path = diff_path(old, options.strip, line_number)?;
+                    new_path = diff_path(new, options.strip, line_number + 1)?;
+                }
+                lines.pos += 2;
+            }
+        }
+        match file.operation {
+            Operation::Addition => old_path = None,
+            Operation::Deletion => new_path = None,
+            Operation::Modification | Operation::Rename | Operation::Copy => {
+                if old_path.is_none() {
+                    old_path = new_path.clone();
+                }
+                if new_path.is_none() {
+                    new_path = old_path.clone();
+                }
+            }
+        }
+        if old_path.is_none() && new_path.is_none() {
+            return Err(Error::MissingPath { line: header_line });
+        }
+        file.old_path = old_path;
+        file.new_path = new_path;
+
+        match lines.peek() {
+            Some(line) if line.starts_with(b"Binary files ") => {
+                lines.pos += 1;
+                file.content = Content::BinaryWithoutData;
+            }
+            Some(line) if trim_newline(line) == b"GIT binary patch" => {
+                lines.pos += 1;
+                let forward = parse_binary_hunk(lines)?;
+                let reverse = match lines.peek() {
+                    Some(line) if line.starts_with(b"literal ") || line.starts_with(b"delta ") => {
+                        Some(parse_binary_hunk(lines)?)
+                    }
+                    _ => None,
+                };
+                file.content = Content::Binary { forward, reverse };
+            }
+            _ => file.content = Content::Text(parse_hunks(lines)?),
+        }
+        Ok(file)
+    }
+
+    fn parse_traditional_file(lines: &mut Lines<'_>, options: Options) -> Result<File, Error> {
+        let line_number = lines.number();
+        let old = &lines.peek().expect("checked by caller")[4..];
+        let new = &lines.peek_at(1).expect("checked by caller")[4..];
+        let old_path = diff_path(old, options.strip, line_number)?;
+        let new_path = diff_path(new, options.strip, line_number + 1)?;
+        lines.pos += 2;
+        let operation = match (&old_path, &new_path) {
+            (None, None) => return Err(Error::MissingPath { line: line_number }),
+            (None, Some(_)) => Operation::Addition,
+            (Some(_), None) => Operation::Deletion,
+            (Some(_), Some(_)) => Operation::Modification,
+        };
+        Ok(File {
+            operation,
+            // The old path of traditional patches often names a backup copy, so the new path is the one to change.
+            old_path: if operation == Operation::Modification {
+                new_path.clone()
+            } else {
+                old_path
+            },
+            new_path,
+            old_mode: None,
+            new_mode: None,
+            old_id: None,
+            new_id: None,
+            similarity: None,
+            content: Content::Text(parse_hunks(lines)?),
+        })
+    }
+
+    fn parse_hunks(lines: &mut Lines<'_>) -> Result<Vec<Hunk>, Error> {
+        let mut hunks = Vec::new();
+        while let Some(header) = lines.peek().filter(|line| line.starts_with(b"@@ -")) {
+            let header_line = lines.number();
+            let mut hunk = parse_hunk_header(trim_newline(header)).ok_or(Error::HunkHeader { line: header_line })?;
+            lines.pos += 1;
+            let (mut old_left, mut new_left) = (hunk.old_lines, hunk.new_lines);
+            while old_left > 0 || new_left > 0 {
+                let line = lines.peek().ok_or(Error::TruncatedHunk { line: header_line })?;
+                let line_number = lines.number();
+                lines.pos += 1;
+                let (kind, content) = match line.first() {
+                    // Some tools strip the space of empty context lines.
+                    Some(b'\n' | b'\r') => (b' ', line),
+                    Some(kind) => (*kind, &line[1..]),
+                    None => return Err(Error::TruncatedHunk { line: header_line }),
+                };
+                let content: BString = content.into();
+                match kind {
+                    b' ' if old_left > 0 && new_left > 0 => {
+                        old_left -= 1;
+                        new_left -= 1;
+                        hunk.lines.push(Line::Context(content));
+                    }
+                    b'-' if old_left > 0 => {
+                        old_left -= 1;
+                        hunk.lines.push(Line::Removal(content));
+                    }
+                    b'+' if new_left > 0 => {
+                        new_left -= 1;
+                        hunk.lines.push(Line::Addition(content));
+                    }
+                    b'\\' => strip_last_newline(&mut hunk),
+                    _ if line.starts_with(b"@@ ") || line.starts_with(b"diff ") => {
+                        return Err(Error::TruncatedHunk { line: header_line })
+                    }
+                    _ => return Err(Error::HunkLine { line: line_number }),
+                }
+            }
+            if matches!(lines.peek(), Some(line) if line.starts_with(b"\\")) {
+                lines.pos += 1;
+                strip_last_newline(&mut hunk);
+            }
+            hunks.push(hunk);
+        }
+        Ok(hunks)
+    }
+
+    /// Handle `\ No newline at end of file` by removing the line terminator of the line before it.
+    fn strip_last_newline(hunk: &mut Hunk) {
+        if let Some(Line::Context(line) | Line::Removal(line) | Line::Addition(line)) = hunk.lines.last_mut() {
+            if line.ends_with(b"\n") {
+                line.pop();
+                if line.ends_with(b"\r") {
+                    line.pop();
+                }
+            }
+        }
+    }
+
+    fn parse_hunk_header(line: &[u8]) -> Option<Hunk> {
+        let rest = line.strip_prefix(b"@@ -")?;
+        let end = rest.find(b" @@")?;
+        let (ranges, section) = (&rest[..end], &rest[end + 3..]);
+        let (old, new) = ranges.split_once_str(" +")?;
+        let range = |range: &[u8]| -> Option<(u32, u32)> {
+            let range = range.to_str().ok()?;
+            Some(match range.split_once(',') {
+                Some((start, lines)) => (start.parse().ok()?, lines.parse().ok()?),
+                None => (range.parse().ok()?, 1),
+            })
+        };
+        let (old_start, old_lines) = range(old)?;
+        let (new_start, new_lines) = range(new)?;
+        Some(Hunk {
+            old_start,
+            old_lines,
+            new_start,
+            new_lines,
+            section: section.trim_start().into(),
+            lines: Vec::new(),
+        })
+    }
+
+    fn parse_binary_hunk(lines: &mut Lines<'_>) -> Result<BinaryHunk, Error> {
+        let header_line = lines.number();
+        let header = trim_newline(lines.peek().ok_or(Error::BinaryPatch { line: header_line })?);
+        let (kind, size) = if let Some(size) = header.strip_prefix(b"literal ") {
+            (BinaryKind::Literal, size)
+        } else if let Some(size) = header.strip_prefix(b"delta ") {
+            (BinaryKind::Delta, size)
+        } else {
+            return Err(Error::BinaryPatch { line: header_line });
+        };
+        let size = size
+            .to_str()
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .ok_or(Error::BinaryPatch { line: header_line })?;
+        lines.pos += 1;
+
+        let mut data = Vec::new();
+        loop {
+            let line_number = lines.number();
+            let line = trim_newline(lines.peek().ok_or(Error::BinaryPatch { line: header_line })?);
+            lines.pos += 1;
+            let Some((&len, encoded)) = line.split_first() else {
+                break;
+            };
+            let len = match len {
+                b'A'..=b'Z' => len - b'A' + 1,
+                b'a'..=b'z' => len - b'a' + 27,
+                _ => return Err(Error::BinaryPatch { line: line_number }),
+            } as usize;
+            let decoded = decode_base85(encoded).ok_or(Error::BinaryPatch { line: line_number })?;
+            if decoded.len() < len {
+                return Err(Error::BinaryPatch { line: line_number });
+            }
+            data.extend_from_slice(&decoded[..len]);
+        }
+        Ok(BinaryHunk { kind, size, data })
+    }
+
+    fn decode_base85(encoded: &[u8]) -> Option<Vec<u8>> {
+        const ALPHABET: &[u8; 85] =
+            b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+        if encoded.len() % 5 != 0 {
+            return None;
+        }
+        let mut out = Vec::with_capacity(encoded.len() / 5 * 4);
+        for group in encoded.chunks(5) {
+            let mut acc = 0u32;
+            for byte in group {
+                let value = ALPHABET.iter().position(|c| c == byte)? as u32;
+                acc = acc.checked_mul(85)?.checked_add(value)?;
+            }
+            out.extend_from_slice(&acc.to_be_bytes());
+        }
+        Some(out)
+    }
+
+    fn parse_mode(value: &[u8]) -> Option<EntryMode> {
+        u16::from_str_radix(value.trim().to_str().ok()?, 8).ok().map(EntryMode)
+    }
+
+    fn trim_newline(line: &[u8]) -> &[u8] {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        line.strip_suffix(b"\r").unwrap_or(line)
+    }
+
+    /// Return the path in a `---` or `+++` line, or `None` if it is `/dev/null`.
+    fn diff_path(value: &[u8], strip_count: usize, line: usize) -> Result<Option<BString>, Error> {
+        let value = trim_newline(value);
+        let path = if value.starts_with(b"\"") {
+            match unquote(value) {
+                Some((path, _)) => path,
+                None => value.into(),
+            }
+        } else {
+            // Traditional diffs may have a timestamp after the path, separated by a tab.
+            value.split_str("\t").next().unwrap_or(value).into()
+        };
+        if path == "/dev/null" {
+            return Ok(None);
+        }
+        strip(path, strip_count, line).map(Some)
+    }
+
+    /// Return the old and new path of `diff --git <old> <new>`, which is only unambiguous if they are quoted or the same.
+    fn git_header_names(names: &[u8]) -> Option<(BString, BString)> {
+        if names.starts_with(b"\"") {
+            let (old, consumed) = unquote(names)?;
+            let rest = names[consumed..].strip_prefix(b" ")?;
+            let new = if rest.starts_with(b"\"") {
+                unquote(rest)?.0
+            } else {
+                rest.into()
+            };
+            return Some((old, new));
+        }
+        if let Some(pos) = names.rfind(b" \"") {
+            let (new, _) = unquote(&names[pos + 1..])?;
+            return Some((names[..pos].into(), new));
+        }
+        if names.len() % 2 == 1 {
+            let half = names.len() / 2;
+            let (old, new) = (&names[..half], &names[half + 1..]);
+            let without_prefix = |name: &[u8]| name.find_byte(b'/').map(|pos| name[pos + 1..].to_owned());
+            if names[half] == b' ' && without_prefix(old) == without_prefix(new) {
+                return Some((old.into(), new.into()));
+            }
+        }
+        None
+    }
+
+    fn unquote_path(value: &[u8]) -> BString {
+        match unquote(value) {
+            Some((path, _)) => path,
+            None => value.into(),
+        }
+    }
+
+    /// Unquote a C-style quoted string at the beginning of `input`, returning it along with the amount of bytes consumed,
+    /// or `None` if it isn't quoted or malformed.
+    fn unquote(input: &[u8]) -> Option<(BString, usize)> {
+        if !input.starts_with(b"\"") {
+            return None;
+        }
+        let (unquoted, consumed) = gix_quote::ansi_c::undo(input.as_bstr()).ok()?;
+        Some((unquoted.into_owned(), consumed))
+    }
+
+    fn strip(path: impl Into<BString>, count: usize, line: usize) -> Result<BString, Error> {
+        let path = path.into();
+        let mut rest: &BStr = path.as_bstr();
+        for _ in 0..count {
+            match rest.find_byte(b'/') {
+                Some(pos) => rest = rest[pos + 1..].as_bstr(),
+                None => {
+                    return Err(Error::Strip {
+                        path,
+                        strip: count,
+                        line,
+                    })
+                }
+            }
+        }
+        Ok(rest.to_owned())
+    }
+}
@@ -0,0 +1,132 @@
+use std::io::Write;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::patch::{BinaryHunk, BinaryKind, Content, File, Operation};
+
+impl File {
+    /// Write this patch in the format of `git diff` to `out`, starting with its `diff --git` line, so that
+    /// [`parse()`](crate::patch::parse()) with default options yields it again.
+    ///
+    /// Paths get the `a/` and `b/` prefixes, and are quoted if they contain special characters, or bytes outside of ASCII
+    /// if `quote_path` is `true`, which is what `core.quotePath` controls.
+    pub fn write_to(&self, mut out: impl Write, quote_path: bool) -> std::io::Result<()> {
+        let old_path = self.old_path.as_ref().or(self.new_path.as_ref()).map(|p| p.as_bstr());
+        let new_path = self.new_path.as_ref().or(self.old_path.as_ref()).map(|p| p.as_bstr());
+        let (old_path, new_path) = (
+            old_path.expect("at least one path is always set"),
+            new_path.expect("at least one path is always set"),
+        );
+        writeln!(
+            out,
+            "diff --git {} {}",
+            quoted("a/", old_path, quote_path),
+            quoted("b/", new_path, quote_path)
+        )?;
+        let mode = |mode: Option<gix_object::tree::EntryMode>| format!("{:06o}", mode.map_or(0o100644, |m| m.0));
+        match self.operation {
+            Operation::Addition => writeln!(out, "new file mode {}", mode(self.new_mode))?,
+            Operation::Deletion => writeln!(out, "deleted file mode {}", mode(self.old_mode))?,
+            Operation::Modification | Operation::Rename | Operation::Copy => {
+                if self.old_mode.is_some() && self.new_mode.is_some() && self.old_mode != self.new_mode {
+                    writeln!(out, "old mode {}", mode(self.old_mode))?;
+                    writeln!(out, "new mode {}", mode(self.new_mode))?;
+                }
+                if let Some(similarity) = self.similarity {
+                    writeln!(out, "similarity index {similarity}%")?;
+                }
+                let kind = match self.operation {
+                    Operation::Rename => Some("rename"),
+                    Operation::Copy => Some("copy"),
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    writeln!(out, "{kind} from {}", quoted("", old_path, quote_path))?;
+                    writeln!(out, "{kind} to {}", quoted("", new_path, quote_path))?;
+                }
+            }
+        }
+        if let (Some(old_id), Some(new_id)) = (&self.old_id, &self.new_id) {
+            write!(out, "index {old_id}..{new_id}")?;
+            if !matches!(self.operation, Operation::Addition | Operation::Deletion) && self.old_mode == self.new_mode {
+                if let Some(mode) = self.new_mode {
+                    write!(out, " {:06o}", mode.0)?;
+                }
+            }
+            out.write_all(b"\n")?;
+        }
+
+        let old_name = match self.operation {
+            Operation::Addition => "/dev/null".into(),
+            _ => quoted("a/", old_path, quote_path),
+        };
+        let new_name = match self.operation {
+            Operation::Deletion => "/dev/null".into(),
+            _ => quoted("b/", new_path, quote_path),
+        };
+        match &self.content {
+            Content::Text(hunks) => {
+                if !hunks.is_empty() {
+                    writeln!(out, "--- {old_name}")?;
+                    writeln!(out, "+++ {new_name}")?;
+                }
+                for hunk in hunks {
+                    hunk.write_to(&mut out)?;
+                }
+            }
+            Content::Binary { forward, reverse } => {
+                out.write_all(b"GIT binary patch\n")?;
+                forward.write_to(&mut out)?;
+                if let Some(reverse) = reverse {
+                    reverse.write_to(&mut out)?;
+                }
+            }
+            Content::BinaryWithoutData => writeln!(out, "Binary files {old_name} and {new_name} differ")?,
+        }
+        Ok(())
+    }
+}
+
+impl BinaryHunk {
+    /// Write this hunk as it appears after `GIT binary patch` to `out`, including its header and the empty line that ends it.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        let kind = match self.kind {
+            BinaryKind::Literal => "literal",
+            BinaryKind::Delta => "delta",
+        };
+        writeln!(out, "{kind} {}", self.size)?;
+        for chunk in self.data.chunks(52) {
+            let len = chunk.len() as u8;
+            out.write_all(&[if len <= 26 { b'A' + len - 1 } else { b'a' + len - 27 }])?;
+            out.write_all(&encode_base85(chunk))?;
+            out.write_all(b"\n")?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+fn encode_base85(data: &[u8]) -> Vec<u8> {
+    const ALPHABET: &[u8; 85] =
+        b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+    let mut out = Vec::with_capacity((data.len() + 3) / 4 * 5);
+    for group in data.chunks(4) {
+        let mut bytes = [0u8; 4];
+        bytes[..group.len()].copy_from_slice(group);
+        let mut acc = u32::from_be_bytes(bytes);
+        let mut encoded = [0u8; 5];
+        for slot in encoded.iter_mut().rev() {
+            *slot = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&encoded);
+    }
+    out
+}
+
+/// Return `prefix` and `path` as is, or quoted in the C-style like `git` does if `path` contains special characters,
+/// or bytes outside of ASCII and `quote_path` is set.
+fn quoted(prefix: &str, path: &BStr, quote_path: bool) -> BString {
+    let mut out = BString::from(prefix);
+    out.extend_from_slice(path);
+    gix_quote::ansi_c::quote(out.as_ref(), quote_path).into_owned()
+}
@@ -534,6 +534,28 @@ pub(crate) mod convert_to_diffable {
         Ok(())
     }
 
+    #[test]
+    fn binary_by_attribute() {
+        use gix_filter::attributes::StateRef;
+        let driver = |name: &str, is_binary| gix_diff::blob::Driver {
+            name: name.into(),
+            is_binary,
+            ..Default::default()
+        };
+        let drivers = [driver("auto", None), driver("bin", Some(true)), driver("text", Some(false))];
+        for (state, expected) in [
+            (StateRef::Unset, (None, Some(true))),
+            (StateRef::Set, (None, Some(false))),
+            (StateRef::Unspecified, (None, None)),
+            (StateRef::Value("auto".into()), (Some(0), None)),
+            (StateRef::Value("bin".into()), (Some(1), Some(true))),
+            (StateRef::Value("text".into()), (Some(2), Some(false))),
+            (StateRef::Value("missing".into()), (None, None)),
+        ] {
+            assert_eq!(pipeline::is_binary_by_attribute(state, &drivers), expected, "{state:?}");
+        }
+    }
+
     #[test]
     fn with_driver() -> crate::Result {
         let root = gix_testtools::scripted_fixture_read_only_standalone("make_blob_repo.sh")?;
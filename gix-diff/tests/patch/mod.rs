@@ -0,0 +1,463 @@
+use gix_diff::patch::{self, apply, BinaryKind, Content, Line, Operation};
+use gix_object::{bstr::ByteSlice, tree::EntryKind};
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn modification_addition_deletion_and_mode_change() -> crate::Result {
+        let files = patch::parse(
+            b"preamble, like an email, is ignored
+diff --git a/modified b/modified
+index 257cc56..3bd1f0e 100644
+--- a/modified
++++ b/modified
+@@ -1,3 +1,3 @@ fn section()
+ one
+-two
++2
+ three
+diff --git a/dir/added b/dir/added
+new file mode 100755
+index 0000000..d00491f
+--- /dev/null
++++ b/dir/added
+@@ -0,0 +1 @@
++1
+\\ No newline at end of file
+diff --git a/deleted b/deleted
+deleted file mode 100644
+index d00491f..0000000
+--- a/deleted
++++ /dev/null
+@@ -1 +0,0 @@
+-1
+diff --git a/exe b/exe
+old mode 100644
+new mode 100755
+--
+2.45.0
+",
+            Default::default(),
+        )?;
+        assert_eq!(files.len(), 4);
+
+        let modified = &files[0];
+        assert_eq!(modified.operation, Operation::Modification);
+        assert_eq!(modified.old_path.as_ref().expect("set"), "modified");
+        assert_eq!(modified.new_path.as_ref().expect("set"), "modified");
+        assert_eq!(modified.old_id.as_ref().expect("set"), "257cc56");
+        assert_eq!(modified.new_id.as_ref().expect("set"), "3bd1f0e");
+        assert_eq!(modified.new_mode, Some(EntryKind::Blob.into()));
+        let Content::Text(hunks) = &modified.content else {
+            unreachable!("text")
+        };
+        assert_eq!(hunks.len(), 1);
+        let hunk = &hunks[0];
+        assert_eq!(
+            (hunk.old_start, hunk.old_lines, hunk.new_start, hunk.new_lines),
+            (1, 3, 1, 3)
+        );
+        assert_eq!(hunk.section, "fn section()");
+        assert_eq!(
+            hunk.lines,
+            [
+                Line::Context("one\n".into()),
+                Line::Removal("two\n".into()),
+                Line::Addition("2\n".into()),
+                Line::Context("three\n".into()),
+            ]
+        );
+
+        let added = &files[1];
+        assert_eq!(added.operation, Operation::Addition);
+        assert_eq!(added.old_path, None);
+        assert_eq!(added.path(), "dir/added");
+        assert_eq!(added.new_mode, Some(EntryKind::BlobExecutable.into()));
+        let Content::Text(hunks) = &added.content else {
+            unreachable!("text")
+        };
+        assert_eq!(
+            hunks[0].lines,
+            [Line::Addition("1".into())],
+            "the missing newline is reflected in the line"
+        );
+
+        let deleted = &files[2];
+        assert_eq!(deleted.operation, Operation::Deletion);
+        assert_eq!(deleted.new_path, None);
+        assert_eq!(deleted.path(), "deleted");
+
+        let mode_change = &files[3];
+        assert_eq!(mode_change.operation, Operation::Modification);
+        assert_eq!(mode_change.path(), "exe");
+        assert_eq!(mode_change.old_mode, Some(EntryKind::Blob.into()));
+        assert_eq!(mode_change.new_mode, Some(EntryKind::BlobExecutable.into()));
+        assert_eq!(mode_change.content, Content::Text(Vec::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn renames_copies_and_quoted_paths() -> crate::Result {
+        let files = patch::parse(
+            b"diff --git a/old name b/new name
+similarity index 90%
+rename from old name
+rename to new name
+index 257cc56..3bd1f0e 100644
+--- a/old name
++++ b/new name
+@@ -1 +1 @@
+-a
++b
+diff --git \"a/tab\\there\" \"b/caf\\303\\251\"
+similarity index 100%
+copy from \"tab\\there\"
+copy to \"caf\\303\\251\"
+",
+            Default::default(),
+        )?;
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].operation, Operation::Rename);
+        assert_eq!(files[0].old_path.as_ref().expect("set"), "old name");
+        assert_eq!(files[0].new_path.as_ref().expect("set"), "new name");
+        assert_eq!(files[0].similarity, Some(90));
+
+        assert_eq!(files[1].operation, Operation::Copy);
+        assert_eq!(files[1].old_path.as_ref().expect("set"), "tab\there");
+        assert_eq!(files[1].new_path.as_ref().expect("set"), "café");
+        assert_eq!(files[1].similarity, Some(100));
+        assert_eq!(files[1].content, Content::Text(Vec::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn binary_patches() -> crate::Result {
+        let files = patch::parse(
+            b"diff --git a/small b/small
+index 20b5be91886d0b6f26dc98a225c0dac05fe2c86e..6ebbd4f1c8eb8313a39119770223e261d41a1adc 100644
+GIT binary patch
+literal 5
+Mcmb<msAQ-D00cq-YybcN
+
+literal 3
+KcmYdfNCE%>hycU@
+
+diff --git a/other b/other
+index 1111111..2222222 100644
+Binary files a/other and b/other differ
+",
+            Default::default(),
+        )?;
+        assert_eq!(files.len(), 2);
+        let Content::Binary { forward, reverse } = &files[0].content else {
+            unreachable!("binary")
+        };
+        assert_eq!(forward.kind, BinaryKind::Literal);
+        assert_eq!(forward.size, 5);
+        assert_eq!(forward.data.len(), 13, "the data is still compressed");
+        assert_eq!(forward.data[0], 0x78, "it's a zlib stream");
+        let reverse = reverse.as_ref().expect("present");
+        assert_eq!(reverse.size, 3);
+        assert_eq!(files[1].content, Content::BinaryWithoutData);
+        Ok(())
+    }
+
+    #[test]
+    fn traditional_diffs_and_stripping() -> crate::Result {
+        let input = b"--- project.orig/src/file.c\t2024-01-01 00:00:00
++++ project/src/file.c\t2024-01-02 00:00:00
+@@ -1,2 +1,2 @@
+ a
+-b
++c
+";
+        let files = patch::parse(input, Default::default())?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].operation, Operation::Modification);
+        assert_eq!(
+            files[0].old_path.as_ref().expect("set"),
+            "src/file.c",
+            "the new path is used as the old one often is a backup"
+        );
+        assert_eq!(files[0].new_path.as_ref().expect("set"), "src/file.c");
+
+        let files = patch::parse(input, patch::parse::Options { strip: 0 })?;
+        assert_eq!(files[0].path(), "project/src/file.c");
+        assert!(matches!(
+            patch::parse(input, patch::parse::Options { strip: 3 }),
+            Err(patch::parse::Error::Strip { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn malformed_hunks_are_errors() {
+        assert!(matches!(
+            patch::parse(b"--- a/f\n+++ b/f\n@@ -1,2 +1,2 @@\n a\n", Default::default()),
+            Err(patch::parse::Error::TruncatedHunk { line: 3 })
+        ));
+        assert!(matches!(
+            patch::parse(b"--- a/f\n+++ b/f\n@@ -1 +1 @@\n*a\n", Default::default()),
+            Err(patch::parse::Error::HunkLine { line: 4 })
+        ));
+        assert!(matches!(
+            patch::parse(b"--- a/f\n+++ b/f\n@@ -x +1 @@\n", Default::default()),
+            Err(patch::parse::Error::HunkHeader { line: 3 })
+        ));
+    }
+
+    #[test]
+    fn hunks_round_trip() -> crate::Result {
+        let input = b"--- a/f
++++ b/f
+@@ -1,2 +1,2 @@ section
+ a
+-b
+\\ No newline at end of file
++c
+\\ No newline at end of file
+";
+        let files = patch::parse(input, Default::default())?;
+        let Content::Text(hunks) = &files[0].content else {
+            unreachable!("text")
+        };
+        let mut out = Vec::new();
+        hunks[0].write_to(&mut out)?;
+        assert_eq!(out.as_bstr(), input[16..].as_bstr());
+        Ok(())
+    }
+}
+
+mod apply_text {
+    use super::*;
+
+    fn parse_hunks(patch: &str) -> Vec<patch::Hunk> {
+        let mut files =
+            patch::parse(format!("--- a/f\n+++ b/f\n{patch}").as_bytes(), Default::default()).expect("valid");
+        match files.pop().expect("one file").content {
+            Content::Text(hunks) => hunks,
+            _ => unreachable!("text"),
+        }
+    }
+
+    #[test]
+    fn hunks_apply_at_their_position_or_with_offset() {
+        let hunks = parse_hunks("@@ -2,3 +2,3 @@\n 2\n-3\n+three\n 4\n@@ -7,2 +7,3 @@\n 7\n+7.5\n 8\n");
+        let outcome = apply::text(b"1\n2\n3\n4\n5\n6\n7\n8\n", &hunks);
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.data.as_bstr(), "1\n2\nthree\n4\n5\n6\n7\n7.5\n8\n");
+
+        let outcome = apply::text(b"0\n0\n1\n2\n3\n4\n5\n6\n7\n8\n", &hunks);
+        assert!(outcome.is_complete(), "lines were added before, shifting all hunks");
+        assert_eq!(outcome.data.as_bstr(), "0\n0\n1\n2\nthree\n4\n5\n6\n7\n7.5\n8\n");
+    }
+
+    #[test]
+    fn mismatching_hunks_are_rejected_while_others_apply() {
+        let hunks = parse_hunks("@@ -1,2 +1,2 @@\n-1\n+one\n 2\n@@ -5,2 +5,2 @@\n 5\n-6\n+six\n");
+        let outcome = apply::text(b"1\n2\n3\n4\n5\nSIX\n", &hunks);
+        assert_eq!(outcome.rejected, [1]);
+        assert_eq!(outcome.data.as_bstr(), "one\n2\n3\n4\n5\nSIX\n");
+    }
+
+    #[test]
+    fn hunks_without_context_are_anchored() {
+        let hunks = parse_hunks("@@ -1 +1,2 @@\n+0\n 1\n");
+        assert_eq!(
+            apply::text(b"1\n1\n", &hunks).data.as_bstr(),
+            "0\n1\n1\n",
+            "it has no leading context and starts at the first line"
+        );
+        let outcome = apply::text(b"x\n1\n", &hunks);
+        assert_eq!(outcome.rejected, [0], "so it must match at the beginning");
+
+        let hunks = parse_hunks("@@ -2 +2,2 @@\n 2\n+3\n");
+        let outcome = apply::text(b"1\n2\n2\n", &hunks);
+        assert_eq!(
+            outcome.data.as_bstr(),
+            "1\n2\n2\n3\n",
+            "without trailing context it must match at the end"
+        );
+    }
+
+    #[test]
+    fn missing_trailing_newlines() {
+        let hunks = parse_hunks("@@ -1 +1 @@\n-a\n\\ No newline at end of file\n+a\n");
+        let outcome = apply::text(b"a", &hunks);
+        assert!(outcome.is_complete());
+        assert_eq!(outcome.data.as_bstr(), "a\n");
+        assert_eq!(apply::text(b"a\n", &hunks).rejected, [0]);
+    }
+
+    #[test]
+    fn creation_from_nothing() {
+        let hunks = parse_hunks("@@ -0,0 +1,2 @@\n+a\n+b\n");
+        assert_eq!(apply::text(b"", &hunks).data.as_bstr(), "a\nb\n");
+    }
+}
+
+mod apply_delta {
+    use gix_diff::patch::apply;
+
+    #[test]
+    fn copy_and_insert() -> crate::Result {
+        // base size 5, result size 8, copy 5 bytes at offset 0, insert 3 bytes
+        let delta = [5, 8, 0x80 | 0x10, 5, 3, b'x', b'y', b'z'];
+        assert_eq!(apply::delta(b"hello", &delta)?, b"helloxyz");
+        assert!(matches!(
+            apply::delta(b"hi", &delta),
+            Err(apply::delta::Error::BaseSize { expected: 5, actual: 2 })
+        ));
+        assert!(matches!(
+            apply::delta(b"hello", &[5, 8, 0x80 | 0x01 | 0x10, 3, 5]),
+            Err(apply::delta::Error::Malformed)
+        ));
+        Ok(())
+    }
+}
+
+mod write {
+    use super::*;
+
+    #[test]
+    fn files_round_trip() -> crate::Result {
+        let input = "diff --git a/modified b/modified
+index 257cc56..3bd1f0e 100644
+--- a/modified
++++ b/modified
+@@ -1,3 +1,3 @@ fn section()
+ one
+-two
++2
+ three
+diff --git a/dir/added b/dir/added
+new file mode 100755
+index 0000000..d00491f
+--- /dev/null
++++ b/dir/added
+@@ -0,0 +1 @@
++1
+\\ No newline at end of file
+diff --git a/deleted b/deleted
+deleted file mode 100644
+index d00491f..0000000
+--- a/deleted
++++ /dev/null
+@@ -1 +0,0 @@
+-1
+diff --git a/exe b/exe
+old mode 100644
+new mode 100755
+diff --git a/old name b/new name
+similarity index 90%
+rename from old name
+rename to new name
+index 257cc56..3bd1f0e 100644
+--- a/old name
++++ b/new name
+@@ -1 +1 @@
+-a
++b
+diff --git \"a/tab\\there\" \"b/caf\\303\\251\"
+similarity index 100%
+copy from \"tab\\there\"
+copy to \"caf\\303\\251\"
+diff --git a/small b/small
+index 20b5be91886d0b6f26dc98a225c0dac05fe2c86e..6ebbd4f1c8eb8313a39119770223e261d41a1adc 100644
+GIT binary patch
+literal 5
+Mcmb<msAQ-D00cq-YybcN
+
+literal 3
+KcmYdfNCE%>hycU@
+
+diff --git a/other b/other
+index 1111111..2222222 100644
+Binary files a/other and b/other differ
+";
+        let files = patch::parse(input.as_bytes(), Default::default())?;
+        assert_eq!(files.len(), 8);
+        let mut out = Vec::new();
+        for file in &files {
+            file.write_to(&mut out, true)?;
+        }
+        assert_eq!(out.as_bstr(), input);
+
+        let mut out = Vec::new();
+        for file in &files {
+            file.write_to(&mut out, false)?;
+        }
+        let out = out.as_bstr();
+        assert!(
+            out.contains_str(
+                "diff --git \"a/tab\\there\" b/café\nsimilarity index 100%\ncopy from \"tab\\there\"\ncopy to café\n"
+            ),
+            "without `core.quotePath`, paths are only quoted if they contain special characters: {out}"
+        );
+        Ok(())
+    }
+}
+
+mod create {
+    use gix_diff::patch::create::{hunks, Options};
+
+    use super::*;
+
+    fn write(hunks: &[patch::Hunk]) -> crate::Result<String> {
+        let mut out = Vec::new();
+        for hunk in hunks {
+            hunk.write_to(&mut out)?;
+        }
+        Ok(String::from_utf8(out)?)
+    }
+
+    #[test]
+    fn nearby_changes_share_a_hunk_with_context_and_section() -> crate::Result {
+        let old = "fn main() {\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16\n17\n}\n";
+        let new = "fn main() {\n1\n2\n3\n4\n5\nsix\n7\n8\n9\n10\n11\n12\nthirteen\n14\n15\n16\n17\n}\n";
+        assert_eq!(
+            write(&hunks(old.as_bytes(), new.as_bytes(), Options::default()))?,
+            "@@ -4,14 +4,14 @@ fn main() {\n 3\n 4\n 5\n-6\n+six\n 7\n 8\n 9\n 10\n 11\n 12\n-13\n+thirteen\n 14\n 15\n 16\n"
+        );
+
+        let separate = hunks(
+            old.as_bytes(),
+            new.as_bytes(),
+            Options {
+                context_lines: 1,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            write(&separate)?,
+            "@@ -6,3 +6,3 @@ fn main() {\n 5\n-6\n+six\n 7\n@@ -13,3 +13,3 @@ fn main() {\n 12\n-13\n+thirteen\n 14\n"
+        );
+        assert!(hunks(old.as_bytes(), old.as_bytes(), Options::default()).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn additions_deletions_and_missing_newlines() -> crate::Result {
+        assert_eq!(
+            write(&hunks(b"", b"a\nb", Options::default()))?,
+            "@@ -0,0 +1,2 @@\n+a\n+b\n\\ No newline at end of file\n"
+        );
+        assert_eq!(write(&hunks(b"a\n", b"", Options::default()))?, "@@ -1 +0,0 @@\n-a\n");
+        assert_eq!(
+            write(&hunks(b"a\nb", b"a\nb\n", Options::default()))?,
+            "@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn created_hunks_apply() {
+        let old = b"1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = b"0\n1\n2\nthree\n4\n5\n6\n7\n8\n10\n11\n";
+        let hunks = hunks(old, new, Options::default());
+        let outcome = apply::text(old, &hunks);
+        assert!(outcome.rejected.is_empty());
+        assert_eq!(outcome.data.as_bstr(), new.as_bstr());
+    }
+}
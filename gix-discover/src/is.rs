@@ -182,34 +182,26 @@ pub(crate) fn git_with_metadata(
     }
 
     let (common_dir, kind) = if git_dir_metadata.is_file() {
-        let common_dir = dot_git.join("commondir");
-        match crate::path::from_plain_file(&common_dir) {
+        match crate::path::common_dir(&dot_git) {
             Some(Err(err)) => {
                 return Err(crate::is_git::Error::MissingCommonDir {
-                    missing: common_dir,
+                    missing: dot_git.join("commondir"),
                     source: err,
                 })
             }
-            Some(Ok(common_dir)) => {
-                let common_dir = dot_git.join(common_dir);
-                (Cow::Owned(common_dir), Kind::LinkedWorkTreeDir)
-            }
+            Some(Ok(common_dir)) => (Cow::Owned(common_dir), Kind::LinkedWorkTreeDir),
             None => (dot_git.clone(), Kind::Submodule),
         }
     } else {
-        let common_dir = dot_git.join("commondir");
-        let worktree_and_common_dir = crate::path::from_plain_file(&common_dir)
+        let worktree_and_common_dir = crate::path::common_dir(&dot_git)
             .and_then(Result::ok)
-            .and_then(|cd| {
+            .and_then(|common_dir| {
                 crate::path::from_plain_file(&dot_git.join("gitdir"))
                     .and_then(Result::ok)
-                    .map(|worktree_gitfile| (crate::path::without_dot_git_dir(worktree_gitfile), cd))
+                    .map(|worktree_gitfile| (crate::path::without_dot_git_dir(worktree_gitfile), common_dir))
             });
         match worktree_and_common_dir {
-            Some((work_dir, common_dir)) => {
-                let common_dir = dot_git.join(common_dir);
-                (Cow::Owned(common_dir), Kind::WorkTreeGitDir { work_dir })
-            }
+            Some((work_dir, common_dir)) => (Cow::Owned(common_dir), Kind::WorkTreeGitDir { work_dir }),
             None => (dot_git.clone(), Kind::MaybeRepo),
         }
     };
@@ -59,6 +59,14 @@ pub fn from_gitdir_file(path: &std::path::Path) -> Result<PathBuf, from_gitdir_f
     Ok(gitdir)
 }
 
+/// Read the `commondir` file within `git_dir` as used by the private git directories of linked worktrees, and return the
+/// common git directory it points to, with relative paths joined to `git_dir`.
+///
+/// Returns `None` if there is no such file, meaning that `git_dir` is its own common directory.
+pub fn common_dir(git_dir: &std::path::Path) -> Option<std::io::Result<PathBuf>> {
+    from_plain_file(&git_dir.join("commondir")).map(|res| res.map(|common_dir| git_dir.join(common_dir)))
+}
+
 /// Conditionally pop a trailing `.git` dir if present.
 pub fn without_dot_git_dir(mut path: PathBuf) -> PathBuf {
     if path.file_name().and_then(std::ffi::OsStr::to_str) == Some(DOT_GIT_DIR) {
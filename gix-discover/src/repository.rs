@@ -21,11 +21,11 @@ pub enum Path {
 }
 
 mod path {
-    use std::path::PathBuf;
+    use std::{borrow::Cow, path::PathBuf};
 
     use crate::{
         path::without_dot_git_dir,
-        repository::{Kind, Path},
+        repository::{Classification, Kind, Path},
         DOT_GIT_DIR,
     };
 
@@ -93,6 +93,54 @@ mod path {
             }
         }
 
+        /// Classify this path more precisely than [`kind()`](Self::kind()) by looking at the git directory on disk,
+        /// which allows to tell linked worktrees from submodule checkouts, and submodule git directories from
+        /// (possibly) bare repositories.
+        pub fn classify(&self) -> Classification {
+            match self {
+                Path::LinkedWorkTree { work_dir: _, git_dir } => {
+                    if git_dir.join("commondir").is_file() {
+                        Classification::LinkedWorkTree
+                    } else {
+                        Classification::Submodule
+                    }
+                }
+                Path::WorkTree(_) => Classification::WorkTree,
+                Path::Repository(git_dir) => {
+                    if crate::is_submodule_git_dir(git_dir) {
+                        Classification::SubmoduleGitDir
+                    } else {
+                        Classification::PossiblyBare
+                    }
+                }
+            }
+        }
+
+        /// Return the common git directory of this path, which is the main git directory a linked worktree's private
+        /// git directory refers to via its `commondir` file, or the git directory itself otherwise.
+        ///
+        /// Submodule checkouts have their own git directory which is also their common directory, unless they are linked
+        /// worktrees of a submodule themselves.
+        pub fn common_dir(&self) -> std::io::Result<PathBuf> {
+            let git_dir = match self {
+                Path::WorkTree(work_dir) => Cow::Owned(work_dir.join(DOT_GIT_DIR)),
+                Path::LinkedWorkTree { work_dir: _, git_dir } | Path::Repository(git_dir) => Cow::Borrowed(git_dir),
+            };
+            crate::path::common_dir(&git_dir).unwrap_or_else(|| Ok(git_dir.into_owned()))
+        }
+
+        /// Consume and split this path into the location of the private `.git` directory, the common git directory it belongs to,
+        /// as well as an optional path to the work tree.
+        ///
+        /// The private and common git directories are the same unless this is a linked worktree.
+        pub fn into_repository_common_and_work_tree_directories(
+            self,
+        ) -> std::io::Result<(PathBuf, PathBuf, Option<PathBuf>)> {
+            let common_dir = self.common_dir()?;
+            let (git_dir, work_dir) = self.into_repository_and_work_tree_directories();
+            Ok((git_dir, common_dir, work_dir))
+        }
+
         /// Consume and split this path into the location of the `.git` directory as well as an optional path to the work tree.
         pub fn into_repository_and_work_tree_directories(self) -> (PathBuf, Option<PathBuf>) {
             match self {
@@ -104,6 +152,23 @@ mod path {
     }
 }
 
+/// What a discovered [repository path](Path) is, as determined by [`Path::classify()`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Classification {
+    /// A repository without a work tree, which is merely a guess as we didn't read the configuration yet.
+    PossiblyBare,
+    /// The main work tree of a repository, with the `.git` directory inside of it.
+    WorkTree,
+    /// A work tree added with `git worktree add`, whose `.git` file points to its private git directory
+    /// in the `worktrees/<name>` directory of the main git directory.
+    LinkedWorkTree,
+    /// The work tree of a submodule, whose `.git` file points to its git directory, typically in the
+    /// `.git/modules/**/<name>` directory of the parent repository.
+    Submodule,
+    /// The git directory of a submodule in the `.git/modules/**/<name>` directory tree of the parent repository.
+    SubmoduleGitDir,
+}
+
 /// The kind of repository path.
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Kind {
@@ -88,15 +88,16 @@ impl Options<'_> {
     ///
     /// The environment variables are:
     /// - `GIT_CEILING_DIRECTORIES` for `ceiling_dirs`
-    ///
-    /// Note that `GIT_DISCOVERY_ACROSS_FILESYSTEM` for `cross_fs` is **not** read,
-    /// as it requires parsing of `git-config` style boolean values.
-    // TODO: test
+    /// - `GIT_DISCOVERY_ACROSS_FILESYSTEM` for `cross_fs`, which is ignored if it isn't a valid boolean.
     pub fn apply_environment(mut self) -> Self {
-        let name = "GIT_CEILING_DIRECTORIES";
-        if let Some(ceiling_dirs) = env::var_os(name) {
+        if let Some(ceiling_dirs) = env::var_os("GIT_CEILING_DIRECTORIES") {
             self.ceiling_dirs = parse_ceiling_dirs(&ceiling_dirs);
         }
+        if let Some(cross_fs) = env::var_os("GIT_DISCOVERY_ACROSS_FILESYSTEM")
+            .and_then(|value| gix_config_value::Boolean::try_from(value).ok())
+        {
+            self.cross_fs = cross_fs.into();
+        }
         self
     }
 }
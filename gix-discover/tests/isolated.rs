@@ -164,3 +164,28 @@ fn assert_repo_is_current_workdir(path: gix_discover::repository::Path, work_dir
         work_dir,
     );
 }
+
+#[test]
+#[serial]
+fn options_from_environment() {
+    {
+        let _env = gix_testtools::Env::new()
+            .unset("GIT_CEILING_DIRECTORIES")
+            .unset("GIT_DISCOVERY_ACROSS_FILESYSTEM");
+        let opts = Options::default().apply_environment();
+        assert!(!opts.cross_fs, "the default is retained without environment variables");
+    }
+    {
+        let _env = gix_testtools::Env::new()
+            .set("GIT_CEILING_DIRECTORIES", "relative-is-ignored")
+            .set("GIT_DISCOVERY_ACROSS_FILESYSTEM", "yes");
+        let opts = Options::default().apply_environment();
+        assert!(opts.cross_fs, "git-style booleans are understood");
+        assert_eq!(opts.ceiling_dirs, Vec::<PathBuf>::new());
+    }
+    {
+        let _env = gix_testtools::Env::new().set("GIT_DISCOVERY_ACROSS_FILESYSTEM", "not-a-boolean");
+        let opts = Options::default().apply_environment();
+        assert!(!opts.cross_fs, "invalid values are ignored");
+    }
+}
@@ -43,3 +43,23 @@ mod from_git_dir_file {
         Ok(file)
     }
 }
+
+mod common_dir {
+    use std::path::Path;
+
+    #[test]
+    fn missing_commondir_file_yields_none() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        assert!(gix_discover::path::common_dir(dir.path()).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn relative_path_is_joined_with_git_dir() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        std::fs::write(dir.path().join("commondir"), "../..\n")?;
+        let common_dir = gix_discover::path::common_dir(dir.path()).expect("present")?;
+        assert_eq!(common_dir, dir.path().join(Path::new("../..")));
+        Ok(())
+    }
+}
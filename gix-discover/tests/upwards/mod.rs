@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use gix_discover::repository::Kind;
+use gix_discover::repository::{Classification, Kind};
 
 fn expected_trust() -> gix_sec::Trust {
     if std::env::var_os("GIX_TEST_EXPECT_REDUCED_TRUST").is_some() {
@@ -18,6 +18,7 @@ fn from_bare_git_dir() -> crate::Result {
     let (path, trust) = gix_discover::upwards(&dir)?;
     assert_eq!(path.as_ref(), dir, "the bare .git dir is directly returned");
     assert_eq!(path.kind(), Kind::PossiblyBare);
+    assert_eq!(path.classify(), Classification::PossiblyBare);
     assert_eq!(trust, expected_trust());
     Ok(())
 }
@@ -89,6 +90,12 @@ fn from_working_dir() -> crate::Result {
     let (path, trust) = gix_discover::upwards(&dir)?;
     assert_eq!(path.as_ref(), dir, "a working tree dir yields the git dir");
     assert_eq!(path.kind(), Kind::WorkTree { linked_git_dir: None });
+    assert_eq!(path.classify(), Classification::WorkTree);
+    assert_eq!(
+        path.common_dir()?,
+        dir.join(".git"),
+        "the common dir of the main worktree is its .git dir"
+    );
     assert_eq!(trust, expected_trust());
     Ok(())
 }
@@ -217,9 +224,15 @@ fn from_existing_worktree() -> crate::Result {
     ] {
         let (path, trust) = gix_discover::upwards(&discover_path)?;
         assert!(matches!(path, gix_discover::repository::Path::LinkedWorkTree { .. }));
+        assert_eq!(path.classify(), Classification::LinkedWorkTree);
 
         assert_eq!(trust, expected_trust());
-        let (git_dir, worktree) = path.into_repository_and_work_tree_directories();
+        let (git_dir, common_dir, worktree) = path.into_repository_common_and_work_tree_directories()?;
+        assert_eq!(
+            gix_path::realpath(&common_dir)?,
+            gix_path::realpath(git_dir.join("../.."))?,
+            "the common dir is read from the private git dir of the linked worktree"
+        );
         assert_eq!(
             git_dir.strip_prefix(gix_path::realpath(&top_level_repo).unwrap()),
             Ok(std::path::Path::new(expected_git_dir)),
@@ -386,6 +399,8 @@ mod dot_git_only {
 }
 
 mod submodules {
+    use gix_discover::repository::Classification;
+
     #[test]
     fn by_their_worktree_checkout() -> crate::Result {
         let dir = gix_testtools::scripted_fixture_read_only("make_submodules.sh")?;
@@ -405,6 +420,16 @@ mod submodules {
                 matches!(path, gix_discover::repository::Path::LinkedWorkTree{ref work_dir, ref git_dir} if work_dir == &submodule_m1_workdir && git_dir == &submodule_m1_gitdir),
                 "{path:?} should match {submodule_m1_workdir:?} {submodule_m1_gitdir:?}"
             );
+            assert_eq!(
+                path.classify(),
+                Classification::Submodule,
+                "submodule checkouts are told apart from linked worktrees"
+            );
+            assert_eq!(
+                path.common_dir()?,
+                submodule_m1_gitdir,
+                "the submodule git dir is its own common dir"
+            );
         }
         Ok(())
     }
@@ -420,6 +445,7 @@ mod submodules {
                 matches!(path, gix_discover::repository::Path::Repository(ref dir) if dir == &submodule_m1_gitdir),
                 "{path:?} should match {submodule_m1_gitdir:?}"
             );
+            assert_eq!(path.classify(), Classification::SubmoduleGitDir);
         }
         Ok(())
     }
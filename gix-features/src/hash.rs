@@ -3,6 +3,10 @@
 //! With the `fast-sha1` feature, the `Sha1` hash type will use a more elaborate implementation utilizing hardware support
 //! in case it is available. Otherwise the `rustsha1` feature should be set. `fast-sha1` will take precedence.
 //! Otherwise, a minimal yet performant implementation is used instead for a decent trade-off between compile times and run-time performance.
+//!
+//! With the `sha1-collision-detection` feature, [`Sha1`] additionally detects input that is part of a known
+//! collision attack, like `git` does with `sha1dc`. It's enabled by default then, but can be toggled at runtime
+//! with [`set_collision_detection()`].
 #[cfg(all(feature = "rustsha1", not(feature = "fast-sha1")))]
 mod _impl {
     use super::Sha1Digest;
@@ -20,6 +24,10 @@ mod _impl {
         pub fn digest(self) -> Sha1Digest {
             self.0.digest().bytes()
         }
+        /// Finalize the hash and produce a digest, which never fails as this implementation can't detect collisions.
+        pub fn try_digest(self) -> Result<Sha1Digest, super::CollisionError> {
+            Ok(self.digest())
+        }
     }
 }
 
@@ -46,11 +54,128 @@ mod _impl {
         pub fn digest(self) -> Sha1Digest {
             self.0.finalize().into()
         }
+        /// Finalize the hash and produce a digest, which never fails as this implementation can't detect collisions.
+        pub fn try_digest(self) -> Result<Sha1Digest, super::CollisionError> {
+            Ok(self.digest())
+        }
     }
 }
 
-#[cfg(any(feature = "rustsha1", feature = "fast-sha1"))]
+#[cfg(all(
+    feature = "sha1-collision-detection",
+    any(feature = "rustsha1", feature = "fast-sha1")
+))]
+mod checked {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use sha1_checked::{CollisionResult, Digest};
+
+    use super::{CollisionError, Sha1Digest};
+
+    static COLLISION_DETECTION: AtomicBool = AtomicBool::new(true);
+
+    /// Enable or disable collision detection for all [`Sha1`] instances created with [`Sha1::default()`] from now on.
+    ///
+    /// It's enabled unless disabled here.
+    pub fn set_collision_detection(enabled: bool) {
+        COLLISION_DETECTION.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Return `true` if [`Sha1::default()`] produces hashers with collision detection.
+    pub fn collision_detection() -> bool {
+        COLLISION_DETECTION.load(Ordering::Relaxed)
+    }
+
+    #[derive(Clone)]
+    enum Inner {
+        Plain(super::_impl::Sha1),
+        Checked(Box<sha1_checked::Sha1>),
+    }
+
+    /// A implementation of the Sha1 hash, which can be used once.
+    ///
+    /// Unless disabled, it detects input that is part of a known collision attack.
+    #[derive(Clone)]
+    pub struct Sha1(Inner);
+
+    impl Default for Sha1 {
+        fn default() -> Self {
+            Sha1::with_collision_detection(collision_detection())
+        }
+    }
+
+    impl Sha1 {
+        /// Create a new instance that detects collisions if `enabled` is `true`, independently of
+        /// the [global setting](set_collision_detection()).
+        pub fn with_collision_detection(enabled: bool) -> Self {
+            Sha1(if enabled {
+                Inner::Checked(Box::new(sha1_checked::Sha1::builder().safe_hash(true).build()))
+            } else {
+                Inner::Plain(Default::default())
+            })
+        }
+        /// Digest the given `bytes`.
+        pub fn update(&mut self, bytes: &[u8]) {
+            match &mut self.0 {
+                Inner::Plain(hash) => hash.update(bytes),
+                Inner::Checked(hash) => Digest::update(hash.as_mut(), bytes),
+            }
+        }
+        /// Finalize the hash and produce a digest.
+        ///
+        /// If a collision attack was detected, the digest is altered so it can't match the digest
+        /// of the object the input tries to impersonate.
+        pub fn digest(self) -> Sha1Digest {
+            self.try_digest().unwrap_or_else(|err| err.digest)
+        }
+        /// Finalize the hash and produce a digest, or fail if the input is part of a collision attack.
+        pub fn try_digest(self) -> Result<Sha1Digest, CollisionError> {
+            match self.0 {
+                Inner::Plain(hash) => hash.try_digest(),
+                Inner::Checked(hash) => match hash.try_finalize() {
+                    CollisionResult::Ok(digest) => Ok(digest.into()),
+                    CollisionResult::Mitigated(digest) | CollisionResult::Collision(digest) => {
+                        Err(CollisionError { digest: digest.into() })
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(all(
+    any(feature = "rustsha1", feature = "fast-sha1"),
+    not(feature = "sha1-collision-detection")
+))]
 pub use _impl::Sha1;
+#[cfg(all(
+    feature = "sha1-collision-detection",
+    any(feature = "rustsha1", feature = "fast-sha1")
+))]
+pub use checked::{collision_detection, set_collision_detection, Sha1};
+
+/// The error returned by [`Sha1::try_digest()`] if the hashed input is part of a collision attack.
+#[cfg(any(feature = "rustsha1", feature = "fast-sha1"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionError {
+    /// The digest that was computed with collision mitigation, and which differs from the one the input
+    /// tries to collide with.
+    pub digest: Sha1Digest,
+}
+
+#[cfg(any(feature = "rustsha1", feature = "fast-sha1"))]
+impl std::fmt::Display for CollisionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SHA-1 input is part of a collision attack, its mitigated hash is {}",
+            gix_hash::ObjectId::from(self.digest)
+        )
+    }
+}
+
+#[cfg(any(feature = "rustsha1", feature = "fast-sha1"))]
+impl std::error::Error for CollisionError {}
 
 /// Compute a CRC32 hash from the given `bytes`, returning the CRC32 hash.
 ///
@@ -1,9 +1,102 @@
 //! Utilities to cause interruptions in common traits, like Read/Write and Iterator.
 use std::{
     io,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
+/// A cloneable token to cooperatively cancel long-running operations, which is shared by all of its clones.
+///
+/// It dereferences to the [`AtomicBool`] that operations like pack indexing, traversals, checkouts or fetches
+/// take as `should_interrupt` parameter and poll regularly, so that triggering it from another thread makes them
+/// stop at the next opportunity.
+#[derive(Clone, Debug)]
+pub struct Token(Flag);
+
+#[derive(Clone, Debug)]
+enum Flag {
+    Owned(Arc<AtomicBool>),
+    Static(&'static AtomicBool),
+}
+
+impl Default for Token {
+    fn default() -> Self {
+        Token::new()
+    }
+}
+
+impl Token {
+    /// Create a new token that isn't triggered.
+    pub fn new() -> Self {
+        Token(Flag::Owned(Default::default()))
+    }
+
+    /// Create a token which uses `flag` as its state, typically a process-wide flag that is set by a signal handler.
+    pub fn from_static(flag: &'static AtomicBool) -> Self {
+        Token(Flag::Static(flag))
+    }
+
+    /// Request all operations observing this token to stop.
+    pub fn trigger(&self) {
+        self.flag().store(true, Ordering::SeqCst);
+    }
+
+    /// Return `true` if an interrupt was requested.
+    pub fn is_triggered(&self) -> bool {
+        self.flag().load(Ordering::Relaxed)
+    }
+
+    /// Clear a previous interrupt request so the token can be used for new operations.
+    pub fn reset(&self) {
+        self.flag().store(false, Ordering::SeqCst);
+    }
+
+    /// Return an error if an interrupt was requested, for use with `?` in loops.
+    pub fn check(&self) -> Result<(), Interrupted> {
+        if self.is_triggered() {
+            Err(Interrupted)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Return the flag to pass as `should_interrupt` to operations supporting interruption.
+    pub fn flag(&self) -> &AtomicBool {
+        match &self.0 {
+            Flag::Owned(flag) => flag,
+            Flag::Static(flag) => flag,
+        }
+    }
+}
+
+impl std::ops::Deref for Token {
+    type Target = AtomicBool;
+
+    fn deref(&self) -> &Self::Target {
+        self.flag()
+    }
+}
+
+/// The error returned by [`Token::check()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interrupted;
+
+impl std::fmt::Display for Interrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Interrupted")
+    }
+}
+
+impl std::error::Error for Interrupted {}
+
+impl From<Interrupted> for io::Error {
+    fn from(err: Interrupted) -> Self {
+        io::Error::new(io::ErrorKind::Other, err)
+    }
+}
+
 /// A wrapper for an inner iterator which will check for interruptions on each iteration, stopping the iteration when
 /// that is requested.
 pub struct Iter<'a, I> {
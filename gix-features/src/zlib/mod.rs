@@ -1,4 +1,98 @@
-pub use flate2::{Decompress, Status};
+pub use flate2::{Compression, Decompress, Status};
+
+/// The zlib implementation to use for compression, which can be chosen at runtime.
+///
+/// Note that only one of the C-based backends can be compiled in, as chosen by the `zlib-*` cargo features,
+/// while the pure-Rust `miniz_oxide` backend is always available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Backend {
+    /// The pure-Rust `miniz_oxide` implementation.
+    MinizOxide,
+    /// The C `zlib` library, available with the `zlib-stock` feature.
+    Zlib,
+    /// The C `zlib-ng` library, available with the `zlib-ng` or `zlib-ng-compat` features.
+    ZlibNg,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod backend {
+    use super::Backend;
+
+    /// The error returned when selecting a [`Backend`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Unknown zlib backend '{name}', expected one of 'miniz_oxide', 'zlib' or 'zlib-ng'")]
+        Unknown { name: String },
+        #[error("The zlib backend '{}' was not compiled in", backend.as_str())]
+        Unavailable { backend: Backend },
+    }
+}
+
+impl Backend {
+    /// Return the backend `flate2` was compiled with, which is also the default.
+    pub const fn compiled() -> Self {
+        if cfg!(any(feature = "zlib-ng", feature = "zlib-ng-compat")) {
+            Backend::ZlibNg
+        } else if cfg!(feature = "zlib-stock") {
+            Backend::Zlib
+        } else {
+            Backend::MinizOxide
+        }
+    }
+
+    /// Return `true` if this backend can be used in this build.
+    pub fn is_available(&self) -> bool {
+        *self == Backend::MinizOxide || *self == Self::compiled()
+    }
+
+    /// Return `self` if it is available, or an error otherwise.
+    pub fn try_available(self) -> Result<Self, backend::Error> {
+        if self.is_available() {
+            Ok(self)
+        } else {
+            Err(backend::Error::Unavailable { backend: self })
+        }
+    }
+
+    /// Return all backends that can be used in this build.
+    pub fn available() -> impl Iterator<Item = Backend> {
+        [Backend::MinizOxide, Backend::Zlib, Backend::ZlibNg]
+            .into_iter()
+            .filter(Backend::is_available)
+    }
+
+    /// Return the name of this backend, as understood by [`FromStr`](std::str::FromStr).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Backend::MinizOxide => "miniz_oxide",
+            Backend::Zlib => "zlib",
+            Backend::ZlibNg => "zlib-ng",
+        }
+    }
+}
+
+impl Default for Backend {
+    fn default() -> Self {
+        Self::compiled()
+    }
+}
+
+impl std::str::FromStr for Backend {
+    type Err = backend::Error;
+
+    /// Parse a backend name, without checking if it is available.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(match name {
+            "miniz_oxide" | "miniz-oxide" | "rust" => Backend::MinizOxide,
+            "zlib" => Backend::Zlib,
+            "zlib-ng" => Backend::ZlibNg,
+            _ => return Err(backend::Error::Unknown { name: name.into() }),
+        })
+    }
+}
 
 /// non-streaming interfaces for decompression
 pub mod inflate {
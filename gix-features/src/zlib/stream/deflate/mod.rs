@@ -1,4 +1,6 @@
-use flate2::Compress;
+use flate2::Compression;
+
+use crate::zlib::Backend;
 
 const BUF_SIZE: usize = 4096 * 8;
 
@@ -6,7 +8,7 @@ const BUF_SIZE: usize = 4096 * 8;
 ///
 /// Be sure to call `flush()` when done to finalize the deflate stream.
 pub struct Write<W> {
-    compressor: Compress,
+    compressor: impls::Compressor,
     inner: W,
     buf: [u8; BUF_SIZE],
 }
@@ -17,37 +19,156 @@ where
 {
     fn clone(&self) -> Self {
         Write {
-            compressor: impls::new_compress(),
+            compressor: impls::Compressor::new(self.compressor.backend(), self.compressor.level()),
             inner: self.inner.clone(),
             buf: self.buf,
         }
     }
 }
 
+impl<W> Write<W> {
+    /// Return the backend used for compression.
+    pub fn backend(&self) -> Backend {
+        self.compressor.backend()
+    }
+
+    /// Return the compression level.
+    pub fn level(&self) -> Compression {
+        self.compressor.level()
+    }
+}
+
 mod impls {
     use std::io;
 
     use flate2::{Compress, Compression, FlushCompress, Status};
+    use miniz_oxide::{
+        deflate::{core::CompressorOxide, stream::deflate},
+        DataFormat, MZError, MZFlush, MZStatus,
+    };
+
+    use crate::zlib::{backend, stream::deflate, Backend};
+
+    /// A compressor using one of the runtime-selectable [backends](Backend).
+    pub(crate) enum Compressor {
+        /// The backend `flate2` was compiled with.
+        Flate2 { compress: Compress, level: Compression },
+        /// `miniz_oxide` used directly, which is needed if `flate2` uses a C backend.
+        MinizOxide {
+            compress: Box<CompressorOxide>,
+            level: Compression,
+        },
+    }
 
-    use crate::zlib::stream::deflate;
+    impl Compressor {
+        /// `backend` must be available.
+        pub(crate) fn new(backend: Backend, level: Compression) -> Self {
+            if backend == Backend::compiled() {
+                Compressor::Flate2 {
+                    compress: Compress::new(level, true),
+                    level,
+                }
+            } else {
+                debug_assert_eq!(backend, Backend::MinizOxide, "BUG: unavailable backends are rejected");
+                let mut compress = Box::<CompressorOxide>::default();
+                compress.set_format_and_level(DataFormat::Zlib, level.level() as u8);
+                Compressor::MinizOxide { compress, level }
+            }
+        }
 
-    pub(crate) fn new_compress() -> Compress {
-        Compress::new(Compression::fast(), true)
+        pub(crate) fn backend(&self) -> Backend {
+            match self {
+                Compressor::Flate2 { .. } => Backend::compiled(),
+                Compressor::MinizOxide { .. } => Backend::MinizOxide,
+            }
+        }
+
+        pub(crate) fn level(&self) -> Compression {
+            match self {
+                Compressor::Flate2 { level, .. } | Compressor::MinizOxide { level, .. } => *level,
+            }
+        }
+
+        fn reset(&mut self) {
+            match self {
+                Compressor::Flate2 { compress, .. } => compress.reset(),
+                Compressor::MinizOxide { compress, level } => {
+                    compress.reset();
+                    compress.set_format_and_level(DataFormat::Zlib, level.level() as u8);
+                }
+            }
+        }
+
+        /// Compress `input` into `out`, returning `(consumed, written, stream_ended)`.
+        fn compress(&mut self, input: &[u8], out: &mut [u8], flush: FlushCompress) -> io::Result<(usize, usize, bool)> {
+            match self {
+                Compressor::Flate2 { compress, .. } => {
+                    let last_total_in = compress.total_in();
+                    let last_total_out = compress.total_out();
+                    let status = compress
+                        .compress(input, out, flush)
+                        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+                    Ok((
+                        (compress.total_in() - last_total_in) as usize,
+                        (compress.total_out() - last_total_out) as usize,
+                        status == Status::StreamEnd,
+                    ))
+                }
+                Compressor::MinizOxide { compress, .. } => {
+                    let flush = if flush == FlushCompress::Finish {
+                        MZFlush::Finish
+                    } else {
+                        MZFlush::None
+                    };
+                    let res = deflate(compress, input, out, flush);
+                    let stream_ended = match res.status {
+                        Ok(status) => status == MZStatus::StreamEnd,
+                        Err(MZError::Buf) => false,
+                        Err(err) => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::Other,
+                                format!("miniz_oxide failed to compress: {err:?}"),
+                            ))
+                        }
+                    };
+                    Ok((res.bytes_consumed, res.bytes_written, stream_ended))
+                }
+            }
+        }
     }
 
     impl<W> deflate::Write<W>
     where
         W: io::Write,
     {
-        /// Create a new instance writing compressed bytes to `inner`.
+        /// Create a new instance writing compressed bytes to `inner`, using the fastest compression level.
         pub fn new(inner: W) -> deflate::Write<W> {
+            Self::new_with_level(inner, Compression::fast())
+        }
+
+        /// Create a new instance writing compressed bytes to `inner`, compressing with the given `level`.
+        pub fn new_with_level(inner: W, level: Compression) -> deflate::Write<W> {
             deflate::Write {
-                compressor: new_compress(),
+                compressor: Compressor::new(Backend::compiled(), level),
                 inner,
                 buf: [0; deflate::BUF_SIZE],
             }
         }
 
+        /// Create a new instance writing compressed bytes to `inner`, compressing with the given `level`
+        /// using `backend`, or fail if `backend` isn't available in this build.
+        pub fn new_with_backend(
+            inner: W,
+            backend: Backend,
+            level: Compression,
+        ) -> Result<deflate::Write<W>, backend::Error> {
+            Ok(deflate::Write {
+                compressor: Compressor::new(backend.try_available()?, level),
+                inner,
+                buf: [0; deflate::BUF_SIZE],
+            })
+        }
+
         /// Reset the compressor, starting a new compression stream.
         ///
         /// That way multiple streams can be written to the same inner writer.
@@ -61,39 +182,24 @@ mod impls {
         }
 
         fn write_inner(&mut self, mut buf: &[u8], flush: FlushCompress) -> io::Result<usize> {
-            let total_in_when_start = self.compressor.total_in();
+            let mut total_consumed = 0;
             loop {
-                let last_total_in = self.compressor.total_in();
-                let last_total_out = self.compressor.total_out();
-
-                let status = self
-                    .compressor
-                    .compress(buf, &mut self.buf, flush)
-                    .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
-
-                let written = self.compressor.total_out() - last_total_out;
+                let (consumed, written, stream_ended) = self.compressor.compress(buf, &mut self.buf, flush)?;
                 if written > 0 {
-                    self.inner.write_all(&self.buf[..written as usize])?;
+                    self.inner.write_all(&self.buf[..written])?;
                 }
+                total_consumed += consumed;
+                buf = &buf[consumed..];
 
-                match status {
-                    Status::StreamEnd => return Ok((self.compressor.total_in() - total_in_when_start) as usize),
-                    Status::Ok | Status::BufError => {
-                        let consumed = self.compressor.total_in() - last_total_in;
-                        buf = &buf[consumed as usize..];
-
-                        // output buffer still makes progress
-                        if self.compressor.total_out() > last_total_out {
-                            continue;
-                        }
-                        // input still makes progress
-                        if self.compressor.total_in() > last_total_in {
-                            continue;
-                        }
-                        // input also makes no progress anymore, need more so leave with what we have
-                        return Ok((self.compressor.total_in() - total_in_when_start) as usize);
-                    }
+                if stream_ended {
+                    return Ok(total_consumed);
+                }
+                // output buffer or input still make progress
+                if written > 0 || consumed > 0 {
+                    continue;
                 }
+                // input also makes no progress anymore, need more so leave with what we have
+                return Ok(total_consumed);
             }
         }
     }
@@ -7,7 +7,7 @@ mod deflate_stream {
     use bstr::ByteSlice;
     use flate2::Decompress;
 
-    use crate::zlib::stream::deflate;
+    use crate::zlib::{stream::deflate, Backend, Compression};
 
     /// Provide streaming decompression using the `std::io::Read` trait.
     /// If `std::io::BufReader` is used, an allocation for the input buffer will be performed.
@@ -98,4 +98,68 @@ mod deflate_stream {
 
         assert_deflate_buffer(w.inner, bytes)
     }
+
+    #[test]
+    fn miniz_oxide_backend_at_all_levels() -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = include_bytes!(
+            "../../../../../gix-odb/tests/fixtures/objects/pack/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.pack"
+        );
+        for level in [0, 1, 6, 9] {
+            let mut w = deflate::Write::new_with_backend(Vec::new(), Backend::MinizOxide, Compression::new(level))?;
+            assert_eq!(w.backend(), Backend::MinizOxide);
+            assert_eq!(w.level().level(), level);
+            for chunk in bytes.chunks(4096 * 9) {
+                assert_eq!(w.write(chunk)?, chunk.len());
+            }
+            w.flush()?;
+            assert_deflate_buffer(w.inner, bytes)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn higher_levels_compress_better() -> Result<(), Box<dyn std::error::Error>> {
+        let bytes = (0..20_000).map(|n| (n * 7 % 1000).to_string()).collect::<String>();
+        let bytes = bytes.as_bytes();
+        let compressed_len = |level| -> std::io::Result<usize> {
+            let mut w = deflate::Write::new_with_level(Vec::new(), Compression::new(level));
+            w.write_all(bytes)?;
+            w.flush()?;
+            Ok(w.inner.len())
+        };
+        let stored = compressed_len(0)?;
+        assert!(stored > bytes.len(), "level 0 only stores the data");
+        assert!(compressed_len(9)? < compressed_len(1)?);
+        Ok(())
+    }
+
+    #[test]
+    fn reset_starts_a_new_stream_with_the_same_backend() -> Result<(), Box<dyn std::error::Error>> {
+        for backend in Backend::available() {
+            let mut w = deflate::Write::new_with_backend(Vec::new(), backend, Compression::best())?;
+            w.write_all(b"hello")?;
+            w.flush()?;
+            let first_len = w.inner.len();
+            w.reset();
+            w.write_all(b"hello")?;
+            w.flush()?;
+            assert_eq!(w.inner[..first_len], w.inner[first_len..], "{backend:?}");
+            assert_deflate_buffer(w.inner[first_len..].to_vec(), b"hello")?;
+            let clone = w.clone();
+            assert_eq!(clone.backend(), backend);
+            assert_eq!(clone.level(), Compression::best());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unavailable_backends_are_rejected() {
+        for backend in [Backend::MinizOxide, Backend::Zlib, Backend::ZlibNg] {
+            let res = deflate::Write::new_with_backend(Vec::new(), backend, Compression::default());
+            assert_eq!(res.is_ok(), backend.is_available(), "{backend:?}");
+            assert_eq!(backend.as_str().parse::<Backend>().expect("valid"), backend);
+        }
+        assert!(Backend::MinizOxide.is_available(), "always compiled in");
+        assert!("zstd".parse::<Backend>().is_err());
+    }
 }
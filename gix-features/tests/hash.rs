@@ -1,12 +1,12 @@
 use gix_features::hash::Sha1;
 
-#[cfg(not(feature = "fast-sha1"))]
+#[cfg(not(any(feature = "fast-sha1", feature = "sha1-collision-detection")))]
 #[test]
 fn size_of_sha1() {
     assert_eq!(std::mem::size_of::<Sha1>(), 96)
 }
 
-#[cfg(feature = "fast-sha1")]
+#[cfg(all(feature = "fast-sha1", not(feature = "sha1-collision-detection")))]
 #[test]
 fn size_of_sha1() {
     assert_eq!(
@@ -14,3 +14,10 @@ fn size_of_sha1() {
         if cfg!(target_arch = "x86") { 96 } else { 104 }
     )
 }
+
+#[test]
+fn try_digest_of_regular_input_matches_digest() {
+    let mut hash = Sha1::default();
+    hash.update(b"hello");
+    assert_eq!(hash.clone().try_digest(), Ok(hash.digest()));
+}
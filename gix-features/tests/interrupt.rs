@@ -0,0 +1,49 @@
+mod token {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use gix_features::interrupt::{Interrupted, Token};
+
+    #[test]
+    fn clones_share_their_state() {
+        let token = Token::new();
+        let clone = token.clone();
+        assert!(!clone.is_triggered());
+        assert_eq!(clone.check(), Ok(()));
+
+        token.trigger();
+        assert!(clone.is_triggered());
+        assert_eq!(clone.check(), Err(Interrupted));
+        assert!(clone.load(Ordering::Relaxed), "it derefs to the flag it is backed by");
+
+        clone.reset();
+        assert!(!token.is_triggered());
+    }
+
+    #[test]
+    fn static_flags_are_shared_with_their_token() {
+        static FLAG: AtomicBool = AtomicBool::new(false);
+        let token = Token::from_static(&FLAG);
+        FLAG.store(true, Ordering::SeqCst);
+        assert!(token.is_triggered());
+        token.reset();
+        assert!(!FLAG.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn operations_polling_the_flag_stop_once_triggered_from_another_thread() {
+        let token = Token::new();
+        let mut iter = gix_features::interrupt::Iter::new(0.., &token);
+        assert_eq!(iter.next(), Some(0));
+
+        std::thread::spawn({
+            let token = token.clone();
+            move || token.trigger()
+        })
+        .join()
+        .expect("no panic");
+        assert_eq!(iter.next(), None);
+
+        let err = std::io::Error::from(token.check().unwrap_err());
+        assert_eq!(err.to_string(), "Interrupted");
+    }
+}
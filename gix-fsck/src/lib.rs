@@ -7,6 +7,9 @@ use gix_hash::ObjectId;
 use gix_hashtable::HashSet;
 use gix_object::{tree::EntryKind, Exists, FindExt, Kind};
 
+/// Sanity checks of the content of objects, like the format of their headers, identities and tree entries.
+pub mod object;
+
 /// Perform a connectivity check.
 pub struct Connectivity<T, F>
 where
@@ -0,0 +1,444 @@
+use gix_hash::ObjectId;
+use gix_object::{bstr::ByteSlice, Kind};
+
+/// How serious a [`Problem`] is, as configured with `fsck.<msg-id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The problem isn't reported.
+    Ignore,
+    /// The problem is reported for information only, and shown as warning like `git` does.
+    Info,
+    /// The problem is reported as warning.
+    Warn,
+    /// The problem is reported as error, which makes the check fail.
+    Error,
+}
+
+impl Severity {
+    /// Parse `name` as it is used in `fsck.<msg-id>`, which is one of `error`, `warn` or `ignore` case-insensitively,
+    /// or return `None` if it isn't valid.
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_slice() {
+            b"error" => Severity::Error,
+            b"warn" => Severity::Warn,
+            b"ignore" => Severity::Ignore,
+            _ => return None,
+        })
+    }
+}
+
+macro_rules! message_ids {
+    ($($(#[$doc:meta])* $variant:ident => $name:literal, $severity:ident;)*) => {
+        /// The identifier of a kind of problem, as used in `fsck.<msg-id>` to configure its [`Severity`].
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        #[allow(missing_docs)]
+        pub enum MessageId {
+            $($(#[$doc])* $variant,)*
+        }
+
+        impl MessageId {
+            /// All message ids.
+            pub const ALL: &'static [MessageId] = &[$(MessageId::$variant,)*];
+
+            /// Return the name of the message id like `badDate`, as used in `fsck.<msg-id>` and in messages of `git fsck`.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(MessageId::$variant => $name,)*
+                }
+            }
+
+            /// Return the severity of the problem if it isn't configured otherwise.
+            pub fn default_severity(&self) -> Severity {
+                match self {
+                    $(MessageId::$variant => Severity::$severity,)*
+                }
+            }
+        }
+    };
+}
+
+message_ids! {
+    NulInHeader => "nulInHeader", Error;
+    UnterminatedHeader => "unterminatedHeader", Error;
+    BadDate => "badDate", Error;
+    BadDateOverflow => "badDateOverflow", Error;
+    BadEmail => "badEmail", Error;
+    BadName => "badName", Error;
+    BadObjectSha1 => "badObjectSha1", Error;
+    BadParentSha1 => "badParentSha1", Error;
+    BadTimezone => "badTimezone", Error;
+    BadTree => "badTree", Error;
+    BadTreeSha1 => "badTreeSha1", Error;
+    BadType => "badType", Error;
+    DuplicateEntries => "duplicateEntries", Error;
+    GitmodulesSymlink => "gitmodulesSymlink", Error;
+    MissingAuthor => "missingAuthor", Error;
+    MissingCommitter => "missingCommitter", Error;
+    MissingEmail => "missingEmail", Error;
+    MissingNameBeforeEmail => "missingNameBeforeEmail", Error;
+    MissingObject => "missingObject", Error;
+    MissingSpaceBeforeDate => "missingSpaceBeforeDate", Error;
+    MissingSpaceBeforeEmail => "missingSpaceBeforeEmail", Error;
+    MissingTag => "missingTag", Error;
+    MissingTagEntry => "missingTagEntry", Error;
+    MissingTree => "missingTree", Error;
+    MissingType => "missingType", Error;
+    MissingTypeEntry => "missingTypeEntry", Error;
+    MultipleAuthors => "multipleAuthors", Error;
+    TreeNotSorted => "treeNotSorted", Error;
+    ZeroPaddedDate => "zeroPaddedDate", Error;
+    BadFilemode => "badFilemode", Warn;
+    EmptyName => "emptyName", Warn;
+    FullPathname => "fullPathname", Warn;
+    HasDot => "hasDot", Warn;
+    HasDotdot => "hasDotdot", Warn;
+    HasDotgit => "hasDotgit", Warn;
+    NullSha1 => "nullSha1", Warn;
+    ZeroPaddedFilemode => "zeroPaddedFilemode", Warn;
+    NulInCommit => "nulInCommit", Warn;
+    BadTagName => "badTagName", Info;
+    MissingTaggerEntry => "missingTaggerEntry", Info;
+}
+
+impl MessageId {
+    /// Find the message id with the given `name` like `badDate`, case-insensitively as `git` does.
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|id| id.as_str().as_bytes().eq_ignore_ascii_case(name))
+            .copied()
+    }
+}
+
+impl std::fmt::Display for MessageId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A problem found in an object by [`check()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Problem {
+    /// The kind of problem.
+    pub id: MessageId,
+    /// A description of the problem like `git fsck` shows it.
+    pub message: String,
+}
+
+/// Check the `data` of an object of `kind` whose ids are of `hash_kind` for the problems `git fsck` would report, and return
+/// them in the order they were found.
+///
+/// Like `git`, checking stops at problems that make the rest of the object impossible to interpret.
+/// Blobs are never problematic.
+pub fn check(kind: Kind, data: &[u8], hash_kind: gix_hash::Kind) -> Vec<Problem> {
+    let mut out = Problems::default();
+    match kind {
+        Kind::Commit => commit(data, hash_kind, &mut out),
+        Kind::Tag => tag(data, hash_kind, &mut out),
+        Kind::Tree => tree(data, hash_kind, &mut out),
+        Kind::Blob => Ok(()),
+    }
+    .ok();
+    out.0
+}
+
+/// Problems collected so far, with `Err` returned by checks to stop checking.
+#[derive(Default)]
+struct Problems(Vec<Problem>);
+
+/// Checking can't continue.
+struct Stop;
+
+impl Problems {
+    fn report(&mut self, id: MessageId, message: impl Into<String>) {
+        self.0.push(Problem {
+            id,
+            message: message.into(),
+        });
+    }
+
+    fn stop<T>(&mut self, id: MessageId, message: impl Into<String>) -> Result<T, Stop> {
+        self.report(id, message);
+        Err(Stop)
+    }
+}
+
+fn headers(data: &[u8], out: &mut Problems) -> Result<(), Stop> {
+    for (idx, byte) in data.iter().enumerate() {
+        match byte {
+            0 => {
+                return out.stop(
+                    MessageId::NulInHeader,
+                    format!("unterminated header: NUL at offset {idx}"),
+                )
+            }
+            b'\n' if data.get(idx + 1) == Some(&b'\n') => return Ok(()),
+            _ => {}
+        }
+    }
+    if data.last() == Some(&b'\n') {
+        return Ok(());
+    }
+    out.stop(MessageId::UnterminatedHeader, "unterminated header")
+}
+
+/// Return the data after the hexadecimal id at the start of `data` if the id is valid and followed by a newline.
+fn hex_line(data: &[u8], hash_kind: gix_hash::Kind) -> Option<&[u8]> {
+    let len = hash_kind.len_in_hex();
+    if data.get(len) != Some(&b'\n') {
+        return None;
+    }
+    ObjectId::from_hex(&data[..len]).ok().map(|_| &data[len + 1..])
+}
+
+fn commit(data: &[u8], hash_kind: gix_hash::Kind, out: &mut Problems) -> Result<(), Stop> {
+    headers(data, out)?;
+    let Some(mut rest) = data.strip_prefix(b"tree ") else {
+        return out.stop(MessageId::MissingTree, "invalid format - expected 'tree' line");
+    };
+    rest = match hex_line(rest, hash_kind) {
+        Some(rest) => rest,
+        None => return out.stop(MessageId::BadTreeSha1, "invalid 'tree' line format - bad sha1"),
+    };
+    while let Some(parent) = rest.strip_prefix(b"parent ") {
+        rest = match hex_line(parent, hash_kind) {
+            Some(rest) => rest,
+            None => return out.stop(MessageId::BadParentSha1, "invalid 'parent' line format - bad sha1"),
+        };
+    }
+    let mut authors = 0;
+    while let Some(author) = rest.strip_prefix(b"author ") {
+        authors += 1;
+        rest = identity(author, out)?;
+    }
+    match authors {
+        0 => return out.stop(MessageId::MissingAuthor, "invalid format - expected 'author' line"),
+        1 => {}
+        _ => return out.stop(MessageId::MultipleAuthors, "invalid format - multiple 'author' lines"),
+    }
+    let Some(committer) = rest.strip_prefix(b"committer ") else {
+        return out.stop(
+            MessageId::MissingCommitter,
+            "invalid format - expected 'committer' line",
+        );
+    };
+    identity(committer, out)?;
+    if data.contains(&0) {
+        out.report(MessageId::NulInCommit, "NUL byte in the commit object body");
+    }
+    Ok(())
+}
+
+fn tag(data: &[u8], hash_kind: gix_hash::Kind, out: &mut Problems) -> Result<(), Stop> {
+    headers(data, out)?;
+    let Some(mut rest) = data.strip_prefix(b"object ") else {
+        return out.stop(MessageId::MissingObject, "invalid format - expected 'object' line");
+    };
+    rest = match hex_line(rest, hash_kind) {
+        Some(rest) => rest,
+        None => return out.stop(MessageId::BadObjectSha1, "invalid 'object' line format - bad sha1"),
+    };
+    let Some(kind) = rest.strip_prefix(b"type ") else {
+        return out.stop(MessageId::MissingTypeEntry, "invalid format - expected 'type' line");
+    };
+    let Some(end) = kind.find_byte(b'\n') else {
+        return out.stop(
+            MessageId::MissingType,
+            "invalid format - unexpected end after 'type' line",
+        );
+    };
+    if Kind::from_bytes(&kind[..end]).is_err() {
+        return out.stop(MessageId::BadType, "invalid 'type' value");
+    }
+    rest = &kind[end + 1..];
+    let Some(name) = rest.strip_prefix(b"tag ") else {
+        return out.stop(MessageId::MissingTagEntry, "invalid format - expected 'tag' line");
+    };
+    let Some(end) = name.find_byte(b'\n') else {
+        return out.stop(
+            MessageId::MissingTag,
+            "invalid format - unexpected end after 'type' line",
+        );
+    };
+    let mut full_name = b"refs/tags/".to_vec();
+    full_name.extend_from_slice(&name[..end]);
+    if gix_validate::reference::name(full_name.as_bstr()).is_err() {
+        out.report(
+            MessageId::BadTagName,
+            format!("invalid 'tag' name: {}", name[..end].as_bstr()),
+        );
+    }
+    rest = &name[end + 1..];
+    match rest.strip_prefix(b"tagger ") {
+        Some(tagger) => {
+            identity(tagger, out)?;
+        }
+        None => out.report(MessageId::MissingTaggerEntry, "invalid format - expected 'tagger' line"),
+    }
+    Ok(())
+}
+
+/// Check the identity line at the start of `data`, and return the data after it.
+fn identity<'a>(data: &'a [u8], out: &mut Problems) -> Result<&'a [u8], Stop> {
+    let line_end = data.find_byte(b'\n').unwrap_or(data.len());
+    let (line, rest) = (&data[..line_end], data.get(line_end + 1..).unwrap_or_default());
+    let bad = |what: &str| format!("invalid author/committer line - {what}");
+    if line.first() == Some(&b'<') {
+        return out.stop(MessageId::MissingNameBeforeEmail, bad("missing space before email"));
+    }
+    let Some(email_start) = line.find_byteset(b"<>") else {
+        return out.stop(MessageId::MissingEmail, bad("missing email"));
+    };
+    if line[email_start] == b'>' {
+        return out.stop(MessageId::BadName, bad("bad name"));
+    }
+    if email_start == 0 || line[email_start - 1] != b' ' {
+        return out.stop(MessageId::MissingSpaceBeforeEmail, bad("missing space before email"));
+    }
+    let after_email_start = &line[email_start + 1..];
+    let Some(email_end) = after_email_start
+        .find_byteset(b"<>")
+        .filter(|&pos| after_email_start[pos] == b'>')
+    else {
+        return out.stop(MessageId::BadEmail, bad("bad email"));
+    };
+    let Some(date) = after_email_start[email_end + 1..].strip_prefix(b" ") else {
+        return out.stop(MessageId::MissingSpaceBeforeDate, bad("missing space before date"));
+    };
+    if date.first() == Some(&b'0') && date.get(1) != Some(&b' ') {
+        return out.stop(MessageId::ZeroPaddedDate, bad("zero-padded date"));
+    }
+    let digits = date.iter().take_while(|b| b.is_ascii_digit()).count();
+    if digits > 0
+        && std::str::from_utf8(&date[..digits])
+            .expect("ascii digits")
+            .parse::<i64>()
+            .is_err()
+    {
+        return out.stop(MessageId::BadDateOverflow, bad("date causes integer overflow"));
+    }
+    if digits == 0 || date.get(digits) != Some(&b' ') {
+        return out.stop(MessageId::BadDate, bad("bad date"));
+    }
+    let timezone = &date[digits + 1..];
+    let valid_timezone = timezone.len() == 5
+        && matches!(timezone[0], b'+' | b'-')
+        && timezone[1..].iter().all(u8::is_ascii_digit)
+        && line_end < data.len();
+    if !valid_timezone {
+        return out.stop(MessageId::BadTimezone, bad("bad time zone"));
+    }
+    Ok(rest)
+}
+
+fn tree(data: &[u8], hash_kind: gix_hash::Kind, out: &mut Problems) -> Result<(), Stop> {
+    let mut entries = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let entry = rest
+            .find_byte(b' ')
+            .and_then(|space| {
+                let name_end = space + 1 + rest[space + 1..].find_byte(0)?;
+                let id_end = name_end + 1 + hash_kind.len_in_bytes();
+                (id_end <= rest.len()).then(|| {
+                    (
+                        &rest[..space],
+                        &rest[space + 1..name_end],
+                        &rest[name_end + 1..id_end],
+                        id_end,
+                    )
+                })
+            })
+            .filter(|(mode, ..)| !mode.is_empty() && mode.iter().all(|b| matches!(b, b'0'..=b'7')));
+        let Some((mode, name, id, end)) = entry else {
+            return out.stop(MessageId::BadTree, "cannot be parsed as a tree");
+        };
+        entries.push((mode, name, id));
+        rest = &rest[end..];
+    }
+
+    let mut problems = Vec::new();
+    for (mode, name, id) in &entries {
+        let mode_value = mode
+            .iter()
+            .fold(0u32, |acc, b| acc.saturating_mul(8).saturating_add(u32::from(b - b'0')));
+        if id.iter().all(|b| *b == 0) {
+            problems.push(MessageId::NullSha1);
+        }
+        match *name {
+            b"" => problems.push(MessageId::EmptyName),
+            b"." => problems.push(MessageId::HasDot),
+            b".." => problems.push(MessageId::HasDotdot),
+            _ if name.contains(&b'/') => problems.push(MessageId::FullPathname),
+            _ => {
+                let symlink = (mode_value == 0o120000).then_some(gix_validate::path::component::Mode::Symlink);
+                let options = gix_validate::path::component::Options {
+                    protect_windows: false,
+                    protect_hfs: true,
+                    protect_ntfs: true,
+                };
+                match gix_validate::path::component(name.as_bstr(), symlink, options) {
+                    Err(gix_validate::path::component::Error::DotGitDir) => problems.push(MessageId::HasDotgit),
+                    Err(gix_validate::path::component::Error::SymlinkedGitModules) => {
+                        problems.push(MessageId::GitmodulesSymlink)
+                    }
+                    _ => {}
+                }
+            }
+        }
+        if mode.first() == Some(&b'0') {
+            problems.push(MessageId::ZeroPaddedFilemode);
+        }
+        if !matches!(
+            mode_value,
+            0o100644 | 0o100755 | 0o100664 | 0o120000 | 0o40000 | 0o160000
+        ) {
+            problems.push(MessageId::BadFilemode);
+        }
+    }
+    for pair in entries.windows(2) {
+        let ((previous_mode, previous, _), (mode, name, _)) = (&pair[0], &pair[1]);
+        let is_tree = |mode: &[u8]| mode == b"40000" || mode == b"040000";
+        if previous == name {
+            problems.push(MessageId::DuplicateEntries);
+        } else if compare_tree_names(previous, is_tree(previous_mode), name, is_tree(mode)) != std::cmp::Ordering::Less
+        {
+            problems.push(MessageId::TreeNotSorted);
+        }
+    }
+
+    problems.sort();
+    problems.dedup();
+    for id in problems {
+        let message = match id {
+            MessageId::NullSha1 => "contains entries pointing to null sha1",
+            MessageId::EmptyName => "contains empty pathname",
+            MessageId::HasDot => "contains '.'",
+            MessageId::HasDotdot => "contains '..'",
+            MessageId::FullPathname => "contains full pathnames",
+            MessageId::HasDotgit => "contains '.git'",
+            MessageId::GitmodulesSymlink => ".gitmodules is a symbolic link",
+            MessageId::ZeroPaddedFilemode => "contains zero-padded file modes",
+            MessageId::BadFilemode => "contains bad file modes",
+            MessageId::DuplicateEntries => "contains duplicate file entries",
+            MessageId::TreeNotSorted => "not properly sorted",
+            _ => unreachable!("only tree problems are collected"),
+        };
+        out.report(id, message);
+    }
+    Ok(())
+}
+
+/// Compare tree entry names like `git` sorts them, with trees sorting as if their name ended with `/`.
+fn compare_tree_names(a: &[u8], a_is_tree: bool, b: &[u8], b_is_tree: bool) -> std::cmp::Ordering {
+    let common = a.len().min(b.len());
+    a[..common].cmp(&b[..common]).then_with(|| {
+        let terminator = |name: &[u8], is_tree: bool| {
+            name.get(common)
+                .copied()
+                .or_else(|| is_tree.then_some(b'/'))
+                .unwrap_or_default()
+        };
+        terminator(a, a_is_tree).cmp(&terminator(b, b_is_tree))
+    })
+}
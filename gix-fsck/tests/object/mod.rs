@@ -0,0 +1,189 @@
+use gix_fsck::object::{check, MessageId, Severity};
+use gix_object::Kind;
+
+fn ids(kind: Kind, data: &[u8]) -> Vec<MessageId> {
+    check(kind, data, gix_hash::Kind::Sha1)
+        .into_iter()
+        .map(|problem| problem.id)
+        .collect()
+}
+
+const TREE: &str = "tree 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n";
+
+fn commit_with_author(author: &str) -> Vec<u8> {
+    format!("{TREE}author {author}\ncommitter C <c@example.com> 1 +0000\n\nmessage\n").into_bytes()
+}
+
+fn tree(entries: &[(&str, &str, u8)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    for (mode, name, id) in entries {
+        out.extend_from_slice(format!("{mode} {name}\0").as_bytes());
+        out.extend_from_slice(&[*id; 20]);
+    }
+    out
+}
+
+#[test]
+fn valid_objects_have_no_problems() {
+    assert!(ids(
+        Kind::Commit,
+        &commit_with_author("A U Thor <a@example.com> 1700000000 +0100")
+    )
+    .is_empty());
+    assert!(ids(
+        Kind::Tag,
+        b"object 4b825dc642cb6eb9a060e54bf8d69288fbee4904\ntype tree\ntag v1.0\ntagger T <t@example.com> 0 -0130\n\nmessage\n"
+    )
+    .is_empty());
+    assert!(
+        ids(
+            Kind::Tree,
+            &tree(&[("100644", "a", 1), ("40000", "a-dir", 2), ("100755", "a.txt", 3)])
+        )
+        .is_empty(),
+        "trees sort as if their name ended with a slash"
+    );
+    assert!(ids(Kind::Blob, b"\0anything").is_empty());
+}
+
+#[test]
+fn identities() {
+    for (author, expected) in [
+        ("<a@example.com> 1 +0000", MessageId::MissingNameBeforeEmail),
+        ("A a@example.com 1 +0000", MessageId::MissingEmail),
+        ("A> <a@example.com> 1 +0000", MessageId::BadName),
+        ("A<a@example.com> 1 +0000", MessageId::MissingSpaceBeforeEmail),
+        ("A <a@example.com 1 +0000", MessageId::BadEmail),
+        ("A <a@example.com>1 +0000", MessageId::MissingSpaceBeforeDate),
+        ("A <a@example.com> 01 +0000", MessageId::ZeroPaddedDate),
+        (
+            "A <a@example.com> 99999999999999999999 +0000",
+            MessageId::BadDateOverflow,
+        ),
+        ("A <a@example.com> yesterday +0000", MessageId::BadDate),
+        ("A <a@example.com> 1 0000", MessageId::BadTimezone),
+        ("A <a@example.com> 1 +00", MessageId::BadTimezone),
+    ] {
+        assert_eq!(ids(Kind::Commit, &commit_with_author(author)), [expected], "{author}");
+    }
+}
+
+#[test]
+fn commits() {
+    let commit = |rest: &str| format!("{TREE}{rest}").into_bytes();
+    assert_eq!(ids(Kind::Commit, b"author A <a@b> 1 +0000\n"), [MessageId::MissingTree]);
+    assert_eq!(ids(Kind::Commit, b"tree 1234\n"), [MessageId::BadTreeSha1]);
+    assert_eq!(
+        ids(Kind::Commit, &commit("parent nonsense\nauthor A <a@b> 1 +0000\n")),
+        [MessageId::BadParentSha1]
+    );
+    assert_eq!(
+        ids(Kind::Commit, &commit("committer A <a@b> 1 +0000\n")),
+        [MessageId::MissingAuthor]
+    );
+    assert_eq!(
+        ids(
+            Kind::Commit,
+            &commit("author A <a@b> 1 +0000\nauthor A <a@b> 1 +0000\ncommitter A <a@b> 1 +0000\n")
+        ),
+        [MessageId::MultipleAuthors]
+    );
+    assert_eq!(
+        ids(Kind::Commit, &commit("author A <a@b> 1 +0000\n")),
+        [MessageId::MissingCommitter]
+    );
+    assert_eq!(
+        ids(Kind::Commit, &commit("author A <a@b> 1 +0000")),
+        [MessageId::UnterminatedHeader]
+    );
+    assert_eq!(
+        ids(Kind::Commit, &commit("author A\0 <a@b> 1 +0000\n")),
+        [MessageId::NulInHeader]
+    );
+    let mut with_nul_in_body = commit_with_author("A <a@b> 1 +0000");
+    with_nul_in_body.push(0);
+    assert_eq!(ids(Kind::Commit, &with_nul_in_body), [MessageId::NulInCommit]);
+}
+
+#[test]
+fn tags() {
+    let tag = |rest: &str| format!("object 4b825dc642cb6eb9a060e54bf8d69288fbee4904\n{rest}").into_bytes();
+    assert_eq!(ids(Kind::Tag, b"type tree\n"), [MessageId::MissingObject]);
+    assert_eq!(ids(Kind::Tag, b"object abc\n"), [MessageId::BadObjectSha1]);
+    assert_eq!(ids(Kind::Tag, &tag("tag v1\n")), [MessageId::MissingTypeEntry]);
+    assert_eq!(ids(Kind::Tag, &tag("type thing\ntag v1\n")), [MessageId::BadType]);
+    assert_eq!(
+        ids(Kind::Tag, &tag("type tree\ntagger T <t@t> 1 +0000\n")),
+        [MessageId::MissingTagEntry]
+    );
+    assert_eq!(
+        ids(Kind::Tag, &tag("type tree\ntag bad..name\ntagger T <t@t> 1 +0000\n")),
+        [MessageId::BadTagName]
+    );
+    assert_eq!(
+        ids(Kind::Tag, &tag("type tree\ntag v1\n\nmessage\n")),
+        [MessageId::MissingTaggerEntry]
+    );
+    assert_eq!(
+        ids(Kind::Tag, &tag("type tree\ntag v1\ntagger T <t@t> +0000\n")),
+        [MessageId::BadDate]
+    );
+}
+
+#[test]
+fn trees() {
+    assert_eq!(ids(Kind::Tree, b"100644 a\0short"), [MessageId::BadTree]);
+    assert_eq!(
+        ids(Kind::Tree, &tree(&[("10x644", "a", 1)])),
+        [MessageId::BadTree],
+        "modes are octal"
+    );
+    assert_eq!(
+        ids(
+            Kind::Tree,
+            &tree(&[
+                ("100644", "", 1),
+                ("100644", ".", 1),
+                ("100644", "..", 1),
+                ("100644", ".GIT", 1),
+                ("120000", ".gitmodules", 1),
+                ("100644", "a/b", 0),
+                ("0100644", "c", 1),
+                ("100600", "d", 1),
+                ("100644", "e", 1),
+                ("100644", "e", 1),
+                ("100644", "b", 1),
+            ])
+        ),
+        [
+            MessageId::DuplicateEntries,
+            MessageId::GitmodulesSymlink,
+            MessageId::TreeNotSorted,
+            MessageId::BadFilemode,
+            MessageId::EmptyName,
+            MessageId::FullPathname,
+            MessageId::HasDot,
+            MessageId::HasDotdot,
+            MessageId::HasDotgit,
+            MessageId::NullSha1,
+            MessageId::ZeroPaddedFilemode,
+        ],
+        "each kind of problem is reported once, with errors first"
+    );
+}
+
+#[test]
+fn message_ids_and_severities() {
+    assert_eq!(MessageId::from_name(b"baddate"), Some(MessageId::BadDate));
+    assert_eq!(
+        MessageId::from_name(b"missingTaggerEntry"),
+        Some(MessageId::MissingTaggerEntry)
+    );
+    assert_eq!(MessageId::from_name(b"unknown"), None);
+    assert_eq!(MessageId::HasDotgit.to_string(), "hasDotgit");
+    assert_eq!(MessageId::BadDate.default_severity(), Severity::Error);
+    assert_eq!(MessageId::NullSha1.default_severity(), Severity::Warn);
+    assert_eq!(MessageId::BadTagName.default_severity(), Severity::Info);
+    assert_eq!(Severity::from_name(b"WARN"), Some(Severity::Warn));
+    assert_eq!(Severity::from_name(b"info"), None, "like git, info can't be configured");
+}
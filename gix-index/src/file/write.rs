@@ -44,7 +44,7 @@ impl File {
         let _span = gix_features::trace::detail!("gix_index::File::write()", path = ?self.path);
         let mut lock = std::io::BufWriter::with_capacity(
             64 * 1024,
-            gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?,
+            gix_lock::File::acquire_to_update_resource(&self.path, options.lock_mode, None)?,
         );
         let (version, digest) = self.write_to(&mut lock, options)?;
         match lock.into_inner() {
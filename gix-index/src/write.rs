@@ -56,6 +56,8 @@ pub struct Options {
     /// via [`File::write()`](crate::File::write()) and [`File::write_to()`](crate::File::write_to()).
     /// Note that
     pub skip_hash: bool,
+    /// Determine how to deal with an index that is locked already when writing it via [`File::write()`](crate::File::write()).
+    pub lock_mode: gix_lock::acquire::Fail,
 }
 
 impl State {
@@ -66,6 +68,7 @@ impl State {
         Options {
             extensions,
             skip_hash: _,
+            lock_mode: _,
         }: Options,
     ) -> std::io::Result<Version> {
         let _span = gix_features::trace::detail!("gix_index::State::write()");
@@ -48,6 +48,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: false,
+        lock_mode: gix_lock::acquire::Fail::Immediately,
     })?;
 
     let actual = gix_index::File::at(
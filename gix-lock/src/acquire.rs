@@ -1,7 +1,8 @@
 use std::{
     fmt,
+    ops::ControlFlow,
     path::{Path, PathBuf},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use gix_tempfile::{AutoRemove, ContainingDirectory};
@@ -16,14 +17,125 @@ pub enum Fail {
     Immediately,
     /// Retry after failure with exponentially longer sleep times to block the current thread.
     /// Fail once the given duration is exceeded, similar to [Fail::Immediately]
+    ///
+    /// This is the same as using [`Fail::WithPolicy`] with a [`Policy`] created from the given duration.
     AfterDurationWithBackoff(Duration),
+    /// Retry after failure as configured by the given policy to block the current thread.
+    WithPolicy(Policy),
+}
+
+/// A function called before waiting for the next attempt to obtain a lock, which can stop retrying by returning
+/// [`ControlFlow::Break`], for instance if the user interrupted the operation.
+pub type OnRetry = fn(&Retry<'_>) -> ControlFlow<()>;
+
+/// Information about an upcoming retry to obtain a lock, passed to [`Policy::on_retry`].
+#[derive(Debug, Clone, Copy)]
+pub struct Retry<'a> {
+    /// The path to the lock file we are trying to create.
+    pub lock_path: &'a Path,
+    /// The amount of failed attempts so far.
+    pub attempts: usize,
+    /// The time that passed since the first attempt.
+    pub elapsed: Duration,
+    /// The time we are about to wait for before the next attempt.
+    pub wait: Duration,
+}
+
+/// Describe how to retry obtaining a lock that is held elsewhere, used with [`Fail::WithPolicy`].
+///
+/// Like in `git`, the time to wait in between attempts grows exponentially.
+#[derive(Clone, Copy, Debug)]
+pub struct Policy {
+    /// Fail once this much time has passed since the first attempt.
+    pub deadline: Duration,
+    /// The longest time to wait in between two attempts.
+    pub max_interval: Duration,
+    /// If `true`, randomly vary each wait time by up to 25% so that competing processes don't retry in lockstep.
+    pub jitter: bool,
+    /// If set, the function to call before each wait, which may stop further attempts.
+    pub on_retry: Option<OnRetry>,
+}
+
+impl Policy {
+    /// Create a new instance which fails after `deadline`, using jitter and a maximum interval of a second
+    /// like `git` does.
+    pub fn new(deadline: Duration) -> Self {
+        Policy {
+            deadline,
+            max_interval: Duration::from_secs(1),
+            jitter: true,
+            on_retry: None,
+        }
+    }
+
+    /// Wait at most `interval` in between two attempts.
+    pub fn with_max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    /// Enable or disable randomization of wait times.
+    pub fn with_jitter(mut self, toggle: bool) -> Self {
+        self.jitter = toggle;
+        self
+    }
+
+    /// Call `on_retry` before each wait.
+    pub fn with_on_retry(mut self, on_retry: OnRetry) -> Self {
+        self.on_retry = Some(on_retry);
+        self
+    }
+}
+
+impl Policy {
+    /// Return all fields as comparable values, with [`on_retry`](Self::on_retry) identified by its address.
+    fn key(&self) -> (Duration, Duration, bool, Option<usize>) {
+        (
+            self.deadline,
+            self.max_interval,
+            self.jitter,
+            self.on_retry.map(|f| f as usize),
+        )
+    }
+}
+
+impl PartialEq for Policy {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Policy {}
+
+impl PartialOrd for Policy {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Policy {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl std::hash::Hash for Policy {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl From<Duration> for Policy {
+    fn from(deadline: Duration) -> Self {
+        Policy::new(deadline)
+    }
 }
 
 impl fmt::Display for Fail {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Fail::Immediately => f.write_str("immediately"),
-            Fail::AfterDurationWithBackoff(duration) => {
+            Fail::AfterDurationWithBackoff(duration) | Fail::WithPolicy(Policy { deadline: duration, .. }) => {
                 write!(f, "after {:.02}s", duration.as_secs_f32())
             }
         }
@@ -52,6 +164,10 @@ pub enum Error {
         mode: Fail,
         attempts: usize,
     },
+    #[error(
+        "Stopped trying to obtain the lock for resource '{resource_path}' after {attempts} attempt(s) as requested"
+    )]
+    Interrupted { resource_path: PathBuf, attempts: usize },
 }
 
 impl File {
@@ -171,33 +287,60 @@ fn lock_with_mode<T>(
 ) -> Result<(PathBuf, T), Error> {
     use std::io::ErrorKind::*;
     let (directory, cleanup) = dir_cleanup(boundary_directory);
-    let lock_path = add_lock_suffix(resource);
+    // Long paths need to be verbatim on Windows, which also applies to the resource path derived from it on commit.
+    let lock_path = gix_path::to_long_path_on_windows(add_lock_suffix(resource)).into_owned();
     let mut attempts = 1;
-    match mode {
-        Fail::Immediately => try_lock(&lock_path, directory, cleanup),
-        Fail::AfterDurationWithBackoff(time) => {
-            for wait in backoff::Exponential::default_with_random().until_no_remaining(time) {
-                attempts += 1;
+    let policy = match mode {
+        Fail::Immediately => None,
+        Fail::AfterDurationWithBackoff(deadline) => Some(Policy::new(deadline)),
+        Fail::WithPolicy(policy) => Some(policy),
+    };
+    let res = match policy {
+        None => try_lock(&lock_path, directory, cleanup),
+        Some(policy) => {
+            let start = Instant::now();
+            let mut backoff = if policy.jitter {
+                backoff::Exponential::default_with_random()
+            } else {
+                backoff::Exponential::default()
+            };
+            loop {
                 match try_lock(&lock_path, directory, cleanup.clone()) {
-                    Ok(v) => return Ok((lock_path, v)),
                     #[cfg(windows)]
-                    Err(err) if err.kind() == AlreadyExists || err.kind() == PermissionDenied => {
-                        std::thread::sleep(wait);
-                        continue;
-                    }
+                    Err(err) if err.kind() == AlreadyExists || err.kind() == PermissionDenied => {}
                     #[cfg(not(windows))]
-                    Err(err) if err.kind() == AlreadyExists => {
-                        std::thread::sleep(wait);
-                        continue;
+                    Err(err) if err.kind() == AlreadyExists => {}
+                    res => break res,
+                }
+                let elapsed = start.elapsed();
+                let Some(remaining) = policy.deadline.checked_sub(elapsed).filter(|d| !d.is_zero()) else {
+                    break Err(AlreadyExists.into());
+                };
+                let wait = backoff
+                    .next()
+                    .expect("infinite")
+                    .min(policy.max_interval)
+                    .min(remaining);
+                if let Some(on_retry) = policy.on_retry {
+                    let retry = Retry {
+                        lock_path: &lock_path,
+                        attempts,
+                        elapsed,
+                        wait,
+                    };
+                    if on_retry(&retry).is_break() {
+                        return Err(Error::Interrupted {
+                            resource_path: resource.into(),
+                            attempts,
+                        });
                     }
-                    Err(err) => return Err(Error::from(err)),
                 }
+                std::thread::sleep(wait);
+                attempts += 1;
             }
-            try_lock(&lock_path, directory, cleanup)
         }
-    }
-    .map(|v| (lock_path, v))
-    .map_err(|err| match err.kind() {
+    };
+    res.map(|v| (lock_path, v)).map_err(|err| match err.kind() {
         AlreadyExists => Error::PermanentlyLocked {
             resource_path: resource.into(),
             mode,
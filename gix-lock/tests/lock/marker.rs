@@ -1,7 +1,11 @@
 mod acquire {
-    use std::time::{Duration, Instant};
+    use std::{
+        ops::ControlFlow,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::{Duration, Instant},
+    };
 
-    use gix_lock::acquire::Fail;
+    use gix_lock::acquire::{Error, Fail, Policy, Retry};
 
     #[test]
     fn fail_mode_immediately_produces_a_descriptive_error() -> crate::Result {
@@ -41,6 +45,67 @@ mod acquire {
         assert!(err_str.contains("the-resource.lock"), "it mentions the lockfile itself");
         Ok(())
     }
+
+    #[test]
+    fn fail_mode_with_policy_respects_the_deadline_and_calls_on_retry() -> crate::Result {
+        static RETRIES: AtomicUsize = AtomicUsize::new(0);
+        fn on_retry(retry: &Retry<'_>) -> ControlFlow<()> {
+            assert!(retry.lock_path.ends_with("the-resource.lock"));
+            assert!(retry.wait <= Duration::from_millis(5), "waits are capped");
+            RETRIES.fetch_add(1, Ordering::SeqCst);
+            ControlFlow::Continue(())
+        }
+
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("the-resource");
+        let _guard = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)?;
+        let start = Instant::now();
+        let time_to_wait = Duration::from_millis(50);
+        let policy = Policy::new(time_to_wait)
+            .with_jitter(false)
+            .with_max_interval(Duration::from_millis(5))
+            .with_on_retry(on_retry);
+        let err = gix_lock::Marker::acquire_to_hold_resource(resource, Fail::WithPolicy(policy), None)
+            .expect_err("the lock is taken and there is a failure obtaining it again after some delay");
+        assert!(
+            start.elapsed() >= time_to_wait,
+            "it should never wait less than the given wait time"
+        );
+        let Error::PermanentlyLocked { attempts, .. } = err else {
+            panic!("unexpected error: {err:?}")
+        };
+        assert_eq!(
+            RETRIES.load(Ordering::SeqCst),
+            attempts - 1,
+            "there is a callback before each retry"
+        );
+        assert!(attempts > 10, "capping the wait time leads to more attempts");
+        assert!(err.to_string().contains("could not be obtained after 0.05s"));
+        Ok(())
+    }
+
+    #[test]
+    fn fail_mode_with_policy_can_be_interrupted_by_on_retry() -> crate::Result {
+        fn on_retry(retry: &Retry<'_>) -> ControlFlow<()> {
+            if retry.attempts == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+
+        let dir = tempfile::tempdir()?;
+        let resource = dir.path().join("the-resource");
+        let _guard = gix_lock::Marker::acquire_to_hold_resource(&resource, Fail::Immediately, None)?;
+        let policy = Policy::new(Duration::from_secs(60)).with_on_retry(on_retry);
+        let err = gix_lock::Marker::acquire_to_hold_resource(resource, Fail::WithPolicy(policy), None)
+            .expect_err("the callback stops retrying");
+        assert!(
+            matches!(err, Error::Interrupted { attempts: 2, .. }),
+            "it stops right away: {err:?}"
+        );
+        Ok(())
+    }
 }
 mod commit {
     use gix_lock::acquire::Fail;
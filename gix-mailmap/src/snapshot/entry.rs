@@ -32,8 +32,13 @@ impl EmailEntry {
         let new_name = new_name.map(ToOwned::to_owned);
         match old_name {
             None => {
-                self.new_email = new_email;
-                self.new_name = new_name;
+                // Like git, only overwrite what's set so multiple sources can amend each other.
+                if new_email.is_some() {
+                    self.new_email = new_email;
+                }
+                if new_name.is_some() {
+                    self.new_name = new_name;
+                }
             }
             Some(old_name) => {
                 let old_name: EncodedStringRef<'_> = old_name.into();
@@ -1,4 +1,4 @@
-use bstr::ByteSlice;
+use bstr::{BStr, ByteSlice};
 use gix_actor::SignatureRef;
 
 use crate::Snapshot;
@@ -31,6 +31,10 @@ impl Snapshot {
 
     /// Merge the given `entries` into this instance, possibly overwriting existing mappings with
     /// new ones should they collide.
+    ///
+    /// Like in `git`, entries that are matched by email only just overwrite the name or email they set, while
+    /// entries matched by name and email replace previous ones entirely. Hence, when merging multiple sources,
+    /// the source merged last takes precedence.
     pub fn merge<'a>(&mut self, entries: impl IntoIterator<Item = crate::Entry<'a>>) -> &mut Self {
         for entry in entries {
             let old_email: EncodedStringRef<'_> = entry.old_email.into();
@@ -79,6 +83,59 @@ impl Snapshot {
         out
     }
 
+    /// Return all entries that map a signature to the canonical `name` and `email`, which is the inverse
+    /// of [`try_resolve()`][Snapshot::try_resolve()] and useful to find all identities of the same person.
+    ///
+    /// Emails are compared ignoring ASCII case, just like during lookup. Entries that don't set a new name keep
+    /// the name of the signature they match, and are returned if their `old_name` is `name`, or if
+    /// they have no `old_name` at all as they map all names with `name` among them.
+    ///
+    /// The entries are ordered by (`old_email`, `old_name`), like in [`entries()`][Snapshot::entries()].
+    pub fn variants_of(&self, name: &BStr, email: &BStr) -> Vec<crate::Entry<'_>> {
+        let mut out = Vec::new();
+        for entry in &self.entries_by_old_email {
+            let old_email = entry.old_email.as_bstr();
+            if (entry.new_email.is_some() || entry.new_name.is_some())
+                && entry
+                    .new_email
+                    .as_ref()
+                    .map_or(old_email, |e| e.as_bstr())
+                    .eq_ignore_ascii_case(email)
+                && !matches!(&entry.new_name, Some(new_name) if new_name != name)
+            {
+                out.push(crate::Entry {
+                    new_name: entry.new_name.as_ref().map(|b| b.as_bstr()),
+                    new_email: entry.new_email.as_ref().map(|b| b.as_bstr()),
+                    old_name: None,
+                    old_email,
+                });
+            }
+
+            for name_entry in &entry.entries_by_old_name {
+                let old_name = name_entry.old_name.as_bstr();
+                let name_matches = match &name_entry.new_name {
+                    Some(new_name) => new_name == name,
+                    None => old_name.eq_ignore_ascii_case(name),
+                };
+                if name_matches
+                    && name_entry
+                        .new_email
+                        .as_ref()
+                        .map_or(old_email, |e| e.as_bstr())
+                        .eq_ignore_ascii_case(email)
+                {
+                    out.push(crate::Entry {
+                        new_name: name_entry.new_name.as_ref().map(|b| b.as_bstr()),
+                        new_email: name_entry.new_email.as_ref().map(|b| b.as_bstr()),
+                        old_name: Some(old_name),
+                        old_email,
+                    });
+                }
+            }
+        }
+        out
+    }
+
     /// Try to resolve `signature` by its contained email and name and provide resolved/mapped names as reference.
     /// Return `None` if no such mapping was found.
     ///
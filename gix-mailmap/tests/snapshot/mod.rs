@@ -113,6 +113,69 @@ fn overwrite_entries() {
     assert_eq!(snapshot.entries().len(), 4);
 }
 
+#[test]
+fn merge_of_multiple_sources_only_overwrites_what_is_set_when_mapping_by_email() {
+    let mut snapshot = Snapshot::from_bytes(b"A <a@example.com>\nC <c@example.com> old-c <old-c@example.com>");
+    snapshot.merge(gix_mailmap::parse_ignore_errors(
+        b"<new-a@example.com> <a@example.com>\nC2 <new-c@example.com> old-c <old-c@example.com>",
+    ));
+    assert_eq!(
+        snapshot.try_resolve(signature("anything", "a@example.com").to_ref()),
+        Some(signature("A", "new-a@example.com")),
+        "the name from the first source is kept, just like git does"
+    );
+    assert_eq!(
+        snapshot.try_resolve(signature("old-c", "old-c@example.com").to_ref()),
+        Some(signature("C2", "new-c@example.com")),
+        "entries by name and email are replaced"
+    );
+}
+
+#[test]
+fn variants_of() {
+    let snapshot = Snapshot::from_bytes(&fixture_bytes("typical.txt"));
+    let variants = |name: &str, email: &str| {
+        snapshot
+            .variants_of(name.into(), email.into())
+            .into_iter()
+            .map(|e| (e.old_name().map(ToString::to_string), e.old_email().to_string()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        variants("Jane Doe", "Jane@example.com"),
+        vec![
+            (Some("Jane".into()), "bugs@example.com".into()),
+            (None, "jane@desktop.(none)".into()),
+            (None, "jane@laptop.(none)".into()),
+        ],
+        "emails are compared case-insensitively"
+    );
+    assert_eq!(
+        variants("Joe R. Developer", "joe@example.com"),
+        vec![
+            (Some("Joe".into()), "bugs@example.com".into()),
+            (None, "joe@example.com".into()),
+        ]
+    );
+    assert_eq!(
+        variants("jane doe", "jane@example.com"),
+        vec![],
+        "names must match exactly"
+    );
+    assert_eq!(variants("Jane Doe", "bugs@example.com"), vec![]);
+
+    let snapshot = Snapshot::from_bytes(b"<new@example.com> <old@example.com>");
+    assert_eq!(
+        snapshot
+            .variants_of("Anyone".into(), "new@example.com".into())
+            .into_iter()
+            .map(|e| e.old_email().to_string())
+            .collect::<Vec<_>>(),
+        vec!["old@example.com"],
+        "names are kept if only the email is mapped, so all names are variants"
+    );
+}
+
 fn signature(name: &str, email: &str) -> gix_actor::Signature {
     gix_actor::Signature {
         name: name.into(),
@@ -1,2 +1,46 @@
-#![deny(rust_2018_idioms)]
+//! Read, edit and write notes trees as they are stored in the commits of references like `refs/notes/commits`, compatible with `git notes`.
+//!
+//! A notes tree associates annotated objects with a blob containing their note by using the hexadecimal object id as path.
+//! To keep trees small, the first characters of the hexadecimal id may be split off into directories, like `ab/cdef…`,
+//! which is called *fan-out* and may be repeated.
+//!
+//! This crate doesn't access the object database itself, which is left to higher-level crates like `gix`.
+#![deny(missing_docs, rust_2018_idioms)]
 #![forbid(unsafe_code)]
+
+use std::collections::BTreeMap;
+
+use bstr::BString;
+use gix_hash::ObjectId;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod read;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod write;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge;
+
+/// All notes of a notes tree.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Notes {
+    /// The ids of annotated objects, mapped to the ids of the blobs containing their note.
+    pub notes: BTreeMap<ObjectId, ObjectId>,
+    /// Entries that aren't notes, like `.gitattributes`, which are retained when writing the notes tree.
+    pub non_notes: Vec<NonNote>,
+}
+
+/// An entry in a notes tree which isn't a note.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NonNote {
+    /// The path of the entry relative to the root of the notes tree, with `/` as separator.
+    pub path: BString,
+    /// The mode of the entry.
+    pub mode: gix_object::tree::EntryMode,
+    /// The id of the object the entry points to.
+    pub id: ObjectId,
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeSet;
+
+use bstr::ByteSlice;
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+
+use crate::Notes;
+
+/// The way to resolve notes that were changed differently on both sides, similar to `git notes merge --strategy`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Don't resolve conflicts, but keep our note and list the conflict in [`Outcome::conflicts`].
+    #[default]
+    Manual,
+    /// Use our note.
+    Ours,
+    /// Use their note.
+    Theirs,
+    /// Concatenate our note and their note, separated by an empty line.
+    Union,
+    /// Concatenate the lines of our note and their note, sort them and remove duplicates.
+    CatSortUniq,
+}
+
+/// A note that was changed differently on both sides, and couldn't be resolved with [`Strategy::Manual`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The id of the annotated object.
+    pub target: ObjectId,
+    /// The id of the note blob in the merge-base, if there was a note.
+    pub base: Option<ObjectId>,
+    /// The id of our note blob, or `None` if we removed the note.
+    pub ours: Option<ObjectId>,
+    /// The id of their note blob, or `None` if they removed the note.
+    pub theirs: Option<ObjectId>,
+}
+
+/// The result of [`merge()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The merged notes. Conflicting notes are resolved according to the strategy, or are set to our version.
+    pub notes: Notes,
+    /// Notes that couldn't be merged, which is only possible with [`Strategy::Manual`].
+    pub conflicts: Vec<Conflict>,
+}
+
+/// The error returned by [`merge()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindBlob(#[from] gix_object::find::existing_object::Error),
+    #[error("Could not write the combined note for {target}")]
+    WriteBlob {
+        target: ObjectId,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+/// Merge `ours` and `theirs` using their common ancestor `base` and return the merged notes.
+///
+/// Notes that were changed on only one side are taken from that side, and notes changed on both sides are resolved according
+/// to `strategy`. `objects` is used to read the notes to combine, and `write_blob` is used to write a combined note,
+/// returning its id.
+///
+/// Entries that aren't notes are taken from `ours`, with those that only exist in `theirs` added.
+pub fn merge<E>(
+    base: &Notes,
+    ours: &Notes,
+    theirs: &Notes,
+    strategy: Strategy,
+    objects: &dyn gix_object::Find,
+    mut write_blob: impl FnMut(&[u8]) -> Result<ObjectId, E>,
+) -> Result<Outcome, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut out = Outcome {
+        notes: ours.clone(),
+        conflicts: Vec::new(),
+    };
+    let targets: BTreeSet<_> = base
+        .notes
+        .keys()
+        .chain(ours.notes.keys())
+        .chain(theirs.notes.keys())
+        .collect();
+    let mut buf = Vec::new();
+    for target in targets {
+        let (base_note, our_note, their_note) = (
+            base.notes.get(target).copied(),
+            ours.notes.get(target).copied(),
+            theirs.notes.get(target).copied(),
+        );
+        if our_note == their_note || base_note == their_note {
+            continue;
+        }
+        let resolved = if base_note == our_note {
+            their_note
+        } else {
+            match (strategy, our_note, their_note) {
+                (Strategy::Manual, _, _) => {
+                    out.conflicts.push(Conflict {
+                        target: *target,
+                        base: base_note,
+                        ours: our_note,
+                        theirs: their_note,
+                    });
+                    continue;
+                }
+                (Strategy::Ours, _, _) => continue,
+                (Strategy::Theirs, _, _) => their_note,
+                (Strategy::Union | Strategy::CatSortUniq, None, note)
+                | (Strategy::Union | Strategy::CatSortUniq, note, None) => note,
+                (Strategy::Union | Strategy::CatSortUniq, Some(our_note), Some(their_note)) => {
+                    let ours = objects.find_blob(&our_note, &mut buf)?.data.to_owned();
+                    let theirs = objects.find_blob(&their_note, &mut buf)?.data;
+                    let combined = if strategy == Strategy::Union {
+                        concatenate(&ours, theirs)
+                    } else {
+                        cat_sort_uniq(&ours, theirs)
+                    };
+                    Some(write_blob(&combined).map_err(|err| Error::WriteBlob {
+                        target: *target,
+                        source: Box::new(err),
+                    })?)
+                }
+            }
+        };
+        match resolved {
+            Some(note) => out.notes.notes.insert(*target, note),
+            None => out.notes.notes.remove(target),
+        };
+    }
+
+    for non_note in &theirs.non_notes {
+        if !out.notes.non_notes.iter().any(|ours| ours.path == non_note.path) {
+            out.notes.non_notes.push(non_note.clone());
+        }
+    }
+    out.notes.non_notes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(out)
+}
+
+/// Like `git`, separate `ours` and `theirs` by an empty line, unless one of them is empty.
+fn concatenate(ours: &[u8], theirs: &[u8]) -> Vec<u8> {
+    if ours.is_empty() {
+        return theirs.into();
+    }
+    if theirs.is_empty() {
+        return ours.into();
+    }
+    let mut out = ours.strip_suffix(b"\n").unwrap_or(ours).to_vec();
+    out.extend_from_slice(b"\n\n");
+    out.extend_from_slice(theirs);
+    out
+}
+
+/// Like `git`, produce the sorted and unique non-empty lines of `ours` and `theirs`, each terminated by a newline.
+fn cat_sort_uniq(ours: &[u8], theirs: &[u8]) -> Vec<u8> {
+    let lines: BTreeSet<_> = ours
+        .lines()
+        .chain(theirs.lines())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let mut out = Vec::new();
+    for line in lines {
+        out.extend_from_slice(line);
+        out.push(b'\n');
+    }
+    out
+}
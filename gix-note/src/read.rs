@@ -0,0 +1,60 @@
+use bstr::{BString, ByteVec};
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+
+use crate::{NonNote, Notes};
+
+/// The error returned by [`Notes::from_tree()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindTree(#[from] gix_object::find::existing_object::Error),
+}
+
+impl Notes {
+    /// Read all notes from the notes tree at `tree`, using `objects` to look up the tree and its subtrees.
+    ///
+    /// Notes are found in any fan-out, and all entries that aren't notes are kept in [`non_notes`](Notes::non_notes).
+    /// Subtrees that aren't part of the fan-out are not entered, but retained as a whole.
+    pub fn from_tree(tree: &gix_hash::oid, objects: &dyn gix_object::Find) -> Result<Self, Error> {
+        let hex_len = tree.kind().len_in_hex();
+        let mut out = Notes::default();
+        let mut buf = Vec::new();
+        // Tuples of tree ids, their path relative to the root of the notes tree, and the hex characters of the id they represent.
+        let mut queue: Vec<(ObjectId, BString, BString)> =
+            vec![(tree.to_owned(), BString::default(), BString::default())];
+        while let Some((tree, path, hex_prefix)) = queue.pop() {
+            for entry in objects.find_tree(&tree, &mut buf)?.entries {
+                let mut entry_path = path.clone();
+                if !entry_path.is_empty() {
+                    entry_path.push_byte(b'/');
+                }
+                entry_path.extend_from_slice(entry.filename);
+
+                let is_hex = entry.filename.iter().all(u8::is_ascii_hexdigit);
+                let hex_len_with_entry = hex_prefix.len() + entry.filename.len();
+                if is_hex && entry.mode.is_tree() && entry.filename.len() == 2 && hex_len_with_entry < hex_len {
+                    let mut hex = hex_prefix.clone();
+                    hex.extend_from_slice(entry.filename);
+                    queue.push((entry.oid.to_owned(), entry_path, hex));
+                    continue;
+                }
+                if is_hex && !entry.mode.is_tree() && hex_len_with_entry == hex_len {
+                    let mut hex = hex_prefix.clone();
+                    hex.extend_from_slice(entry.filename);
+                    let target = ObjectId::from_hex(&hex).expect("valid hex of correct length");
+                    out.notes.insert(target, entry.oid.to_owned());
+                    continue;
+                }
+                out.non_notes.push(NonNote {
+                    path: entry_path,
+                    mode: entry.mode,
+                    id: entry.oid.to_owned(),
+                });
+            }
+        }
+        out.non_notes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(out)
+    }
+}
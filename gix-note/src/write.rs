@@ -0,0 +1,99 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_hash::ObjectId;
+use gix_object::tree;
+
+use crate::Notes;
+
+impl Notes {
+    /// Write all notes and non-notes as notes tree, passing each tree to `write_tree` which is expected to store it and return its id.
+    /// Return the id of the root tree.
+    ///
+    /// Just like `git` does, the notes are placed into fan-out directories only in densely populated portions of the tree,
+    /// which happens if each of the 16 possible next hexadecimal characters is shared by at least two notes.
+    pub fn write_tree<E>(
+        &self,
+        mut write_tree: impl FnMut(&gix_object::Tree) -> Result<ObjectId, E>,
+    ) -> Result<ObjectId, E> {
+        let mut root = Dir::default();
+        let notes: Vec<_> = self
+            .notes
+            .iter()
+            .map(|(target, note)| (target.to_hex().to_string(), *note))
+            .collect();
+        insert_notes(&notes, 0, &mut root);
+        for non_note in &self.non_notes {
+            root.insert(non_note.path.as_ref(), non_note.mode, non_note.id);
+        }
+        root.write(&mut write_tree)
+    }
+}
+
+/// Insert `notes`, whose hexadecimal ids all share the first `consumed` characters, into `dir` and determine their fan-out.
+fn insert_notes(notes: &[(String, ObjectId)], consumed: usize, dir: &mut Dir) {
+    let mut counts = [0usize; 16];
+    for (hex, _) in notes {
+        if let Some(idx) = hex[consumed..].chars().next().and_then(|c| c.to_digit(16)) {
+            counts[idx as usize] += 1;
+        }
+    }
+    let is_dense =
+        matches!(notes.first(), Some((hex, _)) if consumed + 2 < hex.len()) && counts.iter().all(|count| *count >= 2);
+    if !is_dense {
+        dir.entries.extend(notes.iter().map(|(hex, note)| tree::Entry {
+            mode: tree::EntryKind::Blob.into(),
+            filename: hex[consumed..].into(),
+            oid: *note,
+        }));
+        return;
+    }
+
+    let mut rest = notes;
+    while let Some((first, _)) = rest.first() {
+        let name = &first[consumed..][..2];
+        let end = rest
+            .iter()
+            .position(|(hex, _)| &hex[consumed..][..2] != name)
+            .unwrap_or(rest.len());
+        insert_notes(&rest[..end], consumed + 2, dir.dirs.entry(name.into()).or_default());
+        rest = &rest[end..];
+    }
+}
+
+/// A directory of the notes tree to be written.
+#[derive(Default)]
+struct Dir {
+    entries: Vec<tree::Entry>,
+    dirs: BTreeMap<BString, Dir>,
+}
+
+impl Dir {
+    fn insert(&mut self, path: &BStr, mode: tree::EntryMode, oid: ObjectId) {
+        match path.find_byte(b'/') {
+            Some(pos) => self
+                .dirs
+                .entry(path[..pos].into())
+                .or_default()
+                .insert(path[pos + 1..].as_bstr(), mode, oid),
+            None => self.entries.push(tree::Entry {
+                mode,
+                filename: path.into(),
+                oid,
+            }),
+        }
+    }
+
+    fn write<E>(self, write_tree: &mut impl FnMut(&gix_object::Tree) -> Result<ObjectId, E>) -> Result<ObjectId, E> {
+        let mut entries = self.entries;
+        for (filename, dir) in self.dirs {
+            entries.push(tree::Entry {
+                mode: tree::EntryKind::Tree.into(),
+                filename,
+                oid: dir.write(write_tree)?,
+            });
+        }
+        entries.sort();
+        write_tree(&gix_object::Tree { entries })
+    }
+}
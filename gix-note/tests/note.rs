@@ -0,0 +1,279 @@
+use std::{cell::RefCell, collections::HashMap, convert::Infallible};
+
+use gix_hash::ObjectId;
+use gix_object::{Kind, WriteTo};
+
+/// An in-memory object database.
+#[derive(Default)]
+struct Store(RefCell<HashMap<ObjectId, (Kind, Vec<u8>)>>);
+
+impl Store {
+    fn write(&self, kind: Kind, data: Vec<u8>) -> ObjectId {
+        let id = gix_object::compute_hash(gix_hash::Kind::Sha1, kind, &data);
+        self.0.borrow_mut().insert(id, (kind, data));
+        id
+    }
+
+    fn write_blob(&self, data: &[u8]) -> Result<ObjectId, Infallible> {
+        Ok(self.write(Kind::Blob, data.into()))
+    }
+
+    fn write_tree(&self, tree: &gix_object::Tree) -> Result<ObjectId, Infallible> {
+        let mut buf = Vec::new();
+        tree.write_to(&mut buf).expect("in-memory writes don't fail");
+        Ok(self.write(Kind::Tree, buf))
+    }
+
+    fn tree(&self, id: &ObjectId) -> gix_object::Tree {
+        let store = self.0.borrow();
+        let (kind, data) = store.get(id).expect("tree present");
+        assert_eq!(*kind, Kind::Tree);
+        gix_object::TreeRef::from_bytes(data).expect("valid").into()
+    }
+}
+
+impl gix_object::Find for Store {
+    fn try_find<'a>(
+        &self,
+        id: &gix_hash::oid,
+        buffer: &'a mut Vec<u8>,
+    ) -> Result<Option<gix_object::Data<'a>>, gix_object::find::Error> {
+        Ok(self.0.borrow().get(id).map(|(kind, data)| {
+            buffer.clear();
+            buffer.extend_from_slice(data);
+            gix_object::Data {
+                kind: *kind,
+                data: buffer.as_slice(),
+            }
+        }))
+    }
+}
+
+/// Produce an id which starts with the hex characters in `prefix` and is otherwise unique by `seed`.
+fn target(prefix: &str, seed: usize) -> ObjectId {
+    let hex = format!("{prefix}{seed:0>width$x}", width = 40 - prefix.len());
+    ObjectId::from_hex(hex.as_bytes()).expect("valid hex")
+}
+
+mod write_and_read {
+    use gix_note::{NonNote, Notes};
+    use gix_object::tree::EntryKind;
+
+    use crate::{target, Store};
+
+    #[test]
+    fn sparse_notes_are_stored_without_fanout_along_with_non_notes() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Store::default();
+        let mut notes = Notes::default();
+        for seed in 0..3 {
+            let note = store.write_blob(format!("note {seed}\n").as_bytes())?;
+            notes.notes.insert(target("a", seed), note);
+        }
+        let attributes = store.write_blob(b"* -diff\n")?;
+        notes.non_notes.push(NonNote {
+            path: ".gitattributes".into(),
+            mode: EntryKind::Blob.into(),
+            id: attributes,
+        });
+
+        let root = notes.write_tree(|tree| store.write_tree(tree))?;
+        let tree = store.tree(&root);
+        assert_eq!(tree.entries.len(), 4, "three notes and the non-note");
+        assert_eq!(tree.entries[0].filename, ".gitattributes");
+        assert!(tree.entries[1..]
+            .iter()
+            .all(|e| e.filename.len() == 40 && e.mode.is_blob()));
+
+        assert_eq!(Notes::from_tree(&root, &store)?, notes, "a round-trip is lossless");
+        Ok(())
+    }
+
+    #[test]
+    fn dense_notes_are_fanned_out_like_git_does() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Store::default();
+        let note = store.write_blob(b"note\n")?;
+        let mut notes = Notes::default();
+        for nibble in 0..16 {
+            for seed in 0..2 {
+                notes.notes.insert(target(&format!("{nibble:x}"), seed), note);
+            }
+        }
+
+        let root = notes.write_tree(|tree| store.write_tree(tree))?;
+        let tree = store.tree(&root);
+        assert_eq!(
+            tree.entries.len(),
+            16,
+            "each leading nibble is shared by two notes, so one level of fan-out is used"
+        );
+        for entry in &tree.entries {
+            assert!(entry.mode.is_tree());
+            assert_eq!(entry.filename.len(), 2);
+            let subtree = store.tree(&entry.oid);
+            assert!(subtree.entries.iter().all(|e| e.filename.len() == 38));
+        }
+        assert_eq!(Notes::from_tree(&root, &store)?, notes, "a round-trip is lossless");
+
+        notes.notes.remove(&target("f", 0));
+        let root = notes.write_tree(|tree| store.write_tree(tree))?;
+        assert_eq!(
+            store.tree(&root).entries.len(),
+            31,
+            "without enough notes in one of the buckets, there is no fan-out"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn notes_are_read_from_any_fanout_and_other_trees_are_retained() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Store::default();
+        let note = store.write_blob(b"note\n")?;
+        let target = target("abcd", 1);
+        let inner = store.write_tree(&gix_object::Tree {
+            entries: vec![gix_object::tree::Entry {
+                mode: EntryKind::Blob.into(),
+                filename: target.to_hex().to_string()[4..].into(),
+                oid: note,
+            }],
+        })?;
+        let not_fanout = store.write_tree(&gix_object::Tree {
+            entries: vec![gix_object::tree::Entry {
+                mode: EntryKind::Blob.into(),
+                filename: target.to_hex().to_string()[4..].into(),
+                oid: note,
+            }],
+        })?;
+        let middle = store.write_tree(&gix_object::Tree {
+            entries: vec![gix_object::tree::Entry {
+                mode: EntryKind::Tree.into(),
+                filename: "cd".into(),
+                oid: inner,
+            }],
+        })?;
+        let root = store.write_tree(&gix_object::Tree {
+            entries: vec![
+                gix_object::tree::Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: "ab".into(),
+                    oid: middle,
+                },
+                gix_object::tree::Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: "not-fanout".into(),
+                    oid: not_fanout,
+                },
+            ],
+        })?;
+
+        let notes = Notes::from_tree(&root, &store)?;
+        assert_eq!(notes.notes.len(), 1);
+        assert_eq!(notes.notes.get(&target), Some(&note));
+        assert_eq!(
+            notes.non_notes,
+            vec![NonNote {
+                path: "not-fanout".into(),
+                mode: EntryKind::Tree.into(),
+                id: not_fanout,
+            }],
+            "trees that aren't part of the fan-out aren't entered"
+        );
+        Ok(())
+    }
+}
+
+mod merge {
+    use gix_note::{
+        merge::{Conflict, Strategy},
+        Notes,
+    };
+
+    use crate::{target, Store};
+
+    fn notes(store: &Store, items: &[(usize, &str)]) -> Notes {
+        let mut out = Notes::default();
+        for (seed, content) in items {
+            let note = store.write_blob(content.as_bytes()).expect("infallible");
+            out.notes.insert(target("", *seed), note);
+        }
+        out
+    }
+
+    #[test]
+    fn one_sided_changes_are_taken() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Store::default();
+        let base = notes(&store, &[(1, "one\n"), (2, "two\n"), (3, "three\n")]);
+        let ours = notes(&store, &[(1, "one - ours\n"), (2, "two\n")]);
+        let theirs = notes(&store, &[(1, "one\n"), (3, "three\n"), (4, "four\n")]);
+
+        let out = gix_note::merge::merge(&base, &ours, &theirs, Strategy::Manual, &store, |data| {
+            store.write_blob(data)
+        })?;
+        assert!(out.conflicts.is_empty());
+        assert_eq!(
+            out.notes,
+            notes(&store, &[(1, "one - ours\n"), (4, "four\n")]),
+            "our modification and removal are kept, and their removal and addition are taken"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts_are_resolved_by_strategy() -> Result<(), Box<dyn std::error::Error>> {
+        let store = Store::default();
+        let base = notes(&store, &[(1, "base\n"), (2, "removed by us\n")]);
+        let ours = notes(&store, &[(1, "b\nours\n"), (3, "added by us\n")]);
+        let theirs = notes(
+            &store,
+            &[(1, "a\nb\ntheirs\n"), (2, "changed by them\n"), (3, "added by them\n")],
+        );
+
+        for (strategy, expected) in [
+            (Strategy::Ours, vec![(1, "b\nours\n"), (3, "added by us\n")]),
+            (
+                Strategy::Theirs,
+                vec![(1, "a\nb\ntheirs\n"), (2, "changed by them\n"), (3, "added by them\n")],
+            ),
+            (
+                Strategy::Union,
+                vec![
+                    (1, "b\nours\n\na\nb\ntheirs\n"),
+                    (2, "changed by them\n"),
+                    (3, "added by us\n\nadded by them\n"),
+                ],
+            ),
+            (
+                Strategy::CatSortUniq,
+                vec![
+                    (1, "a\nb\nours\ntheirs\n"),
+                    (2, "changed by them\n"),
+                    (3, "added by them\nadded by us\n"),
+                ],
+            ),
+        ] {
+            let out = gix_note::merge::merge(&base, &ours, &theirs, strategy, &store, |data| store.write_blob(data))?;
+            assert!(out.conflicts.is_empty(), "{strategy:?}");
+            assert_eq!(out.notes, notes(&store, &expected), "{strategy:?}");
+        }
+
+        let out = gix_note::merge::merge(&base, &ours, &theirs, Strategy::Manual, &store, |data| {
+            store.write_blob(data)
+        })?;
+        assert_eq!(out.notes, ours, "our notes are kept in case of conflict");
+        assert_eq!(
+            out.conflicts,
+            [1, 2, 3]
+                .into_iter()
+                .map(|seed| {
+                    let id = crate::target("", seed);
+                    Conflict {
+                        target: id,
+                        base: base.notes.get(&id).copied(),
+                        ours: ours.notes.get(&id).copied(),
+                        theirs: theirs.notes.get(&id).copied(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
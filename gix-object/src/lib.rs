@@ -411,6 +411,22 @@ pub fn compute_hash(hash_kind: gix_hash::Kind, object_kind: Kind, data: &[u8]) -
     hasher.digest().into()
 }
 
+/// Like [`compute_hash()`], but fails if `data` is part of a known collision attack, which requires the
+/// `sha1-collision-detection` feature of `gix-features` to be detected.
+pub fn try_compute_hash(
+    hash_kind: gix_hash::Kind,
+    object_kind: Kind,
+    data: &[u8],
+) -> Result<gix_hash::ObjectId, gix_features::hash::CollisionError> {
+    let header = encode::loose_header(object_kind, data.len() as u64);
+
+    let mut hasher = gix_features::hash::hasher(hash_kind);
+    hasher.update(&header);
+    hasher.update(data);
+
+    hasher.try_digest().map(Into::into)
+}
+
 /// A function to compute a hash of kind `hash_kind` for an object of `object_kind` and its data read from `stream`
 /// which has to yield exactly `stream_len` bytes.
 /// Use `progress` to learn about progress in bytes processed and `should_interrupt` to be able to abort the operation
@@ -89,6 +89,12 @@ impl<S> Cache<S> {
         self.object_cache = None;
         self.new_object_cache = None;
     }
+    /// Discard all objects in the current object cache, if present, by replacing it with a new one.
+    pub fn reset_object_cache(&mut self) {
+        if let Some(create) = self.new_object_cache.as_ref() {
+            self.object_cache = Some(RefCell::new(create()));
+        }
+    }
 }
 
 impl<S> From<S> for Cache<S>
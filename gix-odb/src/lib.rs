@@ -73,6 +73,9 @@ mod sink;
 #[allow(clippy::empty_docs)]
 pub mod find;
 
+/// An object database that can keep written objects in memory.
+pub mod memory;
+
 /// An object database equivalent to `/dev/null`, dropping all objects stored into it.
 mod traits;
 
@@ -118,7 +121,10 @@ pub struct Store {
     pub(crate) current_dir: PathBuf,
 
     /// A set of replacements that given a source OID return a destination OID. The vector is sorted.
-    pub(crate) replacements: Vec<(gix_hash::ObjectId, gix_hash::ObjectId)>,
+    /// It's obtained from `load_replacements` when it's needed for the first time.
+    pub(crate) replacements: std::sync::OnceLock<Vec<(gix_hash::ObjectId, gix_hash::ObjectId)>>,
+    /// The function to produce the unsorted `replacements`, which is consumed once they are needed.
+    pub(crate) load_replacements: parking_lot::Mutex<Option<store::init::LoadReplacementsFn>>,
 
     /// A list of indices keeping track of which slots are filled with data. These are usually, but not always, consecutive.
     pub(crate) index: ArcSwap<types::SlotMapIndex>,
@@ -140,6 +146,10 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// The compression level to use when writing loose objects.
+    loose_compression: gix_features::zlib::Compression,
+    /// The zlib implementation to use when writing loose objects.
+    zlib_backend: gix_features::zlib::Backend,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
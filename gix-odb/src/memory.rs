@@ -0,0 +1,280 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    io::Read,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::Arc,
+};
+
+use gix_hash::{oid, ObjectId};
+use gix_object::{Data, Kind};
+
+use crate::{find::Header, pack::data::entry::Location, Cache};
+
+/// An object database that keeps all objects written to it in memory, while reading from both memory and an `inner`
+/// object database.
+///
+/// This is useful to write objects without touching the disk, for instance to stage speculative changes or to test
+/// logic involving objects hermetically.
+/// If object memory is [disabled](Proxy::with_write_passthrough()), all writes go to the `inner` database.
+pub struct Proxy<T> {
+    inner: T,
+    memory: Option<RefCell<Storage>>,
+    object_hash: gix_hash::Kind,
+}
+
+/// The objects kept in memory by a [`Proxy`], along with their kind and data.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Storage(BTreeMap<ObjectId, (Kind, Vec<u8>)>);
+
+impl Deref for Storage {
+    type Target = BTreeMap<ObjectId, (Kind, Vec<u8>)>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Storage {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+/// Lifecycle
+impl<T> Proxy<T> {
+    /// Create a new instance which reads from `inner` as well as from its memory, and which writes objects hashed
+    /// with `object_hash` into memory.
+    pub fn new(inner: T, object_hash: gix_hash::Kind) -> Self {
+        Proxy {
+            inner,
+            memory: Some(Default::default()),
+            object_hash,
+        }
+    }
+
+    /// Turn off object memory so that all writes go to the inner object database.
+    pub fn with_write_passthrough(mut self) -> Self {
+        self.memory = None;
+        self
+    }
+
+    /// Dissolve this instance, discard all objects in memory, and return the inner object database.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Object memory
+impl<T> Proxy<T> {
+    /// Start keeping written objects in memory if this isn't yet the case.
+    pub fn enable_object_memory(&mut self) -> &mut Self {
+        if self.memory.is_none() {
+            self.memory = Some(Default::default());
+        }
+        self
+    }
+
+    /// Return `true` if written objects are kept in memory.
+    pub fn has_object_memory(&self) -> bool {
+        self.memory.is_some()
+    }
+
+    /// Take all objects kept in memory and stop keeping written objects in memory, or return `None` if
+    /// object memory wasn't enabled.
+    pub fn take_object_memory(&mut self) -> Option<Storage> {
+        self.memory.take().map(RefCell::into_inner)
+    }
+
+    /// Set the objects to keep in memory to `memory`, enabling object memory if needed, and return
+    /// the previous objects if object memory was enabled.
+    pub fn set_object_memory(&mut self, memory: Storage) -> Option<Storage> {
+        self.memory.replace(RefCell::new(memory)).map(RefCell::into_inner)
+    }
+
+    /// Return the amount of objects kept in memory.
+    pub fn num_objects_in_memory(&self) -> usize {
+        self.memory.as_ref().map_or(0, |memory| memory.borrow().len())
+    }
+}
+
+impl<T> Proxy<T> {
+    fn contains_in_memory(&self, id: &oid) -> bool {
+        match self.memory.as_ref() {
+            Some(memory) => memory.borrow().contains_key(id),
+            None => false,
+        }
+    }
+
+    fn find_in_memory<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Option<Data<'a>> {
+        let memory = self.memory.as_ref()?.borrow();
+        let (kind, data) = memory.get(id)?;
+        buffer.clear();
+        buffer.extend_from_slice(data);
+        Some(Data::new(*kind, buffer))
+    }
+
+    fn header_in_memory(&self, id: &oid) -> Option<(Kind, u64)> {
+        let memory = self.memory.as_ref()?.borrow();
+        memory.get(id).map(|(kind, data)| (*kind, data.len() as u64))
+    }
+}
+
+impl Proxy<Cache<crate::store::Handle<Rc<crate::Store>>>> {
+    /// Convert this instance's inner handle into one that keeps its store in an arc, keeping all objects in memory.
+    /// This creates an entirely new store, so should be done early to avoid unnecessary work (and mappings).
+    pub fn into_arc(self) -> std::io::Result<Proxy<Cache<crate::store::Handle<Arc<crate::Store>>>>> {
+        Ok(Proxy {
+            inner: self.inner.into_arc()?,
+            memory: self.memory,
+            object_hash: self.object_hash,
+        })
+    }
+}
+
+impl Proxy<Cache<crate::store::Handle<Arc<crate::Store>>>> {
+    /// No op, as we are containing an arc handle already.
+    pub fn into_arc(self) -> std::io::Result<Proxy<Cache<crate::store::Handle<Arc<crate::Store>>>>> {
+        Ok(self)
+    }
+}
+
+impl<T: Clone> Clone for Proxy<T> {
+    fn clone(&self) -> Self {
+        Proxy {
+            inner: self.inner.clone(),
+            memory: self.memory.clone(),
+            object_hash: self.object_hash,
+        }
+    }
+}
+
+impl<T> Deref for Proxy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<T> DerefMut for Proxy<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<T> crate::Write for Proxy<T>
+where
+    T: crate::Write,
+{
+    fn write_stream(&self, kind: Kind, size: u64, from: &mut dyn Read) -> Result<ObjectId, crate::write::Error> {
+        let Some(memory) = self.memory.as_ref() else {
+            return self.inner.write_stream(kind, size, from);
+        };
+        let mut buf = Vec::new();
+        from.take(size).read_to_end(&mut buf)?;
+        if buf.len() as u64 != size {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("Expected {size} bytes of object data, but got only {}", buf.len()),
+            )
+            .into());
+        }
+        let id = gix_object::compute_hash(self.object_hash, kind, &buf);
+        memory.borrow_mut().insert(id, (kind, buf));
+        Ok(id)
+    }
+}
+
+impl<T> gix_object::Find for Proxy<T>
+where
+    T: gix_object::Find,
+{
+    fn try_find<'a>(&self, id: &oid, buffer: &'a mut Vec<u8>) -> Result<Option<Data<'a>>, gix_object::find::Error> {
+        if self.contains_in_memory(id) {
+            return Ok(self.find_in_memory(id, buffer));
+        }
+        self.inner.try_find(id, buffer)
+    }
+}
+
+impl<T> gix_object::Exists for Proxy<T>
+where
+    T: gix_object::Exists,
+{
+    fn exists(&self, id: &oid) -> bool {
+        self.contains_in_memory(id) || self.inner.exists(id)
+    }
+}
+
+impl<T> crate::Header for Proxy<T>
+where
+    T: crate::Header,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<Header>, gix_object::find::Error> {
+        if let Some((kind, size)) = self.header_in_memory(id) {
+            return Ok(Some(Header::Loose { kind, size }));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl<T> gix_object::FindHeader for Proxy<T>
+where
+    T: gix_object::FindHeader,
+{
+    fn try_header(&self, id: &oid) -> Result<Option<gix_object::Header>, gix_object::find::Error> {
+        if let Some((kind, size)) = self.header_in_memory(id) {
+            return Ok(Some(gix_object::Header { kind, size }));
+        }
+        self.inner.try_header(id)
+    }
+}
+
+impl<T> gix_pack::Find for Proxy<T>
+where
+    T: gix_pack::Find,
+{
+    fn contains(&self, id: &oid) -> bool {
+        self.contains_in_memory(id) || self.inner.contains(id)
+    }
+
+    fn try_find<'a>(
+        &self,
+        id: &oid,
+        buffer: &'a mut Vec<u8>,
+    ) -> Result<Option<(Data<'a>, Option<Location>)>, gix_object::find::Error> {
+        if self.contains_in_memory(id) {
+            return Ok(self.find_in_memory(id, buffer).map(|data| (data, None)));
+        }
+        self.inner.try_find(id, buffer)
+    }
+
+    fn try_find_cached<'a>(
+        &self,
+        id: &oid,
+        buffer: &'a mut Vec<u8>,
+        pack_cache: &mut dyn gix_pack::cache::DecodeEntry,
+    ) -> Result<Option<(Data<'a>, Option<Location>)>, gix_object::find::Error> {
+        if self.contains_in_memory(id) {
+            return Ok(self.find_in_memory(id, buffer).map(|data| (data, None)));
+        }
+        self.inner.try_find_cached(id, buffer, pack_cache)
+    }
+
+    fn location_by_oid(&self, id: &oid, buf: &mut Vec<u8>) -> Option<Location> {
+        if self.contains_in_memory(id) {
+            return None;
+        }
+        self.inner.location_by_oid(id, buf)
+    }
+
+    fn pack_offsets_and_oid(&self, pack_id: u32) -> Option<Vec<(u64, ObjectId)>> {
+        self.inner.pack_offsets_and_oid(pack_id)
+    }
+
+    fn entry_by_location(&self, location: &Location) -> Option<gix_pack::find::Entry> {
+        self.inner.entry_by_location(location)
+    }
+}
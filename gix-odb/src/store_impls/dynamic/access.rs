@@ -17,8 +17,39 @@ impl Store {
         self.use_multi_pack_index
     }
 
+    /// The compression level used when writing loose objects.
+    pub fn loose_compression(&self) -> gix_features::zlib::Compression {
+        self.loose_compression
+    }
+
+    /// The zlib implementation used when writing loose objects.
+    pub fn zlib_backend(&self) -> gix_features::zlib::Backend {
+        self.zlib_backend
+    }
+
     /// An iterator over replacements from object-ids `X` to `X-replaced` as `(X, X-replaced)`, sorted by the original id `X`.
+    ///
+    /// Note that this loads the replacements if this didn't happen yet.
     pub fn replacements(&self) -> impl Iterator<Item = (gix_hash::ObjectId, gix_hash::ObjectId)> + '_ {
-        self.replacements.iter().copied()
+        self.replacements_sorted().iter().copied()
+    }
+
+    /// Return `true` if the replacements were already loaded, either because they were needed to find objects
+    /// or because they were [queried](Self::replacements()).
+    pub fn has_loaded_replacements(&self) -> bool {
+        self.replacements.get().is_some()
+    }
+
+    pub(crate) fn replacements_sorted(&self) -> &[(gix_hash::ObjectId, gix_hash::ObjectId)] {
+        self.replacements.get_or_init(|| {
+            let mut replacements = self
+                .load_replacements
+                .lock()
+                .take()
+                .map(|load| load())
+                .unwrap_or_default();
+            replacements.sort_by_key(|(source, _)| *source);
+            replacements
+        })
     }
 }
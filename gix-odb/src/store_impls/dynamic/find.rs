@@ -102,12 +102,9 @@ where
                 });
             }
         } else if !self.ignore_replacements {
-            if let Ok(pos) = self
-                .store
-                .replacements
-                .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
-            {
-                id = self.store.replacements[pos].1.as_ref();
+            let replacements = self.store.replacements_sorted();
+            if let Ok(pos) = replacements.binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id)) {
+                id = replacements[pos].1.as_ref();
             }
         }
 
@@ -363,7 +360,7 @@ where
             "BUG: handle must be configured to `prevent_pack_unload()` before using this method"
         );
 
-        assert!(self.store_ref().replacements.is_empty() || self.ignore_replacements, "Everything related to packing must not use replacements. These are not used here, but it should be turned off for good measure.");
+        assert!(self.ignore_replacements || self.store_ref().replacements_sorted().is_empty(), "Everything related to packing must not use replacements. These are not used here, but it should be turned off for good measure.");
 
         let mut snapshot = self.snapshot.borrow_mut();
         let mut inflate = self.inflate.borrow_mut();
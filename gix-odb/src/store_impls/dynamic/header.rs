@@ -28,12 +28,9 @@ where
                 });
             }
         } else if !self.ignore_replacements {
-            if let Ok(pos) = self
-                .store
-                .replacements
-                .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
-            {
-                id = self.store.replacements[pos].1.as_ref();
+            let replacements = self.store.replacements_sorted();
+            if let Ok(pos) = replacements.binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id)) {
+                id = replacements[pos].1.as_ref();
             }
         }
 
@@ -19,6 +19,10 @@ pub struct Options {
     /// The current directory of the process at the time of instantiation.
     /// If unset, it will be retrieved using `gix_fs::current_dir(false)`.
     pub current_dir: Option<std::path::PathBuf>,
+    /// The compression level to use when writing loose objects, similar to `core.looseCompression`.
+    pub loose_compression: gix_features::zlib::Compression,
+    /// The zlib implementation to compress loose objects with.
+    pub zlib_backend: gix_features::zlib::Backend,
 }
 
 impl Default for Options {
@@ -28,10 +32,15 @@ impl Default for Options {
             object_hash: Default::default(),
             use_multi_pack_index: true,
             current_dir: None,
+            loose_compression: gix_features::zlib::Compression::fast(),
+            zlib_backend: gix_features::zlib::Backend::compiled(),
         }
     }
 }
 
+/// A function to produce pairs of old and new object ids for replacement support, see [`Store::at_opts_with_lazy_replacements()`].
+pub type LoadReplacementsFn = Box<dyn FnOnce() -> Vec<(gix_hash::ObjectId, gix_hash::ObjectId)> + Send>;
+
 /// Configures the amount of slots in the index slotmap, which is fixed throughout the existence of the store.
 #[derive(Copy, Clone, Debug)]
 pub enum Slots {
@@ -72,11 +81,27 @@ impl Store {
     pub fn at_opts(
         objects_dir: PathBuf,
         replacements: &mut dyn Iterator<Item = (gix_hash::ObjectId, gix_hash::ObjectId)>,
+        options: Options,
+    ) -> std::io::Result<Self> {
+        let replacements: Vec<_> = replacements.collect();
+        Self::at_opts_with_lazy_replacements(objects_dir, Box::new(move || replacements), options)
+    }
+
+    /// Like [`at_opts()`](Self::at_opts()), but calls `load_replacements` to obtain the pairs of old and new object ids
+    /// for replacement support only when replacements are needed for the first time.
+    ///
+    /// This avoids the cost of finding replacements if these are never used, for instance because
+    /// all handles [ignore them](super::Handle::ignore_replacements).
+    pub fn at_opts_with_lazy_replacements(
+        objects_dir: PathBuf,
+        load_replacements: LoadReplacementsFn,
         Options {
             slots,
             object_hash,
             use_multi_pack_index,
             current_dir,
+            loose_compression,
+            zlib_backend,
         }: Options,
     ) -> std::io::Result<Self> {
         let _span = gix_features::trace::detail!("gix_odb::Store::at()");
@@ -112,18 +137,18 @@ impl Store {
                 "Cannot use more than 1^15 slots",
             ));
         }
-        let mut replacements: Vec<_> = replacements.collect();
-        replacements.sort_by(|a, b| a.0.cmp(&b.0));
-
         Ok(Store {
             current_dir,
             write: Default::default(),
-            replacements,
+            replacements: Default::default(),
+            load_replacements: parking_lot::Mutex::new(Some(load_replacements)),
             path: objects_dir,
             files: Vec::from_iter(std::iter::repeat_with(MutableIndexAndPack::default).take(slot_count)),
             index: ArcSwap::new(Arc::new(SlotMapIndex::default())),
             use_multi_pack_index,
             object_hash,
+            loose_compression,
+            zlib_backend,
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
@@ -229,7 +229,11 @@ impl super::Store {
             Arc::new(
                 db_paths
                     .iter()
-                    .map(|path| crate::loose::Store::at(path, self.object_hash))
+                    .map(|path| {
+                        crate::loose::Store::at(path, self.object_hash)
+                            .with_compression(self.loose_compression)
+                            .with_zlib_backend(self.zlib_backend)
+                    })
                     .collect::<Vec<_>>(),
             )
         } else {
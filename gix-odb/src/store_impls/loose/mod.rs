@@ -3,7 +3,10 @@
 const HEADER_MAX_SIZE: usize = 64;
 use std::path::{Path, PathBuf};
 
-use gix_features::fs;
+use gix_features::{
+    fs,
+    zlib::{Backend, Compression},
+};
 
 /// A database for reading and writing objects to disk, one file per object.
 #[derive(Clone, PartialEq, Eq)]
@@ -12,6 +15,10 @@ pub struct Store {
     pub(crate) path: PathBuf,
     /// The kind of hash we should assume during iteration and when writing new objects.
     pub(crate) object_hash: gix_hash::Kind,
+    /// The compression level to use when writing new objects.
+    pub(crate) compression: Compression,
+    /// The zlib implementation to compress new objects with.
+    pub(crate) zlib_backend: Backend,
 }
 
 /// Initialization
@@ -26,9 +33,27 @@ impl Store {
         Store {
             path: objects_directory.into(),
             object_hash,
+            compression: Compression::fast(),
+            zlib_backend: Backend::compiled(),
         }
     }
 
+    /// Use `compression` as level when writing new objects, similar to `core.looseCompression`.
+    ///
+    /// It defaults to the fastest compression level.
+    pub fn with_compression(mut self, compression: Compression) -> Store {
+        self.compression = compression;
+        self
+    }
+
+    /// Use `backend` to compress new objects, which fails when writing if it isn't available in this build.
+    ///
+    /// It defaults to the backend `flate2` was compiled with.
+    pub fn with_zlib_backend(mut self, backend: Backend) -> Store {
+        self.zlib_backend = backend;
+        self
+    }
+
     /// Return the path to our `objects` directory.
     pub fn path(&self) -> &Path {
         &self.path
@@ -38,6 +63,16 @@ impl Store {
     pub fn object_hash(&self) -> gix_hash::Kind {
         self.object_hash
     }
+
+    /// Return the compression level used when writing new objects.
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Return the zlib implementation used when writing new objects.
+    pub fn zlib_backend(&self) -> Backend {
+        self.zlib_backend
+    }
 }
 
 fn hash_path(id: &gix_hash::oid, mut root: PathBuf) -> PathBuf {
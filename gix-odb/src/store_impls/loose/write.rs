@@ -24,6 +24,8 @@ pub enum Error {
         source: tempfile::PersistError,
         target: PathBuf,
     },
+    #[error(transparent)]
+    ZlibBackend(#[from] gix_features::zlib::backend::Error),
 }
 
 impl crate::traits::Write for Store {
@@ -116,11 +118,15 @@ impl Store {
             builder.permissions(perms);
         }
         Ok(hash::Write::new(
-            deflate::Write::new(builder.tempfile_in(&self.path).map_err(|err| Error::Io {
-                source: err,
-                message: "create named temp file in",
-                path: self.path.to_owned(),
-            })?),
+            deflate::Write::new_with_backend(
+                builder.tempfile_in(&self.path).map_err(|err| Error::Io {
+                    source: err,
+                    message: "create named temp file in",
+                    path: self.path.to_owned(),
+                })?,
+                self.zlib_backend,
+                self.compression,
+            )?,
             self.object_hash,
         ))
     }
@@ -0,0 +1,43 @@
+use gix_object::{Exists, FindExt};
+use gix_odb::{memory::Proxy, Header, Write};
+
+use crate::{hex_to_id, odb::db};
+
+#[test]
+fn writes_go_to_memory_and_reads_see_memory_and_inner() -> crate::Result {
+    let mut db = Proxy::new(db(), gix_hash::Kind::Sha1);
+    let mut buf = Vec::new();
+    let on_disk = hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980");
+    assert!(db.exists(&on_disk), "objects of the inner database can be found");
+
+    let id = db.write_buf(gix_object::Kind::Blob, b"in memory")?;
+    assert_eq!(
+        id,
+        gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, b"in memory")
+    );
+    assert!(db.exists(&id));
+    assert!(!(*db).exists(&id), "it's not written to the inner database");
+    assert_eq!(db.find_blob(&id, &mut buf)?.data, b"in memory");
+    assert_eq!(
+        db.try_header(&id)?,
+        Some(gix_odb::find::Header::Loose {
+            kind: gix_object::Kind::Blob,
+            size: 9
+        })
+    );
+    assert_eq!(
+        gix_pack::Find::try_find(&db, &id, &mut buf)?.map(|(data, location)| (data.kind, location.is_none())),
+        Some((gix_object::Kind::Blob, true)),
+        "objects in memory have no pack location"
+    );
+    assert_eq!(db.num_objects_in_memory(), 1);
+
+    let memory = db.take_object_memory().expect("object memory is enabled by default");
+    assert_eq!(memory.len(), 1);
+    assert!(!db.exists(&id), "objects in memory are gone along with the memory");
+    assert_eq!(db.num_objects_in_memory(), 0);
+
+    assert!(db.set_object_memory(memory).is_none(), "there was no memory");
+    assert!(db.exists(&id), "memory can be restored");
+    Ok(())
+}
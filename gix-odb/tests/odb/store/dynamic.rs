@@ -321,6 +321,51 @@ fn object_replacement() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn lazy_object_replacement() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_replaced_history.sh")?;
+    let short_history_link = hex_to_id("434e5a872d6738d1fffd1e11e52a1840b73668c6");
+    let long_history_tip = hex_to_id("71f537d9d78bf6ae89a29a17e54b95a914d3d2ef");
+    let store = std::sync::Arc::new(gix_odb::Store::at_opts_with_lazy_replacements(
+        dir.join(".git/objects"),
+        Box::new(move || vec![(short_history_link, long_history_tip)]),
+        Default::default(),
+    )?);
+    let mut handle = store.to_handle_arc();
+    handle.ignore_replacements = true;
+
+    let mut buf = Vec::new();
+    let orphan_of_new_history = hex_to_id("0703c317e28068f39834ae61e7ab941b7d672322");
+    assert_eq!(
+        handle
+            .find_commit(&short_history_link, &mut buf)?
+            .parents()
+            .collect::<Vec<_>>(),
+        vec![orphan_of_new_history]
+    );
+    assert!(
+        !store.has_loaded_replacements(),
+        "replacements aren't loaded if they are ignored"
+    );
+
+    handle.ignore_replacements = false;
+    let long_history_second_id = hex_to_id("753ccf815e7b69c9147db5bbf633fe5f7da24ad7");
+    assert_eq!(
+        handle
+            .find_commit(&short_history_link, &mut buf)?
+            .parents()
+            .collect::<Vec<_>>(),
+        vec![long_history_second_id],
+        "replacements are loaded on first use"
+    );
+    assert!(store.has_loaded_replacements());
+    assert_eq!(
+        store.replacements().collect::<Vec<_>>(),
+        vec![(short_history_link, long_history_tip)]
+    );
+    Ok(())
+}
+
 #[test]
 fn contains() {
     let handle = db();
@@ -45,6 +45,7 @@ fn verify_integrity() {
 }
 
 mod write {
+    use gix_features::zlib::{Backend, Compression};
     use gix_odb::{loose, Write};
 
     use crate::store::loose::{locate_oid, object_ids};
@@ -74,6 +75,41 @@ mod write {
         Ok(())
     }
 
+    #[test]
+    fn compression_level_affects_object_size_but_not_content() -> crate::Result {
+        let mut buf = Vec::new();
+        let big_blob = locate_oid(crate::hex_to_id("a706d7cd20fc8ce71489f34b50cf01011c104193"), &mut buf);
+
+        let mut sizes = Vec::new();
+        for level in [Compression::none(), Compression::best()] {
+            let dir = gix_testtools::tempfile::tempdir()?;
+            let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1).with_compression(level);
+            assert_eq!(db.compression(), level);
+            let id = db.write_buf(big_blob.kind, big_blob.data)?;
+            let mut buf2 = Vec::new();
+            assert_eq!(db.try_find(&id, &mut buf2)?.expect("id present").data, big_blob.data);
+            sizes.push(db.object_path(&id).metadata()?.len());
+        }
+        assert!(sizes[0] > sizes[1], "no compression creates bigger objects: {sizes:?}");
+        Ok(())
+    }
+
+    #[test]
+    fn zlib_backend_must_be_available_to_write() -> crate::Result {
+        for backend in [Backend::MinizOxide, Backend::Zlib, Backend::ZlibNg] {
+            let dir = gix_testtools::tempfile::tempdir()?;
+            let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1).with_zlib_backend(backend);
+            assert_eq!(db.zlib_backend(), backend);
+            let res = db.write_buf(gix_object::Kind::Blob, b"hello");
+            assert_eq!(res.is_ok(), backend.is_available(), "{backend:?}");
+            if let Ok(id) = res {
+                let mut buf = Vec::new();
+                assert_eq!(db.try_find(&id, &mut buf)?.expect("id present").data, b"hello");
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     #[cfg(unix)]
     fn it_writes_objects_with_similar_permissions() -> crate::Result {
@@ -0,0 +1,282 @@
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::{progress::Progress, zlib};
+use gix_hash::ObjectId;
+
+use crate::{data, Bundle};
+
+/// The error returned by [`Bundle::analyze()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    EntryType(#[from] data::entry::decode::Error),
+    #[error("Could not obtain the size of the object at pack offset {pack_offset}")]
+    ObjectSize {
+        pack_offset: data::Offset,
+        source: data::decode::Error,
+    },
+    #[error("The delta base of the entry at pack offset {pack_offset} is not an entry, but pointed to pack offset {base_pack_offset}")]
+    OfsDeltaBaseMissing {
+        pack_offset: data::Offset,
+        base_pack_offset: data::Offset,
+    },
+    #[error("The delta base {id} of the entry at pack offset {pack_offset} is not contained in the pack")]
+    RefDeltaBaseMissing { pack_offset: data::Offset, id: ObjectId },
+    #[error("The delta chain of the entry at pack offset {pack_offset} refers to itself")]
+    DeltaChainCycle { pack_offset: data::Offset },
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Information about a single object in a pack, similar to what `git verify-pack -v` prints.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Object {
+    /// The id of the object.
+    pub id: ObjectId,
+    /// The kind of the object, with deltas resolved to the kind of their chain base.
+    pub kind: gix_object::Kind,
+    /// The offset at which the entry of the object starts in the pack.
+    pub pack_offset: data::Offset,
+    /// The amount of bytes the entry occupies in the pack, including its header.
+    pub compressed_size: u64,
+    /// The amount of bytes of the decompressed entry, which are the delta instructions for deltified objects.
+    pub decompressed_size: u64,
+    /// The size of the object once all deltas are applied.
+    pub object_size: u64,
+    /// The amount of deltas that have to be applied to obtain the object, with 0 for undeltified objects.
+    pub num_deltas: u32,
+    /// The object this one is a delta against, or `None` if it isn't deltified.
+    pub base: Option<ObjectId>,
+    /// The undeltified object at the end of the delta chain, or `None` if this object isn't deltified.
+    pub chain_base: Option<ObjectId>,
+}
+
+/// Summaries about a set of objects in a pack.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Summary {
+    /// The amount of objects.
+    pub count: u32,
+    /// The amount of objects which are deltified.
+    pub num_deltified: u32,
+    /// The amount of bytes all entries occupy in the pack.
+    pub compressed_size: u64,
+    /// The amount of bytes of all decompressed entries.
+    pub decompressed_size: u64,
+    /// The amount of bytes of all objects once their deltas are applied.
+    pub object_size: u64,
+    /// The largest object by [object size](Object::object_size), along with its size.
+    pub largest_object: Option<(ObjectId, u64)>,
+}
+
+impl Summary {
+    fn add(&mut self, object: &Object) {
+        self.count += 1;
+        self.num_deltified += u32::from(object.num_deltas > 0);
+        self.compressed_size += object.compressed_size;
+        self.decompressed_size += object.decompressed_size;
+        self.object_size += object.object_size;
+        if self
+            .largest_object
+            .as_ref()
+            .map_or(true, |(_, size)| object.object_size > *size)
+        {
+            self.largest_object = Some((object.id, object.object_size));
+        }
+    }
+}
+
+/// Statistics about all objects in a pack, similar to what `git verify-pack -v` and `git-sizer` print.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// A mapping of the length of the chain to the amount of objects at that length, with 0 for undeltified objects.
+    pub objects_per_chain_length: BTreeMap<u32, u32>,
+    /// The object with the longest delta chain, along with the length of its chain.
+    pub longest_chain: Option<(ObjectId, u32)>,
+    /// A summary of all objects.
+    pub total: Summary,
+    /// A summary of all commits.
+    pub commits: Summary,
+    /// A summary of all trees.
+    pub trees: Summary,
+    /// A summary of all blobs.
+    pub blobs: Summary,
+    /// A summary of all tags.
+    pub tags: Summary,
+    /// The amount of bytes occupied by the pack itself.
+    pub pack_size: u64,
+}
+
+/// The outcome of [`Bundle::analyze()`].
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// All objects in the pack, in the order of their entries in the pack.
+    pub objects: Vec<Object>,
+    /// Statistics about all `objects`.
+    pub statistics: Statistics,
+}
+
+impl Outcome {
+    /// Return up to `n` objects which occupy the most space in the pack, largest first.
+    pub fn top_space_consumers(&self, n: usize) -> Vec<&Object> {
+        let mut objects: Vec<_> = self.objects.iter().collect();
+        objects.sort_by(|a, b| {
+            b.compressed_size
+                .cmp(&a.compressed_size)
+                .then(a.pack_offset.cmp(&b.pack_offset))
+        });
+        objects.truncate(n);
+        objects
+    }
+}
+
+/// An entry in the pack whose delta chain may not be resolved yet.
+struct Entry {
+    id: ObjectId,
+    pack_offset: data::Offset,
+    compressed_size: u64,
+    decompressed_size: u64,
+    object_size: u64,
+    /// The position of the delta base in the list of entries.
+    base: Option<usize>,
+    /// The kind of the object, the amount of deltas and the position of the chain base, once known.
+    resolved: Option<(gix_object::Kind, u32, usize)>,
+}
+
+impl Bundle {
+    /// Analyze all objects in the pack to learn about their sizes and delta chains, without decompressing more than
+    /// the beginning of deltified entries.
+    ///
+    /// `progress` is informed about each object as its entry is read, and `should_interrupt` is checked as often.
+    pub fn analyze(&self, progress: &mut dyn Progress, should_interrupt: &AtomicBool) -> Result<Outcome, Error> {
+        let mut ids_and_offsets: Vec<_> = self.index.iter().map(|e| (e.pack_offset, e.oid)).collect();
+        ids_and_offsets.sort_by_key(|(pack_offset, _)| *pack_offset);
+        let position_of_offset = |pack_offset: data::Offset| {
+            ids_and_offsets
+                .binary_search_by_key(&pack_offset, |(pack_offset, _)| *pack_offset)
+                .ok()
+        };
+
+        progress.init(Some(ids_and_offsets.len()), gix_features::progress::count("objects"));
+        let pack_end = self.pack.pack_end() as u64;
+        let mut inflate = zlib::Inflate::default();
+        let mut entries = Vec::with_capacity(ids_and_offsets.len());
+        for (idx, (pack_offset, id)) in ids_and_offsets.iter().enumerate() {
+            if should_interrupt.load(Ordering::Relaxed) {
+                return Err(Error::Interrupted);
+            }
+            let pack_offset = *pack_offset;
+            let entry = self.pack.entry(pack_offset)?;
+            let next_offset = ids_and_offsets.get(idx + 1).map_or(pack_end, |(offset, _)| *offset);
+            let (base, object_size) = match entry.header {
+                data::entry::Header::OfsDelta { base_distance } => {
+                    let base_pack_offset = entry.base_pack_offset(base_distance);
+                    let base = position_of_offset(base_pack_offset).ok_or(Error::OfsDeltaBaseMissing {
+                        pack_offset,
+                        base_pack_offset,
+                    })?;
+                    (Some(base), self.delta_object_size(&mut inflate, &entry)?)
+                }
+                data::entry::Header::RefDelta { base_id } => {
+                    let base = self
+                        .index
+                        .lookup(base_id)
+                        .and_then(|idx| position_of_offset(self.index.pack_offset_at_index(idx)))
+                        .ok_or(Error::RefDeltaBaseMissing {
+                            pack_offset,
+                            id: base_id,
+                        })?;
+                    (Some(base), self.delta_object_size(&mut inflate, &entry)?)
+                }
+                _ => (None, entry.decompressed_size),
+            };
+            entries.push(Entry {
+                id: *id,
+                pack_offset,
+                compressed_size: next_offset - pack_offset,
+                decompressed_size: entry.decompressed_size,
+                object_size,
+                base,
+                resolved: entry.header.as_kind().map(|kind| (kind, 0, idx)),
+            });
+            progress.inc();
+        }
+
+        let mut chain = Vec::new();
+        for idx in 0..entries.len() {
+            let mut cursor = idx;
+            let (kind, mut num_deltas, chain_base) = loop {
+                if let Some(resolved) = entries[cursor].resolved {
+                    break resolved;
+                }
+                if chain.len() == entries.len() {
+                    return Err(Error::DeltaChainCycle {
+                        pack_offset: entries[idx].pack_offset,
+                    });
+                }
+                chain.push(cursor);
+                cursor = entries[cursor].base.expect("unresolved entries are deltas");
+            };
+            for cursor in chain.drain(..).rev() {
+                num_deltas += 1;
+                entries[cursor].resolved = Some((kind, num_deltas, chain_base));
+            }
+        }
+
+        let mut statistics = Statistics {
+            pack_size: self.pack.data_len() as u64,
+            ..Default::default()
+        };
+        let objects: Vec<_> = entries
+            .iter()
+            .map(|entry| {
+                let (kind, num_deltas, chain_base) = entry.resolved.expect("all entries are resolved");
+                let object = Object {
+                    id: entry.id,
+                    kind,
+                    pack_offset: entry.pack_offset,
+                    compressed_size: entry.compressed_size,
+                    decompressed_size: entry.decompressed_size,
+                    object_size: entry.object_size,
+                    num_deltas,
+                    base: entry.base.map(|base| entries[base].id),
+                    chain_base: (num_deltas > 0).then(|| entries[chain_base].id),
+                };
+                *statistics.objects_per_chain_length.entry(num_deltas).or_insert(0) += 1;
+                if statistics
+                    .longest_chain
+                    .as_ref()
+                    .map_or(num_deltas > 0, |(_, longest)| num_deltas > *longest)
+                {
+                    statistics.longest_chain = Some((object.id, num_deltas));
+                }
+                statistics.total.add(&object);
+                match kind {
+                    gix_object::Kind::Commit => &mut statistics.commits,
+                    gix_object::Kind::Tree => &mut statistics.trees,
+                    gix_object::Kind::Blob => &mut statistics.blobs,
+                    gix_object::Kind::Tag => &mut statistics.tags,
+                }
+                .add(&object);
+                object
+            })
+            .collect();
+        Ok(Outcome { objects, statistics })
+    }
+
+    fn delta_object_size(&self, inflate: &mut zlib::Inflate, entry: &data::Entry) -> Result<u64, Error> {
+        self.pack
+            .decode_delta_object_size(inflate, entry)
+            .map_err(|source| Error::ObjectSize {
+                pack_offset: entry.pack_offset(),
+                source,
+            })
+    }
+}
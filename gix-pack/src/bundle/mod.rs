@@ -2,6 +2,9 @@
 #[allow(clippy::empty_docs)]
 pub mod init;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod analyze;
 mod find;
 ///
 #[cfg(all(not(feature = "wasm"), feature = "streaming-input"))]
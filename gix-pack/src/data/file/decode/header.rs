@@ -93,7 +93,11 @@ impl File {
     }
 
     #[inline]
-    fn decode_delta_object_size(&self, inflate: &mut zlib::Inflate, entry: &data::Entry) -> Result<u64, Error> {
+    pub(crate) fn decode_delta_object_size(
+        &self,
+        inflate: &mut zlib::Inflate,
+        entry: &data::Entry,
+    ) -> Result<u64, Error> {
         let mut buf = [0_u8; 32];
         let used = self
             .decompress_entry_from_data_offset_2(entry.data_offset, inflate, &mut buf)?
@@ -102,7 +102,12 @@ where
         }
         match self.input.next() {
             Some(entries) => {
-                for entry in entries.map_err(Error::Input)? {
+                let entries = entries.map_err(Error::Input)?;
+                let _span = gix_features::trace::detail!(
+                    "gix_pack::data::output::bytes::FromEntriesIter::next()",
+                    entries = entries.len()
+                );
+                for entry in entries {
                     if entry.is_invalid() {
                         self.pack_offsets_and_validity.push((0, false));
                         continue;
@@ -120,6 +125,11 @@ where
                 }
             }
             None => {
+                gix_features::trace::debug!(
+                    objects = self.pack_offsets_and_validity.len(),
+                    bytes = self.written,
+                    "wrote pack entries"
+                );
                 let digest = self.output.hash.clone().digest();
                 self.output.inner.write_all(&digest[..])?;
                 self.written += digest.len() as u64;
@@ -43,6 +43,7 @@ pub fn objects<Find>(
 where
     Find: crate::Find + Send + Clone,
 {
+    let _span = gix_features::trace::coarse!("gix_pack::data::output::count::objects()");
     let lower_bound = objects_ids.size_hint().0;
     let (chunk_size, thread_limit, _) = parallel::optimize_chunk_size_and_thread_limit(
         chunk_size,
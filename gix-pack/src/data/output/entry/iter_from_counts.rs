@@ -52,6 +52,8 @@ pub(crate) mod function {
             allow_thin_pack,
             thread_limit,
             chunk_size,
+            compression_level,
+            zlib_backend,
         }: Options,
     ) -> impl Iterator<Item = Result<(SequenceId, Vec<output::Entry>), Error>>
            + parallel::reduce::Finalize<Reduce = reduce::Statistics<Error>>
@@ -214,7 +216,12 @@ pub(crate) mod function {
                                     None => match db.try_find(&count.id, buf).map_err(Error::Find)? {
                                         Some((obj, _location)) => {
                                             stats.decoded_and_recompressed_objects += 1;
-                                            output::Entry::from_data(count, &obj)
+                                            output::Entry::from_data_with_compression(
+                                                count,
+                                                &obj,
+                                                zlib_backend,
+                                                gix_features::zlib::Compression::new(compression_level),
+                                            )
                                         }
                                         None => {
                                             stats.missing_objects += 1;
@@ -226,7 +233,12 @@ pub(crate) mod function {
                             None => match db.try_find(&count.id, buf).map_err(Error::Find)? {
                                 Some((obj, _location)) => {
                                     stats.decoded_and_recompressed_objects += 1;
-                                    output::Entry::from_data(count, &obj)
+                                    output::Entry::from_data_with_compression(
+                                        count,
+                                        &obj,
+                                        zlib_backend,
+                                        gix_features::zlib::Compression::new(compression_level),
+                                    )
                                 }
                                 None => {
                                     stats.missing_objects += 1;
@@ -385,6 +397,11 @@ mod types {
         pub chunk_size: usize,
         /// The pack data version to produce for each entry
         pub version: crate::data::Version,
+        /// The compression level from 0 (none) to 9 (best) to use for objects that need to be recompressed,
+        /// similar to `pack.compression`.
+        pub compression_level: u32,
+        /// The zlib implementation to recompress objects with, which must be available in this build.
+        pub zlib_backend: gix_features::zlib::Backend,
     }
 
     impl Default for Options {
@@ -395,6 +412,8 @@ mod types {
                 allow_thin_pack: false,
                 chunk_size: 10,
                 version: Default::default(),
+                compression_level: gix_features::zlib::Compression::fast().level(),
+                zlib_backend: gix_features::zlib::Backend::compiled(),
             }
         }
     }
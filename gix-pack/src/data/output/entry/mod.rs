@@ -40,6 +40,8 @@ pub enum Error {
     ZlibDeflate(#[from] std::io::Error),
     #[error(transparent)]
     EntryType(#[from] crate::data::entry::decode::Error),
+    #[error(transparent)]
+    ZlibBackend(#[from] gix_features::zlib::backend::Error),
 }
 
 impl output::Entry {
@@ -132,14 +134,31 @@ impl output::Entry {
         })
     }
 
-    /// Create a new instance from the given `oid` and its corresponding git object data `obj`.
+    /// Create a new instance from the given `oid` and its corresponding git object data `obj`, compressed with the fastest level.
     pub fn from_data(count: &output::Count, obj: &gix_object::Data<'_>) -> Result<Self, Error> {
+        Self::from_data_with_compression(
+            count,
+            obj,
+            gix_features::zlib::Backend::compiled(),
+            gix_features::zlib::Compression::fast(),
+        )
+    }
+
+    /// Create a new instance from the given `oid` and its corresponding git object data `obj`, compressed with `compression`
+    /// by `backend`, which fails if `backend` isn't available in this build.
+    pub fn from_data_with_compression(
+        count: &output::Count,
+        obj: &gix_object::Data<'_>,
+        backend: gix_features::zlib::Backend,
+        compression: gix_features::zlib::Compression,
+    ) -> Result<Self, Error> {
         Ok(output::Entry {
             id: count.id.to_owned(),
             kind: Kind::Base(obj.kind),
             decompressed_size: obj.data.len(),
             compressed_data: {
-                let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+                let mut out =
+                    gix_features::zlib::stream::deflate::Write::new_with_backend(Vec::new(), backend, compression)?;
                 if let Err(err) = std::io::copy(&mut &*obj.data, &mut out) {
                     match err.kind() {
                         std::io::ErrorKind::Other => return Err(Error::ZlibDeflate(err)),
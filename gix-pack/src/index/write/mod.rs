@@ -98,6 +98,7 @@ impl crate::index::File {
         R: Send + Sync,
         F2: for<'r> Fn(crate::data::EntryRange, &'r R) -> Option<&'r [u8]> + Send + Clone,
     {
+        let _span = gix_features::trace::coarse!("gix_pack::index::File::write_data_iter_to_stream()");
         if version != crate::index::Version::default() {
             return Err(Error::Unsupported(version));
         }
@@ -192,10 +193,7 @@ impl crate::index::File {
                      entry,
                      decompressed: bytes,
                      ..
-                 }| {
-                    modify_base(data, entry, bytes, version.hash());
-                    Ok::<_, Error>(())
-                },
+                 }| { modify_base(data, entry, bytes, version.hash()) },
                 traverse::Options {
                     object_progress: Box::new(
                         root_progress.add_child_with_id("Resolving".into(), ProgressId::ResolveObjects.into()),
@@ -253,8 +251,17 @@ impl crate::index::File {
     }
 }
 
-fn modify_base(entry: &mut TreeEntry, pack_entry: &crate::data::Entry, decompressed: &[u8], hash: gix_hash::Kind) {
+fn modify_base(
+    entry: &mut TreeEntry,
+    pack_entry: &crate::data::Entry,
+    decompressed: &[u8],
+    hash: gix_hash::Kind,
+) -> Result<(), Error> {
     let object_kind = pack_entry.header.as_kind().expect("base object as source of iteration");
-    let id = gix_object::compute_hash(hash, object_kind, decompressed);
+    let id = gix_object::try_compute_hash(hash, object_kind, decompressed).map_err(|err| Error::Collision {
+        pack_offset: pack_entry.pack_offset(),
+        source: err,
+    })?;
     entry.id = id;
+    Ok(())
 }
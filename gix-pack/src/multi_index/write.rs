@@ -83,7 +83,6 @@ impl multi_index::File {
         should_interrupt: &AtomicBool,
         Options { object_hash }: Options,
     ) -> Result<Outcome, Error> {
-        let out = gix_features::hash::Write::new(out, object_hash);
         let (index_paths_sorted, index_filenames_sorted) = {
             index_paths.sort();
             let file_names = index_paths
@@ -171,10 +170,13 @@ impl multi_index::File {
             Some(cf.planned_storage_size() as usize + Self::HEADER_LEN),
             gix_features::progress::bytes(),
         );
-        let mut out = gix_features::progress::Write {
-            inner: out,
-            progress: write_progress,
-        };
+        let mut out = gix_features::hash::Write::new(
+            gix_features::progress::Write {
+                inner: out,
+                progress: write_progress,
+            },
+            object_hash,
+        );
 
         let bytes_written = Self::write_header(
             &mut out,
@@ -183,11 +185,11 @@ impl multi_index::File {
             object_hash,
         )?;
 
-        {
+        let multi_index_checksum = {
             progress.set_name("Writing chunks".into());
             progress.init(Some(cf.num_chunks()), gix_features::progress::count("chunks"));
 
-            let mut chunk_write = cf.into_write(&mut out, bytes_written)?;
+            let mut chunk_write = cf.into_write(out, bytes_written)?;
             while let Some(chunk_to_write) = chunk_write.next_chunk() {
                 match chunk_to_write {
                     multi_index::chunk::index_names::ID => {
@@ -210,12 +212,11 @@ impl multi_index::File {
                     return Err(Error::Interrupted);
                 }
             }
-        }
 
-        // write trailing checksum
-        let multi_index_checksum: gix_hash::ObjectId = out.inner.hash.digest().into();
-        out.inner.inner.write_all(multi_index_checksum.as_slice())?;
-        out.progress.show_throughput(write_start);
+            let (multi_index_checksum, out) = chunk_write.write_trailing_checksum()?;
+            out.progress.show_throughput(write_start);
+            multi_index_checksum
+        };
 
         Ok(Outcome { multi_index_checksum })
     }
@@ -181,3 +181,132 @@ mod write_to_directory {
         .map_err(Into::into)
     }
 }
+
+mod analyze {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::{progress, zlib};
+    use gix_object::Kind;
+    use gix_odb::pack;
+
+    use crate::{
+        fixture_path, hex_to_id,
+        pack::{INDEX_V2, SMALL_PACK_INDEX, V2_PACKS_AND_INDICES},
+    };
+
+    fn analyze(index_path: &str) -> crate::Result<(pack::Bundle, pack::bundle::analyze::Outcome)> {
+        let bundle = pack::Bundle::at(fixture_path(index_path), gix_hash::Kind::Sha1)?;
+        let outcome = bundle.analyze(&mut progress::Discard, &AtomicBool::default())?;
+        Ok((bundle, outcome))
+    }
+
+    #[test]
+    fn objects_match_verify_pack() -> crate::Result {
+        let (_bundle, outcome) = analyze(SMALL_PACK_INDEX)?;
+        assert_eq!(outcome.objects.len(), 42);
+        assert!(
+            outcome
+                .objects
+                .windows(2)
+                .all(|w| w[0].pack_offset + w[0].compressed_size == w[1].pack_offset),
+            "objects are in pack order and their entries follow each other"
+        );
+
+        let object = |hex: &str| {
+            outcome
+                .objects
+                .iter()
+                .find(|o| o.id == hex_to_id(hex))
+                .expect("object present")
+        };
+        assert_eq!(
+            *object("78bed30acc35358406b51120a2e7d29a0cebd6c1"),
+            pack::bundle::analyze::Object {
+                id: hex_to_id("78bed30acc35358406b51120a2e7d29a0cebd6c1"),
+                kind: Kind::Blob,
+                pack_offset: 2392,
+                compressed_size: 617,
+                decompressed_size: 1181,
+                object_size: 1181,
+                num_deltas: 0,
+                base: None,
+                chain_base: None,
+            },
+            "undeltified objects are their own chain base"
+        );
+        let delta = object("b8aa61be84b78d7fcff788e8d844406cc97132bf");
+        assert_eq!(
+            delta.kind,
+            Kind::Blob,
+            "the kind of deltas is the one of their chain base"
+        );
+        assert_eq!(delta.pack_offset, 3033);
+        assert_eq!(delta.compressed_size, 16);
+        assert_eq!(delta.decompressed_size, 6, "the size of the delta instructions");
+        assert_eq!(delta.num_deltas, 2);
+        assert_eq!(delta.base, Some(hex_to_id("e0dc2c78d3bd3d14d20906ad7905a32f0f0918a8")));
+        assert_eq!(
+            delta.chain_base,
+            Some(hex_to_id("78bed30acc35358406b51120a2e7d29a0cebd6c1"))
+        );
+
+        let stats = &outcome.statistics;
+        assert_eq!(
+            stats.objects_per_chain_length,
+            [(0, 30), (1, 6), (2, 6)].into_iter().collect(),
+            "these are the chain lengths reported by `git verify-pack -v`"
+        );
+        assert_eq!(stats.total.count, 42);
+        assert_eq!(stats.total.num_deltified, 12);
+        assert_eq!(
+            stats.commits.count + stats.trees.count + stats.blobs.count + stats.tags.count,
+            stats.total.count
+        );
+        assert_eq!(
+            stats.total.compressed_size,
+            3732 - 12 - 20,
+            "all entries, without pack header and trailer"
+        );
+        assert_eq!(stats.pack_size, 3732);
+        Ok(())
+    }
+
+    #[test]
+    fn object_sizes_and_kinds_match_decoded_objects() -> crate::Result {
+        for (index_path, _data_path) in V2_PACKS_AND_INDICES {
+            let (bundle, outcome) = analyze(index_path)?;
+            let mut buf = Vec::new();
+            for object in &outcome.objects {
+                let (data, _location) = bundle
+                    .find(
+                        &object.id,
+                        &mut buf,
+                        &mut zlib::Inflate::default(),
+                        &mut pack::cache::Never,
+                    )?
+                    .expect("object present");
+                assert_eq!(data.kind, object.kind);
+                assert_eq!(data.data.len() as u64, object.object_size);
+                assert_eq!(object.base.is_some(), object.num_deltas > 0);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn longest_chain_and_top_space_consumers() -> crate::Result {
+        let (_bundle, outcome) = analyze(INDEX_V2)?;
+        assert_eq!(
+            outcome.statistics.longest_chain,
+            Some((hex_to_id("18bd3fc20b0565f94bce0a3e94b6a83b26b88627"), 6))
+        );
+
+        let top = outcome.top_space_consumers(3);
+        assert_eq!(top.len(), 3);
+        assert!(top.windows(2).all(|w| w[0].compressed_size >= w[1].compressed_size));
+        let max = outcome.objects.iter().map(|o| o.compressed_size).max();
+        assert_eq!(Some(top[0].compressed_size), max);
+        assert_eq!(outcome.top_space_consumers(usize::MAX).len(), outcome.objects.len());
+        Ok(())
+    }
+}
@@ -245,6 +245,110 @@ pub fn to_windows_separators<'a>(path: impl Into<Cow<'a, BStr>>) -> Cow<'a, BStr
     replace(path, b'/', b'\\')
 }
 
+/// The length in bytes at which paths need the `\\?\` prefix to be usable on Windows, which is `MAX_PATH`
+/// minus the 12 characters reserved for 8.3 file names when creating directories.
+const WINDOWS_LONG_PATH_LEN: usize = 260 - 12;
+
+/// Turn the absolute Windows `path` into a verbatim path with the `\\?\` prefix if it is too long to be used otherwise,
+/// or return it unchanged, unconditionally.
+///
+/// As verbatim paths are passed to the filesystem as is, separators are turned into backslashes and `.` and `..`
+/// components are resolved. UNC paths like `\\server\share\path` become `\\?\UNC\server\share\path`.
+/// Relative paths, drive-relative paths like `C:path` and root-relative paths like `\path` are returned unchanged as they
+/// can't be turned into verbatim paths without knowing the current directory, as well as paths that already are verbatim.
+///
+/// **Note** Do not use these and prefer the conditional versions of this method.
+pub fn to_windows_long_path<'a>(path: impl Into<Cow<'a, BStr>>) -> Cow<'a, BStr> {
+    let path = path.into();
+    if path.len() < WINDOWS_LONG_PATH_LEN || path.starts_with(br"\\?\") || path.starts_with(br"\\.\") {
+        return path;
+    }
+    let is_separator = |b: &u8| *b == b'\\' || *b == b'/';
+    let (prefix, path_without_prefix, num_root_components): (&[u8], _, _) =
+        if path.len() > 2 && path[0].is_ascii_alphabetic() && path[1] == b':' && is_separator(&path[2]) {
+            (br"\\?\", &path[..], 1)
+        } else if path.len() > 2 && is_separator(&path[0]) && is_separator(&path[1]) {
+            (br"\\?\UNC\", &path[2..], 2)
+        } else {
+            return path;
+        };
+
+    let mut components = Vec::new();
+    for component in path_without_prefix.split(is_separator) {
+        match component {
+            b"" | b"." => {}
+            b".." => {
+                if components.len() > num_root_components {
+                    components.pop();
+                }
+            }
+            component => components.push(component),
+        }
+    }
+    let needs_trailing_separator = components.len() == 1;
+    let mut out = BString::from(prefix);
+    for (idx, component) in components.into_iter().enumerate() {
+        if idx != 0 {
+            out.push(b'\\');
+        }
+        out.extend_from_slice(component);
+    }
+    if needs_trailing_separator {
+        out.push(b'\\');
+    }
+    out.into()
+}
+
+/// Remove the `\\?\` prefix of a verbatim Windows `path`, turning `\\?\UNC\server\share` into `\\server\share`, unconditionally.
+///
+/// This is useful to store paths, or to show them to users, after they were [made verbatim](to_windows_long_path()),
+/// or returned as such by [`std::fs::canonicalize()`].
+///
+/// **Note** Do not use these and prefer the conditional versions of this method.
+pub fn from_windows_long_path<'a>(path: impl Into<Cow<'a, BStr>>) -> Cow<'a, BStr> {
+    let path = path.into();
+    if let Some(unc) = path.strip_prefix(br"\\?\UNC\") {
+        let mut out = BString::from(br"\\".as_slice());
+        out.extend_from_slice(unc);
+        return out.into();
+    }
+    match path {
+        Cow::Borrowed(path) => match path.strip_prefix(br"\\?\") {
+            Some(path) => Cow::Borrowed(path.into()),
+            None => Cow::Borrowed(path),
+        },
+        Cow::Owned(mut path) => {
+            if path.starts_with(br"\\?\") {
+                path.drain(..4);
+            }
+            path.into()
+        }
+    }
+}
+
+/// Turn absolute paths that are too long to be used on Windows into verbatim paths with the `\\?\` prefix,
+/// but only do so on Windows. See [`to_windows_long_path()`] for details.
+///
+/// Paths that can't be represented as UTF-8 are returned unchanged.
+pub fn to_long_path_on_windows<'a>(path: impl Into<Cow<'a, Path>>) -> Cow<'a, Path> {
+    #[cfg(windows)]
+    {
+        let path = path.into();
+        let long_path = match crate::try_into_bstr(path.as_ref()) {
+            Ok(bytes) => match to_windows_long_path(bytes) {
+                Cow::Owned(long_path) => Some(crate::from_bstring(long_path)),
+                Cow::Borrowed(_) => None,
+            },
+            Err(_) => None,
+        };
+        long_path.map_or(path, Cow::Owned)
+    }
+    #[cfg(not(windows))]
+    {
+        path.into()
+    }
+}
+
 /// Resolve relative components virtually without accessing the file system, e.g. turn `a/./b/c/.././..` into `a`,
 /// without keeping intermediate `..` and `/a/../b/..` becomes `/`.
 /// If the input path was relative and ends up being the `current_dir`, `.` is returned instead of the full path to `current_dir`.
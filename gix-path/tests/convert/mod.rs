@@ -98,3 +98,106 @@ mod relativize_with_prefix {
         assert_eq!(r("", ""), "", "empty stays empty");
     }
 }
+
+mod windows_long_path {
+    use bstr::{BStr, ByteSlice};
+    use gix_path::{from_windows_long_path, to_windows_long_path};
+
+    fn long_component() -> String {
+        "c".repeat(250)
+    }
+
+    fn long(path: &str) -> String {
+        to_windows_long_path(path.as_bytes().as_bstr()).to_string()
+    }
+
+    #[test]
+    fn short_paths_are_unchanged() {
+        for path in [r"C:\short\path", "C:/short/path", r"\\server\share\path"] {
+            assert_eq!(long(path), path);
+        }
+    }
+
+    #[test]
+    fn long_absolute_paths_with_drive_are_made_verbatim() {
+        let c = long_component();
+        assert_eq!(long(&format!("C:/dir/{c}")), format!(r"\\?\C:\dir\{c}"));
+        assert_eq!(
+            long(&format!(r"C:\dir\.\other\..\{c}\")),
+            format!(r"\\?\C:\dir\{c}"),
+            "relative components are resolved, trailing separators are removed"
+        );
+        assert_eq!(
+            long(&format!(r"C:\..\..\{c}")),
+            format!(r"\\?\C:\{c}"),
+            "the drive can't be popped"
+        );
+    }
+
+    #[test]
+    fn long_unc_paths_are_made_verbatim() {
+        let c = long_component();
+        assert_eq!(
+            long(&format!(r"\\server\share\..\..\{c}")),
+            format!(r"\\?\UNC\server\share\{c}"),
+            "server and share can't be popped"
+        );
+        assert_eq!(
+            long(&format!("//server/share/{c}")),
+            format!(r"\\?\UNC\server\share\{c}")
+        );
+    }
+
+    #[test]
+    fn long_relative_and_verbatim_paths_are_unchanged() {
+        let c = long_component();
+        for path in [
+            format!(r"relative\{c}"),
+            format!(r"C:drive-relative\{c}"),
+            format!(r"\root-relative\{c}"),
+            format!(r"\\?\C:\already-verbatim\{c}"),
+            format!(r"\\.\device\{c}"),
+        ] {
+            assert_eq!(long(&path), path);
+        }
+    }
+
+    #[test]
+    fn verbatim_prefixes_can_be_removed() {
+        fn r(path: &str) -> &BStr {
+            path.into()
+        }
+        assert_eq!(from_windows_long_path(r(r"\\?\C:\dir")), r(r"C:\dir"));
+        assert_eq!(from_windows_long_path(r(r"\\?\UNC\server\share")), r(r"\\server\share"));
+        assert_eq!(from_windows_long_path(r(r"C:\dir")), r(r"C:\dir"));
+
+        let c = long_component();
+        let path = format!(r"C:\dir\{c}");
+        assert_eq!(
+            from_windows_long_path(to_windows_long_path(r(&path))),
+            r(&path),
+            "a roundtrip is possible for normalized paths"
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn conditional_conversion_allows_creating_long_paths() -> crate::Result {
+        let tmp = tempfile::tempdir()?;
+        let mut path = gix_path::realpath(tmp.path())?;
+        for _ in 0..4 {
+            path.push("c".repeat(100));
+        }
+        let long_path = gix_path::to_long_path_on_windows(path.as_path());
+        std::fs::create_dir_all(&long_path)?;
+        std::fs::write(long_path.join("file"), b"content")?;
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn conditional_conversion_is_a_no_op() {
+        let path = std::path::PathBuf::from(format!("/{}", long_component()));
+        assert_eq!(gix_path::to_long_path_on_windows(path.as_path()), path.as_path());
+    }
+}
@@ -27,10 +27,45 @@ use std::{borrow::Cow, io::Read};
 
 use bstr::{BStr, BString, ByteSlice};
 
+/// Quote `input` in the ansi-c style used by `git` if it contains bytes that need escaping, or return it unaltered otherwise.
+///
+/// Quoting is needed for double-quotes, backslashes and control characters, which are escaped as `\n`, `\t` and the like,
+/// or as three-digit octal escape if there is no shorthand. If `quote_non_ascii` is `true`, bytes with the high-bit set
+/// are escaped in octal as well, which is what `core.quotePath` controls and what `git` does by default.
+/// Quoted output is surrounded by double quotes, and can be turned back into `input` with [`undo()`].
+pub fn quote(input: &BStr, quote_non_ascii: bool) -> Cow<'_, BStr> {
+    let needs_quoting = |b: u8| b == b'"' || b == b'\\' || b < 0x20 || b == 0x7f || (quote_non_ascii && b >= 0x80);
+    if !input.iter().any(|b| needs_quoting(*b)) {
+        return input.into();
+    }
+    let mut out = BString::from(Vec::with_capacity(input.len() + 2));
+    out.push(b'"');
+    for &b in input.iter() {
+        if !needs_quoting(b) {
+            out.push(b);
+            continue;
+        }
+        out.push(b'\\');
+        match b {
+            7 => out.push(b'a'),
+            8 => out.push(b'b'),
+            b'\t' => out.push(b't'),
+            b'\n' => out.push(b'n'),
+            0xb => out.push(b'v'),
+            0xc => out.push(b'f'),
+            b'\r' => out.push(b'r'),
+            b'"' | b'\\' => out.push(b),
+            _ => out.extend_from_slice(&[b'0' + (b >> 6), b'0' + ((b >> 3) & 7), b'0' + (b & 7)]),
+        }
+    }
+    out.push(b'"');
+    out.into()
+}
+
 /// Unquote the given ansi-c quoted `input` string, returning it and all of the consumed bytes.
 ///
 /// The `input` is returned unaltered if it doesn't start with a `"` character to indicate
-/// quotation, otherwise a new unquoted string will always be allocated.
+/// quotation, otherwise a new unquoted string will always be allocated, and it's an error if the closing `"` is missing.
 /// The amount of consumed bytes allow to pass strings that start with a quote, and skip all quoted text for additional processing
 ///
 /// See [the tests][tests] for quotation examples.
@@ -107,11 +142,7 @@ pub fn undo(input: &BStr) -> Result<(Cow<'_, BStr>, usize), undo::Error> {
                     _ => unreachable!("cannot find character that we didn't search for"),
                 }
             }
-            None => {
-                out.extend_from_slice(input);
-                consumed += input.len();
-                break;
-            }
+            None => return Err(undo::Error::new("Missing closing double quote", original)),
         }
     }
     Ok((out.into(), consumed))
@@ -35,6 +35,61 @@ mod single {
 }
 
 mod ansi_c {
+    mod quote {
+        use bstr::ByteSlice;
+        use gix_quote::ansi_c;
+
+        fn quote(input: &[u8], quote_non_ascii: bool) -> String {
+            ansi_c::quote(input.as_bstr(), quote_non_ascii)
+                .to_str_lossy()
+                .into_owned()
+        }
+
+        #[test]
+        fn unquoted_remains_unchanged_and_borrowed() {
+            for input in ["hello", "", "with space/and-slash", "'single'"] {
+                let actual = ansi_c::quote(input.into(), true);
+                assert!(matches!(actual, std::borrow::Cow::Borrowed(_)), "{input:?}");
+                assert_eq!(actual.as_bytes(), input.as_bytes());
+            }
+        }
+
+        #[test]
+        fn special_characters_use_shorthands() {
+            assert_eq!(quote(b"a\n\r\tb", true), r#""a\n\r\tb""#);
+            assert_eq!(quote(b"\x07\x08\x0b\x0c", true), r#""\a\b\v\f""#);
+            assert_eq!(quote(br#"a"b\c"#, true), r#""a\"b\\c""#);
+        }
+
+        #[test]
+        fn other_control_characters_are_octal() {
+            assert_eq!(quote(b"\x00\x1b\x7f", true), r#""\000\033\177""#);
+        }
+
+        #[test]
+        fn non_ascii_bytes_depend_on_configuration() {
+            let input = "ä".as_bytes();
+            assert_eq!(quote(input, true), r#""\303\244""#, "like `core.quotePath = true`");
+            assert_eq!(quote(input, false), "ä", "like `core.quotePath = false`");
+            assert_eq!(
+                ansi_c::quote(b"\xff\n".as_bstr(), false).as_bytes(),
+                b"\"\xff\\n\"",
+                "non-ascii bytes are passed as is"
+            );
+        }
+
+        #[test]
+        fn roundtrip_with_undo() {
+            let input: Vec<u8> = (0..=255).collect();
+            for quote_non_ascii in [false, true] {
+                let quoted = ansi_c::quote(input.as_bstr(), quote_non_ascii);
+                let (unquoted, consumed) = ansi_c::undo(quoted.as_ref()).expect("valid");
+                assert_eq!(unquoted.as_bytes(), input.as_slice());
+                assert_eq!(consumed, quoted.len());
+            }
+        }
+    }
+
     mod undo {
         use bstr::ByteSlice;
         use gix_quote::ansi_c;
@@ -81,6 +136,13 @@ mod ansi_c {
             assert_eq!(&input[consumed..], " out of quote");
         }
 
+        #[test]
+        fn missing_closing_quote_is_an_error() {
+            for invalid in ["\"", "\"unterminated", r#""escaped\""#] {
+                assert!(ansi_c::undo(invalid.into()).is_err(), "{invalid:?}");
+            }
+        }
+
         #[test]
         fn fuzzed() {
             for invalid in ["\"\\", "\"Q\u{2}QT\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\\0\0\\"] {
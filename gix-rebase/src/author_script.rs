@@ -0,0 +1,96 @@
+//! Encode and decode the `author-script` file, which holds the author of the commit at which a rebase stopped
+//! as shell variable assignments.
+use bstr::{BString, ByteSlice, ByteVec};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    /// The error returned by [`decode()`](super::decode()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The author script is missing the '{name}' variable")]
+        MissingVariable { name: &'static str },
+        #[error("The value of '{name}' isn't properly quoted")]
+        Unquote { name: &'static str },
+        #[error(transparent)]
+        Time(#[from] gix_date::parse::Error),
+    }
+}
+
+const NAME: &str = "GIT_AUTHOR_NAME";
+const EMAIL: &str = "GIT_AUTHOR_EMAIL";
+const DATE: &str = "GIT_AUTHOR_DATE";
+
+/// Encode `author` as a script like `git` writes it, with its name, email and date assigned to shell variables.
+pub fn encode(author: gix_actor::SignatureRef<'_>) -> BString {
+    let mut out = BString::default();
+    for (name, value) in [
+        (NAME, author.name.to_owned()),
+        (EMAIL, author.email.to_owned()),
+        (DATE, {
+            let mut date = BString::from("@");
+            date.push_str(author.time.to_bstring());
+            date
+        }),
+    ] {
+        out.push_str(name);
+        out.push_str("=");
+        quote(&value, &mut out);
+        out.push_byte(b'\n');
+    }
+    out
+}
+
+/// Decode the author from a `script` in the format written by [`encode()`].
+pub fn decode(script: &[u8]) -> Result<gix_actor::Signature, decode::Error> {
+    let find = |name: &'static str| -> Result<BString, decode::Error> {
+        let value = script
+            .lines()
+            .find_map(|line| line.strip_prefix(name.as_bytes())?.strip_prefix(b"="))
+            .ok_or(decode::Error::MissingVariable { name })?;
+        unquote(value).ok_or(decode::Error::Unquote { name })
+    };
+    let date = find(DATE)?;
+    let date = date.to_str_lossy();
+    Ok(gix_actor::Signature {
+        name: find(NAME)?,
+        email: find(EMAIL)?,
+        time: gix_date::parse(date.strip_prefix('@').unwrap_or(&date), None)?,
+    })
+}
+
+/// Quote `value` in single quotes like `git` does with `sq_quote_buf()`.
+fn quote(value: &[u8], out: &mut BString) {
+    out.push_byte(b'\'');
+    for &byte in value {
+        match byte {
+            b'\'' | b'!' => {
+                out.push_str(b"'\\");
+                out.push_byte(byte);
+                out.push_byte(b'\'');
+            }
+            _ => out.push_byte(byte),
+        }
+    }
+    out.push_byte(b'\'');
+}
+
+/// Undo what [`quote()`] does, returning `None` if `value` isn't quoted correctly.
+fn unquote(value: &[u8]) -> Option<BString> {
+    let mut out = BString::default();
+    let mut bytes = value.iter().copied();
+    loop {
+        match bytes.next() {
+            None => return Some(out),
+            Some(b'\'') => loop {
+                match bytes.next()? {
+                    b'\'' => break,
+                    byte => out.push_byte(byte),
+                }
+            },
+            Some(b'\\') => out.push_byte(bytes.next()?),
+            Some(_) => return None,
+        }
+    }
+}
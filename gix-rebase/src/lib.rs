@@ -1,2 +1,77 @@
-#![deny(rust_2018_idioms)]
+//! Data structures and file formats to represent a rebase in progress, compatible with the state `git` keeps in `.git/rebase-merge`.
+//!
+//! This crate doesn't apply any changes itself, which is left to higher-level crates like `gix`.
+#![deny(missing_docs, rust_2018_idioms)]
 #![forbid(unsafe_code)]
+
+///
+#[allow(clippy::empty_docs)]
+pub mod todo;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod author_script;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod state;
+
+/// A single step of a rebase, as listed in the `git-rebase-todo` file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Instruction {
+    /// Apply the changes of `commit` and commit them with its original message and author.
+    Pick {
+        /// The commit to apply.
+        commit: gix_hash::ObjectId,
+        /// The first line of the commit message, for display only.
+        summary: bstr::BString,
+    },
+    /// Do not apply `commit`.
+    Drop {
+        /// The commit to skip.
+        commit: gix_hash::ObjectId,
+        /// The first line of the commit message, for display only.
+        summary: bstr::BString,
+    },
+    /// Run `command` using the shell.
+    Exec {
+        /// The command line to run.
+        command: bstr::BString,
+    },
+    /// Stop the rebase here, to be continued later.
+    Break,
+}
+
+/// What to do with commits that become empty once applied, as their changes are already present.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmptyCommits {
+    /// Skip commits that become empty, like `git rebase --empty=drop`.
+    #[default]
+    Drop,
+    /// Commit them even though they are empty, like `git rebase --empty=keep`.
+    Keep,
+    /// Stop the rebase to let the user decide, like `git rebase --empty=stop`.
+    Stop,
+}
+
+/// The state of a rebase in progress, as stored in the `rebase-merge` directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// The full name of the reference that was checked out when the rebase started and which is updated when it finishes,
+    /// or `None` if `HEAD` was detached.
+    pub head_name: Option<bstr::BString>,
+    /// The commit on top of which the commits are replayed.
+    pub onto: gix_hash::ObjectId,
+    /// The commit `HEAD` pointed to before the rebase started.
+    pub orig_head: gix_hash::ObjectId,
+    /// The instructions that are yet to be performed, with the first one being next.
+    pub todo: Vec<Instruction>,
+    /// The instructions that were performed already, with the last one being the most recent one.
+    pub done: Vec<Instruction>,
+    /// The commit at which the rebase stopped, typically due to conflicts, if any.
+    pub stopped_at: Option<gix_hash::ObjectId>,
+    /// The commit holding local changes that were stashed when the rebase started, to be reapplied once it's done.
+    pub autostash: Option<gix_hash::ObjectId>,
+    /// What to do with commits that become empty.
+    pub empty: EmptyCommits,
+}
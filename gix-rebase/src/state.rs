@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use bstr::ByteSlice;
+
+use crate::{todo, EmptyCommits, State};
+
+/// The value `git` writes into `head-name` if `HEAD` was detached.
+const DETACHED_HEAD: &[u8] = b"detached HEAD";
+
+///
+#[allow(clippy::empty_docs)]
+pub mod read {
+    use std::path::PathBuf;
+
+    /// The error returned by [`State::read()`](crate::State::read()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read rebase state file at \"{}\"", path.display())]
+        Io { path: PathBuf, source: std::io::Error },
+        #[error("Could not parse object id in rebase state file at \"{}\"", path.display())]
+        ObjectId {
+            path: PathBuf,
+            source: gix_hash::decode::Error,
+        },
+        #[error("Could not parse instructions in \"{}\"", path.display())]
+        Todo {
+            path: PathBuf,
+            source: crate::todo::decode::Error,
+        },
+    }
+}
+
+/// Lifecycle
+impl State {
+    /// Read the state of a rebase from `dir`, typically `.git/rebase-merge`.
+    pub fn read(dir: &Path) -> Result<Self, read::Error> {
+        let read = |name: &str| -> Result<Option<Vec<u8>>, read::Error> {
+            let path = dir.join(name);
+            match std::fs::read(&path) {
+                Ok(data) => Ok(Some(data)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(err) => Err(read::Error::Io { path, source: err }),
+            }
+        };
+        let required = |name: &str| -> Result<Vec<u8>, read::Error> {
+            read(name)?.ok_or_else(|| read::Error::Io {
+                path: dir.join(name),
+                source: std::io::ErrorKind::NotFound.into(),
+            })
+        };
+        let id = |name: &str, data: Vec<u8>| -> Result<gix_hash::ObjectId, read::Error> {
+            gix_hash::ObjectId::from_hex(data.trim()).map_err(|err| read::Error::ObjectId {
+                path: dir.join(name),
+                source: err,
+            })
+        };
+        let optional_id = |name: &str| -> Result<Option<gix_hash::ObjectId>, read::Error> {
+            read(name)?.map(|data| id(name, data)).transpose()
+        };
+        let instructions = |name: &str| -> Result<Vec<crate::Instruction>, read::Error> {
+            read(name)?
+                .map(|data| todo::decode(&data))
+                .transpose()
+                .map(Option::unwrap_or_default)
+                .map_err(|err| read::Error::Todo {
+                    path: dir.join(name),
+                    source: err,
+                })
+        };
+
+        let head_name = required("head-name")?;
+        let head_name = head_name.trim();
+        Ok(State {
+            head_name: (head_name != DETACHED_HEAD).then(|| head_name.into()),
+            onto: id("onto", required("onto")?)?,
+            orig_head: id("orig-head", required("orig-head")?)?,
+            todo: instructions("git-rebase-todo")?,
+            done: instructions("done")?,
+            stopped_at: optional_id("stopped-sha")?,
+            autostash: optional_id("autostash")?,
+            empty: if dir.join("drop_redundant_commits").is_file() {
+                EmptyCommits::Drop
+            } else if dir.join("keep_redundant_commits").is_file() {
+                EmptyCommits::Keep
+            } else {
+                EmptyCommits::Stop
+            },
+        })
+    }
+
+    /// Write this state into `dir`, typically `.git/rebase-merge`, which is created if needed, so that it can be
+    /// [read](Self::read()) back by us or by `git`.
+    ///
+    /// Files for optional values that are unset are removed.
+    pub fn write(&self, dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(dir)?;
+        let write = |name: &str, data: &[u8]| std::fs::write(dir.join(name), data);
+        let remove = |name: &str| match std::fs::remove_file(dir.join(name)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+        let line = |id: &gix_hash::oid| format!("{id}\n");
+
+        let mut head_name = self
+            .head_name
+            .as_ref()
+            .map_or(DETACHED_HEAD, |name| name.as_slice())
+            .to_vec();
+        head_name.push(b'\n');
+        write("head-name", &head_name)?;
+        write("onto", line(&self.onto).as_bytes())?;
+        write("orig-head", line(&self.orig_head).as_bytes())?;
+        write("interactive", b"")?;
+
+        let mut buf = Vec::new();
+        todo::encode(&self.todo, &mut buf)?;
+        write("git-rebase-todo", &buf)?;
+        buf.clear();
+        todo::encode(&self.done, &mut buf)?;
+        write("done", &buf)?;
+        write("msgnum", format!("{}\n", self.done.len()).as_bytes())?;
+        write("end", format!("{}\n", self.done.len() + self.todo.len()).as_bytes())?;
+
+        for (name, id) in [("stopped-sha", self.stopped_at), ("autostash", self.autostash)] {
+            match id {
+                Some(id) => write(name, line(&id).as_bytes())?,
+                None => remove(name)?,
+            }
+        }
+        let (drop, keep) = match self.empty {
+            EmptyCommits::Drop => (true, false),
+            EmptyCommits::Keep => (false, true),
+            EmptyCommits::Stop => (false, false),
+        };
+        for (name, is_set) in [("drop_redundant_commits", drop), ("keep_redundant_commits", keep)] {
+            if is_set {
+                write(name, b"")?;
+            } else {
+                remove(name)?;
+            }
+        }
+        Ok(())
+    }
+}
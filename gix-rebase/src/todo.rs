@@ -0,0 +1,149 @@
+use bstr::{BString, ByteSlice};
+
+use crate::Instruction;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`decode()`](super::decode()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number}: the command '{command}' isn't supported")]
+        UnsupportedCommand { line_number: usize, command: BString },
+        #[error("Line {line_number}: the command '{command}' needs a commit")]
+        MissingCommit { line_number: usize, command: BString },
+        #[error("Line {line_number}: '{commit}' is not a full object id")]
+        InvalidCommit {
+            line_number: usize,
+            commit: BString,
+            source: gix_hash::decode::Error,
+        },
+    }
+}
+
+/// Parse all instructions from `data` in the format of the `git-rebase-todo` or `done` files, skipping empty lines and comments.
+///
+/// Commands may be spelled out like `pick`, or abbreviated like `p`.
+///
+/// ### Deviation
+///
+/// Only full object ids are supported, which is what `git` writes, but users may also use abbreviated ones.
+pub fn decode(data: &[u8]) -> Result<Vec<Instruction>, decode::Error> {
+    let mut out = Vec::new();
+    for (line_number, line) in data.lines().enumerate().map(|(idx, line)| (idx + 1, line.trim())) {
+        if line.is_empty() || line.starts_with(b"#") {
+            continue;
+        }
+        let (command, rest) = match line.find_byteset(b" \t") {
+            Some(pos) => (&line[..pos], line[pos..].trim_start()),
+            None => (line, &[][..]),
+        };
+        let commit_and_summary = || -> Result<(gix_hash::ObjectId, BString), decode::Error> {
+            let (commit, summary) = match rest.find_byteset(b" \t") {
+                Some(pos) => (&rest[..pos], rest[pos..].trim_start()),
+                None => (rest, &[][..]),
+            };
+            if commit.is_empty() {
+                return Err(decode::Error::MissingCommit {
+                    line_number,
+                    command: command.into(),
+                });
+            }
+            let commit = gix_hash::ObjectId::from_hex(commit).map_err(|err| decode::Error::InvalidCommit {
+                line_number,
+                commit: commit.into(),
+                source: err,
+            })?;
+            let summary = summary.strip_prefix(b"# ").unwrap_or(summary);
+            Ok((commit, summary.into()))
+        };
+        let instruction = match command {
+            b"p" | b"pick" => {
+                let (commit, summary) = commit_and_summary()?;
+                Instruction::Pick { commit, summary }
+            }
+            b"d" | b"drop" => {
+                let (commit, summary) = commit_and_summary()?;
+                Instruction::Drop { commit, summary }
+            }
+            b"x" | b"exec" => Instruction::Exec { command: rest.into() },
+            b"b" | b"break" => Instruction::Break,
+            _ => {
+                return Err(decode::Error::UnsupportedCommand {
+                    line_number,
+                    command: command.into(),
+                })
+            }
+        };
+        out.push(instruction);
+    }
+    Ok(out)
+}
+
+/// Write all `instructions` to `out`, one per line, in the format of the `git-rebase-todo` file.
+pub fn encode<'a>(
+    instructions: impl IntoIterator<Item = &'a Instruction>,
+    mut out: impl std::io::Write,
+) -> std::io::Result<()> {
+    for instruction in instructions {
+        instruction.write_to(&mut out)?;
+    }
+    Ok(())
+}
+
+impl Instruction {
+    /// Return the commit this instruction refers to, if any.
+    pub fn commit(&self) -> Option<&gix_hash::oid> {
+        match self {
+            Instruction::Pick { commit, .. } | Instruction::Drop { commit, .. } => Some(commit),
+            Instruction::Exec { .. } | Instruction::Break => None,
+        }
+    }
+
+    /// Return the name of the command as written in the todo list.
+    pub fn command(&self) -> &'static str {
+        match self {
+            Instruction::Pick { .. } => "pick",
+            Instruction::Drop { .. } => "drop",
+            Instruction::Exec { .. } => "exec",
+            Instruction::Break => "break",
+        }
+    }
+
+    /// Write this instruction as a single line to `out`.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        out.write_all(self.command().as_bytes())?;
+        match self {
+            Instruction::Pick { commit, summary } | Instruction::Drop { commit, summary } => {
+                write!(out, " {commit}")?;
+                if !summary.is_empty() {
+                    out.write_all(b" ")?;
+                    out.write_all(summary)?;
+                }
+            }
+            Instruction::Exec { command } => {
+                out.write_all(b" ")?;
+                out.write_all(command)?;
+            }
+            Instruction::Break => {}
+        }
+        out.write_all(b"\n")
+    }
+
+    /// Return this instruction as a single line, including the trailing newline.
+    pub fn to_bstring(&self) -> BString {
+        let mut buf = Vec::new();
+        self.write_to(&mut buf).expect("writing to a vec never fails");
+        buf.into()
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = self.to_bstring();
+        std::fmt::Display::fmt(line.trim_end().as_bstr(), f)
+    }
+}
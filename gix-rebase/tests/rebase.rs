@@ -0,0 +1,159 @@
+use gix_hash::ObjectId;
+use gix_rebase::{EmptyCommits, Instruction, State};
+
+pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + 'static>>;
+
+fn hex_to_id(hex: &str) -> ObjectId {
+    ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
+}
+
+fn pick(hex: &str, summary: &str) -> Instruction {
+    Instruction::Pick {
+        commit: hex_to_id(hex),
+        summary: summary.into(),
+    }
+}
+
+mod todo {
+    use gix_rebase::{todo, Instruction};
+
+    use crate::{hex_to_id, pick};
+
+    #[test]
+    fn decode_skips_comments_and_accepts_abbreviated_commands() -> crate::Result {
+        let input = b"pick 0000000000000000000000000000000000000001 first\n\
+                      # a comment\n\
+                      \n\
+                      d 0000000000000000000000000000000000000002 # second\n\
+                      x make test\n\
+                      break\n";
+        assert_eq!(
+            todo::decode(input)?,
+            vec![
+                pick("0000000000000000000000000000000000000001", "first"),
+                Instruction::Drop {
+                    commit: hex_to_id("0000000000000000000000000000000000000002"),
+                    summary: "second".into()
+                },
+                Instruction::Exec {
+                    command: "make test".into()
+                },
+                Instruction::Break,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn decode_errors_carry_line_numbers() {
+        assert!(matches!(
+            todo::decode(b"\nfixup 0000000000000000000000000000000000000001\n"),
+            Err(todo::decode::Error::UnsupportedCommand { line_number: 2, .. })
+        ));
+        assert!(matches!(
+            todo::decode(b"pick\n"),
+            Err(todo::decode::Error::MissingCommit { line_number: 1, .. })
+        ));
+        assert!(matches!(
+            todo::decode(b"pick abc summary\n"),
+            Err(todo::decode::Error::InvalidCommit { line_number: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn encode_round_trips() -> crate::Result {
+        let instructions = vec![
+            pick("0000000000000000000000000000000000000001", "first"),
+            Instruction::Exec { command: "true".into() },
+            Instruction::Break,
+        ];
+        let mut buf = Vec::new();
+        todo::encode(&instructions, &mut buf)?;
+        assert_eq!(
+            buf,
+            b"pick 0000000000000000000000000000000000000001 first\nexec true\nbreak\n"
+        );
+        assert_eq!(todo::decode(&buf)?, instructions);
+        assert_eq!(
+            instructions[0].to_string(),
+            "pick 0000000000000000000000000000000000000001 first"
+        );
+        Ok(())
+    }
+}
+
+mod author_script {
+    use gix_rebase::author_script;
+
+    #[test]
+    fn round_trip_with_quotes() -> crate::Result {
+        let author = gix_actor::Signature {
+            name: "Jo 'the' Dev!".into(),
+            email: "jo@example.com".into(),
+            time: gix_date::Time::new(1_700_000_000, 3600),
+        };
+        let script = author_script::encode(author.to_ref());
+        assert_eq!(
+            script,
+            "GIT_AUTHOR_NAME='Jo '\\''the'\\'' Dev'\\!''\n\
+             GIT_AUTHOR_EMAIL='jo@example.com'\n\
+             GIT_AUTHOR_DATE='@1700000000 +0100'\n"
+        );
+        assert_eq!(author_script::decode(&script)?, author);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_variables_are_an_error() {
+        assert!(matches!(
+            author_script::decode(b"GIT_AUTHOR_NAME='a'\nGIT_AUTHOR_DATE='@0 +0000'\n"),
+            Err(author_script::decode::Error::MissingVariable {
+                name: "GIT_AUTHOR_EMAIL"
+            })
+        ));
+    }
+}
+
+mod state {
+    use super::*;
+
+    #[test]
+    fn write_and_read_round_trip() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let dir = tmp.path().join("rebase-merge");
+        let mut state = State {
+            head_name: Some("refs/heads/main".into()),
+            onto: hex_to_id("0000000000000000000000000000000000000001"),
+            orig_head: hex_to_id("0000000000000000000000000000000000000002"),
+            todo: vec![pick("0000000000000000000000000000000000000003", "third")],
+            done: vec![pick("0000000000000000000000000000000000000004", "fourth")],
+            stopped_at: Some(hex_to_id("0000000000000000000000000000000000000004")),
+            autostash: None,
+            empty: EmptyCommits::Keep,
+        };
+        state.write(&dir)?;
+        assert_eq!(std::fs::read(dir.join("msgnum"))?, b"1\n");
+        assert_eq!(std::fs::read(dir.join("end"))?, b"2\n");
+        assert!(dir.join("keep_redundant_commits").is_file());
+        assert_eq!(State::read(&dir)?, state);
+
+        state.head_name = None;
+        state.stopped_at = None;
+        state.empty = EmptyCommits::Stop;
+        state.write(&dir)?;
+        assert_eq!(std::fs::read(dir.join("head-name"))?, b"detached HEAD\n");
+        assert!(!dir.join("stopped-sha").exists(), "unset values are removed");
+        assert!(!dir.join("keep_redundant_commits").exists());
+        assert_eq!(State::read(&dir)?, state);
+        Ok(())
+    }
+
+    #[test]
+    fn read_fails_without_required_files() {
+        let tmp = gix_testtools::tempfile::TempDir::new().unwrap();
+        assert!(matches!(
+            State::read(tmp.path()),
+            Err(gix_rebase::state::read::Error::Io { .. })
+        ));
+    }
+}
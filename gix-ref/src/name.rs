@@ -62,7 +62,9 @@ impl PartialNameRef {
 }
 
 impl PartialNameRef {
-    pub(crate) fn looks_like_full_name(&self) -> bool {
+    /// Return `true` if this name is used as is when looking it up, like `refs/heads/main` or `HEAD`, instead of being
+    /// tried below `refs/`, `refs/tags/`, `refs/heads/` and `refs/remotes/` in turn.
+    pub fn looks_like_full_name(&self) -> bool {
         let name = self.0.as_bstr();
         name.starts_with_str("refs/")
             || name.starts_with(Category::MainPseudoRef.prefix())
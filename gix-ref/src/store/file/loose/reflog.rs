@@ -76,8 +76,10 @@ impl file::Store {
 }
 
 impl file::Store {
-    /// Implements the logic required to transform a fully qualified refname into its log name
-    pub(crate) fn reflog_path(&self, name: &FullNameRef) -> PathBuf {
+    /// Return the path to the reflog of the fully qualified reference `name`, whether it exists or not.
+    ///
+    /// This is useful to rewrite a reflog as a whole, which should be done while holding a lock on it.
+    pub fn reflog_path(&self, name: &FullNameRef) -> PathBuf {
         let (base, rela_path) = self.reflog_base_and_relative_path(name);
         base.join(rela_path)
     }
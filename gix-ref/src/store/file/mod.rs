@@ -90,6 +90,7 @@ pub struct Transaction<'s, 'p> {
     packed_transaction: Option<crate::store_impl::packed::Transaction>,
     updates: Option<Vec<transaction::Edit>>,
     packed_refs: transaction::PackedRefs<'p>,
+    hook: Option<transaction::Hook<'p>>,
 }
 
 pub(in crate::store_impl::file) fn path_to_name<'a>(path: impl Into<Cow<'a, Path>>) -> Cow<'a, BStr> {
@@ -130,5 +131,9 @@ pub mod transaction;
 #[allow(clippy::empty_docs)]
 pub mod packed;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod watch;
+
 mod raw_ext;
 pub use raw_ext::ReferenceExt;
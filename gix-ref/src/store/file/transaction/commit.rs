@@ -1,5 +1,8 @@
 use crate::{
-    store_impl::file::{transaction::PackedRefs, Transaction},
+    store_impl::file::{
+        transaction::{hook_input, HookState, PackedRefs},
+        Transaction,
+    },
     transaction::{Change, LogChange, RefEdit, RefLog},
     Target,
 };
@@ -173,7 +176,11 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 drop(lock)
             }
         }
-        Ok(updates.into_iter().map(|edit| edit.update).collect())
+        let edits: Vec<_> = updates.into_iter().map(|edit| edit.update).collect();
+        if let Some(mut hook) = self.hook {
+            hook(HookState::Committed, &hook_input(&edits, self.store.object_hash)).ok();
+        }
+        Ok(edits)
     }
 }
 mod error {
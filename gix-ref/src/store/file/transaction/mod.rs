@@ -29,6 +29,38 @@ pub enum PackedRefs<'a> {
     DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(Box<dyn gix_object::Find + 'a>),
 }
 
+/// The state of a transaction when its [hook](Transaction::hook()) is called, which is also the argument that `git` passes
+/// to the `reference-transaction` hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookState {
+    /// All references were locked and the transaction is about to be committed.
+    ///
+    /// The hook may reject the transaction in this state by returning an error, which aborts it.
+    Prepared,
+    /// The transaction was committed and all references have their new values.
+    Committed,
+    /// The transaction was aborted and no reference was changed.
+    Aborted,
+}
+
+impl HookState {
+    /// Return the name of the state as passed to the `reference-transaction` hook.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HookState::Prepared => "prepared",
+            HookState::Committed => "committed",
+            HookState::Aborted => "aborted",
+        }
+    }
+}
+
+/// The error a [hook](Transaction::hook()) may return to reject a transaction.
+pub type HookError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A function called with the [state](HookState) of a transaction along with all of its updates, formatted
+/// as `<old-value> SP <new-value> SP <ref-name> LF` just like the standard input of the `reference-transaction` hook.
+pub(crate) type Hook<'a> = Box<dyn FnMut(HookState, &[u8]) -> Result<(), HookError> + 'a>;
+
 #[derive(Debug)]
 pub(in crate::store_impl::file) struct Edit {
     update: RefEdit,
@@ -73,6 +105,7 @@ impl file::Store {
             packed_transaction: None,
             updates: None,
             packed_refs: PackedRefs::default(),
+            hook: None,
         }
     }
 }
@@ -83,6 +116,60 @@ impl<'s, 'p> Transaction<'s, 'p> {
         self.packed_refs = packed_refs;
         self
     }
+
+    /// Call `hook` with the state of the transaction and its updates once all references were locked, and again once
+    /// the transaction was committed or rolled back, just like `git` runs the `reference-transaction` hook.
+    ///
+    /// The updates are formatted as the hook expects them on standard input, one `<old-value> SP <new-value> SP <ref-name> LF`
+    /// line per reference, with the old value being the null id if it is unknown and the new value being the null id if the
+    /// reference is deleted. Symbolic references are represented by `ref:<target>`. Updates that only affect the reflog are omitted.
+    ///
+    /// If `hook` fails in the [prepared](HookState::Prepared) state, the transaction is aborted and [`prepare()`](Transaction::prepare())
+    /// fails. Errors in all other states are ignored.
+    pub fn hook(mut self, hook: impl FnMut(HookState, &[u8]) -> Result<(), HookError> + 'p) -> Self {
+        self.hook = Some(Box::new(hook));
+        self
+    }
+}
+
+/// Format `edits` like `git` passes them to the `reference-transaction` hook.
+fn hook_input<'a>(edits: impl IntoIterator<Item = &'a RefEdit>, object_hash: gix_hash::Kind) -> Vec<u8> {
+    use crate::transaction::{Change, RefLog};
+    use std::io::Write;
+
+    let null = gix_hash::ObjectId::null(object_hash);
+    let mut out = Vec::new();
+    let write_target = |out: &mut Vec<u8>, target: Option<crate::TargetRef<'_>>| match target {
+        Some(crate::TargetRef::Peeled(id)) => write!(out, "{id}").expect("write to memory never fails"),
+        Some(crate::TargetRef::Symbolic(name)) => {
+            out.extend_from_slice(b"ref:");
+            out.extend_from_slice(name.as_bstr());
+        }
+        None => write!(out, "{null}").expect("write to memory never fails"),
+    };
+    for edit in edits {
+        let new = match &edit.change {
+            Change::Update { log, new, .. } => {
+                if log.mode == RefLog::Only {
+                    continue;
+                }
+                Some(new.to_ref())
+            }
+            Change::Delete { log, .. } => {
+                if *log == RefLog::Only {
+                    continue;
+                }
+                None
+            }
+        };
+        write_target(&mut out, edit.change.previous_value());
+        out.push(b' ');
+        write_target(&mut out, new);
+        out.push(b' ');
+        out.extend_from_slice(edit.name.as_bstr());
+        out.push(b'\n');
+    }
+    out
 }
 
 impl std::fmt::Debug for Transaction<'_, '_> {
@@ -5,7 +5,7 @@ use crate::{
         file,
         file::{
             loose,
-            transaction::{Edit, PackedRefs},
+            transaction::{hook_input, Edit, HookState, PackedRefs},
             Transaction,
         },
     },
@@ -401,6 +401,15 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 }
             }
         }
+        if let Some(hook) = self.hook.as_mut() {
+            let input = hook_input(updates.iter().map(|edit| &edit.update), self.store.object_hash);
+            if let Err(err) = hook(HookState::Prepared, &input) {
+                drop(updates);
+                drop(self.packed_transaction.take());
+                hook(HookState::Aborted, &input).ok();
+                return Err(Error::Hook(err));
+            }
+        }
         self.updates = Some(updates);
         Ok(self)
     }
@@ -414,9 +423,15 @@ impl<'s, 'p> Transaction<'s, 'p> {
     ///
     /// A rollback happens automatically as this instance is dropped as well.
     pub fn rollback(self) -> Vec<RefEdit> {
-        self.updates
+        let edits: Vec<_> = self
+            .updates
             .map(|updates| updates.into_iter().map(|u| u.update).collect())
-            .unwrap_or_default()
+            .unwrap_or_default();
+        drop(self.packed_transaction);
+        if let Some(mut hook) = self.hook {
+            hook(HookState::Aborted, &hook_input(&edits, self.store.object_hash)).ok();
+        }
+        edits
     }
 }
 
@@ -486,6 +501,8 @@ mod error {
         },
         #[error("Could not read reference")]
         ReferenceDecode(#[from] file::loose::reference::decode::Error),
+        #[error("The transaction was rejected by its hook")]
+        Hook(#[source] file::transaction::HookError),
     }
 }
 
@@ -0,0 +1,277 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, SystemTime},
+};
+
+use crate::{file, FullName, Target};
+
+/// The time within which a file may be changed again without its modification time changing, as is the case on
+/// filesystems with coarse timestamps. Files modified within this time before we read them are considered _racy_.
+const RACY_DURATION: Duration = Duration::from_secs(2);
+
+/// A change to a reference, as observed by a [`Watcher`].
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+pub enum Change {
+    /// The reference with `name` was created and points to `target`.
+    Added {
+        /// The name of the new reference.
+        name: FullName,
+        /// The target of the new reference.
+        target: Target,
+    },
+    /// The reference with `name` now points to `current`, instead of `previous`.
+    Updated {
+        /// The name of the changed reference.
+        name: FullName,
+        /// The target of the reference when it was last observed.
+        previous: Target,
+        /// The target of the reference now.
+        current: Target,
+    },
+    /// The reference with `name` which pointed to `previous` was deleted.
+    Removed {
+        /// The name of the deleted reference.
+        name: FullName,
+        /// The target of the reference when it was last observed.
+        previous: Target,
+    },
+}
+
+impl Change {
+    /// Return the name of the changed reference.
+    pub fn name(&self) -> &FullName {
+        match self {
+            Change::Added { name, .. } | Change::Updated { name, .. } | Change::Removed { name, .. } => name,
+        }
+    }
+}
+
+/// The error returned by [`Watcher`] methods.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not obtain the metadata of reference files")]
+    Metadata(#[from] std::io::Error),
+    #[error(transparent)]
+    OpenPacked(#[from] crate::packed::buffer::open::Error),
+    #[error(transparent)]
+    Iter(#[from] file::iter::loose_then_packed::Error),
+    #[error(transparent)]
+    FindHead(#[from] file::find::Error),
+}
+
+/// The modification time and size of a file holding references.
+type Stamp = (Option<SystemTime>, u64);
+
+/// A watcher for changes to the references of a [store](file::Store), which emits the [changes](Change) between the
+/// references it observed and the ones that are present now.
+///
+/// Changes are detected by polling the modification time and size of `HEAD`, `packed-refs` and the files below `refs/`,
+/// and references are only read if these changed, which keeps polling cheap.
+/// Use [`wait()`](Watcher::wait()) in a thread of its own to learn about changes as they happen. With the `fs-notify`
+/// feature, it polls as soon as filesystem notifications indicate a change on platforms that support them, and only polls
+/// in the given interval otherwise.
+///
+/// Note that intermediate states between two polls aren't observed, so a reference that was created and deleted in between
+/// doesn't show up at all. Loose references that can't be read or parsed are ignored as if they didn't exist.
+pub struct Watcher {
+    store: file::Store,
+    stamps: BTreeMap<PathBuf, Stamp>,
+    refs: BTreeMap<FullName, Target>,
+    last_read: SystemTime,
+    #[cfg(feature = "fs-notify")]
+    notifier: Option<notify::Notifier>,
+}
+
+/// Watching
+impl file::Store {
+    /// Return a watcher that observes changes to all references of this store, starting with the references as they are now.
+    pub fn watch(&self) -> Result<Watcher, Error> {
+        let mut watcher = Watcher {
+            store: self.clone(),
+            stamps: BTreeMap::new(),
+            refs: BTreeMap::new(),
+            last_read: SystemTime::UNIX_EPOCH,
+            #[cfg(feature = "fs-notify")]
+            notifier: None,
+        };
+        watcher.stamps = watcher.stamps()?;
+        watcher.refs = watcher.read_refs()?;
+        Ok(watcher)
+    }
+}
+
+impl Watcher {
+    /// Return all changes to references since the last call, or since the watcher was created, sorted by reference name.
+    ///
+    /// The result is empty if nothing changed.
+    pub fn poll(&mut self) -> Result<Vec<Change>, Error> {
+        let stamps = self.stamps()?;
+        if stamps == self.stamps && !self.is_racy() {
+            return Ok(Vec::new());
+        }
+        self.stamps = stamps;
+        let refs = self.read_refs()?;
+
+        let mut changes = Vec::new();
+        for (name, previous) in &self.refs {
+            match refs.get(name) {
+                Some(current) if current != previous => changes.push(Change::Updated {
+                    name: name.clone(),
+                    previous: previous.clone(),
+                    current: current.clone(),
+                }),
+                Some(_) => {}
+                None => changes.push(Change::Removed {
+                    name: name.clone(),
+                    previous: previous.clone(),
+                }),
+            }
+        }
+        for (name, target) in &refs {
+            if !self.refs.contains_key(name) {
+                changes.push(Change::Added {
+                    name: name.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+        changes.sort_by(|a, b| a.name().cmp(b.name()));
+        self.refs = refs;
+        Ok(changes)
+    }
+
+    /// [Poll](Self::poll()) for changes every `interval` until there are some, and return them.
+    ///
+    /// With the `fs-notify` feature, polling also happens as soon as files in directories holding references change,
+    /// if filesystem notifications are available, so `interval` can be much longer without delaying the result.
+    ///
+    /// Return an empty list if `should_interrupt` was set before there were changes.
+    pub fn wait(&mut self, interval: Duration, should_interrupt: &AtomicBool) -> Result<Vec<Change>, Error> {
+        while !should_interrupt.load(Ordering::Relaxed) {
+            self.watch_directories();
+            let changes = self.poll()?;
+            if !changes.is_empty() {
+                return Ok(changes);
+            }
+            self.sleep(interval);
+        }
+        Ok(Vec::new())
+    }
+
+    /// Return the references as last observed, along with their targets.
+    pub fn refs(&self) -> &BTreeMap<FullName, Target> {
+        &self.refs
+    }
+}
+
+/// Utilities
+impl Watcher {
+    /// Request notifications for all directories that may hold references, which is done before each poll to also
+    /// watch directories that didn't exist before.
+    fn watch_directories(&mut self) {
+        #[cfg(feature = "fs-notify")]
+        {
+            if self.notifier.is_none() {
+                self.notifier = notify::Notifier::new();
+            }
+            if let Some(notifier) = &mut self.notifier {
+                let git_dir = self.store.git_dir();
+                notifier.add(git_dir, false);
+                notifier.add(&git_dir.join("refs"), true);
+                if let Some(common_dir) = self.store.common_dir() {
+                    notifier.add(common_dir, false);
+                    notifier.add(&common_dir.join("refs"), true);
+                }
+            }
+        }
+    }
+
+    /// Wait for `interval` or, if available, until a filesystem notification arrives, whichever comes first.
+    fn sleep(&mut self, interval: Duration) {
+        #[cfg(feature = "fs-notify")]
+        if let Some(notifier) = &self.notifier {
+            if notifier.wait(interval).is_ok() {
+                return;
+            }
+            // Keep polling if notifications fail for some reason.
+            self.notifier = None;
+        }
+        std::thread::sleep(interval);
+    }
+
+    /// Return `true` if files were modified so shortly before we read them that another modification may not have
+    /// changed their stamp.
+    fn is_racy(&self) -> bool {
+        self.stamps
+            .values()
+            .filter_map(|(modified, _)| *modified)
+            .any(|modified| modified + RACY_DURATION >= self.last_read)
+    }
+
+    fn read_refs(&mut self) -> Result<BTreeMap<FullName, Target>, Error> {
+        self.last_read = SystemTime::now();
+        let mut refs = BTreeMap::new();
+        if let Some(head) = self.store.try_find("HEAD")? {
+            refs.insert(head.name, head.target);
+        }
+        for reference in self.store.iter()?.all()? {
+            let reference = match reference {
+                Ok(reference) => reference,
+                // Loose references may be removed while iterating, or be malformed, and we don't want to fail for good.
+                Err(
+                    file::iter::loose_then_packed::Error::ReadFileContents { .. }
+                    | file::iter::loose_then_packed::Error::ReferenceCreation { .. },
+                ) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            refs.insert(reference.name, reference.target);
+        }
+        Ok(refs)
+    }
+
+    fn stamps(&self) -> std::io::Result<BTreeMap<PathBuf, Stamp>> {
+        let mut stamps = BTreeMap::new();
+        let git_dir = self.store.git_dir();
+        insert_stamp(&mut stamps, git_dir.join("HEAD"))?;
+        insert_stamp(&mut stamps, self.store.packed_refs_path())?;
+        insert_stamps_recursive(&mut stamps, &git_dir.join("refs"))?;
+        if let Some(common_dir) = self.store.common_dir() {
+            insert_stamps_recursive(&mut stamps, &common_dir.join("refs"))?;
+        }
+        Ok(stamps)
+    }
+}
+
+fn insert_stamp(stamps: &mut BTreeMap<PathBuf, Stamp>, path: PathBuf) -> std::io::Result<()> {
+    match std::fs::symlink_metadata(&path) {
+        Ok(metadata) => {
+            stamps.insert(path, (metadata.modified().ok(), metadata.len()));
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    Ok(())
+}
+
+fn insert_stamps_recursive(stamps: &mut BTreeMap<PathBuf, Stamp>, dir: &Path) -> std::io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            insert_stamps_recursive(stamps, &entry.path())?;
+        } else {
+            insert_stamp(stamps, entry.path())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "fs-notify")]
+mod notify;
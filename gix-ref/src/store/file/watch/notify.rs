@@ -0,0 +1,67 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::Watcher as _;
+
+/// A receiver of filesystem notifications about changes in directories holding references.
+pub(super) struct Notifier {
+    watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    /// The directories that are watched already.
+    watched: BTreeSet<PathBuf>,
+}
+
+impl Notifier {
+    /// Return a new instance, or `None` if notifications aren't available, either because the platform doesn't support them
+    /// or because they couldn't be set up, for instance because the limit of instances was reached.
+    pub(super) fn new() -> Option<Self> {
+        if <notify::RecommendedWatcher as notify::Watcher>::kind() == notify::WatcherKind::PollWatcher {
+            return None;
+        }
+        let (tx, events) = mpsc::channel();
+        let watcher = notify::recommended_watcher(tx).ok()?;
+        Some(Notifier {
+            watcher,
+            events,
+            watched: BTreeSet::new(),
+        })
+    }
+
+    /// Watch the entries of `dir` for changes, along with all directories below it if `recursive` is `true`.
+    /// This may be called repeatedly for the same directory, which is only watched once it exists.
+    pub(super) fn add(&mut self, dir: &Path, recursive: bool) {
+        if self.watched.contains(dir) {
+            return;
+        }
+        let mode = if recursive {
+            notify::RecursiveMode::Recursive
+        } else {
+            notify::RecursiveMode::NonRecursive
+        };
+        if self.watcher.watch(dir, mode).is_ok() {
+            self.watched.insert(dir.to_owned());
+        }
+    }
+
+    /// Block until an event was received in one of the watched directories, or until `timeout` passed, and discard all
+    /// pending events as we don't care about their details.
+    pub(super) fn wait(&self, timeout: Duration) -> notify::Result<()> {
+        match self.events.recv_timeout(timeout) {
+            Ok(event) => {
+                event?;
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => return Ok(()),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(notify::Error::generic("the filesystem watcher stopped unexpectedly"))
+            }
+        }
+        for event in self.events.try_iter() {
+            event?;
+        }
+        Ok(())
+    }
+}
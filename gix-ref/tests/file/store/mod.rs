@@ -10,6 +10,7 @@ mod access;
 mod find;
 mod iter;
 mod reflog;
+mod watch;
 
 #[test]
 fn precompose_unicode_journey() -> crate::Result {
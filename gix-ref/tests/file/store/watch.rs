@@ -0,0 +1,150 @@
+use std::{sync::atomic::AtomicBool, time::Duration};
+
+use gix_lock::acquire::Fail;
+use gix_ref::{
+    file::{transaction::PackedRefs, watch::Change},
+    transaction::{LogChange, PreviousValue, RefEdit},
+    Target,
+};
+
+use crate::{
+    file::{
+        transaction::prepare_and_commit::{committer, create_at, create_symbolic_at, delete_at},
+        EmptyCommit,
+    },
+    hex_to_id,
+};
+
+#[test]
+fn changes_to_loose_and_packed_refs_and_head_are_observed() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let store = gix_ref::file::Store::at(tmp.path().into(), Default::default());
+    let mut watcher = store.watch()?;
+    assert!(watcher.refs().is_empty());
+    assert_eq!(watcher.poll()?, vec![], "nothing changed");
+
+    let commit = |edits: Vec<RefEdit>, packed: bool| -> crate::Result {
+        let transaction = store.transaction();
+        let transaction = if packed {
+            transaction.packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+                Box::new(EmptyCommit),
+            ))
+        } else {
+            transaction
+        };
+        transaction
+            .prepare(edits, Fail::Immediately, Fail::Immediately)?
+            .commit(committer().to_ref())?;
+        Ok(())
+    };
+    commit(
+        vec![
+            create_at("refs/heads/main"),
+            create_symbolic_at("HEAD", "refs/heads/main"),
+        ],
+        false,
+    )?;
+    let id = hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+    assert_eq!(
+        watcher.poll()?,
+        vec![
+            Change::Added {
+                name: "HEAD".try_into()?,
+                target: Target::Symbolic("refs/heads/main".try_into()?),
+            },
+            Change::Added {
+                name: "refs/heads/main".try_into()?,
+                target: Target::Peeled(id),
+            }
+        ]
+    );
+    assert_eq!(watcher.poll()?, vec![], "changes are only reported once");
+
+    commit(vec![create_at("refs/heads/packed")], true)?;
+    let new_id = hex_to_id("4b825dc642cb6eb9a060e54bf8d69288fbee4904");
+    commit(
+        vec![RefEdit {
+            change: gix_ref::transaction::Change::Update {
+                log: LogChange::default(),
+                expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                new: Target::Peeled(new_id),
+            },
+            name: "refs/heads/main".try_into()?,
+            deref: false,
+        }],
+        false,
+    )?;
+    assert_eq!(
+        watcher.poll()?,
+        vec![
+            Change::Updated {
+                name: "refs/heads/main".try_into()?,
+                previous: Target::Peeled(id),
+                current: Target::Peeled(new_id),
+            },
+            Change::Added {
+                name: "refs/heads/packed".try_into()?,
+                target: Target::Peeled(id),
+            }
+        ]
+    );
+
+    commit(vec![delete_at("refs/heads/packed")], true)?;
+    assert_eq!(
+        watcher.poll()?,
+        vec![Change::Removed {
+            name: "refs/heads/packed".try_into()?,
+            previous: Target::Peeled(id),
+        }]
+    );
+    assert_eq!(watcher.refs().len(), 2, "HEAD and main remain");
+    Ok(())
+}
+
+#[test]
+fn wait_returns_nothing_once_interrupted() -> crate::Result {
+    let store = crate::file::store()?;
+    let mut watcher = store.watch()?;
+    assert!(!watcher.refs().is_empty());
+    assert_eq!(watcher.wait(Duration::from_millis(1), &AtomicBool::new(true))?, vec![]);
+    Ok(())
+}
+
+#[test]
+fn wait_returns_changes_made_concurrently() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let store = gix_ref::file::Store::at(tmp.path().into(), Default::default());
+    let mut watcher = store.watch()?;
+
+    let writer = std::thread::spawn({
+        let git_dir = tmp.path().to_owned();
+        move || {
+            std::thread::sleep(Duration::from_millis(50));
+            gix_ref::file::Store::at(git_dir, Default::default())
+                .transaction()
+                .prepare(Some(create_at("refs/heads/main")), Fail::Immediately, Fail::Immediately)
+                .expect("valid edit")
+                .commit(committer().to_ref())
+                .expect("can write");
+        }
+    });
+    let interval = if cfg!(all(
+        feature = "fs-notify",
+        any(target_os = "linux", target_os = "macos", target_os = "windows")
+    )) {
+        // Notifications wake us up long before the interval passes.
+        Duration::from_secs(3600)
+    } else {
+        Duration::from_millis(10)
+    };
+    let changes = watcher.wait(interval, &AtomicBool::new(false))?;
+    writer.join().expect("no panic");
+    assert_eq!(
+        changes,
+        vec![Change::Added {
+            name: "refs/heads/main".try_into()?,
+            target: Target::Peeled(hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391")),
+        }]
+    );
+    Ok(())
+}
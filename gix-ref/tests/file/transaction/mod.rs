@@ -63,7 +63,7 @@ pub(crate) mod prepare_and_commit {
         }
     }
 
-    fn create_symbolic_at(name: &str, symbolic_target: &str) -> RefEdit {
+    pub(crate) fn create_symbolic_at(name: &str, symbolic_target: &str) -> RefEdit {
         RefEdit {
             change: Change::Update {
                 log: LogChange::default(),
@@ -75,7 +75,7 @@ pub(crate) mod prepare_and_commit {
         }
     }
 
-    fn delete_at(name: &str) -> RefEdit {
+    pub(crate) fn delete_at(name: &str) -> RefEdit {
         RefEdit {
             change: Change::Delete {
                 expected: PreviousValue::Any,
@@ -89,4 +89,6 @@ pub(crate) mod prepare_and_commit {
     mod create_or_update;
 
     mod delete;
+
+    mod hook;
 }
@@ -640,6 +640,88 @@ fn symbolic_head_missing_referent_then_update_referent() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn peeled_updates_of_new_and_existing_refs_write_reflogs_according_to_write_mode() -> crate::Result {
+    for reflog_writemode in &[WriteReflog::Normal, WriteReflog::Disable, WriteReflog::Always] {
+        let (_keep, mut store) = empty_store()?;
+        store.write_reflog = *reflog_writemode;
+        let first_oid = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+        let second_oid = hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+        let update = |name: &str, new: ObjectId, force_create_reflog: bool, message: &str| -> crate::Result {
+            store
+                .transaction()
+                .prepare(
+                    Some(RefEdit {
+                        change: Change::Update {
+                            log: LogChange {
+                                mode: RefLog::AndReference,
+                                force_create_reflog,
+                                message: message.into(),
+                            },
+                            expected: PreviousValue::Any,
+                            new: Target::Peeled(new),
+                        },
+                        name: name.try_into()?,
+                        deref: false,
+                    }),
+                    Fail::Immediately,
+                    Fail::Immediately,
+                )?
+                .commit(committer().to_ref())?;
+            Ok(())
+        };
+
+        for (name, force_create_reflog) in [
+            ("refs/heads/main", false),
+            ("refs/custom/ref", false),
+            ("refs/tags/forced", true),
+        ] {
+            update(name, first_oid, force_create_reflog, "create")?;
+        }
+        for name in ["refs/heads/main", "refs/custom/ref"] {
+            update(name, second_oid, false, "update")?;
+        }
+
+        let null = gix_hash::Kind::Sha1.null();
+        let created_and_updated = vec![
+            log_line(null, first_oid, "create"),
+            log_line(first_oid, second_oid, "update"),
+        ];
+        let mut buf = Vec::new();
+        match reflog_writemode {
+            WriteReflog::Normal | WriteReflog::Always => {
+                assert_eq!(
+                    reflog_lines(&store, "refs/heads/main")?,
+                    created_and_updated,
+                    "branches always get a reflog, which receives the previous value on update"
+                );
+                assert_eq!(
+                    reflog_lines(&store, "refs/tags/forced")?,
+                    vec![log_line(null, first_oid, "create")],
+                    "a reflog is created for any ref if forced"
+                );
+                if *reflog_writemode == WriteReflog::Always {
+                    assert_eq!(reflog_lines(&store, "refs/custom/ref")?, created_and_updated);
+                } else {
+                    assert!(
+                        store.reflog_iter("refs/custom/ref", &mut buf)?.is_none(),
+                        "refs outside of the well-known namespaces don't get a reflog by default"
+                    );
+                }
+            }
+            WriteReflog::Disable => {
+                for name in ["refs/heads/main", "refs/custom/ref", "refs/tags/forced"] {
+                    assert!(
+                        store.reflog_iter(name, &mut buf)?.is_none(),
+                        "nothing is ever written if its disabled"
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[test]
 /// Writing a peeled ref to which head points to doesn't update HEAD on the fly even though that might be what's would
 /// be needed to keep the reflog consistent
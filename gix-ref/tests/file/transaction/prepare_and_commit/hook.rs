@@ -0,0 +1,122 @@
+use std::{cell::RefCell, rc::Rc};
+
+use gix_lock::acquire::Fail;
+use gix_object::bstr::BString;
+use gix_ref::file::transaction::{prepare, HookState};
+
+use crate::file::transaction::prepare_and_commit::{committer, create_at, create_symbolic_at, delete_at, empty_store};
+
+type Calls = Rc<RefCell<Vec<(HookState, BString)>>>;
+
+fn recorder(calls: &Calls) -> impl FnMut(HookState, &[u8]) -> Result<(), gix_ref::file::transaction::HookError> {
+    let calls = calls.clone();
+    move |state, input| {
+        calls.borrow_mut().push((state, input.into()));
+        Ok(())
+    }
+}
+
+#[test]
+fn is_called_when_prepared_and_committed() -> crate::Result {
+    let (_keep, store) = empty_store()?;
+    let calls = Calls::default();
+    let edits = store
+        .transaction()
+        .hook(recorder(&calls))
+        .prepare(
+            [
+                create_at("refs/heads/main"),
+                create_symbolic_at("HEAD", "refs/heads/main"),
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(edits.len(), 2);
+
+    let expected_input: BString =
+        "0000000000000000000000000000000000000000 e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 refs/heads/main\n\
+                                   0000000000000000000000000000000000000000 ref:refs/heads/main HEAD\n"
+            .into();
+    assert_eq!(
+        *calls.borrow(),
+        [
+            (HookState::Prepared, expected_input.clone()),
+            (HookState::Committed, expected_input)
+        ],
+        "the same updates are passed in each state"
+    );
+
+    calls.borrow_mut().clear();
+    store
+        .transaction()
+        .hook(recorder(&calls))
+        .prepare([delete_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    let expected_input: BString =
+        "e69de29bb2d1d6434b8b29ae775ad8c2e48c5391 0000000000000000000000000000000000000000 refs/heads/main\n".into();
+    assert_eq!(
+        *calls.borrow(),
+        [
+            (HookState::Prepared, expected_input.clone()),
+            (HookState::Committed, expected_input)
+        ],
+        "previous values are known after locking, and deletions use the null id"
+    );
+    Ok(())
+}
+
+#[test]
+fn rejection_when_prepared_aborts_the_transaction() -> crate::Result {
+    let (_keep, store) = empty_store()?;
+    let calls = Calls::default();
+    let mut record = recorder(&calls);
+    let err = store
+        .transaction()
+        .hook(move |state, input| {
+            record(state, input)?;
+            match state {
+                HookState::Prepared => Err("rejected".into()),
+                _ => Ok(()),
+            }
+        })
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)
+        .expect_err("the hook rejects the transaction");
+    assert!(matches!(err, prepare::Error::Hook(_)));
+    assert_eq!(
+        calls.borrow().iter().map(|(state, _)| *state).collect::<Vec<_>>(),
+        [HookState::Prepared, HookState::Aborted]
+    );
+    assert!(
+        store.try_find_loose("main")?.is_none(),
+        "nothing was written and the lock was released"
+    );
+
+    store
+        .transaction()
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .commit(committer().to_ref())?;
+    assert!(
+        store.try_find_loose("main")?.is_some(),
+        "the reference isn't locked anymore"
+    );
+    Ok(())
+}
+
+#[test]
+fn is_called_on_rollback() -> crate::Result {
+    let (_keep, store) = empty_store()?;
+    let calls = Calls::default();
+    let edits = store
+        .transaction()
+        .hook(recorder(&calls))
+        .prepare([create_at("refs/heads/main")], Fail::Immediately, Fail::Immediately)?
+        .rollback();
+    assert_eq!(edits.len(), 1);
+    assert_eq!(
+        calls.borrow().iter().map(|(state, _)| *state).collect::<Vec<_>>(),
+        [HookState::Prepared, HookState::Aborted]
+    );
+    assert!(store.try_find_loose("main")?.is_none());
+    Ok(())
+}
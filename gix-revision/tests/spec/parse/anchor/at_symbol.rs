@@ -47,6 +47,15 @@ fn reflog_by_date_for_current_branch() {
     assert_eq!(rec.calls, 1);
 }
 
+#[test]
+fn reflog_by_date_with_dots_instead_of_spaces() {
+    let rec = parse("main@{2.days.ago}");
+
+    assert_eq!(rec.get_ref(0), "main");
+    assert!(rec.current_branch_reflog_entry[0].is_some());
+    assert_eq!(rec.calls, 2);
+}
+
 #[test]
 fn reflog_by_date_with_date_parse_failure() {
     let err = try_parse("@{foo}").unwrap_err();
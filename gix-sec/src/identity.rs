@@ -17,9 +17,123 @@ pub fn is_path_owned_by_current_user(path: &Path) -> std::io::Result<bool> {
     impl_::is_path_owned_by_current_user(path)
 }
 
+/// The user executing the current process, as known to the operating system.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct User {
+    /// The login name of the user.
+    pub name: String,
+    /// The full name of the user if known, which may be empty.
+    ///
+    /// On unix it's taken from the GECOS field up to the first comma, with `&` replaced by the capitalized login name.
+    pub full_name: Option<String>,
+}
+
+/// Return the user executing the current process, or `None` if it couldn't be determined.
+///
+/// On Windows, it's taken from the `USERNAME` environment variable, without full name.
+pub fn current_user() -> Option<User> {
+    impl_::current_user()
+}
+
+/// Return the name of the host the current process is running on, qualified with its domain if it could be determined,
+/// or `None` if there is no host name.
+///
+/// On unix, host names without domain are canonicalized by name resolution, which may involve network access.
+/// On Windows, the `COMPUTERNAME` and `USERDNSDOMAIN` environment variables are used.
+pub fn hostname() -> Option<String> {
+    impl_::hostname()
+}
+
 #[cfg(not(windows))]
 mod impl_ {
-    use std::path::Path;
+    use std::{
+        ffi::{CStr, CString},
+        path::Path,
+    };
+
+    use crate::identity::User;
+
+    pub fn current_user() -> Option<User> {
+        let mut buf = vec![0 as libc::c_char; 4096];
+        // SAFETY: all fields are plain data or pointers, for which zero is a valid value.
+        #[allow(unsafe_code)]
+        let mut entry: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        loop {
+            // SAFETY: all pointers are valid for the duration of the call, and `buf.len()` is the size of `buf`.
+            #[allow(unsafe_code)]
+            let err = unsafe { libc::getpwuid_r(libc::getuid(), &mut entry, buf.as_mut_ptr(), buf.len(), &mut result) };
+            match err {
+                libc::ERANGE if buf.len() < 1024 * 1024 => buf.resize(buf.len() * 2, 0),
+                0 if !result.is_null() => break,
+                _ => return None,
+            }
+        }
+        // SAFETY: on success, the name is a nul-terminated string stored in `buf`.
+        #[allow(unsafe_code)]
+        let name = unsafe { CStr::from_ptr(entry.pw_name) }.to_string_lossy().into_owned();
+        #[cfg(not(target_os = "android"))]
+        let gecos = (!entry.pw_gecos.is_null()).then(|| {
+            // SAFETY: on success, the GECOS field is a nul-terminated string stored in `buf`.
+            #[allow(unsafe_code)]
+            let gecos = unsafe { CStr::from_ptr(entry.pw_gecos) };
+            gecos.to_string_lossy().into_owned()
+        });
+        #[cfg(target_os = "android")]
+        let gecos: Option<String> = None;
+
+        let full_name = gecos.map(|gecos| {
+            // Traditionally, the GECOS field also contains phone numbers and the like, separated with commas.
+            let full_name = gecos.split(',').next().unwrap_or_default();
+            let mut capitalized_name = name.clone();
+            if let Some(first) = capitalized_name.get_mut(..1) {
+                first.make_ascii_uppercase();
+            }
+            full_name.replace('&', &capitalized_name)
+        });
+        Some(User { name, full_name })
+    }
+
+    pub fn hostname() -> Option<String> {
+        let mut buf = [0 as libc::c_char; 256];
+        // SAFETY: the pointer is valid for `buf.len() - 1` bytes, leaving space for the nul-terminator in any case.
+        #[allow(unsafe_code)]
+        if unsafe { libc::gethostname(buf.as_mut_ptr(), buf.len() - 1) } != 0 {
+            return None;
+        }
+        // SAFETY: the last byte of `buf` is never written, so there is a nul-terminator.
+        #[allow(unsafe_code)]
+        let host = unsafe { CStr::from_ptr(buf.as_ptr()) }.to_string_lossy().into_owned();
+        if host.contains('.') {
+            return Some(host);
+        }
+        Some(canonical_name(&host).unwrap_or(host))
+    }
+
+    /// Resolve `host` to its canonical name, if it has a domain.
+    fn canonical_name(host: &str) -> Option<String> {
+        let host = CString::new(host).ok()?;
+        // SAFETY: all fields are plain data or pointers, for which zero is a valid value.
+        #[allow(unsafe_code)]
+        let mut hints: libc::addrinfo = unsafe { std::mem::zeroed() };
+        hints.ai_flags = libc::AI_CANONNAME;
+        let mut info: *mut libc::addrinfo = std::ptr::null_mut();
+        // SAFETY: all pointers are valid for the duration of the call, and `info` is freed below if it was set.
+        #[allow(unsafe_code)]
+        if unsafe { libc::getaddrinfo(host.as_ptr(), std::ptr::null(), &hints, &mut info) } != 0 {
+            return None;
+        }
+        // SAFETY: on success, `info` points to a valid list whose canonical name is a nul-terminated string, if set.
+        #[allow(unsafe_code)]
+        let name = unsafe {
+            let name = (!info.is_null() && !(*info).ai_canonname.is_null())
+                .then(|| CStr::from_ptr((*info).ai_canonname).to_string_lossy().into_owned());
+            libc::freeaddrinfo(info);
+            name
+        };
+        name.filter(|name| name.contains('.'))
+    }
 
     pub fn is_path_owned_by_current_user(path: &Path) -> std::io::Result<bool> {
         fn owner_from_path(path: &Path) -> std::io::Result<u32> {
@@ -52,6 +166,21 @@ mod impl_ {
 
 #[cfg(windows)]
 mod impl_ {
+    use crate::identity::User;
+
+    pub fn current_user() -> Option<User> {
+        let name = std::env::var("USERNAME").ok().filter(|name| !name.is_empty())?;
+        Some(User { name, full_name: None })
+    }
+
+    pub fn hostname() -> Option<String> {
+        let host = std::env::var("COMPUTERNAME").ok().filter(|host| !host.is_empty())?;
+        Some(match std::env::var("USERDNSDOMAIN") {
+            Ok(domain) if !domain.is_empty() => format!("{host}.{domain}"),
+            _ => host,
+        })
+    }
+
     use std::{
         io,
         mem::MaybeUninit,
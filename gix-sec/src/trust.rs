@@ -9,6 +9,55 @@ impl Trust {
             Trust::Reduced
         })
     }
+
+    /// Derive `Full` trust only if all `paths` are owned by the user executing the current process, or `Reduced` trust otherwise.
+    ///
+    /// This is useful to also take the work tree and the `.git` file of a repository into consideration, like `git` does.
+    pub fn from_paths_ownership<'a>(paths: impl IntoIterator<Item = &'a std::path::Path>) -> std::io::Result<Self> {
+        for path in paths {
+            if Self::from_path_ownership(path)? == Trust::Reduced {
+                return Ok(Trust::Reduced);
+            }
+        }
+        Ok(Trust::Full)
+    }
+}
+
+/// Return `true` if `path` is considered safe by the `safe_directories`, the values of `safe.directory`
+/// in the order they were encountered, which are evaluated like `git` does:
+///
+/// * `*` marks all directories as safe.
+/// * An empty value resets the list, making all previously listed directories unsafe again.
+/// * A value ending in `/*` marks all directories within it as safe.
+/// * Any other value marks exactly this directory as safe.
+///
+/// Note that values must already be interpolated, and `path` should be absolute and free of symlinks
+/// for the comparison to be meaningful.
+pub fn is_safe_directory<'a>(
+    path: &std::path::Path,
+    safe_directories: impl IntoIterator<Item = &'a std::path::Path>,
+) -> bool {
+    let mut is_safe = false;
+    for safe_dir in safe_directories {
+        if safe_dir.as_os_str().is_empty() {
+            is_safe = false;
+            continue;
+        }
+        if is_safe {
+            continue;
+        }
+        is_safe = if safe_dir.as_os_str() == "*" {
+            true
+        } else if safe_dir.ends_with("*") {
+            match safe_dir.parent() {
+                Some(prefix) => path != prefix && path.starts_with(prefix),
+                None => false,
+            }
+        } else {
+            safe_dir == path
+        };
+    }
+    is_safe
 }
 
 /// A trait to help creating default values based on a trust level.
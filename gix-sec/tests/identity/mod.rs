@@ -15,3 +15,16 @@ fn windows_home() -> crate::Result {
     assert!(gix_sec::identity::is_path_owned_by_current_user(&home)?);
     Ok(())
 }
+
+#[test]
+#[cfg(not(windows))]
+fn current_user_and_hostname() {
+    if let Some(user) = gix_sec::identity::current_user() {
+        assert!(!user.name.is_empty());
+        assert!(
+            !user.full_name.unwrap_or_default().contains(','),
+            "only the name is used"
+        );
+    }
+    assert!(!gix_sec::identity::hostname().expect("there is a host name").is_empty());
+}
@@ -7,6 +7,64 @@ mod trust {
     fn ordering() {
         assert!(Trust::Reduced < Trust::Full);
     }
+
+    #[test]
+    fn from_paths_ownership() -> crate::Result {
+        let dir = tempfile::tempdir()?;
+        assert_eq!(Trust::from_paths_ownership([dir.path(), dir.path()])?, Trust::Full);
+        assert_eq!(
+            Trust::from_paths_ownership(None)?,
+            Trust::Full,
+            "no paths, no reason to distrust"
+        );
+        assert!(
+            Trust::from_paths_ownership([dir.path().join("does-not-exist").as_ref()]).is_err(),
+            "the ownership of missing paths can't be determined"
+        );
+        Ok(())
+    }
+
+    mod is_safe_directory {
+        use std::path::Path;
+
+        use gix_sec::trust::is_safe_directory;
+
+        fn is_safe(path: &str, safe_dirs: &[&str]) -> bool {
+            is_safe_directory(Path::new(path), safe_dirs.iter().map(Path::new))
+        }
+
+        #[test]
+        fn exact_matches() {
+            assert!(is_safe("/repo", &["/repo"]));
+            assert!(!is_safe("/repo", &["/other"]));
+            assert!(!is_safe("/repo/sub", &["/repo"]), "sub-directories are not included");
+            assert!(!is_safe("/repo", &[]), "nothing is safe by default");
+        }
+
+        #[test]
+        fn star_matches_everything() {
+            assert!(is_safe("/repo", &["*"]));
+            assert!(is_safe("/repo", &["/other", "*"]));
+        }
+
+        #[test]
+        fn empty_values_reset_the_list() {
+            assert!(!is_safe("/repo", &["*", ""]));
+            assert!(!is_safe("/repo", &["/repo", ""]));
+            assert!(is_safe("/repo", &["*", "", "/repo"]));
+        }
+
+        #[test]
+        fn trailing_star_matches_all_directories_within() {
+            assert!(is_safe("/base/repo", &["/base/*"]));
+            assert!(is_safe("/base/deeply/nested/repo", &["/base/*"]));
+            assert!(
+                !is_safe("/base", &["/base/*"]),
+                "the prefix directory itself isn't included"
+            );
+            assert!(!is_safe("/basement/repo", &["/base/*"]), "only whole components match");
+        }
+    }
 }
 
 mod permission {
@@ -16,6 +16,10 @@
 //! As a general rule of thumb, use `Default::default()` as argument to emulate the default behaviour and
 //! abort the process after cleaning temporary files. Read more about options in [`signal::handler::Mode`].
 //!
+//! To install or remove the handlers at any time, for instance to compose them with handlers of the application,
+//! use [`signal::register()`]. Applications that manage signals themselves, like async runtimes or Windows services,
+//! can opt out of signal handlers entirely and call [`cleanup_now()`] when shutting down.
+//!
 //! # Limitations
 //!
 //! ## Tempfiles might remain on disk
@@ -115,21 +119,7 @@ static NEXT_MAP_INDEX: AtomicUsize = AtomicUsize::new(0);
 static REGISTRY: Lazy<HashMap<usize, Option<ForksafeTempfile>>> = Lazy::new(|| {
     #[cfg(feature = "signals")]
     if signal::handler::MODE.load(std::sync::atomic::Ordering::SeqCst) != signal::handler::Mode::None as usize {
-        for sig in signal_hook::consts::TERM_SIGNALS {
-            // SAFETY: handlers are considered unsafe because a lot can go wrong. See `cleanup_tempfiles()` for details on safety.
-            #[allow(unsafe_code)]
-            unsafe {
-                #[cfg(not(windows))]
-                {
-                    signal_hook_registry::register_sigaction(*sig, signal::handler::cleanup_tempfiles_nix)
-                }
-                #[cfg(windows)]
-                {
-                    signal_hook::low_level::register(*sig, signal::handler::cleanup_tempfiles_windows)
-                }
-            }
-            .expect("signals can always be installed");
-        }
+        signal::handler::install().expect("signals can always be installed");
     }
     HashMap::default()
 });
@@ -190,6 +180,18 @@ pub struct Handle<Marker: std::fmt::Debug> {
     _marker: PhantomData<Marker>,
 }
 
+/// Remove all tempfiles of the current process that are still registered right away, and return how many were removed.
+///
+/// This is meant for applications or runtimes that manage signals themselves, like async runtimes or Windows services,
+/// to call once they are asked to shut down.
+///
+/// # Note
+///
+/// Must not be called from within signal hooks. For that, use [`registry::cleanup_tempfiles_signal_safe()`].
+pub fn cleanup_now() -> usize {
+    registry::cleanup_tempfiles_and_count()
+}
+
 /// A shortcut to [`Handle::<Writable>::new()`], creating a writable temporary file with non-clashing name in a directory.
 pub fn new(
     containing_directory: impl AsRef<Path>,
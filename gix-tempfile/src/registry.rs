@@ -52,17 +52,24 @@ pub fn cleanup_tempfiles_signal_safe() {
 ///
 /// Must not be called from within signal hooks. For that, use [`cleanup_tempfiles_signal_safe()`].
 pub fn cleanup_tempfiles() {
+    cleanup_tempfiles_and_count();
+}
+
+/// Like [`cleanup_tempfiles()`], but returns the amount of tempfiles that were removed.
+pub(crate) fn cleanup_tempfiles_and_count() -> usize {
     let current_pid = std::process::id();
+    let mut count = 0;
     #[cfg(feature = "hp-hashmap")]
     REGISTRY.iter_mut().for_each(|mut tf| {
         if tf.as_ref().map_or(false, |tf| tf.owning_process_id == current_pid) {
-            tf.take();
+            count += usize::from(tf.take().is_some());
         }
     });
     #[cfg(not(feature = "hp-hashmap"))]
     REGISTRY.for_each(|tf| {
         if tf.as_ref().map_or(false, |tf| tf.owning_process_id == current_pid) {
-            tf.take();
+            count += usize::from(tf.take().is_some());
         }
     });
+    count
 }
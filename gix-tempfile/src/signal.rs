@@ -2,6 +2,21 @@ use once_cell::sync::Lazy;
 
 use crate::REGISTRY;
 
+/// A handle to the signal handlers installed by [`register()`], which can be used to remove them again.
+#[derive(Debug)]
+pub struct Registration {
+    _private: (),
+}
+
+impl Registration {
+    /// Remove our signal handlers, returning `true` if they were still installed.
+    ///
+    /// Tempfiles will then only be removed on drop or when calling [`cleanup_now()`][crate::cleanup_now()].
+    pub fn unregister(self) -> bool {
+        handler::uninstall()
+    }
+}
+
 /// Initialize signal handlers and other state to keep track of tempfiles, and **must be called before the first tempfile is created**,
 /// allowing to set the `mode` in which signal handlers are installed.
 ///
@@ -15,12 +30,73 @@ pub fn setup(mode: handler::Mode) {
     Lazy::force(&REGISTRY);
 }
 
+/// Install our signal handlers for all termination signals with the given `mode` right away, unless they are installed already,
+/// and return a handle to remove them again.
+///
+/// As opposed to [`setup()`], this works at any time, even if the handlers were previously disabled with [`handler::Mode::None`]
+/// or removed with [`Registration::unregister()`]. `mode` must not be [`handler::Mode::None`], which is treated like
+/// [`handler::Mode::DeleteTempfilesOnTermination`].
+///
+/// ### Composing with other signal handlers
+///
+/// The handlers are installed with [`signal-hook`](https://docs.rs/signal-hook), which calls all handlers registered for
+/// a signal in the order of their registration. Applications with their own handlers that should run before the process
+/// terminates should register them first, or use [`handler::Mode::DeleteTempfilesOnTermination`] to remain in control
+/// of what happens after tempfiles were removed.
+pub fn register(mode: handler::Mode) -> std::io::Result<Registration> {
+    let mode = match mode {
+        handler::Mode::None => handler::Mode::DeleteTempfilesOnTermination,
+        mode => mode,
+    };
+    handler::MODE.store(mode as usize, std::sync::atomic::Ordering::SeqCst);
+    Lazy::force(&REGISTRY);
+    handler::install()?;
+    Ok(Registration { _private: () })
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod handler {
     use std::sync::atomic::AtomicUsize;
 
+    use parking_lot::Mutex;
+
     pub(crate) static MODE: AtomicUsize = AtomicUsize::new(Mode::None as usize);
+    static INSTALLED: Mutex<Vec<signal_hook::SigId>> = parking_lot::const_mutex(Vec::new());
+
+    /// Install our handlers for all termination signals if they aren't installed yet.
+    pub(crate) fn install() -> std::io::Result<()> {
+        let mut installed = INSTALLED.lock();
+        if !installed.is_empty() {
+            return Ok(());
+        }
+        for sig in signal_hook::consts::TERM_SIGNALS {
+            // SAFETY: handlers are considered unsafe because a lot can go wrong. See `cleanup_tempfiles()` for details on safety.
+            #[allow(unsafe_code)]
+            let id = unsafe {
+                #[cfg(not(windows))]
+                {
+                    signal_hook_registry::register_sigaction(*sig, cleanup_tempfiles_nix)
+                }
+                #[cfg(windows)]
+                {
+                    signal_hook::low_level::register(*sig, cleanup_tempfiles_windows)
+                }
+            }?;
+            installed.push(id);
+        }
+        Ok(())
+    }
+
+    /// Remove all of our handlers, returning `true` if there were any.
+    pub(crate) fn uninstall() -> bool {
+        let mut installed = INSTALLED.lock();
+        let had_handlers = !installed.is_empty();
+        for id in installed.drain(..) {
+            signal_hook::low_level::unregister(id);
+        }
+        had_handlers
+    }
 
     /// Define how our signal handlers act
     #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
@@ -96,6 +172,19 @@ pub mod handler {
                     "the signal triggers removal but won't terminate the process (anymore)"
                 );
             }
+
+            let registration = crate::signal::register(Default::default())?;
+            assert!(registration.unregister(), "handlers were installed by `setup()`");
+            let registration = crate::signal::register(Default::default())?;
+            let _tempfile = crate::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+            signal_hook::low_level::raise(signal_hook::consts::SIGTERM)?;
+            assert_eq!(
+                filecount_in(dir.path()),
+                0,
+                "handlers can be installed again after removing them"
+            );
+            assert!(registration.unregister());
+            assert!(!crate::signal::handler::uninstall(), "nothing is left to remove");
             Ok(())
         }
     }
@@ -26,5 +26,12 @@ fn cleanup_tempfiles() -> Result<(), Box<dyn std::error::Error>> {
         tempfile.write_all(b"bogus").is_err(),
         "cannot write into a tempfile that doesn't exist in registry"
     );
+
+    let _first = gix_tempfile::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+    let _second = gix_tempfile::new(dir.path(), ContainingDirectory::Exists, AutoRemove::Tempfile)?;
+    assert_eq!(filecount_in(dir.path()), 2);
+    assert_eq!(gix_tempfile::cleanup_now(), 2, "both tempfiles were removed");
+    assert_eq!(filecount_in(dir.path()), 0);
+    assert_eq!(gix_tempfile::cleanup_now(), 0, "nothing is left to remove");
     Ok(())
 }
@@ -237,6 +237,7 @@ fn open_options(path: &Path, destination_is_initially_empty: bool, overwrite_exi
     options
         .create_new(destination_is_initially_empty && !overwrite_existing)
         .create(!destination_is_initially_empty || overwrite_existing)
+        .truncate(!destination_is_initially_empty || overwrite_existing)
         .write(true);
     options
 }
@@ -248,6 +249,8 @@ pub(crate) fn open_file(
     fs_supports_executable_bit: bool,
     entry_mode: gix_index::entry::Mode,
 ) -> std::io::Result<(std::fs::File, bool)> {
+    let path = gix_path::to_long_path_on_windows(path);
+    let path = path.as_ref();
     #[cfg_attr(windows, allow(unused_mut))]
     let mut options = open_options(path, destination_is_initially_empty, overwrite_existing);
     let needs_executable_bit = fs_supports_executable_bit && entry_mode == gix_index::entry::Mode::FILE_EXECUTABLE;
@@ -1,9 +1,16 @@
-use std::sync::atomic::AtomicBool;
+use std::{
+    collections::BTreeSet,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
+use bstr::{BStr, ByteSlice};
 use gix_features::{interrupt, parallel::in_parallel_with_finalize};
+use gix_filter::{driver::apply::MaybeDelayed, pipeline::convert::ToWorktreeOutcome};
+use gix_index::entry::Mode;
+use gix_object::FindExt;
 use gix_worktree::{stack, Stack};
 
-use crate::checkout::chunk;
+use crate::checkout::{chunk, Sink};
 
 /// Checkout the entire `index` into `dir`, and resolve objects found in index entries with `objects` to write their content to their
 /// respective path in `dir`.
@@ -28,12 +35,251 @@ pub fn checkout<Find>(
 where
     Find: gix_object::Find + Send + Clone,
 {
+    let _span = gix_features::trace::coarse!("gix_worktree_state::checkout()", num_entries = index.entries().len());
     let paths = index.take_path_backing();
     let res = checkout_inner(index, &paths, dir, objects, files, bytes, should_interrupt, options);
     index.return_path_backing(paths);
     res
 }
 
+/// Checkout the entire `index` into `sink` instead of a directory, and resolve objects found in index entries with `objects`
+/// to pass their filtered content to `sink`.
+/// Use `files` to count each fully checked out file, and count the amount written `bytes`. If `should_interrupt` is `true`, the
+/// operation will abort.
+///
+/// `options` are used like in [`checkout()`], but as nothing is written to disk, the `destination_is_initially_empty`,
+/// `overwrite_existing` and `thread_limit` fields are ignored.
+/// Attributes should be read from the index only, as there is no worktree to read them from.
+///
+/// ### Deviation
+///
+/// * Unlike with [`checkout()`], the `index` isn't updated with the stat information of written files, and collisions
+///   aren't detected.
+#[allow(clippy::too_many_arguments)]
+pub fn checkout_into<Find>(
+    index: &gix_index::State,
+    sink: &mut dyn Sink,
+    objects: Find,
+    files: &dyn gix_features::progress::Count,
+    bytes: &dyn gix_features::progress::Count,
+    should_interrupt: &AtomicBool,
+    mut options: crate::checkout::Options,
+) -> Result<crate::checkout::Outcome, crate::checkout::Error>
+where
+    Find: gix_object::Find,
+{
+    let _span = gix_features::trace::coarse!(
+        "gix_worktree_state::checkout_into()",
+        num_entries = index.entries().len()
+    );
+    let num_files = files.counter();
+    let num_bytes = bytes.counter();
+    let mut stack = Stack::from_state_and_ignore_case(
+        std::path::PathBuf::new(),
+        options.fs.ignore_case,
+        stack::State::AttributesStack(std::mem::take(&mut options.attributes)),
+        index,
+        index.path_backing(),
+    );
+    let mut out = crate::checkout::Outcome::default();
+    let mut buf = Vec::new();
+    let mut delayed = Vec::new();
+    for entry in index.entries() {
+        if should_interrupt.load(Ordering::Relaxed) {
+            break;
+        }
+        let rela_path = entry.path(index);
+        if entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) {
+            num_files.fetch_add(1, Ordering::Relaxed);
+            continue;
+        }
+        let res = entry_into(
+            entry,
+            rela_path,
+            sink,
+            &mut stack,
+            &objects,
+            &mut options,
+            &mut buf,
+            &mut delayed,
+        );
+        match res {
+            Ok(Some(written)) => {
+                out.files_updated += 1;
+                out.bytes_written += written;
+                num_bytes.fetch_add(written as usize, Ordering::Relaxed);
+            }
+            Ok(None) => continue,
+            Err(err) if options.keep_going => out.errors.push(crate::checkout::ErrorRecord {
+                path: rela_path.to_owned(),
+                error: Box::new(err),
+            }),
+            Err(err) => return Err(err),
+        }
+        num_files.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // Entries are sorted by path, and so are the delayed ones.
+    let keys: BTreeSet<_> = delayed.iter().map(|d| d.key.clone()).collect();
+    for key in keys {
+        loop {
+            let rela_paths = options.filters.driver_state_mut().list_delayed_paths(&key)?;
+            if rela_paths.is_empty() {
+                break;
+            }
+            for rela_path in rela_paths {
+                let Ok(idx) = delayed.binary_search_by(|d| d.rela_path.cmp(rela_path.as_ref())) else {
+                    if options.keep_going {
+                        out.delayed_paths_unknown.push(rela_path);
+                        continue;
+                    }
+                    return Err(crate::checkout::Error::FilterPathUnknown { rela_path });
+                };
+                let delayed = &mut delayed[idx];
+                delayed.processed = true;
+                let mut read = options.filters.driver_state_mut().fetch_delayed(
+                    &key,
+                    rela_path.as_ref(),
+                    gix_filter::driver::Operation::Smudge,
+                )?;
+                match sink.write_file(delayed.rela_path, delayed.mode, &mut read) {
+                    Ok(written) => {
+                        out.files_updated += 1;
+                        out.bytes_written += written;
+                        num_bytes.fetch_add(written as usize, Ordering::Relaxed);
+                    }
+                    Err(err) if options.keep_going => {
+                        std::io::copy(&mut read, &mut std::io::sink())?;
+                        out.errors.push(crate::checkout::ErrorRecord {
+                            path: rela_path,
+                            error: Box::new(err),
+                        });
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+                num_files.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    out.delayed_paths_unprocessed = delayed
+        .into_iter()
+        .filter_map(|d| (!d.processed).then(|| d.rela_path.to_owned()))
+        .collect();
+    if !options.keep_going && !out.delayed_paths_unprocessed.is_empty() {
+        return Err(crate::checkout::Error::FilterPathsUnprocessed {
+            rela_paths: out.delayed_paths_unprocessed,
+        });
+    }
+    Ok(out)
+}
+
+/// A file whose content will be provided later by a long-running filter process.
+struct Delayed<'a> {
+    key: gix_filter::driver::Key,
+    rela_path: &'a BStr,
+    mode: Mode,
+    processed: bool,
+}
+
+/// Pass the content of `entry` at `rela_path` to `sink` and return the amount of written bytes,
+/// or `None` if its content will be available later and it was added to `delayed`.
+#[allow(clippy::too_many_arguments)]
+fn entry_into<'a>(
+    entry: &gix_index::Entry,
+    rela_path: &'a BStr,
+    sink: &mut dyn Sink,
+    stack: &mut Stack,
+    objects: &dyn gix_object::Find,
+    options: &mut crate::checkout::Options,
+    buf: &mut Vec<u8>,
+    delayed: &mut Vec<Delayed<'a>>,
+) -> Result<Option<u64>, crate::checkout::Error> {
+    validate_path(rela_path, entry.mode, options.validate)?;
+    let find_blob = |buf| {
+        objects
+            .find_blob(&entry.id, buf)
+            .map_err(|err| crate::checkout::Error::Find {
+                err,
+                path: gix_path::from_bstr(rela_path).into_owned(),
+            })
+    };
+    let written = match entry.mode {
+        Mode::FILE | Mode::FILE_EXECUTABLE => {
+            let mode = if options.fs.executable_bit {
+                entry.mode
+            } else {
+                Mode::FILE
+            };
+            let obj = find_blob(buf)?;
+            let platform = stack.at_entry(rela_path, Some(entry.mode), objects)?;
+            let filtered = options.filters.convert_to_worktree(
+                obj.data,
+                rela_path,
+                &mut |_, attrs| {
+                    platform.matching_attributes(attrs);
+                },
+                options.filter_process_delay,
+            )?;
+            match filtered {
+                ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
+                    sink.write_file(rela_path, mode, &mut &*buf)?
+                }
+                ToWorktreeOutcome::Process(MaybeDelayed::Immediate(mut read)) => {
+                    sink.write_file(rela_path, mode, &mut read)?
+                }
+                ToWorktreeOutcome::Process(MaybeDelayed::Delayed(key)) => {
+                    delayed.push(Delayed {
+                        key,
+                        rela_path,
+                        mode,
+                        processed: false,
+                    });
+                    return Ok(None);
+                }
+            }
+        }
+        Mode::SYMLINK => {
+            let obj = find_blob(buf)?;
+            if options.fs.symlink {
+                sink.write_symlink(rela_path, obj.data.as_bstr())?;
+            } else {
+                sink.write_file(rela_path, Mode::FILE, &mut &*obj.data)?;
+            }
+            obj.data.len() as u64
+        }
+        Mode::COMMIT => {
+            sink.create_directory(rela_path)?;
+            0
+        }
+        Mode::DIR => {
+            gix_features::trace::warn!(
+                "Skipped sparse directory at '{rela_path}' ({id}) as it cannot yet be handled",
+                id = entry.id
+            );
+            0
+        }
+        _ => unreachable!(),
+    };
+    Ok(Some(written))
+}
+
+/// Validate all components of `rela_path` like they would be validated before creating them on disk.
+fn validate_path(
+    rela_path: &BStr,
+    mode: Mode,
+    opts: gix_worktree::validate::path::component::Options,
+) -> std::io::Result<()> {
+    let mut components = rela_path.split_str("/").peekable();
+    while let Some(component) = components.next() {
+        let mode = (components.peek().is_none() && mode == Mode::SYMLINK)
+            .then_some(gix_worktree::validate::path::component::Mode::Symlink);
+        gix_worktree::validate::path::component(component.as_bstr(), mode, opts)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+    }
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 fn checkout_inner<Find>(
     index: &mut gix_index::State,
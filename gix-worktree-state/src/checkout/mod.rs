@@ -1,4 +1,4 @@
-use bstr::BString;
+use bstr::{BStr, BString};
 use gix_index::entry::stat;
 
 /// Information about a path that failed to checkout as something else was already present.
@@ -87,7 +87,7 @@ pub enum Error {
         path: std::path::PathBuf,
     },
     #[error(transparent)]
-    Filter(#[from] gix_filter::pipeline::convert::to_worktree::Error),
+    Filter(Box<gix_filter::pipeline::convert::to_worktree::Error>),
     #[error(transparent)]
     FilterListDelayed(#[from] gix_filter::driver::delayed::list::Error),
     #[error(transparent)]
@@ -98,6 +98,45 @@ pub enum Error {
     FilterPathsUnprocessed { rela_paths: Vec<BString> },
 }
 
+impl From<gix_filter::pipeline::convert::to_worktree::Error> for Error {
+    fn from(err: gix_filter::pipeline::convert::to_worktree::Error) -> Self {
+        Error::Filter(Box::new(err))
+    }
+}
+
+/// A destination for the files of a checkout, as alternative to writing them into a directory on disk.
+///
+/// Use it with [`checkout_into()`](crate::checkout_into()) to place files into virtual filesystems or build sandboxes.
+/// Paths are relative to the root of the checkout, use slashes as separators, and have been validated already.
+pub trait Sink {
+    /// Write a file at `rela_path` with the bytes of `content`, which were already passed through all filters,
+    /// and return the amount of written bytes.
+    ///
+    /// `mode` is [`FILE_EXECUTABLE`](gix_index::entry::Mode::FILE_EXECUTABLE) if the file should be executable,
+    /// or [`FILE`](gix_index::entry::Mode::FILE) otherwise.
+    /// Leading directories aren't created beforehand, and have to be created as needed.
+    fn write_file(
+        &mut self,
+        rela_path: &BStr,
+        mode: gix_index::entry::Mode,
+        content: &mut dyn std::io::Read,
+    ) -> std::io::Result<u64>;
+
+    /// Create a symbolic link at `rela_path` which points to `target`.
+    ///
+    /// This is only called if [symlinks are supported](gix_fs::Capabilities::symlink), otherwise symlinks are
+    /// [written as files](Self::write_file()) that contain their target.
+    fn write_symlink(&mut self, rela_path: &BStr, target: &BStr) -> std::io::Result<()>;
+
+    /// Create the directory at `rela_path`, which is where a submodule would be checked out.
+    ///
+    /// Does nothing by default.
+    fn create_directory(&mut self, rela_path: &BStr) -> std::io::Result<()> {
+        _ = rela_path;
+        Ok(())
+    }
+}
+
 mod chunk;
 mod entry;
 pub(crate) mod function;
@@ -8,7 +8,10 @@ use std::{
 };
 
 use gix_features::progress;
-use gix_object::{bstr::ByteSlice, Data};
+use gix_object::{
+    bstr::{BStr, BString, ByteSlice},
+    Data,
+};
 use gix_testtools::tempfile::TempDir;
 use gix_worktree_state::checkout::Collision;
 use once_cell::sync::Lazy;
@@ -255,6 +258,132 @@ fn overwriting_files_and_lone_directories_works() -> crate::Result {
     Ok(())
 }
 
+#[derive(Default)]
+struct Files(std::collections::BTreeMap<BString, (gix_index::entry::Mode, BString)>);
+
+impl gix_worktree_state::checkout::Sink for Files {
+    fn write_file(
+        &mut self,
+        rela_path: &BStr,
+        mode: gix_index::entry::Mode,
+        content: &mut dyn std::io::Read,
+    ) -> std::io::Result<u64> {
+        let mut buf = Vec::new();
+        content.read_to_end(&mut buf)?;
+        let len = buf.len() as u64;
+        self.0.insert(rela_path.to_owned(), (mode, buf.into()));
+        Ok(len)
+    }
+
+    fn write_symlink(&mut self, rela_path: &BStr, target: &BStr) -> std::io::Result<()> {
+        self.0.insert(
+            rela_path.to_owned(),
+            (gix_index::entry::Mode::SYMLINK, target.to_owned()),
+        );
+        Ok(())
+    }
+}
+
+fn checkout_index_into_files(
+    opts: gix_worktree_state::checkout::Options,
+    script_name: &str,
+    subdir_name: Option<&str>,
+) -> crate::Result<(Files, gix_worktree_state::checkout::Outcome)> {
+    let mut source_tree = fixture_path(script_name);
+    if let Some(name) = subdir_name {
+        source_tree.push(name);
+    }
+    let git_dir = source_tree.join(".git");
+    let index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let odb = gix_odb::at(git_dir.join("objects"))?;
+    let mut files = Files::default();
+    let outcome = gix_worktree_state::checkout_into(
+        &index,
+        &mut files,
+        odb,
+        &progress::Discard,
+        &progress::Discard,
+        &AtomicBool::default(),
+        opts,
+    )?;
+    Ok((files, outcome))
+}
+
+#[test]
+fn checkout_into_sink_with_delayed_driver_process() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.fs.executable_bit = true;
+    opts.filter_process_delay = gix_filter::driver::apply::Delay::Allow;
+    setup_filter_pipeline(opts.filters.options_mut());
+    let (files, outcome) = checkout_index_into_files(opts, "make_mixed_without_submodules_and_symlinks", None)?;
+    assert_eq!(outcome.errors.len(), 0);
+    assert_eq!(outcome.files_updated, 5);
+
+    use gix_index::entry::Mode;
+    let file = |path: &str| {
+        files
+            .0
+            .get(path.as_bytes().as_bstr())
+            .map(|(mode, data)| (*mode, data.as_bstr()))
+    };
+    assert_eq!(file(".gitattributes").map(|t| t.0), Some(Mode::FILE));
+    assert_eq!(
+        file("executable"),
+        Some((Mode::FILE_EXECUTABLE, "content".into())),
+        "unfiltered"
+    );
+    assert_eq!(file("dir/content"), Some((Mode::FILE, "➡other content\r\n".into())));
+    assert_eq!(
+        file("dir/sub-dir/file"),
+        Some((Mode::FILE, "➡even other content\r\n".into())),
+        "delayed files are written as well"
+    );
+    assert_eq!(
+        outcome.bytes_written,
+        files.0.values().map(|(_, data)| data.len() as u64).sum::<u64>()
+    );
+    Ok(())
+}
+
+#[test]
+fn checkout_into_sink_writes_symlinks_or_their_targets() -> crate::Result {
+    let mut opts = opts_from_probe();
+    for symlink in [false, true] {
+        opts.fs.symlink = symlink;
+        let (files, _outcome) = checkout_index_into_files(opts.clone(), "make_mixed_without_submodules", None)?;
+        assert_eq!(
+            files.0.get(b"dir/sub-dir/symlink".as_bstr()),
+            Some(&(
+                if symlink {
+                    gix_index::entry::Mode::SYMLINK
+                } else {
+                    gix_index::entry::Mode::FILE
+                },
+                "../content".into()
+            ))
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn checkout_into_sink_validates_paths() -> crate::Result {
+    let mut opts = opts_from_probe();
+    let err = checkout_index_into_files(opts.clone(), "make_traverse_trees", Some("traverse_dotgit_trees"))
+        .err()
+        .expect("invalid paths are rejected");
+    assert_eq!(
+        err.source().expect("inner").to_string(),
+        "The .git name may never be used"
+    );
+
+    opts.keep_going = true;
+    let (files, outcome) = checkout_index_into_files(opts, "make_traverse_trees", Some("traverse_dotgit_trees"))?;
+    assert_eq!(outcome.errors.len(), 1, "one path could not be checked out");
+    assert!(files.0.is_empty());
+    Ok(())
+}
+
 #[test]
 fn symlinks_become_files_if_disabled() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -31,7 +31,7 @@ use crate::{entry, entry::Error, protocol, AdditionalEntry, SharedErrorSlot, Str
 ///
 /// ### Limitations
 ///
-/// * `export-subst` is not support, as it requires the entire formatting engine of `git log`.
+/// * `export-subst` is not supported, use [`from_tree_with_export_subst()`] for that.
 pub fn from_tree<Find, E>(
     tree: gix_hash::ObjectId,
     objects: Find,
@@ -40,6 +40,42 @@ pub fn from_tree<Find, E>(
         + Send
         + 'static,
 ) -> Stream
+where
+    Find: gix_object::Find + Clone + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    spawn(tree, objects, pipeline, attributes, None)
+}
+
+/// Like [`from_tree()`], but replace `$Format:<format>$` placeholders in blobs with the `export-subst` attribute set
+/// with what `substitute(format, out)` appends to `out`, similar to what `git archive` does when archiving a commit.
+///
+/// Placeholders are replaced before the blob is converted to its worktree representation.
+pub fn from_tree_with_export_subst<Find, E>(
+    tree: gix_hash::ObjectId,
+    objects: Find,
+    pipeline: gix_filter::Pipeline,
+    attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
+        + Send
+        + 'static,
+    substitute: impl FnMut(&BStr, &mut Vec<u8>) + Send + 'static,
+) -> Stream
+where
+    Find: gix_object::Find + Clone + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    spawn(tree, objects, pipeline, attributes, Some(Box::new(substitute)))
+}
+
+fn spawn<Find, E>(
+    tree: gix_hash::ObjectId,
+    objects: Find,
+    pipeline: gix_filter::Pipeline,
+    attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
+        + Send
+        + 'static,
+    substitute: Option<Box<traverse::SubstituteFn>>,
+) -> Stream
 where
     Find: gix_object::Find + Clone + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
@@ -53,6 +89,7 @@ where
                 objects,
                 pipeline,
                 attributes,
+                substitute,
                 &mut write,
                 slot.clone(),
                 additional_entries,
@@ -75,6 +112,7 @@ where
     stream
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run<Find, E>(
     tree: gix_hash::ObjectId,
     objects: Find,
@@ -82,6 +120,7 @@ fn run<Find, E>(
     mut attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
         + Send
         + 'static,
+    substitute: Option<Box<traverse::SubstituteFn>>,
     out: &mut gix_features::io::pipe::Writer,
     err: SharedErrorSlot,
     additional_entries: std::sync::mpsc::Receiver<AdditionalEntry>,
@@ -97,7 +136,7 @@ where
     }
 
     let mut attrs = gix_attributes::search::Outcome::default();
-    attrs.initialize_with_selection(&Default::default(), Some("export-ignore"));
+    attrs.initialize_with_selection(&Default::default(), ["export-ignore", "export-subst"]);
     let mut dlg = traverse::Delegate {
         out,
         err,
@@ -113,6 +152,8 @@ where
         path_deque: Default::default(),
         path: Default::default(),
         buf: Vec::with_capacity(1024),
+        substitute,
+        subst_buf: Vec::new(),
     };
     gix_traverse::tree::breadthfirst(
         tree_iter,
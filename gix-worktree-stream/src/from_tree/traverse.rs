@@ -9,6 +9,9 @@ use gix_traverse::tree::{visit::Action, Visit};
 
 use crate::{entry::Error, protocol, SharedErrorSlot};
 
+/// A function to append the expansion of the format of an `export-subst` placeholder to the given buffer.
+pub(crate) type SubstituteFn = dyn FnMut(&BStr, &mut Vec<u8>) + Send;
+
 pub struct Delegate<'a, AttributesFn, Find>
 where
     Find: gix_object::Find,
@@ -22,6 +25,8 @@ where
     pub(crate) fetch_attributes: AttributesFn,
     pub(crate) objects: Find,
     pub(crate) buf: Vec<u8>,
+    pub(crate) substitute: Option<Box<SubstituteFn>>,
+    pub(crate) subst_buf: Vec<u8>,
 }
 
 impl<AttributesFn, Find> Delegate<'_, AttributesFn, Find>
@@ -49,11 +54,46 @@ where
         self.attrs
             .iter_selected()
             .next()
-            .expect("initialized with one attr")
+            .expect("initialized with two attrs")
+            .assignment
+            .state
+    }
+
+    /// Return the state of the `export-subst` attribute.
+    fn subst_state(&self) -> gix_attributes::StateRef<'_> {
+        self.attrs
+            .iter_selected()
+            .nth(1)
+            .expect("initialized with two attrs")
             .assignment
             .state
     }
 
+    /// Replace all `$Format:<format>$` placeholders in our buffer with what the substitution function produces for `<format>`,
+    /// like `git archive` does.
+    fn substitute_placeholders(&mut self) {
+        let Some(substitute) = self.substitute.as_mut() else {
+            return;
+        };
+        const START: &[u8] = b"$Format:";
+        let mut src = self.buf.as_slice();
+        self.subst_buf.clear();
+        while let Some(start) = src.find(START) {
+            let Some(end) = src[start + START.len()..].find_byte(b'$') else {
+                break;
+            };
+            let format = &src[start + START.len()..][..end];
+            self.subst_buf.extend_from_slice(&src[..start]);
+            substitute(format.as_bstr(), &mut self.subst_buf);
+            src = &src[start + START.len() + end + 1..];
+        }
+        if src.len() == self.buf.len() {
+            return;
+        }
+        self.subst_buf.extend_from_slice(src);
+        std::mem::swap(&mut self.buf, &mut self.subst_buf);
+    }
+
     fn handle_entry(&mut self, entry: &tree::EntryRef<'_>) -> Result<Action, Error> {
         if !entry.mode.is_blob_or_symlink() {
             return Ok(Action::Continue);
@@ -63,6 +103,9 @@ where
             return Ok(Action::Continue);
         }
         self.objects.find(entry.oid, &mut self.buf)?;
+        if self.subst_state().is_set() {
+            self.substitute_placeholders();
+        }
 
         self.pipeline.driver_context_mut().blob = Some(entry.oid.into());
         let converted = self.pipeline.convert_to_worktree(
@@ -53,7 +53,7 @@ pub struct Stack {
     /// If case folding should happen when looking up attributes or exclusions.
     case: gix_glob::pattern::Case,
     /// A lookup table for object ids to read from in some situations when looking up attributes or exclusions.
-    id_mappings: Vec<PathIdMapping>,
+    id_mappings: stack::state::IdMappings,
     statistics: stack::Statistics,
 }
 
@@ -1,5 +1,5 @@
 use crate::stack::mode_is_dir;
-use crate::{stack::State, PathIdMapping};
+use crate::stack::{state::IdMappings, State};
 
 /// Various aggregate numbers related to the stack delegate itself.
 #[derive(Default, Clone, Copy, Debug)]
@@ -22,7 +22,7 @@ pub(crate) struct StackDelegate<'a, 'find> {
     pub buf: &'a mut Vec<u8>,
     #[cfg_attr(not(feature = "attributes"), allow(dead_code))]
     pub mode: Option<gix_index::entry::Mode>,
-    pub id_mappings: &'a Vec<PathIdMapping>,
+    pub id_mappings: &'a IdMappings,
     pub objects: &'find dyn gix_object::Find,
     pub case: gix_glob::pattern::Case,
     pub statistics: &'a mut super::Statistics,
@@ -43,6 +43,7 @@ impl<'a, 'find> gix_fs::stack::Delegate for StackDelegate<'a, 'find> {
                     self.buf,
                     self.id_mappings,
                     self.objects,
+                    self.case,
                     &mut self.statistics.attributes,
                 )?;
             }
@@ -55,6 +56,7 @@ impl<'a, 'find> gix_fs::stack::Delegate for StackDelegate<'a, 'find> {
                     self.buf,
                     self.id_mappings,
                     self.objects,
+                    self.case,
                     &mut self.statistics.attributes,
                 )?;
                 ignore.push_directory(
@@ -171,7 +173,7 @@ fn create_leading_directory(
         return Ok(());
     }
     *mkdir_calls += 1;
-    match std::fs::create_dir(stack.current()) {
+    match std::fs::create_dir(gix_path::to_long_path_on_windows(stack.current())) {
         Ok(()) => Ok(()),
         Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => {
             let meta = stack.current().symlink_metadata()?;
@@ -184,7 +186,7 @@ fn create_leading_directory(
                     std::fs::remove_file(stack.current())?;
                 }
                 *mkdir_calls += 1;
-                std::fs::create_dir(stack.current())
+                std::fs::create_dir(gix_path::to_long_path_on_windows(stack.current()))
             } else {
                 Err(err)
             }
@@ -74,7 +74,7 @@ impl Stack {
             state,
             case,
             buf,
-            id_mappings,
+            id_mappings: state::IdMappings::new(id_mappings, case),
             statistics: Statistics::default(),
         }
     }
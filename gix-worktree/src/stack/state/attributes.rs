@@ -1,12 +1,12 @@
 use std::path::{Path, PathBuf};
 
-use bstr::{BStr, ByteSlice};
+use bstr::BStr;
 use gix_glob::pattern::Case;
 use gix_object::FindExt;
 
 use crate::{
-    stack::state::{AttributeMatchGroup, Attributes},
-    PathIdMapping, Stack,
+    stack::state::{AttributeMatchGroup, Attributes, IdMappings},
+    Stack,
 };
 
 /// Various aggregate numbers related [`Attributes`].
@@ -94,12 +94,13 @@ impl Attributes {
         dir: &Path,
         rela_dir: &BStr,
         buf: &mut Vec<u8>,
-        id_mappings: &[PathIdMapping],
+        id_mappings: &IdMappings,
         objects: &dyn gix_object::Find,
+        case: Case,
         stats: &mut Statistics,
     ) -> std::io::Result<()> {
         let attr_path_relative = gix_path::join_bstr_unix_pathsep(rela_dir, ".gitattributes");
-        let attr_file_in_index = id_mappings.binary_search_by(|t| t.0.as_bstr().cmp(attr_path_relative.as_ref()));
+        let attr_file_in_index = id_mappings.find(attr_path_relative.as_ref(), case);
         // Git does not follow symbolic links as per documentation.
         let no_follow_symlinks = false;
         let read_macros_as_dir_is_root = root == dir;
@@ -107,9 +108,9 @@ impl Attributes {
         let mut added = false;
         match self.source {
             Source::IdMapping | Source::IdMappingThenWorktree => {
-                if let Ok(idx) = attr_file_in_index {
+                if let Some(id) = attr_file_in_index {
                     let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
+                        .find_blob(id, buf)
                         .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                     let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
                     self.stack.add_patterns_buffer(
@@ -146,9 +147,9 @@ impl Attributes {
                 )?;
                 stats.pattern_files += usize::from(added);
                 stats.tried_pattern_files += 1;
-                if let Some(idx) = attr_file_in_index.ok().filter(|_| !added) {
+                if let Some(id) = attr_file_in_index.filter(|_| !added) {
                     let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
+                        .find_blob(id, buf)
                         .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                     let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
                     self.stack.add_patterns_buffer(
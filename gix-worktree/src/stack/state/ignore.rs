@@ -4,10 +4,7 @@ use bstr::{BStr, ByteSlice};
 use gix_glob::pattern::Case;
 use gix_object::FindExt;
 
-use crate::{
-    stack::state::{Ignore, IgnoreMatchGroup},
-    PathIdMapping,
-};
+use crate::stack::state::{IdMappings, Ignore, IgnoreMatchGroup};
 
 /// Decide where to read `.gitignore` files from.
 #[derive(Default, Debug, Clone, Copy)]
@@ -164,7 +161,7 @@ impl Ignore {
         dir: &Path,
         rela_dir: &BStr,
         buf: &mut Vec<u8>,
-        id_mappings: &[PathIdMapping],
+        id_mappings: &IdMappings,
         objects: &dyn gix_object::Find,
         case: Case,
         stats: &mut Statistics,
@@ -173,27 +170,27 @@ impl Ignore {
             .push(self.matching_exclude_pattern_no_dir(rela_dir, Some(true), case));
 
         let ignore_path_relative = gix_path::join_bstr_unix_pathsep(rela_dir, ".gitignore");
-        let ignore_file_in_index = id_mappings.binary_search_by(|t| t.0.as_bstr().cmp(ignore_path_relative.as_ref()));
+        let ignore_file_in_index = id_mappings.find(ignore_path_relative.as_ref(), case);
         match self.source {
             Source::IdMapping => {
                 match ignore_file_in_index {
-                    Ok(idx) => {
+                    Some(id) => {
                         let ignore_blob = objects
-                            .find_blob(&id_mappings[idx].1, buf)
+                            .find_blob(id, buf)
                             .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                         let ignore_path = gix_path::from_bstring(ignore_path_relative.into_owned());
                         self.stack
                             .add_patterns_buffer(ignore_blob.data, ignore_path, Some(Path::new("")));
                         stats.patterns_buffers += 1;
                     }
-                    Err(_) => {
+                    None => {
                         // Need one stack level per component so push and pop matches.
                         self.stack.patterns.push(Default::default())
                     }
                 }
             }
             Source::WorktreeThenIdMappingIfNotSkipped => {
-                let follow_symlinks = ignore_file_in_index.is_err();
+                let follow_symlinks = ignore_file_in_index.is_none();
                 let added = gix_glob::search::add_patterns_file(
                     &mut self.stack.patterns,
                     dir.join(".gitignore"),
@@ -205,16 +202,16 @@ impl Ignore {
                 stats.tried_pattern_files += 1;
                 if !added {
                     match ignore_file_in_index {
-                        Ok(idx) => {
+                        Some(id) => {
                             let ignore_blob = objects
-                                .find_blob(&id_mappings[idx].1, buf)
+                                .find_blob(id, buf)
                                 .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
                             let ignore_path = gix_path::from_bstring(ignore_path_relative.into_owned());
                             self.stack
                                 .add_patterns_buffer(ignore_blob.data, ignore_path, Some(Path::new("")));
                             stats.patterns_buffers += 1;
                         }
-                        Err(_) => {
+                        None => {
                             // Need one stack level per component so push and pop matches.
                             self.stack.patterns.push(Default::default())
                         }
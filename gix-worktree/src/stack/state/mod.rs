@@ -99,6 +99,78 @@ impl State {
         paths: &gix_index::PathStorageRef,
         case: Case,
     ) -> Vec<PathIdMapping> {
+        index
+            .entries()
+            .iter()
+            .filter_map(move |entry| {
+                let path = entry.path_in(paths);
+
+                // Stage 0 means there is no merge going on, stage 2 means it's 'our' side of the merge, but then
+                // there won't be a stage 0.
+                if entry.mode == gix_index::entry::Mode::FILE && (entry.stage_raw() == 0 || entry.stage_raw() == 2) {
+                    let basename = path.rfind_byte(b'/').map_or(path, |pos| path[pos + 1..].as_bstr());
+                    let ignore_source = self.id_mapping_kind(basename, case)?;
+                    if let Some(source) = ignore_source {
+                        match source {
+                            ignore::Source::IdMapping => {}
+                            ignore::Source::WorktreeThenIdMappingIfNotSkipped => {
+                                // See https://github.com/git/git/blob/master/dir.c#L912:L912
+                                if !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) {
+                                    return None;
+                                }
+                            }
+                        };
+                    }
+                    Some((path.to_owned(), entry.id))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Like [`id_mappings_from_index()`](Self::id_mappings_from_index()), but obtains the *ignore* or *attribute* files
+    /// from the tree with `tree_id` and all of its subtrees, which are read from `objects`.
+    /// This is useful in bare repositories, which should use the attributes of the tree they operate on,
+    /// similar to what `git` does with `attr.tree` or `--attr-source`.
+    ///
+    /// Note that all *ignore* files are used as trees have no notion of skip-worktree entries, and that entries which
+    /// aren't blobs are ignored.
+    pub fn id_mappings_from_tree(
+        &self,
+        tree_id: &gix_hash::oid,
+        objects: &dyn gix_object::Find,
+        case: Case,
+    ) -> Result<Vec<PathIdMapping>, gix_object::find::existing_object::Error> {
+        use gix_object::FindExt;
+
+        let mut out = Vec::new();
+        let mut trees = vec![(BString::default(), tree_id.to_owned())];
+        let mut buf = Vec::new();
+        while let Some((prefix, tree_id)) = trees.pop() {
+            for entry in objects.find_tree(&tree_id, &mut buf)?.entries {
+                let mut path = prefix.clone();
+                if !path.is_empty() {
+                    path.push(b'/');
+                }
+                path.extend_from_slice(entry.filename);
+                if entry.mode.is_tree() {
+                    trees.push((path, entry.oid.to_owned()));
+                } else if entry.mode.kind() == gix_object::tree::EntryKind::Blob
+                    && self.id_mapping_kind(entry.filename, case).is_some()
+                {
+                    out.push((path, entry.oid.to_owned()));
+                }
+            }
+        }
+        // Lookups rely on the index order, which sorts by path.
+        out.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(out)
+    }
+
+    /// Return `Some(ignore_source)` if a file with `basename` should be read from the object database,
+    /// with `ignore_source` being set if it's an *ignore* file.
+    fn id_mapping_kind(&self, basename: &bstr::BStr, case: Case) -> Option<Option<ignore::Source>> {
         let a1_backing;
         #[cfg(feature = "attributes")]
         let a2_backing;
@@ -127,41 +199,13 @@ impl State {
                 a1_backing.as_ref()
             }
         };
-
-        index
-            .entries()
-            .iter()
-            .filter_map(move |entry| {
-                let path = entry.path_in(paths);
-
-                // Stage 0 means there is no merge going on, stage 2 means it's 'our' side of the merge, but then
-                // there won't be a stage 0.
-                if entry.mode == gix_index::entry::Mode::FILE && (entry.stage_raw() == 0 || entry.stage_raw() == 2) {
-                    let basename = path.rfind_byte(b'/').map_or(path, |pos| path[pos + 1..].as_bstr());
-                    let ignore_source = names.iter().find_map(|t| {
-                        match case {
-                            Case::Sensitive => basename == t.0,
-                            Case::Fold => basename.eq_ignore_ascii_case(t.0),
-                        }
-                        .then_some(t.1)
-                    })?;
-                    if let Some(source) = ignore_source {
-                        match source {
-                            ignore::Source::IdMapping => {}
-                            ignore::Source::WorktreeThenIdMappingIfNotSkipped => {
-                                // See https://github.com/git/git/blob/master/dir.c#L912:L912
-                                if !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) {
-                                    return None;
-                                }
-                            }
-                        };
-                    }
-                    Some((path.to_owned(), entry.id))
-                } else {
-                    None
-                }
-            })
-            .collect()
+        names.iter().find_map(|t| {
+            match case {
+                Case::Sensitive => basename == t.0,
+                Case::Fold => basename.eq_ignore_ascii_case(t.0),
+            }
+            .then_some(t.1)
+        })
     }
 
     pub(crate) fn ignore_or_panic(&self) -> &Ignore {
@@ -188,3 +232,53 @@ impl State {
         }
     }
 }
+
+/// The id mappings of a [`Stack`](crate::Stack), which can be looked up by path.
+#[derive(Default, Clone)]
+pub(crate) struct IdMappings {
+    /// The mappings, sorted by path.
+    by_path: Vec<PathIdMapping>,
+    /// The ASCII-lowercased paths of all mappings along with their position in `by_path`, sorted by lowercased path.
+    /// It's only filled if paths are looked up case-insensitively.
+    folded: Vec<(BString, usize)>,
+}
+
+impl IdMappings {
+    /// Create a new instance from `by_path`, which is sorted by path, to be looked up with `case`.
+    pub(crate) fn new(by_path: Vec<PathIdMapping>, case: Case) -> Self {
+        let folded = match case {
+            Case::Sensitive => Vec::new(),
+            Case::Fold => {
+                let mut folded: Vec<_> = by_path
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (path, _))| (path.to_ascii_lowercase().into(), idx))
+                    .collect();
+                folded.sort();
+                folded
+            }
+        };
+        IdMappings { by_path, folded }
+    }
+
+    /// Return the id of the mapping for `path`, comparing ASCII characters case-insensitively if `case` is [`Case::Fold`],
+    /// just like a case-insensitive filesystem would.
+    pub(crate) fn find(&self, path: &bstr::BStr, case: Case) -> Option<&gix_hash::oid> {
+        let idx = self
+            .by_path
+            .binary_search_by(|t| t.0.as_bstr().cmp(path))
+            .ok()
+            .or_else(|| match case {
+                Case::Sensitive => None,
+                Case::Fold => {
+                    let path = path.to_ascii_lowercase();
+                    let pos = self.folded.partition_point(|t| t.0.as_slice() < path.as_slice());
+                    self.folded
+                        .get(pos)
+                        .filter(|t| t.0.as_slice() == path.as_slice())
+                        .map(|t| t.1)
+                }
+            })?;
+        Some(&self.by_path[idx].1)
+    }
+}
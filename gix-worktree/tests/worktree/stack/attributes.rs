@@ -73,6 +73,44 @@ fn baseline() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn attribute_files_are_read_once_per_directory_while_it_is_on_the_stack() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_attributes_baseline.sh")?;
+    let base = dir.join("basics");
+
+    let mut buf = Vec::new();
+    let mut collection = gix_attributes::search::MetadataCollection::default();
+    let state = gix_worktree::stack::State::AttributesStack(state::Attributes::new(
+        gix_attributes::Search::new_globals([base.join("user.attributes")], &mut buf, &mut collection)?,
+        Some(base.join(".git").join("info").join("attributes")),
+        gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+        collection,
+    ));
+    let mut cache = gix_worktree::Stack::new(&base, state, probe_case()?, buf, vec![]);
+
+    let mut tried_pattern_files = |rela_path: &str| -> crate::Result<usize> {
+        let _platform = cache.at_entry(rela_path, Some(gix_index::entry::Mode::FILE), &gix_object::find::Never)?;
+        Ok(cache.statistics().attributes.tried_pattern_files)
+    };
+    assert_eq!(
+        tried_pattern_files("a/b/h")?,
+        4,
+        "the root, `info/attributes`, `a` and `a/b` are tried once each"
+    );
+    assert_eq!(
+        tried_pattern_files("a/b/g")?,
+        4,
+        "files in the same directory reuse the parsed pattern lists"
+    );
+    assert_eq!(tried_pattern_files("a/b/d/yes")?, 5, "only the new directory is read");
+    assert_eq!(
+        tried_pattern_files("a/c/x")?,
+        6,
+        "leaving a directory pops its pattern list, and only the sibling directory is read"
+    );
+    Ok(())
+}
+
 fn assert_references(out: &Outcome) {
     for m in out.iter() {
         if let Some(source) = m.kind.source_id() {
@@ -0,0 +1,403 @@
+//! Split mailboxes and parse the mails in them into the parts of a commit, similar to `git mailsplit` and `git mailinfo`.
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// Options for use with [`parse()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, everything in the body before a scissors line like `-- >8 --` is discarded,
+    /// similar to `git mailinfo --scissors`.
+    pub scissors: bool,
+}
+
+/// A patch received by mail, split into the parts that make up a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mail {
+    /// The author as found in the `From` header, or in a `From:` line at the beginning of the body.
+    pub author: gix_actor::Identity,
+    /// The time of authorship as found in the `Date` header, or `None` if there is none or if it couldn't be parsed.
+    pub date: Option<gix_date::Time>,
+    /// The subject with prefixes like `Re:` and `[PATCH 1/2]` removed.
+    pub subject: BString,
+    /// The text between the headers and the patch without surrounding empty lines, ending with a newline unless it is empty.
+    pub body: BString,
+    /// Everything from the first line that looks like the start of a patch, which typically is the `---` separator line,
+    /// suitable for [`diff::patch::parse()`](crate::diff::patch::parse()). It's empty if there is no patch.
+    pub patch: BString,
+}
+
+impl Mail {
+    /// Return the commit message, which is the subject followed by the body.
+    pub fn message(&self) -> BString {
+        let mut message = self.subject.clone();
+        message.push(b'\n');
+        if !self.body.is_empty() {
+            message.push(b'\n');
+            message.push_str(&self.body);
+        }
+        message
+    }
+}
+
+/// The error returned by [`parse()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The mail has no valid author, found {from:?}")]
+    InvalidAuthor { from: BString },
+    #[error("The body of the mail isn't valid base64")]
+    Base64,
+}
+
+/// Split `mbox` into individual mails at lines starting with `From `, similar to `git mailsplit`.
+///
+/// If `mbox` doesn't start with such a line, it's considered to be a single mail.
+pub fn split(mbox: &[u8]) -> Vec<&[u8]> {
+    if mbox.trim().is_empty() {
+        return Vec::new();
+    }
+    let mut mails = Vec::new();
+    let (mut start, mut pos) = (0, 0);
+    for line in mbox.lines_with_terminator() {
+        if pos != start && is_mbox_separator(line) {
+            mails.push(&mbox[start..pos]);
+            start = pos;
+        }
+        pos += line.len();
+    }
+    mails.push(&mbox[start..]);
+    mails
+}
+
+/// Parse `mail` into its parts, decoding its body and headers as needed, similar to `git mailinfo`.
+///
+/// Headers of the form `From:`, `Subject:` and `Date:` at the beginning of the body override those of the mail, and
+/// a leading mbox separator line is skipped.
+///
+/// ### Deviation
+///
+/// * Multipart messages aren't supported, and all text is assumed to be UTF-8 unless it's declared to be ISO-8859-1 in
+///   an encoded header.
+pub fn parse(mail: &[u8], options: Options) -> Result<Mail, Error> {
+    let mut pos = 0;
+    let mut headers = Vec::<(BString, BString)>::new();
+    for line in mail.lines_with_terminator() {
+        pos += line.len();
+        if pos == line.len() && is_mbox_separator(line) {
+            continue;
+        }
+        let line = line.trim_end_with(|c| c == '\n' || c == '\r');
+        if line.is_empty() {
+            break;
+        }
+        match headers.last_mut() {
+            Some((_, value)) if line[0] == b' ' || line[0] == b'\t' => value.push_str(line),
+            _ => {
+                if let Some((name, value)) = line.split_once_str(":") {
+                    headers.push((name.trim().to_ascii_lowercase().into(), value.trim().into()));
+                }
+            }
+        }
+    }
+    let header = |name: &str| {
+        headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| value.as_bstr())
+    };
+
+    let content = &mail[pos.min(mail.len())..];
+    let content = match header("content-transfer-encoding").map(|encoding| encoding.to_ascii_lowercase()) {
+        Some(encoding) if encoding == b"base64" => decode_base64(content).ok_or(Error::Base64)?,
+        Some(encoding) if encoding == b"quoted-printable" => decode_quoted_printable(content, false),
+        _ => content.to_owned(),
+    };
+    let content = content.replace("\r\n", "\n");
+
+    let mail_headers = (
+        header("from").map(decode_header),
+        header("subject").map(decode_header).unwrap_or_default(),
+        header("date").map(ToOwned::to_owned),
+    );
+    let (mut from, mut subject, mut date) = mail_headers.clone();
+    #[derive(PartialEq)]
+    enum InBodyHeaders {
+        Possible,
+        Reading,
+        Done,
+    }
+    let mut in_body_headers = InBodyHeaders::Possible;
+    let mut message = Vec::<&[u8]>::new();
+    let mut patch_start = content.len();
+    let mut pos = 0;
+    for line in content.lines_with_terminator() {
+        if is_patch_start(line) {
+            patch_start = pos;
+            break;
+        }
+        pos += line.len();
+        if options.scissors && is_scissors_line(line) {
+            message.clear();
+            (from, subject, date) = mail_headers.clone();
+            in_body_headers = InBodyHeaders::Possible;
+            continue;
+        }
+        if in_body_headers != InBodyHeaders::Done {
+            let content = line.trim_end();
+            if let Some((name, value)) = content
+                .split_once_str(": ")
+                .filter(|(name, _)| matches!(*name, b"From" | b"Subject" | b"Date"))
+            {
+                let value = decode_header(value.as_bstr());
+                match name {
+                    b"From" => from = Some(value),
+                    b"Subject" => subject = value,
+                    _ => date = Some(value),
+                }
+                in_body_headers = InBodyHeaders::Reading;
+                continue;
+            }
+            if content.is_empty() {
+                if in_body_headers == InBodyHeaders::Reading {
+                    in_body_headers = InBodyHeaders::Done;
+                }
+                continue;
+            }
+            in_body_headers = InBodyHeaders::Done;
+        }
+        message.push(line);
+    }
+
+    let from = from.unwrap_or_default();
+    let author = parse_author(from.as_bstr()).ok_or(Error::InvalidAuthor { from })?;
+    let mut body: BString = message.concat().trim_end().into();
+    if !body.is_empty() {
+        body.push(b'\n');
+    }
+    Ok(Mail {
+        author,
+        date: date.and_then(|date| gix_date::parse(date.to_str().ok()?.trim(), None).ok()),
+        subject: clean_subject(subject.as_bstr()),
+        body,
+        patch: content[patch_start..].into(),
+    })
+}
+
+fn is_mbox_separator(line: &[u8]) -> bool {
+    line.starts_with(b"From ") && line.contains_str(":")
+}
+
+/// Return `true` if `line` starts a patch, like `git mailinfo` determines it.
+fn is_patch_start(line: &[u8]) -> bool {
+    line.starts_with(b"diff -")
+        || line.starts_with(b"Index: ")
+        || matches!(line.strip_prefix(b"---"), Some(rest) if matches!(rest.first(), None | Some(b' ' | b'\t' | b'\r' | b'\n')))
+}
+
+/// Return `true` if `line` is a scissors line like `-- >8 --`, using the same heuristic as `git mailinfo`.
+fn is_scissors_line(line: &[u8]) -> bool {
+    let (mut scissors, mut gap, mut perforation) = (0, 0, 0);
+    let (mut first_nonblank, mut last_nonblank) = (None, None);
+    let mut in_perforation = false;
+    let mut idx = 0;
+    while idx < line.len() {
+        let c = line[idx];
+        if c.is_ascii_whitespace() {
+            if in_perforation {
+                perforation += 1;
+                gap += 1;
+            }
+            idx += 1;
+            continue;
+        }
+        last_nonblank = Some(idx);
+        first_nonblank.get_or_insert(idx);
+        if c == b'-' {
+            in_perforation = true;
+            perforation += 1;
+        } else if [b">8", b"8<", b">%", b"%<"]
+            .iter()
+            .any(|mark| line[idx..].starts_with(*mark))
+        {
+            in_perforation = true;
+            perforation += 2;
+            scissors += 2;
+            idx += 1;
+        } else {
+            in_perforation = false;
+        }
+        idx += 1;
+    }
+    let visible = match (first_nonblank, last_nonblank) {
+        (Some(first), Some(last)) => last - first + 1,
+        _ => 0,
+    };
+    scissors != 0 && 8 <= visible && visible < perforation * 3 && gap * 2 < perforation
+}
+
+/// Remove prefixes like `Re:` and `[PATCH v2 1/3]` from `subject` and collapse runs of whitespace, like `git mailinfo`.
+fn clean_subject(subject: &BStr) -> BString {
+    let mut subject = subject.trim();
+    loop {
+        if subject.len() >= 3 && subject[..3].eq_ignore_ascii_case(b"re:") {
+            subject = subject[3..].trim_start();
+        } else if let Some(end) = subject.strip_prefix(b"[").and_then(|rest| rest.find_byte(b']')) {
+            subject = subject[end + 2..].trim_start();
+        } else {
+            break;
+        }
+    }
+    let mut out = BString::default();
+    for word in subject.fields() {
+        if !out.is_empty() {
+            out.push(b' ');
+        }
+        out.push_str(word);
+    }
+    out
+}
+
+/// Parse an address like `Name <email>`, `"Name" <email>`, `email (Name)` or just `email`.
+fn parse_author(from: &BStr) -> Option<gix_actor::Identity> {
+    let from = from.trim();
+    let (name, email) = match from.rfind_byte(b'<') {
+        Some(open) => {
+            let close = open + from[open..].find_byte(b'>')?;
+            (from[..open].trim(), from[open + 1..close].trim())
+        }
+        None => match from.find_byte(b'(') {
+            Some(open) => {
+                let close = open + from[open..].find_byte(b')')?;
+                (from[open + 1..close].trim(), from[..open].trim())
+            }
+            None => (&b""[..], from),
+        },
+    };
+    if email.is_empty() || email.contains(&b' ') {
+        return None;
+    }
+    let name = match name.strip_prefix(b"\"").and_then(|name| name.strip_suffix(b"\"")) {
+        Some(quoted) => quoted.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => name.to_owned(),
+    };
+    Some(gix_actor::Identity {
+        name: if name.is_empty() { email.into() } else { name.into() },
+        email: email.into(),
+    })
+}
+
+/// Decode all RFC 2047 encoded words like `=?UTF-8?q?caf=C3=A9?=` in `value`, dropping whitespace between them.
+fn decode_header(value: &BStr) -> BString {
+    let mut out = BString::default();
+    let mut rest: &[u8] = value;
+    let mut last_was_encoded = false;
+    while let Some(start) = rest.find("=?") {
+        let (before, candidate) = rest.split_at(start);
+        match decode_word(candidate) {
+            Some((decoded, consumed)) => {
+                if !(last_was_encoded && before.trim().is_empty()) {
+                    out.push_str(before);
+                }
+                out.push_str(decoded);
+                rest = &candidate[consumed..];
+                last_was_encoded = true;
+            }
+            None => {
+                out.push_str(&rest[..start + 2]);
+                rest = &rest[start + 2..];
+                last_was_encoded = false;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Decode the encoded word at the beginning of `word` and return it along with the amount of bytes it occupied.
+fn decode_word(word: &[u8]) -> Option<(Vec<u8>, usize)> {
+    let inner = word.strip_prefix(b"=?")?;
+    let charset_end = inner.find_byte(b'?')?;
+    let charset = &inner[..charset_end];
+    let encoding = *inner.get(charset_end + 1)?;
+    if inner.get(charset_end + 2) != Some(&b'?') {
+        return None;
+    }
+    let text_start = charset_end + 3;
+    let text_end = text_start + inner[text_start..].find("?=")?;
+    let text = &inner[text_start..text_end];
+    let decoded = match encoding.to_ascii_lowercase() {
+        b'q' => decode_quoted_printable(text, true),
+        b'b' => decode_base64(text)?,
+        _ => return None,
+    };
+    let decoded = if charset.eq_ignore_ascii_case(b"iso-8859-1") || charset.eq_ignore_ascii_case(b"latin1") {
+        decoded
+            .iter()
+            .map(|byte| char::from(*byte))
+            .collect::<String>()
+            .into_bytes()
+    } else {
+        decoded
+    };
+    Some((decoded, 2 + text_end + 2))
+}
+
+fn decode_quoted_printable(input: &[u8], underscore_is_space: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut idx = 0;
+    while idx < input.len() {
+        match input[idx] {
+            b'=' => {
+                let rest = &input[idx + 1..];
+                if rest.starts_with(b"\r\n") {
+                    idx += 3;
+                } else if rest.starts_with(b"\n") {
+                    idx += 2;
+                } else if let Some(byte) = rest
+                    .get(..2)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|hex| u8::from_str_radix(hex.to_str().ok()?, 16).ok())
+                {
+                    out.push(byte);
+                    idx += 3;
+                } else {
+                    out.push(b'=');
+                    idx += 1;
+                }
+            }
+            b'_' if underscore_is_space => {
+                out.push(b' ');
+                idx += 1;
+            }
+            byte => {
+                out.push(byte);
+                idx += 1;
+            }
+        }
+    }
+    out
+}
+
+fn decode_base64(input: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len() / 4 * 3);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &byte in input {
+        let value = match byte {
+            b'A'..=b'Z' => byte - b'A',
+            b'a'..=b'z' => byte - b'a' + 26,
+            b'0'..=b'9' => byte - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            b' ' | b'\t' | b'\r' | b'\n' => continue,
+            _ => return None,
+        };
+        acc = (acc << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    Some(out)
+}
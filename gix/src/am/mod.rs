@@ -0,0 +1,118 @@
+//! Types for use with [`Repository::am()`](crate::Repository::am()) and the methods to continue, skip or abort it,
+//! along with the means to parse patches sent by mail.
+use crate::bstr::BString;
+
+pub mod mail;
+
+/// Options for use with [`Repository::am()`](crate::Repository::am()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, fall back to a three-way merge if a patch doesn't apply, similar to `git am --3way`.
+    ///
+    /// See [`apply::Options::three_way`](crate::apply::Options::three_way) for details.
+    pub three_way: bool,
+    /// If `true`, default `false`, discard everything before a scissors line like `-- >8 --` in the body of each mail,
+    /// similar to `git am --scissors`.
+    pub scissors: bool,
+}
+
+/// The reason for [`Repository::am()`](crate::Repository::am()) to stop before all patches were applied.
+#[derive(Debug)]
+pub enum Stop {
+    /// The patch couldn't be applied, and nothing was changed.
+    ///
+    /// Apply the changes by hand and add them to the index before [continuing](crate::Repository::am_continue()), or
+    /// [skip](crate::Repository::am_skip()) the patch.
+    DoesNotApply(Box<crate::apply::Error>),
+    /// The patch couldn't be parsed.
+    MalformedPatch(crate::diff::patch::parse::Error),
+    /// The patch was merged with a [three-way merge](Options::three_way), but there were conflicts.
+    ///
+    /// The index holds the unmerged stages of all conflicting paths, and the worktree their conflicting versions.
+    Conflicts(Vec<crate::merge::Conflict>),
+    /// The mail contains no patch, or its changes are already present.
+    Empty,
+}
+
+/// The outcome of [`Repository::am()`](crate::Repository::am()) and the methods to continue or skip.
+#[derive(Debug)]
+pub enum Outcome {
+    /// All patches were applied and committed.
+    Finished {
+        /// The commit `HEAD` points to now.
+        head_id: gix_hash::ObjectId,
+    },
+    /// The operation stopped at a patch, which can be resolved and committed with
+    /// [`am_continue()`](crate::Repository::am_continue()), skipped with [`am_skip()`](crate::Repository::am_skip()),
+    /// or the whole operation can be undone with [`am_abort()`](crate::Repository::am_abort()).
+    Stopped {
+        /// The one-based number of the patch in the mailbox.
+        patch: usize,
+        /// The subject of the mail that contained the patch.
+        subject: BString,
+        /// The reason for the stop.
+        reason: Stop,
+    },
+}
+
+/// The error returned by [`Repository::am()`](crate::Repository::am()) and the methods to continue, skip or abort it.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and has no worktree to apply patches to", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error("Cannot apply patches while another operation is in progress: {state:?}")]
+    InProgress { state: crate::state::InProgress },
+    #[error("No patches are being applied")]
+    NotInProgress,
+    #[error("Cannot apply patches onto an unborn branch")]
+    UnbornHead,
+    #[error("The index has changes compared to HEAD, which would be committed as well")]
+    DirtyIndex,
+    #[error("The index has unresolved conflicts which have to be resolved before continuing")]
+    Unmerged,
+    #[error("The index has no changes to commit, skip the patch if its changes are not needed")]
+    NoChanges,
+    #[error("Mail {patch} could not be parsed")]
+    Mail { patch: usize, source: mail::Error },
+    #[error("The state file at \"{}\" is invalid", path.display())]
+    InvalidState { path: std::path::PathBuf },
+    #[error("Could not read state at \"{}\"", path.display())]
+    ReadState {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not write or remove state at \"{}\"", path.display())]
+    WriteState {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    Apply(#[from] crate::apply::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    CommitTree(#[from] crate::cherry_pick::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    WriteTree(#[from] crate::repository::write_tree_from_index::Error),
+    #[error(transparent)]
+    WriteCommit(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    ParseTime(#[from] crate::config::time::Error),
+    #[error("Committer identity is not configured")]
+    CommitterMissing,
+    #[error(transparent)]
+    DecodeAuthorScript(#[from] gix_rebase::author_script::decode::Error),
+}
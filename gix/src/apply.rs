@@ -0,0 +1,104 @@
+//! Types for use with [`Repository::apply()`](crate::Repository::apply()).
+use crate::bstr::BString;
+
+/// The place to apply patches to, using [`Repository::apply()`](crate::Repository::apply()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// Apply the patches to the files in the worktree, leaving the index untouched, similar to `git apply`.
+    #[default]
+    Worktree,
+    /// Apply the patches to the index only, leaving the worktree untouched, similar to `git apply --cached`.
+    Index,
+    /// Apply the patches to the index and the worktree, which must match the index for all affected paths,
+    /// similar to `git apply --index`.
+    Both,
+}
+
+/// Options for use with [`Repository::apply()`](crate::Repository::apply()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Where to apply the patches to.
+    pub target: Target,
+    /// If `true`, default `false`, hunks that don't apply are merged in with a three-way merge using the blob the patch was
+    /// created from as common ancestor, similar to `git apply --3way`. This requires the blob to be present in the object
+    /// database.
+    ///
+    /// Conflicts are recorded as unmerged stages in the index, which is why the [target](Self::target) must include the index.
+    /// If it is [`Target::Worktree`], [`Target::Both`] is used instead.
+    pub three_way: bool,
+    /// If `true`, default `false`, apply the hunks that apply and write the remaining hunks of each file into a `<file>.rej`
+    /// file in the worktree, similar to `git apply --reject`. Otherwise, nothing is changed if a single hunk doesn't apply.
+    pub reject: bool,
+}
+
+/// The hunks of a file that couldn't be applied, as written to a `.rej` file with [`reject`](Options::reject) set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// The repository-relative path of the file the hunks were supposed to be applied to.
+    pub rela_path: BString,
+    /// The zero-based indices of the hunks in the patch of the file which couldn't be applied.
+    pub hunks: Vec<usize>,
+}
+
+/// The outcome of [`Repository::apply()`](crate::Repository::apply()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The repository-relative paths that were changed, added or removed, in the order of the patches.
+    pub paths: Vec<BString>,
+    /// All paths that couldn't be merged when falling back to a [three-way merge](Options::three_way).
+    ///
+    /// The index contains their unmerged stages, and the worktree the merged file with conflict markers.
+    pub conflicts: Vec<crate::merge::Conflict>,
+    /// The hunks that couldn't be applied, if [`reject`](Options::reject) is set.
+    pub rejected: Vec<Rejection>,
+}
+
+/// The error returned by [`Repository::apply()`](crate::Repository::apply()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and has no worktree to apply patches to", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error("\"{rela_path}\" already exists")]
+    AlreadyExists { rela_path: BString },
+    #[error("\"{rela_path}\" does not exist")]
+    DoesNotExist { rela_path: BString },
+    #[error("\"{rela_path}\" does not match the index")]
+    WorktreeDiffersFromIndex { rela_path: BString },
+    #[error("\"{rela_path}\" is not a file that a patch can be applied to")]
+    NotAFile { rela_path: BString },
+    #[error("{} hunk(s) of the patch for \"{rela_path}\" could not be applied", hunks.len())]
+    HunksDoNotApply { rela_path: BString, hunks: Vec<usize> },
+    #[error("The binary patch for \"{rela_path}\" contains no data to apply")]
+    BinaryWithoutData { rela_path: BString },
+    #[error("The binary patch for \"{rela_path}\" could not be decompressed")]
+    Inflate {
+        rela_path: BString,
+        source: gix_features::zlib::inflate::Error,
+    },
+    #[error("The binary patch for \"{rela_path}\" does not apply")]
+    BinaryDelta {
+        rela_path: BString,
+        source: gix_diff::patch::apply::delta::Error,
+    },
+    #[error("Could not read \"{}\" from the worktree", path.display())]
+    ReadFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not change \"{}\" in the worktree", path.display())]
+    WriteFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    WriteBlob(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+}
@@ -0,0 +1,118 @@
+//! Decide whether files are binary like `git` does, so diffs, merges and `grep` agree on it.
+//!
+//! Attributes take precedence, and if they don't decide, the content is inspected.
+use crate::{bstr::BStr, AttributeStack};
+
+pub use gix_diff::blob::pipeline::FIRST_FEW_BYTES;
+
+/// Return `true` if `data` looks binary as it contains a null byte within its [first few bytes](FIRST_FEW_BYTES),
+/// which is the heuristic `git` uses.
+pub fn is_binary_content(data: &[u8]) -> bool {
+    gix_diff::blob::pipeline::is_binary_buf(data)
+}
+
+/// What files are classified for, which determines the attribute that is consulted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Purpose {
+    /// Produce diffs or search content, which is controlled by the `diff` attribute.
+    ///
+    /// Files are binary if `diff` is unset, and text if it is set. If it names a driver, the driver's
+    /// `diff.<driver>.binary` configuration decides, if present.
+    Diff,
+    /// Merge content, which is controlled by the `merge` attribute.
+    ///
+    /// Files are binary if `merge` is unset or set to `binary`, which is the name of the builtin binary merge driver.
+    Merge,
+}
+
+impl Purpose {
+    fn attribute(&self) -> &'static str {
+        match self {
+            Purpose::Diff => "diff",
+            Purpose::Merge => "merge",
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod detector {
+    /// The error returned by [`Repository::binary_detector()`](crate::Repository::binary_detector()) and
+    /// [`Detector::new()`](super::Detector::new()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        AttributeStack(#[from] crate::config::attribute_stack::Error),
+        #[error(transparent)]
+        DiffDrivers(#[from] crate::config::diff::drivers::Error),
+    }
+}
+
+/// Classify files as binary or text for a [`Purpose`], using their attributes and falling back to their content.
+///
+/// Note that the `binary` attribute is a macro which unsets both `diff` and `merge`, and makes files binary for all purposes.
+/// The `text` attribute only controls end-of-line conversion, and doesn't affect whether files are binary, as in `git`.
+pub struct Detector<'repo> {
+    attributes: AttributeStack<'repo>,
+    matches: gix_attributes::search::Outcome,
+    purpose: Purpose,
+    /// The configured diff drivers, sorted by name.
+    drivers: Vec<gix_diff::blob::Driver>,
+}
+
+/// Lifecycle
+impl<'repo> Detector<'repo> {
+    /// Create a new instance to classify files for `purpose`, using `attributes` to obtain their attributes.
+    pub fn new(attributes: AttributeStack<'repo>, purpose: Purpose) -> Result<Self, detector::Error> {
+        let mut drivers = match purpose {
+            Purpose::Diff => attributes.repo.diff_drivers()?,
+            Purpose::Merge => Vec::new(),
+        };
+        drivers.sort_by(|a, b| a.name.cmp(&b.name));
+        let matches = attributes.selected_attribute_matches(Some(purpose.attribute()));
+        Ok(Detector {
+            attributes,
+            matches,
+            purpose,
+            drivers,
+        })
+    }
+}
+
+/// Classification
+impl Detector<'_> {
+    /// Return whether the file at `rela_path` with `mode` is binary according to its attributes,
+    /// or `None` if they don't decide and its content has to be inspected.
+    pub fn is_binary_by_attributes(
+        &mut self,
+        rela_path: &BStr,
+        mode: Option<gix_index::entry::Mode>,
+    ) -> std::io::Result<Option<bool>> {
+        self.attributes
+            .at_entry(rela_path, mode)?
+            .matching_attributes(&mut self.matches);
+        let Some(attribute) = self.matches.iter_selected().next() else {
+            return Ok(None);
+        };
+        Ok(match (self.purpose, attribute.assignment.state) {
+            (Purpose::Diff, state) => gix_diff::blob::pipeline::is_binary_by_attribute(state, &self.drivers).1,
+            (Purpose::Merge, gix_attributes::StateRef::Unset) => Some(true),
+            (Purpose::Merge, gix_attributes::StateRef::Value(driver)) => (driver.as_bstr() == "binary").then_some(true),
+            (Purpose::Merge, _) => None,
+        })
+    }
+
+    /// Return whether the file at `rela_path` with `mode` and `data` is binary, with its attributes taking precedence
+    /// over its content. It's sufficient to pass the [first few bytes](FIRST_FEW_BYTES) of the file as `data`.
+    pub fn is_binary(
+        &mut self,
+        rela_path: &BStr,
+        mode: Option<gix_index::entry::Mode>,
+        data: &[u8],
+    ) -> std::io::Result<bool> {
+        Ok(self
+            .is_binary_by_attributes(rela_path, mode)?
+            .unwrap_or_else(|| is_binary_content(data)))
+    }
+}
@@ -0,0 +1,134 @@
+//! Types for use with [`Repository::bisect_start()`](crate::Repository::bisect_start()) and related methods to find the commit
+//! that introduced a change by binary search.
+use gix_hash::ObjectId;
+
+use crate::bstr::BString;
+
+/// The judgement of a commit while bisecting, as passed to [`Repository::bisect_mark()`](crate::Repository::bisect_mark()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// The commit doesn't have the change that is searched for, similar to `git bisect good`.
+    Good,
+    /// The commit has the change that is searched for, similar to `git bisect bad`.
+    Bad,
+    /// The commit can't be tested and shouldn't be chosen again, similar to `git bisect skip`.
+    Skip,
+}
+
+impl Mark {
+    /// Return the name of the mark as used by `git` in the names of references and in `BISECT_LOG`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mark::Good => "good",
+            Mark::Bad => "bad",
+            Mark::Skip => "skip",
+        }
+    }
+}
+
+/// Options for use with [`Repository::bisect_start()`](crate::Repository::bisect_start()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, the commit to test is recorded in the `BISECT_HEAD` reference instead of being checked out,
+    /// similar to `git bisect start --no-checkout`. This also works in bare repositories.
+    pub no_checkout: bool,
+}
+
+/// The state of the bisection in progress, as returned by [`Repository::bisect_state()`](crate::Repository::bisect_state()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// The name of the branch that was checked out when the bisection started, or the hexadecimal id of the commit
+    /// if `HEAD` was detached, as stored in `BISECT_START`.
+    pub start: BString,
+    /// The commit that was marked bad most recently, from `refs/bisect/bad`.
+    pub bad: Option<ObjectId>,
+    /// All commits that were marked good, from `refs/bisect/good-*`.
+    pub good: Vec<ObjectId>,
+    /// All commits that were skipped, from `refs/bisect/skip-*`.
+    pub skip: Vec<ObjectId>,
+    /// If `true`, the commit to test is recorded in `BISECT_HEAD` instead of being checked out.
+    pub no_checkout: bool,
+}
+
+/// What to do next to drive the bisection, as returned by [`Repository::bisect_mark()`](crate::Repository::bisect_mark())
+/// and related methods.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Next {
+    /// No commit was marked bad yet.
+    NeedBad,
+    /// No commit was marked good yet.
+    NeedGood,
+    /// `commit` was checked out, or recorded in `BISECT_HEAD`, and should be tested and [marked](crate::Repository::bisect_mark()).
+    Test {
+        /// The commit to test, which splits the remaining candidates into halves that are as equal as possible.
+        commit: ObjectId,
+        /// The amount of commits that may still be the first bad commit, including the bad commit itself.
+        candidates: usize,
+    },
+    /// The bisection is complete as `first_bad` is the only commit left that may have introduced the change.
+    Found {
+        /// The first commit that has the change.
+        first_bad: ObjectId,
+    },
+    /// Only skipped commits are left to test, so any of the `candidates` may have introduced the change.
+    OnlySkippedLeft {
+        /// The commits that may have introduced the change, starting with the bad commit.
+        candidates: Vec<ObjectId>,
+    },
+}
+
+/// The error returned by [`Repository::bisect_start()`](crate::Repository::bisect_start()) and related methods.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot start bisecting while another operation is in progress: {state:?}")]
+    InProgress { state: crate::state::InProgress },
+    #[error("No bisection is in progress")]
+    NotInProgress,
+    #[error("Repository at \"{}\" is a bare repository and can only be bisected without checkout", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error("Cannot bisect on an unborn branch")]
+    UnbornHead,
+    #[error("The bad commit {bad} is an ancestor of a good commit")]
+    BadIsAncestorOfGood { bad: ObjectId },
+    #[error(transparent)]
+    ParseRevision(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    OpenReferences(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    IterReferences(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    ReferenceDuringIteration(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    WalkItem(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error("Could not read, write or remove bisect state at \"{}\"", path.display())]
+    State {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Testing commit {commit} failed")]
+    Test {
+        commit: ObjectId,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
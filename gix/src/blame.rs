@@ -0,0 +1,349 @@
+//! Attribute each line of a file to the commit that introduced it, similar to `git blame`.
+//!
+//! Use [`Repository::blame()`](crate::Repository::blame()) to obtain all entries at once, or
+//! [`Repository::blame_incremental()`](crate::Repository::blame_incremental()) to receive them as soon as they are known.
+use std::ops::Range;
+
+use gix_hash::ObjectId;
+
+use crate::bstr::BString;
+
+/// A range of lines to blame, similar to `git blame -L <start>,<end>`, with both `start` and `end` being 1-based and inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LineRange {
+    /// The first line to blame, starting at 1.
+    pub start: u32,
+    /// The last line to blame, or `None` to blame all lines up to the end of the file.
+    pub end: Option<u32>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod line_range {
+    use super::LineRange;
+    use crate::bstr::BString;
+
+    /// The error returned when parsing a [`LineRange`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line range \"{spec}\" must have the form <start>[,<end>], <start>,+<count>, <start>,-<count> or ,<end> with positive numbers")]
+        Invalid { spec: BString },
+    }
+
+    impl std::str::FromStr for LineRange {
+        type Err = Error;
+
+        /// Parse `spec` in the numeric forms supported by `git blame -L`, which are `<start>,<end>`, `<start>,+<count>`,
+        /// `<start>,-<count>` for the `count` lines up to and including `start`, as well as `<start>` or `<start>,` and `,<end>`.
+        fn from_str(spec: &str) -> Result<Self, Self::Err> {
+            let invalid = || Error::Invalid { spec: spec.into() };
+            let number = |s: &str| s.parse::<u32>().ok().filter(|n| *n > 0).ok_or_else(invalid);
+            let (start, end) = spec.split_once(',').unwrap_or((spec, ""));
+            let range = match (start, end) {
+                ("", "") => return Err(invalid()),
+                ("", end) => LineRange {
+                    start: 1,
+                    end: Some(number(end)?),
+                },
+                (start, "") => LineRange {
+                    start: number(start)?,
+                    end: None,
+                },
+                (start, end) => {
+                    let start = number(start)?;
+                    if let Some(count) = end.strip_prefix('+') {
+                        LineRange {
+                            start,
+                            end: Some(start + number(count)? - 1),
+                        }
+                    } else if let Some(count) = end.strip_prefix('-') {
+                        LineRange {
+                            start: (start + 1).saturating_sub(number(count)?).max(1),
+                            end: Some(start),
+                        }
+                    } else {
+                        let end = number(end)?;
+                        LineRange {
+                            start: start.min(end),
+                            end: Some(start.max(end)),
+                        }
+                    }
+                }
+            };
+            Ok(range)
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod ignore_revs {
+    use gix_hash::ObjectId;
+
+    use crate::bstr::{BString, ByteSlice};
+
+    /// The error returned by [`parse()`].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number} of the ignore-revs file doesn't contain a full object id: \"{line}\"")]
+        InvalidObjectId { line_number: usize, line: BString },
+    }
+
+    /// Parse the `content` of a file in the format used by `git blame --ignore-revs-file` and `blame.ignoreRevsFile`,
+    /// which has one full hexadecimal object id per line, with empty lines and comments starting with `#` being ignored.
+    pub fn parse(content: &[u8]) -> Result<Vec<ObjectId>, Error> {
+        let mut out = Vec::new();
+        for (line_number, line) in content.lines().enumerate() {
+            let line = line.find_byte(b'#').map_or(line, |pos| &line[..pos]).trim();
+            if line.is_empty() {
+                continue;
+            }
+            out.push(ObjectId::from_hex(line).map_err(|_| Error::InvalidObjectId {
+                line_number: line_number + 1,
+                line: line.into(),
+            })?);
+        }
+        Ok(out)
+    }
+}
+
+/// Options for use with [`Repository::blame()`](crate::Repository::blame()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The lines to blame, or all lines if empty. Overlapping ranges are merged.
+    pub ranges: Vec<LineRange>,
+    /// Commits whose changes should be ignored, similar to `git blame --ignore-rev`. Lines changed by them are attributed to
+    /// the corresponding line of the first parent that has the file, based on their position within the changed hunk.
+    /// Lines that have no such counterpart, like those added in a hunk that grew, remain attributed to the ignored commit.
+    pub ignore_revs: gix_hashtable::HashSet<ObjectId>,
+    /// If set, follow the file across renames using the given configuration to detect them whenever a parent doesn't have a
+    /// file at the path of the child.
+    ///
+    /// Defaults to the default rename detection.
+    pub rewrites: Option<gix_diff::Rewrites>,
+    /// The algorithm to use to diff the versions of the file, or `None` to use the algorithm configured in `diff.algorithm`.
+    pub diff_algorithm: Option<gix_diff::blob::Algorithm>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            ranges: Vec::new(),
+            ignore_revs: Default::default(),
+            rewrites: Some(Default::default()),
+            diff_algorithm: None,
+        }
+    }
+}
+
+/// A run of consecutive lines that were introduced by the same commit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The 0-based index of the first line in the blamed version of the file.
+    pub start_in_blamed_file: u32,
+    /// The 0-based index of the first line in the version of the file in [`commit_id`](Self::commit_id).
+    pub start_in_source_file: u32,
+    /// The amount of lines in this entry.
+    pub len: u32,
+    /// The commit that introduced the lines.
+    pub commit_id: ObjectId,
+    /// The path of the file in [`commit_id`](Self::commit_id), which differs from the blamed path if the file was renamed since.
+    pub source_path: BString,
+}
+
+impl Entry {
+    /// Return the 0-based range of lines of this entry in the blamed version of the file.
+    pub fn range_in_blamed_file(&self) -> Range<u32> {
+        self.start_in_blamed_file..self.start_in_blamed_file + self.len
+    }
+
+    /// Return the 0-based range of lines of this entry in the version of the file in [`commit_id`](Self::commit_id).
+    pub fn range_in_source_file(&self) -> Range<u32> {
+        self.start_in_source_file..self.start_in_source_file + self.len
+    }
+}
+
+/// Information about the work performed to blame a file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Statistics {
+    /// The amount of commits that were looked at for passing on blame.
+    pub commits_traversed: usize,
+    /// The amount of tree diffs performed to follow renames.
+    pub trees_diffed: usize,
+    /// The amount of blob diffs performed to find unchanged lines.
+    pub blobs_diffed: usize,
+}
+
+/// The outcome of [`Repository::blame()`](crate::Repository::blame()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All entries sorted by their position in the blamed file, with adjacent lines from the same commit merged.
+    pub entries: Vec<Entry>,
+    /// The content of the blamed file.
+    pub blob: Vec<u8>,
+    /// Information about the work performed.
+    pub statistics: Statistics,
+}
+
+impl Outcome {
+    /// Return an iterator over all blamed lines along with the entry they belong to.
+    pub fn entries_with_lines(&self) -> impl Iterator<Item = (&Entry, Vec<&crate::bstr::BStr>)> + '_ {
+        use crate::bstr::ByteSlice;
+        let lines: Vec<_> = self.blob.lines_with_terminator().map(ByteSlice::as_bstr).collect();
+        self.entries.iter().map(move |entry| {
+            let range = entry.range_in_blamed_file();
+            (entry, lines[range.start as usize..range.end as usize].to_vec())
+        })
+    }
+}
+
+/// The error returned by [`Repository::blame()`](crate::Repository::blame()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The file \"{path}\" doesn't exist in commit {commit_id}")]
+    FileNotFound { path: BString, commit_id: ObjectId },
+    #[error("Line range {}..{} is invalid for a file with {line_count} lines", range.start, range.end.map_or_else(String::new, |end| end.to_string()))]
+    InvalidLineRange { range: LineRange, line_count: u32 },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToKind(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    TryIntoTree(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    DiffAlgorithm(#[from] crate::config::diff::algorithm::Error),
+    #[error(transparent)]
+    TreeDiff(#[from] crate::object::tree::diff::for_each::Error),
+    #[error(transparent)]
+    TreeDiffPlatform(#[from] crate::diff::new_rewrites::Error),
+    #[error(transparent)]
+    ShallowCommits(#[from] crate::shallow::open::Error),
+}
+
+/// Lines of the file in a commit that are yet to be attributed, mapped to their position in the blamed file.
+#[derive(Debug, Clone)]
+pub(crate) struct UnblamedHunk {
+    pub suspect: Range<u32>,
+    pub start_in_blamed_file: u32,
+}
+
+/// A run of `len` lines at `new_start` in a version of a file that correspond to the lines at `old_start` in its parent.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Segment {
+    pub new_start: u32,
+    pub old_start: u32,
+    pub len: u32,
+    /// If `true`, the lines were changed and are only paired up by their position within the changed hunk.
+    pub changed: bool,
+}
+
+/// Diff `old` against `new` and return all runs of unchanged lines, along with the paired lines of changed hunks,
+/// sorted by their position in `new`.
+pub(crate) fn segments(algorithm: gix_diff::blob::Algorithm, old: &[u8], new: &[u8]) -> Vec<Segment> {
+    use gix_diff::blob::{intern::InternedInput, sources::byte_lines_with_terminator};
+    let input = InternedInput::new(byte_lines_with_terminator(old), byte_lines_with_terminator(new));
+    let mut out = Vec::new();
+    let (mut old_pos, mut new_pos) = (0, 0);
+    gix_diff::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+        if after.start > new_pos {
+            out.push(Segment {
+                new_start: new_pos,
+                old_start: old_pos,
+                len: after.start - new_pos,
+                changed: false,
+            });
+        }
+        let paired = before.len().min(after.len()) as u32;
+        if paired > 0 {
+            out.push(Segment {
+                new_start: after.start,
+                old_start: before.start,
+                len: paired,
+                changed: true,
+            });
+        }
+        old_pos = before.end;
+        new_pos = after.end;
+    });
+    let new_len = input.after.len() as u32;
+    if new_len > new_pos {
+        out.push(Segment {
+            new_start: new_pos,
+            old_start: old_pos,
+            len: new_len - new_pos,
+            changed: false,
+        });
+    }
+    out
+}
+
+/// Split `hunks` into those that map to the parent through `segments`, translated to the line numbers of the parent,
+/// and those that remain with the suspect. Segments of changed lines are only used if `include_changed` is `true`.
+pub(crate) fn pass_blame(
+    hunks: Vec<UnblamedHunk>,
+    segments: &[Segment],
+    include_changed: bool,
+) -> (Vec<UnblamedHunk>, Vec<UnblamedHunk>) {
+    let mut passed = Vec::new();
+    let mut kept = Vec::new();
+    for hunk in hunks {
+        let offset_in_blamed_file = |line: u32| hunk.start_in_blamed_file + (line - hunk.suspect.start);
+        let mut cursor = hunk.suspect.start;
+        let first = segments.partition_point(|segment| segment.new_start + segment.len <= cursor);
+        for segment in segments[first..]
+            .iter()
+            .take_while(|segment| segment.new_start < hunk.suspect.end)
+            .filter(|segment| include_changed || !segment.changed)
+        {
+            let start = segment.new_start.max(cursor);
+            let end = (segment.new_start + segment.len).min(hunk.suspect.end);
+            if start >= end {
+                continue;
+            }
+            if cursor < start {
+                kept.push(UnblamedHunk {
+                    suspect: cursor..start,
+                    start_in_blamed_file: offset_in_blamed_file(cursor),
+                });
+            }
+            let old_start = segment.old_start + (start - segment.new_start);
+            passed.push(UnblamedHunk {
+                suspect: old_start..old_start + (end - start),
+                start_in_blamed_file: offset_in_blamed_file(start),
+            });
+            cursor = end;
+        }
+        if cursor < hunk.suspect.end {
+            kept.push(UnblamedHunk {
+                suspect: cursor..hunk.suspect.end,
+                start_in_blamed_file: offset_in_blamed_file(cursor),
+            });
+        }
+    }
+    (passed, kept)
+}
+
+/// Sort `entries` by their position in the blamed file and merge adjacent ones of the same commit and path.
+pub(crate) fn coalesce(mut entries: Vec<Entry>) -> Vec<Entry> {
+    entries.sort_by_key(|entry| entry.start_in_blamed_file);
+    let mut out: Vec<Entry> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        if let Some(last) = out.last_mut() {
+            if last.commit_id == entry.commit_id
+                && last.source_path == entry.source_path
+                && last.range_in_blamed_file().end == entry.start_in_blamed_file
+                && last.range_in_source_file().end == entry.start_in_source_file
+            {
+                last.len += entry.len;
+                continue;
+            }
+        }
+        out.push(entry);
+    }
+    out
+}
@@ -0,0 +1,290 @@
+//! Read and write [bundle files](https://git-scm.com/docs/gitformat-bundle), which contain references along with a pack
+//! of the objects they need, to transfer history without a connection to the other repository.
+//!
+//! Bundles are created with [`Repository::create_bundle()`](crate::Repository::create_bundle()) and checked with
+//! [`Repository::verify_bundle()`](crate::Repository::verify_bundle()). With a blocking network client, a path to a
+//! bundle file can be used as remote URL to clone or fetch from it.
+use std::io::{BufRead, Write};
+
+use gix_hash::ObjectId;
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+#[cfg(feature = "blocking-network-client")]
+mod transport;
+#[cfg(feature = "blocking-network-client")]
+pub use transport::Transport;
+
+/// The version of a bundle file.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Version {
+    /// The original format, which only supports SHA1.
+    #[default]
+    V2,
+    /// A format with capabilities, like the hash used for objects.
+    V3,
+}
+
+impl Version {
+    /// Return the first line of bundle files of this version, including the newline.
+    pub fn signature(&self) -> &'static [u8] {
+        match self {
+            Version::V2 => b"# v2 git bundle\n",
+            Version::V3 => b"# v3 git bundle\n",
+        }
+    }
+}
+
+/// A commit which must be present in a repository to be able to use the pack of a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Prerequisite {
+    /// The id of the commit.
+    pub id: ObjectId,
+    /// A comment to help humans identify the commit, typically its summary, which may be empty.
+    pub comment: BString,
+}
+
+/// A reference stored in a bundle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ref {
+    /// The object the reference points to.
+    pub id: ObjectId,
+    /// The full name of the reference.
+    pub name: gix_ref::FullName,
+}
+
+/// The part of a bundle file that precedes its pack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    /// The version of the bundle.
+    pub version: Version,
+    /// The kind of hash used for all objects in the bundle.
+    pub object_hash: gix_hash::Kind,
+    /// Capabilities other than `object-format`, as `(key, value)` pairs, which are only stored in [`Version::V3`] bundles.
+    pub capabilities: Vec<(BString, Option<BString>)>,
+    /// The commits the pack builds upon.
+    pub prerequisites: Vec<Prerequisite>,
+    /// The references stored in the bundle, with their objects being in the pack or being prerequisites.
+    pub refs: Vec<Ref>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod header {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Header::from_read()`](super::Header::from_read()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("Not a bundle file as the first line isn't '# v2 git bundle' or '# v3 git bundle'")]
+        Signature,
+        #[error("The bundle header ended unexpectedly")]
+        Truncated,
+        #[error("Could not parse line {line:?} of the bundle header")]
+        Line { line: BString },
+        #[error("Unsupported object format {format:?}")]
+        ObjectFormat { format: BString },
+        #[error(transparent)]
+        RefName(#[from] gix_validate::reference::name::Error),
+    }
+}
+
+impl Header {
+    /// Read the header from `read`, which is left positioned at the beginning of the pack.
+    pub fn from_read(read: &mut impl BufRead) -> Result<Self, header::Error> {
+        let mut line = Vec::new();
+        let mut next_line = |line: &mut Vec<u8>| -> Result<(), header::Error> {
+            line.clear();
+            read.read_until(b'\n', line)?;
+            if line.pop() != Some(b'\n') {
+                return Err(header::Error::Truncated);
+            }
+            Ok(())
+        };
+        next_line(&mut line)?;
+        let version = match line.as_slice() {
+            b"# v2 git bundle" => Version::V2,
+            b"# v3 git bundle" => Version::V3,
+            _ => return Err(header::Error::Signature),
+        };
+        let mut header = Header {
+            version,
+            object_hash: gix_hash::Kind::Sha1,
+            capabilities: Vec::new(),
+            prerequisites: Vec::new(),
+            refs: Vec::new(),
+        };
+        let invalid = |line: &[u8]| header::Error::Line {
+            line: line.as_bstr().to_owned(),
+        };
+        loop {
+            next_line(&mut line)?;
+            if line.is_empty() {
+                break;
+            }
+            if let Some(capability) = line
+                .strip_prefix(b"@")
+                .filter(|_| version == Version::V3 && header.prerequisites.is_empty() && header.refs.is_empty())
+            {
+                let (key, value) = match capability.find_byte(b'=') {
+                    Some(pos) => (&capability[..pos], Some(&capability[pos + 1..])),
+                    None => (capability, None),
+                };
+                if key == b"object-format" {
+                    let format = value.unwrap_or_default();
+                    header.object_hash = format
+                        .to_str()
+                        .ok()
+                        .and_then(|format| format.parse().ok())
+                        .ok_or_else(|| header::Error::ObjectFormat { format: format.into() })?;
+                } else {
+                    header.capabilities.push((key.into(), value.map(Into::into)));
+                }
+            } else if let Some(prerequisite) = line.strip_prefix(b"-") {
+                let (id, comment) = split_id(prerequisite).ok_or_else(|| invalid(&line))?;
+                header.prerequisites.push(Prerequisite {
+                    id,
+                    comment: comment.unwrap_or_default().into(),
+                });
+            } else {
+                let (id, name) = split_id(&line).ok_or_else(|| invalid(&line))?;
+                let name = name.ok_or_else(|| invalid(&line))?;
+                header.refs.push(Ref {
+                    id,
+                    name: name.try_into()?,
+                });
+            }
+        }
+        Ok(header)
+    }
+
+    /// Write this header to `out`, followed by the empty line that separates it from the pack.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        out.write_all(self.version.signature())?;
+        if self.version == Version::V3 {
+            writeln!(out, "@object-format={}", object_format(self.object_hash))?;
+            for (key, value) in &self.capabilities {
+                match value {
+                    Some(value) => writeln!(out, "@{key}={value}")?,
+                    None => writeln!(out, "@{key}")?,
+                }
+            }
+        }
+        for prerequisite in &self.prerequisites {
+            if prerequisite.comment.is_empty() {
+                writeln!(out, "-{}", prerequisite.id)?;
+            } else {
+                writeln!(out, "-{} {}", prerequisite.id, prerequisite.comment)?;
+            }
+        }
+        for reference in &self.refs {
+            writeln!(out, "{} {}", reference.id, reference.name.as_bstr())?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+/// Split `line` into the hexadecimal object id it starts with, and what follows after a separating space, if anything.
+fn split_id(line: &[u8]) -> Option<(ObjectId, Option<&BStr>)> {
+    let (id, rest) = match line.find_byte(b' ') {
+        Some(pos) => (&line[..pos], Some(line[pos + 1..].as_bstr())),
+        None => (line, None),
+    };
+    Some((ObjectId::from_hex(id).ok()?, rest))
+}
+
+/// Return the name of `object_hash` as used in the `object-format` capability.
+fn object_format(object_hash: gix_hash::Kind) -> String {
+    object_hash.to_string().to_ascii_lowercase()
+}
+
+/// Return `true` if `path` is a file that starts like a bundle file.
+pub fn is_bundle(path: &std::path::Path) -> bool {
+    let mut signature = [0; 16];
+    std::fs::File::open(path)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut signature))
+        .is_ok()
+        && [Version::V2, Version::V3]
+            .iter()
+            .any(|version| version.signature() == signature)
+}
+
+/// Options for use with [`Repository::create_bundle()`](crate::Repository::create_bundle()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The version of the bundle to write. Repositories that don't use SHA1 always write [`Version::V3`].
+    pub version: Version,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod create {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::create_bundle()`](crate::Repository::create_bundle()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FollowReference(#[from] gix_ref::file::find::existing::Error),
+        #[error("Reference {name:?} doesn't point to an object")]
+        Unborn { name: BString },
+        #[error("Refusing to create an empty bundle")]
+        Empty,
+        #[error(transparent)]
+        RevList(#[from] crate::rev_list::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        ConfigInteger(#[from] crate::config::key::GenericError),
+        #[error(transparent)]
+        ZlibBackend(#[from] crate::config::zlib_backend::Error),
+        #[error(transparent)]
+        ConfigUnsignedInteger(#[from] crate::config::unsigned_integer::Error),
+        #[error(transparent)]
+        CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+        #[error(transparent)]
+        WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    }
+
+    /// Information about a bundle written by [`Repository::create_bundle()`](crate::Repository::create_bundle()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The header that was written.
+        pub header: super::Header,
+        /// The amount of objects in the pack that was written after the header.
+        pub num_objects: usize,
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod verify {
+    use gix_hash::ObjectId;
+
+    /// The error returned by [`Repository::verify_bundle()`](crate::Repository::verify_bundle()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The bundle uses {actual} objects, but the repository uses {expected}")]
+        ObjectHash {
+            actual: gix_hash::Kind,
+            expected: gix_hash::Kind,
+        },
+        #[error("The bundle requires the capability {name:?} which isn't supported")]
+        UnsupportedCapability { name: crate::bstr::BString },
+        #[error("The repository lacks these prerequisite commits: {}", ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+        MissingPrerequisites { ids: Vec<ObjectId> },
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::Error),
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    io::{Cursor, Read, Seek, SeekFrom},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use gix_protocol::transport::{
+    client::{self, git, MessageKind, RequestWriter, SetServiceResponse, WriteMode},
+    packetline::{self, encode, PacketLineRef},
+    Protocol, Service,
+};
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// The most data that fits into a packet line along with the band it is sent on.
+const MAX_BAND_DATA_LEN: usize = 65515;
+
+/// A transport to fetch from a bundle file as if it was a remote repository speaking protocol V1.
+///
+/// It advertises the references of the bundle, and sends its whole pack once the client is done negotiating.
+/// Note that the prerequisites of the bundle must be present in the receiving repository, which can be checked with
+/// [`Repository::verify_bundle()`](crate::Repository::verify_bundle()).
+pub struct Transport {
+    connection: git::Connection<Responder, Requests>,
+}
+
+impl Transport {
+    /// Open the bundle file at `path` and prepare it for being fetched from.
+    /// If `trace` is `true`, all packetlines will be traced.
+    pub fn open(path: impl Into<PathBuf>, trace: bool) -> Result<Self, super::header::Error> {
+        let path = path.into();
+        let mut file = std::io::BufReader::new(std::fs::File::open(&path)?);
+        let header = super::Header::from_read(&mut file)?;
+        let pack_offset = file.stream_position()?;
+
+        let mut advertisement = Vec::new();
+        let mut capabilities = format!(
+            "multi_ack_detailed side-band-64k ofs-delta include-tag object-format={}",
+            super::object_format(header.object_hash)
+        );
+        let head = header.refs.iter().find(|r| r.name.as_bstr() == "HEAD");
+        if let Some(branch) = head.and_then(|head| {
+            header
+                .refs
+                .iter()
+                .find(|r| r.id == head.id && r.name.as_bstr().starts_with(b"refs/heads/"))
+        }) {
+            capabilities.push_str(&format!(" symref=HEAD:{}", branch.name.as_bstr()));
+        }
+        let mut refs: Vec<(BString, _)> = header
+            .refs
+            .iter()
+            .map(|r| (r.name.as_bstr().to_owned(), r.id))
+            .collect();
+        if refs.is_empty() {
+            refs.push(("capabilities^{}".into(), header.object_hash.null()));
+        }
+        for (index, (name, id)) in refs.iter().enumerate() {
+            let mut line = BString::from(format!("{id} {name}"));
+            if index == 0 {
+                line.push(0);
+                line.extend_from_slice(capabilities.as_bytes());
+            }
+            line.push(b'\n');
+            encode::data_to_write(&line, &mut advertisement)?;
+        }
+        encode::flush_to_write(&mut advertisement)?;
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let url = gix_url::Url::from_parts(
+            gix_url::Scheme::File,
+            None,
+            None,
+            None,
+            None,
+            gix_path::into_bstr(path.as_path()).into_owned(),
+            true,
+        )
+        .expect("valid url")
+        .to_bstring();
+        Ok(Transport {
+            connection: git::Connection::new(
+                Responder {
+                    requests: requests.clone(),
+                    response: Cursor::new(advertisement),
+                    path,
+                    pack_offset,
+                    pack: None,
+                    saw_have: false,
+                },
+                Requests(requests),
+                Protocol::V1,
+                BString::default(),
+                None::<(String, Option<u16>)>,
+                git::ConnectMode::Process,
+                trace,
+            )
+            .custom_url(Some(url)),
+        })
+    }
+}
+
+impl client::TransportWithoutIO for Transport {
+    fn request(
+        &mut self,
+        write_mode: WriteMode,
+        on_into_read: MessageKind,
+        trace: bool,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        self.connection.request(write_mode, on_into_read, trace)
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        self.connection.to_url()
+    }
+
+    fn supported_protocol_versions(&self) -> &[Protocol] {
+        &[Protocol::V1]
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        true
+    }
+
+    fn configure(&mut self, _config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
+
+impl client::Transport for Transport {
+    fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<SetServiceResponse<'_>, client::Error> {
+        if service != Service::UploadPack {
+            return Err(client::Error::Io(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "Bundles can only be fetched from",
+            )));
+        }
+        self.connection.handshake(service, extra_parameters)
+    }
+}
+
+/// Collects everything the client sends.
+struct Requests(Arc<Mutex<Vec<u8>>>);
+
+impl std::io::Write for Requests {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().expect("no panic while locked").extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Produces the responses to the requests collected so far, as `upload-pack` would.
+struct Responder {
+    requests: Arc<Mutex<Vec<u8>>>,
+    response: Cursor<Vec<u8>>,
+    path: PathBuf,
+    pack_offset: u64,
+    /// The bundle file positioned at the pack, once it is sent.
+    pack: Option<std::fs::File>,
+    saw_have: bool,
+}
+
+impl Responder {
+    /// Fill our response with the answers to all complete request lines, and return `false` if there was nothing to respond.
+    fn respond_to_requests(&mut self) -> std::io::Result<bool> {
+        let mut response = Vec::new();
+        let mut requests = self.requests.lock().expect("no panic while locked");
+        let mut consumed = 0;
+        while let Ok(packetline::decode::Stream::Complete { line, bytes_consumed }) =
+            packetline::decode::streaming(&requests[consumed..])
+        {
+            consumed += bytes_consumed;
+            match line {
+                PacketLineRef::Data(data) => {
+                    let data = data.trim_end();
+                    if data == b"done" {
+                        encode::text_to_write(b"NAK", &mut response)?;
+                        let mut pack = std::fs::File::open(&self.path)?;
+                        pack.seek(SeekFrom::Start(self.pack_offset))?;
+                        self.pack = Some(pack);
+                        break;
+                    }
+                    self.saw_have |= data.starts_with(b"have ");
+                }
+                PacketLineRef::Flush => {
+                    // Without common commits, each round of `have` lines is answered with a `NAK`.
+                    if std::mem::take(&mut self.saw_have) {
+                        encode::text_to_write(b"NAK", &mut response)?;
+                    }
+                }
+                PacketLineRef::Delimiter | PacketLineRef::ResponseEnd => {}
+            }
+        }
+        requests.drain(..consumed);
+        let has_response = !response.is_empty();
+        self.response = Cursor::new(response);
+        Ok(has_response)
+    }
+}
+
+impl Read for Responder {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            let read = self.response.read(buf)?;
+            if read != 0 || buf.is_empty() {
+                return Ok(read);
+            }
+            if let Some(pack) = self.pack.as_mut() {
+                let mut data = vec![0; MAX_BAND_DATA_LEN];
+                let len = pack.read(&mut data)?;
+                let mut response = Vec::new();
+                if len == 0 {
+                    encode::flush_to_write(&mut response)?;
+                    self.pack = None;
+                } else {
+                    encode::band_to_write(packetline::Channel::Data, &data[..len], &mut response)?;
+                }
+                self.response = Cursor::new(response);
+                continue;
+            }
+            if !self.respond_to_requests()? {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "The bundle has no response to what was requested",
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Types for use with [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()).
+use std::{collections::VecDeque, io::Write};
+
+use gix_hash::ObjectId;
+use gix_pack::Find;
+
+use crate::{bstr::BString, Repository};
+
+/// What to obtain for each object, for use with [`Options`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Obtain the kind and size of each object, like `git cat-file --batch-check`.
+    Check,
+    /// Obtain the kind, size and data of each object, like `git cat-file --batch`, the default.
+    #[default]
+    Contents,
+}
+
+/// Options for use with [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// What to obtain for each object.
+    pub mode: Mode,
+    /// The amount of inputs to read ahead, which are then looked up in the order in which they are stored in packs,
+    /// before being returned in input order. Defaults to `1024`.
+    ///
+    /// Larger windows make lookups more efficient for inputs in random order, while smaller ones hold fewer objects
+    /// in memory. `0` is treated like `1`.
+    pub window: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            mode: Mode::default(),
+            window: 1024,
+        }
+    }
+}
+
+/// An object, or its absence, as returned by [`Batch`] for each input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// The input named an existing object.
+    Object {
+        /// The id of the object.
+        id: ObjectId,
+        /// The kind of the object.
+        kind: gix_object::Kind,
+        /// The size of the object's data in bytes.
+        size: u64,
+        /// The data of the object if [`Mode::Contents`] was used.
+        data: Option<Vec<u8>>,
+    },
+    /// The input couldn't be resolved to an existing object.
+    Missing {
+        /// The input as it was given.
+        input: BString,
+    },
+}
+
+impl Item {
+    /// Write this item to `out` like `git cat-file --batch` or `git cat-file --batch-check` do, with a line like
+    /// `id kind size`, followed by the data and a newline if present, or a line like `input missing`.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        match self {
+            Item::Object { id, kind, size, data } => {
+                writeln!(out, "{id} {kind} {size}")?;
+                if let Some(data) = data {
+                    out.write_all(data)?;
+                    out.write_all(b"\n")?;
+                }
+            }
+            Item::Missing { input } => writeln!(out, "{input} missing")?,
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Batch`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::Error),
+}
+
+/// An iterator over the objects named by a series of inputs, as returned by
+/// [`Repository::cat_file_batch()`](crate::Repository::cat_file_batch()).
+pub struct Batch<'repo, I> {
+    pub(crate) repo: &'repo Repository,
+    /// A handle to look up the location of objects in packs, which requires packs to remain loaded.
+    pub(crate) objects: crate::OdbHandle,
+    pub(crate) inputs: I,
+    pub(crate) options: Options,
+    /// The items of the current window, in input order.
+    pub(crate) items: VecDeque<Result<Item, Error>>,
+}
+
+impl<I> Batch<'_, I>
+where
+    I: Iterator<Item = BString>,
+{
+    /// Resolve the next window of inputs and look them up in the order of their location in packs, with loose objects last.
+    fn fill_window(&mut self) {
+        let mut resolved: Vec<_> = self
+            .inputs
+            .by_ref()
+            .take(self.options.window.max(1))
+            .map(|input| {
+                let id = self.repo.rev_parse_single(input.as_slice()).ok().map(crate::Id::detach);
+                (input, id)
+            })
+            .enumerate()
+            .collect();
+        let mut buf = Vec::new();
+        let mut locations: Vec<_> = resolved
+            .iter()
+            .map(|(pos, (_, id))| {
+                let location = id
+                    .and_then(|id| self.objects.location_by_oid(&id, &mut buf))
+                    .map_or((u32::MAX, u64::MAX), |location| {
+                        (location.pack_id, location.pack_offset)
+                    });
+                (location, *pos)
+            })
+            .collect();
+        locations.sort_unstable();
+
+        let mut items: Vec<Option<Result<Item, Error>>> = (0..resolved.len()).map(|_| None).collect();
+        for (_, pos) in locations {
+            let (input, id) = std::mem::take(&mut resolved[pos].1);
+            items[pos] = Some(self.lookup(input, id));
+        }
+        self.items = items
+            .into_iter()
+            .map(|item| item.expect("every input was looked up"))
+            .collect();
+    }
+
+    fn lookup(&self, input: BString, id: Option<ObjectId>) -> Result<Item, Error> {
+        let Some(id) = id else {
+            return Ok(Item::Missing { input });
+        };
+        let item = match self.options.mode {
+            Mode::Check => self.repo.try_find_header(id)?.map(|header| Item::Object {
+                id,
+                kind: header.kind(),
+                size: header.size(),
+                data: None,
+            }),
+            Mode::Contents => self.repo.try_find_object(id)?.map(|object| {
+                let object = object.detach();
+                Item::Object {
+                    id,
+                    kind: object.kind,
+                    size: object.data.len() as u64,
+                    data: Some(object.data),
+                }
+            }),
+        };
+        Ok(item.unwrap_or(Item::Missing { input }))
+    }
+}
+
+impl<I> Iterator for Batch<'_, I>
+where
+    I: Iterator<Item = BString>,
+{
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.items.is_empty() {
+            self.fill_window();
+        }
+        self.items.pop_front()
+    }
+}
@@ -0,0 +1,101 @@
+//! Types for use with [`Repository::checkout()`](crate::Repository::checkout()).
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::checkout()`](crate::Repository::checkout()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, local changes in the index and the worktree as well as untracked files in the way
+    /// will be overwritten, similar to `git checkout --force`.
+    pub force: bool,
+    /// If `true`, default `false`, `HEAD` will be detached even if the checkout target is the name of a local branch,
+    /// similar to `git checkout --detach`.
+    pub detach: bool,
+}
+
+/// The outcome of [`Repository::checkout()`](crate::Repository::checkout()).
+#[derive(Debug)]
+pub struct Outcome {
+    /// The commit `HEAD` pointed to before the checkout, or `None` if it was unborn.
+    pub previous_head_id: Option<gix_hash::ObjectId>,
+    /// The commit `HEAD` points to now.
+    pub head_id: gix_hash::ObjectId,
+    /// The name of the branch `HEAD` is attached to, or `None` if `HEAD` is detached.
+    pub head_name: Option<gix_ref::FullName>,
+    /// The amount of files that were removed from the worktree as they are not present in the target tree.
+    pub files_removed: usize,
+    /// The outcome of writing all changed files to the worktree.
+    pub checkout: gix_worktree_state::checkout::Outcome,
+}
+
+/// Describes why a path prevented the checkout from happening.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    /// The worktree file differs from what's in the index.
+    LocalModification,
+    /// The index entry differs from what's in the tree of `HEAD`.
+    StagedChange,
+    /// The entry is not tracked, but exists in the worktree and would be overwritten.
+    Untracked,
+    /// The index contains unresolved conflicts for this path.
+    Unmerged,
+}
+
+/// A path that would be affected by the checkout, but can't be changed without losing data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Conflict {
+    /// The repository-relative path of the conflicting entry.
+    pub rela_path: BString,
+    /// The reason for the conflict.
+    pub kind: ConflictKind,
+}
+
+/// The error returned by [`Repository::checkout()`](crate::Repository::checkout()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and cannot have its worktree checked out", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error(transparent)]
+    ParseTarget(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    StatusPlatform(#[from] crate::status::Error),
+    #[error(transparent)]
+    CreateStatusIterator(#[from] crate::status::index_worktree::iter::Error),
+    #[error(transparent)]
+    StatusItem(#[from] crate::status::index_worktree::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error("Failed to reopen object database as Arc (only if thread-safety wasn't compiled in)")]
+    OpenArcOdb(#[source] std::io::Error),
+    #[error("Could not remove \"{}\" from the worktree", path.display())]
+    RemoveFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error("Local changes to {} path(s) would be overwritten by checkout", conflicts.len())]
+    Conflicts { conflicts: Vec<Conflict> },
+}
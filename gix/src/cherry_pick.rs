@@ -0,0 +1,98 @@
+//! Types for use with [`Repository::cherry_pick()`](crate::Repository::cherry_pick()) and
+//! [`Repository::revert()`](crate::Repository::revert()).
+
+/// Options for use with [`Repository::cherry_pick()`](crate::Repository::cherry_pick()) and
+/// [`Repository::revert()`](crate::Repository::revert()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, only apply the changes to the index and the worktree without creating a commit,
+    /// similar to `git cherry-pick -n`.
+    ///
+    /// This also allows the index to have changes compared to `HEAD`, as long as they don't conflict with the changes to apply.
+    pub no_commit: bool,
+    /// The 1-based number of the parent to consider the mainline of a merge commit, whose changes relative to that parent
+    /// are applied, similar to `git cherry-pick -m <parent-number>`.
+    ///
+    /// It must be set for merge commits, and must not be set otherwise.
+    pub mainline: Option<usize>,
+    /// If `true`, default `false`, create a commit even if the changes are already present, so that the new commit
+    /// would have the same tree as its parent.
+    pub allow_empty: bool,
+}
+
+/// The outcome of [`Repository::cherry_pick()`](crate::Repository::cherry_pick()) and
+/// [`Repository::revert()`](crate::Repository::revert()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The newly created commit that `HEAD` points to now, or `None` if no commit was created as
+    /// [`no_commit`](Options::no_commit) was set or if there were conflicts.
+    pub commit_id: Option<gix_hash::ObjectId>,
+    /// All paths that couldn't be merged.
+    ///
+    /// If there are any, the index contains their unmerged stages and the worktree their conflicting versions,
+    /// and unless [`no_commit`](Options::no_commit) is set, `CHERRY_PICK_HEAD` or `REVERT_HEAD` is written to record
+    /// the operation in progress.
+    pub conflicts: Vec<crate::merge::Conflict>,
+}
+
+/// The error returned by [`Repository::cherry_pick()`](crate::Repository::cherry_pick()) and
+/// [`Repository::revert()`](crate::Repository::revert()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and has no worktree to apply changes to", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error("Cannot apply changes while another operation is in progress: {state:?}")]
+    InProgress { state: crate::state::InProgress },
+    #[error("Cannot apply changes onto an unborn branch")]
+    UnbornHead,
+    #[error("Commit {commit} is a merge but no mainline parent was given")]
+    MissingMainline { commit: gix_hash::ObjectId },
+    #[error("Mainline parent {mainline} was given, but commit {commit} has {parent_count} parent(s)")]
+    InvalidMainline {
+        commit: gix_hash::ObjectId,
+        mainline: usize,
+        parent_count: usize,
+    },
+    #[error("The index has changes compared to HEAD, which would be committed as well")]
+    DirtyIndex,
+    #[error("Applying commit {commit} results in no changes")]
+    Empty { commit: gix_hash::ObjectId },
+    #[error(transparent)]
+    ParseTarget(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Merge(#[from] crate::merge::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    WriteTree(#[from] crate::repository::write_tree_from_index::Error),
+    #[error(transparent)]
+    WriteCommit(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    ParseTime(#[from] crate::config::time::Error),
+    #[error("Committer identity is not configured")]
+    CommitterMissing,
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error("Could not write state file at \"{}\"", path.display())]
+    WriteState {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
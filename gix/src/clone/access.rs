@@ -35,6 +35,26 @@ impl PrepareFetch {
         self
     }
 
+    /// If `toggle` is `true`, only fetch the branch that the remote `HEAD` points to instead of all branches, like
+    /// `git clone --single-branch`, and configure the remote to only fetch this branch in future.
+    ///
+    /// Unless tags were configured with [`configure_remote()`](Self::configure_remote()), only tags pointing into the
+    /// fetched history are fetched.
+    pub fn with_single_branch(mut self, toggle: bool) -> Self {
+        self.single_branch = toggle;
+        self
+    }
+
+    /// Perform a partial clone by asking the server to omit objects according to the filter `spec`, like `blob:none`,
+    /// and configure the remote as promisor remote along with the filter, like `git clone --filter`.
+    ///
+    /// Note that missing objects can't be fetched on demand, so a checkout of a partial clone will fail if objects
+    /// it needs were omitted.
+    pub fn with_filter(mut self, spec: impl Into<String>) -> Self {
+        self.filter = Some(spec.into());
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
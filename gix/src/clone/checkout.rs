@@ -5,7 +5,7 @@ use crate::{clone::PrepareCheckout, Repository};
 pub mod main_worktree {
     use std::{path::PathBuf, sync::atomic::AtomicBool};
 
-    use crate::{clone::PrepareCheckout, Progress, Repository};
+    use crate::{clone::PrepareCheckout, Count, Progress, Repository};
 
     /// The error returned by [`PrepareCheckout::main_worktree()`].
     #[derive(Debug, thiserror::Error)]
@@ -45,6 +45,8 @@ pub mod main_worktree {
         CheckoutFiles,
         /// The amount of bytes written in total, the aggregate of the size of the content of all files thus far.
         BytesWritten,
+        /// The amount of entries written to the index after all files were checked out.
+        IndexEntries,
     }
 
     impl From<ProgressId> for gix_features::progress::Id {
@@ -52,6 +54,7 @@ pub mod main_worktree {
             match v {
                 ProgressId::CheckoutFiles => *b"CLCF",
                 ProgressId::BytesWritten => *b"CLCB",
+                ProgressId::IndexEntries => *b"CLCI",
             }
         }
     }
@@ -128,7 +131,12 @@ pub mod main_worktree {
             files.show_throughput(start);
             bytes.show_throughput(start);
 
+            let mut entries = progress.add_child_with_id("writing index".to_string(), ProgressId::IndexEntries.into());
+            entries.init(Some(index.entries().len()), crate::progress::count("entries"));
+            let start = std::time::Instant::now();
             index.write(Default::default())?;
+            entries.inc_by(index.entries().len());
+            entries.show_throughput(start);
             Ok((self.repo.take().expect("still present"), outcome))
         }
     }
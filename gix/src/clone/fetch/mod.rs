@@ -12,6 +12,8 @@ pub enum Error {
     Fetch(#[from] crate::remote::fetch::Error),
     #[error(transparent)]
     RemoteInit(#[from] crate::remote::init::Error),
+    #[error(transparent)]
+    RefMap(#[from] crate::remote::ref_map::Error),
     #[error("Custom configuration of remote to clone from failed")]
     RemoteConfiguration(#[source] Box<dyn std::error::Error + Send + Sync>),
     #[error("Custom configuration of connection to use when cloning failed")]
@@ -74,6 +76,7 @@ impl PrepareFetch {
     ) -> Result<(crate::Repository, crate::remote::fetch::Outcome), Error> {
         use crate::{bstr::ByteVec, remote, remote::fetch::RefLogMessage};
 
+        let _span = gix_trace::coarse!("gix::clone::PrepareFetch::fetch_only()");
         let repo = self
             .repo
             .as_mut()
@@ -107,14 +110,12 @@ impl PrepareFetch {
         if let Some(f) = self.configure_remote.as_mut() {
             remote = f(remote).map_err(Error::RemoteConfiguration)?;
         } else {
-            clone_fetch_tags = remote::fetch::Tags::All.into();
-        }
-
-        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
-
-        // Now we are free to apply remote configuration we don't want to be written to disk.
-        if let Some(fetch_tags) = clone_fetch_tags {
-            remote = remote.with_fetch_tags(fetch_tags);
+            clone_fetch_tags = if self.single_branch {
+                remote::fetch::Tags::Included
+            } else {
+                remote::fetch::Tags::All
+            }
+            .into();
         }
 
         // Add HEAD after the remote was written to config, we need it to know what to checkout later, and assure
@@ -125,6 +126,35 @@ impl PrepareFetch {
         )
         .expect("valid")
         .to_owned();
+
+        if self.single_branch {
+            let mut connection = remote.connect(remote::Direction::Fetch).await?;
+            if let Some(f) = self.configure_connection.as_mut() {
+                f(&mut connection).map_err(Error::RemoteConnection)?;
+            }
+            let ref_map = connection
+                .ref_map(&mut *progress, {
+                    let mut opts = self.fetch_options.clone();
+                    opts.extra_refspecs.push(head_refspec.clone());
+                    opts
+                })
+                .await?;
+            if let Some(branch) = util::remote_head_branch(&ref_map.remote_refs) {
+                remote
+                    .replace_refspecs(
+                        Some(format!("+refs/heads/{branch}:refs/remotes/{remote_name}/{branch}").as_str()),
+                        remote::Direction::Fetch,
+                    )
+                    .expect("valid branch names make valid refspecs");
+            }
+        }
+
+        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone(), self.filter.as_deref())?;
+
+        // Now we are free to apply remote configuration we don't want to be written to disk.
+        if let Some(fetch_tags) = clone_fetch_tags {
+            remote = remote.with_fetch_tags(fetch_tags);
+        }
         let pending_pack: remote::fetch::Prepare<'_, '_, _> = {
             let mut connection = remote.connect(remote::Direction::Fetch).await?;
             if let Some(f) = self.configure_connection.as_mut() {
@@ -154,6 +184,7 @@ impl PrepareFetch {
                 message: reflog_message.clone(),
             })
             .with_shallow(self.shallow.clone())
+            .with_filter(self.filter.clone())
             .receive_inner(progress, should_interrupt)
             .await?;
 
@@ -20,9 +20,21 @@ enum WriteMode {
 pub fn write_remote_to_local_config_file(
     remote: &mut crate::Remote<'_>,
     remote_name: BString,
+    filter: Option<&str>,
 ) -> Result<gix_config::File<'static>, Error> {
     let mut config = gix_config::File::new(local_config_meta(remote.repo));
-    remote.save_as_to(remote_name, &mut config)?;
+    remote.save_as_to(remote_name.clone(), &mut config)?;
+    if let Some(filter) = filter {
+        use crate::config::tree::Remote;
+        for (key, value) in [
+            (Remote::PROMISOR.name, "true"),
+            (Remote::PARTIAL_CLONE_FILTER.name, filter),
+        ] {
+            config
+                .set_raw_value("remote", Some(remote_name.as_ref()), key, value)
+                .expect("remote names are valid subsection names, and keys are statically known");
+        }
+    }
 
     write_to_local_config(&config, WriteMode::Append)?;
     Ok(config)
@@ -58,6 +70,21 @@ pub fn append_config_to_repo_config(repo: &mut Repository, config: gix_config::F
     repo_config.append(config);
 }
 
+/// Return the short name of the local branch that the remote `HEAD` points to, if it is known.
+pub fn remote_head_branch(remote_refs: &[gix_protocol::handshake::Ref]) -> Option<&BStr> {
+    remote_refs.iter().find_map(|r| match r {
+        gix_protocol::handshake::Ref::Symbolic {
+            full_ref_name, target, ..
+        }
+        | gix_protocol::handshake::Ref::Unborn { full_ref_name, target }
+            if full_ref_name == "HEAD" =>
+        {
+            target.strip_prefix(b"refs/heads/").map(ByteSlice::as_bstr)
+        }
+        _ => None,
+    })
+}
+
 /// HEAD cannot be written by means of refspec by design, so we have to do it manually here. Also create the pointed-to ref
 /// if we have to, as it might not have been naturally included in the ref-specs.
 pub fn update_head(
@@ -103,8 +130,7 @@ pub fn update_head(
                 head_ref_name: referent.to_owned(),
                 source: err,
             })?;
-            repo.refs
-                .transaction()
+            repo.reference_transaction()
                 .packed_refs(gix_ref::file::transaction::PackedRefs::DeletionsAndNonSymbolicUpdates(
                     Box::new(&repo.objects),
                 ))
@@ -34,6 +34,12 @@ pub struct PrepareFetch {
     /// How to handle shallow clones
     #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
     shallow: remote::fetch::Shallow,
+    /// If `true`, only fetch the branch that the remote `HEAD` points to.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    single_branch: bool,
+    /// The filter specification to perform a partial clone with, if set.
+    #[cfg_attr(not(feature = "blocking-network-client"), allow(dead_code))]
+    filter: Option<String>,
 }
 
 /// The error returned by [`PrepareFetch::new()`].
@@ -132,6 +138,8 @@ impl PrepareFetch {
             #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
             configure_connection: None,
             shallow: remote::fetch::Shallow::NoChange,
+            single_branch: false,
+            filter: None,
         })
     }
 }
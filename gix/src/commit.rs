@@ -31,7 +31,11 @@ pub mod describe {
     use gix_hash::ObjectId;
     use gix_hashtable::HashMap;
 
-    use crate::{bstr::BStr, ext::ObjectIdExt, Repository};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        ext::ObjectIdExt,
+        Repository,
+    };
 
     /// The result of [`try_resolve()`][Platform::try_resolve()].
     pub struct Resolution<'repo> {
@@ -100,7 +104,35 @@ pub mod describe {
     }
 
     impl SelectRef {
-        fn names(&self, repo: &Repository) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
+        /// Return `true` if the reference `name` may be used as a name according to the `include` and `exclude` patterns,
+        /// which are matched against tags, and branches if all references are selected, without their prefixes.
+        ///
+        /// Without patterns, all references are selectable.
+        fn is_selectable(&self, name: &gix_ref::FullNameRef, include: &[BString], exclude: &[BString]) -> bool {
+            if include.is_empty() && exclude.is_empty() {
+                return true;
+            }
+            let name = name.as_bstr();
+            let short_name = name.strip_prefix(b"refs/tags/").or_else(|| match self {
+                SelectRef::AllRefs => name
+                    .strip_prefix(b"refs/heads/")
+                    .or_else(|| name.strip_prefix(b"refs/remotes/")),
+                SelectRef::AnnotatedTags | SelectRef::AllTags => None,
+            });
+            let Some(short_name) = short_name else {
+                return false;
+            };
+            let matches =
+                |pattern: &BString| gix_glob::wildmatch(pattern.as_ref(), short_name.as_bstr(), Default::default());
+            !exclude.iter().any(matches) && (include.is_empty() || include.iter().any(matches))
+        }
+
+        fn names(
+            &self,
+            repo: &Repository,
+            include: &[BString],
+            exclude: &[BString],
+        ) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
             let platform = repo.references()?;
 
             Ok(match self {
@@ -111,6 +143,7 @@ pub mod describe {
                         _ => unreachable!(),
                     }
                     .filter_map(Result::ok)
+                    .filter(|r| self.is_selectable(r.name(), include, exclude))
                     .filter_map(|mut r: crate::Reference<'_>| {
                         let target_id = r.target().try_id().map(ToOwned::to_owned);
                         let peeled_id = r.peel_to_id_in_place().ok()?;
@@ -146,6 +179,7 @@ pub mod describe {
                     let mut peeled_commits_and_tag_date: Vec<_> = platform
                         .tags()?
                         .filter_map(Result::ok)
+                        .filter(|r| self.is_selectable(r.name(), include, exclude))
                         .filter_map(|r: crate::Reference<'_>| {
                             // TODO: we assume direct refs for tags, which is the common case, but it doesn't have to be
                             //       so rather follow symrefs till the first object and then peel tags after the first object was found.
@@ -177,6 +211,8 @@ pub mod describe {
         pub(crate) first_parent: bool,
         pub(crate) id_as_fallback: bool,
         pub(crate) max_candidates: usize,
+        pub(crate) include_patterns: Vec<BString>,
+        pub(crate) exclude_patterns: Vec<BString>,
     }
 
     impl<'repo> Platform<'repo> {
@@ -198,6 +234,21 @@ pub mod describe {
             self
         }
 
+        /// Only use tags whose name without the `refs/tags/` prefix matches at least one of the given glob `patterns`, similar to
+        /// `git describe --match`. With [`SelectRef::AllRefs`], branches are matched without their `refs/heads/` or
+        /// `refs/remotes/` prefix, and all other references are ignored as long as patterns are set.
+        pub fn include_patterns(mut self, patterns: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+            self.include_patterns = patterns.into_iter().map(Into::into).collect();
+            self
+        }
+
+        /// Don't use tags whose name matches any of the given glob `patterns`, which are matched like
+        /// [include patterns](Self::include_patterns()), similar to `git describe --exclude`.
+        pub fn exclude_patterns(mut self, patterns: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+            self.exclude_patterns = patterns.into_iter().map(Into::into).collect();
+            self
+        }
+
         /// If true, even if no candidate is available a format will always be produced.
         pub fn id_as_fallback(mut self, use_fallback: bool) -> Self {
             self.id_as_fallback = use_fallback;
@@ -228,7 +279,9 @@ pub mod describe {
                 &self.id,
                 &mut graph,
                 gix_revision::describe::Options {
-                    name_by_oid: self.select.names(self.repo)?,
+                    name_by_oid: self
+                        .select
+                        .names(self.repo, &self.include_patterns, &self.exclude_patterns)?,
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
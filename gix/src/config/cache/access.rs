@@ -1,6 +1,7 @@
 #![allow(clippy::result_large_err)]
 use std::{borrow::Cow, path::PathBuf, time::Duration};
 
+use gix_features::zlib::{Backend, Compression};
 use gix_lock::acquire::Fail;
 
 use crate::{
@@ -9,7 +10,7 @@ use crate::{
     config::{
         boolean,
         cache::util::{ApplyLeniency, ApplyLeniencyDefaultValue},
-        tree::{Core, Key},
+        tree::{keys, Core, Key, Pack},
         Cache,
     },
     remote,
@@ -203,6 +204,50 @@ impl Cache {
         Ok((out[0], out[1]))
     }
 
+    /// The compression level for loose objects as set by `core.looseCompression` or `core.compression`,
+    /// or the fastest level if neither is set.
+    pub(crate) fn loose_compression(&self) -> Result<Compression, config::key::GenericError> {
+        self.compression_level(&[&Core::LOOSE_COMPRESSION, &Core::COMPRESSION])
+    }
+
+    /// The compression level for objects recompressed into packs as set by `pack.compression` or `core.compression`,
+    /// or the fastest level if neither is set.
+    pub(crate) fn pack_compression(&self) -> Result<Compression, config::key::GenericError> {
+        self.compression_level(&[&Pack::COMPRESSION, &Core::COMPRESSION])
+    }
+
+    /// Return the level of the first of `keys` that is set.
+    fn compression_level(
+        &self,
+        keys: &[&'static keys::CompressionLevel],
+    ) -> Result<Compression, config::key::GenericError> {
+        for key in keys {
+            let level = self
+                .resolved
+                .integer_filter_by_key(key.logical_name().as_str(), &mut self.filter_config_section.clone())
+                .map(|res| key.try_into_compression(res))
+                .transpose()
+                .with_leniency(self.lenient_config)?;
+            if let Some(level) = level {
+                return Ok(level);
+            }
+        }
+        Ok(Compression::fast())
+    }
+
+    /// The zlib implementation to compress objects with as set by `gitoxide.core.zlibBackend`,
+    /// or the one selected at compile time if unset.
+    pub(crate) fn zlib_backend(&self) -> Result<Backend, config::zlib_backend::Error> {
+        let key = &config::tree::gitoxide::Core::ZLIB_BACKEND;
+        Ok(self
+            .resolved
+            .string_filter_by_key(key.logical_name().as_str(), &mut self.filter_config_section.clone())
+            .map(|name| key.try_into_zlib_backend(name))
+            .transpose()
+            .with_leniency(self.lenient_config)?
+            .unwrap_or_else(Backend::compiled))
+    }
+
     /// The path to the user-level excludes file to ignore certain files in the worktree.
     #[cfg(feature = "excludes")]
     pub(crate) fn excludes_file(&self) -> Option<Result<PathBuf, gix_config::path::interpolate::Error>> {
@@ -211,6 +256,14 @@ impl Cache {
             .into()
     }
 
+    /// The path to the directory containing hooks, if configured.
+    #[cfg(feature = "command")]
+    pub(crate) fn hooks_path(&self) -> Option<Result<PathBuf, gix_config::path::interpolate::Error>> {
+        self.trusted_file_path("core", None, Core::HOOKS_PATH.name)?
+            .map(std::borrow::Cow::into_owned)
+            .into()
+    }
+
     /// A helper to obtain a file from trusted configuration at `section_name`, `subsection_name`, and `key`, which is interpolated
     /// if present.
     pub(crate) fn trusted_file_path(
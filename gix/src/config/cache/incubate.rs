@@ -1,6 +1,6 @@
 #![allow(clippy::result_large_err)]
 
-use super::{util, Error};
+use super::{util, Error, FileState};
 use crate::config::cache::util::{ApplyLeniency, ApplyLeniencyDefaultValue};
 use crate::config::tree::{Core, Extensions, Key};
 
@@ -9,6 +9,8 @@ use crate::config::tree::{Core, Extensions, Key};
 pub(crate) struct StageOne {
     pub git_dir_config: gix_config::File<'static>,
     pub buf: Vec<u8>,
+    /// The repository-local configuration files along with their state before reading them.
+    pub files: Vec<(std::path::PathBuf, Option<FileState>)>,
 
     pub is_bare: bool,
     pub lossy: Option<bool>,
@@ -28,8 +30,11 @@ impl StageOne {
         lenient: bool,
     ) -> Result<Self, Error> {
         let mut buf = Vec::with_capacity(512);
+        let mut files = Vec::new();
+        let config_path = common_dir.join("config");
+        files.push((config_path.clone(), FileState::at(&config_path)));
         let mut config = load_config(
-            common_dir.join("config"),
+            config_path,
             &mut buf,
             gix_config::Source::Local,
             git_dir_trust,
@@ -63,8 +68,10 @@ impl StageOne {
             lenient,
         )?;
         if extension_worktree {
+            let worktree_config_path = git_dir.join("config.worktree");
+            files.push((worktree_config_path.clone(), FileState::at(&worktree_config_path)));
             let worktree_config = load_config(
-                git_dir.join("config.worktree"),
+                worktree_config_path,
                 &mut buf,
                 gix_config::Source::Worktree,
                 git_dir_trust,
@@ -94,6 +101,7 @@ impl StageOne {
         Ok(StageOne {
             git_dir_config: config,
             buf,
+            files,
             is_bare,
             lossy,
             object_hash,
@@ -3,7 +3,7 @@ use std::{borrow::Cow, ffi::OsString};
 
 use gix_sec::Permission;
 
-use super::{interpolate_context, util, Error, StageOne};
+use super::{interpolate_context, util, Error, FileState, StageOne};
 use crate::{
     bstr::BString,
     config,
@@ -23,6 +23,7 @@ impl Cache {
         StageOne {
             git_dir_config,
             mut buf,
+            mut files,
             lossy,
             is_bare,
             object_hash,
@@ -98,6 +99,11 @@ impl Cache {
                 source: *source,
                 level: 0,
                 trust: gix_sec::Trust::Full,
+            })
+            .inspect(|meta| {
+                if let Some(path) = meta.path.as_deref() {
+                    files.push((path.to_owned(), FileState::at(path)));
+                }
             });
 
             let err_on_nonexisting_paths = false;
@@ -119,6 +125,11 @@ impl Cache {
             let local_meta = git_dir_config.meta_owned();
             globals.append(git_dir_config);
             globals.resolve_includes(options)?;
+            for path in globals.sections().filter_map(|section| section.meta().path.as_deref()) {
+                if !files.iter().any(|(known, _)| known == path) {
+                    files.push((path.to_owned(), FileState::at(path)));
+                }
+            }
             if use_env {
                 globals.append(gix_config::File::from_env(options)?.unwrap_or_default());
             }
@@ -147,6 +158,7 @@ impl Cache {
         let reflog = util::query_refupdates(&config, lenient_config)?;
         let refs_namespace = util::query_refs_namespace(&config, lenient_config)?;
         let ignore_case = config_bool(&config, &Core::IGNORE_CASE, "core.ignoreCase", false, lenient_config)?;
+        let quote_path = config_bool(&config, &Core::QUOTE_PATH, "core.quotePath", true, lenient_config)?;
         let use_multi_pack_index = config_bool(
             &config,
             &Core::MULTIPACK_INDEX,
@@ -154,6 +166,8 @@ impl Cache {
             true,
             lenient_config,
         )?;
+        let use_replace_refs =
+            config::shared::is_replace_refs_enabled(&config, lenient_config, filter_config_section)?.unwrap_or(true);
         #[cfg(feature = "revision")]
         let object_kind_hint = util::disambiguate_hint(&config, lenient_config)?;
         let (static_pack_cache_limit_bytes, pack_cache_bytes, object_cache_bytes) =
@@ -162,6 +176,7 @@ impl Cache {
         Ok(Cache {
             resolved: config.into(),
             use_multi_pack_index,
+            use_replace_refs,
             object_hash,
             #[cfg(feature = "revision")]
             object_kind_hint,
@@ -172,11 +187,13 @@ impl Cache {
             refs_namespace,
             is_bare,
             ignore_case,
+            quote_path,
             hex_len,
             filter_config_section,
             environment,
             lenient_config,
             attributes,
+            files,
             user_agent: Default::default(),
             personas: Default::default(),
             url_rewrite: Default::default(),
@@ -219,6 +236,7 @@ impl Cache {
             false,
             self.lenient_config,
         )?;
+        let quote_path = config_bool(config, &Core::QUOTE_PATH, "core.quotePath", true, self.lenient_config)?;
 
         #[cfg(feature = "revision")]
         {
@@ -227,9 +245,14 @@ impl Cache {
         }
         let reflog = util::query_refupdates(config, self.lenient_config)?;
         let refs_namespace = util::query_refs_namespace(config, self.lenient_config)?;
+        let use_replace_refs =
+            config::shared::is_replace_refs_enabled(config, self.lenient_config, self.filter_config_section)?
+                .unwrap_or(true);
 
         self.hex_len = hex_len;
+        self.use_replace_refs = use_replace_refs;
         self.ignore_case = ignore_case;
+        self.quote_path = quote_path;
         self.reflog = reflog;
         self.refs_namespace = refs_namespace;
 
@@ -305,6 +328,7 @@ impl crate::Repository {
     fn apply_changed_values(&mut self) {
         self.refs.write_reflog = util::reflog_or_default(self.config.reflog, self.work_dir().is_some());
         self.refs.namespace.clone_from(&self.config.refs_namespace);
+        self.set_object_replacements_enabled(self.config.use_replace_refs);
     }
 }
 
@@ -439,6 +463,10 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Core::SHALLOW_FILE;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Core::INDEX_FILE;
+                    (env(key), key.name)
+                },
                 {
                     let key = &gitoxide::Core::REFS_NAMESPACE;
                     (env(key), key.name)
@@ -507,6 +535,10 @@ fn apply_environment_overrides(
                     let key = &gitoxide::Objects::CACHE_LIMIT;
                     (env(key), key.name)
                 },
+                {
+                    let key = &gitoxide::Objects::DIRECTORY;
+                    (env(key), key.name)
+                },
             ],
         ),
         (
@@ -593,10 +625,6 @@ fn apply_environment_overrides(
                 let key = &Core::SSH_COMMAND;
                 (env(key), key.name, git_prefix)
             },
-            {
-                let key = &Core::USE_REPLACE_REFS;
-                (env(key), key.name, objects)
-            },
         ] {
             if let Some(value) = var_as_bstring(var, permission) {
                 section.push_with_comment(
@@ -607,6 +635,19 @@ fn apply_environment_overrides(
             }
         }
 
+        {
+            let key = &Core::USE_REPLACE_REFS;
+            let var = env(key);
+            // Just like in `git`, the presence of the variable disables replacements, no matter its value.
+            if var_as_bstring(var, objects).is_some() {
+                section.push_with_comment(
+                    key.name.try_into().expect("statically known to be valid"),
+                    Some("false".into()),
+                    format!("from {var}").as_str(),
+                );
+            }
+        }
+
         if section.num_values() == 0 {
             let id = section.id();
             env_override.remove_section_by_id(id);
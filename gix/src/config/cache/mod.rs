@@ -13,6 +13,24 @@ impl std::fmt::Debug for Cache {
 
 mod access;
 
+/// The modification time and size of a configuration file, to learn if it changed since it was last seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileState {
+    modified: Option<std::time::SystemTime>,
+    len: u64,
+}
+
+impl FileState {
+    /// Return the state of the file at `path`, or `None` if it doesn't exist or can't be accessed.
+    pub fn at(path: &std::path::Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(FileState {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
 pub(crate) mod util;
 
 pub(crate) use util::interpolate_context;
@@ -496,6 +496,13 @@ pub mod refs_namespace {
     pub type Error = super::key::Error<gix_validate::reference::name::Error, 'v', 'i'>;
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod zlib_backend {
+    /// The error produced when failing to select a zlib backend from the configuration.
+    pub type Error = super::key::Error<gix_features::zlib::backend::Error, 'v', 'i'>;
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod ssl_version {
@@ -593,6 +600,8 @@ pub(crate) struct Cache {
     pub object_hash: gix_hash::Kind,
     /// If true, multi-pack indices, whether present or not, may be used by the object database.
     pub use_multi_pack_index: bool,
+    /// If true, objects are replaced by their replacements when looked up in the object database, as set by `core.useReplaceRefs`.
+    pub use_replace_refs: bool,
     /// The representation of `core.logallrefupdates`, or `None` if the variable wasn't set.
     pub reflog: Option<gix_ref::store::WriteReflog>,
     /// The representation of `gitoxide.core.refsNamespace`, or `None` if the variable wasn't set.
@@ -626,12 +635,17 @@ pub(crate) struct Cache {
     pub object_kind_hint: Option<crate::revision::spec::parse::ObjectKindHint>,
     /// If true, we are on a case-insensitive file system.
     pub ignore_case: bool,
+    /// If true, bytes outside of ASCII are escaped when quoting paths for output, as controlled by `core.quotePath`.
+    pub quote_path: bool,
     /// If true, we should default what's possible if something is misconfigured, on case by case basis, to be more resilient.
     /// Also available in options! Keep in sync!
     pub lenient_config: bool,
     #[cfg_attr(not(feature = "worktree-mutation"), allow(dead_code))]
     attributes: crate::open::permissions::Attributes,
     environment: crate::open::permissions::Environment,
+    /// The configuration files that were read, or that would have been read if they existed, along with their state
+    /// at the time, to learn if the configuration changed on disk.
+    pub(crate) files: Vec<(std::path::PathBuf, Option<cache::FileState>)>,
     // TODO: make core.precomposeUnicode available as well.
 }
 
@@ -152,6 +152,9 @@ pub type Time = Any<validate::Time>;
 /// The `core.(filesRefLockTimeout|packedRefsTimeout)` keys, or any other lock timeout for that matter.
 pub type LockTimeout = Any<validate::LockTimeout>;
 
+/// The `core.(compression|looseCompression)` and `pack.compression` keys, or any other zlib compression level.
+pub type CompressionLevel = Any<validate::CompressionLevel>;
+
 /// Keys specifying durations in milliseconds.
 pub type DurationInMilliseconds = Any<validate::DurationInMilliseconds>;
 
@@ -256,6 +259,35 @@ mod lock_timeout {
     }
 }
 
+mod compression_level {
+    use gix_features::zlib::Compression;
+
+    use crate::{
+        config,
+        config::tree::{keys::CompressionLevel, Section},
+    };
+
+    impl CompressionLevel {
+        /// Create a new instance.
+        pub const fn new_compression_level(name: &'static str, section: &'static dyn Section) -> Self {
+            Self::new_with_validate(name, section, super::validate::CompressionLevel)
+        }
+
+        /// Return the zlib compression level, where `-1` means the default level and `0` to `9` are taken as is.
+        pub fn try_into_compression(
+            &'static self,
+            value: Result<i64, gix_config::value::Error>,
+        ) -> Result<Compression, config::key::GenericError> {
+            let value = value.map_err(|err| config::key::GenericError::from(self).with_source(err))?;
+            Ok(match value {
+                -1 => Compression::default(),
+                level @ 0..=9 => Compression::new(level as u32),
+                _ => return Err(config::key::GenericError::from(self)),
+            })
+        }
+    }
+}
+
 mod refspecs {
     use crate::config::tree::{
         keys::{validate, FetchRefSpec, PushRefSpec},
@@ -609,6 +641,18 @@ pub mod validate {
         }
     }
 
+    /// zlib compression levels.
+    pub struct CompressionLevel;
+    impl Validate for CompressionLevel {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            let value = gix_config::Integer::try_from(value)?
+                .to_decimal()
+                .ok_or_else(|| format!("integer {value} cannot be represented as integer"));
+            super::super::Core::COMPRESSION.try_into_compression(Ok(value?))?;
+            Ok(())
+        }
+    }
+
     /// Durations in milliseconds.
     pub struct DurationInMilliseconds;
     impl Validate for DurationInMilliseconds {
@@ -37,6 +37,12 @@ pub(crate) mod root {
         pub const EXTENSIONS: sections::Extensions = sections::Extensions;
         /// The `fetch` section.
         pub const FETCH: sections::Fetch = sections::Fetch;
+        /// The `fsck` section.
+        #[cfg(feature = "fsck")]
+        pub const FSCK: sections::Fsck = sections::Fsck;
+        /// The `gc` section.
+        #[cfg(feature = "maintenance")]
+        pub const GC: sections::Gc = sections::Gc;
         /// The `gitoxide` section.
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
         /// The `http` section.
@@ -47,6 +53,9 @@ pub(crate) mod root {
         pub const INIT: sections::Init = sections::Init;
         /// The `mailmap` section.
         pub const MAILMAP: sections::Mailmap = sections::Mailmap;
+        /// The `maintenance` section.
+        #[cfg(feature = "maintenance")]
+        pub const MAINTENANCE: sections::Maintenance = sections::Maintenance;
         /// The `pack` section.
         pub const PACK: sections::Pack = sections::Pack;
         /// The `protocol` section.
@@ -62,6 +71,8 @@ pub(crate) mod root {
         /// The `status` section.
         #[cfg(feature = "status")]
         pub const STATUS: sections::Status = sections::Status;
+        /// The `trailer` section.
+        pub const TRAILER: sections::Trailer = sections::Trailer;
         /// The `user` section.
         pub const USER: sections::User = sections::User;
         /// The `url` section.
@@ -81,11 +92,17 @@ pub(crate) mod root {
                 &Self::DIFF,
                 &Self::EXTENSIONS,
                 &Self::FETCH,
+                #[cfg(feature = "fsck")]
+                &Self::FSCK,
+                #[cfg(feature = "maintenance")]
+                &Self::GC,
                 &Self::GITOXIDE,
                 &Self::HTTP,
                 &Self::INDEX,
                 &Self::INIT,
                 &Self::MAILMAP,
+                #[cfg(feature = "maintenance")]
+                &Self::MAINTENANCE,
                 &Self::PACK,
                 &Self::PROTOCOL,
                 &Self::PUSH,
@@ -94,6 +111,7 @@ pub(crate) mod root {
                 &Self::SSH,
                 #[cfg(feature = "status")]
                 &Self::STATUS,
+                &Self::TRAILER,
                 &Self::USER,
                 &Self::URL,
             ]
@@ -103,12 +121,16 @@ pub(crate) mod root {
 
 mod sections;
 pub use sections::{
-    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, trailer,
+    Author, Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init,
+    Mailmap, Pack, Protocol, Push, Remote, Safe, Ssh, Trailer, Url, User,
 };
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
+#[cfg(feature = "fsck")]
+pub use sections::{fsck, Fsck};
+#[cfg(feature = "maintenance")]
+pub use sections::{gc, maintenance, Gc, Maintenance};
 #[cfg(feature = "status")]
 pub use sections::{status, Status};
 
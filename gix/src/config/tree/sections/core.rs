@@ -14,6 +14,9 @@ impl Core {
     /// The `core.checkStat` key.
     pub const CHECK_STAT: CheckStat =
         CheckStat::new_with_validate("checkStat", &config::Tree::CORE, validate::CheckStat);
+    /// The `core.compression` key.
+    pub const COMPRESSION: keys::CompressionLevel =
+        keys::CompressionLevel::new_compression_level("compression", &config::Tree::CORE);
     /// The `core.deltaBaseCacheLimit` key.
     pub const DELTA_BASE_CACHE_LIMIT: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("deltaBaseCacheLimit", &config::Tree::CORE)
@@ -36,6 +39,10 @@ impl Core {
         keys::LockTimeout::new_lock_timeout("packedRefsTimeout", &config::Tree::CORE);
     /// The `core.multiPackIndex` key.
     pub const MULTIPACK_INDEX: keys::Boolean = keys::Boolean::new_boolean("multiPackIndex", &config::Tree::CORE);
+    /// The `core.looseCompression` key.
+    pub const LOOSE_COMPRESSION: keys::CompressionLevel =
+        keys::CompressionLevel::new_compression_level("looseCompression", &config::Tree::CORE)
+            .with_note("if unset, `core.compression` is used, or the fastest level if that is unset as well");
     /// The `core.logAllRefUpdates` key.
     pub const LOG_ALL_REF_UPDATES: LogAllRefUpdates =
         LogAllRefUpdates::new_with_validate("logAllRefUpdates", &config::Tree::CORE, validate::LogAllRefUpdates);
@@ -44,6 +51,8 @@ impl Core {
     /// Needs application to use [`env::args_os`][crate::env::args_os()] to conform all input paths before they are used.
     pub const PRECOMPOSE_UNICODE: keys::Boolean = keys::Boolean::new_boolean("precomposeUnicode", &config::Tree::CORE)
         .with_note("application needs to conform all program input by using gix::env::args_os()");
+    /// The `core.quotePath` key, which controls whether [`gix_quote::ansi_c::quote()`] should escape non-ASCII bytes in paths.
+    pub const QUOTE_PATH: keys::Boolean = keys::Boolean::new_boolean("quotePath", &config::Tree::CORE);
     /// The `core.protectHFS` key.
     pub const PROTECT_HFS: keys::Boolean = keys::Boolean::new_boolean("protectHFS", &config::Tree::CORE);
     /// The `core.protectNTFS` key.
@@ -69,6 +78,8 @@ impl Core {
     pub const ATTRIBUTES_FILE: keys::Path =
         keys::Path::new_path("attributesFile", &config::Tree::CORE)
             .with_deviation("for checkout - it's already queried but needs building of attributes group, and of course support during checkout");
+    /// The `core.hooksPath` key.
+    pub const HOOKS_PATH: keys::Path = keys::Path::new_path("hooksPath", &config::Tree::CORE);
     /// The `core.sshCommand` key.
     pub const SSH_COMMAND: keys::Executable = keys::Executable::new_executable("sshCommand", &config::Tree::CORE)
         .with_environment_override("GIT_SSH_COMMAND");
@@ -106,6 +117,7 @@ impl Section for Core {
             &Self::BARE,
             &Self::BIG_FILE_THRESHOLD,
             &Self::CHECK_STAT,
+            &Self::COMPRESSION,
             &Self::DELTA_BASE_CACHE_LIMIT,
             &Self::DISAMBIGUATE,
             &Self::EDITOR,
@@ -114,8 +126,10 @@ impl Section for Core {
             &Self::FILES_REF_LOCK_TIMEOUT,
             &Self::PACKED_REFS_TIMEOUT,
             &Self::MULTIPACK_INDEX,
+            &Self::LOOSE_COMPRESSION,
             &Self::LOG_ALL_REF_UPDATES,
             &Self::PRECOMPOSE_UNICODE,
+            &Self::QUOTE_PATH,
             &Self::REPOSITORY_FORMAT_VERSION,
             &Self::SYMLINKS,
             &Self::TRUST_C_TIME,
@@ -125,6 +139,7 @@ impl Section for Core {
             &Self::ASKPASS,
             &Self::EXCLUDES_FILE,
             &Self::ATTRIBUTES_FILE,
+            &Self::HOOKS_PATH,
             &Self::SSH_COMMAND,
             &Self::USE_REPLACE_REFS,
             &Self::COMMIT_GRAPH,
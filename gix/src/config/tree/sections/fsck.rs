@@ -0,0 +1,59 @@
+use crate::{
+    config,
+    config::tree::{keys, Fsck, Key, Section},
+};
+
+impl Fsck {
+    /// The `fsck.skipList` key.
+    pub const SKIP_LIST: keys::Path = keys::Path::new_path("skipList", &config::Tree::FSCK);
+    /// The `fsck.<msg-id>` key, which sets the severity of the problem with the given message id.
+    pub const MSG_ID: MsgId = MsgId::new_with_validate("<msg-id>", &config::Tree::FSCK, validate::MsgId);
+}
+
+impl Section for Fsck {
+    fn name(&self) -> &str {
+        "fsck"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::SKIP_LIST, &Self::MSG_ID]
+    }
+}
+
+/// The `fsck.<msg-id>` key.
+pub type MsgId = keys::Any<validate::MsgId>;
+
+mod severity {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::BStr,
+        config::{key::GenericErrorWithValue, tree::sections::fsck::MsgId},
+        fsck,
+    };
+
+    impl MsgId {
+        /// Convert `value` into the severity of a problem, which is one of `error`, `warn` or `ignore`, case-insensitively.
+        pub fn try_into_severity(&'static self, value: Cow<'_, BStr>) -> Result<fsck::Severity, GenericErrorWithValue> {
+            fsck::Severity::from_name(value.as_ref())
+                .ok_or_else(|| GenericErrorWithValue::from_value(self, value.into_owned()))
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod validate {
+    use crate::{
+        bstr::BStr,
+        config::tree::{keys, Fsck},
+    };
+
+    pub struct MsgId;
+    impl keys::Validate for MsgId {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Fsck::MSG_ID.try_into_severity(value.into())?;
+            Ok(())
+        }
+    }
+}
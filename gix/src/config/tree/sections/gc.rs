@@ -0,0 +1,89 @@
+use crate::{
+    config,
+    config::tree::{keys, Gc, Key, Section},
+};
+
+impl Gc {
+    /// The `gc.auto` key.
+    pub const AUTO: keys::UnsignedInteger = keys::UnsignedInteger::new_unsigned_integer("auto", &config::Tree::GC);
+    /// The `gc.autoPackLimit` key.
+    pub const AUTO_PACK_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("autoPackLimit", &config::Tree::GC);
+    /// The `gc.pruneExpire` key.
+    pub const PRUNE_EXPIRE: Expiry = Expiry::new_with_validate("pruneExpire", &config::Tree::GC, validate::Expiry);
+    /// The `gc.reflogExpire` key.
+    pub const REFLOG_EXPIRE: Expiry = Expiry::new_with_validate("reflogExpire", &config::Tree::GC, validate::Expiry);
+    /// The `gc.reflogExpireUnreachable` key.
+    pub const REFLOG_EXPIRE_UNREACHABLE: Expiry =
+        Expiry::new_with_validate("reflogExpireUnreachable", &config::Tree::GC, validate::Expiry);
+}
+
+impl Section for Gc {
+    fn name(&self) -> &str {
+        "gc"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[
+            &Self::AUTO,
+            &Self::AUTO_PACK_LIMIT,
+            &Self::PRUNE_EXPIRE,
+            &Self::REFLOG_EXPIRE,
+            &Self::REFLOG_EXPIRE_UNREACHABLE,
+        ]
+    }
+}
+
+/// The `gc.pruneExpire`, `gc.reflogExpire` and `gc.reflogExpireUnreachable` keys.
+pub type Expiry = keys::Any<validate::Expiry>;
+
+mod expiry {
+    use std::{borrow::Cow, time::SystemTime};
+
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        config::{key::GenericErrorWithValue, tree::sections::gc::Expiry},
+    };
+
+    impl Expiry {
+        /// Convert `value` into the time before which items expire, with `now` being the reference for relative dates,
+        /// or `None` if items never expire.
+        ///
+        /// `never` and `false` mean that nothing expires, and `now` and `all` mean that everything expires.
+        /// Relative dates may be written like `90 days`, `2 weeks ago` or `2.weeks.ago`, and absolute dates are supported as well.
+        pub fn try_into_expiry(
+            &'static self,
+            value: Cow<'_, BStr>,
+            now: SystemTime,
+        ) -> Result<Option<gix_date::Time>, GenericErrorWithValue> {
+            let err = || GenericErrorWithValue::from_value(self, value.clone().into_owned());
+            let input = value.to_str().map_err(|_| err())?.trim();
+            Ok(match input.to_ascii_lowercase().as_str() {
+                "never" | "false" => None,
+                "now" | "all" => Some(gix_date::Time::new(
+                    now.duration_since(SystemTime::UNIX_EPOCH)
+                        .map_or(0, |duration| duration.as_secs() as gix_date::SecondsSinceUnixEpoch),
+                    0,
+                )),
+                _ => Some(gix_date::parse(input, Some(now)).map_err(|_| err())?),
+            })
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod validate {
+    use crate::{
+        bstr::BStr,
+        config::tree::{keys, Gc},
+    };
+
+    pub struct Expiry;
+    impl keys::Validate for Expiry {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Gc::REFLOG_EXPIRE.try_into_expiry(value.into(), std::time::SystemTime::now())?;
+            Ok(())
+        }
+    }
+}
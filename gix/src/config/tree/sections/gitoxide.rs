@@ -89,6 +89,23 @@ mod subsections {
         }
     }
 
+    /// The `gitoxide.core.zlibBackend` key.
+    pub type ZlibBackend = keys::Any<super::validate::ZlibBackend>;
+
+    impl ZlibBackend {
+        /// Derive the zlib backend identified by `name`, which must be available in this build.
+        pub fn try_into_zlib_backend(
+            &'static self,
+            name: std::borrow::Cow<'_, crate::bstr::BStr>,
+        ) -> Result<gix_features::zlib::Backend, crate::config::zlib_backend::Error> {
+            use crate::bstr::ByteSlice;
+            name.to_str_lossy()
+                .parse::<gix_features::zlib::Backend>()
+                .and_then(gix_features::zlib::Backend::try_available)
+                .map_err(|err| crate::config::zlib_backend::Error::from_value(self, name.into_owned()).with_source(err))
+        }
+    }
+
     impl Core {
         /// The `gitoxide.core.defaultPackCacheMemoryLimit` key.
         pub const DEFAULT_PACK_CACHE_MEMORY_LIMIT: keys::UnsignedInteger =
@@ -114,6 +131,11 @@ mod subsections {
                 "relative file paths will always be made relative to the git-common-dir, whereas `git` keeps them as is.",
             );
 
+        /// The `gitoxide.core.indexFile` key.
+        pub const INDEX_FILE: keys::Path = keys::Path::new_path("indexFile", &Gitoxide::CORE)
+            .with_environment_override("GIT_INDEX_FILE")
+            .with_note("relative file paths are relative to the current working directory, like in `git`");
+
         /// The `gitoxide.core.filterProcessDelay` key (default `true`).
         ///
         /// It controls whether or not long running filter driver processes can use the 'delay' capability.
@@ -133,6 +155,12 @@ mod subsections {
         pub const REFS_NAMESPACE: RefsNamespace =
             keys::Any::new_with_validate("refsNamespace", &Gitoxide::CORE, super::validate::RefsNamespace)
                 .with_environment_override("GIT_NAMESPACE");
+
+        /// The `gitoxide.core.zlibBackend` key, one of `miniz_oxide`, `zlib` or `zlib-ng`.
+        pub const ZLIB_BACKEND: ZlibBackend =
+            keys::Any::new_with_validate("zlibBackend", &Gitoxide::CORE, super::validate::ZlibBackend).with_note(
+                "Used to compress objects, where `miniz_oxide` is always available, or the backend selected at compile time otherwise",
+            );
     }
 
     impl Section for Core {
@@ -146,10 +174,12 @@ mod subsections {
                 &Self::USE_NSEC,
                 &Self::USE_STDEV,
                 &Self::SHALLOW_FILE,
+                &Self::INDEX_FILE,
                 &Self::PROTECT_WINDOWS,
                 &Self::FILTER_PROCESS_DELAY,
                 &Self::EXTERNAL_COMMAND_STDERR,
                 &Self::REFS_NAMESPACE,
+                &Self::ZLIB_BACKEND,
             ]
         }
 
@@ -431,6 +461,10 @@ mod subsections {
         /// The `gitoxide.objects.replaceRefBase` key.
         pub const REPLACE_REF_BASE: keys::Any =
             keys::Any::new("replaceRefBase", &Gitoxide::OBJECTS).with_environment_override("GIT_REPLACE_REF_BASE");
+        /// The `gitoxide.objects.directory` key.
+        pub const DIRECTORY: keys::Path = keys::Path::new_path("directory", &Gitoxide::OBJECTS)
+            .with_environment_override("GIT_OBJECT_DIRECTORY")
+            .with_note("relative paths are relative to the current working directory, like in `git`");
     }
 
     impl Section for Objects {
@@ -439,7 +473,7 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::CACHE_LIMIT, &Self::REPLACE_REF_BASE]
+            &[&Self::CACHE_LIMIT, &Self::REPLACE_REF_BASE, &Self::DIRECTORY]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
@@ -556,4 +590,12 @@ pub mod validate {
             Ok(())
         }
     }
+
+    pub struct ZlibBackend;
+    impl Validate for ZlibBackend {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Core::ZLIB_BACKEND.try_into_zlib_backend(value.into())?;
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,137 @@
+use crate::{
+    config,
+    config::tree::{keys, Key, Maintenance, Section, SubSectionRequirement},
+};
+
+const TASK_PARAMETER: Option<SubSectionRequirement> = Some(SubSectionRequirement::Parameter("task"));
+
+impl Maintenance {
+    /// The `maintenance.auto` key.
+    pub const AUTO: keys::Boolean = keys::Boolean::new_boolean("auto", &config::Tree::MAINTENANCE);
+    /// The `maintenance.strategy` key.
+    pub const STRATEGY: Strategy =
+        Strategy::new_with_validate("strategy", &config::Tree::MAINTENANCE, validate::Strategy);
+
+    /// The `maintenance.<task>.enabled` key.
+    pub const TASK_ENABLED: keys::Boolean =
+        keys::Boolean::new_boolean("enabled", &config::Tree::MAINTENANCE).with_subsection_requirement(TASK_PARAMETER);
+    /// The `maintenance.<task>.schedule` key.
+    pub const TASK_SCHEDULE: Schedule =
+        Schedule::new_with_validate("schedule", &config::Tree::MAINTENANCE, validate::Schedule)
+            .with_subsection_requirement(TASK_PARAMETER);
+    /// The `maintenance.<task>.auto` key.
+    pub const TASK_AUTO: AutoLimit =
+        AutoLimit::new_with_validate("auto", &config::Tree::MAINTENANCE, validate::AutoLimit)
+            .with_subsection_requirement(TASK_PARAMETER);
+}
+
+impl Section for Maintenance {
+    fn name(&self) -> &str {
+        "maintenance"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[
+            &Self::AUTO,
+            &Self::STRATEGY,
+            &Self::TASK_ENABLED,
+            &Self::TASK_SCHEDULE,
+            &Self::TASK_AUTO,
+        ]
+    }
+}
+
+/// The `maintenance.strategy` key.
+pub type Strategy = keys::Any<validate::Strategy>;
+
+/// The `maintenance.<task>.schedule` key.
+pub type Schedule = keys::Any<validate::Schedule>;
+
+/// The `maintenance.<task>.auto` key.
+pub type AutoLimit = keys::Any<validate::AutoLimit>;
+
+mod values {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        config,
+        config::{
+            key::GenericErrorWithValue,
+            tree::sections::maintenance::{AutoLimit, Schedule, Strategy},
+        },
+        maintenance,
+    };
+
+    impl Strategy {
+        /// Convert `value` into the strategy that determines the default schedule of tasks, which is `none` or `incremental`.
+        pub fn try_into_strategy(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<maintenance::Strategy, GenericErrorWithValue> {
+            Ok(match value.to_ascii_lowercase().as_bytes() {
+                b"none" => maintenance::Strategy::None,
+                b"incremental" => maintenance::Strategy::Incremental,
+                _ => return Err(GenericErrorWithValue::from_value(self, value.into_owned())),
+            })
+        }
+    }
+
+    impl Schedule {
+        /// Convert `value` into the frequency at which a task is scheduled, which is `hourly`, `daily` or `weekly`.
+        pub fn try_into_schedule(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<maintenance::Schedule, GenericErrorWithValue> {
+            maintenance::Schedule::from_name(value.as_ref())
+                .ok_or_else(|| GenericErrorWithValue::from_value(self, value.into_owned()))
+        }
+    }
+
+    impl AutoLimit {
+        /// Convert `value` into the limit from which on a task runs automatically, with `0` meaning never and negative values
+        /// meaning always.
+        pub fn try_into_auto_limit(
+            &'static self,
+            value: Result<i64, gix_config::value::Error>,
+        ) -> Result<i64, config::key::GenericError> {
+            value.map_err(|err| config::key::GenericError::from(self).with_source(err))
+        }
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod validate {
+    use crate::{
+        bstr::BStr,
+        config::tree::{keys, Maintenance},
+    };
+
+    pub struct Strategy;
+    impl keys::Validate for Strategy {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Maintenance::STRATEGY.try_into_strategy(value.into())?;
+            Ok(())
+        }
+    }
+
+    pub struct Schedule;
+    impl keys::Validate for Schedule {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Maintenance::TASK_SCHEDULE.try_into_schedule(value.into())?;
+            Ok(())
+        }
+    }
+
+    pub struct AutoLimit;
+    impl keys::Validate for AutoLimit {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            Maintenance::TASK_AUTO.try_into_auto_limit(gix_config::Integer::try_from(value).and_then(|int| {
+                int.to_decimal()
+                    .ok_or_else(|| gix_config::value::Error::new("integer out of range", value))
+            }))?;
+            Ok(())
+        }
+    }
+}
@@ -52,6 +52,20 @@ pub mod extensions;
 pub struct Fetch;
 pub mod fetch;
 
+/// The `fsck` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "fsck")]
+pub struct Fsck;
+#[cfg(feature = "fsck")]
+pub mod fsck;
+
+/// The `gc` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "maintenance")]
+pub struct Gc;
+#[cfg(feature = "maintenance")]
+pub mod gc;
+
 /// The `gitoxide` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Gitoxide;
@@ -76,6 +90,13 @@ mod init;
 pub struct Mailmap;
 mod mailmap;
 
+/// The `maintenance` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "maintenance")]
+pub struct Maintenance;
+#[cfg(feature = "maintenance")]
+pub mod maintenance;
+
 /// The `pack` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Pack;
@@ -113,6 +134,11 @@ pub struct Status;
 #[cfg(feature = "status")]
 pub mod status;
 
+/// The `trailer` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Trailer;
+pub mod trailer;
+
 /// The `user` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct User;
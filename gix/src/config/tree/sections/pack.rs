@@ -9,6 +9,11 @@ impl Pack {
         keys::UnsignedInteger::new_unsigned_integer("threads", &config::Tree::PACK)
             .with_deviation("Leaving this key unspecified uses all available cores, instead of 1");
 
+    /// The `pack.compression` key.
+    pub const COMPRESSION: keys::CompressionLevel =
+        keys::CompressionLevel::new_compression_level("compression", &config::Tree::PACK)
+            .with_deviation("if unset and `core.compression` is unset as well, the fastest level is used instead of the zlib default");
+
     /// The `pack.indexVersion` key.
     pub const INDEX_VERSION: IndexVersion =
         IndexVersion::new_with_validate("indexVersion", &config::Tree::PACK, validate::IndexVersion);
@@ -42,7 +47,7 @@ impl Section for Pack {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::THREADS, &Self::INDEX_VERSION]
+        &[&Self::THREADS, &Self::COMPRESSION, &Self::INDEX_VERSION]
     }
 }
 
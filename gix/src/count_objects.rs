@@ -0,0 +1,36 @@
+//! Types for use with [`Repository::count_objects()`](crate::Repository::count_objects()).
+use std::path::PathBuf;
+
+/// Statistics about the object database of a repository, similar to what `git count-objects -v` prints.
+///
+/// All sizes are the length of the files in bytes, as opposed to the disk space they take up.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of loose objects.
+    pub loose_objects: usize,
+    /// The size of all loose object files.
+    pub loose_size: u64,
+    /// The amount of loose objects that are also contained in a pack, and could be removed with `git prune-packed`.
+    pub prune_packable: usize,
+    /// The amount of packs, i.e. pack data files with an index.
+    pub packs: usize,
+    /// The size of all pack data files and their indices.
+    pub pack_size: u64,
+    /// The amount of objects in all packs, with objects that are contained in multiple packs counted multiple times.
+    pub in_pack_objects: u64,
+    /// The paths to files in the object database that don't belong there, like temporary files or pack indices without
+    /// a pack data file, sorted by path.
+    pub garbage: Vec<PathBuf>,
+    /// The size of all [garbage](Self::garbage) files.
+    pub garbage_size: u64,
+}
+
+/// The error returned by [`Repository::count_objects()`](crate::Repository::count_objects()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    OpenPackIndex(#[from] gix_pack::index::init::Error),
+}
@@ -4,7 +4,7 @@ use std::path::Path;
 pub use gix_discover::*;
 use gix_macros::momo;
 
-use crate::{bstr::BString, ThreadSafeRepository};
+use crate::ThreadSafeRepository;
 
 /// The error returned by [`crate::discover()`].
 #[derive(Debug, thiserror::Error)]
@@ -37,15 +37,24 @@ impl ThreadSafeRepository {
         directory: impl AsRef<Path>,
         options: upwards::Options<'_>,
         trust_map: gix_sec::trust::Mapping<crate::open::Options>,
+    ) -> Result<Self, Error> {
+        Self::discover_opts_with_overrides(directory.as_ref(), options, trust_map, Default::default())
+    }
+
+    fn discover_opts_with_overrides(
+        directory: &Path,
+        options: upwards::Options<'_>,
+        trust_map: gix_sec::trust::Mapping<crate::open::Options>,
+        overrides: crate::open::EnvironmentOverrides,
     ) -> Result<Self, Error> {
         let _span = gix_trace::coarse!("ThreadSafeRepository::discover()");
-        let (path, trust) = upwards_opts(directory.as_ref(), options)?;
+        let (path, trust) = upwards_opts(directory, options)?;
         let (git_dir, worktree_dir) = path.into_repository_and_work_tree_directories();
         let mut options = trust_map.into_value_by_level(trust);
         options.git_dir_trust = trust.into();
         // Note that we will adjust the `current_dir` later so it matches the value of `core.precomposeUnicode`.
         options.current_dir = Some(gix_fs::current_dir(false).map_err(upwards::Error::CurrentDir)?);
-        Self::open_from_paths(git_dir, worktree_dir, options).map_err(Into::into)
+        Self::open_from_paths(git_dir, worktree_dir, overrides, options).map_err(Into::into)
     }
 
     /// Try to open a git repository directly from the environment.
@@ -62,6 +71,9 @@ impl ThreadSafeRepository {
     /// - `GIT_DISCOVERY_ACROSS_FILESYSTEM`
     /// - `GIT_CEILING_DIRECTORIES`
     ///
+    /// The discovered repository is then opened with `GIT_WORK_TREE` and `GIT_COMMON_DIR` taking precedence,
+    /// just like [`open_with_environment_overrides()`](Self::open_with_environment_overrides()) would.
+    ///
     /// Finally, use the `trust_map` to determine which of our own repository options to use
     /// based on the trust level of the effective repository directory.
     ///
@@ -76,24 +88,13 @@ impl ThreadSafeRepository {
         mut options: upwards::Options<'_>,
         trust_map: gix_sec::trust::Mapping<crate::open::Options>,
     ) -> Result<Self, Error> {
-        fn apply_additional_environment(mut opts: upwards::Options<'_>) -> upwards::Options<'_> {
-            use crate::bstr::ByteVec;
-
-            if let Some(cross_fs) = std::env::var_os("GIT_DISCOVERY_ACROSS_FILESYSTEM")
-                .and_then(|v| Vec::from_os_string(v).ok().map(BString::from))
-            {
-                if let Ok(b) = gix_config::Boolean::try_from(cross_fs.as_ref()) {
-                    opts.cross_fs = b.into();
-                }
-            }
-            opts
-        }
-
         if std::env::var_os("GIT_DIR").is_some() {
             return Self::open_with_environment_overrides(directory.as_ref(), trust_map).map_err(Error::Open);
         }
 
-        options = apply_additional_environment(options.apply_environment());
-        Self::discover_opts(directory, options, trust_map)
+        options = options.apply_environment();
+        let overrides =
+            crate::open::EnvironmentOverrides::from_env_without_git_dir().map_err(|err| Error::Open(err.into()))?;
+        Self::discover_opts_with_overrides(directory.as_ref(), options, trust_map, overrides)
     }
 }
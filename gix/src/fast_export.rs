@@ -0,0 +1,183 @@
+//! Types for use with [`Repository::fast_export()`](crate::Repository::fast_export()).
+use std::collections::BTreeMap;
+
+use gix_hash::{oid, ObjectId};
+
+use crate::bstr::{BString, ByteSlice};
+
+/// What to do with annotated tags that are signed, like `git fast-export --signed-tags`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedTags {
+    /// Fail with [`Error::SignedTag`], as the signature can't be verified anymore if the tagged history is rewritten.
+    #[default]
+    Abort,
+    /// Keep the signature as part of the tag message.
+    Verbatim,
+    /// Remove the signature from the tag message.
+    Strip,
+}
+
+/// Options for use with [`Repository::fast_export()`](crate::Repository::fast_export()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// How to handle signed tags.
+    pub signed_tags: SignedTags,
+    /// If `true`, replace all names, email addresses, messages, paths, reference names and file contents with
+    /// made-up but consistent values, like `git fast-export --anonymize` does, to be able to share the shape of a history
+    /// without sharing its content.
+    pub anonymize: bool,
+}
+
+/// Information about what was written by [`Repository::fast_export()`](crate::Repository::fast_export()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of `blob` commands.
+    pub blobs: usize,
+    /// The amount of `commit` commands.
+    pub commits: usize,
+    /// The amount of `tag` commands.
+    pub tags: usize,
+    /// The amount of `reset` commands issued to set references to commits that were exported before.
+    pub resets: usize,
+}
+
+/// Marks as used in fast-import streams to refer to objects, each of which is a number associated with an object id.
+///
+/// They are read from and written to files in the format used by `git fast-export --import-marks` and `--export-marks`,
+/// with one `:<mark> <object-id>` line per mark.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Marks {
+    ids: BTreeMap<u32, ObjectId>,
+    marks: gix_hashtable::HashMap<ObjectId, u32>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod marks {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Marks::from_bytes()`](super::Marks::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number} of the marks file is malformed: \"{line}\"")]
+        Malformed { line_number: usize, line: BString },
+    }
+}
+
+impl Marks {
+    /// Parse marks from `data` in the format of a marks file.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, marks::Error> {
+        let mut out = Marks::default();
+        for (idx, line) in data.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let parsed = line
+                .strip_prefix(b":")
+                .and_then(|line| line.split_once_str(b" "))
+                .and_then(|(mark, hex)| {
+                    let mark = mark.to_str().ok()?.parse::<u32>().ok().filter(|mark| *mark != 0)?;
+                    Some((mark, ObjectId::from_hex(hex).ok()?))
+                });
+            let (mark, id) = parsed.ok_or_else(|| marks::Error::Malformed {
+                line_number: idx + 1,
+                line: line.into(),
+            })?;
+            out.insert(mark, id);
+        }
+        Ok(out)
+    }
+
+    /// Write all marks to `out` in the format of a marks file, ordered by mark.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        for (mark, id) in &self.ids {
+            writeln!(out, ":{mark} {id}")?;
+        }
+        Ok(())
+    }
+
+    /// Return the object `mark` refers to.
+    pub fn id(&self, mark: u32) -> Option<&oid> {
+        self.ids.get(&mark).map(AsRef::as_ref)
+    }
+
+    /// Return the mark that refers to `id`, or the most recently inserted one if there are multiple.
+    pub fn mark(&self, id: &oid) -> Option<u32> {
+        self.marks.get(id).copied()
+    }
+
+    /// Let `mark` refer to `id`, replacing the object it previously referred to.
+    pub fn insert(&mut self, mark: u32, id: ObjectId) {
+        if let Some(previous) = self.ids.insert(mark, id) {
+            if self.marks.get(&previous) == Some(&mark) {
+                self.marks.remove(&previous);
+            }
+        }
+        self.marks.insert(id, mark);
+    }
+
+    /// Return the mark following the highest one in use, which is what new objects are assigned.
+    pub fn next_mark(&self) -> u32 {
+        self.ids.keys().next_back().map_or(1, |mark| mark + 1)
+    }
+
+    /// Return all marks along with the objects they refer to, ordered by mark.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &oid)> + '_ {
+        self.ids.iter().map(|(mark, id)| (*mark, id.as_ref()))
+    }
+
+    /// Return the amount of marks.
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Return `true` if there are no marks.
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// The error returned by [`Repository::fast_export()`](crate::Repository::fast_export()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    FollowReference(#[from] gix_ref::file::find::existing::Error),
+    #[error("The reference \"{name}\" doesn't point to an object")]
+    Unborn { name: BString },
+    #[error("The reference \"{name}\" points to a {kind}, but only commits and tags of commits can be exported")]
+    UnsupportedObject { name: BString, kind: gix_object::Kind },
+    #[error("The tag \"{name}\" is signed, and its signature would have to be kept or stripped")]
+    SignedTag { name: BString },
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeObject(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Walk(#[from] gix_traverse::commit::topo::Error),
+    #[error(transparent)]
+    CommitTree(#[from] crate::object::commit::Error),
+    #[cfg(feature = "blob-diff")]
+    #[error(transparent)]
+    DiffRenames(#[from] crate::diff::new_rewrites::Error),
+    #[cfg(feature = "blob-diff")]
+    #[error(transparent)]
+    Diff(#[from] crate::object::tree::diff::for_each::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Return `path` quoted in the C-style if it contains special characters, or bytes outside of ASCII and `quote_path` is set,
+/// or in plain quotes if it contains spaces and `quote_spaces` is set, like `git fast-export` does.
+#[cfg(any(feature = "blob-diff", feature = "revision"))]
+pub(crate) fn quoted(path: &crate::bstr::BStr, quote_spaces: bool, quote_path: bool) -> BString {
+    use std::borrow::Cow;
+    match gix_quote::ansi_c::quote(path, quote_path) {
+        Cow::Owned(quoted) => quoted,
+        Cow::Borrowed(path) if quote_spaces && path.contains(&b' ') => format!("\"{path}\"").into(),
+        Cow::Borrowed(path) => path.to_owned(),
+    }
+}
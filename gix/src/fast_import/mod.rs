@@ -0,0 +1,286 @@
+//! Types for use with [`Repository::fast_import()`](crate::Repository::fast_import()), along with a [`Parser`] for
+//! streams in the format of `git fast-import`.
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+use crate::bstr::BString;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse;
+pub use parse::Parser;
+
+/// The format of dates in `author`, `committer` and `tagger` lines, like `git fast-import --date-format`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateFormat {
+    /// Seconds since the Unix epoch followed by the timezone offset, like `1234567890 +0100`.
+    #[default]
+    Raw,
+    /// Like [`Raw`](DateFormat::Raw), but without validating the timezone offset.
+    RawPermissive,
+    /// A date as used in emails, like `Fri, 13 Feb 2009 23:31:30 +0000`.
+    Rfc2822,
+    /// The literal `now`, which is replaced with the current time.
+    Now,
+}
+
+impl DateFormat {
+    /// Parse `name` as used with the `date-format` feature, like `rfc2822`.
+    pub fn from_name(name: &[u8]) -> Option<Self> {
+        Some(match name {
+            b"raw" => DateFormat::Raw,
+            b"raw-permissive" => DateFormat::RawPermissive,
+            b"rfc2822" => DateFormat::Rfc2822,
+            b"now" => DateFormat::Now,
+            _ => return None,
+        })
+    }
+}
+
+/// A reference to an object as used in `from`, `merge`, `tag`, `ls` and `cat-blob` commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ObjectRef {
+    /// A mark, like `:1`, which refers to an object created by the stream or loaded from a marks file.
+    Mark(u32),
+    /// A full object id.
+    Id(ObjectId),
+    /// Any other revision, typically the name of a branch created by the stream or a reference in the repository.
+    Revision(BString),
+}
+
+/// The content of a file as used in file modifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileData {
+    /// A mark or object id of an existing object.
+    Object(ObjectRef),
+    /// The data follows the command, using the `inline` keyword.
+    Inline(Vec<u8>),
+}
+
+/// A change to the tree of a commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileChange {
+    /// `M`, add or replace the entry at `path`.
+    Modify {
+        /// The mode of the new entry.
+        mode: EntryMode,
+        /// The object or data of the new entry.
+        data: FileData,
+        /// The path at which to place the entry.
+        path: BString,
+    },
+    /// `D`, remove the entry at `path`, recursively if it's a directory.
+    Delete {
+        /// The path of the entry to remove.
+        path: BString,
+    },
+    /// `C`, copy the entry at `source` to `destination`.
+    Copy {
+        /// The path to copy from.
+        source: BString,
+        /// The path to copy to.
+        destination: BString,
+    },
+    /// `R`, move the entry at `source` to `destination`.
+    Rename {
+        /// The path to move from.
+        source: BString,
+        /// The path to move to.
+        destination: BString,
+    },
+    /// `deleteall`, remove all entries to start from an empty tree.
+    DeleteAll,
+    /// `N`, add a note to `commit`.
+    Note {
+        /// The content of the note.
+        data: FileData,
+        /// The commit to annotate.
+        commit: ObjectRef,
+    },
+    /// `ls`, query the entry at `path` in the tree as it is at this point.
+    Ls {
+        /// The tree to look into, or `None` to use the tree of the commit being built.
+        object: Option<ObjectRef>,
+        /// The path of the entry to look up.
+        path: BString,
+    },
+}
+
+/// A `blob` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blob {
+    /// The mark to assign to the blob.
+    pub mark: Option<u32>,
+    /// The id the blob had in the original repository, if provided with `original-oid`.
+    pub original_oid: Option<BString>,
+    /// The content of the blob.
+    pub data: Vec<u8>,
+}
+
+/// A `commit` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The name of the branch to commit to, like `refs/heads/main`.
+    pub reference: BString,
+    /// The mark to assign to the commit.
+    pub mark: Option<u32>,
+    /// The id the commit had in the original repository, if provided with `original-oid`.
+    pub original_oid: Option<BString>,
+    /// The author, or `None` if it's the same as the committer.
+    pub author: Option<gix_actor::Signature>,
+    /// The committer.
+    pub committer: gix_actor::Signature,
+    /// The encoding of the message, if it isn't UTF-8.
+    pub encoding: Option<BString>,
+    /// The commit message.
+    pub message: BString,
+    /// The first parent, or `None` to continue on the current tip of the branch.
+    pub from: Option<ObjectRef>,
+    /// All additional parents.
+    pub merges: Vec<ObjectRef>,
+    /// The changes to apply to the tree of the first parent.
+    pub changes: Vec<FileChange>,
+}
+
+/// A `tag` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The name of the tag, without the `refs/tags/` prefix.
+    pub name: BString,
+    /// The mark to assign to the tag.
+    pub mark: Option<u32>,
+    /// The object to tag.
+    pub from: ObjectRef,
+    /// The id the tag had in the original repository, if provided with `original-oid`.
+    pub original_oid: Option<BString>,
+    /// The tagger, which is optional.
+    pub tagger: Option<gix_actor::Signature>,
+    /// The tag message, including a signature if there is one.
+    pub message: BString,
+}
+
+/// A single command of a fast-import stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Create a blob.
+    Blob(Blob),
+    /// Create a commit and advance a branch to it.
+    Commit(Commit),
+    /// Create an annotated tag.
+    Tag(Tag),
+    /// Create or reset the branch `name`, to point to `from` if given.
+    Reset {
+        /// The name of the branch, like `refs/heads/main`.
+        name: BString,
+        /// The commit to set the branch to, or `None` to make the next commit on it a root commit.
+        from: Option<ObjectRef>,
+    },
+    /// Query the entry at `path` in the tree of `object`.
+    Ls {
+        /// The commit or tree to look into.
+        object: ObjectRef,
+        /// The path of the entry to look up.
+        path: BString,
+    },
+    /// Write the content of a blob to the response stream.
+    CatBlob(ObjectRef),
+    /// Write the object id of a mark to the response stream.
+    GetMark(u32),
+    /// Require the importer to support a feature, possibly with an argument, like `feature date-format=raw`.
+    Feature {
+        /// The name of the feature.
+        name: BString,
+        /// The part after `=`, if present.
+        argument: Option<BString>,
+    },
+    /// An option for a particular importer, like `option git quiet`.
+    Option(BString),
+    /// Write the message to the response stream.
+    Progress(BString),
+    /// Write everything to the repository, including references and marks.
+    Checkpoint,
+    /// Mark the end of the stream.
+    Done,
+}
+
+/// Options for use with [`Repository::fast_import()`](crate::Repository::fast_import()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The format of dates, unless the stream changes it with the `date-format` feature.
+    pub date_format: DateFormat,
+    /// If `true`, update branches even if their previous tip isn't part of the history of their new tip,
+    /// like `git fast-import --force`. Tags are always updated.
+    pub force: bool,
+    /// If `true`, honor the `import-marks`, `import-marks-if-exists` and `export-marks` features of the stream,
+    /// which read and write files at paths of the stream's choosing, like `git fast-import --allow-unsafe-features`.
+    pub allow_unsafe_features: bool,
+}
+
+/// Information about what was imported by [`Repository::fast_import()`](crate::Repository::fast_import()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of blobs that were written.
+    pub blobs: usize,
+    /// The amount of commits that were written.
+    pub commits: usize,
+    /// The amount of annotated tags that were written.
+    pub tags: usize,
+    /// The names of all references that were created, updated or deleted.
+    pub updated_references: Vec<gix_ref::FullName>,
+    /// The names of branches that were left unchanged as their new tip doesn't contain their previous one,
+    /// and [`Options::force`] wasn't set.
+    pub rejected_references: Vec<gix_ref::FullName>,
+}
+
+/// The error returned by [`Repository::fast_import()`](crate::Repository::fast_import()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Parse(#[from] parse::Error),
+    #[error("Mark :{mark} isn't defined")]
+    UnknownMark { mark: u32 },
+    #[error("The branch \"{name}\" doesn't have any commits yet")]
+    UnbornBranch { name: BString },
+    #[error("Could not resolve \"{spec}\"")]
+    RevParse {
+        spec: BString,
+        source: Box<crate::revision::spec::parse::single::Error>,
+    },
+    #[error("Expected {expected} {id}, but it is a {actual}")]
+    ObjectKind {
+        id: ObjectId,
+        expected: gix_object::Kind,
+        actual: gix_object::Kind,
+    },
+    #[error("The path \"{path}\" doesn't exist")]
+    MissingPath { path: BString },
+    #[error("The path \"{path}\" is invalid")]
+    InvalidPath { path: BString },
+    #[error("The feature \"{name}\" isn't supported")]
+    UnsupportedFeature { name: BString },
+    #[error("The feature \"{name}\" reads or writes files and requires `allow_unsafe_features`")]
+    UnsafeFeature { name: BString },
+    #[error("Notes aren't supported")]
+    Notes,
+    #[error(transparent)]
+    Marks(#[from] crate::fast_export::marks::Error),
+    #[error(transparent)]
+    ReferenceName(#[from] gix_validate::reference::name::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeObject(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    Traverse(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    EditReferences(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,485 @@
+use std::io::BufRead;
+
+use gix_hash::ObjectId;
+use gix_object::tree::EntryKind;
+
+use super::{Blob, Command, Commit, DateFormat, FileChange, FileData, ObjectRef, Tag};
+use crate::bstr::{BString, ByteSlice};
+
+/// The error returned when iterating a [`Parser`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Line {line_number}: expected {expected}, got \"{line}\"")]
+    Malformed {
+        line_number: usize,
+        expected: &'static str,
+        line: BString,
+    },
+    #[error("The stream ended in the middle of a command")]
+    UnexpectedEnd,
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// An iterator over the [commands](Command) of a fast-import stream, as produced by `git fast-export` or
+/// [`Repository::fast_export()`](crate::Repository::fast_export()).
+///
+/// Comments are skipped, and the `date-format` feature is applied to all following commands.
+/// The iteration ends after the `done` command or at the end of the input, and after the first error.
+pub struct Parser<R> {
+    input: R,
+    date_format: DateFormat,
+    line_number: usize,
+    peeked: Option<Vec<u8>>,
+    done: bool,
+}
+
+/// Lifecycle
+impl<R: BufRead> Parser<R> {
+    /// Parse the commands in `input`, expecting dates in `date_format` until the stream changes it.
+    pub fn new(input: R, date_format: DateFormat) -> Self {
+        Parser {
+            input,
+            date_format,
+            line_number: 0,
+            peeked: None,
+            done: false,
+        }
+    }
+
+    /// Return the format dates are currently expected in.
+    pub fn date_format(&self) -> DateFormat {
+        self.date_format
+    }
+}
+
+impl<R: BufRead> Iterator for Parser<R> {
+    type Item = Result<Command, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let res = self.command().transpose();
+        if matches!(res, None | Some(Err(_) | Ok(Command::Done))) {
+            self.done = true;
+        }
+        res
+    }
+}
+
+/// Commands
+impl<R: BufRead> Parser<R> {
+    fn command(&mut self) -> Result<Option<Command>, Error> {
+        let line = loop {
+            match self.line()? {
+                None => return Ok(None),
+                Some(line) if line.is_empty() => continue,
+                Some(line) => break line,
+            }
+        };
+        let cmd = if line == b"blob" {
+            Command::Blob(self.blob()?)
+        } else if let Some(reference) = line.strip_prefix(b"commit ") {
+            Command::Commit(self.commit(reference.into())?)
+        } else if let Some(name) = line.strip_prefix(b"tag ") {
+            Command::Tag(self.tag(name.into())?)
+        } else if let Some(name) = line.strip_prefix(b"reset ") {
+            let from = self.optional(b"from ")?.map(|from| object_ref(&from));
+            Command::Reset {
+                name: name.into(),
+                from,
+            }
+        } else if let Some(args) = line.strip_prefix(b"ls ") {
+            match self.ls(args)? {
+                FileChange::Ls {
+                    object: Some(object),
+                    path,
+                } => Command::Ls { object, path },
+                _ => return Err(self.malformed("an object to look into", &line)),
+            }
+        } else if let Some(object) = line.strip_prefix(b"cat-blob ") {
+            Command::CatBlob(object_ref(object))
+        } else if let Some(mark) = line.strip_prefix(b"get-mark ") {
+            Command::GetMark(parse_mark(mark).ok_or_else(|| self.malformed("a mark", &line))?)
+        } else if let Some(feature) = line.strip_prefix(b"feature ") {
+            let (name, argument) = match feature.split_once_str(b"=") {
+                Some((name, argument)) => (name, Some(argument)),
+                None => (feature, None),
+            };
+            if name == b"date-format" {
+                self.date_format = argument
+                    .and_then(DateFormat::from_name)
+                    .ok_or_else(|| self.malformed("a known date format", &line))?;
+            }
+            Command::Feature {
+                name: name.into(),
+                argument: argument.map(Into::into),
+            }
+        } else if let Some(option) = line.strip_prefix(b"option ") {
+            Command::Option(option.into())
+        } else if let Some(message) = line.strip_prefix(b"progress ") {
+            Command::Progress(message.into())
+        } else if line == b"checkpoint" {
+            Command::Checkpoint
+        } else if line == b"done" {
+            Command::Done
+        } else {
+            return Err(self.malformed("a command", &line));
+        };
+        Ok(Some(cmd))
+    }
+
+    fn blob(&mut self) -> Result<Blob, Error> {
+        let mark = self.mark()?;
+        let original_oid = self.optional(b"original-oid ")?.map(Into::into);
+        let data = self.data()?;
+        Ok(Blob {
+            mark,
+            original_oid,
+            data,
+        })
+    }
+
+    fn commit(&mut self, reference: BString) -> Result<Commit, Error> {
+        let mark = self.mark()?;
+        let original_oid = self.optional(b"original-oid ")?.map(Into::into);
+        let author = self.optional_signature(b"author ")?;
+        let committer = self
+            .optional_signature(b"committer ")?
+            .ok_or_else(|| self.malformed_next("a committer"))?;
+        let encoding = self.optional(b"encoding ")?.map(Into::into);
+        let message = self.data()?.into();
+        let from = self.optional(b"from ")?.map(|from| object_ref(&from));
+        let mut merges = Vec::new();
+        while let Some(merge) = self.optional(b"merge ")? {
+            merges.push(object_ref(&merge));
+        }
+
+        let mut changes = Vec::new();
+        while let Some(line) = self.line()? {
+            let change = if let Some(args) = line.strip_prefix(b"M ") {
+                let (mode, args) = args
+                    .split_once_str(b" ")
+                    .ok_or_else(|| self.malformed("a mode", &line))?;
+                let mode = parse_mode(mode).ok_or_else(|| self.malformed("a valid mode", &line))?;
+                let (data, path) = args
+                    .split_once_str(b" ")
+                    .ok_or_else(|| self.malformed("a data reference", &line))?;
+                let data = self.file_data(data, &line)?;
+                let path = self.path(path, false, &line)?.0;
+                FileChange::Modify { mode, data, path }
+            } else if let Some(path) = line.strip_prefix(b"D ") {
+                FileChange::Delete {
+                    path: self.path(path, false, &line)?.0,
+                }
+            } else if let Some(args) = line.strip_prefix(b"C ").or_else(|| line.strip_prefix(b"R ")) {
+                let (source, destination) = self.path(args, true, &line)?;
+                let destination = destination
+                    .strip_prefix(b" ")
+                    .ok_or_else(|| self.malformed("a destination path", &line))?;
+                let destination = self.path(destination, false, &line)?.0;
+                if line[0] == b'C' {
+                    FileChange::Copy { source, destination }
+                } else {
+                    FileChange::Rename { source, destination }
+                }
+            } else if line == b"deleteall" {
+                FileChange::DeleteAll
+            } else if let Some(args) = line.strip_prefix(b"N ") {
+                let (data, commit) = args
+                    .split_once_str(b" ")
+                    .ok_or_else(|| self.malformed("a data reference", &line))?;
+                FileChange::Note {
+                    data: self.file_data(data, &line)?,
+                    commit: object_ref(commit),
+                }
+            } else if let Some(args) = line.strip_prefix(b"ls ") {
+                self.ls(args)?
+            } else {
+                if !line.is_empty() {
+                    self.peeked = Some(line);
+                }
+                break;
+            };
+            changes.push(change);
+        }
+
+        Ok(Commit {
+            reference,
+            mark,
+            original_oid,
+            author,
+            committer,
+            encoding,
+            message,
+            from,
+            merges,
+            changes,
+        })
+    }
+
+    fn tag(&mut self, name: BString) -> Result<Tag, Error> {
+        let mark = self.mark()?;
+        let from = self
+            .optional(b"from ")?
+            .map(|from| object_ref(&from))
+            .ok_or_else(|| self.malformed_next("the object to tag"))?;
+        let original_oid = self.optional(b"original-oid ")?.map(Into::into);
+        let tagger = self.optional_signature(b"tagger ")?;
+        let message = self.data()?.into();
+        Ok(Tag {
+            name,
+            mark,
+            from,
+            original_oid,
+            tagger,
+            message,
+        })
+    }
+
+    /// Parse the arguments of `ls`, where the path must be quoted if there is no object to look into.
+    fn ls(&mut self, args: &[u8]) -> Result<FileChange, Error> {
+        let line = [&b"ls "[..], args].concat();
+        if args.starts_with(b"\"") {
+            return Ok(FileChange::Ls {
+                object: None,
+                path: self.path(args, false, &line)?.0,
+            });
+        }
+        let (object, path) = args
+            .split_once_str(b" ")
+            .ok_or_else(|| self.malformed("an object and a path", &line))?;
+        Ok(FileChange::Ls {
+            object: Some(object_ref(object)),
+            path: self.path(path, false, &line)?.0,
+        })
+    }
+}
+
+/// Utilities
+impl<R: BufRead> Parser<R> {
+    /// Read the next line without its newline, skipping comments.
+    fn line(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        if let Some(line) = self.peeked.take() {
+            return Ok(Some(line));
+        }
+        loop {
+            let Some(line) = self.raw_line()? else {
+                return Ok(None);
+            };
+            if !line.starts_with(b"#") {
+                return Ok(Some(line));
+            }
+        }
+    }
+
+    fn raw_line(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        let mut line = Vec::new();
+        if self.input.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line_number += 1;
+        if line.last() == Some(&b'\n') {
+            line.pop();
+        }
+        Ok(Some(line))
+    }
+
+    /// Return the remainder of the next line if it starts with `prefix`, or leave it to be read again.
+    fn optional(&mut self, prefix: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let Some(line) = self.line()? else {
+            return Ok(None);
+        };
+        match line.strip_prefix(prefix) {
+            Some(rest) => Ok(Some(rest.to_owned())),
+            None => {
+                self.peeked = Some(line);
+                Ok(None)
+            }
+        }
+    }
+
+    fn mark(&mut self) -> Result<Option<u32>, Error> {
+        self.optional(b"mark ")?
+            .map(|mark| parse_mark(&mark).ok_or_else(|| self.malformed("a mark", &[&b"mark "[..], &mark].concat())))
+            .transpose()
+    }
+
+    fn optional_signature(&mut self, prefix: &[u8]) -> Result<Option<gix_actor::Signature>, Error> {
+        self.optional(prefix)?
+            .map(|input| {
+                self.signature(&input)
+                    .ok_or_else(|| self.malformed("a signature with a valid date", &[prefix, &input].concat()))
+            })
+            .transpose()
+    }
+
+    /// Parse `[<name> ]<<email>> <when>`.
+    fn signature(&self, input: &[u8]) -> Option<gix_actor::Signature> {
+        let lt = input.find_byte(b'<')?;
+        let gt = lt + input[lt..].find_byte(b'>')?;
+        let name = &input[..lt];
+        let name = name.strip_suffix(b" ").unwrap_or(name);
+        let when = input[gt + 1..].strip_prefix(b" ")?;
+        Some(gix_actor::Signature {
+            name: name.into(),
+            email: input[lt + 1..gt].into(),
+            time: self.time(when)?,
+        })
+    }
+
+    fn time(&self, when: &[u8]) -> Option<gix_date::Time> {
+        match self.date_format {
+            DateFormat::Raw => parse_raw_time(when, true),
+            DateFormat::RawPermissive => parse_raw_time(when, false),
+            DateFormat::Rfc2822 => gix_date::parse(when.to_str().ok()?, None).ok(),
+            DateFormat::Now => (when == b"now").then(gix_date::Time::now_local_or_utc),
+        }
+    }
+
+    /// Read a `data` command from the next line, along with the data it announces.
+    fn data(&mut self) -> Result<Vec<u8>, Error> {
+        let line = self.line()?.ok_or(Error::UnexpectedEnd)?;
+        let spec = line
+            .strip_prefix(b"data ")
+            .ok_or_else(|| self.malformed("a data command", &line))?;
+        let data = if let Some(delimiter) = spec.strip_prefix(b"<<") {
+            let mut data = Vec::new();
+            loop {
+                let line = self.raw_line()?.ok_or(Error::UnexpectedEnd)?;
+                if line == delimiter {
+                    break;
+                }
+                data.extend_from_slice(&line);
+                data.push(b'\n');
+            }
+            data
+        } else {
+            let len: usize = spec
+                .to_str()
+                .ok()
+                .and_then(|len| len.parse().ok())
+                .ok_or_else(|| self.malformed("a data length", &line))?;
+            let mut data = vec![0; len];
+            std::io::Read::read_exact(&mut self.input, &mut data).map_err(|err| {
+                if err.kind() == std::io::ErrorKind::UnexpectedEof {
+                    Error::UnexpectedEnd
+                } else {
+                    err.into()
+                }
+            })?;
+            self.line_number += data.find_iter(b"\n").count();
+            data
+        };
+        if self.input.fill_buf()?.first() == Some(&b'\n') {
+            self.input.consume(1);
+            self.line_number += 1;
+        }
+        Ok(data)
+    }
+
+    fn file_data(&mut self, data: &[u8], line: &[u8]) -> Result<FileData, Error> {
+        Ok(if data == b"inline" {
+            FileData::Inline(self.data()?)
+        } else {
+            match object_ref(data) {
+                ObjectRef::Revision(_) => return Err(self.malformed("a mark, an object id or 'inline'", line)),
+                object => FileData::Object(object),
+            }
+        })
+    }
+
+    /// Parse a possibly quoted path from `input`, which ends at the first space if `until_space` is set and
+    /// it isn't quoted. Return it along with the remaining input.
+    fn path<'a>(&self, input: &'a [u8], until_space: bool, line: &[u8]) -> Result<(BString, &'a [u8]), Error> {
+        if input.starts_with(b"\"") {
+            let (path, consumed) =
+                gix_quote::ansi_c::undo(input.as_bstr()).map_err(|_| self.malformed("a properly quoted path", line))?;
+            return Ok((path.into_owned(), &input[consumed..]));
+        }
+        let end = if until_space {
+            input.find_byte(b' ').unwrap_or(input.len())
+        } else {
+            input.len()
+        };
+        Ok((input[..end].into(), &input[end..]))
+    }
+
+    fn malformed(&self, expected: &'static str, line: &[u8]) -> Error {
+        Error::Malformed {
+            line_number: self.line_number,
+            expected,
+            line: line.into(),
+        }
+    }
+
+    fn malformed_next(&self, expected: &'static str) -> Error {
+        self.malformed(expected, self.peeked.as_deref().unwrap_or_default())
+    }
+}
+
+fn parse_mark(input: &[u8]) -> Option<u32> {
+    input
+        .strip_prefix(b":")?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+        .filter(|mark| *mark != 0)
+}
+
+fn object_ref(input: &[u8]) -> ObjectRef {
+    if let Some(mark) = parse_mark(input) {
+        ObjectRef::Mark(mark)
+    } else if let Ok(id) = ObjectId::from_hex(input) {
+        ObjectRef::Id(id)
+    } else {
+        ObjectRef::Revision(input.into())
+    }
+}
+
+fn parse_mode(mode: &[u8]) -> Option<gix_object::tree::EntryMode> {
+    Some(
+        match mode {
+            b"100644" | b"644" => EntryKind::Blob,
+            b"100755" | b"755" => EntryKind::BlobExecutable,
+            b"120000" => EntryKind::Link,
+            b"160000" => EntryKind::Commit,
+            b"040000" => EntryKind::Tree,
+            _ => return None,
+        }
+        .into(),
+    )
+}
+
+/// Parse `<seconds> <+|-><HHMM>`, and if `strict`, reject offsets that aren't valid.
+fn parse_raw_time(input: &[u8], strict: bool) -> Option<gix_date::Time> {
+    let (seconds, offset) = input.to_str().ok()?.split_once(' ')?;
+    let seconds = seconds.parse().ok()?;
+    let sign = match offset.as_bytes().first()? {
+        b'+' => gix_date::time::Sign::Plus,
+        b'-' => gix_date::time::Sign::Minus,
+        _ => return None,
+    };
+    let digits = &offset[1..];
+    if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits[2..].parse().ok()?;
+    if strict && (hours >= 14 || minutes >= 60) {
+        return None;
+    }
+    let offset = hours * 3600 + minutes * 60;
+    Some(gix_date::Time {
+        seconds,
+        offset: if sign == gix_date::time::Sign::Minus {
+            -offset
+        } else {
+            offset
+        },
+        sign,
+    })
+}
@@ -0,0 +1,703 @@
+//! Types for use with [`Repository::for_each_ref()`](crate::Repository::for_each_ref()).
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    remote,
+};
+
+/// A format like the one of `git for-each-ref --format`, made of literal text and `%(fieldname)` atoms.
+///
+/// Literal text may contain `%%` for a single `%` and `%xx` for the byte with the hexadecimal value `xx`.
+/// The following atoms are supported, with the modifiers that follow their name after a colon:
+///
+/// * `refname`, `symref`, `upstream` and `push`, with `:short` for the shortest unambiguous name, and `:lstrip=<N>`,
+///   `:strip=<N>` or `:rstrip=<N>` to remove `<N>` path components from the front or back, or to keep `-<N>` if negative.
+/// * `upstream` and `push` with `:track` or `:track,nobracket` for `[ahead 1, behind 2]`, `:trackshort` for `<>`, or
+///   `:remotename` and `:remoteref` for the configured remote and the name of the branch on it.
+/// * `HEAD`, which is `*` if `HEAD` points to the reference and a space otherwise.
+/// * `objectname`, `tree`, `parent` and `object` with `:short` for an abbreviated id, or `:short=<N>` for one with at least
+///   `<N>` characters, as well as `objecttype`, `objectsize`, `numparent`, `type` and `tag`.
+/// * `author`, `committer`, `tagger` and `creator`, along with their `name`, `email` and `date` variants like `authordate`.
+///   Emails support `:trim` and `:localpart`, and dates support `:default`, `:short`, `:iso`, `:iso-strict`, `:rfc`, `:raw`
+///   and `:unix`.
+/// * `subject`, `body` and `contents`, with `contents:subject`, `contents:body` and `contents:signature`, where `body`
+///   includes the signature of signed tags and `contents:body` doesn't.
+/// * `align:<width>[,<position>]` or `align:width=<width>,position=<position>` with `left`, `middle` or `right` positions,
+///   which pads everything up to the next `%(end)`.
+/// * `if`, `if:equals=<string>` or `if:notequals=<string>`, followed by `%(then)`, an optional `%(else)` and `%(end)`.
+///   Without a comparison, the condition holds if everything up to `%(then)` expands to more than whitespace.
+/// * `color:<color>` with colors like in the git configuration, like `bold red` or `reset`.
+///
+/// Prefixing an atom that refers to an object with `*`, like in `%(*objectname)`, makes it refer to the object an annotated
+/// tag points to instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Format {
+    pub(crate) items: Vec<Item>,
+}
+
+impl Format {
+    /// The format used by `git for-each-ref` if none is given.
+    pub const DEFAULT: &'static str = "%(objectname) %(objecttype)\t%(refname)";
+
+    /// Parse `input` into a format, or fail if an atom is unknown or blocks like `%(if)` aren't closed.
+    pub fn parse(input: &BStr) -> Result<Self, parse::Error> {
+        Ok(Format {
+            items: parse::items(input)?,
+        })
+    }
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Format::parse(Format::DEFAULT.into()).expect("default format is valid")
+    }
+}
+
+/// Options for use with [`Repository::for_each_ref()`](crate::Repository::for_each_ref()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, `%(color:…)` atoms produce ANSI escape sequences, like `git for-each-ref --color=always`.
+    /// Otherwise they produce nothing.
+    pub color: bool,
+}
+
+/// The result of [`Repository::for_each_ref()`](crate::Repository::for_each_ref()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The formatted line of each reference, without a trailing newline, ordered by reference name.
+    pub lines: Vec<BString>,
+}
+
+impl Outcome {
+    /// Write all lines to `out` like `git for-each-ref` does, each followed by a newline.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        for line in &self.lines {
+            out.write_all(line)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Item {
+    Literal(BString),
+    Atom {
+        deref: bool,
+        atom: Atom,
+    },
+    /// The ANSI escape sequence to emit if colors are enabled.
+    Color(BString),
+    Align {
+        width: usize,
+        position: Position,
+        items: Vec<Item>,
+    },
+    If {
+        condition: Condition,
+        test: Vec<Item>,
+        then: Vec<Item>,
+        otherwise: Vec<Item>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Position {
+    Left,
+    Middle,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Condition {
+    NotEmpty,
+    Equals(BString),
+    NotEquals(BString),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Name {
+    Full,
+    Short,
+    Lstrip(isize),
+    Rstrip(isize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tracking {
+    Name(Name),
+    Track { brackets: bool },
+    TrackShort,
+    RemoteName,
+    RemoteRef,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Id {
+    Full,
+    /// Abbreviated to at least the given amount of hex characters, or to the configured length.
+    Short(Option<usize>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Role {
+    Author,
+    Committer,
+    Tagger,
+    Creator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Email {
+    Full,
+    Trim,
+    LocalPart,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Date {
+    Default,
+    Short,
+    Iso,
+    IsoStrict,
+    Rfc,
+    Raw,
+    Unix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Person {
+    Full,
+    Name,
+    Email(Email),
+    Date(Date),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Contents {
+    All,
+    Subject,
+    /// The body without a trailing signature.
+    Body,
+    /// The body including a trailing signature, as produced by `%(body)`.
+    BodyAndSignature,
+    Signature,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Atom {
+    RefName(Name),
+    SymRef(Name),
+    Tracking(remote::Direction, Tracking),
+    Head,
+    ObjectName(Id),
+    ObjectType,
+    ObjectSize,
+    Tree(Id),
+    Parent(Id),
+    NumParent,
+    Object(Id),
+    Type,
+    Tag,
+    Person(Role, Person),
+    Contents(Contents),
+}
+
+impl Atom {
+    /// Return `true` if the atom refers to the object the reference points to, which allows it to be dereferenced with `*`.
+    fn is_about_object(&self) -> bool {
+        !matches!(
+            self,
+            Atom::RefName(_) | Atom::SymRef(_) | Atom::Tracking(..) | Atom::Head
+        )
+    }
+}
+
+/// Write the ANSI escape sequence for `color` to `out`, like `git` does.
+fn write_ansi_escape(color: &gix_config::Color, out: &mut BString) {
+    use gix_config::color::{Attribute, Name};
+
+    if color.foreground.is_none() && color.background.is_none() && color.attributes == Attribute::RESET {
+        out.push_str("\x1b[m");
+        return;
+    }
+    let mut codes = Vec::new();
+    for (attribute, code) in [
+        (Attribute::BOLD, 1),
+        (Attribute::DIM, 2),
+        (Attribute::ITALIC, 3),
+        (Attribute::UL, 4),
+        (Attribute::BLINK, 5),
+        (Attribute::REVERSE, 7),
+        (Attribute::STRIKE, 9),
+        (Attribute::NO_BOLD, 22),
+        (Attribute::NO_DIM, 22),
+        (Attribute::NO_ITALIC, 23),
+        (Attribute::NO_UL, 24),
+        (Attribute::NO_BLINK, 25),
+        (Attribute::NO_REVERSE, 27),
+        (Attribute::NO_STRIKE, 29),
+    ] {
+        if color.attributes.contains(attribute) && !codes.contains(&code.to_string()) {
+            codes.push(code.to_string());
+        }
+    }
+    for (name, base) in [(color.foreground, 3), (color.background, 4)] {
+        let basic = |offset: u8| format!("{base}{offset}");
+        let bright = |offset: u8| format!("{}{offset}", base + 6);
+        codes.push(match name {
+            None | Some(Name::Normal) => continue,
+            Some(Name::Default) => basic(9),
+            Some(Name::Black) => basic(0),
+            Some(Name::Red) => basic(1),
+            Some(Name::Green) => basic(2),
+            Some(Name::Yellow) => basic(3),
+            Some(Name::Blue) => basic(4),
+            Some(Name::Magenta) => basic(5),
+            Some(Name::Cyan) => basic(6),
+            Some(Name::White) => basic(7),
+            Some(Name::BrightBlack) => bright(0),
+            Some(Name::BrightRed) => bright(1),
+            Some(Name::BrightGreen) => bright(2),
+            Some(Name::BrightYellow) => bright(3),
+            Some(Name::BrightBlue) => bright(4),
+            Some(Name::BrightMagenta) => bright(5),
+            Some(Name::BrightCyan) => bright(6),
+            Some(Name::BrightWhite) => bright(7),
+            Some(Name::Ansi(value)) if value < 8 => basic(value),
+            Some(Name::Ansi(value)) if value < 16 => bright(value - 8),
+            Some(Name::Ansi(value)) => format!("{base}8;5;{value}"),
+            Some(Name::Rgb(r, g, b)) => format!("{base}8;2;{r};{g};{b}"),
+        });
+    }
+    if codes.is_empty() && !color.attributes.contains(Attribute::RESET) {
+        return;
+    }
+    out.push_str("\x1b[");
+    if color.attributes.contains(Attribute::RESET) {
+        // An empty parameter resets all attributes before the following ones are applied.
+        out.push_str(";");
+    }
+    out.push_str(codes.join(";"));
+    out.push_str("m");
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse {
+    use super::{Atom, Condition, Contents, Date, Email, Id, Item, Name, Person, Position, Role, Tracking};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        remote,
+    };
+
+    /// The error returned by [`Format::parse()`](super::Format::parse()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The atom starting at byte {position} isn't terminated with ')'")]
+        UnterminatedAtom { position: usize },
+        #[error("Unknown field name {atom:?}")]
+        UnknownAtom { atom: BString },
+        #[error("Unrecognized modifier {modifier:?} of {atom:?}")]
+        InvalidModifier { atom: BString, modifier: BString },
+        #[error("{atom:?} doesn't refer to an object and can't be dereferenced with '*'")]
+        InvalidDeref { atom: BString },
+        #[error("%(align) needs a width")]
+        MissingAlignWidth,
+        #[error("Unrecognized color {spec:?}")]
+        InvalidColor {
+            spec: BString,
+            source: gix_config::value::Error,
+        },
+        #[error("%({atom}) isn't used after %(if) or %(align)")]
+        UnexpectedBlockAtom { atom: &'static str },
+        #[error("%(if) needs a %(then) before %({atom})")]
+        MissingThen { atom: &'static str },
+        #[error("%({atom}) isn't closed with %(end)")]
+        MissingEnd { atom: &'static str },
+    }
+
+    /// A piece of the format before blocks are assembled.
+    enum Token<'a> {
+        Literal(BString),
+        Atom { name: &'a BStr, modifier: Option<&'a BStr> },
+    }
+
+    /// An atom that delimits a block.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Delimiter {
+        Then,
+        Else,
+        End,
+    }
+
+    impl Delimiter {
+        fn as_str(&self) -> &'static str {
+            match self {
+                Delimiter::Then => "then",
+                Delimiter::Else => "else",
+                Delimiter::End => "end",
+            }
+        }
+    }
+
+    pub(super) fn items(input: &BStr) -> Result<Vec<Item>, Error> {
+        let mut tokens = tokenize(input)?.into_iter();
+        match parse_block(&mut tokens)? {
+            (items, None) => Ok(items),
+            (_, Some(delimiter)) => Err(Error::UnexpectedBlockAtom {
+                atom: delimiter.as_str(),
+            }),
+        }
+    }
+
+    fn tokenize(input: &BStr) -> Result<Vec<Token<'_>>, Error> {
+        let mut tokens = Vec::new();
+        let mut literal = BString::default();
+        let mut pos = 0;
+        while pos < input.len() {
+            let rest = &input[pos..];
+            if rest.starts_with(b"%(") {
+                let end = rest.find_byte(b')').ok_or(Error::UnterminatedAtom { position: pos })?;
+                let atom = rest[2..end].as_bstr();
+                let (name, modifier) = match atom.find_byte(b':') {
+                    Some(colon) => (atom[..colon].as_bstr(), Some(atom[colon + 1..].as_bstr())),
+                    None => (atom, None),
+                };
+                if !literal.is_empty() {
+                    tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                }
+                tokens.push(Token::Atom { name, modifier });
+                pos += end + 1;
+            } else if rest.starts_with(b"%%") {
+                literal.push(b'%');
+                pos += 2;
+            } else if let Some(byte) = rest
+                .strip_prefix(b"%")
+                .and_then(|hex| hex.get(..2))
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                literal.push(byte);
+                pos += 3;
+            } else {
+                literal.push(rest[0]);
+                pos += 1;
+            }
+        }
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+        Ok(tokens)
+    }
+
+    /// Parse items until the end of input or until a delimiter, which is returned as well.
+    fn parse_block<'a>(tokens: &mut impl Iterator<Item = Token<'a>>) -> Result<(Vec<Item>, Option<Delimiter>), Error> {
+        let mut items = Vec::new();
+        while let Some(token) = tokens.next() {
+            let (name, modifier) = match token {
+                Token::Literal(literal) => {
+                    items.push(Item::Literal(literal));
+                    continue;
+                }
+                Token::Atom { name, modifier } => (name, modifier),
+            };
+            let delimiter = match name.as_bytes() {
+                b"then" => Some(Delimiter::Then),
+                b"else" => Some(Delimiter::Else),
+                b"end" => Some(Delimiter::End),
+                _ => None,
+            };
+            if let Some(delimiter) = delimiter {
+                if modifier.is_some() {
+                    return Err(invalid_modifier(name, modifier));
+                }
+                return Ok((items, Some(delimiter)));
+            }
+            items.push(match name.as_bytes() {
+                b"align" => {
+                    let (width, position) = parse_align(modifier.unwrap_or_default())?;
+                    let items = match parse_block(tokens)? {
+                        (items, Some(Delimiter::End)) => items,
+                        (_, Some(delimiter)) => {
+                            return Err(Error::UnexpectedBlockAtom {
+                                atom: delimiter.as_str(),
+                            })
+                        }
+                        (_, None) => return Err(Error::MissingEnd { atom: "align" }),
+                    };
+                    Item::Align { width, position, items }
+                }
+                b"if" => {
+                    let condition = match modifier {
+                        None => Condition::NotEmpty,
+                        Some(modifier) => {
+                            if let Some(value) = modifier.strip_prefix(b"equals=") {
+                                Condition::Equals(value.into())
+                            } else if let Some(value) = modifier.strip_prefix(b"notequals=") {
+                                Condition::NotEquals(value.into())
+                            } else {
+                                return Err(invalid_modifier(name, Some(modifier)));
+                            }
+                        }
+                    };
+                    let test = match parse_block(tokens)? {
+                        (items, Some(Delimiter::Then)) => items,
+                        (_, Some(delimiter)) => {
+                            return Err(Error::MissingThen {
+                                atom: delimiter.as_str(),
+                            })
+                        }
+                        (_, None) => return Err(Error::MissingEnd { atom: "if" }),
+                    };
+                    let (then, otherwise) = match parse_block(tokens)? {
+                        (then, Some(Delimiter::End)) => (then, Vec::new()),
+                        (then, Some(Delimiter::Else)) => match parse_block(tokens)? {
+                            (otherwise, Some(Delimiter::End)) => (then, otherwise),
+                            (_, Some(delimiter)) => {
+                                return Err(Error::UnexpectedBlockAtom {
+                                    atom: delimiter.as_str(),
+                                })
+                            }
+                            (_, None) => return Err(Error::MissingEnd { atom: "if" }),
+                        },
+                        (_, Some(delimiter)) => {
+                            return Err(Error::UnexpectedBlockAtom {
+                                atom: delimiter.as_str(),
+                            })
+                        }
+                        (_, None) => return Err(Error::MissingEnd { atom: "if" }),
+                    };
+                    Item::If {
+                        condition,
+                        test,
+                        then,
+                        otherwise,
+                    }
+                }
+                b"color" => {
+                    let spec = modifier.unwrap_or_default();
+                    let color = gix_config::Color::try_from(spec).map_err(|source| Error::InvalidColor {
+                        spec: spec.to_owned(),
+                        source,
+                    })?;
+                    let mut escape = BString::default();
+                    super::write_ansi_escape(&color, &mut escape);
+                    Item::Color(escape)
+                }
+                _ => {
+                    let (deref, atom_name) = match name.strip_prefix(b"*") {
+                        Some(name) => (true, name.as_bstr()),
+                        None => (false, name),
+                    };
+                    let atom = parse_atom(atom_name, modifier)?;
+                    if deref && !atom.is_about_object() {
+                        return Err(Error::InvalidDeref { atom: name.to_owned() });
+                    }
+                    Item::Atom { deref, atom }
+                }
+            });
+        }
+        Ok((items, None))
+    }
+
+    fn invalid_modifier(name: &BStr, modifier: Option<&BStr>) -> Error {
+        Error::InvalidModifier {
+            atom: name.to_owned(),
+            modifier: modifier.unwrap_or_default().to_owned(),
+        }
+    }
+
+    fn parse_align(modifier: &BStr) -> Result<(usize, Position), Error> {
+        let mut width = None;
+        let mut position = Position::Left;
+        for arg in modifier.split_str(",").filter(|arg| !arg.is_empty()) {
+            let parse_position = |value: &[u8]| match value {
+                b"left" => Some(Position::Left),
+                b"middle" => Some(Position::Middle),
+                b"right" => Some(Position::Right),
+                _ => None,
+            };
+            let parse_width = |value: &[u8]| value.to_str().ok().and_then(|value| value.parse::<usize>().ok());
+            if let Some(value) = arg.strip_prefix(b"width=") {
+                width = Some(parse_width(value).ok_or_else(|| invalid_modifier("align".into(), Some(modifier)))?);
+            } else if let Some(value) = arg.strip_prefix(b"position=") {
+                position = parse_position(value).ok_or_else(|| invalid_modifier("align".into(), Some(modifier)))?;
+            } else if let Some(value) = parse_position(arg) {
+                position = value;
+            } else if let Some(value) = parse_width(arg) {
+                width = Some(value);
+            } else {
+                return Err(invalid_modifier("align".into(), Some(modifier)));
+            }
+        }
+        Ok((width.ok_or(Error::MissingAlignWidth)?, position))
+    }
+
+    fn parse_atom(name: &BStr, modifier: Option<&BStr>) -> Result<Atom, Error> {
+        let invalid = || invalid_modifier(name, modifier);
+        let no_modifier = |atom: Atom| modifier.map_or(Ok(atom), |_| Err(invalid()));
+        let ref_name = || -> Result<Name, Error> {
+            let Some(modifier) = modifier else {
+                return Ok(Name::Full);
+            };
+            let strip = |value: &[u8]| value.to_str().ok().and_then(|value| value.parse::<isize>().ok());
+            Ok(if modifier == "short" {
+                Name::Short
+            } else if let Some(value) = modifier
+                .strip_prefix(b"lstrip=")
+                .or_else(|| modifier.strip_prefix(b"strip="))
+            {
+                Name::Lstrip(strip(value).ok_or_else(invalid)?)
+            } else if let Some(value) = modifier.strip_prefix(b"rstrip=") {
+                Name::Rstrip(strip(value).ok_or_else(invalid)?)
+            } else {
+                return Err(invalid());
+            })
+        };
+        let id = || -> Result<Id, Error> {
+            Ok(match modifier.map(|modifier| modifier.as_bytes()) {
+                None => Id::Full,
+                Some(b"short") => Id::Short(None),
+                Some(modifier) => Id::Short(Some(
+                    modifier
+                        .strip_prefix(b"short=")
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<usize>().ok())
+                        .ok_or_else(invalid)?,
+                )),
+            })
+        };
+        let tracking = |direction: remote::Direction| -> Result<Atom, Error> {
+            let tracking = match modifier.map(|modifier| modifier.as_bytes()) {
+                Some(b"track") => Tracking::Track { brackets: true },
+                Some(b"track,nobracket") => Tracking::Track { brackets: false },
+                Some(b"trackshort") => Tracking::TrackShort,
+                Some(b"remotename") => Tracking::RemoteName,
+                Some(b"remoteref") => Tracking::RemoteRef,
+                _ => Tracking::Name(ref_name()?),
+            };
+            Ok(Atom::Tracking(direction, tracking))
+        };
+        let person = |role: Role, field: &[u8]| -> Result<Atom, Error> {
+            let person = match field {
+                b"" => Person::Full,
+                b"name" => Person::Name,
+                b"email" => Person::Email(match modifier.map(|modifier| modifier.as_bytes()) {
+                    None => Email::Full,
+                    Some(b"trim") => Email::Trim,
+                    Some(b"localpart") => Email::LocalPart,
+                    Some(_) => return Err(invalid()),
+                }),
+                b"date" => Person::Date(match modifier.map(|modifier| modifier.as_bytes()) {
+                    None | Some(b"default") => Date::Default,
+                    Some(b"short") => Date::Short,
+                    Some(b"iso" | b"iso8601") => Date::Iso,
+                    Some(b"iso-strict" | b"iso8601-strict") => Date::IsoStrict,
+                    Some(b"rfc" | b"rfc2822") => Date::Rfc,
+                    Some(b"raw") => Date::Raw,
+                    Some(b"unix") => Date::Unix,
+                    Some(_) => return Err(invalid()),
+                }),
+                _ => {
+                    return Err(Error::UnknownAtom { atom: name.to_owned() });
+                }
+            };
+            if matches!(person, Person::Full | Person::Name) && modifier.is_some() {
+                return Err(invalid());
+            }
+            Ok(Atom::Person(role, person))
+        };
+
+        Ok(match name.as_bytes() {
+            b"refname" => Atom::RefName(ref_name()?),
+            b"symref" => Atom::SymRef(ref_name()?),
+            b"upstream" => tracking(remote::Direction::Fetch)?,
+            b"push" => tracking(remote::Direction::Push)?,
+            b"HEAD" => no_modifier(Atom::Head)?,
+            b"objectname" => Atom::ObjectName(id()?),
+            b"objecttype" => no_modifier(Atom::ObjectType)?,
+            b"objectsize" => no_modifier(Atom::ObjectSize)?,
+            b"tree" => Atom::Tree(id()?),
+            b"parent" => Atom::Parent(id()?),
+            b"numparent" => no_modifier(Atom::NumParent)?,
+            b"object" => Atom::Object(id()?),
+            b"type" => no_modifier(Atom::Type)?,
+            b"tag" => no_modifier(Atom::Tag)?,
+            b"subject" => no_modifier(Atom::Contents(Contents::Subject))?,
+            b"body" => no_modifier(Atom::Contents(Contents::BodyAndSignature))?,
+            b"contents" => Atom::Contents(match modifier.map(|modifier| modifier.as_bytes()) {
+                None => Contents::All,
+                Some(b"subject") => Contents::Subject,
+                Some(b"body") => Contents::Body,
+                Some(b"signature") => Contents::Signature,
+                Some(_) => return Err(invalid()),
+            }),
+            _ => {
+                let roles = [
+                    (&b"author"[..], Role::Author),
+                    (b"committer", Role::Committer),
+                    (b"tagger", Role::Tagger),
+                    (b"creator", Role::Creator),
+                ];
+                match roles
+                    .iter()
+                    .find_map(|(prefix, role)| name.strip_prefix(*prefix).map(|field| (*role, field)))
+                {
+                    Some((Role::Creator, field)) if !matches!(field, b"" | b"date") => {
+                        return Err(Error::UnknownAtom { atom: name.to_owned() })
+                    }
+                    Some((role, field)) => person(role, field)?,
+                    None => return Err(Error::UnknownAtom { atom: name.to_owned() }),
+                }
+            }
+        })
+    }
+}
+
+/// The error returned by [`Repository::for_each_ref()`](crate::Repository::for_each_ref()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    References(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    ShortenId(#[from] crate::id::shorten::Error),
+    #[error(transparent)]
+    DisambiguatePrefix(#[from] gix_odb::store::prefix::disambiguate::Error),
+    #[error(transparent)]
+    RemoteRefName(#[from] crate::repository::branch_remote_ref_name::Error),
+    #[error(transparent)]
+    RemoteTrackingRefName(#[from] crate::repository::branch_remote_tracking_ref_name::Error),
+    #[error(transparent)]
+    AheadBehind(#[from] crate::repository::ahead_behind::Error),
+}
+
+pub(crate) fn pad(value: BString, width: usize, position: Position) -> BString {
+    let len = value.chars().count();
+    if len >= width {
+        return value;
+    }
+    let missing = width - len;
+    let (left, right) = match position {
+        Position::Left => (0, missing),
+        Position::Middle => (missing / 2, missing - missing / 2),
+        Position::Right => (missing, 0),
+    };
+    let mut out = BString::from(vec![b' '; left]);
+    out.push_str(&value);
+    out.extend(std::iter::repeat(b' ').take(right));
+    out
+}
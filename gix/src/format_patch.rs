@@ -0,0 +1,83 @@
+//! Types for use with [`Repository::format_patch()`](crate::Repository::format_patch()).
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::format_patch()`](crate::Repository::format_patch()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The text in brackets before the subject of each mail, defaulting to `PATCH` like `git format-patch --subject-prefix`.
+    pub subject_prefix: BString,
+    /// If `Some(true)`, number the patches like `[PATCH 1/3]`, and if `Some(false)`, don't, like `git format-patch -n` and `-N`.
+    /// If `None`, the default, patches are numbered only if there is more than one.
+    pub numbered: Option<bool>,
+    /// The number of the first patch, defaulting to `1` like `git format-patch --start-number`.
+    pub start_number: usize,
+    /// The text to put below the `-- ` line at the end of each mail, or `None` to leave out the signature section like
+    /// `git format-patch --no-signature`. Defaults to the name and version of this crate.
+    pub signature: Option<BString>,
+    /// The amount of unchanged lines to show around each change, defaulting to `3`.
+    pub context_lines: u32,
+    /// The maximum width of the lines of the diffstat, defaulting to `72` like `git format-patch` does.
+    pub stat_width: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            subject_prefix: "PATCH".into(),
+            numbered: None,
+            start_number: 1,
+            signature: Some(concat!("gitoxide ", env!("CARGO_PKG_VERSION")).into()),
+            context_lines: 3,
+            stat_width: 72,
+        }
+    }
+}
+
+/// A commit formatted as mail, as produced by [`Repository::format_patch()`](crate::Repository::format_patch()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    /// The commit the patch was created from.
+    pub commit: gix_hash::ObjectId,
+    /// The name of the file `git format-patch` would write the patch to, like `0001-fix-the-thing.patch`.
+    pub file_name: String,
+    /// The entire mail in the mbox format, starting with the `From <commit> Mon Sep 17 00:00:00 2001` line.
+    ///
+    /// Concatenating the mails of multiple patches yields a valid mailbox for use with [`Repository::am()`](crate::Repository::am()).
+    pub mail: BString,
+}
+
+/// The error returned by [`Repository::format_patch()`](crate::Repository::format_patch()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    RevParse(#[from] crate::revision::spec::parse::Error),
+    #[error("The revision specification \"{spec}\" doesn't describe a range of commits")]
+    UnsupportedSpec { spec: BString },
+    #[error(transparent)]
+    HeadId(#[from] crate::reference::head_id::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    WalkItem(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    CommitTree(#[from] crate::object::commit::Error),
+    #[error(transparent)]
+    DiffRenames(#[from] crate::diff::new_rewrites::Error),
+    #[error(transparent)]
+    DiffAlgorithm(#[from] crate::config::diff::algorithm::Error),
+    #[error(transparent)]
+    Diff(#[from] crate::object::tree::diff::for_each::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    BinaryDetector(#[from] crate::binary::detector::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,154 @@
+//! Types for use with [`Repository::fsck()`](crate::Repository::fsck()).
+use std::io::Write;
+
+pub use gix_fsck::object::{MessageId, Problem, Severity};
+use gix_hash::ObjectId;
+use gix_object::Kind;
+
+/// Options for use with [`Repository::fsck()`](crate::Repository::fsck()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, the content of all objects in the object database is checked for problems, like the format of their headers,
+    /// their identities and dates and the entries of trees.
+    pub check_objects: bool,
+    /// If `true`, all objects reachable from references, their reflogs and the index are checked to exist.
+    pub check_connectivity: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            check_objects: true,
+            check_connectivity: true,
+        }
+    }
+}
+
+/// Something found to be wrong by [`Repository::fsck()`](crate::Repository::fsck()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Finding {
+    /// The object with `id` of `kind` has a `problem` with its content, with the `severity` it's configured to have.
+    Object {
+        /// The id of the object with a problem.
+        id: ObjectId,
+        /// The kind of the object.
+        kind: Kind,
+        /// The severity of the problem, which is never [`Severity::Ignore`].
+        severity: Severity,
+        /// The problem itself.
+        problem: Problem,
+    },
+    /// The object with `id` is referenced but doesn't exist in the object database.
+    Missing {
+        /// The id of the missing object.
+        id: ObjectId,
+        /// The kind the object is expected to have by its referrer, or `None` if it's referred to by references, their
+        /// reflogs or the index without its kind being known.
+        kind: Option<Kind>,
+    },
+}
+
+impl Finding {
+    /// Return the id of the object this finding is about.
+    pub fn id(&self) -> ObjectId {
+        match self {
+            Finding::Object { id, .. } | Finding::Missing { id, .. } => *id,
+        }
+    }
+
+    /// Return the severity of this finding, with missing objects always being errors.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Finding::Object { severity, .. } => *severity,
+            Finding::Missing { .. } => Severity::Error,
+        }
+    }
+}
+
+/// The result of [`Repository::fsck()`](crate::Repository::fsck()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All problems with the content of objects ordered by object id, followed by all missing objects in the order they were
+    /// encountered.
+    pub findings: Vec<Finding>,
+}
+
+impl Outcome {
+    /// Return `true` if any of the findings is an error, which is when `git fsck` would fail.
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|finding| finding.severity() == Severity::Error)
+    }
+
+    /// Write all findings to `out` like `git fsck` does, with lines like `error in commit <id>: badDate: <message>`,
+    /// `warning in tree <id>: hasDot: <message>` or `missing blob <id>`.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for finding in &self.findings {
+            match finding {
+                Finding::Object {
+                    id,
+                    kind,
+                    severity,
+                    problem,
+                } => {
+                    let level = if *severity == Severity::Error {
+                        "error"
+                    } else {
+                        "warning"
+                    };
+                    writeln!(out, "{level} in {kind} {id}: {}: {}", problem.id, problem.message)?;
+                }
+                Finding::Missing { id, kind } => match kind {
+                    Some(kind) => writeln!(out, "missing {kind} {id}")?,
+                    None => writeln!(out, "missing object {id}")?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::fsck()`](crate::Repository::fsck()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    LoadIndex(#[from] gix_odb::store::load_index::Error),
+    #[error(transparent)]
+    IterateObjects(#[from] gix_odb::loose::iter::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    TryFindObject(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    References(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    DecodeReflog(#[from] gix_ref::file::log::iter::decode::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Shallow(#[from] crate::shallow::open::Error),
+    #[error(transparent)]
+    SkipListPath(#[from] crate::config::path::interpolate::Error),
+    #[error("Could not read the skip list at '{}'", path.display())]
+    ReadSkipList {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("The skip list at '{}' contains the invalid object id '{line}'", path.display())]
+    InvalidSkipListEntry {
+        path: std::path::PathBuf,
+        line: crate::bstr::BString,
+    },
+    #[error(transparent)]
+    Severity(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,232 @@
+//! Types for use with [`Repository::grep()`](crate::Repository::grep()).
+use std::{io::Write, ops::Range};
+
+use crate::bstr::{BString, ByteSlice};
+
+/// Where to take the files to search from, for use with [`Repository::grep()`](crate::Repository::grep()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Search the files tracked in the index as they are in the worktree, the default like with `git grep`.
+    ///
+    /// Files that don't exist in the worktree are skipped.
+    #[default]
+    Worktree,
+    /// Search the files as they are stored in the index, like `git grep --cached`.
+    Index,
+    /// Search the files of the tree with the given id, or the tree of the commit with the given id, like `git grep <tree-ish>`.
+    Tree(gix_hash::ObjectId),
+}
+
+/// What to search for in each line, for use with [`Repository::grep()`](crate::Repository::grep()).
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Search for the given bytes, like `git grep --fixed-strings`.
+    Fixed(BString),
+    /// Search for matches of a regular expression.
+    ///
+    /// Note that [`Options::ignore_case`] doesn't affect these, so the expression must be case-insensitive itself if needed,
+    /// like `(?i-u)pattern` as the `regex` crate is used without support for Unicode.
+    #[cfg(feature = "revparse-regex")]
+    Regex(regex::bytes::Regex),
+}
+
+impl From<&str> for Pattern {
+    fn from(value: &str) -> Self {
+        Pattern::Fixed(value.into())
+    }
+}
+
+impl From<BString> for Pattern {
+    fn from(value: BString) -> Self {
+        Pattern::Fixed(value)
+    }
+}
+
+#[cfg(feature = "revparse-regex")]
+impl From<regex::bytes::Regex> for Pattern {
+    fn from(value: regex::bytes::Regex) -> Self {
+        Pattern::Regex(value)
+    }
+}
+
+/// Options for use with [`Repository::grep()`](crate::Repository::grep()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Where to take the files to search from.
+    pub source: Source,
+    /// If `true`, [fixed patterns](Pattern::Fixed) match regardless of the case of ASCII letters, like `git grep -i`.
+    pub ignore_case: bool,
+    /// If `true`, only match whole words, like `git grep -w`, so that a match must be at the start of a line or after a character
+    /// that can't be part of a word, and at the end of a line or before such a character.
+    pub word: bool,
+    /// If `true`, search binary files like text files, like `git grep -a`.
+    pub text: bool,
+    /// The amount of threads to search files with, or `None` to use all logical cores.
+    pub threads: Option<usize>,
+}
+
+/// A line of a [`File`] with at least one match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// The one-based number of the line.
+    pub number: usize,
+    /// The content of the line, without its line terminator.
+    pub content: BString,
+    /// The byte ranges of all non-overlapping matches in `content`, in order.
+    pub matches: Vec<Range<usize>>,
+}
+
+impl Line {
+    /// Return the one-based byte column of the first match, like `git grep --column` shows it.
+    pub fn column(&self) -> usize {
+        self.matches.first().map_or(1, |m| m.start + 1)
+    }
+}
+
+/// A file with at least one match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct File {
+    /// The path of the file relative to the root of the repository.
+    pub path: BString,
+    /// If `true`, the file is binary and `lines` is empty, as `git grep` only mentions that binary files match.
+    ///
+    /// Files are classified like [`binary::Purpose::Diff`](crate::binary::Purpose::Diff) describes, so they are binary if their
+    /// `diff` attribute is unset, like it is with the `binary` attribute, or if their content looks binary.
+    pub binary: bool,
+    /// All lines with matches, in order.
+    pub lines: Vec<Line>,
+}
+
+/// The result of [`Repository::grep()`](crate::Repository::grep()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All files with matches, ordered by path.
+    pub files: Vec<File>,
+}
+
+impl Outcome {
+    /// Write all matches to `out` like `git grep --line-number --column` does, with a line like `path:number:column:content`
+    /// for each line with matches, or `Binary file path matches` for binary files.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for file in &self.files {
+            if file.binary {
+                writeln!(out, "Binary file {} matches", file.path)?;
+                continue;
+            }
+            for line in &file.lines {
+                writeln!(out, "{}:{}:{}:{}", file.path, line.number, line.column(), line.content)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::grep()`](crate::Repository::grep()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Pathspec(#[from] crate::pathspec::init::Error),
+    #[error(transparent)]
+    AttributeStack(#[from] crate::config::attribute_stack::Error),
+    #[error(transparent)]
+    BinaryDetector(#[from] crate::binary::detector::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToTree(#[from] crate::object::peel::to_kind::Error),
+    #[error("Searching a worktree requires a repository with a worktree")]
+    MissingWorktree,
+    #[error("Could not read the file at '{}'", path.display())]
+    ReadFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Find all matches of any of `patterns` in the lines of `data` and return the lines that have matches.
+pub(crate) fn search(data: &[u8], patterns: &[Pattern], options: &Options) -> Vec<Line> {
+    let lowercase_patterns: Vec<_> = patterns
+        .iter()
+        .map(|pattern| match pattern {
+            Pattern::Fixed(text) if options.ignore_case => Some(text.to_ascii_lowercase()),
+            _ => None,
+        })
+        .collect();
+    let mut out = Vec::new();
+    for (idx, line) in data.lines_with_terminator().enumerate() {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let lowercase_line = options.ignore_case.then(|| line.to_ascii_lowercase());
+        let mut matches = Vec::new();
+        for (pattern, lowercase_pattern) in patterns.iter().zip(&lowercase_patterns) {
+            match pattern {
+                Pattern::Fixed(text) => {
+                    let (haystack, needle): (&[u8], &[u8]) = match (&lowercase_line, lowercase_pattern) {
+                        (Some(line), Some(pattern)) => (line, pattern),
+                        _ => (line, text),
+                    };
+                    if needle.is_empty() {
+                        matches.push(0..0);
+                        continue;
+                    }
+                    let mut start = 0;
+                    while let Some(pos) = haystack[start..].find(needle) {
+                        let range = start + pos..start + pos + needle.len();
+                        if !options.word || is_word(line, &range) {
+                            start = range.end;
+                            matches.push(range);
+                        } else {
+                            start = range.start + 1;
+                        }
+                    }
+                }
+                #[cfg(feature = "revparse-regex")]
+                Pattern::Regex(regex) => {
+                    let mut start = 0;
+                    while let Some(found) = regex.find_at(line, start) {
+                        let range = found.range();
+                        if !options.word || is_word(line, &range) {
+                            matches.push(range.clone());
+                            start = if range.is_empty() { range.end + 1 } else { range.end };
+                        } else {
+                            start = range.start + 1;
+                        }
+                        if start > line.len() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        if matches.is_empty() {
+            continue;
+        }
+        matches.sort_by_key(|range| range.start);
+        matches.dedup_by(|next, previous| {
+            let overlaps = next.start < previous.end;
+            if overlaps {
+                previous.end = previous.end.max(next.end);
+            }
+            overlaps
+        });
+        out.push(Line {
+            number: idx + 1,
+            content: line.into(),
+            matches,
+        });
+    }
+    out
+}
+
+/// Return `true` if `range` of `line` isn't preceded or followed by a character that can be part of a word.
+fn is_word(line: &[u8], range: &Range<usize>) -> bool {
+    let is_word_byte = |b: &u8| b.is_ascii_alphanumeric() || *b == b'_';
+    let before = range.start.checked_sub(1).and_then(|idx| line.get(idx));
+    before.filter(|b| is_word_byte(b)).is_none() && line.get(range.end).filter(|b| is_word_byte(b)).is_none()
+}
@@ -0,0 +1,129 @@
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{bstr::BString, ext::ObjectIdExt, head::Kind, Head};
+
+mod error {
+    use crate::reference;
+
+    /// The error returned by [`Head::attach_to_branch()`](super::Head::attach_to_branch()),
+    /// [`Head::detach_at()`](super::Head::detach_at()) and [`Head::commit()`](super::Head::commit()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ReferenceNameValidation(#[from] gix_ref::name::Error),
+        #[error(transparent)]
+        FindReference(#[from] reference::find::Error),
+        #[error(transparent)]
+        EditReference(#[from] reference::edit::Error),
+        #[error(transparent)]
+        Commit(#[from] crate::commit::Error),
+    }
+}
+
+pub use error::Error;
+
+/// Editing
+impl<'repo> Head<'repo> {
+    /// Point `HEAD` symbolically to `branch`, like `git symbolic-ref -m <message> HEAD <branch>`, and update this instance accordingly.
+    ///
+    /// If `branch` doesn't exist yet, `HEAD` becomes [unborn](Kind::Unborn) and the branch will be created by the next
+    /// [commit](Self::commit()). Otherwise, the reflog of `HEAD` receives an entry with `message` that records the transition
+    /// to the commit `branch` points to. Note that neither the index nor the worktree are changed.
+    pub fn attach_to_branch<Name, E>(&mut self, branch: Name, message: impl Into<BString>) -> Result<(), Error>
+    where
+        Name: TryInto<FullName, Error = E>,
+        Error: From<E>,
+    {
+        let branch = branch.try_into()?;
+        let reference = self.repo.try_find_reference(&branch)?.map(crate::Reference::detach);
+        let log = LogChange {
+            mode: RefLog::AndReference,
+            force_create_reflog: false,
+            message: message.into(),
+        };
+        if let Some(id) = reference.as_ref().and_then(|r| r.target.try_id()) {
+            // Write the reflog first as changes to symbolic references don't write reflogs.
+            self.repo.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::Only,
+                        ..log.clone()
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(id.to_owned()),
+                },
+                name: self.name().to_owned(),
+                deref: false,
+            })?;
+        }
+        self.repo.edit_reference(RefEdit {
+            change: Change::Update {
+                log,
+                expected: PreviousValue::Any,
+                new: Target::Symbolic(branch.clone()),
+            },
+            name: self.name().to_owned(),
+            deref: false,
+        })?;
+        self.kind = match reference {
+            Some(reference) => Kind::Symbolic(reference),
+            None => Kind::Unborn(branch),
+        };
+        Ok(())
+    }
+
+    /// Point `HEAD` directly to `id`, like `git update-ref -m <message> --no-deref HEAD <id>`, and update this instance accordingly.
+    ///
+    /// The branch `HEAD` pointed to, if any, remains unchanged, as do the index and the worktree.
+    pub fn detach_at(&mut self, id: impl Into<ObjectId>, message: impl Into<BString>) -> Result<(), Error> {
+        let id = id.into();
+        self.repo.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(id),
+            },
+            name: self.name().to_owned(),
+            deref: false,
+        })?;
+        self.kind = Kind::Detached {
+            target: id,
+            peeled: None,
+        };
+        Ok(())
+    }
+
+    /// Create a commit with `message` and `tree` on top of the commit `HEAD` points to, and point `HEAD`, or the branch it points to,
+    /// to it. Return the id of the new commit and update this instance accordingly.
+    ///
+    /// If `HEAD` is [unborn](Kind::Unborn), the commit has no parents and the branch is created, so the first commit of an empty
+    /// repository needs no special treatment. See [`Repository::commit()`](crate::Repository::commit()) for details
+    /// on how author and committer are obtained.
+    pub fn commit(&mut self, message: impl AsRef<str>, tree: impl Into<ObjectId>) -> Result<crate::Id<'repo>, Error> {
+        let parent = self.id().map(crate::Id::detach);
+        let id = self.repo.commit("HEAD", message, tree, parent)?.detach();
+        self.kind = match &self.kind {
+            Kind::Symbolic(gix_ref::Reference { name, .. }) | Kind::Unborn(name) => {
+                Kind::Symbolic(gix_ref::Reference {
+                    name: name.clone(),
+                    target: Target::Peeled(id),
+                    peeled: None,
+                })
+            }
+            Kind::Detached { .. } => Kind::Detached {
+                target: id,
+                peeled: None,
+            },
+        };
+        Ok(id.attach(self.repo))
+    }
+}
@@ -116,6 +116,10 @@ mod remote {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod edit;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod log;
@@ -0,0 +1,367 @@
+//! Find and run hooks like `pre-commit` or `reference-transaction`, as configured by `core.hooksPath`.
+//!
+//! Each hook is described by an [`Invocation`] which carries its name along with the arguments, standard input and
+//! environment documented in [`githooks(5)`](https://git-scm.com/docs/githooks), and which is passed to an implementation
+//! of [`Run`] to execute it. The [`Executor`] obtained by [`Repository::hooks()`](crate::Repository::hooks())
+//! runs actual programs in the hooks directory, while tests or applications that want to handle hooks themselves
+//! can use any `FnMut(&Invocation) -> Result<Option<Outcome>, run::Error>` instead.
+#![allow(clippy::empty_docs)]
+
+use std::{
+    ffi::{OsStr, OsString},
+    path::{Path, PathBuf},
+};
+
+use gix_hash::ObjectId;
+
+use crate::bstr::BString;
+
+/// A description of a hook to run, along with everything it receives when it is run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    /// The name of the hook, like `pre-commit`, which is also the name of the program in the hooks directory.
+    pub name: String,
+    /// The arguments to pass to the hook.
+    pub args: Vec<OsString>,
+    /// The data to write to the standard input of the hook, or `None` if it doesn't receive anything.
+    pub stdin: Option<Vec<u8>>,
+    /// Additional environment variables to set for the hook.
+    pub env: Vec<(OsString, OsString)>,
+}
+
+/// The source of a commit message, as passed to the [`prepare-commit-msg`](Invocation::prepare_commit_msg()) hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageSource {
+    /// The message was given on the command-line, like with `-m` or `-F`.
+    Message,
+    /// The message was read from a template, like with `-t` or `commit.template`.
+    Template,
+    /// The commit is a merge, or a `.git/MERGE_MSG` file exists.
+    Merge,
+    /// A `.git/SQUASH_MSG` file exists.
+    Squash,
+    /// The message was taken from the given commit, like with `-c`, `-C` or `--amend`.
+    Commit(ObjectId),
+}
+
+/// The command that rewrote commits, as passed to the [`post-rewrite`](Invocation::post_rewrite()) hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewriteCommand {
+    /// `git commit --amend`.
+    Amend,
+    /// `git rebase`.
+    Rebase,
+}
+
+/// The state of a reference transaction, as passed to the [`reference-transaction`](Invocation::reference_transaction()) hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionState {
+    /// All reference updates were locked and are about to be committed.
+    Prepared,
+    /// The transaction was committed and all references have their new values.
+    Committed,
+    /// The transaction was aborted and no references were changed.
+    Aborted,
+}
+
+/// A reference to update on the remote, as passed to the [`pre-push`](Invocation::pre_push()) hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushUpdate {
+    /// The name of the local reference that is pushed, or `(delete)` if the remote reference is deleted.
+    pub local_ref: BString,
+    /// The id that is pushed, or the null id if the remote reference is deleted.
+    pub local_id: ObjectId,
+    /// The full name of the reference on the remote.
+    pub remote_ref: gix_ref::FullName,
+    /// The id of the reference on the remote, or the null id if it doesn't exist yet.
+    pub remote_id: ObjectId,
+}
+
+/// A reference update, as passed to the [`reference-transaction`](Invocation::reference_transaction()) hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefUpdate {
+    /// The previous value of the reference, or the null id if it didn't exist or isn't known.
+    pub previous: ObjectId,
+    /// The new value of the reference, or the null id if it is deleted.
+    pub new: ObjectId,
+    /// The name of the reference.
+    pub name: gix_ref::FullName,
+}
+
+/// Lifecycle
+impl Invocation {
+    /// Create a new invocation of the hook `name` without arguments, standard input or additional environment.
+    pub fn new(name: impl Into<String>) -> Self {
+        Invocation {
+            name: name.into(),
+            args: Vec::new(),
+            stdin: None,
+            env: Vec::new(),
+        }
+    }
+
+    /// Add `arg` to the arguments passed to the hook.
+    pub fn arg(mut self, arg: impl Into<OsString>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Set the environment variable `key` to `value` when running the hook.
+    pub fn env(mut self, key: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Write `data` to the standard input of the hook.
+    pub fn stdin(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.stdin = Some(data.into());
+        self
+    }
+}
+
+/// Well-known hooks
+impl Invocation {
+    /// The `pre-commit` hook, run before a commit is created with the index at `index_path`, which is passed in `GIT_INDEX_FILE`.
+    pub fn pre_commit(index_path: impl Into<OsString>) -> Self {
+        Self::new("pre-commit").env("GIT_INDEX_FILE", index_path)
+    }
+
+    /// The `pre-merge-commit` hook, run before a merge commit is created with the index at `index_path`.
+    pub fn pre_merge_commit(index_path: impl Into<OsString>) -> Self {
+        Self::new("pre-merge-commit").env("GIT_INDEX_FILE", index_path)
+    }
+
+    /// The `prepare-commit-msg` hook, which may edit the commit message in `message_file` after it was obtained from `source`.
+    pub fn prepare_commit_msg(message_file: impl AsRef<Path>, source: Option<MessageSource>) -> Self {
+        let hook = Self::new("prepare-commit-msg").arg(message_file.as_ref());
+        match source {
+            None => hook,
+            Some(MessageSource::Message) => hook.arg("message"),
+            Some(MessageSource::Template) => hook.arg("template"),
+            Some(MessageSource::Merge) => hook.arg("merge"),
+            Some(MessageSource::Squash) => hook.arg("squash"),
+            Some(MessageSource::Commit(id)) => hook.arg("commit").arg(id.to_string()),
+        }
+    }
+
+    /// The `commit-msg` hook, which may edit or reject the commit message in `message_file`.
+    pub fn commit_msg(message_file: impl AsRef<Path>) -> Self {
+        Self::new("commit-msg").arg(message_file.as_ref())
+    }
+
+    /// The `post-commit` hook, run after a commit was created.
+    pub fn post_commit() -> Self {
+        Self::new("post-commit")
+    }
+
+    /// The `post-checkout` hook, run after `HEAD` changed from `previous_head` to `new_head`, with `branch_checkout` being `true`
+    /// if a branch was checked out, or `false` if only files were checked out.
+    pub fn post_checkout(previous_head: ObjectId, new_head: ObjectId, branch_checkout: bool) -> Self {
+        Self::new("post-checkout")
+            .arg(previous_head.to_string())
+            .arg(new_head.to_string())
+            .arg(if branch_checkout { "1" } else { "0" })
+    }
+
+    /// The `post-merge` hook, run after a merge, with `squash` being `true` if it was a squash merge.
+    pub fn post_merge(squash: bool) -> Self {
+        Self::new("post-merge").arg(if squash { "1" } else { "0" })
+    }
+
+    /// The `pre-rebase` hook, run before the current branch, or `branch` if set, is rebased onto `upstream`.
+    pub fn pre_rebase(upstream: impl Into<OsString>, branch: Option<&OsStr>) -> Self {
+        let hook = Self::new("pre-rebase").arg(upstream);
+        match branch {
+            Some(branch) => hook.arg(branch),
+            None => hook,
+        }
+    }
+
+    /// The `post-rewrite` hook, run after `command` rewrote commits, receiving one `<old-id> <new-id>` line per
+    /// rewritten commit in `rewritten`.
+    pub fn post_rewrite(command: RewriteCommand, rewritten: impl IntoIterator<Item = (ObjectId, ObjectId)>) -> Self {
+        let mut stdin = Vec::new();
+        for (old, new) in rewritten {
+            stdin.extend_from_slice(format!("{old} {new}\n").as_bytes());
+        }
+        Self::new("post-rewrite")
+            .arg(match command {
+                RewriteCommand::Amend => "amend",
+                RewriteCommand::Rebase => "rebase",
+            })
+            .stdin(stdin)
+    }
+
+    /// The `pre-push` hook, run before pushing `updates` to the remote named `remote` at `url`, receiving one
+    /// `<local-ref> <local-id> <remote-ref> <remote-id>` line per update.
+    ///
+    /// If the push doesn't use a named remote, `remote` is the same as `url`.
+    pub fn pre_push(
+        remote: impl Into<OsString>,
+        url: impl Into<OsString>,
+        updates: impl IntoIterator<Item = PushUpdate>,
+    ) -> Self {
+        let mut stdin = Vec::new();
+        for update in updates {
+            stdin.extend_from_slice(&update.local_ref);
+            stdin.extend_from_slice(format!(" {} ", update.local_id).as_bytes());
+            stdin.extend_from_slice(update.remote_ref.as_bstr());
+            stdin.extend_from_slice(format!(" {}\n", update.remote_id).as_bytes());
+        }
+        Self::new("pre-push").arg(remote).arg(url).stdin(stdin)
+    }
+
+    /// The `reference-transaction` hook, run whenever a reference transaction reaches `state`, receiving one
+    /// `<old-value> <new-value> <ref-name>` line per reference in `updates`.
+    pub fn reference_transaction(state: TransactionState, updates: impl IntoIterator<Item = RefUpdate>) -> Self {
+        let mut stdin = Vec::new();
+        for update in updates {
+            stdin.extend_from_slice(format!("{} {} ", update.previous, update.new).as_bytes());
+            stdin.extend_from_slice(update.name.as_bstr());
+            stdin.push(b'\n');
+        }
+        Self::new("reference-transaction")
+            .arg(match state {
+                TransactionState::Prepared => "prepared",
+                TransactionState::Committed => "committed",
+                TransactionState::Aborted => "aborted",
+            })
+            .stdin(stdin)
+    }
+
+    /// The `pre-auto-gc` hook, run before an automatic garbage collection.
+    pub fn pre_auto_gc() -> Self {
+        Self::new("pre-auto-gc")
+    }
+}
+
+/// The result of running a hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The exit code of the hook, or `None` if it was terminated by a signal.
+    pub code: Option<i32>,
+    /// Everything the hook wrote to its standard output.
+    ///
+    /// `git` shows it to the user as part of its own error output, and callers should do the same.
+    pub stdout: Vec<u8>,
+}
+
+impl Outcome {
+    /// Return `true` if the hook exited successfully, which allows the operation it guards to proceed.
+    pub fn success(&self) -> bool {
+        self.code == Some(0)
+    }
+}
+
+/// A way to run hooks, implemented by [`Executor`] and by closures to stub out hooks in tests.
+pub trait Run {
+    /// Run the hook described by `invocation` and return its outcome, or `None` if there is no such hook,
+    /// in which case the operation should proceed as if it succeeded.
+    fn run(&mut self, invocation: &Invocation) -> Result<Option<Outcome>, run::Error>;
+}
+
+impl<F> Run for F
+where
+    F: FnMut(&Invocation) -> Result<Option<Outcome>, run::Error>,
+{
+    fn run(&mut self, invocation: &Invocation) -> Result<Option<Outcome>, run::Error> {
+        self(invocation)
+    }
+}
+
+/// Run hooks as programs in a hooks directory, obtained by [`Repository::hooks()`](crate::Repository::hooks()).
+#[derive(Debug, Clone)]
+pub struct Executor {
+    /// The directory containing the hooks, which may not exist.
+    pub dir: PathBuf,
+    /// The directory in which hooks are run, which is the root of the worktree or the `.git` directory of bare repositories.
+    pub cwd: PathBuf,
+    /// The context to pass to each hook, which sets `GIT_DIR` and `GIT_WORK_TREE`.
+    pub context: gix_command::Context,
+}
+
+impl Executor {
+    /// Return the path to the hook `name` if it exists and is executable.
+    ///
+    /// On Windows, every file is considered executable, just like `git` does.
+    pub fn find(&self, name: &str) -> Option<PathBuf> {
+        let path = self.dir.join(name);
+        let metadata = std::fs::metadata(&path).ok()?;
+        (metadata.is_file() && (!cfg!(unix) || gix_fs::is_executable(&metadata))).then_some(path)
+    }
+}
+
+impl Run for Executor {
+    fn run(&mut self, invocation: &Invocation) -> Result<Option<Outcome>, run::Error> {
+        let Some(path) = self.find(&invocation.name) else {
+            return Ok(None);
+        };
+        let mut prepare = gix_command::prepare(&path)
+            .with_context(self.context.clone())
+            .args(invocation.args.iter().cloned())
+            .stdin(if invocation.stdin.is_some() {
+                std::process::Stdio::piped()
+            } else {
+                std::process::Stdio::null()
+            })
+            .stdout(std::process::Stdio::piped());
+        for (key, value) in &invocation.env {
+            prepare = prepare.env(key, value);
+        }
+        let mut cmd: std::process::Command = prepare.into();
+        let mut child = cmd
+            .current_dir(&self.cwd)
+            .spawn()
+            .map_err(|err| run::Error::Spawn { source: err, path })?;
+
+        let stdin = child.stdin.take();
+        let mut stdout = Vec::new();
+        std::thread::scope(|scope| -> std::io::Result<()> {
+            if let (Some(mut stdin), Some(data)) = (stdin, invocation.stdin.as_deref()) {
+                scope.spawn(move || {
+                    use std::io::Write;
+                    // Hooks are free to not read their input, just like `git` ignores `SIGPIPE` here.
+                    stdin.write_all(data).ok();
+                });
+            }
+            if let Some(mut out) = child.stdout.take() {
+                use std::io::Read;
+                out.read_to_end(&mut stdout)?;
+            }
+            Ok(())
+        })?;
+        let status = child.wait()?;
+        Ok(Some(Outcome {
+            code: status.code(),
+            stdout,
+        }))
+    }
+}
+
+///
+pub mod run {
+    /// The error returned by [`Run::run()`](super::Run::run()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not run hook at '{}'", path.display())]
+        Spawn {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+pub mod executor {
+    /// The error returned by [`Repository::hooks()`](crate::Repository::hooks()) and
+    /// [`Repository::hooks_dir()`](crate::Repository::hooks_dir()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The value of core.hooksPath could not be interpolated")]
+        PathInterpolation(#[from] gix_config::path::interpolate::Error),
+    }
+}
@@ -246,6 +246,13 @@ where
 /// The flag behind all utility functions in this module.
 pub static IS_INTERRUPTED: AtomicBool = AtomicBool::new(false);
 
+/// Return a token backed by [`IS_INTERRUPTED`], which is triggered by the signal handler installed with `init_handler()`.
+///
+/// Clones of it can be handed to threads or operations to let them observe or request the process-wide interrupt.
+pub fn token() -> gix_features::interrupt::Token {
+    gix_features::interrupt::Token::from_static(&IS_INTERRUPTED)
+}
+
 /// Returns true if an interrupt is requested.
 pub fn is_triggered() -> bool {
     IS_INTERRUPTED.load(Ordering::Relaxed)
@@ -30,6 +30,8 @@
 //! By default, the [`Repository`] isn't `Sync` and thus can't be used in certain contexts which require the `Sync` trait.
 //!
 //! To help with this, convert it with [`.into_sync()`][Repository::into_sync()] into a [`ThreadSafeRepository`].
+//! To hand out handles to threads of a thread-pool, convert it with [`.into_pool()`][Repository::into_pool()] into a
+//! [`repository::Pool`], which creates handles that are configured just like the original repository.
 //!
 //! ### Object-Access Performance
 //!
@@ -158,8 +160,9 @@ pub mod path;
 
 /// The standard type for a store to handle git references.
 pub type RefStore = gix_ref::file::Store;
-/// A handle for finding objects in an object database, abstracting away caches for thread-local use.
-pub type OdbHandle = gix_odb::Handle;
+/// A handle for finding objects in an object database, abstracting away caches for thread-local use, which can also
+/// keep written objects in memory.
+pub type OdbHandle = gix_odb::memory::Proxy<gix_odb::Handle>;
 /// A way to access git configuration
 pub(crate) type Config = OwnShared<gix_config::File<'static>>;
 
@@ -173,24 +176,78 @@ pub use types::{
 #[cfg(feature = "attributes")]
 pub use types::{Pathspec, PathspecDetached, Submodule};
 
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod am;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod apply;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod bisect;
+#[cfg(feature = "blob-diff")]
+pub mod binary;
+#[cfg(feature = "blob-diff")]
+pub mod blame;
+#[cfg(feature = "bundle")]
+pub mod bundle;
+#[cfg(feature = "revision")]
+pub mod cat_file;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod checkout;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod cherry_pick;
 ///
 #[allow(clippy::empty_docs)]
 pub mod clone;
 pub mod commit;
+pub mod count_objects;
 ///
 #[cfg(feature = "dirwalk")]
 #[allow(clippy::empty_docs)]
 pub mod dirwalk;
+pub mod fast_export;
+#[cfg(feature = "revision")]
+pub mod fast_import;
+#[cfg(feature = "revision")]
+pub mod for_each_ref;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+pub mod format_patch;
+#[cfg(feature = "fsck")]
+pub mod fsck;
+#[cfg(feature = "blob-diff")]
+pub mod grep;
 pub mod head;
+#[cfg(feature = "command")]
+pub mod hook;
 pub mod id;
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+pub mod log;
+#[cfg(feature = "status")]
+pub mod ls_files;
+#[cfg(feature = "maintenance")]
+pub mod maintenance;
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+pub mod merge;
 pub mod object;
 #[cfg(feature = "attributes")]
 pub mod pathspec;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+pub mod range_diff;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod rebase;
 pub mod reference;
 pub mod repository;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod reset;
+#[cfg(feature = "revision")]
+pub mod rev_list;
+#[cfg(feature = "mailmap")]
+pub mod shortlog;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod stash;
 #[cfg(feature = "attributes")]
 pub mod submodule;
 pub mod tag;
+pub mod trailers;
+pub mod update_refs;
 #[cfg(any(feature = "dirwalk", feature = "status"))]
 pub(crate) mod util;
 
@@ -311,6 +368,9 @@ pub mod config;
 #[cfg(feature = "mailmap")]
 pub mod mailmap;
 
+#[cfg(feature = "notes")]
+pub mod note;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod worktree;
@@ -0,0 +1,335 @@
+//! Iterate the commits that changed a set of paths, similar to `git log -- <pathspec>`.
+//!
+//! Use [`Repository::log()`](crate::Repository::log()) to create the iterator.
+use std::collections::{BinaryHeap, HashMap};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    ext::ObjectIdExt,
+    Pathspec, Repository,
+};
+
+/// Options for use with [`Repository::log()`](crate::Repository::log()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// The commits to start the iteration at, or `HEAD` if empty.
+    pub tips: Vec<ObjectId>,
+    /// Commits that are reachable from any of these are neither listed nor traversed, similar to `git log <tips> ^<hidden>`,
+    /// which allows to list ranges like `a..b`.
+    pub hidden: Vec<ObjectId>,
+    /// If `true`, only the first parent of merge commits is followed, similar to `git log --first-parent`.
+    pub first_parent: bool,
+    /// If `true`, the default, merge commits that don't change the paths compared to one of their parents are skipped, and
+    /// only that parent is followed, which prunes side branches whose changes didn't make it into the merge.
+    ///
+    /// If `false`, all parents are followed and merges are listed if they differ from at least one of their parents, similar
+    /// to `git log --full-history`.
+    pub simplify: bool,
+    /// If `true`, the single pathspec must be the path of a file, whose history will be followed across renames, similar to
+    /// `git log --follow`.
+    pub follow: bool,
+    /// The configuration to detect renames with when [following](Self::follow) a file.
+    pub rewrites: gix_diff::Rewrites,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            tips: Vec::new(),
+            hidden: Vec::new(),
+            first_parent: false,
+            simplify: true,
+            follow: false,
+            rewrites: Default::default(),
+        }
+    }
+}
+
+/// A commit as returned by the [`Iter`].
+#[derive(Debug, Clone)]
+pub struct Info<'repo> {
+    /// The id of the commit.
+    pub id: ObjectId,
+    /// The ids of all parents of the commit, independently of which of them were followed.
+    pub parent_ids: Vec<ObjectId>,
+    /// The time at which the commit was created, in seconds since the unix epoch.
+    pub commit_time: gix_date::SecondsSinceUnixEpoch,
+    /// The path of the followed file in this commit, which may differ from the path it was found at in the tips
+    /// if it was renamed. It's only set if [`Options::follow`] is `true`.
+    pub location: Option<BString>,
+
+    repo: &'repo Repository,
+}
+
+/// Access
+impl<'repo> Info<'repo> {
+    /// Provide an attached version of our [`id`](Info::id) field.
+    pub fn id(&self) -> crate::Id<'repo> {
+        self.id.attach(self.repo)
+    }
+
+    /// Read the whole commit object from the object database.
+    pub fn object(&self) -> Result<crate::Commit<'repo>, crate::object::find::existing::Error> {
+        Ok(self.id().object()?.into_commit())
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init {
+    use crate::bstr::BString;
+
+    /// The error returned by [`Repository::log()`](crate::Repository::log()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        Pathspec(#[from] crate::pathspec::init::Error),
+        #[error(transparent)]
+        HeadId(#[from] crate::reference::head_id::Error),
+        #[error("Following renames requires exactly one path to a file, got {}", patterns.len())]
+        FollowRequiresSinglePath { patterns: Vec<BString> },
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        ShallowCommits(#[from] crate::shallow::open::Error),
+        #[error(transparent)]
+        HiddenWalk(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        HiddenWalkItem(#[from] gix_traverse::commit::simple::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod iter {
+    /// The error returned by the [`Iter`](super::Iter).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        TryIntoTree(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        TreeDiff(#[from] crate::object::tree::diff::for_each::Error),
+        #[error(transparent)]
+        TreeDiffPlatform(#[from] crate::diff::new_rewrites::Error),
+    }
+}
+
+/// What to compare commits with to learn if they changed something of interest.
+pub(crate) enum Filter<'repo> {
+    /// All commits are of interest.
+    None,
+    /// Commits that change paths matching the pathspec are of interest.
+    Pathspec(Box<Pathspec<'repo>>),
+    /// Commits that change the file at the path stored for each of them are of interest, following renames.
+    Follow { rewrites: gix_diff::Rewrites },
+}
+
+/// An iterator over commits that change a set of paths, newest first, as returned by [`Repository::log()`](crate::Repository::log()).
+pub struct Iter<'repo> {
+    pub(crate) repo: &'repo Repository,
+    pub(crate) filter: Filter<'repo>,
+    pub(crate) first_parent: bool,
+    pub(crate) simplify: bool,
+    pub(crate) queue: BinaryHeap<(gix_date::SecondsSinceUnixEpoch, ObjectId)>,
+    /// The followed path for each queued commit, if following a file.
+    pub(crate) locations: HashMap<ObjectId, BString>,
+    pub(crate) seen: gix_hashtable::HashSet<ObjectId>,
+    /// Commits reachable from hidden tips, which are never listed.
+    pub(crate) hidden: gix_hashtable::HashSet<ObjectId>,
+    /// The commits at the shallow boundary, which are treated as if they had no parents.
+    pub(crate) shallow: Option<crate::shallow::Commits>,
+    pub(crate) buf: Vec<u8>,
+}
+
+struct Commit {
+    time: gix_date::SecondsSinceUnixEpoch,
+    tree: ObjectId,
+    parents: Vec<ObjectId>,
+}
+
+impl<'repo> Iter<'repo> {
+    pub(crate) fn enqueue(&mut self, id: ObjectId, time: gix_date::SecondsSinceUnixEpoch, location: Option<BString>) {
+        if self.hidden.contains(&id) || !self.seen.insert(id) {
+            return;
+        }
+        if let Some(location) = location {
+            self.locations.insert(id, location);
+        }
+        self.queue.push((time, id));
+    }
+
+    fn commit(&self, id: ObjectId) -> Result<Commit, iter::Error> {
+        let commit = self.repo.find_object(id)?.into_commit();
+        let decoded = commit.decode()?;
+        let is_shallow = matches!(&self.shallow, Some(shallow) if shallow.binary_search(&id).is_ok());
+        Ok(Commit {
+            time: decoded.committer.time.seconds,
+            tree: decoded.tree(),
+            parents: if is_shallow {
+                Vec::new()
+            } else {
+                decoded.parents().collect()
+            },
+        })
+    }
+
+    /// Return `true` if `tree` and `parent_tree` are the same as far as our filter is concerned, along with the location of
+    /// the followed file in `parent_tree`.
+    fn is_treesame(
+        &mut self,
+        parent_tree: ObjectId,
+        tree: ObjectId,
+        location: Option<&BStr>,
+    ) -> Result<(bool, Option<BString>), iter::Error> {
+        let repo = self.repo;
+        let old_tree = repo.find_object(parent_tree)?.try_into_tree()?;
+        let new_tree = repo.find_object(tree)?.try_into_tree()?;
+        match &mut self.filter {
+            Filter::None => Ok((false, None)),
+            Filter::Pathspec(pathspec) => {
+                let mut changed = false;
+                let res = old_tree
+                    .changes()?
+                    .track_path()
+                    .track_rewrites(None)
+                    .for_each_to_obtain_tree(&new_tree, |change| {
+                        if !change.event.entry_mode().is_tree() && pathspec.is_included(change.location, Some(false)) {
+                            changed = true;
+                            return Ok::<_, std::convert::Infallible>(crate::object::tree::diff::Action::Cancel);
+                        }
+                        Ok(crate::object::tree::diff::Action::Continue)
+                    });
+                match res {
+                    // Cancelling the diff is how we stop at the first change of interest.
+                    Err(crate::object::tree::diff::for_each::Error::Diff(
+                        gix_diff::tree::changes::Error::Cancelled,
+                    )) if changed => {}
+                    res => {
+                        res?;
+                    }
+                }
+                Ok((!changed, None))
+            }
+            Filter::Follow { rewrites } => {
+                let location = location.expect("always set when following");
+                let old = old_tree
+                    .lookup_entry(location.split_str("/"), &mut self.buf)?
+                    .map(|entry| (entry.mode(), entry.object_id()));
+                let new = new_tree
+                    .lookup_entry(location.split_str("/"), &mut self.buf)?
+                    .map(|entry| (entry.mode(), entry.object_id()));
+                match (old, new) {
+                    (Some(old), new) => Ok((Some(old) == new, Some(location.to_owned()))),
+                    (None, None) => Ok((true, Some(location.to_owned()))),
+                    (None, Some(_)) => {
+                        let mut source = None;
+                        old_tree
+                            .changes()?
+                            .track_path()
+                            .track_rewrites(Some(*rewrites))
+                            .for_each_to_obtain_tree(&new_tree, |change| {
+                                if let crate::object::tree::diff::change::Event::Rewrite {
+                                    source_location,
+                                    copy: false,
+                                    ..
+                                } = change.event
+                                {
+                                    if change.location == location {
+                                        source = Some(source_location.to_owned());
+                                        return Ok::<_, std::convert::Infallible>(
+                                            crate::object::tree::diff::Action::Cancel,
+                                        );
+                                    }
+                                }
+                                Ok(crate::object::tree::diff::Action::Continue)
+                            })?;
+                        Ok((false, Some(source.unwrap_or_else(|| location.to_owned()))))
+                    }
+                }
+            }
+        }
+    }
+
+    fn next_inner(&mut self) -> Result<Option<Info<'repo>>, iter::Error> {
+        while let Some((commit_time, id)) = self.queue.pop() {
+            let commit = self.commit(id)?;
+            let location = self.locations.remove(&id);
+            let followed_parents = if self.first_parent {
+                &commit.parents[..commit.parents.len().min(1)]
+            } else {
+                &commit.parents[..]
+            };
+
+            let mut parents = Vec::with_capacity(followed_parents.len());
+            let mut treesame_parent = None;
+            let mut differs = false;
+            for &parent_id in followed_parents {
+                let parent = self.commit(parent_id)?;
+                let (treesame, parent_location) =
+                    self.is_treesame(parent.tree, commit.tree, location.as_ref().map(AsRef::as_ref))?;
+                if treesame {
+                    treesame_parent.get_or_insert(parents.len());
+                } else {
+                    differs = true;
+                }
+                parents.push((parent_id, parent.time, parent_location));
+            }
+            if parents.is_empty() {
+                let empty_tree = ObjectId::empty_tree(self.repo.object_hash());
+                differs = !self
+                    .is_treesame(empty_tree, commit.tree, location.as_ref().map(AsRef::as_ref))?
+                    .0;
+            }
+
+            let is_merge = parents.len() > 1;
+            let include = match self.filter {
+                Filter::None => true,
+                _ if is_merge && self.simplify => treesame_parent.is_none(),
+                _ => differs,
+            };
+            match treesame_parent.filter(|_| self.simplify && !matches!(self.filter, Filter::None)) {
+                Some(idx) => {
+                    let (parent_id, time, location) = parents.swap_remove(idx);
+                    self.enqueue(parent_id, time, location);
+                }
+                None => {
+                    for (parent_id, time, location) in parents {
+                        self.enqueue(parent_id, time, location);
+                    }
+                }
+            }
+            if include {
+                return Ok(Some(Info {
+                    id,
+                    parent_ids: commit.parents,
+                    commit_time,
+                    location,
+                    repo: self.repo,
+                }));
+            }
+        }
+        Ok(None)
+    }
+}
+
+impl<'repo> Iterator for Iter<'repo> {
+    type Item = Result<Info<'repo>, iter::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_inner().transpose()
+    }
+}
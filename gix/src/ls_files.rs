@@ -0,0 +1,127 @@
+//! Types for use with [`Repository::ls_files()`](crate::Repository::ls_files()).
+use std::io::Write;
+
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::ls_files()`](crate::Repository::ls_files()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, list all entries of the index, like `git ls-files --cached`, which is the default.
+    pub cached: bool,
+    /// If `true`, list entries of the index whose file is missing in the worktree, like `git ls-files --deleted`.
+    pub deleted: bool,
+    /// If `true`, list entries of the index whose file differs from the worktree, including deleted files,
+    /// like `git ls-files --modified`.
+    pub modified: bool,
+    /// If `true`, list untracked files in the worktree that aren't ignored, like `git ls-files --others --exclude-standard`.
+    pub others: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            cached: true,
+            deleted: false,
+            modified: false,
+            others: false,
+        }
+    }
+}
+
+/// The reason an [`Entry`] is listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// The entry is in the index.
+    Cached,
+    /// The entry is in the index, but its file is missing in the worktree.
+    Deleted,
+    /// The entry is in the index, but its file was changed in the worktree, or is missing.
+    Modified,
+    /// The file is in the worktree, but not in the index, and it isn't ignored.
+    Other,
+}
+
+/// A file listed by [`Repository::ls_files()`](crate::Repository::ls_files()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// Why the file is listed.
+    pub kind: Kind,
+    /// The path of the file relative to the root of the worktree.
+    pub rela_path: BString,
+    /// The index entry along with its stage, mode, id and flags, or `None` for [other](Kind::Other) files.
+    ///
+    /// Note that its path can't be obtained without the index it came from, use [`rela_path`](Self::rela_path) instead.
+    pub index_entry: Option<gix_index::Entry>,
+}
+
+impl Entry {
+    /// Return the tag that `git ls-files -t` shows for this entry, like `H` for cached files or `?` for others.
+    pub fn tag(&self) -> char {
+        match self.kind {
+            Kind::Cached => match &self.index_entry {
+                Some(entry) if entry.stage() != gix_index::entry::Stage::Unconflicted => 'M',
+                Some(entry) if entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE) => 'S',
+                _ => 'H',
+            },
+            Kind::Deleted => 'R',
+            Kind::Modified => 'C',
+            Kind::Other => '?',
+        }
+    }
+}
+
+/// The result of [`Repository::ls_files()`](crate::Repository::ls_files()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All [other](Kind::Other) files ordered by path, followed by each index entry once for each of the requested kinds
+    /// that apply to it, in index order.
+    pub entries: Vec<Entry>,
+}
+
+impl Outcome {
+    /// Write all entries to `out` like `git ls-files -t` does, with a line like `tag path` for each entry.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for entry in &self.entries {
+            writeln!(out, "{} {}", entry.tag(), entry.rela_path)?;
+        }
+        Ok(())
+    }
+
+    /// Write all index entries to `out` like `git ls-files --stage` does, with a line like `mode id stage\tpath` for each
+    /// entry, skipping [other](Kind::Other) files.
+    pub fn write_stage_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for (entry, index_entry) in self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.index_entry.as_ref().map(|index_entry| (entry, index_entry)))
+        {
+            writeln!(
+                out,
+                "{:06o} {} {}\t{}",
+                index_entry.mode.bits(),
+                index_entry.id,
+                index_entry.stage() as u8,
+                entry.rela_path
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::ls_files()`](crate::Repository::ls_files()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    Pathspec(#[from] crate::pathspec::init::Error),
+    #[error(transparent)]
+    StatusPlatform(#[from] crate::status::Error),
+    #[error(transparent)]
+    CreateStatusIterator(#[from] crate::status::index_worktree::iter::Error),
+    #[error(transparent)]
+    StatusItem(#[from] crate::status::index_worktree::Error),
+    #[error("Listing deleted, modified or other files requires a repository with a worktree")]
+    MissingWorktree,
+}
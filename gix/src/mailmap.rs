@@ -1,5 +1,64 @@
+use std::collections::HashMap;
+
+use gix_actor::{Identity, IdentityRef, SignatureRef};
 pub use gix_mailmap::*;
 
+/// Resolve the identities of many signatures according to a mailmap, as needed to group commits by author like `git shortlog`
+/// does, or to show the canonical authors of lines like `git blame` does.
+///
+/// Each distinct identity is looked up only once, and the resolved identities are kept for re-use.
+#[derive(Default, Clone)]
+pub struct Resolver {
+    snapshot: Snapshot,
+    resolved: HashMap<Identity, Identity>,
+}
+
+impl From<Snapshot> for Resolver {
+    fn from(snapshot: Snapshot) -> Self {
+        Resolver {
+            snapshot,
+            resolved: Default::default(),
+        }
+    }
+}
+
+impl Resolver {
+    /// Return the mailmap used to resolve identities.
+    pub fn snapshot(&self) -> &Snapshot {
+        &self.snapshot
+    }
+
+    /// Return the name and email of the identity that `signature` belongs to, which is the identity of `signature` itself
+    /// if the mailmap has no entry for it.
+    pub fn resolve(&mut self, signature: SignatureRef<'_>) -> IdentityRef<'_> {
+        let snapshot = &self.snapshot;
+        self.resolved
+            .entry(Identity {
+                name: signature.name.to_owned(),
+                email: signature.email.to_owned(),
+            })
+            .or_insert_with(|| {
+                let resolved = snapshot.resolve_cow(signature);
+                Identity {
+                    name: resolved.name.into_owned(),
+                    email: resolved.email.into_owned(),
+                }
+            })
+            .to_ref()
+    }
+
+    /// Like [`resolve()`](Self::resolve()), but return a signature with the time of `signature`.
+    pub fn resolve_signature(&mut self, signature: SignatureRef<'_>) -> gix_actor::Signature {
+        let time = signature.time;
+        let IdentityRef { name, email } = self.resolve(signature);
+        gix_actor::Signature {
+            name: name.to_owned(),
+            email: email.to_owned(),
+            time,
+        }
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod load {
@@ -0,0 +1,320 @@
+//! Types for use with [`Repository::maintenance()`](crate::Repository::maintenance()).
+use crate::bstr::BStr;
+
+/// A task that can be run by [`Repository::maintenance()`](crate::Repository::maintenance()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Task {
+    /// Move all loose references into the `packed-refs` file, like `git pack-refs --all` does.
+    PackRefs,
+    /// Remove reflog entries older than `gc.reflogExpire`, or older than `gc.reflogExpireUnreachable` if they aren't
+    /// reachable from the current value of their reference, like `git reflog expire --all` does.
+    ReflogExpire,
+    /// Remove loose objects that are also contained in a pack, and put all remaining loose objects into a new pack.
+    ///
+    /// Note that loose objects which were packed are only removed the next time this task runs, which gives concurrent
+    /// readers time to see the new pack.
+    LooseObjects,
+    /// Combine all packs that aren't marked to be kept with a `.keep` file into a single new pack and remove them.
+    IncrementalRepack,
+    /// Write a commit-graph file with all commits reachable from `HEAD` and all references.
+    CommitGraph,
+}
+
+impl Task {
+    /// All tasks in the order in which they are run.
+    pub const ALL: &'static [Task] = &[
+        Task::PackRefs,
+        Task::ReflogExpire,
+        Task::LooseObjects,
+        Task::IncrementalRepack,
+        Task::CommitGraph,
+    ];
+
+    /// Return the name of the task as used in `maintenance.<task>.*` configuration, like `commit-graph`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Task::PackRefs => "pack-refs",
+            Task::ReflogExpire => "reflog-expire",
+            Task::LooseObjects => "loose-objects",
+            Task::IncrementalRepack => "incremental-repack",
+            Task::CommitGraph => "commit-graph",
+        }
+    }
+
+    /// Return the task with the given `name`, compared case-insensitively, or `None` if there is no such task.
+    pub fn from_name(name: &BStr) -> Option<Self> {
+        Task::ALL
+            .iter()
+            .find(|task| name.eq_ignore_ascii_case(task.as_str().as_bytes()))
+            .copied()
+    }
+}
+
+impl std::fmt::Display for Task {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The frequency at which a task is scheduled, with more frequent schedules ordering first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Schedule {
+    /// The task runs every hour.
+    Hourly,
+    /// The task runs once a day.
+    Daily,
+    /// The task runs once a week.
+    Weekly,
+}
+
+impl Schedule {
+    /// Return the schedule with the given `name`, compared case-insensitively, like `hourly`.
+    pub fn from_name(name: &BStr) -> Option<Self> {
+        Some(match name.to_ascii_lowercase().as_slice() {
+            b"hourly" => Schedule::Hourly,
+            b"daily" => Schedule::Daily,
+            b"weekly" => Schedule::Weekly,
+            _ => return None,
+        })
+    }
+}
+
+/// The strategy that determines which tasks are enabled and how they are scheduled by default, as configured with
+/// `maintenance.strategy`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// All tasks are enabled, but none is scheduled.
+    #[default]
+    None,
+    /// Only [`CommitGraph`](Task::CommitGraph) (hourly), [`LooseObjects`](Task::LooseObjects) (daily),
+    /// [`IncrementalRepack`](Task::IncrementalRepack) (daily) and [`PackRefs`](Task::PackRefs) (weekly) are enabled
+    /// and scheduled.
+    Incremental,
+}
+
+impl Strategy {
+    /// Return the schedule of `task` if it isn't configured with `maintenance.<task>.schedule`.
+    pub fn schedule(&self, task: Task) -> Option<Schedule> {
+        match self {
+            Strategy::None => None,
+            Strategy::Incremental => match task {
+                Task::CommitGraph => Some(Schedule::Hourly),
+                Task::LooseObjects | Task::IncrementalRepack => Some(Schedule::Daily),
+                Task::PackRefs => Some(Schedule::Weekly),
+                Task::ReflogExpire => None,
+            },
+        }
+    }
+
+    /// Return `true` if `task` is enabled if it isn't configured with `maintenance.<task>.enabled`.
+    pub fn is_enabled(&self, task: Task) -> bool {
+        match self {
+            Strategy::None => true,
+            Strategy::Incremental => self.schedule(task).is_some(),
+        }
+    }
+}
+
+/// Options for use with [`Repository::maintenance()`](crate::Repository::maintenance()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The tasks to run in the given order, whether they are enabled or not.
+    /// If empty, all tasks enabled with `maintenance.<task>.enabled` or by the configured [`Strategy`] run.
+    pub tasks: Vec<Task>,
+    /// If set, only tasks that are scheduled at least as frequently as this are run, similar to
+    /// `git maintenance run --schedule=<frequency>`.
+    pub schedule: Option<Schedule>,
+    /// If `true`, only tasks whose thresholds indicate that they are needed are run, and none if `maintenance.auto`
+    /// is `false`, similar to `git maintenance run --auto`.
+    ///
+    /// [`PackRefs`](Task::PackRefs) and [`ReflogExpire`](Task::ReflogExpire) are needed if the estimated amount of loose
+    /// objects exceeds `gc.auto` or the amount of packs exceeds `gc.autoPackLimit`, while all other tasks are needed if
+    /// the amount of loose objects, packs or commits missing in the commit-graph reaches `maintenance.<task>.auto`.
+    pub auto: bool,
+}
+
+/// What a single task did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskOutcome {
+    /// The outcome of [`Task::PackRefs`].
+    PackRefs {
+        /// The amount of loose references moved into `packed-refs`.
+        packed: usize,
+    },
+    /// The outcome of [`Task::ReflogExpire`].
+    ReflogExpire {
+        /// The amount of reflog entries removed across all reflogs.
+        removed: usize,
+    },
+    /// The outcome of [`Task::LooseObjects`].
+    LooseObjects {
+        /// The amount of loose objects removed as they were already packed.
+        removed: usize,
+        /// The amount of loose objects written into a new pack.
+        packed: usize,
+    },
+    /// The outcome of [`Task::IncrementalRepack`].
+    IncrementalRepack {
+        /// The amount of packs that were combined into one.
+        packs: usize,
+        /// The amount of objects in the new pack.
+        objects: usize,
+    },
+    /// The outcome of [`Task::CommitGraph`].
+    CommitGraph {
+        /// The amount of commits written into the commit-graph, or `0` if none was written as the repository is shallow.
+        commits: u32,
+    },
+}
+
+impl TaskOutcome {
+    /// Return the task that produced this outcome.
+    pub fn task(&self) -> Task {
+        match self {
+            TaskOutcome::PackRefs { .. } => Task::PackRefs,
+            TaskOutcome::ReflogExpire { .. } => Task::ReflogExpire,
+            TaskOutcome::LooseObjects { .. } => Task::LooseObjects,
+            TaskOutcome::IncrementalRepack { .. } => Task::IncrementalRepack,
+            TaskOutcome::CommitGraph { .. } => Task::CommitGraph,
+        }
+    }
+}
+
+/// The result of [`Repository::maintenance()`](crate::Repository::maintenance()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The outcome of each task that ran, in the order in which they ran.
+    pub tasks: Vec<TaskOutcome>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod prune {
+    use std::time::SystemTime;
+
+    use gix_hash::ObjectId;
+
+    /// Options for use with [`Repository::prune()`](crate::Repository::prune()).
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Options {
+        /// Only unreachable objects whose files were last modified at or before this time are removed, similar to
+        /// `git prune --expire <time>`.
+        /// If `None`, `gc.pruneExpire` is used, which defaults to two weeks ago and can be `never` to not prune anything.
+        pub expire: Option<SystemTime>,
+        /// If `true`, nothing is removed but the outcome reports what would have been removed, similar to `git prune --dry-run`.
+        pub dry_run: bool,
+    }
+
+    /// The result of [`Repository::prune()`](crate::Repository::prune()).
+    #[derive(Default, Debug, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The unreachable loose objects that were removed, sorted by id.
+        pub loose_objects: Vec<ObjectId>,
+        /// The amount of unreachable objects that were removed from packs.
+        pub packed_objects: usize,
+        /// The amount of packs that were removed as they contained only unreachable objects, or that were replaced
+        /// by a pack with only their reachable objects.
+        pub packs: usize,
+        /// The amount of bytes freed on disk, after subtracting the size of packs written in place of the removed ones.
+        ///
+        /// In dry-run mode, packs that would be replaced don't contribute to this number.
+        pub freed_bytes: u64,
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod commit_graph {
+    /// Options for use with [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Options {
+        /// If `true`, only commits that aren't yet in the commit-graph are written into a new file which is added to
+        /// the chain of split commit-graph files, similar to `git commit-graph write --split`.
+        ///
+        /// Like `git` does by default, the topmost files of the chain are merged into the new file as long as the new file
+        /// would contain more than half as many commits as the file below it.
+        pub split: bool,
+    }
+
+    /// The result of [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+    #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The amount of commits written into the new commit-graph file, which is `0` if none was written.
+        pub commits: u32,
+        /// The amount of files in the chain of split commit-graph files after writing, or `0` if the commit-graph isn't split.
+        pub layers: usize,
+    }
+}
+
+/// The error returned by [`Repository::maintenance()`](crate::Repository::maintenance()),
+/// [`Repository::prune()`](crate::Repository::prune()) and [`Repository::write_commit_graph()`](crate::Repository::write_commit_graph()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not acquire the maintenance lock, is maintenance already running?")]
+    Lock(#[from] gix_lock::acquire::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    ConfigUnsignedInteger(#[from] crate::config::unsigned_integer::Error),
+    #[error(transparent)]
+    ConfigValue(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    ConfigInteger(#[from] crate::config::key::GenericError),
+    #[error(transparent)]
+    ZlibBackend(#[from] crate::config::zlib_backend::Error),
+    #[error(transparent)]
+    LockTimeout(#[from] crate::config::lock_timeout::Error),
+    #[error(transparent)]
+    Committer(#[from] crate::config::time::Error),
+    #[error(transparent)]
+    References(#[from] crate::reference::iter::Error),
+    #[error(transparent)]
+    ReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error(transparent)]
+    IterateReferences(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    IterateLooseReferences(#[from] gix_ref::file::iter::loose_then_packed::Error),
+    #[error(transparent)]
+    PackRefs(#[from] gix_ref::file::transaction::prepare::Error),
+    #[error(transparent)]
+    CommitPackedRefs(#[from] gix_ref::file::transaction::commit::Error),
+    #[error(transparent)]
+    DecodeReflog(#[from] gix_ref::file::log::iter::decode::Error),
+    #[error(transparent)]
+    CommitLock(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    TryFindObject(#[from] crate::object::find::Error),
+    #[error(transparent)]
+    OpenWorktree(#[from] crate::open::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Shallow(#[from] crate::shallow::open::Error),
+    #[error(transparent)]
+    IterateLooseObjects(#[from] gix_odb::loose::iter::Error),
+    #[error(transparent)]
+    OpenPackIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error(transparent)]
+    WritePackBundle(#[from] gix_pack::bundle::write::Error),
+    #[error(transparent)]
+    WriteCommitGraph(#[from] gix_commitgraph::file::write::Error),
+    #[error(transparent)]
+    OpenCommitGraphFile(#[from] gix_commitgraph::file::Error),
+    #[error(transparent)]
+    OpenCommitGraph(#[from] gix_commitgraph::init::Error),
+}
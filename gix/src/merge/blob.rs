@@ -0,0 +1,181 @@
+//! Merge the contents of blobs.
+use std::ops::Range;
+
+use gix_diff::blob::{
+    intern::{Interner, Token},
+    sources::byte_lines_with_terminator,
+    Algorithm,
+};
+
+use crate::bstr::BStr;
+
+/// The amount of characters used for conflict markers, like `<<<<<<<`.
+pub const DEFAULT_MARKER_SIZE: usize = 7;
+
+/// The labels to write after the conflict markers to identify each side of a conflict.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Labels<'a> {
+    /// The label for our side, written after `<<<<<<<`.
+    pub ours: Option<&'a BStr>,
+    /// The label for their side, written after `>>>>>>>`.
+    pub theirs: Option<&'a BStr>,
+}
+
+/// The way a merge was resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// All changes could be merged without conflict.
+    Complete,
+    /// At least one region was changed on both sides in different ways, and conflict markers were written into the output.
+    /// If the input was binary, the output is *our* version without conflict markers.
+    Conflict,
+}
+
+/// Perform a line-based three-way merge of `ancestor`, `ours` and `theirs`, writing the result into `out`
+/// (which is cleared first) and returning the way the merge was resolved.
+///
+/// Regions that were changed by both sides in the same way are taken as is, whereas regions that overlap or touch and
+/// differ are written with conflict markers, labelled with `labels`, similar to `git merge-file`.
+/// If one of the inputs [looks binary](crate::binary::is_binary_content()), no attempt to merge is made unless one side
+/// didn't change.
+pub fn text(ancestor: &[u8], ours: &[u8], theirs: &[u8], labels: Labels<'_>, out: &mut Vec<u8>) -> Resolution {
+    out.clear();
+    if ours == theirs || ancestor == theirs {
+        out.extend_from_slice(ours);
+        return Resolution::Complete;
+    }
+    if ancestor == ours {
+        out.extend_from_slice(theirs);
+        return Resolution::Complete;
+    }
+    if [ancestor, ours, theirs]
+        .iter()
+        .any(|data| crate::binary::is_binary_content(data))
+    {
+        out.extend_from_slice(ours);
+        return Resolution::Conflict;
+    }
+
+    let mut interner = Interner::new(ancestor.len() / 16 + ours.len() / 16 + theirs.len() / 16);
+    let ancestor_lines: Vec<_> = byte_lines_with_terminator(ancestor).collect();
+    let ours_lines: Vec<_> = byte_lines_with_terminator(ours).collect();
+    let theirs_lines: Vec<_> = byte_lines_with_terminator(theirs).collect();
+    let ancestor_tokens = intern(&mut interner, &ancestor_lines);
+    let ours_tokens = intern(&mut interner, &ours_lines);
+    let theirs_tokens = intern(&mut interner, &theirs_lines);
+    let num_tokens = interner.num_tokens();
+
+    let hunks = |side: &[Token]| {
+        let mut hunks = Vec::new();
+        gix_diff::blob::diff_with_tokens(
+            Algorithm::Histogram,
+            &ancestor_tokens,
+            side,
+            num_tokens,
+            |before: Range<u32>, after: Range<u32>| hunks.push(Hunk { before, after }),
+        );
+        hunks
+    };
+    let ours_hunks = hunks(&ours_tokens);
+    let theirs_hunks = hunks(&theirs_tokens);
+
+    let mut resolution = Resolution::Complete;
+    let (mut ours_idx, mut theirs_idx) = (0, 0);
+    // The difference in line numbers between each side and the ancestor, up to the current position.
+    let (mut ours_offset, mut theirs_offset) = (0i64, 0i64);
+    let mut ancestor_pos = 0u32;
+    loop {
+        let next_ours = ours_hunks.get(ours_idx);
+        let next_theirs = theirs_hunks.get(theirs_idx);
+        let start = match (next_ours, next_theirs) {
+            (Some(a), Some(b)) => a.before.start.min(b.before.start),
+            (Some(a), None) => a.before.start,
+            (None, Some(b)) => b.before.start,
+            (None, None) => break,
+        };
+        extend_lines(out, &ancestor_lines[ancestor_pos as usize..start as usize]);
+
+        // Collect all hunks on both sides that overlap or touch, transitively.
+        let mut end = start;
+        let (ours_first, theirs_first) = (ours_idx, theirs_idx);
+        loop {
+            let mut extended = false;
+            while let Some(hunk) = ours_hunks.get(ours_idx).filter(|h| h.before.start <= end) {
+                end = end.max(hunk.before.end);
+                ours_idx += 1;
+                extended = true;
+            }
+            while let Some(hunk) = theirs_hunks.get(theirs_idx).filter(|h| h.before.start <= end) {
+                end = end.max(hunk.before.end);
+                theirs_idx += 1;
+                extended = true;
+            }
+            if !extended {
+                break;
+            }
+        }
+
+        let ours_range = side_range(&ours_hunks[ours_first..ours_idx], start, end, &mut ours_offset);
+        let theirs_range = side_range(&theirs_hunks[theirs_first..theirs_idx], start, end, &mut theirs_offset);
+        let ours_region = &ours_lines[ours_range];
+        let theirs_region = &theirs_lines[theirs_range];
+        if ours_first == ours_idx {
+            extend_lines(out, theirs_region);
+        } else if theirs_first == theirs_idx || ours_region == theirs_region {
+            extend_lines(out, ours_region);
+        } else {
+            resolution = Resolution::Conflict;
+            write_conflict(out, ours_region, theirs_region, labels);
+        }
+        ancestor_pos = end;
+    }
+    extend_lines(out, &ancestor_lines[ancestor_pos as usize..]);
+    resolution
+}
+
+fn intern<'a>(interner: &mut Interner<&'a [u8]>, lines: &[&'a [u8]]) -> Vec<Token> {
+    lines.iter().map(|line| interner.intern(*line)).collect()
+}
+
+struct Hunk {
+    before: Range<u32>,
+    after: Range<u32>,
+}
+
+/// Return the range of lines in a side that corresponds to the ancestor lines `start..end`, given all `hunks` of that side
+/// in this region and the `offset` of the side compared to the ancestor at `start`, which is updated to the offset at `end`.
+fn side_range(hunks: &[Hunk], start: u32, end: u32, offset: &mut i64) -> Range<usize> {
+    let side_start = (start as i64 + *offset) as usize;
+    for hunk in hunks {
+        *offset += hunk.after.len() as i64 - hunk.before.len() as i64;
+    }
+    side_start..(end as i64 + *offset) as usize
+}
+
+fn extend_lines(out: &mut Vec<u8>, lines: &[&[u8]]) {
+    for line in lines {
+        out.extend_from_slice(line);
+    }
+}
+
+fn write_conflict(out: &mut Vec<u8>, ours: &[&[u8]], theirs: &[&[u8]], labels: Labels<'_>) {
+    fn marker(out: &mut Vec<u8>, byte: u8, label: Option<&BStr>) {
+        out.extend(std::iter::repeat(byte).take(DEFAULT_MARKER_SIZE));
+        if let Some(label) = label {
+            out.push(b' ');
+            out.extend_from_slice(label);
+        }
+        out.push(b'\n');
+    }
+    fn lines(out: &mut Vec<u8>, lines: &[&[u8]]) {
+        extend_lines(out, lines);
+        if lines.last().map_or(false, |line| !line.ends_with(b"\n")) {
+            out.push(b'\n');
+        }
+    }
+    marker(out, b'<', labels.ours);
+    lines(out, ours);
+    marker(out, b'=', None);
+    lines(out, theirs);
+    marker(out, b'>', labels.theirs);
+}
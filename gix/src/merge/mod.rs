@@ -0,0 +1,119 @@
+//! Types for use with [`Repository::merge_trees()`](crate::Repository::merge_trees()), along with the merge algorithms themselves.
+use gix_hash::ObjectId;
+
+use crate::bstr::{BString, ByteSlice};
+
+pub mod blob;
+
+/// A version of a path as seen in one of the trees participating in a merge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    /// The id of the blob, or the commit in case of submodules.
+    pub id: ObjectId,
+    /// The mode of the entry.
+    pub mode: gix_index::entry::Mode,
+}
+
+/// Describes why a path couldn't be merged automatically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictKind {
+    /// Both sides changed the content of a file in overlapping regions, or added it with different content.
+    ///
+    /// The merged content with conflict markers is available in the merged index.
+    Content,
+    /// One side deleted the path while the other side modified it.
+    ///
+    /// The modified version is available in the merged index.
+    ModifiedDeleted,
+    /// Both sides changed the path in a way that can't be merged, like changing its type or modifying a symlink or submodule
+    /// differently, or changing the executable bit differently.
+    ///
+    /// *Our* version, or theirs if ours doesn't exist, is available in the merged index.
+    Unmergeable,
+    /// The path is a file on one side, but a directory on the other.
+    ///
+    /// Only the directory is available in the merged index.
+    DirectoryFile,
+}
+
+/// A path that couldn't be merged automatically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict {
+    /// The repository-relative path of the conflicting entry.
+    pub rela_path: BString,
+    /// The reason for the conflict.
+    pub kind: ConflictKind,
+    /// The version in the common ancestor, if present.
+    pub ancestor: Option<Version>,
+    /// Our version, if present.
+    pub ours: Option<Version>,
+    /// Their version, if present.
+    pub theirs: Option<Version>,
+}
+
+/// The outcome of [`Repository::merge_trees()`](crate::Repository::merge_trees()).
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The merged index without any unmerged stages, suitable to be checked out into the worktree.
+    ///
+    /// Paths that couldn't be merged are represented by a [best-effort version](ConflictKind), like the file with conflict
+    /// markers in case of [content conflicts](ConflictKind::Content).
+    pub index: gix_index::State,
+    /// All paths that couldn't be merged automatically, sorted by path.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl Outcome {
+    /// Return `true` if at least one path couldn't be merged automatically.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    /// Turn this instance into an index in which each [conflicting path](Self::conflicts) is represented by its unmerged stages,
+    /// as `git` would write it to record an unfinished merge.
+    pub fn into_index_with_conflicts(self) -> gix_index::State {
+        let Outcome { mut index, conflicts } = self;
+        if conflicts.is_empty() {
+            return index;
+        }
+        index.remove_entries(|_, path, _| {
+            conflicts
+                .binary_search_by(|conflict| conflict.rela_path.as_bstr().cmp(path))
+                .is_ok()
+        });
+        for conflict in &conflicts {
+            for (stage, version) in [
+                (gix_index::entry::Stage::Base, conflict.ancestor),
+                (gix_index::entry::Stage::Ours, conflict.ours),
+                (gix_index::entry::Stage::Theirs, conflict.theirs),
+            ] {
+                let Some(version) = version else { continue };
+                index.dangerously_push_entry(
+                    Default::default(),
+                    version.id,
+                    gix_index::entry::Flags::from_bits_retain((stage as u32) << 12),
+                    version.mode,
+                    conflict.rela_path.as_ref(),
+                );
+            }
+        }
+        index.sort_entries();
+        index
+    }
+}
+
+/// The error returned by [`Repository::merge_trees()`](crate::Repository::merge_trees()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    WriteBlob(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    BinaryDetector(#[from] crate::binary::detector::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
@@ -0,0 +1,316 @@
+//! Read and edit notes, as stored in the history of references like `refs/notes/commits`.
+#![allow(clippy::empty_docs)]
+
+use gix_hash::ObjectId;
+pub use gix_note::*;
+use gix_ref::{FullName, FullNameRef};
+
+use crate::{Id, Repository};
+
+/// The reference `git` uses for notes by default, unless `core.notesRef` is set.
+pub const DEFAULT_REF: &str = "refs/notes/commits";
+
+/// A platform to read and edit the notes in the history of a notes reference, obtained by [`Repository::notes()`].
+#[derive(Clone)]
+pub struct Platform<'repo> {
+    pub(crate) repo: &'repo Repository,
+    pub(crate) reference: FullName,
+}
+
+///
+pub mod find {
+    /// The error returned by [`Platform::all()`](super::Platform::all()) and [`Platform::find()`](super::Platform::find()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+        #[error(transparent)]
+        PeelReference(#[from] crate::reference::peel::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        ReadNotes(#[from] gix_note::read::Error),
+    }
+}
+
+///
+pub mod edit {
+    /// The error returned by [`Platform::attach()`](super::Platform::attach()), [`Platform::remove()`](super::Platform::remove())
+    /// and [`Platform::copy()`](super::Platform::copy()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Find(#[from] super::find::Error),
+        #[error("Object {target} already has a note, and overwriting it wasn't allowed")]
+        NoteExists { target: gix_hash::ObjectId },
+        #[error("Object {target} has no note")]
+        NoteMissing { target: gix_hash::ObjectId },
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        Commit(#[from] crate::commit::Error),
+    }
+}
+
+///
+pub mod merge {
+    pub use gix_note::merge::{Conflict, Strategy};
+
+    use crate::Id;
+
+    /// The result of [`Platform::merge()`](super::Platform::merge()).
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Outcome<'repo> {
+        /// Their notes are already contained in ours, so nothing changed.
+        UpToDate,
+        /// Our notes reference now points to their notes commit, which contained all of our notes, or it was created.
+        FastForward {
+            /// The notes commit our notes reference now points to.
+            commit: Id<'repo>,
+        },
+        /// A merge commit with our and their notes commit as parents was created and our notes reference points to it.
+        Merged {
+            /// The newly created merge commit.
+            commit: Id<'repo>,
+        },
+        /// Notes were changed differently on both sides, and nothing was written.
+        ///
+        /// This only happens with [`Strategy::Manual`].
+        Conflicted {
+            /// The notes that couldn't be merged.
+            conflicts: Vec<Conflict>,
+        },
+    }
+
+    /// The error returned by [`Platform::merge()`](super::Platform::merge()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Find(#[from] super::find::Error),
+        #[error(transparent)]
+        Edit(#[from] super::edit::Error),
+        #[error(transparent)]
+        Walk(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        WalkIter(#[from] gix_traverse::commit::simple::Error),
+        #[error(transparent)]
+        Merge(#[from] gix_note::merge::Error),
+        #[error(transparent)]
+        ReferenceEdit(#[from] crate::reference::edit::Error),
+    }
+}
+
+/// Access
+impl<'repo> Platform<'repo> {
+    /// Return the name of the reference whose history contains the notes.
+    pub fn reference(&self) -> &FullNameRef {
+        self.reference.as_ref()
+    }
+
+    /// Return all notes, which is empty if the notes reference doesn't exist yet.
+    pub fn all(&self) -> Result<Notes, find::Error> {
+        Ok(self.tip()?.map(|(_, notes)| notes).unwrap_or_default())
+    }
+
+    /// Return the blob with the note attached to `target`, or `None` if there is no such note.
+    pub fn find(&self, target: impl Into<ObjectId>) -> Result<Option<crate::Blob<'repo>>, find::Error> {
+        let target = target.into();
+        let Some(note) = self.all()?.notes.get(&target).copied() else {
+            return Ok(None);
+        };
+        Ok(Some(self.repo.find_object(note)?.try_into_blob()?))
+    }
+
+    /// Return the commit the notes reference points to along with its notes, or `None` if it doesn't exist yet.
+    fn tip(&self) -> Result<Option<(ObjectId, Notes)>, find::Error> {
+        let Some(mut reference) = self.repo.try_find_reference(self.reference.as_ref())? else {
+            return Ok(None);
+        };
+        let commit = self
+            .repo
+            .find_object(reference.peel_to_id_in_place()?)?
+            .try_into_commit()?;
+        let notes = Notes::from_tree(&commit.tree_id()?, &self.repo.objects)?;
+        Ok(Some((commit.id, notes)))
+    }
+}
+
+/// Edit
+impl<'repo> Platform<'repo> {
+    /// Attach `note` to the object `target`, which replaces an existing note only if `overwrite` is `true`, similar to `git notes add`.
+    ///
+    /// Return the id of the newly created notes commit.
+    pub fn attach(
+        &self,
+        target: impl Into<ObjectId>,
+        note: impl AsRef<[u8]>,
+        overwrite: bool,
+    ) -> Result<Id<'repo>, edit::Error> {
+        let target = target.into();
+        let (parent, mut notes) = self.tip_or_empty()?;
+        if !overwrite && notes.notes.contains_key(&target) {
+            return Err(edit::Error::NoteExists { target });
+        }
+        let note = self.repo.write_blob(note)?.detach();
+        notes.notes.insert(target, note);
+        self.commit(parent, &notes, "Notes added by 'git notes add'")
+    }
+
+    /// Remove the note attached to the object `target`, similar to `git notes remove`.
+    ///
+    /// Return the id of the newly created notes commit.
+    pub fn remove(&self, target: impl Into<ObjectId>) -> Result<Id<'repo>, edit::Error> {
+        let target = target.into();
+        let (parent, mut notes) = self.tip_or_empty()?;
+        if notes.notes.remove(&target).is_none() {
+            return Err(edit::Error::NoteMissing { target });
+        }
+        self.commit(parent, &notes, "Notes removed by 'git notes remove'")
+    }
+
+    /// Attach the note of the object `from` to the object `to` as well, which replaces an existing note only if `overwrite` is `true`,
+    /// similar to `git notes copy`.
+    ///
+    /// Return the id of the newly created notes commit.
+    pub fn copy(
+        &self,
+        from: impl Into<ObjectId>,
+        to: impl Into<ObjectId>,
+        overwrite: bool,
+    ) -> Result<Id<'repo>, edit::Error> {
+        let (from, to) = (from.into(), to.into());
+        let (parent, mut notes) = self.tip_or_empty()?;
+        let note = *notes
+            .notes
+            .get(&from)
+            .ok_or(edit::Error::NoteMissing { target: from })?;
+        if !overwrite && notes.notes.contains_key(&to) {
+            return Err(edit::Error::NoteExists { target: to });
+        }
+        notes.notes.insert(to, note);
+        self.commit(parent, &notes, "Notes added by 'git notes copy'")
+    }
+
+    /// Merge the notes of `other` into our notes, using `strategy` to resolve notes that were changed differently on both sides,
+    /// similar to `git notes merge`.
+    ///
+    /// If our notes reference doesn't exist yet or is contained in the history of `other`, it will be fast-forwarded.
+    /// With [`Strategy::Manual`](merge::Strategy::Manual), conflicts are returned without writing anything so the caller can
+    /// resolve them, for instance by [attaching](Self::attach()) the desired notes and merging again.
+    pub fn merge(
+        &self,
+        other: &Platform<'_>,
+        strategy: merge::Strategy,
+    ) -> Result<merge::Outcome<'repo>, merge::Error> {
+        let Some((theirs, their_notes)) = other.tip()? else {
+            return Ok(merge::Outcome::UpToDate);
+        };
+        let message = format!(
+            "notes: Merged notes from {} into {}",
+            other.reference.as_bstr(),
+            self.reference.as_bstr()
+        );
+        let Some((ours, our_notes)) = self.tip()? else {
+            self.repo.reference(
+                self.reference.clone(),
+                theirs,
+                gix_ref::transaction::PreviousValue::MustNotExist,
+                message,
+            )?;
+            return Ok(merge::Outcome::FastForward {
+                commit: Id::from_id(theirs, self.repo),
+            });
+        };
+
+        let our_ancestors = self
+            .repo
+            .rev_walk([ours])
+            .all()?
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<gix_hashtable::HashSet<_>, _>>()?;
+        if our_ancestors.contains(&theirs) {
+            return Ok(merge::Outcome::UpToDate);
+        }
+        let mut base = None;
+        for info in self
+            .repo
+            .rev_walk([theirs])
+            .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+            .all()?
+        {
+            let id = info?.id;
+            if our_ancestors.contains(&id) {
+                base = Some(id);
+                break;
+            }
+        }
+        if base == Some(ours) {
+            self.repo.reference(
+                self.reference.clone(),
+                theirs,
+                gix_ref::transaction::PreviousValue::MustExistAndMatch(ours.into()),
+                message,
+            )?;
+            return Ok(merge::Outcome::FastForward {
+                commit: Id::from_id(theirs, self.repo),
+            });
+        }
+
+        let base_notes = match base {
+            Some(base) => Notes::from_tree(
+                &self
+                    .repo
+                    .find_object(base)
+                    .map_err(find::Error::from)?
+                    .try_into_commit()
+                    .map_err(find::Error::from)?
+                    .tree_id()
+                    .map_err(find::Error::from)?,
+                &self.repo.objects,
+            )
+            .map_err(find::Error::from)?,
+            None => Notes::default(),
+        };
+        let outcome = gix_note::merge::merge(
+            &base_notes,
+            &our_notes,
+            &their_notes,
+            strategy,
+            &self.repo.objects,
+            |data| self.repo.write_blob(data).map(Id::detach),
+        )?;
+        if !outcome.conflicts.is_empty() {
+            return Ok(merge::Outcome::Conflicted {
+                conflicts: outcome.conflicts,
+            });
+        }
+        let tree = outcome
+            .notes
+            .write_tree(|tree| self.repo.write_object(tree).map(Id::detach))
+            .map_err(edit::Error::from)?;
+        let commit = self
+            .repo
+            .commit(self.reference.as_bstr(), message, tree, [ours, theirs])
+            .map_err(edit::Error::from)?;
+        Ok(merge::Outcome::Merged { commit })
+    }
+
+    fn tip_or_empty(&self) -> Result<(Option<ObjectId>, Notes), find::Error> {
+        Ok(match self.tip()? {
+            Some((commit, notes)) => (Some(commit), notes),
+            None => (None, Notes::default()),
+        })
+    }
+
+    fn commit(&self, parent: Option<ObjectId>, notes: &Notes, message: &str) -> Result<Id<'repo>, edit::Error> {
+        let tree = notes.write_tree(|tree| self.repo.write_object(tree).map(Id::detach))?;
+        Ok(self.repo.commit(self.reference.as_bstr(), message, tree, parent)?)
+    }
+}
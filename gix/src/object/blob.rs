@@ -244,6 +244,17 @@ pub mod diff {
     }
 }
 
+/// Access
+impl Blob<'_> {
+    /// Return the kind and size of this blob, which is known without decoding it or accessing the object database.
+    pub fn header(&self) -> gix_object::Header {
+        gix_object::Header {
+            kind: gix_object::Kind::Blob,
+            size: self.data.len() as u64,
+        }
+    }
+}
+
 /// Remove Lifetime
 impl Blob<'_> {
     /// Create an owned instance of this object, copying our data in the process.
@@ -107,6 +107,28 @@ impl<'repo> Commit<'repo> {
             .map(|s| s.trim())
     }
 
+    /// Return the commits author like [`author()`](Self::author()), but with name and email replaced according to `mailmap`,
+    /// similar to the `%aN` and `%aE` placeholders of `git log`.
+    ///
+    /// Use [`Repository::open_mailmap()`](crate::Repository::open_mailmap()) to obtain the mailmap of the repository.
+    #[cfg(feature = "mailmap")]
+    pub fn author_remapped(
+        &self,
+        mailmap: &gix_mailmap::Snapshot,
+    ) -> Result<gix_mailmap::snapshot::Signature<'_>, gix_object::decode::Error> {
+        Ok(mailmap.resolve_cow(self.author()?))
+    }
+
+    /// Return the commits committer like [`committer()`](Self::committer()), but with name and email replaced according
+    /// to `mailmap`, similar to the `%cN` and `%cE` placeholders of `git log`.
+    #[cfg(feature = "mailmap")]
+    pub fn committer_remapped(
+        &self,
+        mailmap: &gix_mailmap::Snapshot,
+    ) -> Result<gix_mailmap::snapshot::Signature<'_>, gix_object::decode::Error> {
+        Ok(mailmap.resolve_cow(self.committer()?))
+    }
+
     /// Decode this commits parent ids on the fly without allocating.
     // TODO: tests
     pub fn parent_ids(&self) -> impl Iterator<Item = crate::Id<'repo>> + '_ {
@@ -138,6 +160,14 @@ impl<'repo> Commit<'repo> {
         self.id.attach(self.repo)
     }
 
+    /// Return the kind and size of this commit, which is known without decoding it or accessing the object database.
+    pub fn header(&self) -> gix_object::Header {
+        gix_object::Header {
+            kind: gix_object::Kind::Commit,
+            size: self.data.len() as u64,
+        }
+    }
+
     /// Obtain a platform for traversing ancestors of this commit.
     pub fn ancestors(&self) -> crate::revision::walk::Platform<'repo> {
         self.id().ancestors()
@@ -154,6 +184,8 @@ impl<'repo> Commit<'repo> {
             first_parent: false,
             id_as_fallback: false,
             max_candidates: 10,
+            include_patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
         }
     }
 
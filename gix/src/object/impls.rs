@@ -61,6 +61,39 @@ impl<'repo> From<Commit<'repo>> for Object<'repo> {
     }
 }
 
+impl<'repo> From<Tag<'repo>> for Object<'repo> {
+    fn from(mut v: Tag<'repo>) -> Self {
+        Object {
+            id: v.id,
+            kind: gix_object::Kind::Tag,
+            data: steal_from_freelist(&mut v.data),
+            repo: v.repo,
+        }
+    }
+}
+
+impl<'repo> From<Tree<'repo>> for Object<'repo> {
+    fn from(mut v: Tree<'repo>) -> Self {
+        Object {
+            id: v.id,
+            kind: gix_object::Kind::Tree,
+            data: steal_from_freelist(&mut v.data),
+            repo: v.repo,
+        }
+    }
+}
+
+impl<'repo> From<Blob<'repo>> for Object<'repo> {
+    fn from(mut v: Blob<'repo>) -> Self {
+        Object {
+            id: v.id,
+            kind: gix_object::Kind::Blob,
+            data: steal_from_freelist(&mut v.data),
+            repo: v.repo,
+        }
+    }
+}
+
 impl<'repo> AsRef<[u8]> for Object<'repo> {
     fn as_ref(&self) -> &[u8] {
         &self.data
@@ -246,4 +246,12 @@ impl<'repo> Object<'repo> {
     pub fn id(&self) -> Id<'repo> {
         Id::from_id(self.id, self.repo)
     }
+
+    /// Return the kind and size of this object, which is known without decoding it or accessing the object database.
+    pub fn header(&self) -> gix_object::Header {
+        gix_object::Header {
+            kind: self.kind,
+            size: self.data.len() as u64,
+        }
+    }
 }
@@ -3,7 +3,7 @@
 use crate::{
     object,
     object::{peel, Kind},
-    Object, Tree,
+    Commit, Object, Tag, Tree,
 };
 
 ///
@@ -74,6 +74,11 @@ impl<'repo> Object<'repo> {
         Ok(self.peel_to_kind(gix_object::Kind::Tree)?.into_tree())
     }
 
+    /// Peel this object into a commit and return it, if this is possible.
+    pub fn peel_to_commit(self) -> Result<Commit<'repo>, peel::to_kind::Error> {
+        Ok(self.peel_to_kind(gix_object::Kind::Commit)?.into_commit())
+    }
+
     // TODO: tests
     /// Follow all tag object targets until a commit, tree or blob is reached.
     ///
@@ -93,3 +98,28 @@ impl<'repo> Object<'repo> {
         }
     }
 }
+
+/// Peeling
+impl<'repo> Tag<'repo> {
+    /// Follow this tag and all tags it points to until the given `kind` of object is encountered, peeling commits to trees
+    /// along the way, just like [`Object::peel_to_kind()`].
+    pub fn peel_to_kind(self, kind: Kind) -> Result<Object<'repo>, peel::to_kind::Error> {
+        Object::from(self).peel_to_kind(kind)
+    }
+
+    /// Follow this tag and all tags it points to until a commit, tree or blob is reached, just like [`Object::peel_tags_to_end()`].
+    pub fn peel_tags_to_end(self) -> Result<Object<'repo>, object::find::existing::Error> {
+        Object::from(self).peel_tags_to_end()
+    }
+
+    /// Follow this tag and all tags it points to until a commit is reached and return it, if this is possible.
+    pub fn peel_to_commit(self) -> Result<Commit<'repo>, peel::to_kind::Error> {
+        Object::from(self).peel_to_commit()
+    }
+
+    /// Follow this tag and all tags it points to until a tree is reached, possibly by peeling a commit, and return it
+    /// if this is possible.
+    pub fn peel_to_tree(self) -> Result<Tree<'repo>, peel::to_kind::Error> {
+        Object::from(self).peel_to_tree()
+    }
+}
@@ -23,6 +23,14 @@ impl<'repo> Tag<'repo> {
     pub fn tagger(&self) -> Result<Option<gix_actor::SignatureRef<'_>>, gix_object::decode::Error> {
         gix_object::TagRefIter::from_bytes(&self.data).tagger()
     }
+
+    /// Return the kind and size of this tag, which is known without decoding it or accessing the object database.
+    pub fn header(&self) -> gix_object::Header {
+        gix_object::Header {
+            kind: gix_object::Kind::Tag,
+            size: self.data.len() as u64,
+        }
+    }
 }
 
 /// Remove Lifetime
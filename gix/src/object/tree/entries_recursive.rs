@@ -0,0 +1,153 @@
+use gix_object::{bstr::BString, FindExt};
+use gix_traverse::tree::recorder::Entry;
+
+use crate::{bstr::ByteVec, Repository, Tree};
+
+/// Traversal
+impl<'repo> Tree<'repo> {
+    /// Return an iterator over all entries of this tree and its subtrees, depth-first and in tree-entry sort order,
+    /// similar to `git ls-tree -r -t`.
+    ///
+    /// Trees are returned right before their entries, and entries are decoded one tree at a time, so that even very large
+    /// trees can be traversed without holding all of their entries in memory.
+    /// Use [`Iter::with_max_depth()`] and `Iter::with_pathspec()` to limit the traversal.
+    pub fn entries_recursive(&self) -> Iter<'repo> {
+        Iter {
+            repo: self.repo,
+            root: Some(self.data.clone()),
+            stack: Vec::new(),
+            max_depth: None,
+            #[cfg(feature = "attributes")]
+            pathspec: None,
+            buf: Vec::new(),
+        }
+    }
+}
+
+/// The error returned by [`Iter`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    Decode(#[from] gix_object::decode::Error),
+}
+
+/// An iterator over all entries of a tree and its subtrees, as created by [`Tree::entries_recursive()`].
+pub struct Iter<'repo> {
+    repo: &'repo Repository,
+    /// The data of the root tree, until its entries are put onto the stack.
+    root: Option<Vec<u8>>,
+    /// Entries along with their path and depth that are yet to be returned, with the next one last.
+    stack: Vec<(Entry, usize)>,
+    max_depth: Option<usize>,
+    #[cfg(feature = "attributes")]
+    pathspec: Option<crate::Pathspec<'repo>>,
+    buf: Vec<u8>,
+}
+
+/// Builder
+impl<'repo> Iter<'repo> {
+    /// Only return entries up to `depth` trees deep, with `0` only returning the entries of the root tree, or
+    /// all entries if `None`.
+    /// Trees at the maximum depth are still returned, but not traversed.
+    pub fn with_max_depth(mut self, depth: Option<usize>) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Only return entries that match `pathspec`, and only traverse trees that may contain matching entries.
+    /// Note that trees leading up to matching entries are only returned if they match as well.
+    #[cfg(feature = "attributes")]
+    pub fn with_pathspec(mut self, pathspec: crate::Pathspec<'repo>) -> Self {
+        self.pathspec = Some(pathspec);
+        self
+    }
+}
+
+impl Iter<'_> {
+    /// Put the entries of `tree` at `depth` onto the stack, with their paths prefixed by `prefix`.
+    fn push_entries(&mut self, tree: gix_object::TreeRefIter<'_>, prefix: &BString, depth: usize) -> Result<(), Error> {
+        let first = self.stack.len();
+        for entry in tree {
+            let entry = entry?;
+            let mut filepath = prefix.clone();
+            if !filepath.is_empty() {
+                filepath.push_byte(b'/');
+            }
+            filepath.push_str(entry.filename);
+            self.stack.push((
+                Entry {
+                    mode: entry.mode,
+                    filepath,
+                    oid: entry.oid.to_owned(),
+                },
+                depth,
+            ));
+        }
+        self.stack[first..].reverse();
+        Ok(())
+    }
+
+    fn traverse_tree(&mut self, entry: &Entry, depth: usize) -> Result<(), Error> {
+        if matches!(self.max_depth, Some(max_depth) if depth >= max_depth) {
+            return Ok(());
+        }
+        #[cfg(feature = "attributes")]
+        if let Some(pathspec) = &self.pathspec {
+            if !pathspec
+                .search()
+                .can_match_relative_path(entry.filepath.as_ref(), Some(true))
+            {
+                return Ok(());
+            }
+        }
+        let mut buf = std::mem::take(&mut self.buf);
+        let res = self
+            .repo
+            .objects
+            .find_tree_iter(&entry.oid, &mut buf)
+            .map_err(Error::from)
+            .and_then(|tree| self.push_entries(tree, &entry.filepath, depth + 1));
+        self.buf = buf;
+        res
+    }
+
+    #[cfg(feature = "attributes")]
+    fn is_included(&mut self, entry: &Entry) -> bool {
+        match self.pathspec.as_mut() {
+            Some(pathspec) => pathspec.is_included(entry.filepath.as_slice(), Some(entry.mode.is_tree())),
+            None => true,
+        }
+    }
+
+    #[cfg(not(feature = "attributes"))]
+    fn is_included(&mut self, _entry: &Entry) -> bool {
+        true
+    }
+}
+
+impl Iterator for Iter<'_> {
+    type Item = Result<Entry, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(root) = self.root.take() {
+            if let Err(err) = self.push_entries(gix_object::TreeRefIter::from_bytes(&root), &BString::default(), 0) {
+                return Some(Err(err));
+            }
+        }
+        while let Some((entry, depth)) = self.stack.pop() {
+            if entry.mode.is_tree() {
+                if let Err(err) = self.traverse_tree(&entry, depth) {
+                    self.stack.clear();
+                    return Some(Err(err));
+                }
+            }
+            if self.is_included(&entry) {
+                return Some(Ok(entry));
+            }
+        }
+        None
+    }
+}
@@ -24,6 +24,14 @@ impl<'repo> Tree<'repo> {
         Id::from_id(self.id, self.repo)
     }
 
+    /// Return the kind and size of this tree, which is known without decoding it or accessing the object database.
+    pub fn header(&self) -> gix_object::Header {
+        gix_object::Header {
+            kind: gix_object::Kind::Tree,
+            size: self.data.len() as u64,
+        }
+    }
+
     /// Parse our tree data and return the parse tree for direct access to its entries.
     pub fn decode(&self) -> Result<gix_object::TreeRef<'_>, gix_object::decode::Error> {
         gix_object::TreeRef::from_bytes(&self.data)
@@ -177,6 +185,10 @@ pub mod diff;
 #[allow(clippy::empty_docs)]
 pub mod traverse;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod entries_recursive;
+
 ///
 #[allow(clippy::empty_docs)]
 mod iter;
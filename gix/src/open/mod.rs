@@ -18,7 +18,10 @@ pub struct Permissions {
 /// ### Replacement Objects for the object database
 ///
 /// The environment variables `GIT_REPLACE_REF_BASE` and `GIT_NO_REPLACE_OBJECTS` are mapped to `gitoxide.objects.replaceRefBase`
-/// and `gitoxide.objects.noReplace` respectively and then interpreted exactly as their environment variable counterparts.
+/// and `core.useReplaceRefs` respectively and then interpreted exactly as their environment variable counterparts, i.e.
+/// replacements are disabled if `GIT_NO_REPLACE_OBJECTS` is set at all.
+/// Replacements are only loaded when they are needed for the first time, and can be toggled per handle with
+/// [`Repository::set_object_replacements_enabled()`](crate::Repository::set_object_replacements_enabled()).
 ///
 /// Use [Permissions] to control which environment variables can be read, and config-overrides to control these values programmatically.
 #[derive(Clone)]
@@ -52,8 +55,17 @@ pub enum Error {
     },
     #[error(transparent)]
     Io(#[from] std::io::Error),
-    #[error("The git directory at '{}' is considered unsafe as it's not owned by the current user.", .path.display())]
-    UnsafeGitDir { path: PathBuf },
+    #[error("The repository at '{}' is considered unsafe as it's not owned by the current user, and it's not listed in `safe.directory`.", .path.display())]
+    UnsafeGitDir {
+        /// The work tree of the repository, or its git directory if it is bare, which could be added to `safe.directory`.
+        path: PathBuf,
+    },
+    #[error("The directory '{}' configured by {name} does not exist or isn't a directory", .path.display())]
+    MissingOverrideDirectory { name: String, path: PathBuf },
+    #[error(transparent)]
+    CompressionLevel(#[from] config::key::GenericError),
+    #[error(transparent)]
+    ZlibBackend(#[from] config::zlib_backend::Error),
     #[error(transparent)]
     EnvironmentAccessDenied(#[from] gix_sec::permission::Error<std::path::PathBuf>),
 }
@@ -61,6 +73,7 @@ pub enum Error {
 mod options;
 pub mod permissions;
 mod repository;
+pub(crate) use repository::EnvironmentOverrides;
 
 #[cfg(test)]
 mod tests {
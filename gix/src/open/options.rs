@@ -1,7 +1,12 @@
 use std::path::PathBuf;
 
 use super::{Error, Options};
-use crate::{bstr::BString, config, open::Permissions, ThreadSafeRepository};
+use crate::{
+    bstr::BString,
+    config::{self, tree::Key},
+    open::Permissions,
+    ThreadSafeRepository,
+};
 
 impl Default for Options {
     fn default() -> Self {
@@ -56,6 +61,35 @@ impl Options {
         self
     }
 
+    /// Set the amount of memory in `bytes` used at most for caching fully decoded objects in each [`Repository`](crate::Repository)
+    /// handle, with `0` disabling the cache.
+    ///
+    /// This is equivalent to setting `gitoxide.objects.cacheLimit` or `GIX_OBJECT_CACHE_MEMORY`, and is applied in addition to
+    /// the [configuration overrides](Self::config_overrides()).
+    /// Use [`Repository::object_cache_size()`](crate::Repository::object_cache_size()) to change it for a single handle.
+    pub fn object_cache_size(mut self, bytes: usize) -> Self {
+        self.api_config_overrides.push(
+            format!(
+                "{}={bytes}",
+                config::tree::gitoxide::Objects::CACHE_LIMIT.logical_name()
+            )
+            .into(),
+        );
+        self
+    }
+
+    /// Set the amount of memory in `bytes` used at most for caching delta-base objects when decoding objects from packs in each
+    /// [`Repository`](crate::Repository) handle, with `0` disabling the cache.
+    ///
+    /// This is equivalent to setting `core.deltaBaseCacheLimit` or `GIX_PACK_CACHE_MEMORY`, and is applied in addition to
+    /// the [configuration overrides](Self::config_overrides()).
+    /// Use [`Repository::pack_cache_size()`](crate::Repository::pack_cache_size()) to change it for a single handle.
+    pub fn pack_cache_size(mut self, bytes: usize) -> Self {
+        self.api_config_overrides
+            .push(format!("{}={bytes}", config::tree::Core::DELTA_BASE_CACHE_LIMIT.logical_name()).into());
+        self
+    }
+
     /// Set the amount of slots to use for the object database. It's a value that doesn't need changes on the client, typically,
     /// but should be controlled on the server.
     pub fn object_store_slots(mut self, slots: gix_odb::store::init::Slots) -> Self {
@@ -100,7 +134,9 @@ impl Options {
     }
 
     /// If true, default false, and if the repository's trust level is not `Full`
-    /// (see [`with()`][Self::with()] for more), then the open operation will fail.
+    /// (see [`with()`][Self::with()] for more) or its work tree isn't owned by the current user,
+    /// then the open operation will fail with [`UnsafeGitDir`](crate::open::Error::UnsafeGitDir) unless
+    /// the repository is listed in `safe.directory` of the global or system configuration.
     ///
     /// Use this to mimic `git`s way of handling untrusted repositories. Note that `gitoxide` solves
     /// this by not using configuration from untrusted sources and by generally being secured against
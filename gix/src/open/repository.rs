@@ -25,6 +25,8 @@ pub(crate) struct EnvironmentOverrides {
     ///
     /// If set, the passed in `git_dir` parameter will be ignored in favor of this one.
     git_dir: Option<PathBuf>,
+    /// An override for the common directory, typically from the environment, which takes precedence over the `commondir` file.
+    common_dir: Option<PathBuf>,
 }
 
 impl EnvironmentOverrides {
@@ -37,7 +39,23 @@ impl EnvironmentOverrides {
         if let Some(path) = std::env::var_os("GIT_DIR") {
             git_dir = PathBuf::from(path).into();
         }
-        Ok(EnvironmentOverrides { worktree_dir, git_dir })
+        let mut common_dir = None;
+        if let Some(path) = std::env::var_os("GIT_COMMON_DIR") {
+            common_dir = PathBuf::from(path).into();
+        }
+        Ok(EnvironmentOverrides {
+            worktree_dir,
+            git_dir,
+            common_dir,
+        })
+    }
+
+    /// Read the overrides of the environment that affect the layout of a repository that was found by other means,
+    /// i.e. all but `GIT_DIR`.
+    pub(crate) fn from_env_without_git_dir() -> Result<Self, gix_sec::permission::Error<std::path::PathBuf>> {
+        let mut overrides = Self::from_env()?;
+        overrides.git_dir = None;
+        Ok(overrides)
     }
 }
 
@@ -97,7 +115,7 @@ impl ThreadSafeRepository {
             options.git_dir_trust = gix_sec::Trust::from_path_ownership(&git_dir)?.into();
         }
         options.current_dir = Some(cwd);
-        ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, options)
+        ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, Default::default(), options)
     }
 
     /// Try to open a git repository in `fallback_directory` (can be worktree or `.git` directory) only if there is no override
@@ -107,7 +125,9 @@ impl ThreadSafeRepository {
     /// The `.git` directory whether given or computed is used for trust checks.
     ///
     /// Note that this will read various `GIT_*` environment variables to check for overrides, and is probably most useful when implementing
-    /// custom hooks.
+    /// custom hooks. Just like in `git`, `GIT_WORK_TREE` takes precedence over `core.worktree`, and `GIT_COMMON_DIR` takes precedence
+    /// over the `commondir` file of the git directory. `GIT_INDEX_FILE` and `GIT_OBJECT_DIRECTORY` are handled
+    /// by means of `gitoxide.core.indexFile` and `gitoxide.objects.directory` respectively.
     // TODO: tests, with hooks, GIT_QUARANTINE for ref-log and transaction control (needs gix-sec support to remove write access in gix-ref)
     // TODO: The following vars should end up as overrides of the respective configuration values (see git-config).
     //       GIT_PROXY_SSL_CERT, GIT_PROXY_SSL_KEY, GIT_PROXY_SSL_CERT_PASSWORD_PROTECTED.
@@ -119,8 +139,8 @@ impl ThreadSafeRepository {
         trust_map: gix_sec::trust::Mapping<Options>,
     ) -> Result<Self, Error> {
         let _span = gix_trace::coarse!("ThreadSafeRepository::open_with_environment_overrides()");
-        let overrides = EnvironmentOverrides::from_env()?;
-        let (path, path_kind): (PathBuf, _) = match overrides.git_dir {
+        let mut overrides = EnvironmentOverrides::from_env()?;
+        let (path, path_kind): (PathBuf, _) = match overrides.git_dir.take() {
             Some(git_dir) => gix_discover::is_git(&git_dir)
                 .map_err(|err| Error::NotARepository {
                     source: err,
@@ -143,17 +163,18 @@ impl ThreadSafeRepository {
         let (git_dir, worktree_dir) = gix_discover::repository::Path::from_dot_git_dir(path, path_kind, &cwd)
             .expect("we have sanitized path with is_git()")
             .into_repository_and_work_tree_directories();
-        let worktree_dir = worktree_dir.or(overrides.worktree_dir);
 
         let git_dir_trust = gix_sec::Trust::from_path_ownership(&git_dir)?;
         let mut options = trust_map.into_value_by_level(git_dir_trust);
         options.current_dir = Some(cwd);
-        ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, options)
+        ThreadSafeRepository::open_from_paths(git_dir, worktree_dir, overrides, options)
     }
 
+    /// Note that the `git_dir` of `overrides` is expected to have been applied already, and is ignored here.
     pub(crate) fn open_from_paths(
         mut git_dir: PathBuf,
         mut worktree_dir: Option<PathBuf>,
+        overrides: EnvironmentOverrides,
         mut options: Options,
     ) -> Result<Self, Error> {
         let _span = gix_trace::detail!("open_from_paths()");
@@ -177,9 +198,18 @@ impl ThreadSafeRepository {
         } = options;
         let git_dir_trust = git_dir_trust.expect("trust must be determined by now");
 
-        let mut common_dir = gix_discover::path::from_plain_file(git_dir.join("commondir").as_ref())
-            .transpose()?
-            .map(|cd| git_dir.join(cd));
+        let mut common_dir = match overrides.common_dir {
+            Some(common_dir) => {
+                if !common_dir.is_dir() {
+                    return Err(Error::MissingOverrideDirectory {
+                        name: "GIT_COMMON_DIR".into(),
+                        path: common_dir,
+                    });
+                }
+                Some(common_dir)
+            }
+            None => gix_discover::path::common_dir(&git_dir).transpose()?,
+        };
         let repo_config = config::cache::StageOne::new(
             common_dir.as_deref().unwrap_or(&git_dir),
             git_dir.as_ref(),
@@ -213,22 +243,13 @@ impl ThreadSafeRepository {
             current_dir_ref.as_path()
         };
 
-        let mut refs = {
-            let reflog = repo_config.reflog.unwrap_or(gix_ref::store::WriteReflog::Disable);
-            let object_hash = repo_config.object_hash;
-            let ref_store_init_opts = gix_ref::store::init::Options {
-                write_reflog: reflog,
-                object_hash,
-                precompose_unicode: repo_config.precompose_unicode,
-                prohibit_windows_device_names: repo_config.protect_windows,
-            };
-            match &common_dir {
-                Some(common_dir) => {
-                    crate::RefStore::for_linked_worktree(git_dir.to_owned(), common_dir.into(), ref_store_init_opts)
-                }
-                None => crate::RefStore::at(git_dir.to_owned(), ref_store_init_opts),
-            }
+        let ref_store_init_opts = gix_ref::store::init::Options {
+            write_reflog: repo_config.reflog.unwrap_or(gix_ref::store::WriteReflog::Disable),
+            object_hash: repo_config.object_hash,
+            precompose_unicode: repo_config.precompose_unicode,
+            prohibit_windows_device_names: repo_config.protect_windows,
         };
+        let mut refs = ref_store_at(&git_dir, common_dir.as_deref(), ref_store_init_opts);
         let head = refs.find("HEAD").ok();
         let git_install_dir = crate::path::install_dir().ok();
         let home = gix_path::env::home_dir().and_then(|home| env.home.check_opt(home));
@@ -249,9 +270,13 @@ impl ThreadSafeRepository {
             cli_config_overrides,
         )?;
 
-        if bail_if_untrusted && git_dir_trust != gix_sec::Trust::Full {
+        if bail_if_untrusted
+            && (git_dir_trust != gix_sec::Trust::Full
+                || gix_sec::Trust::from_paths_ownership(worktree_dir.as_deref().filter(|wt| wt.exists()))?
+                    != gix_sec::Trust::Full)
+        {
             check_safe_directories(
-                &git_dir,
+                worktree_dir.as_deref().unwrap_or(&git_dir),
                 git_install_dir.as_deref(),
                 current_dir,
                 home.as_deref(),
@@ -259,8 +284,10 @@ impl ThreadSafeRepository {
             )?;
         }
 
-        // core.worktree might be used to overwrite the worktree directory
-        if !config.is_bare {
+        // `GIT_WORK_TREE` overrides everything, but core.worktree might be used to overwrite the worktree directory
+        if let Some(worktree_override) = overrides.worktree_dir {
+            worktree_dir = Some(worktree_override);
+        } else if !config.is_bare {
             if let Some(wt) = config
                 .resolved
                 .path_filter("core", None, Core::WORKTREE.name, &mut filter_config_section)
@@ -302,34 +329,44 @@ impl ThreadSafeRepository {
 
         refs.write_reflog = config::cache::util::reflog_or_default(config.reflog, worktree_dir.is_some());
         refs.namespace.clone_from(&config.refs_namespace);
-        let replacements = replacement_objects_refs_prefix(&config.resolved, lenient_config, filter_config_section)?
-            .and_then(|prefix| {
+        let load_replacements = {
+            let prefix = replacement_objects_refs_prefix(&config.resolved, filter_config_section);
+            let (git_dir, common_dir, namespace) = (git_dir.clone(), common_dir.clone(), refs.namespace.clone());
+            move || {
                 let _span = gix_trace::detail!("find replacement objects");
-                let platform = refs.iter().ok()?;
-                let iter = platform.prefixed(&prefix).ok()?;
-                let prefix = prefix.to_str()?;
-                let replacements = iter
-                    .filter_map(Result::ok)
-                    .filter_map(|r: gix_ref::Reference| {
-                        let target = r.target.try_id()?.to_owned();
-                        let source =
-                            gix_hash::ObjectId::from_hex(r.name.as_bstr().strip_prefix(prefix.as_bytes())?).ok()?;
-                        Some((source, target))
-                    })
-                    .collect::<Vec<_>>();
-                Some(replacements)
-            })
-            .unwrap_or_default();
+                let mut refs = ref_store_at(&git_dir, common_dir.as_deref(), ref_store_init_opts);
+                refs.namespace = namespace;
+                find_replacements(&refs, &prefix).unwrap_or_default()
+            }
+        };
 
+        let objects_dir = match config.resolved.string_filter_by_key(
+            gitoxide::Objects::DIRECTORY.logical_name().as_str(),
+            &mut filter_config_section,
+        ) {
+            Some(objects_dir) => {
+                let objects_dir = current_dir.join(gix_path::from_bstr(objects_dir));
+                if !objects_dir.is_dir() {
+                    return Err(Error::MissingOverrideDirectory {
+                        name: gitoxide::Objects::DIRECTORY.logical_name(),
+                        path: objects_dir,
+                    });
+                }
+                objects_dir
+            }
+            None => common_dir_ref.join("objects"),
+        };
         Ok(ThreadSafeRepository {
-            objects: OwnShared::new(gix_odb::Store::at_opts(
-                common_dir_ref.join("objects"),
-                &mut replacements.into_iter(),
+            objects: OwnShared::new(gix_odb::Store::at_opts_with_lazy_replacements(
+                objects_dir,
+                Box::new(load_replacements),
                 gix_odb::store::init::Options {
                     slots: object_store_slots,
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
                     current_dir: current_dir.to_owned().into(),
+                    loose_compression: config.loose_compression()?,
+                    zlib_backend: config.zlib_backend()?,
                 },
             )?),
             common_dir,
@@ -348,71 +385,80 @@ impl ThreadSafeRepository {
 }
 
 // TODO: tests
+fn ref_store_at(
+    git_dir: &std::path::Path,
+    common_dir: Option<&std::path::Path>,
+    options: gix_ref::store::init::Options,
+) -> crate::RefStore {
+    match common_dir {
+        Some(common_dir) => crate::RefStore::for_linked_worktree(git_dir.to_owned(), common_dir.into(), options),
+        None => crate::RefStore::at(git_dir.to_owned(), options),
+    }
+}
+
 fn replacement_objects_refs_prefix(
     config: &gix_config::File<'static>,
-    lenient: bool,
     mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
-) -> Result<Option<PathBuf>, Error> {
-    let is_disabled = config::shared::is_replace_refs_enabled(config, lenient, filter_config_section)
-        .map_err(config::Error::ConfigBoolean)?
-        .unwrap_or(true);
-
-    if is_disabled {
-        return Ok(None);
-    }
-
-    let ref_base = gix_path::from_bstr({
+) -> PathBuf {
+    gix_path::from_bstr({
         let key = "gitoxide.objects.replaceRefBase";
         debug_assert_eq!(gitoxide::Objects::REPLACE_REF_BASE.logical_name(), key);
         config
             .string_filter_by_key(key, &mut filter_config_section)
             .unwrap_or_else(|| Cow::Borrowed("refs/replace/".into()))
     })
-    .into_owned();
-    Ok(ref_base.into())
+    .into_owned()
+}
+
+fn find_replacements(
+    refs: &crate::RefStore,
+    prefix: &std::path::Path,
+) -> Option<Vec<(gix_hash::ObjectId, gix_hash::ObjectId)>> {
+    let platform = refs.iter().ok()?;
+    let iter = platform.prefixed(prefix).ok()?;
+    let prefix = prefix.to_str()?;
+    let replacements = iter
+        .filter_map(Result::ok)
+        .filter_map(|r: gix_ref::Reference| {
+            let target = r.target.try_id()?.to_owned();
+            let source = gix_hash::ObjectId::from_hex(r.name.as_bstr().strip_prefix(prefix.as_bytes())?).ok()?;
+            Some((source, target))
+        })
+        .collect();
+    Some(replacements)
 }
 
 fn check_safe_directories(
-    git_dir: &std::path::Path,
+    repo_dir: &std::path::Path,
     git_install_dir: Option<&std::path::Path>,
     current_dir: &std::path::Path,
     home: Option<&std::path::Path>,
     config: &config::Cache,
 ) -> Result<(), Error> {
-    let mut is_safe = false;
-    let git_dir = match gix_path::realpath_opts(git_dir, current_dir, gix_path::realpath::MAX_SYMLINKS) {
+    let repo_dir = match gix_path::realpath_opts(repo_dir, current_dir, gix_path::realpath::MAX_SYMLINKS) {
         Ok(p) => p,
-        Err(_) => git_dir.to_owned(),
+        Err(_) => repo_dir.to_owned(),
     };
-    for safe_dir in config
+    let safe_dirs: Vec<_> = config
         .resolved
         .strings_filter("safe", None, Safe::DIRECTORY.name, &mut Safe::directory_filter)
         .unwrap_or_default()
-    {
-        if safe_dir.as_ref() == "*" {
-            is_safe = true;
-            continue;
-        }
-        if safe_dir.is_empty() {
-            is_safe = false;
-            continue;
-        }
-        if !is_safe {
-            let safe_dir = match gix_config::Path::from(std::borrow::Cow::Borrowed(safe_dir.as_ref()))
+        .into_iter()
+        .map(|safe_dir| {
+            if safe_dir.as_ref() == "*" || safe_dir.is_empty() {
+                return gix_path::from_bstr(safe_dir).into_owned();
+            }
+            match gix_config::Path::from(std::borrow::Cow::Borrowed(safe_dir.as_ref()))
                 .interpolate(interpolate_context(git_install_dir, home))
             {
-                Ok(path) => path,
-                Err(_) => gix_path::from_bstr(safe_dir),
-            };
-            if safe_dir == git_dir {
-                is_safe = true;
-                continue;
+                Ok(path) => path.into_owned(),
+                Err(_) => gix_path::from_bstr(safe_dir).into_owned(),
             }
-        }
-    }
-    if is_safe {
+        })
+        .collect();
+    if gix_sec::trust::is_safe_directory(&repo_dir, safe_dirs.iter().map(AsRef::as_ref)) {
         Ok(())
     } else {
-        Err(Error::UnsafeGitDir { path: git_dir })
+        Err(Error::UnsafeGitDir { path: repo_dir })
     }
 }
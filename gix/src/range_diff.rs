@@ -0,0 +1,153 @@
+//! Types for use with [`Repository::range_diff()`](crate::Repository::range_diff()).
+use std::io::Write;
+
+use crate::bstr::{BString, ByteSlice};
+
+/// Options for use with [`Repository::range_diff()`](crate::Repository::range_diff()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The percentage of the size of a patch that pairing it with another patch may cost at most, so that it is rather shown
+    /// as removed and added otherwise, defaulting to `60` like `git range-diff --creation-factor`.
+    pub creation_factor: u32,
+    /// The amount of unchanged lines to show around each change in the [interdiff](Pair::interdiff), defaulting to `3`.
+    pub context_lines: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            creation_factor: 60,
+            context_lines: 3,
+        }
+    }
+}
+
+/// A commit of one of the ranges compared by [`Repository::range_diff()`](crate::Repository::range_diff()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The one-based position of the commit in its range, oldest first.
+    pub position: usize,
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The shortest unambiguous abbreviation of `id`.
+    pub short_id: String,
+    /// The first line of the commit message.
+    pub subject: BString,
+    /// The author, message and changes of the commit in the form that is compared with other commits, like
+    /// ` ## Commit message ##` followed by the indented message, and ` ## path ##` followed by the hunks of the file.
+    pub patch: BString,
+}
+
+/// How the commits of a [`Pair`] relate to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// Both commits have the same patch, shown as `=`.
+    Equal,
+    /// The patches of both commits differ, shown as `!`.
+    Changed,
+    /// The commit only exists in the old range, shown as `<`.
+    Removed,
+    /// The commit only exists in the new range, shown as `>`.
+    Added,
+}
+
+/// A commit of the old range along with the commit of the new range that corresponds to it, or either one of them if
+/// there is no counterpart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pair {
+    /// The commit of the old range.
+    pub old: Option<Commit>,
+    /// The commit of the new range.
+    pub new: Option<Commit>,
+    /// The hunks that turn the [patch](Commit::patch) of the old commit into the one of the new commit, empty unless both
+    /// exist and differ.
+    ///
+    /// The [section](gix_diff::patch::Hunk::section) of each hunk is the part of the patch that precedes it, like
+    /// `Commit message` or the path of a file, or `path: section` if a hunk of the file with a section precedes it.
+    pub interdiff: Vec<gix_diff::patch::Hunk>,
+}
+
+impl Pair {
+    /// Return how the commits of this pair relate to each other.
+    pub fn status(&self) -> Status {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) if old.patch == new.patch => Status::Equal,
+            (Some(_), Some(_)) => Status::Changed,
+            (Some(_), None) => Status::Removed,
+            (None, _) => Status::Added,
+        }
+    }
+}
+
+/// The result of [`Repository::range_diff()`](crate::Repository::range_diff()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All commits of both ranges, paired up if they correspond to each other, in the order `git range-diff` shows them.
+    pub pairs: Vec<Pair>,
+}
+
+impl Outcome {
+    /// Write all pairs to `out` like `git range-diff --no-color` does, with a line like `1:  abc1234 ! 1:  def5678 subject`
+    /// for each pair, followed by its indented interdiff.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        let commits = || {
+            self.pairs
+                .iter()
+                .flat_map(|pair| pair.old.iter().chain(pair.new.iter()))
+        };
+        let width = commits()
+            .map(|c| c.position)
+            .max()
+            .unwrap_or_default()
+            .to_string()
+            .len();
+        let id_width = commits().map(|c| c.short_id.len()).max().unwrap_or_default();
+        let side = |commit: Option<&Commit>| match commit {
+            Some(commit) => format!("{:>width$}:  {:<id_width$}", commit.position, commit.short_id),
+            None => format!("{:>width$}:  {}", "-", "-".repeat(id_width)),
+        };
+        for pair in &self.pairs {
+            let status = match pair.status() {
+                Status::Equal => '=',
+                Status::Changed => '!',
+                Status::Removed => '<',
+                Status::Added => '>',
+            };
+            let subject = pair.old.as_ref().or(pair.new.as_ref()).map(|c| c.subject.as_bstr());
+            writeln!(
+                out,
+                "{} {status} {} {}",
+                side(pair.old.as_ref()),
+                side(pair.new.as_ref()),
+                subject.unwrap_or_default()
+            )?;
+            for hunk in &pair.interdiff {
+                out.write_all(b"    @@")?;
+                if !hunk.section.is_empty() {
+                    out.write_all(b" ")?;
+                    out.write_all(&hunk.section)?;
+                }
+                out.write_all(b"\n")?;
+                for line in &hunk.lines {
+                    let (prefix, content) = match line {
+                        gix_diff::patch::Line::Context(line) => (b' ', line),
+                        gix_diff::patch::Line::Removal(line) => (b'-', line),
+                        gix_diff::patch::Line::Addition(line) => (b'+', line),
+                    };
+                    out.write_all(&[b' ', b' ', b' ', b' ', prefix])?;
+                    out.write_all(content.trim_end_with(|c| c == '\n'))?;
+                    out.write_all(b"\n")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::range_diff()`](crate::Repository::range_diff()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not obtain the commits of a range or their patches")]
+    Patches(#[from] crate::format_patch::Error),
+}
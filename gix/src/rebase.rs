@@ -0,0 +1,137 @@
+//! Types for use with [`Repository::rebase()`](crate::Repository::rebase()) and the methods to continue or abort it.
+use crate::bstr::BString;
+pub use gix_rebase as plumbing;
+pub use gix_rebase::{EmptyCommits, Instruction};
+
+/// Options for use with [`Repository::rebase()`](crate::Repository::rebase()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// A revision specification of the commit to replay the commits onto, instead of the upstream commit itself,
+    /// similar to `git rebase --onto <newbase>`.
+    pub onto: Option<BString>,
+    /// What to do with commits whose changes are already present, so they would be empty.
+    pub empty: EmptyCommits,
+    /// If `true`, default `false`, local changes are stashed before the rebase starts and reapplied once it is done or
+    /// aborted, similar to `git rebase --autostash`.
+    ///
+    /// Otherwise, the rebase is refused if there are local changes to tracked files.
+    pub autostash: bool,
+}
+
+/// The reason for a rebase to stop before all instructions were performed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stop {
+    /// The changes of the commit to pick couldn't be merged.
+    ///
+    /// The index holds the unmerged stages of all conflicting paths, and the worktree their conflicting versions.
+    /// Once resolved and added to the index, [`rebase_continue()`](crate::Repository::rebase_continue()) commits the result.
+    Conflicts(Vec<crate::merge::Conflict>),
+    /// The commit to pick would be empty and [`EmptyCommits::Stop`] is configured.
+    Empty,
+    /// A [`Instruction::Break`] was encountered.
+    Break,
+    /// The command of an [`Instruction::Exec`] failed.
+    ExecFailed {
+        /// The exit status of the command.
+        status: std::process::ExitStatus,
+    },
+}
+
+/// The outcome of [`Repository::rebase()`](crate::Repository::rebase()) and
+/// [`Repository::rebase_continue()`](crate::Repository::rebase_continue()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// All instructions were performed and the rebase is finished.
+    Finished {
+        /// The commit `HEAD` points to now.
+        head_id: gix_hash::ObjectId,
+        /// If the reapplication of the autostash caused conflicts, these are listed here.
+        ///
+        /// The autostash is also stored in `refs/stash` in that case so it won't be lost.
+        autostash_conflicts: Vec<crate::merge::Conflict>,
+    },
+    /// The rebase stopped at `instruction`, which was performed to the extent possible, and can be continued with
+    /// [`rebase_continue()`](crate::Repository::rebase_continue()) or aborted with
+    /// [`rebase_abort()`](crate::Repository::rebase_abort()).
+    Stopped {
+        /// The instruction at which the rebase stopped.
+        instruction: Instruction,
+        /// The reason for the stop.
+        reason: Stop,
+    },
+}
+
+/// The error returned by [`Repository::rebase()`](crate::Repository::rebase()) and the methods to continue or abort it.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and has no worktree to rebase", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error("Cannot rebase while another operation is in progress: {state:?}")]
+    InProgress { state: crate::state::InProgress },
+    #[error("No rebase is in progress")]
+    NotInProgress,
+    #[error("Cannot rebase an unborn branch")]
+    UnbornHead,
+    #[error(
+        "Cannot rebase as there are local changes, which can be stashed automatically with the 'autostash' option"
+    )]
+    LocalChanges,
+    #[error("The index has unresolved conflicts which have to be resolved before continuing")]
+    Unmerged,
+    #[error(transparent)]
+    ParseRevision(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    WalkItem(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    IsDirty(#[from] crate::status::is_dirty::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    StashCreate(#[from] crate::stash::create::Error),
+    #[error(transparent)]
+    StashApply(#[from] crate::stash::apply::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    Pick(#[from] crate::cherry_pick::Error),
+    #[error(transparent)]
+    WriteTree(#[from] crate::repository::write_tree_from_index::Error),
+    #[error(transparent)]
+    WriteCommit(#[from] crate::object::write::Error),
+    #[error(transparent)]
+    ParseTime(#[from] crate::config::time::Error),
+    #[error("Committer identity is not configured")]
+    CommitterMissing,
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error("The name of the branch being rebased is invalid")]
+    HeadName(#[from] gix_validate::reference::name::Error),
+    #[error(transparent)]
+    ReadState(#[from] gix_rebase::state::read::Error),
+    #[error(transparent)]
+    DecodeAuthorScript(#[from] gix_rebase::author_script::decode::Error),
+    #[error("Could not write or remove rebase state at \"{}\"", path.display())]
+    WriteState {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not run command '{command}'")]
+    Exec { command: BString, source: std::io::Error },
+}
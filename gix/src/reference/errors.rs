@@ -13,6 +13,8 @@ pub mod edit {
         #[error(transparent)]
         FileTransactionCommit(#[from] gix_ref::file::transaction::commit::Error),
         #[error(transparent)]
+        FindReference(#[from] gix_ref::file::find::Error),
+        #[error(transparent)]
         NameValidation(#[from] gix_validate::reference::name::Error),
         #[error("Could not interpret core.filesRefLockTimeout or core.packedRefsTimeout, it must be the number in milliseconds to wait for locks or negative to wait forever")]
         LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
@@ -1,9 +1,10 @@
 //!
 #![allow(clippy::empty_docs)]
-use std::path::Path;
+use std::{iter::Peekable, path::Path};
 
 use gix_macros::momo;
-use gix_ref::file::ReferenceExt;
+
+use crate::bstr::BStr;
 
 /// A platform to create iterators over references.
 #[must_use = "Iterators should be obtained from this iterator platform"]
@@ -14,19 +15,68 @@ pub struct Platform<'r> {
 
 /// An iterator over references, with or without filter.
 pub struct Iter<'r> {
-    inner: gix_ref::file::iter::LooseThenPacked<'r, 'r>,
+    inner: Peekable<gix_ref::file::iter::LooseThenPacked<'r, 'r>>,
+    /// References in memory whose name matches the filter, sorted by name.
+    memory: Peekable<std::vec::IntoIter<gix_ref::Reference>>,
     peel: bool,
     repo: &'r crate::Repository,
 }
 
 impl<'r> Iter<'r> {
-    fn new(repo: &'r crate::Repository, platform: gix_ref::file::iter::LooseThenPacked<'r, 'r>) -> Self {
+    fn new(
+        repo: &'r crate::Repository,
+        platform: gix_ref::file::iter::LooseThenPacked<'r, 'r>,
+        prefix: Option<&BStr>,
+    ) -> Self {
+        let prefix = prefix.unwrap_or("refs/".into());
+        let memory: Vec<_> = repo
+            .refs_memory
+            .as_ref()
+            .map(|memory| {
+                memory
+                    .borrow()
+                    .iter()
+                    .filter(|(name, _)| name.as_bstr().starts_with(prefix))
+                    .filter_map(|(name, target)| {
+                        Some(gix_ref::Reference {
+                            name: name.clone(),
+                            target: target.clone()?,
+                            peeled: None,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
         Iter {
-            inner: platform,
+            inner: platform.peekable(),
+            memory: memory.into_iter().peekable(),
             peel: false,
             repo,
         }
     }
+
+    /// Return the next reference, preferring references in memory over those on disk.
+    fn next_inner(&mut self) -> Option<Result<gix_ref::Reference, gix_ref::file::iter::loose_then_packed::Error>> {
+        loop {
+            let memory_is_next = match (self.inner.peek(), self.memory.peek()) {
+                (_, None) => false,
+                (Some(Ok(on_disk)), Some(in_memory)) => in_memory.name <= on_disk.name,
+                (Some(Err(_)), Some(_)) => false,
+                (None, Some(_)) => true,
+            };
+            if memory_is_next {
+                return self.memory.next().map(Ok);
+            }
+            let res = self.inner.next()?;
+            if let (Ok(on_disk), Some(memory)) = (&res, &self.repo.refs_memory) {
+                if memory.borrow().contains_key(&on_disk.name) {
+                    // It was changed or deleted in memory.
+                    continue;
+                }
+            }
+            return Some(res);
+        }
+    }
 }
 
 impl<'r> Platform<'r> {
@@ -35,7 +85,7 @@ impl<'r> Platform<'r> {
     /// Even broken or otherwise unparsable or inaccessible references are returned and have to be handled by the caller on a
     /// case by case basis.
     pub fn all(&self) -> Result<Iter<'_>, init::Error> {
-        Ok(Iter::new(self.repo, self.platform.all()?))
+        Ok(Iter::new(self.repo, self.platform.all()?, None))
     }
 
     /// Return an iterator over all references that match the given `prefix`.
@@ -45,7 +95,12 @@ impl<'r> Platform<'r> {
     //       and when not using a trailing '/' to signal directories.
     #[momo]
     pub fn prefixed(&self, prefix: impl AsRef<Path>) -> Result<Iter<'_>, init::Error> {
-        Ok(Iter::new(self.repo, self.platform.prefixed(prefix.as_ref())?))
+        let prefix = prefix.as_ref();
+        Ok(Iter::new(
+            self.repo,
+            self.platform.prefixed(prefix)?,
+            Some(gix_path::to_unix_separators_on_windows(gix_path::into_bstr(prefix)).as_ref()),
+        ))
     }
 
     // TODO: tests
@@ -53,7 +108,11 @@ impl<'r> Platform<'r> {
     ///
     /// They are all prefixed with `refs/tags`.
     pub fn tags(&self) -> Result<Iter<'_>, init::Error> {
-        Ok(Iter::new(self.repo, self.platform.prefixed("refs/tags/".as_ref())?))
+        Ok(Iter::new(
+            self.repo,
+            self.platform.prefixed("refs/tags/".as_ref())?,
+            Some("refs/tags/".into()),
+        ))
     }
 
     // TODO: tests
@@ -61,7 +120,11 @@ impl<'r> Platform<'r> {
     ///
     /// They are all prefixed with `refs/heads`.
     pub fn local_branches(&self) -> Result<Iter<'_>, init::Error> {
-        Ok(Iter::new(self.repo, self.platform.prefixed("refs/heads/".as_ref())?))
+        Ok(Iter::new(
+            self.repo,
+            self.platform.prefixed("refs/heads/".as_ref())?,
+            Some("refs/heads/".into()),
+        ))
     }
 
     // TODO: tests
@@ -69,7 +132,11 @@ impl<'r> Platform<'r> {
     ///
     /// They are all prefixed with `refs/remotes`.
     pub fn remote_branches(&self) -> Result<Iter<'_>, init::Error> {
-        Ok(Iter::new(self.repo, self.platform.prefixed("refs/remotes/".as_ref())?))
+        Ok(Iter::new(
+            self.repo,
+            self.platform.prefixed("refs/remotes/".as_ref())?,
+            Some("refs/remotes/".into()),
+        ))
     }
 }
 
@@ -92,19 +159,18 @@ impl<'r> Iterator for Iter<'r> {
     type Item = Result<crate::Reference<'r>, Box<dyn std::error::Error + Send + Sync + 'static>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next().map(|res| {
+        self.next_inner().map(|res| {
             res.map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync + 'static>)
+                .map(|r| crate::Reference::from_ref(r, self.repo))
                 .and_then(|mut r| {
                     if self.peel {
-                        let repo = &self.repo;
-                        r.peel_to_id_in_place(&repo.refs, &repo.objects)
+                        r.peel_to_id_in_place()
                             .map_err(|err| Box::new(err) as Box<dyn std::error::Error + Send + Sync + 'static>)
                             .map(|_| r)
                     } else {
                         Ok(r)
                     }
                 })
-                .map(|r| crate::Reference::from_ref(r, self.repo))
         })
     }
 }
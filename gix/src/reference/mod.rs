@@ -70,6 +70,21 @@ impl<'repo> Reference<'repo> {
     ///
     /// This is useful to learn where this reference is ultimately pointing to.
     pub fn peel_to_id_in_place(&mut self) -> Result<Id<'repo>, peel::Error> {
+        if self.repo.refs_memory.is_some() && self.inner.peeled.is_none() {
+            // Follow symbolic references ourselves as they may be in memory.
+            const MAX_REF_DEPTH: usize = 5;
+            let mut depth = 0;
+            while let Some(next) = self.follow() {
+                *self = next.map_err(gix_ref::peel::to_id::Error::from)?;
+                depth += 1;
+                if depth == MAX_REF_DEPTH {
+                    return Err(gix_ref::peel::to_id::Error::DepthLimitExceeded {
+                        max_depth: MAX_REF_DEPTH,
+                    }
+                    .into());
+                }
+            }
+        }
         let oid = self.inner.peel_to_id_in_place(&self.repo.refs, &self.repo.objects)?;
         Ok(Id::from_id(oid, self.repo))
     }
@@ -83,6 +98,23 @@ impl<'repo> Reference<'repo> {
     ///
     /// Returns `None` if this is not a symbolic reference, hence the leaf of the chain.
     pub fn follow(&self) -> Option<Result<Reference<'repo>, gix_ref::file::find::existing::Error>> {
+        if let (Some(memory), gix_ref::Target::Symbolic(name)) = (&self.repo.refs_memory, &self.inner.target) {
+            if let Some(target) = memory.borrow().get(name) {
+                return Some(match target {
+                    Some(target) => Ok(Reference {
+                        inner: gix_ref::Reference {
+                            name: name.clone(),
+                            target: target.clone(),
+                            peeled: None,
+                        },
+                        repo: self.repo,
+                    }),
+                    None => Err(gix_ref::file::find::existing::Error::NotFound {
+                        name: name.to_path().to_owned(),
+                    }),
+                });
+            }
+        }
         self.inner.follow(&self.repo.refs).map(|res| {
             res.map(|r| Reference {
                 inner: r,
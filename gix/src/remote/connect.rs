@@ -29,6 +29,9 @@ mod error {
         MissingUrl { direction: remote::Direction },
         #[error("The given protocol version was invalid. Choose between 1 and 2")]
         UnknownProtocol { source: config::key::GenericErrorWithValue },
+        #[cfg(feature = "bundle")]
+        #[error(transparent)]
+        OpenBundle(#[from] crate::bundle::header::Error),
         #[error("Could not verify that \"{}\" url is a valid git directory before attempting to use it", url.to_bstring())]
         FileUrl {
             source: Box<gix_discover::is_git::Error>,
@@ -73,6 +76,9 @@ impl<'repo> Remote<'repo> {
     /// Note that the `protocol.version` configuration key affects the transport protocol used to connect,
     /// with `2` being the default.
     ///
+    /// If the `bundle` feature is enabled and the url points to a [bundle file](crate::bundle), a [transport](crate::bundle::Transport)
+    /// to fetch from it is used.
+    ///
     /// The transport used for connection can be configured via `transport_mut().configure()` assuming the actually
     /// used transport is well known. If that's not the case, the transport can be created by hand and passed to
     /// [to_connection_with_transport()][Self::to_connection_with_transport()].
@@ -83,6 +89,15 @@ impl<'repo> Remote<'repo> {
         direction: crate::remote::Direction,
     ) -> Result<Connection<'_, 'repo, Box<dyn Transport + Send>>, Error> {
         let (url, version) = self.sanitized_url_and_version(direction)?;
+        #[cfg(all(feature = "bundle", feature = "blocking-network-client"))]
+        if let Some(path) = bundle_path(&url) {
+            return Ok(
+                self.to_connection_with_transport(Box::new(crate::bundle::Transport::open(
+                    path,
+                    self.repo.config.trace_packet(),
+                )?)),
+            );
+        }
         #[cfg(feature = "blocking-network-client")]
         let scheme_is_ssh = url.scheme == gix_url::Scheme::Ssh;
         let transport = gix_protocol::transport::connect(
@@ -109,6 +124,10 @@ impl<'repo> Remote<'repo> {
         direction: crate::remote::Direction,
     ) -> Result<(gix_url::Url, gix_protocol::transport::Protocol), Error> {
         fn sanitize(mut url: gix_url::Url) -> Result<gix_url::Url, Error> {
+            #[cfg(feature = "bundle")]
+            if bundle_path(&url).is_some() {
+                return Ok(url);
+            }
             if url.scheme == gix_url::Scheme::File {
                 let mut dir = gix_path::to_native_path_on_windows(Cow::Borrowed(url.path.as_ref()));
                 let kind = gix_discover::is_git(dir.as_ref())
@@ -150,3 +169,13 @@ impl<'repo> Remote<'repo> {
         Ok((sanitize(url)?, version))
     }
 }
+
+/// Return the path to the bundle file `url` points to, if it does.
+#[cfg(feature = "bundle")]
+fn bundle_path(url: &gix_url::Url) -> Option<std::path::PathBuf> {
+    if url.scheme != gix_url::Scheme::File {
+        return None;
+    }
+    let path = gix_path::to_native_path_on_windows(Cow::Borrowed(url.path.as_ref())).into_owned();
+    crate::bundle::is_bundle(&path).then_some(path)
+}
@@ -126,12 +126,15 @@ pub mod outcome {
 pub enum ProgressId {
     /// The progress name is defined by the remote and the progress messages it sets, along with their progress values and limits.
     RemoteProgress,
+    /// The amount of ref mappings that were turned into updates of local references.
+    UpdateRefs,
 }
 
 impl From<ProgressId> for gix_features::progress::Id {
     fn from(v: ProgressId) -> Self {
         match v {
             ProgressId::RemoteProgress => *b"FERP",
+            ProgressId::UpdateRefs => *b"FEUR",
         }
     }
 }
@@ -195,6 +198,8 @@ where
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
             shallow: Default::default(),
+            filter: None,
+            prune: false,
         })
     }
 }
@@ -226,6 +231,8 @@ where
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
     shallow: remote::fetch::Shallow,
+    filter: Option<String>,
+    prune: bool,
 }
 
 /// Builder
@@ -267,6 +274,27 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// Ask the server to omit objects according to the filter `spec`, like `blob:none` or `tree:0`, to perform a partial fetch,
+    /// or fetch all objects if `None`.
+    ///
+    /// Note that the server must support the `filter` capability, and that omitted objects can't be fetched on demand later.
+    pub fn with_filter(mut self, spec: impl Into<Option<String>>) -> Self {
+        self.filter = spec.into();
+        self
+    }
+
+    /// If enabled, delete local references that are written by the fetch refspecs of the remote, but whose counterpart
+    /// doesn't exist on the remote anymore.
+    ///
+    /// Symbolic references and references that are checked out in a worktree are never deleted.
+    /// The deletions are available in [`refs::update::Outcome::pruned`].
+    ///
+    /// Note that `fetch.prune` and `remote.<name>.prune` are not consulted.
+    pub fn with_prune(mut self, enabled: bool) -> Self {
+        self.prune = enabled;
+        self
+    }
 }
 
 impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
@@ -3,6 +3,7 @@ use std::{
     sync::atomic::{AtomicBool, Ordering},
 };
 
+use gix_features::progress::{Count, Progress};
 use gix_odb::store::RefreshMode;
 use gix_protocol::{
     fetch::Arguments,
@@ -116,6 +117,16 @@ where
             }
             arguments.use_include_tag();
         }
+        if let Some(spec) = self.filter.as_deref() {
+            if !arguments.can_use_filter() {
+                return Err(Error::MissingServerFeature {
+                    feature: "filter",
+                    description:
+                        "Partial fetches need the server to omit objects according to the filter specification",
+                });
+            }
+            arguments.filter(spec);
+        }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
 
         if self.ref_map.object_hash != repo.object_hash() {
@@ -266,6 +277,7 @@ where
                 };
 
                 let write_pack_bundle = if matches!(self.dry_run, fetch::DryRun::No) {
+                    let _span = gix_trace::coarse!("fetch::Prepare::receive() write pack");
                     #[cfg(not(feature = "async-network-client"))]
                     let mut rd = reader;
                     #[cfg(feature = "async-network-client")]
@@ -289,6 +301,15 @@ where
                     if !has_read_to_end {
                         std::io::copy(&mut rd, &mut std::io::sink()).map_err(Error::ReadRemainingBytes)?;
                     }
+                    gix_trace::debug!(
+                        objects = res.index.num_objects,
+                        bytes = res
+                            .data_path
+                            .as_deref()
+                            .and_then(|path| path.metadata().ok())
+                            .map(|md| md.len()),
+                        "received pack"
+                    );
                     #[cfg(feature = "async-network-client")]
                     {
                         reader = rd.into_inner();
@@ -319,7 +340,13 @@ where
             }
         };
 
-        let update_refs = refs::update(
+        let mut refs_progress = progress.add_child_with_id("updating refs".into(), ProgressId::UpdateRefs.into());
+        refs_progress.init(
+            Some(self.ref_map.mappings.len()),
+            gix_features::progress::count("mappings"),
+        );
+        let start = std::time::Instant::now();
+        let mut update_refs = refs::update(
             repo,
             self.reflog_message
                 .take()
@@ -331,6 +358,17 @@ where
             self.dry_run,
             self.write_packed_refs,
         )?;
+        refs_progress.inc_by(self.ref_map.mappings.len());
+        refs_progress.show_throughput(start);
+        drop(refs_progress);
+        if self.prune {
+            update_refs.pruned = refs::prune(
+                repo,
+                &self.ref_map.mappings,
+                con.remote.refspecs(remote::Direction::Fetch),
+                self.dry_run,
+            )?;
+        }
 
         if let Some(bundle) = write_pack_bundle.as_mut() {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
@@ -1,5 +1,9 @@
 #![allow(clippy::result_large_err)]
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 use gix_object::Exists;
 use gix_ref::{
@@ -8,6 +12,7 @@ use gix_ref::{
 };
 
 use crate::{
+    bstr::{BStr, ByteSlice},
     ext::ObjectIdExt,
     remote::{
         fetch,
@@ -321,8 +326,7 @@ pub(crate) fn update(
                 .config
                 .lock_timeout()
                 .map_err(crate::reference::edit::Error::from)?;
-            repo.refs
-                .transaction()
+            repo.reference_transaction()
                 .packed_refs(
                     match write_packed_refs {
                         fetch::WritePackedRefs::Only => {
@@ -338,7 +342,122 @@ pub(crate) fn update(
         fetch::DryRun::Yes => edits,
     };
 
-    Ok(update::Outcome { edits, updates })
+    Ok(update::Outcome {
+        edits,
+        updates,
+        pruned: Vec::new(),
+    })
+}
+
+/// Delete all local references that are a destination of one of the `refspecs`, but whose source doesn't exist on the remote anymore,
+/// which is when they aren't the local side of any of the `mappings`.
+/// Return the edits that performed the deletion.
+///
+/// If `dry_run` is true, the edits are returned without applying them.
+///
+/// Just like `git`, we don't prune…
+///
+/// * …symbolic references, like `refs/remotes/origin/HEAD`
+/// * …references whose remote counterpart is excluded by a negative refspec
+///
+/// Additionally, we don't prune references that are checked out in any worktree.
+pub(crate) fn prune(
+    repo: &Repository,
+    mappings: &[fetch::Mapping],
+    refspecs: &[gix_refspec::RefSpec],
+    dry_run: fetch::DryRun,
+) -> Result<Vec<RefEdit>, update::Error> {
+    let _span = gix_trace::detail!("prune_refs()", refspecs = refspecs.len());
+    let checked_out_branches = worktree_branches(repo)?;
+    let mapped_locals: BTreeSet<_> = mappings
+        .iter()
+        .filter_map(|m| m.local.as_ref().map(|local| local.as_bstr()))
+        .collect();
+    let mut edits = Vec::new();
+    for reference in repo.refs.iter()?.all()? {
+        let reference = reference?;
+        let name = reference.name.as_bstr();
+        let Target::Peeled(id) = reference.target else {
+            continue;
+        };
+        let is_stale = !mapped_locals.contains(name)
+            && refspecs.iter().any(|spec| {
+                remote_name_of_destination(spec.to_ref(), name)
+                    .map_or(false, |remote_name| !is_excluded(refspecs, remote_name.as_ref()))
+            });
+        if !is_stale || checked_out_branches.contains_key(&reference.name) {
+            continue;
+        }
+        edits.push(RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                log: RefLog::AndReference,
+            },
+            name: reference.name,
+            deref: false,
+        });
+    }
+
+    if edits.is_empty() || matches!(dry_run, fetch::DryRun::Yes) {
+        return Ok(edits);
+    }
+    let _span = gix_trace::detail!("apply", edits = edits.len());
+    let (file_lock_fail, packed_refs_lock_fail) = repo
+        .config
+        .lock_timeout()
+        .map_err(crate::reference::edit::Error::from)?;
+    Ok(repo
+        .reference_transaction()
+        .packed_refs(gix_ref::file::transaction::PackedRefs::DeletionsOnly)
+        .prepare(edits, file_lock_fail, packed_refs_lock_fail)
+        .map_err(crate::reference::edit::Error::from)?
+        .commit(
+            repo.committer()
+                .transpose()
+                .map_err(|err| update::Error::EditReferences(crate::reference::edit::Error::ParseCommitterTime(err)))?,
+        )
+        .map_err(crate::reference::edit::Error::from)?)
+}
+
+/// If `name` is a local reference that is written by `spec`, return the name of the reference on the remote that it corresponds to.
+fn remote_name_of_destination<'a>(spec: gix_refspec::RefSpecRef<'a>, name: &BStr) -> Option<Cow<'a, BStr>> {
+    let gix_refspec::Instruction::Fetch(gix_refspec::instruction::Fetch::AndUpdate { src, dst, .. }) =
+        spec.instruction()
+    else {
+        return None;
+    };
+    if !dst.starts_with(b"refs/") {
+        return None;
+    }
+    match dst.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&dst[..pos], &dst[pos + 1..]);
+            if name.len() < prefix.len() + suffix.len() || !name.starts_with(prefix) || !name.ends_with(suffix) {
+                return None;
+            }
+            let matched = &name[prefix.len()..name.len() - suffix.len()];
+            Some(Cow::Owned(src.replace("*", matched).into()))
+        }
+        None => (dst == name).then_some(Cow::Borrowed(src)),
+    }
+}
+
+/// Return `true` if `remote_name` is excluded by one of the negative `refspecs`.
+fn is_excluded(refspecs: &[gix_refspec::RefSpec], remote_name: &BStr) -> bool {
+    refspecs.iter().any(|spec| match spec.to_ref().instruction() {
+        gix_refspec::Instruction::Fetch(gix_refspec::instruction::Fetch::Exclude { src }) => {
+            match src.find_byte(b'*') {
+                Some(pos) => {
+                    let (prefix, suffix) = (&src[..pos], &src[pos + 1..]);
+                    remote_name.len() >= prefix.len() + suffix.len()
+                        && remote_name.starts_with(prefix)
+                        && remote_name.ends_with(suffix)
+                }
+                None => src == remote_name,
+            }
+        }
+        _ => false,
+    })
 }
 
 /// Figure out if target of `edit` points to a reference that doesn't exist in `repo` and won't exist as it's not in any of `edits`.
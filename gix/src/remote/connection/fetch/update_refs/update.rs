@@ -23,6 +23,10 @@ mod error {
         PeelToId(#[from] crate::reference::peel::Error),
         #[error("Failed to follow a symbolic reference to assure worktree isn't affected")]
         FollowSymref(#[from] gix_ref::file::find::existing::Error),
+        #[error("Could not open packed-refs to find references to prune")]
+        OpenPackedRefs(#[from] gix_ref::packed::buffer::open::Error),
+        #[error("Could not iterate local references to find those to prune")]
+        IterReferences(#[from] gix_ref::file::iter::loose_then_packed::Error),
     }
 }
 
@@ -37,6 +41,11 @@ pub struct Outcome {
     /// Use [`iter_mapping_updates()`][Self::iter_mapping_updates()] to recombine the update information with ref-edits and their
     /// mapping.
     pub updates: Vec<super::Update>,
+    /// The deletions of local refs whose counterpart doesn't exist on the remote anymore, if pruning was enabled
+    /// with [`Prepare::with_prune()`](crate::remote::fetch::Prepare::with_prune()).
+    ///
+    /// These are unrelated to any mapping and thus can't be obtained with [`iter_mapping_updates()`][Self::iter_mapping_updates()].
+    pub pruned: Vec<gix_ref::transaction::RefEdit>,
 }
 
 /// Describe the way a ref was updated, with particular focus on how the (peeled) target commit was affected.
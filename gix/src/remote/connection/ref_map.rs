@@ -59,6 +59,12 @@ pub struct Options {
     ///
     /// This is useful for handling `remote.<name>.tagOpt` for example.
     pub extra_refspecs: Vec<gix_refspec::RefSpec>,
+    /// Prefixes like `refs/tags/` to pass as `ref-prefix` arguments to the `ls-refs` command, in addition to the ones
+    /// derived from refspecs if [`prefix_from_spec_as_filter_on_remote`](Self::prefix_from_spec_as_filter_on_remote) is `true`.
+    ///
+    /// As the server sends all references matching any of the prefixes, each of them widens the set of received references.
+    /// Note that this only has an effect with protocol V2, as older protocols send all references as part of the handshake.
+    pub extra_ref_prefixes: Vec<BString>,
 }
 
 impl Default for Options {
@@ -67,6 +73,7 @@ impl Default for Options {
             prefix_from_spec_as_filter_on_remote: true,
             handshake_parameters: Vec::new(),
             extra_refspecs: Vec::new(),
+            extra_ref_prefixes: Vec::new(),
         }
     }
 }
@@ -110,6 +117,7 @@ where
             prefix_from_spec_as_filter_on_remote,
             handshake_parameters,
             mut extra_refspecs,
+            extra_ref_prefixes,
         }: Options,
     ) -> Result<fetch::RefMap, Error> {
         let _span = gix_trace::coarse!("remote::Connection::ref_map()");
@@ -128,6 +136,7 @@ where
         let remote = self
             .fetch_refs(
                 prefix_from_spec_as_filter_on_remote,
+                &extra_ref_prefixes,
                 handshake_parameters,
                 &specs,
                 progress,
@@ -183,6 +192,7 @@ where
     async fn fetch_refs(
         &mut self,
         filter_by_prefix: bool,
+        extra_ref_prefixes: &[BString],
         extra_parameters: Vec<(String, Option<String>)>,
         refspecs: &[gix_refspec::RefSpec],
         mut progress: impl Progress,
@@ -240,6 +250,11 @@ where
                                 }
                             }
                         }
+                        for prefix in extra_ref_prefixes {
+                            let mut argument = BString::from("ref-prefix ");
+                            argument.push_str(prefix);
+                            arguments.push(argument);
+                        }
                         Ok(gix_protocol::ls_refs::Action::Continue)
                     },
                     &mut progress,
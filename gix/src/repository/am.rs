@@ -0,0 +1,309 @@
+use std::path::PathBuf;
+
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    am,
+    am::Stop,
+    apply,
+    bstr::{BString, ByteSlice},
+    Repository,
+};
+
+/// Apply patches from mailboxes
+impl Repository {
+    /// Apply the patches in all mails of `mbox` one by one and commit each of them with the author, date and message of its
+    /// mail, similar to `git am`.
+    ///
+    /// Patches are applied to the index and the worktree with [`apply()`](Self::apply()), and the branch that `HEAD` points to
+    /// advances with each commit. Patches whose changes are already present are skipped.
+    /// If a patch can't be applied, the operation [stops](am::Outcome::Stopped) so the patch can be applied by hand before
+    /// [continuing](Self::am_continue()), or it can be [skipped](Self::am_skip()) or the whole operation [aborted](Self::am_abort()).
+    ///
+    /// All mails are parsed before anything is changed. The progress is kept in `.git/rebase-apply` in the same format as `git` uses,
+    /// so `git am --continue` or `git am --abort` can take over as well, and the previous value of `HEAD` is recorded in `ORIG_HEAD`.
+    pub fn am(&self, mbox: &[u8], options: am::Options) -> Result<am::Outcome, am::Error> {
+        let _span = gix_trace::coarse!("gix::am()", options = ?options);
+        if self.work_dir().is_none() {
+            return Err(am::Error::BareRepository {
+                git_dir: self.git_dir().to_owned(),
+            });
+        }
+        if let Some(state) = self.state() {
+            return Err(am::Error::InProgress { state });
+        }
+        let head_id = self.am_head_id()?;
+        if !self.index_matches_tree(&*self.index_or_empty()?, self.commit_tree(head_id)?)? {
+            return Err(am::Error::DirtyIndex);
+        }
+        let mails = am::mail::split(mbox);
+        let mail_options = am::mail::Options {
+            scissors: options.scissors,
+        };
+        for (idx, mail) in mails.iter().enumerate() {
+            am::mail::parse(mail, mail_options).map_err(|source| am::Error::Mail { patch: idx + 1, source })?;
+        }
+
+        let dir = self.am_state_dir();
+        std::fs::create_dir_all(&dir).map_err(|source| am::Error::WriteState {
+            path: dir.clone(),
+            source,
+        })?;
+        for (idx, mail) in mails.iter().enumerate() {
+            self.write_am_state_file(&format!("{:04}", idx + 1), mail)?;
+        }
+        let flag = |value: bool| if value { "t" } else { "f" };
+        for (name, content) in [
+            ("threeway", flag(options.three_way)),
+            ("quiet", "f"),
+            ("sign", "f"),
+            ("utf8", "t"),
+            ("keep", "f"),
+            ("messageid", "f"),
+            ("scissors", flag(options.scissors)),
+            ("apply-opt", ""),
+            ("applying", ""),
+        ] {
+            self.write_am_state_file(name, format!("{content}\n").as_bytes())?;
+        }
+        self.write_am_state_file("abort-safety", format!("{head_id}\n").as_bytes())?;
+        self.write_am_state_file("next", b"1\n")?;
+        self.write_am_state_file("last", format!("{}\n", mails.len()).as_bytes())?;
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: "am".into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(head_id),
+            },
+            name: "ORIG_HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        self.am_run()
+    }
+
+    /// Commit the changes in the index with the message and author of the patch at which the operation [stopped](am::Outcome::Stopped)
+    /// and continue with the next patch, similar to `git am --continue`.
+    ///
+    /// The message and author are read from the `final-commit` and `author-script` files in `.git/rebase-apply`, so they may be
+    /// edited before continuing. Changes in the worktree that are not added to the index are ignored.
+    pub fn am_continue(&self) -> Result<am::Outcome, am::Error> {
+        let _span = gix_trace::coarse!("gix::am_continue()");
+        let next = self.am_state_count("next")?;
+        let index = self.open_index()?;
+        if index
+            .entries()
+            .iter()
+            .any(|e| e.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(am::Error::Unmerged);
+        }
+        let head_id = self.am_head_id()?;
+        let tree = self.write_tree_from_index(&index)?.detach();
+        if tree == self.commit_tree(head_id)? {
+            return Err(am::Error::NoChanges);
+        }
+        self.am_commit(head_id, tree)?;
+        self.write_am_state_file("next", format!("{}\n", next + 1).as_bytes())?;
+        self.am_run()
+    }
+
+    /// Discard the changes of the patch at which the operation [stopped](am::Outcome::Stopped) from the index and the worktree
+    /// and continue with the next patch, similar to `git am --skip`.
+    pub fn am_skip(&self) -> Result<am::Outcome, am::Error> {
+        let _span = gix_trace::coarse!("gix::am_skip()");
+        let next = self.am_state_count("next")?;
+        let head_tree = self.commit_tree(self.am_head_id()?)?;
+        let (mut index, _files_removed, _checkout) = self.checkout_tree(Some(head_tree), head_tree, true)?;
+        index.write(Default::default())?;
+        self.write_am_state_file("next", format!("{}\n", next + 1).as_bytes())?;
+        self.am_run()
+    }
+
+    /// Undo all commits created so far and restore the index and the worktree to their state before
+    /// [`am()`](Self::am()) started, similar to `git am --abort`.
+    pub fn am_abort(&self) -> Result<(), am::Error> {
+        let _span = gix_trace::coarse!("gix::am_abort()");
+        self.am_state_count("next")?;
+        let orig_head = self
+            .find_reference("ORIG_HEAD")?
+            .peel_to_id_in_place()
+            .map_err(crate::head::peel::Error::from)?
+            .detach();
+        let head_tree = self.commit_tree(self.am_head_id()?)?;
+        let (mut index, _files_removed, _checkout) =
+            self.checkout_tree(Some(head_tree), self.commit_tree(orig_head)?, true)?;
+        index.write(Default::default())?;
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: "am --abort".into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(orig_head),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        })?;
+        self.remove_am_state()
+    }
+}
+
+/// Utilities
+impl Repository {
+    fn am_state_dir(&self) -> PathBuf {
+        self.path().join("rebase-apply")
+    }
+
+    fn am_head_id(&self) -> Result<ObjectId, am::Error> {
+        Ok(self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(am::Error::UnbornHead)?
+            .detach())
+    }
+
+    /// Apply and commit all remaining patches, or stop at the first one that doesn't apply.
+    fn am_run(&self) -> Result<am::Outcome, am::Error> {
+        let last = self.am_state_count("last")?;
+        let mut next = self.am_state_count("next")?;
+        let options = am::mail::Options {
+            scissors: self.am_state_flag("scissors")?,
+        };
+        let three_way = self.am_state_flag("threeway")?;
+        while next <= last {
+            let mail = self.read_am_state_file(&format!("{next:04}"))?;
+            let mail = am::mail::parse(&mail, options).map_err(|source| am::Error::Mail { patch: next, source })?;
+            let message = mail.message();
+            let author = gix_actor::Signature {
+                name: mail.author.name.clone(),
+                email: mail.author.email.clone(),
+                time: mail.date.unwrap_or_else(gix_date::Time::now_local_or_utc),
+            };
+            self.write_am_state_file("msg", &message)?;
+            self.write_am_state_file("final-commit", &message)?;
+            self.write_am_state_file("patch", &mail.patch)?;
+            self.write_am_state_file("author-script", &gix_rebase::author_script::encode(author.to_ref()))?;
+
+            let head_id = self.am_head_id()?;
+            let reason = match crate::diff::patch::parse(&mail.patch, Default::default()) {
+                Ok(patches) if patches.is_empty() => Some(Stop::Empty),
+                Ok(patches) => {
+                    let options = apply::Options {
+                        target: apply::Target::Both,
+                        three_way,
+                        reject: false,
+                    };
+                    match self.apply(&patches, options) {
+                        Ok(outcome) if outcome.conflicts.is_empty() => None,
+                        Ok(outcome) => Some(Stop::Conflicts(outcome.conflicts)),
+                        Err(
+                            err @ (apply::Error::AlreadyExists { .. }
+                            | apply::Error::DoesNotExist { .. }
+                            | apply::Error::WorktreeDiffersFromIndex { .. }
+                            | apply::Error::NotAFile { .. }
+                            | apply::Error::HunksDoNotApply { .. }
+                            | apply::Error::BinaryWithoutData { .. }
+                            | apply::Error::Inflate { .. }
+                            | apply::Error::BinaryDelta { .. }),
+                        ) => Some(Stop::DoesNotApply(Box::new(err))),
+                        Err(err) => return Err(err.into()),
+                    }
+                }
+                Err(err) => Some(Stop::MalformedPatch(err)),
+            };
+            if let Some(reason) = reason {
+                return Ok(am::Outcome::Stopped {
+                    patch: next,
+                    subject: mail.subject,
+                    reason,
+                });
+            }
+
+            let index = self.open_index()?;
+            let tree = self.write_tree_from_index(&index)?.detach();
+            if tree != self.commit_tree(head_id)? {
+                self.am_commit(head_id, tree)?;
+            }
+            next += 1;
+            self.write_am_state_file("next", format!("{next}\n").as_bytes())?;
+        }
+        let head_id = self.am_head_id()?;
+        self.remove_am_state()?;
+        Ok(am::Outcome::Finished { head_id })
+    }
+
+    /// Commit `tree` on top of `head_id` with the message and author of the current patch, and move `HEAD` to it.
+    fn am_commit(&self, head_id: ObjectId, tree: ObjectId) -> Result<(), am::Error> {
+        let message: BString = self.read_am_state_file("final-commit")?.into();
+        let author = gix_rebase::author_script::decode(&self.read_am_state_file("author-script")?)?;
+        let committer = self.committer().ok_or(am::Error::CommitterMissing)??;
+        let commit = gix_object::Commit {
+            tree,
+            parents: [head_id].into(),
+            author,
+            committer: committer.into(),
+            encoding: None,
+            message,
+            extra_headers: Default::default(),
+        };
+        let commit_id = self.write_object(&commit)?.detach();
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: crate::reference::log::message("am", commit.message.as_ref(), 1),
+                },
+                expected: PreviousValue::MustExistAndMatch(Target::Peeled(head_id)),
+                new: Target::Peeled(commit_id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        })?;
+        self.write_am_state_file("abort-safety", format!("{commit_id}\n").as_bytes())
+    }
+
+    fn am_state_count(&self, name: &str) -> Result<usize, am::Error> {
+        let path = self.am_state_dir().join(name);
+        if !self.am_state_dir().join("applying").is_file() {
+            return Err(am::Error::NotInProgress);
+        }
+        std::fs::read(&path)
+            .ok()
+            .and_then(|content| content.trim().to_str().ok()?.parse().ok())
+            .ok_or(am::Error::InvalidState { path })
+    }
+
+    fn am_state_flag(&self, name: &str) -> Result<bool, am::Error> {
+        Ok(match self.read_am_state_file(name) {
+            Ok(content) => content.trim() == b"t",
+            Err(am::Error::ReadState { source, .. }) if source.kind() == std::io::ErrorKind::NotFound => false,
+            Err(err) => return Err(err),
+        })
+    }
+
+    fn read_am_state_file(&self, name: &str) -> Result<Vec<u8>, am::Error> {
+        let path = self.am_state_dir().join(name);
+        std::fs::read(&path).map_err(|source| am::Error::ReadState { path, source })
+    }
+
+    fn write_am_state_file(&self, name: &str, content: &[u8]) -> Result<(), am::Error> {
+        let path = self.am_state_dir().join(name);
+        std::fs::write(&path, content).map_err(|source| am::Error::WriteState { path, source })
+    }
+
+    fn remove_am_state(&self) -> Result<(), am::Error> {
+        let dir = self.am_state_dir();
+        std::fs::remove_dir_all(&dir).map_err(|source| am::Error::WriteState { path: dir, source })
+    }
+}
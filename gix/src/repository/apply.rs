@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use gix_diff::patch::{self, Content, Operation};
+use gix_hash::ObjectId;
+use gix_index::entry::Mode;
+
+use crate::{
+    apply,
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    merge, Repository,
+};
+
+/// A file as it is before or after applying a patch.
+#[derive(Clone)]
+struct Entry {
+    data: Vec<u8>,
+    mode: Mode,
+}
+
+/// Apply
+impl Repository {
+    /// Apply `patches`, as obtained with [`diff::patch::parse()`](crate::diff::patch::parse()), to the worktree, the index or both
+    /// as [`options.target`](apply::Options::target) determines, similar to `git apply`.
+    ///
+    /// Hunks are applied where their lines match, even if they moved, see [`diff::patch::apply::text()`](crate::diff::patch::apply::text()),
+    /// and patches are applied in order so that later patches see the changes of earlier patches to the same file.
+    /// Nothing is changed if one of the patches doesn't apply, unless [`options.reject`](apply::Options::reject) is set to apply
+    /// what applies and write the remaining hunks into `.rej` files, or [`options.three_way`](apply::Options::three_way) is set
+    /// to merge the changes of files whose hunks don't apply.
+    ///
+    /// ### Deviation
+    ///
+    /// * Files are read from the worktree without applying filters, but they are written with filters applied.
+    /// * Patches can't be applied in reverse, and whitespace errors are neither detected nor fixed.
+    pub fn apply(&self, patches: &[patch::File], options: apply::Options) -> Result<apply::Outcome, apply::Error> {
+        let _span = gix_trace::coarse!("gix::apply()", options = ?options);
+        let target = match options.target {
+            apply::Target::Worktree if options.three_way => apply::Target::Both,
+            target => target,
+        };
+        let workdir = match (target, options.reject) {
+            (apply::Target::Index, false) => None,
+            _ => Some(self.work_dir().ok_or_else(|| apply::Error::BareRepository {
+                git_dir: self.git_dir().to_owned(),
+            })?),
+        };
+        let index = self.index_or_empty()?;
+
+        // The state of each changed path after applying the patches so far, or `None` if it was removed.
+        let mut changed = HashMap::<BString, Option<Entry>>::new();
+        let mut paths = Vec::<BString>::new();
+        let mut conflicts = Vec::new();
+        let mut rejected = Vec::new();
+        let mut rejects = Vec::<(BString, Vec<u8>)>::new();
+        let read = |rela_path: &BStr, changed: &HashMap<BString, Option<Entry>>| match changed.get(rela_path) {
+            Some(entry) => Ok(entry.clone()),
+            None => self.read_apply_entry(&index, workdir, target, rela_path),
+        };
+        for file in patches {
+            let rela_path = file.path().to_owned();
+            let old = match file.operation {
+                Operation::Addition => None,
+                _ => {
+                    let source = file.old_path.as_ref().unwrap_or(&rela_path);
+                    Some(
+                        read(source.as_bstr(), &changed)?.ok_or_else(|| apply::Error::DoesNotExist {
+                            rela_path: source.clone(),
+                        })?,
+                    )
+                }
+            };
+            if matches!(
+                file.operation,
+                Operation::Addition | Operation::Rename | Operation::Copy
+            ) && read(rela_path.as_bstr(), &changed)?.is_some()
+            {
+                return Err(apply::Error::AlreadyExists { rela_path });
+            }
+
+            let new = if file.operation == Operation::Deletion {
+                None
+            } else {
+                let old_data = old.as_ref().map(|old| old.data.as_slice()).unwrap_or_default();
+                let mode = file
+                    .new_mode
+                    .map(Mode::from)
+                    .or(old.as_ref().map(|old| old.mode))
+                    .unwrap_or(Mode::FILE);
+                let data = match &file.content {
+                    Content::Text(hunks) => {
+                        let outcome = patch::apply::text(old_data, hunks);
+                        let base = if options.three_way && !outcome.is_complete() {
+                            file.old_id
+                                .as_ref()
+                                .and_then(|id| self.find_blob_by_prefix(id.as_bstr()))
+                        } else {
+                            None
+                        };
+                        match base.map(|(id, base)| (id, patch::apply::text(&base, hunks), base)) {
+                            _ if outcome.is_complete() => outcome.data,
+                            Some((base_id, theirs, base)) if theirs.is_complete() => {
+                                let mut merged = Vec::new();
+                                let labels = merge::blob::Labels {
+                                    ours: Some("ours".into()),
+                                    theirs: Some("theirs".into()),
+                                };
+                                if merge::blob::text(&base, old_data, &theirs.data, labels, &mut merged)
+                                    == merge::blob::Resolution::Conflict
+                                {
+                                    let old_mode = old.as_ref().map_or(mode, |old| old.mode);
+                                    conflicts.push(merge::Conflict {
+                                        rela_path: rela_path.clone(),
+                                        kind: merge::ConflictKind::Content,
+                                        ancestor: Some(merge::Version {
+                                            id: base_id,
+                                            mode: old_mode,
+                                        }),
+                                        ours: Some(merge::Version {
+                                            id: self.write_blob(old_data)?.detach(),
+                                            mode: old_mode,
+                                        }),
+                                        theirs: Some(merge::Version {
+                                            id: self.write_blob(&theirs.data)?.detach(),
+                                            mode,
+                                        }),
+                                    });
+                                }
+                                merged
+                            }
+                            _ if options.reject => {
+                                let mut rej = format!(
+                                    "diff a/{} b/{}\t(rejected hunks)\n",
+                                    file.old_path.as_ref().unwrap_or(&rela_path),
+                                    rela_path
+                                )
+                                .into_bytes();
+                                for idx in &outcome.rejected {
+                                    hunks[*idx].write_to(&mut rej).expect("writing to memory never fails");
+                                }
+                                rejects.push((rela_path.clone(), rej));
+                                rejected.push(apply::Rejection {
+                                    rela_path: rela_path.clone(),
+                                    hunks: outcome.rejected,
+                                });
+                                outcome.data
+                            }
+                            _ => {
+                                return Err(apply::Error::HunksDoNotApply {
+                                    rela_path,
+                                    hunks: outcome.rejected,
+                                })
+                            }
+                        }
+                    }
+                    Content::Binary { forward, .. } => {
+                        let inflate_error = |source| apply::Error::Inflate {
+                            rela_path: rela_path.clone(),
+                            source,
+                        };
+                        let mut inflated = vec![0; forward.size as usize];
+                        let (status, _consumed, written) = gix_features::zlib::Inflate::default()
+                            .once(&forward.data, &mut inflated)
+                            .map_err(inflate_error)?;
+                        if status != gix_features::zlib::Status::StreamEnd || written != inflated.len() {
+                            return Err(inflate_error(gix_features::zlib::inflate::Error::Status(status)));
+                        }
+                        match forward.kind {
+                            patch::BinaryKind::Literal => inflated,
+                            patch::BinaryKind::Delta => patch::apply::delta(old_data, &inflated).map_err(|source| {
+                                apply::Error::BinaryDelta {
+                                    rela_path: rela_path.clone(),
+                                    source,
+                                }
+                            })?,
+                        }
+                    }
+                    Content::BinaryWithoutData => {
+                        // Like `git`, use the new version if the patch names it in full and we have it.
+                        let new_id = file
+                            .new_id
+                            .as_ref()
+                            .and_then(|id| ObjectId::from_hex(id).ok())
+                            .filter(|id| self.has_object(id));
+                        match new_id {
+                            Some(id) => self.find_object(id)?.detach().data,
+                            None => return Err(apply::Error::BinaryWithoutData { rela_path }),
+                        }
+                    }
+                };
+                Some(Entry { data, mode })
+            };
+
+            if file.operation == Operation::Rename {
+                let source = file.old_path.clone().expect("renames have an old path");
+                if !paths.contains(&source) {
+                    paths.push(source.clone());
+                }
+                changed.insert(source, None);
+            }
+            if !paths.contains(&rela_path) {
+                paths.push(rela_path.clone());
+            }
+            changed.insert(rela_path, new);
+        }
+
+        let mut written = Vec::with_capacity(paths.len());
+        for path in &paths {
+            let entry = match &changed[path] {
+                Some(entry) => Some((self.write_blob(&entry.data)?.detach(), entry.mode)),
+                None => None,
+            };
+            written.push((path.as_bstr(), entry));
+        }
+        let mut files = gix_index::State::new(self.object_hash());
+        for (path, entry) in &written {
+            if let Some((id, mode)) = entry {
+                files.dangerously_push_entry(Default::default(), *id, gix_index::entry::Flags::empty(), *mode, path);
+            }
+        }
+        files.sort_entries();
+
+        if let Some(workdir) = workdir {
+            if target != apply::Target::Index {
+                for (path, _) in written.iter().filter(|(_, entry)| entry.is_none()) {
+                    let path = workdir.join(gix_path::from_bstr(*path));
+                    match std::fs::remove_file(&path) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(err) => return Err(apply::Error::WriteFile { path, source: err }),
+                    }
+                    if let Some(parent) = path.parent() {
+                        gix_fs::dir::remove::empty_upward_until_boundary(parent, workdir).ok();
+                    }
+                }
+                self.write_apply_files(&mut files)?;
+            }
+            for (mut rela_path, content) in rejects {
+                rela_path.push_str(".rej");
+                let path = workdir.join(gix_path::from_bstr(rela_path.as_bstr()));
+                std::fs::write(&path, content).map_err(|source| apply::Error::WriteFile { path, source })?;
+            }
+        }
+
+        if target != apply::Target::Worktree {
+            let mut new_index = gix_index::State::clone(&index);
+            new_index.remove_entries(|_, path, _| changed.contains_key(path));
+            for entry in files.entries() {
+                new_index.dangerously_push_entry(
+                    entry.stat,
+                    entry.id,
+                    gix_index::entry::Flags::empty(),
+                    entry.mode,
+                    entry.path(&files),
+                );
+            }
+            new_index.sort_entries();
+            conflicts.sort_by(|a: &merge::Conflict, b| a.rela_path.cmp(&b.rela_path));
+            let new_index = merge::Outcome {
+                index: new_index,
+                conflicts: conflicts.clone(),
+            }
+            .into_index_with_conflicts();
+            gix_index::File::from_state(new_index, self.index_path()).write(Default::default())?;
+        }
+
+        Ok(apply::Outcome {
+            paths,
+            conflicts,
+            rejected,
+        })
+    }
+
+    /// Read `rela_path` from where `target` says patches apply to, and return `None` if it doesn't exist.
+    fn read_apply_entry(
+        &self,
+        index: &gix_index::State,
+        workdir: Option<&std::path::Path>,
+        target: apply::Target,
+        rela_path: &BStr,
+    ) -> Result<Option<Entry>, apply::Error> {
+        let not_a_file = || apply::Error::NotAFile {
+            rela_path: rela_path.to_owned(),
+        };
+        let from_index = match target {
+            apply::Target::Worktree => None,
+            apply::Target::Index | apply::Target::Both => match index.entry_by_path(rela_path) {
+                Some(entry) => {
+                    if !matches!(entry.mode, Mode::FILE | Mode::FILE_EXECUTABLE | Mode::SYMLINK) {
+                        return Err(not_a_file());
+                    }
+                    Some(Entry {
+                        data: self.find_object(entry.id)?.detach().data,
+                        mode: entry.mode,
+                    })
+                }
+                None => None,
+            },
+        };
+        if target == apply::Target::Index {
+            return Ok(from_index);
+        }
+
+        let path = workdir
+            .expect("present for all targets involving the worktree")
+            .join(gix_path::from_bstr(rela_path));
+        let read_error = |source| apply::Error::ReadFile {
+            path: path.clone(),
+            source,
+        };
+        let from_worktree = match path.symlink_metadata() {
+            Ok(meta) if meta.is_symlink() => Some(Entry {
+                data: gix_path::into_bstr(std::fs::read_link(&path).map_err(read_error)?)
+                    .into_owned()
+                    .into(),
+                mode: Mode::SYMLINK,
+            }),
+            Ok(meta) if meta.is_file() => Some(Entry {
+                data: std::fs::read(&path).map_err(read_error)?,
+                mode: if gix_fs::is_executable(&meta) {
+                    Mode::FILE_EXECUTABLE
+                } else {
+                    Mode::FILE
+                },
+            }),
+            Ok(_) => return Err(not_a_file()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(read_error(err)),
+        };
+        if target == apply::Target::Both {
+            if from_worktree.as_ref().map(|e| &e.data) != from_index.as_ref().map(|e| &e.data) {
+                return Err(apply::Error::WorktreeDiffersFromIndex {
+                    rela_path: rela_path.to_owned(),
+                });
+            }
+            // The index knows the mode better in case the filesystem doesn't support the executable bit.
+            return Ok(from_index);
+        }
+        Ok(from_worktree)
+    }
+
+    /// Return the id and data of the blob that the abbreviated hexadecimal `id` refers to, if it exists and is unambiguous.
+    fn find_blob_by_prefix(&self, id: &BStr) -> Option<(ObjectId, Vec<u8>)> {
+        let prefix = gix_hash::Prefix::from_hex(id.to_str().ok()?).ok()?;
+        let id = match self.objects.lookup_prefix(prefix, None).ok()?? {
+            Ok(id) => id,
+            Err(()) => return None,
+        };
+        let object = self.find_object(id).ok()?;
+        (object.kind == gix_object::Kind::Blob).then(|| (id, object.detach().data))
+    }
+
+    /// Write all `files` into the worktree, overwriting what's there.
+    fn write_apply_files(&self, files: &mut gix_index::State) -> Result<(), crate::checkout::Error> {
+        let workdir = self.work_dir().expect("checked by caller");
+        let mut opts = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        opts.overwrite_existing = true;
+        gix_worktree_state::checkout(
+            files,
+            workdir,
+            self.objects
+                .clone()
+                .into_arc()
+                .map_err(crate::checkout::Error::OpenArcOdb)?,
+            &gix_features::progress::Discard,
+            &gix_features::progress::Discard,
+            &crate::interrupt::IS_INTERRUPTED,
+            opts,
+        )?;
+        Ok(())
+    }
+}
@@ -11,6 +11,23 @@ pub enum Error {
     ConfigureExcludes(#[from] config::exclude_stack::Error),
 }
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "attributes")]
+pub mod from_tree {
+    use crate::config;
+
+    /// The error returned by [`Repository::attributes_only_from_tree()`](crate::Repository::attributes_only_from_tree()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ConfigureAttributes(#[from] config::attribute_stack::Error),
+        #[error("Could not read the attribute files from the tree")]
+        FindTree(#[from] gix_object::find::existing_object::Error),
+    }
+}
+
 impl Repository {
     /// Configure a file-system cache for accessing git attributes *and* excludes on a per-path basis.
     ///
@@ -92,6 +109,55 @@ impl Repository {
         ))
     }
 
+    /// Like [attributes_only()][Self::attributes_only()], but reads `.gitattributes` files from the tree with `tree_id`
+    /// instead of from an index, similar to `git --attr-source=<tree>`.
+    ///
+    /// This is most useful in bare repositories, for instance to obtain the attributes of the tree of a commit to archive.
+    /// `$GIT_DIR/info/attributes` and global attribute files are still taken into account.
+    #[cfg(feature = "attributes")]
+    pub fn attributes_only_from_tree(
+        &self,
+        tree_id: impl Into<gix_hash::ObjectId>,
+    ) -> Result<AttributeStack<'_>, from_tree::Error> {
+        let case = if self.config.ignore_case {
+            gix_glob::pattern::Case::Fold
+        } else {
+            gix_glob::pattern::Case::Sensitive
+        };
+        let (attributes, buf) = self.config.assemble_attribute_globals(
+            self.git_dir(),
+            gix_worktree::stack::state::attributes::Source::IdMapping,
+            self.options.permissions.attributes,
+        )?;
+        let state = gix_worktree::stack::State::AttributesStack(attributes);
+        let attribute_list = state.id_mappings_from_tree(&tree_id.into(), &self.objects, case)?;
+        Ok(AttributeStack::new(
+            gix_worktree::Stack::new(
+                // this is alright as we don't cause mutation of that directory, it's virtual.
+                self.work_dir().unwrap_or(self.git_dir()),
+                state,
+                case,
+                buf,
+                attribute_list,
+            ),
+            self,
+        ))
+    }
+
+    /// Return a detector to decide whether files are binary for `purpose`, based on their attributes as read from
+    /// `attributes_source` and `index`, or their content, like `git` does.
+    ///
+    /// Use [`binary::Detector::new()`](crate::binary::Detector::new()) to use other attributes, like those of a tree.
+    #[cfg(feature = "blob-diff")]
+    pub fn binary_detector(
+        &self,
+        index: &gix_index::State,
+        attributes_source: gix_worktree::stack::state::attributes::Source,
+        purpose: crate::binary::Purpose,
+    ) -> Result<crate::binary::Detector<'_>, crate::binary::detector::Error> {
+        crate::binary::Detector::new(self.attributes_only(index, attributes_source)?, purpose)
+    }
+
     /// Configure a file-system cache checking if files below the repository are excluded, reading `.gitignore` files from
     /// the specified `source`.
     ///
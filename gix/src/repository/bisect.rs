@@ -0,0 +1,402 @@
+use std::io::Write;
+
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, PreviousValue, RefEdit, RefLog},
+    FullName,
+};
+
+use crate::{
+    bisect,
+    bisect::Mark,
+    bstr::{BStr, BString, ByteSlice},
+    checkout, Repository,
+};
+
+/// Bisect
+impl Repository {
+    /// Start a bisection to find the first commit that introduced a change by binary search, similar to
+    /// `git bisect start [<bad> [<good>...]]`.
+    ///
+    /// `bad` and `good` are revision specifications that resolve to commits with and without the change respectively.
+    /// Neither are required, as commits can also be [marked](Self::bisect_mark()) later.
+    /// The branch or commit `HEAD` points to is remembered to be restored by [`bisect_reset()`](Self::bisect_reset()).
+    ///
+    /// The bookkeeping is the same as the one of `git`, with marks stored in `refs/bisect/*` and the progress logged
+    /// to `BISECT_LOG`, so `git bisect` can take over at any time.
+    ///
+    /// Return what to do next, which is [testing](bisect::Next::Test) the commit that was checked out if both a bad and
+    /// a good commit are known.
+    ///
+    /// ### Deviation
+    ///
+    /// * Only the terms `bad` and `good` are supported.
+    /// * Bisecting can't be limited to commits touching certain paths.
+    /// * Good commits that are not ancestors of the bad commit are not tested for their merge-bases first, but their
+    ///   ancestry is considered good right away.
+    pub fn bisect_start<'a>(
+        &self,
+        bad: Option<&'a BStr>,
+        good: impl IntoIterator<Item = &'a BStr>,
+        options: bisect::Options,
+    ) -> Result<bisect::Next, bisect::Error> {
+        let _span = gix_trace::coarse!("gix::bisect_start()", options = ?options);
+        if let Some(state) = self.state() {
+            return Err(bisect::Error::InProgress { state });
+        }
+        if !options.no_checkout && self.work_dir().is_none() {
+            return Err(bisect::Error::BareRepository {
+                git_dir: self.git_dir().to_owned(),
+            });
+        }
+        let mut head = self.head()?;
+        let head_name = head.referent_name().map(|name| name.shorten().to_owned());
+        let head_id = head
+            .try_peel_to_id_in_place()?
+            .ok_or(bisect::Error::UnbornHead)?
+            .detach();
+        let good: Vec<_> = good.into_iter().collect();
+        let marks = bad
+            .map(|spec| Ok((Mark::Bad, self.bisect_resolve(spec)?)))
+            .into_iter()
+            .chain(good.iter().map(|spec| Ok((Mark::Good, self.bisect_resolve(spec)?))))
+            .collect::<Result<Vec<_>, bisect::Error>>()?;
+
+        let start = head_name.unwrap_or_else(|| head_id.to_string().into());
+        self.write_bisect_file("BISECT_START", format!("{start}\n").as_bytes())?;
+        self.write_bisect_file("BISECT_TERMS", b"bad\ngood\n")?;
+        self.write_bisect_file("BISECT_NAMES", b"\n")?;
+        if options.no_checkout {
+            self.reference("BISECT_HEAD", head_id, PreviousValue::Any, "bisect: start")?;
+        }
+
+        let mut log = Vec::new();
+        for (mark, id) in &marks {
+            self.write_bisect_mark(*mark, *id)?;
+            self.log_bisect_commit(&mut log, mark.as_str(), *id)?;
+        }
+        log.extend_from_slice(b"git bisect start");
+        for arg in options
+            .no_checkout
+            .then(|| "--no-checkout".into())
+            .into_iter()
+            .chain(bad)
+            .chain(good)
+        {
+            log.extend_from_slice(b" '");
+            log.extend_from_slice(&arg.replace("'", "'\\''").replace("!", "'\\!'"));
+            log.push(b'\'');
+        }
+        log.push(b'\n');
+        self.write_bisect_file("BISECT_LOG", &log)?;
+        self.bisect_next()
+    }
+
+    /// Mark `commit` as good, bad or to be skipped, or the commit that is currently tested if `None`,
+    /// similar to `git bisect good|bad|skip [<commit>]`.
+    ///
+    /// Return what to do next, which also checks out the next commit to test.
+    pub fn bisect_mark(&self, mark: Mark, commit: Option<ObjectId>) -> Result<bisect::Next, bisect::Error> {
+        let _span = gix_trace::coarse!("gix::bisect_mark()", mark = ?mark);
+        let state = self.bisect_state()?.ok_or(bisect::Error::NotInProgress)?;
+        let id = match commit {
+            Some(id) => id,
+            None if state.no_checkout => self.find_reference("BISECT_HEAD")?.into_fully_peeled_id()?.detach(),
+            None => self
+                .head()?
+                .try_peel_to_id_in_place()?
+                .ok_or(bisect::Error::UnbornHead)?
+                .detach(),
+        };
+        self.write_bisect_mark(mark, id)?;
+        let mut log = Vec::new();
+        self.log_bisect_commit(&mut log, mark.as_str(), id)?;
+        writeln!(log, "git bisect {} {id}", mark.as_str()).expect("cannot fail when writing to memory");
+        self.append_bisect_log(&log)?;
+        self.bisect_next()
+    }
+
+    /// Determine the next commit to test from the marks of the bisection in progress and check it out,
+    /// similar to `git bisect next`.
+    ///
+    /// The commit to test is the one that splits the commits which may have introduced the change into two parts
+    /// that are as equal in size as possible, with skipped commits never being chosen.
+    /// If the bisection is complete, the outcome is logged to `BISECT_LOG`.
+    pub fn bisect_next(&self) -> Result<bisect::Next, bisect::Error> {
+        let state = self.bisect_state()?.ok_or(bisect::Error::NotInProgress)?;
+        let next = self.bisect_next_commit(&state)?;
+        match &next {
+            bisect::Next::Test { commit, .. } => {
+                if state.no_checkout {
+                    self.reference("BISECT_HEAD", *commit, PreviousValue::Any, "bisect: next")?;
+                } else {
+                    self.checkout(
+                        commit.to_string().as_str(),
+                        checkout::Options {
+                            detach: true,
+                            ..Default::default()
+                        },
+                    )?;
+                }
+            }
+            bisect::Next::Found { first_bad } => {
+                let mut log = Vec::new();
+                self.log_bisect_commit(&mut log, "first bad commit", *first_bad)?;
+                self.append_bisect_log(&log)?;
+            }
+            bisect::Next::OnlySkippedLeft { candidates } => {
+                let mut log = b"# only skipped commits left to test\n".to_vec();
+                for id in candidates {
+                    self.log_bisect_commit(&mut log, "possible first bad commit", *id)?;
+                }
+                self.append_bisect_log(&log)?;
+            }
+            bisect::Next::NeedBad | bisect::Next::NeedGood => {}
+        }
+        Ok(next)
+    }
+
+    /// Drive the bisection in progress by calling `test` with each commit to test, which was checked out before, and mark it
+    /// with the returned [`Mark`] until the bisection is complete, similar to `git bisect run`.
+    ///
+    /// Return the last step, which is the [first bad commit](bisect::Next::Found) unless only skipped commits were left,
+    /// or a bad or good commit is still missing.
+    pub fn bisect_run<E>(
+        &self,
+        mut test: impl FnMut(ObjectId) -> Result<Mark, E>,
+    ) -> Result<bisect::Next, bisect::Error>
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    {
+        let mut next = self.bisect_next()?;
+        while let bisect::Next::Test { commit, .. } = next {
+            let mark = test(commit).map_err(|err| bisect::Error::Test {
+                commit,
+                source: err.into(),
+            })?;
+            next = self.bisect_mark(mark, Some(commit))?;
+        }
+        Ok(next)
+    }
+
+    /// End the bisection in progress, remove all of its state and check out the branch or commit that was checked out
+    /// when it started, similar to `git bisect reset`.
+    pub fn bisect_reset(&self) -> Result<(), bisect::Error> {
+        let _span = gix_trace::coarse!("gix::bisect_reset()");
+        let state = self.bisect_state()?.ok_or(bisect::Error::NotInProgress)?;
+        if !state.no_checkout {
+            self.checkout(state.start.as_bstr(), checkout::Options::default())?;
+        }
+
+        let mut names = Vec::new();
+        for reference in self.references()?.prefixed("refs/bisect/")? {
+            names.push(reference?.detach().name);
+        }
+        if let Some(reference) = self.try_find_reference("BISECT_HEAD")? {
+            names.push(reference.detach().name);
+        }
+        self.edit_references(names.into_iter().map(|name: FullName| RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::Any,
+                log: RefLog::AndReference,
+            },
+            name,
+            deref: false,
+        }))?;
+        for name in [
+            "BISECT_START",
+            "BISECT_LOG",
+            "BISECT_TERMS",
+            "BISECT_NAMES",
+            "BISECT_EXPECTED_REV",
+            "BISECT_ANCESTORS_OK",
+            "BISECT_RUN",
+            "BISECT_FIRST_PARENT",
+        ] {
+            let path = self.path().join(name);
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(bisect::Error::State { path, source: err }),
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the state of the bisection in progress, or `None` if there is none.
+    pub fn bisect_state(&self) -> Result<Option<bisect::State>, bisect::Error> {
+        let path = self.path().join("BISECT_START");
+        let start = match std::fs::read(&path) {
+            Ok(content) => content.trim_end().into(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(bisect::Error::State { path, source: err }),
+        };
+        let mut state = bisect::State {
+            start,
+            bad: None,
+            good: Vec::new(),
+            skip: Vec::new(),
+            no_checkout: self.try_find_reference("BISECT_HEAD")?.is_some(),
+        };
+        for reference in self.references()?.prefixed("refs/bisect/")? {
+            let mut reference = reference?;
+            let id = reference.peel_to_id_in_place()?.detach();
+            let name = reference.name().as_bstr();
+            let name = name.strip_prefix(b"refs/bisect/").unwrap_or(name);
+            if name == b"bad" {
+                state.bad = Some(id);
+            } else if name.starts_with(b"good-") {
+                state.good.push(id);
+            } else if name.starts_with(b"skip-") {
+                state.skip.push(id);
+            }
+        }
+        Ok(Some(state))
+    }
+}
+
+/// Utilities
+impl Repository {
+    fn bisect_resolve(&self, spec: &BStr) -> Result<ObjectId, bisect::Error> {
+        Ok(self
+            .rev_parse_single(spec)?
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .id)
+    }
+
+    /// Compute the commit to test next from the marks in `state`, without changing anything.
+    fn bisect_next_commit(&self, state: &bisect::State) -> Result<bisect::Next, bisect::Error> {
+        let Some(bad) = state.bad else {
+            return Ok(bisect::Next::NeedBad);
+        };
+        if state.good.is_empty() {
+            return Ok(bisect::Next::NeedGood);
+        }
+        let mut good = gix_hashtable::HashSet::default();
+        for info in self.rev_walk(state.good.iter().copied()).all()? {
+            good.insert(info?.id);
+        }
+        if good.contains(&bad) {
+            return Err(bisect::Error::BadIsAncestorOfGood { bad });
+        }
+
+        let mut candidates = Vec::new();
+        let mut parent_ids = Vec::new();
+        for info in self.rev_walk([bad]).selected(move |id| !good.contains(id))? {
+            let info = info?;
+            candidates.push(info.id);
+            parent_ids.push(info.parent_ids);
+        }
+        let index: gix_hashtable::HashMap<_, _> = candidates.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
+        let parents: Vec<Vec<usize>> = parent_ids
+            .iter()
+            .map(|ids| ids.iter().filter_map(|id| index.get(id).copied()).collect())
+            .collect();
+
+        let total = candidates.len();
+        let best = candidates
+            .iter()
+            .zip(reachable_counts(&parents))
+            .filter(|(id, _)| **id != bad && !state.skip.contains(id))
+            .min_by_key(|(_, count)| std::cmp::Reverse((*count).min(total - *count)));
+        Ok(match best {
+            Some((commit, _)) => bisect::Next::Test {
+                commit: *commit,
+                candidates: total,
+            },
+            None if total == 1 => bisect::Next::Found { first_bad: bad },
+            None => bisect::Next::OnlySkippedLeft { candidates },
+        })
+    }
+
+    fn write_bisect_mark(&self, mark: Mark, id: ObjectId) -> Result<(), bisect::Error> {
+        let name = match mark {
+            Mark::Bad => "refs/bisect/bad".to_owned(),
+            Mark::Good | Mark::Skip => format!("refs/bisect/{}-{id}", mark.as_str()),
+        };
+        self.reference(name, id, PreviousValue::Any, format!("bisect: {}", mark.as_str()))?;
+        Ok(())
+    }
+
+    /// Append a comment line like `# <label>: [<id>] <summary>` to `out`.
+    fn log_bisect_commit(&self, out: &mut Vec<u8>, label: &str, id: ObjectId) -> Result<(), bisect::Error> {
+        let commit = self
+            .find_object(id)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let summary: BString = commit.decode()?.message_summary().into_owned();
+        writeln!(out, "# {label}: [{id}] {summary}").expect("cannot fail when writing to memory");
+        Ok(())
+    }
+
+    fn write_bisect_file(&self, name: &str, content: &[u8]) -> Result<(), bisect::Error> {
+        let path = self.path().join(name);
+        std::fs::write(&path, content).map_err(|source| bisect::Error::State { path, source })
+    }
+
+    fn append_bisect_log(&self, content: &[u8]) -> Result<(), bisect::Error> {
+        let path = self.path().join("BISECT_LOG");
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(content))
+            .map_err(|source| bisect::Error::State { path, source })
+    }
+}
+
+/// Return the amount of commits reachable from each commit, including itself, in a graph of commits where `parents` contains
+/// the indices of the parents of each commit.
+fn reachable_counts(parents: &[Vec<usize>]) -> Vec<usize> {
+    // Order commits so that parents come before their children.
+    let mut order = Vec::with_capacity(parents.len());
+    let mut visited = vec![false; parents.len()];
+    let mut stack = Vec::new();
+    for start in 0..parents.len() {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        stack.push((start, 0));
+        while let Some((node, next_parent)) = stack.pop() {
+            match parents[node].get(next_parent) {
+                Some(&parent) => {
+                    stack.push((node, next_parent + 1));
+                    if !visited[parent] {
+                        visited[parent] = true;
+                        stack.push((parent, 0));
+                    }
+                }
+                None => order.push(node),
+            }
+        }
+    }
+
+    let mut counts = vec![0; parents.len()];
+    let mut seen_by = vec![usize::MAX; parents.len()];
+    let mut queue = Vec::new();
+    for node in order {
+        counts[node] = match parents[node].as_slice() {
+            [] => 1,
+            [parent] => counts[*parent] + 1,
+            _ => {
+                // The ancestries of the parents of merges may overlap, so they have to be traversed.
+                let mut count = 0;
+                seen_by[node] = node;
+                queue.push(node);
+                while let Some(current) = queue.pop() {
+                    count += 1;
+                    for &parent in &parents[current] {
+                        if seen_by[parent] != node {
+                            seen_by[parent] = node;
+                            queue.push(parent);
+                        }
+                    }
+                }
+                count
+            }
+        };
+    }
+    counts
+}
@@ -0,0 +1,302 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    blame,
+    blame::{Entry, UnblamedHunk},
+    bstr::{BStr, BString, ByteSlice},
+    Repository,
+};
+
+/// Blame
+impl Repository {
+    /// Attribute each line of the file at `path` in the commit `commit` to the commit that introduced it, similar to `git blame`.
+    ///
+    /// Starting at `commit`, lines are passed on to the parents that have the same line, so they end up with the oldest commit
+    /// that changed them. Lines of merge commits are passed on to the first parent that has them, and if the file is unchanged
+    /// compared to one of the parents, all lines are passed to it. When [renames are followed](blame::Options::rewrites), parents
+    /// are checked for the file at its previous location. In shallow repositories, lines reaching the shallow boundary are
+    /// attributed to the commit at the boundary.
+    ///
+    /// ### Deviation
+    ///
+    /// * Moved and copied lines within or across files are not detected, like `git blame -M` and `-C` would.
+    /// * Files are compared as they are stored, without applying `textconv` drivers.
+    pub fn blame(
+        &self,
+        commit: impl Into<ObjectId>,
+        path: &BStr,
+        options: blame::Options,
+    ) -> Result<blame::Outcome, blame::Error> {
+        let mut entries = Vec::new();
+        let (blob, statistics) = self.blame_inner(commit.into(), path, options, &mut |entry| entries.push(entry))?;
+        Ok(blame::Outcome {
+            entries: blame::coalesce(entries),
+            blob,
+            statistics,
+        })
+    }
+
+    /// Like [`blame()`](Self::blame()), but call `on_entry` with each entry as soon as its lines are attributed, similar
+    /// to `git blame --incremental`.
+    ///
+    /// Entries are not produced in any particular order, and adjacent lines of the same commit may be spread over multiple entries.
+    pub fn blame_incremental(
+        &self,
+        commit: impl Into<ObjectId>,
+        path: &BStr,
+        options: blame::Options,
+        mut on_entry: impl FnMut(Entry),
+    ) -> Result<blame::Statistics, blame::Error> {
+        self.blame_inner(commit.into(), path, options, &mut on_entry)
+            .map(|(_blob, statistics)| statistics)
+    }
+
+    fn blame_inner(
+        &self,
+        commit: ObjectId,
+        path: &BStr,
+        options: blame::Options,
+        on_entry: &mut dyn FnMut(Entry),
+    ) -> Result<(Vec<u8>, blame::Statistics), blame::Error> {
+        let _span = gix_trace::coarse!("gix::blame()", path = ?path);
+        let algorithm = match options.diff_algorithm {
+            Some(algorithm) => algorithm,
+            None => self.config.diff_algorithm()?,
+        };
+        let mut stats = blame::Statistics::default();
+        let mut commits = CommitCache {
+            commits: Default::default(),
+            shallow: self.shallow_commits()?,
+        };
+        let mut buf = Vec::new();
+
+        let commit = self.find_object(commit)?.peel_to_kind(gix_object::Kind::Commit)?.id;
+        let tree = commits.get(self, commit)?.tree;
+        let blob_id = self
+            .blob_at(tree, path, &mut buf)?
+            .ok_or_else(|| blame::Error::FileNotFound {
+                path: path.to_owned(),
+                commit_id: commit,
+            })?;
+        let blob = self.find_object(blob_id)?.detach().data;
+        let line_count = blob.lines_with_terminator().count() as u32;
+
+        let mut ranges = Vec::new();
+        if options.ranges.is_empty() {
+            ranges.push(0..line_count);
+        } else {
+            for range in &options.ranges {
+                let end = range.end.unwrap_or(line_count).min(line_count);
+                if range.start > line_count || range.start > end {
+                    return Err(blame::Error::InvalidLineRange {
+                        range: *range,
+                        line_count,
+                    });
+                }
+                ranges.push(range.start - 1..end);
+            }
+            ranges.sort_by_key(|range| range.start);
+            ranges.dedup_by(|next, previous| {
+                if next.start <= previous.end {
+                    previous.end = previous.end.max(next.end);
+                    true
+                } else {
+                    false
+                }
+            });
+        }
+        let hunks: Vec<_> = ranges
+            .into_iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| UnblamedHunk {
+                start_in_blamed_file: range.start,
+                suspect: range,
+            })
+            .collect();
+
+        let mut queue = Queue::default();
+        queue.push(commits.get(self, commit)?.time, commit, path.to_owned(), hunks);
+        while let Some((suspect, suspect_path, hunks)) = queue.pop() {
+            stats.commits_traversed += 1;
+            let info = commits.get(self, suspect)?.clone();
+            let blob_id = self
+                .blob_at(info.tree, suspect_path.as_ref(), &mut buf)?
+                .ok_or_else(|| blame::Error::FileNotFound {
+                    path: suspect_path.clone(),
+                    commit_id: suspect,
+                })?;
+
+            let mut parents = Vec::with_capacity(info.parents.len());
+            for &parent in &info.parents {
+                let parent_tree = commits.get(self, parent)?.tree;
+                if let Some(parent_blob) = self.blob_at(parent_tree, suspect_path.as_ref(), &mut buf)? {
+                    parents.push((parent, suspect_path.clone(), parent_blob));
+                } else if let Some(rewrites) = options.rewrites {
+                    stats.trees_diffed += 1;
+                    if let Some((source_path, source_blob)) =
+                        self.rename_source(parent_tree, info.tree, suspect_path.as_ref(), rewrites)?
+                    {
+                        parents.push((parent, source_path, source_blob));
+                    }
+                }
+            }
+
+            if let Some((parent, parent_path, _)) = parents.iter().find(|(_, _, parent_blob)| *parent_blob == blob_id) {
+                let time = commits.get(self, *parent)?.time;
+                queue.push(time, *parent, parent_path.clone(), hunks);
+                continue;
+            }
+
+            let mut kept = hunks;
+            let data = self.find_object(blob_id)?.detach().data;
+            let mut first_parent_segments = None;
+            for (parent, parent_path, parent_blob) in parents {
+                if kept.is_empty() {
+                    break;
+                }
+                stats.blobs_diffed += 1;
+                let parent_data = self.find_object(parent_blob)?.detach().data;
+                let segments = blame::segments(algorithm, &parent_data, &data);
+                let (passed, rest) = blame::pass_blame(kept, &segments, false);
+                kept = rest;
+                let time = commits.get(self, parent)?.time;
+                if first_parent_segments.is_none() {
+                    first_parent_segments = Some((parent, parent_path.clone(), time, segments));
+                }
+                queue.push(time, parent, parent_path, passed);
+            }
+            if !kept.is_empty() && options.ignore_revs.contains(&suspect) {
+                if let Some((parent, parent_path, time, segments)) = first_parent_segments {
+                    let (passed, rest) = blame::pass_blame(kept, &segments, true);
+                    kept = rest;
+                    queue.push(time, parent, parent_path, passed);
+                }
+            }
+            for hunk in kept {
+                on_entry(Entry {
+                    start_in_blamed_file: hunk.start_in_blamed_file,
+                    start_in_source_file: hunk.suspect.start,
+                    len: hunk.suspect.end - hunk.suspect.start,
+                    commit_id: suspect,
+                    source_path: suspect_path.clone(),
+                });
+            }
+        }
+        Ok((blob, stats))
+    }
+
+    /// Return the id of the non-tree entry at `path` in `tree`, if there is one.
+    fn blob_at(&self, tree: ObjectId, path: &BStr, buf: &mut Vec<u8>) -> Result<Option<ObjectId>, blame::Error> {
+        Ok(self
+            .find_object(tree)?
+            .try_into_tree()?
+            .lookup_entry(path.split_str("/"), buf)?
+            .filter(|entry| entry.mode().is_blob_or_symlink())
+            .map(|entry| entry.object_id()))
+    }
+
+    /// Return the path and blob id of the file in `old_tree` that was renamed to `path` in `new_tree`, if there is one.
+    fn rename_source(
+        &self,
+        old_tree: ObjectId,
+        new_tree: ObjectId,
+        path: &BStr,
+        rewrites: gix_diff::Rewrites,
+    ) -> Result<Option<(BString, ObjectId)>, blame::Error> {
+        let old_tree = self.find_object(old_tree)?.try_into_tree()?;
+        let new_tree = self.find_object(new_tree)?.try_into_tree()?;
+        let mut source = None;
+        old_tree
+            .changes()?
+            .track_path()
+            .track_rewrites(Some(rewrites))
+            .for_each_to_obtain_tree(&new_tree, |change| {
+                if let crate::object::tree::diff::change::Event::Rewrite {
+                    source_location,
+                    source_id,
+                    copy: false,
+                    ..
+                } = change.event
+                {
+                    if change.location == path {
+                        source = Some((source_location.to_owned(), source_id.detach()));
+                        return Ok::<_, std::convert::Infallible>(crate::object::tree::diff::Action::Cancel);
+                    }
+                }
+                Ok(crate::object::tree::diff::Action::Continue)
+            })?;
+        Ok(source)
+    }
+}
+
+#[derive(Clone)]
+struct CommitInfo {
+    time: gix_date::SecondsSinceUnixEpoch,
+    tree: ObjectId,
+    parents: Vec<ObjectId>,
+}
+
+/// Decoded commits, as each of them is needed multiple times.
+struct CommitCache {
+    commits: HashMap<ObjectId, CommitInfo>,
+    /// The commits at the shallow boundary, which are treated as if they had no parents.
+    shallow: Option<crate::shallow::Commits>,
+}
+
+impl CommitCache {
+    fn get(&mut self, repo: &Repository, id: ObjectId) -> Result<&CommitInfo, blame::Error> {
+        Ok(match self.commits.entry(id) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                let commit = repo
+                    .find_object(id)?
+                    .peel_to_kind(gix_object::Kind::Commit)?
+                    .into_commit();
+                let decoded = commit.decode()?;
+                let is_shallow = matches!(&self.shallow, Some(shallow) if shallow.binary_search(&id).is_ok());
+                entry.insert(CommitInfo {
+                    time: decoded.committer.time.seconds,
+                    tree: decoded.tree(),
+                    parents: if is_shallow {
+                        Vec::new()
+                    } else {
+                        decoded.parents().collect()
+                    },
+                })
+            }
+        })
+    }
+}
+
+/// The commits that may still be blamed for lines, along with the path of the file in them, newest first.
+#[derive(Default)]
+struct Queue {
+    by_time: BinaryHeap<(gix_date::SecondsSinceUnixEpoch, ObjectId, BString)>,
+    hunks: HashMap<(ObjectId, BString), Vec<UnblamedHunk>>,
+}
+
+impl Queue {
+    fn push(&mut self, time: gix_date::SecondsSinceUnixEpoch, id: ObjectId, path: BString, hunks: Vec<UnblamedHunk>) {
+        if hunks.is_empty() {
+            return;
+        }
+        match self.hunks.entry((id, path)) {
+            std::collections::hash_map::Entry::Occupied(mut entry) => entry.get_mut().extend(hunks),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                self.by_time.push((time, id, entry.key().1.clone()));
+                entry.insert(hunks);
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<(ObjectId, BString, Vec<UnblamedHunk>)> {
+        let (_time, id, path) = self.by_time.pop()?;
+        let hunks = self
+            .hunks
+            .remove(&(id, path.clone()))
+            .expect("queued along with the time");
+        Some((id, path, hunks))
+    }
+}
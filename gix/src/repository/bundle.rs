@@ -0,0 +1,171 @@
+use std::{io::Write, sync::atomic::AtomicBool};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bundle::{self, create, verify},
+    config::{cache::util::ApplyLeniency, tree::Pack},
+    rev_list, Repository,
+};
+
+/// Bundles
+impl Repository {
+    /// Write a bundle with `refs` and all objects reachable from them, but not from `hidden`, to `out`,
+    /// like `git bundle create <file> <refs> --not <hidden>`.
+    ///
+    /// Symbolic references are stored with the object their target points to. The parents of the bundled commits that are
+    /// hidden become [prerequisites](bundle::Header::prerequisites), which must be present in repositories that
+    /// use the bundle.
+    ///
+    /// ### Deviation
+    ///
+    /// * The pack is never thin, so objects that could be stored as delta against objects reachable from prerequisites are
+    ///   stored as they are in this repository instead.
+    pub fn create_bundle(
+        &self,
+        mut out: impl Write,
+        refs: impl IntoIterator<Item = gix_ref::FullName>,
+        hidden: impl IntoIterator<Item = impl Into<ObjectId>>,
+        options: bundle::Options,
+    ) -> Result<create::Outcome, create::Error> {
+        let _span = gix_trace::coarse!("gix::create_bundle()", options = ?options);
+        let mut header = bundle::Header {
+            version: if self.object_hash() == gix_hash::Kind::Sha1 {
+                options.version
+            } else {
+                bundle::Version::V3
+            },
+            object_hash: self.object_hash(),
+            capabilities: Vec::new(),
+            prerequisites: Vec::new(),
+            refs: Vec::new(),
+        };
+        for name in refs {
+            let mut reference = self.find_reference(name.as_ref())?;
+            while let Some(target) = reference.follow() {
+                reference = target?;
+            }
+            let id = reference
+                .target()
+                .try_id()
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| create::Error::Unborn {
+                    name: name.as_bstr().to_owned(),
+                })?;
+            header.refs.push(bundle::Ref { id, name });
+        }
+        if header.refs.is_empty() {
+            return Err(create::Error::Empty);
+        }
+
+        let listed = self.rev_list(
+            header.refs.iter().map(|r| r.id),
+            hidden,
+            rev_list::Options {
+                objects: true,
+                boundary: true,
+            },
+        )?;
+        let mut ids = Vec::with_capacity(listed.items.len());
+        for item in listed.items {
+            match item {
+                rev_list::Item::Boundary(id) => {
+                    let commit = self.find_object(id)?;
+                    let comment = gix_object::CommitRef::from_bytes(&commit.data)?
+                        .message_summary()
+                        .into_owned();
+                    header.prerequisites.push(bundle::Prerequisite { id, comment });
+                }
+                rev_list::Item::Commit(id) | rev_list::Item::Object { id, .. } => ids.push(id),
+            }
+        }
+
+        header.write_to(&mut out)?;
+        let num_objects = ids.len();
+        self.write_bundle_pack(ids, &mut out)?;
+        out.flush()?;
+        Ok(create::Outcome { header, num_objects })
+    }
+
+    /// Check that the bundle with `header` can be used in this repository, which is the case if it uses the same
+    /// kind of hash and if all of its prerequisites are present, like `git bundle verify` does.
+    ///
+    /// ### Deviation
+    ///
+    /// * Prerequisites don't have to be reachable from a reference, they merely have to exist as commits.
+    pub fn verify_bundle(&self, header: &bundle::Header) -> Result<(), verify::Error> {
+        if header.object_hash != self.object_hash() {
+            return Err(verify::Error::ObjectHash {
+                actual: header.object_hash,
+                expected: self.object_hash(),
+            });
+        }
+        if let Some((name, _)) = header.capabilities.first() {
+            return Err(verify::Error::UnsupportedCapability { name: name.clone() });
+        }
+        let mut missing = Vec::new();
+        for prerequisite in &header.prerequisites {
+            let is_commit = self
+                .try_find_header(prerequisite.id)?
+                .is_some_and(|header| header.kind() == gix_object::Kind::Commit);
+            if !is_commit {
+                missing.push(prerequisite.id);
+            }
+        }
+        if !missing.is_empty() {
+            return Err(verify::Error::MissingPrerequisites { ids: missing });
+        }
+        Ok(())
+    }
+}
+
+/// Utilities
+impl Repository {
+    /// Write a pack with all objects with `ids` to `out`, copying packed objects and their deltas as they are.
+    fn write_bundle_pack(&self, ids: Vec<ObjectId>, out: &mut dyn Write) -> Result<(), create::Error> {
+        let _span = gix_trace::detail!("gix::write_bundle_pack()", objects = ids.len());
+        let should_interrupt = AtomicBool::new(false);
+        let thread_limit = self
+            .config
+            .resolved
+            .integer_filter("pack", None, Pack::THREADS.name, &mut self.filter_config_section())
+            .map(|threads| Pack::THREADS.try_into_usize(threads))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?;
+        let mut handle = self.objects.clone().into_arc()?;
+        handle.prevent_pack_unload();
+        handle.ignore_replacements = true;
+        let num_objects = ids.len();
+        let (counts, _) = gix_pack::data::output::count::objects_unthreaded(
+            &handle,
+            &mut ids.into_iter().map(Ok),
+            &gix_features::progress::Discard,
+            &should_interrupt,
+            gix_pack::data::output::count::objects::ObjectExpansion::AsIs,
+        )?;
+        let entries = gix_features::parallel::InOrderIter::from(gix_pack::data::output::entry::iter_from_counts(
+            counts,
+            handle,
+            Box::new(gix_features::progress::Discard),
+            gix_pack::data::output::entry::iter_from_counts::Options {
+                thread_limit,
+                mode: gix_pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                chunk_size: 1000,
+                version: gix_pack::data::Version::V2,
+                compression_level: self.config.pack_compression()?.level(),
+                zlib_backend: self.config.zlib_backend()?,
+            },
+        ));
+        for written in gix_pack::data::output::bytes::FromEntriesIter::new(
+            entries,
+            out,
+            num_objects as u32,
+            gix_pack::data::Version::V2,
+            self.object_hash(),
+        ) {
+            written?;
+        }
+        Ok(())
+    }
+}
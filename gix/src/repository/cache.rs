@@ -27,4 +27,28 @@ impl crate::Repository {
             self.object_cache_size(bytes)
         }
     }
+
+    /// Sets the amount of space used at most for caching delta-base objects while decoding objects from packs, to `Some(bytes)`,
+    /// or `None` to deactivate it entirely.
+    ///
+    /// By default, it is configured by `core.deltaBaseCacheLimit`, or a small fixed-size cache is used if unset.
+    /// The setting only affects this handle, while [`open::Options::pack_cache_size()`](crate::open::Options::pack_cache_size())
+    /// affects all handles of a repository.
+    #[cfg(feature = "pack-cache-lru-dynamic")]
+    pub fn pack_cache_size(&mut self, bytes: impl Into<Option<usize>>) {
+        match bytes.into() {
+            Some(0) | None => self.objects.unset_pack_cache(),
+            Some(bytes) => self.objects.set_pack_cache(move || -> Box<gix_odb::cache::PackCache> {
+                Box::new(gix_pack::cache::lru::MemoryCappedHashmap::new(bytes))
+            }),
+        }
+    }
+
+    /// Set a pack cache of size `bytes` if none is set.
+    #[cfg(feature = "pack-cache-lru-dynamic")]
+    pub fn pack_cache_size_if_unset(&mut self, bytes: usize) {
+        if !self.objects.has_pack_cache() {
+            self.pack_cache_size(bytes)
+        }
+    }
 }
@@ -0,0 +1,34 @@
+use std::collections::VecDeque;
+
+use crate::{bstr::BString, cat_file, Repository};
+
+/// Batched object access
+impl Repository {
+    /// Return an iterator over the objects named by `inputs`, which are revision specifications like hexadecimal object ids
+    /// or `HEAD:path`, in input order, like `git cat-file --batch` or `git cat-file --batch-check` depending on
+    /// [`options.mode`](cat_file::Options::mode).
+    ///
+    /// Inputs are read ahead in windows of [`options.window`](cat_file::Options::window) items, which are looked up in the order
+    /// in which they are stored in packs to make the best use of caches and the locality of their data, similar to
+    /// `git cat-file --batch-all-objects --unordered`, but without changing the order of the output.
+    /// Inputs that can't be resolved to an existing object, for instance because they are ambiguous, are returned as
+    /// [missing](cat_file::Item::Missing).
+    pub fn cat_file_batch<I>(
+        &self,
+        inputs: impl IntoIterator<IntoIter = I>,
+        options: cat_file::Options,
+    ) -> cat_file::Batch<'_, I>
+    where
+        I: Iterator<Item = BString>,
+    {
+        let mut objects = self.objects.clone();
+        objects.prevent_pack_unload();
+        cat_file::Batch {
+            repo: self,
+            objects,
+            inputs: inputs.into_iter(),
+            options,
+            items: VecDeque::new(),
+        }
+    }
+}
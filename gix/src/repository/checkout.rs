@@ -0,0 +1,393 @@
+use std::collections::BTreeMap;
+
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    checkout,
+    checkout::{Conflict, ConflictKind},
+    Repository,
+};
+
+/// Checkout
+impl Repository {
+    /// Switch the worktree, the index and `HEAD` to `target`, which is either the short name of a local branch like `main`,
+    /// or any revision specification that resolves to a commit, like `HEAD~2` or `v1.0`.
+    ///
+    /// If `target` names a local branch, `HEAD` will be attached to it, otherwise `HEAD` is detached and points to the commit
+    /// directly. Use [`options.detach`](checkout::Options::detach) to detach `HEAD` even if a branch is named.
+    ///
+    /// Only paths that differ between the tree of the current `HEAD` and the target tree are touched, and local changes to paths
+    /// that are not affected by the switch are carried over. If a changed path has local modifications in the index or the worktree,
+    /// or if an untracked file is in the way, the operation fails with [`Error::Conflicts`](checkout::Error::Conflicts) before
+    /// anything was changed, unless [`options.force`](checkout::Options::force) is set. In that case, the index and worktree are
+    /// made to match the target tree entirely, similar to `git checkout --force`.
+    ///
+    /// A reflog entry like `checkout: moving from <old> to <target>` is written for `HEAD`.
+    ///
+    /// ### Deviation
+    ///
+    /// * No reflog entry is written if `HEAD` already pointed to the target commit, even if the branch changes.
+    /// * Untracked files are only detected if they are exactly at the location of a file that is about to be written,
+    ///   not if they are a leading path of it.
+    pub fn checkout<'a>(
+        &self,
+        target: impl Into<&'a BStr>,
+        options: checkout::Options,
+    ) -> Result<checkout::Outcome, checkout::Error> {
+        let _span = gix_trace::coarse!("gix::checkout()", options = ?options);
+        let target = target.into();
+        let branch = if options.detach {
+            None
+        } else {
+            match FullName::try_from(format!("refs/heads/{target}")) {
+                Ok(name) => self.try_find_reference(&name)?.map(|_| name),
+                Err(_) => None,
+            }
+        };
+        let new_commit_id = match &branch {
+            Some(name) => self.find_reference(name)?.into_fully_peeled_id()?.detach(),
+            None => self.rev_parse_single(target)?.detach(),
+        };
+        let new_commit = self
+            .find_object(new_commit_id)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let new_commit_id = new_commit.id;
+        let new_tree = new_commit.tree_id()?.detach();
+
+        let mut head = self.head()?;
+        let previous_head_name = head.referent_name().map(ToOwned::to_owned);
+        let previous_head_id = head.try_peel_to_id_in_place()?.map(crate::Id::detach);
+        let previous_tree = match previous_head_id {
+            Some(id) => Some(
+                self.find_object(id)?
+                    .peel_to_kind(gix_object::Kind::Commit)?
+                    .into_commit()
+                    .tree_id()?
+                    .detach(),
+            ),
+            None => None,
+        };
+
+        let (mut index, files_removed, checkout) = self.checkout_tree(previous_tree, new_tree, options.force)?;
+        index.write(Default::default())?;
+
+        let from = match (&previous_head_name, previous_head_id) {
+            (Some(name), _) => name.shorten().to_owned(),
+            (None, Some(id)) => id.to_hex().to_string().into(),
+            (None, None) => "HEAD".into(),
+        };
+        let message: BString = format!("checkout: moving from {from} to {target}").into();
+        self.set_head_after_checkout(branch.clone(), new_commit_id, message)?;
+
+        Ok(checkout::Outcome {
+            previous_head_id,
+            head_id: new_commit_id,
+            head_name: branch,
+            files_removed,
+            checkout,
+        })
+    }
+
+    /// Point `HEAD` to `branch` (or detach it if `None`) and record the transition to `new_commit_id` in the reflog of `HEAD`
+    /// using `message`.
+    pub(crate) fn set_head_after_checkout(
+        &self,
+        branch: Option<FullName>,
+        new_commit_id: ObjectId,
+        message: BString,
+    ) -> Result<(), crate::reference::edit::Error> {
+        let head: FullName = "HEAD".try_into().expect("valid");
+        let log = LogChange {
+            mode: RefLog::AndReference,
+            force_create_reflog: false,
+            message,
+        };
+        match branch {
+            Some(branch) => {
+                // Write the reflog first while `HEAD` still points to the previous commit, then re-attach it
+                // as symbolic ref changes don't write reflogs.
+                self.edit_reference(RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            mode: RefLog::Only,
+                            ..log.clone()
+                        },
+                        expected: PreviousValue::Any,
+                        new: Target::Peeled(new_commit_id),
+                    },
+                    name: head.clone(),
+                    deref: false,
+                })?;
+                self.edit_reference(RefEdit {
+                    change: Change::Update {
+                        log,
+                        expected: PreviousValue::Any,
+                        new: Target::Symbolic(branch),
+                    },
+                    name: head,
+                    deref: false,
+                })?;
+            }
+            None => {
+                self.edit_reference(RefEdit {
+                    change: Change::Update {
+                        log,
+                        expected: PreviousValue::Any,
+                        new: Target::Peeled(new_commit_id),
+                    },
+                    name: head,
+                    deref: false,
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Transition the index and the worktree from `current_tree` (or nothing if `None`) to `new_tree`, writing only the changed
+    /// paths to the worktree, while carrying over local changes of paths that don't change between both trees.
+    ///
+    /// If `force` is `true`, index and worktree will be made to match `new_tree` exactly, discarding all local changes, but
+    /// only writing files that actually differ.
+    ///
+    /// Return the new index (which still has to be written), the amount of files deleted from the worktree and the outcome of
+    /// writing files to the worktree.
+    pub(crate) fn checkout_tree(
+        &self,
+        current_tree: Option<ObjectId>,
+        new_tree: ObjectId,
+        force: bool,
+    ) -> Result<(gix_index::File, usize, gix_worktree_state::checkout::Outcome), checkout::Error> {
+        let target_index = self.index_from_tree(&new_tree)?;
+        self.checkout_index(current_tree, &target_index, force)
+    }
+
+    /// Like [`checkout_tree()`](Self::checkout_tree()), but transition to the entries of `target_index`, which must not have
+    /// unmerged entries, instead of a tree.
+    pub(crate) fn checkout_index(
+        &self,
+        current_tree: Option<ObjectId>,
+        target_index: &gix_index::State,
+        force: bool,
+    ) -> Result<(gix_index::File, usize, gix_worktree_state::checkout::Outcome), checkout::Error> {
+        let workdir = self.work_dir().ok_or_else(|| checkout::Error::BareRepository {
+            git_dir: self.git_dir().to_owned(),
+        })?;
+        let head_index = match current_tree {
+            Some(tree) => self.index_from_tree(&tree)?.into(),
+            None => gix_index::State::new(self.object_hash()),
+        };
+        let index = self.index_or_empty()?;
+
+        #[derive(Default, Clone, Copy)]
+        struct Sides<'a> {
+            head: Option<&'a gix_index::Entry>,
+            index: Option<&'a gix_index::Entry>,
+            target: Option<&'a gix_index::Entry>,
+            unmerged: bool,
+        }
+        let mut paths = BTreeMap::<&BStr, Sides<'_>>::new();
+        for (entry, path) in head_index.entries().iter().map(|e| (e, e.path(&head_index))) {
+            paths.entry(path).or_default().head = Some(entry);
+        }
+        for (entry, path) in index.entries().iter().map(|e| (e, e.path(&index))) {
+            let sides = paths.entry(path).or_default();
+            if entry.stage() == gix_index::entry::Stage::Unconflicted {
+                sides.index = Some(entry);
+            } else {
+                sides.unmerged = true;
+            }
+        }
+        for (entry, path) in target_index.entries().iter().map(|e| (e, e.path(target_index))) {
+            paths.entry(path).or_default().target = Some(entry);
+        }
+
+        fn same(a: Option<&gix_index::Entry>, b: Option<&gix_index::Entry>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => a.id == b.id && a.mode == b.mode,
+                (None, None) => true,
+                _ => false,
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        let mut maybe_modified = gix_index::State::new(self.object_hash());
+        let mut to_remove = Vec::new();
+        // The entries of the new index, along with a flag to indicate if they have to be written to the worktree.
+        let mut entries = Vec::<(&BStr, &gix_index::Entry, bool)>::new();
+        for (path, sides) in &paths {
+            if force {
+                match (sides.index, sides.target) {
+                    (Some(current), Some(target)) if !sides.unmerged && same(Some(current), Some(target)) => {
+                        maybe_modified.dangerously_push_entry(
+                            current.stat,
+                            current.id,
+                            current.flags,
+                            current.mode,
+                            path,
+                        );
+                        entries.push((path, current, false));
+                    }
+                    (_, Some(target)) => entries.push((path, target, true)),
+                    (current, None) => {
+                        if let Some(current) = current.or(sides.head) {
+                            to_remove.push((path.to_owned(), current.mode));
+                        }
+                    }
+                }
+                continue;
+            }
+            if sides.unmerged {
+                conflicts.push(Conflict {
+                    rela_path: path.to_owned().into(),
+                    kind: ConflictKind::Unmerged,
+                });
+                continue;
+            }
+            if same(sides.head, sides.target) || same(sides.index, sides.target) {
+                if let Some(current) = sides.index {
+                    entries.push((path, current, false));
+                }
+                continue;
+            }
+            if !same(sides.index, sides.head) {
+                conflicts.push(Conflict {
+                    rela_path: path.to_owned().into(),
+                    kind: ConflictKind::StagedChange,
+                });
+                continue;
+            }
+            match sides.index {
+                Some(current) => {
+                    maybe_modified.dangerously_push_entry(current.stat, current.id, current.flags, current.mode, path);
+                    if sides.target.is_none() {
+                        to_remove.push((path.to_owned(), current.mode));
+                    }
+                }
+                None => {
+                    if workdir.join(gix_path::from_bstr(*path)).symlink_metadata().is_ok() {
+                        conflicts.push(Conflict {
+                            rela_path: path.to_owned().into(),
+                            kind: ConflictKind::Untracked,
+                        });
+                        continue;
+                    }
+                }
+            }
+            if let Some(target) = sides.target {
+                entries.push((path, target, true));
+            }
+        }
+
+        if !maybe_modified.entries().is_empty() {
+            let iter = self
+                .status(gix_features::progress::Discard)?
+                .index(gix_index::File::from_state(maybe_modified, self.index_path()).into())
+                .index_worktree_rewrites(None)
+                .index_worktree_submodules(crate::status::Submodule::Given {
+                    ignore: crate::submodule::config::Ignore::All,
+                    check_dirty: false,
+                })
+                .index_worktree_options_mut(|opts| {
+                    opts.dirwalk_options = None;
+                })
+                .into_index_worktree_iter(Vec::new())?;
+            for item in iter {
+                let item = item?;
+                let Some(summary) = item.summary() else { continue };
+                let crate::status::index_worktree::iter::Item::Modification { rela_path, .. } = item else {
+                    continue;
+                };
+                if force {
+                    // Whatever the local change, the worktree has to be restored.
+                    if let Ok(idx) = entries.binary_search_by(|(path, _, _)| (*path).cmp(rela_path.as_bstr())) {
+                        entries[idx].2 = true;
+                    }
+                } else if summary != gix_status::index_as_worktree_with_renames::Summary::Removed {
+                    conflicts.push(Conflict {
+                        rela_path,
+                        kind: ConflictKind::LocalModification,
+                    });
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            conflicts.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+            return Err(checkout::Error::Conflicts { conflicts });
+        }
+
+        let mut new_state = gix_index::State::new(self.object_hash());
+        let mut skip_worktree_was_set = Vec::with_capacity(entries.len());
+        for (path, entry, write) in entries {
+            let mut flags = entry.flags;
+            skip_worktree_was_set.push(flags.contains(gix_index::entry::Flags::SKIP_WORKTREE));
+            if !write {
+                flags.insert(gix_index::entry::Flags::SKIP_WORKTREE);
+            }
+            new_state.dangerously_push_entry(
+                if write { Default::default() } else { entry.stat },
+                entry.id,
+                flags,
+                entry.mode,
+                path,
+            );
+        }
+
+        let mut files_removed = 0;
+        for (path, mode) in &to_remove {
+            if *mode == gix_index::entry::Mode::COMMIT {
+                continue;
+            }
+            let path = workdir.join(gix_path::from_bstr(path.as_bstr()));
+            if has_symlink_leading_path(workdir, &path) {
+                continue;
+            }
+            match std::fs::remove_file(&path) {
+                Ok(()) => files_removed += 1,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(checkout::Error::RemoveFile { path, source: err }),
+            }
+            if let Some(parent) = path.parent() {
+                gix_fs::dir::remove::empty_upward_until_boundary(parent, workdir).ok();
+            }
+        }
+
+        let mut opts = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        opts.overwrite_existing = true;
+        let outcome = gix_worktree_state::checkout(
+            &mut new_state,
+            workdir,
+            self.objects.clone().into_arc().map_err(checkout::Error::OpenArcOdb)?,
+            &gix_features::progress::Discard,
+            &gix_features::progress::Discard,
+            &crate::interrupt::IS_INTERRUPTED,
+            opts,
+        )?;
+        for (entry, skip_worktree_was_set) in new_state.entries_mut().iter_mut().zip(skip_worktree_was_set) {
+            entry
+                .flags
+                .set(gix_index::entry::Flags::SKIP_WORKTREE, skip_worktree_was_set);
+        }
+        Ok((
+            gix_index::File::from_state(new_state, self.index_path()),
+            files_removed,
+            outcome,
+        ))
+    }
+}
+
+/// Return `true` if any directory between `root` (exclusive) and `path` (exclusive) is a symlink, which means
+/// we must not follow it to avoid changing files outside of the worktree.
+fn has_symlink_leading_path(root: &std::path::Path, path: &std::path::Path) -> bool {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| *dir != root)
+        .any(|dir| dir.symlink_metadata().map_or(false, |m| m.file_type().is_symlink()))
+}
@@ -0,0 +1,256 @@
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    bstr::{BStr, ByteVec},
+    cherry_pick, Repository,
+};
+
+/// Cherry-pick and revert
+impl Repository {
+    /// Apply the changes introduced by the commit that `commit` resolves to onto `HEAD`, and commit them with the original
+    /// author and message, similar to `git cherry-pick`.
+    ///
+    /// The changes are merged with [`merge_trees()`](Self::merge_trees()) using the commit's parent as common ancestor.
+    /// For merge commits, the parent to compute the changes against has to be chosen with
+    /// [`options.mainline`](cherry_pick::Options::mainline).
+    ///
+    /// If there are conflicts, no commit is created. Instead, the index receives the unmerged stages of all conflicting paths,
+    /// conflict markers are written to the worktree, and `CHERRY_PICK_HEAD` as well as `MERGE_MSG` are written so the operation
+    /// can be concluded by committing the resolved changes.
+    /// With [`options.no_commit`](cherry_pick::Options::no_commit), the changes are only applied to the index and the worktree.
+    ///
+    /// Local changes are carried over, but the operation fails without making changes if any affected path has local changes.
+    pub fn cherry_pick<'a>(
+        &self,
+        commit: impl Into<&'a BStr>,
+        options: cherry_pick::Options,
+    ) -> Result<cherry_pick::Outcome, cherry_pick::Error> {
+        let _span = gix_trace::coarse!("gix::cherry_pick()", options = ?options);
+        self.apply_commit(self.resolve_commit(commit.into())?, options, Operation::CherryPick)
+    }
+
+    /// Apply the reverse of the changes introduced by the commit that `commit` resolves to onto `HEAD` and commit them
+    /// with a message like `Revert "<subject>"`, similar to `git revert`.
+    ///
+    /// It works just like [`cherry_pick()`](Self::cherry_pick()), but the commit's tree acts as common ancestor to merge
+    /// the tree of its (mainline) parent, and `REVERT_HEAD` is written in case of conflicts.
+    pub fn revert<'a>(
+        &self,
+        commit: impl Into<&'a BStr>,
+        options: cherry_pick::Options,
+    ) -> Result<cherry_pick::Outcome, cherry_pick::Error> {
+        let _span = gix_trace::coarse!("gix::revert()", options = ?options);
+        self.apply_commit(self.resolve_commit(commit.into())?, options, Operation::Revert)
+    }
+
+    fn resolve_commit(&self, spec: &BStr) -> Result<ObjectId, cherry_pick::Error> {
+        Ok(self
+            .rev_parse_single(spec)?
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .id)
+    }
+
+    /// Apply `commit_id` onto `HEAD` as `operation` describes it, which is the shared implementation of cherry-picks,
+    /// reverts and the picks of a rebase.
+    pub(crate) fn apply_commit(
+        &self,
+        commit_id: ObjectId,
+        options: cherry_pick::Options,
+        operation: Operation,
+    ) -> Result<cherry_pick::Outcome, cherry_pick::Error> {
+        if self.work_dir().is_none() {
+            return Err(cherry_pick::Error::BareRepository {
+                git_dir: self.git_dir().to_owned(),
+            });
+        }
+        if operation != Operation::RebasePick {
+            if let Some(state) = self.state() {
+                return Err(cherry_pick::Error::InProgress { state });
+            }
+        }
+        let commit = self
+            .find_object(commit_id)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let (commit, subject) = {
+            let commit = commit.decode()?;
+            (
+                gix_object::Commit::from(commit.clone()),
+                commit.message_summary().into_owned(),
+            )
+        };
+        let parent_tree = match (commit.parents.len(), options.mainline) {
+            (0, None) => ObjectId::empty_tree(self.object_hash()),
+            (1, None) => self.commit_tree(commit.parents[0])?,
+            (_, None) => return Err(cherry_pick::Error::MissingMainline { commit: commit_id }),
+            (parent_count, Some(mainline)) if parent_count < 2 || mainline == 0 || mainline > parent_count => {
+                return Err(cherry_pick::Error::InvalidMainline {
+                    commit: commit_id,
+                    mainline,
+                    parent_count,
+                })
+            }
+            (_, Some(mainline)) => self.commit_tree(commit.parents[mainline - 1])?,
+        };
+
+        let head_id = self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(cherry_pick::Error::UnbornHead)?
+            .detach();
+        let head_tree = self.commit_tree(head_id)?;
+        if !options.no_commit && !self.index_matches_tree(&*self.index_or_empty()?, head_tree)? {
+            return Err(cherry_pick::Error::DirtyIndex);
+        }
+
+        let short_id = self.find_object(commit_id)?.id().shorten_or_id();
+        let (ancestor, theirs, their_label, message, ref_name) = match operation {
+            Operation::CherryPick | Operation::RebasePick => (
+                parent_tree,
+                commit.tree,
+                format!("{short_id} ({subject})"),
+                commit.message.clone(),
+                (operation == Operation::CherryPick).then_some("CHERRY_PICK_HEAD"),
+            ),
+            Operation::Revert => {
+                let mut message = format!("Revert \"{subject}\"\n\nThis reverts commit {commit_id}");
+                if let Some(mainline) = options.mainline {
+                    message.push_str(&format!(
+                        ", reversing\nchanges made to {}",
+                        commit.parents[mainline - 1]
+                    ));
+                }
+                message.push_str(".\n");
+                (
+                    commit.tree,
+                    parent_tree,
+                    format!("parent of {short_id} ({subject})"),
+                    message.into(),
+                    Some("REVERT_HEAD"),
+                )
+            }
+        };
+        let merge = self.merge_trees(
+            ancestor,
+            head_tree,
+            theirs,
+            crate::merge::blob::Labels {
+                ours: Some("HEAD".into()),
+                theirs: Some(their_label.as_str().into()),
+            },
+        )?;
+
+        let (index, _files_removed, _checkout) = self.checkout_index(Some(head_tree), &merge.index, false)?;
+        let conflicts = merge.conflicts.clone();
+        let mut index = if conflicts.is_empty() {
+            index
+        } else {
+            let merge = crate::merge::Outcome {
+                index: index.into(),
+                conflicts: merge.conflicts,
+            };
+            gix_index::File::from_state(merge.into_index_with_conflicts(), self.index_path())
+        };
+        index.write(Default::default())?;
+
+        if options.no_commit || !conflicts.is_empty() {
+            let mut merge_msg = message;
+            if !conflicts.is_empty() {
+                merge_msg.push_str("\n# Conflicts:\n");
+                for conflict in &conflicts {
+                    merge_msg.push_str("#\t");
+                    merge_msg.push_str(&conflict.rela_path);
+                    merge_msg.push(b'\n');
+                }
+            }
+            self.write_state_file("MERGE_MSG", &merge_msg)?;
+            if let Some(ref_name) = ref_name.filter(|_| !options.no_commit) {
+                self.write_state_file(ref_name, format!("{commit_id}\n").as_bytes())?;
+            }
+            return Ok(cherry_pick::Outcome {
+                commit_id: None,
+                conflicts,
+            });
+        }
+
+        let tree = self.write_tree_from_index(&index)?.detach();
+        if tree == head_tree && !options.allow_empty {
+            return Err(cherry_pick::Error::Empty { commit: commit_id });
+        }
+        let committer = self.committer().ok_or(cherry_pick::Error::CommitterMissing)??;
+        let author = match operation {
+            Operation::CherryPick | Operation::RebasePick => commit.author.clone(),
+            Operation::Revert => committer.into(),
+        };
+        let new_commit = gix_object::Commit {
+            tree,
+            parents: [head_id].into(),
+            author,
+            committer: committer.into(),
+            encoding: commit.encoding.clone().filter(|_| operation != Operation::Revert),
+            message,
+            extra_headers: Default::default(),
+        };
+        let new_commit_id = self.write_object(&new_commit)?.detach();
+        let log_message = crate::reference::log::message(
+            operation.as_str(),
+            new_commit.message.as_ref(),
+            new_commit.parents.len(),
+        );
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: log_message,
+                },
+                expected: PreviousValue::MustExistAndMatch(Target::Peeled(head_id)),
+                new: Target::Peeled(new_commit_id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        })?;
+        Ok(cherry_pick::Outcome {
+            commit_id: Some(new_commit_id),
+            conflicts,
+        })
+    }
+
+    pub(crate) fn commit_tree(&self, commit: ObjectId) -> Result<ObjectId, cherry_pick::Error> {
+        Ok(self
+            .find_object(commit)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit()
+            .tree_id()?
+            .detach())
+    }
+
+    pub(crate) fn write_state_file(&self, name: &str, content: &[u8]) -> Result<(), cherry_pick::Error> {
+        let path = self.path().join(name);
+        std::fs::write(&path, content).map_err(|source| cherry_pick::Error::WriteState { path, source })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Operation {
+    CherryPick,
+    Revert,
+    /// A pick as part of a rebase, which may happen while the rebase is in progress and doesn't leave `CHERRY_PICK_HEAD`
+    /// behind on conflicts.
+    RebasePick,
+}
+
+impl Operation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operation::CherryPick => "cherry-pick",
+            Operation::Revert => "revert",
+            Operation::RebasePick => "rebase (pick)",
+        }
+    }
+}
@@ -22,6 +22,90 @@ impl crate::Repository {
         }
     }
 
+    /// Apply `values` like `core.abbrev=10` or `user.name=me` on top of the current configuration, similar to `git -c`, and
+    /// return the repository with the new configuration.
+    ///
+    /// Unlike changes made with [`config_snapshot_mut()`](Self::config_snapshot_mut()), these values are kept
+    /// when [reloading the configuration](Self::reload_config()) and are applied to clones as well, but just like them
+    /// they are never written to disk.
+    pub fn with_config_overrides(
+        mut self,
+        values: impl IntoIterator<Item = impl Into<crate::bstr::BString>>,
+    ) -> Result<Self, config::Error> {
+        let values: Vec<crate::bstr::BString> = values.into_iter().map(Into::into).collect();
+        let mut snapshot = self.config_snapshot_mut();
+        snapshot
+            .append_config(values.iter(), gix_config::Source::Api)
+            .map_err(|err| config::Error::ConfigOverrides {
+                err,
+                source: gix_config::Source::Api,
+            })?;
+        snapshot.commit()?;
+        self.options.api_config_overrides.extend(values);
+        Ok(self)
+    }
+
+    /// Return `true` if one of the configuration files that were read when opening the repository, or that would have been read
+    /// if it existed, was changed, added or removed since, as determined by its modification time and size.
+    ///
+    /// Use [`reload_config()`](Self::reload_config()) to bring the configuration up to date.
+    pub fn is_config_outdated(&self) -> bool {
+        self.config
+            .files
+            .iter()
+            .any(|(path, state)| config::cache::FileState::at(path) != *state)
+    }
+
+    /// Read the configuration from disk just like it was when opening the repository, and refresh all values and caches
+    /// that depend on it, which is useful for long-running processes that don't want to reopen the repository.
+    ///
+    /// Note that none of the new values are applied if one of them is invalid, and that changes made
+    /// with [`config_snapshot_mut()`](Self::config_snapshot_mut()) are lost.
+    /// Values that determine the layout of the repository, like `core.bare` or the object hash, aren't changed either.
+    pub fn reload_config(&mut self) -> Result<(), config::Error> {
+        let _span = gix_trace::coarse!("gix::reload_config()");
+        let options = &self.options;
+        let stage_one = config::cache::StageOne::new(
+            self.common_dir(),
+            self.git_dir(),
+            self.git_dir_trust(),
+            options.lossy_config,
+            options.lenient_config,
+        )?;
+        let mut refs = self.refs.clone();
+        refs.namespace = None;
+        let head = refs.find("HEAD").ok();
+        let git_install_dir = crate::path::install_dir().ok();
+        let home = gix_path::env::home_dir().and_then(|home| options.permissions.env.home.check_opt(home));
+        let config::Cache { resolved, files, .. } = config::Cache::from_stage_one(
+            stage_one,
+            self.common_dir(),
+            head.as_ref().and_then(|head| head.target.try_name()),
+            self.filter_config_section(),
+            git_install_dir.as_deref(),
+            home.as_deref(),
+            options.permissions.env,
+            options.permissions.attributes,
+            options.permissions.config,
+            options.lenient_config,
+            &options.api_config_overrides,
+            &options.cli_config_overrides,
+        )?;
+        self.reread_values_and_clear_caches_replacing_config(resolved)?;
+        self.config.files = files;
+        Ok(())
+    }
+
+    /// Like [`reload_config()`](Self::reload_config()), but only reloads if the
+    /// [configuration is outdated](Self::is_config_outdated()), returning `true` if it was reloaded.
+    pub fn reload_config_if_outdated(&mut self) -> Result<bool, config::Error> {
+        if !self.is_config_outdated() {
+            return Ok(false);
+        }
+        self.reload_config()?;
+        Ok(true)
+    }
+
     /// Return filesystem options as retrieved from the repository configuration.
     ///
     /// Note that these values have not been [probed](gix_fs::Capabilities::probe()).
@@ -37,6 +121,31 @@ impl crate::Repository {
         self.config.stat_options()
     }
 
+    /// Return the compression level for objects that are recompressed when writing packs, as configured by
+    /// `pack.compression` or `core.compression`, or the fastest level if neither is set.
+    pub fn pack_compression(&self) -> Result<gix_features::zlib::Compression, config::key::GenericError> {
+        self.config.pack_compression()
+    }
+
+    /// Return the zlib implementation to compress objects with, as configured by `gitoxide.core.zlibBackend`,
+    /// or the one selected at compile time if unset.
+    pub fn zlib_backend(&self) -> Result<gix_features::zlib::Backend, config::zlib_backend::Error> {
+        self.config.zlib_backend()
+    }
+
+    /// Return the drivers configured in `diff.<driver>` sections, which customize how files are diffed
+    /// and whether they are binary, as selected by their `diff` attribute.
+    #[cfg(feature = "blob-diff")]
+    pub fn diff_drivers(&self) -> Result<Vec<gix_diff::blob::Driver>, config::diff::drivers::Error> {
+        self.config.diff_drivers()
+    }
+
+    /// Return `true` if bytes outside of ASCII are escaped when quoting paths for output, as configured by `core.quotePath`,
+    /// which is the default.
+    pub fn quote_path(&self) -> bool {
+        self.config.quote_path
+    }
+
     /// The options used to open the repository.
     pub fn open_options(&self) -> &crate::open::Options {
         &self.options
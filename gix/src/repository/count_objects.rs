@@ -0,0 +1,94 @@
+use std::{collections::BTreeSet, ffi::OsStr, path::Path};
+
+use crate::{count_objects, Repository};
+
+/// Object database statistics
+impl Repository {
+    /// Count the loose and packed objects in the object database of this repository along with the size they take up,
+    /// and find files that don't belong there, similar to `git count-objects -v`.
+    ///
+    /// Objects in alternate object databases aren't counted.
+    pub fn count_objects(&self) -> Result<count_objects::Outcome, count_objects::Error> {
+        let _span = gix_trace::coarse!("gix::count_objects()");
+        let objects_dir = self.objects.store_ref().path();
+        let mut out = count_objects::Outcome::default();
+
+        let pack_dir = objects_dir.join("pack");
+        let mut pack_files = BTreeSet::new();
+        for entry in read_dir_if_present(&pack_dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_file() {
+                pack_files.insert(entry.file_name());
+            }
+        }
+        let mut indices = Vec::new();
+        for file_name in &pack_files {
+            let path = pack_dir.join(file_name);
+            let has_pack_file_with =
+                |extension: &str| pack_files.contains(path.with_extension(extension).file_name().expect("non-empty"));
+            let is_garbage = match path.extension().and_then(OsStr::to_str) {
+                _ if !file_name.to_string_lossy().starts_with("pack-") => file_name != "multi-pack-index",
+                Some("idx") => !has_pack_file_with("pack"),
+                Some("pack") => !has_pack_file_with("idx"),
+                Some("keep" | "rev" | "bitmap" | "mtimes" | "promisor") => !has_pack_file_with("pack"),
+                _ => true,
+            };
+            let size = std::fs::metadata(&path)?.len();
+            if is_garbage {
+                out.garbage.push(path);
+                out.garbage_size += size;
+                continue;
+            }
+            match path.extension().and_then(OsStr::to_str) {
+                Some("idx") => {
+                    let index = gix_pack::index::File::at(&path, self.object_hash())?;
+                    out.packs += 1;
+                    out.pack_size += size;
+                    out.in_pack_objects += u64::from(index.num_objects());
+                    indices.push(index);
+                }
+                Some("pack") => out.pack_size += size,
+                _ => {}
+            }
+        }
+
+        let hex_len = self.object_hash().len_in_hex();
+        for fan_out in 0..=u8::MAX {
+            let dir = objects_dir.join(format!("{fan_out:02x}"));
+            for entry in read_dir_if_present(&dir)? {
+                let entry = entry?;
+                let path = entry.path();
+                let size = entry.metadata()?.len();
+                let id = entry
+                    .file_name()
+                    .to_str()
+                    .filter(|name| name.len() == hex_len - 2)
+                    .and_then(|name| gix_hash::ObjectId::from_hex(format!("{fan_out:02x}{name}").as_bytes()).ok());
+                match id {
+                    Some(id) => {
+                        out.loose_objects += 1;
+                        out.loose_size += size;
+                        if indices.iter().any(|index| index.lookup(id).is_some()) {
+                            out.prune_packable += 1;
+                        }
+                    }
+                    None => {
+                        out.garbage.push(path);
+                        out.garbage_size += size;
+                    }
+                }
+            }
+        }
+        out.garbage.sort();
+        Ok(out)
+    }
+}
+
+/// Iterate the entries of `dir`, or nothing if it doesn't exist.
+fn read_dir_if_present(dir: &Path) -> std::io::Result<impl Iterator<Item = std::io::Result<std::fs::DirEntry>>> {
+    match std::fs::read_dir(dir) {
+        Ok(entries) => Ok(Some(entries).into_iter().flatten()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None.into_iter().flatten()),
+        Err(err) => Err(err),
+    }
+}
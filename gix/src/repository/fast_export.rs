@@ -0,0 +1,498 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    io::Write,
+};
+
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    fast_export,
+    object::tree::diff::change::Event,
+    Repository,
+};
+
+/// Export history
+impl Repository {
+    /// Write the history leading to `references` to `out` as a stream of fast-import commands like `git fast-export` does,
+    /// for consumption by `git fast-import` and other tools that understand the format.
+    ///
+    /// `references` are names like `main` or `v1.0`, which are used with their full name in the stream.
+    /// Each commit is exported for the first of them that reaches it, and the others are set with `reset` commands once all
+    /// commits were exported. Annotated tags are exported last, using the name of the reference that points to them.
+    ///
+    /// Objects with [`marks`](fast_export::Marks) from a previous export aren't exported again, nor is the history of marked
+    /// commits, similar to `git fast-export --import-marks`. Instead, they are referred to by their mark.
+    /// All objects that are exported are assigned new marks, so `marks` can be used to continue with an incremental export later.
+    ///
+    /// ### Deviation
+    ///
+    /// * Tags are always marked like with `git fast-export --mark-tags`.
+    /// * Commits keep their `encoding` header like with `git fast-export --reencode=no`, and their signatures are always stripped.
+    /// * References that only point to commits reachable from marked commits are set to these commits, instead of being deleted.
+    /// * Deleting a file is written before adding files to a directory of the same name, whereas `git` writes it afterwards.
+    pub fn fast_export(
+        &self,
+        references: impl IntoIterator<Item = impl AsRef<BStr>>,
+        marks: &mut fast_export::Marks,
+        mut out: impl Write,
+        options: fast_export::Options,
+    ) -> Result<fast_export::Outcome, fast_export::Error> {
+        let _span = gix_trace::coarse!("gix::fast_export()", options = ?options);
+        let mut refs = Vec::new();
+        for name in references {
+            refs.push(self.fast_export_ref(name.as_ref())?);
+        }
+
+        let marked_before: gix_hashtable::HashSet<_> = marks.iter().map(|(_, id)| id.to_owned()).collect();
+        let marked_commits = marked_before.iter().filter(|id| {
+            self.find_header(**id)
+                .map_or(false, |header| header.kind() == gix_object::Kind::Commit)
+        });
+        let mut tips = gix_hashtable::HashSet::default();
+        let commits = gix_traverse::commit::topo::Builder::from_iters(
+            &self.objects,
+            refs.iter()
+                .map(|r| r.commit)
+                .filter(|id| !marked_before.contains(id) && tips.insert(*id)),
+            Some(marked_commits.copied()),
+        )
+        .sorting(gix_traverse::commit::topo::Sorting::TopoOrder)
+        .build()?
+        .collect::<Result<Vec<_>, _>>()?;
+        let labels = commit_labels(&refs, &commits);
+
+        let mut exporter = Exporter {
+            repo: self,
+            marks,
+            out: &mut out,
+            anonymizer: options.anonymize.then(Anonymizer::default),
+            outcome: Default::default(),
+        };
+        let mut used_labels = vec![false; refs.len()];
+        for info in commits.iter().rev() {
+            let label = labels[&info.id];
+            used_labels[label] = true;
+            exporter.commit(info.id, refs[label].name.as_ref())?;
+        }
+
+        let mut unused_refs: Vec<_> = refs
+            .iter()
+            .zip(used_labels)
+            .filter(|(r, used)| r.tags.is_empty() && !used)
+            .map(|(r, _)| r)
+            .collect();
+        unused_refs.sort_by(|a, b| a.name.cmp(&b.name));
+        unused_refs.dedup_by(|a, b| a.name == b.name);
+        for r in unused_refs.into_iter().rev() {
+            exporter.reset(r.name.as_ref(), r.commit)?;
+        }
+        for r in refs.iter().rev() {
+            for tag in r.tags.iter().rev().filter(|tag| !marked_before.contains(*tag)) {
+                exporter.tag(r.name.as_ref(), *tag, options.signed_tags)?;
+            }
+        }
+        exporter.out.flush()?;
+        Ok(exporter.outcome)
+    }
+
+    fn fast_export_ref(&self, name: &BStr) -> Result<Ref, fast_export::Error> {
+        let mut reference = self.find_reference(name)?;
+        let name = reference.name().as_bstr().to_owned();
+        while reference.target().try_name().is_some() {
+            reference = match reference.follow().expect("symbolic references can be followed") {
+                Ok(next) => next,
+                Err(gix_ref::file::find::existing::Error::NotFound { .. }) => {
+                    return Err(fast_export::Error::Unborn { name })
+                }
+                Err(err) => return Err(err.into()),
+            };
+        }
+        let mut id = reference
+            .try_id()
+            .ok_or_else(|| fast_export::Error::Unborn { name: name.clone() })?
+            .detach();
+        let mut tags = Vec::new();
+        loop {
+            let object = self.find_object(id)?;
+            match object.kind {
+                gix_object::Kind::Commit => break,
+                gix_object::Kind::Tag => {
+                    tags.push(id);
+                    id = object.into_tag().target_id()?.detach();
+                }
+                kind => return Err(fast_export::Error::UnsupportedObject { name, kind }),
+            }
+        }
+        Ok(Ref { name, tags, commit: id })
+    }
+}
+
+/// A reference to export.
+struct Ref {
+    /// The full name of the reference.
+    name: BString,
+    /// The annotated tags the reference points to, outermost first.
+    tags: Vec<ObjectId>,
+    /// The commit the reference ultimately points to.
+    commit: ObjectId,
+}
+
+/// Assign each commit to export the index of the reference in `refs` it is exported for, which is the first one that
+/// reaches it when walking the history newest first, like `git` does.
+fn commit_labels(refs: &[Ref], commits: &[gix_traverse::commit::Info]) -> gix_hashtable::HashMap<ObjectId, usize> {
+    let commits: gix_hashtable::HashMap<_, _> = commits.iter().map(|info| (info.id, info)).collect();
+    let mut labels = gix_hashtable::HashMap::default();
+    for (idx, r) in refs.iter().enumerate() {
+        labels.entry(r.commit).or_insert(idx);
+    }
+    // Newest first, and in the order of insertion for commits of the same time.
+    let mut queue = BinaryHeap::new();
+    let mut seen = gix_hashtable::HashSet::default();
+    let mut push = |queue: &mut BinaryHeap<_>, info: &gix_traverse::commit::Info| {
+        if seen.insert(info.id) {
+            queue.push((info.commit_time.unwrap_or_default(), Reverse(seen.len()), info.id));
+        }
+    };
+    for r in refs {
+        if let Some(info) = commits.get(&r.commit) {
+            push(&mut queue, info);
+        }
+    }
+    while let Some((_, _, id)) = queue.pop() {
+        let label = labels[&id];
+        for parent in &commits[&id].parent_ids {
+            if let Some(info) = commits.get(parent) {
+                labels.entry(*parent).or_insert(label);
+                push(&mut queue, info);
+            }
+        }
+    }
+    labels
+}
+
+struct Exporter<'a> {
+    repo: &'a Repository,
+    marks: &'a mut fast_export::Marks,
+    out: &'a mut dyn Write,
+    anonymizer: Option<Anonymizer>,
+    outcome: fast_export::Outcome,
+}
+
+impl Exporter<'_> {
+    fn commit(&mut self, id: ObjectId, label: &BStr) -> Result<(), fast_export::Error> {
+        let commit = self.repo.find_object(id)?.into_commit();
+        let decoded = gix_object::CommitRef::from_bytes(&commit.data)?;
+        let parents: Vec<_> = decoded.parents().collect();
+        let new_tree = commit.tree()?;
+        let old_tree = match parents.first() {
+            Some(parent) => self.repo.find_object(*parent)?.into_commit().tree()?,
+            None => self.repo.empty_tree(),
+        };
+
+        let mut changes = Vec::<(BString, Option<(EntryMode, ObjectId)>)>::new();
+        old_tree
+            .changes()?
+            .track_path()
+            .track_rewrites(None)
+            .for_each_to_obtain_tree(&new_tree, |change| {
+                let path = change.location.to_owned();
+                match change.event {
+                    _ if change.event.entry_mode().is_tree() => {}
+                    Event::Deletion { .. } => changes.push((path, None)),
+                    Event::Addition { entry_mode, id } | Event::Modification { entry_mode, id, .. } => {
+                        changes.push((path, Some((entry_mode, id.detach()))))
+                    }
+                    Event::Rewrite { .. } => unreachable!("rewrite tracking is disabled"),
+                }
+                Ok::<_, std::convert::Infallible>(crate::object::tree::diff::Action::Continue)
+            })?;
+        // Export blobs in the order of paths, like `git` does.
+        changes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (_, change) in &changes {
+            if let Some((mode, id)) = change {
+                if !mode.is_commit() && self.marks.mark(id).is_none() {
+                    self.blob(*id)?;
+                }
+            }
+        }
+        sort_changes(&mut changes);
+
+        let label = self.reference_name(label);
+        if parents.is_empty() {
+            writeln!(self.out, "reset {label}")?;
+        }
+        let mark = self.assign_mark(id);
+        writeln!(self.out, "commit {label}\nmark :{mark}")?;
+        if let Some(anonymizer) = self.anonymizer.as_mut() {
+            // Number the committer before the author, like `git` does.
+            anonymizer.ident(decoded.committer);
+        }
+        self.write_ident("author", decoded.author)?;
+        self.write_ident("committer", decoded.committer)?;
+        let message = match self.anonymizer.as_mut() {
+            Some(anonymizer) => {
+                anonymizer.commit_messages += 1;
+                format!("subject {}\n\nbody\n", anonymizer.commit_messages - 1).into()
+            }
+            None => {
+                if let Some(encoding) = decoded.encoding {
+                    writeln!(self.out, "encoding {encoding}")?;
+                }
+                raw_message(&commit.data).to_owned()
+            }
+        };
+        write_data(self.out, &message)?;
+        for (idx, parent) in parents.into_iter().enumerate() {
+            let command = if idx == 0 { "from" } else { "merge" };
+            writeln!(self.out, "{command} {}", self.object_ref(parent))?;
+        }
+        for (path, change) in changes {
+            let path = match self.anonymizer.as_mut() {
+                Some(anonymizer) => anonymizer.path(path.as_ref()),
+                None => path,
+            };
+            let path = fast_export::quoted(path.as_ref(), true, self.repo.quote_path());
+            match change {
+                None => writeln!(self.out, "D {path}")?,
+                Some((mode, id)) => {
+                    let id = if mode.is_commit() {
+                        let hash_kind = self.repo.object_hash();
+                        match self.anonymizer.as_mut() {
+                            Some(anonymizer) => anonymizer.submodule_commit(id, hash_kind).to_string(),
+                            None => id.to_string(),
+                        }
+                    } else {
+                        self.object_ref(id)
+                    };
+                    writeln!(self.out, "M {} {id} {path}", mode.kind().as_octal_str())?;
+                }
+            }
+        }
+        writeln!(self.out)?;
+        self.outcome.commits += 1;
+        Ok(())
+    }
+
+    fn blob(&mut self, id: ObjectId) -> Result<(), fast_export::Error> {
+        let object = self.repo.find_object(id)?;
+        let mark = self.assign_mark(id);
+        writeln!(self.out, "blob\nmark :{mark}")?;
+        match self.anonymizer.as_mut() {
+            Some(anonymizer) => {
+                anonymizer.blobs += 1;
+                write_data(self.out, format!("anonymous blob {}", anonymizer.blobs - 1).as_bytes())?;
+            }
+            None => write_data(self.out, &object.data)?,
+        }
+        writeln!(self.out)?;
+        self.outcome.blobs += 1;
+        Ok(())
+    }
+
+    fn tag(
+        &mut self,
+        reference_name: &BStr,
+        id: ObjectId,
+        signed_tags: fast_export::SignedTags,
+    ) -> Result<(), fast_export::Error> {
+        let object = self.repo.find_object(id)?;
+        let decoded = gix_object::TagRef::from_bytes(&object.data)?;
+        let mut message = raw_message(&object.data);
+        if let Some(pos) = [
+            b"\n-----BEGIN PGP SIGNATURE-----\n",
+            b"\n-----BEGIN SSH SIGNATURE-----\n",
+        ]
+        .iter()
+        .find_map(|marker| message.find(marker))
+        {
+            match signed_tags {
+                fast_export::SignedTags::Abort => {
+                    return Err(fast_export::Error::SignedTag {
+                        name: reference_name.to_owned(),
+                    })
+                }
+                fast_export::SignedTags::Verbatim => {}
+                fast_export::SignedTags::Strip => message = &message[..pos + 1],
+            }
+        }
+        let target = decoded.target();
+
+        let full_name = self.reference_name(reference_name);
+        if decoded.target_kind == gix_object::Kind::Tag {
+            // Delete the reference first, as `git` does for nested tags.
+            writeln!(self.out, "reset {full_name}\nfrom {}\n", self.repo.object_hash().null())?;
+        }
+        let name = full_name.strip_prefix(b"refs/tags/").unwrap_or(&full_name).as_bstr();
+        let mark = self.assign_mark(id);
+        writeln!(self.out, "tag {name}\nmark :{mark}\nfrom {}", self.object_ref(target))?;
+        if let Some(tagger) = decoded.tagger {
+            self.write_ident("tagger", tagger)?;
+        }
+        let message = match self.anonymizer.as_mut() {
+            Some(anonymizer) => {
+                let count = anonymizer.tag_messages.len();
+                anonymizer
+                    .tag_messages
+                    .entry(message.into())
+                    .or_insert_with(|| format!("tag message {count}").into())
+                    .clone()
+            }
+            None => BString::from(message),
+        };
+        write_data(self.out, &message)?;
+        writeln!(self.out)?;
+        self.outcome.tags += 1;
+        Ok(())
+    }
+
+    fn reset(&mut self, reference_name: &BStr, commit: ObjectId) -> Result<(), fast_export::Error> {
+        let name = self.reference_name(reference_name);
+        writeln!(self.out, "reset {name}\nfrom {}\n", self.object_ref(commit))?;
+        self.outcome.resets += 1;
+        Ok(())
+    }
+
+    fn assign_mark(&mut self, id: ObjectId) -> u32 {
+        let mark = self.marks.next_mark();
+        self.marks.insert(mark, id);
+        mark
+    }
+
+    /// Return how to refer to `id` in the stream, which is by mark if possible.
+    fn object_ref(&self, id: ObjectId) -> String {
+        match self.marks.mark(&id) {
+            Some(mark) => format!(":{mark}"),
+            None => id.to_string(),
+        }
+    }
+
+    fn reference_name(&mut self, name: &BStr) -> BString {
+        match self.anonymizer.as_mut() {
+            Some(anonymizer) => anonymizer.reference_name(name),
+            None => name.to_owned(),
+        }
+    }
+
+    fn write_ident(&mut self, field: &str, signature: gix_actor::SignatureRef<'_>) -> std::io::Result<()> {
+        write!(self.out, "{field} ")?;
+        match self.anonymizer.as_mut() {
+            Some(anonymizer) => {
+                self.out.write_all(&anonymizer.ident(signature))?;
+                self.out.write_all(b" ")?;
+                signature.time.write_to(self.out)?;
+            }
+            None => signature.write_to(self.out)?,
+        }
+        writeln!(self.out)
+    }
+}
+
+/// Return the message of the commit or tag in `data`, which follows the first empty line.
+fn raw_message(data: &[u8]) -> &[u8] {
+    data.find(b"\n\n").map_or(&[], |pos| &data[pos + 2..])
+}
+
+fn write_data(out: &mut dyn Write, data: &[u8]) -> std::io::Result<()> {
+    writeln!(out, "data {}", data.len())?;
+    out.write_all(data)
+}
+
+/// Sort `changes` by path like `git` does, but with paths below a directory before the directory itself, so that
+/// directories can be replaced by files. Only if a file is replaced by a directory, it's deleted first.
+fn sort_changes(changes: &mut [(BString, Option<(EntryMode, ObjectId)>)]) {
+    changes.sort_by(|(a, _), (b, _)| {
+        let len = a.len().min(b.len());
+        a[..len].cmp(&b[..len]).then_with(|| b.len().cmp(&a.len()))
+    });
+    for idx in 0..changes.len() {
+        if changes[idx].1.is_some() {
+            continue;
+        }
+        let mut dir = changes[idx].0.clone();
+        dir.push(b'/');
+        // All paths starting with the deleted path are right before it.
+        let first_below = (0..idx)
+            .rev()
+            .take_while(|other| changes[*other].0.starts_with(&changes[idx].0))
+            .filter(|other| changes[*other].0.starts_with(&dir))
+            .last();
+        if let Some(first_below) = first_below {
+            changes[first_below..=idx].rotate_right(1);
+        }
+    }
+}
+
+/// Made-up replacements for everything that could reveal the content of a repository, numbered in order of appearance.
+#[derive(Default)]
+struct Anonymizer {
+    idents: HashMap<BString, BString>,
+    path_components: HashMap<BString, BString>,
+    reference_components: HashMap<BString, BString>,
+    submodule_commits: gix_hashtable::HashMap<ObjectId, ObjectId>,
+    blobs: usize,
+    commit_messages: usize,
+    tag_messages: HashMap<BString, BString>,
+}
+
+impl Anonymizer {
+    fn ident(&mut self, signature: gix_actor::SignatureRef<'_>) -> BString {
+        let mut key = BString::from(signature.name);
+        key.push_str(b" <");
+        key.push_str(signature.email);
+        key.push(b'>');
+        let count = self.idents.len();
+        self.idents
+            .entry(key)
+            .or_insert_with(|| format!("User {count} <user{count}@example.com>").into())
+            .clone()
+    }
+
+    fn path(&mut self, path: &BStr) -> BString {
+        anonymized_components(&mut self.path_components, path, "path")
+    }
+
+    /// Anonymize `name` but keep its category, so branches remain branches and tags remain tags.
+    fn reference_name(&mut self, name: &BStr) -> BString {
+        let prefix = ["refs/heads/", "refs/tags/", "refs/remotes/", "refs/"]
+            .into_iter()
+            .find(|prefix| name.starts_with(prefix.as_bytes()))
+            .unwrap_or_default();
+        let mut out = BString::from(prefix);
+        out.push_str(anonymized_components(
+            &mut self.reference_components,
+            name[prefix.len()..].as_bstr(),
+            "ref",
+        ));
+        out
+    }
+
+    fn submodule_commit(&mut self, id: ObjectId, hash_kind: gix_hash::Kind) -> ObjectId {
+        let count = self.submodule_commits.len();
+        *self.submodule_commits.entry(id).or_insert_with(|| {
+            gix_object::compute_hash(
+                hash_kind,
+                gix_object::Kind::Commit,
+                format!("anonymous submodule commit {count}").as_bytes(),
+            )
+        })
+    }
+}
+
+/// Replace each `/`-separated component of `path` with `<kind><number>`, remembering replacements in `map`.
+fn anonymized_components(map: &mut HashMap<BString, BString>, path: &BStr, kind: &str) -> BString {
+    let mut out = BString::default();
+    for (idx, component) in path.split(|b| *b == b'/').enumerate() {
+        if idx != 0 {
+            out.push(b'/');
+        }
+        let count = map.len();
+        out.push_str(
+            map.entry(component.into())
+                .or_insert_with(|| format!("{kind}{count}").into()),
+        );
+    }
+    out
+}
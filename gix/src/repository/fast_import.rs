@@ -0,0 +1,624 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, Write},
+    path::PathBuf,
+};
+
+use gix_hash::ObjectId;
+use gix_object::{
+    tree::{EntryKind, EntryMode},
+    Kind,
+};
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    fast_export::{self, Marks},
+    fast_import::{self, Command, FileChange, FileData, ObjectRef},
+    Repository,
+};
+
+/// Import history
+impl Repository {
+    /// Read a stream of fast-import commands from `input`, like the ones produced by `git fast-export` or
+    /// [`fast_export()`](Self::fast_export()), write the objects they describe and update the references they
+    /// create, like `git fast-import` does.
+    ///
+    /// `marks` are used to resolve marks in the stream, and receive the marks of all objects written by it. They may be
+    /// loaded from the marks file of a previous import to continue it. Answers to the `ls`, `cat-blob` and `get-mark`
+    /// commands as well as `progress` messages are written to `response`.
+    ///
+    /// References are only updated when the stream ends and on `checkpoint` commands.
+    pub fn fast_import(
+        &self,
+        input: impl BufRead,
+        response: impl Write,
+        marks: &mut Marks,
+        options: fast_import::Options,
+    ) -> Result<fast_import::Outcome, fast_import::Error> {
+        let _span = gix_trace::coarse!("gix::fast_import()");
+        let mut state = State {
+            repo: self,
+            marks,
+            response,
+            options,
+            branches: BTreeMap::new(),
+            tags: BTreeMap::new(),
+            export_marks: None,
+            outcome: Default::default(),
+        };
+        for command in fast_import::Parser::new(input, options.date_format) {
+            match command? {
+                Command::Blob(blob) => {
+                    let id = self.write_blob(&blob.data)?.detach();
+                    state.outcome.blobs += 1;
+                    if let Some(mark) = blob.mark {
+                        state.marks.insert(mark, id);
+                    }
+                }
+                Command::Commit(commit) => state.commit(commit)?,
+                Command::Tag(tag) => state.tag(tag)?,
+                Command::Reset { name, from } => state.reset(name, from)?,
+                Command::Ls { object, path } => {
+                    let mut tree = Tree::from_id(state.tree_of(&object)?);
+                    state.ls(&mut tree, path.as_ref())?;
+                }
+                Command::CatBlob(object) => {
+                    let id = state.resolve(&object)?;
+                    let blob = self.find_object(id)?;
+                    if blob.kind != Kind::Blob {
+                        return Err(fast_import::Error::ObjectKind {
+                            id,
+                            expected: Kind::Blob,
+                            actual: blob.kind,
+                        });
+                    }
+                    writeln!(state.response, "{id} blob {}", blob.data.len())?;
+                    state.response.write_all(&blob.data)?;
+                    writeln!(state.response)?;
+                }
+                Command::GetMark(mark) => {
+                    let id = state.resolve(&ObjectRef::Mark(mark))?;
+                    writeln!(state.response, "{id}")?;
+                }
+                Command::Feature { name, argument } => state.feature(name, argument)?,
+                Command::Progress(message) => {
+                    state.response.write_all(b"progress ")?;
+                    state.response.write_all(&message)?;
+                    writeln!(state.response)?;
+                }
+                Command::Checkpoint => state.checkpoint()?,
+                Command::Option(_) | Command::Done => {}
+            }
+        }
+        state.checkpoint()?;
+        Ok(state.outcome)
+    }
+}
+
+struct State<'a, W> {
+    repo: &'a Repository,
+    marks: &'a mut Marks,
+    response: W,
+    options: fast_import::Options,
+    /// All branches the stream created or changed, by full reference name.
+    branches: BTreeMap<BString, Branch>,
+    /// Tags that still have to be written, by name.
+    tags: BTreeMap<BString, ObjectId>,
+    export_marks: Option<PathBuf>,
+    outcome: fast_import::Outcome,
+}
+
+struct Branch {
+    /// The commit the branch points to, or `None` if the next commit on it is a root commit.
+    tip: Option<ObjectId>,
+    /// The tree of `tip`, as changed by the commands of the stream.
+    tree: Tree,
+    /// If `true`, the branch was reset to the null id and should be deleted.
+    delete: bool,
+    /// If `true`, the reference of the branch must be updated on the next checkpoint.
+    pending: bool,
+}
+
+impl<'a, W: Write> State<'a, W> {
+    fn commit(&mut self, commit: fast_import::Commit) -> Result<(), fast_import::Error> {
+        let (first_parent, tree) = match &commit.from {
+            Some(from) => {
+                let (id, tree) = self.commit_and_tree(from)?;
+                (Some(id), tree)
+            }
+            None => match self.branches.remove(&commit.reference) {
+                Some(branch) => (branch.tip, branch.tree),
+                None => (None, Tree::empty()),
+            },
+        };
+        let mut parents: Vec<_> = first_parent.into_iter().collect();
+        for merge in &commit.merges {
+            parents.push(self.commit_and_tree(merge)?.0);
+        }
+
+        let mut tree = tree;
+        for change in commit.changes {
+            match change {
+                FileChange::Modify { mode, data, path } => {
+                    let id = match data {
+                        FileData::Inline(data) => {
+                            self.outcome.blobs += 1;
+                            self.repo.write_blob(data)?.detach()
+                        }
+                        FileData::Object(object) => self.resolve(&object)?,
+                    };
+                    let entry = if mode.is_tree() {
+                        Entry::Tree(Tree::from_id(id))
+                    } else {
+                        Entry::Leaf { mode, id }
+                    };
+                    if path.is_empty() {
+                        match entry {
+                            Entry::Tree(new) => tree = new,
+                            Entry::Leaf { .. } => return Err(fast_import::Error::InvalidPath { path }),
+                        }
+                    } else {
+                        tree.set(self.repo, path.as_ref(), entry)?;
+                    }
+                }
+                FileChange::Delete { path } => {
+                    tree.remove(self.repo, path.as_ref())?;
+                }
+                FileChange::Copy { source, destination } => {
+                    let entry = tree
+                        .get(self.repo, source.as_ref())?
+                        .ok_or(fast_import::Error::MissingPath { path: source })?;
+                    tree.set(self.repo, destination.as_ref(), entry)?;
+                }
+                FileChange::Rename { source, destination } => {
+                    let entry = tree
+                        .remove(self.repo, source.as_ref())?
+                        .ok_or(fast_import::Error::MissingPath { path: source })?;
+                    tree.set(self.repo, destination.as_ref(), entry)?;
+                }
+                FileChange::DeleteAll => tree = Tree::empty(),
+                FileChange::Note { .. } => return Err(fast_import::Error::Notes),
+                FileChange::Ls { object, path } => match object {
+                    Some(object) => {
+                        let mut tree = Tree::from_id(self.tree_of(&object)?);
+                        self.ls(&mut tree, path.as_ref())?;
+                    }
+                    None => self.ls(&mut tree, path.as_ref())?,
+                },
+            }
+        }
+
+        let object = gix_object::Commit {
+            tree: tree.write(self.repo)?,
+            parents: parents.into(),
+            author: commit.author.unwrap_or_else(|| commit.committer.clone()),
+            committer: commit.committer,
+            encoding: commit.encoding,
+            message: commit.message,
+            extra_headers: Vec::new(),
+        };
+        let id = self.repo.write_object(&object)?.detach();
+        self.outcome.commits += 1;
+        if let Some(mark) = commit.mark {
+            self.marks.insert(mark, id);
+        }
+        self.branches.insert(
+            commit.reference,
+            Branch {
+                tip: Some(id),
+                tree,
+                delete: false,
+                pending: true,
+            },
+        );
+        Ok(())
+    }
+
+    fn tag(&mut self, tag: fast_import::Tag) -> Result<(), fast_import::Error> {
+        let target = self.resolve(&tag.from)?;
+        let target_kind = self.repo.find_header(target)?.kind();
+        let object = gix_object::Tag {
+            target,
+            target_kind,
+            name: tag.name.clone(),
+            tagger: tag.tagger,
+            message: tag.message,
+            pgp_signature: None,
+        };
+        let id = self.repo.write_object(&object)?.detach();
+        self.outcome.tags += 1;
+        if let Some(mark) = tag.mark {
+            self.marks.insert(mark, id);
+        }
+        // The tag replaces whatever a `reset` of the same reference did, as tags are written last.
+        let reference = format!("refs/tags/{}", tag.name);
+        self.branches.remove(reference.as_bytes().as_bstr());
+        self.tags.insert(tag.name, id);
+        Ok(())
+    }
+
+    fn reset(&mut self, name: BString, from: Option<ObjectRef>) -> Result<(), fast_import::Error> {
+        let branch = match from {
+            Some(ObjectRef::Id(id)) if id.is_null() => Branch {
+                tip: None,
+                tree: Tree::empty(),
+                delete: true,
+                pending: true,
+            },
+            Some(from) => {
+                let (tip, tree) = self.commit_and_tree(&from)?;
+                Branch {
+                    tip: Some(tip),
+                    tree,
+                    delete: false,
+                    pending: true,
+                }
+            }
+            None => Branch {
+                tip: None,
+                tree: Tree::empty(),
+                delete: false,
+                pending: false,
+            },
+        };
+        self.branches.insert(name, branch);
+        Ok(())
+    }
+
+    fn feature(&mut self, name: BString, argument: Option<BString>) -> Result<(), fast_import::Error> {
+        match name.as_slice() {
+            b"date-format" | b"done" | b"ls" | b"cat-blob" | b"get-mark" | b"force" => {
+                if name == "force" {
+                    self.options.force = true;
+                }
+            }
+            b"import-marks" | b"import-marks-if-exists" | b"export-marks" => {
+                if !self.options.allow_unsafe_features {
+                    return Err(fast_import::Error::UnsafeFeature { name });
+                }
+                let path = gix_path::from_bstring(argument.unwrap_or_default());
+                if name == "export-marks" {
+                    self.export_marks = Some(path);
+                } else {
+                    let data = match std::fs::read(&path) {
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound && name == "import-marks-if-exists" => {
+                            return Ok(())
+                        }
+                        res => res?,
+                    };
+                    for (mark, id) in Marks::from_bytes(&data)?.iter() {
+                        self.marks.insert(mark, id.to_owned());
+                    }
+                }
+            }
+            _ => return Err(fast_import::Error::UnsupportedFeature { name }),
+        }
+        Ok(())
+    }
+
+    /// Update all references that changed since the last checkpoint, and write the marks file if requested.
+    fn checkpoint(&mut self) -> Result<(), fast_import::Error> {
+        let mut edits = Vec::new();
+        for (name, branch) in self.branches.iter_mut().filter(|(_, branch)| branch.pending) {
+            branch.pending = false;
+            let name: gix_ref::FullName = name.clone().try_into()?;
+            let existing = self.repo.try_find_reference(name.as_ref())?;
+            let change = match branch.tip {
+                None if branch.delete && existing.is_some() => Change::Delete {
+                    expected: PreviousValue::Any,
+                    log: RefLog::AndReference,
+                },
+                None => continue,
+                Some(new) => {
+                    let previous = existing.and_then(|r| r.target().try_id().map(ToOwned::to_owned));
+                    if let Some(previous) = previous {
+                        if !self.options.force && !is_ancestor(self.repo, previous, new)? {
+                            if !self.outcome.rejected_references.contains(&name) {
+                                self.outcome.rejected_references.push(name);
+                            }
+                            continue;
+                        }
+                    }
+                    update(new)
+                }
+            };
+            edits.push(RefEdit {
+                change,
+                name,
+                deref: false,
+            });
+        }
+        for (name, id) in std::mem::take(&mut self.tags) {
+            let name: gix_ref::FullName = format!("refs/tags/{name}").try_into()?;
+            edits.push(RefEdit {
+                change: update(id),
+                name,
+                deref: false,
+            });
+        }
+        for edit in self.repo.edit_references(edits)? {
+            if !self.outcome.updated_references.contains(&edit.name) {
+                self.outcome.updated_references.push(edit.name);
+            }
+        }
+
+        if let Some(path) = &self.export_marks {
+            let mut file = std::fs::File::create(path)?;
+            self.marks.write_to(&mut file)?;
+        }
+        Ok(())
+    }
+
+    /// Write the entry at `path` in `tree` to the response stream.
+    fn ls(&mut self, tree: &mut Tree, path: &BStr) -> Result<(), fast_import::Error> {
+        let quoted = fast_export::quoted(path, false, self.repo.quote_path());
+        match tree.get(self.repo, path)? {
+            Some(entry) => {
+                let (mode, id) = match entry {
+                    Entry::Leaf { mode, id } => (mode, id),
+                    Entry::Tree(mut tree) => (EntryKind::Tree.into(), tree.write(self.repo)?),
+                };
+                let kind = match mode.kind() {
+                    EntryKind::Tree => "tree",
+                    EntryKind::Commit => "commit",
+                    EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link => "blob",
+                };
+                writeln!(self.response, "{:06o} {kind} {id}\t{quoted}", *mode)?;
+            }
+            None => writeln!(self.response, "missing {quoted}")?,
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, object: &ObjectRef) -> Result<ObjectId, fast_import::Error> {
+        Ok(match object {
+            ObjectRef::Mark(mark) => self
+                .marks
+                .id(*mark)
+                .ok_or(fast_import::Error::UnknownMark { mark: *mark })?
+                .to_owned(),
+            ObjectRef::Id(id) => *id,
+            ObjectRef::Revision(name) => match self.branches.get(name) {
+                Some(branch) => branch
+                    .tip
+                    .ok_or_else(|| fast_import::Error::UnbornBranch { name: name.clone() })?,
+                None => self
+                    .repo
+                    .rev_parse_single(name.as_bstr())
+                    .map_err(|err| fast_import::Error::RevParse {
+                        spec: name.clone(),
+                        source: Box::new(err),
+                    })?
+                    .detach(),
+            },
+        })
+    }
+
+    /// Resolve `object` to a commit, and return it along with its tree, which is reused from the branch it's the tip of
+    /// if possible.
+    fn commit_and_tree(&self, object: &ObjectRef) -> Result<(ObjectId, Tree), fast_import::Error> {
+        let id = self.resolve(object)?;
+        if let Some(branch) = self.branches.values().find(|branch| branch.tip == Some(id)) {
+            return Ok((id, branch.tree.clone()));
+        }
+        let commit = self.repo.find_object(id)?;
+        if commit.kind != Kind::Commit {
+            return Err(fast_import::Error::ObjectKind {
+                id,
+                expected: Kind::Commit,
+                actual: commit.kind,
+            });
+        }
+        let tree = commit.into_commit().tree_id()?.detach();
+        Ok((id, Tree::from_id(tree)))
+    }
+
+    /// Resolve `object` to a tree, peeling commits.
+    fn tree_of(&self, object: &ObjectRef) -> Result<ObjectId, fast_import::Error> {
+        let id = self.resolve(object)?;
+        let object = self.repo.find_object(id)?;
+        Ok(match object.kind {
+            Kind::Tree => id,
+            Kind::Commit => object.into_commit().tree_id()?.detach(),
+            actual => {
+                return Err(fast_import::Error::ObjectKind {
+                    id,
+                    expected: Kind::Tree,
+                    actual,
+                })
+            }
+        })
+    }
+}
+
+fn update(new: ObjectId) -> Change {
+    Change::Update {
+        log: LogChange {
+            mode: RefLog::AndReference,
+            force_create_reflog: false,
+            message: "fast-import".into(),
+        },
+        expected: PreviousValue::Any,
+        new: gix_ref::Target::Peeled(new),
+    }
+}
+
+/// Return `true` if `ancestor` is reachable from `id`.
+fn is_ancestor(repo: &Repository, ancestor: ObjectId, id: ObjectId) -> Result<bool, fast_import::Error> {
+    if ancestor == id {
+        return Ok(true);
+    }
+    if repo.find_header(ancestor)?.kind() != Kind::Commit {
+        return Ok(false);
+    }
+    for info in repo.rev_walk([id]).all()? {
+        if info?.id == ancestor {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// A tree that is loaded from the object database only when it's looked into, and written only if it changed.
+#[derive(Clone)]
+struct Tree {
+    /// The id of the tree object, or `None` if it changed since it was loaded or written.
+    id: Option<ObjectId>,
+    /// The entries of the tree by name, or `None` if they weren't loaded yet.
+    entries: Option<BTreeMap<BString, Entry>>,
+}
+
+#[derive(Clone)]
+enum Entry {
+    Leaf { mode: EntryMode, id: ObjectId },
+    Tree(Tree),
+}
+
+impl Tree {
+    fn empty() -> Self {
+        Tree {
+            id: None,
+            entries: Some(BTreeMap::new()),
+        }
+    }
+
+    fn from_id(id: ObjectId) -> Self {
+        Tree {
+            id: Some(id),
+            entries: None,
+        }
+    }
+
+    fn entries(&mut self, repo: &Repository) -> Result<&mut BTreeMap<BString, Entry>, fast_import::Error> {
+        if self.entries.is_none() {
+            let id = self.id.expect("trees without entries always have an id");
+            let tree = repo.find_object(id)?;
+            if tree.kind != Kind::Tree {
+                return Err(fast_import::Error::ObjectKind {
+                    id,
+                    expected: Kind::Tree,
+                    actual: tree.kind,
+                });
+            }
+            let entries = tree
+                .into_tree()
+                .decode()?
+                .entries
+                .iter()
+                .map(|entry| {
+                    let id = entry.oid.to_owned();
+                    let value = if entry.mode.is_tree() {
+                        Entry::Tree(Tree::from_id(id))
+                    } else {
+                        Entry::Leaf { mode: entry.mode, id }
+                    };
+                    (entry.filename.to_owned(), value)
+                })
+                .collect();
+            self.entries = Some(entries);
+        }
+        Ok(self.entries.as_mut().expect("just loaded"))
+    }
+
+    fn get(&mut self, repo: &Repository, path: &BStr) -> Result<Option<Entry>, fast_import::Error> {
+        if path.is_empty() {
+            return Ok(Some(Entry::Tree(self.clone())));
+        }
+        let (name, rest) = split(path);
+        Ok(match (self.entries(repo)?.get_mut(name), rest) {
+            (None, _) => None,
+            (Some(entry), None) => Some(entry.clone()),
+            (Some(Entry::Tree(tree)), Some(rest)) => tree.get(repo, rest)?,
+            (Some(Entry::Leaf { .. }), Some(_)) => None,
+        })
+    }
+
+    fn set(&mut self, repo: &Repository, path: &BStr, entry: Entry) -> Result<(), fast_import::Error> {
+        let (name, rest) = split(path);
+        if name.is_empty() || name == "." || name == ".." {
+            return Err(fast_import::Error::InvalidPath { path: path.into() });
+        }
+        self.id = None;
+        let entries = self.entries(repo)?;
+        match rest {
+            None => {
+                entries.insert(name.into(), entry);
+            }
+            Some(rest) => {
+                let slot = entries.entry(name.into()).or_insert_with(|| Entry::Tree(Tree::empty()));
+                if matches!(slot, Entry::Leaf { .. }) {
+                    *slot = Entry::Tree(Tree::empty());
+                }
+                let Entry::Tree(tree) = slot else {
+                    unreachable!("leaves were replaced with trees")
+                };
+                tree.set(repo, rest, entry)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the entry at `path` and return it, removing trees that become empty along the way.
+    fn remove(&mut self, repo: &Repository, path: &BStr) -> Result<Option<Entry>, fast_import::Error> {
+        let (name, rest) = split(path);
+        let entries = self.entries(repo)?;
+        let removed = match rest {
+            None => entries.remove(name),
+            Some(rest) => match entries.get_mut(name) {
+                Some(Entry::Tree(tree)) => {
+                    let removed = tree.remove(repo, rest)?;
+                    if tree.entries.as_ref().map_or(false, BTreeMap::is_empty) {
+                        entries.remove(name);
+                    }
+                    removed
+                }
+                _ => None,
+            },
+        };
+        if removed.is_some() {
+            self.id = None;
+        }
+        Ok(removed)
+    }
+
+    /// Write this tree and all changed trees within it, and return its id. Empty trees are written, but not
+    /// added to their parent.
+    fn write(&mut self, repo: &Repository) -> Result<ObjectId, fast_import::Error> {
+        if let Some(id) = self.id {
+            return Ok(id);
+        }
+        let mut tree = gix_object::Tree::empty();
+        for (name, entry) in self.entries.as_mut().expect("changed trees are loaded") {
+            let (mode, oid) = match entry {
+                Entry::Leaf { mode, id } => (*mode, *id),
+                Entry::Tree(subtree) => {
+                    if subtree.entries.as_ref().map_or(false, BTreeMap::is_empty) {
+                        continue;
+                    }
+                    (EntryKind::Tree.into(), subtree.write(repo)?)
+                }
+            };
+            tree.entries.push(gix_object::tree::Entry {
+                mode,
+                filename: name.clone(),
+                oid,
+            });
+        }
+        tree.entries.sort();
+        let id = repo.write_object(&tree)?.detach();
+        self.id = Some(id);
+        Ok(id)
+    }
+}
+
+/// Split the first component off `path`, and return it along with the remaining path, if there is one.
+fn split(path: &BStr) -> (&BStr, Option<&BStr>) {
+    match path.find_byte(b'/') {
+        Some(pos) => (
+            path[..pos].as_bstr(),
+            Some(path[pos + 1..].as_bstr()).filter(|rest| !rest.is_empty()),
+        ),
+        None => (path, None),
+    }
+}
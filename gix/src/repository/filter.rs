@@ -22,6 +22,22 @@ pub mod pipeline {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod write_blob_stream_filtered {
+    /// The error returned by [Repository::write_blob_stream_filtered()](super::Repository::write_blob_stream_filtered()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Pipeline(#[from] super::pipeline::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        Write(#[from] crate::object::write::Error),
+    }
+}
+
 impl Repository {
     /// Configure a pipeline for converting byte buffers to the worktree representation, and byte streams to the git-internal
     /// representation. Also return the index that was used when initializing the pipeline as it may be useful when calling
@@ -62,4 +78,19 @@ impl Repository {
         };
         Ok((filter::Pipeline::new(self, cache.detach())?, index))
     }
+
+    /// Like [`write_blob_stream()`](Self::write_blob_stream()), but apply the clean filters configured for `rela_path`
+    /// to `bytes` first, like `git hash-object -w --path <rela_path>` does.
+    ///
+    /// This includes end-of-line conversions and filter drivers, whose output is streamed into the object database as well.
+    /// Use [`filter_pipeline()`](Self::filter_pipeline()) directly to reuse the pipeline for many files.
+    pub fn write_blob_stream_filtered(
+        &self,
+        bytes: impl std::io::Read,
+        rela_path: &std::path::Path,
+    ) -> Result<Id<'_>, write_blob_stream_filtered::Error> {
+        let (mut pipeline, index) = self.filter_pipeline(None)?;
+        let filtered = pipeline.convert_to_git(bytes, rela_path, &index)?;
+        Ok(self.write_blob_stream(filtered)?)
+    }
 }
@@ -0,0 +1,510 @@
+use std::collections::HashSet;
+
+use gix_hash::ObjectId;
+use gix_ref::{FullName, FullNameRef, Target};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    for_each_ref::{
+        pad, Atom, Condition, Contents, Date, Email, Error, Format, Id, Item, Name, Options, Outcome, Person, Role,
+        Tracking,
+    },
+    remote, Object, Repository,
+};
+
+/// Formatting references
+impl Repository {
+    /// Format all references whose names match one of `patterns`, or all references if there is none, ordered by name and
+    /// with `format`, like `git for-each-ref --format=<format> <patterns>…`.
+    ///
+    /// A pattern matches if it matches the reference name as glob, or if it is the reference name or a leading part of it
+    /// up to a `/`, so `refs/heads` matches all local branches.
+    ///
+    /// ### Deviation
+    ///
+    /// * Aligned text is measured in characters, not in the columns they take up on a terminal.
+    pub fn for_each_ref(
+        &self,
+        patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+        format: &Format,
+        options: Options,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix::for_each_ref()", options = ?options);
+        let patterns: Vec<BString> = patterns.into_iter().map(|p| p.as_ref().to_owned()).collect();
+        let mut names = HashSet::new();
+        let mut references = Vec::new();
+        for reference in self.references()?.all()? {
+            let reference = reference?.detach();
+            names.insert(reference.name.as_bstr().to_owned());
+            if patterns.is_empty()
+                || patterns
+                    .iter()
+                    .any(|pattern| matches_pattern(pattern.as_ref(), reference.name.as_bstr()))
+            {
+                references.push(reference);
+            }
+        }
+        references.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let formatter = Formatter {
+            repo: self,
+            names,
+            head_name: self.head_name()?,
+            color: options.color,
+        };
+        let mut lines = Vec::with_capacity(references.len());
+        for reference in &references {
+            let mut subject = Subject {
+                reference,
+                id: None,
+                object: None,
+                peeled: None,
+            };
+            let mut line = BString::default();
+            formatter.render(&mut subject, &format.items, &mut line)?;
+            lines.push(line);
+        }
+        Ok(Outcome { lines })
+    }
+}
+
+/// Match `name` against `pattern` like `git for-each-ref` does.
+fn matches_pattern(pattern: &BStr, name: &BStr) -> bool {
+    if gix_glob::wildmatch(pattern, name, gix_glob::wildmatch::Mode::empty()) {
+        return true;
+    }
+    matches!(
+        name.strip_prefix(pattern.as_bytes()),
+        Some(rest) if rest.is_empty() || rest.starts_with(b"/") || pattern.ends_with(b"/")
+    )
+}
+
+/// The reference to format along with the objects it points to, which are looked up on demand.
+struct Subject<'a, 'repo> {
+    reference: &'a gix_ref::Reference,
+    /// The id the reference points to after following symbolic references, or `None` if it is dangling.
+    id: Option<Option<ObjectId>>,
+    object: Option<Option<Object<'repo>>>,
+    /// The object after peeling tags, or `None` if the reference doesn't point to a tag.
+    peeled: Option<Option<Object<'repo>>>,
+}
+
+struct Formatter<'repo> {
+    repo: &'repo Repository,
+    /// The names of all references, to determine if shortened names are ambiguous.
+    names: HashSet<BString>,
+    head_name: Option<FullName>,
+    color: bool,
+}
+
+/// The prefixes `git rev-parse` tries in order to turn a short name into a reference name.
+/// `refs/remotes/<name>/HEAD` is left out as `git` doesn't shorten references with it.
+const NAME_PREFIXES: &[&str] = &["", "refs/", "refs/tags/", "refs/heads/", "refs/remotes/"];
+
+impl<'repo> Formatter<'repo> {
+    fn render(&self, subject: &mut Subject<'_, 'repo>, items: &[Item], out: &mut BString) -> Result<(), Error> {
+        for item in items {
+            match item {
+                Item::Literal(literal) => out.push_str(literal),
+                Item::Color(escape) => {
+                    if self.color {
+                        out.push_str(escape);
+                    }
+                }
+                Item::Align { width, position, items } => {
+                    let mut aligned = BString::default();
+                    self.render(subject, items, &mut aligned)?;
+                    out.push_str(pad(aligned, *width, *position));
+                }
+                Item::If {
+                    condition,
+                    test,
+                    then,
+                    otherwise,
+                } => {
+                    let mut value = BString::default();
+                    self.render(subject, test, &mut value)?;
+                    let holds = match condition {
+                        Condition::NotEmpty => !value.trim().is_empty(),
+                        Condition::Equals(expected) => value == *expected,
+                        Condition::NotEquals(expected) => value != *expected,
+                    };
+                    self.render(subject, if holds { then } else { otherwise }, out)?;
+                }
+                Item::Atom { deref, atom } => self.render_atom(subject, *deref, *atom, out)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn render_atom(
+        &self,
+        subject: &mut Subject<'_, 'repo>,
+        deref: bool,
+        atom: Atom,
+        out: &mut BString,
+    ) -> Result<(), Error> {
+        let reference = subject.reference;
+        match atom {
+            Atom::RefName(name) => self.write_name(reference.name.as_ref(), name, out)?,
+            Atom::SymRef(name) => {
+                if let Target::Symbolic(target) = &reference.target {
+                    self.write_name(target.as_ref(), name, out)?;
+                }
+            }
+            Atom::Head => out.push(if self.head_name.as_ref() == Some(&reference.name) {
+                b'*'
+            } else {
+                b' '
+            }),
+            Atom::Tracking(direction, tracking) => self.write_tracking(subject, direction, tracking, out)?,
+            _ => {
+                let Some(object) = self.object(subject, deref)? else {
+                    return Ok(());
+                };
+                self.write_object_atom(object, atom, out)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn object<'s>(&self, subject: &'s mut Subject<'_, 'repo>, deref: bool) -> Result<Option<&'s Object<'repo>>, Error> {
+        if subject.id.is_none() {
+            subject.id = Some(self.resolve(subject.reference)?);
+        }
+        if subject.object.is_none() {
+            subject.object = Some(match subject.id.flatten() {
+                Some(id) => Some(self.repo.find_object(id)?),
+                None => None,
+            });
+        }
+        let object = subject.object.as_ref().and_then(Option::as_ref);
+        if !deref {
+            return Ok(object);
+        }
+        if subject.peeled.is_none() {
+            subject.peeled = Some(match object {
+                Some(object) if object.kind == gix_object::Kind::Tag => Some(object.clone().peel_tags_to_end()?),
+                _ => None,
+            });
+        }
+        Ok(subject.peeled.as_ref().and_then(Option::as_ref))
+    }
+
+    /// Follow symbolic references starting at `reference` to the id they point to, or `None` if they are dangling.
+    fn resolve(&self, reference: &gix_ref::Reference) -> Result<Option<ObjectId>, Error> {
+        const MAX_REF_DEPTH: usize = 5;
+        let mut target = reference.target.clone();
+        for _ in 0..MAX_REF_DEPTH {
+            match target {
+                Target::Peeled(id) => return Ok(Some(id)),
+                Target::Symbolic(name) => match self.repo.try_find_reference(&name)? {
+                    Some(next) => target = next.inner.target,
+                    None => return Ok(None),
+                },
+            }
+        }
+        Ok(None)
+    }
+
+    fn write_object_atom(&self, object: &Object<'repo>, atom: Atom, out: &mut BString) -> Result<(), Error> {
+        match atom {
+            Atom::ObjectName(format) => self.write_id(object.id, format, out)?,
+            Atom::ObjectType => out.push_str(object.kind.as_bytes()),
+            Atom::ObjectSize => out.push_str(object.data.len().to_string()),
+            Atom::Tree(_) | Atom::Parent(_) | Atom::NumParent if object.kind == gix_object::Kind::Commit => {
+                let commit = gix_object::CommitRef::from_bytes(&object.data)?;
+                match atom {
+                    Atom::Tree(format) => self.write_id(commit.tree(), format, out)?,
+                    Atom::Parent(format) => {
+                        for (index, parent) in commit.parents().enumerate() {
+                            if index != 0 {
+                                out.push(b' ');
+                            }
+                            self.write_id(parent, format, out)?;
+                        }
+                    }
+                    _ => out.push_str(commit.parents.len().to_string()),
+                }
+            }
+            Atom::Object(_) | Atom::Type | Atom::Tag if object.kind == gix_object::Kind::Tag => {
+                let tag = gix_object::TagRef::from_bytes(&object.data)?;
+                match atom {
+                    Atom::Object(format) => self.write_id(tag.target(), format, out)?,
+                    Atom::Type => out.push_str(tag.target_kind.as_bytes()),
+                    _ => out.push_str(tag.name),
+                }
+            }
+            Atom::Person(role, person) => {
+                let signature = match object.kind {
+                    gix_object::Kind::Commit => match role {
+                        Role::Author => Some(gix_object::CommitRef::from_bytes(&object.data)?.author),
+                        Role::Committer | Role::Creator => {
+                            Some(gix_object::CommitRef::from_bytes(&object.data)?.committer)
+                        }
+                        Role::Tagger => None,
+                    },
+                    gix_object::Kind::Tag => match role {
+                        Role::Tagger | Role::Creator => gix_object::TagRef::from_bytes(&object.data)?.tagger,
+                        Role::Author | Role::Committer => None,
+                    },
+                    gix_object::Kind::Tree | gix_object::Kind::Blob => None,
+                };
+                if let Some(signature) = signature {
+                    write_person(signature, person, out);
+                }
+            }
+            Atom::Contents(contents) if matches!(object.kind, gix_object::Kind::Commit | gix_object::Kind::Tag) => {
+                write_contents(object.data.as_bstr(), contents, out);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn write_id(&self, id: impl Into<ObjectId>, format: Id, out: &mut BString) -> Result<(), Error> {
+        let id = id.into();
+        match format {
+            Id::Full => out.push_str(id.to_hex().to_string()),
+            Id::Short(None) => out.push_str(crate::Id::from_id(id, self.repo).shorten()?.to_string()),
+            Id::Short(Some(hex_len)) => {
+                let hex_len = hex_len.clamp(4, id.kind().len_in_hex());
+                let candidate = gix_odb::store::prefix::disambiguate::Candidate::new(id, hex_len)
+                    .expect("BUG: hex-len is always valid");
+                let prefix = self
+                    .repo
+                    .objects
+                    .disambiguate_prefix(candidate)?
+                    .ok_or(crate::id::shorten::Error::NotFound { oid: id })?;
+                out.push_str(prefix.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn write_name(&self, full_name: &FullNameRef, format: Name, out: &mut BString) -> Result<(), Error> {
+        let name = full_name.as_bstr();
+        match format {
+            Name::Full => out.push_str(name),
+            Name::Short => out.push_str(self.shorten(name)?),
+            Name::Lstrip(count) | Name::Rstrip(count) => {
+                let components: Vec<_> = name.split_str("/").collect();
+                let remove = if count < 0 {
+                    components.len().saturating_sub(count.unsigned_abs())
+                } else {
+                    (count as usize).min(components.len())
+                };
+                let kept = if matches!(format, Name::Lstrip(_)) {
+                    &components[remove..]
+                } else {
+                    &components[..components.len() - remove]
+                };
+                out.push_str(kept.join(&b"/"[..]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the shortest version of `name` that doesn't also refer to another reference, like `git` does in strict mode.
+    fn shorten<'a>(&self, name: &'a BStr) -> Result<&'a BStr, Error> {
+        'prefixes: for (index, prefix) in NAME_PREFIXES.iter().enumerate().skip(1).rev() {
+            let Some(short) = name.strip_prefix(prefix.as_bytes()).filter(|short| !short.is_empty()) else {
+                continue;
+            };
+            for (other_index, other_prefix) in NAME_PREFIXES.iter().enumerate() {
+                if other_index == index {
+                    continue;
+                }
+                let mut candidate = BString::from(*other_prefix);
+                candidate.push_str(short);
+                if self.exists(candidate.as_ref())? {
+                    continue 'prefixes;
+                }
+            }
+            return Ok(short.as_bstr());
+        }
+        Ok(name)
+    }
+
+    fn exists(&self, name: &BStr) -> Result<bool, Error> {
+        if self.names.contains(name) {
+            return Ok(true);
+        }
+        if name.starts_with(b"refs/") {
+            return Ok(false);
+        }
+        let Ok(partial) = <&gix_ref::PartialNameRef>::try_from(name) else {
+            return Ok(false);
+        };
+        // Lookups are performed with the usual rules, so only an exact match means that the reference exists.
+        Ok(matches!(
+            self.repo.try_find_reference(partial)?,
+            Some(reference) if reference.name().as_bstr() == name
+        ))
+    }
+
+    fn write_tracking(
+        &self,
+        subject: &mut Subject<'_, 'repo>,
+        direction: remote::Direction,
+        tracking: Tracking,
+        out: &mut BString,
+    ) -> Result<(), Error> {
+        let name = subject.reference.name.as_ref();
+        if name.category() != Some(gix_ref::Category::LocalBranch) {
+            return Ok(());
+        }
+        match tracking {
+            Tracking::RemoteName => {
+                if let Some(remote_name) = self.repo.branch_remote_name(name.shorten(), direction) {
+                    out.push_str(remote_name.as_bstr());
+                }
+            }
+            Tracking::RemoteRef => {
+                if let Some(remote_ref) = self.repo.branch_remote_ref_name(name, direction).transpose()? {
+                    out.push_str(remote_ref.as_bstr());
+                }
+            }
+            Tracking::Name(format) => {
+                if let Some(tracking_ref) = self.repo.branch_remote_tracking_ref_name(name, direction).transpose()? {
+                    self.write_name(tracking_ref.as_ref(), format, out)?;
+                }
+            }
+            Tracking::Track { .. } | Tracking::TrackShort => {
+                let Some(tracking_ref) = self.repo.branch_remote_tracking_ref_name(name, direction).transpose()? else {
+                    return Ok(());
+                };
+                let upstream = if self.names.contains(tracking_ref.as_bstr()) {
+                    self.repo
+                        .try_find_reference(tracking_ref.as_ref())?
+                        .map(|upstream| self.resolve(&upstream.detach()))
+                        .transpose()?
+                        .flatten()
+                } else {
+                    None
+                };
+                if subject.id.is_none() {
+                    subject.id = Some(self.resolve(subject.reference)?);
+                }
+                let (Some(upstream), Some(local)) = (upstream, subject.id.flatten()) else {
+                    if let Tracking::Track { brackets } = tracking {
+                        out.push_str(if brackets { "[gone]" } else { "gone" });
+                    }
+                    return Ok(());
+                };
+                let (ahead, behind) = self.repo.ahead_behind(local, upstream)?;
+                match tracking {
+                    Tracking::Track { brackets } => {
+                        let counts = match (ahead, behind) {
+                            (0, 0) => return Ok(()),
+                            (ahead, 0) => format!("ahead {ahead}"),
+                            (0, behind) => format!("behind {behind}"),
+                            (ahead, behind) => format!("ahead {ahead}, behind {behind}"),
+                        };
+                        if brackets {
+                            out.push_str(format!("[{counts}]"));
+                        } else {
+                            out.push_str(counts);
+                        }
+                    }
+                    _ => out.push_str(match (ahead, behind) {
+                        (0, 0) => "=",
+                        (_, 0) => ">",
+                        (0, _) => "<",
+                        _ => "<>",
+                    }),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_person(signature: gix_actor::SignatureRef<'_>, person: Person, out: &mut BString) {
+    match person {
+        Person::Full => signature.write_to(&mut **out).expect("writing to memory never fails"),
+        Person::Name => out.push_str(signature.name),
+        Person::Email(Email::Full) => {
+            out.push(b'<');
+            out.push_str(signature.email);
+            out.push(b'>');
+        }
+        Person::Email(Email::Trim) => out.push_str(signature.email),
+        Person::Email(Email::LocalPart) => out.push_str(
+            signature
+                .email
+                .find_byte(b'@')
+                .map_or(signature.email.as_bytes(), |at| &signature.email[..at]),
+        ),
+        Person::Date(date) => {
+            use gix_date::time::format;
+            let time = signature.time;
+            out.push_str(match date {
+                Date::Default => time.format(format::DEFAULT),
+                Date::Short => time.format(format::SHORT),
+                Date::Iso => time.format(format::ISO8601),
+                Date::IsoStrict => time.format(format::ISO8601_STRICT),
+                Date::Rfc => time.format(format::GIT_RFC2822),
+                Date::Raw => time.format(format::RAW),
+                Date::Unix => time.format(format::UNIX),
+            });
+        }
+    }
+}
+
+/// Write the part of the message of the commit or tag in `data` selected by `contents`, splitting it like `git` does.
+fn write_contents(data: &BStr, contents: Contents, out: &mut BString) {
+    let message = match data.find(b"\n\n") {
+        Some(pos) => {
+            let message = &data[pos + 2..];
+            &message[message.iter().take_while(|b| **b == b'\n').count()..]
+        }
+        None => &data[data.len()..],
+    };
+    let signature_start = {
+        let mut start = message.len();
+        let mut line_start = 0;
+        for line in message.lines_with_terminator() {
+            if [
+                &b"-----BEGIN PGP SIGNATURE-----"[..],
+                b"-----BEGIN PGP MESSAGE-----",
+                b"-----BEGIN SIGNED MESSAGE-----",
+                b"-----BEGIN SSH SIGNATURE-----",
+            ]
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
+            {
+                start = line_start;
+            }
+            line_start += line.len();
+        }
+        start
+    };
+    let subject_end = match message.find(b"\n\n").or_else(|| message.find(b"\r\n\r\n")) {
+        Some(end) => end.min(signature_start),
+        None => message.find(b"\r\n").unwrap_or(signature_start),
+    };
+    let subject = message[..subject_end].trim_end_with(|c| c == '\n' || c == '\r');
+    let body_start = subject_end
+        + message[subject_end..]
+            .iter()
+            .take_while(|b| matches!(b, b'\n' | b'\r'))
+            .count();
+
+    match contents {
+        Contents::All => out.push_str(message),
+        Contents::Subject => {
+            let mut bytes = subject.iter().peekable();
+            while let Some(byte) = bytes.next() {
+                match byte {
+                    b'\r' if bytes.peek() == Some(&&b'\n') => {}
+                    b'\n' => out.push(b' '),
+                    byte => out.push(*byte),
+                }
+            }
+        }
+        Contents::Body => out.push_str(&message[body_start..signature_start.max(body_start)]),
+        Contents::BodyAndSignature => out.push_str(&message[body_start..]),
+        Contents::Signature => out.push_str(&message[signature_start..]),
+    }
+}
@@ -0,0 +1,662 @@
+use std::io::Write;
+
+use gix_diff::patch::{BinaryHunk, BinaryKind, Content, Operation};
+use gix_hash::ObjectId;
+use gix_object::tree::EntryMode;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    format_patch,
+    object::tree::diff::change::Event,
+    Repository,
+};
+
+/// Format patches
+impl Repository {
+    /// Turn each non-merge commit in `range` into a mail containing its patch, oldest first, similar to `git format-patch`.
+    ///
+    /// `range` is a revision specification like `main..topic`, or a single revision like `main` to format all commits
+    /// reachable from `HEAD` but not from it, or `topic^!` to format a single commit.
+    ///
+    /// Each mail has the author and date of its commit in its headers, the commit message as subject and body, and a diffstat
+    /// followed by the patch in the format of `git diff`, including renames, mode changes and binary data.
+    /// Pass the [mails](format_patch::Patch::mail) to [`am()`](Self::am()) to apply them.
+    ///
+    /// ### Deviation
+    ///
+    /// * Binary data is always encoded as literal, and may compress differently than with `git`.
+    /// * Diff attributes are only used to [detect binary files](crate::binary::Purpose::Diff), `diff.noprefix` isn't respected,
+    ///   and paths aren't quoted in the diffstat.
+    pub fn format_patch(
+        &self,
+        range: impl AsRef<BStr>,
+        options: format_patch::Options,
+    ) -> Result<Vec<format_patch::Patch>, format_patch::Error> {
+        let _span = gix_trace::coarse!("gix::format_patch()", options = ?options);
+        let commits = self.commits_in_range(range.as_ref())?;
+        let total = options.start_number + commits.len().saturating_sub(1);
+        let numbered = options.numbered.unwrap_or(commits.len() > 1);
+        commits
+            .into_iter()
+            .enumerate()
+            .map(|(idx, commit)| {
+                let number = options.start_number + idx;
+                self.format_patch_mail(commit, number, numbered.then_some(total), &options)
+            })
+            .collect()
+    }
+}
+
+/// A changed file as shown in the diffstat.
+pub(crate) struct Stat {
+    name: BString,
+    added: usize,
+    deleted: usize,
+    binary: bool,
+}
+
+/// Utilities
+impl Repository {
+    /// Return all non-merge commits in `range`, oldest first, where `range` is interpreted like
+    /// [`format_patch()`](Self::format_patch()) does.
+    pub(crate) fn commits_in_range(&self, range: &BStr) -> Result<Vec<ObjectId>, format_patch::Error> {
+        let (hidden, tip) = match self.rev_parse(range)?.detach() {
+            gix_revision::Spec::Include(since) | gix_revision::Spec::Exclude(since) => {
+                (vec![since], self.head_id()?.detach())
+            }
+            gix_revision::Spec::Range { from, to } => (vec![from], to),
+            gix_revision::Spec::ExcludeParents(commit) => {
+                let parents = self
+                    .find_object(commit)?
+                    .peel_to_kind(gix_object::Kind::Commit)?
+                    .into_commit()
+                    .parent_ids()
+                    .map(crate::Id::detach)
+                    .collect();
+                (parents, commit)
+            }
+            gix_revision::Spec::Merge { .. } | gix_revision::Spec::IncludeOnlyParents(_) => {
+                return Err(format_patch::Error::UnsupportedSpec { spec: range.into() })
+            }
+        };
+
+        let mut hidden_commits = gix_hashtable::HashSet::default();
+        for info in self.rev_walk(hidden).all()? {
+            hidden_commits.insert(info?.id);
+        }
+        let mut commits = Vec::new();
+        for info in self.rev_walk([tip]).selected(move |id| !hidden_commits.contains(id))? {
+            let info = info?;
+            if info.parent_ids.len() <= 1 {
+                commits.push(info.id);
+            }
+        }
+        commits.reverse();
+        Ok(commits)
+    }
+
+    fn format_patch_mail(
+        &self,
+        id: ObjectId,
+        number: usize,
+        total: Option<usize>,
+        options: &format_patch::Options,
+    ) -> Result<format_patch::Patch, format_patch::Error> {
+        let commit = self
+            .find_object(id)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let decoded = commit.decode()?;
+        let message = decoded.message();
+        let title = message.summary();
+        let title = title.as_bytes();
+        let body = message.body.map(|body| body.trim_end()).unwrap_or_default();
+        let non_ascii = |text: &[u8]| !text.is_ascii();
+
+        let mut mail = Vec::<u8>::new();
+        writeln!(mail, "From {id} Mon Sep 17 00:00:00 2001")?;
+        mail.push_str("From: ");
+        let name = decoded.author.name;
+        if non_ascii(name) || name.contains_str("=?") {
+            write_rfc2047(&mut mail, name, true);
+        } else if name.iter().any(|b| b"()<>@,;:\\\".[]".contains(b)) {
+            mail.push(b'"');
+            for &b in name.iter() {
+                if b == b'"' || b == b'\\' {
+                    mail.push(b'\\');
+                }
+                mail.push(b);
+            }
+            mail.push(b'"');
+        } else {
+            mail.push_str(name);
+        }
+        writeln!(mail, " <{}>", decoded.author.email)?;
+        writeln!(
+            mail,
+            "Date: {}",
+            decoded.author.time.format(gix_date::time::format::GIT_RFC2822)
+        )?;
+
+        mail.push_str("Subject: ");
+        let prefix = match total {
+            Some(total) => {
+                let width = total.to_string().len();
+                let space = if options.subject_prefix.is_empty() { "" } else { " " };
+                format!("[{}{space}{number:0width$}/{total}] ", options.subject_prefix)
+            }
+            None if options.subject_prefix.is_empty() => String::new(),
+            None => format!("[{}] ", options.subject_prefix),
+        };
+        mail.push_str(&prefix);
+        if non_ascii(title) || title.contains_str("=?") {
+            write_rfc2047(&mut mail, title, false);
+        } else {
+            write_wrapped(&mut mail, title, "Subject: ".len() + prefix.len());
+        }
+        mail.push(b'\n');
+        if non_ascii(decoded.message) || non_ascii(name) {
+            mail.push_str(
+                "MIME-Version: 1.0\nContent-Type: text/plain; charset=UTF-8\nContent-Transfer-Encoding: 8bit\n",
+            );
+        }
+        mail.push(b'\n');
+        if !body.is_empty() {
+            mail.push_str(body);
+            mail.push(b'\n');
+        }
+        mail.push_str("---\n");
+
+        let parent_tree = match decoded.parents().next() {
+            Some(parent) => self
+                .find_object(parent)?
+                .peel_to_kind(gix_object::Kind::Commit)?
+                .into_commit()
+                .tree()?,
+            None => self.empty_tree(),
+        };
+        let (files, stats) = self.format_patch_diff(&parent_tree, &commit.tree()?, options.context_lines)?;
+        write_stat(&mut mail, &stats, options.stat_width)?;
+        for file in &files {
+            let (old_mode, new_mode) = (file.old_mode.map(|m| m.0), file.new_mode.map(|m| m.0));
+            match file.operation {
+                Operation::Addition => writeln!(
+                    mail,
+                    " create mode {:06o} {}",
+                    new_mode.unwrap_or_default(),
+                    file.path()
+                )?,
+                Operation::Deletion => writeln!(
+                    mail,
+                    " delete mode {:06o} {}",
+                    old_mode.unwrap_or_default(),
+                    file.path()
+                )?,
+                Operation::Rename | Operation::Copy => {
+                    let kind = if file.operation == Operation::Rename {
+                        "rename"
+                    } else {
+                        "copy"
+                    };
+                    let name = rename_name(file.old_path.as_ref().expect("set for renames").as_ref(), file.path());
+                    writeln!(mail, " {kind} {name} ({}%)", file.similarity.unwrap_or(100))?;
+                }
+                Operation::Modification => {}
+            }
+            if let (Some(old_mode), Some(new_mode)) = (old_mode, new_mode) {
+                if old_mode != new_mode {
+                    write!(mail, " mode change {old_mode:06o} => {new_mode:06o}")?;
+                    if file.operation == Operation::Modification {
+                        write!(mail, " {}", file.path())?;
+                    }
+                    mail.push(b'\n');
+                }
+            }
+        }
+        mail.push(b'\n');
+        for file in &files {
+            file.write_to(&mut mail, self.quote_path())?;
+        }
+        if let Some(signature) = &options.signature {
+            writeln!(mail, "-- \n{signature}\n")?;
+        }
+
+        let mut file_name = format!("{number:04}-");
+        let start = file_name.len();
+        let mut separate = false;
+        let mut bytes = title.iter().copied().peekable();
+        while let Some(b) = bytes.next() {
+            if b.is_ascii_alphanumeric() || b == b'.' || b == b'_' {
+                if separate && file_name.len() > start {
+                    file_name.push('-');
+                }
+                separate = false;
+                file_name.push(b as char);
+                if b == b'.' {
+                    while bytes.peek() == Some(&b'.') {
+                        bytes.next();
+                    }
+                }
+            } else {
+                separate = true;
+            }
+        }
+        file_name.truncate(
+            file_name
+                .trim_end_matches(['.', '-'])
+                .len()
+                .max(start)
+                .min(64 - ".patch".len() - 1),
+        );
+        file_name.push_str(".patch");
+        Ok(format_patch::Patch {
+            commit: id,
+            file_name,
+            mail: mail.into(),
+        })
+    }
+
+    /// Return the patches and the diffstat entries of all files that changed between `old_tree` and `new_tree`, sorted by path.
+    ///
+    /// Files are binary according to the attributes in the worktree and the index, if present, or their content.
+    pub(crate) fn format_patch_diff(
+        &self,
+        old_tree: &crate::Tree<'_>,
+        new_tree: &crate::Tree<'_>,
+        context_lines: u32,
+    ) -> Result<(Vec<gix_diff::patch::File>, Vec<Stat>), format_patch::Error> {
+        struct Side {
+            path: BString,
+            mode: EntryMode,
+            id: ObjectId,
+        }
+        let mut changes = Vec::<(Option<Side>, Option<Side>, Option<u8>, bool)>::new();
+        old_tree
+            .changes()?
+            .track_path()
+            .for_each_to_obtain_tree(new_tree, |change| {
+                let side = |path: &BStr, mode: EntryMode, id: crate::Id<'_>| Side {
+                    path: path.to_owned(),
+                    mode,
+                    id: id.detach(),
+                };
+                let path = change.location;
+                match change.event {
+                    _ if change.event.entry_mode().is_tree() => {}
+                    Event::Addition { entry_mode, id } => {
+                        changes.push((None, Some(side(path, entry_mode, id)), None, false))
+                    }
+                    Event::Deletion { entry_mode, id } => {
+                        changes.push((Some(side(path, entry_mode, id)), None, None, false))
+                    }
+                    Event::Modification {
+                        previous_entry_mode,
+                        previous_id,
+                        entry_mode,
+                        id,
+                    } => {
+                        let (old, new) = (side(path, previous_entry_mode, previous_id), side(path, entry_mode, id));
+                        if kind_of(previous_entry_mode) == kind_of(entry_mode) {
+                            changes.push((Some(old), Some(new), None, false));
+                        } else {
+                            changes.push((Some(old), None, None, false));
+                            changes.push((None, Some(new), None, false));
+                        }
+                    }
+                    Event::Rewrite {
+                        source_location,
+                        source_entry_mode,
+                        source_id,
+                        diff,
+                        entry_mode,
+                        id,
+                        copy,
+                    } => {
+                        let similarity = diff.map_or(100, |diff| (diff.similarity * 100.0) as u8);
+                        changes.push((
+                            Some(side(source_location, source_entry_mode, source_id)),
+                            Some(side(path, entry_mode, id)),
+                            Some(similarity),
+                            copy,
+                        ));
+                    }
+                }
+                Ok::<_, std::convert::Infallible>(crate::object::tree::diff::Action::Continue)
+            })?;
+        changes.sort_by(|a, b| {
+            let path = |change: &(Option<Side>, Option<Side>, Option<u8>, bool)| {
+                change.1.as_ref().or(change.0.as_ref()).map(|side| side.path.clone())
+            };
+            path(a).cmp(&path(b))
+        });
+
+        let diff_options = gix_diff::patch::create::Options {
+            context_lines,
+            algorithm: self.config.diff_algorithm()?,
+        };
+        let attributes_source = if self.work_dir().is_some() {
+            gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping
+        } else {
+            gix_worktree::stack::state::attributes::Source::IdMapping
+        };
+        let mut detector =
+            self.binary_detector(&*self.index_or_empty()?, attributes_source, crate::binary::Purpose::Diff)?;
+        let mut files = Vec::new();
+        let mut stats = Vec::new();
+        for (old, new, similarity, copy) in changes {
+            let data = |side: &Option<Side>| -> Result<Vec<u8>, format_patch::Error> {
+                Ok(match side {
+                    Some(side) if side.mode.is_commit() => format!("Subproject commit {}\n", side.id).into_bytes(),
+                    Some(side) => self.find_object(side.id)?.detach().data,
+                    None => Vec::new(),
+                })
+            };
+            let (old_data, new_data) = (data(&old)?, data(&new)?);
+            let mut is_binary = |side: &Option<Side>, data: &[u8]| -> std::io::Result<bool> {
+                Ok(match side {
+                    Some(side) if !side.mode.is_commit() => {
+                        detector.is_binary(side.path.as_ref(), Some(side.mode.into()), data)?
+                    }
+                    _ => false,
+                })
+            };
+            let binary = is_binary(&old, &old_data)? || is_binary(&new, &new_data)?;
+            let id = |side: &Option<Side>| {
+                side.as_ref().map(|side| {
+                    if binary {
+                        side.id.to_string()
+                    } else {
+                        crate::Id::from_id(side.id, self).shorten_or_id().to_string()
+                    }
+                })
+            };
+            let (old_id, new_id) = match (id(&old), id(&new)) {
+                (Some(old_id), Some(new_id)) => (old_id, new_id),
+                (Some(old_id), None) => (old_id.clone(), "0".repeat(old_id.len())),
+                (None, Some(new_id)) => ("0".repeat(new_id.len()), new_id),
+                (None, None) => unreachable!("at least one side is always set"),
+            };
+            let operation = match (&old, &new, similarity) {
+                (None, _, _) => Operation::Addition,
+                (_, None, _) => Operation::Deletion,
+                (_, _, Some(_)) if copy => Operation::Copy,
+                (_, _, Some(_)) => Operation::Rename,
+                (_, _, None) => Operation::Modification,
+            };
+            let content_changed = old_data != new_data;
+            let content = if !content_changed
+                && matches!(operation, Operation::Modification | Operation::Rename | Operation::Copy)
+            {
+                Content::Text(Vec::new())
+            } else if binary {
+                let literal = |data: &[u8]| -> std::io::Result<BinaryHunk> {
+                    let mut compressed = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+                    compressed.write_all(data)?;
+                    compressed.flush()?;
+                    Ok(BinaryHunk {
+                        kind: BinaryKind::Literal,
+                        size: data.len() as u64,
+                        data: compressed.into_inner(),
+                    })
+                };
+                Content::Binary {
+                    forward: literal(&new_data)?,
+                    reverse: Some(literal(&old_data)?),
+                }
+            } else {
+                Content::Text(gix_diff::patch::create::hunks(&old_data, &new_data, diff_options))
+            };
+
+            let stat = match &content {
+                Content::Text(hunks) => {
+                    let lines = hunks.iter().flat_map(|hunk| hunk.lines.iter());
+                    let (mut added, mut deleted) = (0, 0);
+                    for line in lines {
+                        match line {
+                            gix_diff::patch::Line::Addition(_) => added += 1,
+                            gix_diff::patch::Line::Removal(_) => deleted += 1,
+                            gix_diff::patch::Line::Context(_) => {}
+                        }
+                    }
+                    (added, deleted, false)
+                }
+                _ => (new_data.len(), old_data.len(), true),
+            };
+            let path = |side: &Option<Side>| side.as_ref().map(|side| side.path.clone());
+            let file = gix_diff::patch::File {
+                operation,
+                old_path: path(&old),
+                new_path: path(&new),
+                old_mode: old.as_ref().map(|side| side.mode),
+                new_mode: new.as_ref().map(|side| side.mode),
+                old_id: content_changed.then(|| old_id.into()),
+                new_id: content_changed.then(|| new_id.into()),
+                similarity,
+                content,
+            };
+            stats.push(Stat {
+                name: match &file.old_path {
+                    Some(old_path) if similarity.is_some() => rename_name(old_path.as_ref(), file.path()),
+                    _ => file.path().to_owned(),
+                },
+                added: stat.0,
+                deleted: stat.1,
+                binary: stat.2,
+            });
+            files.push(file);
+        }
+        Ok((files, stats))
+    }
+}
+
+/// Return a number that is the same for entry modes whose changes can be shown as a modification.
+fn kind_of(mode: EntryMode) -> u8 {
+    if mode.is_commit() {
+        0
+    } else if mode.is_link() {
+        1
+    } else {
+        2
+    }
+}
+
+/// Write the diffstat of `stats` with lines no wider than `width` where possible, along with the summary line,
+/// like `git diff --stat` does.
+fn write_stat(out: &mut Vec<u8>, stats: &[Stat], width: usize) -> std::io::Result<()> {
+    let decimal_width = |n: usize| n.to_string().len();
+    let max_len = stats.iter().map(|s| s.name.chars().count()).max().unwrap_or(0);
+    let max_change = stats
+        .iter()
+        .filter(|s| !s.binary)
+        .map(|s| s.added + s.deleted)
+        .max()
+        .unwrap_or(0);
+    let bin_width = stats
+        .iter()
+        .filter(|s| s.binary)
+        .map(|s| 14 + decimal_width(s.added) + decimal_width(s.deleted))
+        .max()
+        .unwrap_or(0);
+    let number_width = decimal_width(max_change).max(if bin_width > 0 { 3 } else { 0 });
+
+    let width = width.max(16 + 6 + number_width);
+    let mut graph_width = if max_change + 4 > bin_width {
+        max_change
+    } else {
+        bin_width - 4
+    };
+    let mut name_width = max_len;
+    if name_width + number_width + 6 + graph_width > width {
+        if graph_width + number_width + 6 > width * 3 / 8 {
+            graph_width = (width * 3 / 8).saturating_sub(number_width + 6).max(6);
+        }
+        if name_width + number_width + 6 + graph_width > width {
+            name_width = width.saturating_sub(number_width + 6 + graph_width);
+        } else {
+            graph_width = width - number_width - 6 - name_width;
+        }
+    }
+
+    let scale = |it: usize| {
+        if it == 0 {
+            0
+        } else {
+            1 + it * (graph_width - 1) / max_change
+        }
+    };
+    let (mut insertions, mut deletions) = (0, 0);
+    for stat in stats {
+        let chars: Vec<char> = stat.name.chars().collect();
+        let (prefix, name) = if chars.len() > name_width {
+            let keep = name_width.saturating_sub(3);
+            let name: String = chars[chars.len() - keep..].iter().collect();
+            let name = match name.find('/') {
+                Some(slash) => name[slash..].to_owned(),
+                None => name,
+            };
+            ("...", name)
+        } else {
+            ("", chars.iter().collect())
+        };
+        let padding = (name_width - prefix.len()).saturating_sub(name.chars().count());
+        write!(out, " {prefix}{name}{:padding$} | ", "")?;
+        if stat.binary {
+            write!(out, "{:>number_width$}", "Bin")?;
+            if stat.added != 0 || stat.deleted != 0 {
+                write!(out, " {} -> {} bytes", stat.deleted, stat.added)?;
+            }
+            out.push(b'\n');
+            continue;
+        }
+        insertions += stat.added;
+        deletions += stat.deleted;
+        let (mut add, mut del) = (stat.added, stat.deleted);
+        if graph_width <= max_change {
+            let mut total = scale(add + del);
+            if total < 2 && add != 0 && del != 0 {
+                total = 2;
+            }
+            if add < del {
+                add = scale(add);
+                del = total - add;
+            } else {
+                del = scale(del);
+                add = total - del;
+            }
+        }
+        let changes = stat.added + stat.deleted;
+        write!(out, "{changes:>number_width$}")?;
+        if changes != 0 {
+            write!(out, " {}{}", "+".repeat(add), "-".repeat(del))?;
+        }
+        out.push(b'\n');
+    }
+
+    if stats.is_empty() {
+        out.push_str(" 0 files changed\n");
+        return Ok(());
+    }
+    let plural = |n: usize, word: &str| if n == 1 { word.to_owned() } else { format!("{word}s") };
+    write!(out, " {} {} changed", stats.len(), plural(stats.len(), "file"))?;
+    if insertions != 0 || deletions == 0 {
+        write!(out, ", {insertions} {}(+)", plural(insertions, "insertion"))?;
+    }
+    if deletions != 0 || insertions == 0 {
+        write!(out, ", {deletions} {}(-)", plural(deletions, "deletion"))?;
+    }
+    out.push(b'\n');
+    Ok(())
+}
+
+/// Return the name of a file renamed from `old` to `new` like `git` shows it, with the common leading and trailing path
+/// components factored out, like `dir/{old => new}/file`.
+fn rename_name(old: &BStr, new: &BStr) -> BString {
+    let mut prefix = 0;
+    for (idx, (a, b)) in old.iter().zip(new.iter()).enumerate() {
+        if a != b {
+            break;
+        }
+        if *a == b'/' {
+            prefix = idx + 1;
+        }
+    }
+    let mut suffix = 0;
+    let adjust = usize::from(prefix > 0);
+    let (mut a, mut b) = (old.len(), new.len());
+    while a + adjust > prefix && b + adjust > prefix && a > 0 && b > 0 && old[a - 1] == new[b - 1] {
+        a -= 1;
+        b -= 1;
+        if old[a] == b'/' {
+            suffix = old.len() - a;
+        }
+    }
+    let old_mid = &old[prefix..old.len().saturating_sub(suffix).max(prefix)];
+    let new_mid = &new[prefix..new.len().saturating_sub(suffix).max(prefix)];
+
+    let mut out = BString::default();
+    if prefix + suffix > 0 {
+        out.push_str(&old[..prefix]);
+        out.push(b'{');
+    }
+    out.push_str(old_mid);
+    out.push_str(" => ");
+    out.push_str(new_mid);
+    if prefix + suffix > 0 {
+        out.push(b'}');
+        out.push_str(&old[old.len() - suffix..]);
+    }
+    out
+}
+
+/// Append `text` as RFC 2047 encoded words to `out` like `git` does for mail headers, breaking lines so that they aren't
+/// longer than 76 characters. If `phrase` is `true`, encode `text` to be used as name before an address.
+fn write_rfc2047(out: &mut Vec<u8>, text: &[u8], phrase: bool) {
+    const MAX_LINE_LEN: usize = 76;
+    const START: &str = "=?UTF-8?q?";
+    let mut line_len = out.len() - out.rfind_byte(b'\n').map_or(0, |pos| pos + 1);
+    out.push_str(START);
+    line_len += START.len();
+    for (start, end, _) in text.char_indices() {
+        let chars = &text[start..end];
+        let c = chars[0];
+        let special = chars.len() > 1
+            || !c.is_ascii_graphic()
+            || matches!(c, b'=' | b'?' | b'_')
+            || (phrase && !(c.is_ascii_alphanumeric() || matches!(c, b'!' | b'*' | b'+' | b'-' | b'/')));
+        let encoded_len = if special { 3 * chars.len() } else { 1 };
+        if line_len + encoded_len + 2 > MAX_LINE_LEN {
+            out.push_str("?=\n ");
+            out.push_str(START);
+            line_len = START.len() + 1;
+        }
+        for &b in chars {
+            if special {
+                out.push_str(format!("={b:02X}"));
+            } else {
+                out.push(b);
+            }
+        }
+        line_len += encoded_len;
+    }
+    out.push_str("?=");
+}
+
+/// Append `text` to `out`, breaking it into lines at spaces so that lines are no longer than 78 characters if possible,
+/// assuming the current line is `indent` characters long already. Continuation lines start with a space.
+fn write_wrapped(out: &mut Vec<u8>, text: &[u8], indent: usize) {
+    const MAX_LINE_LEN: usize = 78;
+    let mut line_len = indent;
+    for (idx, word) in text.split_str(" ").filter(|word| !word.is_empty()).enumerate() {
+        if idx > 0 {
+            if line_len + 1 + word.len() > MAX_LINE_LEN {
+                out.push_str("\n ");
+                line_len = 1;
+            } else {
+                out.push(b' ');
+                line_len += 1;
+            }
+        }
+        out.push_str(word);
+        line_len += word.len();
+    }
+}
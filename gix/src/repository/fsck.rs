@@ -0,0 +1,226 @@
+use std::collections::{HashMap, VecDeque};
+
+use gix_hash::ObjectId;
+use gix_hashtable::HashSet;
+use gix_object::{tree::EntryKind, Kind};
+
+use crate::{
+    bstr::ByteSlice,
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Fsck, Key},
+    },
+    fsck, Repository,
+};
+
+/// Fsck
+impl Repository {
+    /// Check this repository for problems like `git fsck` does and return all findings, without failing if problems are found.
+    ///
+    /// If [`options.check_objects`](fsck::Options::check_objects) is `true`, the content of each object in the object database
+    /// is checked for problems, with the [`Severity`](fsck::Severity) of each kind of problem configured with
+    /// `fsck.<msg-id>`, like `fsck.badDate=ignore`. Objects listed in the file at `fsck.skipList` aren't checked.
+    ///
+    /// If [`options.check_connectivity`](fsck::Options::check_connectivity) is `true`, all objects reachable from `HEAD`,
+    /// all references, the entries of their reflogs and the entries and tree cache of the index are checked to exist.
+    /// The parents of commits at the boundary of a shallow repository are expected to be missing.
+    ///
+    /// ### Deviation
+    ///
+    /// * Dangling and unreachable objects aren't reported.
+    /// * The hashes of objects aren't verified.
+    pub fn fsck(&self, options: fsck::Options) -> Result<fsck::Outcome, fsck::Error> {
+        let _span = gix_trace::coarse!("gix::fsck()", options = ?options);
+        let mut findings = Vec::new();
+        if options.check_objects {
+            let severities = self.fsck_severities()?;
+            let skip_list = self.fsck_skip_list()?;
+            let mut seen = HashSet::default();
+            let mut problems = Vec::new();
+            for id in self.objects.iter()? {
+                let id = id?;
+                if !seen.insert(id) || skip_list.contains(&id) {
+                    continue;
+                }
+                let object = self.find_object(id)?;
+                for problem in gix_fsck::object::check(object.kind, &object.data, self.object_hash()) {
+                    let severity = severities
+                        .get(&problem.id)
+                        .copied()
+                        .unwrap_or_else(|| problem.id.default_severity());
+                    if severity == fsck::Severity::Ignore {
+                        continue;
+                    }
+                    problems.push(fsck::Finding::Object {
+                        id,
+                        kind: object.kind,
+                        severity,
+                        problem,
+                    });
+                }
+            }
+            problems.sort_by_key(fsck::Finding::id);
+            findings.extend(problems);
+        }
+        if options.check_connectivity {
+            self.fsck_connectivity(&mut findings)?;
+        }
+        Ok(fsck::Outcome { findings })
+    }
+}
+
+/// Utilities
+impl Repository {
+    /// Return the severities configured with `fsck.<msg-id>`, ignoring unknown message ids.
+    fn fsck_severities(&self) -> Result<HashMap<fsck::MessageId, fsck::Severity>, fsck::Error> {
+        let mut filter = self.filter_config_section();
+        let lenient = self.config.lenient_config;
+        let mut out = HashMap::new();
+        for section in self
+            .config
+            .resolved
+            .sections_by_name_and_filter("fsck", &mut filter)
+            .into_iter()
+            .flatten()
+            .filter(|section| section.header().subsection_name().is_none())
+        {
+            for key in section.keys() {
+                let Some(id) = fsck::MessageId::from_name(key.as_ref().as_bytes()) else {
+                    continue;
+                };
+                let severity = section
+                    .value(key)
+                    .map(|value| Fsck::MSG_ID.try_into_severity(value))
+                    .transpose()
+                    .with_leniency(lenient)?;
+                if let Some(severity) = severity {
+                    out.insert(id, severity);
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Read the ids of the objects listed in the file at `fsck.skipList`, one per line, ignoring empty lines and comments.
+    fn fsck_skip_list(&self) -> Result<HashSet, fsck::Error> {
+        let mut out = HashSet::default();
+        let Some(path) = self
+            .config_snapshot()
+            .trusted_path(Fsck::SKIP_LIST.logical_name().as_str())
+            .transpose()?
+        else {
+            return Ok(out);
+        };
+        let data = std::fs::read(&path).map_err(|source| fsck::Error::ReadSkipList {
+            path: path.clone().into_owned(),
+            source,
+        })?;
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(b"#") {
+                continue;
+            }
+            let id = ObjectId::from_hex(line).map_err(|_| fsck::Error::InvalidSkipListEntry {
+                path: path.clone().into_owned(),
+                line: line.into(),
+            })?;
+            out.insert(id);
+        }
+        Ok(out)
+    }
+
+    /// Walk all objects reachable from references, their reflogs and the index, and add all missing objects to `findings`.
+    fn fsck_connectivity(&self, findings: &mut Vec<fsck::Finding>) -> Result<(), fsck::Error> {
+        let mut queue = VecDeque::new();
+        let head = self.head()?;
+        queue.extend(head.id().map(|id| (id.detach(), None)));
+        queue_reflog(head.log_iter(), &mut queue)?;
+        for reference in self.references()?.all()? {
+            let reference = reference?;
+            queue.extend(reference.target().try_id().map(|id| (id.to_owned(), None)));
+            queue_reflog(reference.log_iter(), &mut queue)?;
+        }
+        let index = self.index_or_empty()?;
+        for entry in index.entries() {
+            let kind = match entry.mode {
+                gix_index::entry::Mode::COMMIT => continue,
+                gix_index::entry::Mode::DIR => Kind::Tree,
+                _ => Kind::Blob,
+            };
+            queue.push_back((entry.id, Some(kind)));
+        }
+        let mut trees: Vec<_> = index.tree().into_iter().collect();
+        while let Some(tree) = trees.pop() {
+            if tree.num_entries.is_some() {
+                queue.push_back((tree.id, Some(Kind::Tree)));
+            }
+            trees.extend(&tree.children);
+        }
+
+        let shallow = self.shallow_commits()?;
+        let boundary = shallow.as_ref().map(|commits| commits.as_slice()).unwrap_or_default();
+        let mut seen = HashSet::default();
+        while let Some((id, kind)) = queue.pop_front() {
+            if !seen.insert(id) {
+                continue;
+            }
+            if kind == Some(Kind::Blob) {
+                if !self.has_object(id) {
+                    findings.push(fsck::Finding::Missing { id, kind });
+                }
+                continue;
+            }
+            let Some(object) = self.try_find_object(id)? else {
+                findings.push(fsck::Finding::Missing { id, kind });
+                continue;
+            };
+            // Objects that can't be decoded are reported by the object checks, and we can't know what they refer to.
+            match object.kind {
+                Kind::Commit => {
+                    if let Ok(commit) = gix_object::CommitRef::from_bytes(&object.data) {
+                        queue.push_back((commit.tree(), Some(Kind::Tree)));
+                        if !boundary.contains(&id) {
+                            queue.extend(commit.parents().map(|parent| (parent, Some(Kind::Commit))));
+                        }
+                    }
+                }
+                Kind::Tree => {
+                    if let Ok(tree) = gix_object::TreeRef::from_bytes(&object.data) {
+                        for entry in tree.entries {
+                            let kind = match entry.mode.kind() {
+                                EntryKind::Commit => continue,
+                                EntryKind::Tree => Kind::Tree,
+                                EntryKind::Blob | EntryKind::BlobExecutable | EntryKind::Link => Kind::Blob,
+                            };
+                            queue.push_back((entry.oid.to_owned(), Some(kind)));
+                        }
+                    }
+                }
+                Kind::Tag => {
+                    if let Ok(tag) = gix_object::TagRef::from_bytes(&object.data) {
+                        queue.push_back((tag.target(), Some(tag.target_kind)));
+                    }
+                }
+                Kind::Blob => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Add the previous and new ids of all entries of `reflog` to `queue`.
+fn queue_reflog(
+    mut reflog: gix_ref::file::log::iter::Platform<'_, '_>,
+    queue: &mut VecDeque<(ObjectId, Option<Kind>)>,
+) -> Result<(), fsck::Error> {
+    for line in reflog.all()?.into_iter().flatten() {
+        let line = line?;
+        queue.extend(
+            [line.previous_oid(), line.new_oid()]
+                .into_iter()
+                .filter(|id| !id.is_null())
+                .map(|id| (id, None)),
+        );
+    }
+    Ok(())
+}
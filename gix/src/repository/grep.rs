@@ -0,0 +1,143 @@
+use gix_features::parallel::{in_parallel, Reduce};
+
+use crate::{
+    bstr::{BStr, BString},
+    grep,
+    worktree::IndexPersistedOrInMemory,
+    Repository,
+};
+
+/// Grep
+impl Repository {
+    /// Search the files of [`options.source`](grep::Options::source) for lines matching any of `patterns`, like `git grep`,
+    /// limited to the files that match `pathspecs`, or all files if there are none.
+    ///
+    /// Files are searched in parallel according to [`options.threads`](grep::Options::threads), and the outcome contains the
+    /// matching lines of each file along with the location of the matches.
+    /// Binary files are only reported as matching, and are detected by a [binary detector](Self::binary_detector()) for
+    /// diffs, unless [`options.text`](grep::Options::text) is `true`.
+    ///
+    /// ### Deviation
+    ///
+    /// * Only files are searched, submodules aren't recursed into.
+    pub fn grep(
+        &self,
+        patterns: &[grep::Pattern],
+        pathspecs: impl IntoIterator<Item = impl AsRef<BStr>>,
+        options: grep::Options,
+    ) -> Result<grep::Outcome, grep::Error> {
+        let _span = gix_trace::coarse!("gix::grep()", options = ?options);
+        let (index, attributes_source) = match options.source {
+            grep::Source::Tree(id) => {
+                let tree = self.find_object(id)?.peel_to_tree()?;
+                (
+                    IndexPersistedOrInMemory::InMemory(self.index_from_tree(&tree.id)?),
+                    gix_worktree::stack::state::attributes::Source::IdMapping,
+                )
+            }
+            grep::Source::Index => (
+                IndexPersistedOrInMemory::Persisted(self.index_or_empty()?),
+                gix_worktree::stack::state::attributes::Source::IdMapping,
+            ),
+            grep::Source::Worktree => (
+                IndexPersistedOrInMemory::Persisted(self.index_or_empty()?),
+                gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+            ),
+        };
+        let work_dir = match options.source {
+            grep::Source::Worktree => Some(self.work_dir().ok_or(grep::Error::MissingWorktree)?.to_owned()),
+            _ => None,
+        };
+
+        let mut pathspec = self.pathspec(true, pathspecs, true, &index, attributes_source)?;
+        let mut detector = self.binary_detector(&index, attributes_source, crate::binary::Purpose::Diff)?;
+        let mut candidates = Vec::new();
+        if let Some(entries) = pathspec.index_entries_with_paths(&index) {
+            for (path, entry) in entries {
+                let searchable = match entry.mode {
+                    gix_index::entry::Mode::FILE | gix_index::entry::Mode::FILE_EXECUTABLE => true,
+                    gix_index::entry::Mode::SYMLINK => matches!(options.source, grep::Source::Tree(_)),
+                    _ => false,
+                };
+                if !searchable {
+                    continue;
+                }
+                candidates.push(Candidate {
+                    path: path.to_owned(),
+                    id: entry.id,
+                    binary: detector.is_binary_by_attributes(path, Some(entry.mode))?,
+                });
+            }
+        }
+
+        let repo = self.clone().into_sync();
+        let files = in_parallel(
+            candidates.chunks(32),
+            options.threads,
+            move |_| repo.to_thread_local(),
+            |chunk, repo| -> Result<Vec<grep::File>, grep::Error> {
+                let mut out = Vec::new();
+                for candidate in chunk {
+                    let data = match &work_dir {
+                        Some(work_dir) => {
+                            let path = work_dir.join(gix_path::from_bstr(candidate.path.as_ref()));
+                            match std::fs::read(&path) {
+                                Ok(data) => data,
+                                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                                Err(source) => return Err(grep::Error::ReadFile { path, source }),
+                            }
+                        }
+                        None => repo.find_object(candidate.id)?.detach().data,
+                    };
+                    let lines = grep::search(&data, patterns, &options);
+                    if lines.is_empty() {
+                        continue;
+                    }
+                    let binary = !options.text
+                        && candidate
+                            .binary
+                            .unwrap_or_else(|| crate::binary::is_binary_content(&data));
+                    out.push(grep::File {
+                        path: candidate.path.clone(),
+                        binary,
+                        lines: if binary { Vec::new() } else { lines },
+                    });
+                }
+                Ok(out)
+            },
+            Collect::default(),
+        )?;
+        Ok(grep::Outcome { files })
+    }
+}
+
+/// A file to search.
+struct Candidate {
+    path: BString,
+    id: gix_hash::ObjectId,
+    /// Whether the file is binary according to its attributes, or `None` if its content has to decide.
+    binary: Option<bool>,
+}
+
+/// Collect the matching files of all chunks, in order of their paths.
+#[derive(Default)]
+struct Collect {
+    files: Vec<grep::File>,
+}
+
+impl Reduce for Collect {
+    type Input = Result<Vec<grep::File>, grep::Error>;
+    type FeedProduce = ();
+    type Output = Vec<grep::File>;
+    type Error = grep::Error;
+
+    fn feed(&mut self, item: Self::Input) -> Result<Self::FeedProduce, Self::Error> {
+        self.files.extend(item?);
+        Ok(())
+    }
+
+    fn finalize(mut self) -> Result<Self::Output, Self::Error> {
+        self.files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(self.files)
+    }
+}
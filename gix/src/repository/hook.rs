@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use crate::hook;
+
+const REFERENCE_TRANSACTION: &str = "reference-transaction";
+
+/// Hooks
+impl crate::Repository {
+    /// Return the directory containing the hooks, which is `core.hooksPath` if set or `hooks` in the
+    /// [common directory](Self::common_dir()) otherwise.
+    ///
+    /// A relative `core.hooksPath` is relative to the directory in which hooks are run, i.e. the root of the worktree,
+    /// or the `.git` directory in bare repositories. The returned directory may not exist.
+    pub fn hooks_dir(&self) -> Result<PathBuf, hook::executor::Error> {
+        let dir = match self.config.apply_leniency(self.config.hooks_path())? {
+            Some(path) => self.hooks_cwd().join(path),
+            None => self.current_dir().join(self.common_dir()).join("hooks"),
+        };
+        Ok(dir)
+    }
+
+    /// Return an executor to run hooks in the [hooks directory](Self::hooks_dir()) like `git` would, i.e. in the root
+    /// of the worktree, or in the `.git` directory in bare repositories, with `GIT_DIR` and `GIT_WORK_TREE` set.
+    pub fn hooks(&self) -> Result<hook::Executor, hook::executor::Error> {
+        let cwd = self.hooks_cwd();
+        Ok(hook::Executor {
+            dir: self.hooks_dir()?,
+            context: gix_command::Context {
+                git_dir: Some(self.current_dir().join(self.git_dir())),
+                worktree_dir: self.work_dir().map(|dir| self.current_dir().join(dir)),
+                ..Default::default()
+            },
+            cwd,
+        })
+    }
+
+    /// Return a function to run the `reference-transaction` hook with in each state of a reference transaction,
+    /// or `None` if the hook doesn't exist in the [hooks directory](Self::hooks_dir()) or if it can't be determined.
+    ///
+    /// The function fails if the hook fails, which aborts the transaction in the `prepared` state.
+    pub(crate) fn reference_transaction_hook(
+        &self,
+    ) -> Option<
+        impl FnMut(gix_ref::file::transaction::HookState, &[u8]) -> Result<(), gix_ref::file::transaction::HookError>,
+    > {
+        let mut hooks = self
+            .hooks()
+            .ok()
+            .filter(|hooks| hooks.find(REFERENCE_TRANSACTION).is_some())?;
+        Some(move |state: gix_ref::file::transaction::HookState, input: &[u8]| {
+            use hook::Run;
+            let invocation = hook::Invocation::new(REFERENCE_TRANSACTION)
+                .arg(state.as_str())
+                .stdin(input);
+            match hooks.run(&invocation)? {
+                Some(outcome) if !outcome.success() => Err(format!(
+                    "in '{}' phase, update aborted by the {REFERENCE_TRANSACTION} hook",
+                    state.as_str()
+                )
+                .into()),
+                _ => Ok(()),
+            }
+        })
+    }
+
+    fn hooks_cwd(&self) -> PathBuf {
+        self.current_dir()
+            .join(self.work_dir().unwrap_or_else(|| self.git_dir()))
+    }
+}
@@ -1,11 +1,45 @@
-use std::time::SystemTime;
+use std::{borrow::Cow, time::SystemTime};
 
 use crate::{
-    bstr::BString,
+    bstr::{BStr, BString},
     config,
-    config::tree::{gitoxide, keys, Author, Committer, Key, User},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{gitoxide, keys, Author, Committer, Key, User},
+    },
 };
 
+/// The identity to determine with [`Repository::identity()`](crate::Repository::identity()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// The author of a commit, configured with `GIT_AUTHOR_*` environment variables or in the `author` section.
+    Author,
+    /// The committer of a commit, configured with `GIT_COMMITTER_*` environment variables or in the `committer` section.
+    Committer,
+}
+
+/// The error returned by [`Repository::identity()`](crate::Repository::identity()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("No name was given and auto-detection is disabled by user.useConfigOnly - please set user.name")]
+    NameAutoDetectionDisabled,
+    #[error("No email was given and auto-detection is disabled by user.useConfigOnly - please set user.email")]
+    EmailAutoDetectionDisabled,
+    #[error("Unable to auto-detect name (got {name:?}) - please set user.name")]
+    BogusName { name: String },
+    #[error("Unable to auto-detect email address (got {email:?}) - please set user.email")]
+    BogusEmail { email: String },
+    #[error("Empty name (for <{email}>) is not allowed - please set user.name")]
+    EmptyName { email: BString },
+    #[error("Name consists only of disallowed characters: {name:?}")]
+    NameWithoutValidCharacters { name: BString },
+    #[error(transparent)]
+    UseConfigOnly(#[from] config::boolean::Error),
+    #[error(transparent)]
+    Time(#[from] config::time::Error),
+}
+
 /// Identity handling.
 ///
 /// # Deviation
@@ -72,6 +106,180 @@ impl crate::Repository {
     }
 }
 
+/// Identity resolution like `git`.
+impl crate::Repository {
+    /// Determine the author or committer as identified by `kind` exactly like `git` does when creating commits,
+    /// which unlike [`author()`](Self::author()) and [`committer()`](Self::committer()) falls back to information
+    /// about the current user and host. The name and email are determined by…
+    ///
+    /// * …the `GIT_(AUTHOR|COMMITTER)_(NAME|EMAIL)` environment variables…
+    /// * …the git configuration `author.name|email` or `committer.name|email`…
+    /// * …the `gitoxide.author.name|emailFallback` or `gitoxide.committer.name|emailFallback` configuration…
+    /// * …the configuration for `user.name|email`…
+    /// * …the `EMAIL` environment variable for the email…
+    /// * …the full name of the current user and `<user>@<host>` as email, unless `user.useConfigOnly` is set
+    ///   and no name or email respectively was configured…
+    ///
+    /// …and in that order, failing if no valid identity could be determined.
+    /// The time is determined by the `GIT_(AUTHOR|COMMITTER)_DATE` environment variables, or is the current time.
+    ///
+    /// Like `git`, leading and trailing whitespace and punctuation as well as `<`, `>` and newlines are removed
+    /// from the name and email.
+    pub fn identity(&self, kind: Kind) -> Result<gix_actor::Signature, Error> {
+        let config = &self.config.resolved;
+        let personas = self.config.personas();
+        let (name_key, email_key, name_fallback_key, email_fallback_key, time, time_key) = match kind {
+            Kind::Author => (
+                &Author::NAME,
+                &Author::EMAIL,
+                &gitoxide::Author::NAME_FALLBACK,
+                &gitoxide::Author::EMAIL_FALLBACK,
+                &personas.author.time,
+                &gitoxide::Commit::AUTHOR_DATE,
+            ),
+            Kind::Committer => (
+                &Committer::NAME,
+                &Committer::EMAIL,
+                &gitoxide::Committer::NAME_FALLBACK,
+                &gitoxide::Committer::EMAIL_FALLBACK,
+                &personas.committer.time,
+                &gitoxide::Commit::COMMITTER_DATE,
+            ),
+        };
+        let use_config_only = config
+            .boolean("user", None, User::USE_CONFIG_ONLY.name)
+            .map(|value| User::USE_CONFIG_ONLY.enrich_error(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(false);
+        let is_set = |key: &keys::Any| config.string(key.section.name(), None, key.name).is_some();
+        // Empty values in configuration files are ignored, but empty values in the environment are not.
+        let configured = |key: &keys::Any| {
+            config
+                .string(key.section.name(), None, key.name)
+                .filter(|value| !value.is_empty())
+        };
+        let fallback = |key: &keys::Any, from_env: bool| {
+            config
+                .string_filter(
+                    "gitoxide",
+                    Some(key.section.name().into()),
+                    key.name,
+                    &mut move |meta| (meta.source == gix_config::Source::EnvOverride) == from_env,
+                )
+                .filter(|value| from_env || !value.is_empty())
+        };
+
+        let email = match fallback(email_fallback_key, true)
+            .or_else(|| configured(email_key))
+            .or_else(|| fallback(email_fallback_key, false))
+        {
+            Some(email) => email,
+            None if use_config_only
+                && ![&User::EMAIL, &Author::EMAIL, &Committer::EMAIL]
+                    .into_iter()
+                    .any(is_set) =>
+            {
+                return Err(Error::EmailAutoDetectionDisabled)
+            }
+            None => match configured(&User::EMAIL).or_else(|| {
+                config
+                    .string_by_key(gitoxide::User::EMAIL_FALLBACK.logical_name().as_str())
+                    .filter(|email| !email.is_empty())
+            }) {
+                Some(email) => email,
+                None => Cow::Owned(default_email()?.into()),
+            },
+        };
+        let name = match fallback(name_fallback_key, true)
+            .or_else(|| configured(name_key))
+            .or_else(|| fallback(name_fallback_key, false))
+        {
+            Some(name) => name,
+            None if use_config_only && ![&User::NAME, &Author::NAME, &Committer::NAME].into_iter().any(is_set) => {
+                return Err(Error::NameAutoDetectionDisabled)
+            }
+            None => match configured(&User::NAME) {
+                Some(name) => name,
+                None => Cow::Owned(default_name()?.into()),
+            },
+        };
+
+        if name.is_empty() {
+            return Err(Error::EmptyName {
+                email: email.into_owned(),
+            });
+        }
+        if name.iter().all(|b| is_crud(*b)) {
+            return Err(Error::NameWithoutValidCharacters {
+                name: name.into_owned(),
+            });
+        }
+        Ok(gix_actor::Signature {
+            name: without_crud(name.as_ref()),
+            email: without_crud(email.as_ref()),
+            time: extract_time_or_default(time.as_ref(), time_key)?,
+        })
+    }
+}
+
+/// Return the full name of the current user, like `git` does.
+fn default_name() -> Result<String, Error> {
+    let user = gix_sec::identity::current_user().ok_or_else(|| Error::BogusName { name: "Unknown".into() })?;
+    let name = match user.full_name {
+        Some(full_name) => full_name.trim().to_owned(),
+        None => user.name,
+    };
+    Ok(name)
+}
+
+/// Return `<user>@<host>` as email for the current user, like `git` does.
+///
+/// Note that the host is taken from `/etc/mailname` if present.
+fn default_email() -> Result<String, Error> {
+    let user = gix_sec::identity::current_user();
+    let mut is_bogus = user.is_none();
+    let host = match std::fs::read_to_string("/etc/mailname")
+        .ok()
+        .and_then(|mailname| mailname.lines().next().map(ToOwned::to_owned))
+    {
+        Some(mailname) => mailname,
+        None => match gix_sec::identity::hostname() {
+            Some(host) if host.contains('.') => host,
+            Some(host) => {
+                is_bogus = true;
+                format!("{host}.(none)")
+            }
+            None => {
+                is_bogus = true;
+                "(none)".into()
+            }
+        },
+    };
+    let email = format!("{}@{host}", user.map_or_else(|| "unknown".into(), |user| user.name));
+    if is_bogus {
+        return Err(Error::BogusEmail { email });
+    }
+    Ok(email)
+}
+
+/// Return `true` for bytes that are removed from the beginning and end of names and emails.
+fn is_crud(b: u8) -> bool {
+    b <= 32 || b".,:;<>\"\\'".contains(&b)
+}
+
+/// Return `value` without leading and trailing crud and without characters that delimit names and emails.
+fn without_crud(value: &BStr) -> BString {
+    let start = value.iter().position(|b| !is_crud(*b)).unwrap_or(value.len());
+    let end = value.iter().rposition(|b| !is_crud(*b)).map_or(start, |pos| pos + 1);
+    value[start..end]
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, b'\n' | b'<' | b'>'))
+        .collect::<Vec<_>>()
+        .into()
+}
+
 fn extract_time_or_default(
     time: Option<&Result<gix_date::Time, gix_date::parse::Error>>,
     config_key: &'static keys::Time,
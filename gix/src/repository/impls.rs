@@ -1,6 +1,6 @@
 impl Clone for crate::Repository {
     fn clone(&self) -> Self {
-        crate::Repository::from_refs_and_objects(
+        let mut repo = crate::Repository::from_refs_and_objects(
             self.refs.clone(),
             self.objects.clone(),
             self.work_tree.clone(),
@@ -12,7 +12,9 @@ impl Clone for crate::Repository {
             self.shallow_commits.clone(),
             #[cfg(feature = "attributes")]
             self.modules.clone(),
-        )
+        );
+        repo.refs_memory = self.refs_memory.clone();
+        repo
     }
 }
 
@@ -38,7 +40,7 @@ impl From<&crate::ThreadSafeRepository> for crate::Repository {
     fn from(repo: &crate::ThreadSafeRepository) -> Self {
         crate::Repository::from_refs_and_objects(
             repo.refs.clone(),
-            repo.objects.to_handle().into(),
+            crate::OdbHandle::new(repo.objects.to_handle().into(), repo.config.object_hash).with_write_passthrough(),
             repo.work_tree.clone(),
             repo.common_dir.clone(),
             repo.config.clone(),
@@ -56,7 +58,7 @@ impl From<crate::ThreadSafeRepository> for crate::Repository {
     fn from(repo: crate::ThreadSafeRepository) -> Self {
         crate::Repository::from_refs_and_objects(
             repo.refs,
-            repo.objects.to_handle().into(),
+            crate::OdbHandle::new(repo.objects.to_handle().into(), repo.config.object_hash).with_write_passthrough(),
             repo.work_tree,
             repo.common_dir,
             repo.config,
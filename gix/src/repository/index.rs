@@ -119,9 +119,96 @@ impl crate::Repository {
                     source: err,
                 }
             })?,
-            self.git_dir().join("index"),
+            self.index_path(),
         ))
     }
+
+    /// Return `true` if `index` has no unmerged entries and the same entries as `tree`.
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    pub(crate) fn index_matches_tree(
+        &self,
+        index: &gix_index::State,
+        tree: gix_hash::ObjectId,
+    ) -> Result<bool, super::index_from_tree::Error> {
+        let tree_index = self.index_from_tree(&tree)?;
+        Ok(tree_index.entries().len() == index.entries().len()
+            && tree_index.entries().iter().zip(index.entries()).all(|(a, b)| {
+                a.id == b.id
+                    && a.mode == b.mode
+                    && b.stage() == gix_index::entry::Stage::Unconflicted
+                    && a.path(&tree_index) == b.path(index)
+            }))
+    }
+
+    /// Write the tree hierarchy described by all entries in `index` to the object database and return the id of the root tree,
+    /// similar to `git write-tree`.
+    ///
+    /// The operation fails if `index` has unresolved conflicts. Entries marked for [removal](gix_index::entry::Flags::REMOVE)
+    /// are ignored, and the tree-cache extension isn't used.
+    pub fn write_tree_from_index(
+        &self,
+        index: &gix_index::State,
+    ) -> Result<crate::Id<'_>, super::write_tree_from_index::Error> {
+        use gix_index::entry::{Flags, Stage};
+        let entries: Vec<_> = index
+            .entries()
+            .iter()
+            .filter(|e| !e.flags.contains(Flags::REMOVE))
+            .map(|e| {
+                if e.stage() != Stage::Unconflicted {
+                    return Err(super::write_tree_from_index::Error::Unmerged {
+                        path: e.path(index).to_owned(),
+                    });
+                }
+                Ok((e.path(index), e))
+            })
+            .collect::<Result<_, _>>()?;
+        let id = self.write_tree_from_sorted_entries(&entries, 0)?;
+        Ok(crate::Id::from_id(id, self))
+    }
+
+    /// Write the tree for `entries` whose paths are sorted and have their first `prefix_len` bytes in common, recursively.
+    fn write_tree_from_sorted_entries(
+        &self,
+        entries: &[(&crate::bstr::BStr, &gix_index::Entry)],
+        prefix_len: usize,
+    ) -> Result<gix_hash::ObjectId, crate::object::write::Error> {
+        let mut tree = gix_object::Tree::empty();
+        let mut cursor = 0;
+        while let Some((path, entry)) = entries.get(cursor) {
+            let rela_path = &path[prefix_len..];
+            match rela_path.iter().position(|b| *b == b'/') {
+                None => {
+                    tree.entries.push(gix_object::tree::Entry {
+                        mode: entry
+                            .mode
+                            .to_tree_entry_mode()
+                            .unwrap_or(gix_object::tree::EntryKind::Blob.into()),
+                        filename: rela_path.into(),
+                        oid: entry.id,
+                    });
+                    cursor += 1;
+                }
+                Some(slash) => {
+                    let dir = &rela_path[..=slash];
+                    let end = cursor
+                        + entries[cursor..]
+                            .iter()
+                            .take_while(|(path, _)| path[prefix_len..].starts_with(dir))
+                            .count();
+                    let oid = self.write_tree_from_sorted_entries(&entries[cursor..end], prefix_len + dir.len())?;
+                    tree.entries.push(gix_object::tree::Entry {
+                        mode: gix_object::tree::EntryKind::Tree.into(),
+                        filename: rela_path[..slash].into(),
+                        oid,
+                    });
+                    cursor = end;
+                }
+            }
+        }
+        tree.entries.sort();
+        Ok(self.write_object(&tree)?.detach())
+    }
 }
 
 impl std::ops::Deref for IndexPersistedOrInMemory {
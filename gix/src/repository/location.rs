@@ -32,8 +32,18 @@ impl crate::Repository {
     }
 
     /// Return the path to the worktree index file, which may or may not exist.
+    ///
+    /// It can be overridden with `gitoxide.core.indexFile` or `GIT_INDEX_FILE`, with relative paths
+    /// being relative to the [current working directory](Self::current_dir()).
     pub fn index_path(&self) -> PathBuf {
-        self.git_dir().join("index")
+        use crate::config::tree::{gitoxide, Key};
+        match self.config.resolved.string_filter_by_key(
+            gitoxide::Core::INDEX_FILE.logical_name().as_str(),
+            &mut self.filter_config_section(),
+        ) {
+            Some(index_file) => self.current_dir().join(gix_path::from_bstr(index_file)),
+            None => self.git_dir().join("index"),
+        }
     }
 
     /// The path to the `.gitmodules` file in the worktree, if a worktree is available.
@@ -0,0 +1,93 @@
+use crate::{
+    bstr::{BStr, BString},
+    log, Repository,
+};
+
+/// Log
+impl Repository {
+    /// Return an iterator over the commits reachable from [`options.tips`](log::Options::tips) that change paths matching
+    /// `patterns`, newest first, similar to `git log -- <patterns>`. If `patterns` is empty, all commits are returned.
+    ///
+    /// Commits are compared to their parents to see if a path of interest changed, with root commits being compared
+    /// to the empty tree. By default, history is [simplified](log::Options::simplify) to skip merges, and the side branches they
+    /// brought in, if the merge didn't change the paths compared to one of its parents.
+    ///
+    /// With [`follow`](log::Options::follow) enabled, `patterns` must be the path to a single file, whose history is
+    /// followed across renames.
+    ///
+    /// In shallow repositories, commits at the shallow boundary are treated like root commits.
+    ///
+    /// ### Deviation
+    ///
+    /// * Parents are not rewritten, so [`log::Info::parent_ids`] are the actual parents of a commit, not the closest ancestors
+    ///   that are part of the log, like `git log --parents` would show.
+    pub fn log(
+        &self,
+        patterns: impl IntoIterator<Item = impl AsRef<BStr>>,
+        options: log::Options,
+    ) -> Result<log::Iter<'_>, log::init::Error> {
+        let _span = gix_trace::coarse!("gix::log()");
+        let patterns: Vec<BString> = patterns.into_iter().map(|p| p.as_ref().to_owned()).collect();
+        let (filter, location) = if options.follow {
+            if patterns.len() != 1 {
+                return Err(log::init::Error::FollowRequiresSinglePath { patterns });
+            }
+            let location = patterns.into_iter().next().expect("one pattern");
+            (
+                log::Filter::Follow {
+                    rewrites: options.rewrites,
+                },
+                Some(location),
+            )
+        } else if patterns.is_empty() {
+            (log::Filter::None, None)
+        } else {
+            let index = self.index_or_empty()?;
+            let pathspec = self.pathspec(
+                true,
+                patterns,
+                true,
+                &index,
+                gix_worktree::stack::state::attributes::Source::IdMapping,
+            )?;
+            (log::Filter::Pathspec(Box::new(pathspec)), None)
+        };
+
+        let tips = if options.tips.is_empty() {
+            vec![self.head_id()?.detach()]
+        } else {
+            options.tips
+        };
+        let mut hidden = gix_hashtable::HashSet::default();
+        if !options.hidden.is_empty() {
+            let mut hidden_commits = Vec::with_capacity(options.hidden.len());
+            for id in options.hidden {
+                hidden_commits.push(self.find_object(id)?.peel_to_kind(gix_object::Kind::Commit)?.id);
+            }
+            for info in self.rev_walk(hidden_commits).all()? {
+                hidden.insert(info?.id);
+            }
+        }
+        let mut iter = log::Iter {
+            repo: self,
+            filter,
+            first_parent: options.first_parent,
+            simplify: options.simplify,
+            queue: Default::default(),
+            locations: Default::default(),
+            seen: Default::default(),
+            hidden,
+            shallow: self.shallow_commits()?,
+            buf: Vec::new(),
+        };
+        for tip in tips {
+            let commit = self
+                .find_object(tip)?
+                .peel_to_kind(gix_object::Kind::Commit)?
+                .into_commit();
+            let time = commit.decode()?.committer.time.seconds;
+            iter.enqueue(commit.id, time, location.clone());
+        }
+        Ok(iter)
+    }
+}
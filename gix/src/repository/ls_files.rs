@@ -0,0 +1,122 @@
+use crate::{
+    bstr::{BStr, BString},
+    ls_files,
+    worktree::IndexPersistedOrInMemory,
+    Repository,
+};
+
+/// List files
+impl Repository {
+    /// List the files in the index and in the worktree according to `options`, like `git ls-files`, limited to the files that
+    /// match `pathspecs`, or all files if there are none.
+    ///
+    /// Index entries are listed once for each kind that applies to them, along with their stage, mode, id and flags, which is
+    /// useful to see conflicts or skip-worktree entries.
+    /// Deleted and modified files are determined by comparing the index with the worktree, just like
+    /// [`status()`](Self::status()) does, and untracked files are found with a directory walk which respects ignore files.
+    ///
+    /// ### Deviation
+    ///
+    /// * Conflicting entries and submodules are never listed as deleted or modified.
+    pub fn ls_files(
+        &self,
+        pathspecs: impl IntoIterator<Item = impl AsRef<BStr>>,
+        options: ls_files::Options,
+    ) -> Result<ls_files::Outcome, ls_files::Error> {
+        let _span = gix_trace::coarse!("gix::ls_files()", options = ?options);
+        let pathspecs: Vec<BString> = pathspecs.into_iter().map(|p| p.as_ref().to_owned()).collect();
+        let index = self.index_or_empty()?;
+        let needs_worktree = options.deleted || options.modified || options.others;
+        if needs_worktree && self.work_dir().is_none() {
+            return Err(ls_files::Error::MissingWorktree);
+        }
+
+        let mut entries = Vec::new();
+        // Paths of changed entries, mapped to `true` if they were deleted.
+        let mut changes = std::collections::HashMap::<BString, bool>::new();
+        if needs_worktree {
+            let mut platform = self
+                .status(gix_features::progress::Discard)?
+                .index(IndexPersistedOrInMemory::Persisted(index.clone()))
+                .index_worktree_rewrites(None)
+                .index_worktree_submodules(crate::status::Submodule::Given {
+                    ignore: crate::submodule::config::Ignore::All,
+                    check_dirty: false,
+                });
+            platform = if options.others {
+                platform
+                    .dirwalk_options(|opts| opts.emit_ignored(None).emit_empty_directories(false))
+                    .untracked_files(crate::status::UntrackedFiles::Files)
+            } else {
+                platform.index_worktree_options_mut(|opts| opts.dirwalk_options = None)
+            };
+            let mut others = Vec::new();
+            for item in platform.into_index_worktree_iter(pathspecs.clone())? {
+                match item? {
+                    crate::status::index_worktree::iter::Item::Modification { rela_path, status, .. } => {
+                        let deleted = match status {
+                            gix_status::index_as_worktree::EntryStatus::Change(
+                                gix_status::index_as_worktree::Change::Removed,
+                            ) => true,
+                            gix_status::index_as_worktree::EntryStatus::Change(_)
+                            | gix_status::index_as_worktree::EntryStatus::IntentToAdd => false,
+                            gix_status::index_as_worktree::EntryStatus::Conflict(_)
+                            | gix_status::index_as_worktree::EntryStatus::NeedsUpdate(_) => continue,
+                        };
+                        changes.insert(rela_path, deleted);
+                    }
+                    crate::status::index_worktree::iter::Item::DirectoryContents { entry, .. }
+                        if options.others && entry.status == gix_dir::entry::Status::Untracked =>
+                    {
+                        let mut rela_path = entry.rela_path;
+                        if entry.disk_kind == Some(gix_dir::entry::Kind::Repository) {
+                            rela_path.push(b'/');
+                        }
+                        others.push(ls_files::Entry {
+                            kind: ls_files::Kind::Other,
+                            rela_path,
+                            index_entry: None,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            others.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+            entries.extend(others);
+        }
+
+        let mut pathspec = self.pathspec(
+            true,
+            pathspecs,
+            true,
+            &index,
+            gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+        )?;
+        if options.cached || options.deleted || options.modified {
+            if let Some(index_entries) = pathspec.index_entries_with_paths(&index) {
+                for (rela_path, index_entry) in index_entries {
+                    let deleted = match changes.get(rela_path) {
+                        Some(deleted) if index_entry.stage() == gix_index::entry::Stage::Unconflicted => Some(*deleted),
+                        _ => None,
+                    };
+                    let kinds = [
+                        (options.cached, ls_files::Kind::Cached),
+                        (options.deleted && deleted == Some(true), ls_files::Kind::Deleted),
+                        (options.modified && deleted.is_some(), ls_files::Kind::Modified),
+                    ];
+                    entries.extend(
+                        kinds
+                            .into_iter()
+                            .filter(|(listed, _)| *listed)
+                            .map(|(_, kind)| ls_files::Entry {
+                                kind,
+                                rela_path: rela_path.to_owned(),
+                                index_entry: Some(index_entry.clone()),
+                            }),
+                    );
+                }
+            }
+        }
+        Ok(ls_files::Outcome { entries })
+    }
+}
@@ -0,0 +1,971 @@
+use std::{
+    io::{BufReader, Seek, Write},
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+    time::SystemTime,
+};
+
+use gix_hash::ObjectId;
+use gix_hashtable::HashSet;
+use gix_ref::{
+    file::transaction::PackedRefs,
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    FullName, Target,
+};
+
+use crate::{
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{gc::Expiry, Gc, Maintenance, Pack},
+    },
+    maintenance::{self, Task, TaskOutcome},
+    Repository,
+};
+
+/// Maintenance
+impl Repository {
+    /// Run maintenance tasks to keep this repository fast, similar to `git maintenance run`, and return what each task did.
+    ///
+    /// Which tasks run is determined by [`options`](maintenance::Options), `maintenance.<task>.enabled`, `maintenance.strategy`
+    /// and, if [`options.schedule`](maintenance::Options::schedule) is set, `maintenance.<task>.schedule`.
+    /// If [`options.auto`](maintenance::Options::auto) is `true`, tasks only run if their thresholds indicate they are needed,
+    /// as determined by `gc.auto`, `gc.autoPackLimit` and `maintenance.<task>.auto`.
+    ///
+    /// The lock at `objects/maintenance.lock` is held while tasks are running, and if it's already held by another process,
+    /// an error is returned right away.
+    ///
+    /// ### Deviation
+    ///
+    /// * There is no `gc` task, instead all [tasks](Task) are enabled by default, which covers most of what `git gc` does.
+    ///   Unreachable objects are not pruned, use [`prune()`](Self::prune()) for that.
+    /// * [`Task::IncrementalRepack`] combines all packs into one instead of writing and using a multi-pack-index,
+    ///   which is removed if present.
+    /// * The `prefetch` task isn't available, and tasks are not registered with the system scheduler.
+    pub fn maintenance(&self, options: maintenance::Options) -> Result<maintenance::Outcome, maintenance::Error> {
+        let _span = gix_trace::coarse!("gix::maintenance()", options = ?options);
+        let _lock = self.maintenance_lock()?;
+        let mut outcome = maintenance::Outcome { tasks: Vec::new() };
+        if options.auto && !self.maintenance_auto()? {
+            return Ok(outcome);
+        }
+
+        let strategy = self.maintenance_strategy()?;
+        let mut tasks = Vec::new();
+        if options.tasks.is_empty() {
+            for task in Task::ALL {
+                if self.maintenance_task_enabled(*task, strategy)? {
+                    tasks.push(*task);
+                }
+            }
+        } else {
+            for task in &options.tasks {
+                if !tasks.contains(task) {
+                    tasks.push(*task);
+                }
+            }
+        }
+
+        for task in tasks {
+            if let Some(schedule) = options.schedule {
+                let task_schedule = self.maintenance_task_schedule(task, strategy)?;
+                if !matches!(task_schedule, Some(task_schedule) if task_schedule <= schedule) {
+                    continue;
+                }
+            }
+            if options.auto && !self.maintenance_task_is_needed(task)? {
+                continue;
+            }
+            let _span = gix_trace::detail!("gix::maintenance::task", task = task.as_str());
+            outcome.tasks.push(match task {
+                Task::PackRefs => self.maintenance_pack_refs()?,
+                Task::ReflogExpire => self.maintenance_reflog_expire()?,
+                Task::LooseObjects => self.maintenance_loose_objects()?,
+                Task::IncrementalRepack => self.maintenance_incremental_repack()?,
+                Task::CommitGraph => self.maintenance_commit_graph()?,
+            });
+        }
+        Ok(outcome)
+    }
+
+    /// Remove objects that aren't reachable from references, their reflogs, `HEAD` or the index of any worktree,
+    /// similar to `git prune`, and return what was removed.
+    ///
+    /// Only objects whose files were last modified before [`options.expire`](maintenance::prune::Options::expire)
+    /// or `gc.pruneExpire` are removed, which gives concurrent writers the time to make new objects reachable.
+    /// Unreachable loose objects are deleted, and packs with unreachable objects are removed or replaced with a pack
+    /// of only their reachable objects, while packs marked to be kept with a `.keep` file are never touched.
+    ///
+    /// The same lock as for [`maintenance()`](Self::maintenance()) is held while objects are pruned.
+    ///
+    /// ### Deviation
+    ///
+    /// * Unlike `git prune`, unreachable objects in packs are removed as well, which `git` only does with `git gc`
+    ///   and `git repack`.
+    /// * Objects that are only reachable from unreachable objects that are still within the grace period are removed.
+    pub fn prune(
+        &self,
+        options: maintenance::prune::Options,
+    ) -> Result<maintenance::prune::Outcome, maintenance::Error> {
+        let _span = gix_trace::coarse!("gix::prune()", options = ?options);
+        let _lock = self.maintenance_lock()?;
+        let mut outcome = maintenance::prune::Outcome::default();
+        let expire = match options.expire {
+            Some(expire) => expire,
+            None => match self.maintenance_expiry(&Gc::PRUNE_EXPIRE, "2.weeks.ago", SystemTime::now())? {
+                Some(expire) => SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(expire.seconds.max(0) as u64),
+                None => return Ok(outcome),
+            },
+        };
+        let is_expired = |path: &Path| -> std::io::Result<Option<u64>> {
+            let metadata = std::fs::metadata(path)?;
+            Ok((metadata.modified()? <= expire).then_some(metadata.len()))
+        };
+
+        let reachable = self.prune_reachable_objects()?;
+        let loose = self.maintenance_loose_objects_store();
+        for id in loose.iter() {
+            let id = id?;
+            if reachable.contains(&id) {
+                continue;
+            }
+            let path = loose.object_path(&id);
+            let Some(size) = is_expired(&path)? else {
+                continue;
+            };
+            if !options.dry_run {
+                std::fs::remove_file(&path)?;
+                if let Some(fan_out_dir) = path.parent() {
+                    std::fs::remove_dir(fan_out_dir).ok();
+                }
+            }
+            outcome.loose_objects.push(id);
+            outcome.freed_bytes += size;
+        }
+        outcome.loose_objects.sort();
+
+        let mut packs_changed = false;
+        for index_path in self.maintenance_packs()? {
+            let pack_path = index_path.with_extension("pack");
+            if is_expired(&pack_path)?.is_none() {
+                continue;
+            }
+            let index = gix_pack::index::File::at(&index_path, self.object_hash())?;
+            let (reachable_in_pack, unreachable_in_pack): (Vec<_>, Vec<_>) = index
+                .iter()
+                .map(|entry| entry.oid)
+                .partition(|id| reachable.contains(id));
+            if unreachable_in_pack.is_empty() {
+                continue;
+            }
+            outcome.packed_objects += unreachable_in_pack.len();
+            outcome.packs += 1;
+            if options.dry_run {
+                if reachable_in_pack.is_empty() {
+                    outcome.freed_bytes += pack_files_size(&index_path)?;
+                }
+                continue;
+            }
+            if !reachable_in_pack.is_empty() {
+                let bundle = self.maintenance_write_pack(reachable_in_pack)?;
+                if bundle.index_path.as_deref() == Some(index_path.as_path()) {
+                    continue;
+                }
+                if let Some(new_index_path) = &bundle.index_path {
+                    outcome.freed_bytes = outcome.freed_bytes.saturating_sub(pack_files_size(new_index_path)?);
+                }
+            }
+            outcome.freed_bytes += pack_files_size(&index_path)?;
+            remove_pack_files(&index_path)?;
+            packs_changed = true;
+        }
+        if packs_changed {
+            let multi_index_path = self.objects.store_ref().path().join("pack").join("multi-pack-index");
+            if multi_index_path.is_file() {
+                std::fs::remove_file(multi_index_path)?;
+            }
+        }
+        Ok(outcome)
+    }
+}
+
+/// Configuration
+impl Repository {
+    fn maintenance_auto(&self) -> Result<bool, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .boolean_filter(
+                "maintenance",
+                None,
+                Maintenance::AUTO.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Maintenance::AUTO.enrich_error(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(true))
+    }
+
+    fn maintenance_strategy(&self) -> Result<maintenance::Strategy, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .string_filter(
+                "maintenance",
+                None,
+                Maintenance::STRATEGY.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Maintenance::STRATEGY.try_into_strategy(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or_default())
+    }
+
+    fn maintenance_task_enabled(
+        &self,
+        task: Task,
+        strategy: maintenance::Strategy,
+    ) -> Result<bool, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .boolean_filter(
+                "maintenance",
+                Some(task.as_str().into()),
+                Maintenance::TASK_ENABLED.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Maintenance::TASK_ENABLED.enrich_error(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or_else(|| strategy.is_enabled(task)))
+    }
+
+    fn maintenance_task_schedule(
+        &self,
+        task: Task,
+        strategy: maintenance::Strategy,
+    ) -> Result<Option<maintenance::Schedule>, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .string_filter(
+                "maintenance",
+                Some(task.as_str().into()),
+                Maintenance::TASK_SCHEDULE.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Maintenance::TASK_SCHEDULE.try_into_schedule(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .or_else(|| strategy.schedule(task)))
+    }
+
+    /// Return `maintenance.<task>.auto` or `default`.
+    fn maintenance_task_auto_limit(&self, task: Task, default: i64) -> Result<i64, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .integer_filter(
+                "maintenance",
+                Some(task.as_str().into()),
+                Maintenance::TASK_AUTO.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Maintenance::TASK_AUTO.try_into_auto_limit(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(default))
+    }
+
+    fn maintenance_gc_limit(
+        &self,
+        key: &'static crate::config::tree::keys::UnsignedInteger,
+        default: u64,
+    ) -> Result<u64, maintenance::Error> {
+        Ok(self
+            .config
+            .resolved
+            .integer_filter("gc", None, key.name, &mut self.filter_config_section())
+            .map(|value| key.try_into_u64(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(default))
+    }
+
+    fn maintenance_expiry(
+        &self,
+        key: &'static Expiry,
+        default: &str,
+        now: SystemTime,
+    ) -> Result<Option<gix_date::Time>, maintenance::Error> {
+        let value = self
+            .config
+            .resolved
+            .string_filter("gc", None, key.name, &mut self.filter_config_section())
+            .map(|value| key.try_into_expiry(value, now))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?;
+        Ok(match value {
+            Some(expiry) => expiry,
+            None => key.try_into_expiry(std::borrow::Cow::Borrowed(default.into()), now)?,
+        })
+    }
+}
+
+/// Tasks
+impl Repository {
+    /// Return `true` if `task` should run according to its thresholds.
+    fn maintenance_task_is_needed(&self, task: Task) -> Result<bool, maintenance::Error> {
+        let limit = match task {
+            Task::PackRefs | Task::ReflogExpire => return self.maintenance_gc_is_needed(),
+            Task::LooseObjects => self.maintenance_task_auto_limit(task, 100)?,
+            Task::IncrementalRepack => self.maintenance_task_auto_limit(task, 10)?,
+            Task::CommitGraph => self.maintenance_task_auto_limit(task, 100)?,
+        };
+        let limit = match limit {
+            0 => return Ok(false),
+            limit if limit < 0 => return Ok(true),
+            limit => limit as usize,
+        };
+        Ok(match task {
+            Task::LooseObjects => {
+                let mut count = 0;
+                for id in self.maintenance_loose_objects_store().iter() {
+                    id?;
+                    count += 1;
+                    if count >= limit {
+                        break;
+                    }
+                }
+                count >= limit
+            }
+            Task::IncrementalRepack => self.maintenance_packs()?.len() >= limit,
+            Task::CommitGraph => {
+                if self.is_shallow() {
+                    return Ok(false);
+                }
+                let graph = self.commit_graph().ok();
+                let mut count = 0;
+                let mut seen = HashSet::default();
+                let mut queue = self.maintenance_commit_tips()?;
+                while let Some(id) = queue.pop() {
+                    if !seen.insert(id) || graph.as_ref().and_then(|graph| graph.lookup(id)).is_some() {
+                        continue;
+                    }
+                    count += 1;
+                    if count >= limit {
+                        break;
+                    }
+                    let object = self.find_object(id)?;
+                    queue.extend(gix_object::CommitRef::from_bytes(&object.data)?.parents());
+                }
+                count >= limit
+            }
+            Task::PackRefs | Task::ReflogExpire => unreachable!("handled above"),
+        })
+    }
+
+    /// Return `true` if the estimated amount of loose objects exceeds `gc.auto` or the amount of packs exceeds `gc.autoPackLimit`.
+    fn maintenance_gc_is_needed(&self) -> Result<bool, maintenance::Error> {
+        let auto = self.maintenance_gc_limit(&Gc::AUTO, 6700)?;
+        if auto == 0 {
+            return Ok(false);
+        }
+        let pack_limit = self.maintenance_gc_limit(&Gc::AUTO_PACK_LIMIT, 50)?;
+        if pack_limit != 0 && self.maintenance_packs()?.len() as u64 > pack_limit {
+            return Ok(true);
+        }
+
+        // Like git, estimate the amount of loose objects by counting those in one of the 256 fan-out directories.
+        let hex_len = self.object_hash().len_in_hex();
+        let mut loose_in_17 = 0;
+        match std::fs::read_dir(self.objects.store_ref().path().join("17")) {
+            Ok(entries) => {
+                for entry in entries {
+                    let name = entry?.file_name();
+                    let name = name.to_string_lossy();
+                    if name.len() == hex_len - 2 && name.bytes().all(|b| b.is_ascii_hexdigit()) {
+                        loose_in_17 += 1;
+                    }
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+        Ok(loose_in_17 > (auto + 255) / 256)
+    }
+
+    fn maintenance_pack_refs(&self) -> Result<TaskOutcome, maintenance::Error> {
+        let mut edits = Vec::new();
+        for reference in self.refs.loose_iter()? {
+            let reference = reference?;
+            if let Target::Peeled(id) = reference.target {
+                edits.push(RefEdit {
+                    change: Change::Update {
+                        log: LogChange::default(),
+                        expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                        new: Target::Peeled(id),
+                    },
+                    name: reference.name,
+                    deref: false,
+                });
+            }
+        }
+        let packed = edits.len();
+        if !edits.is_empty() {
+            let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
+            self.refs
+                .transaction()
+                .packed_refs(PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+                    Box::new(self.objects.clone()),
+                ))
+                .prepare(edits, file_lock_fail, packed_refs_lock_fail)?
+                .commit(self.committer().transpose()?)?;
+        }
+        Ok(TaskOutcome::PackRefs { packed })
+    }
+
+    fn maintenance_reflog_expire(&self) -> Result<TaskOutcome, maintenance::Error> {
+        let now = SystemTime::now();
+        let expire = self.maintenance_expiry(&Gc::REFLOG_EXPIRE, "90 days", now)?;
+        let expire_unreachable = self.maintenance_expiry(&Gc::REFLOG_EXPIRE_UNREACHABLE, "30 days", now)?;
+        if expire.is_none() && expire_unreachable.is_none() {
+            return Ok(TaskOutcome::ReflogExpire { removed: 0 });
+        }
+
+        let mut logs: Vec<(FullName, Option<ObjectId>)> = vec![(
+            "HEAD".try_into().expect("valid"),
+            self.head()?.id().map(crate::Id::detach),
+        )];
+        for reference in self.references()?.all()? {
+            let mut reference = reference?;
+            let tip = reference.peel_to_id_in_place().ok().map(crate::Id::detach);
+            logs.push((reference.inner.name, tip));
+        }
+
+        let lock_fail = self.config.lock_timeout()?.0;
+        let mut removed = 0;
+        for (name, tip) in logs {
+            let path = self.refs.reflog_path(name.as_ref());
+            if !path.is_file() {
+                continue;
+            }
+            let mut lock = gix_lock::File::acquire_to_update_resource(&path, lock_fail, None)?;
+            let data = std::fs::read(&path)?;
+            let mut reachable = None;
+            let mut kept = Vec::new();
+            let mut removed_from_log = 0;
+            for line in gix_ref::file::log::iter::forward(&data) {
+                let line: gix_ref::log::Line = line?.into();
+                let time = line.signature.time.seconds;
+                let is_expired = matches!(expire, Some(expire) if time < expire.seconds)
+                    || (matches!(expire_unreachable, Some(expire) if time < expire.seconds) && {
+                        let reachable = match &mut reachable {
+                            Some(reachable) => reachable,
+                            None => reachable.insert(self.maintenance_ancestors(tip)?),
+                        };
+                        !reachable.contains(&line.new_oid)
+                    });
+                if is_expired {
+                    removed_from_log += 1;
+                } else {
+                    kept.push(line);
+                }
+            }
+            if removed_from_log != 0 {
+                for line in &kept {
+                    line.write_to(&mut lock)?;
+                }
+                lock.commit()?;
+                removed += removed_from_log;
+            }
+        }
+        Ok(TaskOutcome::ReflogExpire { removed })
+    }
+
+    fn maintenance_loose_objects(&self) -> Result<TaskOutcome, maintenance::Error> {
+        let loose = self.maintenance_loose_objects_store();
+        let indices = self.maintenance_pack_indices(true)?;
+        let mut removed = 0;
+        let mut unpacked = Vec::new();
+        for id in loose.iter() {
+            let id = id?;
+            if indices.iter().any(|index| index.lookup(id).is_some()) {
+                let path = loose.object_path(&id);
+                std::fs::remove_file(&path)?;
+                if let Some(fan_out_dir) = path.parent() {
+                    std::fs::remove_dir(fan_out_dir).ok();
+                }
+                removed += 1;
+            } else {
+                unpacked.push(id);
+            }
+        }
+        let packed = unpacked.len();
+        if !unpacked.is_empty() {
+            self.maintenance_write_pack(unpacked)?;
+        }
+        Ok(TaskOutcome::LooseObjects { removed, packed })
+    }
+
+    fn maintenance_incremental_repack(&self) -> Result<TaskOutcome, maintenance::Error> {
+        let packs = self.maintenance_packs()?;
+        if packs.len() < 2 {
+            return Ok(TaskOutcome::IncrementalRepack { packs: 0, objects: 0 });
+        }
+        let mut ids = Vec::new();
+        for index_path in &packs {
+            let index = gix_pack::index::File::at(index_path, self.object_hash())?;
+            ids.extend(index.iter().map(|entry| entry.oid));
+        }
+        ids.sort();
+        ids.dedup();
+        let objects = ids.len();
+        let bundle = self.maintenance_write_pack(ids)?;
+
+        let pack_dir = self.objects.store_ref().path().join("pack");
+        let multi_index_path = pack_dir.join("multi-pack-index");
+        if multi_index_path.is_file() {
+            std::fs::remove_file(multi_index_path)?;
+        }
+        for index_path in packs
+            .iter()
+            .filter(|path| Some(path.as_path()) != bundle.index_path.as_deref())
+        {
+            remove_pack_files(index_path)?;
+        }
+        Ok(TaskOutcome::IncrementalRepack {
+            packs: packs.len(),
+            objects,
+        })
+    }
+
+    fn maintenance_commit_graph(&self) -> Result<TaskOutcome, maintenance::Error> {
+        let outcome = self.write_commit_graph(Default::default(), &mut gix_features::progress::Discard)?;
+        Ok(TaskOutcome::CommitGraph {
+            commits: outcome.commits,
+        })
+    }
+}
+
+/// Commit-graph
+impl Repository {
+    /// Write a commit-graph with all commits reachable from `HEAD` and all references, similar to
+    /// `git commit-graph write --reachable`, and return what was written.
+    ///
+    /// Each traversed commit is counted with `progress`.
+    /// Unless [`options.split`](maintenance::commit_graph::Options::split) is `true`, a single `objects/info/commit-graph`
+    /// file is written and a chain of split commit-graph files is removed, and vice versa.
+    /// Nothing is written in shallow repositories.
+    ///
+    /// ### Deviation
+    ///
+    /// * Only the commit data of the commit-graph format version 1 is written, without generation data or bloom filters.
+    /// * Commits of merged split commit-graph files are dropped if they are no longer reachable.
+    pub fn write_commit_graph(
+        &self,
+        options: maintenance::commit_graph::Options,
+        progress: &mut dyn gix_features::progress::Progress,
+    ) -> Result<maintenance::commit_graph::Outcome, maintenance::Error> {
+        let _span = gix_trace::coarse!("gix::write_commit_graph()", options = ?options);
+        if self.is_shallow() {
+            return Ok(Default::default());
+        }
+        progress.init(None, gix_features::progress::count("commits"));
+        let mut commits = Vec::new();
+        let mut seen = HashSet::default();
+        let mut queue = self.maintenance_commit_tips()?;
+        while let Some(id) = queue.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            let object = self.find_object(id)?;
+            let commit = gix_object::CommitRef::from_bytes(&object.data)?;
+            queue.extend(commit.parents());
+            commits.push(gix_commitgraph::file::write::Commit {
+                id,
+                tree: commit.tree(),
+                parents: commit.parents().collect(),
+                commit_time: commit.committer.time.seconds.max(0) as u64,
+            });
+            progress.inc();
+        }
+
+        let info_dir = self.objects.store_ref().path().join("info");
+        std::fs::create_dir_all(&info_dir)?;
+        if options.split {
+            let (commits, layers) = self.write_split_commit_graph(commits, &info_dir)?;
+            return Ok(maintenance::commit_graph::Outcome { commits, layers });
+        }
+        let mut lock = gix_lock::File::acquire_to_update_resource(
+            info_dir.join("commit-graph"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+        let outcome = gix_commitgraph::File::write_to(commits, &mut lock, self.object_hash())?;
+        lock.commit()?;
+        // A chain of split commit-graphs is superseded by the new commit-graph file.
+        let chain_dir = info_dir.join("commit-graphs");
+        if chain_dir.is_dir() {
+            std::fs::remove_dir_all(chain_dir)?;
+        }
+        Ok(maintenance::commit_graph::Outcome {
+            commits: outcome.num_commits,
+            layers: 0,
+        })
+    }
+}
+
+/// Utilities
+impl Repository {
+    /// Write the commit-graph split into multiple files and return the amount of commits written and the amount of files in the chain.
+    fn write_split_commit_graph(
+        &self,
+        commits: Vec<gix_commitgraph::file::write::Commit>,
+        info_dir: &Path,
+    ) -> Result<(u32, usize), maintenance::Error> {
+        let chain_dir = info_dir.join("commit-graphs");
+        std::fs::create_dir_all(&chain_dir)?;
+        let mut chain_lock = gix_lock::File::acquire_to_update_resource(
+            chain_dir.join("commit-graph-chain"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+        let mut layers = Vec::new();
+        if let Ok(chain) = std::fs::read_to_string(chain_dir.join("commit-graph-chain")) {
+            for checksum in chain.lines() {
+                layers.push(gix_commitgraph::File::at(
+                    chain_dir.join(format!("graph-{checksum}.graph")),
+                )?);
+            }
+        }
+
+        let mut num_new_commits = commits
+            .iter()
+            .filter(|commit| layers.iter().all(|layer| layer.lookup(commit.id).is_none()))
+            .count();
+        if num_new_commits == 0 {
+            return Ok((0, layers.len()));
+        }
+        let mut merged_layers = Vec::new();
+        while let Some(layer) = layers.pop() {
+            if num_new_commits * 2 <= layer.num_commits() as usize {
+                layers.push(layer);
+                break;
+            }
+            num_new_commits += layer.num_commits() as usize;
+            merged_layers.push(layer.path().to_owned());
+        }
+
+        let base = gix_commitgraph::Graph::new(layers)?;
+        let mut buf = Vec::new();
+        let outcome = gix_commitgraph::File::write_split_to(commits, &base, &mut buf, self.object_hash())?;
+        std::fs::write(chain_dir.join(format!("graph-{}.graph", outcome.checksum)), buf)?;
+        let mut chain: Vec<_> = base.files().iter().map(|layer| layer.checksum().to_owned()).collect();
+        chain.push(outcome.checksum);
+        drop(base);
+        for checksum in &chain {
+            writeln!(chain_lock, "{checksum}")?;
+        }
+        chain_lock.commit()?;
+
+        for path in merged_layers {
+            std::fs::remove_file(path)?;
+        }
+        // The chain of split commit-graphs is only used if there is no commit-graph file.
+        let graph_path = info_dir.join("commit-graph");
+        if graph_path.is_file() {
+            std::fs::remove_file(graph_path)?;
+        }
+        Ok((outcome.num_commits, chain.len()))
+    }
+
+    fn maintenance_lock(&self) -> Result<gix_lock::Marker, maintenance::Error> {
+        Ok(gix_lock::Marker::acquire_to_hold_resource(
+            self.objects.store_ref().path().join("maintenance"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?)
+    }
+
+    /// Return the ids of all objects reachable from references, their reflogs, `HEAD` and the index of this and all
+    /// linked worktrees. Missing objects are ignored.
+    fn prune_reachable_objects(&self) -> Result<HashSet, maintenance::Error> {
+        let mut tips = Vec::new();
+        self.prune_add_tips(&mut tips)?;
+        for worktree in self.worktrees()? {
+            worktree
+                .into_repo_with_possibly_inaccessible_worktree()?
+                .prune_add_tips(&mut tips)?;
+        }
+
+        let mut reachable = HashSet::default();
+        while let Some(id) = tips.pop() {
+            if id.is_null() || !reachable.insert(id) {
+                continue;
+            }
+            let Some(object) = self.try_find_object(id)? else {
+                continue;
+            };
+            match object.kind {
+                gix_object::Kind::Commit => {
+                    let mut commit = gix_object::CommitRefIter::from_bytes(&object.data);
+                    tips.push(commit.tree_id()?);
+                    tips.extend(commit.parent_ids());
+                }
+                gix_object::Kind::Tree => {
+                    for entry in gix_object::TreeRefIter::from_bytes(&object.data) {
+                        let entry = entry?;
+                        if entry.mode.is_tree() {
+                            tips.push(entry.oid.to_owned());
+                        } else if !entry.mode.is_commit() {
+                            // Blobs don't refer to other objects, so there is no need to read them.
+                            reachable.insert(entry.oid.to_owned());
+                        }
+                    }
+                }
+                gix_object::Kind::Tag => tips.push(gix_object::TagRefIter::from_bytes(&object.data).target_id()?),
+                gix_object::Kind::Blob => {}
+            }
+        }
+        Ok(reachable)
+    }
+
+    /// Add the objects pointed to by `HEAD`, all references, their reflogs and the index of this worktree to `tips`.
+    fn prune_add_tips(&self, tips: &mut Vec<ObjectId>) -> Result<(), maintenance::Error> {
+        let mut add_log = |log: Option<gix_ref::file::log::iter::Forward<'_>>| -> Result<(), maintenance::Error> {
+            for line in log.into_iter().flatten() {
+                let line = line?;
+                tips.push(line.previous_oid());
+                tips.push(line.new_oid());
+            }
+            Ok(())
+        };
+        let head = self.head()?;
+        add_log(head.log_iter().all()?)?;
+        for reference in self.references()?.all()? {
+            let reference = reference?;
+            add_log(reference.log_iter().all()?)?;
+        }
+        tips.extend(head.id().map(crate::Id::detach));
+        for reference in self.references()?.all()? {
+            if let Some(id) = reference?.try_id() {
+                tips.push(id.detach());
+            }
+        }
+
+        if let Some(index) = self.try_index()? {
+            tips.extend(
+                index
+                    .entries()
+                    .iter()
+                    .filter(|entry| !entry.mode.is_submodule())
+                    .map(|entry| entry.id),
+            );
+            let mut trees: Vec<_> = index.tree().into_iter().collect();
+            while let Some(tree) = trees.pop() {
+                if tree.num_entries.is_some() {
+                    tips.push(tree.id);
+                }
+                trees.extend(&tree.children);
+            }
+        }
+        Ok(())
+    }
+
+    fn maintenance_loose_objects_store(&self) -> gix_odb::loose::Store {
+        gix_odb::loose::Store::at(self.objects.store_ref().path(), self.object_hash())
+            .with_compression(self.objects.store_ref().loose_compression())
+            .with_zlib_backend(self.objects.store_ref().zlib_backend())
+    }
+
+    /// Return the paths to the indices of all packs that aren't marked to be kept with a `.keep` file, sorted by path.
+    fn maintenance_packs(&self) -> Result<Vec<PathBuf>, maintenance::Error> {
+        pack_index_paths(&self.objects.store_ref().path().join("pack"), false)
+    }
+
+    /// Open the indices of all packs, including those marked to be kept if `include_kept` is `true`.
+    fn maintenance_pack_indices(&self, include_kept: bool) -> Result<Vec<gix_pack::index::File>, maintenance::Error> {
+        pack_index_paths(&self.objects.store_ref().path().join("pack"), include_kept)?
+            .into_iter()
+            .map(|path| Ok(gix_pack::index::File::at(path, self.object_hash())?))
+            .collect()
+    }
+
+    /// Return the ids of all commits `HEAD` and all references point to, after peeling tags.
+    fn maintenance_commit_tips(&self) -> Result<Vec<ObjectId>, maintenance::Error> {
+        let mut tips: Vec<_> = self.head()?.id().map(crate::Id::detach).into_iter().collect();
+        for reference in self.references()?.all()? {
+            let mut reference = reference?;
+            tips.push(reference.peel_to_id_in_place()?.detach());
+        }
+        let mut commits = Vec::with_capacity(tips.len());
+        for tip in tips {
+            let object = self.find_object(tip)?.peel_tags_to_end()?;
+            if object.kind == gix_object::Kind::Commit {
+                commits.push(object.id);
+            }
+        }
+        Ok(commits)
+    }
+
+    /// Return the ids of all commits reachable from `tip`, ignoring missing commits.
+    fn maintenance_ancestors(&self, tip: Option<ObjectId>) -> Result<HashSet, maintenance::Error> {
+        let mut seen = HashSet::default();
+        let mut queue: Vec<_> = tip.into_iter().collect();
+        while let Some(id) = queue.pop() {
+            if seen.contains(&id) {
+                continue;
+            }
+            let Some(object) = self.try_find_object(id).ok().flatten() else {
+                continue;
+            };
+            seen.insert(id);
+            if object.kind == gix_object::Kind::Commit {
+                queue.extend(gix_object::CommitRef::from_bytes(&object.data)?.parents());
+            }
+        }
+        Ok(seen)
+    }
+
+    /// Write all objects with `ids` into a new pack, copying packed objects and their deltas as they are,
+    /// and return information about the new pack.
+    fn maintenance_write_pack(
+        &self,
+        ids: Vec<ObjectId>,
+    ) -> Result<gix_pack::bundle::write::Outcome, maintenance::Error> {
+        let _span = gix_trace::detail!("gix::maintenance_write_pack()", objects = ids.len());
+        let should_interrupt = AtomicBool::new(false);
+        let pack_dir = self.objects.store_ref().path().join("pack");
+        std::fs::create_dir_all(&pack_dir)?;
+        let thread_limit = self
+            .config
+            .resolved
+            .integer_filter("pack", None, Pack::THREADS.name, &mut self.filter_config_section())
+            .map(|threads| Pack::THREADS.try_into_usize(threads))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?;
+        let index_version = self
+            .config
+            .resolved
+            .integer_filter(
+                "pack",
+                None,
+                Pack::INDEX_VERSION.name,
+                &mut self.filter_config_section(),
+            )
+            .map(|value| Pack::INDEX_VERSION.try_into_index_version(value))
+            .transpose()
+            .with_leniency(self.config.lenient_config)?
+            .unwrap_or(gix_pack::index::Version::V2);
+
+        let mut handle = self.objects.clone().into_arc()?;
+        handle.prevent_pack_unload();
+        handle.ignore_replacements = true;
+        let num_objects = ids.len();
+        let (counts, _) = gix_pack::data::output::count::objects_unthreaded(
+            &handle,
+            &mut ids.into_iter().map(Ok),
+            &gix_features::progress::Discard,
+            &should_interrupt,
+            gix_pack::data::output::count::objects::ObjectExpansion::AsIs,
+        )?;
+        let entries = gix_features::parallel::InOrderIter::from(gix_pack::data::output::entry::iter_from_counts(
+            counts,
+            handle,
+            Box::new(gix_features::progress::Discard),
+            gix_pack::data::output::entry::iter_from_counts::Options {
+                thread_limit,
+                mode: gix_pack::data::output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                chunk_size: 1000,
+                version: gix_pack::data::Version::V2,
+                compression_level: self.config.pack_compression()?.level(),
+                zlib_backend: self.config.zlib_backend()?,
+            },
+        ));
+
+        let mut pack = gix_tempfile::new(
+            &pack_dir,
+            gix_tempfile::ContainingDirectory::Exists,
+            gix_tempfile::AutoRemove::Tempfile,
+        )?;
+        let outcome = pack.with_mut(|file| -> Result<_, maintenance::Error> {
+            for written in gix_pack::data::output::bytes::FromEntriesIter::new(
+                entries,
+                &mut *file,
+                num_objects as u32,
+                gix_pack::data::Version::V2,
+                self.object_hash(),
+            ) {
+                written?;
+            }
+            file.rewind()?;
+            Ok(gix_pack::Bundle::write_to_directory(
+                &mut BufReader::new(&mut *file),
+                Some(&pack_dir),
+                &mut gix_features::progress::Discard,
+                &should_interrupt,
+                None::<gix_object::find::Never>,
+                gix_pack::bundle::write::Options {
+                    thread_limit,
+                    iteration_mode: gix_pack::data::input::Mode::Verify,
+                    index_version,
+                    object_hash: self.object_hash(),
+                },
+            )?)
+        })??;
+        if let Some(keep_path) = &outcome.keep_path {
+            std::fs::remove_file(keep_path)?;
+        }
+        Ok(outcome)
+    }
+}
+
+/// Return the size of all files belonging to the pack with `index_path`.
+fn pack_files_size(index_path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for extension in ["idx", "pack", "rev", "bitmap", "mtimes"] {
+        match std::fs::metadata(index_path.with_extension(extension)) {
+            Ok(metadata) => size += metadata.len(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(size)
+}
+
+/// Remove all files belonging to the pack with `index_path`, with the index being removed last like `git` does.
+fn remove_pack_files(index_path: &Path) -> std::io::Result<()> {
+    for extension in ["pack", "rev", "bitmap", "mtimes"] {
+        let path = index_path.with_extension(extension);
+        if path.is_file() {
+            std::fs::remove_file(path)?;
+        }
+    }
+    std::fs::remove_file(index_path)
+}
+
+/// Return the paths to the indices of all packs in `pack_dir`, including those marked to be kept if `include_kept` is `true`,
+/// sorted by path.
+fn pack_index_paths(pack_dir: &Path, include_kept: bool) -> Result<Vec<PathBuf>, maintenance::Error> {
+    let entries = match std::fs::read_dir(pack_dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut out = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|ext| ext.to_str()) == Some("idx")
+            && path.with_extension("pack").is_file()
+            && (include_kept || !path.with_extension("keep").exists())
+        {
+            out.push(path);
+        }
+    }
+    out.sort();
+    Ok(out)
+}
@@ -0,0 +1,191 @@
+use std::collections::{BTreeMap, HashSet};
+
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    merge,
+    merge::{Conflict, ConflictKind, Version},
+    Repository,
+};
+
+/// Merge
+impl Repository {
+    /// Perform a three-way merge of the trees `ours` and `theirs`, using `ancestor` as their common ancestor, and return an index
+    /// with the result along with all paths that couldn't be merged automatically.
+    ///
+    /// Paths that were changed on only one side are taken from that side, while files that were changed on both sides are merged
+    /// line by line with [`merge::blob::text()`], using `labels` to annotate conflict markers. The merged blobs, including those
+    /// with conflict markers, are written to the object database.
+    /// Files that are [binary for merging](crate::binary::Purpose::Merge) according to the attributes in `ours`, or by their
+    /// content, aren't merged, and *our* version is kept as conflict.
+    ///
+    /// Nothing is written to the index or the worktree, see [`merge::Outcome`] for ways to use the result.
+    ///
+    /// ### Deviation
+    ///
+    /// * There is no rename tracking, so renames are seen as deletion and addition.
+    /// * Merge drivers configured via `git-attributes` are not respected except for the builtin `binary` driver, and files are
+    ///   merged without applying filters.
+    pub fn merge_trees(
+        &self,
+        ancestor: impl Into<ObjectId>,
+        ours: impl Into<ObjectId>,
+        theirs: impl Into<ObjectId>,
+        labels: merge::blob::Labels<'_>,
+    ) -> Result<merge::Outcome, merge::Error> {
+        let ancestor = self.index_from_tree(&ancestor.into())?;
+        let ours = self.index_from_tree(&ours.into())?;
+        let theirs = self.index_from_tree(&theirs.into())?;
+
+        #[derive(Default, Clone, Copy)]
+        struct Sides<'a> {
+            ancestor: Option<&'a gix_index::Entry>,
+            ours: Option<&'a gix_index::Entry>,
+            theirs: Option<&'a gix_index::Entry>,
+        }
+        let mut paths = BTreeMap::<&BStr, Sides<'_>>::new();
+        for (entry, path) in ancestor.entries().iter().map(|e| (e, e.path(&ancestor))) {
+            paths.entry(path).or_default().ancestor = Some(entry);
+        }
+        for (entry, path) in ours.entries().iter().map(|e| (e, e.path(&ours))) {
+            paths.entry(path).or_default().ours = Some(entry);
+        }
+        for (entry, path) in theirs.entries().iter().map(|e| (e, e.path(&theirs))) {
+            paths.entry(path).or_default().theirs = Some(entry);
+        }
+
+        fn version(entry: Option<&gix_index::Entry>) -> Option<Version> {
+            entry.map(|e| Version { id: e.id, mode: e.mode })
+        }
+        fn is_mergeable_file(entry: Option<&gix_index::Entry>) -> bool {
+            entry.map_or(true, |e| {
+                matches!(
+                    e.mode.to_tree_entry_mode().map(|mode| mode.kind()),
+                    Some(gix_object::tree::EntryKind::Blob | gix_object::tree::EntryKind::BlobExecutable)
+                )
+            })
+        }
+
+        let mut detector = self.binary_detector(
+            &ours,
+            gix_worktree::stack::state::attributes::Source::IdMapping,
+            crate::binary::Purpose::Merge,
+        )?;
+        let mut merged = Vec::<(&BStr, Version)>::with_capacity(paths.len());
+        let mut conflicts = Vec::new();
+        let (mut ancestor_buf, mut ours_buf, mut theirs_buf, mut out) =
+            (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for (path, sides) in paths {
+            let (ancestor, ours, theirs) = (version(sides.ancestor), version(sides.ours), version(sides.theirs));
+            if ours == theirs || ancestor == theirs {
+                merged.extend(ours.map(|v| (path, v)));
+                continue;
+            }
+            if ancestor == ours {
+                merged.extend(theirs.map(|v| (path, v)));
+                continue;
+            }
+            let conflict = |kind| Conflict {
+                rela_path: path.to_owned(),
+                kind,
+                ancestor,
+                ours,
+                theirs,
+            };
+            let (Some(our_version), Some(their_version)) = (ours, theirs) else {
+                merged.extend(ours.or(theirs).map(|v| (path, v)));
+                conflicts.push(conflict(ConflictKind::ModifiedDeleted));
+                continue;
+            };
+            if !(is_mergeable_file(sides.ancestor) && is_mergeable_file(sides.ours) && is_mergeable_file(sides.theirs))
+            {
+                merged.push((path, our_version));
+                conflicts.push(conflict(ConflictKind::Unmergeable));
+                continue;
+            }
+            let mode = match ancestor.map(|v| v.mode) {
+                Some(mode) if mode == our_version.mode => Some(their_version.mode),
+                Some(mode) if mode == their_version.mode => Some(our_version.mode),
+                _ if our_version.mode == their_version.mode => Some(our_version.mode),
+                _ => None,
+            };
+            let resolution = if our_version.id == their_version.id {
+                out.clear();
+                merge::blob::Resolution::Complete
+            } else if detector.is_binary_by_attributes(path, Some(our_version.mode))? == Some(true) {
+                out.clear();
+                out.extend_from_slice(&self.find_object(our_version.id)?.data);
+                merge::blob::Resolution::Conflict
+            } else {
+                ancestor_buf.clear();
+                if let Some(ancestor) = ancestor {
+                    ancestor_buf.extend_from_slice(&self.find_object(ancestor.id)?.data);
+                }
+                ours_buf.clear();
+                ours_buf.extend_from_slice(&self.find_object(our_version.id)?.data);
+                theirs_buf.clear();
+                theirs_buf.extend_from_slice(&self.find_object(their_version.id)?.data);
+                merge::blob::text(&ancestor_buf, &ours_buf, &theirs_buf, labels, &mut out)
+            };
+            let id = if our_version.id == their_version.id {
+                our_version.id
+            } else {
+                self.write_blob(&out)?.detach()
+            };
+            merged.push((
+                path,
+                Version {
+                    id,
+                    mode: mode.unwrap_or(our_version.mode),
+                },
+            ));
+            match (resolution, mode) {
+                (merge::blob::Resolution::Complete, Some(_)) => {}
+                (merge::blob::Resolution::Complete, None) => conflicts.push(conflict(ConflictKind::Unmergeable)),
+                (merge::blob::Resolution::Conflict, _) => conflicts.push(conflict(ConflictKind::Content)),
+            }
+        }
+
+        // A file can't be at a location that is also a directory, in which case the directory wins.
+        let directories: HashSet<&BStr> = merged
+            .iter()
+            .flat_map(|(path, _)| path.rfind_iter("/").map(|pos| path[..pos].as_bstr()))
+            .collect();
+        if !directories.is_empty() {
+            merged.retain(|(path, merged_version)| {
+                if !directories.contains(path) {
+                    return true;
+                }
+                match conflicts.iter_mut().find(|c| c.rela_path == *path) {
+                    Some(conflict) => conflict.kind = ConflictKind::DirectoryFile,
+                    None => {
+                        let side = |entry: Option<&gix_index::Entry>| version(entry).filter(|v| v == merged_version);
+                        conflicts.push(Conflict {
+                            rela_path: (*path).to_owned(),
+                            kind: ConflictKind::DirectoryFile,
+                            ancestor: None,
+                            ours: side(ours.entry_by_path(path)),
+                            theirs: side(theirs.entry_by_path(path)),
+                        });
+                    }
+                }
+                false
+            });
+            conflicts.sort_by(|a, b| a.rela_path.cmp(&b.rela_path));
+        }
+
+        let mut index = gix_index::State::new(self.object_hash());
+        for (path, version) in merged {
+            index.dangerously_push_entry(
+                Default::default(),
+                version.id,
+                gix_index::entry::Flags::empty(),
+                version.mode,
+                path,
+            );
+        }
+        index.sort_entries();
+        Ok(merge::Outcome { index, conflicts })
+    }
+}
@@ -17,6 +17,33 @@ pub enum Kind {
     },
 }
 
+/// A source of thread-local [`Repository`](crate::Repository) handles that is cheap to clone, and `Send + Sync` with the
+/// `parallel` feature, for use with thread-pools.
+///
+/// Unlike [`ThreadSafeRepository::to_thread_local()`](crate::ThreadSafeRepository::to_thread_local()), each
+/// [handle](Pool::handle()) is a clone of the repository the pool was created from, and thus is configured just like it,
+/// including object and pack cache sizes, object database settings, the reference namespace and in-memory configuration changes.
+///
+/// ### What's shared and what's per-handle
+///
+/// All handles share:
+///
+/// * the object database, with its memory-mapped pack and index files and the knowledge about which of them are loaded,
+/// * the reference store along with the in-memory `packed-refs` buffer,
+/// * the lazily loaded index, `.gitmodules` file and shallow commits, each of which is refreshed if it changes on disk.
+///
+/// Each handle has its own:
+///
+/// * pack and object caches, which start empty but use the same constructors, and thus sizes,
+/// * buffers and decompression state for reading objects,
+/// * configuration snapshot, which starts as copy, so that changes via
+///   [`config_snapshot_mut()`](crate::Repository::config_snapshot_mut()) or
+///   [`reload_config()`](crate::Repository::reload_config()) only affect that handle.
+#[derive(Clone)]
+pub struct Pool {
+    repo: gix_features::threading::OwnShared<gix_features::threading::Mutable<crate::Repository>>,
+}
+
 /// Internal
 impl crate::Repository {
     #[inline]
@@ -36,10 +63,27 @@ impl crate::Repository {
     }
 }
 
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod am;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod apply;
 #[cfg(any(feature = "attributes", feature = "excludes"))]
 pub mod attributes;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod bisect;
+#[cfg(feature = "blob-diff")]
+mod blame;
+#[cfg(feature = "bundle")]
+mod bundle;
 mod cache;
+#[cfg(feature = "revision")]
+mod cat_file;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod checkout;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod cherry_pick;
 mod config;
+mod count_objects;
 ///
 #[allow(clippy::empty_docs)]
 #[cfg(feature = "blob-diff")]
@@ -48,32 +92,73 @@ pub mod diff;
 #[allow(clippy::empty_docs)]
 #[cfg(feature = "dirwalk")]
 mod dirwalk;
+#[cfg(feature = "blob-diff")]
+mod fast_export;
+#[cfg(feature = "revision")]
+mod fast_import;
 ///
 #[allow(clippy::empty_docs)]
 #[cfg(feature = "attributes")]
 pub mod filter;
+#[cfg(feature = "revision")]
+mod for_each_ref;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+mod format_patch;
+#[cfg(feature = "fsck")]
+mod fsck;
 mod graph;
-pub(crate) mod identity;
+#[cfg(feature = "blob-diff")]
+mod grep;
+#[cfg(feature = "command")]
+mod hook;
+///
+#[allow(clippy::empty_docs)]
+pub mod identity;
 mod impls;
 #[cfg(feature = "index")]
 mod index;
 pub(crate) mod init;
 mod kind;
 mod location;
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+mod log;
+#[cfg(feature = "status")]
+mod ls_files;
 #[cfg(feature = "mailmap")]
 mod mailmap;
+#[cfg(feature = "maintenance")]
+mod maintenance;
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+mod merge;
+#[cfg(feature = "notes")]
+mod note;
 mod object;
 #[cfg(feature = "attributes")]
 mod pathspec;
+mod pool;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+mod range_diff;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod rebase;
 mod reference;
 mod remote;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod reset;
+#[cfg(feature = "revision")]
+mod rev_list;
 #[cfg(feature = "revision")]
 mod revision;
 mod shallow;
+#[cfg(feature = "mailmap")]
+mod shortlog;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod stash;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
 mod thread_safe;
+mod trailers;
+mod update_refs;
 mod worktree;
 
 ///
@@ -94,6 +179,21 @@ pub mod index_from_tree {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "index")]
+pub mod write_tree_from_index {
+    /// The error returned by [Repository::write_tree_from_index()](crate::Repository::write_tree_from_index).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Cannot write a tree from an index with unresolved conflicts, first one at '{path}'")]
+        Unmerged { path: crate::bstr::BString },
+        #[error(transparent)]
+        WriteTree(#[from] crate::object::write::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod branch_remote_ref_name {
@@ -177,7 +277,9 @@ pub mod worktree_stream {
         FilterPipeline(#[from] crate::filter::pipeline::options::Error),
         #[error(transparent)]
         CommandContext(#[from] crate::config::command_context::Error),
-        #[error("Needed {id} to be a tree to turn into a workspace stream, got {actual}")]
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error("Needed {id} to be a tree or commit to turn into a workspace stream, got {actual}")]
         NotATree {
             id: gix_hash::ObjectId,
             actual: gix_object::Kind,
@@ -191,3 +293,39 @@ pub mod worktree_archive {
     /// The error returned by [`Repository::worktree_archive()`][crate::Repository::worktree_archive()].
     pub type Error = gix_archive::Error;
 }
+
+///
+#[cfg(feature = "revision")]
+pub mod ahead_behind {
+    /// The error returned by [`Repository::ahead_behind()`][crate::Repository::ahead_behind()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The commit {id} to start counting from could not be found")]
+        NotFound { id: gix_hash::ObjectId },
+        #[error(transparent)]
+        LookupCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+    }
+}
+
+///
+#[cfg(feature = "revision")]
+pub mod resolve_prefix {
+    use crate::revision::spec::parse::error::CandidateInfo;
+
+    /// The error returned by [`Repository::resolve_prefix()`][crate::Repository::resolve_prefix()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        LookupPrefix(#[from] gix_odb::store::prefix::lookup::Error),
+        #[error("An object prefixed {prefix} could not be found")]
+        NotFound { prefix: gix_hash::Prefix },
+        #[error("Short id {prefix} is ambiguous. Candidates are:\n{}", candidates.iter().map(|(oid, info)| format!("\t{oid} {info}")).collect::<Vec<_>>().join("\n"))]
+        Ambiguous {
+            prefix: gix_hash::Prefix,
+            /// The shortest unambiguous prefix of each matching object, along with information to tell them apart.
+            candidates: Vec<(gix_hash::Prefix, CandidateInfo)>,
+        },
+    }
+}
@@ -0,0 +1,18 @@
+use gix_ref::FullName;
+
+/// Notes
+impl crate::Repository {
+    /// Return a platform to read and edit the notes stored in the history of `reference`,
+    /// which is [`refs/notes/commits`](crate::note::DEFAULT_REF) by default in `git`.
+    ///
+    /// The reference doesn't have to exist yet, and will be created when the first note is attached.
+    pub fn notes<Name, E>(&self, reference: Name) -> Result<crate::note::Platform<'_>, E>
+    where
+        Name: TryInto<FullName, Error = E>,
+    {
+        Ok(crate::note::Platform {
+            repo: self,
+            reference: reference.try_into()?,
+        })
+    }
+}
@@ -1,5 +1,8 @@
 #![allow(clippy::result_large_err)]
-use std::ops::DerefMut;
+use std::{
+    io::{Read, Seek, Write as _},
+    ops::DerefMut,
+};
 
 use gix_hash::ObjectId;
 use gix_macros::momo;
@@ -13,6 +16,14 @@ use smallvec::SmallVec;
 
 use crate::{commit, ext::ObjectIdExt, object, tag, Blob, Id, Object, Reference, Tree};
 
+/// Blobs larger than this are streamed into a temporary file by [`write_blob_stream()`](crate::Repository::write_blob_stream())
+/// instead of being held in memory.
+const MAX_IN_MEMORY_BLOB_SIZE: u64 = 1024 * 1024;
+
+fn into_write_error(err: std::io::Error) -> object::write::Error {
+    object::write::Error(Box::new(err))
+}
+
 /// Methods related to object creation.
 impl crate::Repository {
     /// Find the object with `id` in the object database or return an error if it could not be found.
@@ -168,20 +179,62 @@ impl crate::Repository {
             .map(|oid| oid.attach(self))
     }
 
-    /// Write a blob from the given `Read` implementation.
+    /// Write a blob from the given `Read` implementation, reading it from its current position to the end.
     ///
-    /// Note that we hash the object in memory to avoid storing objects that are already present. That way,
+    /// Note that we hash the object before writing it to avoid storing objects that are already present. That way,
     /// we avoid writing duplicate objects using slow disks that will eventually have to be garbage collected.
     ///
-    /// If that is prohibitive, use the object database directly.
-    pub fn write_blob_stream(
-        &self,
-        mut bytes: impl std::io::Read + std::io::Seek,
-    ) -> Result<Id<'_>, object::write::Error> {
+    /// Small inputs are hashed in memory, while larger ones are streamed into a temporary file in the object database
+    /// first as the size of the object must be known before it can be hashed. This way, inputs of any size can be written
+    /// without holding them in memory, unless [objects are kept in memory](Self::with_object_memory()) anyway.
+    pub fn write_blob_stream(&self, mut bytes: impl std::io::Read) -> Result<Id<'_>, object::write::Error> {
         let mut buf = self.shared_empty_buf();
-        std::io::copy(&mut bytes, buf.deref_mut()).expect("write to memory works");
+        (&mut bytes)
+            .take(MAX_IN_MEMORY_BLOB_SIZE + 1)
+            .read_to_end(buf.deref_mut())
+            .map_err(into_write_error)?;
+        if buf.len() as u64 <= MAX_IN_MEMORY_BLOB_SIZE {
+            return self.write_blob_stream_inner(&buf);
+        }
+        if self.objects.has_object_memory() {
+            bytes.read_to_end(buf.deref_mut()).map_err(into_write_error)?;
+            return self.write_blob_stream_inner(&buf);
+        }
 
-        self.write_blob_stream_inner(&buf)
+        let mut spool = gix_tempfile::new(
+            self.objects.store_ref().path(),
+            gix_tempfile::ContainingDirectory::Exists,
+            gix_tempfile::AutoRemove::Tempfile,
+        )
+        .and_then(|tempfile| {
+            tempfile
+                .take()
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Interrupted, "tempfile was removed"))
+        })
+        .map_err(into_write_error)?;
+        spool.write_all(&buf).map_err(into_write_error)?;
+        drop(buf);
+        std::io::copy(&mut bytes, &mut spool).map_err(into_write_error)?;
+        let size = spool.stream_position().map_err(into_write_error)?;
+
+        spool.rewind().map_err(into_write_error)?;
+        let oid = gix_object::compute_stream_hash(
+            self.object_hash(),
+            gix_object::Kind::Blob,
+            &mut spool,
+            size,
+            &mut gix_features::progress::Discard,
+            &std::sync::atomic::AtomicBool::default(),
+        )
+        .map_err(into_write_error)?;
+        if self.objects.exists(&oid) {
+            return Ok(oid.attach(self));
+        }
+        spool.rewind().map_err(into_write_error)?;
+        self.objects
+            .write_stream(gix_object::Kind::Blob, size, &mut spool)
+            .map_err(Into::into)
+            .map(|oid| oid.attach(self))
     }
 
     fn write_blob_stream_inner(&self, buf: &[u8]) -> Result<Id<'_>, object::write::Error> {
@@ -352,3 +405,58 @@ impl crate::Repository {
         }
     }
 }
+
+/// Object memory
+impl crate::Repository {
+    /// Keep all objects that are written from now on in memory instead of writing them to the object database, while
+    /// still finding all objects on disk. This is useful to stage speculative changes, or to test logic hermetically.
+    ///
+    /// The objects in memory can be obtained with [`take_object_memory()`](gix_odb::memory::Proxy::take_object_memory())
+    /// on [`objects`](Self::objects), and they are discarded when converting this instance
+    /// [into a thread-safe repository](Self::into_sync()).
+    ///
+    /// ### Limitations
+    ///
+    /// * References are still written to disk, so they must not be made to point to objects that only exist in memory.
+    /// * Objects in memory can't be found by abbreviated ids, and aren't part of object iterators.
+    pub fn with_object_memory(mut self) -> Self {
+        self.objects.enable_object_memory();
+        self
+    }
+}
+
+/// Object replacements
+impl crate::Repository {
+    /// Return `true` if objects are substituted by their replacements when they are looked up, as defined by references
+    /// in `refs/replace/` or the namespace configured with `gitoxide.objects.replaceRefBase`.
+    ///
+    /// This is the case unless `core.useReplaceRefs` is `false` or the `GIT_NO_REPLACE_OBJECTS` environment variable is set,
+    /// or unless it was changed with [`set_object_replacements_enabled()`](Self::set_object_replacements_enabled()).
+    pub fn object_replacements_enabled(&self) -> bool {
+        !self.objects.ignore_replacements
+    }
+
+    /// Substitute objects by their replacements when they are looked up if `enabled` is `true`, or find the original objects
+    /// if it is `false`.
+    ///
+    /// The setting only affects this handle, and is reset to the configured value when the configuration is reloaded.
+    pub fn set_object_replacements_enabled(&mut self, enabled: bool) -> &mut Self {
+        if self.objects.ignore_replacements == enabled {
+            self.objects.ignore_replacements = !enabled;
+            self.objects.reset_object_cache();
+        }
+        self
+    }
+
+    /// Return all active object replacements as pairs of `(original, replacement)` object ids, sorted by the original id,
+    /// or nothing if [replacements are disabled](Self::object_replacements_enabled()).
+    ///
+    /// Replacements are loaded from references when they are needed for the first time, and are shared by all handles
+    /// of the repository.
+    pub fn object_replacements(&self) -> impl Iterator<Item = (ObjectId, ObjectId)> + '_ {
+        self.object_replacements_enabled()
+            .then(|| self.objects.store_ref().replacements())
+            .into_iter()
+            .flatten()
+    }
+}
@@ -0,0 +1,48 @@
+use gix_features::threading::{lock, Mutable, OwnShared};
+
+use crate::{repository::Pool, Repository, ThreadSafeRepository};
+
+impl Pool {
+    /// Return a new thread-local handle that is configured like the repository this pool was created from.
+    ///
+    /// This is cheap as all memory-mapped data is shared, but the caches of the new handle are empty.
+    pub fn handle(&self) -> Repository {
+        let repo = lock(&self.repo);
+        let mut handle = repo.clone();
+        // Cloning configures caches from configuration, but we want them to match the original.
+        handle.objects = repo.objects.clone();
+        handle
+    }
+}
+
+impl From<Repository> for Pool {
+    fn from(repo: Repository) -> Self {
+        Pool {
+            repo: OwnShared::new(Mutable::new(repo)),
+        }
+    }
+}
+
+impl From<ThreadSafeRepository> for Pool {
+    fn from(repo: ThreadSafeRepository) -> Self {
+        Repository::from(repo).into()
+    }
+}
+
+impl std::fmt::Debug for Pool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Pool").field("repo", &*lock(&self.repo)).finish()
+    }
+}
+
+/// Thread-pools
+impl Repository {
+    /// Convert this instance into a [`Pool`] to create handles for use in other threads, each of which is configured
+    /// like this instance.
+    ///
+    /// Use this instead of [`into_sync()`](Self::into_sync()) if the handles should inherit settings that are specific to this
+    /// instance, like the cache sizes.
+    pub fn into_pool(self) -> Pool {
+        self.into()
+    }
+}
@@ -0,0 +1,345 @@
+use gix_diff::patch::{Content, Hunk, Line, Operation};
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    format_patch, range_diff, Repository,
+};
+
+/// Range diff
+impl Repository {
+    /// Compare the non-merge commits of `old_range` with those of `new_range`, like `git range-diff old_range new_range`,
+    /// to see how a series of commits changed when it was rebased or amended.
+    ///
+    /// Both ranges are revision specifications like `main..topic`, interpreted like [`format_patch()`](Self::format_patch())
+    /// does. Commits with the same changes are paired up first, and all others are paired to minimize the size of the
+    /// differences between their patches, unless these are larger than allowed by the
+    /// [creation factor](range_diff::Options::creation_factor), in which case they are shown as removed and added instead.
+    ///
+    /// ### Deviation
+    ///
+    /// * The costs of pairing commits are similar to those of `git`, but not identical, which may pair commits differently
+    ///   if costs are close to the limit given by the creation factor.
+    pub fn range_diff(
+        &self,
+        old_range: impl AsRef<BStr>,
+        new_range: impl AsRef<BStr>,
+        options: range_diff::Options,
+    ) -> Result<range_diff::Outcome, range_diff::Error> {
+        let _span = gix_trace::coarse!("gix::range_diff()", options = ?options);
+        let old = self.range_diff_patches(old_range.as_ref())?;
+        let new = self.range_diff_patches(new_range.as_ref())?;
+
+        let mut old_match = vec![None::<usize>; old.len()];
+        let mut new_match = vec![None::<usize>; new.len()];
+        for (new_idx, new_patch) in new.iter().enumerate() {
+            if let Some(old_idx) =
+                (0..old.len()).find(|&old_idx| old_match[old_idx].is_none() && old[old_idx].diff() == new_patch.diff())
+            {
+                old_match[old_idx] = Some(new_idx);
+                new_match[new_idx] = Some(old_idx);
+            }
+        }
+
+        const IMPOSSIBLE: i64 = 1 << 40;
+        let creation_cost = |patch: &Patch, matched: bool| {
+            if matched {
+                IMPOSSIBLE
+            } else {
+                patch.diff_size as i64 * i64::from(options.creation_factor) / 100
+            }
+        };
+        let n = old.len() + new.len();
+        let mut cost = vec![0i64; n * n];
+        for (old_idx, old_patch) in old.iter().enumerate() {
+            for (new_idx, new_patch) in new.iter().enumerate() {
+                cost[old_idx * n + new_idx] = match (old_match[old_idx], new_match[new_idx]) {
+                    (Some(matched), _) if matched == new_idx => 0,
+                    (None, None) => difference(old_patch.diff(), new_patch.diff()),
+                    _ => IMPOSSIBLE,
+                };
+            }
+            let cost_of_removal = creation_cost(old_patch, old_match[old_idx].is_some());
+            for new_idx in new.len()..n {
+                cost[old_idx * n + new_idx] = cost_of_removal;
+            }
+        }
+        for (new_idx, new_patch) in new.iter().enumerate() {
+            let cost_of_addition = creation_cost(new_patch, new_match[new_idx].is_some());
+            for old_idx in old.len()..n {
+                cost[old_idx * n + new_idx] = cost_of_addition;
+            }
+        }
+        for (old_idx, new_idx) in assignment(n, |row, column| cost[row * n + column])
+            .into_iter()
+            .enumerate()
+            .take(old.len())
+        {
+            if new_idx < new.len() && old_match[old_idx].is_none() {
+                old_match[old_idx] = Some(new_idx);
+                new_match[new_idx] = Some(old_idx);
+            }
+        }
+
+        let diff_options = gix_diff::patch::create::Options {
+            context_lines: options.context_lines,
+            algorithm: gix_diff::blob::Algorithm::Myers,
+        };
+        let mut shown = vec![false; old.len()];
+        let mut pairs = Vec::new();
+        let (mut old_idx, mut new_idx) = (0, 0);
+        while old_idx < old.len() || new_idx < new.len() {
+            while old_idx < old.len() && shown[old_idx] {
+                old_idx += 1;
+            }
+            if old_idx < old.len() && old_match[old_idx].is_none() {
+                pairs.push(range_diff::Pair {
+                    old: Some(old[old_idx].commit.clone()),
+                    new: None,
+                    interdiff: Vec::new(),
+                });
+                old_idx += 1;
+                continue;
+            }
+            while new_idx < new.len() && new_match[new_idx].is_none() {
+                pairs.push(range_diff::Pair {
+                    old: None,
+                    new: Some(new[new_idx].commit.clone()),
+                    interdiff: Vec::new(),
+                });
+                new_idx += 1;
+            }
+            if new_idx < new.len() {
+                let matched = new_match[new_idx].expect("unmatched commits were shown");
+                let (old_commit, new_commit) = (&old[matched].commit, &new[new_idx].commit);
+                let mut interdiff = gix_diff::patch::create::hunks(&old_commit.patch, &new_commit.patch, diff_options);
+                for hunk in &mut interdiff {
+                    hunk.section = section(&old_commit.patch, hunk.old_start.saturating_sub(1) as usize);
+                }
+                pairs.push(range_diff::Pair {
+                    old: Some(old_commit.clone()),
+                    new: Some(new_commit.clone()),
+                    interdiff,
+                });
+                shown[matched] = true;
+                new_idx += 1;
+            }
+        }
+        Ok(range_diff::Outcome { pairs })
+    }
+}
+
+/// A commit along with the information needed to pair it.
+struct Patch {
+    commit: range_diff::Commit,
+    /// The position in the patch of the commit at which the changes of files start.
+    diff_offset: usize,
+    /// The amount of lines of the changes of files.
+    diff_size: usize,
+}
+
+impl Patch {
+    fn diff(&self) -> &[u8] {
+        &self.commit.patch[self.diff_offset..]
+    }
+}
+
+/// Utilities
+impl Repository {
+    fn range_diff_patches(&self, range: &BStr) -> Result<Vec<Patch>, format_patch::Error> {
+        self.commits_in_range(range)?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, id)| self.range_diff_patch(id, idx + 1))
+            .collect()
+    }
+
+    fn range_diff_patch(&self, id: ObjectId, position: usize) -> Result<Patch, format_patch::Error> {
+        let commit = self.find_object(id)?.into_commit();
+        let decoded = commit.decode()?;
+        let parent_tree = match decoded.parents().next() {
+            Some(parent) => self.find_object(parent)?.into_commit().tree()?,
+            None => self.empty_tree(),
+        };
+        let (files, _) = self.format_patch_diff(&parent_tree, &commit.tree()?, 3)?;
+
+        let mut patch = BString::from(" ## Metadata ##\n");
+        patch.push_str("Author: ");
+        patch.push_str(decoded.author.name);
+        patch.push_str(" <");
+        patch.push_str(decoded.author.email);
+        patch.push_str(">\n\n ## Commit message ##\n");
+        for line in decoded.message.trim_end().lines() {
+            if !line.trim().is_empty() {
+                patch.push_str("    ");
+                patch.push_str(line.trim_end());
+            }
+            patch.push(b'\n');
+        }
+        patch.push(b'\n');
+
+        let diff_offset = patch.len();
+        let mut diff_size = 0;
+        for file in &files {
+            patch.push_str(" ## ");
+            match (file.operation, &file.old_path) {
+                (Operation::Addition, _) => {
+                    patch.push_str(file.path());
+                    patch.push_str(" (new)");
+                }
+                (Operation::Deletion, _) => {
+                    patch.push_str(file.path());
+                    patch.push_str(" (deleted)");
+                }
+                (Operation::Rename | Operation::Copy, Some(old_path)) => {
+                    patch.push_str(old_path);
+                    patch.push_str(" => ");
+                    patch.push_str(file.path());
+                }
+                _ => patch.push_str(file.path()),
+            }
+            if let (Some(old_mode), Some(new_mode)) = (file.old_mode, file.new_mode) {
+                if old_mode != new_mode {
+                    patch.push_str(format!(" (mode change {:06o} => {:06o})", old_mode.0, new_mode.0));
+                }
+            }
+            patch.push_str(" ##\n");
+            match &file.content {
+                Content::Text(hunks) => {
+                    for hunk in hunks {
+                        patch.push_str("@@");
+                        if !hunk.section.is_empty() {
+                            patch.push(b' ');
+                            patch.push_str(file.path());
+                            patch.push_str(": ");
+                            patch.push_str(&hunk.section);
+                        }
+                        patch.push(b'\n');
+                        diff_size += 1 + hunk.lines.len();
+                        for line in &hunk.lines {
+                            let (prefix, content) = match line {
+                                Line::Context(line) => (b' ', line),
+                                Line::Removal(line) => (b'-', line),
+                                Line::Addition(line) => (b'+', line),
+                            };
+                            patch.push(prefix);
+                            patch.push_str(content);
+                            if !content.ends_with(b"\n") {
+                                patch.push_str("\n \\ No newline at end of file\n");
+                            }
+                        }
+                    }
+                }
+                Content::Binary { .. } | Content::BinaryWithoutData => {
+                    patch.push_str(" Binary files differ\n");
+                    diff_size += 1;
+                }
+            }
+        }
+
+        let message = decoded.message();
+        Ok(Patch {
+            commit: range_diff::Commit {
+                position,
+                id,
+                short_id: commit.id().shorten_or_id().to_string(),
+                subject: message.summary().into_owned(),
+                patch,
+            },
+            diff_offset,
+            diff_size,
+        })
+    }
+}
+
+/// Return the amount of changed lines and hunks between `old` and `new`.
+fn difference(old: &[u8], new: &[u8]) -> i64 {
+    let options = gix_diff::patch::create::Options {
+        context_lines: 0,
+        algorithm: gix_diff::blob::Algorithm::Myers,
+    };
+    gix_diff::patch::create::hunks(old, new, options)
+        .iter()
+        .map(|hunk: &Hunk| 1 + hunk.lines.len() as i64)
+        .sum()
+}
+
+/// Return the name of the part of `patch` before line `line_idx`, like `Commit message` or a path, or the path and section
+/// of the closest hunk of a file if it has one.
+fn section(patch: &[u8], line_idx: usize) -> BString {
+    patch
+        .lines()
+        .take(line_idx)
+        .filter_map(|line| {
+            line.strip_prefix(b" ## ")
+                .and_then(|name| name.strip_suffix(b" ##"))
+                .or_else(|| line.strip_prefix(b"@@ "))
+        })
+        .last()
+        .unwrap_or_default()
+        .into()
+}
+
+/// Solve the assignment problem for the square matrix of size `n` whose costs are returned by `cost(row, column)`,
+/// and return the column assigned to each row so that the sum of their costs is minimal.
+///
+/// This is the Hungarian algorithm, which runs in `O(n³)`.
+fn assignment(n: usize, cost: impl Fn(usize, usize) -> i64) -> Vec<usize> {
+    let infinity = i64::MAX / 4;
+    let (mut row_potential, mut column_potential) = (vec![0i64; n + 1], vec![0i64; n + 1]);
+    let mut row_of_column = vec![0usize; n + 1];
+    let mut way = vec![0usize; n + 1];
+    for row in 1..=n {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut min_slack = vec![infinity; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current_row = row_of_column[column];
+            let (mut delta, mut next_column) = (infinity, 0);
+            for candidate in 1..=n {
+                if used[candidate] {
+                    continue;
+                }
+                let slack =
+                    cost(current_row - 1, candidate - 1) - row_potential[current_row] - column_potential[candidate];
+                if slack < min_slack[candidate] {
+                    min_slack[candidate] = slack;
+                    way[candidate] = column;
+                }
+                if min_slack[candidate] < delta {
+                    delta = min_slack[candidate];
+                    next_column = candidate;
+                }
+            }
+            for candidate in 0..=n {
+                if used[candidate] {
+                    row_potential[row_of_column[candidate]] += delta;
+                    column_potential[candidate] -= delta;
+                } else {
+                    min_slack[candidate] -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+        loop {
+            let previous = way[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+            if column == 0 {
+                break;
+            }
+        }
+    }
+    let mut column_of_row = vec![0; n];
+    for column in 1..=n {
+        if row_of_column[column] != 0 {
+            column_of_row[row_of_column[column] - 1] = column - 1;
+        }
+    }
+    column_of_row
+}
@@ -0,0 +1,422 @@
+use std::path::PathBuf;
+
+use gix_hash::ObjectId;
+use gix_rebase::{EmptyCommits, Instruction, State};
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use super::cherry_pick::Operation;
+use crate::{
+    bstr::{BStr, ByteSlice},
+    cherry_pick, merge, rebase,
+    rebase::Stop,
+    Repository,
+};
+
+/// Rebase
+impl Repository {
+    /// Replay all commits that are reachable from `HEAD` but not from the commit that `upstream` resolves to on top of it,
+    /// or on top of [`options.onto`](rebase::Options::onto), similar to `git rebase [--onto <newbase>] <upstream>`.
+    ///
+    /// The commits are picked one by one, oldest first, using the same machinery as [`cherry_pick()`](Self::cherry_pick()),
+    /// while commits whose parent already is `HEAD` are fast-forwarded to. Merge commits are skipped.
+    /// Once all commits are replayed, the branch that was checked out is updated and `HEAD` is attached to it again.
+    ///
+    /// The progress is kept in `.git/rebase-merge` in the same format as `git` uses, so `git rebase --continue` or
+    /// `git rebase --abort` can take over if the rebase [stops](rebase::Outcome::Stopped), for instance due to conflicts.
+    /// Alternatively, use [`rebase_continue()`](Self::rebase_continue()) or [`rebase_abort()`](Self::rebase_abort()).
+    ///
+    /// The previous value of `HEAD` is recorded in `ORIG_HEAD`.
+    ///
+    /// ### Deviation
+    ///
+    /// * Commits are not compared by patch-id to skip those that are already present upstream, they are skipped only
+    ///   if they turn out to be empty and [`options.empty`](rebase::Options::empty) says so.
+    /// * The progress is recorded as if it was an interactive rebase, as it's the most flexible representation.
+    /// * No reflog entry is written for `HEAD` when it is attached to the rebased branch again, as the commit doesn't change.
+    pub fn rebase<'a>(
+        &self,
+        upstream: impl Into<&'a BStr>,
+        options: rebase::Options,
+    ) -> Result<rebase::Outcome, rebase::Error> {
+        let _span = gix_trace::coarse!("gix::rebase()", options = ?options);
+        let upstream = upstream.into();
+        if self.work_dir().is_none() {
+            return Err(rebase::Error::BareRepository {
+                git_dir: self.git_dir().to_owned(),
+            });
+        }
+        if let Some(state) = self.state() {
+            return Err(rebase::Error::InProgress { state });
+        }
+        let head_name = self.head_name()?;
+        let head_id = self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(rebase::Error::UnbornHead)?
+            .detach();
+        let resolve = |spec: &BStr| -> Result<ObjectId, rebase::Error> {
+            Ok(self
+                .rev_parse_single(spec)?
+                .object()?
+                .peel_to_kind(gix_object::Kind::Commit)?
+                .id)
+        };
+        let upstream_id = resolve(upstream)?;
+        let onto = match &options.onto {
+            Some(onto) => resolve(onto.as_bstr())?,
+            None => upstream_id,
+        };
+        let todo = self.rebase_todo(head_id, upstream_id)?;
+
+        let head_tree = self.commit_tree(head_id)?;
+        let autostash = if options.autostash {
+            let stash = self.stash_create(Some("autostash"))?.map(crate::Id::detach);
+            if stash.is_some() {
+                let (mut index, _files_removed, _checkout) = self.checkout_tree(Some(head_tree), head_tree, true)?;
+                index.write(Default::default())?;
+            }
+            stash
+        } else {
+            if !self.index_matches_tree(&*self.index_or_empty()?, head_tree)? || self.is_dirty()? {
+                return Err(rebase::Error::LocalChanges);
+            }
+            None
+        };
+
+        let start_message = format!(
+            "rebase (start): checkout {}",
+            options.onto.as_ref().map_or(upstream, |onto| onto.as_bstr())
+        );
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: start_message.clone().into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(head_id),
+            },
+            name: "ORIG_HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        let state = State {
+            head_name: head_name.map(|name| name.as_bstr().to_owned()),
+            onto,
+            orig_head: head_id,
+            todo,
+            done: Vec::new(),
+            stopped_at: None,
+            autostash,
+            empty: options.empty,
+        };
+        self.write_rebase_state(&state)?;
+
+        let (mut index, _files_removed, _checkout) =
+            self.checkout_tree(Some(head_tree), self.commit_tree(onto)?, false)?;
+        index.write(Default::default())?;
+        self.set_head_after_checkout(None, onto, start_message.into())?;
+        self.rebase_run(state)
+    }
+
+    /// Continue the rebase in progress after it [stopped](rebase::Outcome::Stopped), similar to `git rebase --continue`.
+    ///
+    /// If it stopped at a commit to pick, the changes in the index are committed first with the original message and author,
+    /// unless they are empty and [empty commits](rebase::Options::empty) are not kept.
+    /// Changes in the worktree that are not added to the index are ignored.
+    pub fn rebase_continue(&self) -> Result<rebase::Outcome, rebase::Error> {
+        let _span = gix_trace::coarse!("gix::rebase_continue()");
+        let mut state = self.rebase_state()?.ok_or(rebase::Error::NotInProgress)?;
+        if let Some(stopped_at) = state.stopped_at.take() {
+            let index = self.index_or_empty()?;
+            if index
+                .entries()
+                .iter()
+                .any(|e| e.stage() != gix_index::entry::Stage::Unconflicted)
+            {
+                return Err(rebase::Error::Unmerged);
+            }
+            let head_id = self
+                .head()?
+                .try_into_peeled_id()?
+                .ok_or(rebase::Error::UnbornHead)?
+                .detach();
+            let tree = self.write_tree_from_index(&index)?.detach();
+            if tree != self.commit_tree(head_id)? || state.empty == EmptyCommits::Keep {
+                let original: gix_object::Commit = self
+                    .find_object(stopped_at)?
+                    .peel_to_kind(gix_object::Kind::Commit)?
+                    .into_commit()
+                    .decode()?
+                    .into();
+                let dir = self.rebase_state_dir();
+                let message = std::fs::read(dir.join("message")).map_or(original.message, Into::into);
+                let author = match std::fs::read(dir.join("author-script")) {
+                    Ok(script) => gix_rebase::author_script::decode(&script)?,
+                    Err(_) => original.author,
+                };
+                let committer = self.committer().ok_or(rebase::Error::CommitterMissing)??;
+                let commit = gix_object::Commit {
+                    tree,
+                    parents: [head_id].into(),
+                    author,
+                    committer: committer.into(),
+                    encoding: original.encoding,
+                    message,
+                    extra_headers: Default::default(),
+                };
+                let commit_id = self.write_object(&commit)?.detach();
+                self.set_head_after_checkout(
+                    None,
+                    commit_id,
+                    crate::reference::log::message("rebase (continue)", commit.message.as_ref(), 1),
+                )?;
+            }
+            self.remove_rebase_stop_files()?;
+        }
+        self.rebase_run(state)
+    }
+
+    /// Abort the rebase in progress and restore the index, the worktree and `HEAD` to their state before the rebase started,
+    /// similar to `git rebase --abort`.
+    ///
+    /// Return the conflicts that occurred when reapplying the autostash, if there was one.
+    pub fn rebase_abort(&self) -> Result<Vec<merge::Conflict>, rebase::Error> {
+        let _span = gix_trace::coarse!("gix::rebase_abort()");
+        let state = self.rebase_state()?.ok_or(rebase::Error::NotInProgress)?;
+        let current_tree = match self.head()?.try_into_peeled_id()? {
+            Some(id) => Some(self.commit_tree(id.detach())?),
+            None => None,
+        };
+        let (mut index, _files_removed, _checkout) =
+            self.checkout_tree(current_tree, self.commit_tree(state.orig_head)?, true)?;
+        index.write(Default::default())?;
+        let branch = state.head_name.clone().map(FullName::try_from).transpose()?;
+        let message = format!(
+            "rebase (abort): returning to {}",
+            state
+                .head_name
+                .clone()
+                .unwrap_or_else(|| state.orig_head.to_string().into())
+        );
+        self.set_head_after_checkout(branch, state.orig_head, message.into())?;
+        self.remove_rebase_state()?;
+        self.remove_merge_state().map_err(|err| rebase::Error::WriteState {
+            path: self.path().to_owned(),
+            source: err,
+        })?;
+        self.apply_autostash(state.autostash)
+    }
+
+    /// Return the state of the rebase in progress, or `None` if there is none.
+    ///
+    /// Note that only rebases that keep their state in `.git/rebase-merge` are detected, which is the default for `git`.
+    pub fn rebase_state(&self) -> Result<Option<State>, gix_rebase::state::read::Error> {
+        let dir = self.rebase_state_dir();
+        if !dir.is_dir() {
+            return Ok(None);
+        }
+        State::read(&dir).map(Some)
+    }
+}
+
+/// Utilities
+impl Repository {
+    fn rebase_state_dir(&self) -> PathBuf {
+        self.path().join("rebase-merge")
+    }
+
+    /// Return the instructions to pick all non-merge commits reachable from `head_id` but not from `upstream_id`, oldest first.
+    fn rebase_todo(&self, head_id: ObjectId, upstream_id: ObjectId) -> Result<Vec<Instruction>, rebase::Error> {
+        let mut upstream = gix_hashtable::HashSet::default();
+        for info in self.rev_walk([upstream_id]).all()? {
+            upstream.insert(info?.id);
+        }
+        let mut todo = Vec::new();
+        for info in self.rev_walk([head_id]).selected(move |id| !upstream.contains(id))? {
+            let info = info?;
+            if info.parent_ids.len() > 1 {
+                continue;
+            }
+            let summary = info.object()?.decode()?.message_summary().into_owned();
+            todo.push(Instruction::Pick {
+                commit: info.id,
+                summary,
+            });
+        }
+        todo.reverse();
+        Ok(todo)
+    }
+
+    /// Perform all remaining instructions in `state` and finish the rebase, or stop if an instruction requires it.
+    fn rebase_run(&self, mut state: State) -> Result<rebase::Outcome, rebase::Error> {
+        while !state.todo.is_empty() {
+            let instruction = state.todo.remove(0);
+            state.done.push(instruction.clone());
+            self.write_rebase_state(&state)?;
+            let stop = match &instruction {
+                Instruction::Pick { commit, .. } => self.rebase_pick(&mut state, *commit)?,
+                Instruction::Drop { .. } => None,
+                Instruction::Exec { command } => {
+                    let mut cmd: std::process::Command =
+                        gix_command::prepare(gix_path::from_bstr(command.as_bstr()).into_owned())
+                            .with_shell()
+                            .into();
+                    let status = cmd
+                        .current_dir(self.work_dir().expect("non-bare"))
+                        .status()
+                        .map_err(|err| rebase::Error::Exec {
+                            command: command.clone(),
+                            source: err,
+                        })?;
+                    (!status.success()).then_some(Stop::ExecFailed { status })
+                }
+                Instruction::Break => Some(Stop::Break),
+            };
+            if let Some(reason) = stop {
+                self.write_rebase_state(&state)?;
+                return Ok(rebase::Outcome::Stopped { instruction, reason });
+            }
+        }
+        self.rebase_finish(state)
+    }
+
+    /// Pick `commit` onto `HEAD` and return the reason to stop, if any.
+    fn rebase_pick(&self, state: &mut State, commit: ObjectId) -> Result<Option<Stop>, rebase::Error> {
+        let head_id = self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(rebase::Error::UnbornHead)?
+            .detach();
+        let to_pick: gix_object::Commit = self
+            .find_object(commit)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit()
+            .decode()?
+            .into();
+        if to_pick.parents.as_slice() == [head_id] {
+            let (mut index, _files_removed, _checkout) =
+                self.checkout_tree(Some(self.commit_tree(head_id)?), to_pick.tree, false)?;
+            index.write(Default::default())?;
+            self.set_head_after_checkout(
+                None,
+                commit,
+                crate::reference::log::message("rebase (pick)", to_pick.message.as_ref(), 1),
+            )?;
+            return Ok(None);
+        }
+
+        let options = cherry_pick::Options {
+            allow_empty: state.empty == EmptyCommits::Keep,
+            ..Default::default()
+        };
+        let reason = match self.apply_commit(commit, options, Operation::RebasePick) {
+            Ok(outcome) if outcome.conflicts.is_empty() => return Ok(None),
+            Ok(outcome) => Stop::Conflicts(outcome.conflicts),
+            Err(cherry_pick::Error::Empty { .. }) if state.empty == EmptyCommits::Drop => return Ok(None),
+            Err(cherry_pick::Error::Empty { .. }) => Stop::Empty,
+            Err(err) => return Err(err.into()),
+        };
+
+        state.stopped_at = Some(commit);
+        let dir = self.rebase_state_dir();
+        let write = |path: PathBuf, content: &[u8]| {
+            std::fs::write(&path, content).map_err(|err| rebase::Error::WriteState { path, source: err })
+        };
+        write(dir.join("message"), &to_pick.message)?;
+        write(
+            dir.join("author-script"),
+            &gix_rebase::author_script::encode(to_pick.author.to_ref()),
+        )?;
+        write(self.path().join("REBASE_HEAD"), format!("{commit}\n").as_bytes())?;
+        Ok(Some(reason))
+    }
+
+    /// Update the rebased branch, attach `HEAD` to it, clean up and reapply the autostash.
+    fn rebase_finish(&self, state: State) -> Result<rebase::Outcome, rebase::Error> {
+        let head_id = self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(rebase::Error::UnbornHead)?
+            .detach();
+        if let Some(head_name) = &state.head_name {
+            let branch = FullName::try_from(head_name.as_bstr())?;
+            self.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: format!("rebase (finish): {head_name} onto {}", state.onto).into(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(head_id),
+                },
+                name: branch.clone(),
+                deref: false,
+            })?;
+            self.set_head_after_checkout(
+                Some(branch),
+                head_id,
+                format!("rebase (finish): returning to {head_name}").into(),
+            )?;
+        }
+        self.remove_rebase_state()?;
+        let autostash_conflicts = self.apply_autostash(state.autostash)?;
+        Ok(rebase::Outcome::Finished {
+            head_id,
+            autostash_conflicts,
+        })
+    }
+
+    /// Apply `autostash` if set, and store it in `refs/stash` if that didn't go cleanly so it won't be lost.
+    fn apply_autostash(&self, autostash: Option<ObjectId>) -> Result<Vec<merge::Conflict>, rebase::Error> {
+        let Some(autostash) = autostash else {
+            return Ok(Vec::new());
+        };
+        match self.apply_stash(autostash) {
+            Ok(conflicts) => {
+                if !conflicts.is_empty() {
+                    self.store_stash(autostash, "autostash")?;
+                }
+                Ok(conflicts)
+            }
+            Err(err) => {
+                self.store_stash(autostash, "autostash")?;
+                Err(err.into())
+            }
+        }
+    }
+
+    fn write_rebase_state(&self, state: &State) -> Result<(), rebase::Error> {
+        let dir = self.rebase_state_dir();
+        state
+            .write(&dir)
+            .map_err(|err| rebase::Error::WriteState { path: dir, source: err })
+    }
+
+    fn remove_rebase_stop_files(&self) -> Result<(), rebase::Error> {
+        let dir = self.rebase_state_dir();
+        for path in [
+            dir.join("message"),
+            dir.join("author-script"),
+            self.path().join("REBASE_HEAD"),
+            self.path().join("MERGE_MSG"),
+        ] {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(rebase::Error::WriteState { path, source: err }),
+            }
+        }
+        Ok(())
+    }
+
+    fn remove_rebase_state(&self) -> Result<(), rebase::Error> {
+        self.remove_rebase_stop_files()?;
+        let dir = self.rebase_state_dir();
+        std::fs::remove_dir_all(&dir).map_err(|err| rebase::Error::WriteState { path: dir, source: err })
+    }
+}
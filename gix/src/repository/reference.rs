@@ -1,7 +1,9 @@
+use std::{cell::RefCell, collections::BTreeMap};
+
 use gix_hash::ObjectId;
 use gix_macros::momo;
 use gix_ref::{
-    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefEditsExt, RefLog},
     FullName, PartialNameRef, Target,
 };
 
@@ -141,18 +143,32 @@ impl crate::Repository {
     ///
     /// Returns all reference edits, which might be more than where provided due the splitting of symbolic references, and
     /// whose previous (_old_) values are the ones seen on in storage after the reference was locked.
+    ///
+    /// With [reference memory](Self::with_reference_memory()), the edits are applied in memory only.
     pub fn edit_references(
         &self,
         edits: impl IntoIterator<Item = RefEdit>,
     ) -> Result<Vec<RefEdit>, reference::edit::Error> {
+        if let Some(memory) = &self.refs_memory {
+            return self.edit_references_in_memory(memory, &mut edits.into_iter());
+        }
         let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
-        self.refs
-            .transaction()
+        self.reference_transaction()
             .prepare(edits, file_lock_fail, packed_refs_lock_fail)?
             .commit(self.committer().transpose()?)
             .map_err(Into::into)
     }
 
+    /// Return a new reference transaction that runs the `reference-transaction` hook in each of its states if it exists.
+    pub(crate) fn reference_transaction(&self) -> gix_ref::file::Transaction<'_, '_> {
+        let transaction = self.refs.transaction();
+        #[cfg(feature = "command")]
+        if let Some(hook) = self.reference_transaction_hook() {
+            return transaction.hook(hook);
+        }
+        transaction
+    }
+
     /// Return the repository head, an abstraction to help dealing with the `HEAD` reference.
     ///
     /// The `HEAD` reference can be in various states, for more information, the documentation of [`Head`][crate::Head].
@@ -251,13 +267,218 @@ impl crate::Repository {
         Name: TryInto<&'a PartialNameRef, Error = E>,
         gix_ref::file::find::Error: From<E>,
     {
-        let state = self;
-        match state.refs.try_find(name) {
-            Ok(r) => match r {
-                Some(r) => Ok(Some(Reference::from_ref(r, self))),
-                None => Ok(None),
-            },
-            Err(err) => Err(err.into()),
+        let name = name.try_into().map_err(gix_ref::file::find::Error::from)?;
+        Ok(self
+            .try_find_reference_inner(name)?
+            .map(|r| Reference::from_ref(r, self)))
+    }
+
+    /// Like [`find_reference()`](Self::find_reference()), but returns the reference without attaching it, along with the
+    /// error of the reference store.
+    #[cfg(feature = "revision")]
+    pub(crate) fn find_reference_inner(
+        &self,
+        name: &crate::bstr::BStr,
+    ) -> Result<gix_ref::Reference, gix_ref::file::find::existing::Error> {
+        use gix_ref::file::find::{existing::Error, Error as FindError};
+        let name: &PartialNameRef = name
+            .try_into()
+            .map_err(|err| Error::Find(FindError::RefnameValidation(err)))?;
+        self.try_find_reference_inner(name)?.ok_or_else(|| Error::NotFound {
+            name: name.to_partial_path().to_owned(),
+        })
+    }
+
+    /// Like [`try_find_reference()`](Self::try_find_reference()), but returns the reference without attaching it.
+    pub(crate) fn try_find_reference_inner(
+        &self,
+        name: &PartialNameRef,
+    ) -> Result<Option<gix_ref::Reference>, gix_ref::file::find::Error> {
+        let Some(memory) = &self.refs_memory else {
+            return self.refs.try_find(name);
+        };
+        let memory = memory.borrow();
+        let candidates = full_name_candidates(name);
+        if !candidates.iter().any(|name| memory.contains_key(name)) {
+            return self.refs.try_find(name);
+        }
+        for name in candidates {
+            match memory.get(&name) {
+                Some(Some(target)) => {
+                    return Ok(Some(gix_ref::Reference {
+                        target: target.clone(),
+                        name,
+                        peeled: None,
+                    }))
+                }
+                Some(None) => {}
+                None => {
+                    if let Some(r) = self.refs.try_find(name.as_ref().as_partial_name())? {
+                        return Ok(Some(r));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Reference memory
+impl crate::Repository {
+    /// Keep all reference edits made from now on in memory instead of writing them to disk, while still finding all
+    /// references on disk unless they were changed or deleted in memory.
+    /// Together with [object memory](Self::with_object_memory()) and [configuration overrides](Self::with_config_overrides()),
+    /// this allows to stage speculative changes, or to test logic hermetically.
+    ///
+    /// Edits in memory are validated like edits on disk, but don't write reflogs and don't run the `reference-transaction` hook.
+    /// The references in memory are copied into clones of this instance, and are discarded when converting it
+    /// [into a thread-safe repository](Self::into_sync()).
+    ///
+    /// ### Limitations
+    ///
+    /// * Fetching and cloning, as well as [packing references](Self::maintenance()), still write references to disk.
+    /// * Reference namespaces are ignored for references in memory.
+    pub fn with_reference_memory(mut self) -> Self {
+        if self.refs_memory.is_none() {
+            self.refs_memory = Some(Default::default());
+        }
+        self
+    }
+
+    /// Return `true` if reference edits are [kept in memory](Self::with_reference_memory()).
+    pub fn has_reference_memory(&self) -> bool {
+        self.refs_memory.is_some()
+    }
+
+    /// Stop keeping reference edits in memory and return the references that were changed, with `None` marking deleted ones,
+    /// or `None` if they weren't kept in memory.
+    pub fn take_reference_memory(&mut self) -> Option<BTreeMap<FullName, Option<Target>>> {
+        self.refs_memory.take().map(RefCell::into_inner)
+    }
+
+    fn edit_references_in_memory(
+        &self,
+        memory: &RefCell<BTreeMap<FullName, Option<Target>>>,
+        edits: &mut dyn Iterator<Item = RefEdit>,
+    ) -> Result<Vec<RefEdit>, reference::edit::Error> {
+        use gix_ref::file::transaction::prepare::Error;
+        let mut edits: Vec<RefEdit> = edits.collect();
+        edits
+            .pre_process(
+                &mut |name| self.try_find_reference_inner(name).ok().flatten().map(|r| r.target),
+                &mut |_, edit| edit,
+            )
+            .map_err(Error::PreprocessingFailed)?;
+
+        let mut refs = memory.borrow().clone();
+        for edit in &mut edits {
+            let existing = match refs.get(&edit.name) {
+                Some(target) => target.clone(),
+                None => self
+                    .refs
+                    .try_find(edit.name.as_ref().as_partial_name())?
+                    .map(|r| r.target),
+            };
+            let full_name = || edit.name.as_bstr().to_owned();
+            match &mut edit.change {
+                Change::Delete { expected, log } => {
+                    match (&expected, &existing) {
+                        (PreviousValue::MustNotExist, _) => {
+                            panic!("BUG: MustNotExist constraint makes no sense if references are to be deleted")
+                        }
+                        (PreviousValue::ExistingMustMatch(_) | PreviousValue::Any, None)
+                        | (PreviousValue::MustExist | PreviousValue::Any, Some(_)) => {}
+                        (PreviousValue::MustExist | PreviousValue::MustExistAndMatch(_), None) => {
+                            return Err(Error::DeleteReferenceMustExist { full_name: full_name() }.into())
+                        }
+                        (
+                            PreviousValue::MustExistAndMatch(previous) | PreviousValue::ExistingMustMatch(previous),
+                            Some(actual),
+                        ) => {
+                            if previous != actual {
+                                return Err(Error::ReferenceOutOfDate {
+                                    full_name: full_name(),
+                                    expected: previous.clone(),
+                                    actual: actual.clone(),
+                                }
+                                .into());
+                            }
+                        }
+                    }
+                    if *log != RefLog::Only {
+                        refs.insert(edit.name.clone(), None);
+                    }
+                    if let Some(existing) = existing {
+                        *expected = PreviousValue::MustExistAndMatch(existing);
+                    }
+                }
+                Change::Update { expected, new, log } => {
+                    match (&expected, &existing) {
+                        (PreviousValue::Any, _)
+                        | (PreviousValue::MustExist, Some(_))
+                        | (PreviousValue::MustNotExist | PreviousValue::ExistingMustMatch(_), None) => {}
+                        (PreviousValue::MustExist, None) => {
+                            return Err(Error::MustExist {
+                                full_name: full_name(),
+                                expected: Target::Peeled(self.object_hash().null()),
+                            }
+                            .into())
+                        }
+                        (PreviousValue::MustNotExist, Some(actual)) => {
+                            if actual != new {
+                                return Err(Error::MustNotExist {
+                                    full_name: full_name(),
+                                    actual: actual.clone(),
+                                    new: new.clone(),
+                                }
+                                .into());
+                            }
+                        }
+                        (
+                            PreviousValue::MustExistAndMatch(previous) | PreviousValue::ExistingMustMatch(previous),
+                            Some(actual),
+                        ) => {
+                            if previous != actual {
+                                return Err(Error::ReferenceOutOfDate {
+                                    full_name: full_name(),
+                                    expected: previous.clone(),
+                                    actual: actual.clone(),
+                                }
+                                .into());
+                            }
+                        }
+                        (PreviousValue::MustExistAndMatch(previous), None) => {
+                            return Err(Error::MustExist {
+                                full_name: full_name(),
+                                expected: previous.clone(),
+                            }
+                            .into())
+                        }
+                    }
+                    if log.mode != RefLog::Only {
+                        refs.insert(edit.name.clone(), Some(new.clone()));
+                    }
+                    if let Some(existing) = existing {
+                        *expected = PreviousValue::MustExistAndMatch(existing);
+                    }
+                }
+            }
         }
+        *memory.borrow_mut() = refs;
+        Ok(edits)
+    }
+}
+
+/// Return the full names `name` may refer to, in the order in which they are tried when looking it up.
+fn full_name_candidates(name: &PartialNameRef) -> Vec<FullName> {
+    if name.looks_like_full_name() {
+        return FullName::try_from(name.as_bstr()).into_iter().collect();
     }
+    let name = name.as_bstr();
+    ["", "tags/", "heads/", "remotes/"]
+        .iter()
+        .map(|inbetween| format!("refs/{inbetween}{name}"))
+        .chain(Some(format!("refs/remotes/{name}/HEAD")))
+        .filter_map(|name| FullName::try_from(name).ok())
+        .collect()
 }
@@ -0,0 +1,115 @@
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{bstr::BStr, reset, Repository};
+
+/// Reset
+impl Repository {
+    /// Move `HEAD`, or the branch it points to, to the commit that `target` resolves to, and depending on
+    /// [`options.mode`](reset::Options::mode) also make the index and the worktree match its tree, similar to `git reset`.
+    ///
+    /// `options` can be a [`reset::Mode`] for convenience, like `repo.reset("HEAD~1", reset::Mode::Hard)`.
+    ///
+    /// The previous value of `HEAD` is recorded in `ORIG_HEAD`, and a reflog entry like `reset: moving to <target>` is written.
+    /// If a merge is in progress or the index contains unresolved conflicts, the operation is refused unless
+    /// [`options.allow_unfinished_merge`](reset::Options::allow_unfinished_merge) is set.
+    ///
+    /// A [mixed](reset::Mode::Mixed) reset retains the stat information of all index entries that don't change, so unchanged
+    /// files won't appear modified. A [hard](reset::Mode::Hard) reset only writes files to the worktree which actually differ
+    /// from the target tree.
+    pub fn reset<'a>(
+        &self,
+        target: impl Into<&'a BStr>,
+        options: impl Into<reset::Options>,
+    ) -> Result<reset::Outcome, reset::Error> {
+        let options = options.into();
+        let _span = gix_trace::coarse!("gix::reset()", options = ?options);
+        let target = target.into();
+        let new_commit = self
+            .rev_parse_single(target)?
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let new_commit_id = new_commit.id;
+        let new_tree = new_commit.tree_id()?.detach();
+
+        let previous_head_id = self.head()?.try_into_peeled_id()?.map(crate::Id::detach);
+        if !options.allow_unfinished_merge
+            && (self.state() == Some(crate::state::InProgress::Merge)
+                || self
+                    .index_or_empty()?
+                    .entries()
+                    .iter()
+                    .any(|e| e.stage() != gix_index::entry::Stage::Unconflicted))
+        {
+            return Err(reset::Error::UnfinishedMerge);
+        }
+
+        let mut files_removed = 0;
+        let mut checkout = None;
+        match options.mode {
+            reset::Mode::Soft => {}
+            reset::Mode::Mixed => {
+                let mut index = self.index_from_tree(&new_tree)?;
+                let current = self.index_or_empty()?;
+                for (entry, path) in index.entries_mut_with_paths() {
+                    if let Some(current) = current
+                        .entry_by_path(path)
+                        .filter(|current| current.id == entry.id && current.mode == entry.mode)
+                    {
+                        entry.stat = current.stat;
+                        entry.flags = current.flags;
+                    }
+                }
+                index.write(Default::default())?;
+            }
+            reset::Mode::Hard => {
+                let previous_tree = match previous_head_id {
+                    Some(id) => Some(
+                        self.find_object(id)?
+                            .peel_to_kind(gix_object::Kind::Commit)?
+                            .into_commit()
+                            .tree_id()?
+                            .detach(),
+                    ),
+                    None => None,
+                };
+                let (mut index, removed, outcome) = self.checkout_tree(previous_tree, new_tree, true)?;
+                index.write(Default::default())?;
+                files_removed = removed;
+                checkout = Some(outcome);
+            }
+        }
+        let message = format!("reset: moving to {target}");
+        let mut changes = crate::state::Changes::default();
+        if options.allow_unfinished_merge && options.mode != reset::Mode::Soft {
+            changes = changes.remove_merge_state();
+        }
+        if let Some(previous_head_id) = previous_head_id {
+            changes = changes.set_orig_head(previous_head_id, message.clone());
+        }
+        changes = changes.edit(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(new_commit_id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: true,
+        });
+        self.apply_state_changes(changes)?;
+
+        Ok(reset::Outcome {
+            previous_head_id,
+            head_id: new_commit_id,
+            files_removed,
+            checkout,
+        })
+    }
+}
@@ -0,0 +1,188 @@
+use gix_hash::ObjectId;
+use gix_hashtable::HashSet;
+
+use crate::{
+    bstr::{BString, ByteVec},
+    rev_list, Repository,
+};
+
+/// Listing objects
+impl Repository {
+    /// List all commits reachable from `tips` but not from `hidden`, newest first, like `git rev-list <tips> --not <hidden>`.
+    ///
+    /// With [`options.objects`](rev_list::Options::objects), all trees and blobs reachable from the listed commits are listed
+    /// as well, along with the path at which they were first discovered, unless they are reachable from the trees of hidden tips
+    /// or boundary commits. This is what's needed to determine which objects to send in a pack, or to check that all objects
+    /// reachable from new commits are present.
+    ///
+    /// Tips and hidden tips may be tags, which are peeled, as well as trees or blobs, which are listed or hidden respectively.
+    /// Submodule commits in trees are never listed.
+    ///
+    /// ### Deviation
+    ///
+    /// * Tags that are tips are listed with an empty path instead of the name they were passed as.
+    pub fn rev_list(
+        &self,
+        tips: impl IntoIterator<Item = impl Into<ObjectId>>,
+        hidden: impl IntoIterator<Item = impl Into<ObjectId>>,
+        options: rev_list::Options,
+    ) -> Result<rev_list::Outcome, rev_list::Error> {
+        let _span = gix_trace::coarse!("gix::rev_list()", options = ?options);
+        let mut tip_commits = Vec::new();
+        let mut tip_objects = Vec::new();
+        for tip in tips {
+            self.rev_list_peel(tip.into(), &mut tip_commits, &mut tip_objects)?;
+        }
+        let mut hidden_commits = Vec::new();
+        let mut hidden_objects = Vec::new();
+        for tip in hidden {
+            self.rev_list_peel(tip.into(), &mut hidden_commits, &mut hidden_objects)?;
+        }
+
+        let mut hidden = HashSet::default();
+        if !hidden_commits.is_empty() {
+            for info in self.rev_walk(hidden_commits.iter().copied()).all()? {
+                hidden.insert(info?.id);
+            }
+        }
+        let mut commits = Vec::new();
+        let mut boundary = Vec::new();
+        if !tip_commits.is_empty() {
+            let mut seen_boundary = HashSet::default();
+            let walk = self
+                .rev_walk(tip_commits)
+                .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+                .selected({
+                    let hidden = hidden.clone();
+                    move |id| !hidden.contains(id)
+                })?;
+            for info in walk {
+                let info = info?;
+                boundary.extend(
+                    info.parent_ids
+                        .iter()
+                        .filter(|parent| hidden.contains(*parent) && seen_boundary.insert(**parent))
+                        .copied(),
+                );
+                commits.push(info.id);
+            }
+        }
+
+        let mut items: Vec<_> = commits.iter().copied().map(rev_list::Item::Commit).collect();
+        if options.boundary {
+            items.extend(boundary.iter().copied().map(rev_list::Item::Boundary));
+        }
+        if options.objects {
+            // Everything reachable from the trees of hidden tips and the commits at the edge of the hidden set is
+            // known to exist on the other side, and isn't listed.
+            let mut seen = HashSet::default();
+            let mut uninteresting = Vec::new();
+            for commit in hidden_commits.iter().chain(&boundary) {
+                uninteresting.push((
+                    self.rev_list_commit_tree(*commit)?,
+                    gix_object::Kind::Tree,
+                    BString::default(),
+                ));
+            }
+            uninteresting.extend(hidden_objects);
+            self.rev_list_traverse(uninteresting, &mut seen, &mut |_| {})?;
+
+            let mut add = |item| items.push(item);
+            for object in tip_objects {
+                self.rev_list_traverse(vec![object], &mut seen, &mut add)?;
+            }
+            for commit in &commits {
+                let tree = self.rev_list_commit_tree(*commit)?;
+                self.rev_list_traverse(
+                    vec![(tree, gix_object::Kind::Tree, BString::default())],
+                    &mut seen,
+                    &mut add,
+                )?;
+            }
+        }
+        Ok(rev_list::Outcome { items })
+    }
+}
+
+/// Utilities
+impl Repository {
+    /// Peel `id` to a commit which is added to `commits`, or to a tree or blob which is added to `objects`.
+    /// Tags along the way are added to `objects` as well.
+    fn rev_list_peel(
+        &self,
+        mut id: ObjectId,
+        commits: &mut Vec<ObjectId>,
+        objects: &mut Vec<(ObjectId, gix_object::Kind, BString)>,
+    ) -> Result<(), rev_list::Error> {
+        loop {
+            let object = self.find_object(id)?;
+            match object.kind {
+                gix_object::Kind::Commit => {
+                    commits.push(id);
+                    return Ok(());
+                }
+                gix_object::Kind::Tag => {
+                    objects.push((id, object.kind, BString::default()));
+                    id = gix_object::TagRefIter::from_bytes(&object.data).target_id()?;
+                }
+                kind @ (gix_object::Kind::Tree | gix_object::Kind::Blob) => {
+                    objects.push((id, kind, BString::default()));
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn rev_list_commit_tree(&self, commit: ObjectId) -> Result<ObjectId, rev_list::Error> {
+        Ok(gix_object::CommitRefIter::from_bytes(&self.find_object(commit)?.data).tree_id()?)
+    }
+
+    /// Traverse `stack` depth-first and pass each object that wasn't `seen` yet to `add`, along with its path.
+    /// Trees are listed before their entries, which are visited in order.
+    fn rev_list_traverse(
+        &self,
+        mut stack: Vec<(ObjectId, gix_object::Kind, BString)>,
+        seen: &mut HashSet,
+        add: &mut dyn FnMut(rev_list::Item),
+    ) -> Result<(), rev_list::Error> {
+        while let Some((id, kind, path)) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            match kind {
+                gix_object::Kind::Tree => {
+                    let object = self.find_object(id)?;
+                    let first_entry = stack.len();
+                    for entry in gix_object::TreeRefIter::from_bytes(&object.data) {
+                        let entry = entry?;
+                        let kind = if entry.mode.is_tree() {
+                            gix_object::Kind::Tree
+                        } else if entry.mode.is_commit() {
+                            continue;
+                        } else {
+                            gix_object::Kind::Blob
+                        };
+                        let mut entry_path = path.clone();
+                        if !entry_path.is_empty() {
+                            entry_path.push_byte(b'/');
+                        }
+                        entry_path.push_str(entry.filename);
+                        stack.push((entry.oid.to_owned(), kind, entry_path));
+                    }
+                    stack[first_entry..].reverse();
+                }
+                gix_object::Kind::Tag => {
+                    let object = self.find_object(id)?;
+                    let target = gix_object::TagRefIter::from_bytes(&object.data).target_id()?;
+                    let target_kind = self.find_header(target)?.kind();
+                    if target_kind != gix_object::Kind::Commit {
+                        stack.push((target, target_kind, BString::default()));
+                    }
+                }
+                gix_object::Kind::Blob | gix_object::Kind::Commit => {}
+            }
+            add(rev_list::Item::Object { id, kind, path });
+        }
+        Ok(())
+    }
+}
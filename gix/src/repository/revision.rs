@@ -1,6 +1,15 @@
+use std::collections::HashSet;
+
 use gix_macros::momo;
 
-use crate::{bstr::BStr, revision, Id};
+use crate::{
+    bstr::BStr,
+    ext::ObjectIdExt,
+    repository::{ahead_behind, resolve_prefix},
+    revision,
+    revision::spec::parse::error::CandidateInfo,
+    Id,
+};
 
 /// Methods for resolving revisions by spec or working with the commit graph.
 impl crate::Repository {
@@ -35,6 +44,37 @@ impl crate::Repository {
             .ok_or(revision::spec::parse::single::Error::RangedRev { spec: spec.into() })
     }
 
+    /// Resolve the abbreviated object id `prefix` to the only object in loose objects or packs whose id starts with it,
+    /// similar to how `git rev-parse` resolves short hashes.
+    ///
+    /// Unlike with [`rev_parse()`](Self::rev_parse()), references aren't considered, and if more than one object matches,
+    /// the error lists all of them along with their shortest unambiguous prefix.
+    /// Use [`Id::shorten()`] to abbreviate an id so it can be resolved unambiguously, respecting `core.abbrev`.
+    pub fn resolve_prefix(&self, prefix: gix_hash::Prefix) -> Result<Id<'_>, resolve_prefix::Error> {
+        let mut candidates = HashSet::new();
+        match self.objects.lookup_prefix(prefix, Some(&mut candidates))? {
+            None => Err(resolve_prefix::Error::NotFound { prefix }),
+            Some(Ok(id)) => Ok(id.attach(self)),
+            Some(Err(())) => Err(resolve_prefix::Error::Ambiguous {
+                prefix,
+                candidates: CandidateInfo::sorted_from_ids(candidates, self),
+            }),
+        }
+    }
+
+    /// Return the ids of all objects in loose objects or packs whose id starts with `prefix`, in ascending order,
+    /// similar to `git rev-parse --disambiguate=<prefix>`.
+    pub fn objects_with_prefix(
+        &self,
+        prefix: gix_hash::Prefix,
+    ) -> Result<Vec<gix_hash::ObjectId>, gix_odb::store::prefix::lookup::Error> {
+        let mut candidates = HashSet::new();
+        self.objects.lookup_prefix(prefix, Some(&mut candidates))?;
+        let mut ids: Vec<_> = candidates.into_iter().collect();
+        ids.sort();
+        Ok(ids)
+    }
+
     /// Create the baseline for a revision walk by initializing it with the `tips` to start iterating on.
     ///
     /// It can be configured further before starting the actual walk.
@@ -45,4 +85,99 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Return the amount of commits reachable from `a` but not from `b`, and the amount of commits reachable from `b`
+    /// but not from `a`, like `git rev-list --left-right --count a...b` does.
+    ///
+    /// Commits are visited in order of their generation number if a commit-graph is available, or by commit time otherwise,
+    /// and the walk stops as soon as all commits left to visit are reachable from both `a` and `b`.
+    /// Thus only the commits that differ and those that lead to their merge-bases are visited.
+    ///
+    /// ### Deviation
+    ///
+    /// * Without a commit-graph, commits with timestamps that are older than the ones of their parents cause commits to be
+    ///   visited too early. This is corrected once discovered, but the walk may stop before that, so that commits
+    ///   reachable from both sides may be counted in rare cases.
+    pub fn ahead_behind(
+        &self,
+        a: impl Into<gix_hash::ObjectId>,
+        b: impl Into<gix_hash::ObjectId>,
+    ) -> Result<(usize, usize), ahead_behind::Error> {
+        /// The commit is reachable from `a`.
+        const A: u8 = 1 << 0;
+        /// The commit is reachable from `b`.
+        const B: u8 = 1 << 1;
+        /// The commit was ever added to the queue.
+        const SEEN: u8 = 1 << 2;
+        /// The commit is currently in the queue.
+        const QUEUED: u8 = 1 << 3;
+        /// The commit was visited before all of its children due to clock skew, and is queued again to propagate
+        /// the reachability it was missing to its parents.
+        const REVISIT: u8 = 1 << 4;
+        const BOTH: u8 = A | B;
+
+        let (a, b) = (a.into(), b.into());
+        if a == b {
+            return Ok((0, 0));
+        }
+        let mut graph = self.revision_graph::<gix_revwalk::graph::Commit<u8>>();
+        let mut queue = gix_revwalk::PriorityQueue::new();
+        let key = |commit: &gix_revwalk::graph::Commit<u8>| {
+            (
+                commit.generation.unwrap_or(gix_commitgraph::GENERATION_NUMBER_INFINITY),
+                commit.commit_time,
+            )
+        };
+        for (id, side) in [(a, A), (b, B)] {
+            let commit = graph
+                .try_lookup_or_insert_commit(id, |flags| *flags |= side | SEEN | QUEUED)?
+                .ok_or(ahead_behind::Error::NotFound { id })?;
+            queue.insert(key(commit), id);
+        }
+
+        // The amount of queued commits that aren't reachable from both sides or that are revisited, and which may
+        // thus change the counts of their ancestors.
+        let mut pending = 2;
+        let (mut ahead, mut behind) = (0usize, 0usize);
+        let mut count = |flags: u8, delta: isize| match flags & BOTH {
+            A => ahead = ahead.wrapping_add_signed(delta),
+            B => behind = behind.wrapping_add_signed(delta),
+            _ => {}
+        };
+        while pending != 0 {
+            let Some(id) = queue.pop_value() else { break };
+            let commit = graph.get_mut(&id).expect("queued commits are in the graph");
+            let flags = commit.data;
+            commit.data &= !(QUEUED | REVISIT);
+            if flags & REVISIT != 0 || flags & BOTH != BOTH {
+                pending -= 1;
+            }
+            count(flags, 1);
+            for parent_id in commit.parents.clone() {
+                let Some(parent) = graph.try_lookup_or_insert_commit(parent_id, |_| {})? else {
+                    // The parent is missing in shallow repositories.
+                    continue;
+                };
+                let previous = parent.data;
+                parent.data |= flags & BOTH;
+                if previous & SEEN == 0 {
+                    parent.data |= SEEN | QUEUED;
+                    if flags & BOTH != BOTH {
+                        pending += 1;
+                    }
+                    queue.insert(key(parent), parent_id);
+                } else if previous & QUEUED != 0 {
+                    if previous & REVISIT == 0 && previous & BOTH != BOTH && parent.data & BOTH == BOTH {
+                        pending -= 1;
+                    }
+                } else if parent.data != previous {
+                    count(previous, -1);
+                    parent.data |= QUEUED | REVISIT;
+                    pending += 1;
+                    queue.insert(key(parent), parent_id);
+                }
+            }
+        }
+        Ok((ahead, behind))
+    }
 }
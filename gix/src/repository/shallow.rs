@@ -47,6 +47,45 @@ impl Repository {
         )
     }
 
+    /// Replace the shallow boundary with `commits`, whose parents are considered absent, by rewriting the `shallow` file.
+    ///
+    /// If `commits` is empty, the `shallow` file is removed and the repository isn't shallow anymore.
+    pub fn set_shallow_commits(
+        &self,
+        commits: impl IntoIterator<Item = gix_hash::ObjectId>,
+    ) -> Result<(), crate::shallow::write::Error> {
+        let file = gix_lock::File::acquire_to_update_resource(
+            self.shallow_file(),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+        let mut commits: Vec<_> = commits.into_iter().collect();
+        commits.sort();
+        commits.dedup();
+        crate::shallow::write::function::write_commits(file, commits)
+    }
+
+    /// Remove all commits from the shallow boundary whose parents are all available in the object database,
+    /// which is the case after their history was fetched completely, and return the remaining shallow commits.
+    ///
+    /// If no shallow commits remain, the `shallow` file is removed and the repository isn't shallow anymore.
+    pub fn unshallow(&self) -> Result<Vec<gix_hash::ObjectId>, crate::shallow::unshallow::Error> {
+        let Some(shallow_commits) = self.shallow_commits()? else {
+            return Ok(Vec::new());
+        };
+        let mut remaining = Vec::with_capacity(shallow_commits.len());
+        for id in shallow_commits.iter() {
+            let commit = self.find_object(*id)?.try_into_commit()?;
+            if !commit.parent_ids().all(|parent| self.has_object(parent)) {
+                remaining.push(*id);
+            }
+        }
+        if remaining.len() != shallow_commits.len() {
+            self.set_shallow_commits(remaining.iter().copied())?;
+        }
+        Ok(remaining)
+    }
+
     /// Return the path to the `shallow` file which contains hashes, one per line, that describe commits that don't have their
     /// parents within this repository.
     ///
@@ -0,0 +1,95 @@
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, BString},
+    shortlog, Repository,
+};
+
+/// Shortlog
+impl Repository {
+    /// Summarize the commits reachable from `spec` by the identity of their author, like `git shortlog <spec>`, with names
+    /// and emails resolved through the [mailmap](Self::open_mailmap()).
+    ///
+    /// `spec` is a revision specification like `main`, which includes all commits reachable from it, or a range like
+    /// `v1.0..main`, `^v1.0` which is the same as `v1.0..HEAD`, `main^!` or `main^@`. Symmetric differences like `a...b`
+    /// aren't supported.
+    ///
+    /// The subjects of the commits are the first paragraph of their message, joined into a single line.
+    pub fn shortlog(
+        &self,
+        spec: impl AsRef<BStr>,
+        options: shortlog::Options,
+    ) -> Result<shortlog::Outcome, shortlog::Error> {
+        let _span = gix_trace::coarse!("gix::shortlog()", options = ?options);
+        let spec = spec.as_ref();
+        let (tips, hidden) = match self.rev_parse(spec)?.detach() {
+            gix_revision::Spec::Include(tip) => (vec![tip], Vec::new()),
+            gix_revision::Spec::Exclude(hidden) => (vec![self.head_id()?.detach()], vec![hidden]),
+            gix_revision::Spec::Range { from, to } => (vec![to], vec![from]),
+            gix_revision::Spec::ExcludeParents(commit) => (vec![commit], self.shortlog_parents(commit)?),
+            gix_revision::Spec::IncludeOnlyParents(commit) => (self.shortlog_parents(commit)?, Vec::new()),
+            gix_revision::Spec::Merge { .. } => return Err(shortlog::Error::UnsupportedSpec { spec: spec.into() }),
+        };
+
+        let mut hidden_commits = gix_hashtable::HashSet::default();
+        if !hidden.is_empty() {
+            for info in self.rev_walk(hidden).all()? {
+                hidden_commits.insert(info?.id);
+            }
+        }
+        let mailmap = self.open_mailmap();
+        let mut entries = Vec::<shortlog::Entry>::new();
+        let mut entry_by_identity = std::collections::HashMap::<BString, usize>::new();
+        if !tips.is_empty() {
+            for info in self
+                .rev_walk(tips)
+                .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+                .selected(move |id| !hidden_commits.contains(id))?
+            {
+                let info = info?;
+                if !options.merges && info.parent_ids.len() > 1 {
+                    continue;
+                }
+                let commit = self.find_object(info.id)?.into_commit();
+                let decoded = commit.decode()?;
+                let signature = match options.group {
+                    shortlog::Group::Author => decoded.author(),
+                    shortlog::Group::Committer => decoded.committer(),
+                };
+                let signature = mailmap.resolve_cow(signature);
+                let entry = shortlog::Entry {
+                    name: signature.name.as_ref().into(),
+                    email: options.email.then(|| signature.email.as_ref().into()),
+                    subjects: Vec::new(),
+                };
+                let idx = *entry_by_identity.entry(entry.identity()).or_insert_with(|| {
+                    entries.push(entry);
+                    entries.len() - 1
+                });
+                entries[idx].subjects.push(decoded.message().summary().into_owned());
+            }
+        }
+
+        for entry in &mut entries {
+            entry.subjects.reverse();
+        }
+        entries.sort_by_cached_key(shortlog::Entry::identity);
+        if options.sort == shortlog::Sort::Count {
+            entries.sort_by_key(|entry| std::cmp::Reverse(entry.count()));
+        }
+        Ok(shortlog::Outcome { entries })
+    }
+}
+
+/// Utilities
+impl Repository {
+    fn shortlog_parents(&self, commit: ObjectId) -> Result<Vec<ObjectId>, shortlog::Error> {
+        Ok(self
+            .find_object(commit)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit()
+            .parent_ids()
+            .map(crate::Id::detach)
+            .collect())
+    }
+}
@@ -0,0 +1,517 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    ext::ObjectIdExt,
+    merge, stash, Repository,
+};
+
+const STASH_REF: &str = "refs/stash";
+
+/// A stash commit as created by [`Repository::create_stash()`].
+struct CreatedStash {
+    id: ObjectId,
+    message: BString,
+    /// The tree of `HEAD` at the time the stash was created.
+    head_tree: ObjectId,
+    /// The paths of the untracked files that were stashed.
+    untracked: Vec<BString>,
+}
+
+/// Stash
+impl Repository {
+    /// Record all local changes to tracked files in the index and the worktree as a stash commit and return its id, without
+    /// changing the index, the worktree or any reference, similar to `git stash create [<message>]`.
+    ///
+    /// Return `None` if there are no local changes to stash.
+    ///
+    /// The stash commit has `HEAD` as first parent and a commit with the tree of the index as second parent. Its tree holds
+    /// the tracked files as they are in the worktree, after applying the conversions configured for them.
+    /// Its message is `On <branch>: <message>` if `message` is set, or `WIP on <branch>: <short-id> <subject>` otherwise.
+    ///
+    /// Untracked files are not included.
+    pub fn stash_create(&self, message: Option<&str>) -> Result<Option<crate::Id<'_>>, stash::create::Error> {
+        let _span = gix_trace::coarse!("gix::stash_create()");
+        Ok(self
+            .create_stash(message.map(Into::into), false)?
+            .map(|created| created.id.attach(self)))
+    }
+
+    /// Save all local changes in a new stash commit, make it the top of the stash list and reset the index and the worktree
+    /// to `HEAD`, similar to `git stash push`.
+    ///
+    /// Return the id of the stash commit, or `None` if there were no local changes to stash.
+    ///
+    /// The stash commit is created like with [`stash_create()`](Self::stash_create()). If
+    /// [`options.include_untracked`](stash::push::Options::include_untracked) is set, untracked files that aren't ignored are
+    /// recorded in a parentless commit that becomes its third parent, and are removed from the worktree.
+    pub fn stash_push(&self, options: stash::push::Options) -> Result<Option<crate::Id<'_>>, stash::push::Error> {
+        let _span = gix_trace::coarse!("gix::stash_push()", options = ?options);
+        let Some(created) =
+            self.create_stash(options.message.as_ref().map(AsRef::as_ref), options.include_untracked)?
+        else {
+            return Ok(None);
+        };
+        self.store_stash(created.id, created.message.trim_end().to_str_lossy().as_ref())?;
+
+        let (mut index, _files_removed, _checkout) =
+            self.checkout_tree(Some(created.head_tree), created.head_tree, true)?;
+        index.write(Default::default())?;
+
+        let workdir = self.work_dir().expect("a stash could be created");
+        for rela_path in &created.untracked {
+            let path = workdir.join(gix_path::from_bstr(rela_path.as_bstr()));
+            match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(stash::push::Error::RemoveUntracked { path, source: err }),
+            }
+            if let Some(parent) = path.parent() {
+                gix_fs::dir::remove::empty_upward_until_boundary(parent, workdir).ok();
+            }
+        }
+        Ok(Some(created.id.attach(self)))
+    }
+
+    /// Return all stashes, most recent first, so that the entry at index `n` is `stash@{n}`, similar to `git stash list`.
+    pub fn stash_list(&self) -> Result<Vec<stash::Entry>, stash::list::Error> {
+        Ok(self
+            .stash_log()?
+            .into_iter()
+            .rev()
+            .map(|line| stash::Entry {
+                id: line.new_oid,
+                message: line.message,
+            })
+            .collect())
+    }
+
+    /// Apply the changes of the stash at `index`, with `0` being the most recent one, to the index and the worktree,
+    /// similar to `git stash apply stash@{<index>}`.
+    ///
+    /// Changes are merged with the current state of the index, and are unstaged afterwards, except for files that the stash
+    /// added. Untracked files that were stashed are restored, but only if none of them exists in the worktree.
+    ///
+    /// Return the conflicts that occurred, whose unmerged stages are kept in the index, or an empty list if the stash
+    /// applied cleanly.
+    pub fn stash_apply(&self, index: usize) -> Result<Vec<merge::Conflict>, stash::apply::Error> {
+        let _span = gix_trace::coarse!("gix::stash_apply()", index);
+        let id = self
+            .stash_list()?
+            .get(index)
+            .map(|entry| entry.id)
+            .ok_or(stash::apply::Error::NoSuchStash { index })?;
+        self.apply_stash(id)
+    }
+
+    /// [Apply](Self::stash_apply()) the stash at `index` and [drop](Self::stash_drop()) it if there were no conflicts,
+    /// similar to `git stash pop stash@{<index>}`.
+    ///
+    /// Return the conflicts that occurred, in which case the stash is kept.
+    pub fn stash_pop(&self, index: usize) -> Result<Vec<merge::Conflict>, stash::pop::Error> {
+        let _span = gix_trace::coarse!("gix::stash_pop()", index);
+        let conflicts = self.stash_apply(index)?;
+        if conflicts.is_empty() {
+            self.stash_drop(index)?;
+        }
+        Ok(conflicts)
+    }
+
+    /// Remove the stash at `index`, with `0` being the most recent one, from the stash list and return its id, similar to
+    /// `git stash drop stash@{<index>}`.
+    ///
+    /// The reflog of `refs/stash` is rewritten without the entry and `refs/stash` is updated to point to the most recent
+    /// remaining stash, or deleted along with its reflog if there is none left.
+    pub fn stash_drop(&self, index: usize) -> Result<crate::Id<'_>, stash::drop::Error> {
+        let _span = gix_trace::coarse!("gix::stash_drop()", index);
+        let name: FullName = STASH_REF.try_into().expect("valid");
+        let log_path = self.refs.reflog_path(name.as_ref());
+        let mut lock = gix_lock::File::acquire_to_update_resource(&log_path, self.config.lock_timeout()?.0, None)?;
+
+        let mut lines = self.stash_log()?;
+        let pos = lines
+            .len()
+            .checked_sub(index + 1)
+            .ok_or(stash::drop::Error::NoSuchStash { index })?;
+        let dropped = lines.remove(pos);
+        let previous_oid = pos
+            .checked_sub(1)
+            .map_or_else(|| self.object_hash().null(), |prev| lines[prev].new_oid);
+        if let Some(next) = lines.get_mut(pos) {
+            next.previous_oid = previous_oid;
+        }
+
+        let Some(top) = lines.last() else {
+            drop(lock);
+            self.edit_reference(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExistAndMatch(Target::Peeled(dropped.new_oid)),
+                    log: RefLog::AndReference,
+                },
+                name,
+                deref: false,
+            })?;
+            return Ok(dropped.new_oid.attach(self));
+        };
+        if pos == lines.len() {
+            self.edit_reference(RefEdit {
+                change: Change::Update {
+                    log: LogChange {
+                        mode: RefLog::AndReference,
+                        force_create_reflog: false,
+                        message: top.message.clone(),
+                    },
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(top.new_oid),
+                },
+                name,
+                deref: false,
+            })?;
+        }
+        for line in &lines {
+            line.write_to(&mut lock)?;
+        }
+        lock.commit()?;
+        Ok(dropped.new_oid.attach(self))
+    }
+
+    /// Create the stash commit for [`stash_create()`](Self::stash_create()) and [`stash_push()`](Self::stash_push()),
+    /// also recording untracked files if `include_untracked` is set.
+    fn create_stash(
+        &self,
+        message: Option<&BStr>,
+        include_untracked: bool,
+    ) -> Result<Option<CreatedStash>, stash::create::Error> {
+        let workdir = self.work_dir().ok_or_else(|| stash::create::Error::BareRepository {
+            git_dir: self.git_dir().to_owned(),
+        })?;
+        let branch = self
+            .head_name()?
+            .map_or_else(|| "(no branch)".into(), |name| name.shorten().to_owned());
+        let head_id = self
+            .head()?
+            .try_into_peeled_id()?
+            .ok_or(stash::create::Error::UnbornHead)?;
+        let head_commit = head_id.object()?.peel_to_kind(gix_object::Kind::Commit)?.into_commit();
+        let head_tree = head_commit.tree_id()?.detach();
+        let subject = head_commit.decode()?.message_summary().into_owned();
+
+        let index = self.index_or_empty()?;
+        if index
+            .entries()
+            .iter()
+            .any(|e| e.stage() != gix_index::entry::Stage::Unconflicted)
+        {
+            return Err(stash::create::Error::Unmerged);
+        }
+        let index_tree = self.write_tree_from_index(&index)?.detach();
+
+        let capabilities = self.config.fs_capabilities()?;
+        let (mut pipeline, _) = self.filter_pipeline(None)?;
+        let mut buf = Vec::new();
+        // Write the worktree version of `rela_path` as blob and return it with its mode, or `None` if it doesn't exist.
+        let mut hash_worktree_file =
+            |rela_path: &BStr,
+             index_mode: gix_index::entry::Mode|
+             -> Result<Option<(ObjectId, gix_index::entry::Mode)>, stash::create::Error> {
+                let path = workdir.join(gix_path::from_bstr(rela_path));
+                let metadata = match path.symlink_metadata() {
+                    Ok(metadata) if !metadata.is_dir() => metadata,
+                    Ok(_) => return Ok(None),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                    Err(err) => return Err(stash::create::Error::ReadFile { path, source: err }),
+                };
+                buf.clear();
+                let mode = if metadata.is_symlink() && capabilities.symlink {
+                    let target = std::fs::read_link(&path)
+                        .map_err(|err| stash::create::Error::ReadFile { path, source: err })?;
+                    buf.extend_from_slice(&gix_path::into_bstr(target));
+                    gix_index::entry::Mode::SYMLINK
+                } else {
+                    let file = std::fs::File::open(&path).map_err(|err| stash::create::Error::ReadFile {
+                        path: path.clone(),
+                        source: err,
+                    })?;
+                    let mut converted =
+                        pipeline.convert_to_git(file, gix_path::from_bstr(rela_path).as_ref(), &index)?;
+                    std::io::copy(&mut converted, &mut buf)
+                        .map_err(|err| stash::create::Error::ReadFile { path, source: err })?;
+                    if !capabilities.executable_bit {
+                        if index_mode == gix_index::entry::Mode::FILE_EXECUTABLE {
+                            index_mode
+                        } else {
+                            gix_index::entry::Mode::FILE
+                        }
+                    } else if gix_fs::is_executable(&metadata) {
+                        gix_index::entry::Mode::FILE_EXECUTABLE
+                    } else {
+                        gix_index::entry::Mode::FILE
+                    }
+                };
+                Ok(Some((self.write_blob(&buf)?.detach(), mode)))
+            };
+
+        let mut worktree_index = gix_index::State::clone(&index);
+        let mut has_worktree_changes = false;
+        let iter = self
+            .status(gix_features::progress::Discard)?
+            .index_worktree_rewrites(None)
+            .index_worktree_submodules(crate::status::Submodule::Given {
+                ignore: crate::submodule::config::Ignore::All,
+                check_dirty: false,
+            })
+            .index_worktree_options_mut(|opts| {
+                opts.dirwalk_options = None;
+            })
+            .into_index_worktree_iter(Vec::new())?;
+        for item in iter {
+            let item = item?;
+            if item.summary().is_none() {
+                continue;
+            }
+            let crate::status::index_worktree::iter::Item::Modification { rela_path, .. } = item else {
+                continue;
+            };
+            let Some(entry) =
+                worktree_index.entry_mut_by_path_and_stage(rela_path.as_bstr(), gix_index::entry::Stage::Unconflicted)
+            else {
+                continue;
+            };
+            if entry.mode == gix_index::entry::Mode::COMMIT {
+                continue;
+            }
+            has_worktree_changes = true;
+            match hash_worktree_file(rela_path.as_bstr(), entry.mode)? {
+                Some((id, mode)) => {
+                    entry.id = id;
+                    entry.mode = mode;
+                }
+                None => entry.flags.insert(gix_index::entry::Flags::REMOVE),
+            }
+        }
+
+        let mut untracked = Vec::new();
+        let mut untracked_index = gix_index::State::new(self.object_hash());
+        if include_untracked {
+            let mut collect = gix_dir::walk::delegate::Collect::default();
+            self.dirwalk(
+                &index,
+                None::<&str>,
+                &AtomicBool::default(),
+                self.dirwalk_options()?
+                    .emit_untracked(gix_dir::walk::EmissionMode::Matching),
+                &mut collect,
+            )?;
+            for (entry, _dir_status) in collect.into_entries_by_path() {
+                if entry.status != gix_dir::entry::Status::Untracked
+                    || !matches!(
+                        entry.disk_kind,
+                        Some(gix_dir::entry::Kind::File | gix_dir::entry::Kind::Symlink)
+                    )
+                {
+                    continue;
+                }
+                let Some((id, mode)) = hash_worktree_file(entry.rela_path.as_bstr(), gix_index::entry::Mode::FILE)?
+                else {
+                    continue;
+                };
+                untracked_index.dangerously_push_entry(
+                    Default::default(),
+                    id,
+                    gix_index::entry::Flags::empty(),
+                    mode,
+                    entry.rela_path.as_bstr(),
+                );
+                untracked.push(entry.rela_path);
+            }
+        }
+
+        if index_tree == head_tree && !has_worktree_changes && untracked.is_empty() {
+            return Ok(None);
+        }
+        let worktree_tree = self.write_tree_from_index(&worktree_index)?.detach();
+
+        let committer: gix_actor::Signature = self.committer().ok_or(stash::create::Error::CommitterMissing)??.into();
+        let commit = |tree: ObjectId, parents: Vec<ObjectId>, message: BString| {
+            self.write_object(&gix_object::Commit {
+                tree,
+                parents: parents.into_iter().collect(),
+                author: committer.clone(),
+                committer: committer.clone(),
+                encoding: None,
+                message,
+                extra_headers: Default::default(),
+            })
+            .map(crate::Id::detach)
+        };
+        let title = format!("{branch}: {short_id} {subject}", short_id = head_id.shorten_or_id());
+        let mut parents = vec![
+            head_id.detach(),
+            commit(index_tree, vec![head_id.detach()], format!("index on {title}\n").into())?,
+        ];
+        if !untracked.is_empty() {
+            let untracked_tree = self.write_tree_from_index(&untracked_index)?.detach();
+            parents.push(commit(
+                untracked_tree,
+                Vec::new(),
+                format!("untracked files on {title}\n").into(),
+            )?);
+        }
+        let message: BString = match message {
+            Some(message) => format!("On {branch}: {message}\n").into(),
+            None => format!("WIP on {title}\n").into(),
+        };
+        Ok(Some(CreatedStash {
+            id: commit(worktree_tree, parents, message.clone())?,
+            message,
+            head_tree,
+            untracked,
+        }))
+    }
+
+    /// Return all lines of the reflog of `refs/stash`, oldest first.
+    fn stash_log(&self) -> Result<Vec<gix_ref::log::Line>, stash::list::Error> {
+        let Some(stash) = self.try_find_reference(STASH_REF)? else {
+            return Ok(Vec::new());
+        };
+        let mut platform = stash.log_iter();
+        let Some(lines) = platform.all()? else {
+            return Ok(Vec::new());
+        };
+        Ok(lines.map(|line| line.map(Into::into)).collect::<Result<_, _>>()?)
+    }
+
+    /// Point `refs/stash` to `stash` and record `message` in its reflog, similar to `git stash store -m <message>`.
+    pub(crate) fn store_stash(&self, stash: ObjectId, message: &str) -> Result<(), crate::reference::edit::Error> {
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: true,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(stash),
+            },
+            name: STASH_REF.try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(())
+    }
+
+    /// Merge the changes recorded in the `stash` commit into the index and the worktree, similar to `git stash apply`.
+    ///
+    /// Changes are unstaged afterwards, except for files that the stash added. On conflicts, the unmerged stages are kept
+    /// in the index and the conflicts are returned.
+    pub(crate) fn apply_stash(&self, stash: ObjectId) -> Result<Vec<merge::Conflict>, stash::apply::Error> {
+        let workdir = self.work_dir().ok_or_else(|| stash::apply::Error::BareRepository {
+            git_dir: self.git_dir().to_owned(),
+        })?;
+        let stash_commit = self
+            .find_object(stash)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let stash_tree = stash_commit.tree_id()?.detach();
+        let base = stash_commit
+            .parent_ids()
+            .next()
+            .ok_or(stash::apply::Error::NotAStash { id: stash })?;
+        let base_tree = base
+            .object()?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit()
+            .tree_id()?
+            .detach();
+        let mut untracked = match stash_commit.parent_ids().nth(2) {
+            Some(id) => {
+                let tree = id
+                    .object()?
+                    .peel_to_kind(gix_object::Kind::Commit)?
+                    .into_commit()
+                    .tree_id()?;
+                Some(self.index_from_tree(&tree)?)
+            }
+            None => None,
+        };
+        if let Some(untracked) = &untracked {
+            if let Some(entry) = untracked.entries().iter().find(|entry| {
+                workdir
+                    .join(gix_path::from_bstr(entry.path(untracked)))
+                    .symlink_metadata()
+                    .is_ok()
+            }) {
+                return Err(stash::apply::Error::UntrackedFileExists {
+                    rela_path: entry.path(untracked).to_owned(),
+                });
+            }
+        }
+
+        let index = self.index_or_empty()?;
+        let current_tree = self.write_tree_from_index(&index)?.detach();
+        let merge = self.merge_trees(
+            base_tree,
+            current_tree,
+            stash_tree,
+            merge::blob::Labels {
+                ours: Some("Updated upstream".into()),
+                theirs: Some("Stashed changes".into()),
+            },
+        )?;
+        let (merged_index, _files_removed, _checkout) = self.checkout_index(Some(current_tree), &merge.index, false)?;
+        if let Some(untracked) = &mut untracked {
+            self.write_untracked_files(untracked)?;
+        }
+        if merge.has_conflicts() {
+            let conflicts = merge.conflicts.clone();
+            let merge = merge::Outcome {
+                index: merged_index.into(),
+                conflicts: merge.conflicts,
+            };
+            gix_index::File::from_state(merge.into_index_with_conflicts(), self.index_path())
+                .write(Default::default())?;
+            return Ok(conflicts);
+        }
+
+        // Keep the previous index to leave all changes unstaged, but add what's new so it isn't lost as untracked file.
+        let mut new_index = gix_index::State::new(self.object_hash());
+        for entry in index.entries() {
+            new_index.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, entry.path(&index));
+        }
+        for entry in merged_index.entries() {
+            let path = entry.path(&merged_index);
+            if index.entry_by_path(path).is_none() {
+                new_index.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, path);
+            }
+        }
+        new_index.sort_entries();
+        gix_index::File::from_state(new_index, self.index_path()).write(Default::default())?;
+        Ok(Vec::new())
+    }
+
+    /// Write all `files` into the worktree, which must not exist yet, without adding them to the index.
+    fn write_untracked_files(&self, files: &mut gix_index::State) -> Result<(), crate::checkout::Error> {
+        let workdir = self.work_dir().expect("checked by caller");
+        let opts = self
+            .config
+            .checkout_options(self, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+        gix_worktree_state::checkout(
+            files,
+            workdir,
+            self.objects
+                .clone()
+                .into_arc()
+                .map_err(crate::checkout::Error::OpenArcOdb)?,
+            &gix_features::progress::Discard,
+            &gix_features::progress::Discard,
+            &crate::interrupt::IS_INTERRUPTED,
+            opts,
+        )?;
+        Ok(())
+    }
+}
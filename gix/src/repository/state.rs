@@ -1,3 +1,5 @@
+use gix_ref::transaction::RefEdit;
+
 use crate::state;
 
 impl crate::Repository {
@@ -41,4 +43,89 @@ impl crate::Repository {
             None
         }
     }
+
+    /// Apply `changes` to references and state files like `ORIG_HEAD` and `MERGE_MSG` so that either all of them are applied,
+    /// or none of them if a state file can't be locked or a reference can't be edited.
+    ///
+    /// All state files are locked and their new content is written before the references are edited, and they are only put
+    /// into place once all reference edits were committed. Removing files that don't exist isn't an error.
+    ///
+    /// Returns the performed reference edits, just like [`edit_references()`](Self::edit_references()).
+    pub fn apply_state_changes(&self, changes: state::Changes) -> Result<Vec<RefEdit>, state::apply::Error> {
+        use std::io::Write;
+
+        use state::apply::Error;
+
+        enum Lock {
+            Write(gix_lock::File),
+            Remove(gix_lock::Marker),
+        }
+
+        let (file_lock_fail, _packed_refs_lock_fail) = self
+            .config
+            .lock_timeout()
+            .map_err(crate::reference::edit::Error::from)?;
+        let mut locks = Vec::with_capacity(changes.files.len());
+        for (file, content) in changes.files {
+            let path = self.path().join(file.as_str());
+            let lock_error = |source| Error::LockFile {
+                path: path.clone(),
+                source,
+            };
+            let lock = match content {
+                Some(content) => {
+                    let mut lock =
+                        gix_lock::File::acquire_to_update_resource(&path, file_lock_fail, None).map_err(lock_error)?;
+                    lock.write_all(&content).map_err(|source| Error::WriteFile {
+                        path: path.clone(),
+                        source,
+                    })?;
+                    Lock::Write(lock)
+                }
+                None => Lock::Remove(
+                    gix_lock::Marker::acquire_to_hold_resource(&path, file_lock_fail, None).map_err(lock_error)?,
+                ),
+            };
+            locks.push((path, lock));
+        }
+
+        let edits = if changes.edits.is_empty() {
+            Vec::new()
+        } else {
+            self.edit_references(changes.edits)?
+        };
+
+        for (path, lock) in locks {
+            match lock {
+                Lock::Write(file) => {
+                    file.commit().map_err(|err| Error::WriteFile {
+                        path,
+                        source: err.error,
+                    })?;
+                }
+                Lock::Remove(marker) => {
+                    match std::fs::remove_file(&path) {
+                        Ok(()) => {}
+                        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                        Err(source) => return Err(Error::RemoveFile { path, source }),
+                    }
+                    drop(marker);
+                }
+            }
+        }
+        Ok(edits)
+    }
+
+    /// Remove all files that keep the state of an unfinished merge, cherry-pick or revert, similar to what `git reset` does.
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    pub(crate) fn remove_merge_state(&self) -> std::io::Result<()> {
+        for file in state::File::MERGE_STATE {
+            match std::fs::remove_file(self.path().join(file.as_str())) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
 }
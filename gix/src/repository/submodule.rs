@@ -94,3 +94,332 @@ impl Repository {
         ))
     }
 }
+
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "status",
+    feature = "revision",
+    feature = "blocking-network-client"
+))]
+mod update {
+    use std::{
+        io::Write,
+        path::{Component, Path, PathBuf},
+    };
+
+    use crate::{
+        bstr::{BStr, BString, ByteSlice, ByteVec},
+        remote,
+        submodule::update,
+        Repository, Submodule,
+    };
+
+    /// Submodule update
+    impl Repository {
+        /// Bring all initialized and active submodules to the commit recorded for them in the index, similar to
+        /// `git submodule update`, and return what happened to each of them.
+        ///
+        /// Submodules whose repository doesn't exist yet are cloned into `.git/modules/<name>` of their superproject, with
+        /// their worktree connected to it by a `.git` file. The recorded commit is then checked out on a detached `HEAD`, which
+        /// fails if local changes in the submodule would be overwritten. If the commit isn't present in an existing submodule
+        /// repository, it is fetched from its default remote first.
+        ///
+        /// Relative submodule URLs like `../other` are resolved against the URL of the default remote of the superproject, or
+        /// its worktree if it has none.
+        ///
+        /// With [`options.init`](update::Options::init), uninitialized submodules are initialized first. With
+        /// [`options.recursive`](update::Options::recursive), the submodules of each updated submodule are updated as well,
+        /// up to [`options.max_depth`](update::Options::max_depth) levels deep, and submodules that use the repository of one of
+        /// their superprojects are rejected.
+        ///
+        /// Only the `checkout` update mode is supported, and submodules configured with `update = none` are skipped.
+        ///
+        /// Note that the configuration written when initializing submodules isn't visible in the configuration snapshot of
+        /// this instance, which has to be reopened to see it.
+        pub fn submodule_update(&self, options: update::Options) -> Result<update::Outcome, update::Error> {
+            let _span = gix_trace::coarse!("gix::submodule_update()", options = ?options);
+            let mut out = update::Outcome::default();
+            self.submodule_update_inner(options, 1, "".into(), &mut Vec::new(), &mut out.entries)?;
+            Ok(out)
+        }
+
+        fn submodule_update_inner(
+            &self,
+            options: update::Options,
+            depth: usize,
+            prefix: &BStr,
+            superprojects: &mut Vec<PathBuf>,
+            out: &mut Vec<update::Entry>,
+        ) -> Result<(), update::Error> {
+            if self.work_dir().is_none() {
+                return Err(update::Error::BareRepository {
+                    git_dir: self.git_dir().to_owned(),
+                });
+            }
+            let Some(submodules) = self.submodules()? else {
+                return Ok(());
+            };
+            superprojects.push(gix_path::realpath(self.git_dir())?);
+            for sm in submodules {
+                let name = sm.name().to_owned();
+                let mut path = prefix.to_owned();
+                if !path.is_empty() {
+                    path.push_byte(b'/');
+                }
+                path.push_str(sm.path()?.as_ref());
+
+                let Some(id) = sm.index_id()? else {
+                    continue;
+                };
+                let state = sm.state()?;
+                if !state.superproject_configuration {
+                    if !options.init {
+                        continue;
+                    }
+                    self.init_submodule(&sm)?;
+                } else if !sm.is_active()? {
+                    continue;
+                }
+                match sm.update()?.unwrap_or_default() {
+                    gix_submodule::config::Update::Checkout => {}
+                    gix_submodule::config::Update::None => continue,
+                    mode => return Err(update::Error::UnsupportedUpdateMode { name, mode }),
+                }
+                if depth > options.max_depth {
+                    return Err(update::Error::MaxDepthExceeded {
+                        path,
+                        max_depth: options.max_depth,
+                    });
+                }
+
+                let cloned = !state.repository_exists;
+                if cloned {
+                    self.clone_submodule(&sm)?;
+                } else if !state.worktree_checkout {
+                    connect_worktree(&sm.git_dir(), &sm.work_dir()?)?;
+                }
+                let sm_repo = sm
+                    .open()?
+                    .ok_or_else(|| update::Error::MissingCommit { name: name.clone(), id })?;
+                if !sm_repo.has_object(id) && !cloned {
+                    if let Some(remote) = sm_repo.find_default_remote(remote::Direction::Fetch).transpose()? {
+                        remote
+                            .connect(remote::Direction::Fetch)?
+                            .prepare_fetch(gix_features::progress::Discard, Default::default())?
+                            .receive(gix_features::progress::Discard, &crate::interrupt::IS_INTERRUPTED)?;
+                    }
+                }
+                if !sm_repo.has_object(id) {
+                    return Err(update::Error::MissingCommit { name, id });
+                }
+
+                let head_id = sm_repo.head()?.try_into_peeled_id()?.map(crate::Id::detach);
+                let action = if cloned {
+                    update::Action::Cloned
+                } else if head_id == Some(id) && state.worktree_checkout {
+                    update::Action::UpToDate
+                } else {
+                    update::Action::CheckedOut
+                };
+                if action != update::Action::UpToDate {
+                    sm_repo.checkout(
+                        id.to_hex().to_string().as_str(),
+                        crate::checkout::Options {
+                            // A fresh worktree has no index yet, so every file has to be written.
+                            force: cloned || !state.worktree_checkout,
+                            detach: true,
+                        },
+                    )?;
+                }
+                out.push(update::Entry {
+                    path: path.clone(),
+                    name,
+                    id,
+                    action,
+                });
+
+                if options.recursive {
+                    let git_dir = gix_path::realpath(sm_repo.git_dir())?;
+                    if superprojects.contains(&git_dir) {
+                        return Err(update::Error::Cycle { git_dir });
+                    }
+                    sm_repo.submodule_update_inner(options, depth + 1, path.as_ref(), superprojects, out)?;
+                }
+            }
+            superprojects.pop();
+            Ok(())
+        }
+
+        /// Write the resolved URL of `sm` into our local configuration, and mark it active unless `submodule.active` decides
+        /// that, similar to `git submodule init`.
+        ///
+        /// As our configuration snapshot isn't updated, the file is checked first to not initialize a submodule twice.
+        fn init_submodule(&self, sm: &Submodule<'_>) -> Result<(), update::Error> {
+            let path = self.common_dir().join("config");
+            let local =
+                gix_config::File::from_path_no_includes(path.clone(), gix_config::Source::Local).map_err(|err| {
+                    update::Error::ReadConfig {
+                        path: path.clone(),
+                        source: err,
+                    }
+                })?;
+            let subsection = Some(sm.name());
+            if local.section("submodule", subsection).is_ok() {
+                return Ok(());
+            }
+
+            let url = self.submodule_url(sm)?;
+            let mut config = gix_config::File::new(gix_config::file::Metadata::from(gix_config::Source::Local));
+            config
+                .set_raw_value("submodule", subsection, "url", url.as_bstr())
+                .expect("statically known key");
+            if self.config.resolved.string("submodule", None, "active").is_none() {
+                config
+                    .set_raw_value("submodule", subsection, "active", "true")
+                    .expect("statically known key");
+            }
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&path)
+                .and_then(|mut file| {
+                    file.write_all(config.detect_newline_style())?;
+                    Ok(file)
+                })
+                .map_err(|err| update::Error::Write {
+                    path: path.clone(),
+                    source: err,
+                })?;
+            config
+                .write_to(&mut file)
+                .map_err(|err| update::Error::Write { path, source: err })
+        }
+
+        /// Return the URL of `sm`, with relative URLs resolved against our default remote or our worktree.
+        fn submodule_url(&self, sm: &Submodule<'_>) -> Result<BString, update::Error> {
+            let url = sm.url()?;
+            let raw = sm
+                .state
+                .modules
+                .config()
+                .string("submodule", Some(sm.name()), "url")
+                .map_or_else(|| url.to_bstring(), std::borrow::Cow::into_owned);
+            if !(raw.starts_with(b"./") || raw.starts_with(b"../")) {
+                return Ok(raw);
+            }
+            let base = match self.find_default_remote(remote::Direction::Fetch).transpose()? {
+                Some(remote) => remote.url(remote::Direction::Fetch).map(gix_url::Url::to_bstring),
+                None => None,
+            };
+            let base = match base {
+                Some(base) => base,
+                None => {
+                    gix_path::into_bstr(gix_path::realpath(self.work_dir().expect("checked by caller"))?).into_owned()
+                }
+            };
+            Ok(resolve_relative_url(base.as_ref(), raw.as_ref()))
+        }
+
+        /// Clone the repository of `sm` into its location in our `.git/modules` directory and connect it to its worktree.
+        fn clone_submodule(&self, sm: &Submodule<'_>) -> Result<(), update::Error> {
+            let url = self.submodule_url(sm)?;
+            let url = gix_url::parse(url.as_ref()).map_err(|err| update::Error::ParseUrl {
+                name: sm.name().to_owned(),
+                source: err,
+            })?;
+            let git_dir = sm.git_dir();
+            if let Some(parent) = git_dir.parent() {
+                std::fs::create_dir_all(parent).map_err(|err| update::Error::Write {
+                    path: parent.to_owned(),
+                    source: err,
+                })?;
+            }
+            let mut prepare = crate::clone::PrepareFetch::new(
+                url,
+                &git_dir,
+                crate::create::Kind::Bare,
+                crate::create::Options::default(),
+                self.options.clone(),
+            )?;
+            prepare.fetch_only(gix_features::progress::Discard, &crate::interrupt::IS_INTERRUPTED)?;
+            connect_worktree(&git_dir, &sm.work_dir()?)
+        }
+    }
+
+    /// Turn the bare repository at `git_dir` into one with a worktree at `work_dir`, and point the worktree to it with a `.git` file.
+    fn connect_worktree(git_dir: &Path, work_dir: &Path) -> Result<(), update::Error> {
+        let write_error = |path: &Path| {
+            let path = path.to_owned();
+            move |err| update::Error::Write { path, source: err }
+        };
+        std::fs::create_dir_all(work_dir).map_err(write_error(work_dir))?;
+        let git_dir = gix_path::realpath(git_dir)?;
+        let work_dir = gix_path::realpath(work_dir)?;
+
+        let config_path = git_dir.join("config");
+        let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)
+            .map_err(|err| update::Error::ReadConfig {
+                path: config_path.clone(),
+                source: err,
+            })?;
+        config
+            .set_raw_value("core", None, "bare", "false")
+            .expect("statically known key");
+        config
+            .set_raw_value(
+                "core",
+                None,
+                "worktree",
+                gix_path::to_unix_separators_on_windows(gix_path::into_bstr(relative_path(&git_dir, &work_dir)))
+                    .as_ref(),
+            )
+            .expect("statically known key");
+        std::fs::write(&config_path, config.to_bstring()).map_err(write_error(&config_path))?;
+
+        let dot_git = work_dir.join(gix_discover::DOT_GIT_DIR);
+        let mut content = BString::from("gitdir: ");
+        content.push_str(
+            gix_path::to_unix_separators_on_windows(gix_path::into_bstr(relative_path(&work_dir, &git_dir))).as_ref(),
+        );
+        content.push_byte(b'\n');
+        std::fs::write(&dot_git, content).map_err(write_error(&dot_git))
+    }
+
+    /// Return the path to `to` relative to the directory `from`, with both paths being absolute.
+    fn relative_path(from: &Path, to: &Path) -> PathBuf {
+        let from: Vec<_> = from.components().collect();
+        let to: Vec<_> = to.components().collect();
+        let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+        std::iter::repeat(Component::ParentDir)
+            .take(from.len() - common)
+            .chain(to[common..].iter().copied())
+            .collect()
+    }
+
+    /// Resolve the submodule `url` starting with `./` or `../` against `base`, like `git` does, by removing one path component
+    /// of `base` for each `../`.
+    fn resolve_relative_url(base: &BStr, mut url: &BStr) -> BString {
+        let mut base = base.to_owned();
+        while base.ends_with(b"/") {
+            base.pop();
+        }
+        loop {
+            if let Some(rest) = url.strip_prefix(b"./") {
+                url = rest.as_bstr();
+            } else if let Some(rest) = url.strip_prefix(b"../") {
+                url = rest.as_bstr();
+                match base.rfind_byteset(b"/:") {
+                    Some(pos) if base[pos] == b':' => base.truncate(pos + 1),
+                    Some(pos) => base.truncate(pos),
+                    None => base.clear(),
+                }
+            } else {
+                break;
+            }
+        }
+        if !base.is_empty() && !base.ends_with(b":") {
+            base.push_byte(b'/');
+        }
+        base.push_str(url);
+        base
+    }
+}
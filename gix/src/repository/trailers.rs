@@ -0,0 +1,90 @@
+use crate::{
+    bstr::{BStr, BString},
+    config::{cache::util::ApplyLeniency, tree::Trailer},
+    trailers,
+};
+
+/// Trailers
+impl crate::Repository {
+    /// Obtain the configuration of trailers from the `trailer.*` values, for use with
+    /// [`Configuration::edit()`](trailers::Configuration::edit()).
+    ///
+    /// Invalid values are ignored if the configuration is lenient. Note that `trailer.<token>.command` and
+    /// `trailer.<token>.cmd` aren't supported, so no trailers are added automatically.
+    pub fn trailer_configuration(&self) -> Result<trailers::Configuration, trailers::Error> {
+        let mut filter = self.filter_config_section();
+        let lenient = self.config.lenient_config;
+        let mut out = trailers::Configuration::default();
+        for section in self
+            .config
+            .resolved
+            .sections_by_name_and_filter("trailer", &mut filter)
+            .into_iter()
+            .flatten()
+        {
+            let position = section
+                .value(Trailer::WHERE.name)
+                .map(|value| Trailer::WHERE.try_into_where(value))
+                .transpose()
+                .with_leniency(lenient)?;
+            let if_exists = section
+                .value(Trailer::IF_EXISTS.name)
+                .map(|value| Trailer::IF_EXISTS.try_into_if_exists(value))
+                .transpose()
+                .with_leniency(lenient)?;
+            let if_missing = section
+                .value(Trailer::IF_MISSING.name)
+                .map(|value| Trailer::IF_MISSING.try_into_if_missing(value))
+                .transpose()
+                .with_leniency(lenient)?;
+            let overrides = trailers::Overrides {
+                position,
+                if_exists,
+                if_missing,
+            };
+
+            match section.header().subsection_name() {
+                None => {
+                    if let Some(separators) = section.value(Trailer::SEPARATORS.name).filter(|s| !s.is_empty()) {
+                        out.separators = separators.into_owned();
+                    }
+                    out.policy = overrides.apply_to(out.policy);
+                }
+                Some(name) => {
+                    let token = match out.tokens.iter_mut().find(|token| token.name == name) {
+                        Some(existing) => existing,
+                        None => {
+                            out.tokens.push(trailers::Token {
+                                name: name.to_owned(),
+                                ..Default::default()
+                            });
+                            out.tokens.last_mut().expect("just pushed")
+                        }
+                    };
+                    if let Some(key) = section.value(Trailer::TOKEN_KEY.name) {
+                        token.key = Some(key.into_owned());
+                    }
+                    token.overrides = trailers::Overrides {
+                        position: position.or(token.overrides.position),
+                        if_exists: if_exists.or(token.overrides.if_exists),
+                        if_missing: if_missing.or(token.overrides.if_missing),
+                    };
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Apply `edits` to the trailers of `message` according to the [trailer configuration](Self::trailer_configuration())
+    /// of this repository, similar to `git interpret-trailers`, and return the edited message.
+    ///
+    /// This is useful to add `Signed-off-by` trailers or similar to messages before creating commits with them.
+    pub fn interpret_trailers(
+        &self,
+        message: impl AsRef<BStr>,
+        edits: &[trailers::Edit],
+    ) -> Result<BString, trailers::Error> {
+        let _span = gix_trace::coarse!("gix::interpret_trailers()", edits = edits.len());
+        Ok(self.trailer_configuration()?.edit(message.as_ref(), edits))
+    }
+}
@@ -0,0 +1,117 @@
+use gix_hash::ObjectId;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use crate::{
+    update_refs::{Command, Error, Operation, Options},
+    Repository,
+};
+
+/// Updating references in bulk
+impl Repository {
+    /// Perform all `commands` in a single reference transaction, like `git update-ref --stdin`, so that either all of them
+    /// are applied or none of them is.
+    ///
+    /// The [`update_refs::parse()`](crate::update_refs::parse()) function turns the text format of `git update-ref --stdin`
+    /// into commands.
+    ///
+    /// Returns all reference edits that were performed, which may be more than were provided due to the splitting of symbolic
+    /// references, with their previous values set to what was seen in storage after the references were locked.
+    ///
+    /// ### Deviation
+    ///
+    /// * References that are only verified aren't locked, but are checked after all other references were locked.
+    pub fn update_refs(
+        &self,
+        commands: impl IntoIterator<Item = Command>,
+        options: Options,
+    ) -> Result<Vec<RefEdit>, Error> {
+        let _span = gix_trace::coarse!("gix::update_refs()", options = ?options);
+        let expected = |old: Option<ObjectId>| match old {
+            None => PreviousValue::Any,
+            Some(old) if old.is_null() => PreviousValue::MustNotExist,
+            Some(old) => PreviousValue::MustExistAndMatch(Target::Peeled(old)),
+        };
+
+        let mut edits = Vec::new();
+        let mut verifications = Vec::new();
+        for Command { name, operation, deref } in commands {
+            let operation = match operation {
+                Operation::Update { new, old } if new.is_null() => Operation::Delete { old },
+                Operation::Delete { old: Some(old) } if old.is_null() => Operation::Verify { old: None },
+                operation => operation,
+            };
+            let log = LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: options.create_reflog,
+                message: options.message.clone(),
+            };
+            let change = match operation {
+                Operation::Create { new } => Change::Update {
+                    log,
+                    expected: PreviousValue::MustNotExist,
+                    new: Target::Peeled(new),
+                },
+                Operation::Update { new, old } => Change::Update {
+                    log,
+                    expected: expected(old),
+                    new: Target::Peeled(new),
+                },
+                Operation::Delete { old } => Change::Delete {
+                    expected: expected(old),
+                    log: RefLog::AndReference,
+                },
+                Operation::Verify { old } => {
+                    verifications.push((name, old, deref));
+                    continue;
+                }
+            };
+            edits.push(RefEdit { change, name, deref });
+        }
+
+        let verify = |verifications: Vec<(FullName, Option<ObjectId>, bool)>| {
+            verifications.into_iter().try_for_each(|(name, expected, deref)| {
+                let actual = self.verified_reference_id(&name, deref)?;
+                if actual != expected {
+                    return Err(Error::VerifyFailed {
+                        name: name.into_inner(),
+                        expected,
+                        actual,
+                    });
+                }
+                Ok(())
+            })
+        };
+        if self.has_reference_memory() {
+            verify(verifications)?;
+            return Ok(self.edit_references(edits)?);
+        }
+
+        let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
+        let transaction = self
+            .reference_transaction()
+            .prepare(edits, file_lock_fail, packed_refs_lock_fail)?;
+        if let Err(err) = verify(verifications) {
+            // Roll back explicitly so the `reference-transaction` hook learns that the transaction was aborted.
+            transaction.rollback();
+            return Err(err);
+        }
+        Ok(transaction.commit(self.committer().transpose()?)?)
+    }
+
+    /// Return the id `name` points to, following symbolic references if `deref` is `true`, or `None` if it doesn't exist
+    /// or is symbolic.
+    fn verified_reference_id(&self, name: &FullName, deref: bool) -> Result<Option<ObjectId>, Error> {
+        const MAX_REF_DEPTH: usize = 5;
+        let mut reference = self.try_find_reference(name)?;
+        for _ in 0..MAX_REF_DEPTH {
+            match reference.as_ref().map(|r| r.inner.target.clone()) {
+                Some(Target::Symbolic(target)) if deref => reference = self.try_find_reference(&target)?,
+                _ => break,
+            }
+        }
+        Ok(reference.and_then(|r| r.try_id().map(crate::Id::detach)))
+    }
+}
@@ -34,6 +34,262 @@ impl crate::Repository {
         crate::ThreadSafeRepository::open_opts(self.common_dir(), self.options.clone()).map(Into::into)
     }
 
+    /// Create a new linked worktree at `path`, which must not exist or be an empty directory, and check out the commit or branch
+    /// configured in `options` there, similar to `git worktree add`. Return the repository of the new worktree.
+    ///
+    /// The administrative files of the worktree are placed into `$GIT_COMMON_DIR/worktrees/<name>`, with `<name>` derived from the
+    /// last component of `path`, made unique by appending a number if needed. `<path>/.git` and `$GIT_COMMON_DIR/worktrees/<name>/gitdir`
+    /// point to each other using absolute paths.
+    ///
+    /// Branches that are checked out in another worktree, including the main worktree, can't be checked out again
+    /// unless [`options.force`](worktree::add::Options::force) is set.
+    ///
+    /// ### Deviation
+    ///
+    /// * No reflog entry is written for `HEAD` of the new worktree.
+    /// * If an error occurs, the files written up to that point are not removed.
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    pub fn worktree_add(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        options: worktree::add::Options,
+    ) -> Result<crate::Repository, worktree::add::Error> {
+        use gix_ref::{transaction::PreviousValue, FullName};
+
+        use crate::bstr::{BString, ByteSlice, ByteVec};
+        use worktree::add::Error;
+
+        let _span = gix_trace::coarse!("gix::worktree_add()", options = ?options);
+        let path = path.as_ref();
+        let io_error = |path: &std::path::Path| {
+            let path = path.to_owned();
+            move |source| Error::Io { path, source }
+        };
+        if path.exists() && std::fs::read_dir(path).map_or(true, |mut entries| entries.next().is_some()) {
+            return Err(Error::PathExists { path: path.to_owned() });
+        }
+        let base_name = path
+            .file_name()
+            .and_then(|name| gix_path::os_str_into_bstr(name).ok())
+            .filter(|name| !name.is_empty() && name.find_byteset(b"/\\").is_none())
+            .ok_or_else(|| Error::InvalidName { path: path.to_owned() })?
+            .to_owned();
+
+        let branch_name = |name: &BString| {
+            FullName::try_from(format!("refs/heads/{name}")).map_err(|err| Error::InvalidBranchName {
+                name: name.clone(),
+                source: err,
+            })
+        };
+        let mut create_branch = false;
+        let branch = if let Some(name) = &options.new_branch {
+            let branch = branch_name(name)?;
+            if !options.force && self.try_find_reference(&branch)?.is_some() {
+                return Err(Error::BranchExists { name: name.clone() });
+            }
+            create_branch = true;
+            Some(branch)
+        } else if options.detach {
+            None
+        } else if let Some(start) = &options.start {
+            match FullName::try_from(format!("refs/heads/{start}")) {
+                Ok(branch) => self.try_find_reference(&branch)?.map(|_| branch),
+                Err(_) => None,
+            }
+        } else {
+            let branch = branch_name(&base_name)?;
+            create_branch = self.try_find_reference(&branch)?.is_none();
+            Some(branch)
+        };
+        let start = options.start.as_ref().map_or("HEAD".into(), |start| start.as_bstr());
+        let commit_id = match &branch {
+            Some(branch) if !create_branch => self.find_reference(branch)?.into_fully_peeled_id()?.detach(),
+            _ => self.rev_parse_single(start)?.detach(),
+        };
+        let commit = self
+            .find_object(commit_id)?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .into_commit();
+        let commit_id = commit.id;
+        let tree_id = commit.tree_id()?.detach();
+
+        if let Some(branch) = branch.as_ref().filter(|_| !options.force) {
+            if let Some(worktree) = self.worktree_with_branch(branch)? {
+                return Err(Error::BranchCheckedOut {
+                    name: branch.shorten().to_owned(),
+                    worktree,
+                });
+            }
+        }
+        if let Some(branch) = branch.as_ref().filter(|_| create_branch) {
+            self.reference(
+                branch.clone(),
+                commit_id,
+                if options.force {
+                    PreviousValue::Any
+                } else {
+                    PreviousValue::MustNotExist
+                },
+                format!("branch: Created from {start}"),
+            )?;
+        }
+
+        let worktrees_dir = self.common_dir().join("worktrees");
+        let mut id = base_name.clone();
+        let mut counter = 0;
+        while worktrees_dir.join(gix_path::from_bstr(id.as_bstr())).exists() {
+            counter += 1;
+            id = base_name.clone();
+            id.push_str(counter.to_string());
+        }
+        let git_dir = worktrees_dir.join(gix_path::from_bstr(id.as_bstr()));
+        std::fs::create_dir_all(&git_dir).map_err(io_error(&git_dir))?;
+        let locked = git_dir.join("locked");
+        std::fs::write(&locked, "initializing").map_err(io_error(&locked))?;
+        std::fs::create_dir_all(path).map_err(io_error(path))?;
+        let git_dir = gix_path::realpath(&git_dir)?;
+        let work_dir = gix_path::realpath(path)?;
+
+        let write_path_file = |file: std::path::PathBuf, prefix: &str, path: &std::path::Path| {
+            let mut content = BString::from(prefix);
+            content.push_str(gix_path::to_unix_separators_on_windows(gix_path::into_bstr(path)).as_ref());
+            content.push_byte(b'\n');
+            std::fs::write(&file, content).map_err(io_error(&file))
+        };
+        write_path_file(git_dir.join("gitdir"), "", &work_dir.join(gix_discover::DOT_GIT_DIR))?;
+        write_path_file(work_dir.join(gix_discover::DOT_GIT_DIR), "gitdir: ", &git_dir)?;
+        let commondir = git_dir.join("commondir");
+        std::fs::write(&commondir, "../..\n").map_err(io_error(&commondir))?;
+        let head = git_dir.join("HEAD");
+        let head_content = match &branch {
+            Some(branch) => format!("ref: {}\n", branch.as_bstr()),
+            None => format!("{commit_id}\n"),
+        };
+        std::fs::write(&head, head_content).map_err(io_error(&head))?;
+
+        let repo: crate::Repository = crate::ThreadSafeRepository::open_from_paths(
+            git_dir,
+            Some(work_dir),
+            Default::default(),
+            self.options.clone(),
+        )?
+        .into();
+        let (mut index, _files_removed, _outcome) = repo.checkout_tree(None, tree_id, true)?;
+        index.write(Default::default())?;
+
+        match &options.lock {
+            Some(reason) => std::fs::write(&locked, reason).map_err(io_error(&locked))?,
+            None => std::fs::remove_file(&locked).map_err(io_error(&locked))?,
+        }
+        Ok(repo)
+    }
+
+    /// Return the worktree base of the main or a linked worktree that has `branch` checked out, if there is one.
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    fn worktree_with_branch(
+        &self,
+        branch: &gix_ref::FullName,
+    ) -> Result<Option<std::path::PathBuf>, worktree::add::Error> {
+        let main = self.main_repo()?;
+        if let Some(work_dir) = main.work_dir() {
+            if main.head_name()?.as_ref() == Some(branch) {
+                return Ok(Some(work_dir.to_owned()));
+            }
+        }
+        for proxy in self.worktrees()? {
+            let base = proxy.base().unwrap_or_else(|_| proxy.git_dir().to_owned());
+            let repo = proxy.into_repo_with_possibly_inaccessible_worktree()?;
+            if repo.head_name()?.as_ref() == Some(branch) {
+                return Ok(Some(base));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Remove the administrative files of linked worktrees that are invalid or whose checkout doesn't exist anymore,
+    /// similar to `git worktree prune`, and return the pruned worktrees.
+    ///
+    /// Locked worktrees are never pruned unless their administrative directory is invalid.
+    pub fn worktree_prune(
+        &self,
+        options: worktree::prune::Options,
+    ) -> Result<worktree::prune::Outcome, worktree::prune::Error> {
+        use worktree::prune::{Entry, Error, Outcome, Reason};
+
+        let mut out = Outcome::default();
+        let worktrees_dir = self.common_dir().join("worktrees");
+        let iter = match std::fs::read_dir(&worktrees_dir) {
+            Ok(iter) => iter,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(out),
+            Err(err) => {
+                return Err(Error::ReadDir {
+                    path: worktrees_dir,
+                    source: err,
+                })
+            }
+        };
+        let prune_reason = |git_dir: &std::path::Path| -> Option<Reason> {
+            if !git_dir.is_dir() {
+                return Some(Reason::NotADirectory);
+            }
+            if git_dir.join("locked").exists() {
+                return None;
+            }
+            let gitdir_file = git_dir.join("gitdir");
+            if !gitdir_file.exists() {
+                return Some(Reason::GitDirFileMissing);
+            }
+            let Some(Ok(dot_git)) = gix_discover::path::from_plain_file(&gitdir_file) else {
+                return Some(Reason::GitDirFileInvalid);
+            };
+            if dot_git.as_os_str().is_empty() {
+                return Some(Reason::GitDirFileInvalid);
+            }
+            if git_dir.join(dot_git).exists() {
+                return None;
+            }
+            let expired = options.expire.map_or(true, |expire| {
+                std::fs::metadata(git_dir.join("index"))
+                    .and_then(|meta| meta.modified())
+                    .map_or(true, |modified| modified <= expire)
+            });
+            expired.then_some(Reason::WorktreeMissing)
+        };
+        for entry in iter {
+            let entry = entry.map_err(|err| Error::ReadDir {
+                path: worktrees_dir.clone(),
+                source: err,
+            })?;
+            let git_dir = entry.path();
+            let Some(reason) = prune_reason(&git_dir) else {
+                continue;
+            };
+            if !options.dry_run {
+                if git_dir.is_dir() {
+                    std::fs::remove_dir_all(&git_dir)
+                } else {
+                    std::fs::remove_file(&git_dir)
+                }
+                .map_err(|err| Error::Remove {
+                    path: git_dir.clone(),
+                    source: err,
+                })?;
+            }
+            out.entries.push(Entry {
+                id: gix_path::os_str_into_bstr(&entry.file_name())
+                    .expect("no illformed UTF-8")
+                    .to_owned(),
+                reason,
+            });
+        }
+        if !options.dry_run {
+            // Like `git`, remove the directory once the last worktree is gone, which fails harmlessly if there are more.
+            std::fs::remove_dir(&worktrees_dir).ok();
+        }
+        out.entries.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(out)
+    }
+
     /// Return the currently set worktree if there is one, acting as platform providing a validated worktree base path.
     ///
     /// Note that there would be `None` if this repository is `bare` and the parent [`Repository`][crate::Repository] was instantiated without
@@ -56,6 +312,12 @@ impl crate::Repository {
     ///
     /// The entries will look exactly like they would if one would check them out, with filters applied.
     /// The `export-ignore` attribute is used to skip blobs or directories to which it applies.
+    ///
+    /// If `id` points to a commit, or to a tag that peels to a commit, its tree is used instead and `$Format:<format>$`
+    /// placeholders in files with the `export-subst` attribute are replaced with information about the commit, like `git archive` does.
+    /// The supported placeholders are `%H`, `%h`, `%T`, `%t`, `%P`, `%p`, `%an`, `%ae`, `%ad`, `%aD`, `%at`, `%ai`, `%aI`, `%as`,
+    /// their committer counterparts starting with `%c`, as well as `%s`, `%b`, `%B`, `%n` and `%%`.
+    /// Unknown placeholders are left as is.
     #[cfg(feature = "worktree-stream")]
     #[gix_macros::momo]
     pub fn worktree_stream(
@@ -65,12 +327,21 @@ impl crate::Repository {
         use gix_odb::HeaderExt;
         let id = id.into();
         let header = self.objects.header(id)?;
-        if !header.kind().is_tree() {
-            return Err(crate::repository::worktree_stream::Error::NotATree {
-                id,
-                actual: header.kind(),
-            });
-        }
+        let (id, substitute) = match header.kind() {
+            gix_object::Kind::Tree => (id, None),
+            gix_object::Kind::Commit | gix_object::Kind::Tag => {
+                let object = self.find_object(id)?.peel_tags_to_end()?;
+                match object.kind {
+                    gix_object::Kind::Tree => (object.id, None),
+                    gix_object::Kind::Commit => {
+                        let commit = object.into_commit();
+                        (commit.tree_id()?.detach(), Some(export_subst::Commit::new(&commit)?))
+                    }
+                    actual => return Err(crate::repository::worktree_stream::Error::NotATree { id, actual }),
+                }
+            }
+            actual => return Err(crate::repository::worktree_stream::Error::NotATree { id, actual }),
+        };
 
         // TODO(perf): potential performance improvements could be to use the index at `HEAD` if possible (`index_from_head_tree…()`)
         // TODO(perf): when loading a non-HEAD tree, we effectively traverse the tree twice. This is usually fast though, and sharing
@@ -81,16 +352,27 @@ impl crate::Repository {
             .detach();
         let pipeline = gix_filter::Pipeline::new(self.command_context()?, crate::filter::Pipeline::options(self)?);
         let objects = self.objects.clone().into_arc().expect("TBD error handling");
-        let stream = gix_worktree_stream::from_tree(
-            id,
-            objects.clone(),
-            pipeline,
-            move |path, mode, attrs| -> std::io::Result<()> {
+        let attributes = {
+            let objects = objects.clone();
+            move |path: &crate::bstr::BStr,
+                  mode: gix_object::tree::EntryMode,
+                  attrs: &mut gix_attributes::search::Outcome|
+                  -> std::io::Result<()> {
                 let entry = cache.at_entry(path, Some(mode.into()), &objects)?;
                 entry.matching_attributes(attrs);
                 Ok(())
-            },
-        );
+            }
+        };
+        let stream = match substitute {
+            Some(commit) => gix_worktree_stream::from_tree_with_export_subst(
+                id,
+                objects,
+                pipeline,
+                attributes,
+                move |format, out| commit.expand(format, out),
+            ),
+            None => gix_worktree_stream::from_tree(id, objects, pipeline, attributes),
+        };
         Ok((stream, index))
     }
 
@@ -139,3 +421,117 @@ impl crate::Repository {
         Ok(())
     }
 }
+
+#[cfg(feature = "worktree-stream")]
+mod export_subst {
+    use crate::{
+        bstr::{BStr, BString, ByteSlice, ByteVec},
+        ext::ObjectIdExt,
+    };
+
+    /// All information about a commit that `export-subst` placeholders may refer to.
+    pub(super) struct Commit {
+        id: gix_hash::ObjectId,
+        short_id: String,
+        tree: gix_hash::ObjectId,
+        short_tree: String,
+        parents: Vec<(gix_hash::ObjectId, String)>,
+        author: gix_actor::Signature,
+        committer: gix_actor::Signature,
+        message: BString,
+    }
+
+    impl Commit {
+        pub(super) fn new(commit: &crate::Commit<'_>) -> Result<Self, crate::repository::worktree_stream::Error> {
+            let repo = commit.repo;
+            let decoded = commit.decode()?;
+            let tree = decoded.tree();
+            Ok(Commit {
+                id: commit.id,
+                short_id: commit.id().shorten_or_id().to_string(),
+                tree,
+                short_tree: tree.attach(repo).shorten_or_id().to_string(),
+                parents: decoded
+                    .parents()
+                    .map(|id| (id, id.attach(repo).shorten_or_id().to_string()))
+                    .collect(),
+                author: decoded.author.to_owned(),
+                committer: decoded.committer.to_owned(),
+                message: decoded.message.to_owned(),
+            })
+        }
+
+        /// Append the expansion of `format` to `out`, similar to what `git log --pretty=format:<format>` would produce.
+        pub(super) fn expand(&self, format: &BStr, out: &mut Vec<u8>) {
+            let mut rest = format.as_bytes();
+            while let Some(pos) = rest.find_byte(b'%') {
+                out.extend_from_slice(&rest[..pos]);
+                rest = &rest[pos..];
+                let consumed = self.expand_placeholder(&rest[1..], out);
+                if consumed == 0 {
+                    out.push(b'%');
+                }
+                rest = &rest[1 + consumed..];
+            }
+            out.extend_from_slice(rest);
+        }
+
+        /// Expand the placeholder at the beginning of `spec` into `out`, and return the amount of bytes it consumed,
+        /// or `0` if it wasn't understood.
+        fn expand_placeholder(&self, spec: &[u8], out: &mut Vec<u8>) -> usize {
+            let message = gix_object::commit::MessageRef::from_bytes(&self.message);
+            match spec {
+                [b'%', ..] => out.push(b'%'),
+                [b'n', ..] => out.push(b'\n'),
+                [b'H', ..] => out.push_str(self.id.to_string()),
+                [b'h', ..] => out.push_str(&self.short_id),
+                [b'T', ..] => out.push_str(self.tree.to_string()),
+                [b't', ..] => out.push_str(&self.short_tree),
+                [b'P', ..] => join(out, self.parents.iter().map(|(id, _)| id.to_string())),
+                [b'p', ..] => join(out, self.parents.iter().map(|(_, short)| short.clone())),
+                [b's', ..] => out.extend_from_slice(message.summary().as_ref()),
+                [b'b', ..] => {
+                    if let Some(body) = message.body {
+                        out.extend_from_slice(body);
+                        if !body.ends_with(b"\n") {
+                            out.push(b'\n');
+                        }
+                    }
+                }
+                [b'B', ..] => out.extend_from_slice(&self.message),
+                [who @ (b'a' | b'c'), what, ..] => {
+                    let signature = if *who == b'a' { &self.author } else { &self.committer };
+                    return if expand_signature(signature, *what, out) { 2 } else { 0 };
+                }
+                _ => return 0,
+            }
+            1
+        }
+    }
+
+    fn join(out: &mut Vec<u8>, items: impl Iterator<Item = String>) {
+        for (idx, item) in items.enumerate() {
+            if idx != 0 {
+                out.push(b' ');
+            }
+            out.push_str(item);
+        }
+    }
+
+    fn expand_signature(signature: &gix_actor::Signature, what: u8, out: &mut Vec<u8>) -> bool {
+        use gix_date::time::format;
+        let time = &signature.time;
+        match what {
+            b'n' => out.extend_from_slice(&signature.name),
+            b'e' => out.extend_from_slice(&signature.email),
+            b'd' => out.push_str(time.format(format::DEFAULT)),
+            b'D' => out.push_str(time.format(format::GIT_RFC2822)),
+            b't' => out.push_str(time.format(format::UNIX)),
+            b'i' => out.push_str(time.format(format::ISO8601)),
+            b'I' => out.push_str(time.format(format::ISO8601_STRICT)),
+            b's' => out.push_str(time.format(format::SHORT)),
+            _ => return false,
+        }
+        true
+    }
+}
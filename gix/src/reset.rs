@@ -0,0 +1,77 @@
+//! Types for use with [`Repository::reset()`](crate::Repository::reset()).
+
+/// Determine how far-reaching a reset is.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Only move `HEAD` (or the branch it points to), leaving the index and the worktree untouched, like `git reset --soft`.
+    Soft,
+    /// Move `HEAD` and make the index match the target tree, leaving the worktree untouched, like `git reset --mixed`.
+    #[default]
+    Mixed,
+    /// Move `HEAD` and make the index and worktree match the target tree, discarding all local changes to tracked files,
+    /// like `git reset --hard`.
+    Hard,
+}
+
+/// Options for use with [`Repository::reset()`](crate::Repository::reset()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The kind of reset to perform.
+    pub mode: Mode,
+    /// If `true`, default `false`, allow the reset even though a merge is in progress or the index has unresolved conflicts.
+    ///
+    /// For [`Mode::Mixed`] and [`Mode::Hard`], this will also remove the state of the unfinished merge, cherry-pick or revert.
+    pub allow_unfinished_merge: bool,
+}
+
+impl From<Mode> for Options {
+    fn from(mode: Mode) -> Self {
+        Options {
+            mode,
+            ..Default::default()
+        }
+    }
+}
+
+/// The outcome of [`Repository::reset()`](crate::Repository::reset()).
+#[derive(Debug)]
+pub struct Outcome {
+    /// The commit `HEAD` pointed to before the reset, which is now also stored in `ORIG_HEAD`, or `None` if `HEAD` was unborn.
+    pub previous_head_id: Option<gix_hash::ObjectId>,
+    /// The commit `HEAD` points to now.
+    pub head_id: gix_hash::ObjectId,
+    /// The amount of files removed from the worktree, only non-zero for [`Mode::Hard`].
+    pub files_removed: usize,
+    /// The outcome of writing changed files to the worktree, only set for [`Mode::Hard`].
+    pub checkout: Option<gix_worktree_state::checkout::Outcome>,
+}
+
+/// The error returned by [`Repository::reset()`](crate::Repository::reset()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot reset in the middle of a merge, it has to be concluded or aborted first")]
+    UnfinishedMerge,
+    #[error(transparent)]
+    ParseTarget(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    ApplyState(#[from] crate::state::apply::Error),
+}
@@ -0,0 +1,81 @@
+//! Types for use with [`Repository::rev_list()`](crate::Repository::rev_list()).
+use std::io::Write;
+
+use gix_hash::ObjectId;
+
+use crate::bstr::{BString, ByteSlice};
+
+/// Options for use with [`Repository::rev_list()`](crate::Repository::rev_list()).
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, list the trees, blobs and tags reachable from the listed commits and the tips as well, like
+    /// `git rev-list --objects`.
+    pub objects: bool,
+    /// If `true`, list the hidden parents of listed commits as [boundary](Item::Boundary), like `git rev-list --boundary`.
+    pub boundary: bool,
+}
+
+/// An item listed by [`Repository::rev_list()`](crate::Repository::rev_list()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Item {
+    /// A commit reachable from the tips, but not from the hidden tips.
+    Commit(ObjectId),
+    /// A hidden commit which is the parent of a listed commit.
+    Boundary(ObjectId),
+    /// A tree, blob or tag that isn't reachable from hidden tips or boundary commits.
+    Object {
+        /// The id of the object.
+        id: ObjectId,
+        /// The kind of the object, which is never a commit.
+        kind: gix_object::Kind,
+        /// The path at which the object was first discovered, relative to the root tree of the commit it was discovered in.
+        /// It's empty for root trees and for objects that were passed as tips.
+        path: BString,
+    },
+}
+
+impl Item {
+    /// Return the id of the listed object.
+    pub fn id(&self) -> &ObjectId {
+        match self {
+            Item::Commit(id) | Item::Boundary(id) | Item::Object { id, .. } => id,
+        }
+    }
+}
+
+/// The result of [`Repository::rev_list()`](crate::Repository::rev_list()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All commits, newest first, followed by all boundary commits and all other objects, in the order in which
+    /// they were discovered.
+    pub items: Vec<Item>,
+}
+
+impl Outcome {
+    /// Write all items to `out` like `git rev-list --objects --boundary` does, with one object per line,
+    /// boundary commits prefixed with `-` and other objects followed by a space and their path.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for item in &self.items {
+            match item {
+                Item::Commit(id) => writeln!(out, "{id}")?,
+                Item::Boundary(id) => writeln!(out, "-{id}")?,
+                Item::Object { id, path, .. } => writeln!(out, "{id} {}", path.as_bstr())?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::rev_list()`](crate::Repository::rev_list()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    WalkItem(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+}
@@ -19,7 +19,7 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         if !self.err.is_empty() && self.refs[self.idx].is_some() {
             return None;
         }
-        match self.repo.refs.find(name) {
+        match self.repo.find_reference_inner(name) {
             Ok(r) => {
                 assert!(self.refs[self.idx].is_none(), "BUG: cannot set the same ref twice");
                 self.refs[self.idx] = Some(r);
@@ -32,11 +32,7 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
         }
     }
 
-    fn disambiguate_prefix(
-        &mut self,
-        prefix: gix_hash::Prefix,
-        _must_be_commit: Option<delegate::PrefixHint<'_>>,
-    ) -> Option<()> {
+    fn disambiguate_prefix(&mut self, prefix: gix_hash::Prefix, hint: Option<delegate::PrefixHint<'_>>) -> Option<()> {
         self.last_call_was_disambiguate_prefix[self.idx] = true;
         let mut candidates = Some(HashSet::default());
         self.prefix[self.idx] = Some(prefix);
@@ -60,7 +56,23 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
             }
             Ok(Some(Ok(_) | Err(()))) => {
                 assert!(self.objs[self.idx].is_none(), "BUG: cannot set the same prefix twice");
-                let candidates = candidates.expect("set above");
+                let mut candidates = candidates.expect("set above");
+                if hint.is_some() && candidates.len() > 1 {
+                    // Prefixes from `git describe` output always refer to commits, which may be the only one among the candidates.
+                    let commits: HashSet<_> = candidates
+                        .iter()
+                        .filter(|id| {
+                            matches!(
+                                self.repo.find_header(**id).map(|header| header.kind()),
+                                Ok(gix_object::Kind::Commit)
+                            )
+                        })
+                        .copied()
+                        .collect();
+                    if !commits.is_empty() {
+                        candidates = commits;
+                    }
+                }
                 match self.opts.refs_hint {
                     RefsHint::PreferObjectOnFullLengthHexShaUseRefOtherwise
                         if prefix.hex_len() == candidates.iter().next().expect("at least one").kind().len_in_hex() =>
@@ -75,7 +87,7 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
                         Some(())
                     }
                     RefsHint::PreferRef | RefsHint::PreferObjectOnFullLengthHexShaUseRefOtherwise | RefsHint::Fail => {
-                        match self.repo.refs.find(&prefix.to_string()) {
+                        match self.repo.find_reference_inner(prefix.to_string().as_str().into()) {
                             Ok(ref_) => {
                                 assert!(self.refs[self.idx].is_none(), "BUG: cannot set the same ref twice");
                                 if self.opts.refs_hint == RefsHint::Fail {
@@ -105,60 +117,93 @@ impl<'repo> delegate::Revision for Delegate<'repo> {
 
     fn reflog(&mut self, query: ReflogLookup) -> Option<()> {
         self.unset_disambiguate_call();
+        let r = match &mut self.refs[self.idx] {
+            Some(r) => r.clone().attach(self.repo),
+            val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
+                Ok(Some(r)) => {
+                    *val = Some(r.clone().detach());
+                    r
+                }
+                Ok(None) => {
+                    self.err.push(Error::UnbornHeadsHaveNoRefLog);
+                    return None;
+                }
+                Err(err) => {
+                    self.err.push(err.into());
+                    return None;
+                }
+            },
+        };
+        let mut platform = r.log_iter();
         match query {
-            ReflogLookup::Date(_date) => {
-                // TODO: actually do this - this should be possible now despite incomplete date parsing
-                self.err.push(Error::Planned {
-                    dependency: "remote handling and ref-specs are fleshed out more",
-                });
-                None
-            }
-            ReflogLookup::Entry(no) => {
-                let r = match &mut self.refs[self.idx] {
-                    Some(r) => r.clone().attach(self.repo),
-                    val @ None => match self.repo.head().map(crate::Head::try_into_referent) {
-                        Ok(Some(r)) => {
-                            *val = Some(r.clone().detach());
-                            r
-                        }
-                        Ok(None) => {
-                            self.err.push(Error::UnbornHeadsHaveNoRefLog);
-                            return None;
-                        }
-                        Err(err) => {
-                            self.err.push(err.into());
-                            return None;
-                        }
-                    },
+            ReflogLookup::Date(date) => {
+                let mut it = match platform.rev().ok().flatten() {
+                    Some(it) => it,
+                    None => {
+                        self.err.push(Error::MissingRefLog {
+                            reference: r.name().as_bstr().into(),
+                            action: "lookup entry by date",
+                        });
+                        return None;
+                    }
                 };
-                let mut platform = r.log_iter();
-                match platform.rev().ok().flatten() {
-                    Some(mut it) => match it.nth(no).and_then(Result::ok) {
-                        Some(line) => {
-                            self.objs[self.idx]
-                                .get_or_insert_with(HashSet::default)
-                                .insert(line.new_oid);
-                            Some(())
-                        }
-                        None => {
-                            let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
-                            self.err.push(Error::RefLogEntryOutOfRange {
-                                reference: r.detach(),
-                                desired: no,
-                                available,
-                            });
-                            None
+                // Like git, use the most recent entry that isn't newer than `date`, or the state before
+                // the oldest entry if all of them are newer.
+                let mut oldest = None;
+                let id = loop {
+                    match it.next() {
+                        Some(Ok(line)) if line.signature.time.seconds <= date.seconds => break Some(line.new_oid),
+                        Some(Ok(line)) => oldest = Some(line),
+                        Some(Err(_)) | None => {
+                            break oldest.map(|line| {
+                                if line.previous_oid.is_null() {
+                                    line.new_oid
+                                } else {
+                                    line.previous_oid
+                                }
+                            })
                         }
-                    },
+                    }
+                };
+                match id {
+                    Some(id) => {
+                        self.objs[self.idx].get_or_insert_with(HashSet::default).insert(id);
+                        Some(())
+                    }
                     None => {
-                        self.err.push(Error::MissingRefLog {
+                        self.err.push(Error::EmptyRefLog {
                             reference: r.name().as_bstr().into(),
-                            action: "lookup entry",
                         });
                         None
                     }
                 }
             }
+            ReflogLookup::Entry(no) => match platform.rev().ok().flatten() {
+                Some(mut it) => match it.nth(no).and_then(Result::ok) {
+                    Some(line) => {
+                        self.objs[self.idx]
+                            .get_or_insert_with(HashSet::default)
+                            .insert(line.new_oid);
+                        Some(())
+                    }
+                    None => {
+                        let available = platform.rev().ok().flatten().map_or(0, Iterator::count);
+                        self.err.push(Error::RefLogEntryOutOfRange {
+                            reference: r.detach(),
+                            desired: no,
+                            available,
+                        });
+                        None
+                    }
+                },
+                None => {
+                    self.err.push(Error::MissingRefLog {
+                        reference: r.name().as_bstr().into(),
+                        action: "lookup entry",
+                    });
+                    None
+                }
+            },
         }
     }
 
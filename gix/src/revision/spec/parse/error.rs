@@ -45,8 +45,13 @@ impl std::fmt::Display for CandidateInfo {
     }
 }
 
-impl Error {
-    pub(crate) fn ambiguous(candidates: HashSet<ObjectId>, prefix: gix_hash::Prefix, repo: &Repository) -> Self {
+impl CandidateInfo {
+    /// Obtain information about each of the `candidates` ids, sorted by kind (tags first) and id, along with the shortest
+    /// unambiguous prefix of each.
+    pub(crate) fn sorted_from_ids(
+        candidates: impl IntoIterator<Item = ObjectId>,
+        repo: &Repository,
+    ) -> Vec<(gix_hash::Prefix, CandidateInfo)> {
         #[derive(PartialOrd, Ord, Eq, PartialEq, Copy, Clone)]
         enum Order {
             Tag,
@@ -75,32 +80,38 @@ impl Error {
             c.sort_by(|lhs, rhs| lhs.2.cmp(&rhs.2).then_with(|| lhs.0.cmp(&rhs.0)));
             c
         };
+        candidates
+            .into_iter()
+            .map(|(oid, find_result, _)| {
+                let info = match find_result {
+                    Ok(obj) => match obj.kind {
+                        gix_object::Kind::Tree | gix_object::Kind::Blob => CandidateInfo::Object { kind: obj.kind },
+                        gix_object::Kind::Tag => {
+                            let tag = obj.to_tag_ref();
+                            CandidateInfo::Tag { name: tag.name.into() }
+                        }
+                        gix_object::Kind::Commit => {
+                            use bstr::ByteSlice;
+                            let commit = obj.to_commit_ref();
+                            CandidateInfo::Commit {
+                                date: commit.committer().time,
+                                title: commit.message().title.trim().into(),
+                            }
+                        }
+                    },
+                    Err(err) => CandidateInfo::FindError { source: err },
+                };
+                (oid.attach(repo).shorten().unwrap_or_else(|_| oid.into()), info)
+            })
+            .collect()
+    }
+}
+
+impl Error {
+    pub(crate) fn ambiguous(candidates: HashSet<ObjectId>, prefix: gix_hash::Prefix, repo: &Repository) -> Self {
         Error::AmbiguousPrefix {
             prefix,
-            info: candidates
-                .into_iter()
-                .map(|(oid, find_result, _)| {
-                    let info = match find_result {
-                        Ok(obj) => match obj.kind {
-                            gix_object::Kind::Tree | gix_object::Kind::Blob => CandidateInfo::Object { kind: obj.kind },
-                            gix_object::Kind::Tag => {
-                                let tag = obj.to_tag_ref();
-                                CandidateInfo::Tag { name: tag.name.into() }
-                            }
-                            gix_object::Kind::Commit => {
-                                use bstr::ByteSlice;
-                                let commit = obj.to_commit_ref();
-                                CandidateInfo::Commit {
-                                    date: commit.committer().time,
-                                    title: commit.message().title.trim().into(),
-                                }
-                            }
-                        },
-                        Err(err) => CandidateInfo::FindError { source: err },
-                    };
-                    (oid.attach(repo).shorten().unwrap_or_else(|_| oid.into()), info)
-                })
-                .collect(),
+            info: CandidateInfo::sorted_from_ids(candidates, repo),
         }
     }
 
@@ -1,3 +1,5 @@
+use std::rc::Rc;
+
 use gix_hash::ObjectId;
 use gix_object::FindExt;
 
@@ -23,8 +25,11 @@ pub struct Info<'repo> {
     /// All parent ids we have encountered. Note that these will be at most one if [`Parents::First`][gix_traverse::commit::Parents::First] is enabled.
     pub parent_ids: gix_traverse::commit::ParentIds,
     /// The time at which the commit was created. It will only be `Some(_)` if the chosen traversal was
-    /// taking dates into consideration.
+    /// taking dates into consideration, or if this is a boundary commit.
     pub commit_time: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// If `true`, this commit is reachable from a [hidden tip](Platform::hide()) and a parent of a commit that was returned earlier.
+    /// These are only returned, after all other commits, if [boundary commits](Platform::boundary()) are requested.
+    pub is_boundary: bool,
 
     repo: &'repo Repository,
 }
@@ -67,6 +72,7 @@ impl<'repo> Info<'repo> {
             id: info.id,
             parent_ids: info.parent_ids,
             commit_time: info.commit_time,
+            is_boundary: false,
             repo,
         }
     }
@@ -95,6 +101,10 @@ pub struct Platform<'repo> {
     pub(crate) parents: gix_traverse::commit::Parents,
     pub(crate) use_commit_graph: Option<bool>,
     pub(crate) commit_graph: Option<gix_commitgraph::Graph>,
+    pub(crate) hidden: Vec<ObjectId>,
+    pub(crate) boundary: bool,
+    #[cfg(feature = "blob-diff")]
+    pub(crate) pathspec: Option<crate::Pathspec<'repo>>,
 }
 
 impl<'repo> Platform<'repo> {
@@ -106,6 +116,10 @@ impl<'repo> Platform<'repo> {
             parents: Default::default(),
             use_commit_graph: None,
             commit_graph: None,
+            hidden: Vec::new(),
+            boundary: false,
+            #[cfg(feature = "blob-diff")]
+            pathspec: None,
         }
     }
 }
@@ -143,6 +157,33 @@ impl<'repo> Platform<'repo> {
         self.commit_graph = graph;
         self
     }
+
+    /// Do not return or traverse commits that are reachable from any of the given `tips`, similar to `git rev-list <tips> ^<hidden>`,
+    /// which allows to walk ranges like `a..b`. All of `tips` must be commits.
+    ///
+    /// Note that all commits reachable from hidden tips are collected before the iteration starts.
+    pub fn hide(mut self, tips: impl IntoIterator<Item = impl Into<ObjectId>>) -> Self {
+        self.hidden.extend(tips.into_iter().map(Into::into));
+        self
+    }
+
+    /// If `toggle` is `true`, return all [hidden](Platform::hide()) commits which are parents of returned commits after
+    /// all other commits, similar to `git rev-list --boundary`. These are marked with [`Info::is_boundary`].
+    pub fn boundary(mut self, toggle: bool) -> Self {
+        self.boundary = toggle;
+        self
+    }
+
+    /// Only return commits that change paths matching `pathspec` compared to at least one of their parents, or compared to the
+    /// empty tree if they have no parents, similar to `git rev-list --full-history <tips> -- <pathspec>`.
+    /// Note that all parents are still traversed, no matter whether or not their commit was returned.
+    ///
+    /// Parents ignored due to [`first_parent_only()`](Platform::first_parent_only()) are also ignored when comparing trees.
+    #[cfg(feature = "blob-diff")]
+    pub fn with_pathspec(mut self, pathspec: crate::Pathspec<'repo>) -> Self {
+        self.pathspec = Some(pathspec);
+        self
+    }
 }
 
 /// Produce the iterator
@@ -162,9 +203,37 @@ impl<'repo> Platform<'repo> {
             parents,
             use_commit_graph,
             commit_graph,
+            hidden,
+            boundary,
+            #[cfg(feature = "blob-diff")]
+            pathspec,
         } = self;
+        let hidden = if hidden.is_empty() {
+            None
+        } else {
+            let mut commits = gix_hashtable::HashSet::default();
+            for info in Platform::new(hidden, repo).use_commit_graph(use_commit_graph).all()? {
+                commits.insert(info?.id);
+            }
+            Some(Rc::new(commits))
+        };
         Ok(revision::Walk {
             repo,
+            boundary: boundary.then(|| iter::Boundary {
+                hidden: hidden.clone().unwrap_or_default(),
+                first_parent_only: matches!(parents, gix_traverse::commit::Parents::First),
+                seen: Default::default(),
+                pending: Default::default(),
+                exhausted: false,
+            }),
+            #[cfg(feature = "blob-diff")]
+            pathspec: pathspec.map(|pathspec| iter::PathspecFilter {
+                pathspec,
+                state: Default::default(),
+                buf: Vec::new(),
+                tree_buf: Vec::new(),
+                parent_tree_buf: Vec::new(),
+            }),
             inner: Box::new(
                 gix_traverse::commit::Simple::filtered(tips, &repo.objects, {
                     // Note that specific shallow handling for commit-graphs isn't needed as these contain
@@ -173,7 +242,7 @@ impl<'repo> Platform<'repo> {
                     let mut grafted_parents_to_skip = Vec::new();
                     let mut buf = Vec::new();
                     move |id| {
-                        if !filter(id) {
+                        if hidden.as_ref().is_some_and(|hidden| hidden.contains(id)) || !filter(id) {
                             return false;
                         }
                         match shallow_commits.as_ref() {
@@ -218,20 +287,186 @@ impl<'repo> Platform<'repo> {
 }
 
 pub(crate) mod iter {
-    /// The iterator returned by [`crate::revision::walk::Platform::all()`].
+    use std::{collections::VecDeque, rc::Rc};
+
+    use gix_hash::ObjectId;
+    use gix_object::FindExt;
+    use gix_traverse::commit::simple;
+
+    /// The iterator returned by [`crate::revision::walk::Platform::selected()`] and [`crate::revision::walk::Platform::all()`].
     pub struct Walk<'repo> {
         pub(crate) repo: &'repo crate::Repository,
-        pub(crate) inner:
-            Box<dyn Iterator<Item = Result<gix_traverse::commit::Info, gix_traverse::commit::simple::Error>> + 'repo>,
+        pub(crate) inner: Box<dyn Iterator<Item = Result<gix_traverse::commit::Info, simple::Error>> + 'repo>,
+        pub(crate) boundary: Option<Boundary>,
+        #[cfg(feature = "blob-diff")]
+        pub(crate) pathspec: Option<PathspecFilter<'repo>>,
+    }
+
+    /// The state needed to return boundary commits once the traversal is done.
+    pub(crate) struct Boundary {
+        /// All commits reachable from hidden tips.
+        pub(crate) hidden: Rc<gix_hashtable::HashSet>,
+        pub(crate) first_parent_only: bool,
+        /// Boundary commits we already queued.
+        pub(crate) seen: gix_hashtable::HashSet,
+        pub(crate) pending: VecDeque<ObjectId>,
+        /// If `true`, the traversal is done and boundary commits are returned.
+        pub(crate) exhausted: bool,
+    }
+
+    /// The state needed to check if commits change paths matching a pathspec.
+    #[cfg(feature = "blob-diff")]
+    pub(crate) struct PathspecFilter<'repo> {
+        pub(crate) pathspec: crate::Pathspec<'repo>,
+        pub(crate) state: gix_diff::tree::State,
+        pub(crate) buf: Vec<u8>,
+        pub(crate) tree_buf: Vec<u8>,
+        pub(crate) parent_tree_buf: Vec<u8>,
+    }
+
+    impl<'repo> Walk<'repo> {
+        fn next_boundary(&mut self) -> Option<Result<super::Info<'repo>, simple::Error>> {
+            let boundary = self.boundary.as_mut()?;
+            boundary.exhausted = true;
+            let id = boundary.pending.pop_front()?;
+            let mut buf = Vec::new();
+            let res = (|| {
+                let commit = self.repo.objects.find_commit_iter(&id, &mut buf)?;
+                let parent_ids = commit
+                    .parent_ids()
+                    .take(if boundary.first_parent_only { 1 } else { usize::MAX })
+                    .collect();
+                let commit_time = commit.committer()?.time.seconds;
+                Ok(super::Info {
+                    id,
+                    parent_ids,
+                    commit_time: Some(commit_time),
+                    is_boundary: true,
+                    repo: self.repo,
+                })
+            })();
+            Some(res)
+        }
+    }
+
+    #[cfg(feature = "blob-diff")]
+    impl<'repo> PathspecFilter<'repo> {
+        /// Return `true` if the commit described by `info` changes at least one path matching our pathspec compared to
+        /// any of its parents, or compared to the empty tree if it has none.
+        fn is_interesting(
+            &mut self,
+            info: &gix_traverse::commit::Info,
+            objects: &crate::OdbHandle,
+        ) -> Result<bool, simple::Error> {
+            let tree_id = objects.find_commit_iter(&info.id, &mut self.buf)?.tree_id()?;
+            if info.parent_ids.is_empty() {
+                return self.changes_paths(None, tree_id, objects);
+            }
+            for parent_id in &info.parent_ids {
+                let parent_tree_id = objects.find_commit_iter(parent_id, &mut self.buf)?.tree_id()?;
+                if self.changes_paths(Some(parent_tree_id), tree_id, objects)? {
+                    return Ok(true);
+                }
+            }
+            Ok(false)
+        }
+
+        fn changes_paths(
+            &mut self,
+            parent_tree_id: Option<ObjectId>,
+            tree_id: ObjectId,
+            objects: &crate::OdbHandle,
+        ) -> Result<bool, simple::Error> {
+            let parent_tree = parent_tree_id
+                .map(|id| objects.find_tree_iter(&id, &mut self.parent_tree_buf))
+                .transpose()?;
+            let tree = objects.find_tree_iter(&tree_id, &mut self.tree_buf)?;
+            let mut delegate = PathspecDelegate {
+                recorder: Default::default(),
+                pathspec: &mut self.pathspec,
+                changed: false,
+            };
+            match gix_diff::tree::Changes::from(parent_tree).needed_to_obtain(
+                tree,
+                &mut self.state,
+                objects,
+                &mut delegate,
+            ) {
+                Ok(()) => Ok(false),
+                // Cancelling the diff is how we stop at the first change of interest.
+                Err(gix_diff::tree::changes::Error::Cancelled) => Ok(delegate.changed),
+                Err(gix_diff::tree::changes::Error::Find(err)) => Err(err.into()),
+                Err(gix_diff::tree::changes::Error::EntriesDecode(err)) => Err(err.into()),
+            }
+        }
+    }
+
+    /// Track paths like a recorder, but stop at the first change matching the pathspec instead of recording it.
+    #[cfg(feature = "blob-diff")]
+    struct PathspecDelegate<'a, 'repo> {
+        recorder: gix_diff::tree::Recorder,
+        pathspec: &'a mut crate::Pathspec<'repo>,
+        changed: bool,
+    }
+
+    #[cfg(feature = "blob-diff")]
+    impl gix_diff::tree::Visit for PathspecDelegate<'_, '_> {
+        fn pop_front_tracked_path_and_set_current(&mut self) {
+            self.recorder.pop_front_tracked_path_and_set_current();
+        }
+
+        fn push_back_tracked_path_component(&mut self, component: &gix_object::bstr::BStr) {
+            self.recorder.push_back_tracked_path_component(component);
+        }
+
+        fn push_path_component(&mut self, component: &gix_object::bstr::BStr) {
+            self.recorder.push_path_component(component);
+        }
+
+        fn pop_path_component(&mut self) {
+            self.recorder.pop_path_component();
+        }
+
+        fn visit(&mut self, change: gix_diff::tree::visit::Change) -> gix_diff::tree::visit::Action {
+            if !change.entry_mode().is_tree() && self.pathspec.is_included(self.recorder.path(), Some(false)) {
+                self.changed = true;
+                gix_diff::tree::visit::Action::Cancel
+            } else {
+                gix_diff::tree::visit::Action::Continue
+            }
+        }
     }
 
     impl<'repo> Iterator for Walk<'repo> {
-        type Item = Result<super::Info<'repo>, gix_traverse::commit::simple::Error>;
+        type Item = Result<super::Info<'repo>, simple::Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
-            self.inner
-                .next()
-                .map(|res| res.map(|info| super::Info::new(info, self.repo)))
+            if self.boundary.as_ref().is_some_and(|boundary| boundary.exhausted) {
+                return self.next_boundary();
+            }
+            loop {
+                let info = match self.inner.next() {
+                    Some(Ok(info)) => info,
+                    Some(Err(err)) => return Some(Err(err)),
+                    None => return self.next_boundary(),
+                };
+                if let Some(boundary) = self.boundary.as_mut() {
+                    for parent_id in &info.parent_ids {
+                        if boundary.hidden.contains(parent_id) && boundary.seen.insert(*parent_id) {
+                            boundary.pending.push_back(*parent_id);
+                        }
+                    }
+                }
+                #[cfg(feature = "blob-diff")]
+                if let Some(filter) = self.pathspec.as_mut() {
+                    match filter.is_interesting(&info, &self.repo.objects) {
+                        Ok(true) => {}
+                        Ok(false) => continue,
+                        Err(err) => return Some(Err(err)),
+                    }
+                }
+                return Some(Ok(super::Info::new(info, self.repo)));
+            }
         }
     }
 }
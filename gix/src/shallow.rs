@@ -5,14 +5,14 @@ pub(crate) type CommitsStorage =
 pub type Commits = gix_fs::SharedFileSnapshot<Vec<gix_hash::ObjectId>>;
 
 ///
-#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+#[allow(clippy::empty_docs)]
 pub mod write {
     pub(crate) mod function {
         use std::io::Write;
 
-        use gix_protocol::fetch::response::ShallowUpdate;
-
-        use crate::shallow::{write::Error, Commits};
+        use crate::shallow::write::Error;
+        #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+        use crate::shallow::Commits;
 
         /// Write the previously obtained (possibly non-existing) `shallow_commits` to the shallow `file`
         /// after applying all `updates`.
@@ -22,11 +22,14 @@ pub mod write {
         /// ### Deviation
         ///
         /// Git also prunes the set of shallow commits while writing, we don't until we support some sort of pruning.
+        #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
         pub fn write(
-            mut file: gix_lock::File,
+            file: gix_lock::File,
             shallow_commits: Option<Commits>,
-            updates: &[ShallowUpdate],
+            updates: &[gix_protocol::fetch::response::ShallowUpdate],
         ) -> Result<(), Error> {
+            use gix_protocol::fetch::response::ShallowUpdate;
+
             let mut shallow_commits = shallow_commits.map(|sc| (**sc).to_owned()).unwrap_or_default();
             for update in updates {
                 match update {
@@ -36,39 +39,46 @@ pub mod write {
                     ShallowUpdate::Unshallow(id) => shallow_commits.retain(|oid| oid != id),
                 }
             }
-            if shallow_commits.is_empty() {
-                std::fs::remove_file(file.resource_path())?;
-                drop(file);
-                return Ok(());
-            }
+            write_commits(file, shallow_commits)
+        }
 
+        /// Write `shallow_commits` to the locked shallow `file`, or remove it if there are none.
+        pub(crate) fn write_commits(
+            mut file: gix_lock::File,
+            mut shallow_commits: Vec<gix_hash::ObjectId>,
+        ) -> Result<(), Error> {
             if shallow_commits.is_empty() {
                 if let Err(err) = std::fs::remove_file(file.resource_path()) {
                     if err.kind() != std::io::ErrorKind::NotFound {
                         return Err(err.into());
                     }
                 }
-            } else {
-                shallow_commits.sort();
-                let mut buf = Vec::<u8>::new();
-                for commit in shallow_commits {
-                    commit.write_hex_to(&mut buf).map_err(Error::Io)?;
-                    buf.push(b'\n');
-                }
-                file.write_all(&buf).map_err(Error::Io)?;
-                file.flush()?;
+                drop(file);
+                return Ok(());
             }
+
+            shallow_commits.sort();
+            let mut buf = Vec::<u8>::new();
+            for commit in shallow_commits {
+                commit.write_hex_to(&mut buf).map_err(Error::Io)?;
+                buf.push(b'\n');
+            }
+            file.write_all(&buf).map_err(Error::Io)?;
+            file.flush()?;
             file.commit()?;
             Ok(())
         }
     }
 
-    /// The error returned by [`write()`][crate::shallow::write()].
+    /// The error returned by [`write()`][crate::shallow::write()] and
+    /// [`Repository::set_shallow_commits()`][crate::Repository::set_shallow_commits()].
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
         #[error(transparent)]
         Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+        #[error("Could not lock the shallow file for writing")]
+        Lock(#[from] gix_lock::acquire::Error),
         #[error("Could not remove an empty shallow file")]
         RemoveEmpty(#[from] std::io::Error),
         #[error("Failed to write object id to shallow file")]
@@ -78,6 +88,24 @@ pub mod write {
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use write::function::write;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod unshallow {
+    /// The error returned by [`Repository::unshallow()`][crate::Repository::unshallow()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Open(#[from] super::open::Error),
+        #[error(transparent)]
+        FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        Write(#[from] super::write::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod open {
@@ -0,0 +1,131 @@
+//! Types for use with [`Repository::shortlog()`](crate::Repository::shortlog()).
+use std::io::Write;
+
+use crate::bstr::{BString, ByteSlice};
+
+/// Whose identity to group commits by, for use in [`Options`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Group {
+    /// Group commits by their author, the default.
+    #[default]
+    Author,
+    /// Group commits by their committer, like `git shortlog --committer`.
+    Committer,
+}
+
+/// How to order the [entries](Outcome::entries), for use in [`Options`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sort {
+    /// Order entries by name, and by email if it is shown, the default.
+    #[default]
+    Name,
+    /// Order entries by their amount of commits, most commits first, like `git shortlog --numbered`.
+    ///
+    /// Entries with the same amount of commits are ordered by name.
+    Count,
+}
+
+/// Options for use with [`Repository::shortlog()`](crate::Repository::shortlog()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Whose identity to group commits by.
+    pub group: Group,
+    /// If `true`, group commits by name and email instead of only by name, like `git shortlog --email`.
+    pub email: bool,
+    /// How to order the entries.
+    pub sort: Sort,
+    /// If `false`, ignore commits with more than one parent, like `git shortlog --no-merges`. Defaults to `true`.
+    pub merges: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            group: Group::default(),
+            email: false,
+            sort: Sort::default(),
+            merges: true,
+        }
+    }
+}
+
+/// The commits of a single identity, as produced by [`Repository::shortlog()`](crate::Repository::shortlog()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The name of the identity, after applying the mailmap.
+    pub name: BString,
+    /// The email of the identity after applying the mailmap, if [`Options::email`] is set.
+    pub email: Option<BString>,
+    /// The subjects of all commits of this identity, oldest first.
+    pub subjects: Vec<BString>,
+}
+
+impl Entry {
+    /// Return the amount of commits of this identity.
+    pub fn count(&self) -> usize {
+        self.subjects.len()
+    }
+
+    /// Return the identity like `git shortlog` shows it, which is `name` or `name <email>`.
+    pub fn identity(&self) -> BString {
+        let mut out = self.name.clone();
+        if let Some(email) = &self.email {
+            out.extend_from_slice(b" <");
+            out.extend_from_slice(email);
+            out.push(b'>');
+        }
+        out
+    }
+}
+
+/// The result of [`Repository::shortlog()`](crate::Repository::shortlog()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// An entry for each identity, ordered according to [`Options::sort`].
+    pub entries: Vec<Entry>,
+}
+
+impl Outcome {
+    /// Write all entries to `out` like `git shortlog` does, with the identity and amount of commits of each entry followed
+    /// by the indented subjects of its commits.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for entry in &self.entries {
+            writeln!(out, "{} ({}):", entry.identity(), entry.count())?;
+            for subject in &entry.subjects {
+                writeln!(out, "      {}", subject.as_bstr())?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Write the amount of commits and the identity of each entry to `out` like `git shortlog --summary` does.
+    pub fn write_summary_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        for entry in &self.entries {
+            writeln!(out, "{:6}\t{}", entry.count(), entry.identity())?;
+        }
+        Ok(())
+    }
+}
+
+/// The error returned by [`Repository::shortlog()`](crate::Repository::shortlog()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    RevParse(#[from] crate::revision::spec::parse::Error),
+    #[error("The revision specification \"{spec}\" isn't supported for obtaining a shortlog")]
+    UnsupportedSpec { spec: BString },
+    #[error(transparent)]
+    HeadId(#[from] crate::reference::head_id::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    WalkItem(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+}
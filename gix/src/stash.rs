@@ -0,0 +1,195 @@
+//! Types for use with [`Repository::stash_push()`](crate::Repository::stash_push()) and related methods to save and restore
+//! local changes.
+use crate::bstr::BString;
+
+/// An entry of the stash list as returned by [`Repository::stash_list()`](crate::Repository::stash_list()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The id of the stash commit.
+    pub id: gix_hash::ObjectId,
+    /// The message recorded in the reflog of `refs/stash`, like `WIP on main: 1a2b3c4 subject`.
+    pub message: BString,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod create {
+    /// The error returned by [`Repository::stash_create()`](crate::Repository::stash_create()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Repository at \"{}\" is a bare repository and has no local changes to stash", git_dir.display())]
+        BareRepository { git_dir: std::path::PathBuf },
+        #[error("Cannot stash changes on an unborn branch")]
+        UnbornHead,
+        #[error("Cannot stash changes while the index has unresolved conflicts")]
+        Unmerged,
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        PeelHead(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        StatusPlatform(#[from] crate::status::Error),
+        #[error(transparent)]
+        CreateStatusIterator(#[from] crate::status::index_worktree::iter::Error),
+        #[error(transparent)]
+        StatusItem(#[from] crate::status::index_worktree::Error),
+        #[error(transparent)]
+        Dirwalk(#[from] crate::dirwalk::Error),
+        #[error(transparent)]
+        FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        FileSystemCapabilities(#[from] crate::config::boolean::Error),
+        #[error("Could not read \"{}\" from the worktree", path.display())]
+        ReadFile {
+            path: std::path::PathBuf,
+            source: std::io::Error,
+        },
+        #[error(transparent)]
+        WriteTree(#[from] crate::repository::write_tree_from_index::Error),
+        #[error(transparent)]
+        WriteObject(#[from] crate::object::write::Error),
+        #[error(transparent)]
+        ParseTime(#[from] crate::config::time::Error),
+        #[error("Committer identity is not configured")]
+        CommitterMissing,
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    /// The error returned by [`Repository::stash_apply()`](crate::Repository::stash_apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Repository at \"{}\" is a bare repository and has no worktree to apply changes to", git_dir.display())]
+        BareRepository { git_dir: std::path::PathBuf },
+        #[error("There is no stash entry at index {index}")]
+        NoSuchStash { index: usize },
+        #[error("Commit {id} doesn't look like a stash commit as it has no parents")]
+        NotAStash { id: gix_hash::ObjectId },
+        #[error("The untracked file \"{rela_path}\" of the stash already exists in the worktree")]
+        UntrackedFileExists { rela_path: crate::bstr::BString },
+        #[error(transparent)]
+        List(#[from] super::list::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        WriteTree(#[from] crate::repository::write_tree_from_index::Error),
+        #[error(transparent)]
+        IndexFromTree(#[from] crate::repository::index_from_tree::Error),
+        #[error(transparent)]
+        Merge(#[from] crate::merge::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::checkout::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod push {
+    use crate::bstr::BString;
+
+    /// Options for use in [`Repository::stash_push()`](crate::Repository::stash_push()).
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    pub struct Options {
+        /// The message to describe the stash with, leading to a stash message like `On <branch>: <message>`.
+        /// If `None`, it will be `WIP on <branch>: <short-id> <subject>`.
+        pub message: Option<BString>,
+        /// If `true`, untracked files that aren't ignored are stashed as well and removed from the worktree,
+        /// similar to `git stash push --include-untracked`.
+        pub include_untracked: bool,
+    }
+
+    /// The error returned by [`Repository::stash_push()`](crate::Repository::stash_push()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Create(#[from] super::create::Error),
+        #[error(transparent)]
+        EditReference(#[from] crate::reference::edit::Error),
+        #[error(transparent)]
+        Checkout(#[from] crate::checkout::Error),
+        #[error(transparent)]
+        WriteIndex(#[from] gix_index::file::write::Error),
+        #[error("Could not remove the stashed untracked file \"{}\"", path.display())]
+        RemoveUntracked {
+            path: std::path::PathBuf,
+            source: std::io::Error,
+        },
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod list {
+    /// The error returned by [`Repository::stash_list()`](crate::Repository::stash_list()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindReference(#[from] crate::reference::find::Error),
+        #[error("Could not read the reflog of refs/stash")]
+        ReadLog(#[from] std::io::Error),
+        #[error(transparent)]
+        DecodeLog(#[from] gix_ref::file::log::iter::decode::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod drop {
+    /// The error returned by [`Repository::stash_drop()`](crate::Repository::stash_drop()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("There is no stash entry at index {index}")]
+        NoSuchStash { index: usize },
+        #[error(transparent)]
+        List(#[from] super::list::Error),
+        #[error(transparent)]
+        LockTimeout(#[from] crate::config::lock_timeout::Error),
+        #[error(transparent)]
+        Lock(#[from] gix_lock::acquire::Error),
+        #[error(transparent)]
+        EditReference(#[from] crate::reference::edit::Error),
+        #[error("Could not write the reflog of refs/stash")]
+        WriteLog(#[from] std::io::Error),
+        #[error(transparent)]
+        CommitLog(#[from] gix_lock::commit::Error<gix_lock::File>),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod pop {
+    /// The error returned by [`Repository::stash_pop()`](crate::Repository::stash_pop()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Apply(#[from] super::apply::Error),
+        #[error(transparent)]
+        Drop(#[from] super::drop::Error),
+    }
+}
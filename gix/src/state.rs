@@ -1,10 +1,9 @@
 /// Tell what operation is currently in progress.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InProgress {
     /// A mailbox is being applied.
     ApplyMailbox,
     /// A rebase is happening while a mailbox is being applied.
-    // TODO: test
     ApplyMailboxRebase,
     /// A git bisect operation has not yet been concluded.
     Bisect,
@@ -23,3 +22,140 @@ pub enum InProgress {
     /// A revert operation with multiple commits pending.
     RevertSequence,
 }
+
+/// A file in the `.git` directory that keeps the state of an operation in progress, as written by `git merge`, `git cherry-pick`
+/// and similar commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum File {
+    /// `MERGE_HEAD`, with the ids of the commits being merged, one per line.
+    MergeHead,
+    /// `MERGE_MSG`, the message to use when committing the merge, cherry-pick or revert.
+    MergeMsg,
+    /// `MERGE_MODE`, which is `no-ff` if the merge should create a merge commit in any case.
+    MergeMode,
+    /// `MERGE_RR`, with the conflicts recorded by `git rerere`.
+    MergeRr,
+    /// `AUTO_MERGE`, with the id of the tree produced by the merge, including conflict markers.
+    AutoMerge,
+    /// `SQUASH_MSG`, the message to use when committing a squashed merge.
+    SquashMsg,
+    /// `CHERRY_PICK_HEAD`, with the id of the commit being cherry-picked.
+    CherryPickHead,
+    /// `REVERT_HEAD`, with the id of the commit being reverted.
+    RevertHead,
+}
+
+impl File {
+    /// All files that keep the state of an unfinished merge, cherry-pick or revert, which are removed once it is concluded
+    /// or aborted.
+    pub const MERGE_STATE: [File; 8] = [
+        File::MergeHead,
+        File::MergeRr,
+        File::MergeMsg,
+        File::MergeMode,
+        File::AutoMerge,
+        File::SquashMsg,
+        File::CherryPickHead,
+        File::RevertHead,
+    ];
+
+    /// Return the name of the file, relative to the `.git` directory.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            File::MergeHead => "MERGE_HEAD",
+            File::MergeMsg => "MERGE_MSG",
+            File::MergeMode => "MERGE_MODE",
+            File::MergeRr => "MERGE_RR",
+            File::AutoMerge => "AUTO_MERGE",
+            File::SquashMsg => "SQUASH_MSG",
+            File::CherryPickHead => "CHERRY_PICK_HEAD",
+            File::RevertHead => "REVERT_HEAD",
+        }
+    }
+}
+
+/// Changes to `ORIG_HEAD`, references and [state files](File), to be applied together with
+/// [`Repository::apply_state_changes()`](crate::Repository::apply_state_changes()).
+#[derive(Default, Debug, Clone)]
+pub struct Changes {
+    /// The reference edits to perform, including the one to `ORIG_HEAD` if it is [set](Self::set_orig_head()).
+    pub edits: Vec<gix_ref::transaction::RefEdit>,
+    /// The state files to write if their content is `Some`, or to remove otherwise, each mentioned at most once.
+    pub files: Vec<(File, Option<Vec<u8>>)>,
+}
+
+impl Changes {
+    /// Point `ORIG_HEAD` to `id`, using `message` for its reflog if it has one, just like `git` does before moving `HEAD`
+    /// in a way that should be easy to undo.
+    pub fn set_orig_head(self, id: impl Into<gix_hash::ObjectId>, message: impl Into<crate::bstr::BString>) -> Self {
+        use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+        self.edit(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: gix_ref::Target::Peeled(id.into()),
+            },
+            name: "ORIG_HEAD".try_into().expect("valid"),
+            deref: false,
+        })
+    }
+
+    /// Perform `edit` along with all other changes.
+    pub fn edit(mut self, edit: gix_ref::transaction::RefEdit) -> Self {
+        self.edits.push(edit);
+        self
+    }
+
+    /// Write `content` to `file`, replacing it if it exists.
+    ///
+    /// Later changes to the same file replace earlier ones.
+    pub fn write(self, file: File, content: impl Into<Vec<u8>>) -> Self {
+        self.file(file, Some(content.into()))
+    }
+
+    /// Remove `file` if it exists.
+    pub fn remove(self, file: File) -> Self {
+        self.file(file, None)
+    }
+
+    /// Remove all files that keep the [state of an unfinished merge](File::MERGE_STATE), cherry-pick or revert.
+    pub fn remove_merge_state(self) -> Self {
+        File::MERGE_STATE.into_iter().fold(self, Self::remove)
+    }
+
+    /// Write or remove `file` depending on `content`, replacing an earlier change to the same file.
+    fn file(mut self, file: File, content: Option<Vec<u8>>) -> Self {
+        match self.files.iter_mut().find(|(existing, _)| *existing == file) {
+            Some((_, existing_content)) => *existing_content = content,
+            None => self.files.push((file, content)),
+        }
+        self
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Repository::apply_state_changes()`](crate::Repository::apply_state_changes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not lock state file at '{}'", path.display())]
+        LockFile {
+            path: PathBuf,
+            source: gix_lock::acquire::Error,
+        },
+        #[error("Could not write state file at '{}'", path.display())]
+        WriteFile { path: PathBuf, source: std::io::Error },
+        #[error("Could not remove state file at '{}'", path.display())]
+        RemoveFile { path: PathBuf, source: std::io::Error },
+        #[error(transparent)]
+        EditReferences(#[from] crate::reference::edit::Error),
+    }
+}
@@ -367,6 +367,7 @@ pub mod iter {
             Some(index.write(crate::index::write::Options {
                 extensions: Default::default(),
                 skip_hash: self.skip_hash,
+                lock_mode: Default::default(),
             }))
         }
     }
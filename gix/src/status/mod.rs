@@ -178,3 +178,7 @@ mod platform;
 ///
 #[allow(clippy::empty_docs)]
 pub mod index_worktree;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree_index;
@@ -0,0 +1,69 @@
+use crate::{
+    diff::Rewrites,
+    object::tree::diff::{for_each, Action, Change},
+    Repository,
+};
+
+/// The error returned by [Repository::tree_index_status()].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    FindTreeish(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToTree(#[from] crate::object::peel::to_kind::Error),
+    #[error("Could not write the trees of the index into memory")]
+    WriteIndexTree(#[from] crate::repository::write_tree_from_index::Error),
+    #[error(transparent)]
+    RewritesConfiguration(#[from] crate::diff::new_rewrites::Error),
+    #[error(transparent)]
+    Diff(#[from] for_each::Error),
+}
+
+/// The outcome of [Repository::tree_index_status()].
+pub type Outcome = for_each::Outcome;
+
+impl Repository {
+    /// Call `cb` with each change that is needed to turn the tree of `tree_ish` into the tree represented by `index`, which
+    /// is the status of the index relative to `HEAD` if `tree_ish` is the `HEAD` commit, similar to `git diff --cached`.
+    ///
+    /// `rewrites` controls how renames and copies are detected, with `None` disabling rewrite tracking.
+    /// Changes are only reported for files, symlinks and submodules, along with their full path relative to the
+    /// root of the repository.
+    ///
+    /// Note that unmerged entries and entries that are only [intended to be added](gix_index::entry::Flags::INTENT_TO_ADD)
+    /// are ignored as they have no representation in a tree.
+    /// The trees of `index` are only written into memory.
+    pub fn tree_index_status<E>(
+        &self,
+        tree_ish: &gix_hash::oid,
+        index: &gix_index::State,
+        rewrites: Option<Rewrites>,
+        mut cb: impl FnMut(Change<'_, '_, '_>) -> Result<Action, E>,
+    ) -> Result<Outcome, Error>
+    where
+        E: Into<Box<dyn std::error::Error + Sync + Send + 'static>>,
+    {
+        let repo = self.clone().with_object_memory();
+        let mut index = index.clone();
+        index.remove_entries(|_, _, entry| {
+            entry.stage() != gix_index::entry::Stage::Unconflicted
+                || entry.flags.contains(gix_index::entry::Flags::INTENT_TO_ADD)
+        });
+        let index_tree = repo.write_tree_from_index(&index)?.object()?.peel_to_tree()?;
+        let tree = repo.find_object(tree_ish)?.peel_to_tree()?;
+
+        let mut platform = tree.changes()?;
+        let outcome =
+            platform
+                .track_path()
+                .track_rewrites(rewrites)
+                .for_each_to_obtain_tree(&index_tree, |change| {
+                    if change.event.entry_mode().is_tree() {
+                        return Ok(Action::Continue);
+                    }
+                    cb(change)
+                })?;
+        Ok(outcome)
+    }
+}
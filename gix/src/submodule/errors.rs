@@ -101,6 +101,8 @@ pub mod head_id {
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
     pub enum Error {
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
         #[error(transparent)]
         HeadCommit(#[from] crate::reference::head_commit::Error),
         #[error("Could not get tree of head commit")]
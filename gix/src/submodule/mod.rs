@@ -21,6 +21,14 @@ pub(crate) const MODULES_FILE: &str = ".gitmodules";
 mod errors;
 pub use errors::*;
 
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "status",
+    feature = "revision",
+    feature = "blocking-network-client"
+))]
+pub mod update;
+
 /// A platform maintaining state needed to interact with submodules, created by [`Repository::submodules()].
 pub(crate) struct SharedState<'repo> {
     pub(crate) repo: &'repo Repository,
@@ -176,13 +184,17 @@ impl<'repo> Submodule<'repo> {
             .and_then(|entry| (entry.mode == gix_index::entry::Mode::COMMIT).then_some(entry.id)))
     }
 
-    /// Return the object id of the submodule as stored in `HEAD^{tree}` of the superproject, or `None` if it wasn't yet committed.
+    /// Return the object id of the submodule as stored in `HEAD^{tree}` of the superproject, or `None` if it wasn't yet committed,
+    /// which includes the case of an unborn `HEAD`.
     ///
     /// If `Some()`, but `None` when calling [`Self::index_id()`], then the submodule was just deleted but the change
     /// wasn't yet committed. Note that `None` is also returned if the entry at the submodule path isn't a submodule.
     /// If `None`, but `Some()` when calling [`Self::index_id()`], then the submodule was just added without having committed the change.
     pub fn head_id(&self) -> Result<Option<gix_hash::ObjectId>, head_id::Error> {
         let path = self.path()?;
+        if self.state.repo.head()?.is_unborn() {
+            return Ok(None);
+        }
         Ok(self
             .state
             .repo
@@ -329,9 +341,12 @@ pub mod status {
         ///
         /// The status allows to easily determine if a submodule [has changes](Status::is_dirty).
         ///
+        /// To learn if a new submodule commit was staged, compare [`Status::head_id`] to [`Status::index_id`], which
+        /// is also what [`Status::is_staged()`] does.
+        ///
         /// ### Incomplete Implementation Warning
         ///
-        /// Currently, changes between the head and the index aren't computed.
+        /// Currently, changes between the head and the index of the submodule itself aren't computed.
         // TODO: Run the full status, including tree->index once available.
         #[doc(alias = "submodule_status", alias = "git2")]
         pub fn status_opts(
@@ -351,10 +366,12 @@ pub mod status {
                 });
             }
 
+            let head_id = self.head_id()?;
             let index_id = self.index_id()?;
             if !state.repository_exists {
                 return Ok(Status {
                     state,
+                    head_id,
                     index_id,
                     ..Default::default()
                 });
@@ -364,6 +381,7 @@ pub mod status {
                     state.repository_exists = false;
                     return Ok(Status {
                         state,
+                        head_id,
                         index_id,
                         ..Default::default()
                     });
@@ -374,6 +392,7 @@ pub mod status {
             let checked_out_head_id = sm_repo.head_id().ok().map(crate::Id::detach);
             let mut status = Status {
                 state,
+                head_id,
                 index_id,
                 checked_out_head_id,
                 ..Default::default()
@@ -402,6 +421,14 @@ pub mod status {
     }
 
     impl Status {
+        /// Return `true` if the super-project's index records a different submodule commit than its `HEAD` commit,
+        /// which happens if the submodule was added, removed or moved to another commit and the change was staged.
+        ///
+        /// Note that this is always `false` if the computation wasn't performed due to [`Ignore::All`](config::Ignore::All).
+        pub fn is_staged(&self) -> bool {
+            self.head_id != self.index_id
+        }
+
         /// Return `Some(true)` if the submodule status could be determined sufficiently and
         /// if there are changes that would render this submodule dirty.
         ///
@@ -434,6 +461,11 @@ pub mod status {
             /// The cheapest part of the status that is always performed, to learn if the repository is cloned
             /// and if there is a worktree checkout.
             pub state: State,
+            /// The commit at which the submodule is recorded in the tree of the super-project's `HEAD` commit.
+            /// `None` means the computation wasn't performed, or the submodule wasn't committed to the super-project yet.
+            ///
+            /// If it differs from [`index_id`](Self::index_id), a new submodule commit was staged in the super-project.
+            pub head_id: Option<gix_hash::ObjectId>,
             /// The commit at which the submodule is supposed to be according to the super-project's index.
             /// `None` means the computation wasn't performed, or the submodule didn't exist in the super-project's index anymore.
             pub index_id: Option<gix_hash::ObjectId>,
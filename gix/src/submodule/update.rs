@@ -0,0 +1,128 @@
+//! Types for use with [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, default `false`, submodules that aren't initialized yet will be initialized by writing their
+    /// resolved URL into the configuration of the superproject before updating them, similar to `git submodule update --init`.
+    pub init: bool,
+    /// If `true`, default `false`, submodules of the updated submodules will be updated as well, with the same options,
+    /// similar to `git submodule update --recursive`.
+    pub recursive: bool,
+    /// The maximum nesting level of submodules to update when [recursing](Self::recursive), with `1` meaning that only the
+    /// submodules of the superproject are updated. Encountering deeper submodules is an error.
+    ///
+    /// Defaults to `32`.
+    pub max_depth: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            init: false,
+            recursive: false,
+            max_depth: 32,
+        }
+    }
+}
+
+/// What happened to a submodule during [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// The submodule repository didn't exist and was cloned, and the recorded commit was checked out.
+    Cloned,
+    /// The recorded commit was checked out in the existing submodule repository.
+    CheckedOut,
+    /// The submodule already was at the recorded commit.
+    UpToDate,
+}
+
+/// A submodule that was visited by [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The path of the submodule relative to the worktree of the repository the update was started in.
+    pub path: BString,
+    /// The name of the submodule in the `.gitmodules` file of its superproject.
+    pub name: BString,
+    /// The commit the submodule is checked out at now, as recorded in the index of its superproject.
+    pub id: gix_hash::ObjectId,
+    /// What happened to the submodule.
+    pub action: Action,
+}
+
+/// The outcome of [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// All submodules that were updated, in the order they were visited, with nested submodules following their superproject.
+    /// Submodules that are not initialized, inactive or configured to not be updated are not listed.
+    pub entries: Vec<Entry>,
+}
+
+/// The error returned by [`Repository::submodule_update()`](crate::Repository::submodule_update()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Repository at \"{}\" is a bare repository and has no submodules to update", git_dir.display())]
+    BareRepository { git_dir: std::path::PathBuf },
+    #[error(transparent)]
+    Modules(#[from] crate::submodule::modules::Error),
+    #[error(transparent)]
+    Path(#[from] gix_submodule::config::path::Error),
+    #[error(transparent)]
+    Url(#[from] gix_submodule::config::url::Error),
+    #[error(transparent)]
+    UpdateMode(#[from] gix_submodule::config::update::Error),
+    #[error(transparent)]
+    IsActive(#[from] crate::submodule::is_active::Error),
+    #[error(transparent)]
+    IndexId(#[from] crate::submodule::index_id::Error),
+    #[error(transparent)]
+    OpenSubmodule(#[from] crate::submodule::open::Error),
+    #[error("Submodule \"{name}\" is configured with update mode {mode:?}, but only checkouts are supported")]
+    UnsupportedUpdateMode {
+        name: BString,
+        mode: gix_submodule::config::Update,
+    },
+    #[error(transparent)]
+    Realpath(#[from] gix_path::realpath::Error),
+    #[error(transparent)]
+    FindRemote(#[from] crate::remote::find::existing::Error),
+    #[error("Could not parse the resolved URL of submodule \"{name}\"")]
+    ParseUrl {
+        name: BString,
+        source: gix_url::parse::Error,
+    },
+    #[error("Could not read the configuration file at \"{}\"", path.display())]
+    ReadConfig {
+        path: std::path::PathBuf,
+        source: gix_config::file::init::from_paths::Error,
+    },
+    #[error("Could not write \"{}\"", path.display())]
+    Write {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    PrepareClone(#[from] crate::clone::Error),
+    #[error(transparent)]
+    Clone(#[from] crate::clone::fetch::Error),
+    #[error(transparent)]
+    Connect(#[from] crate::remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+    #[error(transparent)]
+    Fetch(#[from] crate::remote::fetch::Error),
+    #[error("Submodule \"{name}\" doesn't contain the recorded commit {id}, even after fetching")]
+    MissingCommit { name: BString, id: gix_hash::ObjectId },
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelHead(#[from] crate::head::peel::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error("Submodule at \"{}\" uses the repository of one of its superprojects", git_dir.display())]
+    Cycle { git_dir: std::path::PathBuf },
+    #[error("Submodule at \"{path}\" is nested deeper than the maximum of {max_depth} levels")]
+    MaxDepthExceeded { path: BString, max_depth: usize },
+}
@@ -0,0 +1,454 @@
+//! Add, replace and remove trailers like `Signed-off-by: …` in commit messages, similar to `git interpret-trailers`.
+//!
+//! Obtain the [`Configuration`] from the `trailer.*` values of a repository with
+//! [`Repository::trailer_configuration()`](crate::Repository::trailer_configuration()), or use
+//! [`Repository::interpret_trailers()`](crate::Repository::interpret_trailers()) to edit a message directly.
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// The error returned by [`Repository::trailer_configuration()`](crate::Repository::trailer_configuration()) and
+/// [`Repository::interpret_trailers()`](crate::Repository::interpret_trailers()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ConfigValue(#[from] crate::config::key::GenericErrorWithValue),
+}
+
+/// Where a new trailer is placed, as configured by `trailer.where` or `trailer.<token>.where`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Where {
+    /// After all other trailers, the default.
+    #[default]
+    End,
+    /// Before all other trailers.
+    Start,
+    /// After the last trailer with the same token, or at the end if there is none.
+    After,
+    /// Before the first trailer with the same token, or at the start if there is none.
+    Before,
+}
+
+/// What to do if a trailer with the same token already exists, as configured by `trailer.ifExists` or
+/// `trailer.<token>.ifExists`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IfExists {
+    /// Add the trailer unless the trailer next to where it would be placed has the same token and value, the default.
+    #[default]
+    AddIfDifferentNeighbor,
+    /// Add the trailer unless any trailer has the same token and value.
+    AddIfDifferent,
+    /// Add the trailer even if the same trailer exists already.
+    Add,
+    /// Replace the existing trailer with the same token.
+    Replace,
+    /// Leave the message unchanged.
+    DoNothing,
+}
+
+/// What to do if no trailer with the same token exists, as configured by `trailer.ifMissing` or
+/// `trailer.<token>.ifMissing`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IfMissing {
+    /// Add the trailer, the default.
+    #[default]
+    Add,
+    /// Leave the message unchanged.
+    DoNothing,
+}
+
+/// The rules to follow when adding a trailer.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Policy {
+    /// Where to place the new trailer.
+    pub position: Where,
+    /// What to do if a trailer with the same token exists already.
+    pub if_exists: IfExists,
+    /// What to do if no trailer with the same token exists.
+    pub if_missing: IfMissing,
+}
+
+/// Rules that replace those of a [`Policy`] if they are set.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Overrides {
+    /// Where to place the new trailer.
+    pub position: Option<Where>,
+    /// What to do if a trailer with the same token exists already.
+    pub if_exists: Option<IfExists>,
+    /// What to do if no trailer with the same token exists.
+    pub if_missing: Option<IfMissing>,
+}
+
+impl Overrides {
+    /// Return `policy` with all rules replaced that are set in `self`.
+    pub fn apply_to(&self, policy: Policy) -> Policy {
+        Policy {
+            position: self.position.unwrap_or(policy.position),
+            if_exists: self.if_exists.unwrap_or(policy.if_exists),
+            if_missing: self.if_missing.unwrap_or(policy.if_missing),
+        }
+    }
+}
+
+/// The configuration of a single token, as found in the `trailer.<name>` subsection.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The name of the subsection, which can be used in place of the token itself.
+    pub name: BString,
+    /// The token to write into the message, like `Signed-off-by`, from `trailer.<name>.key`.
+    pub key: Option<BString>,
+    /// The rules for this token that override those of the [`Configuration`].
+    pub overrides: Overrides,
+}
+
+impl Token {
+    /// Return the token as it should appear in messages, which is the configured key or the name.
+    pub fn key(&self) -> &BStr {
+        self.key.as_ref().unwrap_or(&self.name).as_bstr()
+    }
+}
+
+/// The configuration of all trailers, typically obtained with
+/// [`Repository::trailer_configuration()`](crate::Repository::trailer_configuration()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Configuration {
+    /// The characters that separate a token from its value, from `trailer.separators`, defaulting to `:`.
+    ///
+    /// The first one is used when writing trailers.
+    pub separators: BString,
+    /// The rules for all tokens that aren't overridden.
+    pub policy: Policy,
+    /// The configuration of individual tokens.
+    pub tokens: Vec<Token>,
+}
+
+impl Default for Configuration {
+    fn default() -> Self {
+        Configuration {
+            separators: ":".into(),
+            policy: Policy::default(),
+            tokens: Vec::new(),
+        }
+    }
+}
+
+/// A change to make to the trailers of a message with [`Configuration::edit()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    /// Add a trailer according to the configured [`Policy`] of its token, which may be the name of a configured [`Token`].
+    Add {
+        /// The token, like `Signed-off-by`.
+        token: BString,
+        /// The value of the trailer, like `Name <email>`.
+        value: BString,
+        /// The rules to use instead of the configured ones, like `--where`, `--if-exists` and `--if-missing` of
+        /// `git interpret-trailers`.
+        overrides: Overrides,
+    },
+    /// Remove all trailers with the given token, or the token configured for it.
+    Remove {
+        /// The token of the trailers to remove.
+        token: BString,
+    },
+}
+
+/// Edit
+impl Configuration {
+    /// Apply all `edits` in order to the trailers of `message` and return the edited message.
+    ///
+    /// Trailers are found in the last paragraph of the message, before any trailing comment lines and a `---` line
+    /// which starts a patch. A paragraph is considered to be made of trailers if all of its lines are trailers,
+    /// or if at least one of them is generated by `git` or configured and at least a quarter of them are trailers,
+    /// just like `git` does it. Comment lines within the trailers are removed.
+    /// If no trailers exist yet, new ones are added in a paragraph of their own.
+    pub fn edit(&self, message: &BStr, edits: &[Edit]) -> BString {
+        let mut message = message.to_owned();
+        if !message.is_empty() && !message.ends_with(b"\n") {
+            message.push(b'\n');
+        }
+        let end = self.end_of_trailers(&message);
+        let start = self.start_of_trailers(&message[..end]);
+        let mut items = self.parse_trailers(&message[start..end]);
+
+        for edit in edits {
+            match edit {
+                Edit::Add {
+                    token,
+                    value,
+                    overrides,
+                } => {
+                    let (token, policy) = match self.token(token.as_ref()) {
+                        Some(conf) => (
+                            conf.key().to_owned(),
+                            overrides.apply_to(conf.overrides.apply_to(self.policy)),
+                        ),
+                        None => (token.clone(), overrides.apply_to(self.policy)),
+                    };
+                    let item = Item {
+                        token: Some(token),
+                        value: value.trim().into(),
+                    };
+                    self.add(&mut items, item, policy);
+                }
+                Edit::Remove { token } => {
+                    let token = self.token(token.as_ref()).map_or(token.as_bstr(), Token::key);
+                    items.retain(|item| !item.has_token(token));
+                }
+            }
+        }
+
+        let mut out = BString::from(&message[..start]);
+        if items.is_empty() {
+            if start != end {
+                while out.ends_with(b"\n\n") {
+                    out.pop();
+                }
+            }
+        } else if start == end && !out.is_empty() && !out.ends_with(b"\n\n") {
+            out.push(b'\n');
+        }
+        for item in &items {
+            match &item.token {
+                Some(token) => {
+                    out.push_str(token);
+                    if !matches!(token.trim_end().last(), Some(c) if self.separators.contains(c)) {
+                        out.push(self.separators.first().copied().unwrap_or(b':'));
+                        out.push(b' ');
+                    }
+                    out.push_str(&item.value);
+                }
+                None => out.push_str(&item.value),
+            }
+            out.push(b'\n');
+        }
+        out.push_str(&message[end..]);
+        out
+    }
+}
+
+/// A line in the trailer paragraph, which is a trailer if it has a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Item {
+    token: Option<BString>,
+    value: BString,
+}
+
+impl Item {
+    fn has_token(&self, token: &BStr) -> bool {
+        let trim = |token: &[u8]| -> BString {
+            token
+                .trim_end_with(|c| !(c.is_alphanumeric() || c == '-'))
+                .to_ascii_lowercase()
+                .into()
+        };
+        matches!(&self.token, Some(t) if trim(t) == trim(token))
+    }
+
+    fn is_same(&self, other: &Item) -> bool {
+        matches!(&other.token, Some(token) if self.has_token(token.as_ref())) && self.value == other.value
+    }
+}
+
+/// Utilities
+impl Configuration {
+    fn token(&self, token: &BStr) -> Option<&Token> {
+        let trim = |token: &BStr| -> BString {
+            token
+                .trim_end_with(|c| c.is_whitespace() || (c.is_ascii() && self.separators.contains(&(c as u8))))
+                .into()
+        };
+        let token = trim(token);
+        self.tokens.iter().find(|conf| {
+            [conf.name.as_bstr(), conf.key()]
+                .into_iter()
+                .any(|name| trim(name).eq_ignore_ascii_case(&token))
+        })
+    }
+
+    /// Add `item` to `items` according to `policy`, like `git` does.
+    fn add(&self, items: &mut Vec<Item>, item: Item, policy: Policy) {
+        let token = item.token.clone().expect("always set for new trailers");
+        let after = matches!(policy.position, Where::End | Where::After);
+        let in_order: Vec<usize> = if after {
+            (0..items.len()).rev().collect()
+        } else {
+            (0..items.len()).collect()
+        };
+        let Some(existing) = in_order
+            .iter()
+            .copied()
+            .find(|&idx| items[idx].has_token(token.as_ref()))
+        else {
+            if policy.if_missing == IfMissing::Add {
+                match policy.position {
+                    Where::End | Where::After => items.push(item),
+                    Where::Start | Where::Before => items.insert(0, item),
+                }
+            }
+            return;
+        };
+        let neighbor = if matches!(policy.position, Where::After | Where::Before) {
+            existing
+        } else {
+            in_order[0]
+        };
+        let insert_at = if after { neighbor + 1 } else { neighbor };
+        match policy.if_exists {
+            IfExists::DoNothing => {}
+            IfExists::Add => items.insert(insert_at, item),
+            IfExists::AddIfDifferentNeighbor => {
+                if !items[neighbor].is_same(&item) {
+                    items.insert(insert_at, item);
+                }
+            }
+            IfExists::AddIfDifferent => {
+                if !items.iter().any(|existing| existing.is_same(&item)) {
+                    items.insert(insert_at, item);
+                }
+            }
+            IfExists::Replace => {
+                items.insert(insert_at, item);
+                items.remove(if existing >= insert_at { existing + 1 } else { existing });
+            }
+        }
+    }
+
+    /// Return the position after the last line that may contain trailers, which is before a patch and before
+    /// trailing blank and comment lines.
+    fn end_of_trailers(&self, message: &[u8]) -> usize {
+        let mut end = message.len();
+        let mut pos = 0;
+        for line in message.lines_with_terminator() {
+            if line.starts_with(b"---") && line.get(3).filter(|b| !b.is_ascii_whitespace()).is_none() {
+                end = pos;
+                break;
+            }
+            pos += line.len();
+        }
+
+        let mut start_of_comments = None;
+        let mut pos = 0;
+        for line in message[..end].lines_with_terminator() {
+            if line.starts_with(b"#") || line.trim().is_empty() {
+                start_of_comments.get_or_insert(pos);
+            } else {
+                start_of_comments = None;
+            }
+            pos += line.len();
+        }
+        start_of_comments.unwrap_or(end)
+    }
+
+    /// Return the position of the trailers in `message`, or its length if there are none.
+    fn start_of_trailers(&self, message: &[u8]) -> usize {
+        let mut lines: Vec<(usize, &[u8])> = Vec::new();
+        let mut pos = 0;
+        for line in message.lines_with_terminator() {
+            lines.push((pos, line));
+            pos += line.len();
+        }
+        let end_of_title = lines
+            .iter()
+            .position(|(_, line)| line.trim().is_empty())
+            .unwrap_or(lines.len());
+
+        let (mut trailer_lines, mut non_trailer_lines, mut possible_continuation_lines) = (0, 0, 0);
+        let mut recognized_prefix = false;
+        let mut only_spaces = true;
+        for (pos, line) in lines[end_of_title..].iter().rev() {
+            if line.starts_with(b"#") {
+                non_trailer_lines += possible_continuation_lines;
+                possible_continuation_lines = 0;
+                continue;
+            }
+            if line.trim().is_empty() {
+                if only_spaces {
+                    continue;
+                }
+                non_trailer_lines += possible_continuation_lines;
+                return if (recognized_prefix && trailer_lines * 3 >= non_trailer_lines)
+                    || (trailer_lines > 0 && non_trailer_lines == 0)
+                {
+                    pos + line.len()
+                } else {
+                    message.len()
+                };
+            }
+            only_spaces = false;
+
+            if GENERATED_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+                trailer_lines += 1;
+                possible_continuation_lines = 0;
+                recognized_prefix = true;
+                continue;
+            }
+            match self.separator_position(line) {
+                Some(separator) => {
+                    trailer_lines += 1;
+                    possible_continuation_lines = 0;
+                    recognized_prefix |= self.token(line[..separator].as_bstr()).is_some();
+                }
+                None if line[0].is_ascii_whitespace() => possible_continuation_lines += 1,
+                None => {
+                    non_trailer_lines += 1 + possible_continuation_lines;
+                    possible_continuation_lines = 0;
+                }
+            }
+        }
+        message.len()
+    }
+
+    /// Parse all lines of the trailer paragraph `trailers`, joining continuation lines and dropping comments.
+    fn parse_trailers(&self, trailers: &[u8]) -> Vec<Item> {
+        let mut items = Vec::<Item>::new();
+        for line in trailers.lines() {
+            if line.starts_with(b"#") || line.trim().is_empty() {
+                continue;
+            }
+            if line[0].is_ascii_whitespace() {
+                if let Some(last) = items.last_mut() {
+                    last.value.push(b'\n');
+                    last.value.push_str(line.trim_end());
+                    continue;
+                }
+            }
+            let item = match self.separator_position(line) {
+                Some(separator) => {
+                    let token = line[..separator].trim().as_bstr();
+                    Item {
+                        token: Some(self.token(token).map_or(token, Token::key).to_owned()),
+                        value: line[separator + 1..].trim().into(),
+                    }
+                }
+                None => Item {
+                    token: None,
+                    value: line.trim_end().into(),
+                },
+            };
+            items.push(item);
+        }
+        items
+    }
+
+    /// Return the position of the separator in `line` if it is a trailer, which must follow a token made of alphanumeric
+    /// characters and dashes, possibly followed by whitespace.
+    fn separator_position(&self, line: &[u8]) -> Option<usize> {
+        let mut whitespace_found = false;
+        for (pos, &b) in line.iter().enumerate() {
+            if self.separators.contains(&b) {
+                return (pos > 0).then_some(pos);
+            }
+            if !whitespace_found && (b.is_ascii_alphanumeric() || b == b'-') {
+                continue;
+            }
+            if pos > 0 && (b == b' ' || b == b'\t') {
+                whitespace_found = true;
+                continue;
+            }
+            break;
+        }
+        None
+    }
+}
+
+/// Lines starting with these are always considered trailers as `git` generates them.
+const GENERATED_PREFIXES: &[&[u8]] = &[b"Signed-off-by: ", b"(cherry picked from commit "];
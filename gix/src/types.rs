@@ -163,6 +163,8 @@ pub struct Repository {
     #[cfg(feature = "attributes")]
     pub(crate) modules: crate::submodule::ModulesFileStorage,
     pub(crate) shallow_commits: crate::shallow::CommitsStorage,
+    /// If set, reference edits are kept here instead of being written, with `None` marking deleted references.
+    pub(crate) refs_memory: Option<RefCell<std::collections::BTreeMap<gix_ref::FullName, Option<gix_ref::Target>>>>,
 }
 
 /// An instance with access to everything a git repository entails, best imagined as container implementing `Sync + Send` for _most_
@@ -247,7 +249,7 @@ pub struct PathspecDetached {
     /// The prepared search to use for checking matches.
     pub search: gix_pathspec::Search,
     /// A thread-safe version of an ODB.
-    pub odb: gix_odb::HandleArc,
+    pub odb: gix_odb::memory::Proxy<gix_odb::HandleArc>,
 }
 
 /// A stand-in for the submodule of a particular name.
@@ -0,0 +1,252 @@
+//! Types for use with [`Repository::update_refs()`](crate::Repository::update_refs()).
+use gix_hash::ObjectId;
+use gix_ref::FullName;
+
+use crate::bstr::{BStr, BString, ByteSlice};
+
+/// What to do with a single reference, like the commands of `git update-ref --stdin`.
+///
+/// Old values that are the null id require the reference to not exist.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    /// Create the reference with the `new` value, which fails if it already exists.
+    Create {
+        /// The value of the new reference.
+        new: ObjectId,
+    },
+    /// Set the reference to `new`, creating it if it doesn't exist, after checking that its current value is `old`
+    /// if it is set. If `new` is the null id, the reference is deleted.
+    Update {
+        /// The value to set the reference to.
+        new: ObjectId,
+        /// The value the reference must currently have.
+        old: Option<ObjectId>,
+    },
+    /// Delete the reference after checking that its current value is `old` if it is set.
+    Delete {
+        /// The value the reference must currently have.
+        old: Option<ObjectId>,
+    },
+    /// Check that the reference currently has the `old` value, or that it doesn't exist if `old` isn't set.
+    Verify {
+        /// The value the reference must currently have.
+        old: Option<ObjectId>,
+    },
+}
+
+/// A single operation on a reference as part of [`Repository::update_refs()`](crate::Repository::update_refs()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Command {
+    /// The name of the reference to operate on, like `refs/heads/main`.
+    pub name: FullName,
+    /// What to do with the reference.
+    pub operation: Operation,
+    /// If `true`, symbolic references are followed and the reference they point to is operated on, which is the default.
+    /// Otherwise, symbolic references are operated on directly, like after `option no-deref`.
+    pub deref: bool,
+}
+
+impl Command {
+    /// Write this command as a line in the format of `git update-ref --stdin` to `out`, preceded by `option no-deref` if
+    /// symbolic references aren't followed, so that [`parse()`] yields it again.
+    ///
+    /// The reference name is quoted in the C-style if it contains special characters, or bytes outside of ASCII if
+    /// `quote_path` is `true`, which is what `core.quotePath` controls.
+    pub fn write_to(&self, mut out: impl std::io::Write, quote_path: bool) -> std::io::Result<()> {
+        if !self.deref {
+            out.write_all(b"option no-deref\n")?;
+        }
+        let (command, new, old) = match &self.operation {
+            Operation::Create { new } => ("create", Some(new), None),
+            Operation::Update { new, old } => ("update", Some(new), old.as_ref()),
+            Operation::Delete { old } => ("delete", None, old.as_ref()),
+            Operation::Verify { old } => ("verify", None, old.as_ref()),
+        };
+        write!(out, "{command} ")?;
+        out.write_all(&gix_quote::ansi_c::quote(self.name.as_bstr(), quote_path))?;
+        for id in new.into_iter().chain(old) {
+            write!(out, " {id}")?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+/// Options for use with [`Repository::update_refs()`](crate::Repository::update_refs()).
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The message to write into the reflog of each changed reference, like `git update-ref -m <message>`.
+    pub message: BString,
+    /// If `true`, reflogs are created even for references that wouldn't have one by default, like `git update-ref --create-reflog`.
+    pub create_reflog: bool,
+}
+
+/// Parse `input` in the format of `git update-ref --stdin` into commands whose object ids are of `object_hash`.
+///
+/// Each line holds one command, with its arguments separated by a single space:
+///
+/// * `update <ref> <new-oid> [<old-oid>]`
+/// * `create <ref> <new-oid>`
+/// * `delete <ref> [<old-oid>]`
+/// * `verify <ref> [<old-oid>]`
+/// * `option no-deref`, which affects only the next command
+///
+/// Reference names may be quoted in the C-style, and object ids must be given as full hexadecimal hashes.
+pub fn parse(input: &BStr, object_hash: gix_hash::Kind) -> Result<Vec<Command>, parse::Error> {
+    use parse::Error;
+    let mut commands = Vec::new();
+    let mut deref = true;
+    for (line_number, line) in input.lines().enumerate() {
+        let line_number = line_number + 1;
+        let mut tokens = line.split_str(" ");
+        let command = tokens.next().unwrap_or_default().as_bstr();
+        let args: Vec<_> = tokens.map(ByteSlice::as_bstr).collect();
+        let (required, optional) = match command.as_bytes() {
+            b"update" => (2, 1),
+            b"create" => (2, 0),
+            b"delete" | b"verify" => (1, 1),
+            b"option" => {
+                match args.as_slice() {
+                    [option] if *option == "no-deref" => deref = false,
+                    _ => {
+                        return Err(Error::UnsupportedOption {
+                            line_number,
+                            option: line[command.len()..].trim_start().as_bstr().to_owned(),
+                        })
+                    }
+                }
+                continue;
+            }
+            _ => {
+                return Err(Error::UnknownCommand {
+                    line_number,
+                    command: command.to_owned(),
+                })
+            }
+        };
+        if args.len() < required || args.len() > required + optional {
+            return Err(Error::ArgumentCount {
+                line_number,
+                command: command.to_owned(),
+            });
+        }
+        let name = match gix_quote::ansi_c::undo(args[0]) {
+            Ok((name, consumed)) if consumed == args[0].len() => name.into_owned(),
+            Ok(_) => {
+                return Err(Error::TrailingCharacters {
+                    line_number,
+                    name: args[0].to_owned(),
+                })
+            }
+            Err(source) => return Err(Error::Unquote { line_number, source }),
+        };
+        let name = FullName::try_from(name).map_err(|source| Error::InvalidName { line_number, source })?;
+        let id = |hex: &BStr| -> Result<ObjectId, Error> {
+            let id = ObjectId::from_hex(hex).map_err(|source| Error::InvalidObjectId { line_number, source })?;
+            if id.kind() != object_hash {
+                return Err(Error::ObjectHashMismatch {
+                    line_number,
+                    expected: object_hash,
+                    actual: id.kind(),
+                });
+            }
+            Ok(id)
+        };
+        let old = args.get(required).map(|hex| id(hex)).transpose()?;
+        let operation = match command.as_bytes() {
+            b"update" => Operation::Update { new: id(args[1])?, old },
+            b"create" => {
+                let new = id(args[1])?;
+                if new.is_null() {
+                    return Err(Error::NullNewValue { line_number });
+                }
+                Operation::Create { new }
+            }
+            b"delete" => {
+                if matches!(old, Some(old) if old.is_null()) {
+                    return Err(Error::NullOldValue { line_number });
+                }
+                Operation::Delete { old }
+            }
+            b"verify" => Operation::Verify {
+                old: old.filter(|old| !old.is_null()),
+            },
+            _ => unreachable!("only known commands get here"),
+        };
+        commands.push(Command { name, operation, deref });
+        deref = true;
+    }
+    Ok(commands)
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse {
+    use crate::bstr::BString;
+
+    /// The error returned by [`parse()`](super::parse()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Line {line_number}: unknown command {command:?}")]
+        UnknownCommand { line_number: usize, command: BString },
+        #[error("Line {line_number}: unsupported option {option:?}, only 'no-deref' is supported")]
+        UnsupportedOption { line_number: usize, option: BString },
+        #[error("Line {line_number}: wrong number of arguments for {command:?}")]
+        ArgumentCount { line_number: usize, command: BString },
+        #[error("Line {line_number}: could not unquote reference name")]
+        Unquote {
+            line_number: usize,
+            source: gix_quote::ansi_c::undo::Error,
+        },
+        #[error("Line {line_number}: unexpected characters after the quoted reference name in {name:?}")]
+        TrailingCharacters { line_number: usize, name: BString },
+        #[error("Line {line_number}: invalid reference name")]
+        InvalidName {
+            line_number: usize,
+            source: gix_validate::reference::name::Error,
+        },
+        #[error("Line {line_number}: invalid object id")]
+        InvalidObjectId {
+            line_number: usize,
+            source: gix_hash::decode::Error,
+        },
+        #[error("Line {line_number}: expected an object id of kind {expected}, got {actual}")]
+        ObjectHashMismatch {
+            line_number: usize,
+            expected: gix_hash::Kind,
+            actual: gix_hash::Kind,
+        },
+        #[error("Line {line_number}: a reference can't be created with the null id")]
+        NullNewValue { line_number: usize },
+        #[error("Line {line_number}: a reference to delete can't be expected to have the null id")]
+        NullOldValue { line_number: usize },
+    }
+}
+
+/// The error returned by [`Repository::update_refs()`](crate::Repository::update_refs()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    LockTimeout(#[from] crate::config::lock_timeout::Error),
+    #[error(transparent)]
+    Committer(#[from] crate::config::time::Error),
+    #[error(transparent)]
+    Prepare(#[from] gix_ref::file::transaction::prepare::Error),
+    #[error(transparent)]
+    Commit(#[from] gix_ref::file::transaction::commit::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    EditInMemory(#[from] crate::reference::edit::Error),
+    #[error("Reference {name:?} was expected at {}, but was at {}", display(.expected), display(.actual))]
+    VerifyFailed {
+        name: BString,
+        expected: Option<ObjectId>,
+        actual: Option<ObjectId>,
+    },
+}
+
+fn display(id: &Option<ObjectId>) -> String {
+    id.map_or_else(|| "nothing".into(), |id| id.to_string())
+}
@@ -0,0 +1,77 @@
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// The revision to check out in the new worktree, like `HEAD~1` or the short name of a local branch like `main`,
+    /// or `HEAD` of the current worktree if `None`.
+    ///
+    /// If it names a local branch, that branch will be checked out in the new worktree, otherwise `HEAD` will be detached.
+    /// If it is `None` and neither [`new_branch`](Self::new_branch) nor [`detach`](Self::detach) are set, the branch named
+    /// like the worktree is checked out, and created if needed, similar to `git worktree add <path>`.
+    pub start: Option<BString>,
+    /// If set, a branch of the given name will be created at the [start](Self::start) commit and checked out in
+    /// the new worktree, similar to `git worktree add -b <branch>`. It's an error if the branch exists already
+    /// unless [`force`](Self::force) is set, in which case it will be reset to the start commit.
+    pub new_branch: Option<BString>,
+    /// If `true`, `HEAD` in the new worktree will be detached even if [`start`](Self::start) names a branch.
+    pub detach: bool,
+    /// If `true`, check out a branch even though it is already checked out in another worktree, and allow resetting
+    /// an existing [`new_branch`](Self::new_branch).
+    pub force: bool,
+    /// If set, the new worktree will be locked with the given reason, which may be empty, to prevent it from being pruned.
+    pub lock: Option<BString>,
+}
+
+/// The error returned by [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Worktree path \"{}\" exists already and is not an empty directory", path.display())]
+    PathExists { path: std::path::PathBuf },
+    #[error("Could not derive a worktree name from \"{}\"", path.display())]
+    InvalidName { path: std::path::PathBuf },
+    #[error("The branch name \"{name}\" is invalid")]
+    InvalidBranchName {
+        name: BString,
+        source: gix_validate::reference::name::Error,
+    },
+    #[error("A branch named \"{name}\" exists already")]
+    BranchExists { name: BString },
+    #[error("Branch \"{name}\" is already checked out at \"{}\"", worktree.display())]
+    BranchCheckedOut {
+        name: BString,
+        worktree: std::path::PathBuf,
+    },
+    #[error("Could not write \"{}\"", path.display())]
+    Io {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error(transparent)]
+    Realpath(#[from] gix_path::realpath::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::Error),
+    #[error(transparent)]
+    FindExistingReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    RevParse(#[from] crate::revision::spec::parse::single::Error),
+    #[error(transparent)]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    EditReference(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    ListWorktrees(#[from] std::io::Error),
+    #[error(transparent)]
+    OpenRepository(#[from] crate::open::Error),
+    #[error(transparent)]
+    Checkout(#[from] crate::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
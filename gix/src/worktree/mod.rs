@@ -111,6 +111,15 @@ pub(crate) fn id(git_dir: &std::path::Path, has_common_dir: bool) -> Option<&BSt
 #[allow(clippy::empty_docs)]
 pub mod proxy;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+pub mod add;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod prune;
+
 ///
 #[cfg(feature = "index")]
 pub mod open_index {
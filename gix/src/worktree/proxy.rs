@@ -24,6 +24,23 @@ pub mod into_repo {
     }
 }
 
+#[allow(missing_docs)]
+pub mod lock {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Proxy::lock()`][super::Proxy::lock()] and [`Proxy::unlock()`][super::Proxy::unlock()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Worktree at '{}' is already locked", .git_dir.display())]
+        AlreadyLocked { git_dir: PathBuf },
+        #[error("Worktree at '{}' is not locked", .git_dir.display())]
+        NotLocked { git_dir: PathBuf },
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
 impl<'repo> Proxy<'repo> {
     pub(crate) fn new(parent: &'repo Repository, git_dir: impl Into<PathBuf>) -> Self {
         Proxy {
@@ -74,6 +91,31 @@ impl<'repo> Proxy<'repo> {
             .map(|contents| contents.trim().into())
     }
 
+    /// Lock this worktree with an optional `reason` to prevent it from being pruned, moved or deleted, similar to `git worktree lock`.
+    pub fn lock(&self, reason: Option<&BStr>) -> Result<(), lock::Error> {
+        if self.is_locked() {
+            return Err(lock::Error::AlreadyLocked {
+                git_dir: self.git_dir.clone(),
+            });
+        }
+        std::fs::write(
+            self.git_dir.join("locked"),
+            reason.map_or(&[][..], |reason| reason.as_bytes()),
+        )?;
+        Ok(())
+    }
+
+    /// Unlock this worktree if it was [locked](Self::lock()), allowing it to be pruned, moved or deleted again.
+    pub fn unlock(&self) -> Result<(), lock::Error> {
+        if !self.is_locked() {
+            return Err(lock::Error::NotLocked {
+                git_dir: self.git_dir.clone(),
+            });
+        }
+        std::fs::remove_file(self.git_dir.join("locked"))?;
+        Ok(())
+    }
+
     /// Transform this proxy into a [`Repository`] while ignoring issues reading `base()` and ignoring that it might not exist.
     ///
     /// Most importantly, the `Repository` might be initialized with a non-existing work tree directory as the checkout
@@ -82,7 +124,8 @@ impl<'repo> Proxy<'repo> {
     /// a lot of information if work tree access is avoided.
     pub fn into_repo_with_possibly_inaccessible_worktree(self) -> Result<Repository, crate::open::Error> {
         let base = self.base().ok();
-        let repo = ThreadSafeRepository::open_from_paths(self.git_dir, base, self.parent.options.clone())?;
+        let repo =
+            ThreadSafeRepository::open_from_paths(self.git_dir, base, Default::default(), self.parent.options.clone())?;
         Ok(repo.into())
     }
 
@@ -95,7 +138,12 @@ impl<'repo> Proxy<'repo> {
         if !base.is_dir() {
             return Err(into_repo::Error::MissingWorktree { base });
         }
-        let repo = ThreadSafeRepository::open_from_paths(self.git_dir, base.into(), self.parent.options.clone())?;
+        let repo = ThreadSafeRepository::open_from_paths(
+            self.git_dir,
+            base.into(),
+            Default::default(),
+            self.parent.options.clone(),
+        )?;
         Ok(repo.into())
     }
 }
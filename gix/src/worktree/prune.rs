@@ -0,0 +1,56 @@
+use crate::bstr::BString;
+
+/// Options for use with [`Repository::worktree_prune()`](crate::Repository::worktree_prune()).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, only report which worktrees would be pruned without removing their administrative files.
+    pub dry_run: bool,
+    /// If set, worktrees whose checkout is missing are only pruned if their `gitdir` file wasn't modified after the given time,
+    /// similar to `git worktree prune --expire`. If `None`, they are always pruned.
+    pub expire: Option<std::time::SystemTime>,
+}
+
+/// The reason for a worktree to be pruned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    /// The entry in the `worktrees` directory isn't a directory.
+    NotADirectory,
+    /// The `gitdir` file pointing to the worktree doesn't exist.
+    GitDirFileMissing,
+    /// The `gitdir` file pointing to the worktree couldn't be read or is empty.
+    GitDirFileInvalid,
+    /// The `gitdir` file points to a location that doesn't exist.
+    WorktreeMissing,
+}
+
+/// A worktree that was pruned by [`Repository::worktree_prune()`](crate::Repository::worktree_prune()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The name of the worktree, which is the name of its directory in the `worktrees` directory.
+    pub id: BString,
+    /// Why the worktree was pruned.
+    pub reason: Reason,
+}
+
+/// The outcome of [`Repository::worktree_prune()`](crate::Repository::worktree_prune()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// All worktrees that were pruned, or would have been pruned in a dry run, sorted by their id.
+    pub entries: Vec<Entry>,
+}
+
+/// The error returned by [`Repository::worktree_prune()`](crate::Repository::worktree_prune()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not list the worktrees in \"{}\"", path.display())]
+    ReadDir {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not remove the administrative files at \"{}\"", path.display())]
+    Remove {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
+}
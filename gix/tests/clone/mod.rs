@@ -582,6 +582,92 @@ mod blocking_io {
         }
         Ok(())
     }
+
+    #[test]
+    fn fetch_only_single_branch() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+            .with_single_branch(true)
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        let remote = repo.find_remote("origin")?;
+        assert_eq!(
+            remote
+                .refspecs(Direction::Fetch)
+                .iter()
+                .map(|spec| spec.to_ref().to_bstring())
+                .collect::<Vec<_>>(),
+            ["+refs/heads/main:refs/remotes/origin/main"],
+            "only the branch that the remote HEAD points to is configured to be fetched"
+        );
+        let ref_names = |prefix: &str| -> crate::Result<Vec<BString>> {
+            Ok(repo
+                .references()?
+                .prefixed(prefix)?
+                .filter_map(Result::ok)
+                .map(|r| r.name().as_bstr().to_owned())
+                .collect())
+        };
+        assert_eq!(
+            ref_names("refs/remotes/")?,
+            ["refs/remotes/origin/HEAD", "refs/remotes/origin/main"]
+        );
+        assert_eq!(
+            ref_names("refs/tags/")?,
+            ["refs/tags/b-tag", "refs/tags/i-tag"],
+            "only tags pointing into the fetched history are included"
+        );
+        assert_eq!(
+            repo.head_name()?.expect("symbolic").as_bstr(),
+            "refs/heads/main",
+            "HEAD is set up as usual"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_filter() -> crate::Result {
+        let source = remote::repo("allow-filter");
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, _out) = gix::prepare_clone_bare(source.path(), tmp.path())?
+            .with_filter("blob:none")
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+
+        let config = repo.config_snapshot();
+        assert_eq!(config.boolean("remote.origin.promisor"), Some(true));
+        assert_eq!(
+            config.string("remote.origin.partialCloneFilter").expect("set").as_ref(),
+            "blob:none"
+        );
+
+        let head = repo.head_commit()?;
+        assert_eq!(head.id, source.head_id()?, "commits are fetched…");
+        let tree = head.tree()?;
+        let entry = tree.find_entry("file").expect("present");
+        assert!(repo.has_object(tree.id), "…as well as trees…");
+        assert!(!repo.has_object(entry.oid()), "…but no blobs");
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_only_with_filter_needs_server_support() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+            .with_filter("blob:none")
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::clone::fetch::Error::Fetch(gix::remote::fetch::Error::MissingServerFeature {
+                    feature: "filter",
+                    ..
+                })
+            ),
+            "{err:?}"
+        );
+        Ok(())
+    }
 }
 
 #[test]
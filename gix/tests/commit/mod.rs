@@ -69,6 +69,41 @@ mod describe {
         Ok(())
     }
 
+    #[test]
+    fn include_and_exclude_patterns() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let describe = |select, include: &[&str], exclude: &[&str]| -> crate::Result<Option<(String, u32)>> {
+            Ok(repo
+                .head_commit()?
+                .describe()
+                .names(select)
+                .include_patterns(include.iter().copied())
+                .exclude_patterns(exclude.iter().copied())
+                .try_resolve()?
+                .map(|res| (res.outcome.name.expect("no fallback").to_string(), res.outcome.depth)))
+        };
+        assert_eq!(describe(AnnotatedTags, &["v2*"], &[])?, Some(("v2".into(), 0)));
+        assert_eq!(
+            describe(AnnotatedTags, &[], &["v4"])?,
+            Some(("v5".into(), 0)),
+            "the next best tag is used if the best one is excluded"
+        );
+        assert_eq!(
+            describe(AnnotatedTags, &["v*"], &["v[245]"])?,
+            Some(("v1".into(), 1)),
+            "excludes take precedence"
+        );
+        assert_eq!(describe(AnnotatedTags, &["l*"], &[])?, None);
+        assert_eq!(describe(AllTags, &["l*"], &[])?, Some(("l0".into(), 2)));
+        assert_eq!(
+            describe(AllTags, &["main"], &[])?,
+            None,
+            "branches are only considered when all refs are selected"
+        );
+        assert_eq!(describe(AllRefs, &["ma*"], &[])?, Some(("main".into(), 0)));
+        Ok(())
+    }
+
     #[test]
     fn lightweight_tags_are_sorted_lexicographically() -> crate::Result {
         let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
@@ -726,6 +726,23 @@ mod pack {
         assert!(Pack::INDEX_VERSION.validate("-1".into()).is_err());
         Ok(())
     }
+
+    #[test]
+    fn compression() -> crate::Result {
+        use gix::features::zlib::Compression;
+        assert_eq!(Pack::COMPRESSION.try_into_compression(Ok(-1))?, Compression::default());
+        assert_eq!(Pack::COMPRESSION.try_into_compression(Ok(0))?, Compression::none());
+        assert_eq!(Pack::COMPRESSION.try_into_compression(Ok(9))?, Compression::best());
+        assert!(Pack::COMPRESSION.validate("1".into()).is_ok());
+        assert_eq!(
+            Pack::COMPRESSION.try_into_compression(Ok(10)).unwrap_err().to_string(),
+            "The value of key \"pack.compression\" was invalid"
+        );
+        assert!(Pack::COMPRESSION.validate("10".into()).is_err());
+        assert!(Pack::COMPRESSION.validate("-2".into()).is_err());
+        assert!(Pack::COMPRESSION.validate("fast".into()).is_err());
+        Ok(())
+    }
 }
 
 mod protocol {
@@ -887,6 +904,38 @@ mod gitoxide {
             );
         }
     }
+    mod core {
+        use gix::{
+            config::tree::{gitoxide, Key},
+            features::zlib::Backend,
+        };
+
+        use crate::config::tree::bcow;
+
+        #[test]
+        fn zlib_backend() -> crate::Result {
+            assert_eq!(
+                gitoxide::Core::ZLIB_BACKEND.try_into_zlib_backend(bcow("miniz_oxide"))?,
+                Backend::MinizOxide
+            );
+            assert!(gitoxide::Core::ZLIB_BACKEND.validate("miniz_oxide".into()).is_ok());
+            assert_eq!(
+                gitoxide::Core::ZLIB_BACKEND
+                    .try_into_zlib_backend(bcow("zstd"))
+                    .unwrap_err()
+                    .to_string(),
+                "The key \"gitoxide.core.zlibBackend=zstd\" was invalid"
+            );
+            for backend in [Backend::Zlib, Backend::ZlibNg] {
+                assert_eq!(
+                    gitoxide::Core::ZLIB_BACKEND.validate(backend.as_str().into()).is_ok(),
+                    backend.is_available(),
+                    "only the backend selected at compile time can be used besides miniz_oxide"
+                );
+            }
+            Ok(())
+        }
+    }
 }
 
 #[cfg(any(
@@ -1111,3 +1160,140 @@ mod remote {
         );
     }
 }
+
+mod trailer {
+    use gix::{
+        config::tree::{Key, Trailer},
+        trailers,
+    };
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn policies() -> crate::Result {
+        assert_eq!(Trailer::WHERE.try_into_where(bcow("after"))?, trailers::Where::After);
+        assert_eq!(
+            Trailer::TOKEN_WHERE.try_into_where(bcow("Start"))?,
+            trailers::Where::Start
+        );
+        assert!(Trailer::WHERE.validate("end".into()).is_ok());
+        assert_eq!(
+            Trailer::WHERE.try_into_where(bcow("middle")).unwrap_err().to_string(),
+            "The key \"trailer.where=middle\" was invalid"
+        );
+
+        assert_eq!(
+            Trailer::IF_EXISTS.try_into_if_exists(bcow("addIfDifferentNeighbor"))?,
+            trailers::IfExists::AddIfDifferentNeighbor
+        );
+        assert_eq!(
+            Trailer::TOKEN_IF_EXISTS.try_into_if_exists(bcow("replace"))?,
+            trailers::IfExists::Replace
+        );
+        assert!(Trailer::IF_EXISTS.validate("doNothing".into()).is_ok());
+        assert!(Trailer::IF_EXISTS.validate("overwrite".into()).is_err());
+
+        assert_eq!(
+            Trailer::IF_MISSING.try_into_if_missing(bcow("doNothing"))?,
+            trailers::IfMissing::DoNothing
+        );
+        assert!(Trailer::TOKEN_IF_MISSING.validate("add".into()).is_ok());
+        assert!(Trailer::IF_MISSING.validate("replace".into()).is_err());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "fsck")]
+mod fsck {
+    use gix::{
+        config::tree::{Fsck, Key},
+        fsck::Severity,
+    };
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn msg_id() -> crate::Result {
+        assert_eq!(Fsck::MSG_ID.try_into_severity(bcow("Ignore"))?, Severity::Ignore);
+        assert_eq!(Fsck::MSG_ID.try_into_severity(bcow("warn"))?, Severity::Warn);
+        assert!(Fsck::MSG_ID.validate("error".into()).is_ok());
+        assert!(Fsck::MSG_ID.validate("info".into()).is_err());
+        assert_eq!(
+            Fsck::MSG_ID.try_into_severity(bcow("fatal")).unwrap_err().to_string(),
+            "The key \"fsck.<msg-id>=fatal\" was invalid"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "maintenance")]
+mod gc {
+    use std::time::{Duration, SystemTime};
+
+    use gix::config::tree::{Gc, Key};
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn reflog_expire() -> crate::Result {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100 * 86400);
+        let expiry = |value: &str| Gc::REFLOG_EXPIRE.try_into_expiry(bcow(value), now);
+        assert_eq!(expiry("never")?, None);
+        assert_eq!(expiry("false")?, None);
+        assert_eq!(expiry("now")?.map(|time| time.seconds), Some(100 * 86400));
+        assert_eq!(expiry("all")?.map(|time| time.seconds), Some(100 * 86400));
+        for relative in ["90 days", "90 days ago", "90.days.ago", "90.days"] {
+            assert_eq!(
+                expiry(relative)?.map(|time| time.seconds),
+                Some(10 * 86400),
+                "{relative}"
+            );
+        }
+        assert!(Gc::REFLOG_EXPIRE_UNREACHABLE.validate("2.weeks.ago".into()).is_ok());
+        assert_eq!(
+            expiry("sometimes").unwrap_err().to_string(),
+            "The key \"gc.reflogExpire=sometimes\" was invalid"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(feature = "maintenance")]
+mod maintenance {
+    use gix::{
+        config::tree::{Key, Maintenance},
+        maintenance::{Schedule, Strategy},
+    };
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn strategy() -> crate::Result {
+        assert_eq!(
+            Maintenance::STRATEGY.try_into_strategy(bcow("Incremental"))?,
+            Strategy::Incremental
+        );
+        assert_eq!(Maintenance::STRATEGY.try_into_strategy(bcow("none"))?, Strategy::None);
+        assert_eq!(
+            Maintenance::STRATEGY
+                .try_into_strategy(bcow("gc"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"maintenance.strategy=gc\" was invalid"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn task_schedule_and_auto() -> crate::Result {
+        assert_eq!(
+            Maintenance::TASK_SCHEDULE.try_into_schedule(bcow("daily"))?,
+            Schedule::Daily
+        );
+        assert!(Maintenance::TASK_SCHEDULE.validate("monthly".into()).is_err());
+        assert!(Maintenance::TASK_AUTO.validate("-1".into()).is_ok());
+        assert!(Maintenance::TASK_AUTO.validate("1k".into()).is_ok());
+        assert!(Maintenance::TASK_AUTO.validate("many".into()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function commit_at() {
+  local seconds=$1; shift
+  GIT_COMMITTER_DATE="$seconds +0000" GIT_AUTHOR_DATE="$seconds +0000" git commit -q --allow-empty "$@"
+}
+
+git init -q
+git checkout -q -b main
+commit_at 1000000100 -m c1
+commit_at 1000000200 -m c2
+git branch feature
+commit_at 1000000300 -m c3
+git branch side
+commit_at 1000000400 -m c4
+
+git checkout -q feature
+# a commit whose clock is skewed into the past
+commit_at 1000000050 -m f1
+commit_at 1000000500 -m f2
+
+git checkout -q side
+commit_at 1000000350 -m s1
+commit_at 1000000360 -m s2
+
+git checkout -q main
+GIT_COMMITTER_DATE="1000000600 +0000" GIT_AUTHOR_DATE="1000000600 +0000" git merge -q --no-ff -m "merge side" side
+commit_at 1000000700 -m c5
+
+git checkout -q --orphan unrelated
+commit_at 1000000800 -m u1
+git checkout -q main
+
+git clone -q --mirror --no-local . with-graph.git
+git -C with-graph.git commit-graph write --no-progress --reachable
+
+for pair in "main feature" "feature main" "main side" "side main" "main main~1" "main~1 main" \
+            "main~2 side" "feature side" "main unrelated" "unrelated feature" "main main"; do
+  set -- $pair
+  echo "$1 $2 $(git rev-list --left-right --count "$1...$2" | tr '\t' ' ')"
+done > baseline
@@ -0,0 +1,27 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+seq 1 10 > numbers
+git add numbers
+git commit -q -m "base"
+git tag base
+
+{ echo 1; echo two; seq 3 10; } > numbers
+git commit -q -am "second line" -m "with a body" --author "Jane Doe <jane@example.com>" --date "2005-04-07T22:13:13 +0200"
+{ echo 1; echo two; seq 3 9; echo ten; } > numbers
+echo new > new
+git add new
+git commit -q -am "last line and a new file" --author "Sam Smith <sam@example.com>" --date "2006-01-02T03:04:05 -0700"
+git format-patch -q --stdout base > ../series.mbox
+mv ../series.mbox .
+
+git reset -q --hard base
+{ echo 1; echo two; seq 3 10; } > numbers
+git commit -q -am "already applied"
+{ echo 1; echo two; seq 3 9; echo TEN; } > numbers
+git commit -q -am "conflicting last line"
+git tag conflicting
+
+git reset -q --hard base
+echo series.mbox > .git/info/exclude
@@ -0,0 +1,32 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+seq 1 10 > numbers
+printf '%s\n' a b c d e f g h i j > letters
+echo gone > gone
+echo "content to be moved" > old
+echo "#!/bin/sh" > script
+printf '\000\001binary' > bin
+git add .
+git commit -q -m "base"
+git tag base
+
+{ echo 1; echo two; seq 3 10; } > numbers
+echo new > new
+git rm -q gone
+git mv old renamed
+chmod +x script
+printf '\000\001binary, changed' > bin
+git add -A
+git diff --cached -M --binary > all-changes.patch
+git reset -q --hard
+
+{ seq 1 8; echo nine; echo 10; } > numbers
+printf '%s\n' a B c d e f g h i j > letters
+git diff > three-way.patch
+git checkout -q -- .
+
+{ seq 1 8; echo NINE; echo 10; } > numbers
+printf '%s\n' a b c d E f g h i j > letters
+git commit -q -am "local changes"
@@ -0,0 +1,25 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q worktree
+(cd worktree
+  mkdir -p dir/sub other
+  cat <<'CONTENT' > .gitattributes
+*.txt text
+*.bin binary
+dir/** custom=root
+CONTENT
+  cat <<'CONTENT' > dir/.gitattributes
+*.txt -text
+sub/* custom=dir
+CONTENT
+  echo "*.txt eol=crlf" > dir/sub/.gitattributes
+  echo "*.txt eol=lf" > other/.gitattributes
+  touch a.txt a.bin dir/a.txt dir/sub/a.txt dir/sub/a.bin other/a.txt
+  git add .
+  git commit -q -m "attributes at various levels"
+
+  git check-attr -a a.txt a.bin dir/a.txt dir/sub/a.txt dir/sub/a.bin other/a.txt > ../baseline
+)
+
+git clone -q --bare worktree bare.git
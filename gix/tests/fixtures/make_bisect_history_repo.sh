@@ -0,0 +1,33 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function commit() {
+  local name=${1:?}
+  echo "$name" > "$name"
+  git add "$name"
+  git commit -q -m "$name"
+  git tag "$name"
+}
+
+git init -q
+for name in c1 c2 c3; do
+  commit $name
+done
+
+git checkout -q -b side
+commit s1
+commit s2
+
+git checkout -q -
+commit c4
+git merge -q --no-edit side
+git tag m
+
+commit c5
+echo broken > broken
+git add broken
+commit c6
+commit c7
+commit c8
+
+git clone -q --bare . bare.git
@@ -0,0 +1,31 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+printf '1\n2\n3\n4\n5\n' > file
+git add file
+git commit -q -m "add file"
+
+printf '1\n2 changed\n3\n4\n5\n6\n' > file
+git commit -q -am "change second line and append"
+
+git checkout -q -b side
+printf '1 on side\n2 changed\n3\n4\n5\n6\n' > file
+git commit -q -am "change first line on side"
+
+git checkout -q main
+git mv file renamed
+printf '1\n2 changed\n3\n4 changed\n5\n6\n' > renamed
+git commit -q -am "rename and change fourth line"
+
+git merge -q --no-edit side
+
+printf '1 on side\n2 changed\n3\n4 changed\n5 reformatted\n6\n7\n' > renamed
+git commit -q -am "reformat fifth line and append"
+
+git blame --porcelain renamed > blame.baseline
+git blame --porcelain -L 2,4 renamed > blame-range.baseline
+git blame --porcelain --ignore-rev HEAD renamed > blame-ignore-rev.baseline
+git blame --porcelain HEAD~1 -- renamed > blame-merge.baseline
@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+for n in 1 2 3; do
+  echo "$n" > file
+  mkdir -p "dir$n" && echo "$n" > "dir$n/file"
+  git add . && git commit -q -m "c$n"
+done
+git tag -a -m "the first tag" v1 HEAD~1
+git checkout -q -b other HEAD~2
+echo other > other && git add other && git commit -q -m "other"
+git checkout -q main
+echo 4 > file && git commit -q -am "c4"
+
+git branch base main~2
+git bundle create -q base.bundle base 2>/dev/null
+git bundle create -q all.bundle --all 2>/dev/null
+git bundle create -q incremental.bundle main ^main~2 2>/dev/null
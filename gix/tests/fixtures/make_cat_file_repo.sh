@@ -0,0 +1,23 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+echo a > a && git add a && git commit -q -m first
+mkdir dir && echo b > dir/b && git add dir && git commit -q -m second
+git repack -adq
+echo c > c && git add c && git commit -q -m "third, loose"
+
+cat <<EOF2 > input
+HEAD
+HEAD:c
+HEAD~2:a
+does-not-exist
+HEAD~1^{tree}
+HEAD:dir/b
+$(git rev-parse HEAD:a)
+0000000000000000000000000000000000000000
+HEAD~2
+EOF2
+
+git cat-file --batch < input > batch.out
+git cat-file --batch-check < input > batch-check.out
@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+echo a > a
+echo unchanged > unchanged
+mkdir dir
+echo d > dir/file
+git add .
+git commit -q -m initial
+
+git checkout -q -b other
+echo a.other > a
+git rm -q dir/file
+echo new > new
+git add .
+git commit -q -m other
+
+git checkout -q main
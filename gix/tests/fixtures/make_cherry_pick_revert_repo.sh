@@ -0,0 +1,20 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+printf '1\n2\n3\n4\n5\n6\n7\n' > lines
+echo c > conflict
+git add .
+git commit -q -m initial
+
+git checkout -q -b topic
+printf '1 topic\n2\n3\n4\n5\n6\n7\n' > lines
+git commit -q -am "change first line"
+echo c.topic > conflict
+git commit -q -am "change conflict"
+
+git checkout -q main
+printf '1\n2\n3\n4\n5\n6\n7 main\n' > lines
+echo c.main > conflict
+git commit -q -am "change last line and conflict"
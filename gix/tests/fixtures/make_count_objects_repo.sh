@@ -0,0 +1,19 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q empty
+
+git init -q repo
+(cd repo
+  echo a > a && git add a && git commit -q -m first
+  git repack -q
+  echo b > b && git add b && git commit -q -m second
+  git repack -q
+  echo c > c && git add c && git commit -q -m third
+
+  touch .git/objects/pack/pack-0000000000000000000000000000000000000000.idx
+  echo garbage > .git/objects/pack/tmp_pack_garbage
+  mkdir -p .git/objects/17 && echo garbage > .git/objects/17/tmp_obj_garbage
+
+  git count-objects -v > ../count-objects.out 2>/dev/null
+)
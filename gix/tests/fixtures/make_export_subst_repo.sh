@@ -0,0 +1,30 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+cat <<'CONTENT' > version
+commit: $Format:%H$ ($Format:%h$)
+tree: $Format:%T$
+parents: $Format:%P$
+author: $Format:%an <%ae> %ad$
+committer: $Format:%cn <%ce> %cI$
+subject: $Format:%s$
+unknown: $Format:%Z %%$
+unterminated: $Format:%H
+CONTENT
+cp version not-substituted
+echo "export-ignore" > ignored
+
+cat <<'CONTENT' > .gitattributes
+/version export-subst
+/ignored export-ignore
+CONTENT
+
+git add .
+git commit -q -m "first"
+echo "change" >> ignored
+git commit -q -am "second" -m "with a body"
+
+git archive HEAD | tar -xOf - version > version.baseline
+git archive HEAD | tar -tf - > paths.baseline
@@ -0,0 +1,58 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+mkdir -p dir/sub
+echo a > dir/sub/file
+echo "with space" > "a file"
+printf 'ü\n' > ümlaut
+echo '#!/bin/sh' > script
+chmod +x script
+ln -s dir/sub/file link
+git add .
+git commit -q -m "initial"
+
+git checkout -q -b topic
+echo more >> "a file"
+git rm -q link
+git commit -q -m "change on topic"
+
+git checkout -q main
+git rm -q -r dir
+echo replaced > dir
+git add dir
+git commit -q -m "replace directory with file" -m "With a body."
+git branch other
+
+git merge -q --no-ff -m "merge topic" topic
+echo b > b
+git add b
+git commit -q -m "add b" --author "Other Author <other@example.com>"
+git tag -a -m "first release" v1
+git tag -a -m "tag of a tag" nested v1 2>/dev/null
+git tag light HEAD~1
+
+git fast-export --mark-tags main topic v1 nested light other > all.stream
+
+git fast-export --mark-tags --export-marks=other.marks other > other.stream
+git fast-export --mark-tags --import-marks=other.marks main topic > incremental.stream
+
+git mktag <<EOF >signed-tag-id
+object $(git rev-parse main)
+type commit
+tag signed
+tagger T Agger <tagger@example.com> 946684800 +0000
+
+signed message
+-----BEGIN PGP SIGNATURE-----
+
+not really a signature
+-----END PGP SIGNATURE-----
+EOF
+git update-ref refs/tags/signed "$(cat signed-tag-id)"
+git fast-export --mark-tags --import-marks=other.marks --signed-tags=strip signed > signed-stripped.stream
+git fast-export --mark-tags --import-marks=other.marks --signed-tags=verbatim signed > signed-verbatim.stream
+
+git init -q imported
+git -C imported fast-import --quiet < all.stream
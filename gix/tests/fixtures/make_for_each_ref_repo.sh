@@ -0,0 +1,50 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+echo a > a && git add a && git commit -q -m "first" -m "with a body
+of two lines"
+git branch feature
+echo b > b && git add b && git commit -q -m "second
+continued subject"
+git tag -a -m "annotated tag" v1
+git tag light HEAD~1
+git tag feature HEAD~1
+git checkout -q feature
+echo c > c && git add c && git commit -q -m "on feature"
+git checkout -q main
+
+git config remote.origin.url https://example.com/repo
+git config remote.origin.fetch '+refs/heads/*:refs/remotes/origin/*'
+git config branch.main.remote origin
+git config branch.main.merge refs/heads/main
+git config branch.feature.remote origin
+git config branch.feature.merge refs/heads/feature
+git update-ref refs/remotes/origin/main main~1
+git update-ref refs/remotes/origin/feature main
+git symbolic-ref refs/remotes/origin/HEAD refs/remotes/origin/main
+git branch gone main~1
+git config branch.gone.remote origin
+git config branch.gone.merge refs/heads/gone
+git pack-refs --all
+git branch loose main
+
+formats=(
+  "%(objectname) %(objecttype)	%(refname)"
+  "%(refname:short) %(refname:lstrip=1) %(refname:lstrip=-1) %(refname:rstrip=1) %(refname:rstrip=-2) %(refname:strip=5)|"
+  "%(HEAD)%(objectname:short) %(objectname:short=10) %(objectsize) %(symref) %(symref:short)"
+  "%(upstream) %(upstream:short) %(upstream:track) %(upstream:trackshort) %(upstream:track,nobracket) %(upstream:remotename) %(upstream:remoteref) %(push:short)"
+  "%(tree) %(parent) %(numparent) %(object) %(type) %(tag) %(*objectname) %(*objecttype) %(*subject)"
+  "%(author)|%(authorname)|%(authoremail)|%(authoremail:trim)|%(authoremail:localpart)|%(authordate)|%(committerdate:short)|%(committerdate:iso)|%(committerdate:iso-strict)|%(committerdate:rfc)|%(committerdate:raw)|%(committerdate:unix)"
+  "%(tagger)|%(taggername)|%(taggerdate)|%(creator)|%(creatordate)"
+  "%(subject)|%(contents:subject)|%(body)|%(contents:body)|%(contents)"
+  "[%(align:12)%(refname:short)%(end)][%(align:width=12,position=right)%(objecttype)%(end)][%(align:position=middle,width=9)%(HEAD)%(end)]"
+  "%(if)%(upstream)%(then)tracks %(upstream:short)%(else)local%(end) %(if:equals=commit)%(objecttype)%(then)C%(end)%(if:notequals=main)%(refname:short)%(then)!%(else)=%(end)%(if)%(symref)%(then)%(if)%(HEAD)%(then)x%(end)%(end)"
+  "%%%(refname:short)%00%41%(color:red)!%(color:bold blue ul)?%(color:reset)"
+)
+for i in "${!formats[@]}"; do
+  printf '%s' "${formats[$i]}" > "format-$i"
+  git for-each-ref --format="${formats[$i]}" > "format-$i.out"
+done
+git for-each-ref --color=always --format="${formats[10]}" > "format-10-color.out"
+git for-each-ref --format="${formats[1]}" refs/heads 'refs/tags/l*' refs/remotes/origin/H > patterns.out
@@ -0,0 +1,44 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+seq 1 20 > numbers
+echo "to be removed" > gone
+mkdir -p dir/sub
+printf '%s\n' a b c d e f g h > dir/sub/old
+echo '#!/bin/sh' > script
+git add .
+git commit -q -m "base"
+git tag base
+
+{ echo 1; echo two; seq 3 14; echo fifteen; seq 16 20; } > numbers
+git commit -q -am "change two distant lines" -m "The body explains
+why over two lines."
+
+echo new > new
+git rm -q gone
+git mv dir/sub/old dir/sub/renamed
+chmod +x script
+git add -A
+git commit -q -m "add, remove, rename and make executable"
+
+echo more >> new
+GIT_AUTHOR_NAME="Jürgen Müller" git commit -q -am "Ümlauts in a subject that is long enough to need more than one encoded word"
+
+echo 21 >> numbers
+git commit -q -am "a very long subject line that goes on and on and on to see how it is wrapped by format-patch" --author "J. Doe <j@example.com>"
+git tag text
+
+git format-patch -q --signature=sig -o patches base
+git format-patch -q --stdout --signature=sig -1 HEAD~3 > patches/single.patch
+
+printf '\000\001binary' > bin
+git add bin
+git commit -q -m "add binary"
+
+git checkout -q -b attributes
+printf 'text\n' > marked.txt
+printf 'a\000b\n' > forced.dat
+git add marked.txt forced.dat
+git commit -q -m "add files whose attributes decide if they are binary"
+git checkout -q -b applied base
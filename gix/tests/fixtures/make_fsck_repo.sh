@@ -0,0 +1,34 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q clean
+(cd clean
+  echo a > a && git add a && git commit -q -m first
+  git tag -a -m "annotated" v1
+  echo b > b && git add b && git commit -q -m second
+  git checkout -q -b topic
+  echo c > c && git add c && git commit -q -m third
+  git checkout -q -
+)
+
+git init -q corrupt
+(cd corrupt
+  echo a > a && git add a && git commit -q -m first
+
+  tree=$(git rev-parse HEAD^{tree})
+  printf 'tree %s\nauthor A <a@example.com> yesterday +0000\ncommitter C <c@example.com> 1 +0000\n\nbad date\n' "$tree" \
+    | git hash-object -t commit --literally -w --stdin > bad-commit.id
+  git update-ref refs/heads/bad "$(cat bad-commit.id)"
+
+  blob=$(git rev-parse HEAD:a)
+  { printf '100644 .GIT\0'; printf '%b' "$(echo "$blob" | sed 's/../\\x&/g')"; } \
+    | git hash-object -t tree --literally -w --stdin > dotgit-tree.id
+  git update-ref refs/tags/dotgit "$(cat dotgit-tree.id)"
+
+  echo missing > missing && git add missing && git commit -q -m "missing blob"
+  git rev-parse HEAD:missing > missing-blob.id
+  missing_blob=$(cat missing-blob.id)
+  rm ".git/objects/${missing_blob:0:2}/${missing_blob:2}"
+
+  git fsck > fsck.out 2>&1 || true
+)
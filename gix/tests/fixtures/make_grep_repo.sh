@@ -0,0 +1,28 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+mkdir -p src docs
+printf 'fn main() {\n    let needle = 1;\n}\n' > src/main.rs
+printf 'fn needles() {}\nNEEDLE in caps\r\nneedle_case and a needle\n' > src/lib.rs
+printf 'no match here\n' > docs/readme
+printf 'needle\000binary\n' > data.bin
+printf 'needle in a text file that is marked as binary\n' > marked.txt
+printf 'needle\000 in a file with forced diff\n' > forced.dat
+printf 'needle in a file with a binary diff driver\n' > driver.txt
+echo target > link-target
+ln -s needle-link-target link
+printf 'marked.txt -diff\nforced.dat diff\ndriver.txt diff=bin\nsrc/lib.rs -merge\n' > .gitattributes
+git config diff.bin.binary true
+git add .
+git commit -q -m "init"
+
+printf 'needle only in the index\n' > docs/readme
+git add docs/readme
+printf 'needle in the worktree\n' > docs/readme
+printf 'untracked needle\n' > untracked
+
+git grep -n --column needle > grep-worktree.out
+git grep -n --column --cached needle > grep-index.out || true
+git grep -n --column -w -i needle HEAD -- src > grep-tree-src.out
+git grep -n --column -a needle > grep-text.out
@@ -0,0 +1,65 @@
+#!/bin/bash
+set -eu -o pipefail
+
+time=1000000000
+function commit() {
+  time=$((time + 60))
+  GIT_AUTHOR_DATE="$time +0000" GIT_COMMITTER_DATE="$time +0000" git commit -q "$@"
+}
+
+git init -q
+git checkout -q -b main
+
+mkdir dir
+echo a > a
+echo b > dir/b
+seq 1 20 > file
+git add .
+commit -m "initial"
+
+echo a2 > a
+git add a
+commit -m "change a"
+
+git checkout -q -b side
+echo b2 > dir/b
+git add dir/b
+commit -m "change dir/b on side"
+
+echo other > other
+git add other
+commit -m "add other on side"
+
+git checkout -q main
+git mv file moved
+echo 21 >> moved
+git add moved
+commit -m "rename file"
+
+echo a3 > a
+git add a
+commit -m "change a again"
+
+GIT_AUTHOR_DATE="$((time + 60)) +0000" GIT_COMMITTER_DATE="$((time + 60)) +0000" git merge -q --no-edit side
+time=$((time + 60))
+
+git checkout -q -b same main^1~1
+echo a3 > a
+git add a
+commit -m "change a the same way"
+git checkout -q main
+GIT_AUTHOR_DATE="$((time + 60)) +0000" GIT_COMMITTER_DATE="$((time + 60)) +0000" git merge -q --no-edit same
+time=$((time + 60))
+
+echo 22 >> moved
+git add moved
+commit -m "change moved"
+
+git log --format=%H > all.baseline
+git log --format=%H -- a > a.baseline
+git log --format=%H --full-history -- a > a-full-history.baseline
+git log --format=%H -- dir > dir.baseline
+git log --format=%H --first-parent -- dir > dir-first-parent.baseline
+git log --format=%H -- moved > moved.baseline
+git log --format=%H --follow -- moved > moved-follow.baseline
+git log --format=%H side..main -- a > a-side-main.baseline
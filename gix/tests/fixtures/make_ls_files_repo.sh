@@ -0,0 +1,37 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q changes
+(cd changes
+  mkdir dir
+  echo a > a && echo b > dir/b && echo c > dir/c
+  echo deleted > deleted && echo skipped > skipped
+  echo "ignored*" > .gitignore
+  git add . && git commit -q -m "initial"
+  git update-index --skip-worktree skipped
+
+  echo changed > a && echo changed > dir/b
+  rm deleted skipped
+  echo untracked > untracked && echo untracked > dir/untracked
+  mkdir new && echo untracked > new/file
+  echo ignored > ignored-file && echo ignored > dir/ignored-file
+
+  git ls-files -t --cached --deleted --modified --others --exclude-standard > ../changes.out
+  git ls-files -t --deleted --modified --others --exclude-standard dir > ../changes-dir.out
+)
+
+git clone -q --bare changes bare.git
+
+git init -q conflict
+(cd conflict
+  echo base > file && echo other > other
+  git add . && git commit -q -m "base"
+  git checkout -q -b side
+  echo side > file && git commit -q -am "side"
+  git checkout -q main
+  echo main > file && git commit -q -am "main"
+  git merge side >/dev/null || true
+
+  git ls-files --stage > ../conflict-stage.out
+  git ls-files -t > ../conflict.out
+)
@@ -0,0 +1,17 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q worktree
+(cd worktree
+  cat <<EOF >.mailmap
+Canonical Author <canonical@example.com> <author@example.com>
+EOF
+  git add .mailmap
+  git commit -q -m "add mailmap"
+
+  cat <<EOF >extra.mailmap
+Canonical Committer <committer@example.com>
+EOF
+)
+
+git clone -q --bare worktree bare.git
@@ -0,0 +1,51 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  echo a > a && git add a && git commit -q -m first
+  git tag -a -m "annotated" v1
+  git repack -q -d
+
+  # objects of the second pack are kept loose as well
+  echo b > b && git add b && git commit -q -m second
+  git repack -q
+
+  git checkout -q -b one
+  echo one > one && git add one && git commit -q -m one
+  git checkout -q -b two main
+  echo two > two && git add two && git commit -q -m two
+  git checkout -q main
+  echo three > three && git add three && git commit -q -m three
+  git merge -q --no-edit one two >/dev/null
+
+  echo dropped > dropped && git add dropped && git commit -q -m dropped
+  git rev-parse HEAD > dropped.id
+  git reset -q --hard HEAD~1
+)
+
+git clone -q --depth 1 "file://$PWD/repo" shallow
+
+git init -q unreachable
+(cd unreachable
+  echo a > a && git add a && git commit -q -m first
+  git worktree add -q ../worktree
+  echo b > b && git add b && git commit -q -m second
+  echo reflog > c && git add c && git commit -q -m "reachable through reflog" && git reset -q --hard HEAD~1
+  echo unreachable-commit > d && git add d
+  git commit-tree -p HEAD -m unreachable "$(git write-tree)" > unreachable-commit.id
+  git reset -q
+  # put all objects, including the unreachable commit, into a single pack
+  git pack-objects -q --revs --all --reflog .git/objects/pack/pack < unreachable-commit.id > /dev/null
+  git prune-packed
+
+  echo staged > staged && git add staged
+  echo loose | git hash-object -w --stdin > loose.id
+  echo kept | git hash-object -w --stdin | git pack-objects -q .git/objects/pack/pack > kept-pack.id
+  touch ".git/objects/pack/pack-$(cat kept-pack.id).keep"
+  git prune-packed
+)
+
+(cd worktree
+  echo staged-in-worktree > staged && git add staged
+)
@@ -0,0 +1,41 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git commit -q --allow-empty -m first
+git commit -q --allow-empty -m second
+git commit -q --allow-empty -m third
+
+git notes add -m "note for first" HEAD~2
+git notes add -m "note for second" HEAD~1
+
+# diverge from the common base in a way that merges cleanly
+git update-ref refs/notes/other refs/notes/commits
+git notes --ref other add -m "other note for third" HEAD
+git notes --ref other remove HEAD~2 2>/dev/null
+
+# diverge from the common base by changing the same note
+git update-ref refs/notes/conflicting refs/notes/commits
+git notes --ref conflicting add -f -m "conflicting note for second" HEAD~1 2>/dev/null
+
+git notes add -f -m "changed note for second" HEAD~1 2>/dev/null
+
+# many notes on many commits, written with a fan-out by fast-import
+{
+  for i in $(seq 300); do
+    echo "commit refs/heads/many"
+    echo "mark :$i"
+    echo "committer c <c@example.com> $((1000000000 + i)) +0000"
+    echo "data ${#i}"
+    echo -n "$i"
+  done
+  echo "commit refs/notes/dense"
+  echo "committer c <c@example.com> 1000000000 +0000"
+  echo "data 5"
+  echo -n "dense"
+  for i in $(seq 300); do
+    echo "N inline :$i"
+    echo "data 5"
+    echo "note"
+  done
+} | git fast-import --quiet
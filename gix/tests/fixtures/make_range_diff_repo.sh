@@ -0,0 +1,28 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+seq 1 20 > numbers
+git add numbers
+git commit -q -m "base"
+git tag base
+
+git checkout -q -b v1
+sed -i 's/^3$/3 three/' numbers
+git commit -q -am "change three" -m "With a body."
+echo eleven >> numbers
+git commit -q -am "append eleven"
+echo "to be dropped" > gone
+git add gone
+git commit -q -m "add gone"
+
+git checkout -q -b v2 base
+sed -i 's/^3$/3 THREE/' numbers
+git commit -q -am "change three" -m "With a better body."
+echo eleven >> numbers
+git commit -q -am "append eleven"
+echo "n" > n
+git add n
+git commit -q -m "add n"
+
+git range-diff --no-color base..v1 base..v2 > range-diff.out
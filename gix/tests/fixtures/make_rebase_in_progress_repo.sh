@@ -0,0 +1,22 @@
+#!/bin/bash
+set -eu -o pipefail
+
+backend="${1:?First argument is the rebase backend, 'merge' or 'apply'}"
+
+git init -q
+
+echo base > file
+git add file
+git commit -q -m base
+git branch other
+main=$(git symbolic-ref --short HEAD)
+
+echo ours > file
+git commit -q -am ours
+
+git checkout -q other
+echo theirs > file
+git commit -q -am theirs
+
+# stop on the conflict to leave the rebase in progress
+git rebase --"$backend" "$main" || true
@@ -0,0 +1,44 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+printf '1\n2\n3\n4\n5\n6\n7\n' > lines
+echo c > conflict
+echo o > other
+git add .
+git commit -q -m initial
+git branch clean
+git branch conflicting
+git branch dup
+
+printf '1\n2\n3\n4\n5\n6\n7 main\n' > lines
+git commit -q -am "change last line"
+echo c.main > conflict
+git commit -q -am "change conflict on main"
+
+git checkout -q clean
+printf '1 clean\n2\n3\n4\n5\n6\n7\n' > lines
+git commit -q -am "change first line"
+echo new > new
+git add new
+git commit -q -m "add new file"
+git checkout -q -b stacked
+echo stacked > stacked
+git add stacked
+git commit -q -m "add stacked file"
+
+git checkout -q conflicting
+printf '1 conflicting\n2\n3\n4\n5\n6\n7\n' > lines
+git commit -q -am "change first line"
+echo c.topic > conflict
+git commit -q -am "change conflict"
+
+git checkout -q dup
+printf '1\n2\n3\n4\n5\n6\n7 main\n' > lines
+git commit -q -am "change last line as well"
+echo dup > dup
+git add dup
+git commit -q -m "add dup file"
+
+git checkout -q main
@@ -111,6 +111,11 @@ git init base
 git clone --shared --depth 2 file://$PWD/base base.shallow
 
 
+git clone --shared base allow-filter
+(cd allow-filter
+  git config uploadpack.allowFilter true
+)
+
 git clone --shared base clone
 (cd clone
   git remote add myself .
@@ -0,0 +1,32 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function commit_at() {
+  local seconds=$1; shift
+  GIT_COMMITTER_DATE="$seconds +0000" GIT_AUTHOR_DATE="$seconds +0000" git commit -q "$@"
+}
+
+git init -q
+mkdir -p dir/sub
+echo a > a && echo b > dir/b && echo c > dir/sub/c
+git add . && commit_at 946684800 -m first
+git tag -a -m "annotated" v1
+
+echo changed > dir/b && git add dir/b
+git update-index --add --cacheinfo 160000,"$(git rev-parse HEAD)",submodule
+commit_at 946684900 -m second
+
+git checkout -q -b side v1
+echo side > side && git add side && commit_at 946685000 -m side
+
+git checkout -q main
+echo c > dir/c && git add dir/c && commit_at 946685100 -m third
+git merge -q --no-ff -m merge side -- >/dev/null
+GIT_COMMITTER_DATE="946685200 +0000" git commit -q --amend --no-edit
+
+git rev-list HEAD > commits.out
+git rev-list --objects HEAD > objects.out
+git rev-list --objects HEAD ^v1 > objects-hidden.out
+git rev-list --objects --boundary HEAD ^v1 > objects-boundary.out
+git rev-list --objects --boundary HEAD ^side -- > objects-boundary-side.out
+git rev-list --objects v1 "HEAD^{tree}" > objects-tag-and-tree.out
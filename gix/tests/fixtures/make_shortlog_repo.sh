@@ -0,0 +1,36 @@
+#!/bin/bash
+set -eu -o pipefail
+
+count=0
+function commit() {
+  local name=$1 email=$2 message=$3
+  count=$((count + 1))
+  echo "$message" > "file-$count"
+  git add .
+  GIT_AUTHOR_NAME="$name" GIT_AUTHOR_EMAIL="$email" GIT_COMMITTER_NAME="$name" GIT_COMMITTER_EMAIL="$email" \
+    GIT_COMMITTER_DATE="$((946684800 + count * 60)) +0000" git commit -q -m "$message"
+}
+
+git init -q
+commit "Zed" zed@example.com "first"
+git tag first
+commit "Alice" alice@example.com "second"
+commit "alice" alice@old.example.com "third
+continued on the next line
+
+and a body"
+commit "Bob" bob@example.com "fourth"
+
+git checkout -q -b side HEAD~1
+commit "Bob" bob@example.com "on the side"
+git checkout -q -
+GIT_AUTHOR_NAME=Zed GIT_AUTHOR_EMAIL=zed@example.com GIT_COMMITTER_DATE="946688400 +0000" \
+  git merge -q --no-ff -m "merge side" side
+
+cat <<EOF2 > .mailmap
+Alice <alice@example.com> <alice@old.example.com>
+EOF2
+
+git shortlog HEAD > shortlog.out
+git shortlog -sn --no-merges first..HEAD > shortlog-sn.out
+git shortlog -se -c HEAD > shortlog-sec.out
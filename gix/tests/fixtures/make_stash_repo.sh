@@ -0,0 +1,17 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+echo a > a
+echo b > b
+mkdir dir
+echo c > dir/c
+git add .
+git commit -q -m initial
+
+git checkout -q -b conflicting
+echo a.conflicting > a
+git commit -q -am "change a"
+
+git checkout -q main
@@ -13,3 +13,20 @@ git init -q untracked-only
   mkdir new
   touch new/untracked subdir/untracked
 )
+
+git init -q staged-changes
+(cd staged-changes
+  echo content >modified
+  echo "content that is long enough to be recognized as rename" >to-be-renamed
+  touch removed
+
+  git add .
+  git commit -q -m init
+
+  echo change >>modified
+  git mv to-be-renamed renamed
+  git rm -q removed
+  echo new >added
+  git add .
+  echo worktree-only-change >>added
+)
@@ -0,0 +1,32 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+echo /module1 >> .git/info/exclude
+echo /module2 >> .git/info/exclude
+
+git init -q module2
+(cd module2
+  echo nested > file
+  git add file
+  git commit -q -m "nested"
+)
+
+git init -q module1
+(cd module1
+  echo first > file
+  git add file
+  git commit -q -m "first"
+  git submodule add ../module2 nested
+  git commit -q -m "add nested submodule"
+  echo second > file
+  git commit -q -am "second"
+)
+
+echo content > file
+git add file
+git submodule add ./module1 m1
+git commit -q -m "add submodule"
+
+git submodule deinit -q -f m1
+rm -Rf .git/modules/m1
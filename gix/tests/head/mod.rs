@@ -53,3 +53,94 @@ mod into_remote {
         Ok(())
     }
 }
+
+mod edit {
+    use gix::head::Kind;
+
+    use crate::{freeze_time, restricted_and_git};
+
+    fn empty_repo() -> crate::Result<(gix_testtools::tempfile::TempDir, gix::Repository)> {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::ThreadSafeRepository::init_opts(
+            &tmp,
+            gix::create::Kind::WithWorktree,
+            Default::default(),
+            restricted_and_git(),
+        )?
+        .to_thread_local();
+        Ok((tmp, repo))
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn commit_works_on_unborn_and_born_heads() -> crate::Result {
+        let _env = freeze_time();
+        let (_tmp, repo) = empty_repo()?;
+        let empty_tree_id = repo.write_object(gix::objs::Tree::empty())?.detach();
+
+        let mut head = repo.head()?;
+        assert!(head.is_unborn());
+        let first = head.commit("initial", empty_tree_id)?;
+        assert!(!head.is_unborn(), "the branch was created");
+        assert_eq!(head.id(), Some(first));
+        assert_eq!(repo.head_id()?, first);
+        assert_eq!(first.object()?.into_commit().parent_ids().count(), 0);
+
+        let second = head.commit("second", empty_tree_id)?;
+        assert_eq!(
+            second.object()?.into_commit().parent_ids().collect::<Vec<_>>(),
+            [first],
+            "the previous commit is the parent"
+        );
+        assert_eq!(
+            repo.find_reference("refs/heads/main")?.id(),
+            second,
+            "the branch HEAD points to is updated"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn detach_and_attach() -> crate::Result {
+        let _env = freeze_time();
+        let (_tmp, repo) = empty_repo()?;
+        let empty_tree_id = repo.write_object(gix::objs::Tree::empty())?.detach();
+        let mut head = repo.head()?;
+        let first = head.commit("initial", empty_tree_id)?.detach();
+
+        head.detach_at(first, "detach")?;
+        assert!(head.is_detached());
+        assert!(repo.head()?.is_detached(), "the change was written");
+        let detached_commit = head.commit("detached", empty_tree_id)?;
+        assert!(matches!(head.kind, Kind::Detached { target, .. } if target == detached_commit));
+        assert_eq!(
+            repo.find_reference("refs/heads/main")?.id(),
+            first,
+            "commits on a detached HEAD don't affect branches"
+        );
+
+        head.attach_to_branch("refs/heads/main", "attach")?;
+        assert_eq!(head.referent_name().expect("attached").as_bstr(), "refs/heads/main");
+        assert_eq!(head.id().map(gix::Id::detach), Some(first));
+        assert_eq!(
+            repo.head()?
+                .log_iter()
+                .rev()?
+                .expect("log present")
+                .next()
+                .expect("at least one line")?
+                .message,
+            "attach",
+            "the move of HEAD is recorded"
+        );
+
+        head.attach_to_branch("refs/heads/new", "attach to unborn")?;
+        assert!(head.is_unborn());
+        assert!(repo.head()?.is_unborn(), "branches that don't exist yet leave HEAD unborn");
+        let orphan = head.commit("orphan", empty_tree_id)?;
+        assert_eq!(orphan.object()?.into_commit().parent_ids().count(), 0);
+        assert_eq!(repo.find_reference("refs/heads/new")?.id(), orphan);
+        Ok(())
+    }
+}
@@ -165,4 +165,89 @@ mod ancestors {
         }
         Ok(())
     }
+
+    fn baseline_ids(
+        repo: &gix::Repository,
+        name: &str,
+        mut select: impl FnMut(&str) -> Option<&str>,
+    ) -> crate::Result<Vec<gix_hash::ObjectId>> {
+        let content = std::fs::read_to_string(repo.work_dir().expect("non-bare").join(name))?;
+        Ok(content
+            .lines()
+            .filter_map(&mut select)
+            .map(|hex| gix_hash::ObjectId::from_hex(hex.as_bytes()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn hidden_with_boundary() -> crate::Result {
+        let repo = crate::named_repo("make_rev_list_repo.sh")?;
+        let hidden = repo.rev_parse_single("v1^{commit}")?.detach();
+        for use_commit_graph in [false, true] {
+            let commits = repo
+                .rev_walk(Some(repo.head_id()?))
+                .sorting(commit::simple::Sorting::ByCommitTimeNewestFirst)
+                .use_commit_graph(use_commit_graph)
+                .hide(Some(hidden))
+                .boundary(true)
+                .all()?
+                .map(|info| info.map(|info| (info.id, info.is_boundary)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let expected = baseline_ids(&repo, "objects-boundary.out", |line| {
+                (!line.contains(' ') && !line.starts_with('-')).then_some(line)
+            })?
+            .into_iter()
+            .map(|id| (id, false))
+            .chain(
+                baseline_ids(&repo, "objects-boundary.out", |line| line.strip_prefix('-'))?
+                    .into_iter()
+                    .map(|id| (id, true)),
+            )
+            .collect::<Vec<_>>();
+            assert_eq!(commits, expected, "boundary commits come last");
+            assert_eq!(commits.last(), Some(&(hidden, true)));
+
+            let without_boundary = repo
+                .rev_walk(Some(repo.head_id()?))
+                .use_commit_graph(use_commit_graph)
+                .hide(Some(hidden))
+                .all()?
+                .count();
+            assert_eq!(
+                without_boundary,
+                commits.len() - 1,
+                "hidden commits are never returned by default"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn pathspec() -> crate::Result {
+        let repo = crate::named_repo("make_log_repo.sh")?;
+        let index = repo.index_or_empty()?;
+        for use_commit_graph in [false, true] {
+            let pathspec = repo.pathspec(
+                true,
+                Some("a"),
+                true,
+                &index,
+                gix_worktree::stack::state::attributes::Source::IdMapping,
+            )?;
+            let commits = repo
+                .rev_walk(Some(repo.head_id()?))
+                .sorting(commit::simple::Sorting::ByCommitTimeNewestFirst)
+                .use_commit_graph(use_commit_graph)
+                .with_pathspec(pathspec)
+                .all()?
+                .map(|info| info.map(|info| info.id))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(
+                commits,
+                baseline_ids(&repo, "a-full-history.baseline", |line| Some(line))?,
+                "merges are listed if they change the path compared to any of their parents"
+            );
+        }
+        Ok(())
+    }
 }
@@ -22,9 +22,12 @@ mod needs_feature {
         let reg2 = unsafe { gix::interrupt::init_handler(2, || V2.store(true, Ordering::SeqCst)) }
             .expect("multi-initialization is OK");
         assert!(!V2.load(Ordering::Relaxed));
+        let token = gix::interrupt::token();
+        assert!(!token.is_triggered());
 
         signal_hook::low_level::raise(SIGTERM).expect("signal can be raised");
         assert!(gix::interrupt::is_triggered(), "this happens automatically");
+        assert!(token.is_triggered(), "tokens observe the signal handler");
         assert_eq!(V1.load(Ordering::Relaxed), 1, "the first trigger is invoked");
         assert!(!V2.load(Ordering::Relaxed), "the second trigger was ignored");
 
@@ -0,0 +1,53 @@
+use crate::util::{hex_to_id, named_subrepo_opts};
+
+fn tag_of_tag() -> crate::Result<(gix::Repository, gix::ObjectId)> {
+    let repo = named_subrepo_opts("make_head_repos.sh", "tag-symbolic", gix::open::Options::isolated())?;
+    let id = repo.rev_parse_single("refs/tags/point-at-tag-start")?.detach();
+    Ok((repo, id))
+}
+
+#[test]
+fn peeling_follows_nested_tags() -> crate::Result {
+    let expected_commit = hex_to_id("fafd9d08a839d99db60b222cd58e2e0bfaf1f7b2");
+    let (repo, id) = tag_of_tag()?;
+    let tag = || -> crate::Result<gix::Tag<'_>> { Ok(repo.find_object(id)?.try_into_tag()?) };
+
+    assert_eq!(tag()?.peel_tags_to_end()?.id, expected_commit);
+    assert_eq!(tag()?.peel_to_commit()?.id, expected_commit);
+    assert_eq!(tag()?.peel_to_kind(gix::object::Kind::Commit)?.id, expected_commit);
+    let tree = tag()?.peel_to_tree()?;
+    assert_eq!(tree.id, repo.find_object(expected_commit)?.into_commit().tree_id()?);
+
+    let err = tag()?
+        .peel_to_kind(gix::object::Kind::Blob)
+        .expect_err("there is no blob to peel to");
+    assert!(matches!(err, gix::object::peel::to_kind::Error::NotFound { .. }));
+    Ok(())
+}
+
+#[test]
+fn typed_objects_convert_back_and_know_their_header() -> crate::Result {
+    let (repo, id) = tag_of_tag()?;
+    let object = repo.find_object(id)?;
+    let header = object.header();
+    assert_eq!(header.kind, gix::object::Kind::Tag);
+    assert_eq!(header.size, object.data.len() as u64);
+
+    let tag = object.into_tag();
+    assert_eq!(tag.header(), header, "the typed header is the same");
+    let object: gix::Object<'_> = tag.into();
+    assert_eq!(object.id, id);
+    assert_eq!(object.kind, gix::object::Kind::Tag);
+
+    let tree = object.peel_to_tree()?;
+    let header = tree.header();
+    assert_eq!(header.kind, gix::object::Kind::Tree);
+    let object: gix::Object<'_> = tree.into();
+    assert_eq!(object.kind, gix::object::Kind::Tree);
+    assert_eq!(
+        repo.find_header(object.id)?.size(),
+        header.size,
+        "the header matches what's in the object database"
+    );
+    Ok(())
+}
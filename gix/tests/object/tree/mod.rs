@@ -25,3 +25,68 @@ fn lookup_entry_by_path() -> crate::Result {
     );
     Ok(())
 }
+
+mod entries_recursive {
+    use crate::util::named_repo;
+
+    fn paths(iter: gix::object::tree::entries_recursive::Iter<'_>) -> crate::Result<Vec<String>> {
+        Ok(iter
+            .map(|entry| entry.map(|entry| entry.filepath.to_string()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    fn depth_first_with_trees_before_their_entries() -> crate::Result {
+        let repo = named_repo("make_rev_list_repo.sh")?;
+        let tree = repo.head_commit()?.tree()?;
+        assert_eq!(
+            paths(tree.entries_recursive())?,
+            ["a", "dir", "dir/b", "dir/c", "dir/sub", "dir/sub/c", "side", "submodule"],
+            "it's the same order as `git ls-tree -r -t`, and submodules aren't traversed"
+        );
+
+        let modes: Vec<_> = tree
+            .entries_recursive()
+            .map(|entry| entry.map(|entry| entry.mode.kind()))
+            .collect::<Result<_, _>>()?;
+        use gix::object::tree::EntryKind::*;
+        assert_eq!(modes, [Blob, Tree, Blob, Blob, Tree, Blob, Blob, Commit]);
+        Ok(())
+    }
+
+    #[test]
+    fn max_depth() -> crate::Result {
+        let repo = named_repo("make_rev_list_repo.sh")?;
+        let tree = repo.head_commit()?.tree()?;
+        assert_eq!(
+            paths(tree.entries_recursive().with_max_depth(Some(0)))?,
+            ["a", "dir", "side", "submodule"],
+            "trees at the maximum depth are returned, but not traversed"
+        );
+        assert_eq!(
+            paths(tree.entries_recursive().with_max_depth(Some(1)))?,
+            ["a", "dir", "dir/b", "dir/c", "dir/sub", "side", "submodule"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "attributes")]
+    fn pathspec() -> crate::Result {
+        let repo = named_repo("make_rev_list_repo.sh")?;
+        let tree = repo.head_commit()?.tree()?;
+        let pathspec = |patterns: &[&str]| {
+            gix::Pathspec::new(&repo, false, patterns, false, || unreachable!("no attributes are used"))
+        };
+        assert_eq!(
+            paths(tree.entries_recursive().with_pathspec(pathspec(&["dir/sub"])?))?,
+            ["dir/sub", "dir/sub/c"],
+            "trees leading up to matches aren't returned unless they match themselves"
+        );
+        assert_eq!(
+            paths(tree.entries_recursive().with_pathspec(pathspec(&["*c", ":!dir/sub"])?))?,
+            ["dir/c"]
+        );
+        Ok(())
+    }
+}
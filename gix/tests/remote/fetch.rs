@@ -109,6 +109,61 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_prune_deletes_stale_tracking_refs() -> crate::Result {
+        for (dry_run, extra_refspec, expected_pruned) in [
+            (true, None, &["refs/remotes/origin/gone"][..]),
+            (false, None, &["refs/remotes/origin/gone"]),
+            (false, Some("^refs/heads/gone"), &[]),
+        ] {
+            let (repo, _tmp) = repo_rw("two-origins");
+            let stale_name = "refs/remotes/origin/gone";
+            repo.reference(
+                stale_name,
+                repo.head_id()?,
+                gix::refs::transaction::PreviousValue::MustNotExist,
+                "a tracking branch whose remote branch was deleted",
+            )?;
+            let mut remote = repo.find_remote("origin")?;
+            if let Some(spec) = extra_refspec {
+                remote.replace_refspecs(["+refs/heads/*:refs/remotes/origin/*", spec], Fetch)?;
+            }
+
+            let res = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .with_dry_run(dry_run)
+                .with_prune(true)
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            let update_refs = match res.status {
+                Status::Change { update_refs, .. } | Status::NoPackReceived { update_refs, .. } => update_refs,
+            };
+            assert_eq!(
+                update_refs
+                    .pruned
+                    .iter()
+                    .map(|edit| edit.name.as_bstr().to_string())
+                    .collect::<Vec<_>>(),
+                expected_pruned
+            );
+            assert_eq!(
+                repo.try_find_reference(stale_name)?.is_some(),
+                dry_run || expected_pruned.is_empty(),
+                "the stale reference is only deleted if it was pruned without dry-run"
+            );
+            assert!(
+                repo.try_find_reference("refs/remotes/origin/HEAD")?.is_some(),
+                "symbolic refs are never pruned"
+            );
+            assert!(
+                repo.try_find_reference("refs/remotes/origin/main")?.is_some(),
+                "refs that exist on the remote remain"
+            );
+        }
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -89,4 +89,32 @@ mod blocking_and_async_io {
         }
         Ok(())
     }
+
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn extra_ref_prefixes_filter_on_the_remote() -> crate::Result {
+        let daemon = spawn_git_daemon_if_async(remote::repo_path("base"))?;
+        let mut repo = remote::repo("clone");
+        repo.config_snapshot_mut()
+            .set_raw_value("protocol", None, "version", "2")?;
+
+        let remote = into_daemon_remote_if_async(repo.find_remote("origin")?, daemon.as_ref(), None);
+        let map = remote
+            .connect(Fetch)
+            .await?
+            .ref_map(
+                progress::Discard,
+                gix::remote::ref_map::Options {
+                    prefix_from_spec_as_filter_on_remote: false,
+                    extra_ref_prefixes: vec!["refs/tags/".into()],
+                    ..Default::default()
+                },
+            )
+            .await?;
+        assert_eq!(map.remote_refs.len(), 6, "only tags are sent by the server");
+        assert!(map.remote_refs.iter().all(|r| r.unpack().0.starts_with(b"refs/tags/")));
+        Ok(())
+    }
 }
@@ -0,0 +1,312 @@
+use gix::am::{mail, Options, Outcome, Stop};
+
+use crate::repo_rw;
+
+fn mbox(repo: &gix::Repository) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(repo.work_dir().expect("non-bare").join("series.mbox"))?)
+}
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::objs::Commit> {
+    Ok(repo.rev_parse_single(spec)?.object()?.into_commit().decode()?.into())
+}
+
+fn read(repo: &gix::Repository, rela_path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(repo.work_dir().expect("non-bare").join(rela_path))
+}
+
+mod mails {
+    use gix::am::mail::{parse, split, Options};
+
+    #[test]
+    fn headers_are_decoded_and_the_subject_is_cleaned() -> crate::Result {
+        let mail = parse(
+            b"From 1234 Mon Sep 17 00:00:00 2001
+From: =?UTF-8?q?J=C3=BCrgen=20M=C3=BCller?=
+ <jm@example.com>
+Date: Thu, 7 Apr 2005 22:13:13 +0200
+Subject: Re: [PATCH v2 3/7]   fix
+ the thing
+
+The body.
+---
+ file | 2 +-
+diff --git a/file b/file
+",
+            Options::default(),
+        )?;
+        assert_eq!(mail.author.name, "Jürgen Müller");
+        assert_eq!(mail.author.email, "jm@example.com");
+        assert_eq!(mail.date.expect("present").seconds, 1112904793);
+        assert_eq!(mail.subject, "fix the thing");
+        assert_eq!(mail.message(), "fix the thing\n\nThe body.\n");
+        assert!(mail.patch.starts_with(b"---\n file |"));
+
+        let mail = parse(
+            b"From: jm@example.com (J M)\nSubject: =?ISO-8859-1?B?U2No9m4=?=\n\n",
+            Options::default(),
+        )?;
+        assert_eq!(mail.author.name, "J M");
+        assert_eq!(mail.subject, "Schön");
+        assert_eq!(mail.message(), "Schön\n");
+        assert!(mail.patch.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn transfer_encodings_are_decoded() -> crate::Result {
+        let mail = parse(
+            b"From: A <a@b>\r\nSubject: base64\r\nContent-Transfer-Encoding: base64\r\n\r\nYm9keQoKLS0tCmRpZmYgLS1naXQgYS9mIGIvZgo=\r\n",
+            Options::default(),
+        )?;
+        assert_eq!(mail.body, "body\n");
+        assert_eq!(mail.patch, "---\ndiff --git a/f b/f\n");
+
+        let mail = parse(
+            b"From: A <a@b>\nSubject: qp\nContent-Transfer-Encoding: quoted-printable\n\nlong =\nline =3D x\n",
+            Options::default(),
+        )?;
+        assert_eq!(mail.body, "long line = x\n");
+        Ok(())
+    }
+
+    #[test]
+    fn in_body_headers_and_scissors_override_the_mail_headers() -> crate::Result {
+        let data = b"From: Sender <sender@example.com>
+Subject: [PATCH] discussion
+
+From: Author <author@example.com>
+Subject: the real subject
+
+Some discussion.
+
+-- >8 --
+Subject: after scissors
+
+The message.
+";
+        let mail = parse(data, Options::default())?;
+        assert_eq!(mail.author.email, "author@example.com");
+        assert_eq!(mail.subject, "the real subject");
+        assert!(mail.body.starts_with(b"Some discussion.\n\n-- >8 --"));
+
+        let mail = parse(data, Options { scissors: true })?;
+        assert_eq!(
+            mail.author.email, "sender@example.com",
+            "everything before the scissors is dropped"
+        );
+        assert_eq!(mail.subject, "after scissors");
+        assert_eq!(mail.body, "The message.\n");
+        Ok(())
+    }
+
+    #[test]
+    fn mailboxes_split_at_from_lines() {
+        let mbox = b"From abc Mon Sep 17 00:00:00 2001\nSubject: one\n\nFrom here on\nFrom def Mon Sep 17 00:00:00 2001\nSubject: two\n";
+        let mails = split(mbox);
+        assert_eq!(mails.len(), 2);
+        assert!(
+            mails[0].ends_with(b"From here on\n"),
+            "lines without a date aren't separators"
+        );
+        assert!(mails[1].starts_with(b"From def"));
+    }
+}
+
+#[test]
+fn all_patches_are_committed_with_their_authorship() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_am_mbox_repo.sh")?;
+    let base = repo.head_id()?.detach();
+    let out = repo.am(&mbox(&repo)?, Options::default())?;
+    let Outcome::Finished { head_id } = out else {
+        panic!("expected all patches to apply, got {out:?}");
+    };
+    assert_eq!(repo.head_id()?, head_id);
+    assert_eq!(repo.head_name()?.expect("attached").shorten(), "main");
+
+    let last = commit(&repo, "HEAD")?;
+    assert_eq!(last.message, "last line and a new file\n");
+    assert_eq!(last.author.name, "Sam Smith");
+    assert_eq!(last.author.email, "sam@example.com");
+    assert_eq!(last.author.time.seconds, 1136196245);
+    assert_eq!(last.author.time.offset, -7 * 3600);
+    assert_eq!(last.committer.name, "gitoxide");
+    let first = commit(&repo, "HEAD~1")?;
+    assert_eq!(first.message, "second line\n\nwith a body\n");
+    assert_eq!(first.author.name, "Jane Doe");
+    assert_eq!(first.parents[0], base);
+
+    assert_eq!(read(&repo, "numbers")?, "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nten\n");
+    assert_eq!(read(&repo, "new")?, "new\n");
+    assert!(repo.open_index()?.entry_by_path("new".into()).is_some());
+    assert_eq!(repo.rev_parse_single("ORIG_HEAD")?, base);
+    assert_eq!(repo.state(), None);
+    assert!(!repo.path().join("rebase-apply").exists());
+    Ok(())
+}
+
+#[test]
+fn invalid_mails_and_dirty_indices_are_rejected_before_anything_changes() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_am_mbox_repo.sh")?;
+    assert!(matches!(
+        repo.am(
+            b"From abc Mon Sep 17 00:00:00 2001\nSubject: no author\n\n",
+            Options::default()
+        ),
+        Err(gix::am::Error::Mail {
+            patch: 1,
+            source: mail::Error::InvalidAuthor { .. }
+        })
+    ));
+    assert!(!repo.path().join("rebase-apply").exists());
+
+    std::fs::write(repo.work_dir().expect("non-bare").join("numbers"), "changed\n")?;
+    let mut index = repo.open_index()?;
+    let entry = index.entry_mut_by_path_and_stage("numbers".into(), gix::index::entry::Stage::Unconflicted);
+    entry.expect("present").id = repo.write_blob("changed\n")?.detach();
+    index.write(Default::default())?;
+    assert!(matches!(
+        repo.am(&mbox(&repo)?, Options::default()),
+        Err(gix::am::Error::DirtyIndex)
+    ));
+    assert!(matches!(repo.am_continue(), Err(gix::am::Error::NotInProgress)));
+    Ok(())
+}
+
+#[test]
+fn stopped_patches_can_be_skipped_and_state_is_compatible_with_git() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_am_mbox_repo.sh")?;
+    repo.checkout("conflicting", gix::checkout::Options::default())?;
+    let conflicting = repo.head_id()?.detach();
+    let out = repo.am(
+        &mbox(&repo)?,
+        Options {
+            three_way: true,
+            ..Default::default()
+        },
+    )?;
+    let Outcome::Stopped {
+        patch,
+        subject,
+        reason: Stop::Conflicts(conflicts),
+    } = out
+    else {
+        panic!("expected a conflict, got {out:?}");
+    };
+    assert_eq!(patch, 2, "the first patch was already applied and is skipped silently");
+    assert_eq!(subject, "last line and a new file");
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].rela_path, "numbers");
+    assert_eq!(repo.head_id()?, conflicting, "no commit was made");
+    assert_eq!(repo.state(), Some(gix::state::InProgress::ApplyMailbox));
+
+    let state = repo.path().join("rebase-apply");
+    let file = |name: &str| std::fs::read_to_string(state.join(name));
+    assert_eq!(file("next")?, "2\n");
+    assert_eq!(file("last")?, "2\n");
+    assert_eq!(file("threeway")?, "t\n");
+    assert_eq!(file("final-commit")?, "last line and a new file\n");
+    assert!(file("0001")?.contains("Subject: [PATCH 1/2] second line"));
+    assert!(file("patch")?.contains("diff --git a/new b/new"));
+    assert_eq!(
+        file("author-script")?,
+        "GIT_AUTHOR_NAME='Sam Smith'\nGIT_AUTHOR_EMAIL='sam@example.com'\nGIT_AUTHOR_DATE='@1136196245 -0700'\n"
+    );
+    assert!(matches!(
+        repo.am(&mbox(&repo)?, Options::default()),
+        Err(gix::am::Error::InProgress { .. })
+    ));
+    assert!(matches!(repo.am_continue(), Err(gix::am::Error::Unmerged)));
+
+    let out = repo.am_skip()?;
+    assert!(matches!(out, Outcome::Finished { head_id } if head_id == conflicting));
+    assert_eq!(read(&repo, "numbers")?, "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nTEN\n");
+    assert!(read(&repo, "new").is_err(), "the changes of the skipped patch are gone");
+    assert_eq!(repo.state(), None);
+    Ok(())
+}
+
+#[test]
+fn stopped_patches_can_be_resolved_and_continued_or_aborted() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_am_mbox_repo.sh")?;
+    repo.checkout("conflicting", gix::checkout::Options::default())?;
+    let conflicting = repo.head_id()?.detach();
+    let out = repo.am(&mbox(&repo)?, Options::default())?;
+    assert!(
+        matches!(
+            out,
+            Outcome::Stopped {
+                patch: 1,
+                reason: Stop::DoesNotApply(_),
+                ..
+            }
+        ),
+        "without three-way merges, even the already applied patch fails"
+    );
+    assert_eq!(
+        read(&repo, "numbers")?,
+        "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nTEN\n",
+        "nothing changed"
+    );
+    assert!(matches!(repo.am_continue(), Err(gix::am::Error::NoChanges)));
+
+    let out = repo.am_skip()?;
+    assert!(matches!(
+        out,
+        Outcome::Stopped {
+            patch: 2,
+            reason: Stop::DoesNotApply(_),
+            ..
+        }
+    ));
+
+    let workdir = repo.work_dir().expect("non-bare");
+    std::fs::write(workdir.join("numbers"), "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nten\n")?;
+    let mut index = repo.open_index()?;
+    let entry = index.entry_mut_by_path_and_stage("numbers".into(), gix::index::entry::Stage::Unconflicted);
+    entry.expect("present").id = repo.write_blob("1\ntwo\n3\n4\n5\n6\n7\n8\n9\nten\n")?.detach();
+    index.write(Default::default())?;
+    let out = repo.am_continue()?;
+    let Outcome::Finished { head_id } = out else {
+        panic!("expected to finish, got {out:?}");
+    };
+    let resolved = commit(&repo, "HEAD")?;
+    assert_eq!(resolved.message, "last line and a new file\n");
+    assert_eq!(resolved.author.name, "Sam Smith");
+    assert_eq!(resolved.parents[0], conflicting);
+    assert_eq!(repo.head_id()?, head_id);
+    assert_eq!(repo.state(), None);
+
+    let (repo, _tmp) = repo_rw("make_am_mbox_repo.sh")?;
+    repo.checkout("conflicting", gix::checkout::Options::default())?;
+    let out = repo.am(
+        &mbox(&repo)?,
+        Options {
+            three_way: true,
+            ..Default::default()
+        },
+    )?;
+    assert!(matches!(
+        out,
+        Outcome::Stopped {
+            patch: 2,
+            reason: Stop::Conflicts(_),
+            ..
+        }
+    ));
+    assert!(read(&repo, "numbers")?.contains("<<<<<<<"));
+    repo.am_abort()?;
+    assert_eq!(repo.head_id()?, conflicting);
+    assert_eq!(read(&repo, "numbers")?, "1\ntwo\n3\n4\n5\n6\n7\n8\n9\nTEN\n");
+    assert!(read(&repo, "new").is_err());
+    let index = repo.open_index()?;
+    assert!(
+        index
+            .entries()
+            .iter()
+            .all(|e| e.stage() == gix::index::entry::Stage::Unconflicted),
+        "the conflicts are gone"
+    );
+    assert_eq!(repo.state(), None);
+    assert!(matches!(repo.am_abort(), Err(gix::am::Error::NotInProgress)));
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use gix::apply::{Options, Target};
+
+use crate::repo_rw;
+
+fn patch(repo: &gix::Repository, name: &str) -> crate::Result<Vec<gix::diff::patch::File>> {
+    let data = std::fs::read(repo.work_dir().expect("non-bare").join(name))?;
+    Ok(gix::diff::patch::parse(&data, Default::default())?)
+}
+
+fn read(repo: &gix::Repository, rela_path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(repo.work_dir().expect("non-bare").join(rela_path))
+}
+
+fn index_blob(repo: &gix::Repository, rela_path: &str) -> crate::Result<Option<String>> {
+    let index = repo.open_index()?;
+    Ok(match index.entry_by_path(rela_path.into()) {
+        Some(entry) => Some(String::from_utf8(repo.find_object(entry.id)?.detach().data)?),
+        None => None,
+    })
+}
+
+#[test]
+fn all_kinds_of_changes_apply_to_the_worktree() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_apply_repo.sh")?;
+    let outcome = repo.apply(&patch(&repo, "all-changes.patch")?, Options::default())?;
+    assert_eq!(
+        outcome.paths,
+        ["bin", "gone", "new", "numbers", "old", "renamed", "script"]
+    );
+    assert!(outcome.conflicts.is_empty());
+    assert!(outcome.rejected.is_empty());
+
+    assert!(
+        read(&repo, "numbers")?.starts_with("1\ntwo\n3\n"),
+        "the hunk applies even though the file changed elsewhere"
+    );
+    assert!(read(&repo, "numbers")?.contains("NINE"));
+    assert_eq!(read(&repo, "new")?, "new\n");
+    assert_eq!(read(&repo, "renamed")?, "content to be moved\n");
+    for removed in ["gone", "old"] {
+        assert!(read(&repo, removed).is_err(), "{removed} was removed");
+    }
+    assert_eq!(
+        std::fs::read(repo.work_dir().expect("non-bare").join("bin"))?,
+        b"\x00\x01binary, changed"
+    );
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(repo.work_dir().expect("non-bare").join("script"))?
+            .permissions()
+            .mode();
+        assert_ne!(mode & 0o100, 0, "the executable bit is set");
+    }
+
+    assert_eq!(
+        index_blob(&repo, "gone")?.as_deref(),
+        Some("gone\n"),
+        "the index isn't touched"
+    );
+    assert_eq!(index_blob(&repo, "new")?, None);
+    Ok(())
+}
+
+#[test]
+fn changes_can_apply_to_the_index_only_or_to_both() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_apply_repo.sh")?;
+    let patches = patch(&repo, "all-changes.patch")?;
+    repo.apply(
+        &patches,
+        Options {
+            target: Target::Index,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(index_blob(&repo, "new")?.as_deref(), Some("new\n"));
+    assert_eq!(index_blob(&repo, "gone")?, None);
+    assert_eq!(index_blob(&repo, "renamed")?.as_deref(), Some("content to be moved\n"));
+    assert!(index_blob(&repo, "numbers")?.expect("present").starts_with("1\ntwo\n"));
+    let index = repo.open_index()?;
+    assert_eq!(
+        index.entry_by_path("script".into()).expect("present").mode,
+        gix::index::entry::Mode::FILE_EXECUTABLE
+    );
+    assert!(read(&repo, "new").is_err(), "the worktree isn't touched");
+    assert_eq!(read(&repo, "gone")?, "gone\n");
+
+    let (repo, _tmp) = repo_rw("make_apply_repo.sh")?;
+    std::fs::write(repo.work_dir().expect("non-bare").join("numbers"), "changed\n")?;
+    let both = Options {
+        target: Target::Both,
+        ..Default::default()
+    };
+    assert!(
+        matches!(
+            repo.apply(&patches, both),
+            Err(gix::apply::Error::WorktreeDiffersFromIndex { rela_path }) if rela_path == "numbers"
+        ),
+        "the worktree must match the index"
+    );
+    assert!(
+        read(&repo, "new").is_err(),
+        "nothing is changed if one patch doesn't apply"
+    );
+
+    std::fs::write(
+        repo.work_dir().expect("non-bare").join("numbers"),
+        "1\n2\n3\n4\n5\n6\n7\n8\nNINE\n10\n",
+    )?;
+    repo.apply(&patches, both)?;
+    assert_eq!(read(&repo, "new")?, "new\n");
+    assert_eq!(index_blob(&repo, "new")?.as_deref(), Some("new\n"));
+    assert!(read(&repo, "gone").is_err());
+    assert_eq!(index_blob(&repo, "gone")?, None);
+    Ok(())
+}
+
+#[test]
+fn hunks_that_do_not_apply_fail_the_operation_or_are_rejected() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_apply_repo.sh")?;
+    let patches = gix::diff::patch::parse(
+        b"--- a/numbers
++++ b/numbers
+@@ -1,3 +1,3 @@
+ 1
+-2
++two
+ 3
+@@ -8,3 +8,3 @@
+ 8
+-9
++nine
+ 10
+",
+        Default::default(),
+    )?;
+    assert!(matches!(
+        repo.apply(&patches, Options::default()),
+        Err(gix::apply::Error::HunksDoNotApply { rela_path, hunks }) if rela_path == "numbers" && hunks == [1]
+    ));
+    assert!(read(&repo, "numbers")?.starts_with("1\n2\n"), "nothing was changed");
+
+    let outcome = repo.apply(
+        &patches,
+        Options {
+            reject: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.rejected.len(), 1);
+    assert_eq!(outcome.rejected[0].rela_path, "numbers");
+    assert_eq!(outcome.rejected[0].hunks, [1]);
+    assert_eq!(read(&repo, "numbers")?, "1\ntwo\n3\n4\n5\n6\n7\n8\nNINE\n10\n");
+    assert_eq!(
+        read(&repo, "numbers.rej")?,
+        "diff a/numbers b/numbers\t(rejected hunks)\n@@ -8,3 +8,3 @@\n 8\n-9\n+nine\n 10\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn three_way_merges_record_conflicts_in_the_index() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_apply_repo.sh")?;
+    let patches = patch(&repo, "three-way.patch")?;
+    assert!(matches!(
+        repo.apply(&patches, Options::default()),
+        Err(gix::apply::Error::HunksDoNotApply { .. })
+    ));
+
+    let outcome = repo.apply(
+        &patches,
+        Options {
+            three_way: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.paths, ["letters", "numbers"]);
+    assert_eq!(outcome.conflicts.len(), 1);
+    let conflict = &outcome.conflicts[0];
+    assert_eq!(conflict.rela_path, "numbers");
+    assert_eq!(conflict.kind, gix::merge::ConflictKind::Content);
+    assert_eq!(
+        read(&repo, "letters")?,
+        "a\nB\nc\nd\nE\nf\ng\nh\ni\nj\n",
+        "changes that don't overlap are merged cleanly"
+    );
+    assert_eq!(
+        index_blob(&repo, "letters")?.as_deref(),
+        Some("a\nB\nc\nd\nE\nf\ng\nh\ni\nj\n")
+    );
+    assert!(read(&repo, "numbers")?.contains("<<<<<<< ours\nNINE\n=======\nnine\n>>>>>>> theirs\n"));
+
+    let index = repo.open_index()?;
+    let stages: Vec<_> = index
+        .entries()
+        .iter()
+        .filter(|e| e.path(&index) == "numbers")
+        .map(|e| e.stage() as u32)
+        .collect();
+    assert_eq!(stages, [1, 2, 3], "the conflict is recorded in the index");
+    Ok(())
+}
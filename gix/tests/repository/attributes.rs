@@ -0,0 +1,73 @@
+use gix::bstr::ByteSlice;
+
+#[test]
+fn from_tree_in_bare_repository() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_attributes_repo.sh")?;
+    let repo = gix::open_opts(dir.join("bare.git"), crate::restricted())?;
+    assert!(repo.is_bare());
+
+    let tree_id = repo.head_commit()?.tree_id()?;
+    let mut stack = repo.attributes_only_from_tree(tree_id)?;
+    let mut outcome = stack.attribute_matches();
+    let baseline = std::fs::read(dir.join("baseline"))?;
+    let mut expected = std::collections::BTreeMap::<_, Vec<_>>::new();
+    for line in baseline.lines() {
+        let mut tokens = line.splitn_str(3, ": ");
+        let (path, name, value) = (tokens.next().unwrap(), tokens.next().unwrap(), tokens.next().unwrap());
+        expected
+            .entry(path.as_bstr().to_owned())
+            .or_default()
+            .push(format!("{}={}", name.as_bstr(), value.as_bstr()));
+    }
+    assert_eq!(expected.len(), 6, "all paths have attributes");
+
+    for (path, mut expected) in expected {
+        stack.at_entry(path.as_bstr(), None)?.matching_attributes(&mut outcome);
+        let mut actual: Vec<_> = outcome
+            .iter()
+            .filter_map(|m| {
+                use gix::attrs::StateRef::*;
+                let value = match m.assignment.state {
+                    Set => "set".into(),
+                    Unset => "unset".into(),
+                    Value(value) => value.as_bstr().to_string(),
+                    Unspecified => return None,
+                };
+                Some(format!("{}={value}", m.assignment.name.as_str()))
+            })
+            .collect();
+        actual.sort();
+        expected.sort();
+        assert_eq!(
+            actual, expected,
+            "{path}: attributes are read from the tree, just like git reads them from the worktree"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn core_ignore_case_folds_case_of_paths_and_attribute_files() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_attributes_repo.sh")?;
+    for (ignore_case, expected) in [(false, &[][..]), (true, &["-text", "custom=root"][..])] {
+        let repo = gix::open_opts(
+            dir.join("bare.git"),
+            crate::restricted().config_overrides([format!("core.ignoreCase={ignore_case}")]),
+        )?;
+        let tree_id = repo.head_commit()?.tree_id()?;
+        let mut stack = repo.attributes_only_from_tree(tree_id)?;
+        let mut outcome = stack.attribute_matches();
+        stack.at_entry("DIR/A.TXT", None)?.matching_attributes(&mut outcome);
+        let mut actual: Vec<_> = outcome
+            .iter()
+            .filter(|m| !m.assignment.state.is_unspecified())
+            .map(|m| m.assignment.to_string())
+            .collect();
+        actual.sort();
+        assert_eq!(
+            actual, expected,
+            "core.ignoreCase={ignore_case}: with case-folding, `dir/.gitattributes` applies as well"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use gix::binary::{is_binary_content, Purpose, FIRST_FEW_BYTES};
+
+use crate::named_repo;
+
+#[test]
+fn content_is_binary_if_it_has_a_null_byte_at_the_beginning() {
+    assert!(!is_binary_content(b""));
+    assert!(!is_binary_content(b"text\r\n"));
+    assert!(is_binary_content(b"text\0"));
+
+    let mut data = vec![b'a'; FIRST_FEW_BYTES];
+    data.push(0);
+    assert!(!is_binary_content(&data), "only the first few bytes are inspected");
+}
+
+#[test]
+fn attributes_take_precedence_over_content_depending_on_the_purpose() -> crate::Result {
+    let repo = named_repo("make_grep_repo.sh")?;
+    let index = repo.index()?;
+    let mut diff = repo.binary_detector(
+        &index,
+        gix_worktree::stack::state::attributes::Source::IdMapping,
+        Purpose::Diff,
+    )?;
+    let mut merge = repo.binary_detector(
+        &index,
+        gix_worktree::stack::state::attributes::Source::IdMapping,
+        Purpose::Merge,
+    )?;
+    for (path, diff_expected, merge_expected) in [
+        ("src/main.rs", None, None),
+        ("marked.txt", Some(true), None),
+        ("forced.dat", Some(false), None),
+        ("driver.txt", Some(true), None),
+        ("src/lib.rs", None, Some(true)),
+    ] {
+        assert_eq!(
+            diff.is_binary_by_attributes(path.into(), None)?,
+            diff_expected,
+            "{path}: diff"
+        );
+        assert_eq!(
+            merge.is_binary_by_attributes(path.into(), None)?,
+            merge_expected,
+            "{path}: merge"
+        );
+    }
+
+    assert!(diff.is_binary("data.bin".into(), None, b"needle\0binary")?);
+    assert!(
+        !diff.is_binary("forced.dat".into(), None, b"needle\0")?,
+        "the diff attribute forces text"
+    );
+    assert!(diff.is_binary("marked.txt".into(), None, b"needle")?);
+    Ok(())
+}
+
+#[test]
+fn the_binary_macro_unsets_diff_and_merge() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_attributes_repo.sh")?;
+    let repo = gix::open_opts(dir.join("bare.git"), crate::restricted())?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+    for purpose in [Purpose::Diff, Purpose::Merge] {
+        let mut detector = gix::binary::Detector::new(repo.attributes_only_from_tree(tree_id)?, purpose)?;
+        assert_eq!(detector.is_binary_by_attributes("a.bin".into(), None)?, Some(true));
+        assert_eq!(
+            detector.is_binary_by_attributes("a.txt".into(), None)?,
+            None,
+            "`text` doesn't affect binary detection"
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use gix::bisect::{Mark, Next, Options};
+
+use crate::{repo_rw, restricted};
+
+fn id(repo: &gix::Repository, spec: &str) -> gix::ObjectId {
+    repo.rev_parse_single(spec).expect("valid spec").detach()
+}
+
+#[test]
+fn run_finds_first_bad_commit_across_merges_and_reset_restores_head() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_bisect_history_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+
+    let next = repo.bisect_start(Some("c8".into()), ["c1".into()], Options::default())?;
+    assert_eq!(
+        next,
+        Next::Test {
+            commit: id(&repo, "m"),
+            candidates: 10
+        },
+        "the merge splits the 10 candidates best, as it has 6 of them in its ancestry"
+    );
+    assert_eq!(repo.state(), Some(gix::state::InProgress::Bisect));
+    assert!(repo.head()?.is_detached(), "the commit to test is checked out");
+    assert_eq!(repo.head_id()?, id(&repo, "m"));
+
+    let mut tested = Vec::new();
+    let outcome = repo.bisect_run(|commit| -> Result<_, std::convert::Infallible> {
+        tested.push(commit);
+        Ok(if workdir.join("broken").is_file() {
+            Mark::Bad
+        } else {
+            Mark::Good
+        })
+    })?;
+    assert_eq!(
+        outcome,
+        Next::Found {
+            first_bad: id(&repo, "c6")
+        }
+    );
+    assert!(
+        tested.len() <= 4,
+        "about log2(10) steps are needed, got {}",
+        tested.len()
+    );
+
+    let state = repo.bisect_state()?.expect("still in progress until reset");
+    assert_eq!(state.start, "main");
+    assert_eq!(state.bad, Some(id(&repo, "c6")));
+    assert!(state.good.contains(&id(&repo, "c1")));
+    assert!(state.skip.is_empty());
+    assert!(!state.no_checkout);
+
+    let log = std::fs::read_to_string(repo.path().join("BISECT_LOG"))?;
+    assert!(log.contains("git bisect start 'c8' 'c1'\n"), "{log}");
+    assert!(
+        log.ends_with(&format!("# first bad commit: [{}] c6\n", id(&repo, "c6"))),
+        "{log}"
+    );
+
+    repo.bisect_reset()?;
+    assert_eq!(repo.state(), None);
+    assert_eq!(repo.bisect_state()?, None);
+    assert_eq!(
+        repo.head_name()?.expect("attached").as_bstr(),
+        "refs/heads/main",
+        "the original branch is checked out again"
+    );
+    assert!(workdir.join("c8").is_file());
+    assert_eq!(repo.references()?.prefixed("refs/bisect/")?.count(), 0);
+    Ok(())
+}
+
+#[test]
+fn marks_can_be_added_one_by_one_and_skipped_commits_are_never_chosen() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_bisect_history_repo.sh")?;
+
+    assert_eq!(repo.bisect_start(None, None, Options::default())?, Next::NeedBad);
+    assert_eq!(
+        repo.bisect_mark(Mark::Bad, None)?,
+        Next::NeedGood,
+        "`HEAD` is marked by default"
+    );
+    assert_eq!(repo.bisect_state()?.expect("in progress").bad, Some(id(&repo, "c8")));
+
+    assert_eq!(
+        repo.bisect_mark(Mark::Good, Some(id(&repo, "c5")))?,
+        Next::Test {
+            commit: id(&repo, "c7"),
+            candidates: 3
+        }
+    );
+    assert_eq!(
+        repo.bisect_mark(Mark::Bad, None)?,
+        Next::Test {
+            commit: id(&repo, "c6"),
+            candidates: 2
+        }
+    );
+    assert_eq!(
+        repo.bisect_mark(Mark::Skip, None)?,
+        Next::OnlySkippedLeft {
+            candidates: vec![id(&repo, "c7"), id(&repo, "c6")]
+        },
+        "skipped commits are never chosen, even if they are the only ones left"
+    );
+    let log = std::fs::read_to_string(repo.path().join("BISECT_LOG"))?;
+    assert!(log.contains(&format!("git bisect skip {}\n", id(&repo, "c6"))), "{log}");
+    assert!(log.ends_with(&format!("# possible first bad commit: [{}] c6\n", id(&repo, "c6"))));
+
+    assert!(
+        matches!(
+            repo.bisect_start(None, None, Options::default()),
+            Err(gix::bisect::Error::InProgress { .. })
+        ),
+        "only one bisection can be in progress"
+    );
+    repo.bisect_reset()?;
+    Ok(())
+}
+
+#[test]
+fn no_checkout_works_in_bare_repositories() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_bisect_history_repo.sh")?;
+    let repo = gix::open_opts(repo.work_dir().expect("non-bare").join("bare.git"), restricted())?;
+    let head = repo.head_id()?.detach();
+
+    assert!(matches!(
+        repo.bisect_start(None, None, Options::default()),
+        Err(gix::bisect::Error::BareRepository { .. })
+    ));
+
+    let next = repo.bisect_start(Some("c8".into()), ["c1".into()], Options { no_checkout: true })?;
+    assert!(matches!(next, Next::Test { .. }));
+    let outcome = repo.bisect_run(|commit| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+        assert_eq!(
+            repo.find_reference("BISECT_HEAD")?.into_fully_peeled_id()?,
+            commit,
+            "the commit to test is recorded in `BISECT_HEAD`"
+        );
+        let tree = repo.find_object(commit)?.peel_to_tree()?;
+        Ok(if tree.find_entry("broken").is_some() {
+            Mark::Bad
+        } else {
+            Mark::Good
+        })
+    })?;
+    assert_eq!(
+        outcome,
+        Next::Found {
+            first_bad: id(&repo, "c6")
+        }
+    );
+    assert_eq!(repo.head_id()?, head, "`HEAD` is never touched");
+
+    repo.bisect_reset()?;
+    assert!(repo.try_find_reference("BISECT_HEAD")?.is_none());
+    assert_eq!(repo.bisect_state()?, None);
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use gix::{
+    blame::{LineRange, Options},
+    bstr::ByteSlice,
+};
+
+use crate::named_repo;
+
+/// Return `(commit, line in source file, line in blamed file)` for each line in the `git blame --porcelain` output in `baseline`,
+/// with 1-based line numbers.
+fn baseline(repo: &gix::Repository, baseline: &str) -> crate::Result<Vec<(gix::ObjectId, u32, u32)>> {
+    let content = std::fs::read(repo.work_dir().expect("non-bare").join(baseline))?;
+    let mut out = Vec::new();
+    for line in content.lines() {
+        let mut tokens = line.split_str(" ");
+        let Some(id) = tokens.next().and_then(|hex| gix::ObjectId::from_hex(hex).ok()) else {
+            continue;
+        };
+        let mut number = || -> u32 {
+            tokens
+                .next()
+                .expect("present")
+                .to_str()
+                .expect("ascii")
+                .parse()
+                .expect("number")
+        };
+        out.push((id, number(), number()));
+    }
+    Ok(out)
+}
+
+fn lines(outcome: &gix::blame::Outcome) -> Vec<(gix::ObjectId, u32, u32)> {
+    outcome
+        .entries
+        .iter()
+        .flat_map(|entry| {
+            (0..entry.len).map(move |offset| {
+                (
+                    entry.commit_id,
+                    entry.start_in_source_file + offset + 1,
+                    entry.start_in_blamed_file + offset + 1,
+                )
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn follows_renames_and_merges() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let out = repo.blame(repo.head_id()?, "renamed".into(), Options::default())?;
+    assert_eq!(lines(&out), baseline(&repo, "blame.baseline")?);
+    assert_eq!(out.blob, repo.rev_parse_single("HEAD:renamed")?.object()?.data);
+    let first_commit = repo.rev_parse_single(":/add file")?.detach();
+    assert_eq!(
+        out.entries
+            .iter()
+            .filter(|entry| entry.commit_id == first_commit)
+            .map(|entry| entry.source_path.to_string())
+            .collect::<Vec<_>>(),
+        ["file"],
+        "the lines are attributed to the file before the rename"
+    );
+    assert_eq!(out.entries.len(), 7, "no adjacent lines are from the same commit");
+
+    let out = repo.blame(repo.rev_parse_single("HEAD~1")?, "renamed".into(), Options::default())?;
+    assert_eq!(lines(&out), baseline(&repo, "blame-merge.baseline")?);
+    let (entry, content) = out.entries_with_lines().next().expect("at least one line");
+    assert_eq!(entry.commit_id, repo.rev_parse_single("side")?);
+    assert_eq!(content, ["1 on side\n"]);
+
+    let out = repo.blame(
+        repo.head_id()?,
+        "renamed".into(),
+        Options {
+            rewrites: None,
+            ..Default::default()
+        },
+    )?;
+    assert!(
+        out.entries.iter().all(|entry| entry.commit_id != first_commit),
+        "without following renames, the commit that renamed the file is blamed instead"
+    );
+    Ok(())
+}
+
+#[test]
+fn line_ranges() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let out = repo.blame(
+        repo.head_id()?,
+        "renamed".into(),
+        Options {
+            ranges: vec!["2,+2".parse()?, "3,4".parse()?],
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(lines(&out), baseline(&repo, "blame-range.baseline")?);
+
+    assert!(matches!(
+        repo.blame(
+            repo.head_id()?,
+            "renamed".into(),
+            Options {
+                ranges: vec!["8".parse()?],
+                ..Default::default()
+            },
+        ),
+        Err(gix::blame::Error::InvalidLineRange { line_count: 7, .. })
+    ));
+    assert!(matches!(
+        repo.blame(repo.head_id()?, "missing".into(), Options::default()),
+        Err(gix::blame::Error::FileNotFound { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn line_range_parsing() -> crate::Result {
+    for (spec, start, end) in [
+        ("2,4", 2, Some(4)),
+        ("4,2", 2, Some(4)),
+        ("3", 3, None),
+        ("3,", 3, None),
+        (",5", 1, Some(5)),
+        ("3,+2", 3, Some(4)),
+        ("3,-2", 2, Some(3)),
+        ("2,-5", 1, Some(2)),
+    ] {
+        assert_eq!(spec.parse::<LineRange>()?, LineRange { start, end }, "{spec}");
+    }
+    for invalid in ["", ",", "0", "a,b", "1,+0", "-1"] {
+        assert!(invalid.parse::<LineRange>().is_err(), "{invalid}");
+    }
+    Ok(())
+}
+
+#[test]
+fn ignored_revisions_pass_changed_lines_to_their_parent() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let head = repo.head_id()?.detach();
+    let ignore_revs = gix::blame::ignore_revs::parse(format!("# formatting\n{head} # reformat\n\n").as_bytes())?;
+    assert_eq!(ignore_revs, [head]);
+    let out = repo.blame(
+        head,
+        "renamed".into(),
+        Options {
+            ignore_revs: ignore_revs.into_iter().collect(),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(lines(&out), baseline(&repo, "blame-ignore-rev.baseline")?);
+    assert!(gix::blame::ignore_revs::parse(b"abc\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn incremental_produces_the_same_lines() -> crate::Result {
+    let repo = named_repo("make_blame_repo.sh")?;
+    let mut entries = Vec::new();
+    let stats = repo.blame_incremental(repo.head_id()?, "renamed".into(), Options::default(), |entry| {
+        entries.push(entry);
+    })?;
+    entries.sort_by_key(|entry| entry.start_in_blamed_file);
+    let out = repo.blame(repo.head_id()?, "renamed".into(), Options::default())?;
+    assert_eq!(
+        lines(&gix::blame::Outcome {
+            entries,
+            blob: Vec::new(),
+            statistics: stats,
+        }),
+        lines(&out)
+    );
+    assert_eq!(stats, out.statistics);
+    assert_eq!(stats.commits_traversed, 6, "each commit is visited once");
+    assert_eq!(
+        stats.trees_diffed, 2,
+        "renames are looked for in the commit that renamed the file and in the merge with the side branch"
+    );
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use gix::bundle;
+
+fn header_at(path: &Path) -> crate::Result<bundle::Header> {
+    Ok(bundle::Header::from_read(&mut std::io::BufReader::new(
+        std::fs::File::open(path)?,
+    ))?)
+}
+
+fn full_name(name: &str) -> gix_ref::FullName {
+    name.try_into().expect("valid")
+}
+
+#[test]
+fn header_round_trips() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_bundle_repo.sh")?;
+    for name in ["all.bundle", "base.bundle", "incremental.bundle"] {
+        let path = dir.join(name);
+        assert!(bundle::is_bundle(&path));
+        let header = header_at(&path)?;
+        let mut buf = Vec::new();
+        header.write_to(&mut buf)?;
+        assert!(
+            std::fs::read(&path)?.starts_with(&buf),
+            "{name}: the header is written exactly like git writes it"
+        );
+    }
+    assert!(!bundle::is_bundle(&dir.join("file")));
+    Ok(())
+}
+
+#[test]
+fn create_like_git() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_bundle_repo.sh")?;
+    let repo = gix::open_opts(&dir, crate::restricted())?;
+    let base = repo.rev_parse_single("main~2")?.detach();
+    for (refs, hidden, name) in [
+        (&["refs/heads/base"][..], None, "base.bundle"),
+        (&["refs/heads/main"], Some(base), "incremental.bundle"),
+        (
+            &[
+                "refs/heads/base",
+                "refs/heads/main",
+                "refs/heads/other",
+                "refs/tags/v1",
+                "HEAD",
+            ],
+            None,
+            "all.bundle",
+        ),
+    ] {
+        let mut buf = Vec::new();
+        let outcome = repo.create_bundle(
+            &mut buf,
+            refs.iter().copied().map(full_name),
+            hidden,
+            Default::default(),
+        )?;
+        let expected = header_at(&dir.join(name))?;
+        assert_eq!(outcome.header, expected, "{name}: refs and prerequisites match");
+        let header = bundle::Header::from_read(&mut buf.as_slice())?;
+        assert_eq!(header, expected, "{name}: the written header can be read back");
+    }
+
+    let err = repo
+        .create_bundle(
+            Vec::new(),
+            None::<gix_ref::FullName>,
+            None::<gix_hash::ObjectId>,
+            Default::default(),
+        )
+        .unwrap_err();
+    assert!(matches!(err, bundle::create::Error::Empty));
+    Ok(())
+}
+
+#[test]
+fn verify() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_bundle_repo.sh")?;
+    let repo = gix::open_opts(&dir, crate::restricted())?;
+    let incremental = header_at(&dir.join("incremental.bundle"))?;
+    repo.verify_bundle(&incremental)?;
+
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let empty = gix::init_bare(tmp.path())?;
+    empty.verify_bundle(&header_at(&dir.join("all.bundle"))?)?;
+    match empty.verify_bundle(&incremental) {
+        Err(bundle::verify::Error::MissingPrerequisites { ids }) => {
+            assert_eq!(ids, [incremental.prerequisites[0].id]);
+        }
+        res => unreachable!("the prerequisite is missing: {res:?}"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "blocking-network-client")]
+mod fetch {
+    use std::sync::atomic::AtomicBool;
+
+    use gix::remote::Direction;
+
+    use super::full_name;
+
+    #[test]
+    fn clone_from_created_bundle_then_fetch_incremental_bundle() -> crate::Result {
+        let dir = gix_testtools::scripted_fixture_read_only("make_bundle_repo.sh")?;
+        let source = gix::open_opts(&dir, crate::restricted())?;
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let bundle_path = tmp.path().join("created.bundle");
+        source.create_bundle(
+            std::fs::File::create(&bundle_path)?,
+            ["HEAD", "refs/heads/main", "refs/heads/other", "refs/tags/v1"]
+                .into_iter()
+                .map(full_name),
+            None::<gix_hash::ObjectId>,
+            Default::default(),
+        )?;
+
+        let (repo, _out) = gix::prepare_clone_bare(bundle_path, tmp.path().join("clone.git"))?
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        for (name, expected) in [
+            ("refs/remotes/origin/main", "main"),
+            ("refs/remotes/origin/other", "other"),
+            ("refs/tags/v1", "v1"),
+        ] {
+            assert_eq!(
+                repo.find_reference(name)?.id(),
+                source.rev_parse_single(expected)?,
+                "{name} was fetched from the bundle"
+            );
+        }
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/main",
+            "HEAD is derived from the branch pointing to the same commit"
+        );
+        assert_eq!(
+            repo.rev_list(
+                [source.rev_parse_single("v1^{}")?],
+                None::<gix_hash::ObjectId>,
+                gix::rev_list::Options {
+                    objects: true,
+                    boundary: false,
+                }
+            )?
+            .items
+            .len(),
+            source
+                .rev_list(
+                    [source.rev_parse_single("v1^{}")?],
+                    None::<gix_hash::ObjectId>,
+                    gix::rev_list::Options {
+                        objects: true,
+                        boundary: false,
+                    }
+                )?
+                .items
+                .len(),
+            "all objects were received"
+        );
+
+        let (repo, _out) = gix::prepare_clone_bare(dir.join("base.bundle"), tmp.path().join("incremental.git"))?
+            .fetch_only(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(
+            repo.find_reference("refs/remotes/origin/base")?.id(),
+            source.rev_parse_single("base")?
+        );
+        let outcome = repo
+            .remote_at(dir.join("incremental.bundle"))?
+            .with_refspecs(Some("+refs/heads/main:refs/remotes/origin/main"), Direction::Fetch)?
+            .connect(Direction::Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(
+            matches!(outcome.status, gix::remote::fetch::Status::Change { .. }),
+            "the thin pack of the bundle was resolved against the objects we have"
+        );
+        assert_eq!(
+            repo.find_reference("refs/remotes/origin/main")?.id(),
+            source.rev_parse_single("main")?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use gix::{
+    bstr::{BString, ByteSlice},
+    cat_file,
+};
+
+use crate::util::named_repo;
+
+fn batch(repo: &gix::Repository, options: cat_file::Options) -> crate::Result<Vec<u8>> {
+    let input = std::fs::read(repo.work_dir().expect("non-bare").join("input"))?;
+    let mut out = Vec::new();
+    for item in repo.cat_file_batch(input.lines().map(BString::from), options) {
+        item?.write_to(&mut out)?;
+    }
+    Ok(out)
+}
+
+fn expected(repo: &gix::Repository, name: &str) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(repo.work_dir().expect("non-bare").join(name))?)
+}
+
+#[test]
+fn contents_in_input_order_regardless_of_window() -> crate::Result {
+    let repo = named_repo("make_cat_file_repo.sh")?;
+    let expected = expected(&repo, "batch.out")?;
+    for window in [0, 1, 2, 1024] {
+        let out = batch(
+            &repo,
+            cat_file::Options {
+                window,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(out.as_bstr(), expected.as_bstr(), "window = {window}");
+    }
+    Ok(())
+}
+
+#[test]
+fn check() -> crate::Result {
+    let repo = named_repo("make_cat_file_repo.sh")?;
+    let expected = expected(&repo, "batch-check.out")?;
+    for window in [1, 3, 1024] {
+        let out = batch(
+            &repo,
+            cat_file::Options {
+                mode: cat_file::Mode::Check,
+                window,
+            },
+        )?;
+        assert_eq!(out.as_bstr(), expected.as_bstr(), "window = {window}");
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_objects() -> crate::Result {
+    let repo = named_repo("make_cat_file_repo.sh")?;
+    let items = repo
+        .cat_file_batch(
+            ["does-not-exist".into(), "HEAD:missing".into()],
+            cat_file::Options::default(),
+        )
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        items,
+        [
+            cat_file::Item::Missing {
+                input: "does-not-exist".into()
+            },
+            cat_file::Item::Missing {
+                input: "HEAD:missing".into()
+            }
+        ]
+    );
+    Ok(())
+}
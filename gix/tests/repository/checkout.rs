@@ -0,0 +1,114 @@
+use crate::repo_rw;
+
+fn head_log_messages(repo: &gix::Repository) -> crate::Result<Vec<gix::bstr::BString>> {
+    Ok(repo
+        .head()?
+        .log_iter()
+        .rev()?
+        .expect("log present")
+        .map(|line| line.map(|line| line.message))
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn switch_to_branch_attaches_head_and_updates_worktree() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+
+    let out = repo.checkout("other", Default::default())?;
+    assert_eq!(out.head_name.as_ref().expect("attached").as_bstr(), "refs/heads/other");
+    assert_eq!(out.files_removed, 1, "dir/file is gone");
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a.other\n");
+    assert_eq!(std::fs::read(workdir.join("new"))?, b"new\n");
+    assert!(!workdir.join("dir").exists(), "empty directories are removed as well");
+
+    let head = repo.head()?;
+    assert_eq!(head.referent_name().expect("attached").as_bstr(), "refs/heads/other");
+    assert_eq!(head.id().expect("born"), out.head_id);
+    assert_eq!(
+        head_log_messages(&repo)?[0],
+        "checkout: moving from main to other",
+        "the reflog is written like git would"
+    );
+    assert!(!repo.is_dirty()?, "the index matches the worktree after checkout");
+    Ok(())
+}
+
+#[test]
+fn switch_to_revision_detaches_head() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let main_id = repo.head_id()?.detach();
+    repo.checkout("other", Default::default())?;
+
+    let out = repo.checkout("other~1", Default::default())?;
+    assert_eq!(out.head_id, main_id);
+    assert!(out.head_name.is_none());
+    assert!(repo.head()?.is_detached());
+    assert_eq!(head_log_messages(&repo)?[0], "checkout: moving from other to other~1");
+
+    let out = repo.checkout(
+        "main",
+        gix::checkout::Options {
+            detach: true,
+            ..Default::default()
+        },
+    )?;
+    assert!(out.head_name.is_none(), "detaching can be forced even for branch names");
+    assert!(repo.head()?.is_detached());
+    Ok(())
+}
+
+#[test]
+fn local_changes_to_affected_paths_prevent_checkout_unless_forced() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "local change")?;
+    std::fs::write(workdir.join("new"), "untracked")?;
+
+    let err = repo.checkout("other", Default::default()).unwrap_err();
+    match err {
+        gix::checkout::Error::Conflicts { conflicts } => {
+            assert_eq!(
+                conflicts
+                    .iter()
+                    .map(|c| (c.rela_path.to_string(), c.kind))
+                    .collect::<Vec<_>>(),
+                [
+                    ("a".to_string(), gix::checkout::ConflictKind::LocalModification),
+                    ("new".to_string(), gix::checkout::ConflictKind::Untracked)
+                ]
+            );
+        }
+        err => panic!("unexpected error: {err}"),
+    }
+    assert_eq!(
+        repo.head_name()?.expect("attached").as_bstr(),
+        "refs/heads/main",
+        "nothing changed"
+    );
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"local change");
+
+    repo.checkout(
+        "other",
+        gix::checkout::Options {
+            force: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a.other\n");
+    assert_eq!(std::fs::read(workdir.join("new"))?, b"new\n");
+    assert!(!repo.is_dirty()?);
+    Ok(())
+}
+
+#[test]
+fn local_changes_to_unaffected_paths_are_kept() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("unchanged"), "local change")?;
+
+    repo.checkout("other", Default::default())?;
+    assert_eq!(std::fs::read(workdir.join("unchanged"))?, b"local change");
+    assert!(repo.is_dirty()?, "the modification is still visible");
+    Ok(())
+}
@@ -0,0 +1,208 @@
+use gix::{cherry_pick::Options, merge::ConflictKind};
+
+use crate::repo_rw;
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::objs::Commit> {
+    Ok(repo.rev_parse_single(spec)?.object()?.into_commit().decode()?.into())
+}
+
+#[test]
+fn clean_pick_creates_commit_with_original_author_and_message() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_cherry_pick_revert_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let previous_head = repo.head_id()?.detach();
+
+    let out = repo.cherry_pick("topic~1", Options::default())?;
+    assert!(out.conflicts.is_empty());
+    let new_id = out.commit_id.expect("a commit was created");
+    assert_eq!(repo.head_id()?, new_id, "the branch HEAD points to was moved");
+    assert_eq!(repo.head_name()?.expect("attached").as_bstr(), "refs/heads/main");
+
+    let picked = commit(&repo, "topic~1")?;
+    let new = commit(&repo, "HEAD")?;
+    assert_eq!(new.parents.as_slice(), &[previous_head]);
+    assert_eq!(new.message, picked.message);
+    assert_eq!(new.author, picked.author);
+    assert_eq!(new.committer.name, "gitoxide");
+    assert_eq!(
+        std::fs::read(workdir.join("lines"))?,
+        b"1 topic\n2\n3\n4\n5\n6\n7 main\n",
+        "both changes are present"
+    );
+    assert_eq!(repo.state(), None);
+    assert!(!repo.is_dirty()?);
+    Ok(())
+}
+
+#[test]
+fn conflicting_pick_records_state_and_unmerged_index() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_cherry_pick_revert_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let previous_head = repo.head_id()?;
+
+    let out = repo.cherry_pick("topic", Options::default())?;
+    assert_eq!(out.commit_id, None);
+    assert_eq!(out.conflicts.len(), 1);
+    assert_eq!(out.conflicts[0].rela_path, "conflict");
+    assert_eq!(out.conflicts[0].kind, ConflictKind::Content);
+    assert_eq!(repo.head_id()?, previous_head, "HEAD didn't move");
+    assert_eq!(repo.state(), Some(gix::state::InProgress::CherryPick));
+    assert_eq!(
+        std::fs::read_to_string(repo.path().join("CHERRY_PICK_HEAD"))?.trim(),
+        repo.rev_parse_single("topic")?.to_string()
+    );
+    assert!(std::fs::read_to_string(repo.path().join("MERGE_MSG"))?.starts_with("change conflict\n"));
+
+    let index = repo.open_index()?;
+    let stages: Vec<_> = index
+        .entries()
+        .iter()
+        .filter(|e| e.path(&index) == "conflict")
+        .map(gix::index::Entry::stage)
+        .collect();
+    assert_eq!(
+        stages,
+        [
+            gix::index::entry::Stage::Base,
+            gix::index::entry::Stage::Ours,
+            gix::index::entry::Stage::Theirs
+        ]
+    );
+    let conflict = std::fs::read_to_string(workdir.join("conflict"))?;
+    assert!(conflict.starts_with("<<<<<<< HEAD\nc.main\n=======\nc.topic\n>>>>>>> "));
+    assert!(conflict.ends_with(" (change conflict)\n"));
+
+    assert!(
+        matches!(
+            repo.cherry_pick("topic~1", Options::default()),
+            Err(gix::cherry_pick::Error::InProgress { .. })
+        ),
+        "the unfinished operation has to be concluded first"
+    );
+    Ok(())
+}
+
+#[test]
+fn no_commit_only_changes_index_and_worktree() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_cherry_pick_revert_repo.sh")?;
+    let previous_head = repo.head_id()?;
+
+    let out = repo.cherry_pick(
+        "topic~1",
+        Options {
+            no_commit: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(out.commit_id, None);
+    assert!(out.conflicts.is_empty());
+    assert_eq!(repo.head_id()?, previous_head);
+    assert_eq!(repo.state(), None, "there is no CHERRY_PICK_HEAD");
+    assert!(repo.path().join("MERGE_MSG").is_file());
+    let index = repo.open_index()?;
+    let lines = index.entry_by_path("lines".into()).expect("tracked");
+    assert_eq!(
+        repo.find_object(lines.id)?.data,
+        b"1 topic\n2\n3\n4\n5\n6\n7 main\n",
+        "the change was staged"
+    );
+    Ok(())
+}
+
+#[test]
+fn revert_applies_reverse_changes() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_cherry_pick_revert_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let reverted = repo.head_id()?.detach();
+
+    let out = repo.revert("HEAD", Options::default())?;
+    assert!(out.conflicts.is_empty());
+    let new = commit(&repo, "HEAD")?;
+    assert_eq!(
+        new.message,
+        format!("Revert \"change last line and conflict\"\n\nThis reverts commit {reverted}.\n")
+    );
+    assert_eq!(new.author.name, "gitoxide", "the author is the one reverting");
+    assert_eq!(std::fs::read(workdir.join("lines"))?, b"1\n2\n3\n4\n5\n6\n7\n");
+    assert_eq!(std::fs::read(workdir.join("conflict"))?, b"c\n");
+
+    assert!(
+        matches!(
+            repo.revert("HEAD~1", Options::default()),
+            Err(gix::cherry_pick::Error::Empty { .. })
+        ),
+        "the changes of the original commit are gone already"
+    );
+    Ok(())
+}
+
+#[test]
+fn mainline_must_only_be_set_for_merges() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_cherry_pick_revert_repo.sh")?;
+    let err = repo
+        .cherry_pick(
+            "topic",
+            Options {
+                mainline: Some(1),
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        gix::cherry_pick::Error::InvalidMainline { parent_count: 1, .. }
+    ));
+    Ok(())
+}
+
+mod text_merge {
+    use gix::merge::blob::{text, Labels, Resolution};
+
+    fn merge(ancestor: &str, ours: &str, theirs: &str) -> (Resolution, String) {
+        let mut out = Vec::new();
+        let labels = Labels {
+            ours: Some("ours".into()),
+            theirs: Some("theirs".into()),
+        };
+        let res = text(
+            ancestor.as_bytes(),
+            ours.as_bytes(),
+            theirs.as_bytes(),
+            labels,
+            &mut out,
+        );
+        (res, String::from_utf8(out).expect("valid"))
+    }
+
+    #[test]
+    fn non_overlapping_changes_merge_cleanly() {
+        assert_eq!(
+            merge("a\nb\nc\nd\n", "A\nb\nc\nd\n", "a\nb\nc\nD\n"),
+            (Resolution::Complete, "A\nb\nc\nD\n".into())
+        );
+        assert_eq!(
+            merge("a\nb\n", "a\nx\nb\n", "a\nx\nb\n"),
+            (Resolution::Complete, "a\nx\nb\n".into()),
+            "identical changes are taken once"
+        );
+    }
+
+    #[test]
+    fn overlapping_changes_conflict() {
+        assert_eq!(
+            merge("a\nb\nc\n", "a\nB\nc\n", "a\nb2\nc\n"),
+            (
+                Resolution::Conflict,
+                "a\n<<<<<<< ours\nB\n=======\nb2\n>>>>>>> theirs\nc\n".into()
+            )
+        );
+        assert_eq!(
+            merge("", "a", "b"),
+            (
+                Resolution::Conflict,
+                "<<<<<<< ours\na\n=======\nb\n>>>>>>> theirs\n".into()
+            ),
+            "missing newlines are added to keep markers on their own line"
+        );
+    }
+}
@@ -191,3 +191,107 @@ fn author_from_different_config_sections() -> crate::Result {
     );
     Ok(())
 }
+
+mod repository_identity {
+    use gix::repository::identity::{Error, Kind};
+    use gix_testtools::{tempfile, Env};
+    use serial_test::serial;
+
+    fn repo_with_config(config: &[&str]) -> crate::Result<(gix::Repository, tempfile::TempDir)> {
+        let tmp = tempfile::tempdir()?;
+        let mut opts = gix::open::Options::isolated().config_overrides(config.iter().copied());
+        opts.permissions.env.identity = gix_sec::Permission::Allow;
+        opts.permissions.env.git_prefix = gix_sec::Permission::Allow;
+        let repo = gix::ThreadSafeRepository::init_opts(tmp.path(), gix::create::Kind::Bare, Default::default(), opts)?
+            .to_thread_local();
+        Ok((repo, tmp))
+    }
+
+    fn env() -> Env<'static> {
+        Env::new()
+            .unset("GIT_AUTHOR_NAME")
+            .unset("GIT_AUTHOR_EMAIL")
+            .set("GIT_AUTHOR_DATE", "@42 +0030")
+            .unset("GIT_COMMITTER_NAME")
+            .unset("GIT_COMMITTER_EMAIL")
+            .set("GIT_COMMITTER_DATE", "@43 -0100")
+            .unset("EMAIL")
+    }
+
+    #[test]
+    #[serial]
+    fn environment_then_section_then_user_then_email_variable() -> crate::Result {
+        let _env = env()
+            .set("GIT_AUTHOR_NAME", " env author. ")
+            .set("GIT_AUTHOR_EMAIL", "<author@env>")
+            .set("EMAIL", "email@env");
+        let (repo, _tmp) = repo_with_config(&[
+            "author.name=config author",
+            "author.email=",
+            "committer.name=config committer",
+            "user.email=user@config",
+        ])?;
+
+        let author = repo.identity(Kind::Author)?;
+        assert_eq!(
+            author.name, "env author",
+            "crud is removed, and the environment comes first"
+        );
+        assert_eq!(author.email, "author@env");
+        assert_eq!(author.time, gix_date::Time::new(42, 1800));
+
+        let committer = repo.identity(Kind::Committer)?;
+        assert_eq!(committer.name, "config committer");
+        assert_eq!(
+            committer.email, "user@config",
+            "empty values in configuration are ignored"
+        );
+        assert_eq!(committer.time, gix_date::Time::new(43, -3600));
+
+        let (repo, _tmp) = repo_with_config(&["user.name=user"])?;
+        let committer = repo.identity(Kind::Committer)?;
+        assert_eq!(committer.name, "user");
+        assert_eq!(
+            committer.email, "email@env",
+            "EMAIL is used if there is no configured email"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn use_config_only_prevents_auto_detection() -> crate::Result {
+        let _env = env().set("EMAIL", "email@env");
+        let (repo, _tmp) = repo_with_config(&["user.useConfigOnly=true"])?;
+        assert!(matches!(
+            repo.identity(Kind::Author),
+            Err(Error::EmailAutoDetectionDisabled)
+        ));
+
+        let (repo, _tmp) = repo_with_config(&["user.useConfigOnly=true", "committer.email=committer@config"])?;
+        assert!(
+            matches!(repo.identity(Kind::Author), Err(Error::NameAutoDetectionDisabled)),
+            "like in git, any configured email allows falling back to EMAIL"
+        );
+
+        let (repo, _tmp) = repo_with_config(&["user.useConfigOnly=true", "user.name=user", "user.email=user@config"])?;
+        assert_eq!(repo.identity(Kind::Author)?.name, "user");
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn invalid_names() -> crate::Result {
+        let _env = env().set("GIT_COMMITTER_NAME", "").set("GIT_AUTHOR_NAME", " .; ");
+        let (repo, _tmp) = repo_with_config(&["user.name=user", "user.email=user@config"])?;
+        assert!(matches!(
+            repo.identity(Kind::Committer),
+            Err(Error::EmptyName { email }) if email == "user@config"
+        ));
+        assert!(matches!(
+            repo.identity(Kind::Author),
+            Err(Error::NameWithoutValidCharacters { .. })
+        ));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 mod config_snapshot;
 mod identity;
+mod reload;
 mod remote;
 
 #[cfg(feature = "blocking-network-client")]
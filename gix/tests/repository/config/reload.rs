@@ -0,0 +1,112 @@
+use std::io::Write;
+
+fn repo_rw() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let mut opts = crate::util::restricted().strict_config(true);
+    opts.permissions.config.includes = true;
+    crate::util::repo_rw_opts("make_basic_repo.sh", opts)
+}
+
+fn append(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    std::fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)?
+        .write_all(content.as_bytes())
+}
+
+#[test]
+fn changes_on_disk_are_detected_and_applied() -> crate::Result {
+    let (mut repo, _keep) = repo_rw()?;
+    assert!(!repo.is_config_outdated(), "nothing changed yet");
+    assert!(!repo.reload_config_if_outdated()?, "no need to reload");
+    assert_eq!(repo.head_id()?.shorten()?.to_string(), "3189cd3");
+
+    append(&repo.git_dir().join("config"), "[core]\n\tabbrev = 4\n")?;
+    assert!(repo.is_config_outdated());
+    assert_eq!(
+        repo.head_id()?.shorten()?.to_string(),
+        "3189cd3",
+        "the configuration isn't reloaded automatically"
+    );
+    assert!(repo.reload_config_if_outdated()?);
+    assert!(!repo.is_config_outdated());
+    assert_eq!(
+        repo.head_id()?.shorten()?.to_string(),
+        "3189",
+        "cached values are refreshed"
+    );
+    assert_eq!(repo.config_snapshot().integer("core.abbrev"), Some(4));
+    Ok(())
+}
+
+#[test]
+fn included_files_are_watched() -> crate::Result {
+    let (mut repo, _keep) = repo_rw()?;
+    let included = repo.git_dir().join("included");
+    append(&repo.git_dir().join("config"), "[include]\n\tpath = included\n")?;
+    append(&included, "[test]\n\tvalue = first\n")?;
+    repo.reload_config()?;
+    assert_eq!(
+        repo.config_snapshot().string("test.value").expect("set").as_ref(),
+        "first"
+    );
+    assert!(!repo.is_config_outdated());
+
+    append(&included, "[test]\n\tvalue = second\n")?;
+    assert!(repo.is_config_outdated(), "included files are watched as well");
+    assert!(repo.reload_config_if_outdated()?);
+    assert_eq!(
+        repo.config_snapshot().string("test.value").expect("set").as_ref(),
+        "second"
+    );
+
+    std::fs::remove_file(&included)?;
+    assert!(repo.is_config_outdated(), "removed files are detected");
+    assert!(repo.reload_config_if_outdated()?);
+    assert_eq!(repo.config_snapshot().string("test.value"), None);
+    Ok(())
+}
+
+#[test]
+fn invalid_values_leave_the_configuration_untouched() -> crate::Result {
+    let (mut repo, _keep) = repo_rw()?;
+    append(&repo.git_dir().join("config"), "[core]\n\tabbrev = invalid\n")?;
+    assert!(repo.reload_config().is_err());
+    assert_eq!(repo.config_snapshot().string("core.abbrev"), None);
+    assert!(repo.is_config_outdated(), "it can be tried again later");
+    Ok(())
+}
+
+#[test]
+fn config_overrides_survive_reloading_and_cloning() -> crate::Result {
+    let (repo, _keep) = repo_rw()?;
+    let config = std::fs::read(repo.git_dir().join("config"))?;
+    let mut repo = repo.with_config_overrides(["core.abbrev=5", "test.value=api"])?;
+    assert_eq!(repo.head_id()?.shorten()?.to_string(), "3189c");
+    assert_eq!(
+        repo.config_snapshot().string("test.value").expect("set").as_ref(),
+        "api"
+    );
+
+    repo.reload_config()?;
+    assert_eq!(
+        repo.config_snapshot().integer("core.abbrev"),
+        Some(5),
+        "overrides are applied again after reloading"
+    );
+    assert_eq!(
+        repo.clone()
+            .config_snapshot()
+            .string("test.value")
+            .expect("set")
+            .as_ref(),
+        "api",
+        "clones see the overrides as well"
+    );
+    assert_eq!(
+        std::fs::read(repo.git_dir().join("config"))?,
+        config,
+        "nothing is written to disk"
+    );
+    Ok(())
+}
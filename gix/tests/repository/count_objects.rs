@@ -0,0 +1,52 @@
+use gix::bstr::ByteSlice;
+
+use crate::util::{named_subrepo_opts, restricted};
+
+#[test]
+fn empty_repository_has_no_objects() -> crate::Result {
+    let repo = named_subrepo_opts("make_count_objects_repo.sh", "empty", restricted())?;
+    assert_eq!(repo.count_objects()?, gix::count_objects::Outcome::default());
+    Ok(())
+}
+
+#[test]
+fn counts_match_git() -> crate::Result {
+    let repo = named_subrepo_opts("make_count_objects_repo.sh", "repo", restricted())?;
+    let outcome = repo.count_objects()?;
+    let expected = std::fs::read(
+        gix_testtools::scripted_fixture_read_only("make_count_objects_repo.sh")?.join("count-objects.out"),
+    )?;
+    let expected = |key: &str| -> u64 {
+        expected
+            .lines()
+            .find_map(|line| line.strip_prefix(format!("{key}: ").as_bytes()))
+            .and_then(|value| value.to_str().ok()?.parse().ok())
+            .expect("present in git output")
+    };
+    assert_eq!(outcome.loose_objects as u64, expected("count"));
+    assert_eq!(outcome.prune_packable as u64, expected("prune-packable"));
+    assert_eq!(outcome.packs as u64, expected("packs"));
+    assert_eq!(outcome.in_pack_objects, expected("in-pack"));
+    assert_eq!(outcome.garbage.len() as u64, expected("garbage"));
+
+    let objects_dir = repo.objects.store_ref().path();
+    assert_eq!(
+        outcome.garbage,
+        [
+            objects_dir.join("17/tmp_obj_garbage"),
+            objects_dir.join("pack/pack-0000000000000000000000000000000000000000.idx"),
+            objects_dir.join("pack/tmp_pack_garbage"),
+        ]
+    );
+    assert_eq!(outcome.garbage_size, 16, "the index is empty");
+    assert!(
+        outcome.loose_size > 0 && outcome.loose_size <= expected("size") * 1024,
+        "git reports the disk usage in KiB, which is at least the length of the files"
+    );
+    assert_eq!(
+        outcome.pack_size / 1024,
+        expected("size-pack"),
+        "git reports the size of packs and their indices in KiB"
+    );
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use gix::{
+    bstr::ByteSlice,
+    fast_export::{Error, Marks, Options, Outcome, SignedTags},
+};
+
+use crate::named_repo;
+
+fn expected(repo: &gix::Repository, file_name: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(repo.work_dir().expect("non-bare").join(file_name))
+}
+
+fn export(
+    repo: &gix::Repository,
+    references: &[&str],
+    marks: &mut Marks,
+    options: Options,
+) -> Result<(Vec<u8>, Outcome), Error> {
+    let mut out = Vec::new();
+    let outcome = repo.fast_export(references, marks, &mut out, options)?;
+    Ok((out, outcome))
+}
+
+#[test]
+fn stream_matches_git() -> crate::Result {
+    let repo = named_repo("make_fast_export_repo.sh")?;
+    let mut marks = Marks::default();
+    let (out, outcome) = export(
+        &repo,
+        &["main", "topic", "v1", "nested", "light", "other"],
+        &mut marks,
+        Options::default(),
+    )?;
+    assert_eq!(
+        out.as_bstr(),
+        expected(&repo, "all.stream")?.as_bstr(),
+        "commits are labelled with the first reference reaching them, and nested tags are supported"
+    );
+    assert_eq!(
+        outcome,
+        Outcome {
+            blobs: 7,
+            commits: 5,
+            tags: 3,
+            resets: 0,
+        }
+    );
+    assert_eq!(marks.len(), 15);
+    assert_eq!(marks.id(12), Some(repo.rev_parse_single("main")?.detach().as_ref()));
+    Ok(())
+}
+
+#[test]
+fn marks_allow_incremental_exports() -> crate::Result {
+    let repo = named_repo("make_fast_export_repo.sh")?;
+    let mut marks = Marks::default();
+    let (out, _) = export(&repo, &["other"], &mut marks, Options::default())?;
+    assert_eq!(out.as_bstr(), expected(&repo, "other.stream")?.as_bstr());
+
+    let git_marks = Marks::from_bytes(&expected(&repo, "other.marks")?)?;
+    for (mark, id) in git_marks.iter() {
+        assert_eq!(
+            marks.id(mark),
+            Some(id),
+            "git only writes the marks of commits, but they are the same"
+        );
+    }
+    let mut buf = Vec::new();
+    marks.write_to(&mut buf)?;
+    assert_eq!(Marks::from_bytes(&buf)?, marks, "marks can be written and read back");
+
+    let (out, outcome) = export(&repo, &["main", "topic"], &mut marks, Options::default())?;
+    assert_eq!(
+        out.as_bstr(),
+        expected(&repo, "incremental.stream")?.as_bstr(),
+        "marked objects and their history aren't exported again, but marked tips are set with `reset`"
+    );
+    assert_eq!(
+        outcome,
+        Outcome {
+            blobs: 1,
+            commits: 3,
+            tags: 0,
+            resets: 0,
+        }
+    );
+
+    let (out, outcome) = export(&repo, &["main", "topic"], &mut marks, Options::default())?;
+    assert_eq!(
+        out.as_bstr(),
+        "reset refs/heads/topic\nfrom :9\n\nreset refs/heads/main\nfrom :12\n\n",
+        "with everything marked, only references are set"
+    );
+    assert_eq!(outcome.resets, 2);
+    Ok(())
+}
+
+#[test]
+fn signed_tags() -> crate::Result {
+    let repo = named_repo("make_fast_export_repo.sh")?;
+    let marks = Marks::from_bytes(&expected(&repo, "other.marks")?)?;
+    assert!(matches!(
+        export(&repo, &["signed"], &mut marks.clone(), Options::default()),
+        Err(Error::SignedTag { name }) if name == "refs/tags/signed"
+    ));
+
+    for (signed_tags, file_name) in [
+        (SignedTags::Strip, "signed-stripped.stream"),
+        (SignedTags::Verbatim, "signed-verbatim.stream"),
+    ] {
+        let (out, _) = export(
+            &repo,
+            &["signed"],
+            &mut marks.clone(),
+            Options {
+                signed_tags,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(out.as_bstr(), expected(&repo, file_name)?.as_bstr(), "{signed_tags:?}");
+    }
+    Ok(())
+}
+
+#[test]
+fn anonymize() -> crate::Result {
+    let repo = named_repo("make_fast_export_repo.sh")?;
+    let references = ["main", "topic", "v1", "nested", "light", "other"];
+    let options = Options {
+        anonymize: true,
+        ..Default::default()
+    };
+    let (out, outcome) = export(&repo, &references, &mut Marks::default(), options)?;
+    let (_, plain_outcome) = export(&repo, &references, &mut Marks::default(), Options::default())?;
+    assert_eq!(outcome, plain_outcome, "the shape of the history is the same");
+
+    for revealing in [
+        "author@",
+        "committer@",
+        "Other Author",
+        "initial",
+        "release",
+        "main",
+        "topic",
+        "v1",
+        "dir/",
+        "file",
+        "script",
+        "#!/bin/sh",
+    ] {
+        assert!(
+            out.find(revealing).is_none(),
+            "'{revealing}' doesn't appear in the anonymized stream"
+        );
+    }
+    let out = out.to_str()?;
+    assert!(
+        out.contains("\nauthor User 1 <user1@example.com> 946684800 +0000\ncommitter User 0 <user0@example.com> 946771200 +0000\n"),
+        "identities keep their time"
+    );
+    assert!(
+        out.contains("\ncommit refs/heads/ref1\n"),
+        "reference categories are kept"
+    );
+    assert!(
+        out.contains("\nM 100644 :2 path1/path2/path3\n"),
+        "paths are anonymized by component"
+    );
+    assert!(out.contains("\ntag ref5\n"));
+    assert!(out.contains("\ndata 16\nanonymous blob 0\n"));
+    assert!(out.contains("\ndata 16\nsubject 0\n\nbody\n"));
+    assert_eq!(
+        out.matches("\ndata 13\ntag message 0\n").count(),
+        2,
+        "tags with the same message have the same anonymized message"
+    );
+
+    let (again, _) = export(&repo, &references, &mut Marks::default(), options)?;
+    assert_eq!(again, out.as_bytes(), "anonymization is deterministic");
+    Ok(())
+}
+
+#[test]
+fn marks_are_validated() {
+    for (input, line_number) in [
+        (&b":1 abc\n"[..], 1),
+        (b"\n:0 0000000000000000000000000000000000000000", 2),
+    ] {
+        assert!(matches!(
+            Marks::from_bytes(input),
+            Err(gix::fast_export::marks::Error::Malformed { line_number: actual, .. }) if actual == line_number
+        ));
+    }
+}
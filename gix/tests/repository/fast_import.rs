@@ -0,0 +1,344 @@
+use gix::{
+    bstr::ByteSlice,
+    fast_export::Marks,
+    fast_import::{Command, Error, FileChange, FileData, ObjectRef, Options, Outcome, Parser},
+};
+use gix_testtools::tempfile;
+
+use crate::{named_repo, util::named_subrepo_opts};
+
+fn empty_bare_repo() -> crate::Result<(tempfile::TempDir, gix::Repository)> {
+    let tmp = tempfile::tempdir()?;
+    let repo = gix::ThreadSafeRepository::init_opts(
+        tmp.path(),
+        gix::create::Kind::Bare,
+        gix::create::Options::default(),
+        gix::open::Options::isolated(),
+    )?
+    .into();
+    Ok((tmp, repo))
+}
+
+fn import(
+    repo: &gix::Repository,
+    stream: &[u8],
+    marks: &mut Marks,
+    options: Options,
+) -> Result<(Vec<u8>, Outcome), Error> {
+    let mut response = Vec::new();
+    let outcome = repo.fast_import(stream, &mut response, marks, options)?;
+    Ok((response, outcome))
+}
+
+fn stream(repo: &gix::Repository, file_name: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(repo.work_dir().expect("non-bare").join(file_name))
+}
+
+fn id(repo: &gix::Repository, spec: &str) -> Result<gix::ObjectId, gix::revision::spec::parse::single::Error> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+fn references(repo: &gix::Repository) -> crate::Result<Vec<(gix::refs::FullName, gix::ObjectId)>> {
+    Ok(repo
+        .references()?
+        .all()?
+        .map(|reference| {
+            reference
+                .map(|reference| (reference.name().to_owned(), reference.id().detach()))
+                .map_err(|err| err.to_string())
+        })
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn objects_and_references_match_git() -> crate::Result {
+    let source = named_repo("make_fast_export_repo.sh")?;
+    let (_tmp, repo) = empty_bare_repo()?;
+    let mut marks = Marks::default();
+    let (response, outcome) = import(&repo, &stream(&source, "all.stream")?, &mut marks, Options::default())?;
+    assert!(response.is_empty());
+    assert_eq!(outcome.blobs, 7);
+    assert_eq!(outcome.commits, 5);
+    assert_eq!(
+        outcome.tags, 3,
+        "the tag of a tag causes the inner tag to be written twice"
+    );
+    assert!(outcome.rejected_references.is_empty());
+    assert_eq!(marks.len(), 15);
+
+    let expected = named_subrepo_opts("make_fast_export_repo.sh", "imported", gix::open::Options::isolated())?;
+    let expected_refs = references(&expected)?;
+    let actual_refs = references(&repo)?;
+    assert_eq!(actual_refs, expected_refs, "git fast-import produces the same objects");
+    assert_eq!(
+        outcome.updated_references.len(),
+        expected_refs.len(),
+        "each reference is reported once"
+    );
+    for name in ["main", "topic", "other", "v1", "light"] {
+        assert_eq!(
+            id(&repo, name)?,
+            id(&source, name)?,
+            "{name}: exported history is reproduced exactly"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn marks_allow_incremental_imports() -> crate::Result {
+    let source = named_repo("make_fast_export_repo.sh")?;
+    let (_tmp, repo) = empty_bare_repo()?;
+    let mut marks = Marks::default();
+    import(&repo, &stream(&source, "other.stream")?, &mut marks, Options::default())?;
+    for (mark, id) in Marks::from_bytes(&stream(&source, "other.marks")?)?.iter() {
+        assert_eq!(marks.id(mark), Some(id), "marks refer to the same objects as on export");
+    }
+
+    let (_, outcome) = import(
+        &repo,
+        &stream(&source, "incremental.stream")?,
+        &mut marks,
+        Options::default(),
+    )?;
+    assert_eq!(outcome.commits, 3);
+    for name in ["main", "topic", "other"] {
+        assert_eq!(id(&repo, name)?, id(&source, name)?, "{name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn responses_and_tree_edits() -> crate::Result {
+    let (_tmp, repo) = empty_bare_repo()?;
+    let mut marks = Marks::default();
+    let stream = br#"# a comment
+feature date-format=rfc2822
+blob
+mark :1
+data 6
+hello
+
+commit refs/heads/main
+mark :2
+committer C O Mitter <c@example.com> Fri, 13 Feb 2009 23:31:30 +0100
+data <<EOT
+first
+EOT
+M 100644 :1 dir/sub/file
+M 644 :1 dir/other
+M 755 inline "with \"quote\""
+data 2
+x
+ls "dir/sub/file"
+ls "missing"
+
+get-mark :2
+cat-blob :1
+commit refs/heads/main
+mark :3
+committer C O Mitter <c@example.com> Fri, 13 Feb 2009 23:31:30 +0100
+data 7
+second
+R dir/sub moved
+C "with \"quote\"" with space
+D dir/other
+ls :2 dir
+
+progress finished
+done
+ignored
+"#;
+    let (response, outcome) = import(&repo, stream, &mut marks, Options::default())?;
+    assert_eq!(outcome.blobs, 2, "inline data counts as blob");
+    assert_eq!(outcome.commits, 2);
+    assert_eq!(
+        outcome.updated_references,
+        vec![gix::refs::FullName::try_from("refs/heads/main")?]
+    );
+
+    let blob = marks.id(1).expect("set");
+    let first = marks.id(2).expect("set");
+    let first_dir = id(&repo, &format!("{first}:dir"))?;
+    assert_eq!(
+        response.as_bstr(),
+        format!(
+            "100644 blob {blob}\tdir/sub/file\nmissing missing\n{first}\n{blob} blob 6\nhello\n\n040000 tree {first_dir}\tdir\nprogress finished\n"
+        ),
+        "ls, get-mark, cat-blob and progress are answered"
+    );
+
+    let commit = repo.find_object(first)?.into_commit();
+    let commit = commit.decode()?;
+    assert_eq!(commit.message, "first\n");
+    assert_eq!(commit.author, commit.committer, "the author defaults to the committer");
+    assert_eq!(commit.committer.time.seconds, 1234567890 - 3600);
+    assert_eq!(commit.committer.time.offset, 3600);
+
+    let main = id(&repo, "main")?;
+    assert_eq!(repo.find_object(main)?.into_commit().parent_ids().count(), 1);
+    assert_eq!(id(&repo, "main:moved/file")?, blob, "directories can be renamed");
+    assert_eq!(
+        id(&repo, "main:with space")?,
+        id(&repo, r#"main:with "quote""#)?,
+        "paths can be quoted"
+    );
+    assert!(
+        repo.rev_parse_single("main:dir").is_err(),
+        "directories disappear with their last entry"
+    );
+    Ok(())
+}
+
+#[test]
+fn branches_that_do_not_fast_forward_are_rejected_unless_forced() -> crate::Result {
+    let (_tmp, repo) = empty_bare_repo()?;
+    let root_commit = |message: &str| {
+        format!(
+            "commit refs/heads/main\ncommitter C <c@example.com> 1234567890 +0000\ndata {}\n{message}\n",
+            message.len() + 1
+        )
+    };
+    import(
+        &repo,
+        root_commit("first").as_bytes(),
+        &mut Marks::default(),
+        Options::default(),
+    )?;
+    let first = id(&repo, "main")?;
+
+    let (_, outcome) = import(
+        &repo,
+        root_commit("second").as_bytes(),
+        &mut Marks::default(),
+        Options::default(),
+    )?;
+    assert_eq!(
+        outcome.rejected_references,
+        vec![gix::refs::FullName::try_from("refs/heads/main")?]
+    );
+    assert!(outcome.updated_references.is_empty());
+    assert_eq!(id(&repo, "main")?, first, "the branch is unchanged");
+
+    let (_, outcome) = import(
+        &repo,
+        format!("feature force\n{}", root_commit("second")).as_bytes(),
+        &mut Marks::default(),
+        Options::default(),
+    )?;
+    assert!(outcome.rejected_references.is_empty());
+    assert_ne!(id(&repo, "main")?, first, "forced updates are allowed");
+
+    import(
+        &repo,
+        format!(
+            "reset refs/heads/main\nfrom {}\n",
+            gix::ObjectId::null(gix::hash::Kind::Sha1)
+        )
+        .as_bytes(),
+        &mut Marks::default(),
+        Options::default(),
+    )?;
+    assert!(repo.try_find_reference("main")?.is_none(), "the null id deletes");
+    Ok(())
+}
+
+#[test]
+fn parser() -> crate::Result {
+    let stream = b"commit refs/heads/main\nauthor <a@example.com> 1 -0130\ncommitter C <c@example.com> 2 +0000\ndata 0\nfrom refs/heads/main^0\nmerge :1\nM 160000 0000000000000000000000000000000000000001 sub\nR \"a b\" c d\nN inline :1\ndata 4\nnote\ndeleteall\nls \"x\"\nreset refs/tags/t\n";
+    let commands = Parser::new(&stream[..], Default::default()).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(commands.len(), 2);
+    let Command::Commit(commit) = &commands[0] else {
+        panic!("first command is a commit")
+    };
+    let author = commit.author.as_ref().expect("present");
+    assert_eq!(author.name, "", "names are optional");
+    assert_eq!(author.time.offset, -5400);
+    assert_eq!(commit.from, Some(ObjectRef::Revision("refs/heads/main^0".into())));
+    assert_eq!(commit.merges, vec![ObjectRef::Mark(1)]);
+    assert_eq!(
+        commit.changes,
+        vec![
+            FileChange::Modify {
+                mode: gix::objs::tree::EntryKind::Commit.into(),
+                data: FileData::Object(ObjectRef::Id(gix::ObjectId::from_hex(
+                    b"0000000000000000000000000000000000000001"
+                )?)),
+                path: "sub".into(),
+            },
+            FileChange::Rename {
+                source: "a b".into(),
+                destination: "c d".into(),
+            },
+            FileChange::Note {
+                data: FileData::Inline(b"note".to_vec()),
+                commit: ObjectRef::Mark(1),
+            },
+            FileChange::DeleteAll,
+            FileChange::Ls {
+                object: None,
+                path: "x".into(),
+            },
+        ]
+    );
+    assert_eq!(
+        commands[1],
+        Command::Reset {
+            name: "refs/tags/t".into(),
+            from: None
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() -> crate::Result {
+    let (_tmp, repo) = empty_bare_repo()?;
+    let mut marks = Marks::default();
+    let run = |stream: &str| import(&repo, stream.as_bytes(), &mut Marks::default(), Options::default());
+
+    assert!(matches!(
+        run("blob\ndata 1\nx\nnonsense\n"),
+        Err(Error::Parse(gix::fast_import::parse::Error::Malformed {
+            line_number: 4,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        run("blob\ndata 10\nx\n"),
+        Err(Error::Parse(gix::fast_import::parse::Error::UnexpectedEnd))
+    ));
+    assert!(matches!(
+        run("commit refs/heads/main\ncommitter C <c@example.com> 1 +2500\ndata 0\n"),
+        Err(Error::Parse(gix::fast_import::parse::Error::Malformed {
+            line_number: 2,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        run("commit refs/heads/main\ncommitter C <c@example.com> 1 +0000\ndata 0\nfrom :3\n"),
+        Err(Error::UnknownMark { mark: 3 })
+    ));
+    assert!(matches!(
+        run("feature export-marks=marks\n"),
+        Err(Error::UnsafeFeature { .. })
+    ));
+    assert!(matches!(
+        run("feature unknown\n"),
+        Err(Error::UnsupportedFeature { .. })
+    ));
+
+    let tmp = tempfile::tempdir()?;
+    let marks_path = tmp.path().join("marks");
+    import(
+        &repo,
+        format!("feature export-marks={}\nblob\nmark :1\ndata 0\n", marks_path.display()).as_bytes(),
+        &mut marks,
+        Options {
+            allow_unsafe_features: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(Marks::from_bytes(&std::fs::read(marks_path)?)?, marks);
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use gix::{bstr::ByteSlice, for_each_ref};
+
+use crate::util::named_repo;
+
+fn fixture(name: &str) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(
+        gix_testtools::scripted_fixture_read_only("make_for_each_ref_repo.sh")?.join(name),
+    )?)
+}
+
+fn for_each_ref(
+    repo: &gix::Repository,
+    format: &for_each_ref::Format,
+    patterns: &[&str],
+    options: for_each_ref::Options,
+) -> crate::Result<Vec<u8>> {
+    let outcome = repo.for_each_ref(patterns, format, options)?;
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    Ok(out)
+}
+
+#[test]
+fn formats_are_rendered_like_git() -> crate::Result {
+    let repo = named_repo("make_for_each_ref_repo.sh")?;
+    for index in 0..11 {
+        let format = fixture(&format!("format-{index}"))?;
+        let actual = for_each_ref(
+            &repo,
+            &for_each_ref::Format::parse(format.as_bstr())?,
+            &[],
+            Default::default(),
+        )?;
+        assert_eq!(
+            actual.as_bstr(),
+            fixture(&format!("format-{index}.out"))?.as_bstr(),
+            "{format:?}",
+            format = format.as_bstr()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn default_format() -> crate::Result {
+    let repo = named_repo("make_for_each_ref_repo.sh")?;
+    let actual = for_each_ref(&repo, &Default::default(), &[], Default::default())?;
+    assert_eq!(actual.as_bstr(), fixture("format-0.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn colors() -> crate::Result {
+    let repo = named_repo("make_for_each_ref_repo.sh")?;
+    let format = for_each_ref::Format::parse(fixture("format-10")?.as_bstr())?;
+    let actual = for_each_ref(&repo, &format, &[], for_each_ref::Options { color: true })?;
+    assert_eq!(actual.as_bstr(), fixture("format-10-color.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn patterns() -> crate::Result {
+    let repo = named_repo("make_for_each_ref_repo.sh")?;
+    let format = for_each_ref::Format::parse(fixture("format-1")?.as_bstr())?;
+    let actual = for_each_ref(
+        &repo,
+        &format,
+        &["refs/heads", "refs/tags/l*", "refs/remotes/origin/H"],
+        Default::default(),
+    )?;
+    assert_eq!(
+        actual.as_bstr(),
+        fixture("patterns.out")?.as_bstr(),
+        "patterns match as globs or up to a slash"
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_formats() {
+    for (format, expected) in [
+        ("%(refname", "The atom starting at byte 0 isn't terminated with ')'"),
+        ("%(unknown)", "Unknown field name \"unknown\""),
+        ("%(refname:long)", "Unrecognized modifier \"long\" of \"refname\""),
+        (
+            "%(*refname)",
+            "\"*refname\" doesn't refer to an object and can't be dereferenced with '*'",
+        ),
+        ("%(align)x%(end)", "%(align) needs a width"),
+        ("%(align:5)x", "%(align) isn't closed with %(end)"),
+        ("%(if)x%(end)", "%(if) needs a %(then) before %(end)"),
+        (
+            "%(if)x%(then)y%(else)z%(else)",
+            "%(else) isn't used after %(if) or %(align)",
+        ),
+        ("x%(end)", "%(end) isn't used after %(if) or %(align)"),
+        ("%(color:rainbow)", "Unrecognized color \"rainbow\""),
+    ] {
+        let err = for_each_ref::Format::parse(format.into()).unwrap_err();
+        assert_eq!(err.to_string(), expected, "{format}");
+    }
+}
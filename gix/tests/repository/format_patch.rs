@@ -0,0 +1,114 @@
+use gix::format_patch::{Error, Options};
+
+use crate::{named_repo, repo_rw};
+
+fn options() -> Options {
+    Options {
+        signature: Some("sig".into()),
+        ..Default::default()
+    }
+}
+
+fn expected(repo: &gix::Repository, file_name: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(repo.work_dir().expect("non-bare").join("patches").join(file_name))
+}
+
+#[test]
+fn mails_match_git_for_a_range() -> crate::Result {
+    let repo = named_repo("make_format_patch_repo.sh")?;
+    let patches = repo.format_patch("base..text", options())?;
+    assert_eq!(
+        patches.iter().map(|p| p.file_name.as_str()).collect::<Vec<_>>(),
+        [
+            "0001-change-two-distant-lines.patch",
+            "0002-add-remove-rename-and-make-executable.patch",
+            "0003-mlauts-in-a-subject-that-is-long-enough-to-need-more.patch",
+            "0004-a-very-long-subject-line-that-goes-on-and-on-and-on-.patch",
+        ],
+        "oldest first, with file names sanitized like git does"
+    );
+    for patch in &patches {
+        assert_eq!(
+            patch.mail,
+            expected(&repo, &patch.file_name)?,
+            "{} is the same as produced by git",
+            patch.file_name
+        );
+    }
+    assert_eq!(patches[3].commit, repo.rev_parse_single("text")?.detach());
+    Ok(())
+}
+
+#[test]
+fn single_commits_are_not_numbered_by_default() -> crate::Result {
+    let repo = named_repo("make_format_patch_repo.sh")?;
+    let patches = repo.format_patch("text~3^!", options())?;
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].mail, expected(&repo, "single.patch")?);
+
+    let patches = repo.format_patch(
+        "text~3^!",
+        Options {
+            numbered: Some(true),
+            start_number: 7,
+            subject_prefix: "RFC PATCH".into(),
+            signature: None,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(patches[0].file_name, "0007-change-two-distant-lines.patch");
+    let mail = patches[0].mail.to_string();
+    assert!(mail.contains("\nSubject: [RFC PATCH 7/7] change two distant lines\n"));
+    assert!(!mail.contains("\n-- \n"), "there is no signature section");
+    Ok(())
+}
+
+#[test]
+fn mails_can_be_applied_with_am() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_format_patch_repo.sh")?;
+    let patches = repo.format_patch("base..main", options())?;
+    assert_eq!(patches.len(), 5, "the binary change is included as well");
+    assert!(patches[4].mail.to_string().contains("\nGIT binary patch\nliteral 8\n"));
+
+    let mbox: Vec<u8> = patches.iter().flat_map(|p| p.mail.iter().copied()).collect();
+    let head_id = match repo.am(&mbox, Default::default())? {
+        gix::am::Outcome::Finished { head_id } => head_id,
+        other => unreachable!("all patches apply cleanly, got {other:?}"),
+    };
+    let applied = repo.find_object(head_id)?.into_commit();
+    let original = repo.rev_parse_single("main")?.object()?.into_commit();
+    assert_eq!(applied.tree_id()?, original.tree_id()?, "the mails carry all changes");
+    assert_eq!(applied.message_raw()?, original.message_raw()?);
+    assert_eq!(applied.author()?, original.author()?);
+    Ok(())
+}
+
+#[test]
+fn diff_attributes_decide_which_files_are_binary() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_format_patch_repo.sh")?;
+    let mail = repo.format_patch("main..attributes", options())?[0].mail.to_string();
+    assert!(mail.contains(" marked.txt |   1 +\n"), "{mail}");
+    assert!(mail.contains(" forced.dat | Bin 0 -> 4 bytes\n"), "{mail}");
+
+    std::fs::write(repo.git_dir().join("info").join("attributes"), "*.txt -diff\n*.dat diff\n")?;
+    let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+    let mail = repo.format_patch("main..attributes", options())?[0].mail.to_string();
+    assert!(mail.contains(" marked.txt | Bin 0 -> 5 bytes\n"), "{mail}");
+    assert!(mail.contains(" forced.dat |   1 +\n"), "{mail}");
+    assert!(mail.contains("\n+a\0b\n"), "{mail}");
+    Ok(())
+}
+
+#[test]
+fn ranges_must_be_simple() -> crate::Result {
+    let repo = named_repo("make_format_patch_repo.sh")?;
+    assert!(matches!(
+        repo.format_patch("base...text", options()),
+        Err(Error::UnsupportedSpec { .. })
+    ));
+    assert!(
+        repo.format_patch("text..base", options())?.is_empty(),
+        "nothing to do if the range is empty"
+    );
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use gix::{
+    bstr::ByteSlice,
+    fsck::{Finding, MessageId, Options, Severity},
+};
+
+use crate::util::{named_subrepo_opts, restricted};
+
+fn repo(name: &str) -> crate::Result<gix::Repository> {
+    Ok(named_subrepo_opts("make_fsck_repo.sh", name, restricted())?)
+}
+
+fn id_from_file(repo: &gix::Repository, file_name: &str) -> crate::Result<gix::ObjectId> {
+    let hex = std::fs::read(repo.work_dir().expect("non-bare").join(file_name))?;
+    Ok(gix::ObjectId::from_hex(hex.trim_end())?)
+}
+
+#[test]
+fn clean_repository_has_no_findings() -> crate::Result {
+    let outcome = repo("clean")?.fsck(Options::default())?;
+    assert_eq!(outcome.findings, []);
+    assert!(!outcome.has_errors());
+    Ok(())
+}
+
+#[test]
+fn output_matches_git() -> crate::Result {
+    let repo = repo("corrupt")?;
+    let outcome = repo.fsck(Options::default())?;
+    assert!(outcome.has_errors());
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    assert_eq!(
+        out,
+        std::fs::read(repo.work_dir().expect("non-bare").join("fsck.out"))?,
+        "git fsck"
+    );
+
+    let missing_blob = id_from_file(&repo, "missing-blob.id")?;
+    assert_eq!(
+        outcome.findings.last(),
+        Some(&Finding::Missing {
+            id: missing_blob,
+            kind: Some(gix::object::Kind::Blob)
+        }),
+        "it's referenced by the index and a commit, but reported only once"
+    );
+    Ok(())
+}
+
+#[test]
+fn checks_can_be_disabled() -> crate::Result {
+    let repo = repo("corrupt")?;
+    let outcome = repo.fsck(Options {
+        check_objects: false,
+        ..Default::default()
+    })?;
+    assert_eq!(outcome.findings.len(), 1, "only the missing blob is found");
+
+    let outcome = repo.fsck(Options {
+        check_connectivity: false,
+        ..Default::default()
+    })?;
+    assert_eq!(
+        outcome
+            .findings
+            .iter()
+            .map(|finding| match finding {
+                Finding::Object { problem, severity, .. } => (problem.id, *severity),
+                Finding::Missing { .. } => unreachable!("connectivity isn't checked"),
+            })
+            .collect::<Vec<_>>(),
+        [
+            (MessageId::BadDate, Severity::Error),
+            (MessageId::HasDotgit, Severity::Warn)
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn severities_are_configurable() -> crate::Result {
+    let mut repo = repo("corrupt")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value("fsck", None, "badDate", "ignore")?;
+        config.set_raw_value("fsck", None, "hasdotgit", "error")?;
+        config.set_raw_value("fsck", None, "someFutureId", "ignore")?;
+    }
+    let outcome = repo.fsck(Options {
+        check_connectivity: false,
+        ..Default::default()
+    })?;
+    assert_eq!(outcome.findings.len(), 1);
+    assert_eq!(outcome.findings[0].id(), id_from_file(&repo, "dotgit-tree.id")?);
+    assert_eq!(
+        outcome.findings[0].severity(),
+        Severity::Error,
+        "message ids are case-insensitive"
+    );
+
+    repo.config_snapshot_mut()
+        .set_raw_value("fsck", None, "badDate", "sometimes")?;
+    let outcome = repo.fsck(Options::default())?;
+    assert_eq!(
+        outcome.findings[0].severity(),
+        Severity::Error,
+        "invalid severities are ignored in lenient mode"
+    );
+
+    let mut repo = named_subrepo_opts("make_fsck_repo.sh", "corrupt", restricted().strict_config(true))?;
+    repo.config_snapshot_mut()
+        .set_raw_value("fsck", None, "badDate", "sometimes")?;
+    assert_eq!(
+        repo.fsck(Options::default()).unwrap_err().to_string(),
+        "The key \"fsck.<msg-id>=sometimes\" was invalid"
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_list_excludes_objects_from_checks() -> crate::Result {
+    let mut repo = repo("corrupt")?;
+    let tmp = gix_testtools::tempfile::tempdir()?;
+    let skip_list = tmp.path().join("skip-list");
+    std::fs::write(
+        &skip_list,
+        format!("# known to be broken\n{}\n\n", id_from_file(&repo, "bad-commit.id")?),
+    )?;
+    repo.config_snapshot_mut().set_raw_value(
+        "fsck",
+        None,
+        "skipList",
+        gix::path::into_bstr(skip_list.as_path()).as_ref(),
+    )?;
+    let outcome = repo.fsck(Options {
+        check_connectivity: false,
+        ..Default::default()
+    })?;
+    assert_eq!(outcome.findings.len(), 1);
+    assert_eq!(outcome.findings[0].id(), id_from_file(&repo, "dotgit-tree.id")?);
+    Ok(())
+}
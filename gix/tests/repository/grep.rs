@@ -0,0 +1,132 @@
+use gix::{
+    bstr::ByteSlice,
+    grep::{Options, Pattern, Source},
+};
+
+use crate::named_repo;
+
+fn grep(repo: &gix::Repository, pathspecs: &[&str], options: Options) -> crate::Result<Vec<u8>> {
+    let outcome = repo.grep(&[Pattern::from("needle")], pathspecs, options)?;
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    Ok(out)
+}
+
+fn ranges(line: &gix::grep::Line) -> Vec<(usize, usize)> {
+    line.matches.iter().map(|m| (m.start, m.end)).collect()
+}
+
+fn expected(repo: &gix::Repository, file_name: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(repo.work_dir().expect("non-bare").join(file_name))
+}
+
+#[test]
+fn output_matches_git_for_all_sources() -> crate::Result {
+    let repo = named_repo("make_grep_repo.sh")?;
+    for threads in [Some(1), None] {
+        let options = Options {
+            threads,
+            ..Default::default()
+        };
+        assert_eq!(
+            grep(&repo, &[], options)?,
+            expected(&repo, "grep-worktree.out")?,
+            "untracked files are ignored, and binary files are detected by content or attributes"
+        );
+        assert_eq!(
+            grep(
+                &repo,
+                &[],
+                Options {
+                    source: Source::Index,
+                    ..options
+                }
+            )?,
+            expected(&repo, "grep-index.out")?
+        );
+        assert_eq!(
+            grep(&repo, &[], Options { text: true, ..options })?,
+            expected(&repo, "grep-text.out")?
+        );
+    }
+
+    let head = repo.head_id()?.detach();
+    let out = grep(
+        &repo,
+        &["src"],
+        Options {
+            source: Source::Tree(head),
+            word: true,
+            ignore_case: true,
+            ..Default::default()
+        },
+    )?;
+    let expected = expected(&repo, "grep-tree-src.out")?;
+    assert_eq!(
+        out,
+        expected
+            .strip_prefix(b"HEAD:")
+            .expect("prefixed")
+            .replace("\nHEAD:", "\n")
+            .as_slice(),
+        "only the prefix of the tree-ish is missing"
+    );
+    Ok(())
+}
+
+#[test]
+fn matches_and_columns() -> crate::Result {
+    let repo = named_repo("make_grep_repo.sh")?;
+    let outcome = repo.grep(
+        &[Pattern::from("needle"), Pattern::from("case and")],
+        Some("src/lib.rs"),
+        Options::default(),
+    )?;
+    assert_eq!(outcome.files.len(), 1);
+    let lines = &outcome.files[0].lines;
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0].number, 1);
+    assert_eq!(ranges(&lines[0]), [(3, 9)]);
+    assert_eq!(lines[1].number, 3);
+    assert_eq!(
+        ranges(&lines[1]),
+        [(0, 6), (7, 15), (18, 24)],
+        "all matches of all patterns are listed, with overlapping ones merged"
+    );
+
+    let outcome = repo.grep(&[Pattern::from("link-target")], None::<&str>, Options::default())?;
+    assert!(outcome.files.is_empty(), "symlinks aren't followed in the worktree");
+    let outcome = repo.grep(
+        &[Pattern::from("link-target")],
+        Some("link"),
+        Options {
+            source: Source::Tree(repo.head_id()?.detach()),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(outcome.files.len(), 1, "but their target is searched in trees");
+    Ok(())
+}
+
+#[cfg(feature = "revparse-regex")]
+#[test]
+fn regular_expressions() -> crate::Result {
+    let repo = named_repo("make_grep_repo.sh")?;
+    let outcome = repo.grep(
+        &[Pattern::Regex(regex::bytes::Regex::new("(?i-u)needle[sS]?")?)],
+        Some("src/lib.rs"),
+        Options {
+            word: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome.files[0]
+            .lines
+            .iter()
+            .map(|line| (line.number, ranges(line)))
+            .collect::<Vec<_>>(),
+        [(1, vec![(3, 10)]), (2, vec![(0, 6)]), (3, vec![(18, 24)])]
+    );
+    Ok(())
+}
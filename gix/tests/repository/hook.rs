@@ -0,0 +1,180 @@
+use gix::hook::{Invocation, Outcome, RefUpdate, Run, TransactionState};
+
+use crate::{repo_rw, repo_rw_opts, restricted};
+
+#[cfg(unix)]
+fn install_hook(dir: &std::path::Path, name: &str, script: &str) -> crate::Result {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(name);
+    std::fs::write(&path, format!("#!/bin/sh\n{script}"))?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+#[test]
+fn hooks_dir_defaults_to_hooks_in_common_dir() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    assert_eq!(
+        repo.hooks_dir()?,
+        repo.current_dir().join(repo.common_dir()).join("hooks")
+    );
+    Ok(())
+}
+
+#[test]
+fn missing_hooks_are_not_run() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let outcome = repo.hooks()?.run(&Invocation::post_commit())?;
+    assert_eq!(outcome, None, "there is nothing to run");
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn non_executable_hooks_are_ignored() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let hooks = repo.hooks()?;
+    std::fs::create_dir_all(&hooks.dir)?;
+    std::fs::write(hooks.dir.join("post-commit"), "#!/bin/sh\nexit 1\n")?;
+    assert_eq!(
+        hooks.find("post-commit"),
+        None,
+        "git ignores hooks that can't be executed"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_receive_arguments_stdin_and_environment() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let mut hooks = repo.hooks()?;
+    install_hook(
+        &hooks.dir,
+        "reference-transaction",
+        "echo \"args: $*\"\necho \"cwd: $(pwd)\"\necho \"git-dir: $GIT_DIR\"\necho \"custom: $CUSTOM\"\ncat\nexit 3\n",
+    )?;
+
+    let id = repo.head_id()?.detach();
+    let null = gix::ObjectId::null(id.kind());
+    let invocation = Invocation::reference_transaction(
+        TransactionState::Prepared,
+        [RefUpdate {
+            previous: null,
+            new: id,
+            name: "refs/heads/new".try_into()?,
+        }],
+    )
+    .env("CUSTOM", "value");
+    let outcome = hooks.run(&invocation)?.expect("hook exists");
+    assert!(!outcome.success());
+    assert_eq!(outcome.code, Some(3));
+
+    let work_dir = gix_path::realpath(repo.work_dir().expect("non-bare"))?;
+    let git_dir = repo.current_dir().join(repo.git_dir());
+    assert_eq!(
+        String::from_utf8(outcome.stdout)?,
+        format!(
+            "args: prepared\ncwd: {}\ngit-dir: {}\ncustom: value\n{null} {id} refs/heads/new\n",
+            work_dir.display(),
+            git_dir.display()
+        ),
+        "hooks run in the worktree root and receive the documented arguments and input"
+    );
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn hooks_path_is_relative_to_the_worktree_root() -> crate::Result {
+    let (repo, _tmp) = repo_rw_opts(
+        "make_basic_repo.sh",
+        restricted().config_overrides(["core.hooksPath=custom"]),
+    )?;
+    let mut hooks = repo.hooks()?;
+    assert_eq!(
+        hooks.dir,
+        repo.current_dir()
+            .join(repo.work_dir().expect("non-bare"))
+            .join("custom")
+    );
+    install_hook(&hooks.dir, "post-checkout", "echo \"$*\"\n")?;
+
+    let id = repo.head_id()?.detach();
+    let outcome = hooks
+        .run(&Invocation::post_checkout(id, id, true))?
+        .expect("hook exists");
+    assert!(outcome.success());
+    assert_eq!(outcome.stdout, format!("{id} {id} 1\n").into_bytes());
+    Ok(())
+}
+
+#[test]
+fn closures_can_stub_hooks() -> crate::Result {
+    let mut seen = Vec::new();
+    let mut stub = |invocation: &Invocation| {
+        seen.push(invocation.clone());
+        Ok(Some(Outcome {
+            code: Some(1),
+            stdout: Vec::new(),
+        }))
+    };
+    let outcome = stub
+        .run(&Invocation::commit_msg(".git/COMMIT_EDITMSG"))?
+        .expect("stubbed");
+    assert!(!outcome.success());
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].name, "commit-msg");
+    assert_eq!(seen[0].args, [std::ffi::OsString::from(".git/COMMIT_EDITMSG")]);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn reference_transaction_hook_runs_when_editing_references() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+    let hooks = repo.hooks()?;
+    let log = repo.git_dir().join("hook.log");
+    install_hook(
+        &hooks.dir,
+        "reference-transaction",
+        &format!("echo \"$1\" >>{log}\ncat >>{log}\n", log = log.display()),
+    )?;
+
+    let id = repo.head_id()?.detach();
+    let null = gix::ObjectId::null(id.kind());
+    repo.reference(
+        "refs/heads/new",
+        id,
+        gix_ref::transaction::PreviousValue::MustNotExist,
+        "create",
+    )?;
+    assert_eq!(
+        std::fs::read_to_string(&log)?,
+        format!("prepared\n{null} {id} refs/heads/new\ncommitted\n{null} {id} refs/heads/new\n")
+    );
+
+    std::fs::remove_file(&log)?;
+    install_hook(
+        &hooks.dir,
+        "reference-transaction",
+        &format!("echo \"$1\" >>{log}\ntest \"$1\" != prepared\n", log = log.display()),
+    )?;
+    let err = repo
+        .reference(
+            "refs/heads/rejected",
+            id,
+            gix_ref::transaction::PreviousValue::MustNotExist,
+            "create",
+        )
+        .expect_err("the hook rejects the transaction");
+    assert_eq!(
+        err.to_string(),
+        "The transaction was rejected by its hook",
+        "the outcome is reported"
+    );
+    assert_eq!(std::fs::read_to_string(&log)?, "prepared\naborted\n");
+    assert!(repo.try_find_reference("refs/heads/rejected")?.is_none());
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use gix::log::Options;
+
+use crate::named_repo;
+
+fn baseline(repo: &gix::Repository, name: &str) -> crate::Result<Vec<gix::ObjectId>> {
+    let content = std::fs::read_to_string(repo.work_dir().expect("non-bare").join(name))?;
+    Ok(content
+        .lines()
+        .map(|hex| gix::ObjectId::from_hex(hex.as_bytes()))
+        .collect::<Result<_, _>>()?)
+}
+
+fn log(repo: &gix::Repository, patterns: &[&str], options: Options) -> crate::Result<Vec<gix::ObjectId>> {
+    Ok(repo
+        .log(patterns, options)?
+        .map(|info| info.map(|info| info.id))
+        .collect::<Result<_, _>>()?)
+}
+
+#[test]
+fn without_pathspec_all_commits_are_returned_newest_first() -> crate::Result {
+    let repo = named_repo("make_log_repo.sh")?;
+    assert_eq!(log(&repo, &[], Options::default())?, baseline(&repo, "all.baseline")?);
+    let info = repo
+        .log(None::<&str>, Options::default())?
+        .next()
+        .expect("one commit")?;
+    assert_eq!(info.id, repo.head_id()?);
+    assert_eq!(info.object()?.message()?.summary().as_ref(), "change moved");
+    assert_eq!(info.location, None);
+    Ok(())
+}
+
+#[test]
+fn pathspec_with_history_simplification() -> crate::Result {
+    let repo = named_repo("make_log_repo.sh")?;
+    assert_eq!(log(&repo, &["a"], Options::default())?, baseline(&repo, "a.baseline")?);
+    assert_eq!(
+        log(&repo, &["dir"], Options::default())?,
+        baseline(&repo, "dir.baseline")?,
+        "merges that take a side unchanged are skipped along with the other side"
+    );
+    assert_eq!(
+        log(&repo, &["moved"], Options::default())?,
+        baseline(&repo, "moved.baseline")?
+    );
+    assert_eq!(
+        log(&repo, &["d*"], Options::default())?,
+        baseline(&repo, "dir.baseline")?,
+        "patterns are supported"
+    );
+    Ok(())
+}
+
+#[test]
+fn pathspec_with_full_history_and_first_parent() -> crate::Result {
+    let repo = named_repo("make_log_repo.sh")?;
+    assert_eq!(
+        log(
+            &repo,
+            &["a"],
+            Options {
+                simplify: false,
+                ..Default::default()
+            }
+        )?,
+        baseline(&repo, "a-full-history.baseline")?
+    );
+    assert_eq!(
+        log(
+            &repo,
+            &["dir"],
+            Options {
+                first_parent: true,
+                ..Default::default()
+            }
+        )?,
+        baseline(&repo, "dir-first-parent.baseline")?
+    );
+    Ok(())
+}
+
+#[test]
+fn follow_renames_of_a_single_file() -> crate::Result {
+    let repo = named_repo("make_log_repo.sh")?;
+    let options = Options {
+        follow: true,
+        ..Default::default()
+    };
+    let infos = repo
+        .log(Some("moved"), options.clone())?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        infos.iter().map(|info| info.id).collect::<Vec<_>>(),
+        baseline(&repo, "moved-follow.baseline")?
+    );
+    assert_eq!(
+        infos
+            .iter()
+            .map(|info| info.location.as_ref().expect("set when following").to_string())
+            .collect::<Vec<_>>(),
+        ["moved", "moved", "file"]
+    );
+
+    assert!(matches!(
+        repo.log(["a", "moved"], options),
+        Err(gix::log::init::Error::FollowRequiresSinglePath { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn hidden_commits_and_their_ancestors_are_not_listed() -> crate::Result {
+    let repo = named_repo("make_log_repo.sh")?;
+    let side = repo.rev_parse_single("side")?.detach();
+    assert_eq!(
+        log(
+            &repo,
+            &["a"],
+            Options {
+                hidden: vec![side],
+                ..Default::default()
+            }
+        )?,
+        baseline(&repo, "a-side-main.baseline")?,
+        "this is like `side..main`"
+    );
+    assert!(
+        log(
+            &repo,
+            &[],
+            Options {
+                tips: vec![side],
+                hidden: vec![repo.head_id()?.detach()],
+                ..Default::default()
+            }
+        )?
+        .is_empty(),
+        "tips that are reachable from hidden commits are hidden as well"
+    );
+    Ok(())
+}
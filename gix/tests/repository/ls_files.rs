@@ -0,0 +1,103 @@
+use gix::{bstr::ByteSlice, ls_files};
+
+use crate::util::{named_subrepo_opts, restricted};
+
+fn expected(name: &str) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(
+        gix_testtools::scripted_fixture_read_only("make_ls_files_repo.sh")?.join(name),
+    )?)
+}
+
+fn ls_files(
+    repo: &gix::Repository,
+    pathspecs: &[&str],
+    options: ls_files::Options,
+) -> crate::Result<(ls_files::Outcome, Vec<u8>)> {
+    let outcome = repo.ls_files(pathspecs, options)?;
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    Ok((outcome, out))
+}
+
+const ALL: ls_files::Options = ls_files::Options {
+    cached: true,
+    deleted: true,
+    modified: true,
+    others: true,
+};
+
+#[test]
+fn cached_deleted_modified_and_others() -> crate::Result {
+    let repo = named_subrepo_opts("make_ls_files_repo.sh", "changes", restricted())?;
+    let (outcome, out) = ls_files(&repo, &[], ALL)?;
+    assert_eq!(out.as_bstr(), expected("changes.out")?.as_bstr());
+
+    let skipped = outcome
+        .entries
+        .iter()
+        .find(|entry| entry.rela_path == "skipped")
+        .and_then(|entry| entry.index_entry.as_ref())
+        .expect("present in the index");
+    assert!(skipped.flags.contains(gix::index::entry::Flags::SKIP_WORKTREE));
+    assert!(
+        outcome
+            .entries
+            .iter()
+            .all(|entry| entry.rela_path.find(b"ignored").is_none()),
+        "ignored files aren't listed"
+    );
+    Ok(())
+}
+
+#[test]
+fn cached_only_by_default() -> crate::Result {
+    let repo = named_subrepo_opts("make_ls_files_repo.sh", "changes", restricted())?;
+    let (outcome, _) = ls_files(&repo, &[], ls_files::Options::default())?;
+    assert!(outcome
+        .entries
+        .iter()
+        .all(|entry| entry.kind == ls_files::Kind::Cached && entry.index_entry.is_some()));
+    assert_eq!(outcome.entries.len(), 6, "each index entry is listed once");
+    Ok(())
+}
+
+#[test]
+fn pathspec() -> crate::Result {
+    let repo = named_subrepo_opts("make_ls_files_repo.sh", "changes", restricted())?;
+    let (_, out) = ls_files(&repo, &["dir"], ls_files::Options { cached: false, ..ALL })?;
+    assert_eq!(out.as_bstr(), expected("changes-dir.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn conflicts_are_listed_with_their_stages() -> crate::Result {
+    let repo = named_subrepo_opts("make_ls_files_repo.sh", "conflict", restricted())?;
+    let (outcome, out) = ls_files(&repo, &[], ls_files::Options::default())?;
+    assert_eq!(out.as_bstr(), expected("conflict.out")?.as_bstr());
+
+    let mut out = Vec::new();
+    outcome.write_stage_to(&mut out)?;
+    assert_eq!(out.as_bstr(), expected("conflict-stage.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn worktree_is_required_for_anything_but_cached_files() -> crate::Result {
+    let repo = named_subrepo_opts("make_ls_files_repo.sh", "bare.git", restricted())?;
+    assert_eq!(
+        repo.ls_files(None::<&str>, ls_files::Options::default())?.entries,
+        [],
+        "bare repositories have no index"
+    );
+    assert!(matches!(
+        repo.ls_files(
+            None::<&str>,
+            ls_files::Options {
+                others: true,
+                ..Default::default()
+            }
+        ),
+        Err(ls_files::Error::MissingWorktree)
+    ));
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use crate::util::named_subrepo_opts;
+
+#[test]
+fn worktree_mailmap_is_used_and_mailmap_file_is_merged() -> crate::Result {
+    let repo = named_subrepo_opts("make_mailmap_repo.sh", "worktree", crate::restricted())?;
+    let commit = repo.head_commit()?;
+    let mailmap = repo.open_mailmap();
+    let author = commit.author_remapped(&mailmap)?;
+    assert_eq!(&*author.name, "Canonical Author");
+    assert_eq!(&*author.email, "canonical@example.com");
+    assert_eq!(author.time, commit.author()?.time, "the time is retained");
+    let committer = commit.committer_remapped(&mailmap)?;
+    assert_eq!(&*committer.name, "committer", "`mailmap.file` isn't configured yet");
+
+    let extra = repo.work_dir().expect("non-bare").join("extra.mailmap");
+    let repo = named_subrepo_opts(
+        "make_mailmap_repo.sh",
+        "worktree",
+        crate::restricted().config_overrides([format!("mailmap.file={}", extra.display())]),
+    )?;
+    let commit = repo.head_commit()?;
+    let mailmap = repo.open_mailmap();
+    assert_eq!(&*commit.author_remapped(&mailmap)?.name, "Canonical Author");
+    assert_eq!(&*commit.committer_remapped(&mailmap)?.name, "Canonical Committer");
+    Ok(())
+}
+
+#[test]
+fn bare_repositories_read_the_mailmap_from_head() -> crate::Result {
+    let repo = named_subrepo_opts("make_mailmap_repo.sh", "bare.git", crate::restricted())?;
+    let commit = repo.head_commit()?;
+    assert_eq!(&*commit.author_remapped(&repo.open_mailmap())?.name, "Canonical Author");
+    Ok(())
+}
+
+#[test]
+fn resolver_maps_many_signatures() -> crate::Result {
+    let repo = named_subrepo_opts("make_mailmap_repo.sh", "worktree", crate::restricted())?;
+    let commit = repo.head_commit()?;
+    let mut resolver = repo.mailmap_resolver();
+    for _ in 0..2 {
+        let identity = resolver.resolve(commit.author()?);
+        assert_eq!(identity.name, "Canonical Author");
+        assert_eq!(identity.email, "canonical@example.com");
+    }
+    let committer = resolver.resolve_signature(commit.committer()?);
+    assert_eq!(committer.name, "committer", "unmapped identities are returned as is");
+    assert_eq!(committer.time, commit.committer()?.time);
+    Ok(())
+}
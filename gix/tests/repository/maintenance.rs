@@ -0,0 +1,445 @@
+use gix::{
+    bstr::ByteSlice,
+    config::tree::{Gc, Maintenance},
+    maintenance::{Options, Schedule, Task, TaskOutcome},
+};
+
+use crate::util::restricted;
+
+fn repo_rw(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    repo_rw_opts(name, restricted())
+}
+
+fn repo_rw_opts(
+    name: &str,
+    opts: gix::open::Options,
+) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+    let dir = gix_testtools::scripted_fixture_writable("make_maintenance_repo.sh")?;
+    let repo = gix::open_opts(dir.path().join(name), opts)?;
+    Ok((repo, dir))
+}
+
+fn run(repo: &gix::Repository, tasks: &[Task]) -> crate::Result<Vec<TaskOutcome>> {
+    Ok(repo
+        .maintenance(Options {
+            tasks: tasks.to_vec(),
+            ..Default::default()
+        })?
+        .tasks)
+}
+
+fn num_packs(repo: &gix::Repository) -> crate::Result<usize> {
+    Ok(std::fs::read_dir(repo.objects.store_ref().path().join("pack"))?
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension() == Some("idx".as_ref()))
+        .count())
+}
+
+#[test]
+fn all_tasks_run_in_order_by_default() -> crate::Result {
+    let (repo, _tmp) = repo_rw("repo")?;
+    let outcome = repo.maintenance(Options::default())?;
+    assert_eq!(
+        outcome.tasks,
+        [
+            TaskOutcome::PackRefs { packed: 4 },
+            TaskOutcome::ReflogExpire { removed: 21 },
+            TaskOutcome::LooseObjects { removed: 3, packed: 15 },
+            TaskOutcome::IncrementalRepack { packs: 3, objects: 22 },
+            TaskOutcome::CommitGraph { commits: 6 },
+        ],
+        "all reflog entries are older than 90 days, and the commit that was reset isn't part of the commit-graph"
+    );
+
+    let repo = gix::open_opts(repo.path(), restricted())?;
+    assert_eq!(num_packs(&repo)?, 1, "all packs were combined into one");
+    let head = repo.head_commit()?;
+    assert_eq!(head.parent_ids().count(), 3, "all objects are still accessible");
+    assert!(repo.find_reference("refs/tags/v1")?.peel_to_id_in_place().is_ok());
+    assert!(!repo.path().join("refs/heads/main").exists(), "references were packed");
+
+    let graph = repo.commit_graph()?;
+    assert_eq!(graph.num_commits(), 6);
+    graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    let head = graph.commit_at(graph.lookup(head.id).expect("present"));
+    assert_eq!(
+        head.iter_parents().count(),
+        3,
+        "octopus merges are stored with the extra-edges chunk"
+    );
+
+    assert_eq!(
+        run(&repo, &[Task::LooseObjects])?,
+        [TaskOutcome::LooseObjects { removed: 15, packed: 0 }],
+        "loose objects that were packed are removed the next time"
+    );
+    Ok(())
+}
+
+#[test]
+fn reflog_expire_respects_reachability() -> crate::Result {
+    let (mut repo, _tmp) = repo_rw("repo")?;
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Gc::REFLOG_EXPIRE, "never")?;
+        config.set_value(&Gc::REFLOG_EXPIRE_UNREACHABLE, "now")?;
+    }
+    assert_eq!(
+        run(&repo, &[Task::ReflogExpire])?,
+        [TaskOutcome::ReflogExpire { removed: 2 }],
+        "only the entries of the reset commit in the logs of HEAD and main are unreachable"
+    );
+    let dropped = std::fs::read(repo.work_dir().expect("non-bare").join("dropped.id"))?;
+    let dropped = dropped.trim_end().as_bstr();
+    let head_log = std::fs::read(repo.path().join("logs/HEAD"))?;
+    assert_eq!(
+        gix::refs::file::log::iter::forward(&head_log).count(),
+        10,
+        "all other entries are kept"
+    );
+    assert!(gix::refs::file::log::iter::forward(&head_log).all(|line| line.expect("valid").new_oid != dropped));
+
+    assert_eq!(
+        run(&repo, &[Task::ReflogExpire])?,
+        [TaskOutcome::ReflogExpire { removed: 0 }],
+        "nothing else is unreachable"
+    );
+    Ok(())
+}
+
+#[test]
+fn tasks_are_selected_by_configuration_and_schedule() -> crate::Result {
+    let (mut repo, _tmp) = repo_rw("repo")?;
+    let tasks = |repo: &gix::Repository, schedule: Option<Schedule>| -> crate::Result<Vec<Task>> {
+        Ok(repo
+            .maintenance(Options {
+                schedule,
+                ..Default::default()
+            })?
+            .tasks
+            .iter()
+            .map(TaskOutcome::task)
+            .collect())
+    };
+    {
+        let mut config = repo.config_snapshot_mut();
+        for task in Task::ALL {
+            config.set_subsection_value(&Maintenance::TASK_ENABLED, task.as_str(), "false")?;
+        }
+    }
+    assert_eq!(tasks(&repo, None)?, [], "all tasks are disabled");
+
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Maintenance::STRATEGY, "incremental")?;
+        for task in Task::ALL {
+            config.set_subsection_value(&Maintenance::TASK_ENABLED, task.as_str(), "true")?;
+        }
+    }
+    assert_eq!(
+        tasks(&repo, Some(Schedule::Hourly))?,
+        [Task::CommitGraph],
+        "only the commit-graph is written hourly"
+    );
+    assert_eq!(
+        tasks(&repo, Some(Schedule::Daily))?,
+        [Task::LooseObjects, Task::IncrementalRepack, Task::CommitGraph]
+    );
+    assert_eq!(
+        tasks(&repo, Some(Schedule::Weekly))?,
+        [
+            Task::PackRefs,
+            Task::LooseObjects,
+            Task::IncrementalRepack,
+            Task::CommitGraph
+        ],
+        "reflog-expire isn't scheduled with the incremental strategy"
+    );
+
+    repo.config_snapshot_mut()
+        .set_subsection_value(&Maintenance::TASK_SCHEDULE, "reflog-expire", "weekly")?;
+    assert_eq!(
+        tasks(&repo, Some(Schedule::Weekly))?.len(),
+        5,
+        "schedules can be configured"
+    );
+
+    assert_eq!(
+        run(&repo, &[Task::CommitGraph, Task::PackRefs, Task::CommitGraph])?
+            .iter()
+            .map(TaskOutcome::task)
+            .collect::<Vec<_>>(),
+        [Task::CommitGraph, Task::PackRefs],
+        "explicitly passed tasks run in the given order, once"
+    );
+    Ok(())
+}
+
+#[test]
+fn auto_runs_tasks_only_if_thresholds_are_reached() -> crate::Result {
+    let (mut repo, _tmp) = repo_rw("repo")?;
+    let auto = Options {
+        auto: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        repo.maintenance(auto.clone())?.tasks,
+        [],
+        "there are not enough loose objects, packs or commits to do anything"
+    );
+
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_subsection_value(&Maintenance::TASK_AUTO, "loose-objects", "18")?;
+        config.set_subsection_value(&Maintenance::TASK_AUTO, "incremental-repack", "2")?;
+        config.set_subsection_value(&Maintenance::TASK_AUTO, "commit-graph", "-1")?;
+        config.set_value(&Gc::AUTO_PACK_LIMIT, "1")?;
+    }
+    assert_eq!(
+        repo.maintenance(auto.clone())?
+            .tasks
+            .iter()
+            .map(TaskOutcome::task)
+            .collect::<Vec<_>>(),
+        Task::ALL,
+        "more packs than allowed trigger pack-refs and reflog-expire"
+    );
+
+    repo.config_snapshot_mut().set_value(&Maintenance::AUTO, "false")?;
+    assert_eq!(
+        repo.maintenance(auto)?.tasks,
+        [],
+        "nothing runs automatically if disabled"
+    );
+    Ok(())
+}
+
+#[test]
+fn commit_graph_is_not_written_in_shallow_repositories() -> crate::Result {
+    let (repo, _tmp) = repo_rw("shallow")?;
+    assert_eq!(
+        run(&repo, &[Task::CommitGraph])?,
+        [TaskOutcome::CommitGraph { commits: 0 }]
+    );
+    assert!(repo.commit_graph().is_err());
+    Ok(())
+}
+
+#[test]
+fn only_one_maintenance_can_run_at_a_time() -> crate::Result {
+    let (repo, _tmp) = repo_rw("repo")?;
+    let _lock = gix::lock::Marker::acquire_to_hold_resource(
+        repo.objects.store_ref().path().join("maintenance"),
+        gix::lock::acquire::Fail::Immediately,
+        None,
+    )?;
+    let err = repo.maintenance(Options::default()).unwrap_err();
+    assert!(matches!(err, gix::maintenance::Error::Lock(_)));
+    assert_eq!(
+        err.to_string(),
+        "Could not acquire the maintenance lock, is maintenance already running?"
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_configuration_is_an_error() -> crate::Result {
+    let options = Options {
+        schedule: Some(Schedule::Weekly),
+        ..Default::default()
+    };
+    let (mut repo, _tmp) = repo_rw("repo")?;
+    repo.config_snapshot_mut()
+        .set_raw_value("maintenance", Some("commit-graph".into()), "schedule", "monthly")?;
+    assert_eq!(
+        repo.maintenance(options.clone())?.tasks,
+        [],
+        "invalid schedules are ignored in lenient mode"
+    );
+
+    let (mut repo, _tmp) = repo_rw_opts("repo", restricted().strict_config(true))?;
+    repo.config_snapshot_mut()
+        .set_raw_value("maintenance", Some("commit-graph".into()), "schedule", "monthly")?;
+    assert_eq!(
+        repo.maintenance(options).unwrap_err().to_string(),
+        "The key \"maintenance.<task>.schedule=monthly\" was invalid"
+    );
+    Ok(())
+}
+
+mod prune {
+    use std::time::SystemTime;
+
+    use gix::{bstr::ByteSlice, config::tree::Gc, maintenance::prune};
+
+    use super::repo_rw;
+
+    fn id_from_file(repo: &gix::Repository, file_name: &str) -> crate::Result<gix::ObjectId> {
+        let hex = std::fs::read(repo.work_dir().expect("non-bare").join(file_name))?;
+        Ok(gix::ObjectId::from_hex(hex.trim_end())?)
+    }
+
+    fn now() -> prune::Options {
+        prune::Options {
+            expire: Some(SystemTime::now()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn objects_within_the_grace_period_are_kept() -> crate::Result {
+        let (mut repo, _tmp) = repo_rw("unreachable")?;
+        assert_eq!(
+            repo.prune(prune::Options::default())?,
+            prune::Outcome::default(),
+            "by default, objects modified within the last two weeks are kept"
+        );
+        assert_eq!(
+            repo.prune(prune::Options {
+                expire: Some(SystemTime::UNIX_EPOCH),
+                ..Default::default()
+            })?,
+            prune::Outcome::default()
+        );
+
+        repo.config_snapshot_mut().set_value(&Gc::PRUNE_EXPIRE, "never")?;
+        assert_eq!(repo.prune(prune::Options::default())?, prune::Outcome::default());
+        Ok(())
+    }
+
+    #[test]
+    fn unreachable_loose_and_packed_objects_are_removed() -> crate::Result {
+        let (repo, tmp) = repo_rw("unreachable")?;
+        let loose = id_from_file(&repo, "loose.id")?;
+        let unreachable_commit = id_from_file(&repo, "unreachable-commit.id")?;
+        let loose_size = std::fs::metadata(
+            gix::odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash()).object_path(&loose),
+        )?
+        .len();
+
+        let outcome = repo.prune(prune::Options { dry_run: true, ..now() })?;
+        assert_eq!(
+            outcome,
+            prune::Outcome {
+                loose_objects: vec![loose],
+                packed_objects: 3,
+                packs: 1,
+                freed_bytes: loose_size,
+            },
+            "the unreachable commit, its tree and its blob are packed, and the pack would be rewritten"
+        );
+        assert!(repo.has_object(loose), "nothing is removed in a dry-run");
+
+        let outcome = repo.prune(now())?;
+        assert_eq!(outcome.loose_objects, [loose]);
+        assert_eq!(outcome.packed_objects, 3);
+        assert_eq!(outcome.packs, 1);
+        assert!(
+            outcome.freed_bytes > loose_size,
+            "the new pack is smaller than the old one"
+        );
+
+        let repo = gix::open_opts(repo.path(), crate::util::restricted())?;
+        assert!(!repo.has_object(loose));
+        assert!(!repo.has_object(unreachable_commit));
+        let kept_pack = id_from_file(&repo, "kept-pack.id")?;
+        assert!(
+            repo.objects
+                .store_ref()
+                .path()
+                .join(format!("pack/pack-{kept_pack}.pack"))
+                .is_file(),
+            "kept packs are never touched"
+        );
+        for line in repo.head()?.log_iter().all()?.expect("present") {
+            assert!(
+                repo.has_object(line?.new_oid()),
+                "objects reachable from the reflog are kept"
+            );
+        }
+        for worktree in [
+            repo.clone(),
+            gix::open_opts(tmp.path().join("worktree"), crate::util::restricted())?,
+        ] {
+            for entry in worktree.index()?.entries() {
+                assert!(
+                    repo.has_object(entry.id),
+                    "objects in the index of all worktrees are kept"
+                );
+            }
+        }
+
+        assert_eq!(
+            repo.prune(now())?,
+            prune::Outcome::default(),
+            "there is nothing left to prune"
+        );
+        Ok(())
+    }
+}
+
+mod write_commit_graph {
+    use gix::maintenance::commit_graph::{Options, Outcome};
+
+    use super::repo_rw;
+
+    fn write(repo: &gix::Repository, split: bool) -> crate::Result<Outcome> {
+        Ok(repo.write_commit_graph(Options { split }, &mut gix::progress::Discard)?)
+    }
+
+    fn add_commits(repo: &gix::Repository, count: usize) -> crate::Result {
+        for num in 0..count {
+            let head = repo.head_commit()?;
+            repo.commit("HEAD", format!("commit {num}"), head.tree_id()?, [head.id])?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn split_layers_are_added_and_merged() -> crate::Result {
+        let (repo, _tmp) = repo_rw("repo")?;
+        let info_dir = repo.objects.store_ref().path().join("info");
+
+        assert_eq!(write(&repo, false)?, Outcome { commits: 6, layers: 0 });
+        assert_eq!(
+            write(&repo, true)?,
+            Outcome { commits: 6, layers: 1 },
+            "the first layer contains all commits"
+        );
+        assert!(
+            !info_dir.join("commit-graph").exists(),
+            "the commit-graph file would shadow the chain"
+        );
+        assert_eq!(
+            write(&repo, true)?,
+            Outcome { commits: 0, layers: 1 },
+            "nothing is written if all commits are present"
+        );
+
+        add_commits(&repo, 1)?;
+        assert_eq!(
+            write(&repo, true)?,
+            Outcome { commits: 1, layers: 2 },
+            "a small layer is added on top"
+        );
+        let graph = repo.commit_graph()?;
+        assert_eq!(graph.files().len(), 2);
+        assert_eq!(graph.num_commits(), 7);
+        graph.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+
+        add_commits(&repo, 3)?;
+        assert_eq!(
+            write(&repo, true)?,
+            Outcome { commits: 10, layers: 1 },
+            "layers are merged while the new layer is more than half the size of the one below"
+        );
+        let chain_dir = info_dir.join("commit-graphs");
+        assert_eq!(std::fs::read_dir(&chain_dir)?.count(), 2, "merged layers are removed");
+        repo.commit_graph()?
+            .verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+
+        assert_eq!(write(&repo, false)?, Outcome { commits: 10, layers: 0 });
+        assert!(!chain_dir.exists(), "the chain is superseded by the commit-graph file");
+        Ok(())
+    }
+}
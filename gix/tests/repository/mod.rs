@@ -1,20 +1,86 @@
 use gix::Repository;
 
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod am;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod apply;
+#[cfg(feature = "attributes")]
+mod attributes;
+#[cfg(feature = "attributes")]
+mod binary;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod bisect;
+#[cfg(feature = "blob-diff")]
+mod blame;
+#[cfg(feature = "bundle")]
+mod bundle;
+#[cfg(feature = "revision")]
+mod cat_file;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod checkout;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod cherry_pick;
 mod config;
+mod count_objects;
 #[cfg(feature = "excludes")]
 mod excludes;
+#[cfg(feature = "blob-diff")]
+mod fast_export;
+#[cfg(feature = "revision")]
+mod fast_import;
 #[cfg(feature = "attributes")]
 mod filter;
+#[cfg(feature = "revision")]
+mod for_each_ref;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+mod format_patch;
+#[cfg(feature = "fsck")]
+mod fsck;
+#[cfg(feature = "attributes")]
+mod grep;
+#[cfg(feature = "command")]
+mod hook;
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+mod log;
+#[cfg(feature = "status")]
+mod ls_files;
+#[cfg(feature = "mailmap")]
+mod mailmap;
+#[cfg(feature = "maintenance")]
+mod maintenance;
+#[cfg(feature = "notes")]
+mod note;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
 mod pathspec;
+#[cfg(all(feature = "blob-diff", feature = "revision"))]
+mod range_diff;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod rebase;
 mod reference;
 mod remote;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod reset;
+#[cfg(feature = "revision")]
+mod rev_list;
 mod shallow;
+#[cfg(feature = "mailmap")]
+mod shortlog;
+#[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+mod stash;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
+#[cfg(all(
+    feature = "worktree-mutation",
+    feature = "status",
+    feature = "revision",
+    feature = "blocking-network-client"
+))]
+mod submodule_update;
+mod trailers;
+mod update_refs;
 mod worktree;
 
 #[cfg(feature = "dirwalk")]
@@ -93,3 +159,38 @@ fn thread_safe_repository_is_sync() -> crate::Result {
     f(crate::util::basic_repo()?.into_sync());
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "parallel")]
+fn pool_handles_are_configured_like_the_original() -> crate::Result {
+    fn f<T: Send + Sync + Clone>(_t: T) {}
+    let mut repo = crate::util::basic_repo()?;
+    repo.object_cache_size(1024 * 1024);
+    let head = repo.head_id()?.detach();
+    let pool = repo.into_pool();
+    f(pool.clone());
+
+    std::thread::scope(|scope| -> crate::Result {
+        let threads: Vec<_> = (0..2)
+            .map(|_| {
+                let pool = &pool;
+                scope.spawn(move || -> Result<_, gix::object::find::existing::Error> {
+                    let repo = pool.handle();
+                    let commit = repo.find_object(head)?;
+                    Ok((repo.objects.has_object_cache(), commit.kind))
+                })
+            })
+            .collect();
+        for thread in threads {
+            let (has_object_cache, kind) = thread.join().expect("no panic")?;
+            assert!(has_object_cache, "per-handle settings are retained");
+            assert_eq!(kind, gix::object::Kind::Commit);
+        }
+        Ok(())
+    })?;
+    assert!(
+        !pool.handle().into_sync().to_thread_local().objects.has_object_cache(),
+        "thread-safe repositories create handles as configured by the configuration"
+    );
+    Ok(())
+}
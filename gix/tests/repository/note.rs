@@ -0,0 +1,178 @@
+use gix::note::{edit, merge, DEFAULT_REF};
+
+use crate::{named_repo, repo_rw};
+
+fn id(repo: &gix::Repository, spec: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.rev_parse_single(spec)?.detach())
+}
+
+fn note(notes: &gix::note::Platform<'_>, target: gix::ObjectId) -> crate::Result<Option<String>> {
+    Ok(notes
+        .find(target)?
+        .map(|blob| String::from_utf8(blob.data.clone()))
+        .transpose()?)
+}
+
+#[test]
+fn notes_written_by_git_can_be_read() -> crate::Result {
+    let repo = named_repo("make_notes_repo.sh")?;
+    let notes = repo.notes(DEFAULT_REF)?;
+    assert_eq!(notes.reference().as_bstr(), DEFAULT_REF);
+    assert_eq!(notes.all()?.notes.len(), 2);
+    assert_eq!(note(&notes, id(&repo, "HEAD~2")?)?.as_deref(), Some("note for first\n"));
+    assert_eq!(
+        note(&notes, id(&repo, "HEAD~1")?)?.as_deref(),
+        Some("changed note for second\n")
+    );
+    assert_eq!(note(&notes, id(&repo, "HEAD")?)?, None);
+
+    let missing = repo.notes("refs/notes/missing")?;
+    assert!(missing.all()?.notes.is_empty(), "missing references have no notes");
+    assert_eq!(note(&missing, id(&repo, "HEAD")?)?, None);
+    Ok(())
+}
+
+#[test]
+fn fanned_out_notes_written_by_git_can_be_read_and_are_written_the_same() -> crate::Result {
+    let repo = named_repo("make_notes_repo.sh")?;
+    let all = repo.notes("refs/notes/dense")?.all()?;
+    assert_eq!(all.notes.len(), 300);
+    assert!(all.non_notes.is_empty());
+
+    let tree = all.write_tree(|tree| repo.write_object(tree).map(gix::Id::detach))?;
+    assert_eq!(
+        tree,
+        id(&repo, "refs/notes/dense^{tree}")?,
+        "the fan-out is the same as the one produced by git"
+    );
+    Ok(())
+}
+
+#[test]
+fn attach_remove_and_copy() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_notes_repo.sh")?;
+    let (first, second, third) = (id(&repo, "HEAD~2")?, id(&repo, "HEAD~1")?, id(&repo, "HEAD")?);
+    let notes = repo.notes(DEFAULT_REF)?;
+    let previous_tip = id(&repo, DEFAULT_REF)?;
+
+    let commit = notes.attach(third, "note for third\n", false)?;
+    assert_eq!(commit, id(&repo, DEFAULT_REF)?, "the notes reference is updated");
+    let commit = commit.object()?.into_commit();
+    assert_eq!(commit.message_raw()?, "Notes added by 'git notes add'");
+    assert_eq!(
+        commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+        [previous_tip]
+    );
+    assert_eq!(note(&notes, third)?.as_deref(), Some("note for third\n"));
+
+    assert!(matches!(
+        notes.attach(third, "replacement", false),
+        Err(edit::Error::NoteExists { target }) if target == third
+    ));
+    notes.attach(third, "replacement", true)?;
+    assert_eq!(note(&notes, third)?.as_deref(), Some("replacement"));
+
+    assert!(matches!(
+        notes.copy(first, third, false),
+        Err(edit::Error::NoteExists { target }) if target == third
+    ));
+    notes.copy(first, third, true)?;
+    assert_eq!(note(&notes, third)?.as_deref(), Some("note for first\n"));
+
+    notes.remove(first)?;
+    assert_eq!(note(&notes, first)?, None);
+    assert!(matches!(
+        notes.remove(first),
+        Err(edit::Error::NoteMissing { target }) if target == first
+    ));
+    assert!(matches!(
+        notes.copy(first, second, true),
+        Err(edit::Error::NoteMissing { target }) if target == first
+    ));
+    assert_eq!(
+        notes.all()?.notes.len(),
+        2,
+        "the notes of the second and third commit remain"
+    );
+
+    let fresh = repo.notes("refs/notes/fresh")?;
+    let commit = fresh.attach(first, "fresh", false)?;
+    assert_eq!(
+        commit.object()?.into_commit().parent_ids().count(),
+        0,
+        "the first notes commit has no parent"
+    );
+    assert_eq!(note(&fresh, first)?.as_deref(), Some("fresh"));
+    Ok(())
+}
+
+#[test]
+fn merge_fast_forwards_and_merges() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_notes_repo.sh")?;
+    let (first, second, third) = (id(&repo, "HEAD~2")?, id(&repo, "HEAD~1")?, id(&repo, "HEAD")?);
+    let notes = repo.notes(DEFAULT_REF)?;
+    let other = repo.notes("refs/notes/other")?;
+
+    let new = repo.notes("refs/notes/new")?;
+    let outcome = new.merge(&other, merge::Strategy::Manual)?;
+    assert_eq!(
+        outcome,
+        merge::Outcome::FastForward {
+            commit: repo.find_reference("refs/notes/other")?.id()
+        },
+        "a missing reference is created"
+    );
+    assert_eq!(new.merge(&other, merge::Strategy::Manual)?, merge::Outcome::UpToDate);
+
+    let previous_tip = id(&repo, DEFAULT_REF)?;
+    let merge::Outcome::Merged { commit } = notes.merge(&other, merge::Strategy::Manual)? else {
+        panic!("the histories diverged, but don't conflict")
+    };
+    let commit = commit.object()?.into_commit();
+    assert_eq!(
+        commit.message_raw()?,
+        "notes: Merged notes from refs/notes/other into refs/notes/commits"
+    );
+    assert_eq!(
+        commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+        [previous_tip, id(&repo, "refs/notes/other")?]
+    );
+    assert_eq!(note(&notes, first)?, None, "their removal is applied");
+    assert_eq!(
+        note(&notes, second)?.as_deref(),
+        Some("changed note for second\n"),
+        "our change is kept"
+    );
+    assert_eq!(
+        note(&notes, third)?.as_deref(),
+        Some("other note for third\n"),
+        "their addition is applied"
+    );
+    assert_eq!(notes.merge(&other, merge::Strategy::Manual)?, merge::Outcome::UpToDate);
+    Ok(())
+}
+
+#[test]
+fn merge_conflicts_are_returned_or_resolved() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_notes_repo.sh")?;
+    let second = id(&repo, "HEAD~1")?;
+    let notes = repo.notes(DEFAULT_REF)?;
+    let conflicting = repo.notes("refs/notes/conflicting")?;
+    let previous_tip = id(&repo, DEFAULT_REF)?;
+
+    let outcome = notes.merge(&conflicting, merge::Strategy::Manual)?;
+    let merge::Outcome::Conflicted { conflicts } = outcome else {
+        panic!("both sides changed the same note")
+    };
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].target, second);
+    assert_eq!(id(&repo, DEFAULT_REF)?, previous_tip, "nothing was written");
+
+    let outcome = notes.merge(&conflicting, merge::Strategy::Union)?;
+    assert!(matches!(outcome, merge::Outcome::Merged { .. }));
+    assert_eq!(
+        note(&notes, second)?.as_deref(),
+        Some("changed note for second\n\nconflicting note for second\n")
+    );
+    Ok(())
+}
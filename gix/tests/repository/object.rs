@@ -52,6 +52,58 @@ mod write_blob {
         );
         Ok(())
     }
+
+    #[test]
+    fn from_large_stream_without_known_size() -> crate::Result {
+        let (tmp, repo) = empty_bare_repo()?;
+        let data = b"0123456789abcdef".repeat(256 * 1024);
+        let oid = repo.write_blob_stream(std::io::Read::chain(&data[..3], &data[3..]))?;
+        assert_eq!(
+            oid,
+            gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &data),
+            "the input is hashed as a whole even though it's larger than what's kept in memory"
+        );
+        assert_eq!(oid.object()?.data, data);
+
+        let loose_objects = || {
+            gix::odb::loose::Store::at(tmp.path().join("objects"), repo.object_hash())
+                .iter()
+                .count()
+        };
+        assert_eq!(loose_objects(), 1);
+        assert_eq!(repo.write_blob_stream(&data[..])?, oid);
+        assert_eq!(loose_objects(), 1, "existing objects aren't written again");
+        assert_eq!(
+            std::fs::read_dir(tmp.path().join("objects"))?
+                .filter_map(Result::ok)
+                .filter(|entry| entry.path().is_file())
+                .count(),
+            0,
+            "the temporary file is removed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "attributes")]
+    fn from_stream_with_clean_filters() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo: gix::Repository = gix::ThreadSafeRepository::init_opts(
+            tmp.path(),
+            gix::create::Kind::WithWorktree,
+            gix::create::Options::default(),
+            gix::open::Options::isolated(),
+        )?
+        .into();
+        std::fs::write(tmp.path().join(".gitattributes"), "*.txt text\n")?;
+
+        let oid = repo.write_blob_stream_filtered(&b"a\r\nb\r\n"[..], "file.txt".as_ref())?;
+        assert_eq!(oid.object()?.data, b"a\nb\n", "line endings are normalized");
+
+        let oid = repo.write_blob_stream_filtered(&b"a\r\nb\r\n"[..], "file.bin".as_ref())?;
+        assert_eq!(oid.object()?.data, b"a\r\nb\r\n", "no filter applies to other files");
+        Ok(())
+    }
 }
 
 #[test]
@@ -108,6 +160,175 @@ fn writes_avoid_io_using_duplicate_check() -> crate::Result {
     Ok(())
 }
 
+mod object_memory {
+    use gix::objs::tree;
+
+    #[test]
+    fn written_objects_stay_in_memory() -> crate::Result {
+        let (_tmp, repo) = super::empty_bare_repo()?;
+        let mut repo = repo.with_object_memory();
+        let loose = gix::odb::loose::Store::at(repo.git_dir().join("objects"), repo.object_hash());
+
+        let blob = repo.write_blob("hello")?.detach();
+        let tree = repo
+            .write_object(gix::objs::Tree {
+                entries: vec![tree::Entry {
+                    mode: tree::EntryKind::Blob.into(),
+                    filename: "file".into(),
+                    oid: blob,
+                }],
+            })?
+            .detach();
+        let large = vec![b'x'; 2 * 1024 * 1024];
+        let large_blob = repo.write_blob_stream(large.as_slice())?.detach();
+
+        assert_eq!(loose.iter().count(), 0, "nothing was written to disk");
+        assert_eq!(
+            std::fs::read_dir(repo.objects.store_ref().path())?.count(),
+            2,
+            "no temporary files are left behind either, just the 'info' and 'pack' directories"
+        );
+        assert_eq!(repo.objects.num_objects_in_memory(), 3);
+        assert_eq!(repo.find_object(blob)?.data, b"hello");
+        assert_eq!(repo.find_object(large_blob)?.data.len(), large.len());
+        assert_eq!(repo.find_header(tree)?.kind(), gix::object::Kind::Tree);
+        assert_eq!(
+            repo.find_object(tree)?.into_tree().decode()?.entries[0].oid,
+            blob.as_ref()
+        );
+        assert!(repo.has_object(blob));
+
+        let memory = repo.objects.take_object_memory().expect("enabled");
+        assert_eq!(memory.len(), 3);
+        assert!(!repo.has_object(blob), "objects are only in memory");
+
+        repo.write_blob("hello")?;
+        assert_eq!(
+            loose.iter().count(),
+            1,
+            "without object memory, objects are written to disk"
+        );
+        Ok(())
+    }
+}
+
+mod object_replacements {
+    use crate::util::hex_to_id;
+
+    fn replaced_history_repo(opts: gix::open::Options) -> crate::Result<gix::Repository> {
+        let path = gix_testtools::scripted_fixture_read_only("make_replaced_history.sh")?;
+        Ok(gix::open_opts(path, opts)?)
+    }
+
+    fn parents_of(repo: &gix::Repository, id: gix_hash::ObjectId) -> crate::Result<Vec<gix_hash::ObjectId>> {
+        Ok(repo
+            .find_object(id)?
+            .into_commit()
+            .parent_ids()
+            .map(gix::Id::detach)
+            .collect())
+    }
+
+    #[test]
+    fn are_loaded_on_first_use_and_can_be_toggled() -> crate::Result {
+        let mut repo = replaced_history_repo(crate::restricted())?;
+        let short_history_link = hex_to_id("434e5a872d6738d1fffd1e11e52a1840b73668c6");
+        let long_history_tip = hex_to_id("71f537d9d78bf6ae89a29a17e54b95a914d3d2ef");
+        assert!(
+            repo.object_replacements_enabled(),
+            "replacements are enabled by default"
+        );
+        assert!(
+            !repo.objects.store_ref().has_loaded_replacements(),
+            "replace-refs aren't read when opening the repository"
+        );
+
+        assert_eq!(
+            parents_of(&repo, short_history_link)?,
+            [hex_to_id("753ccf815e7b69c9147db5bbf633fe5f7da24ad7")],
+            "the replacement is found instead of the original"
+        );
+        assert!(repo.objects.store_ref().has_loaded_replacements());
+        assert_eq!(
+            repo.object_replacements().collect::<Vec<_>>(),
+            [(short_history_link, long_history_tip)]
+        );
+
+        repo.set_object_replacements_enabled(false);
+        assert!(!repo.object_replacements_enabled());
+        assert_eq!(
+            parents_of(&repo, short_history_link)?,
+            [hex_to_id("0703c317e28068f39834ae61e7ab941b7d672322")],
+            "the original is found if replacements are disabled"
+        );
+        assert_eq!(repo.object_replacements().count(), 0, "no replacement is active");
+        Ok(())
+    }
+
+    #[test]
+    fn can_be_disabled_by_configuration() -> crate::Result {
+        let mut repo = replaced_history_repo(crate::restricted().config_overrides(["core.useReplaceRefs=false"]))?;
+        let short_history_link = hex_to_id("434e5a872d6738d1fffd1e11e52a1840b73668c6");
+        assert!(!repo.object_replacements_enabled());
+        assert_eq!(
+            parents_of(&repo, short_history_link)?,
+            [hex_to_id("0703c317e28068f39834ae61e7ab941b7d672322")]
+        );
+        assert!(
+            !repo.objects.store_ref().has_loaded_replacements(),
+            "replacements aren't loaded if they aren't used"
+        );
+
+        repo.set_object_replacements_enabled(true);
+        assert_eq!(
+            parents_of(&repo, short_history_link)?,
+            [hex_to_id("753ccf815e7b69c9147db5bbf633fe5f7da24ad7")],
+            "they can still be enabled on a handle"
+        );
+        Ok(())
+    }
+}
+
+mod cache {
+    fn basic_repo_opts(opts: gix::open::Options) -> crate::Result<gix::Repository> {
+        let path = gix_testtools::scripted_fixture_read_only("make_basic_repo.sh")?;
+        Ok(gix::open_opts(path, opts)?)
+    }
+
+    #[test]
+    fn object_cache_can_be_sized_when_opening() -> crate::Result {
+        let repo = basic_repo_opts(crate::restricted())?;
+        assert!(!repo.objects.has_object_cache(), "there is no object cache by default");
+
+        let repo = basic_repo_opts(crate::restricted().object_cache_size(1024 * 1024))?;
+        assert!(repo.objects.has_object_cache());
+
+        let repo = basic_repo_opts(crate::restricted().object_cache_size(1024).object_cache_size(0))?;
+        assert!(
+            !repo.objects.has_object_cache(),
+            "the last value wins, and 0 disables the cache"
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "pack-cache-lru-static", feature = "pack-cache-lru-dynamic"))]
+    fn pack_cache_can_be_sized_when_opening_and_per_handle() -> crate::Result {
+        let mut repo = basic_repo_opts(crate::restricted())?;
+        assert!(repo.objects.has_pack_cache(), "there is a small pack cache by default");
+        repo.pack_cache_size(None);
+        assert!(!repo.objects.has_pack_cache(), "it can be disabled for this handle");
+        repo.pack_cache_size_if_unset(1024 * 1024);
+        assert!(repo.objects.has_pack_cache());
+
+        let repo = basic_repo_opts(crate::restricted().pack_cache_size(0))?;
+        assert!(!repo.objects.has_pack_cache());
+        let repo = basic_repo_opts(crate::restricted().pack_cache_size(1024 * 1024))?;
+        assert!(repo.objects.has_pack_cache());
+        Ok(())
+    }
+}
+
 mod find {
     use gix_pack::Find;
 
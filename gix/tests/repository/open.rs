@@ -289,6 +289,60 @@ mod object_caches {
     }
 }
 
+mod compression {
+    use gix::features::zlib::{Backend, Compression};
+
+    use crate::util::repo_opts;
+
+    fn loose_compression(overrides: &[&str]) -> crate::Result<Compression> {
+        let opts = gix::open::Options::isolated()
+            .strict_config(true)
+            .config_overrides(overrides.iter().copied());
+        Ok(repo_opts("make_basic_repo.sh", opts)?.objects.loose_compression())
+    }
+
+    #[test]
+    fn loose_objects_use_core_loose_compression_then_core_compression() -> crate::Result {
+        assert_eq!(loose_compression(&[])?, Compression::fast(), "the default");
+        assert_eq!(loose_compression(&["core.compression=-1"])?, Compression::default());
+        assert_eq!(
+            loose_compression(&["core.compression=9", "core.looseCompression=0"])?,
+            Compression::none()
+        );
+        assert!(
+            loose_compression(&["core.looseCompression=10"]).is_err(),
+            "invalid levels are rejected"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn zlib_backend_is_used_for_loose_objects_and_packs() -> crate::Result {
+        let opts = |overrides: &[&str]| {
+            gix::open::Options::isolated()
+                .strict_config(true)
+                .config_overrides(overrides.iter().copied())
+        };
+        let repo = repo_opts("make_basic_repo.sh", opts(&[]))?.to_thread_local();
+        assert_eq!(repo.zlib_backend()?, Backend::compiled(), "the default");
+        assert_eq!(repo.objects.store_ref().zlib_backend(), Backend::compiled());
+
+        let repo = repo_opts(
+            "make_basic_repo.sh",
+            opts(&["gitoxide.core.zlibBackend=miniz_oxide", "pack.compression=9"]),
+        )?
+        .to_thread_local();
+        assert_eq!(repo.zlib_backend()?, Backend::MinizOxide);
+        assert_eq!(repo.objects.store_ref().zlib_backend(), Backend::MinizOxide);
+        assert_eq!(repo.pack_compression()?, Compression::best());
+        assert!(
+            repo_opts("make_basic_repo.sh", opts(&["gitoxide.core.zlibBackend=zstd"])).is_err(),
+            "unknown backends are rejected"
+        );
+        Ok(())
+    }
+}
+
 mod worktree {
     use gix::open;
 
@@ -389,3 +443,122 @@ mod worktree {
         Ok(())
     }
 }
+
+mod safe_directory {
+    use gix_testtools::Env;
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn untrusted_repositories_fail_to_open_unless_listed() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let work_dir = tmp.path().join("repo");
+        gix::init(&work_dir)?;
+        let work_dir = gix_path::realpath(&work_dir)?;
+        let global_config = tmp.path().join("global.config");
+        let _env = Env::new()
+            .set("GIT_CONFIG_GLOBAL", global_config.display().to_string())
+            .set("GIT_CONFIG_NOSYSTEM", "1");
+        let open = || {
+            gix::open_opts(
+                &work_dir,
+                gix::open::Options::default()
+                    .with(gix_sec::Trust::Reduced)
+                    .bail_if_untrusted(true),
+            )
+        };
+
+        let err = open().unwrap_err();
+        assert!(
+            matches!(err, gix::open::Error::UnsafeGitDir { ref path } if path == &work_dir),
+            "the work tree is what needs to be listed: {err:?}"
+        );
+
+        let parent = work_dir.parent().expect("parent").display().to_string();
+        for (safe_dirs, is_safe) in [
+            (vec![work_dir.display().to_string()], true),
+            (vec![work_dir.join(".git").display().to_string()], false),
+            (vec!["*".into()], true),
+            (vec!["*".into(), String::new()], false),
+            (vec![format!("{parent}/*")], true),
+            (vec![format!("{}/*", work_dir.display())], false),
+        ] {
+            let mut config = String::from("[safe]\n");
+            for safe_dir in &safe_dirs {
+                config.push_str(&format!("\tdirectory = {safe_dir}\n"));
+            }
+            std::fs::write(&global_config, config)?;
+            assert_eq!(open().is_ok(), is_safe, "{safe_dirs:?}");
+        }
+        Ok(())
+    }
+}
+
+mod environment_overrides {
+    use gix_testtools::Env;
+    use serial_test::serial;
+
+    fn open_with_environment_overrides(dir: &std::path::Path) -> Result<gix::Repository, gix::open::Error> {
+        gix::ThreadSafeRepository::open_with_environment_overrides(dir, Default::default()).map(Into::into)
+    }
+
+    #[test]
+    #[serial]
+    fn git_work_tree_has_precedence_over_core_worktree() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::init(tmp.path().join("repo"))?;
+        let git_dir = repo.git_dir().to_owned();
+        let mut config = std::fs::read_to_string(git_dir.join("config"))?;
+        config.push_str("\tworktree = ../configured\n");
+        std::fs::write(git_dir.join("config"), config)?;
+        let work_tree = tmp.path().join("from-env");
+        std::fs::create_dir(&work_tree)?;
+
+        let _env = Env::new()
+            .set("GIT_DIR", git_dir.display().to_string())
+            .set("GIT_WORK_TREE", work_tree.display().to_string());
+        let repo = open_with_environment_overrides(tmp.path())?;
+        assert_eq!(repo.work_dir(), Some(work_tree.as_ref()));
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn index_file_and_object_directory() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::init(tmp.path().join("repo"))?;
+        let objects_dir = tmp.path().join("objects");
+        std::fs::create_dir(&objects_dir)?;
+
+        let _env = Env::new()
+            .set("GIT_INDEX_FILE", "relative-index")
+            .set("GIT_OBJECT_DIRECTORY", objects_dir.display().to_string());
+        let repo = open_with_environment_overrides(repo.git_dir())?;
+        assert_eq!(
+            repo.index_path(),
+            repo.current_dir().join("relative-index"),
+            "relative index paths are relative to the current working directory"
+        );
+        assert_eq!(repo.objects.store_ref().path(), objects_dir);
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn missing_directories_are_rejected() -> crate::Result {
+        let tmp = gix_testtools::tempfile::tempdir()?;
+        let repo = gix::init(tmp.path().join("repo"))?;
+        let git_dir = repo.git_dir();
+        let missing = tmp.path().join("missing");
+
+        for var in ["GIT_COMMON_DIR", "GIT_OBJECT_DIRECTORY"] {
+            let _env = Env::new().set(var, missing.display().to_string());
+            let err = open_with_environment_overrides(git_dir).unwrap_err();
+            assert!(
+                matches!(err, gix::open::Error::MissingOverrideDirectory { ref path, .. } if path == &missing),
+                "{var}: {err:?}"
+            );
+        }
+        Ok(())
+    }
+}
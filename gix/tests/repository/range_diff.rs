@@ -0,0 +1,60 @@
+use gix::range_diff::{Options, Pair, Status};
+
+use crate::named_repo;
+
+#[test]
+fn output_matches_git() -> crate::Result {
+    let repo = named_repo("make_range_diff_repo.sh")?;
+    let outcome = repo.range_diff("base..v1", "base..v2", Options::default())?;
+    assert_eq!(
+        outcome.pairs.iter().map(Pair::status).collect::<Vec<_>>(),
+        [Status::Changed, Status::Equal, Status::Removed, Status::Added]
+    );
+    assert_eq!(
+        outcome.pairs[0]
+            .interdiff
+            .iter()
+            .map(|h| h.section.to_string())
+            .collect::<Vec<_>>(),
+        ["Metadata"],
+        "the section is the closest header before the hunk"
+    );
+    assert_eq!(outcome.pairs[1].old.as_ref().map(|c| c.position), Some(2));
+    assert_eq!(outcome.pairs[1].new.as_ref().map(|c| c.position), Some(2));
+    assert!(outcome.pairs[1].interdiff.is_empty());
+
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    let expected = std::fs::read(repo.work_dir().expect("non-bare").join("range-diff.out"))?;
+    assert_eq!(out.as_slice(), expected.as_slice(), "the same as produced by git");
+    Ok(())
+}
+
+#[test]
+fn creation_factor_controls_pairing_of_changed_commits() -> crate::Result {
+    let repo = named_repo("make_range_diff_repo.sh")?;
+    let outcome = repo.range_diff(
+        "base..v1",
+        "base..v2",
+        Options {
+            creation_factor: 0,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome.pairs.iter().map(Pair::status).collect::<Vec<_>>(),
+        [
+            Status::Removed,
+            Status::Added,
+            Status::Equal,
+            Status::Removed,
+            Status::Added
+        ],
+        "without the budget to change commits, only equal commits are paired"
+    );
+
+    let outcome = repo.range_diff("base..v1", "base..v1", Options::default())?;
+    assert!(outcome.pairs.iter().all(|pair| pair.status() == Status::Equal));
+    assert_eq!(outcome.pairs.len(), 3);
+    Ok(())
+}
@@ -0,0 +1,246 @@
+use gix::rebase::{EmptyCommits, Instruction, Options, Outcome, Stop};
+
+use crate::repo_rw;
+
+fn checkout(repo: &gix::Repository, branch: &str) -> crate::Result {
+    repo.checkout(branch, gix::checkout::Options::default())?;
+    Ok(())
+}
+
+fn commit(repo: &gix::Repository, spec: &str) -> crate::Result<gix::objs::Commit> {
+    Ok(repo.rev_parse_single(spec)?.object()?.into_commit().decode()?.into())
+}
+
+fn summaries(repo: &gix::Repository, range: &str) -> crate::Result<Vec<String>> {
+    let head = repo.head_id()?;
+    let hidden = repo.rev_parse_single(range)?.detach();
+    let hidden: Vec<_> = repo
+        .rev_walk([hidden])
+        .all()?
+        .map(|info| info.map(|info| info.id))
+        .collect::<Result<_, _>>()?;
+    repo.rev_walk([head])
+        .selected(move |id| !hidden.contains(&id.to_owned()))?
+        .map(|info| -> crate::Result<String> { Ok(info?.object()?.decode()?.message_summary().to_string()) })
+        .collect()
+}
+
+#[test]
+fn clean_rebase_replays_commits_and_updates_branch() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    checkout(&repo, "clean")?;
+    let orig_head = repo.head_id()?.detach();
+
+    let out = repo.rebase("main", Options::default())?;
+    let Outcome::Finished {
+        head_id,
+        autostash_conflicts,
+    } = out
+    else {
+        panic!("expected the rebase to finish, got {out:?}");
+    };
+    assert!(autostash_conflicts.is_empty());
+    assert_eq!(repo.head_id()?, head_id);
+    assert_eq!(repo.head_name()?.expect("attached").as_bstr(), "refs/heads/clean");
+    assert_eq!(
+        summaries(&repo, "main")?,
+        ["add new file", "change first line"],
+        "the commits are on top of main now"
+    );
+    assert_eq!(commit(&repo, "HEAD")?.author, commit(&repo, "stacked~1")?.author);
+    assert_eq!(commit(&repo, "HEAD")?.committer.name, "gitoxide");
+    assert_eq!(
+        std::fs::read(workdir.join("lines"))?,
+        b"1 clean\n2\n3\n4\n5\n6\n7 main\n"
+    );
+    assert_eq!(std::fs::read(workdir.join("conflict"))?, b"c.main\n");
+    assert_eq!(repo.rev_parse_single("ORIG_HEAD")?, orig_head);
+    assert_eq!(repo.state(), None);
+    assert!(!repo.path().join("rebase-merge").exists());
+    assert!(!repo.is_dirty()?);
+
+    let reflog = |name: &str| -> crate::Result<Vec<String>> {
+        Ok(repo
+            .find_reference(name)?
+            .log_iter()
+            .rev()?
+            .expect("log present")
+            .map(|line| line.map(|line| line.message.to_string()))
+            .collect::<Result<_, _>>()?)
+    };
+    assert_eq!(
+        reflog("HEAD")?[..3],
+        [
+            "rebase (pick): add new file",
+            "rebase (pick): change first line",
+            "rebase (start): checkout main"
+        ],
+        "re-attaching HEAD to the branch isn't logged as the commit doesn't change"
+    );
+    assert_eq!(
+        reflog("refs/heads/clean")?[0],
+        format!(
+            "rebase (finish): refs/heads/clean onto {}",
+            repo.rev_parse_single("main")?
+        )
+    );
+    Ok(())
+}
+
+#[test]
+fn onto_only_replays_commits_not_in_upstream() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+    checkout(&repo, "stacked")?;
+
+    let out = repo.rebase(
+        "clean",
+        Options {
+            onto: Some("main".into()),
+            ..Default::default()
+        },
+    )?;
+    assert!(matches!(out, Outcome::Finished { .. }));
+    assert_eq!(summaries(&repo, "main")?, ["add stacked file"]);
+    assert!(repo.work_dir().expect("non-bare").join("stacked").is_file());
+    assert!(!repo.work_dir().expect("non-bare").join("new").exists());
+    Ok(())
+}
+
+#[test]
+fn conflicts_stop_and_can_be_continued_or_aborted() -> crate::Result {
+    for abort in [false, true] {
+        let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+        let workdir = repo.work_dir().expect("non-bare").to_owned();
+        checkout(&repo, "conflicting")?;
+        let orig_head = repo.head_id()?.detach();
+        let to_pick = repo.rev_parse_single("conflicting")?.detach();
+
+        let out = repo.rebase("main", Options::default())?;
+        let Outcome::Stopped {
+            instruction: Instruction::Pick { commit: picked, .. },
+            reason: Stop::Conflicts(conflicts),
+        } = out
+        else {
+            panic!("expected a conflict, got {out:?}");
+        };
+        assert_eq!(picked, to_pick);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].rela_path, "conflict");
+        assert_eq!(repo.state(), Some(gix::state::InProgress::RebaseInteractive));
+        assert!(repo.head_name()?.is_none(), "HEAD is detached while rebasing");
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("REBASE_HEAD"))?.trim(),
+            to_pick.to_string()
+        );
+
+        let state = repo.rebase_state()?.expect("in progress");
+        assert_eq!(state.head_name.expect("branch").as_slice(), b"refs/heads/conflicting");
+        assert_eq!(state.orig_head, orig_head);
+        assert_eq!(state.done.len(), 2);
+        assert!(state.todo.is_empty());
+        assert_eq!(state.stopped_at, Some(to_pick));
+        assert!(matches!(repo.rebase_continue(), Err(gix::rebase::Error::Unmerged)));
+
+        if abort {
+            let autostash_conflicts = repo.rebase_abort()?;
+            assert!(autostash_conflicts.is_empty());
+            assert_eq!(repo.head_id()?, orig_head);
+            assert_eq!(repo.head_name()?.expect("attached").as_bstr(), "refs/heads/conflicting");
+            assert_eq!(std::fs::read(workdir.join("conflict"))?, b"c.topic\n");
+            assert_eq!(repo.state(), None);
+            assert!(!repo.is_dirty()?);
+            continue;
+        }
+
+        std::fs::write(workdir.join("conflict"), "resolved\n")?;
+        let mut index = repo.index_from_tree(&repo.head_commit()?.tree_id()?)?;
+        index
+            .entry_mut_by_path_and_stage("conflict".into(), gix::index::entry::Stage::Unconflicted)
+            .expect("present")
+            .id = repo.write_blob("resolved\n")?.detach();
+        index.write(Default::default())?;
+
+        let out = repo.rebase_continue()?;
+        assert!(matches!(out, Outcome::Finished { .. }));
+        assert_eq!(summaries(&repo, "main")?, ["change conflict", "change first line"]);
+        let head = commit(&repo, "HEAD")?;
+        assert_eq!(head.author, commit(&repo, &to_pick.to_string())?.author);
+        assert_eq!(head.message, "change conflict\n");
+        assert_eq!(repo.rev_parse_single("HEAD:conflict")?.object()?.data, b"resolved\n");
+        assert_eq!(repo.state(), None);
+    }
+    Ok(())
+}
+
+#[test]
+fn empty_commits_are_dropped_or_stop_the_rebase() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+    checkout(&repo, "dup")?;
+    let out = repo.rebase("main", Options::default())?;
+    assert!(matches!(out, Outcome::Finished { .. }));
+    assert_eq!(
+        summaries(&repo, "main")?,
+        ["add dup file"],
+        "the commit whose changes are present already was dropped"
+    );
+
+    let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+    checkout(&repo, "dup")?;
+    let out = repo.rebase(
+        "main",
+        Options {
+            empty: EmptyCommits::Stop,
+            ..Default::default()
+        },
+    )?;
+    assert!(matches!(
+        out,
+        Outcome::Stopped {
+            reason: Stop::Empty,
+            ..
+        }
+    ));
+    let out = repo.rebase_continue()?;
+    assert!(matches!(out, Outcome::Finished { .. }));
+    assert_eq!(summaries(&repo, "main")?, ["add dup file"]);
+    Ok(())
+}
+
+#[test]
+fn local_changes_need_autostash() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_rebase_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    checkout(&repo, "clean")?;
+    std::fs::write(workdir.join("other"), "local change\n")?;
+
+    assert!(matches!(
+        repo.rebase("main", Options::default()),
+        Err(gix::rebase::Error::LocalChanges)
+    ));
+    assert_eq!(repo.state(), None, "nothing was changed");
+
+    let out = repo.rebase(
+        "main",
+        Options {
+            autostash: true,
+            ..Default::default()
+        },
+    )?;
+    let Outcome::Finished {
+        autostash_conflicts, ..
+    } = out
+    else {
+        panic!("expected the rebase to finish, got {out:?}");
+    };
+    assert!(autostash_conflicts.is_empty());
+    assert_eq!(summaries(&repo, "main")?, ["add new file", "change first line"]);
+    assert_eq!(std::fs::read(workdir.join("other"))?, b"local change\n");
+    assert_eq!(
+        repo.index()?.entry_by_path("other".into()).expect("tracked").id,
+        repo.rev_parse_single("HEAD:other")?,
+        "the change isn't staged"
+    );
+    assert!(repo.try_find_reference("refs/stash")?.is_none());
+    Ok(())
+}
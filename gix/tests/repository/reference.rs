@@ -224,3 +224,93 @@ mod head {
         Ok(())
     }
 }
+
+mod reference_memory {
+    use gix::refs::{transaction::PreviousValue, Target};
+
+    fn names(iter: gix::reference::iter::Iter<'_>) -> Vec<String> {
+        iter.filter_map(Result::ok)
+            .map(|r| r.name().as_bstr().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn edits_are_visible_but_not_written() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_references_repo.sh")?;
+        let packed_refs = std::fs::read(repo.git_dir().join("packed-refs"))?;
+        let mut repo = repo.with_reference_memory();
+        assert!(repo.has_reference_memory());
+
+        let id = repo.head_id()?.detach();
+        repo.reference("refs/heads/new", id, PreviousValue::MustNotExist, "in memory")?;
+        assert_eq!(
+            repo.find_reference("new")?.name().as_bstr(),
+            "refs/heads/new",
+            "partial names are looked up in memory as well"
+        );
+        assert!(
+            repo.reference(
+                "refs/heads/new",
+                gix::ObjectId::empty_tree(gix::hash::Kind::Sha1),
+                PreviousValue::MustNotExist,
+                "again"
+            )
+            .is_err(),
+            "edits in memory are validated against the references in memory"
+        );
+
+        repo.find_reference("d1")?.delete()?;
+        assert_eq!(
+            repo.find_reference("d1")?.name().as_bstr(),
+            "refs/heads/d1",
+            "deleting `refs/d1` in memory reveals the next candidate"
+        );
+        repo.find_reference("refs/heads/dt1")?.delete()?;
+        assert!(repo.try_find_reference("refs/heads/dt1")?.is_none());
+
+        repo.edit_reference(gix::refs::transaction::RefEdit {
+            change: gix::refs::transaction::Change::Update {
+                log: Default::default(),
+                expected: PreviousValue::MustNotExist,
+                new: Target::Symbolic("refs/heads/new".try_into()?),
+            },
+            name: "refs/heads/sym".try_into()?,
+            deref: false,
+        })?;
+        let mut sym = repo.find_reference("sym")?;
+        assert_eq!(
+            sym.peel_to_id_in_place()?,
+            id,
+            "symbolic references are followed through memory"
+        );
+
+        assert_eq!(
+            names(repo.references()?.prefixed("refs/heads/")?),
+            [
+                "refs/heads/d1",
+                "refs/heads/main",
+                "refs/heads/multi-link-target1",
+                "refs/heads/new",
+                "refs/heads/sym"
+            ],
+            "references in memory are merged into those on disk, hiding deleted ones"
+        );
+        assert_eq!(repo.clone().find_reference("new")?.name().as_bstr(), "refs/heads/new");
+
+        let memory = repo.take_reference_memory().expect("enabled");
+        assert_eq!(memory.len(), 4);
+        assert!(memory[&gix::refs::FullName::try_from("refs/heads/dt1")?].is_none());
+        assert_eq!(
+            std::fs::read(repo.git_dir().join("packed-refs"))?,
+            packed_refs,
+            "packed references weren't touched"
+        );
+        assert!(
+            !repo.git_dir().join("refs/heads/new").exists(),
+            "no loose references were written"
+        );
+        assert!(repo.try_find_reference("new")?.is_none());
+        assert!(repo.try_find_reference("refs/heads/dt1")?.is_some());
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use gix::reset::Mode;
+
+use crate::repo_rw;
+
+fn entry_id(repo: &gix::Repository, path: &str) -> crate::Result<gix::ObjectId> {
+    Ok(repo.index()?.entry_by_path(path.into()).expect("tracked").id)
+}
+
+#[test]
+fn soft_only_moves_head_and_records_orig_head() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let main_id = repo.head_id()?.detach();
+    let a_id = entry_id(&repo, "a")?;
+
+    let out = repo.reset("other", Mode::Soft)?;
+    assert_eq!(out.previous_head_id, Some(main_id));
+    assert!(out.checkout.is_none());
+    assert_eq!(
+        repo.head_name()?.expect("attached").as_bstr(),
+        "refs/heads/main",
+        "the branch is moved, not HEAD itself"
+    );
+    assert_eq!(repo.head_id()?, out.head_id);
+    assert_eq!(repo.find_reference("ORIG_HEAD")?.id(), main_id);
+    assert_eq!(entry_id(&repo, "a")?, a_id, "the index wasn't touched");
+    assert_eq!(
+        repo.head()?
+            .log_iter()
+            .rev()?
+            .expect("log present")
+            .next()
+            .expect("one line")?
+            .message,
+        "reset: moving to other"
+    );
+    Ok(())
+}
+
+#[test]
+fn mixed_resets_index_but_not_worktree() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+
+    repo.reset("other", Mode::Mixed)?;
+    let other_a_id = repo.rev_parse_single("other:a")?.detach();
+    assert_eq!(entry_id(&repo, "a")?, other_a_id);
+    assert!(repo.index()?.entry_by_path("dir/file".into()).is_none());
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a\n", "worktree is untouched");
+    assert!(repo.is_dirty()?, "the worktree now differs from the index");
+    Ok(())
+}
+
+#[test]
+fn hard_resets_index_and_worktree() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_checkout_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("unchanged"), "local change")?;
+
+    let out = repo.reset("other", Mode::Hard)?;
+    assert_eq!(out.files_removed, 1);
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a.other\n");
+    assert_eq!(std::fs::read(workdir.join("new"))?, b"new\n");
+    assert_eq!(
+        std::fs::read(workdir.join("unchanged"))?,
+        b"unchanged\n",
+        "local changes are discarded"
+    );
+    assert!(!workdir.join("dir").exists());
+    assert!(!repo.is_dirty()?);
+    Ok(())
+}
+
+#[test]
+fn unfinished_merge_is_protected_unless_allowed() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_merge_repo.sh")?;
+    assert!(matches!(
+        repo.reset("HEAD", Mode::Hard),
+        Err(gix::reset::Error::UnfinishedMerge)
+    ));
+    assert_eq!(repo.state(), Some(gix::state::InProgress::Merge), "nothing changed");
+
+    repo.reset(
+        "HEAD",
+        gix::reset::Options {
+            mode: Mode::Hard,
+            allow_unfinished_merge: true,
+        },
+    )?;
+    assert_eq!(repo.state(), None, "the merge state was removed");
+    assert!(!repo.is_dirty()?, "conflicts are resolved in favor of HEAD");
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use gix::{bstr::ByteSlice, rev_list};
+
+use crate::util::named_repo;
+
+fn repo() -> crate::Result<gix::Repository> {
+    named_repo("make_rev_list_repo.sh")
+}
+
+fn expected(name: &str) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(
+        gix_testtools::scripted_fixture_read_only("make_rev_list_repo.sh")?.join(name),
+    )?)
+}
+
+fn rev_list(
+    repo: &gix::Repository,
+    tips: &[&str],
+    hidden: &[&str],
+    options: rev_list::Options,
+) -> crate::Result<Vec<u8>> {
+    let ids = |specs: &[&str]| -> crate::Result<Vec<gix::ObjectId>> {
+        specs
+            .iter()
+            .map(|spec| Ok(repo.rev_parse_single(*spec)?.detach()))
+            .collect()
+    };
+    let outcome = repo.rev_list(ids(tips)?, ids(hidden)?, options)?;
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    Ok(out)
+}
+
+const OBJECTS: rev_list::Options = rev_list::Options {
+    objects: true,
+    boundary: false,
+};
+
+#[test]
+fn commits_only() -> crate::Result {
+    let repo = repo()?;
+    let out = rev_list(&repo, &["HEAD"], &[], rev_list::Options::default())?;
+    assert_eq!(out.as_bstr(), expected("commits.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn objects_with_paths() -> crate::Result {
+    let repo = repo()?;
+    let outcome = repo.rev_list(Some(repo.head_id()?), None::<gix::ObjectId>, OBJECTS)?;
+    assert!(
+        outcome.items.iter().all(|item| !matches!(
+            item,
+            rev_list::Item::Object { path, .. } if path == "submodule"
+        )),
+        "submodule commits are not part of this repository"
+    );
+
+    let out = rev_list(&repo, &["HEAD"], &[], OBJECTS)?;
+    assert_eq!(out.as_bstr(), expected("objects.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn objects_reachable_from_hidden_commits_and_their_trees_are_excluded() -> crate::Result {
+    let repo = repo()?;
+    let out = rev_list(&repo, &["HEAD"], &["v1"], OBJECTS)?;
+    assert_eq!(out.as_bstr(), expected("objects-hidden.out")?.as_bstr());
+    Ok(())
+}
+
+#[test]
+fn boundary() -> crate::Result {
+    let repo = repo()?;
+    let options = rev_list::Options {
+        boundary: true,
+        ..OBJECTS
+    };
+    let out = rev_list(&repo, &["HEAD"], &["v1"], options)?;
+    assert_eq!(out.as_bstr(), expected("objects-boundary.out")?.as_bstr());
+
+    let out = rev_list(&repo, &["HEAD"], &["side"], options)?;
+    assert_eq!(
+        out.as_bstr(),
+        expected("objects-boundary-side.out")?.as_bstr(),
+        "the trees of all boundary commits are excluded"
+    );
+    Ok(())
+}
+
+#[test]
+fn tags_and_trees_as_tips() -> crate::Result {
+    let repo = repo()?;
+    let tag = repo.find_reference("v1")?.target().id().to_owned();
+    let outcome = repo.rev_list(
+        [tag, repo.rev_parse_single("HEAD^{tree}")?.detach()],
+        None::<gix::ObjectId>,
+        OBJECTS,
+    )?;
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    assert_eq!(
+        out.as_bstr(),
+        expected("objects-tag-and-tree.out")?.replace(" v1\n", " \n").as_bstr(),
+        "tags are listed without the name they were given as, which is a deviation"
+    );
+    Ok(())
+}
@@ -43,6 +43,89 @@ fn yes() -> crate::Result {
     Ok(())
 }
 
+mod edit {
+    use serial_test::parallel;
+
+    use crate::util::hex_to_id;
+
+    fn ancestors(repo: &gix::Repository) -> crate::Result<Vec<gix::ObjectId>> {
+        Ok(repo
+            .head_id()?
+            .ancestors()
+            .all()?
+            .map(|c| c.map(|c| c.id))
+            .collect::<Result<_, _>>()?)
+    }
+
+    #[test]
+    #[parallel]
+    fn set_shallow_commits_changes_the_boundary() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_shallow_repo.sh")?;
+        let repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let boundary = hex_to_id("d8523dfd5a7aa16562fa1c3e1d3b4a4494f97876");
+        repo.set_shallow_commits(Some(boundary))?;
+        assert!(repo.is_shallow());
+        assert_eq!(repo.shallow_commits()?.expect("present").as_slice(), [boundary]);
+        assert_eq!(
+            ancestors(&repo)?,
+            [hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12"), boundary],
+            "traversals stop at the new boundary"
+        );
+
+        repo.set_shallow_commits(None)?;
+        assert!(!repo.is_shallow());
+        assert!(!repo.shallow_file().exists(), "the file is removed");
+        assert_eq!(ancestors(&repo)?.len(), 3);
+
+        repo.set_shallow_commits(None)?;
+        assert!(!repo.is_shallow(), "removing a non-existing shallow file is fine");
+        Ok(())
+    }
+
+    #[test]
+    #[parallel]
+    fn unshallow_removes_the_boundary_once_all_parents_are_available() -> crate::Result {
+        let tmp = gix_testtools::scripted_fixture_writable("make_shallow_repo.sh")?;
+        let repo = gix::open_opts(tmp.path().join("shallow"), crate::restricted())?;
+        let boundary = hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12");
+        assert_eq!(repo.unshallow()?, [boundary], "the parents are still missing");
+        assert!(repo.is_shallow());
+
+        std::fs::write(
+            repo.git_dir().join("objects").join("info").join("alternates"),
+            gix_path::into_bstr(tmp.path().join("base").join(".git").join("objects")).as_ref(),
+        )?;
+        let repo = gix::open_opts(tmp.path().join("shallow"), crate::restricted())?;
+        assert!(repo.unshallow()?.is_empty(), "all history is available now");
+        assert!(!repo.is_shallow());
+        assert_eq!(ancestors(&repo)?.len(), 3);
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "blob-diff", feature = "index"))]
+mod log {
+    use serial_test::parallel;
+
+    use crate::util::{hex_to_id, named_subrepo_opts};
+
+    #[test]
+    #[parallel]
+    fn shallow_commits_are_treated_like_root_commits() -> crate::Result {
+        let repo = named_subrepo_opts("make_shallow_repo.sh", "shallow", crate::restricted())?;
+        let commits: Vec<_> = repo
+            .log(None::<&str>, Default::default())?
+            .map(|info| info.map(|info| (info.id, info.parent_ids)))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(
+            commits,
+            [(hex_to_id("30887839de28edf7ab66c860e5c58b4d445f6b12"), Vec::new())],
+            "the missing parents of the boundary aren't looked up"
+        );
+        Ok(())
+    }
+}
+
 mod traverse {
     use gix_traverse::commit::simple::Sorting;
     use serial_test::parallel;
@@ -0,0 +1,81 @@
+use gix::shortlog::{Group, Options, Sort};
+
+use crate::named_repo;
+
+fn expected(repo: &gix::Repository, file_name: &str) -> std::io::Result<Vec<u8>> {
+    std::fs::read(repo.work_dir().expect("non-bare").join(file_name))
+}
+
+#[test]
+fn output_matches_git() -> crate::Result {
+    let repo = named_repo("make_shortlog_repo.sh")?;
+    let outcome = repo.shortlog("HEAD", Options::default())?;
+    assert_eq!(
+        outcome.entries.iter().map(|e| e.name.to_string()).collect::<Vec<_>>(),
+        ["Alice", "Bob", "Zed"],
+        "identities are merged by the mailmap"
+    );
+    let mut out = Vec::new();
+    outcome.write_to(&mut out)?;
+    assert_eq!(out, expected(&repo, "shortlog.out")?, "git shortlog HEAD");
+
+    let outcome = repo.shortlog(
+        "first..HEAD",
+        Options {
+            sort: Sort::Count,
+            merges: false,
+            ..Default::default()
+        },
+    )?;
+    let mut out = Vec::new();
+    outcome.write_summary_to(&mut out)?;
+    assert_eq!(
+        out,
+        expected(&repo, "shortlog-sn.out")?,
+        "git shortlog -sn --no-merges first..HEAD"
+    );
+
+    let outcome = repo.shortlog(
+        "HEAD",
+        Options {
+            group: Group::Committer,
+            email: true,
+            ..Default::default()
+        },
+    )?;
+    let mut out = Vec::new();
+    outcome.write_summary_to(&mut out)?;
+    assert_eq!(out, expected(&repo, "shortlog-sec.out")?, "git shortlog -se -c HEAD");
+    Ok(())
+}
+
+#[test]
+fn ranges_of_parents() -> crate::Result {
+    let repo = named_repo("make_shortlog_repo.sh")?;
+    let outcome = repo.shortlog("HEAD^!", Options::default())?;
+    assert_eq!(outcome.entries.len(), 1);
+    assert_eq!(outcome.entries[0].subjects, ["merge side"]);
+
+    let outcome = repo.shortlog(
+        "HEAD^@",
+        Options {
+            sort: Sort::Count,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome
+            .entries
+            .iter()
+            .map(|e| (e.name.to_string(), e.count()))
+            .collect::<Vec<_>>(),
+        [("Alice".into(), 2), ("Bob".into(), 2), ("Zed".into(), 1)],
+        "both parents are included, with ties ordered by name"
+    );
+
+    assert!(matches!(
+        repo.shortlog("first...HEAD", Options::default()),
+        Err(gix::shortlog::Error::UnsupportedSpec { .. })
+    ));
+    Ok(())
+}
@@ -0,0 +1,193 @@
+use gix::stash::push::Options;
+
+use crate::repo_rw;
+
+fn parents(repo: &gix::Repository, id: gix::ObjectId) -> crate::Result<Vec<gix::ObjectId>> {
+    Ok(repo
+        .find_object(id)?
+        .into_commit()
+        .parent_ids()
+        .map(gix::Id::detach)
+        .collect())
+}
+
+fn stash_tree_file(repo: &gix::Repository, spec: &str) -> crate::Result<Vec<u8>> {
+    Ok(repo.rev_parse_single(spec)?.object()?.detach().data)
+}
+
+#[test]
+fn push_and_pop_restore_staged_and_unstaged_changes() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_stash_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    assert_eq!(repo.stash_push(Options::default())?, None, "nothing to stash");
+
+    std::fs::write(workdir.join("a"), "a changed\n")?;
+    std::fs::remove_file(workdir.join("dir/c"))?;
+    std::fs::write(workdir.join("b"), "b staged\n")?;
+    let mut index = repo.open_index()?;
+    index
+        .entry_mut_by_path_and_stage("b".into(), gix::index::entry::Stage::Unconflicted)
+        .expect("present")
+        .id = repo.write_blob("b staged\n")?.detach();
+    index.write(Default::default())?;
+    std::fs::write(workdir.join("untracked"), "untracked\n")?;
+    let head = repo.head_id()?.detach();
+
+    let id = repo
+        .stash_push(Options::default())?
+        .expect("changes were stashed")
+        .detach();
+    assert_eq!(repo.find_reference("refs/stash")?.id(), id);
+    let parents = parents(&repo, id)?;
+    assert_eq!(parents.len(), 2, "untracked files aren't stashed by default");
+    assert_eq!(parents[0], head);
+    assert_eq!(stash_tree_file(&repo, &format!("{}:b", parents[1]))?, b"b staged\n");
+    assert_eq!(stash_tree_file(&repo, &format!("{id}:a"))?, b"a changed\n");
+    assert!(repo.rev_parse_single(format!("{id}:dir/c").as_str()).is_err());
+
+    let list = repo.stash_list()?;
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0].id, id);
+    assert_eq!(
+        list[0].message,
+        format!("WIP on main: {} initial", head.to_hex_with_len(7))
+    );
+    assert!(!repo.is_dirty()?, "index and worktree were reset");
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a\n");
+    assert_eq!(std::fs::read(workdir.join("dir/c"))?, b"c\n");
+    assert!(workdir.join("untracked").is_file());
+
+    let conflicts = repo.stash_pop(0)?;
+    assert!(conflicts.is_empty());
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"a changed\n");
+    assert_eq!(std::fs::read(workdir.join("b"))?, b"b staged\n");
+    assert!(!workdir.join("dir/c").exists());
+    assert_eq!(
+        repo.index()?.entry_by_path("b".into()).expect("tracked").id,
+        repo.rev_parse_single("HEAD:b")?,
+        "changes are unstaged when applied"
+    );
+    assert!(repo.stash_list()?.is_empty());
+    assert!(repo.try_find_reference("refs/stash")?.is_none());
+    Ok(())
+}
+
+#[test]
+fn untracked_files_are_stashed_and_restored_on_request() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_stash_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::create_dir(workdir.join("new"))?;
+    std::fs::write(workdir.join("new/file"), "untracked\n")?;
+
+    let id = repo
+        .stash_push(Options {
+            message: Some("with untracked".into()),
+            include_untracked: true,
+        })?
+        .expect("untracked files are changes")
+        .detach();
+    assert!(
+        !workdir.join("new").exists(),
+        "untracked files and their directories are removed"
+    );
+    let parents = parents(&repo, id)?;
+    assert_eq!(parents.len(), 3);
+    assert!(repo
+        .find_object(parents[2])?
+        .into_commit()
+        .parent_ids()
+        .next()
+        .is_none());
+    assert_eq!(
+        stash_tree_file(&repo, &format!("{}:new/file", parents[2]))?,
+        b"untracked\n"
+    );
+    assert_eq!(repo.stash_list()?[0].message, "On main: with untracked");
+
+    assert!(repo.stash_apply(0)?.is_empty());
+    assert_eq!(std::fs::read(workdir.join("new/file"))?, b"untracked\n");
+    assert!(
+        repo.index()?.entry_by_path("new/file".into()).is_none(),
+        "untracked files stay untracked"
+    );
+    assert!(matches!(
+        repo.stash_apply(0),
+        Err(gix::stash::apply::Error::UntrackedFileExists { rela_path }) if rela_path == "new/file"
+    ));
+    assert_eq!(repo.stash_list()?.len(), 1, "apply keeps the stash");
+    Ok(())
+}
+
+#[test]
+fn drop_rewrites_the_stash_list() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_stash_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let mut ids = Vec::new();
+    for content in ["first\n", "second\n", "third\n"] {
+        std::fs::write(workdir.join("a"), content)?;
+        ids.push(repo.stash_push(Options::default())?.expect("changed").detach());
+    }
+    let list = |repo: &gix::Repository| -> crate::Result<Vec<gix::ObjectId>> {
+        Ok(repo.stash_list()?.into_iter().map(|entry| entry.id).collect())
+    };
+    assert_eq!(list(&repo)?, [ids[2], ids[1], ids[0]], "most recent first");
+
+    assert!(matches!(
+        repo.stash_drop(3),
+        Err(gix::stash::drop::Error::NoSuchStash { index: 3 })
+    ));
+    assert_eq!(repo.stash_drop(1)?, ids[1]);
+    assert_eq!(list(&repo)?, [ids[2], ids[0]]);
+    assert_eq!(repo.find_reference("refs/stash")?.id(), ids[2]);
+
+    assert_eq!(repo.stash_drop(0)?, ids[2]);
+    assert_eq!(list(&repo)?, [ids[0]]);
+    assert_eq!(
+        repo.find_reference("refs/stash")?.id(),
+        ids[0],
+        "the reference follows the top of the list"
+    );
+    let log: Vec<_> = repo
+        .find_reference("refs/stash")?
+        .log_iter()
+        .all()?
+        .expect("log present")
+        .map(|line| line.map(|line| line.previous_oid()))
+        .collect::<Result<_, _>>()?;
+    assert_eq!(log.len(), 1);
+    assert!(
+        log[0].is_null(),
+        "the previous value of the remaining entry is adjusted"
+    );
+
+    assert_eq!(repo.stash_pop(0)?, Vec::new());
+    assert_eq!(std::fs::read(workdir.join("a"))?, b"first\n");
+    assert!(repo.try_find_reference("refs/stash")?.is_none());
+    assert!(matches!(
+        repo.stash_drop(0),
+        Err(gix::stash::drop::Error::NoSuchStash { index: 0 })
+    ));
+    Ok(())
+}
+
+#[test]
+fn conflicting_pop_keeps_the_stash() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_stash_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "a.stashed\n")?;
+    let id = repo.stash_push(Options::default())?.expect("changed").detach();
+    repo.checkout("conflicting", gix::checkout::Options::default())?;
+
+    let conflicts = repo.stash_pop(0)?;
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].rela_path, "a");
+    assert_eq!(repo.stash_list()?[0].id, id, "the stash is kept on conflict");
+    let index = repo.open_index()?;
+    assert!(index
+        .entry_by_path_and_stage("a".into(), gix::index::entry::Stage::Theirs)
+        .is_some());
+    let content = std::fs::read_to_string(workdir.join("a"))?;
+    assert!(content.contains("<<<<<<< Updated upstream"));
+    assert!(content.contains(">>>>>>> Stashed changes"));
+    Ok(())
+}
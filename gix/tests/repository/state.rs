@@ -48,6 +48,43 @@ fn merge() -> Result {
     Ok(())
 }
 
+#[test]
+fn rebase() -> Result {
+    let repo_path = gix_testtools::scripted_fixture_read_only_with_args("make_rebase_in_progress_repo.sh", ["merge"])?;
+    let repo = gix::open_opts(repo_path, crate::util::restricted())?;
+
+    assert!(repo.head()?.is_detached());
+    assert_eq!(
+        repo.state(),
+        Some(gix::state::InProgress::RebaseInteractive),
+        "the merge backend marks each rebase as interactive, and so does `git status`"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn rebase_apply() -> Result {
+    let tmp = gix_testtools::scripted_fixture_writable_with_args(
+        "make_rebase_in_progress_repo.sh",
+        ["apply"],
+        gix_testtools::Creation::ExecuteScript,
+    )?;
+    let repo = gix::open_opts(tmp.path(), crate::util::restricted())?;
+
+    assert!(repo.head()?.is_detached());
+    assert_eq!(repo.state(), Some(gix::state::InProgress::Rebase));
+
+    std::fs::remove_file(repo.path().join("rebase-apply").join("rebasing"))?;
+    assert_eq!(
+        repo.state(),
+        Some(gix::state::InProgress::ApplyMailboxRebase),
+        "without knowing which command started it, it could be either"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn rebase_interactive() -> Result {
     let repo = named_repo("make_rebase_i_repo.sh")?;
@@ -77,3 +114,65 @@ fn revert_sequence() -> Result {
 
     Ok(())
 }
+
+mod apply_state_changes {
+    use gix::state::{Changes, File, InProgress};
+
+    use crate::{repo_rw, Result};
+
+    #[test]
+    fn writes_files_and_orig_head_together() -> Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+
+        let edits = repo.apply_state_changes(
+            Changes::default()
+                .set_orig_head(head_id, "merge")
+                .write(File::MergeHead, format!("{head_id}\n"))
+                .write(File::MergeMsg, "first message")
+                .write(File::MergeMsg, "Merge branch 'other'\n"),
+        )?;
+        assert_eq!(edits.len(), 1, "only ORIG_HEAD was edited");
+        assert_eq!(repo.find_reference("ORIG_HEAD")?.id(), head_id);
+        assert_eq!(repo.state(), Some(InProgress::Merge));
+        assert_eq!(
+            std::fs::read(repo.path().join("MERGE_MSG"))?,
+            b"Merge branch 'other'\n",
+            "later changes to the same file win"
+        );
+
+        let edits = repo.apply_state_changes(Changes::default().remove_merge_state())?;
+        assert!(edits.is_empty());
+        assert_eq!(repo.state(), None);
+        assert!(!repo.path().join("MERGE_MSG").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn nothing_is_written_if_a_file_is_locked() -> Result {
+        let (repo, _tmp) = repo_rw("make_basic_repo.sh")?;
+        let head_id = repo.head_id()?.detach();
+        let _lock = gix_lock::Marker::acquire_to_hold_resource(
+            repo.path().join("SQUASH_MSG"),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+
+        let err = repo
+            .apply_state_changes(
+                Changes::default()
+                    .set_orig_head(head_id, "merge --squash")
+                    .write(File::MergeMsg, "message")
+                    .write(File::SquashMsg, "squashed"),
+            )
+            .expect_err("SQUASH_MSG is locked");
+        assert!(matches!(err, gix::state::apply::Error::LockFile { .. }));
+        assert!(repo.try_find_reference("ORIG_HEAD")?.is_none());
+        assert!(!repo.path().join("MERGE_MSG").exists());
+        assert!(
+            !repo.path().join("MERGE_MSG.lock").exists(),
+            "locks that were already taken are released"
+        );
+        Ok(())
+    }
+}
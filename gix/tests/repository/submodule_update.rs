@@ -0,0 +1,93 @@
+use gix::submodule::update::{Action, Error, Options};
+
+use crate::repo_rw;
+
+fn actions(out: &gix::submodule::update::Outcome) -> Vec<(String, Action)> {
+    out.entries
+        .iter()
+        .map(|entry| (entry.path.to_string(), entry.action))
+        .collect()
+}
+
+#[test]
+fn init_clones_and_checks_out_recursively() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_submodule_update_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    assert!(
+        repo.submodule_update(Options::default())?.entries.is_empty(),
+        "uninitialized submodules are skipped"
+    );
+
+    let options = Options {
+        init: true,
+        recursive: true,
+        ..Default::default()
+    };
+    let out = repo.submodule_update(options)?;
+    assert_eq!(
+        actions(&out),
+        [("m1".into(), Action::Cloned), ("m1/nested".into(), Action::Cloned)]
+    );
+    let reopened = gix::open_opts(&workdir, crate::restricted())?;
+    assert_eq!(
+        reopened
+            .config_snapshot()
+            .string("submodule.m1.url")
+            .expect("initialized")
+            .as_ref(),
+        gix::path::into_bstr(gix::path::realpath(workdir.join("module1"))?).as_ref(),
+        "relative URLs are resolved against the worktree without a remote"
+    );
+    assert_eq!(std::fs::read(workdir.join("m1/file"))?, b"second\n");
+    assert_eq!(std::fs::read(workdir.join("m1/nested/file"))?, b"nested\n");
+    assert_eq!(std::fs::read(workdir.join("m1/.git"))?, b"gitdir: ../.git/modules/m1\n");
+    assert_eq!(
+        std::fs::read(workdir.join("m1/nested/.git"))?,
+        b"gitdir: ../../.git/modules/m1/modules/nested\n"
+    );
+
+    let sm = repo.submodules()?.expect("present").next().expect("one submodule");
+    let sm_repo = sm.open()?.expect("cloned");
+    assert_eq!(sm_repo.head_id()?, out.entries[0].id);
+    assert!(
+        sm_repo.head_name()?.is_none(),
+        "submodules are checked out on a detached HEAD"
+    );
+    assert!(!sm_repo.is_dirty()?);
+    assert!(!repo.is_dirty()?);
+
+    let out = repo.submodule_update(options)?;
+    assert_eq!(
+        actions(&out),
+        [("m1".into(), Action::UpToDate), ("m1/nested".into(), Action::UpToDate)]
+    );
+
+    sm_repo.checkout("HEAD~1", gix::checkout::Options::default())?;
+    assert_eq!(std::fs::read(workdir.join("m1/file"))?, b"first\n");
+    let out = reopened.submodule_update(Options::default())?;
+    assert_eq!(
+        actions(&out),
+        [("m1".into(), Action::CheckedOut)],
+        "once initialized, no option is needed"
+    );
+    assert_eq!(std::fs::read(workdir.join("m1/file"))?, b"second\n");
+    assert_eq!(sm_repo.head_id()?, out.entries[0].id);
+    Ok(())
+}
+
+#[test]
+fn nesting_is_limited() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_submodule_update_repo.sh")?;
+    let err = repo
+        .submodule_update(Options {
+            init: true,
+            recursive: true,
+            max_depth: 1,
+        })
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::MaxDepthExceeded { path, max_depth: 1 } if path == "m1/nested"),
+        "{err:?}"
+    );
+    Ok(())
+}
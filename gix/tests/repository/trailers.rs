@@ -0,0 +1,252 @@
+use gix::{
+    config::tree::Trailer,
+    trailers::{Configuration, Edit, IfExists, IfMissing, Overrides, Where},
+};
+
+fn add(token: &str, value: &str) -> Edit {
+    add_with(token, value, Overrides::default())
+}
+
+fn add_with(token: &str, value: &str, overrides: Overrides) -> Edit {
+    Edit::Add {
+        token: token.into(),
+        value: value.into(),
+        overrides,
+    }
+}
+
+fn edit(message: &str, edits: &[Edit]) -> String {
+    Configuration::default().edit(message.into(), edits).to_string()
+}
+
+mod edit {
+    use gix::trailers::{Configuration, Edit, IfExists, IfMissing, Overrides, Token, Where};
+
+    use super::{add, add_with, edit};
+
+    #[test]
+    fn trailers_get_their_own_paragraph_before_comments_and_patches() {
+        assert_eq!(
+            edit("subject\n", &[add("Signed-off-by", "A <a@example.com>")]),
+            "subject\n\nSigned-off-by: A <a@example.com>\n"
+        );
+        assert_eq!(
+            edit("subject\n\nbody\n---\n file | 1 +\n", &[add("Signed-off-by", "t")]),
+            "subject\n\nbody\n\nSigned-off-by: t\n---\n file | 1 +\n",
+            "a patch is kept after the trailers"
+        );
+        assert_eq!(
+            edit(
+                "subject\n\nbody text\n\nAcked-by: x\nFixes:y\n  continued here\n# a comment\n\n# trailing\n",
+                &[add("Reviewed-by", "r")]
+            ),
+            "subject\n\nbody text\n\nAcked-by: x\nFixes: y\n  continued here\nReviewed-by: r\n# a comment\n\n# trailing\n",
+            "existing trailers are normalized, and trailing comments stay at the end"
+        );
+        assert_eq!(
+            edit(
+                "subject\n\nthis is not\na trailer paragraph\nat all: no\n",
+                &[add("Signed-off-by", "t")]
+            ),
+            "subject\n\nthis is not\na trailer paragraph\nat all: no\n\nSigned-off-by: t\n"
+        );
+        assert_eq!(
+            edit(
+                "subject\n\nthis is not\na trailer paragraph\nSigned-off-by: s\n",
+                &[add("Signed-off-by", "t")]
+            ),
+            "subject\n\nthis is not\na trailer paragraph\nSigned-off-by: s\nSigned-off-by: t\n",
+            "a paragraph with enough trailers and one generated by git counts as trailers"
+        );
+        assert_eq!(edit("subject\n\nbody\n", &[]), "subject\n\nbody\n", "nothing to do");
+    }
+
+    #[test]
+    fn existing_trailers_are_handled_according_to_the_policy() {
+        let message = "subject\n\nbody\n\nAcked-by: x\nSigned-off-by: s\nAcked-by: z\n";
+        let replace = |position| Overrides {
+            position: Some(position),
+            if_exists: Some(IfExists::Replace),
+            if_missing: None,
+        };
+        assert_eq!(
+            edit(message, &[add_with("acked-by", "new", replace(Where::After))]),
+            "subject\n\nbody\n\nAcked-by: x\nSigned-off-by: s\nacked-by: new\n"
+        );
+        assert_eq!(
+            edit(message, &[add_with("acked-by", "new", replace(Where::Before))]),
+            "subject\n\nbody\n\nacked-by: new\nSigned-off-by: s\nAcked-by: z\n"
+        );
+
+        let message = "subject\n\nbody\n\nAcked-by: x\nSigned-off-by: s\n";
+        assert_eq!(
+            edit(message, &[add("Signed-off-by", "s")]),
+            message,
+            "the neighbor is the same"
+        );
+        let at_start = |if_exists| Overrides {
+            position: Some(Where::Start),
+            if_exists: Some(if_exists),
+            if_missing: None,
+        };
+        assert_eq!(
+            edit(
+                message,
+                &[add_with(
+                    "Signed-off-by",
+                    "s",
+                    at_start(IfExists::AddIfDifferentNeighbor)
+                )]
+            ),
+            "subject\n\nbody\n\nSigned-off-by: s\nAcked-by: x\nSigned-off-by: s\n",
+            "the neighbor at the start is different"
+        );
+        assert_eq!(
+            edit(
+                message,
+                &[add_with("Signed-off-by", "s", at_start(IfExists::AddIfDifferent))]
+            ),
+            message
+        );
+        assert_eq!(
+            edit(message, &[add_with("Acked-by", "x", at_start(IfExists::Add))]),
+            "subject\n\nbody\n\nAcked-by: x\nAcked-by: x\nSigned-off-by: s\n"
+        );
+        assert_eq!(
+            edit(message, &[add_with("Acked-by", "y", at_start(IfExists::DoNothing))]),
+            message
+        );
+        assert_eq!(
+            edit(
+                message,
+                &[add_with(
+                    "Reviewed-by",
+                    "r",
+                    Overrides {
+                        if_missing: Some(IfMissing::DoNothing),
+                        ..Default::default()
+                    }
+                )]
+            ),
+            message
+        );
+        assert_eq!(
+            edit(
+                message,
+                &[Edit::Remove {
+                    token: "acked-BY".into()
+                }]
+            ),
+            "subject\n\nbody\n\nSigned-off-by: s\n"
+        );
+        assert_eq!(
+            edit(
+                message,
+                &[
+                    Edit::Remove {
+                        token: "acked-by".into()
+                    },
+                    Edit::Remove {
+                        token: "signed-off-by".into()
+                    }
+                ]
+            ),
+            "subject\n\nbody\n",
+            "the paragraph disappears with the last trailer"
+        );
+    }
+
+    #[test]
+    fn configured_tokens_can_be_used_by_name_and_have_their_own_policy() {
+        let config = Configuration {
+            separators: ":#".into(),
+            tokens: vec![
+                Token {
+                    name: "sign".into(),
+                    key: Some("Signed-off-by: ".into()),
+                    overrides: Overrides {
+                        if_exists: Some(IfExists::AddIfDifferent),
+                        ..Default::default()
+                    },
+                },
+                Token {
+                    name: "bug".into(),
+                    key: Some("Bug #".into()),
+                    overrides: Overrides {
+                        position: Some(Where::Start),
+                        ..Default::default()
+                    },
+                },
+            ],
+            ..Default::default()
+        };
+        let message = "subject\n\nSigned-off-by: a\nAcked-by: b\n";
+        assert_eq!(
+            config
+                .edit(
+                    message.into(),
+                    &[
+                        super::add("sign", "a"),
+                        super::add("sign", "c"),
+                        super::add("bug", "42"),
+                        super::add("Bug", "7"),
+                    ]
+                )
+                .to_string(),
+            "subject\n\nBug #7\nBug #42\nSigned-off-by: a\nAcked-by: b\nSigned-off-by: c\n"
+        );
+    }
+}
+
+#[test]
+fn trailer_configuration_and_interpretation() -> crate::Result {
+    let (mut repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+    assert_eq!(repo.trailer_configuration()?, Configuration::default());
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Trailer::WHERE, "start")?;
+        config.set_value(&Trailer::SEPARATORS, "=:")?;
+        config.set_subsection_value(&Trailer::TOKEN_KEY, "sign", "Signed-off-by")?;
+        config.set_subsection_value(&Trailer::TOKEN_IF_EXISTS, "sign", "replace")?;
+        config.set_subsection_value(&Trailer::TOKEN_IF_MISSING, "sign", "doNothing")?;
+    }
+    let config = repo.trailer_configuration()?;
+    assert_eq!(config.separators, "=:");
+    assert_eq!(config.policy.position, Where::Start);
+    assert_eq!(config.tokens.len(), 1);
+    assert_eq!(config.tokens[0].key(), "Signed-off-by");
+    assert_eq!(config.tokens[0].overrides.if_exists, Some(IfExists::Replace));
+    assert_eq!(config.tokens[0].overrides.if_missing, Some(IfMissing::DoNothing));
+    assert_eq!(config.tokens[0].overrides.position, None);
+
+    assert_eq!(
+        repo.interpret_trailers(
+            "subject\n\nSigned-off-by=old\nAcked-by: a\n",
+            &[add("sign", "new"), add("Reviewed-by", "r")]
+        )?,
+        "subject\n\nReviewed-by= r\nSigned-off-by= new\nAcked-by= a\n",
+        "all trailers are written with the first separator"
+    );
+    assert_eq!(
+        repo.interpret_trailers("subject\n", &[add("sign", "new")])?,
+        "subject\n",
+        "nothing is added if it's missing"
+    );
+
+    repo.config_snapshot_mut()
+        .set_raw_value("trailer", None, "ifExists", "sometimes")?;
+    assert_eq!(
+        repo.trailer_configuration()?.policy.if_exists,
+        IfExists::AddIfDifferentNeighbor,
+        "invalid values are ignored as the configuration is lenient by default"
+    );
+
+    let (mut repo, _tmp) = crate::repo_rw_opts("make_basic_repo.sh", crate::restricted().strict_config(true))?;
+    repo.config_snapshot_mut()
+        .set_raw_value("trailer", None, "ifExists", "sometimes")?;
+    assert!(
+        repo.trailer_configuration().is_err(),
+        "invalid values are an error if the configuration is strict"
+    );
+    Ok(())
+}
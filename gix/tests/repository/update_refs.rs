@@ -0,0 +1,221 @@
+use gix::{
+    bstr::ByteSlice,
+    update_refs::{self, Command, Operation},
+};
+
+use crate::util::hex_to_id;
+
+fn input(repo: &gix::Repository, script: &str) -> crate::Result<String> {
+    let head = repo.rev_parse_single("HEAD")?.to_string();
+    let parent = repo.rev_parse_single("HEAD~1")?.to_string();
+    Ok(script
+        .replace("<head>", &head)
+        .replace("<parent>", &parent)
+        .replace("<null>", &gix::ObjectId::null(repo.object_hash()).to_string()))
+}
+
+fn parse(repo: &gix::Repository, script: &str) -> crate::Result<Vec<Command>> {
+    Ok(update_refs::parse(
+        input(repo, script)?.as_bytes().as_bstr(),
+        repo.object_hash(),
+    )?)
+}
+
+fn id(repo: &gix::Repository, name: &str) -> crate::Result<Option<gix::ObjectId>> {
+    Ok(repo
+        .try_find_reference(name)?
+        .and_then(|r| r.try_id().map(gix::Id::detach)))
+}
+
+#[test]
+fn parse_all_commands() -> crate::Result {
+    let repo = crate::basic_repo()?;
+    let head = repo.head_id()?.detach();
+    let parent = repo.rev_parse_single("HEAD~1")?.detach();
+    let commands = parse(
+        &repo,
+        "update refs/heads/main <parent> <head>\n\
+         update refs/heads/any <head>\n\
+         create refs/heads/new <head>\n\
+         delete refs/heads/old\n\
+         option no-deref\n\
+         verify HEAD <null>\n\
+         verify refs/heads/main <head>\n",
+    )?;
+    let command = |name: &str, operation, deref| Command {
+        name: name.try_into().expect("valid"),
+        operation,
+        deref,
+    };
+    assert_eq!(
+        commands,
+        [
+            command(
+                "refs/heads/main",
+                Operation::Update {
+                    new: parent,
+                    old: Some(head)
+                },
+                true
+            ),
+            command("refs/heads/any", Operation::Update { new: head, old: None }, true),
+            command("refs/heads/new", Operation::Create { new: head }, true),
+            command("refs/heads/old", Operation::Delete { old: None }, true),
+            command("HEAD", Operation::Verify { old: None }, false),
+            command("refs/heads/main", Operation::Verify { old: Some(head) }, true),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn commands_round_trip_with_quoted_names() -> crate::Result {
+    let mut repo = crate::basic_repo()?;
+    let script = "update \"refs/heads/caf\\303\\251\" <head> <parent>\noption no-deref\ndelete refs/heads/main\n";
+    let commands = parse(&repo, script)?;
+    assert_eq!(commands[0].name.as_bstr(), "refs/heads/café");
+
+    let write = |repo: &gix::Repository| -> std::io::Result<String> {
+        let mut out = Vec::new();
+        for command in &commands {
+            command.write_to(&mut out, repo.quote_path())?;
+        }
+        Ok(String::from_utf8(out).expect("valid UTF-8"))
+    };
+    assert!(repo.quote_path(), "non-ASCII bytes are quoted by default");
+    assert_eq!(write(&repo)?, input(&repo, script)?);
+
+    repo.config_snapshot_mut()
+        .set_value(&gix::config::tree::Core::QUOTE_PATH, "false")?;
+    assert!(!repo.quote_path());
+    assert_eq!(
+        write(&repo)?,
+        input(
+            &repo,
+            "update refs/heads/café <head> <parent>\noption no-deref\ndelete refs/heads/main\n"
+        )?
+    );
+    Ok(())
+}
+
+#[test]
+fn parse_errors() -> crate::Result {
+    let repo = crate::basic_repo()?;
+    for (script, expected) in [
+        ("frobnicate refs/heads/main\n", "Line 1: unknown command \"frobnicate\""),
+        (
+            "verify HEAD\ncreate refs/heads/main\n",
+            "Line 2: wrong number of arguments for \"create\"",
+        ),
+        (
+            "create refs/heads/new <null>",
+            "Line 1: a reference can't be created with the null id",
+        ),
+        (
+            "option no-deref\ndelete refs/heads/main <null>",
+            "Line 2: a reference to delete can't be expected to have the null id",
+        ),
+        (
+            "option deref\n",
+            "Line 1: unsupported option \"deref\", only 'no-deref' is supported",
+        ),
+        ("delete refs/heads/main 1234\n", "Line 1: invalid object id"),
+        ("delete main\n", "Line 1: invalid reference name"),
+        ("delete \"refs/heads/main\n", "Line 1: could not unquote reference name"),
+        (
+            "delete \"refs/heads/main\"x\n",
+            "Line 1: unexpected characters after the quoted reference name in \"\\\"refs/heads/main\\\"x\"",
+        ),
+    ] {
+        let err = parse(&repo, script).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+    Ok(())
+}
+
+#[test]
+fn all_commands_are_applied_in_one_transaction() -> crate::Result {
+    let (repo, _keep) = crate::basic_rw_repo()?;
+    let head = repo.head_id()?.detach();
+    let parent = repo.rev_parse_single("HEAD~1")?.detach();
+    repo.reference("refs/heads/old", parent, gix::refs::transaction::PreviousValue::Any, "")?;
+
+    let commands = parse(
+        &repo,
+        "update refs/heads/main <parent> <head>\n\
+         create refs/heads/new <head>\n\
+         delete refs/heads/old <parent>\n\
+         verify refs/heads/missing\n\
+         verify HEAD <head>\n",
+    )?;
+    let edits = repo.update_refs(
+        commands,
+        update_refs::Options {
+            message: "batch".into(),
+            create_reflog: false,
+        },
+    )?;
+    assert_eq!(edits.len(), 3, "verifications don't show up as edits");
+
+    assert_eq!(id(&repo, "refs/heads/main")?, Some(parent));
+    assert_eq!(id(&repo, "refs/heads/new")?, Some(head));
+    assert_eq!(id(&repo, "refs/heads/old")?, None);
+    let reference = repo.find_reference("refs/heads/main")?;
+    let mut log = reference.log_iter();
+    let last = log.rev()?.expect("log exists").next().expect("one line")?;
+    assert_eq!(last.message, "batch");
+    assert_eq!(last.previous_oid, head);
+    Ok(())
+}
+
+#[test]
+fn failed_verification_changes_nothing() -> crate::Result {
+    let (repo, _keep) = crate::basic_rw_repo()?;
+    let head = repo.head_id()?.detach();
+    let parent = repo.rev_parse_single("HEAD~1")?.detach();
+
+    let commands = parse(
+        &repo,
+        "update refs/heads/main <parent>\n\
+         create refs/heads/new <head>\n\
+         verify refs/heads/main <parent>\n",
+    )?;
+    let err = repo.update_refs(commands, Default::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Reference \"refs/heads/main\" was expected at {parent}, but was at {head}")
+    );
+    assert_eq!(id(&repo, "refs/heads/main")?, Some(head), "nothing was changed");
+    assert_eq!(id(&repo, "refs/heads/new")?, None);
+
+    let commands = parse(
+        &repo,
+        "create refs/heads/new <head>\n\
+         update refs/heads/main <parent> <parent>\n",
+    )?;
+    assert!(
+        matches!(
+            repo.update_refs(commands, Default::default()),
+            Err(update_refs::Error::Prepare(_))
+        ),
+        "out-of-date old values fail the whole transaction"
+    );
+    assert_eq!(id(&repo, "refs/heads/main")?, Some(head));
+    assert_eq!(id(&repo, "refs/heads/new")?, None);
+
+    let commands = [Command {
+        name: "HEAD".try_into()?,
+        operation: Operation::Verify {
+            old: Some(hex_to_id("3189cd3cb0af8586c39a838aa3e54fd72a872a41")),
+        },
+        deref: false,
+    }];
+    assert!(
+        matches!(
+            repo.update_refs(commands, Default::default()),
+            Err(update_refs::Error::VerifyFailed { actual: None, .. })
+        ),
+        "symbolic references don't have an id if they aren't followed"
+    );
+    Ok(())
+}
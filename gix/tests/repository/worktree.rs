@@ -13,6 +13,62 @@ fn stream() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(feature = "worktree-stream")]
+fn stream_of_commit_substitutes_placeholders_like_git_archive() -> crate::Result {
+    use std::io::Read;
+
+    let repo = crate::named_repo("make_export_subst_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare");
+    let (mut stream, _index) = repo.worktree_stream(repo.head_id()?)?;
+    let mut paths = Vec::new();
+    let mut version = Vec::new();
+    let mut not_substituted = Vec::new();
+    while let Some(mut entry) = stream.next_entry()? {
+        let path = entry.relative_path().to_owned();
+        match path.as_slice() {
+            b"version" => entry.read_to_end(&mut version)?,
+            b"not-substituted" => entry.read_to_end(&mut not_substituted)?,
+            _ => entry.read_to_end(&mut Vec::new())?,
+        };
+        paths.push(path);
+    }
+    paths.sort();
+
+    assert_eq!(
+        paths,
+        std::fs::read_to_string(workdir.join("paths.baseline"))?
+            .lines()
+            .collect::<Vec<_>>(),
+        "`export-ignore` is respected"
+    );
+    assert_eq!(
+        version.as_slice(),
+        std::fs::read(workdir.join("version.baseline"))?,
+        "placeholders are expanded just like git does it"
+    );
+    assert_eq!(
+        not_substituted,
+        std::fs::read(workdir.join("not-substituted"))?,
+        "files without the `export-subst` attribute are left alone"
+    );
+
+    let (mut stream, _index) = repo.worktree_stream(repo.head_commit()?.tree_id()?)?;
+    let mut version_from_tree = Vec::new();
+    while let Some(mut entry) = stream.next_entry()? {
+        if entry.relative_path() == "version" {
+            entry.read_to_end(&mut version_from_tree)?;
+        } else {
+            entry.read_to_end(&mut Vec::new())?;
+        }
+    }
+    assert_eq!(
+        version_from_tree, not_substituted,
+        "trees have no commit information, so there is nothing to substitute"
+    );
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "worktree-archive")]
 fn archive() -> crate::Result {
@@ -336,3 +392,199 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         );
     }
 }
+
+mod add_and_prune {
+    use gix::worktree::{add, prune};
+
+    fn repo_rw() -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let dir = gix_testtools::scripted_fixture_writable("make_worktree_repo.sh")?;
+        let repo = gix::open_opts(dir.path().join("repo"), crate::restricted())?;
+        Ok((repo, dir))
+    }
+
+    #[test]
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    fn add_checks_out_a_new_branch_named_like_the_worktree() -> crate::Result {
+        let (repo, tmp) = repo_rw()?;
+        let path = tmp.path().join("new/wt");
+        let wt = repo.worktree_add(&path, add::Options::default())?;
+        assert_eq!(wt.head_name()?.expect("attached").as_bstr(), "refs/heads/wt");
+        assert_eq!(wt.head_id()?, repo.head_id()?);
+        assert_eq!(
+            repo.find_reference("wt")?.id(),
+            repo.head_id()?,
+            "the branch was created at HEAD"
+        );
+        assert_eq!(wt.worktree().expect("linked").id(), Some("wt".into()));
+        assert_eq!(std::fs::read(path.join("a"))?, b"hello\n");
+        assert!(path.join("dir/c").is_file());
+        assert!(!wt.is_dirty()?, "index and worktree match HEAD");
+        assert_eq!(wt.main_repo()?, repo);
+
+        let proxy = repo
+            .worktrees()?
+            .into_iter()
+            .find(|proxy| proxy.id() == "wt")
+            .expect("listed");
+        assert_eq!(proxy.base()?, gix_path::realpath(&path)?);
+        assert!(!proxy.is_locked(), "the lock used during creation is removed");
+        assert_eq!(
+            std::fs::read(path.join(".git"))?,
+            format!("gitdir: {}\n", gix_path::realpath(proxy.git_dir())?.display()).as_bytes()
+        );
+
+        let wt = repo.worktree_add(
+            tmp.path().join("other/wt"),
+            add::Options {
+                start: Some("HEAD~1".into()),
+                lock: Some("on a stick".into()),
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            wt.worktree().expect("linked").id(),
+            Some("wt1".into()),
+            "names are made unique"
+        );
+        assert_eq!(wt.head_name()?, None, "non-branches are checked out detached");
+        assert_eq!(wt.head_id()?, repo.rev_parse_single("HEAD~1")?);
+        assert_eq!(wt.worktree().expect("linked").lock_reason(), Some("on a stick".into()));
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "worktree-mutation", feature = "status", feature = "revision"))]
+    fn add_refuses_to_check_out_a_branch_twice() -> crate::Result {
+        let (repo, tmp) = repo_rw()?;
+        let main_options = add::Options {
+            start: Some("main".into()),
+            ..Default::default()
+        };
+        let err = repo
+            .worktree_add(tmp.path().join("twice"), main_options.clone())
+            .unwrap_err();
+        assert!(
+            matches!(&err, add::Error::BranchCheckedOut { name, worktree } if name == "main" && *worktree == repo.work_dir().expect("non-bare")),
+            "{err:?}"
+        );
+        let err = repo
+            .worktree_add(
+                tmp.path().join("twice"),
+                add::Options {
+                    start: Some("wt-a".into()),
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, add::Error::BranchCheckedOut { name, .. } if name == "wt-a"),
+            "linked worktrees are checked as well: {err:?}"
+        );
+        let err = repo
+            .worktree_add(
+                tmp.path().join("twice"),
+                add::Options {
+                    new_branch: Some("wt-a".into()),
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, add::Error::BranchExists { name } if name == "wt-a"),
+            "{err:?}"
+        );
+        assert!(matches!(
+            repo.worktree_add(tmp.path().join("repo"), Default::default()),
+            Err(add::Error::PathExists { .. })
+        ));
+
+        let wt = repo.worktree_add(
+            tmp.path().join("twice"),
+            add::Options {
+                force: true,
+                ..main_options
+            },
+        )?;
+        assert_eq!(wt.head_name()?.expect("attached").as_bstr(), "refs/heads/main");
+
+        let wt = repo.worktree_add(
+            tmp.path().join("detached"),
+            add::Options {
+                start: Some("main".into()),
+                detach: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(wt.head_name()?, None, "detached checkouts don't conflict");
+        Ok(())
+    }
+
+    #[test]
+    fn lock_and_unlock() -> crate::Result {
+        let (repo, _tmp) = repo_rw()?;
+        let proxy = repo
+            .worktrees()?
+            .into_iter()
+            .find(|proxy| proxy.id() == "wt-b")
+            .expect("present");
+        assert!(!proxy.is_locked());
+        proxy.lock(Some("reason".into()))?;
+        assert_eq!(proxy.lock_reason(), Some("reason".into()));
+        assert!(matches!(
+            proxy.lock(None),
+            Err(gix::worktree::proxy::lock::Error::AlreadyLocked { .. })
+        ));
+        proxy.unlock()?;
+        assert!(!proxy.is_locked());
+        assert!(matches!(
+            proxy.unlock(),
+            Err(gix::worktree::proxy::lock::Error::NotLocked { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn prune_removes_worktrees_without_checkout_unless_locked() -> crate::Result {
+        let (repo, tmp) = repo_rw()?;
+        std::fs::remove_dir_all(tmp.path().join("wt-c-locked"))?;
+        std::fs::remove_file(repo.common_dir().join("worktrees/wt-b/gitdir"))?;
+        let ids = |out: &prune::Outcome| {
+            out.entries
+                .iter()
+                .map(|entry| (entry.id.to_string(), entry.reason))
+                .collect::<Vec<_>>()
+        };
+
+        let out = repo.worktree_prune(prune::Options {
+            expire: Some(std::time::SystemTime::UNIX_EPOCH),
+            dry_run: true,
+        })?;
+        assert_eq!(
+            ids(&out),
+            [("wt-b".into(), prune::Reason::GitDirFileMissing)],
+            "recently used worktrees are kept if they expire"
+        );
+
+        let count = repo.worktrees()?.len();
+        let out = repo.worktree_prune(prune::Options {
+            dry_run: true,
+            ..Default::default()
+        })?;
+        let expected = [
+            ("wt-b".into(), prune::Reason::GitDirFileMissing),
+            ("wt-deleted".into(), prune::Reason::WorktreeMissing),
+        ];
+        assert_eq!(ids(&out), expected);
+        assert_eq!(repo.worktrees()?.len(), count, "nothing is removed in a dry-run");
+
+        let out = repo.worktree_prune(Default::default())?;
+        assert_eq!(ids(&out), expected);
+        assert_eq!(repo.worktrees()?.len(), count - 1, "wt-b wasn't listed anymore");
+        assert!(
+            repo.common_dir().join("worktrees/wt-c-locked").is_dir(),
+            "locked worktrees are never pruned"
+        );
+        assert!(repo.worktree_prune(Default::default())?.entries.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::util::named_subrepo_opts;
+
+#[test]
+fn counts_match_git_with_and_without_commit_graph() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only("make_ahead_behind_repo.sh")?;
+    let baseline = std::fs::read_to_string(dir.join("baseline"))?;
+    for (name, has_commit_graph) in [(".", false), ("with-graph.git", true)] {
+        let repo = named_subrepo_opts("make_ahead_behind_repo.sh", name, crate::restricted())?;
+        assert_eq!(repo.commit_graph().is_ok(), has_commit_graph);
+        for line in baseline.lines() {
+            let mut tokens = line.split(' ');
+            let mut next = || tokens.next().expect("four tokens");
+            let (a, b) = (repo.rev_parse_single(next())?, repo.rev_parse_single(next())?);
+            let expected = (next().parse()?, next().parse()?);
+            assert_eq!(repo.ahead_behind(a, b)?, expected, "{line} in '{name}'");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_commits_are_an_error() -> crate::Result {
+    let repo = crate::named_repo("make_ahead_behind_repo.sh")?;
+    let head = repo.head_id()?;
+    let missing = repo.object_hash().null();
+    assert!(matches!(
+        repo.ahead_behind(head, missing),
+        Err(gix::repository::ahead_behind::Error::NotFound { id }) if id == missing
+    ));
+    Ok(())
+}
@@ -1 +1,2 @@
+mod ahead_behind;
 mod spec;
@@ -39,6 +39,38 @@ fn prefix() {
     }
 }
 
+#[test]
+fn resolve_prefix() -> crate::Result {
+    let repo = repo("blob.prefix")?;
+    let prefix = gix::hash::Prefix::from_hex("dead")?;
+    let err = repo.resolve_prefix(prefix).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Short id dead is ambiguous. Candidates are:\n\tdead7b2 blob\n\tdead9d3 blob"
+    );
+    let gix::repository::resolve_prefix::Error::Ambiguous { candidates, .. } = err else {
+        panic!("ambiguous error expected")
+    };
+    let ids = repo.objects_with_prefix(prefix)?;
+    assert_eq!(
+        candidates.iter().map(|(prefix, _)| prefix.to_string()).collect::<Vec<_>>(),
+        ids.iter().map(|id| id.to_hex_with_len(7).to_string()).collect::<Vec<_>>(),
+        "all candidates are listed, in order"
+    );
+
+    for id in ids {
+        let short = id.attach(&repo).shorten()?;
+        assert_eq!(short.hex_len(), 7, "the default of core.abbrev is unambiguous here");
+        assert_eq!(repo.resolve_prefix(short)?, id, "shortened ids can be resolved");
+    }
+    assert!(matches!(
+        repo.resolve_prefix(gix::hash::Prefix::from_hex("0123")?),
+        Err(gix::repository::resolve_prefix::Error::NotFound { .. })
+    ));
+    assert!(repo.objects_with_prefix(gix::hash::Prefix::from_hex("0123")?)?.is_empty());
+    Ok(())
+}
+
 #[test]
 fn fully_failed_disambiguation_still_yields_an_ambiguity_error() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
@@ -69,6 +101,24 @@ fn ranges_are_auto_disambiguated_by_committish() {
     }
 }
 
+#[test]
+fn describe_output_is_disambiguated_by_commit() {
+    let repo = repo("ambiguous_blob_tree_commit").unwrap();
+    let expected = Spec::from_id(hex_to_id("0000000000e4f9fbd19cf1e932319e5ad0d1d00b").attach(&repo));
+    for spec in ["v1.0.0-1-g0000000000", "anything-0-g0000000000"] {
+        assert_eq!(
+            parse_spec_no_baseline(spec, &repo).unwrap(),
+            expected,
+            "the name of describe output always refers to a commit, the only one with this prefix"
+        );
+    }
+    assert_eq!(
+        parse_spec_no_baseline("0000000000-dirty", &repo).unwrap_err().to_string(),
+        "Short id 0000000000 is ambiguous. Candidates are:\n\t0000000000e commit 2005-04-07 \"a2onsxbvj\"\n\t0000000000c tree\n\t0000000000b blob",
+        "short describe output isn't necessarily describe output"
+    );
+}
+
 #[test]
 fn blob_and_tree_can_be_disambiguated_by_type() {
     let repo = repo("ambiguous_blob_tree_commit").unwrap();
@@ -78,10 +78,35 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
+fn by_date() {
     let repo = repo("complex_graph").unwrap();
+    for (spec, expected) in [
+        (
+            "main@{2005-04-07 22:16:00 +0000}",
+            "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc",
+        ),
+        (
+            "main@{2005-04-07 22:20:13 +0000}",
+            "5b3f9e24965d0b28780b7ce5daf2b5b7f7e0459f",
+        ),
+        ("main@{1112912160 +0000}", "a8fbd8e0c8753ef07ccdbfa93da030d53b68f0bc"),
+        ("@{2.days.ago}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+        ("HEAD@{2 days ago}", "55e825ebe8fd2ff78cad3826afb696b96b576a7e"),
+    ] {
+        assert_eq!(
+            parse_spec_no_baseline(spec, &repo).unwrap(),
+            Spec::from_id(hex_to_id(expected).attach(&repo)),
+            "{spec}"
+        );
+    }
+
+    assert_eq!(
+        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap(),
+        Spec::from_id(hex_to_id("9f9eac6bd1cd4b4cc6a494f044b28c985a22972b").attach(&repo)),
+        "dates before the first entry yield the oldest known state"
+    );
     assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
+        parse_spec_no_baseline("i-tag@{2005-04-07 22:16:00 +0000}", &repo).unwrap_err(),
+        Error::MissingRefLog { .. }
     ));
 }
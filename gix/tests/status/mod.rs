@@ -26,7 +26,7 @@ mod index_worktree {
         fn item_size() {
             assert_eq!(
                 std::mem::size_of::<gix::status::index_worktree::iter::Item>(),
-                264,
+                288,
                 "The size is pretty huge and goes down ideally"
             );
         }
@@ -131,6 +131,91 @@ mod index_worktree {
     }
 }
 
+mod tree_index {
+    use crate::status::repo;
+    use gix::object::tree::diff::{change::Event, Action};
+
+    fn changes(
+        repo: &gix::Repository,
+        rewrites: Option<gix::diff::Rewrites>,
+    ) -> crate::Result<Vec<(char, String, Option<String>)>> {
+        let head_tree = repo.head_commit()?.tree_id()?;
+        let index = repo.index()?;
+        let mut out = Vec::new();
+        repo.tree_index_status(&head_tree, &index, rewrites, |change| {
+            let status = match change.event {
+                Event::Addition { .. } => ('A', None),
+                Event::Deletion { .. } => ('D', None),
+                Event::Modification { .. } => ('M', None),
+                Event::Rewrite { source_location, .. } => ('R', Some(source_location.to_string())),
+            };
+            out.push((status.0, change.location.to_string(), status.1));
+            Ok::<_, std::convert::Infallible>(Action::Continue)
+        })?;
+        out.sort();
+        Ok(out)
+    }
+
+    #[test]
+    fn changes_between_head_and_index() -> crate::Result {
+        let repo = repo("staged-changes")?;
+        assert_eq!(
+            changes(&repo, Some(Default::default()))?,
+            [
+                ('A', "added".into(), None),
+                ('D', "removed".into(), None),
+                ('M', "modified".into(), None),
+                ('R', "renamed".into(), Some("to-be-renamed".into())),
+            ],
+            "the worktree modification of 'added' isn't visible"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn trees_of_the_index_are_not_written() -> crate::Result {
+        let repo = repo("staged-changes")?;
+        let head_tree = repo.head_commit()?.tree_id()?;
+        let mut index = repo.index()?.as_ref().clone();
+        let blob = index.entries()[0].clone();
+        index.dangerously_push_entry(blob.stat, blob.id, blob.flags, blob.mode, "dir/sub/new".into());
+        index.sort_entries();
+
+        let loose_objects = gix::odb::loose::Store::at(repo.objects.store_ref().path(), repo.object_hash());
+        let num_loose_objects = loose_objects.iter().count();
+        let mut out = Vec::new();
+        repo.tree_index_status(&head_tree, &index, None, |change| {
+            if let Event::Addition { .. } = change.event {
+                out.push(change.location.to_string());
+            }
+            Ok::<_, std::convert::Infallible>(Action::Continue)
+        })?;
+        assert_eq!(out, ["added", "renamed", "dir/sub/new"]);
+        assert_eq!(
+            loose_objects.iter().count(),
+            num_loose_objects,
+            "the trees of the index are only kept in memory"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn without_rewrite_tracking() -> crate::Result {
+        let repo = repo("staged-changes")?;
+        assert_eq!(
+            changes(&repo, None)?,
+            [
+                ('A', "added".into(), None),
+                ('A', "renamed".into(), None),
+                ('D', "removed".into(), None),
+                ('D', "to-be-renamed".into(), None),
+                ('M', "modified".into(), None),
+            ]
+        );
+        Ok(())
+    }
+}
+
 mod is_dirty {
     use crate::status::submodule_repo;
 
@@ -149,6 +149,11 @@ mod open {
                 Some(hex_to_id("362cb5539acbd3c8ca355471f97c6a68d3db0da7")),
                 "the checked out head was reset to something else after the superproject commit"
             );
+            assert_eq!(
+                status.head_id, status.index_id,
+                "the superproject's HEAD commit records the same submodule commit as its index"
+            );
+            assert!(!status.is_staged());
             assert_eq!(
                 status.changes,
                 Some(Vec::new()),
@@ -183,6 +188,7 @@ mod open {
                 status_with_ignore.checked_out_head_id, None,
                 "this check is ignored as it requires opening a repository"
             );
+            assert_eq!(status_with_ignore.head_id, None, "this is skipped as well");
             Ok(())
         }
 
@@ -16,8 +16,8 @@ use gix::bstr::{io::BufReadExt, BString};
 
 use crate::plumbing::{
     options::{
-        attributes, commit, commitgraph, config, credential, exclude, free, fsck, index, mailmap, odb, revision, tree,
-        Args, Subcommands,
+        attributes, blame, commit, commitgraph, config, credential, exclude, free, fsck, index, log, mailmap, odb,
+        revision, tree, Args, Subcommands,
     },
     show_progress,
 };
@@ -213,6 +213,8 @@ pub fn main() -> Result<()> {
             no_write,
             pathspec,
             index_worktree_renames,
+            branch,
+            null_terminated,
         }) => prepare_and_run(
             "status",
             trace,
@@ -250,6 +252,8 @@ pub fn main() -> Result<()> {
                         thread_limit: thread_limit.or(cfg!(target_os = "macos").then_some(3)), // TODO: make this a configurable when in `gix`, this seems to be optimal on MacOS, linux scales though! MacOS also scales if reading a lot of files for refresh index
                         allow_write: !no_write,
                         index_worktree_renames: index_worktree_renames.map(|percentage| percentage.unwrap_or(0.5)),
+                        branch,
+                        null_terminated,
                         submodules: submodules.map(|submodules| match submodules {
                             Submodules::All => core::repository::status::Submodules::All,
                             Submodules::RefChange => core::repository::status::Submodules::RefChange,
@@ -316,18 +320,7 @@ pub fn main() -> Result<()> {
                             .chunks_exact(2)
                             .map(|c| (c[0].clone(), c[1].clone()))
                             .collect(),
-                        format: format.map(|f| match f {
-                            crate::plumbing::options::archive::Format::Internal => {
-                                gix::worktree::archive::Format::InternalTransientNonPersistable
-                            }
-                            crate::plumbing::options::archive::Format::Tar => gix::worktree::archive::Format::Tar,
-                            crate::plumbing::options::archive::Format::TarGz => {
-                                gix::worktree::archive::Format::TarGz { compression_level }
-                            }
-                            crate::plumbing::options::archive::Format::Zip => {
-                                gix::worktree::archive::Format::Zip { compression_level }
-                            }
-                        }),
+                        format: format.map(|f| archive_format(f, compression_level)),
                     },
                 )
             },
@@ -378,6 +371,17 @@ pub fn main() -> Result<()> {
                 },
             )
             .map(|_| ()),
+            commitgraph::Subcommands::Write { split } => prepare_and_run(
+                "commitgraph-write",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |progress, out, _err| {
+                    core::repository::commitgraph::write(repository(Mode::Strict)?, split, progress, out, format)
+                },
+            ),
             commitgraph::Subcommands::Verify { statistics } => prepare_and_run(
                 "commitgraph-verify",
                 trace,
@@ -385,10 +389,11 @@ pub fn main() -> Result<()> {
                 progress,
                 progress_keep_open,
                 None,
-                move |_progress, out, err| {
+                move |progress, out, err| {
                     let output_statistics = if statistics { Some(format) } else { None };
                     core::repository::commitgraph::verify(
                         repository(Mode::Lenient)?,
+                        progress,
                         core::repository::commitgraph::verify::Context {
                             err,
                             out,
@@ -404,6 +409,8 @@ pub fn main() -> Result<()> {
             handshake_info,
             bare,
             no_tags,
+            single_branch,
+            filter,
             remote,
             shallow,
             directory,
@@ -413,6 +420,8 @@ pub fn main() -> Result<()> {
                 bare,
                 handshake_info,
                 no_tags,
+                single_branch,
+                filter,
                 shallow: shallow.into(),
             };
             prepare_and_run(
@@ -426,11 +435,40 @@ pub fn main() -> Result<()> {
             )
         }
         #[cfg(feature = "gitoxide-core-blocking-client")]
+        Subcommands::Serve(crate::plumbing::options::serve::Platform {
+            git,
+            http,
+            export_all,
+            timeout,
+            base_path,
+        }) => prepare_and_run(
+            "serve",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, _out, err| {
+                core::serve::serve(
+                    core::serve::Options {
+                        base_path,
+                        git_address: git,
+                        http_address: http,
+                        export_all,
+                        timeout: timeout.map(std::time::Duration::from_secs),
+                    },
+                    err,
+                )
+            },
+        ),
+        #[cfg(feature = "gitoxide-core-blocking-client")]
         Subcommands::Fetch(crate::plumbing::options::fetch::Platform {
             dry_run,
             handshake_info,
             negotiation_info,
             open_negotiation_graph,
+            prune,
+            tags,
             remote,
             shallow,
             ref_spec,
@@ -442,6 +480,8 @@ pub fn main() -> Result<()> {
                 handshake_info,
                 negotiation_info,
                 open_negotiation_graph,
+                prune,
+                tags,
                 shallow: shallow.into(),
                 ref_specs: ref_spec,
             };
@@ -474,9 +514,11 @@ pub fn main() -> Result<()> {
         }) => {
             use crate::plumbing::options::remote;
             match cmd {
-                remote::Subcommands::Refs | remote::Subcommands::RefMap { .. } => {
+                remote::Subcommands::Refs { .. } | remote::Subcommands::RefMap { .. } => {
                     let kind = match cmd {
-                        remote::Subcommands::Refs => core::repository::remote::refs::Kind::Remote,
+                        remote::Subcommands::Refs { ref_prefixes } => {
+                            core::repository::remote::refs::Kind::Remote { ref_prefixes }
+                        }
                         remote::Subcommands::RefMap {
                             ref_spec,
                             show_unmapped_remote_refs,
@@ -675,6 +717,60 @@ pub fn main() -> Result<()> {
                     move |_progress, out, _err| core::mailmap::verify(path, format, out),
                 ),
             },
+            #[cfg(feature = "gitoxide-core-tools-archive")]
+            free::Subcommands::Archive(free::archive::Platform {
+                objects_directory,
+                format,
+                prefix,
+                compression_level,
+                output_file,
+                treeish,
+            }) => prepare_and_run(
+                "free-archive",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |progress, _out, _err| {
+                    core::archive::from_tree(
+                        &objects_directory,
+                        &treeish,
+                        &output_file,
+                        progress,
+                        core::archive::Options {
+                            format: format.map(|f| archive_format(f, compression_level)),
+                            prefix,
+                        },
+                    )
+                },
+            ),
+            #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
+            free::Subcommands::Remote(free::remote::Subcommands::LsTags { protocol, url }) => {
+                let options = core::remote::ls_tags::Options { format, protocol };
+                #[cfg(feature = "gitoxide-core-blocking-client")]
+                {
+                    prepare_and_run(
+                        "remote-ls-tags",
+                        trace,
+                        auto_verbose,
+                        progress,
+                        progress_keep_open,
+                        core::remote::ls_tags::PROGRESS_RANGE,
+                        move |progress, out, _err| core::remote::ls_tags(&url, progress, out, options),
+                    )
+                }
+                #[cfg(feature = "gitoxide-core-async-client")]
+                {
+                    let (_handle, progress) = async_util::prepare(
+                        auto_verbose,
+                        trace,
+                        "remote-ls-tags",
+                        Some(core::remote::ls_tags::PROGRESS_RANGE),
+                    );
+                    futures_lite::future::block_on(core::remote::ls_tags(&url, progress, std::io::stdout(), options))
+                }
+            }
             free::Subcommands::Pack(subcommands) => match subcommands {
                 free::pack::Subcommands::Create {
                     repository,
@@ -843,6 +939,28 @@ pub fn main() -> Result<()> {
                     },
                 )
                 .map(|_| ()),
+                free::pack::Subcommands::Stats { objects, top, path } => prepare_and_run(
+                    "pack-stats",
+                    trace,
+                    verbose,
+                    progress,
+                    progress_keep_open,
+                    core::pack::stats::PROGRESS_RANGE,
+                    move |progress, out, _err| {
+                        core::pack::stats::pack_or_pack_index(
+                            path,
+                            progress,
+                            core::pack::stats::Context {
+                                objects,
+                                top,
+                                format,
+                                out,
+                                should_interrupt: &should_interrupt,
+                                object_hash,
+                            },
+                        )
+                    },
+                ),
                 free::pack::Subcommands::MultiIndex(free::pack::multi_index::Platform { multi_index_path, cmd }) => {
                     match cmd {
                         free::pack::multi_index::Subcommands::Entries => prepare_and_run(
@@ -1083,6 +1201,8 @@ pub fn main() -> Result<()> {
                 max_candidates,
                 rev_spec,
                 dirty_suffix,
+                include_patterns,
+                exclude_patterns,
             } => prepare_and_run(
                 "commit-describe",
                 trace,
@@ -1105,6 +1225,8 @@ pub fn main() -> Result<()> {
                             max_candidates,
                             always,
                             dirty_suffix: dirty_suffix.map(|suffix| suffix.unwrap_or_else(|| "dirty".to_string())),
+                            include_patterns,
+                            exclude_patterns,
                         },
                     )
                 },
@@ -1193,6 +1315,75 @@ pub fn main() -> Result<()> {
                 move |_progress, out, err| core::repository::odb::info(repository(Mode::Strict)?, format, out, err),
             ),
         },
+        Subcommands::Log(log::Platform {
+            format: log_format,
+            oneline,
+            max_count,
+            topo_order,
+            first_parent,
+            follow,
+            revisions,
+            pathspec,
+        }) => prepare_and_run(
+            "log",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, _err| {
+                core::repository::log(
+                    repository(Mode::Lenient)?,
+                    revisions,
+                    pathspec,
+                    out,
+                    core::repository::log::Options {
+                        format: match log_format {
+                            _ if oneline => core::repository::log::Format::Oneline,
+                            None | Some(log::Format::Medium) => core::repository::log::Format::Medium,
+                            Some(log::Format::Oneline) => core::repository::log::Format::Oneline,
+                            Some(log::Format::Short) => core::repository::log::Format::Short,
+                            Some(log::Format::Full) => core::repository::log::Format::Full,
+                            Some(log::Format::Fuller) => core::repository::log::Format::Fuller,
+                        },
+                        output_format: format,
+                        max_count,
+                        topo_order,
+                        first_parent,
+                        follow,
+                    },
+                )
+            },
+        ),
+        Subcommands::Blame(blame::Platform {
+            ranges,
+            porcelain,
+            statistics,
+            rev_spec,
+            file,
+        }) => prepare_and_run(
+            "blame",
+            trace,
+            verbose,
+            progress,
+            progress_keep_open,
+            None,
+            move |_progress, out, err| {
+                core::repository::blame(
+                    repository(Mode::Lenient)?,
+                    file,
+                    rev_spec.as_deref(),
+                    out,
+                    err,
+                    core::repository::blame::Options {
+                        output_format: format,
+                        ranges,
+                        porcelain,
+                        statistics,
+                    },
+                )
+            },
+        ),
         Subcommands::Fsck(fsck::Platform { spec }) => prepare_and_run(
             "fsck",
             trace,
@@ -1310,6 +1501,7 @@ pub fn main() -> Result<()> {
                 attributes_from_index,
                 statistics,
                 recurse_submodules,
+                extensions,
                 pathspec,
             } => prepare_and_run(
                 "index-entries",
@@ -1341,10 +1533,20 @@ pub fn main() -> Result<()> {
                             },
                             recurse_submodules,
                             statistics,
+                            extensions,
                         },
                     )
                 },
             ),
+            index::Subcommands::Verify => prepare_and_run(
+                "index-verify",
+                trace,
+                auto_verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, _err| core::repository::index::verify(repository(Mode::Strict)?, out, format),
+            ),
             index::Subcommands::FromTree {
                 force,
                 index_output_path,
@@ -1395,6 +1597,20 @@ fn stdin_or_bail() -> Result<std::io::BufReader<std::io::Stdin>> {
     Ok(BufReader::new(stdin()))
 }
 
+#[cfg(feature = "gitoxide-core-tools-archive")]
+fn archive_format(
+    format: crate::plumbing::options::archive::Format,
+    compression_level: Option<u8>,
+) -> gix::worktree::archive::Format {
+    use crate::plumbing::options::archive::Format;
+    match format {
+        Format::Internal => gix::worktree::archive::Format::InternalTransientNonPersistable,
+        Format::Tar => gix::worktree::archive::Format::Tar,
+        Format::TarGz => gix::worktree::archive::Format::TarGz { compression_level },
+        Format::Zip => gix::worktree::archive::Format::Zip { compression_level },
+    }
+}
+
 fn verify_mode(decode: bool, re_encode: bool) -> verify::Mode {
     match (decode, re_encode) {
         (true, false) => verify::Mode::HashCrc32Decode,
@@ -16,6 +16,13 @@ pub enum Subcommands {
     Index(index::Platform),
     /// Show information about repository discovery and when opening a repository at the current path.
     Discover,
+    /// Subcommands for interacting with remotes without a local repository
+    #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
+    #[clap(subcommand)]
+    Remote(remote::Subcommands),
+    /// Write a commit or tree to an archive, reading objects directly from an object database
+    #[cfg(feature = "gitoxide-core-tools-archive")]
+    Archive(archive::Platform),
 }
 
 ///
@@ -255,6 +262,20 @@ pub mod pack {
             /// The '.pack', '.idx' or 'multi-pack-index' file to validate.
             path: PathBuf,
         },
+        /// Print statistics about the objects of a pack, their sizes and delta chains.
+        Stats {
+            /// List all objects as *oid* *kind* *entry-size* *size-in-pack* *pack-offset* [*delta-chain-length* *base-oid*],
+            /// just like `git verify-pack -v` does.
+            #[clap(long, short = 'o')]
+            objects: bool,
+
+            /// The amount of objects occupying the most space in the pack to list.
+            #[clap(long, short = 't', default_value_t = 10)]
+            top: usize,
+
+            /// The '.pack' or '.idx' file to analyze.
+            path: PathBuf,
+        },
     }
 
     #[derive(Debug, clap::Parser)]
@@ -483,3 +504,52 @@ pub mod mailmap {
         Verify,
     }
 }
+
+#[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
+pub mod remote {
+    use gitoxide_core as core;
+
+    #[derive(Debug, clap::Subcommand)]
+    pub enum Subcommands {
+        /// List the tags of a remote along with the objects they point to, and the objects annotated tags peel to.
+        LsTags {
+            /// The protocol version to use. Valid values are 1 and 2
+            #[clap(long, short = 'p')]
+            protocol: Option<core::net::Protocol>,
+
+            /// The URL or path of the remote.
+            ///
+            /// See here for a list of supported URLs: <https://www.git-scm.com/docs/git-clone#_git_urls>
+            url: String,
+        },
+    }
+}
+
+#[cfg(feature = "gitoxide-core-tools-archive")]
+pub mod archive {
+    use std::path::PathBuf;
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The directory containing all objects, typically '.git/objects'.
+        #[clap(long, short = 'o', default_value = ".git/objects")]
+        pub objects_directory: PathBuf,
+        /// Explicitly set the format. Otherwise derived from the suffix of the output file.
+        #[clap(long, short = 'f', value_enum)]
+        pub format: Option<super::super::archive::Format>,
+        /// Apply the prefix verbatim to any path we add to the archive. Use a trailing `/` if prefix is a directory.
+        #[clap(long)]
+        pub prefix: Option<String>,
+        /// The compression strength to use for `.zip` and `.tar.gz` archives, valid from 0-9.
+        #[clap(long, short = 'l', requires = "format")]
+        pub compression_level: Option<u8>,
+        /// The file to write the archive to.
+        ///
+        /// It's extension determines the archive format, unless `--format` is set.
+        pub output_file: PathBuf,
+        /// The hexadecimal id of the commit, tag or tree to archive.
+        ///
+        /// If commit, the commit timestamp will be used as timestamp for each file in the archive.
+        pub treeish: String,
+    }
+}
@@ -60,6 +60,8 @@ pub struct Args {
     pub progress_keep_open: bool,
 
     /// Determine the format to use when outputting statistics.
+    ///
+    /// With `json`, pack verification, reference listings, ref-maps and index entries are written as one JSON object per line.
     #[clap(
         long,
         short = 'f',
@@ -105,6 +107,10 @@ pub enum Subcommands {
     /// Query and obtain information about revisions.
     #[clap(subcommand)]
     Revision(revision::Subcommands),
+    /// List commits and their messages, similar to `git log`.
+    Log(log::Platform),
+    /// Show the commit that last changed each line of a file, similar to `git blame`.
+    Blame(blame::Platform),
     /// A program just like `git credential`.
     #[clap(subcommand)]
     Credential(credential::Subcommands),
@@ -113,6 +119,9 @@ pub enum Subcommands {
     Fetch(fetch::Platform),
     #[cfg(feature = "gitoxide-core-blocking-client")]
     Clone(clone::Platform),
+    /// Serve the repositories below a directory read-only via the `git://` protocol and smart HTTP.
+    #[cfg(feature = "gitoxide-core-blocking-client")]
+    Serve(serve::Platform),
     /// Interact with the mailmap.
     #[clap(subcommand)]
     Mailmap(mailmap::Subcommands),
@@ -261,6 +270,12 @@ pub mod status {
         /// Enable rename tracking between the index and the working tree, preventing the collapse of folders as well.
         #[clap(long, value_parser = ParseRenameFraction)]
         pub index_worktree_renames: Option<Option<f32>>,
+        /// Show the branch and tracking information, similar to `git status --branch`.
+        #[clap(long, short = 'b')]
+        pub branch: bool,
+        /// Terminate entries with NUL instead of a newline and don't quote paths, similar to `git status -z`.
+        #[clap(short = 'z')]
+        pub null_terminated: bool,
         /// The git path specifications to list attributes for, or unset to read from stdin one per line.
         #[clap(value_parser = CheckPathSpec)]
         pub pathspec: Vec<BString>,
@@ -349,13 +364,20 @@ pub mod fetch {
         #[clap(long, value_name = "PATH", short = 'g')]
         pub open_negotiation_graph: Option<std::path::PathBuf>,
 
+        /// Delete local references written by the ref-specs whose counterpart doesn't exist on the remote anymore.
+        #[clap(long, short = 'p')]
+        pub prune: bool,
+
+        /// Fetch all tags from the remote, along with the objects they point to.
+        #[clap(long, short = 't')]
+        pub tags: bool,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
         /// The name of the remote to connect to, or the url of the remote to connect to directly.
         ///
         /// If unset, the current branch will determine the remote.
-        #[clap(long, short = 'r')]
         pub remote: Option<String>,
 
         /// Override the built-in and configured ref-specs with one or more of the given ones.
@@ -428,6 +450,16 @@ pub mod clone {
         #[clap(long)]
         pub no_tags: bool,
 
+        /// Only clone the branch that the remote `HEAD` points to, along with the tags pointing into its history.
+        #[clap(long)]
+        pub single_branch: bool,
+
+        /// Perform a partial clone, omitting objects according to the given filter specification like `blob:none` or `tree:0`.
+        ///
+        /// As missing objects can't be fetched on demand, this requires `--bare`.
+        #[clap(long, value_name = "FILTER_SPEC", requires = "bare")]
+        pub filter: Option<String>,
+
         #[clap(flatten)]
         pub shallow: ShallowOptions,
 
@@ -471,6 +503,33 @@ pub mod clone {
     }
 }
 
+#[cfg(feature = "gitoxide-core-blocking-client")]
+pub mod serve {
+    use std::{net::SocketAddr, path::PathBuf};
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The address to listen on for connections using the `git://` protocol, like `127.0.0.1:9418`.
+        #[clap(long, value_name = "ADDRESS", required_unless_present = "http")]
+        pub git: Option<SocketAddr>,
+
+        /// The address to listen on for smart HTTP connections, like `127.0.0.1:8080`.
+        #[clap(long, value_name = "ADDRESS")]
+        pub http: Option<SocketAddr>,
+
+        /// Serve all repositories, not only those containing a `git-daemon-export-ok` file.
+        #[clap(long)]
+        pub export_all: bool,
+
+        /// Drop connections whose client doesn't send or receive data for the given amount of seconds.
+        #[clap(long, value_name = "SECONDS")]
+        pub timeout: Option<u64>,
+
+        /// The directory below which all served repositories are located.
+        pub base_path: PathBuf,
+    }
+}
+
 #[cfg(any(feature = "gitoxide-core-async-client", feature = "gitoxide-core-blocking-client"))]
 pub mod remote {
     #[derive(Debug, clap::Parser)]
@@ -493,8 +552,14 @@ pub mod remote {
     #[derive(Debug, clap::Subcommand)]
     #[clap(visible_alias = "remotes")]
     pub enum Subcommands {
-        /// Print all references available on the remote.
-        Refs,
+        /// Print all references available on the remote, along with the targets of symbolic references and peeled tags.
+        Refs {
+            /// Only list references starting with the given prefix, like `refs/tags/`, or `HEAD`.
+            ///
+            /// With protocol V2, the server applies the filter so only matching references are transferred.
+            #[clap(long = "ref-prefix", short = 'p', value_parser = gitoxide::shared::AsBString)]
+            ref_prefixes: Vec<gix::bstr::BString>,
+        },
         /// Print all references available on the remote as filtered through ref-specs.
         RefMap {
             /// Also display remote references that were sent by the server, but filtered by the refspec locally.
@@ -594,6 +659,75 @@ pub mod odb {
     }
 }
 
+pub mod log {
+    use gitoxide::shared::CheckPathSpec;
+    use gix::bstr::BString;
+
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+    pub enum Format {
+        /// The abbreviated commit id followed by the title line.
+        Oneline,
+        /// The commit id, author and title line.
+        Short,
+        /// The commit id, author, author date and message.
+        #[default]
+        Medium,
+        /// The commit id, author, committer and message.
+        Full,
+        /// The commit id, author, author date, committer, commit date and message.
+        Fuller,
+    }
+
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The way each commit is displayed.
+        #[clap(long, value_enum, conflicts_with = "oneline")]
+        pub format: Option<Format>,
+        /// Display each commit on a single line, a shorthand for `--format oneline`.
+        #[clap(long)]
+        pub oneline: bool,
+        /// The maximum amount of commits to display.
+        #[clap(long, short = 'n')]
+        pub max_count: Option<usize>,
+        /// Show no parents before all of their children, and avoid intermixing multiple lines of history.
+        #[clap(long)]
+        pub topo_order: bool,
+        /// Only follow the first parent of merge commits.
+        #[clap(long)]
+        pub first_parent: bool,
+        /// Follow the history of the single file given as pathspec across renames.
+        #[clap(long)]
+        pub follow: bool,
+        /// Revisions like `main` or ranges like `main..feature` to list commits from, or `HEAD` if unset.
+        pub revisions: Vec<std::ffi::OsString>,
+        /// Only list commits that change paths matching these git path specifications.
+        #[clap(last = true, value_parser = CheckPathSpec)]
+        pub pathspec: Vec<BString>,
+    }
+}
+
+pub mod blame {
+    #[derive(Debug, clap::Parser)]
+    pub struct Platform {
+        /// The lines to blame, like `10,20`, `10,+5` or `,20`, which may be given multiple times.
+        #[clap(short = 'L')]
+        pub ranges: Vec<gix::blame::LineRange>,
+        /// Show information about each commit in a format designed for machine consumption, like `git blame --porcelain`.
+        ///
+        /// Note that `previous` lines, naming the parent commit and path that lines were passed on from, aren't shown.
+        #[clap(long)]
+        pub porcelain: bool,
+        /// Print statistics about the work performed to stderr.
+        #[clap(long, short = 's')]
+        pub statistics: bool,
+        /// The revision to blame the file at, or `HEAD` if unset.
+        #[clap(long, short = 'r')]
+        pub rev_spec: Option<String>,
+        /// The path of the file to blame.
+        pub file: std::ffi::OsString,
+    }
+}
+
 pub mod fsck {
     #[derive(Debug, clap::Parser)]
     pub struct Platform {
@@ -671,6 +805,14 @@ pub mod commit {
             #[clap(short = 'd', long)]
             dirty_suffix: Option<Option<String>>,
 
+            /// Only consider tags matching the given glob pattern, without the `refs/tags/` prefix. Can be given multiple times.
+            #[clap(long = "match", short = 'm', value_name = "PATTERN")]
+            include_patterns: Vec<String>,
+
+            /// Do not consider tags matching the given glob pattern, without the `refs/tags/` prefix. Can be given multiple times.
+            #[clap(long = "exclude", short = 'e', value_name = "PATTERN")]
+            exclude_patterns: Vec<String>,
+
             /// A specification of the revision to use, or the current `HEAD` if unset.
             rev_spec: Option<String>,
         },
@@ -703,6 +845,12 @@ pub mod commitgraph {
             #[clap(long, short = 's')]
             statistics: bool,
         },
+        /// Write a commit-graph with all commits reachable from `HEAD` and all references, like `git commit-graph write --reachable`.
+        Write {
+            /// Only write commits that aren't yet in the commit-graph into a new file of a chain of split commit-graph files.
+            #[clap(long)]
+            split: bool,
+        },
         /// List all entries in the commit-graph as reachable by starting from `HEAD`.
         List {
             /// The rev-spec to list reachable commits from.
@@ -882,10 +1030,15 @@ pub mod index {
             /// Print various statistics to stderr
             #[clap(long, short = 's')]
             statistics: bool,
+            /// Print details about the index extensions, like the cached trees, after the entries.
+            #[clap(long, short = 'e')]
+            extensions: bool,
             /// The git path specifications to match entries to print.
             #[clap(value_parser = CheckPathSpec)]
             pathspec: Vec<BString>,
         },
+        /// Validate the checksum, the entries and the extensions of the index, including the cached trees.
+        Verify,
         /// Create an index from a tree-ish.
         #[clap(visible_alias = "read-tree")]
         FromTree {
@@ -78,6 +78,9 @@ pub fn main() -> Result<()> {
                             Some(crate::porcelain::options::tools::query::Command::TracePath { path }) => {
                                 engine.run(query::Command::TracePath { spec: path }, out, progress)?;
                             }
+                            Some(crate::porcelain::options::tools::query::Command::Authors) => {
+                                engine.run(query::Command::Authors, out, progress)?;
+                            }
                         }
                         Ok(())
                     },
@@ -92,9 +95,31 @@ pub fn main() -> Result<()> {
                     line_stats,
                     show_pii,
                     omit_unify_identities,
+                    breakdown,
+                    pathspecs,
+                    breakdown_format,
                 },
             ) => {
+                use crate::porcelain::options::tools::estimate_hours;
                 use gitoxide_core::hours;
+                let breakdown = match (breakdown, pathspecs.is_empty(), breakdown_format) {
+                    (None, true, None) => None,
+                    (breakdown, _, format) => Some(hours::Breakdown {
+                        kind: match breakdown {
+                            Some(estimate_hours::Breakdown::Directory) => hours::BreakdownKind::TopLevelDirectory,
+                            Some(estimate_hours::Breakdown::File) => hours::BreakdownKind::File,
+                            None if !pathspecs.is_empty() => hours::BreakdownKind::File,
+                            None => hours::BreakdownKind::TopLevelDirectory,
+                        },
+                        pathspecs,
+                        format: match format {
+                            None | Some(estimate_hours::BreakdownFormat::Human) => hours::BreakdownFormat::Human,
+                            Some(estimate_hours::BreakdownFormat::Csv) => hours::BreakdownFormat::Csv,
+                            #[cfg(feature = "pretty-cli")]
+                            Some(estimate_hours::BreakdownFormat::Json) => hours::BreakdownFormat::Json,
+                        },
+                    }),
+                };
                 prepare_and_run(
                     "estimate-hours",
                     trace,
@@ -114,6 +139,7 @@ pub fn main() -> Result<()> {
                                 file_stats,
                                 line_stats,
                                 omit_unify_identities,
+                                breakdown,
                                 out,
                             },
                         )
@@ -144,6 +170,8 @@ pub fn main() -> Result<()> {
                 destination_directory,
                 execute,
                 repository_source,
+                layout,
+                excludes,
             } => {
                 use gitoxide_core::organize;
                 prepare_and_run(
@@ -153,7 +181,7 @@ pub fn main() -> Result<()> {
                     progress,
                     progress_keep_open,
                     gitoxide::shared::STANDARD_RANGE,
-                    move |progress, _out, _err| {
+                    move |progress, out, _err| {
                         organize::run(
                             if execute {
                                 organize::Mode::Execute
@@ -162,6 +190,8 @@ pub fn main() -> Result<()> {
                             },
                             repository_source.unwrap_or_else(|| [std::path::Component::CurDir].iter().collect()),
                             destination_directory.unwrap_or_else(|| [std::path::Component::CurDir].iter().collect()),
+                            organize::Options { layout, excludes },
+                            out,
                             progress,
                             threads,
                         )
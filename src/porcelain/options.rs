@@ -88,6 +88,17 @@ pub enum ToolCommands {
         ///
         /// Defaults to the current working directory.
         destination_directory: Option<PathBuf>,
+
+        #[clap(long, short = 'l', default_value = gitoxide_core::organize::DEFAULT_LAYOUT)]
+        /// The path of each repository within the destination directory, with placeholders substituted by parts of its `origin` URL.
+        ///
+        /// Supported placeholders are `{host}`, `{port}`, `{path}`, `{owner}` for all but the last component of the path,
+        /// and `{repo}` for its last component, like in `{host}/{owner}/{repo}`.
+        layout: String,
+
+        #[clap(long = "exclude", short = 'e', value_parser = gitoxide::shared::AsBString)]
+        /// Skip repositories whose path relative to the repository-source, or whose `origin` URL, matches the given glob pattern.
+        excludes: Vec<gix::bstr::BString>,
     },
     #[cfg(feature = "gitoxide-core-tools-query")]
     Query(tools::Query),
@@ -137,6 +148,8 @@ pub mod tools {
                 #[clap(value_parser = AsPathSpec)]
                 path: gix::pathspec::Pattern,
             },
+            /// Show commit and line statistics for each author, and the hours of the day at which commits were authored.
+            Authors,
         }
     }
 
@@ -173,6 +186,40 @@ pub mod tools {
         /// due to using different names or email addresses.
         #[clap(short = 'i', long)]
         pub omit_unify_identities: bool,
+        /// Attribute the estimated time to paths, splitting the time spent on each commit among the files it changed.
+        ///
+        /// Note that this requires a tree-diff for each commit, and that merge commits are skipped.
+        #[clap(short = 'B', long, value_enum)]
+        pub breakdown: Option<estimate_hours::Breakdown>,
+        /// Only attribute time to files matching the given pathspecs, which implies `--breakdown=file` if not set.
+        #[clap(short = 'P', long = "path", value_parser = gitoxide::shared::AsBString)]
+        pub pathspecs: Vec<BString>,
+        /// The format in which to write the breakdown of time by path, with `csv` and `json` writing only the breakdown.
+        ///
+        /// This implies `--breakdown=directory` if not set.
+        #[clap(long, value_enum)]
+        pub breakdown_format: Option<estimate_hours::BreakdownFormat>,
+    }
+
+    pub mod estimate_hours {
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+        pub enum Breakdown {
+            /// Attribute time to the top-level directories of the repository, with files at its root attributed to `.`.
+            Directory,
+            /// Attribute time to each file individually.
+            File,
+        }
+
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+        pub enum BreakdownFormat {
+            /// Display the breakdown after the summary.
+            Human,
+            /// Comma-separated values with a header line.
+            Csv,
+            /// An array of JSON objects.
+            #[cfg(feature = "pretty-cli")]
+            Json,
+        }
     }
 
     mod validator {
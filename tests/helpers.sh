@@ -67,3 +67,12 @@ function launch-git-daemon() {
     trap 'kill $daemon_pid' EXIT
 }
 
+function launch-gix-serve() {
+    "$exe_plumbing" serve --git 127.0.0.1:9419 --export-all . &>/dev/null &
+    serve_pid=$!
+    while ! nc -z 127.0.0.1 9419; do
+      sleep 0.1
+    done
+    trap 'kill $serve_pid' EXIT
+}
+
@@ -73,6 +73,12 @@ title "Porcelain ${kind}"
                 expect_run_sh $SUCCESSFULLY "$exe tool estimate-hours -pfl 2>/dev/null"
               }
             )
+            (with "a breakdown by file as CSV"
+              it "succeeds and shows only the time attributed to each file" && {
+                WITH_SNAPSHOT="$snapshot/breakdown-by-file-csv-success" \
+                expect_run_sh $SUCCESSFULLY "$exe tool estimate-hours --breakdown file --breakdown-format csv 2>/dev/null"
+              }
+            )
             (with "a branch name that doesn't exist"
               it "fails and shows a decent enough error message" && {
                 WITH_SNAPSHOT="$snapshot/invalid-branch-name-failure" \
@@ -116,6 +122,13 @@ title "Porcelain ${kind}"
               }
             )
 
+            (with "a custom --layout and --exclude patterns"
+              it "succeeds and informs about the operations that it WOULD do" && {
+                WITH_SNAPSHOT="$snapshot/layout-and-excludes-success" \
+                expect_run_sh $SUCCESSFULLY "$exe tool organize --layout '{host}-{repo}' --exclude 'dir/*' --exclude '*/origin-and-fork' 2>/dev/null"
+              }
+            )
+
             (with "--execute"
               it "succeeds" && {
                 WITH_SNAPSHOT="$snapshot/execute-success" \
@@ -130,7 +143,7 @@ title "Porcelain ${kind}"
 
             (with "--execute again"
               it "succeeds" && {
-                WITH_SNAPSHOT="$snapshot/execute-success" \
+                WITH_SNAPSHOT="$snapshot/execute-again-success" \
                 expect_run_sh $SUCCESSFULLY "$exe tool organize --execute 2>/dev/null"
               }
 
@@ -123,6 +123,15 @@ title "gix (with repository)"
             }
           )
         )
+        if [[ "$kind" != "async" ]]; then
+        (with "git:// protocol served by 'gix serve'"
+          launch-gix-serve
+          it "generates the correct output" && {
+            WITH_SNAPSHOT="$snapshot/file-v-any" \
+            expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose -c protocol.version=2 remote -n git://127.0.0.1:9419/ refs
+          }
+        )
+        fi
         if [[ "$kind" == "small" ]]; then
         (with "https:// protocol (in small builds)"
           it "fails as http is not compiled in" && {
@@ -194,6 +203,22 @@ title "gix commit-graph"
       )
     )
   )
+  title "gix commit-graph write"
+  (with "the 'write' sub-command"
+    snapshot="$snapshot/write"
+
+    (small-repo-in-sandbox
+      (with "--split"
+        it "writes all reachable commits into the first layer" && {
+          WITH_SNAPSHOT="$snapshot/split-success" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose commit-graph write --split
+        }
+        it "produces a commit-graph that git can verify" && {
+          expect_run $SUCCESSFULLY git commit-graph verify
+        }
+      )
+    )
+  )
 )
 
 (with "gix free"
@@ -712,5 +737,23 @@ title "gix commit-graph"
         )
       )
     )
+
+    title "gix free pack stats"
+    (with "the 'stats' sub-command"
+      snapshot="$snapshot/stats"
+      (with "a valid pack INDEX file"
+        PACK_INDEX="$fixtures/packs/pack-11fdfa9e156ab73caae3b6da867192221f2089c2.idx"
+        it "prints statistics about objects and their delta chains" && {
+          WITH_SNAPSHOT="$snapshot/index-success" \
+          expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose free pack stats --top 3 "$PACK_INDEX"
+        }
+        (with "objects"
+          it "lists all objects like 'git verify-pack -v' before the statistics" && {
+            WITH_SNAPSHOT="$snapshot/index-with-objects-success" \
+            expect_run $SUCCESSFULLY "$exe_plumbing" --no-verbose free pack stats --objects --top 0 "$PACK_INDEX"
+          }
+        )
+      )
+    )
   )
 )